@@ -1,25 +1,36 @@
 //! ABCI application interface.
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
 #[cfg(feature = "echo-app")]
 pub mod echo;
 #[cfg(feature = "kvstore-app")]
 pub mod kvstore;
 
-use tendermint_proto::v0_37::abci::{
-    request::Value, response, response_process_proposal, Request, RequestApplySnapshotChunk,
-    RequestBeginBlock, RequestCheckTx, RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo,
-    RequestInitChain, RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestPrepareProposal,
-    RequestProcessProposal, RequestQuery, Response, ResponseApplySnapshotChunk, ResponseBeginBlock,
-    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock,
-    ResponseFlush, ResponseInfo, ResponseInitChain, ResponseListSnapshots,
-    ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponsePrepareProposal,
-    ResponseProcessProposal, ResponseQuery,
+use tendermint_proto::v0_37::{
+    abci::{
+        response_process_proposal, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
+        RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo, RequestLoadSnapshotChunk,
+        RequestOfferSnapshot, RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock,
+        ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock,
+        ResponseFlush, ResponseInfo, ResponseListSnapshots, ResponseLoadSnapshotChunk,
+        ResponseOfferSnapshot, ResponsePrepareProposal, ResponseProcessProposal, ResponseQuery,
+    },
+    types::Header,
+};
+
+use crate::messages::{
+    last_commit_info, request::Value, response, response_verify_vote_extension, transcode, Request,
+    RequestExtendVote, RequestFinalizeBlock, RequestInitChain, RequestPrepareProposal,
+    RequestProcessProposal, RequestVerifyVoteExtension, Response, ResponseExtendVote,
+    ResponseFinalizeBlock, ResponseInitChain, ResponseVerifyVoteExtension,
 };
 
 /// An ABCI application.
 ///
 /// Applications are `Send` + `Clone` + `'static` because they are cloned for
-/// each incoming connection to the ABCI [`Server`]. It is up to the
+/// each incoming connection to the ABCI [`Server`], and for each request it
+/// handles concurrently with others (e.g. queries). It is up to the
 /// application developer to manage shared state between these clones of their
 /// application.
 ///
@@ -112,29 +123,7 @@ pub trait Application: Send + Clone + 'static {
     ///
     /// This method is introduced in ABCI++.
     fn prepare_proposal(&self, request: RequestPrepareProposal) -> ResponsePrepareProposal {
-        // Per the ABCI++ spec: if the size of RequestPrepareProposal.txs is
-        // greater than RequestPrepareProposal.max_tx_bytes, the Application
-        // MUST remove transactions to ensure that the
-        // RequestPrepareProposal.max_tx_bytes limit is respected by those
-        // transactions returned in ResponsePrepareProposal.txs.
-        let RequestPrepareProposal {
-            mut txs,
-            max_tx_bytes,
-            ..
-        } = request;
-        let max_tx_bytes: usize = max_tx_bytes.try_into().unwrap_or(0);
-        let mut total_tx_bytes: usize = txs
-            .iter()
-            .map(|tx| tx.len())
-            .fold(0, |acc, len| acc.saturating_add(len));
-        while total_tx_bytes > max_tx_bytes {
-            if let Some(tx) = txs.pop() {
-                total_tx_bytes = total_tx_bytes.saturating_sub(tx.len());
-            } else {
-                break;
-            }
-        }
-        ResponsePrepareProposal { txs }
+        prepare_proposal(request)
     }
 
     /// A stage where the application can accept or reject the proposed block.
@@ -147,11 +136,129 @@ pub trait Application: Send + Clone + 'static {
             status: response_process_proposal::ProposalStatus::Accept as i32,
         }
     }
+
+    /// Provide the extension of the precommit of this validator for the
+    /// given block.
+    ///
+    /// The default implementation returns an empty vote extension.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    fn extend_vote(&self, _request: RequestExtendVote) -> ResponseExtendVote {
+        Default::default()
+    }
+
+    /// Verify the extension of the precommit of another validator.
+    ///
+    /// The default implementation returns the status value of `ACCEPT`.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    fn verify_vote_extension(
+        &self,
+        _request: RequestVerifyVoteExtension,
+    ) -> ResponseVerifyVoteExtension {
+        ResponseVerifyVoteExtension {
+            status: response_verify_vote_extension::VerifyStatus::Accept as i32,
+        }
+    }
+
+    /// Execute a decided block, in place of the `BeginBlock`, `DeliverTx` and
+    /// `EndBlock` calls of ABCI 1.0.
+    ///
+    /// The default implementation executes the block by calling
+    /// [`begin_block`](Self::begin_block), [`deliver_tx`](Self::deliver_tx)
+    /// for each transaction, and [`end_block`](Self::end_block), but returns
+    /// an empty app hash: applications served to CometBFT 0.38 must override
+    /// it to return the hash of their state after executing the block.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    fn finalize_block(&self, request: RequestFinalizeBlock) -> ResponseFinalizeBlock {
+        let begin_block = self.begin_block(begin_block_request(&request));
+        let tx_results = request
+            .txs
+            .into_iter()
+            .map(|tx| self.deliver_tx(RequestDeliverTx { tx }))
+            .collect();
+        let end_block = self.end_block(RequestEndBlock {
+            height: request.height,
+        });
+        finalize_block_response(begin_block, tx_results, end_block)
+    }
+}
+
+// The default implementation of `prepare_proposal`, shared by the sync and
+// async applications.
+pub(crate) fn prepare_proposal(request: RequestPrepareProposal) -> ResponsePrepareProposal {
+    // Per the ABCI++ spec: if the size of RequestPrepareProposal.txs is
+    // greater than RequestPrepareProposal.max_tx_bytes, the Application
+    // MUST remove transactions to ensure that the
+    // RequestPrepareProposal.max_tx_bytes limit is respected by those
+    // transactions returned in ResponsePrepareProposal.txs.
+    let RequestPrepareProposal {
+        mut txs,
+        max_tx_bytes,
+        ..
+    } = request;
+    let max_tx_bytes: usize = max_tx_bytes.try_into().unwrap_or(0);
+    let mut total_tx_bytes: usize = txs
+        .iter()
+        .map(|tx| tx.len())
+        .fold(0, |acc, len| acc.saturating_add(len));
+    while total_tx_bytes > max_tx_bytes {
+        if let Some(tx) = txs.pop() {
+            total_tx_bytes = total_tx_bytes.saturating_sub(tx.len());
+        } else {
+            break;
+        }
+    }
+    ResponsePrepareProposal { txs }
+}
+
+// The `BeginBlock` request of the default implementation of
+// `finalize_block`, shared by the sync and async applications.
+pub(crate) fn begin_block_request(request: &RequestFinalizeBlock) -> RequestBeginBlock {
+    RequestBeginBlock {
+        hash: request.hash.clone(),
+        header: Some(Header {
+            height: request.height,
+            time: request.time.clone(),
+            next_validators_hash: request.next_validators_hash.to_vec(),
+            proposer_address: request.proposer_address.to_vec(),
+            ..Default::default()
+        }),
+        last_commit_info: request.decided_last_commit.as_ref().map(last_commit_info),
+        byzantine_validators: request.misbehavior.iter().map(transcode).collect(),
+    }
+}
+
+// The response of the default implementation of `finalize_block`, shared by
+// the sync and async applications.
+pub(crate) fn finalize_block_response(
+    begin_block: ResponseBeginBlock,
+    tx_results: Vec<ResponseDeliverTx>,
+    end_block: ResponseEndBlock,
+) -> ResponseFinalizeBlock {
+    ResponseFinalizeBlock {
+        events: begin_block
+            .events
+            .iter()
+            .chain(&end_block.events)
+            .map(transcode)
+            .collect(),
+        tx_results: tx_results.iter().map(transcode).collect(),
+        validator_updates: end_block.validator_updates.iter().map(transcode).collect(),
+        consensus_param_updates: end_block.consensus_param_updates.as_ref().map(transcode),
+        app_hash: Default::default(),
+    }
 }
 
 /// Provides a mechanism for the [`Server`] to execute incoming requests while
 /// expecting the correct response types.
-pub trait RequestDispatcher {
+///
+/// It is implemented by all the [`Application`]s, and by the applications
+/// wrapped in the [`layer`](crate::layer)s, which the [`Server`] serves alike.
+///
+/// [`Server`]: crate::Server
+pub trait RequestDispatcher: Send + Clone + 'static {
     /// Executes the relevant application method based on the type of the
     /// request, and produces the corresponding response.
     fn handle(&self, request: Request) -> Response;
@@ -189,6 +296,13 @@ impl<A: Application> RequestDispatcher for A {
                 Value::ProcessProposal(req) => {
                     response::Value::ProcessProposal(self.process_proposal(req))
                 },
+                Value::ExtendVote(req) => response::Value::ExtendVote(self.extend_vote(req)),
+                Value::VerifyVoteExtension(req) => {
+                    response::Value::VerifyVoteExtension(self.verify_vote_extension(req))
+                },
+                Value::FinalizeBlock(req) => {
+                    response::Value::FinalizeBlock(self.finalize_block(req))
+                },
             }),
         }
     }
@@ -0,0 +1,383 @@
+//! Asynchronous ABCI application interface.
+
+use std::{
+    panic,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use tendermint_proto::v0_37::abci::{
+    response_process_proposal, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
+    RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo, RequestLoadSnapshotChunk,
+    RequestOfferSnapshot, RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock,
+    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock,
+    ResponseFlush, ResponseInfo, ResponseListSnapshots, ResponseLoadSnapshotChunk,
+    ResponseOfferSnapshot, ResponsePrepareProposal, ResponseProcessProposal, ResponseQuery,
+};
+
+use crate::{
+    messages::{
+        request::Value, response, response_verify_vote_extension, Request, RequestExtendVote,
+        RequestFinalizeBlock, RequestInitChain, RequestPrepareProposal, RequestProcessProposal,
+        RequestVerifyVoteExtension, Response, ResponseExtendVote, ResponseFinalizeBlock,
+        ResponseInitChain, ResponseVerifyVoteExtension,
+    },
+    Application,
+};
+
+/// An asynchronous ABCI application, served by an [`AsyncServer`].
+///
+/// Applications are `Send` + `Sync` + `Clone` + `'static` because they are
+/// cloned for each incoming connection to the server, and shared by the
+/// requests of a connection which are handled concurrently. It is up to the
+/// application developer to manage shared state between these clones of their
+/// application.
+///
+/// The methods have the same default implementations as the ones of
+/// [`Application`], which can be served by an [`AsyncServer`] through a
+/// [`SyncAdapter`].
+///
+/// [`AsyncServer`]: crate::AsyncServer
+#[async_trait]
+pub trait AsyncApplication: Send + Sync + Clone + 'static {
+    /// Echo back the same message as provided in the request.
+    async fn echo(&self, request: RequestEcho) -> ResponseEcho {
+        ResponseEcho {
+            message: request.message,
+        }
+    }
+
+    /// Provide information about the ABCI application.
+    async fn info(&self, _request: RequestInfo) -> ResponseInfo {
+        Default::default()
+    }
+
+    /// Called once upon genesis.
+    async fn init_chain(&self, _request: RequestInitChain) -> ResponseInitChain {
+        Default::default()
+    }
+
+    /// Query the application for data at the current or past height.
+    async fn query(&self, _request: RequestQuery) -> ResponseQuery {
+        Default::default()
+    }
+
+    /// Check the given transaction before putting it into the local mempool.
+    async fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
+        Default::default()
+    }
+
+    /// Signals the beginning of a new block, prior to any `DeliverTx` calls.
+    async fn begin_block(&self, _request: RequestBeginBlock) -> ResponseBeginBlock {
+        Default::default()
+    }
+
+    /// Apply a transaction to the application's state.
+    async fn deliver_tx(&self, _request: RequestDeliverTx) -> ResponseDeliverTx {
+        Default::default()
+    }
+
+    /// Signals the end of a block.
+    async fn end_block(&self, _request: RequestEndBlock) -> ResponseEndBlock {
+        Default::default()
+    }
+
+    /// Signals that messages queued on the client should be flushed to the server.
+    async fn flush(&self) -> ResponseFlush {
+        ResponseFlush {}
+    }
+
+    /// Commit the current state at the current height.
+    async fn commit(&self) -> ResponseCommit {
+        Default::default()
+    }
+
+    /// Used during state sync to discover available snapshots on peers.
+    async fn list_snapshots(&self) -> ResponseListSnapshots {
+        Default::default()
+    }
+
+    /// Called when bootstrapping the node using state sync.
+    async fn offer_snapshot(&self, _request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+        Default::default()
+    }
+
+    /// Used during state sync to retrieve chunks of snapshots from peers.
+    async fn load_snapshot_chunk(
+        &self,
+        _request: RequestLoadSnapshotChunk,
+    ) -> ResponseLoadSnapshotChunk {
+        Default::default()
+    }
+
+    /// Apply the given snapshot chunk to the application's state.
+    async fn apply_snapshot_chunk(
+        &self,
+        _request: RequestApplySnapshotChunk,
+    ) -> ResponseApplySnapshotChunk {
+        Default::default()
+    }
+
+    /// A stage where the application can modify the list of transactions
+    /// in the preliminary proposal.
+    ///
+    /// The default implementation is the one of
+    /// [`Application::prepare_proposal`].
+    ///
+    /// This method is introduced in ABCI++.
+    async fn prepare_proposal(&self, request: RequestPrepareProposal) -> ResponsePrepareProposal {
+        super::prepare_proposal(request)
+    }
+
+    /// A stage where the application can accept or reject the proposed block.
+    ///
+    /// The default implementation returns the status value of `ACCEPT`.
+    ///
+    /// This method is introduced in ABCI++.
+    async fn process_proposal(&self, _request: RequestProcessProposal) -> ResponseProcessProposal {
+        ResponseProcessProposal {
+            status: response_process_proposal::ProposalStatus::Accept as i32,
+        }
+    }
+
+    /// Provide the extension of the precommit of this validator for the
+    /// given block.
+    ///
+    /// The default implementation returns an empty vote extension.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    async fn extend_vote(&self, _request: RequestExtendVote) -> ResponseExtendVote {
+        Default::default()
+    }
+
+    /// Verify the extension of the precommit of another validator.
+    ///
+    /// The default implementation returns the status value of `ACCEPT`.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    async fn verify_vote_extension(
+        &self,
+        _request: RequestVerifyVoteExtension,
+    ) -> ResponseVerifyVoteExtension {
+        ResponseVerifyVoteExtension {
+            status: response_verify_vote_extension::VerifyStatus::Accept as i32,
+        }
+    }
+
+    /// Execute a decided block, in place of the `BeginBlock`, `DeliverTx` and
+    /// `EndBlock` calls of ABCI 1.0.
+    ///
+    /// The default implementation is the one of
+    /// [`Application::finalize_block`], which returns an empty app hash.
+    ///
+    /// This method is introduced in ABCI 2.0.
+    async fn finalize_block(&self, request: RequestFinalizeBlock) -> ResponseFinalizeBlock {
+        let begin_block = self.begin_block(super::begin_block_request(&request)).await;
+        let mut tx_results = Vec::with_capacity(request.txs.len());
+        for tx in request.txs {
+            tx_results.push(self.deliver_tx(RequestDeliverTx { tx }).await);
+        }
+        let end_block = self
+            .end_block(RequestEndBlock {
+                height: request.height,
+            })
+            .await;
+        super::finalize_block_response(begin_block, tx_results, end_block)
+    }
+}
+
+/// Provides a mechanism for the [`AsyncServer`] and the gRPC server to
+/// execute incoming requests while expecting the correct response types, as
+/// [`RequestDispatcher`] does for the [`Server`].
+///
+/// It is implemented by all the [`AsyncApplication`]s, and by the
+/// applications wrapped in the [`layer`](crate::layer)s, which the servers
+/// serve alike.
+///
+/// [`AsyncServer`]: crate::AsyncServer
+/// [`RequestDispatcher`]: crate::RequestDispatcher
+/// [`Server`]: crate::Server
+#[async_trait]
+pub trait AsyncRequestDispatcher: Send + Sync + Clone + 'static {
+    /// Executes the relevant application method based on the type of the
+    /// request, and produces the corresponding response.
+    async fn handle(&self, request: Request) -> Response;
+}
+
+// Implement `AsyncRequestDispatcher` for all `AsyncApplication`s.
+#[async_trait]
+impl<A: AsyncApplication> AsyncRequestDispatcher for A {
+    async fn handle(&self, request: Request) -> Response {
+        dispatch(self, request).await
+    }
+}
+
+async fn dispatch<A: AsyncApplication>(app: &A, request: Request) -> Response {
+    tracing::debug!("Incoming request: {:?}", request);
+    Response {
+        value: Some(match request.value.unwrap() {
+            Value::Echo(req) => response::Value::Echo(app.echo(req).await),
+            Value::Flush(_) => response::Value::Flush(app.flush().await),
+            Value::Info(req) => response::Value::Info(app.info(req).await),
+            Value::InitChain(req) => response::Value::InitChain(app.init_chain(req).await),
+            Value::Query(req) => response::Value::Query(app.query(req).await),
+            Value::BeginBlock(req) => response::Value::BeginBlock(app.begin_block(req).await),
+            Value::CheckTx(req) => response::Value::CheckTx(app.check_tx(req).await),
+            Value::DeliverTx(req) => response::Value::DeliverTx(app.deliver_tx(req).await),
+            Value::EndBlock(req) => response::Value::EndBlock(app.end_block(req).await),
+            Value::Commit(_) => response::Value::Commit(app.commit().await),
+            Value::ListSnapshots(_) => response::Value::ListSnapshots(app.list_snapshots().await),
+            Value::OfferSnapshot(req) => {
+                response::Value::OfferSnapshot(app.offer_snapshot(req).await)
+            },
+            Value::LoadSnapshotChunk(req) => {
+                response::Value::LoadSnapshotChunk(app.load_snapshot_chunk(req).await)
+            },
+            Value::ApplySnapshotChunk(req) => {
+                response::Value::ApplySnapshotChunk(app.apply_snapshot_chunk(req).await)
+            },
+            Value::PrepareProposal(req) => {
+                response::Value::PrepareProposal(app.prepare_proposal(req).await)
+            },
+            Value::ProcessProposal(req) => {
+                response::Value::ProcessProposal(app.process_proposal(req).await)
+            },
+            Value::ExtendVote(req) => response::Value::ExtendVote(app.extend_vote(req).await),
+            Value::VerifyVoteExtension(req) => {
+                response::Value::VerifyVoteExtension(app.verify_vote_extension(req).await)
+            },
+            Value::FinalizeBlock(req) => {
+                response::Value::FinalizeBlock(app.finalize_block(req).await)
+            },
+        }),
+    }
+}
+
+/// Serves a synchronous [`Application`] as an [`AsyncApplication`].
+///
+/// The methods of the application are called on the blocking threads of the
+/// Tokio runtime, one at a time for each clone of the adapter, so that the
+/// application is used exactly as by the synchronous [`Server`]: cloning the
+/// adapter clones the application, once for each connection to the server.
+///
+/// [`Server`]: crate::Server
+pub struct SyncAdapter<A> {
+    app: Arc<Mutex<A>>,
+}
+
+impl<A: Application> SyncAdapter<A> {
+    /// Adapt the given application.
+    pub fn new(app: A) -> Self {
+        Self {
+            app: Arc::new(Mutex::new(app)),
+        }
+    }
+
+    // Call the application on a blocking thread, resuming its panics, if
+    // any, on the calling task.
+    async fn call<R, F>(&self, f: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce(&A) -> R + Send + 'static,
+    {
+        let app = self.app.clone();
+        match tokio::task::spawn_blocking(move || f(&app.lock().unwrap())).await {
+            Ok(response) => response,
+            Err(e) => panic::resume_unwind(e.into_panic()),
+        }
+    }
+}
+
+impl<A: Application> Clone for SyncAdapter<A> {
+    fn clone(&self) -> Self {
+        Self::new(self.app.lock().unwrap().clone())
+    }
+}
+
+#[async_trait]
+impl<A: Application> AsyncApplication for SyncAdapter<A> {
+    async fn echo(&self, request: RequestEcho) -> ResponseEcho {
+        self.call(|app| app.echo(request)).await
+    }
+
+    async fn info(&self, request: RequestInfo) -> ResponseInfo {
+        self.call(|app| app.info(request)).await
+    }
+
+    async fn init_chain(&self, request: RequestInitChain) -> ResponseInitChain {
+        self.call(|app| app.init_chain(request)).await
+    }
+
+    async fn query(&self, request: RequestQuery) -> ResponseQuery {
+        self.call(|app| app.query(request)).await
+    }
+
+    async fn check_tx(&self, request: RequestCheckTx) -> ResponseCheckTx {
+        self.call(|app| app.check_tx(request)).await
+    }
+
+    async fn begin_block(&self, request: RequestBeginBlock) -> ResponseBeginBlock {
+        self.call(|app| app.begin_block(request)).await
+    }
+
+    async fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
+        self.call(|app| app.deliver_tx(request)).await
+    }
+
+    async fn end_block(&self, request: RequestEndBlock) -> ResponseEndBlock {
+        self.call(|app| app.end_block(request)).await
+    }
+
+    async fn flush(&self) -> ResponseFlush {
+        self.call(|app| app.flush()).await
+    }
+
+    async fn commit(&self) -> ResponseCommit {
+        self.call(|app| app.commit()).await
+    }
+
+    async fn list_snapshots(&self) -> ResponseListSnapshots {
+        self.call(|app| app.list_snapshots()).await
+    }
+
+    async fn offer_snapshot(&self, request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+        self.call(|app| app.offer_snapshot(request)).await
+    }
+
+    async fn load_snapshot_chunk(
+        &self,
+        request: RequestLoadSnapshotChunk,
+    ) -> ResponseLoadSnapshotChunk {
+        self.call(|app| app.load_snapshot_chunk(request)).await
+    }
+
+    async fn apply_snapshot_chunk(
+        &self,
+        request: RequestApplySnapshotChunk,
+    ) -> ResponseApplySnapshotChunk {
+        self.call(|app| app.apply_snapshot_chunk(request)).await
+    }
+
+    async fn prepare_proposal(&self, request: RequestPrepareProposal) -> ResponsePrepareProposal {
+        self.call(|app| app.prepare_proposal(request)).await
+    }
+
+    async fn process_proposal(&self, request: RequestProcessProposal) -> ResponseProcessProposal {
+        self.call(|app| app.process_proposal(request)).await
+    }
+
+    async fn extend_vote(&self, request: RequestExtendVote) -> ResponseExtendVote {
+        self.call(|app| app.extend_vote(request)).await
+    }
+
+    async fn verify_vote_extension(
+        &self,
+        request: RequestVerifyVoteExtension,
+    ) -> ResponseVerifyVoteExtension {
+        self.call(|app| app.verify_vote_extension(request)).await
+    }
+
+    async fn finalize_block(&self, request: RequestFinalizeBlock) -> ResponseFinalizeBlock {
+        self.call(|app| app.finalize_block(request)).await
+    }
+}
@@ -1,24 +1,56 @@
 //! In-memory key/value store ABCI application.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::mpsc::{channel, Receiver, Sender},
 };
 
-use bytes::BytesMut;
+use bytes::{Buf, Bytes, BytesMut};
+use sha2::{Digest, Sha256};
 use tendermint_proto::v0_37::abci::{
-    Event, EventAttribute, RequestCheckTx, RequestDeliverTx, RequestInfo, RequestQuery,
-    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseInfo, ResponseQuery,
+    response_offer_snapshot, Event, EventAttribute, RequestApplySnapshotChunk, RequestCheckTx,
+    RequestDeliverTx, RequestInfo, RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery,
+    ResponseApplySnapshotChunk, ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseInfo,
+    ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponseQuery,
+    Snapshot,
 };
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::{codec::MAX_VARINT_LENGTH, Application, Error};
+use crate::{
+    codec::MAX_VARINT_LENGTH,
+    messages::{transcode, RequestFinalizeBlock, ResponseFinalizeBlock},
+    snapshot::{self, ApplyChunk, SnapshotProvider},
+    Application, Error,
+};
+
+/// The format of the snapshots of the key/value store: the key/value pairs,
+/// sorted by key, each one encoded as the length-prefixed key followed by the
+/// length-prefixed value.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// The size of the chunks of the snapshots of the key/value store, unless
+/// configured otherwise (64KB).
+pub const DEFAULT_SNAPSHOT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The number of snapshots of the key/value store which are kept, unless
+/// configured otherwise.
+pub const DEFAULT_SNAPSHOT_KEEP_RECENT: usize = 2;
+
+/// The size of the hash of each chunk, as listed in the metadata of the
+/// snapshots.
+const CHUNK_HASH_LENGTH: usize = 32;
 
 /// In-memory, hashmap-backed key/value store ABCI application.
 ///
 /// This structure effectively just serves as a handle to the actual key/value
 /// store - the [`KeyValueStoreDriver`].
 ///
+/// The store takes part in state sync once snapshots are enabled with
+/// [`KeyValueStoreDriver::snapshot_interval`]: it takes a snapshot of its
+/// state every few heights, serves them in chunks, and restores its state
+/// from the snapshots of other nodes, verifying the hash of every chunk and
+/// the application hash of the restored state.
+///
 /// ## Example
 /// ```rust
 /// use tendermint_abci::{KeyValueStoreApp, ServerBuilder, ClientBuilder};
@@ -108,6 +140,47 @@ impl KeyValueStoreApp {
         )?;
         channel_recv(&result_rx)
     }
+
+    fn request<T>(&self, make_command: impl FnOnce(Sender<T>) -> Command) -> Result<T, Error> {
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, make_command(result_tx))?;
+        channel_recv(&result_rx)
+    }
+}
+
+impl SnapshotProvider for KeyValueStoreApp {
+    fn snapshots(&self) -> Vec<Snapshot> {
+        self.request(|result_tx| Command::ListSnapshots { result_tx })
+            .unwrap()
+    }
+
+    fn load_chunk(&self, height: u64, format: u32, index: u32) -> Option<Bytes> {
+        self.request(|result_tx| Command::LoadSnapshotChunk {
+            height,
+            format,
+            index,
+            result_tx,
+        })
+        .unwrap()
+    }
+
+    fn offer(&self, snapshot: Snapshot, app_hash: Bytes) -> response_offer_snapshot::Result {
+        self.request(|result_tx| Command::OfferSnapshot {
+            snapshot,
+            app_hash,
+            result_tx,
+        })
+        .unwrap()
+    }
+
+    fn apply_chunk(&self, index: u32, chunk: Bytes) -> ApplyChunk {
+        self.request(|result_tx| Command::ApplySnapshotChunk {
+            index,
+            chunk,
+            result_tx,
+        })
+        .unwrap()
+    }
 }
 
 impl Application for KeyValueStoreApp {
@@ -219,6 +292,22 @@ impl Application for KeyValueStoreApp {
         }
     }
 
+    fn finalize_block(&self, request: RequestFinalizeBlock) -> ResponseFinalizeBlock {
+        let tx_results = request
+            .txs
+            .into_iter()
+            .map(|tx| transcode(&self.deliver_tx(RequestDeliverTx { tx })))
+            .collect();
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, Command::AppHash { result_tx }).unwrap();
+        let app_hash = channel_recv(&result_rx).unwrap();
+        ResponseFinalizeBlock {
+            tx_results,
+            app_hash: app_hash.into(),
+            ..Default::default()
+        }
+    }
+
     fn commit(&self) -> ResponseCommit {
         let (result_tx, result_rx) = channel();
         channel_send(&self.cmd_tx, Command::Commit { result_tx }).unwrap();
@@ -229,6 +318,25 @@ impl Application for KeyValueStoreApp {
             retain_height: height - 1,
         }
     }
+
+    fn list_snapshots(&self) -> ResponseListSnapshots {
+        snapshot::list_snapshots(self)
+    }
+
+    fn offer_snapshot(&self, request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+        snapshot::offer_snapshot(self, request)
+    }
+
+    fn load_snapshot_chunk(&self, request: RequestLoadSnapshotChunk) -> ResponseLoadSnapshotChunk {
+        snapshot::load_snapshot_chunk(self, request)
+    }
+
+    fn apply_snapshot_chunk(
+        &self,
+        request: RequestApplySnapshotChunk,
+    ) -> ResponseApplySnapshotChunk {
+        snapshot::apply_snapshot_chunk(self, request)
+    }
 }
 
 /// Manages key/value store state.
@@ -238,6 +346,28 @@ pub struct KeyValueStoreDriver {
     height: i64,
     app_hash: Vec<u8>,
     cmd_rx: Receiver<Command>,
+    snapshot_interval: u64,
+    snapshot_chunk_size: usize,
+    snapshot_keep_recent: usize,
+    /// The snapshots taken, by height
+    snapshots: BTreeMap<u64, StoredSnapshot>,
+    /// The snapshot being restored, if any
+    restoring: Option<Restoring>,
+}
+
+/// A snapshot of the store, along with its chunks.
+#[derive(Debug)]
+struct StoredSnapshot {
+    snapshot: Snapshot,
+    chunks: Vec<Bytes>,
+}
+
+/// A snapshot being restored, along with the chunks applied so far.
+#[derive(Debug)]
+struct Restoring {
+    snapshot: Snapshot,
+    app_hash: Bytes,
+    chunks: Vec<Bytes>,
 }
 
 impl KeyValueStoreDriver {
@@ -247,9 +377,37 @@ impl KeyValueStoreDriver {
             height: 0,
             app_hash: vec![0_u8; MAX_VARINT_LENGTH],
             cmd_rx,
+            snapshot_interval: 0,
+            snapshot_chunk_size: DEFAULT_SNAPSHOT_CHUNK_SIZE,
+            snapshot_keep_recent: DEFAULT_SNAPSHOT_KEEP_RECENT,
+            snapshots: BTreeMap::new(),
+            restoring: None,
         }
     }
 
+    /// Take a snapshot of the store every `interval` heights, for state sync
+    /// (zero, the default, disables snapshots).
+    #[must_use]
+    pub fn snapshot_interval(mut self, interval: u64) -> Self {
+        self.snapshot_interval = interval;
+        self
+    }
+
+    /// Set the size of the chunks of the snapshots, in bytes (at least one).
+    #[must_use]
+    pub fn snapshot_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.snapshot_chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set the number of snapshots which are kept (at least one), the older
+    /// ones being removed.
+    #[must_use]
+    pub fn snapshot_keep_recent(mut self, keep_recent: usize) -> Self {
+        self.snapshot_keep_recent = keep_recent.max(1);
+        self
+    }
+
     /// Run the driver in the current thread (blocking).
     pub fn run(mut self) -> Result<(), Error> {
         loop {
@@ -273,20 +431,208 @@ impl KeyValueStoreDriver {
                     debug!("Setting \"{}\" = \"{}\"", key, value);
                     channel_send(&result_tx, self.store.insert(key, value))?;
                 },
+                Command::AppHash { result_tx } => channel_send(&result_tx, self.app_hash())?,
                 Command::Commit { result_tx } => self.commit(result_tx)?,
+                Command::ListSnapshots { result_tx } => {
+                    let snapshots = self
+                        .snapshots
+                        .values()
+                        .map(|stored| stored.snapshot.clone())
+                        .collect();
+                    channel_send(&result_tx, snapshots)?
+                },
+                Command::LoadSnapshotChunk {
+                    height,
+                    format,
+                    index,
+                    result_tx,
+                } => {
+                    let chunk = self
+                        .snapshots
+                        .get(&height)
+                        .filter(|stored| stored.snapshot.format == format)
+                        .and_then(|stored| stored.chunks.get(index as usize))
+                        .cloned();
+                    channel_send(&result_tx, chunk)?
+                },
+                Command::OfferSnapshot {
+                    snapshot,
+                    app_hash,
+                    result_tx,
+                } => channel_send(&result_tx, self.offer_snapshot(snapshot, app_hash))?,
+                Command::ApplySnapshotChunk {
+                    index,
+                    chunk,
+                    result_tx,
+                } => channel_send(&result_tx, self.apply_snapshot_chunk(index, chunk))?,
             }
         }
     }
 
-    fn commit(&mut self, result_tx: Sender<(i64, Vec<u8>)>) -> Result<(), Error> {
+    fn app_hash(&self) -> Vec<u8> {
         // As in the Go-based key/value store, simply encode the number of
         // items as the "app hash"
         let mut app_hash = BytesMut::with_capacity(MAX_VARINT_LENGTH);
         prost::encoding::encode_varint(self.store.len() as u64, &mut app_hash);
-        self.app_hash = app_hash.to_vec();
+        app_hash.to_vec()
+    }
+
+    fn commit(&mut self, result_tx: Sender<(i64, Vec<u8>)>) -> Result<(), Error> {
+        self.app_hash = self.app_hash();
         self.height += 1;
+        if self.snapshot_interval > 0 && self.height as u64 % self.snapshot_interval == 0 {
+            self.take_snapshot();
+        }
         channel_send(&result_tx, (self.height, self.app_hash.clone()))
     }
+
+    /// Take a snapshot of the store at the current height, removing the
+    /// oldest snapshots beyond the ones to keep.
+    fn take_snapshot(&mut self) {
+        let content = encode_snapshot(&self.store);
+        let mut chunks = content
+            .chunks(self.snapshot_chunk_size)
+            .map(Bytes::copy_from_slice)
+            .collect::<Vec<_>>();
+        // A snapshot has at least one chunk, even when the store is empty.
+        if chunks.is_empty() {
+            chunks.push(Bytes::new());
+        }
+        let metadata = chunks
+            .iter()
+            .flat_map(|chunk| Sha256::digest(chunk))
+            .collect::<Vec<u8>>();
+        let height = self.height as u64;
+        let snapshot = Snapshot {
+            height,
+            format: SNAPSHOT_FORMAT,
+            chunks: chunks.len() as u32,
+            hash: Sha256::digest(&content).to_vec().into(),
+            metadata: metadata.into(),
+        };
+        info!(
+            "Took snapshot at height {} ({} chunks)",
+            height, snapshot.chunks
+        );
+        self.snapshots
+            .insert(height, StoredSnapshot { snapshot, chunks });
+        while self.snapshots.len() > self.snapshot_keep_recent {
+            self.snapshots.pop_first();
+        }
+    }
+
+    fn offer_snapshot(
+        &mut self,
+        snapshot: Snapshot,
+        app_hash: Bytes,
+    ) -> response_offer_snapshot::Result {
+        if snapshot.format != SNAPSHOT_FORMAT {
+            return response_offer_snapshot::Result::RejectFormat;
+        }
+        if snapshot.chunks == 0
+            || snapshot.metadata.len() != snapshot.chunks as usize * CHUNK_HASH_LENGTH
+            || i64::try_from(snapshot.height).is_err()
+        {
+            return response_offer_snapshot::Result::Reject;
+        }
+        info!(
+            "Restoring snapshot at height {} ({} chunks)",
+            snapshot.height, snapshot.chunks
+        );
+        self.restoring = Some(Restoring {
+            snapshot,
+            app_hash,
+            chunks: Vec::new(),
+        });
+        response_offer_snapshot::Result::Accept
+    }
+
+    fn apply_snapshot_chunk(&mut self, index: u32, chunk: Bytes) -> ApplyChunk {
+        let Some(restoring) = &mut self.restoring else {
+            return ApplyChunk::NotRestoring;
+        };
+        let index = index as usize;
+        let hashes = &restoring.snapshot.metadata;
+        if index != restoring.chunks.len()
+            || index >= restoring.snapshot.chunks as usize
+            || Sha256::digest(&chunk)[..]
+                != hashes[index * CHUNK_HASH_LENGTH..(index + 1) * CHUNK_HASH_LENGTH]
+        {
+            warn!("Invalid chunk {} of snapshot", index);
+            return ApplyChunk::InvalidChunk;
+        }
+        restoring.chunks.push(chunk);
+        if restoring.chunks.len() < restoring.snapshot.chunks as usize {
+            return ApplyChunk::Applied;
+        }
+
+        // The last chunk was applied: the whole snapshot is verified before
+        // replacing the store with its content.
+        let Some(restoring) = self.restoring.take() else {
+            return ApplyChunk::NotRestoring;
+        };
+        let content = restoring.chunks.concat();
+        let decoded = if Sha256::digest(&content)[..] == restoring.snapshot.hash[..] {
+            decode_snapshot(&content)
+        } else {
+            None
+        };
+        let store = match decoded {
+            Some(store) => store,
+            None => {
+                warn!("Invalid snapshot at height {}", restoring.snapshot.height);
+                return ApplyChunk::InvalidSnapshot;
+            },
+        };
+        let previous = std::mem::replace(&mut self.store, store);
+        let app_hash = self.app_hash();
+        if app_hash != restoring.app_hash {
+            warn!(
+                "Snapshot at height {} does not match the app hash",
+                restoring.snapshot.height
+            );
+            self.store = previous;
+            return ApplyChunk::InvalidSnapshot;
+        }
+        self.app_hash = app_hash;
+        self.height = restoring.snapshot.height as i64;
+        info!("Restored snapshot at height {}", self.height);
+        ApplyChunk::Applied
+    }
+}
+
+/// Encode the key/value pairs of the store in the snapshot format.
+fn encode_snapshot(store: &HashMap<String, String>) -> Vec<u8> {
+    let sorted = store.iter().collect::<BTreeMap<_, _>>();
+    let mut content = Vec::new();
+    for (key, value) in sorted {
+        for bytes in [key.as_bytes(), value.as_bytes()] {
+            prost::encoding::encode_varint(bytes.len() as u64, &mut content);
+            content.extend_from_slice(bytes);
+        }
+    }
+    content
+}
+
+/// Decode the key/value pairs of a snapshot, if it is well-formed.
+fn decode_snapshot(mut content: &[u8]) -> Option<HashMap<String, String>> {
+    fn decode_string(content: &mut &[u8]) -> Option<String> {
+        let len = usize::try_from(prost::encoding::decode_varint(content).ok()?).ok()?;
+        if content.remaining() < len {
+            return None;
+        }
+        let string = String::from_utf8(content[..len].to_vec()).ok()?;
+        content.advance(len);
+        Some(string)
+    }
+
+    let mut store = HashMap::new();
+    while content.has_remaining() {
+        let key = decode_string(&mut content)?;
+        let value = decode_string(&mut content)?;
+        store.insert(key, value);
+    }
+    Some(store)
 }
 
 #[derive(Debug, Clone)]
@@ -304,9 +650,33 @@ enum Command {
         value: String,
         result_tx: Sender<Option<String>>,
     },
+    /// Compute the hash of the current state of the application, which is
+    /// only recorded on commit.
+    AppHash { result_tx: Sender<Vec<u8>> },
     /// Commit the current state of the application, which involves recomputing
     /// the application's hash.
     Commit { result_tx: Sender<(i64, Vec<u8>)> },
+    /// List the snapshots taken.
+    ListSnapshots { result_tx: Sender<Vec<Snapshot>> },
+    /// Load a chunk of a snapshot.
+    LoadSnapshotChunk {
+        height: u64,
+        format: u32,
+        index: u32,
+        result_tx: Sender<Option<Bytes>>,
+    },
+    /// Start restoring a snapshot.
+    OfferSnapshot {
+        snapshot: Snapshot,
+        app_hash: Bytes,
+        result_tx: Sender<response_offer_snapshot::Result>,
+    },
+    /// Apply the next chunk of the snapshot being restored.
+    ApplySnapshotChunk {
+        index: u32,
+        chunk: Bytes,
+        result_tx: Sender<ApplyChunk>,
+    },
 }
 
 fn channel_send<T>(tx: &Sender<T>, value: T) -> Result<(), Error> {
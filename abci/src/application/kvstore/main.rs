@@ -1,7 +1,7 @@
 //! In-memory key/value store application for Tendermint.
 
 use structopt::StructOpt;
-use tendermint_abci::{KeyValueStoreApp, ServerBuilder};
+use tendermint_abci::{Address, KeyValueStoreApp, ServerBuilder};
 use tracing_subscriber::filter::LevelFilter;
 
 #[derive(Debug, StructOpt)]
@@ -14,11 +14,26 @@ struct Opt {
     #[structopt(short, long, default_value = "26658")]
     port: u16,
 
+    /// Bind the server to this address instead, e.g.
+    /// `unix:///path/to/kvstore.sock` to serve over a Unix domain socket
+    /// (overrides --host and --port).
+    #[structopt(short, long)]
+    address: Option<Address>,
+
     /// The default server read buffer size, in bytes, for each incoming client
     /// connection.
     #[structopt(short, long, default_value = "1048576")]
     read_buf_size: usize,
 
+    /// Take a snapshot of the store every this many heights, for state sync
+    /// (0 disables snapshots).
+    #[structopt(long, default_value = "0")]
+    snapshot_interval: u64,
+
+    /// The size of the chunks of the snapshots, in bytes.
+    #[structopt(long, default_value = "65536")]
+    snapshot_chunk_size: usize,
+
     /// Increase output logging verbosity to DEBUG level.
     #[structopt(short, long)]
     verbose: bool,
@@ -40,9 +55,15 @@ fn main() {
     tracing_subscriber::fmt().with_max_level(log_level).init();
 
     let (app, driver) = KeyValueStoreApp::new();
-    let server = ServerBuilder::new(opt.read_buf_size)
-        .bind(format!("{}:{}", opt.host, opt.port), app)
-        .unwrap();
+    let driver = driver
+        .snapshot_interval(opt.snapshot_interval)
+        .snapshot_chunk_size(opt.snapshot_chunk_size);
+    let builder = ServerBuilder::new(opt.read_buf_size);
+    let server = match opt.address {
+        Some(address) => builder.bind_address(&address, app),
+        None => builder.bind(format!("{}:{}", opt.host, opt.port), app),
+    }
+    .unwrap();
     std::thread::spawn(move || driver.run());
     server.listen().unwrap();
 }
@@ -1,18 +1,33 @@
 //! Blocking ABCI client.
+//!
+//! Speaks the socket protocol from the side of the consensus engine, so that
+//! applications can be driven from their integration tests without running a
+//! node: every request is followed by a `Flush` request, which servers may
+//! wait for before writing out their responses, as do the servers of
+//! Tendermint and CometBFT.
 
 use std::net::{TcpStream, ToSocketAddrs};
 
 use tendermint_proto::v0_37::abci::{
-    request, response, Request, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
-    RequestCommit, RequestDeliverTx, RequestEcho, RequestEndBlock, RequestFlush, RequestInfo,
-    RequestInitChain, RequestListSnapshots, RequestLoadSnapshotChunk, RequestOfferSnapshot,
-    RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
-    ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush, ResponseInfo,
-    ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
-    ResponseQuery,
+    RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestCommit, RequestDeliverTx,
+    RequestEcho, RequestEndBlock, RequestFlush, RequestInfo, RequestListSnapshots,
+    RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery, ResponseApplySnapshotChunk,
+    ResponseBeginBlock, ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho,
+    ResponseEndBlock, ResponseFlush, ResponseInfo, ResponseListSnapshots,
+    ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponsePrepareProposal,
+    ResponseProcessProposal, ResponseQuery,
 };
 
-use crate::{codec::ClientCodec, Error};
+use crate::{
+    codec::ClientCodec,
+    messages::{
+        request, response, Request, RequestExtendVote, RequestFinalizeBlock, RequestInitChain,
+        RequestPrepareProposal, RequestProcessProposal, RequestVerifyVoteExtension,
+        ResponseExtendVote, ResponseFinalizeBlock, ResponseInitChain, ResponseVerifyVoteExtension,
+    },
+    transport::{Address, Stream},
+    Error,
+};
 
 /// The size of the read buffer for the client in its receiving of responses
 /// from the server.
@@ -33,9 +48,28 @@ impl ClientBuilder {
     /// address.
     pub fn connect<A: ToSocketAddrs>(self, addr: A) -> Result<Client, Error> {
         let stream = TcpStream::connect(addr).map_err(Error::io)?;
-        Ok(Client {
+        Ok(self.client(Stream::Tcp(stream)))
+    }
+
+    /// Client constructor that attempts to connect to the Unix domain socket
+    /// at the given path.
+    #[cfg(unix)]
+    pub fn connect_unix<P: AsRef<std::path::Path>>(self, path: P) -> Result<Client, Error> {
+        self.connect_address(&Address::Unix(path.as_ref().to_path_buf()))
+    }
+
+    /// Client constructor that attempts to connect to the given [`Address`],
+    /// e.g. parsed from a `tcp://127.0.0.1:26658` or
+    /// `unix:///path/to/app.sock` URL.
+    pub fn connect_address(self, address: &Address) -> Result<Client, Error> {
+        let stream = Stream::connect(address)?;
+        Ok(self.client(stream))
+    }
+
+    fn client(self, stream: Stream) -> Client {
+        Client {
             codec: ClientCodec::new(stream, self.read_buf_size),
-        })
+        }
     }
 }
 
@@ -49,13 +83,14 @@ impl Default for ClientBuilder {
 
 /// Blocking ABCI client.
 pub struct Client {
-    codec: ClientCodec<TcpStream>,
+    codec: ClientCodec<Stream>,
 }
 
 macro_rules! perform {
     ($self:expr, $type:ident, $req:expr) => {
         match $self.perform(request::Value::$type($req))? {
             response::Value::$type(r) => Ok(r),
+            response::Value::Exception(e) => Err(Error::server_exception(e.error)),
             r => {
                 Err(Error::unexpected_server_response_type(stringify!($type).to_string(), r).into())
             },
@@ -104,6 +139,7 @@ impl Client {
         perform!(self, EndBlock, req)
     }
 
+    /// Ask the server to write out its responses to the requests sent so far.
     pub fn flush(&mut self) -> Result<ResponseFlush, Error> {
         perform!(self, Flush, RequestFlush {})
     }
@@ -142,8 +178,91 @@ impl Client {
         perform!(self, ApplySnapshotChunk, req)
     }
 
+    /// Ask the application to prepare the proposal of a block.
+    pub fn prepare_proposal(
+        &mut self,
+        req: RequestPrepareProposal,
+    ) -> Result<ResponsePrepareProposal, Error> {
+        perform!(self, PrepareProposal, req)
+    }
+
+    /// Ask the application whether to accept the proposal of a block.
+    pub fn process_proposal(
+        &mut self,
+        req: RequestProcessProposal,
+    ) -> Result<ResponseProcessProposal, Error> {
+        perform!(self, ProcessProposal, req)
+    }
+
+    /// Ask the application for the extension of a precommit (ABCI 2.0).
+    pub fn extend_vote(&mut self, req: RequestExtendVote) -> Result<ResponseExtendVote, Error> {
+        perform!(self, ExtendVote, req)
+    }
+
+    /// Ask the application to verify the extension of the precommit of
+    /// another validator (ABCI 2.0).
+    pub fn verify_vote_extension(
+        &mut self,
+        req: RequestVerifyVoteExtension,
+    ) -> Result<ResponseVerifyVoteExtension, Error> {
+        perform!(self, VerifyVoteExtension, req)
+    }
+
+    /// Deliver a decided block to the application (ABCI 2.0).
+    pub fn finalize_block(
+        &mut self,
+        req: RequestFinalizeBlock,
+    ) -> Result<ResponseFinalizeBlock, Error> {
+        perform!(self, FinalizeBlock, req)
+    }
+
+    /// Send the given requests without waiting for their responses, like the
+    /// mempool connection of a node does, then flush them and return their
+    /// responses in order.
+    ///
+    /// Exceptions are returned as responses rather than errors, so that the
+    /// responses to the other requests are not lost.
+    pub fn pipeline<I>(&mut self, reqs: I) -> Result<Vec<response::Value>, Error>
+    where
+        I: IntoIterator<Item = request::Value>,
+    {
+        let mut pending = 0;
+        for req in reqs {
+            self.codec.send(Request { value: Some(req) })?;
+            pending += 1;
+        }
+        self.flush_pending(pending)
+    }
+
     fn perform(&mut self, req: request::Value) -> Result<response::Value, Error> {
+        let is_flush = matches!(req, request::Value::Flush(_));
         self.codec.send(Request { value: Some(req) })?;
+        if is_flush {
+            return self.receive();
+        }
+        let mut responses = self.flush_pending(1)?;
+        Ok(responses.remove(0))
+    }
+
+    // Send a `Flush` request after the given number of pending requests, and
+    // receive their responses, followed by that of the flush.
+    fn flush_pending(&mut self, pending: usize) -> Result<Vec<response::Value>, Error> {
+        self.codec.send(Request {
+            value: Some(request::Value::Flush(RequestFlush {})),
+        })?;
+        let responses = (0..pending)
+            .map(|_| self.receive())
+            .collect::<Result<Vec<_>, _>>()?;
+        match self.receive()? {
+            response::Value::Flush(_) => Ok(responses),
+            r => Err(Error::unexpected_server_response_type(
+                "Flush".to_string(),
+                r,
+            )),
+        }
+    }
+
+    fn receive(&mut self) -> Result<response::Value, Error> {
         let res = self
             .codec
             .next()
@@ -9,11 +9,12 @@ use std::{
     marker::PhantomData,
 };
 
+use crate::{
+    error::Error,
+    messages::{Request, Response},
+};
 use bytes::{Buf, BufMut, BytesMut};
 use prost::Message;
-use tendermint_proto::v0_37::abci::{Request, Response};
-
-use crate::error::Error;
 
 /// The maximum number of bytes we expect in a varint. We use this to check if
 /// we're encountering a decoding error for a varint.
@@ -98,7 +99,23 @@ where
 {
     /// Send a message using this codec.
     pub fn send(&mut self, message: O) -> Result<(), Error> {
-        encode_length_delimited(message, &mut self.write_buf)?;
+        self.buffer(message)?;
+        self.flush()
+    }
+
+    /// Encode a message into the write buffer, without writing it out until
+    /// the next call to [`Codec::flush`].
+    pub fn buffer(&mut self, message: O) -> Result<(), Error> {
+        encode_length_delimited(message, &mut self.write_buf)
+    }
+
+    /// The number of bytes of the buffered messages.
+    pub fn buffered_len(&self) -> usize {
+        self.write_buf.len()
+    }
+
+    /// Write out the buffered messages, and flush the stream.
+    pub fn flush(&mut self) -> Result<(), Error> {
         while !self.write_buf.is_empty() {
             let bytes_written = self
                 .stream
@@ -1,7 +1,8 @@
 //! tendermint-abci errors
 
 use flex_error::{define_error, DisplayError};
-use tendermint_proto::v0_37::abci::response::Value;
+
+use crate::messages::response::Value;
 
 define_error! {
     Error {
@@ -17,12 +18,20 @@ define_error! {
             [ DisplayError<prost::DecodeError> ]
             | _ | { "error encoding protocol buffer" },
 
+        InvalidAddress
+            { address: String }
+            | e | { format_args!("invalid ABCI server address: {}", e.address) },
+
         ServerConnectionTerminated
             | _ | { "server connection terminated" },
 
         MalformedServerResponse
             | _ | { "malformed server response" },
 
+        ServerException
+            { error: String }
+            | e | { format_args!("the application responded with an exception: {}", e.error) },
+
         UnexpectedServerResponseType
             {
                 expected: String,
@@ -0,0 +1,483 @@
+//! Middleware wrapping ABCI applications, after the layers of [Tower].
+//!
+//! A [`Layer`] wraps an application into another one, which handles the
+//! requests of the servers before and after the wrapped application does.
+//! Cross-cutting concerns can thus be implemented once, rather than inside
+//! each application:
+//!
+//! - [`LoggingLayer`] logs the requests, responses and handling times,
+//! - [`CatchPanicLayer`] responds with an exception when the application
+//!   panics, instead of closing the connection,
+//! - [`MetricsLayer`] records the latency of each ABCI method,
+//! - [`SizeLimitLayer`] rejects the requests larger than a limit.
+//!
+//! Layers are stacked with a [`LayerBuilder`], the first one added being the
+//! outermost:
+//!
+//! ```
+//! use tendermint_abci::{
+//!     layer::{CatchPanicLayer, LayerBuilder, LoggingLayer, MetricsLayer},
+//!     Application, ServerBuilder,
+//! };
+//!
+//! #[derive(Clone)]
+//! struct MyApp;
+//!
+//! impl Application for MyApp {}
+//!
+//! let metrics = MetricsLayer::new();
+//! let app = LayerBuilder::new()
+//!     .layer(LoggingLayer)
+//!     .layer(metrics.clone())
+//!     .layer(CatchPanicLayer)
+//!     .build(MyApp);
+//! let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+//! ```
+//!
+//! The wrapped applications implement [`RequestDispatcher`], as do all the
+//! [`Application`]s, and are served by the [`Server`]. With the `async`
+//! feature, the wrapped asynchronous applications implement
+//! [`AsyncRequestDispatcher`], as do all the [`AsyncApplication`]s, and are
+//! served by the [`AsyncServer`] and the gRPC server.
+//!
+//! [Tower]: https://docs.rs/tower
+//! [`Server`]: crate::Server
+//! [`Application`]: crate::Application
+//! [`AsyncRequestDispatcher`]: crate::AsyncRequestDispatcher
+//! [`AsyncApplication`]: crate::AsyncApplication
+//! [`AsyncServer`]: crate::AsyncServer
+
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+#[cfg(feature = "async")]
+use futures::FutureExt;
+use prost::Message;
+use tendermint_proto::v0_37::abci::{ResponseCheckTx, ResponseException, ResponseQuery};
+use tracing::{debug, error, trace, warn};
+
+#[cfg(feature = "async")]
+use crate::application::asynchronous::AsyncRequestDispatcher;
+use crate::{
+    application::RequestDispatcher,
+    messages::{request, response, Request, Response},
+};
+
+/// The response code of the transactions and queries rejected by the
+/// [`SizeLimitLayer`].
+pub const REQUEST_TOO_LARGE_CODE: u32 = 1;
+
+/// Wraps an application into another one.
+pub trait Layer<App> {
+    /// The wrapping application.
+    type Application;
+
+    /// Wrap the given application.
+    fn layer(&self, app: App) -> Self::Application;
+}
+
+/// The layer which leaves applications as they are.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity;
+
+impl<App> Layer<App> for Identity {
+    type Application = App;
+
+    fn layer(&self, app: App) -> App {
+        app
+    }
+}
+
+/// Two layers, the outer one wrapping the application wrapped by the inner
+/// one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stack<Inner, Outer> {
+    inner: Inner,
+    outer: Outer,
+}
+
+impl<App, Inner, Outer> Layer<App> for Stack<Inner, Outer>
+where
+    Inner: Layer<App>,
+    Outer: Layer<Inner::Application>,
+{
+    type Application = Outer::Application;
+
+    fn layer(&self, app: App) -> Self::Application {
+        self.outer.layer(self.inner.layer(app))
+    }
+}
+
+/// Stacks layers around an application.
+#[derive(Clone, Debug, Default)]
+pub struct LayerBuilder<L> {
+    layer: L,
+}
+
+impl LayerBuilder<Identity> {
+    /// A builder without any layer.
+    pub fn new() -> Self {
+        Self { layer: Identity }
+    }
+}
+
+impl<L> LayerBuilder<L> {
+    /// Add a layer, wrapped by the layers added before it.
+    pub fn layer<T>(self, layer: T) -> LayerBuilder<Stack<T, L>> {
+        LayerBuilder {
+            layer: Stack {
+                inner: layer,
+                outer: self.layer,
+            },
+        }
+    }
+
+    /// Wrap the given application in the layers.
+    pub fn build<App>(&self, app: App) -> L::Application
+    where
+        L: Layer<App>,
+    {
+        self.layer.layer(app)
+    }
+}
+
+/// The name of the ABCI method of a request, as used by the [`LoggingLayer`]
+/// and the [`MetricsLayer`].
+pub fn method_name(request: &Request) -> &'static str {
+    match &request.value {
+        Some(request::Value::Echo(_)) => "echo",
+        Some(request::Value::Flush(_)) => "flush",
+        Some(request::Value::Info(_)) => "info",
+        Some(request::Value::InitChain(_)) => "init_chain",
+        Some(request::Value::Query(_)) => "query",
+        Some(request::Value::BeginBlock(_)) => "begin_block",
+        Some(request::Value::CheckTx(_)) => "check_tx",
+        Some(request::Value::DeliverTx(_)) => "deliver_tx",
+        Some(request::Value::EndBlock(_)) => "end_block",
+        Some(request::Value::Commit(_)) => "commit",
+        Some(request::Value::ListSnapshots(_)) => "list_snapshots",
+        Some(request::Value::OfferSnapshot(_)) => "offer_snapshot",
+        Some(request::Value::LoadSnapshotChunk(_)) => "load_snapshot_chunk",
+        Some(request::Value::ApplySnapshotChunk(_)) => "apply_snapshot_chunk",
+        Some(request::Value::PrepareProposal(_)) => "prepare_proposal",
+        Some(request::Value::ProcessProposal(_)) => "process_proposal",
+        Some(request::Value::ExtendVote(_)) => "extend_vote",
+        Some(request::Value::VerifyVoteExtension(_)) => "verify_vote_extension",
+        Some(request::Value::FinalizeBlock(_)) => "finalize_block",
+        None => "unknown",
+    }
+}
+
+fn exception(error: String) -> Response {
+    Response {
+        value: Some(response::Value::Exception(ResponseException { error })),
+    }
+}
+
+/// Logs the method and handling time of every request at the debug level,
+/// and the requests and responses themselves at the trace level.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoggingLayer;
+
+impl<App> Layer<App> for LoggingLayer {
+    type Application = Logging<App>;
+
+    fn layer(&self, app: App) -> Logging<App> {
+        Logging { app }
+    }
+}
+
+/// An application wrapped by a [`LoggingLayer`].
+#[derive(Clone, Debug)]
+pub struct Logging<App> {
+    app: App,
+}
+
+impl<App: RequestDispatcher> RequestDispatcher for Logging<App> {
+    fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        trace!("Handling {} request: {:?}", method, request);
+        let start = Instant::now();
+        let response = self.app.handle(request);
+        log_response(method, start.elapsed(), &response);
+        response
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<App: AsyncRequestDispatcher> AsyncRequestDispatcher for Logging<App> {
+    async fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        trace!("Handling {} request: {:?}", method, request);
+        let start = Instant::now();
+        let response = self.app.handle(request).await;
+        log_response(method, start.elapsed(), &response);
+        response
+    }
+}
+
+fn log_response(method: &str, elapsed: Duration, response: &Response) {
+    if let Some(response::Value::Exception(exception)) = &response.value {
+        warn!(
+            "{} request failed after {:?}: {}",
+            method, elapsed, exception.error
+        );
+    } else {
+        debug!("Handled {} request in {:?}", method, elapsed);
+    }
+    trace!("Responding to {} request: {:?}", method, response);
+}
+
+/// Responds with an exception when the application panics handling a
+/// request.
+///
+/// Without this layer, the servers close the connection whose request made
+/// the application panic. Either way, the consensus engine stops, but the
+/// exception tells its operator why.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CatchPanicLayer;
+
+impl<App> Layer<App> for CatchPanicLayer {
+    type Application = CatchPanic<App>;
+
+    fn layer(&self, app: App) -> CatchPanic<App> {
+        CatchPanic { app }
+    }
+}
+
+/// An application wrapped by a [`CatchPanicLayer`].
+#[derive(Clone, Debug)]
+pub struct CatchPanic<App> {
+    app: App,
+}
+
+impl<App: RequestDispatcher> RequestDispatcher for CatchPanic<App> {
+    fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        panic::catch_unwind(AssertUnwindSafe(|| self.app.handle(request)))
+            .unwrap_or_else(|payload| panicked(method, payload.as_ref()))
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<App: AsyncRequestDispatcher> AsyncRequestDispatcher for CatchPanic<App> {
+    async fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        AssertUnwindSafe(self.app.handle(request))
+            .catch_unwind()
+            .await
+            .unwrap_or_else(|payload| panicked(method, payload.as_ref()))
+    }
+}
+
+fn panicked(method: &str, payload: &(dyn Any + Send)) -> Response {
+    let message = panic_message(payload);
+    error!(
+        "Application panicked handling {} request: {}",
+        method, message
+    );
+    exception(format!(
+        "application panicked handling {method} request: {message}"
+    ))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// The latency of the requests of an ABCI method, as recorded by a
+/// [`MetricsLayer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MethodMetrics {
+    /// The number of requests handled.
+    pub count: u64,
+    /// The total time spent handling the requests.
+    pub total: Duration,
+    /// The longest time spent handling a request.
+    pub max: Duration,
+}
+
+impl MethodMetrics {
+    /// The average time spent handling a request, if any was handled.
+    pub fn mean(&self) -> Option<Duration> {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|count| *count > 0)
+            .map(|count| self.total / count)
+    }
+}
+
+/// Records the latency of the requests of each ABCI method.
+///
+/// The metrics are shared by the clones of the layer, and thus by the clones
+/// of the application which the servers make for each connection.
+#[derive(Clone, Debug, Default)]
+pub struct MetricsLayer {
+    methods: Arc<Mutex<BTreeMap<&'static str, MethodMetrics>>>,
+}
+
+impl MetricsLayer {
+    /// A layer without any recorded metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The metrics recorded so far, by method name (see [`method_name`]).
+    pub fn snapshot(&self) -> BTreeMap<&'static str, MethodMetrics> {
+        self.methods.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: &'static str, elapsed: Duration) {
+        let mut methods = self.methods.lock().unwrap();
+        let metrics = methods.entry(method).or_default();
+        metrics.count += 1;
+        metrics.total += elapsed;
+        metrics.max = metrics.max.max(elapsed);
+    }
+}
+
+impl<App> Layer<App> for MetricsLayer {
+    type Application = Metered<App>;
+
+    fn layer(&self, app: App) -> Metered<App> {
+        Metered {
+            app,
+            metrics: self.clone(),
+        }
+    }
+}
+
+/// An application wrapped by a [`MetricsLayer`].
+#[derive(Clone, Debug)]
+pub struct Metered<App> {
+    app: App,
+    metrics: MetricsLayer,
+}
+
+impl<App: RequestDispatcher> RequestDispatcher for Metered<App> {
+    fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        let start = Instant::now();
+        let response = self.app.handle(request);
+        self.metrics.record(method, start.elapsed());
+        response
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<App: AsyncRequestDispatcher> AsyncRequestDispatcher for Metered<App> {
+    async fn handle(&self, request: Request) -> Response {
+        let method = method_name(&request);
+        let start = Instant::now();
+        let response = self.app.handle(request).await;
+        self.metrics.record(method, start.elapsed());
+        response
+    }
+}
+
+/// Rejects the requests whose encoded size exceeds a limit, without passing
+/// them to the application.
+///
+/// Transactions and queries are rejected with a response whose code is
+/// [`REQUEST_TOO_LARGE_CODE`], as the consensus engine only reports these to
+/// their submitters. The other requests are rejected with an exception.
+#[derive(Clone, Copy, Debug)]
+pub struct SizeLimitLayer {
+    max_bytes: usize,
+}
+
+impl SizeLimitLayer {
+    /// A layer rejecting the requests larger than the given number of bytes.
+    pub fn new(max_bytes: usize) -> Self {
+        Self { max_bytes }
+    }
+}
+
+impl<App> Layer<App> for SizeLimitLayer {
+    type Application = SizeLimit<App>;
+
+    fn layer(&self, app: App) -> SizeLimit<App> {
+        SizeLimit {
+            app,
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+/// An application wrapped by a [`SizeLimitLayer`].
+#[derive(Clone, Debug)]
+pub struct SizeLimit<App> {
+    app: App,
+    max_bytes: usize,
+}
+
+impl<App> SizeLimit<App> {
+    /// The response rejecting the given request, if it is too large.
+    fn reject(&self, request: &Request) -> Option<Response> {
+        let size = request.encoded_len();
+        if size <= self.max_bytes {
+            return None;
+        }
+
+        let method = method_name(request);
+        warn!(
+            "Rejecting {} request of {} bytes (limit: {})",
+            method, size, self.max_bytes
+        );
+        let log = format!(
+            "request too large: {size} bytes, limit is {}",
+            self.max_bytes
+        );
+        let value = match &request.value {
+            Some(request::Value::CheckTx(_)) => response::Value::CheckTx(ResponseCheckTx {
+                code: REQUEST_TOO_LARGE_CODE,
+                log,
+                ..Default::default()
+            }),
+            Some(request::Value::Query(query)) => response::Value::Query(ResponseQuery {
+                code: REQUEST_TOO_LARGE_CODE,
+                log,
+                height: query.height,
+                ..Default::default()
+            }),
+            _ => return Some(exception(format!("{method} {log}"))),
+        };
+        Some(Response { value: Some(value) })
+    }
+}
+
+impl<App: RequestDispatcher> RequestDispatcher for SizeLimit<App> {
+    fn handle(&self, request: Request) -> Response {
+        match self.reject(&request) {
+            Some(response) => response,
+            None => self.app.handle(request),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait]
+impl<App: AsyncRequestDispatcher> AsyncRequestDispatcher for SizeLimit<App> {
+    async fn handle(&self, request: Request) -> Response {
+        match self.reject(&request) {
+            Some(response) => response,
+            None => self.app.handle(request).await,
+        }
+    }
+}
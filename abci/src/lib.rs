@@ -7,16 +7,27 @@ mod application;
 mod client;
 mod codec;
 pub mod error;
+pub mod layer;
+pub mod messages;
 mod server;
+pub mod snapshot;
+mod transport;
 
 // Common exports
 // Example applications
+#[cfg(feature = "async")]
+pub use application::asynchronous::{AsyncApplication, AsyncRequestDispatcher, SyncAdapter};
 #[cfg(feature = "echo-app")]
 pub use application::echo::EchoApp;
 #[cfg(feature = "kvstore-app")]
 pub use application::kvstore::{KeyValueStoreApp, KeyValueStoreDriver};
-pub use application::Application;
+pub use application::{Application, RequestDispatcher};
 #[cfg(feature = "client")]
 pub use client::{Client, ClientBuilder};
 pub use error::Error;
+#[cfg(feature = "async")]
+pub use server::asynchronous::{AsyncServer, AsyncServerBuilder};
+#[cfg(feature = "grpc")]
+pub use server::grpc::{GrpcServer, GrpcServerBuilder};
 pub use server::{Server, ServerBuilder};
+pub use transport::Address;
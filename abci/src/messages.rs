@@ -0,0 +1,214 @@
+//! ABCI requests and responses, covering both ABCI 1.0 (Tendermint 0.37) and
+//! ABCI 2.0 (CometBFT 0.38).
+//!
+//! ABCI 2.0 removes the `BeginBlock`, `DeliverTx` and `EndBlock` methods, in
+//! favor of `FinalizeBlock`, and adds the `ExtendVote` and
+//! `VerifyVoteExtension` methods, without renumbering the other ones. The
+//! [`Request`] and [`Response`] messages of this module have the methods of
+//! both versions, and can thus be exchanged with either version of the
+//! consensus engine.
+//!
+//! The messages of the methods added by ABCI 2.0, and the ones of the methods
+//! to which CometBFT 0.38 added fields, are the ones of CometBFT 0.38, from
+//! [`tendermint_proto::v0_38::abci`], and are re-exported by this module
+//! along with the descriptor of the [`abci`] gRPC service of CometBFT 0.38:
+//!
+//! - the `InitChain` request and response, whose consensus parameters have
+//!   the ABCI ones;
+//! - the `PrepareProposal` and `ProcessProposal` requests, whose votes of the
+//!   last commit have a `block_id_flag`, and in `PrepareProposal` the
+//!   signatures of the vote extensions.
+//!
+//! Tendermint 0.37 sends the `signed_last_block` of ABCI 1.0 instead of the
+//! `block_id_flag` of these votes, which is then left unknown.
+//!
+//! The messages of the other methods are the ones of Tendermint 0.37, from
+//! [`tendermint_proto::v0_37::abci`]: those of the methods removed by ABCI
+//! 2.0, those which are the same in both versions, and the `CheckTx` and
+//! `Commit` responses, from which CometBFT 0.38 removed fields that ABCI 1.0
+//! still uses, e.g. the app hash of `Commit`.
+
+use prost::Message;
+pub use tendermint_proto::v0_38::abci::{
+    abci, response_verify_vote_extension, CommitInfo, ExecTxResult, ExtendedCommitInfo,
+    ExtendedVoteInfo, RequestExtendVote, RequestFinalizeBlock, RequestInitChain,
+    RequestPrepareProposal, RequestProcessProposal, RequestVerifyVoteExtension, ResponseExtendVote,
+    ResponseFinalizeBlock, ResponseInitChain, ResponseVerifyVoteExtension, VoteInfo,
+};
+use tendermint_proto::{v0_37, v0_38};
+
+/// An ABCI request, of either ABCI 1.0 or ABCI 2.0.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Request {
+    #[prost(
+        oneof = "request::Value",
+        tags = "1, 2, 3, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20"
+    )]
+    pub value: ::core::option::Option<request::Value>,
+}
+
+/// Nested message and enum types in `Request`.
+pub mod request {
+    use tendermint_proto::v0_37::abci::{
+        RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestCommit,
+        RequestDeliverTx, RequestEcho, RequestEndBlock, RequestFlush, RequestInfo,
+        RequestListSnapshots, RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery,
+    };
+
+    use super::{
+        RequestExtendVote, RequestFinalizeBlock, RequestInitChain, RequestPrepareProposal,
+        RequestProcessProposal, RequestVerifyVoteExtension,
+    };
+
+    #[allow(clippy::derive_partial_eq_without_eq, clippy::large_enum_variant)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Echo(RequestEcho),
+        #[prost(message, tag = "2")]
+        Flush(RequestFlush),
+        #[prost(message, tag = "3")]
+        Info(RequestInfo),
+        #[prost(message, tag = "5")]
+        InitChain(RequestInitChain),
+        #[prost(message, tag = "6")]
+        Query(RequestQuery),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "7")]
+        BeginBlock(RequestBeginBlock),
+        #[prost(message, tag = "8")]
+        CheckTx(RequestCheckTx),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "9")]
+        DeliverTx(RequestDeliverTx),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "10")]
+        EndBlock(RequestEndBlock),
+        #[prost(message, tag = "11")]
+        Commit(RequestCommit),
+        #[prost(message, tag = "12")]
+        ListSnapshots(RequestListSnapshots),
+        #[prost(message, tag = "13")]
+        OfferSnapshot(RequestOfferSnapshot),
+        #[prost(message, tag = "14")]
+        LoadSnapshotChunk(RequestLoadSnapshotChunk),
+        #[prost(message, tag = "15")]
+        ApplySnapshotChunk(RequestApplySnapshotChunk),
+        #[prost(message, tag = "16")]
+        PrepareProposal(RequestPrepareProposal),
+        #[prost(message, tag = "17")]
+        ProcessProposal(RequestProcessProposal),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "18")]
+        ExtendVote(RequestExtendVote),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "19")]
+        VerifyVoteExtension(RequestVerifyVoteExtension),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "20")]
+        FinalizeBlock(RequestFinalizeBlock),
+    }
+}
+
+/// An ABCI response, of either ABCI 1.0 or ABCI 2.0.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Response {
+    #[prost(
+        oneof = "response::Value",
+        tags = "1, 2, 3, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21"
+    )]
+    pub value: ::core::option::Option<response::Value>,
+}
+
+/// Nested message and enum types in `Response`.
+pub mod response {
+    use tendermint_proto::v0_37::abci::{
+        ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
+        ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseException, ResponseFlush,
+        ResponseInfo, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
+        ResponsePrepareProposal, ResponseProcessProposal, ResponseQuery,
+    };
+
+    use super::{
+        ResponseExtendVote, ResponseFinalizeBlock, ResponseInitChain, ResponseVerifyVoteExtension,
+    };
+
+    #[allow(clippy::derive_partial_eq_without_eq, clippy::large_enum_variant)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Exception(ResponseException),
+        #[prost(message, tag = "2")]
+        Echo(ResponseEcho),
+        #[prost(message, tag = "3")]
+        Flush(ResponseFlush),
+        #[prost(message, tag = "4")]
+        Info(ResponseInfo),
+        #[prost(message, tag = "6")]
+        InitChain(ResponseInitChain),
+        #[prost(message, tag = "7")]
+        Query(ResponseQuery),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "8")]
+        BeginBlock(ResponseBeginBlock),
+        #[prost(message, tag = "9")]
+        CheckTx(ResponseCheckTx),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "10")]
+        DeliverTx(ResponseDeliverTx),
+        /// ABCI 1.0 only.
+        #[prost(message, tag = "11")]
+        EndBlock(ResponseEndBlock),
+        #[prost(message, tag = "12")]
+        Commit(ResponseCommit),
+        #[prost(message, tag = "13")]
+        ListSnapshots(ResponseListSnapshots),
+        #[prost(message, tag = "14")]
+        OfferSnapshot(ResponseOfferSnapshot),
+        #[prost(message, tag = "15")]
+        LoadSnapshotChunk(ResponseLoadSnapshotChunk),
+        #[prost(message, tag = "16")]
+        ApplySnapshotChunk(ResponseApplySnapshotChunk),
+        #[prost(message, tag = "17")]
+        PrepareProposal(ResponsePrepareProposal),
+        #[prost(message, tag = "18")]
+        ProcessProposal(ResponseProcessProposal),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "19")]
+        ExtendVote(ResponseExtendVote),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "20")]
+        VerifyVoteExtension(ResponseVerifyVoteExtension),
+        /// ABCI 2.0 only.
+        #[prost(message, tag = "21")]
+        FinalizeBlock(ResponseFinalizeBlock),
+    }
+}
+
+// Convert a message to the one of the other version, whose definition is the
+// same, through its encoding.
+pub(crate) fn transcode<M: Message, N: Message + Default>(message: &M) -> N {
+    N::decode(message.encode_to_vec().as_slice())
+        .expect("the definitions of the messages are the same")
+}
+
+// The votes of a commit, as in a `BeginBlock` request. As Tendermint 0.37
+// did, a validator signed the last block unless its vote is absent.
+pub(crate) fn last_commit_info(commit: &CommitInfo) -> v0_37::abci::CommitInfo {
+    use v0_38::types::BlockIdFlag;
+
+    v0_37::abci::CommitInfo {
+        round: commit.round,
+        votes: commit
+            .votes
+            .iter()
+            .map(|vote| v0_37::abci::VoteInfo {
+                validator: vote.validator.as_ref().map(transcode),
+                signed_last_block: vote.block_id_flag == BlockIdFlag::Commit as i32
+                    || vote.block_id_flag == BlockIdFlag::Nil as i32,
+            })
+            .collect(),
+    }
+}
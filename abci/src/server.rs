@@ -1,21 +1,80 @@
 //! ABCI application server interface.
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+mod pool;
+
 use std::{
-    net::{TcpListener, TcpStream, ToSocketAddrs},
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    net::{Shutdown, TcpListener, ToSocketAddrs},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
     thread,
 };
 
-use tracing::{error, info};
+use tendermint_proto::v0_37::abci::ResponseQuery;
+use tracing::{error, info, warn};
 
-use crate::{application::RequestDispatcher, codec::ServerCodec, error::Error, Application};
+use self::pool::WorkerPool;
+use crate::{
+    application::RequestDispatcher,
+    codec::ServerCodec,
+    error::Error,
+    messages::{request, response, Request, Response},
+    transport::{Address, Listener, Stream, UnixSocketOptions},
+};
 
 /// The size of the read buffer for each incoming connection to the ABCI
 /// server (1MB).
 pub const DEFAULT_SERVER_READ_BUF_SIZE: usize = 1024 * 1024;
 
+/// The number of worker threads handling requests across all the
+/// connections to the ABCI server.
+pub const DEFAULT_SERVER_WORKER_THREADS: usize = 4;
+
+/// The maximum number of connections to the ABCI server open at the same
+/// time. The consensus engine opens four of them.
+pub const DEFAULT_SERVER_MAX_CONNECTIONS: usize = 16;
+
+/// The maximum number of requests read from a connection that may be
+/// waiting to be handled before the server stops reading from it.
+pub const DEFAULT_SERVER_QUEUE_DEPTH: usize = 128;
+
+/// The number of requests waiting to be handled on a connection above which
+/// incoming queries are rejected instead of being queued.
+pub const DEFAULT_SERVER_QUERY_QUEUE_DEPTH: usize = 32;
+
+/// The maximum number of requests of the query connection handled
+/// concurrently.
+pub const DEFAULT_SERVER_MAX_CONCURRENT_QUERIES: usize = 4;
+
+/// The maximum number of `CheckTx` requests of the mempool connection
+/// handled concurrently.
+pub const DEFAULT_SERVER_MAX_CONCURRENT_CHECK_TXS: usize = 1;
+
+/// The number of bytes of buffered responses above which they are written
+/// out without waiting for a `Flush` request.
+const MAX_BUFFERED_RESPONSES_LEN: usize = 64 * 1024;
+
+/// The response code of queries rejected because the server is overloaded.
+const OVERLOADED_CODE: u32 = 1;
+
 /// Allows us to configure and construct an ABCI server.
 pub struct ServerBuilder {
     read_buf_size: usize,
+    worker_threads: usize,
+    max_connections: usize,
+    queue_depth: usize,
+    query_queue_depth: usize,
+    max_concurrent_queries: usize,
+    max_concurrent_check_txs: usize,
+    unix_socket: UnixSocketOptions,
 }
 
 impl ServerBuilder {
@@ -25,27 +84,149 @@ impl ServerBuilder {
     /// incoming data from the client. This needs to be tuned for your
     /// application.
     pub fn new(read_buf_size: usize) -> Self {
-        Self { read_buf_size }
+        Self {
+            read_buf_size,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of worker threads handling requests across all the
+    /// connections to the server (at least one).
+    pub fn worker_threads(mut self, worker_threads: usize) -> Self {
+        self.worker_threads = worker_threads.max(1);
+        self
+    }
+
+    /// Set the maximum number of connections to the server open at the same
+    /// time (at least one).
+    ///
+    /// Each open connection has a thread of its own reading its requests.
+    /// The connections accepted beyond this limit are closed right away.
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+
+    /// Set the maximum number of requests read from a connection that may
+    /// be waiting to be handled (at least one).
+    ///
+    /// Once this depth is reached, the server stops reading from the
+    /// connection until some of its requests have been handled.
+    pub fn queue_depth(mut self, queue_depth: usize) -> Self {
+        self.queue_depth = queue_depth.max(1);
+        self
+    }
+
+    /// Set the number of requests waiting to be handled on a connection
+    /// above which incoming queries are rejected.
+    ///
+    /// Rejected queries are answered with a non-zero response code without
+    /// reaching the application, which sheds load from the query connection
+    /// under pressure while preserving the order of the responses.
+    pub fn query_queue_depth(mut self, query_queue_depth: usize) -> Self {
+        self.query_queue_depth = query_queue_depth;
+        self
+    }
+
+    /// Set the maximum number of requests of the query connection (`Info`
+    /// and `Query`) handled concurrently (at least one).
+    ///
+    /// The requests handled concurrently are handled by clones of the
+    /// application of the connection.
+    pub fn max_concurrent_queries(mut self, max_concurrent_queries: usize) -> Self {
+        self.max_concurrent_queries = max_concurrent_queries.max(1);
+        self
+    }
+
+    /// Set the maximum number of `CheckTx` requests of the mempool
+    /// connection handled concurrently (at least one, the default).
+    ///
+    /// The requests handled concurrently are handled by clones of the
+    /// application of the connection, which must thus not rely on the
+    /// transactions being checked one after the other, e.g. to check the
+    /// sequence numbers of the accounts.
+    pub fn max_concurrent_check_txs(mut self, max_concurrent_check_txs: usize) -> Self {
+        self.max_concurrent_check_txs = max_concurrent_check_txs.max(1);
+        self
+    }
+
+    /// Set whether to remove the socket file of a server bound to a Unix
+    /// domain socket once the server is dropped, as well as a stale socket
+    /// file left at its path by a server which is no longer running, before
+    /// binding (enabled by default).
+    #[cfg(unix)]
+    pub fn unix_socket_cleanup(mut self, cleanup: bool) -> Self {
+        self.unix_socket.cleanup = cleanup;
+        self
+    }
+
+    /// Set the permissions of the socket file of a server bound to a Unix
+    /// domain socket, e.g. `0o660` to only allow the connections of the
+    /// owner and group of the file. The socket only appears at its path once
+    /// its permissions are set.
+    #[cfg(unix)]
+    pub fn unix_socket_permissions(mut self, mode: u32) -> Self {
+        self.unix_socket.permissions = Some(mode);
+        self
     }
 
     /// Constructor for an ABCI server.
     ///
-    /// Binds the server to the given address. You must subsequently call the
-    /// [`Server::listen`] method in order for incoming connections' requests
-    /// to be routed to the specified ABCI application.
+    /// Binds the server to the given TCP address. You must subsequently call
+    /// the [`Server::listen`] method in order for incoming connections'
+    /// requests to be routed to the specified ABCI application.
     pub fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<Server<App>, Error>
     where
         Addr: ToSocketAddrs,
-        App: Application,
+        App: RequestDispatcher,
     {
         let listener = TcpListener::bind(addr).map_err(Error::io)?;
-        let local_addr = listener.local_addr().map_err(Error::io)?.to_string();
+        self.serve(Listener::Tcp(listener), app)
+    }
+
+    /// Constructor for an ABCI server bound to the Unix domain socket at the
+    /// given path.
+    #[cfg(unix)]
+    pub fn bind_unix<P, App>(self, path: P, app: App) -> Result<Server<App>, Error>
+    where
+        P: AsRef<std::path::Path>,
+        App: RequestDispatcher,
+    {
+        let address = Address::Unix(path.as_ref().to_path_buf());
+        self.bind_address(&address, app)
+    }
+
+    /// Constructor for an ABCI server bound to the given [`Address`], e.g.
+    /// parsed from a `tcp://127.0.0.1:26658` or `unix:///path/to/app.sock`
+    /// URL.
+    pub fn bind_address<App>(self, address: &Address, app: App) -> Result<Server<App>, Error>
+    where
+        App: RequestDispatcher,
+    {
+        let listener = Listener::bind(address, self.unix_socket)?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App: RequestDispatcher>(
+        self,
+        listener: Listener,
+        app: App,
+    ) -> Result<Server<App>, Error> {
+        let local_addr = listener.local_addr()?;
+        let pool = WorkerPool::new(self.worker_threads)?;
         info!("ABCI server running at {}", local_addr);
         Ok(Server {
             app,
             listener,
             local_addr,
             read_buf_size: self.read_buf_size,
+            pool,
+            max_connections: self.max_connections,
+            connections: Arc::new(AtomicUsize::new(0)),
+            queue_depth: self.queue_depth,
+            query_queue_depth: self.query_queue_depth,
+            max_concurrent_queries: self.max_concurrent_queries,
+            max_concurrent_check_txs: self.max_concurrent_check_txs,
         })
     }
 }
@@ -54,31 +235,78 @@ impl Default for ServerBuilder {
     fn default() -> Self {
         Self {
             read_buf_size: DEFAULT_SERVER_READ_BUF_SIZE,
+            worker_threads: DEFAULT_SERVER_WORKER_THREADS,
+            max_connections: DEFAULT_SERVER_MAX_CONNECTIONS,
+            queue_depth: DEFAULT_SERVER_QUEUE_DEPTH,
+            query_queue_depth: DEFAULT_SERVER_QUERY_QUEUE_DEPTH,
+            max_concurrent_queries: DEFAULT_SERVER_MAX_CONCURRENT_QUERIES,
+            max_concurrent_check_txs: DEFAULT_SERVER_MAX_CONCURRENT_CHECK_TXS,
+            unix_socket: UnixSocketOptions::default(),
         }
     }
 }
 
-/// A TCP-based server for serving a specific ABCI application.
+/// A server for serving a specific ABCI application, over TCP or a Unix
+/// domain socket.
+///
+/// Requests from all the incoming connections are handled by a fixed-size
+/// pool of worker threads, and the requests of different connections are
+/// handled concurrently. The requests of each connection are responded to in
+/// the order in which they were received, as required by ABCI.
+///
+/// The requests of each connection are read by a thread of its own, so the
+/// number of connections open at the same time is limited, to
+/// [`DEFAULT_SERVER_MAX_CONNECTIONS`] by default (see
+/// [`ServerBuilder::max_connections`]). The connections accepted beyond this
+/// limit are closed right away.
+///
+/// The consensus engine opens four connections to the application, whose
+/// roles (consensus, mempool, query and state sync) are identified by the
+/// requests they send. The requests of the consensus and snapshot connections
+/// are handled one at a time, in order. Up to a configurable number of the
+/// `Info` and `Query` requests of the query connection, and of the `CheckTx`
+/// requests of the mempool connection, are handled concurrently.
+///
+/// Responses are buffered, and written out when responding to a `Flush`
+/// request, as specified by ABCI, or once enough of them are buffered.
 ///
-/// Each incoming connection is handled in a separate thread. The ABCI
-/// application is cloned for access in each thread. It is up to the
-/// application developer to manage shared state across these different
-/// threads.
+/// The ABCI application is cloned for each connection, and for each request
+/// handled concurrently with others. It is up to the application developer
+/// to manage shared state across these different clones.
 pub struct Server<App> {
     app: App,
-    listener: TcpListener,
+    listener: Listener,
     local_addr: String,
     read_buf_size: usize,
+    pool: WorkerPool,
+    max_connections: usize,
+    // The number of connections whose requests are being read.
+    connections: Arc<AtomicUsize>,
+    queue_depth: usize,
+    query_queue_depth: usize,
+    max_concurrent_queries: usize,
+    max_concurrent_check_txs: usize,
 }
 
-impl<App: Application> Server<App> {
+impl<App: RequestDispatcher> Server<App> {
     /// Initiate a blocking listener for incoming connections.
     pub fn listen(self) -> Result<(), Error> {
         loop {
-            let (stream, addr) = self.listener.accept().map_err(Error::io)?;
-            let addr = addr.to_string();
+            let (stream, addr) = self.listener.accept()?;
+            // Only this thread opens connections, so that the limit cannot
+            // be exceeded.
+            if self.connections.load(Ordering::Acquire) >= self.max_connections {
+                warn!(
+                    "Refusing connection from {}: {} connections open",
+                    addr, self.max_connections
+                );
+                let _ = stream.shutdown(Shutdown::Both);
+                continue;
+            }
             info!("Incoming connection from: {}", addr);
-            self.spawn_client_handler(stream, addr);
+            if let Err(e) = self.spawn_client_handler(stream, addr.clone()) {
+                error!("Failed to set up connection from {}: {:?}", addr, e);
+            }
         }
     }
 
@@ -87,15 +315,143 @@ impl<App: Application> Server<App> {
         self.local_addr.clone()
     }
 
-    fn spawn_client_handler(&self, stream: TcpStream, addr: String) {
-        let app = self.app.clone();
-        let read_buf_size = self.read_buf_size;
-        let _ = thread::spawn(move || Self::handle_client(stream, addr, app, read_buf_size));
+    fn spawn_client_handler(&self, stream: Stream, addr: String) -> Result<(), Error> {
+        // The workers only write responses, so the write side of the
+        // connection does not need a read buffer.
+        let writer = ServerCodec::new(stream.try_clone().map_err(Error::io)?, 0);
+        let reader = ServerCodec::new(stream.try_clone().map_err(Error::io)?, self.read_buf_size);
+        let connection = Arc::new(Connection {
+            addr,
+            stream,
+            pool: self.pool.clone(),
+            queue_depth: self.queue_depth,
+            query_queue_depth: self.query_queue_depth,
+            max_concurrent_queries: self.max_concurrent_queries,
+            max_concurrent_check_txs: self.max_concurrent_check_txs,
+            app: Mutex::new(self.app.clone()),
+            writer: Mutex::new(writer),
+            state: Mutex::new(ConnectionState::default()),
+            dequeued: Condvar::new(),
+        });
+        let slot = ConnectionSlot::acquire(&self.connections);
+        let _ = thread::spawn(move || {
+            connection.read_requests(reader);
+            drop(slot);
+        });
+        Ok(())
     }
+}
 
-    fn handle_client(stream: TcpStream, addr: String, app: App, read_buf_size: usize) {
-        let mut codec = ServerCodec::new(stream, read_buf_size);
-        info!("Listening for incoming requests from {}", addr);
+/// Counts a connection as open until dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(connections: &Arc<AtomicUsize>) -> Self {
+        connections.fetch_add(1, Ordering::AcqRel);
+        Self(connections.clone())
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// The role of a connection of the consensus engine to the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConnectionRole {
+    Consensus,
+    Mempool,
+    Query,
+    Snapshot,
+}
+
+impl ConnectionRole {
+    /// The role of the connections through which the given request is sent,
+    /// if it is specific to one of them.
+    fn of(request: &Request) -> Option<Self> {
+        use request::Value;
+
+        match request.value.as_ref()? {
+            Value::Echo(_) | Value::Flush(_) => None,
+            Value::Info(_) | Value::Query(_) => Some(Self::Query),
+            Value::CheckTx(_) => Some(Self::Mempool),
+            Value::ListSnapshots(_)
+            | Value::OfferSnapshot(_)
+            | Value::LoadSnapshotChunk(_)
+            | Value::ApplySnapshotChunk(_) => Some(Self::Snapshot),
+            Value::InitChain(_)
+            | Value::BeginBlock(_)
+            | Value::DeliverTx(_)
+            | Value::EndBlock(_)
+            | Value::Commit(_)
+            | Value::PrepareProposal(_)
+            | Value::ProcessProposal(_)
+            | Value::ExtendVote(_)
+            | Value::VerifyVoteExtension(_)
+            | Value::FinalizeBlock(_) => Some(Self::Consensus),
+        }
+    }
+}
+
+impl fmt::Display for ConnectionRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Consensus => write!(f, "consensus"),
+            Self::Mempool => write!(f, "mempool"),
+            Self::Query => write!(f, "query"),
+            Self::Snapshot => write!(f, "snapshot"),
+        }
+    }
+}
+
+/// A connection to the server, whose requests are read on a dedicated thread
+/// and handled on the worker pool.
+struct Connection<App> {
+    addr: String,
+    stream: Stream,
+    pool: WorkerPool,
+    queue_depth: usize,
+    query_queue_depth: usize,
+    max_concurrent_queries: usize,
+    max_concurrent_check_txs: usize,
+    // The application of the connection, which handles the requests which
+    // are not handled concurrently, one at a time.
+    app: Mutex<App>,
+    // Locked before the state, when both are.
+    writer: Mutex<ServerCodec<Stream>>,
+    state: Mutex<ConnectionState>,
+    // Notified whenever a request is taken from the queue.
+    dequeued: Condvar,
+}
+
+#[derive(Default)]
+struct ConnectionState {
+    role: Option<ConnectionRole>,
+    // The tasks waiting to be handled, along with their sequence numbers.
+    queue: VecDeque<(u64, Task)>,
+    // The sequence number of the next task to be queued.
+    next_queued: u64,
+    // The number of requests being handled.
+    in_flight: usize,
+    // Whether the request being handled, if any, must be handled alone.
+    exclusive: bool,
+    // The responses which cannot be written yet, by sequence number.
+    completed: BTreeMap<u64, Response>,
+    // The sequence number of the next response to be written.
+    next_written: u64,
+    closed: bool,
+}
+
+enum Task {
+    Handle(Request),
+    Respond(Response),
+}
+
+impl<App: RequestDispatcher> Connection<App> {
+    fn read_requests(self: Arc<Self>, mut codec: ServerCodec<Stream>) {
+        info!("Listening for incoming requests from {}", self.addr);
         loop {
             let request = match codec.next() {
                 Some(result) => match result {
@@ -103,21 +459,178 @@ impl<App: Application> Server<App> {
                     Err(e) => {
                         error!(
                             "Failed to read incoming request from client {}: {:?}",
-                            addr, e
+                            self.addr, e
                         );
                         return;
                     },
                 },
                 None => {
-                    info!("Client {} terminated stream", addr);
+                    info!("Client {} terminated stream", self.addr);
                     return;
                 },
             };
-            let response = app.handle(request);
-            if let Err(e) = codec.send(response) {
-                error!("Failed sending response to client {}: {:?}", addr, e);
+            if !self.enqueue(request) {
+                return;
+            }
+        }
+    }
+
+    /// Queue a request for handling, or the rejection of a query, waiting
+    /// for room in the queue if needed. Returns `false` if the connection
+    /// has been closed.
+    fn enqueue(self: &Arc<Self>, request: Request) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.role.is_none() {
+            state.role = ConnectionRole::of(&request);
+            if let Some(role) = state.role {
+                info!("Client {} opened the {} connection", self.addr, role);
+            }
+        }
+        // Rejections are queued as well, so that the responses of a client
+        // flooding the connection cannot pile up while it is not reading
+        // them.
+        while !state.closed && state.queue.len() >= self.queue_depth {
+            state = self.dequeued.wait(state).unwrap();
+        }
+        if state.closed {
+            return false;
+        }
+        let pending = state.queue.len() + state.in_flight;
+        let task = match request.value {
+            Some(request::Value::Query(query)) if pending >= self.query_queue_depth => {
+                warn!(
+                    "Rejecting query from client {}: {} requests pending",
+                    self.addr, pending
+                );
+                Task::Respond(Response {
+                    value: Some(response::Value::Query(ResponseQuery {
+                        code: OVERLOADED_CODE,
+                        log: "server overloaded".to_string(),
+                        height: query.height,
+                        ..Default::default()
+                    })),
+                })
+            },
+            value => Task::Handle(Request { value }),
+        };
+        let sequence = state.next_queued;
+        state.next_queued += 1;
+        state.queue.push_back((sequence, task));
+        self.schedule(&mut state);
+        true
+    }
+
+    /// The maximum number of requests of the connection handled
+    /// concurrently with the given task, according to the role of the
+    /// connection.
+    fn max_concurrent(&self, role: Option<ConnectionRole>, task: &Task) -> usize {
+        let request = match task {
+            Task::Handle(request) => request,
+            // Nothing to handle.
+            Task::Respond(_) => return usize::MAX,
+        };
+        match (role, &request.value) {
+            (
+                Some(ConnectionRole::Query),
+                Some(request::Value::Info(_) | request::Value::Query(_)),
+            ) => self.max_concurrent_queries,
+            (Some(ConnectionRole::Mempool), Some(request::Value::CheckTx(_))) => {
+                self.max_concurrent_check_txs
+            },
+            _ => 1,
+        }
+    }
+
+    /// Hand the queued tasks which can be handled now over to the workers.
+    fn schedule(self: &Arc<Self>, state: &mut ConnectionState) {
+        while !state.closed && !state.exclusive {
+            let Some((_, task)) = state.queue.front() else {
+                return;
+            };
+            let max_concurrent = self.max_concurrent(state.role, task);
+            let exclusive = max_concurrent == 1;
+            if (exclusive && state.in_flight > 0) || state.in_flight >= max_concurrent {
                 return;
             }
+            let (sequence, task) = state.queue.pop_front().unwrap();
+            self.dequeued.notify_one();
+            state.in_flight += 1;
+            state.exclusive = exclusive;
+
+            let connection = self.clone();
+            self.pool
+                .execute(move || connection.handle(sequence, task, exclusive));
+        }
+    }
+
+    /// Handle a task, then write out the responses which are due.
+    fn handle(self: Arc<Self>, sequence: u64, task: Task, exclusive: bool) {
+        let response = match task {
+            Task::Handle(request) => {
+                let handled = panic::catch_unwind(AssertUnwindSafe(|| {
+                    if exclusive {
+                        self.app.lock().unwrap().handle(request)
+                    } else {
+                        let app = self.app.lock().unwrap().clone();
+                        app.handle(request)
+                    }
+                }));
+                match handled {
+                    Ok(response) => response,
+                    Err(_) => {
+                        error!("Application panicked handling request from {}", self.addr);
+                        self.close();
+                        return;
+                    },
+                }
+            },
+            Task::Respond(response) => response,
+        };
+
+        let mut writer = self.writer.lock().unwrap();
+        let mut state = self.state.lock().unwrap();
+        state.in_flight -= 1;
+        state.exclusive = false;
+        state.completed.insert(sequence, response);
+        let mut due = Vec::new();
+        while let Some(response) = state.completed.remove(&state.next_written) {
+            state.next_written += 1;
+            due.push(response);
+        }
+        self.schedule(&mut state);
+        drop(state);
+
+        if let Err(e) = self.write(&mut writer, due) {
+            error!("Failed sending response to client {}: {:?}", self.addr, e);
+            drop(writer);
+            self.close();
         }
     }
+
+    /// Buffer the given responses, and write them out if one of them
+    /// responds to a `Flush` request, or once enough of them are buffered.
+    fn write(
+        &self,
+        writer: &mut ServerCodec<Stream>,
+        responses: Vec<Response>,
+    ) -> Result<(), Error> {
+        let mut flush = false;
+        for response in responses {
+            flush |= matches!(response.value, Some(response::Value::Flush(_)));
+            writer.buffer(response)?;
+        }
+        if flush || writer.buffered_len() >= MAX_BUFFERED_RESPONSES_LEN {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.queue.clear();
+        drop(state);
+        self.dequeued.notify_all();
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
 }
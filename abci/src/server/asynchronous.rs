@@ -0,0 +1,425 @@
+//! Asynchronous ABCI application server, running on Tokio.
+
+use std::future::{self, Future};
+
+use bytes::BytesMut;
+use futures::stream::{FuturesOrdered, StreamExt};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::watch,
+    task::JoinSet,
+};
+use tracing::{error, info};
+
+use super::DEFAULT_SERVER_READ_BUF_SIZE;
+#[cfg(unix)]
+use crate::transport::SocketFile;
+use crate::{
+    codec::{decode_length_delimited, encode_length_delimited},
+    error::Error,
+    messages::{request::Value, Request, Response},
+    transport::{Address, UnixSocketOptions},
+    AsyncRequestDispatcher,
+};
+
+/// The maximum number of requests of a single connection to the async ABCI
+/// server which may be handled concurrently.
+pub const DEFAULT_SERVER_MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Allows us to configure and construct an asynchronous ABCI server.
+pub struct AsyncServerBuilder {
+    read_buf_size: usize,
+    max_concurrent_requests: usize,
+    unix_socket: UnixSocketOptions,
+}
+
+impl AsyncServerBuilder {
+    /// Builder constructor.
+    ///
+    /// Allows you to specify the read buffer size used when reading chunks of
+    /// incoming data from the client. This needs to be tuned for your
+    /// application.
+    pub fn new(read_buf_size: usize) -> Self {
+        Self {
+            read_buf_size,
+            ..Self::default()
+        }
+    }
+
+    /// Set the maximum number of requests of a connection which may be
+    /// handled concurrently (at least one).
+    ///
+    /// Once this number of requests is being handled, the server stops
+    /// reading from the connection until some of them have been responded
+    /// to. Setting it to one handles the requests of each connection one at a
+    /// time, like the synchronous [`Server`](crate::Server).
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
+    }
+
+    /// Set whether to remove the socket file of a server bound to a Unix
+    /// domain socket once the server is dropped, as well as a stale socket
+    /// file left at its path before binding (enabled by default).
+    ///
+    /// See [`ServerBuilder::unix_socket_cleanup`](crate::ServerBuilder::unix_socket_cleanup).
+    #[cfg(unix)]
+    pub fn unix_socket_cleanup(mut self, cleanup: bool) -> Self {
+        self.unix_socket.cleanup = cleanup;
+        self
+    }
+
+    /// Set the permissions of the socket file of a server bound to a Unix
+    /// domain socket.
+    ///
+    /// See [`ServerBuilder::unix_socket_permissions`](crate::ServerBuilder::unix_socket_permissions).
+    #[cfg(unix)]
+    pub fn unix_socket_permissions(mut self, mode: u32) -> Self {
+        self.unix_socket.permissions = Some(mode);
+        self
+    }
+
+    /// Constructor for an asynchronous ABCI server.
+    ///
+    /// Binds the server to the given TCP address. You must subsequently call
+    /// the [`AsyncServer::listen`] method in order for incoming connections'
+    /// requests to be routed to the specified ABCI application.
+    pub async fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        Addr: ToSocketAddrs,
+        App: AsyncRequestDispatcher,
+    {
+        let listener = TcpListener::bind(addr).await.map_err(Error::io)?;
+        self.serve(Listener::Tcp(listener), app)
+    }
+
+    /// Constructor for an asynchronous ABCI server bound to the Unix domain
+    /// socket at the given path.
+    #[cfg(unix)]
+    pub async fn bind_unix<P, App>(self, path: P, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        P: AsRef<std::path::Path>,
+        App: AsyncRequestDispatcher,
+    {
+        let address = Address::Unix(path.as_ref().to_path_buf());
+        self.bind_address(&address, app).await
+    }
+
+    /// Constructor for an asynchronous ABCI server bound to the given
+    /// [`Address`], e.g. parsed from a `tcp://127.0.0.1:26658` or
+    /// `unix:///path/to/app.sock` URL.
+    pub async fn bind_address<App>(
+        self,
+        address: &Address,
+        app: App,
+    ) -> Result<AsyncServer<App>, Error>
+    where
+        App: AsyncRequestDispatcher,
+    {
+        let listener = Listener::bind(address, self.unix_socket).await?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App>(self, listener: Listener, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        App: AsyncRequestDispatcher,
+    {
+        let local_addr = listener.local_addr()?;
+        info!("ABCI server running at {}", local_addr);
+        Ok(AsyncServer {
+            app,
+            listener,
+            local_addr,
+            read_buf_size: self.read_buf_size,
+            max_concurrent_requests: self.max_concurrent_requests,
+        })
+    }
+}
+
+impl Default for AsyncServerBuilder {
+    fn default() -> Self {
+        Self {
+            read_buf_size: DEFAULT_SERVER_READ_BUF_SIZE,
+            max_concurrent_requests: DEFAULT_SERVER_MAX_CONCURRENT_REQUESTS,
+            unix_socket: UnixSocketOptions::default(),
+        }
+    }
+}
+
+/// An asynchronous server for serving a specific ABCI application, over TCP
+/// or a Unix domain socket.
+///
+/// Each connection is served by its own task. The requests of a connection
+/// are responded to in the order in which they were received, as required by
+/// ABCI. Up to a bounded number of consecutive requests which only read the
+/// state of the application (`Echo`, `Flush`, `Info`, `Query`,
+/// `ListSnapshots` and `LoadSnapshotChunk`) are handled concurrently, while
+/// each of the other requests is only handled once all the requests received
+/// before it have been responded to, and before any request received after
+/// it is handled.
+///
+/// The ABCI application is cloned for each connection. It is up to the
+/// application developer to manage shared state across these different
+/// clones.
+pub struct AsyncServer<App> {
+    app: App,
+    listener: Listener,
+    local_addr: String,
+    read_buf_size: usize,
+    max_concurrent_requests: usize,
+}
+
+impl<App: AsyncRequestDispatcher> AsyncServer<App> {
+    /// Listen for incoming connections, forever.
+    pub async fn listen(self) -> Result<(), Error> {
+        self.listen_until(future::pending()).await
+    }
+
+    /// Listen for incoming connections until the given signal completes, then
+    /// shut down gracefully.
+    ///
+    /// Once the signal completes, the server stops accepting connections and
+    /// reading requests, responds to the requests it has already read, and
+    /// closes the connections. The returned future completes once all the
+    /// connections have been closed.
+    pub async fn listen_until<S>(self, signal: S) -> Result<(), Error>
+    where
+        S: Future<Output = ()>,
+    {
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let mut connections = JoinSet::new();
+        tokio::pin!(signal);
+
+        let result = loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, addr) = match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => break Err(Error::io(e)),
+                    };
+                    info!("Incoming connection from: {}", addr);
+                    let connection = Connection {
+                        app: self.app.clone(),
+                        addr,
+                        read_buf_size: self.read_buf_size,
+                        max_concurrent_requests: self.max_concurrent_requests,
+                    };
+                    connections.spawn(connection.serve(stream, shutdown_rx.clone()));
+                },
+                Some(joined) = connections.join_next() => log_join(joined),
+                () = &mut signal => {
+                    info!("Shutting down ABCI server at {}", self.local_addr);
+                    break Ok(());
+                },
+            }
+        };
+
+        drop(self.listener);
+        let _ = shutdown.send(true);
+        while let Some(joined) = connections.join_next().await {
+            log_join(joined);
+        }
+        result
+    }
+
+    /// Getter for this server's local address.
+    pub fn local_addr(&self) -> String {
+        self.local_addr.clone()
+    }
+}
+
+pub(super) fn log_join(joined: Result<(), tokio::task::JoinError>) {
+    if let Err(e) = joined {
+        error!("ABCI connection task failed: {}", e);
+    }
+}
+
+/// A listener for the incoming connections of the server.
+pub(super) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, SocketFile),
+}
+
+impl Listener {
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    pub(super) async fn bind(address: &Address, options: UnixSocketOptions) -> Result<Self, Error> {
+        match address {
+            Address::Tcp(addr) => TcpListener::bind(addr.as_str())
+                .await
+                .map(Self::Tcp)
+                .map_err(Error::io),
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                let (listener, file) =
+                    SocketFile::bind(path, options, |path| UnixListener::bind(path))?;
+                Ok(Self::Unix(listener, file))
+            },
+        }
+    }
+
+    pub(super) fn local_addr(&self) -> Result<String, Error> {
+        match self {
+            Self::Tcp(listener) => listener
+                .local_addr()
+                .map(|addr| addr.to_string())
+                .map_err(Error::io),
+            #[cfg(unix)]
+            Self::Unix(_, file) => Ok(Address::Unix(file.path().to_path_buf()).to_string()),
+        }
+    }
+
+    // Cancel safe, as the `accept` methods of the Tokio listeners are.
+    pub(super) async fn accept(&self) -> std::io::Result<(Stream, String)> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((Stream::Tcp(stream), addr.to_string()))
+            },
+            #[cfg(unix)]
+            Self::Unix(listener, file) => {
+                let (stream, _) = listener.accept().await?;
+                // The peers of Unix domain sockets are usually unnamed.
+                let addr = Address::Unix(file.path().to_path_buf()).to_string();
+                Ok((Stream::Unix(stream), addr))
+            },
+        }
+    }
+}
+
+pub(super) enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+struct Connection<App> {
+    app: App,
+    addr: String,
+    read_buf_size: usize,
+    max_concurrent_requests: usize,
+}
+
+impl<App: AsyncRequestDispatcher> Connection<App> {
+    async fn serve(self, stream: Stream, shutdown: watch::Receiver<bool>) {
+        match stream {
+            Stream::Tcp(stream) => {
+                let (reader, writer) = stream.into_split();
+                self.serve_halves(reader, writer, shutdown).await
+            },
+            #[cfg(unix)]
+            Stream::Unix(stream) => {
+                let (reader, writer) = stream.into_split();
+                self.serve_halves(reader, writer, shutdown).await
+            },
+        }
+    }
+
+    async fn serve_halves<R, W>(self, reader: R, mut writer: W, shutdown: watch::Receiver<bool>)
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        info!("Listening for incoming requests from {}", self.addr);
+        if let Err(e) = self.handle_requests(reader, &mut writer, shutdown).await {
+            error!("Failed serving client {}: {:?}", self.addr, e);
+        }
+        let _ = writer.shutdown().await;
+    }
+
+    async fn handle_requests<R, W>(
+        &self,
+        mut reader: R,
+        writer: &mut W,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> Result<(), Error>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut read_buf = BytesMut::new();
+        let mut write_buf = BytesMut::new();
+        let mut in_flight = FuturesOrdered::new();
+
+        while !*shutdown.borrow() {
+            tokio::select! {
+                Some(response) = in_flight.next() => send(writer, &mut write_buf, response).await?,
+                request = self.read_request(&mut reader, &mut read_buf),
+                    if in_flight.len() < self.max_concurrent_requests =>
+                {
+                    let request = match request? {
+                        Some(request) => request,
+                        None => {
+                            info!("Client {} terminated stream", self.addr);
+                            break;
+                        },
+                    };
+                    if is_read_only(&request) {
+                        in_flight.push_back(self.app.handle(request));
+                        continue;
+                    }
+                    while let Some(response) = in_flight.next().await {
+                        send(writer, &mut write_buf, response).await?;
+                    }
+                    let response = self.app.handle(request).await;
+                    send(writer, &mut write_buf, response).await?;
+                },
+                // Also completes if the server is gone.
+                _ = shutdown.changed() => break,
+            }
+        }
+
+        while let Some(response) = in_flight.next().await {
+            send(writer, &mut write_buf, response).await?;
+        }
+        Ok(())
+    }
+
+    // Cancel safe: the bytes read are kept in the buffer until a whole
+    // request has been read.
+    async fn read_request<R: AsyncRead + Unpin>(
+        &self,
+        reader: &mut R,
+        buf: &mut BytesMut,
+    ) -> Result<Option<Request>, Error> {
+        loop {
+            if let Some(request) = decode_length_delimited(buf)? {
+                return Ok(Some(request));
+            }
+            buf.reserve(self.read_buf_size);
+            if reader.read_buf(buf).await.map_err(Error::io)? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+// Whether the request only reads the state of the application, and may thus
+// be handled concurrently with the neighbouring read-only requests.
+pub(super) fn is_read_only(request: &Request) -> bool {
+    matches!(
+        request.value,
+        Some(
+            Value::Echo(_)
+                | Value::Flush(_)
+                | Value::Info(_)
+                | Value::Query(_)
+                | Value::ListSnapshots(_)
+                | Value::LoadSnapshotChunk(_)
+        )
+    )
+}
+
+async fn send<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    buf: &mut BytesMut,
+    response: Response,
+) -> Result<(), Error> {
+    buf.clear();
+    encode_length_delimited(response, buf)?;
+    writer.write_all(buf).await.map_err(Error::io)?;
+    writer.flush().await.map_err(Error::io)
+}
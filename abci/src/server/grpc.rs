@@ -0,0 +1,471 @@
+//! gRPC ABCI application server, for consensus engines configured with
+//! `abci = "grpc"`.
+//!
+//! Serves the `ABCIApplication` service of Tendermint 0.37 and the `ABCI`
+//! service of CometBFT 0.38 over plain HTTP/2, as the gRPC clients of the
+//! consensus engines connect without TLS, through the `tonic` servers of
+//! [`tendermint_proto`].
+
+use std::{
+    future::{self, Future},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use hyper::{server::conn::Http, service::service_fn, Body};
+use prost::Message;
+use tendermint_proto::{
+    v0_37::abci::{
+        abci_application,
+        abci_application_server::{AbciApplication, AbciApplicationServer},
+    },
+    v0_38::abci::abci_server::{Abci, AbciServer},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::ToSocketAddrs,
+    sync::{watch, RwLock},
+    task::JoinSet,
+};
+use tonic::{codegen::Service, Status};
+use tracing::{debug, error, info};
+
+use super::asynchronous::{is_read_only, log_join, Listener, Stream};
+use crate::{
+    error::Error,
+    messages::{self, request, response, transcode, Request},
+    transport::{Address, UnixSocketOptions},
+    AsyncRequestDispatcher,
+};
+
+/// The maximum number of requests of a single connection to the gRPC ABCI
+/// server which may be handled concurrently.
+pub const DEFAULT_GRPC_SERVER_MAX_CONCURRENT_REQUESTS: u32 = 16;
+
+/// The maximum size of the messages received by the gRPC ABCI server, as in
+/// gRPC.
+pub const DEFAULT_GRPC_SERVER_MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Allows us to configure and construct a gRPC ABCI server.
+pub struct GrpcServerBuilder {
+    max_concurrent_requests: u32,
+    max_message_size: usize,
+    unix_socket: UnixSocketOptions,
+}
+
+impl GrpcServerBuilder {
+    /// Set the maximum number of requests of a connection which may be
+    /// handled concurrently (at least one), i.e. the maximum number of
+    /// concurrent HTTP/2 streams of the connection.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: u32) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
+    }
+
+    /// Set the maximum size of the messages received, beyond which the calls
+    /// fail with the `OUT_OF_RANGE` status.
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Set whether to remove the socket file of a server bound to a Unix
+    /// domain socket once the server is dropped, as well as a stale socket
+    /// file left at its path before binding (enabled by default).
+    ///
+    /// See [`ServerBuilder::unix_socket_cleanup`](crate::ServerBuilder::unix_socket_cleanup).
+    #[cfg(unix)]
+    pub fn unix_socket_cleanup(mut self, cleanup: bool) -> Self {
+        self.unix_socket.cleanup = cleanup;
+        self
+    }
+
+    /// Set the permissions of the socket file of a server bound to a Unix
+    /// domain socket.
+    ///
+    /// See [`ServerBuilder::unix_socket_permissions`](crate::ServerBuilder::unix_socket_permissions).
+    #[cfg(unix)]
+    pub fn unix_socket_permissions(mut self, mode: u32) -> Self {
+        self.unix_socket.permissions = Some(mode);
+        self
+    }
+
+    /// Constructor for a gRPC ABCI server.
+    ///
+    /// Binds the server to the given TCP address. You must subsequently call
+    /// the [`GrpcServer::listen`] method in order for incoming calls to be
+    /// routed to the specified ABCI application.
+    pub async fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        Addr: ToSocketAddrs,
+        App: AsyncRequestDispatcher,
+    {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(Error::io)?;
+        self.serve(Listener::Tcp(listener), app)
+    }
+
+    /// Constructor for a gRPC ABCI server bound to the Unix domain socket at
+    /// the given path.
+    #[cfg(unix)]
+    pub async fn bind_unix<P, App>(self, path: P, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        P: AsRef<std::path::Path>,
+        App: AsyncRequestDispatcher,
+    {
+        let address = Address::Unix(path.as_ref().to_path_buf());
+        self.bind_address(&address, app).await
+    }
+
+    /// Constructor for a gRPC ABCI server bound to the given [`Address`],
+    /// e.g. parsed from a `tcp://127.0.0.1:26658` or
+    /// `unix:///path/to/app.sock` URL.
+    pub async fn bind_address<App>(
+        self,
+        address: &Address,
+        app: App,
+    ) -> Result<GrpcServer<App>, Error>
+    where
+        App: AsyncRequestDispatcher,
+    {
+        let listener = Listener::bind(address, self.unix_socket).await?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App>(self, listener: Listener, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        App: AsyncRequestDispatcher,
+    {
+        let local_addr = listener.local_addr()?;
+        info!("gRPC ABCI server running at {}", local_addr);
+        Ok(GrpcServer {
+            app,
+            listener,
+            local_addr,
+            max_concurrent_requests: self.max_concurrent_requests,
+            max_message_size: self.max_message_size,
+        })
+    }
+}
+
+impl Default for GrpcServerBuilder {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: DEFAULT_GRPC_SERVER_MAX_CONCURRENT_REQUESTS,
+            max_message_size: DEFAULT_GRPC_SERVER_MAX_MESSAGE_SIZE,
+            unix_socket: UnixSocketOptions::default(),
+        }
+    }
+}
+
+/// A gRPC server for serving a specific ABCI application, over TCP or a Unix
+/// domain socket.
+///
+/// Each call is handled by the method of the application named after it,
+/// as with the [`AsyncServer`](crate::AsyncServer): the calls of a
+/// connection which only read the state of the application are handled
+/// concurrently, while each of the other calls is only handled once all the
+/// calls received before it have been responded to. A call for which the
+/// application responds with an exception fails with the `UNKNOWN` status.
+///
+/// Each service only serves the methods of its version of ABCI, whose
+/// messages are converted to and from the ones of the [`messages`] module.
+///
+/// The ABCI application is cloned for each connection. It is up to the
+/// application developer to manage shared state across these different
+/// clones.
+pub struct GrpcServer<App> {
+    app: App,
+    listener: Listener,
+    local_addr: String,
+    max_concurrent_requests: u32,
+    max_message_size: usize,
+}
+
+impl<App: AsyncRequestDispatcher> GrpcServer<App> {
+    /// Listen for incoming connections, forever.
+    pub async fn listen(self) -> Result<(), Error> {
+        self.listen_until(future::pending()).await
+    }
+
+    /// Listen for incoming connections until the given signal completes, then
+    /// shut down gracefully.
+    ///
+    /// Once the signal completes, the server stops accepting connections and
+    /// calls, responds to the calls it has already received, and closes the
+    /// connections. The returned future completes once all the connections
+    /// have been closed.
+    pub async fn listen_until<S>(self, signal: S) -> Result<(), Error>
+    where
+        S: Future<Output = ()>,
+    {
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let mut connections = JoinSet::new();
+        tokio::pin!(signal);
+
+        let result = loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, addr) = match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => break Err(Error::io(e)),
+                    };
+                    info!("Incoming gRPC connection from: {}", addr);
+                    let connection = Connection {
+                        app: self.app.clone(),
+                        addr,
+                        max_concurrent_requests: self.max_concurrent_requests,
+                        max_message_size: self.max_message_size,
+                    };
+                    connections.spawn(connection.serve(stream, shutdown_rx.clone()));
+                },
+                Some(joined) = connections.join_next() => log_join(joined),
+                () = &mut signal => {
+                    info!("Shutting down gRPC ABCI server at {}", self.local_addr);
+                    break Ok(());
+                },
+            }
+        };
+
+        drop(self.listener);
+        let _ = shutdown.send(true);
+        while let Some(joined) = connections.join_next().await {
+            log_join(joined);
+        }
+        result
+    }
+
+    /// Getter for this server's local address.
+    pub fn local_addr(&self) -> String {
+        self.local_addr.clone()
+    }
+}
+
+struct Connection<App> {
+    app: App,
+    addr: String,
+    max_concurrent_requests: u32,
+    max_message_size: usize,
+}
+
+impl<App: AsyncRequestDispatcher> Connection<App> {
+    async fn serve(self, stream: Stream, shutdown: watch::Receiver<bool>) {
+        match stream {
+            Stream::Tcp(stream) => self.serve_io(stream, shutdown).await,
+            #[cfg(unix)]
+            Stream::Unix(stream) => self.serve_io(stream, shutdown).await,
+        }
+    }
+
+    async fn serve_io<I>(self, io: I, mut shutdown: watch::Receiver<bool>)
+    where
+        I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let calls = Arc::new(Calls {
+            app: self.app,
+            order: RwLock::new(()),
+        });
+        let v0_37 = AbciApplicationServer::from_arc(calls.clone())
+            .max_decoding_message_size(self.max_message_size);
+        let v0_38 = AbciServer::from_arc(calls).max_decoding_message_size(self.max_message_size);
+        let service = service_fn(move |request: hyper::Request<Body>| {
+            let mut v0_37 = v0_37.clone();
+            let mut v0_38 = v0_38.clone();
+            // The generated servers are always ready.
+            async move {
+                match service_name(request.uri().path()) {
+                    Some(abci_application::NAME) => v0_37.call(request).await,
+                    Some(messages::abci::NAME) => v0_38.call(request).await,
+                    _ => {
+                        let status = format!("unknown service: {}", request.uri().path());
+                        error!("gRPC call failed: {}", status);
+                        Ok(Status::unimplemented(status).to_http())
+                    },
+                }
+            }
+        });
+        let connection = Http::new()
+            .http2_only(true)
+            .http2_max_concurrent_streams(self.max_concurrent_requests)
+            .serve_connection(io, service);
+        tokio::pin!(connection);
+
+        let result = tokio::select! {
+            result = &mut connection => result,
+            // Also completes if the server is gone.
+            _ = shutdown.changed() => {
+                connection.as_mut().graceful_shutdown();
+                connection.await
+            },
+        };
+        match result {
+            Ok(()) => info!("gRPC client {} closed the connection", self.addr),
+            Err(e) => error!("Failed serving gRPC client {}: {}", self.addr, e),
+        }
+    }
+}
+
+/// The service called by the given path.
+fn service_name(path: &str) -> Option<&str> {
+    let (service, _method) = path.strip_prefix('/')?.split_once('/')?;
+    Some(service)
+}
+
+/// Handles the calls of a connection, of either service.
+struct Calls<App> {
+    app: App,
+    // Held for reading by the read-only calls, and for writing by the other
+    // ones.
+    order: RwLock<()>,
+}
+
+impl<App: AsyncRequestDispatcher> Calls<App> {
+    async fn call(&self, method: &str, value: request::Value) -> Result<response::Value, Status> {
+        debug!("Incoming gRPC call: {}", method);
+        let request = Request { value: Some(value) };
+        let response = if is_read_only(&request) {
+            let _order = self.order.read().await;
+            self.app.handle(request).await
+        } else {
+            let _order = self.order.write().await;
+            self.app.handle(request).await
+        };
+        let status = match response.value {
+            Some(response::Value::Exception(exception)) => Status::unknown(exception.error),
+            Some(value) => return Ok(value),
+            None => Status::internal("no response from the application"),
+        };
+        error!("gRPC call {} failed: {}", method, status.message());
+        Err(status)
+    }
+}
+
+// The message itself, of the version the application handles.
+fn same<M>(message: M) -> M {
+    message
+}
+
+// The corresponding message of the other version, through its encoding.
+fn transcoded<M: Message, N: Message + Default>(message: M) -> N {
+    transcode(&message)
+}
+
+/// Implement the methods of a generated service by calling the application,
+/// converting their messages to and from the ones of the [`messages`]
+/// module with the given functions.
+macro_rules! serve {
+    (
+        $version:ident::$Service:ident {
+            $(
+                $method:ident: $Variant:ident(
+                    $Request:ident => $into:ident,
+                    $Response:ident <= $from:ident $(,)?
+                )
+            ),* $(,)?
+        }
+    ) => {
+        #[async_trait]
+        impl<App: AsyncRequestDispatcher> $Service for Calls<App> {
+            $(
+                async fn $method(
+                    &self,
+                    request: tonic::Request<tendermint_proto::$version::abci::$Request>,
+                ) -> Result<tonic::Response<tendermint_proto::$version::abci::$Response>, Status>
+                {
+                    let value = request::Value::$Variant($into(request.into_inner()));
+                    match self.call(stringify!($Variant), value).await? {
+                        response::Value::$Variant(response) => {
+                            Ok(tonic::Response::new($from(response)))
+                        },
+                        _ => Err(Status::internal("unexpected response from the application")),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+serve! {
+    v0_37::AbciApplication {
+        echo: Echo(RequestEcho => same, ResponseEcho <= same),
+        flush: Flush(RequestFlush => same, ResponseFlush <= same),
+        info: Info(RequestInfo => same, ResponseInfo <= same),
+        deliver_tx: DeliverTx(RequestDeliverTx => same, ResponseDeliverTx <= same),
+        check_tx: CheckTx(RequestCheckTx => same, ResponseCheckTx <= same),
+        query: Query(RequestQuery => same, ResponseQuery <= same),
+        commit: Commit(RequestCommit => same, ResponseCommit <= same),
+        init_chain: InitChain(RequestInitChain => transcoded, ResponseInitChain <= transcoded),
+        begin_block: BeginBlock(RequestBeginBlock => same, ResponseBeginBlock <= same),
+        end_block: EndBlock(RequestEndBlock => same, ResponseEndBlock <= same),
+        list_snapshots: ListSnapshots(
+            RequestListSnapshots => same,
+            ResponseListSnapshots <= same,
+        ),
+        offer_snapshot: OfferSnapshot(
+            RequestOfferSnapshot => same,
+            ResponseOfferSnapshot <= same,
+        ),
+        load_snapshot_chunk: LoadSnapshotChunk(
+            RequestLoadSnapshotChunk => same,
+            ResponseLoadSnapshotChunk <= same,
+        ),
+        apply_snapshot_chunk: ApplySnapshotChunk(
+            RequestApplySnapshotChunk => same,
+            ResponseApplySnapshotChunk <= same,
+        ),
+        prepare_proposal: PrepareProposal(
+            RequestPrepareProposal => transcoded,
+            ResponsePrepareProposal <= same,
+        ),
+        process_proposal: ProcessProposal(
+            RequestProcessProposal => transcoded,
+            ResponseProcessProposal <= same,
+        ),
+    }
+}
+
+serve! {
+    v0_38::Abci {
+        echo: Echo(RequestEcho => transcoded, ResponseEcho <= transcoded),
+        flush: Flush(RequestFlush => transcoded, ResponseFlush <= transcoded),
+        info: Info(RequestInfo => transcoded, ResponseInfo <= transcoded),
+        check_tx: CheckTx(RequestCheckTx => transcoded, ResponseCheckTx <= transcoded),
+        query: Query(RequestQuery => transcoded, ResponseQuery <= transcoded),
+        commit: Commit(RequestCommit => transcoded, ResponseCommit <= transcoded),
+        init_chain: InitChain(RequestInitChain => same, ResponseInitChain <= same),
+        list_snapshots: ListSnapshots(
+            RequestListSnapshots => transcoded,
+            ResponseListSnapshots <= transcoded,
+        ),
+        offer_snapshot: OfferSnapshot(
+            RequestOfferSnapshot => transcoded,
+            ResponseOfferSnapshot <= transcoded,
+        ),
+        load_snapshot_chunk: LoadSnapshotChunk(
+            RequestLoadSnapshotChunk => transcoded,
+            ResponseLoadSnapshotChunk <= transcoded,
+        ),
+        apply_snapshot_chunk: ApplySnapshotChunk(
+            RequestApplySnapshotChunk => transcoded,
+            ResponseApplySnapshotChunk <= transcoded,
+        ),
+        prepare_proposal: PrepareProposal(
+            RequestPrepareProposal => same,
+            ResponsePrepareProposal <= transcoded,
+        ),
+        process_proposal: ProcessProposal(
+            RequestProcessProposal => same,
+            ResponseProcessProposal <= transcoded,
+        ),
+        extend_vote: ExtendVote(RequestExtendVote => same, ResponseExtendVote <= same),
+        verify_vote_extension: VerifyVoteExtension(
+            RequestVerifyVoteExtension => same,
+            ResponseVerifyVoteExtension <= same,
+        ),
+        finalize_block: FinalizeBlock(
+            RequestFinalizeBlock => same,
+            ResponseFinalizeBlock <= same,
+        ),
+    }
+}
@@ -0,0 +1,61 @@
+//! A fixed-size pool of worker threads.
+
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use tracing::error;
+
+use crate::error::Error;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Executes jobs on a fixed number of threads, in the order in which they
+/// are submitted.
+#[derive(Clone)]
+pub struct WorkerPool {
+    sender: Sender<Job>,
+}
+
+impl WorkerPool {
+    /// Spawn a pool of `size` worker threads.
+    pub fn new(size: usize) -> Result<Self, Error> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for id in 0..size {
+            let receiver = receiver.clone();
+            let _ = thread::Builder::new()
+                .name(format!("abci-worker-{id}"))
+                .spawn(move || Self::work(receiver))
+                .map_err(Error::io)?;
+        }
+        Ok(Self { sender })
+    }
+
+    /// Submit a job for execution by the first available worker.
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.sender.send(Box::new(job)).is_err() {
+            error!("All ABCI server workers have terminated");
+        }
+    }
+
+    fn work(receiver: Arc<Mutex<Receiver<Job>>>) {
+        loop {
+            // The lock must be released before running the job, so that
+            // the other workers can pick up jobs in the meantime.
+            let job = receiver.lock().unwrap().recv();
+            match job {
+                Ok(job) => job(),
+                // All the senders, and thus the server, are gone.
+                Err(_) => return,
+            }
+        }
+    }
+}
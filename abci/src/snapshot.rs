@@ -0,0 +1,135 @@
+//! State sync support for ABCI applications.
+//!
+//! Nodes joining a network with state sync enabled restore the state of
+//! their application from a snapshot taken by the applications of other
+//! nodes, rather than replaying every block. A snapshot is served in chunks
+//! through the `ListSnapshots` and `LoadSnapshotChunk` methods, and restored
+//! through the `OfferSnapshot` and `ApplySnapshotChunk` methods, on the
+//! snapshot connection of the consensus engine.
+//!
+//! Applications implement the [`SnapshotProvider`] trait to take, serve and
+//! restore their snapshots, and answer the snapshot requests of the
+//! [`Application`] trait with the functions of this module, which deal with
+//! the ABCI messages:
+//!
+//! ```ignore
+//! impl Application for MyApp {
+//!     fn list_snapshots(&self) -> ResponseListSnapshots {
+//!         snapshot::list_snapshots(&self.snapshots)
+//!     }
+//!
+//!     fn offer_snapshot(&self, request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+//!         snapshot::offer_snapshot(&self.snapshots, request)
+//!     }
+//!
+//!     // ...
+//! }
+//! ```
+//!
+//! See the [`KeyValueStoreApp`] for an implementation.
+//!
+//! [`Application`]: crate::Application
+//! [`KeyValueStoreApp`]: crate::KeyValueStoreApp
+
+use bytes::Bytes;
+use tendermint_proto::v0_37::abci::{
+    response_apply_snapshot_chunk, response_offer_snapshot, RequestApplySnapshotChunk,
+    RequestLoadSnapshotChunk, RequestOfferSnapshot, ResponseApplySnapshotChunk,
+    ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot, Snapshot,
+};
+
+/// The outcome of applying a chunk of a snapshot, see
+/// [`SnapshotProvider::apply_chunk`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApplyChunk {
+    /// The chunk was applied. The snapshot is restored once its last chunk
+    /// is applied.
+    Applied,
+    /// The chunk is invalid: it must be fetched again, from another node
+    /// than the one which sent it.
+    InvalidChunk,
+    /// The snapshot turned out to be invalid, e.g. its restored state does
+    /// not match the application hash it was offered with: another snapshot
+    /// must be offered.
+    InvalidSnapshot,
+    /// No snapshot is being restored.
+    NotRestoring,
+}
+
+/// Takes, serves and restores snapshots of the state of an application.
+pub trait SnapshotProvider {
+    /// The snapshots which can be served to other nodes.
+    fn snapshots(&self) -> Vec<Snapshot>;
+
+    /// The chunk of the given index of the snapshot at the given height and
+    /// in the given format, if there is one.
+    fn load_chunk(&self, height: u64, format: u32, index: u32) -> Option<Bytes>;
+
+    /// Start restoring the given snapshot, whose restored state must match
+    /// the given application hash, as verified by the light client of the
+    /// consensus engine.
+    fn offer(&self, snapshot: Snapshot, app_hash: Bytes) -> response_offer_snapshot::Result;
+
+    /// Apply the chunk of the given index of the snapshot being restored.
+    ///
+    /// The chunks are applied in order, and each one is applied once:
+    /// invalid ones are fetched again before the next chunks are applied.
+    fn apply_chunk(&self, index: u32, chunk: Bytes) -> ApplyChunk;
+}
+
+/// Answer a `ListSnapshots` request with the snapshots of the provider.
+pub fn list_snapshots(provider: &impl SnapshotProvider) -> ResponseListSnapshots {
+    ResponseListSnapshots {
+        snapshots: provider.snapshots(),
+    }
+}
+
+/// Answer a `LoadSnapshotChunk` request with the chunk of the provider, or
+/// an empty chunk if the provider does not have it.
+pub fn load_snapshot_chunk(
+    provider: &impl SnapshotProvider,
+    request: RequestLoadSnapshotChunk,
+) -> ResponseLoadSnapshotChunk {
+    ResponseLoadSnapshotChunk {
+        chunk: provider
+            .load_chunk(request.height, request.format, request.chunk)
+            .unwrap_or_default(),
+    }
+}
+
+/// Answer an `OfferSnapshot` request by starting the restoration of the
+/// snapshot with the provider. Requests without a snapshot are rejected.
+pub fn offer_snapshot(
+    provider: &impl SnapshotProvider,
+    request: RequestOfferSnapshot,
+) -> ResponseOfferSnapshot {
+    let result = match request.snapshot {
+        Some(snapshot) => provider.offer(snapshot, request.app_hash),
+        None => response_offer_snapshot::Result::Reject,
+    };
+    ResponseOfferSnapshot {
+        result: result as i32,
+    }
+}
+
+/// Answer an `ApplySnapshotChunk` request by applying the chunk with the
+/// provider. Invalid chunks are fetched again, from another node.
+pub fn apply_snapshot_chunk(
+    provider: &impl SnapshotProvider,
+    request: RequestApplySnapshotChunk,
+) -> ResponseApplySnapshotChunk {
+    use response_apply_snapshot_chunk::Result;
+
+    let (result, refetch_chunks, reject_senders) =
+        match provider.apply_chunk(request.index, request.chunk) {
+            ApplyChunk::Applied => (Result::Accept, vec![], vec![]),
+            ApplyChunk::InvalidChunk => (Result::Retry, vec![request.index], vec![request.sender]),
+            ApplyChunk::InvalidSnapshot => (Result::RejectSnapshot, vec![], vec![]),
+            ApplyChunk::NotRestoring => (Result::Abort, vec![], vec![]),
+        };
+    ResponseApplySnapshotChunk {
+        result: result as i32,
+        refetch_chunks,
+        reject_senders,
+    }
+}
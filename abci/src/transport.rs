@@ -0,0 +1,300 @@
+//! The transports over which ABCI servers and clients communicate: TCP and,
+//! on Unix platforms, Unix domain sockets.
+
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    str::FromStr,
+};
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::{DirBuilderExt, FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::error::Error;
+
+/// The address of an ABCI server, as given to CometBFT through its
+/// `proxy_app` setting.
+///
+/// Parsed from a `tcp://host:port` URL, a `unix:///path/to/socket` URL or a
+/// bare `host:port` address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Address {
+    /// A TCP address, in the `host:port` form.
+    Tcp(String),
+    /// The path of a Unix domain socket.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix://") {
+            #[cfg(unix)]
+            if !path.is_empty() {
+                return Ok(Self::Unix(PathBuf::from(path)));
+            }
+            return Err(Error::invalid_address(s.to_string()));
+        }
+        let addr = s.strip_prefix("tcp://").unwrap_or(s);
+        if addr.is_empty() || addr.contains("://") {
+            return Err(Error::invalid_address(s.to_string()));
+        }
+        Ok(Self::Tcp(addr.to_string()))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+/// The options of the Unix domain sockets bound by a server.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(crate) struct UnixSocketOptions {
+    // Whether to remove a stale socket file before binding, and the socket
+    // file once the server is dropped.
+    pub cleanup: bool,
+    // The permissions given to the socket file, if any.
+    pub permissions: Option<u32>,
+}
+
+impl Default for UnixSocketOptions {
+    fn default() -> Self {
+        Self {
+            cleanup: true,
+            permissions: None,
+        }
+    }
+}
+
+/// The file of a Unix domain socket bound by a server, removed when dropped
+/// if requested.
+#[cfg(unix)]
+pub(crate) struct SocketFile {
+    path: PathBuf,
+    cleanup: bool,
+}
+
+#[cfg(unix)]
+impl SocketFile {
+    /// Prepare the given path for binding a socket, removing a stale socket
+    /// file left there by a server which is no longer running.
+    fn prepare(path: &Path, options: UnixSocketOptions) -> Result<(), Error> {
+        if options.cleanup && is_stale_socket(path) {
+            fs::remove_file(path).map_err(Error::io)?;
+        }
+        Ok(())
+    }
+
+    /// Bind a socket to the given path with `bind`, applying the options to
+    /// its file.
+    ///
+    /// When permissions are requested, the socket is bound within a private
+    /// directory, where its permissions are set before it is linked to the
+    /// given path, so that it is never reachable with broader ones.
+    pub fn bind<L>(
+        path: &Path,
+        options: UnixSocketOptions,
+        bind: impl FnOnce(&Path) -> io::Result<L>,
+    ) -> Result<(L, Self), Error> {
+        Self::prepare(path, options)?;
+        let listener = match options.permissions {
+            Some(mode) => bind_private(path, mode, bind),
+            None => bind(path),
+        }
+        .map_err(Error::io)?;
+        let file = Self {
+            path: path.to_path_buf(),
+            cleanup: options.cleanup,
+        };
+        Ok((listener, file))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        if self.cleanup {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+// Bind a socket within a new directory only accessible to its owner, next to
+// `path`, set its permissions and link it to `path`, which fails rather than
+// replacing a file already there.
+#[cfg(unix)]
+fn bind_private<L>(
+    path: &Path,
+    mode: u32,
+    bind: impl FnOnce(&Path) -> io::Result<L>,
+) -> io::Result<L> {
+    static NEXT_DIR: AtomicU64 = AtomicU64::new(0);
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let dir = parent.join(format!(
+        ".abci.{}.{}",
+        process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::DirBuilder::new().mode(0o700).create(&dir)?;
+    let private_path = dir.join("s");
+    let bound = bind(&private_path).and_then(|listener| {
+        fs::set_permissions(&private_path, fs::Permissions::from_mode(mode))?;
+        fs::hard_link(&private_path, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_file(&private_path);
+    let _ = fs::remove_dir(&dir);
+    bound
+}
+
+// A socket file is stale if nothing accepts connections on it anymore.
+#[cfg(unix)]
+fn is_stale_socket(path: &Path) -> bool {
+    let is_socket = fs::symlink_metadata(path)
+        .map(|metadata| metadata.file_type().is_socket())
+        .unwrap_or(false);
+    is_socket
+        && matches!(
+            UnixStream::connect(path),
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused
+        )
+}
+
+/// A listener for the incoming connections of a blocking server.
+pub(crate) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, SocketFile),
+}
+
+impl Listener {
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    pub fn bind(address: &Address, options: UnixSocketOptions) -> Result<Self, Error> {
+        match address {
+            Address::Tcp(addr) => TcpListener::bind(addr).map(Self::Tcp).map_err(Error::io),
+            #[cfg(unix)]
+            Address::Unix(path) => {
+                let (listener, file) =
+                    SocketFile::bind(path, options, |path| UnixListener::bind(path))?;
+                Ok(Self::Unix(listener, file))
+            },
+        }
+    }
+
+    /// The address the listener is bound to.
+    pub fn local_addr(&self) -> Result<String, Error> {
+        match self {
+            Self::Tcp(listener) => listener
+                .local_addr()
+                .map(|addr| addr.to_string())
+                .map_err(Error::io),
+            #[cfg(unix)]
+            Self::Unix(_, file) => Ok(Address::Unix(file.path().to_path_buf()).to_string()),
+        }
+    }
+
+    /// Accept an incoming connection, along with the address of its peer.
+    pub fn accept(&self) -> Result<(Stream, String), Error> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, addr) = listener.accept().map_err(Error::io)?;
+                Ok((Stream::Tcp(stream), addr.to_string()))
+            },
+            #[cfg(unix)]
+            Self::Unix(listener, file) => {
+                let (stream, _) = listener.accept().map_err(Error::io)?;
+                // The peers of Unix domain sockets are usually unnamed.
+                let addr = Address::Unix(file.path().to_path_buf()).to_string();
+                Ok((Stream::Unix(stream), addr))
+            },
+        }
+    }
+}
+
+/// A blocking connection between an ABCI client and server.
+pub(crate) enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    #[cfg(feature = "client")]
+    pub fn connect(address: &Address) -> Result<Self, Error> {
+        match address {
+            Address::Tcp(addr) => TcpStream::connect(addr).map(Self::Tcp).map_err(Error::io),
+            #[cfg(unix)]
+            Address::Unix(path) => UnixStream::connect(path).map(Self::Unix).map_err(Error::io),
+        }
+    }
+
+    pub fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            Self::Tcp(stream) => stream.try_clone().map(Self::Tcp),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.try_clone().map(Self::Unix),
+        }
+    }
+
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.shutdown(how),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.shutdown(how),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
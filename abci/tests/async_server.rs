@@ -0,0 +1,198 @@
+//! Integration tests for the async ABCI server.
+
+#[cfg(feature = "async")]
+mod async_server_integration {
+    use std::{
+        sync::{
+            atomic::{AtomicI64, AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use prost::Message;
+    use tendermint_abci::{
+        messages::{request, response, Request, Response},
+        AsyncApplication, AsyncServerBuilder,
+    };
+    use tendermint_proto::v0_37::abci::{
+        RequestDeliverTx, RequestFlush, RequestQuery, ResponseDeliverTx, ResponseQuery,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        sync::oneshot,
+        time::sleep,
+    };
+
+    /// Queries sleep for the number of milliseconds given by their data, and
+    /// return the number of transactions delivered so far as their height.
+    #[derive(Clone, Default)]
+    struct SlowApp {
+        running: Arc<AtomicUsize>,
+        max_running: Arc<AtomicUsize>,
+        delivered: Arc<AtomicI64>,
+    }
+
+    #[async_trait]
+    impl AsyncApplication for SlowApp {
+        async fn query(&self, request: RequestQuery) -> ResponseQuery {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            sleep(Duration::from_millis(request.data[0].into())).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            ResponseQuery {
+                key: request.data,
+                height: self.delivered.load(Ordering::SeqCst),
+                ..Default::default()
+            }
+        }
+
+        async fn deliver_tx(&self, _request: RequestDeliverTx) -> ResponseDeliverTx {
+            sleep(Duration::from_millis(20)).await;
+            self.delivered.fetch_add(1, Ordering::SeqCst);
+            Default::default()
+        }
+    }
+
+    fn query(delay_ms: u8, name: u8) -> request::Value {
+        request::Value::Query(RequestQuery {
+            data: vec![delay_ms, name].into(),
+            ..Default::default()
+        })
+    }
+
+    async fn send(stream: &mut TcpStream, requests: Vec<request::Value>) {
+        let mut buf = Vec::new();
+        for value in requests {
+            Request { value: Some(value) }
+                .encode_length_delimited(&mut buf)
+                .unwrap();
+        }
+        stream.write_all(&buf).await.unwrap();
+    }
+
+    async fn receive(stream: &mut TcpStream, count: usize) -> Vec<response::Value> {
+        let mut buf = Vec::new();
+        loop {
+            let mut bytes = buf.as_slice();
+            let mut responses = Vec::new();
+            while let Ok(response) = Response::decode_length_delimited(&mut bytes) {
+                responses.push(response.value.unwrap());
+            }
+            if responses.len() >= count {
+                return responses;
+            }
+            assert_ne!(stream.read_buf(&mut buf).await.unwrap(), 0);
+        }
+    }
+
+    fn query_response(response: &response::Value) -> (u8, i64) {
+        match response {
+            response::Value::Query(query) => (query.key[1], query.height),
+            r => panic!("expected a query response, got {r:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn responds_in_order_to_concurrent_requests() {
+        let app = SlowApp::default();
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", app.clone())
+            .await
+            .unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        tokio::spawn(server.listen());
+
+        send(
+            &mut stream,
+            vec![
+                query(100, b'a'),
+                query(0, b'b'),
+                request::Value::DeliverTx(Default::default()),
+                query(0, b'c'),
+                request::Value::Flush(RequestFlush {}),
+            ],
+        )
+        .await;
+        let responses = receive(&mut stream, 5).await;
+
+        assert_eq!(query_response(&responses[0]), (b'a', 0));
+        assert_eq!(query_response(&responses[1]), (b'b', 0));
+        assert!(matches!(responses[2], response::Value::DeliverTx(_)));
+        // The transaction is delivered before the next query is handled.
+        assert_eq!(query_response(&responses[3]), (b'c', 1));
+        assert!(matches!(responses[4], response::Value::Flush(_)));
+        assert_eq!(app.max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn handles_requests_one_at_a_time_if_configured() {
+        let app = SlowApp::default();
+        let server = AsyncServerBuilder::default()
+            .max_concurrent_requests(1)
+            .bind("127.0.0.1:0", app.clone())
+            .await
+            .unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        tokio::spawn(server.listen());
+
+        send(&mut stream, vec![query(20, b'a'), query(0, b'b')]).await;
+        let responses = receive(&mut stream, 2).await;
+
+        assert_eq!(query_response(&responses[0]), (b'a', 0));
+        assert_eq!(query_response(&responses[1]), (b'b', 0));
+        assert_eq!(app.max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn responds_to_pending_requests_on_shutdown() {
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", SlowApp::default())
+            .await
+            .unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        let (shutdown, signal) = oneshot::channel();
+        let server = tokio::spawn(server.listen_until(async {
+            signal.await.unwrap();
+        }));
+
+        send(&mut stream, vec![query(100, b'a')]).await;
+        sleep(Duration::from_millis(20)).await;
+        shutdown.send(()).unwrap();
+
+        let responses = receive(&mut stream, 1).await;
+        assert_eq!(query_response(&responses[0]), (b'a', 0));
+        // The connection is then closed, and the server stops.
+        let mut rest = Vec::new();
+        assert_eq!(stream.read_to_end(&mut rest).await.unwrap(), 0);
+        server.await.unwrap().unwrap();
+    }
+
+    #[cfg(all(feature = "client", feature = "echo-app"))]
+    #[tokio::test]
+    async fn serves_sync_applications() {
+        use tendermint_abci::{ClientBuilder, EchoApp, SyncAdapter};
+        use tendermint_proto::v0_37::abci::RequestEcho;
+
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let response = tokio::task::spawn_blocking(move || {
+            let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+            client
+                .echo(RequestEcho {
+                    message: "Hello ABCI!".to_string(),
+                })
+                .unwrap()
+        })
+        .await
+        .unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+    }
+}
@@ -0,0 +1,141 @@
+//! Integration tests for the ABCI client.
+
+#[cfg(feature = "client")]
+mod client_integration {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use bytes::{Buf, BytesMut};
+    use prost::Message;
+    use tendermint_abci::{
+        messages::{request, response, Request, Response},
+        ClientBuilder, Error,
+    };
+    use tendermint_proto::v0_37::abci::{
+        RequestCheckTx, RequestEcho, RequestInfo, ResponseCheckTx, ResponseEcho, ResponseException,
+        ResponseFlush,
+    };
+
+    /// Serves a connection like the servers of Tendermint and CometBFT do,
+    /// only writing out the responses once flushed. Info requests are
+    /// responded to with an exception.
+    fn serve_until_flushed(mut stream: TcpStream) {
+        let mut read_buf = BytesMut::new();
+        let mut write_buf = Vec::new();
+        let mut chunk = [0_u8; 1024];
+        loop {
+            let request = loop {
+                let mut buf = read_buf.clone().freeze();
+                if let Ok(request) = Request::decode_length_delimited(&mut buf) {
+                    read_buf.advance(read_buf.len() - buf.remaining());
+                    break request;
+                }
+                match stream.read(&mut chunk) {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => read_buf.extend_from_slice(&chunk[..n]),
+                }
+            };
+            let value = match request.value.unwrap() {
+                request::Value::Echo(req) => response::Value::Echo(ResponseEcho {
+                    message: req.message,
+                }),
+                request::Value::CheckTx(req) => response::Value::CheckTx(ResponseCheckTx {
+                    data: req.tx,
+                    ..Default::default()
+                }),
+                request::Value::Info(_) => response::Value::Exception(ResponseException {
+                    error: "no info".to_string(),
+                }),
+                request::Value::Flush(_) => response::Value::Flush(ResponseFlush {}),
+                req => panic!("unexpected request: {req:?}"),
+            };
+            let is_flush = matches!(value, response::Value::Flush(_));
+            Response { value: Some(value) }
+                .encode_length_delimited(&mut write_buf)
+                .unwrap();
+            if is_flush {
+                stream.write_all(&write_buf).unwrap();
+                write_buf.clear();
+            }
+        }
+    }
+
+    fn spawn_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                thread::spawn(move || serve_until_flushed(stream));
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn flushes_every_request() {
+        let mut client = ClientBuilder::default().connect(spawn_server()).unwrap();
+        for i in 0..3 {
+            let res = client
+                .echo(RequestEcho {
+                    message: format!("Hello ABCI {i}!"),
+                })
+                .unwrap();
+            assert_eq!(res.message, format!("Hello ABCI {i}!"));
+        }
+        client.flush().unwrap();
+    }
+
+    #[test]
+    fn pipelines_requests() {
+        let mut client = ClientBuilder::default().connect(spawn_server()).unwrap();
+        let responses = client
+            .pipeline((0..10_u8).map(|i| {
+                request::Value::CheckTx(RequestCheckTx {
+                    tx: vec![i].into(),
+                    ..Default::default()
+                })
+            }))
+            .unwrap();
+        assert_eq!(responses.len(), 10);
+        for (i, res) in responses.into_iter().enumerate() {
+            match res {
+                response::Value::CheckTx(res) => assert_eq!(res.data, [i as u8].as_slice()),
+                res => panic!("unexpected response: {res:?}"),
+            }
+        }
+        assert!(client.pipeline([]).unwrap().is_empty());
+
+        // The client can still be used for single requests afterwards.
+        let res = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(res.message, "Hello ABCI!");
+    }
+
+    #[test]
+    fn surfaces_exceptions() {
+        let mut client = ClientBuilder::default().connect(spawn_server()).unwrap();
+        let err: Error = client.info(RequestInfo::default()).unwrap_err();
+        assert!(err.to_string().contains("no info"), "{err}");
+
+        // Pipelined exceptions are returned as responses.
+        let responses = client
+            .pipeline([request::Value::Info(RequestInfo::default())])
+            .unwrap();
+        assert!(matches!(responses[..], [response::Value::Exception(_)]));
+
+        // The connection is still in sync.
+        let res = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(res.message, "Hello ABCI!");
+    }
+}
@@ -21,4 +21,62 @@ mod echo_app_integration {
             .unwrap();
         assert_eq!(response.message, "Hello ABCI!");
     }
+
+    #[test]
+    fn echo_many_clients_few_workers() {
+        let server = ServerBuilder::default()
+            .worker_threads(2)
+            .queue_depth(1)
+            .bind("127.0.0.1:0", EchoApp::default())
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+
+        let handles = (0..8)
+            .map(|id| {
+                let mut client = ClientBuilder::default()
+                    .connect(server_addr.clone())
+                    .unwrap();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        let message = format!("Hello from client {id}: {i}");
+                        let response = client
+                            .echo(RequestEcho {
+                                message: message.clone(),
+                            })
+                            .unwrap();
+                        assert_eq!(response.message, message);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn vote_extensions() {
+        use tendermint_abci::messages::{
+            response_verify_vote_extension::VerifyStatus, RequestExtendVote,
+            RequestVerifyVoteExtension,
+        };
+
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", EchoApp::default())
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+
+        let response = client.extend_vote(RequestExtendVote::default()).unwrap();
+        assert!(response.vote_extension.is_empty());
+        let response = client
+            .verify_vote_extension(RequestVerifyVoteExtension {
+                vote_extension: "extension".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(response.status, VerifyStatus::Accept as i32);
+    }
 }
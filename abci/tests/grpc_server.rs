@@ -0,0 +1,228 @@
+//! Integration tests for the gRPC ABCI server.
+
+#[cfg(all(feature = "grpc", feature = "echo-app"))]
+mod grpc_server_integration {
+    use bytes::{Buf, BufMut, Bytes, BytesMut};
+    use hyper::{body::HttpBody, client::conn, Body, Method, Request};
+    use prost::Message;
+    use tendermint_abci::{messages, EchoApp, GrpcServerBuilder, SyncAdapter};
+    use tendermint_proto::v0_37::abci::{
+        abci_application, RequestEcho, RequestFlush, ResponseEcho, ResponseFlush,
+    };
+    use tokio::{
+        io::{AsyncRead, AsyncWrite},
+        net::TcpStream,
+        sync::oneshot,
+    };
+
+    /// The status, status message and message of the response to a call.
+    struct Reply {
+        status: u32,
+        message: String,
+        body: Bytes,
+    }
+
+    async fn call<I>(io: I, path: &str, request: Bytes) -> Reply
+    where
+        I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sender, connection) = conn::Builder::new()
+            .http2_only(true)
+            .handshake::<_, Body>(io)
+            .await
+            .unwrap();
+        tokio::spawn(connection);
+
+        let mut frame = BytesMut::new();
+        frame.put_u8(0);
+        frame.put_u32(request.len() as u32);
+        frame.put(request);
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://localhost{path}"))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .body(Body::from(frame.freeze()))
+            .unwrap();
+        let mut response = sender.send_request(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let headers = response.headers().clone();
+        let mut body = BytesMut::new();
+        while let Some(data) = response.data().await {
+            body.put(data.unwrap());
+        }
+        let trailers = response.trailers().await.unwrap().unwrap_or_default();
+        // The status of a failed call may be sent in the headers instead,
+        // without any trailers.
+        let field = |name: &str| trailers.get(name).or_else(|| headers.get(name));
+        let status = field("grpc-status")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let message = field("grpc-message")
+            .map(|message| message.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        let mut body = body.freeze();
+        if !body.is_empty() {
+            assert_eq!(body.get_u8(), 0);
+            assert_eq!(body.get_u32() as usize, body.len());
+        }
+        Reply {
+            status,
+            message,
+            body,
+        }
+    }
+
+    fn echo_request() -> Bytes {
+        RequestEcho {
+            message: "Hello ABCI!".to_string(),
+        }
+        .encode_to_vec()
+        .into()
+    }
+
+    #[tokio::test]
+    async fn serves_both_services() {
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let handle = tokio::spawn(server.listen_until(async {
+            let _ = stopped.await;
+        }));
+
+        for service in [abci_application::NAME, messages::abci::NAME] {
+            let stream = TcpStream::connect(&addr).await.unwrap();
+            let reply = call(stream, &format!("/{service}/Echo"), echo_request()).await;
+            assert_eq!(reply.status, 0, "{}", reply.message);
+            let response = ResponseEcho::decode(reply.body).unwrap();
+            assert_eq!(response.message, "Hello ABCI!");
+
+            let stream = TcpStream::connect(&addr).await.unwrap();
+            let request = RequestFlush {}.encode_to_vec().into();
+            let reply = call(stream, &format!("/{service}/Flush"), request).await;
+            assert_eq!(reply.status, 0, "{}", reply.message);
+            ResponseFlush::decode(reply.body).unwrap();
+        }
+
+        stop.send(()).unwrap();
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn serves_every_method() {
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let services = [
+            (abci_application::NAME, abci_application::METHODS),
+            (messages::abci::NAME, messages::abci::METHODS),
+        ];
+        for (service, methods) in services {
+            for method in methods {
+                // An empty message decodes as the default request of any
+                // method.
+                let stream = TcpStream::connect(&addr).await.unwrap();
+                let reply = call(stream, &format!("/{service}/{method}"), Bytes::new()).await;
+                assert_eq!(reply.status, 0, "{service}/{method}: {}", reply.message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_calls() {
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let path = format!("/{}/SetOption", abci_application::NAME);
+        let reply = call(stream, &path, Bytes::new()).await;
+        assert_eq!(reply.status, 12);
+        assert!(reply.body.is_empty());
+
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let reply = call(stream, "/tendermint.abci.Unknown/Echo", echo_request()).await;
+        assert_eq!(reply.status, 12);
+
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let path = format!("/{}/Echo", abci_application::NAME);
+        let reply = call(stream, &path, Bytes::from_static(&[0xff, 0xff])).await;
+        assert_eq!(reply.status, 13);
+    }
+
+    #[tokio::test]
+    async fn serves_the_methods_of_each_service_only() {
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let calls = [
+            (abci_application::NAME, "FinalizeBlock"),
+            (abci_application::NAME, "ExtendVote"),
+            (messages::abci::NAME, "DeliverTx"),
+            (messages::abci::NAME, "BeginBlock"),
+        ];
+        for (service, method) in calls {
+            let stream = TcpStream::connect(&addr).await.unwrap();
+            let reply = call(stream, &format!("/{service}/{method}"), Bytes::new()).await;
+            assert_eq!(reply.status, 12, "{service}/{method}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_messages() {
+        let server = GrpcServerBuilder::default()
+            .max_message_size(8)
+            .bind("127.0.0.1:0", SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let path = format!("/{}/Echo", messages::abci::NAME);
+        let reply = call(stream, &path, echo_request()).await;
+        assert_eq!(reply.status, 11, "{}", reply.message);
+        assert!(reply.body.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serves_over_unix_socket() {
+        let path =
+            std::env::temp_dir().join(format!("tendermint-abci-{}-grpc.sock", std::process::id()));
+        let server = GrpcServerBuilder::default()
+            .bind_unix(&path, SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        tokio::spawn(server.listen());
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let reply = call(
+            stream,
+            &format!("/{}/Echo", messages::abci::NAME),
+            echo_request(),
+        )
+        .await;
+        assert_eq!(reply.status, 0, "{}", reply.message);
+        let response = ResponseEcho::decode(reply.body).unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+    }
+}
@@ -4,8 +4,25 @@
 mod kvstore_app_integration {
     use std::thread;
 
-    use tendermint_abci::{ClientBuilder, KeyValueStoreApp, ServerBuilder};
-    use tendermint_proto::v0_37::abci::{RequestDeliverTx, RequestEcho, RequestQuery};
+    use tendermint_abci::{
+        messages::RequestFinalizeBlock, Client, ClientBuilder, KeyValueStoreApp,
+        KeyValueStoreDriver, ServerBuilder,
+    };
+    use tendermint_proto::v0_37::abci::{
+        response_apply_snapshot_chunk, response_offer_snapshot, RequestApplySnapshotChunk,
+        RequestDeliverTx, RequestEcho, RequestInfo, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+        RequestQuery,
+    };
+
+    fn serve(configure: impl FnOnce(KeyValueStoreDriver) -> KeyValueStoreDriver) -> Client {
+        let (app, driver) = KeyValueStoreApp::new();
+        let driver = configure(driver);
+        let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+        ClientBuilder::default().connect(server_addr).unwrap()
+    }
 
     #[test]
     fn happy_path() {
@@ -40,4 +57,232 @@ mod kvstore_app_integration {
             .unwrap();
         assert_eq!(res.value, "test-value".as_bytes());
     }
+
+    #[test]
+    fn finalize_block() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+        let res = client
+            .finalize_block(RequestFinalizeBlock {
+                txs: vec!["a=1".into(), "b=2".into()],
+                height: 1,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.tx_results.len(), 2);
+        // The number of keys in the store, encoded as a varint.
+        assert_eq!(res.app_hash, [2].as_slice());
+        let commit = client.commit().unwrap();
+        assert_eq!(commit.data, res.app_hash);
+
+        let res = client
+            .query(RequestQuery {
+                data: "b".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.value, "2".as_bytes());
+        assert_eq!(res.height, 1);
+    }
+
+    #[test]
+    fn state_sync() {
+        let mut source = serve(|driver| {
+            driver
+                .snapshot_interval(2)
+                .snapshot_chunk_size(16)
+                .snapshot_keep_recent(1)
+        });
+        let mut app_hash = Default::default();
+        for height in 1..=4 {
+            let txs = (0..5)
+                .map(|i| format!("key-{height}-{i}=value-{height}-{i}").into())
+                .collect();
+            source
+                .finalize_block(RequestFinalizeBlock {
+                    txs,
+                    height,
+                    ..Default::default()
+                })
+                .unwrap();
+            app_hash = source.commit().unwrap().data;
+        }
+
+        // Only the most recent snapshot is kept.
+        let snapshots = source.list_snapshots().unwrap().snapshots;
+        assert_eq!(snapshots.len(), 1);
+        let snapshot = snapshots[0].clone();
+        assert_eq!(snapshot.height, 4);
+        assert!(snapshot.chunks > 1);
+
+        let mut target = serve(|driver| driver);
+        let offered = target
+            .offer_snapshot(RequestOfferSnapshot {
+                snapshot: Some(snapshot.clone()),
+                app_hash,
+            })
+            .unwrap();
+        assert_eq!(
+            offered.result,
+            response_offer_snapshot::Result::Accept as i32
+        );
+
+        for index in 0..snapshot.chunks {
+            let chunk = source
+                .load_snapshot_chunk(RequestLoadSnapshotChunk {
+                    height: snapshot.height,
+                    format: snapshot.format,
+                    chunk: index,
+                })
+                .unwrap()
+                .chunk;
+
+            // A corrupted chunk is fetched again, from another node.
+            let mut corrupted = chunk.to_vec();
+            corrupted[0] ^= 1;
+            let applied = target
+                .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                    index,
+                    chunk: corrupted.into(),
+                    sender: "faulty".to_string(),
+                })
+                .unwrap();
+            assert_eq!(
+                applied.result,
+                response_apply_snapshot_chunk::Result::Retry as i32
+            );
+            assert_eq!(applied.refetch_chunks, vec![index]);
+            assert_eq!(applied.reject_senders, vec!["faulty".to_string()]);
+
+            let applied = target
+                .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                    index,
+                    chunk,
+                    sender: "correct".to_string(),
+                })
+                .unwrap();
+            assert_eq!(
+                applied.result,
+                response_apply_snapshot_chunk::Result::Accept as i32
+            );
+        }
+
+        let info = target.info(RequestInfo::default()).unwrap();
+        assert_eq!(info.last_block_height, 4);
+        let res = target
+            .query(RequestQuery {
+                data: "key-3-2".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.value, "value-3-2".as_bytes());
+    }
+
+    #[test]
+    fn state_sync_empty_store() {
+        let mut source = serve(|driver| driver.snapshot_interval(1));
+        source
+            .finalize_block(RequestFinalizeBlock {
+                height: 1,
+                ..Default::default()
+            })
+            .unwrap();
+        let app_hash = source.commit().unwrap().data;
+
+        // The snapshot of an empty store has a single, empty, chunk.
+        let snapshot = source.list_snapshots().unwrap().snapshots[0].clone();
+        assert_eq!(snapshot.chunks, 1);
+        let chunk = source
+            .load_snapshot_chunk(RequestLoadSnapshotChunk {
+                height: snapshot.height,
+                format: snapshot.format,
+                chunk: 0,
+            })
+            .unwrap()
+            .chunk;
+        assert!(chunk.is_empty());
+
+        let mut target = serve(|driver| driver);
+        let offered = target
+            .offer_snapshot(RequestOfferSnapshot {
+                snapshot: Some(snapshot),
+                app_hash,
+            })
+            .unwrap();
+        assert_eq!(
+            offered.result,
+            response_offer_snapshot::Result::Accept as i32
+        );
+        let applied = target
+            .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                index: 0,
+                chunk,
+                sender: "source".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            applied.result,
+            response_apply_snapshot_chunk::Result::Accept as i32
+        );
+        assert_eq!(
+            target
+                .info(RequestInfo::default())
+                .unwrap()
+                .last_block_height,
+            1
+        );
+    }
+
+    #[test]
+    fn state_sync_app_hash_mismatch() {
+        let mut source = serve(|driver| driver.snapshot_interval(1));
+        source
+            .finalize_block(RequestFinalizeBlock {
+                txs: vec!["a=1".into()],
+                height: 1,
+                ..Default::default()
+            })
+            .unwrap();
+        source.commit().unwrap();
+        let snapshot = source.list_snapshots().unwrap().snapshots[0].clone();
+
+        let mut target = serve(|driver| driver);
+        target
+            .offer_snapshot(RequestOfferSnapshot {
+                snapshot: Some(snapshot.clone()),
+                app_hash: vec![2].into(),
+            })
+            .unwrap();
+        let chunk = source
+            .load_snapshot_chunk(RequestLoadSnapshotChunk {
+                height: snapshot.height,
+                format: snapshot.format,
+                chunk: 0,
+            })
+            .unwrap()
+            .chunk;
+        let applied = target
+            .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                index: 0,
+                chunk,
+                sender: "source".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            applied.result,
+            response_apply_snapshot_chunk::Result::RejectSnapshot as i32
+        );
+        assert_eq!(
+            target
+                .info(RequestInfo::default())
+                .unwrap()
+                .last_block_height,
+            0
+        );
+    }
 }
@@ -0,0 +1,177 @@
+//! Integration tests for the layers wrapping ABCI applications.
+
+#[cfg(feature = "client")]
+mod layers_integration {
+    use tendermint_abci::{
+        layer::{
+            CatchPanicLayer, Layer, LayerBuilder, LoggingLayer, MetricsLayer, SizeLimitLayer,
+            REQUEST_TOO_LARGE_CODE,
+        },
+        Application, Client, ClientBuilder, Error, RequestDispatcher, ServerBuilder,
+    };
+    use tendermint_proto::v0_37::abci::{
+        RequestCheckTx, RequestEcho, RequestInfo, RequestQuery, ResponseInfo,
+    };
+
+    /// Echoes messages, and panics on info requests.
+    #[derive(Clone)]
+    struct PanickingApp;
+
+    impl Application for PanickingApp {
+        fn info(&self, _request: RequestInfo) -> ResponseInfo {
+            panic!("no info");
+        }
+    }
+
+    fn serve<App: RequestDispatcher>(app: App) -> Client {
+        let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+        ClientBuilder::default().connect(server_addr).unwrap()
+    }
+
+    #[test]
+    fn catch_panic() {
+        let mut client = serve(CatchPanicLayer.layer(PanickingApp));
+
+        let err: Error = client.info(RequestInfo::default()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("panicked handling info request: no info"),
+            "{err}"
+        );
+
+        // The connection is still usable.
+        let response = client
+            .echo(RequestEcho {
+                message: "still here".to_string(),
+            })
+            .unwrap();
+        assert_eq!(response.message, "still here");
+    }
+
+    #[test]
+    fn metrics() {
+        let metrics = MetricsLayer::new();
+        let mut client = serve(
+            LayerBuilder::new()
+                .layer(LoggingLayer)
+                .layer(metrics.clone())
+                .layer(CatchPanicLayer)
+                .build(PanickingApp),
+        );
+
+        for _ in 0..3 {
+            client.echo(RequestEcho::default()).unwrap();
+        }
+        client.info(RequestInfo::default()).unwrap_err();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot["echo"].count, 3);
+        assert!(snapshot["echo"].max <= snapshot["echo"].total);
+        assert_eq!(snapshot["info"].count, 1);
+        // Every request is followed by a flush.
+        assert_eq!(snapshot["flush"].count, 4);
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut client = serve(
+            LayerBuilder::new()
+                .layer(SizeLimitLayer::new(64))
+                .build(PanickingApp),
+        );
+
+        let response = client
+            .check_tx(RequestCheckTx {
+                tx: vec![0; 32].into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(response.code, 0);
+        let response = client
+            .check_tx(RequestCheckTx {
+                tx: vec![0; 128].into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(response.code, REQUEST_TOO_LARGE_CODE);
+
+        let response = client
+            .query(RequestQuery {
+                data: vec![0; 128].into(),
+                height: 7,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(response.code, REQUEST_TOO_LARGE_CODE);
+        assert_eq!(response.height, 7);
+
+        client
+            .echo(RequestEcho {
+                message: "x".repeat(128),
+            })
+            .unwrap_err();
+    }
+}
+
+#[cfg(all(feature = "client", feature = "async"))]
+mod async_layers_integration {
+    use async_trait::async_trait;
+    use tendermint_abci::{
+        layer::{CatchPanicLayer, LayerBuilder, LoggingLayer, MetricsLayer},
+        AsyncApplication, AsyncServerBuilder, ClientBuilder, Error,
+    };
+    use tendermint_proto::v0_37::abci::{RequestEcho, RequestInfo, ResponseInfo};
+
+    /// Echoes messages, and panics on info requests.
+    #[derive(Clone)]
+    struct PanickingApp;
+
+    #[async_trait]
+    impl AsyncApplication for PanickingApp {
+        async fn info(&self, _request: RequestInfo) -> ResponseInfo {
+            panic!("no info");
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn catch_panic_and_metrics() {
+        let metrics = MetricsLayer::new();
+        let app = LayerBuilder::new()
+            .layer(LoggingLayer)
+            .layer(metrics.clone())
+            .layer(CatchPanicLayer)
+            .build(PanickingApp);
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", app)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        tokio::task::spawn_blocking(move || {
+            let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+            let err: Error = client.info(RequestInfo::default()).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("panicked handling info request: no info"),
+                "{err}"
+            );
+
+            // The connection is still usable.
+            let response = client
+                .echo(RequestEcho {
+                    message: "still here".to_string(),
+                })
+                .unwrap();
+            assert_eq!(response.message, "still here");
+        })
+        .await
+        .unwrap();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot["info"].count, 1);
+        assert_eq!(snapshot["echo"].count, 1);
+    }
+}
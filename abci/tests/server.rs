@@ -0,0 +1,205 @@
+//! Integration tests for the handling of the connections by the ABCI server.
+
+#[cfg(feature = "client")]
+mod server_integration {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use tendermint_abci::{
+        messages::{request, response},
+        Application, Client, ClientBuilder, ServerBuilder,
+    };
+    use tendermint_proto::v0_37::abci::{
+        RequestCheckTx, RequestDeliverTx, RequestEcho, RequestQuery, ResponseCheckTx,
+        ResponseDeliverTx, ResponseQuery,
+    };
+
+    /// Sleeps for the number of milliseconds given by the first byte of the
+    /// queries and transactions, keeping track of the number of requests
+    /// handled at the same time.
+    #[derive(Clone, Default)]
+    struct SlowApp {
+        running: Arc<AtomicUsize>,
+        max_running: Arc<AtomicUsize>,
+    }
+
+    impl SlowApp {
+        fn run(&self, data: &[u8]) {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(data[0].into()));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        fn max_running(&self) -> usize {
+            self.max_running.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Application for SlowApp {
+        fn query(&self, request: RequestQuery) -> ResponseQuery {
+            self.run(&request.data);
+            ResponseQuery {
+                value: request.data,
+                ..Default::default()
+            }
+        }
+
+        fn check_tx(&self, request: RequestCheckTx) -> ResponseCheckTx {
+            self.run(&request.tx);
+            ResponseCheckTx {
+                data: request.tx,
+                ..Default::default()
+            }
+        }
+
+        fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
+            self.run(&request.tx);
+            ResponseDeliverTx {
+                data: request.tx,
+                ..Default::default()
+            }
+        }
+    }
+
+    fn serve(builder: ServerBuilder, app: SlowApp) -> Client {
+        let server = builder.worker_threads(8).bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        let _ = thread::spawn(move || server.listen());
+        ClientBuilder::default().connect(server_addr).unwrap()
+    }
+
+    // The later requests are handled faster than the earlier ones.
+    fn delays() -> impl Iterator<Item = Vec<u8>> {
+        (1..=6_u8).rev().map(|i| vec![i * 20])
+    }
+
+    #[test]
+    fn concurrent_queries_in_order() {
+        let app = SlowApp::default();
+        let mut client = serve(
+            ServerBuilder::default().max_concurrent_queries(3),
+            app.clone(),
+        );
+
+        let responses = client
+            .pipeline(delays().map(|data| {
+                request::Value::Query(RequestQuery {
+                    data: data.into(),
+                    ..Default::default()
+                })
+            }))
+            .unwrap();
+        let values = responses
+            .into_iter()
+            .map(|response| match response {
+                response::Value::Query(response) => response.value.to_vec(),
+                response => panic!("unexpected response: {response:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, delays().collect::<Vec<_>>());
+        assert!(app.max_running() > 1);
+        assert!(app.max_running() <= 3);
+    }
+
+    #[test]
+    fn serial_check_txs_by_default() {
+        let app = SlowApp::default();
+        let mut client = serve(ServerBuilder::default(), app.clone());
+
+        let responses = client
+            .pipeline(delays().map(|tx| {
+                request::Value::CheckTx(RequestCheckTx {
+                    tx: tx.into(),
+                    ..Default::default()
+                })
+            }))
+            .unwrap();
+        assert_eq!(responses.len(), 6);
+        assert_eq!(app.max_running(), 1);
+    }
+
+    #[test]
+    fn concurrent_check_txs() {
+        let app = SlowApp::default();
+        let mut client = serve(
+            ServerBuilder::default().max_concurrent_check_txs(2),
+            app.clone(),
+        );
+
+        let responses = client
+            .pipeline(delays().map(|tx| {
+                request::Value::CheckTx(RequestCheckTx {
+                    tx: tx.into(),
+                    ..Default::default()
+                })
+            }))
+            .unwrap();
+        let data = responses
+            .into_iter()
+            .map(|response| match response {
+                response::Value::CheckTx(response) => response.data.to_vec(),
+                response => panic!("unexpected response: {response:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(data, delays().collect::<Vec<_>>());
+        assert_eq!(app.max_running(), 2);
+    }
+
+    #[test]
+    fn serial_consensus_connection() {
+        let app = SlowApp::default();
+        let mut client = serve(
+            ServerBuilder::default()
+                .max_concurrent_queries(4)
+                .max_concurrent_check_txs(4),
+            app.clone(),
+        );
+
+        let responses = client
+            .pipeline(
+                delays().map(|tx| request::Value::DeliverTx(RequestDeliverTx { tx: tx.into() })),
+            )
+            .unwrap();
+        assert_eq!(responses.len(), 6);
+        assert_eq!(app.max_running(), 1);
+    }
+
+    #[test]
+    fn connections_beyond_the_limit_are_closed() {
+        let server = ServerBuilder::default()
+            .max_connections(1)
+            .bind("127.0.0.1:0", SlowApp::default())
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = thread::spawn(move || server.listen());
+        let echo = |client: &mut Client| {
+            client.echo(RequestEcho {
+                message: "ping".to_string(),
+            })
+        };
+
+        let mut client = ClientBuilder::default().connect(&server_addr).unwrap();
+        assert_eq!(echo(&mut client).unwrap().message, "ping");
+
+        let mut refused = ClientBuilder::default().connect(&server_addr).unwrap();
+        assert!(echo(&mut refused).is_err());
+
+        // Another connection is accepted once the first one is closed.
+        drop(client);
+        let accepted = (0..50).any(|_| {
+            thread::sleep(Duration::from_millis(20));
+            ClientBuilder::default()
+                .connect(&server_addr)
+                .and_then(|mut client| echo(&mut client))
+                .is_ok()
+        });
+        assert!(accepted);
+    }
+}
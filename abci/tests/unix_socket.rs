@@ -0,0 +1,136 @@
+//! Integration tests for ABCI servers and clients over Unix domain sockets.
+
+use tendermint_abci::Address;
+
+#[test]
+fn parses_addresses() {
+    let tcp = Address::Tcp("127.0.0.1:26658".to_string());
+    assert_eq!("tcp://127.0.0.1:26658".parse::<Address>().unwrap(), tcp);
+    assert_eq!("127.0.0.1:26658".parse::<Address>().unwrap(), tcp);
+    assert_eq!(tcp.to_string(), "tcp://127.0.0.1:26658");
+    #[cfg(unix)]
+    assert_eq!(
+        "unix:///tmp/app.sock".parse::<Address>().unwrap(),
+        Address::Unix("/tmp/app.sock".into())
+    );
+
+    assert!("unix://".parse::<Address>().is_err());
+    assert!("grpc://127.0.0.1:26658".parse::<Address>().is_err());
+    assert!("".parse::<Address>().is_err());
+}
+
+#[cfg(all(unix, feature = "client", feature = "echo-app"))]
+mod unix_socket_integration {
+    use std::{
+        fs,
+        os::unix::{fs::PermissionsExt, net::UnixListener},
+        path::PathBuf,
+    };
+
+    use tendermint_abci::{Address, ClientBuilder, EchoApp, ServerBuilder};
+    use tendermint_proto::v0_37::abci::RequestEcho;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "tendermint-abci-{}-{}.sock",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn echo(address: &Address) {
+        let mut client = ClientBuilder::default().connect_address(address).unwrap();
+        let response = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+    }
+
+    #[test]
+    fn echo_over_unix_socket() {
+        let path = socket_path("echo");
+        let server = ServerBuilder::default()
+            .unix_socket_permissions(0o600)
+            .bind_unix(&path, EchoApp)
+            .unwrap();
+        let address: Address = server.local_addr().parse().unwrap();
+        assert_eq!(address, Address::Unix(path.clone()));
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let _ = std::thread::spawn(move || server.listen());
+
+        echo(&address);
+    }
+
+    #[test]
+    fn cleans_up_socket_files() {
+        let path = socket_path("cleanup");
+        // A socket file left behind by a server which is no longer running.
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let server = ServerBuilder::default().bind_unix(&path, EchoApp).unwrap();
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn keeps_socket_files_if_configured() {
+        let path = socket_path("keep");
+        drop(UnixListener::bind(&path).unwrap());
+
+        assert!(ServerBuilder::default()
+            .unix_socket_cleanup(false)
+            .bind_unix(&path, EchoApp)
+            .is_err());
+
+        fs::remove_file(&path).unwrap();
+        let server = ServerBuilder::default()
+            .unix_socket_cleanup(false)
+            .bind_unix(&path, EchoApp)
+            .unwrap();
+        drop(server);
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn does_not_replace_running_servers() {
+        let path = socket_path("running");
+        let server = ServerBuilder::default().bind_unix(&path, EchoApp).unwrap();
+        let address: Address = server.local_addr().parse().unwrap();
+        let _ = std::thread::spawn(move || server.listen());
+
+        assert!(ServerBuilder::default().bind_unix(&path, EchoApp).is_err());
+        echo(&address);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_echo_over_unix_socket() {
+        use tendermint_abci::{AsyncServerBuilder, SyncAdapter};
+
+        let path = socket_path("async");
+        let server = AsyncServerBuilder::default()
+            .bind_unix(&path, SyncAdapter::new(EchoApp))
+            .await
+            .unwrap();
+        let address: Address = server.local_addr().parse().unwrap();
+        let (shutdown, signal) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(server.listen_until(async {
+            let _ = signal.await;
+        }));
+
+        tokio::task::spawn_blocking(move || echo(&address))
+            .await
+            .unwrap();
+
+        shutdown.send(()).unwrap();
+        server.await.unwrap().unwrap();
+        assert!(!path.exists());
+    }
+}
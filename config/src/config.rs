@@ -19,8 +19,19 @@ use tendermint::{genesis::Genesis, node, Moniker, Timeout};
 use crate::{net, node_key::NodeKey, prelude::*, Error};
 
 /// Tendermint `config.toml` file
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+///
+/// Files written by Tendermint Core v0.34 up to CometBFT v1.0 are supported:
+/// the keys which only some versions have are optional, and the keys which
+/// are not known are kept in the `unknown` field of each section, so that
+/// serializing a parsed file with [`TendermintConfig::to_toml`] loses none of
+/// its settings.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct TendermintConfig {
+    /// Version of CometBFT which created or last modified the file. This key
+    /// was only first made available in CometBFT v0.38.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
     /// TCP or UNIX socket address of the ABCI application,
     /// or the name of an ABCI application compiled in with the Tendermint binary.
     pub proxy_app: net::Address,
@@ -30,8 +41,14 @@ pub struct TendermintConfig {
 
     /// If this node is many blocks behind the tip of the chain, FastSync
     /// allows them to catchup quickly by downloading blocks in parallel
-    /// and verifying their commits
-    pub fast_sync: bool,
+    /// and verifying their commits. Renamed to `block_sync` in CometBFT v0.37.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fast_sync: Option<bool>,
+
+    /// Whether BlockSync, formerly FastSync, is enabled. This key was removed
+    /// in CometBFT v1.0, where BlockSync is always enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_sync: Option<bool>,
 
     /// Database backend: `goleveldb | cleveldb | boltdb | rocksdb | badgerdb`
     pub db_backend: DbBackend,
@@ -100,8 +117,17 @@ pub struct TendermintConfig {
     /// statesync configuration options
     pub statesync: StatesyncConfig,
 
-    /// fastsync configuration options
-    pub fastsync: FastsyncConfig,
+    /// fastsync configuration options, up to Tendermint Core v0.34
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fastsync: Option<FastsyncConfig>,
+
+    /// blocksync configuration options, from CometBFT v0.37 on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocksync: Option<BlocksyncConfig>,
+
+    /// Keys and sections not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 impl TendermintConfig {
@@ -123,6 +149,35 @@ impl TendermintConfig {
         Self::parse_toml(toml_string)
     }
 
+    /// Serialize to the `config.toml` format. The keys of each section are
+    /// sorted, and the comments of the parsed file are not kept.
+    pub fn to_toml(&self) -> Result<String, Error> {
+        // Going through a `toml::Value` puts the keys of each table before
+        // its subtables, as TOML requires, including the unknown ones.
+        let value = toml::Value::try_from(self).map_err(Error::toml_ser)?;
+        toml::to_string(&value).map_err(Error::toml_ser)
+    }
+
+    /// The release series of CometBFT the file was written for, as far as it
+    /// can tell. Files without a `version` key were written before CometBFT
+    /// v0.38, and the ones with a `[blocksync]` section by CometBFT v0.37.
+    pub fn config_version(&self) -> ConfigVersion {
+        match self.version.as_deref() {
+            Some(version) if version.starts_with("0.34.") => ConfigVersion::V0_34,
+            Some(version) if version.starts_with("0.37.") => ConfigVersion::V0_37,
+            Some(version) if version.starts_with("0.38.") => ConfigVersion::V0_38,
+            Some(_) => ConfigVersion::V1,
+            None if self.blocksync.is_some() => ConfigVersion::V0_37,
+            None => ConfigVersion::V0_34,
+        }
+    }
+
+    /// Whether BlockSync, formerly FastSync, is enabled, which it is unless
+    /// disabled by either `fast_sync` or `block_sync`
+    pub fn block_sync_enabled(&self) -> bool {
+        self.block_sync.or(self.fast_sync).unwrap_or(true)
+    }
+
     /// Load `genesis.json` file from the configured location
     pub fn load_genesis_file(&self, home: impl AsRef<Path>) -> Result<Genesis, Error> {
         let path = home.as_ref().join(&self.genesis_file);
@@ -141,6 +196,27 @@ impl TendermintConfig {
     }
 }
 
+/// Release series of CometBFT (or Tendermint Core) a `config.toml` file was
+/// written for
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum ConfigVersion {
+    /// Tendermint Core v0.34
+    V0_34,
+
+    /// CometBFT v0.37
+    V0_37,
+
+    /// CometBFT v0.38
+    V0_38,
+
+    /// CometBFT v1.0, or any later version
+    V1,
+}
+
+/// Keys of a section of `config.toml` which are not known to this crate,
+/// along with their values
+pub type UnknownKeys = BTreeMap<String, toml::Value>;
+
 /// Database backend
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum DbBackend {
@@ -163,6 +239,10 @@ pub enum DbBackend {
     /// BadgerDB backend
     #[serde(rename = "badgerdb")]
     BadgerDb,
+
+    /// PebbleDB backend, from CometBFT v0.38 on
+    #[serde(rename = "pebbledb")]
+    PebbleDb,
 }
 
 /// Loglevel configuration
@@ -283,7 +363,7 @@ pub enum AbciMode {
 }
 
 /// Tendermint `config.toml` file's `[rpc]` section
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct RpcConfig {
     /// TCP or UNIX socket address for the RPC server to listen on
     pub laddr: net::Address,
@@ -302,6 +382,7 @@ pub struct RpcConfig {
     /// TCP or UNIX socket address for the gRPC server to listen on
     /// NOTE: This server only supports `/broadcast_tx_commit`
     #[serde(
+        default,
         deserialize_with = "deserialize_optional_value",
         serialize_with = "serialize_optional_value"
     )]
@@ -309,7 +390,9 @@ pub struct RpcConfig {
 
     /// Maximum number of simultaneous GRPC connections.
     /// Does not include RPC (HTTP&WebSocket) connections. See `max_open_connections`.
-    pub grpc_max_open_connections: u64,
+    /// This key was removed in CometBFT v1.0, along with the gRPC server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_max_open_connections: Option<u64>,
 
     /// Activate unsafe RPC commands like `/dial_seeds` and `/unsafe_flush_mempool`
     #[serde(rename = "unsafe")]
@@ -325,6 +408,21 @@ pub struct RpcConfig {
     /// Maximum number of unique queries a given client can `/subscribe` to.
     pub max_subscriptions_per_client: u64,
 
+    /// Number of events buffered for each subscription before it is dropped,
+    /// or the client is disconnected if `experimental_close_on_slow_client`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_subscription_buffer_size: Option<u64>,
+
+    /// Number of messages buffered for each WebSocket client before writing
+    /// them, which should be at least `experimental_subscription_buffer_size`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_websocket_write_buffer_size: Option<u64>,
+
+    /// Whether to disconnect the WebSocket clients which do not read their
+    /// events fast enough, rather than dropping their subscription
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_close_on_slow_client: Option<bool>,
+
     /// How long to wait for a tx to be committed during `/broadcast_tx_commit`.
     pub timeout_broadcast_tx_commit: Timeout,
 
@@ -334,6 +432,11 @@ pub struct RpcConfig {
     /// Maximum size of request header, in bytes
     pub max_header_bytes: u64,
 
+    /// Maximum number of requests in a batch request, from CometBFT v0.38 on
+    /// (0 means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_batch_size: Option<u64>,
+
     /// The name of a file containing certificate that is used to create the HTTPS server.
     #[serde(
         deserialize_with = "deserialize_optional_value",
@@ -354,6 +457,10 @@ pub struct RpcConfig {
         serialize_with = "serialize_optional_value"
     )]
     pub pprof_laddr: Option<net::Address>,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// Origin hosts allowed with CORS requests to the RPC API
@@ -408,7 +515,7 @@ impl fmt::Display for CorsHeader {
 }
 
 /// peer to peer configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct P2PConfig {
     /// Address to listen for incoming connections
     pub laddr: net::Address,
@@ -437,8 +544,9 @@ pub struct P2PConfig {
     )]
     pub persistent_peers: Vec<net::Address>,
 
-    /// UPNP port forwarding
-    pub upnp: bool,
+    /// UPNP port forwarding. This key was removed in CometBFT v0.38.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upnp: Option<bool>,
 
     /// Path to address book
     pub addr_book_file: PathBuf,
@@ -500,11 +608,24 @@ pub struct P2PConfig {
 
     /// Timeout when dialing other peers
     pub dial_timeout: Timeout,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// mempool configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct MempoolConfig {
+    /// Mempool implementation: "v0" (FIFO) or "v1" (prioritized), which
+    /// CometBFT v0.38 removed along with the prioritized mempool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Type of mempool, from CometBFT v1.0 on
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub mempool_type: Option<MempoolType>,
+
     /// Recheck enabled
     pub recheck: bool,
 
@@ -542,11 +663,40 @@ pub struct MempoolConfig {
     /// Maximum size of a batch of transactions to send to a peer
     /// Including space needed by encoding (one varint per transaction).
     /// XXX: Unused due to <https://github.com/tendermint/tendermint/issues/5796>
-    pub max_batch_bytes: u64,
+    /// This key was removed in CometBFT v0.38.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_batch_bytes: Option<u64>,
+
+    /// Maximum number of persistent peers to gossip transactions to at the
+    /// same time, from CometBFT v0.38 on (0 means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_max_gossip_connections_to_persistent_peers: Option<u64>,
+
+    /// Maximum number of non-persistent peers to gossip transactions to at
+    /// the same time, from CometBFT v0.38 on (0 means unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_max_gossip_connections_to_non_persistent_peers: Option<u64>,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
+}
+
+/// Type of mempool
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum MempoolType {
+    /// Stores transactions and gossips them to the peers
+    #[serde(rename = "flood")]
+    Flood,
+
+    /// Neither stores nor gossips transactions, which the application
+    /// handles itself
+    #[serde(rename = "nop")]
+    Nop,
 }
 
 /// consensus configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct ConsensusConfig {
     /// Path to WAL file
     pub wal_file: PathBuf,
@@ -557,17 +707,31 @@ pub struct ConsensusConfig {
     /// Propose timeout delta
     pub timeout_propose_delta: Timeout,
 
-    /// Prevote timeout
-    pub timeout_prevote: Timeout,
+    /// Prevote timeout, which CometBFT v1.0 merged into `timeout_vote`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_prevote: Option<Timeout>,
+
+    /// Prevote timeout delta, which CometBFT v1.0 merged into
+    /// `timeout_vote_delta`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_prevote_delta: Option<Timeout>,
 
-    /// Prevote timeout delta
-    pub timeout_prevote_delta: Timeout,
+    /// Precommit timeout, which CometBFT v1.0 merged into `timeout_vote`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_precommit: Option<Timeout>,
 
-    /// Precommit timeout
-    pub timeout_precommit: Timeout,
+    /// Precommit timeout delta, which CometBFT v1.0 merged into
+    /// `timeout_vote_delta`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_precommit_delta: Option<Timeout>,
 
-    /// Precommit timeout delta
-    pub timeout_precommit_delta: Timeout,
+    /// Prevote and precommit timeout, from CometBFT v1.0 on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_vote: Option<Timeout>,
+
+    /// Prevote and precommit timeout delta, from CometBFT v1.0 on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_vote_delta: Option<Timeout>,
 
     /// Commit timeout
     pub timeout_commit: Timeout,
@@ -579,8 +743,10 @@ pub struct ConsensusConfig {
     /// state machine to avoid panic.
     pub double_sign_check_height: u64,
 
-    /// Make progress as soon as we have all the precommits (as if TimeoutCommit = 0)
-    pub skip_timeout_commit: bool,
+    /// Make progress as soon as we have all the precommits (as if TimeoutCommit = 0).
+    /// This key was removed in CometBFT v1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_timeout_commit: Option<bool>,
 
     /// EmptyBlocks mode
     pub create_empty_blocks: bool,
@@ -591,23 +757,55 @@ pub struct ConsensusConfig {
     /// Reactor sleep duration
     pub peer_gossip_sleep_duration: Timeout,
 
+    /// Reactor sleep duration between the gossip of two messages to a peer,
+    /// from CometBFT v0.38 on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_gossip_intraloop_sleep_duration: Option<Timeout>,
+
     /// Reactor query sleep duration
     pub peer_query_maj23_sleep_duration: Timeout,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// Storage configuration options.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct StorageConfig {
     #[serde(default)]
     pub discard_abci_responses: bool,
+
+    /// Layout of the keys in the databases, "v1" or "v2", from CometBFT v1.0
+    /// on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental_db_key_layout: Option<String>,
+
+    /// Whether to compact the databases after pruning, from CometBFT v1.0 on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact: Option<bool>,
+
+    /// Keys and subsections, such as the `[storage.pruning]` section of
+    /// CometBFT v1.0, not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// transactions indexer configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct TxIndexConfig {
     /// What indexer to use for transactions
     #[serde(default)]
     pub indexer: TxIndexer,
+
+    /// Connection string of the PostgreSQL database of the "psql" indexer,
+    /// from CometBFT v0.37 on
+    #[serde(default, rename = "psql-conn", skip_serializing_if = "Option::is_none")]
+    pub psql_conn: Option<String>,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// What indexer to use for transactions
@@ -623,10 +821,15 @@ pub enum TxIndexer {
     #[serde(rename = "kv")]
     #[default]
     Kv,
+
+    /// "psql" - indexes transactions and blocks in a PostgreSQL database,
+    /// from CometBFT v0.37 on
+    #[serde(rename = "psql")]
+    Psql,
 }
 
 /// instrumentation configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct InstrumentationConfig {
     /// When `true`, Prometheus metrics are served under /metrics on
     /// PrometheusListenAddr.
@@ -641,10 +844,14 @@ pub struct InstrumentationConfig {
 
     /// Instrumentation namespace
     pub namespace: String,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// statesync configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct StatesyncConfig {
     /// State sync rapidly bootstraps a new node by discovering, fetching, and restoring a state
     /// machine snapshot from peers instead of fetching and replaying historical blocks.
@@ -681,15 +888,35 @@ pub struct StatesyncConfig {
     /// Temporary directory for state sync snapshot chunks, defaults to the OS tempdir (typically
     /// /tmp). Will create a new, randomly named directory within, and remove it when done.
     pub temp_dir: String,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// fastsync configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct FastsyncConfig {
     /// Fast Sync version to use:
     ///   1) "v0" (default) - the legacy fast sync implementation
     ///   2) "v2" - complete redesign of v0, optimized for testability & readability
     pub version: String,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
+}
+
+/// blocksync configuration options, which replaced the fastsync ones
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BlocksyncConfig {
+    /// Block Sync version to use, which only CometBFT v0.37 and v0.38 have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Keys not known to this version of the crate
+    #[serde(flatten)]
+    pub unknown: UnknownKeys,
 }
 
 /// Rate at which bytes can be sent/received
@@ -27,6 +27,10 @@ define_error! {
             [ DisplayOnly<toml::de::Error> ]
             |_| { format_args!("toml de error") },
 
+        TomlSer
+            [ DisplayOnly<toml::ser::Error> ]
+            |_| { format_args!("toml ser error") },
+
         ParseUrl
             [ DisplayOnly<url::ParseError> ]
             |_| { format_args!("error parsing url error") },
@@ -2,7 +2,7 @@
 //!
 //! Test config files are located in the `tests/support/config` subdirectory.
 
-use std::{fs, path::PathBuf, time::Duration};
+use std::{collections::BTreeSet, fs, path::PathBuf, time::Duration};
 
 #[cfg(test)]
 use pretty_assertions::assert_eq;
@@ -28,7 +28,9 @@ fn config_toml_parser() {
         "tcp://127.0.0.1:26658".parse::<net::Address>().unwrap()
     );
     assert_eq!(config.moniker.as_ref(), "technodrome");
-    assert!(config.fast_sync);
+    assert_eq!(config.fast_sync, Some(true));
+    assert!(config.block_sync_enabled());
+    assert_eq!(config.config_version(), ConfigVersion::V0_34);
     assert_eq!(config.db_backend, DbBackend::GoLevelDb);
     assert_eq!(config.db_dir, PathBuf::from("data"));
     assert_eq!(config.log_level.global, Some("info".to_string()));
@@ -69,7 +71,7 @@ fn config_toml_parser() {
     assert_eq!(rpc.cors_allowed_headers[3].as_ref(), "X-Requested-With");
     assert_eq!(rpc.cors_allowed_headers[4].as_ref(), "X-Server-Time");
     assert_eq!(rpc.grpc_laddr, None);
-    assert_eq!(rpc.grpc_max_open_connections, 900);
+    assert_eq!(rpc.grpc_max_open_connections, Some(900));
     assert!(!rpc.unsafe_commands);
     assert_eq!(rpc.max_open_connections, 900);
     assert_eq!(rpc.max_subscription_clients, 100);
@@ -112,7 +114,7 @@ fn config_toml_parser() {
             .parse::<net::Address>()
             .unwrap()
     );
-    assert!(!p2p.upnp);
+    assert_eq!(p2p.upnp, Some(false));
     assert_eq!(p2p.addr_book_file, PathBuf::from("config/addrbook.json"));
     assert!(p2p.addr_book_strict);
     assert_eq!(p2p.max_num_inbound_peers, 40);
@@ -162,15 +164,24 @@ fn config_toml_parser() {
     assert_eq!(consensus.wal_file, PathBuf::from("data/cs.wal/wal"));
     assert_eq!(*consensus.timeout_propose, Duration::from_secs(3));
     assert_eq!(*consensus.timeout_propose_delta, Duration::from_millis(500));
-    assert_eq!(*consensus.timeout_prevote, Duration::from_secs(1));
-    assert_eq!(*consensus.timeout_prevote_delta, Duration::from_millis(500));
-    assert_eq!(*consensus.timeout_precommit, Duration::from_secs(1));
     assert_eq!(
-        *consensus.timeout_precommit_delta,
-        Duration::from_millis(500)
+        consensus.timeout_prevote.map(Duration::from),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        consensus.timeout_prevote_delta.map(Duration::from),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(
+        consensus.timeout_precommit.map(Duration::from),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        consensus.timeout_precommit_delta.map(Duration::from),
+        Some(Duration::from_millis(500))
     );
     assert_eq!(*consensus.timeout_commit, Duration::from_secs(5));
-    assert!(!consensus.skip_timeout_commit);
+    assert_eq!(consensus.skip_timeout_commit, Some(false));
     assert_eq!(
         *consensus.create_empty_blocks_interval,
         Duration::from_secs(0)
@@ -240,3 +251,174 @@ fn parsing_roundtrip() {
         written_config_toml
     );
 }
+
+/// Parse an example CometBFT v0.38 `config.toml` file to a `TendermintConfig`
+/// struct
+#[test]
+fn config_toml_parser_v0_38() {
+    let config_toml = read_fixture("config-v0.38.toml");
+    let config = TendermintConfig::parse_toml(config_toml).unwrap();
+
+    assert_eq!(config.version.as_deref(), Some("0.38.6"));
+    assert_eq!(config.config_version(), ConfigVersion::V0_38);
+    assert_eq!(config.fast_sync, None);
+    assert_eq!(config.block_sync, None);
+    assert!(config.block_sync_enabled());
+    assert_eq!(config.db_backend, DbBackend::PebbleDb);
+    assert_eq!(config.fastsync, None);
+    assert_eq!(
+        config.blocksync.as_ref().unwrap().version.as_deref(),
+        Some("v0")
+    );
+
+    let rpc = &config.rpc;
+    assert_eq!(rpc.experimental_subscription_buffer_size, Some(200));
+    assert_eq!(rpc.experimental_websocket_write_buffer_size, Some(200));
+    assert_eq!(rpc.experimental_close_on_slow_client, Some(false));
+    assert_eq!(rpc.max_request_batch_size, Some(10));
+    assert_eq!(
+        rpc.pprof_laddr,
+        Some("tcp://localhost:6060".parse::<net::Address>().unwrap())
+    );
+
+    assert_eq!(config.p2p.upnp, None);
+
+    let mempool = &config.mempool;
+    assert_eq!(mempool.version, None);
+    assert_eq!(mempool.max_batch_bytes, None);
+    assert_eq!(
+        mempool.experimental_max_gossip_connections_to_persistent_peers,
+        Some(0)
+    );
+    assert_eq!(
+        mempool.experimental_max_gossip_connections_to_non_persistent_peers,
+        Some(0)
+    );
+
+    let consensus = &config.consensus;
+    assert_eq!(
+        consensus.timeout_prevote.map(Duration::from),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(consensus.timeout_vote, None);
+    assert_eq!(
+        consensus.peer_gossip_intraloop_sleep_duration,
+        Some(Duration::from_secs(0).into())
+    );
+
+    assert_eq!(config.tx_index.psql_conn.as_deref(), Some(""));
+
+    // keys which are not known to this crate
+    let statesync = &config.statesync;
+    assert_eq!(
+        statesync.unknown.get("chunk_request_timeout"),
+        Some(&toml::Value::String("10s".to_string()))
+    );
+    assert_eq!(
+        statesync.unknown.get("chunk_fetchers"),
+        Some(&toml::Value::String("4".to_string()))
+    );
+    assert!(config.unknown.is_empty());
+    assert!(config.consensus.unknown.is_empty());
+}
+
+/// Parse an example CometBFT v1.0 `config.toml` file to a `TendermintConfig`
+/// struct
+#[test]
+fn config_toml_parser_v1_0() {
+    let config_toml = read_fixture("config-v1.0.toml");
+    let config = TendermintConfig::parse_toml(config_toml).unwrap();
+
+    assert_eq!(config.config_version(), ConfigVersion::V1);
+    assert!(config.block_sync_enabled());
+    assert_eq!(config.fastsync, None);
+    assert_eq!(config.blocksync, None);
+
+    let rpc = &config.rpc;
+    assert_eq!(rpc.grpc_laddr, None);
+    assert_eq!(rpc.grpc_max_open_connections, None);
+
+    let mempool = &config.mempool;
+    assert_eq!(mempool.mempool_type, Some(MempoolType::Flood));
+    assert_eq!(
+        mempool.unknown.get("recheck_timeout"),
+        Some(&toml::Value::String("1s".to_string()))
+    );
+
+    let consensus = &config.consensus;
+    assert_eq!(consensus.timeout_prevote, None);
+    assert_eq!(consensus.timeout_precommit_delta, None);
+    assert_eq!(
+        consensus.timeout_vote.map(Duration::from),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        consensus.timeout_vote_delta.map(Duration::from),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(consensus.skip_timeout_commit, None);
+
+    let storage = &config.storage;
+    assert_eq!(storage.experimental_db_key_layout.as_deref(), Some("v1"));
+    assert_eq!(storage.compact, Some(false));
+    assert_eq!(
+        storage.unknown["pruning"]["data_companion"]["enabled"],
+        toml::Value::Boolean(false)
+    );
+
+    let tx_index = &config.tx_index;
+    assert_eq!(tx_index.indexer, TxIndexer::Psql);
+    assert!(tx_index
+        .psql_conn
+        .as_ref()
+        .unwrap()
+        .starts_with("postgresql://"));
+
+    // the `[grpc]` section, which is not known to this crate
+    let grpc = &config.unknown["grpc"];
+    assert_eq!(grpc["laddr"].as_str(), Some("tcp://127.0.0.1:26090"));
+    assert_eq!(
+        grpc["privileged"]["pruning_service"]["enabled"].as_bool(),
+        Some(false)
+    );
+}
+
+/// Collect the dotted paths of all the keys of a TOML document
+fn key_paths(value: &toml::Value, prefix: &str, paths: &mut BTreeSet<String>) {
+    if let toml::Value::Table(table) = value {
+        for (key, value) in table {
+            let path = format!("{prefix}{key}");
+            key_paths(value, &format!("{path}."), paths);
+            paths.insert(path);
+        }
+    }
+}
+
+/// Parse the `config.toml` files of every version, serialize them with
+/// `to_toml` and parse them again, checking that no key is lost.
+#[test]
+fn parsing_roundtrip_all_versions() {
+    for name in ["config.toml", "config-v0.38.toml", "config-v1.0.toml"] {
+        let config_toml = read_fixture(name);
+        let config = TendermintConfig::parse_toml(&config_toml).unwrap();
+
+        let written_config_toml = config.to_toml().unwrap();
+        let written_config = TendermintConfig::parse_toml(&written_config_toml).unwrap();
+        assert_eq!(
+            config, written_config,
+            "written config {}",
+            written_config_toml
+        );
+
+        let mut keys = BTreeSet::new();
+        key_paths(&toml::from_str(&config_toml).unwrap(), "", &mut keys);
+        let mut written_keys = BTreeSet::new();
+        key_paths(
+            &toml::from_str(&written_config_toml).unwrap(),
+            "",
+            &mut written_keys,
+        );
+        let lost: Vec<_> = keys.difference(&written_keys).collect();
+        assert!(lost.is_empty(), "keys of {name} lost: {lost:?}");
+    }
+}
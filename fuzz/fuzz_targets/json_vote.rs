@@ -0,0 +1,16 @@
+//! Deserializes votes from JSON, checking that the valid ones round-trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tendermint::vote;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = serde_json::from_slice::<vote::Vote>(data) else {
+        return;
+    };
+    let encoded = serde_json::to_vec(&value).expect("valid value must serialize");
+    let decoded =
+        serde_json::from_slice::<vote::Vote>(&encoded).expect("serialized value must parse");
+    assert_eq!(decoded, value);
+});
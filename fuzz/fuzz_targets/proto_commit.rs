@@ -0,0 +1,20 @@
+//! Decodes commits from protobuf, checking that the valid ones round-trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+use tendermint::block::Commit;
+use tendermint_proto::v0_37::types::Commit as Raw;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = Raw::decode(data) else {
+        return;
+    };
+    let Ok(value) = Commit::try_from(raw) else {
+        return;
+    };
+    let encoded = Raw::from(value.clone()).encode_to_vec();
+    let decoded = Raw::decode(encoded.as_slice()).expect("re-encoded value must decode");
+    assert_eq!(Commit::try_from(decoded).ok(), Some(value));
+});
@@ -0,0 +1,20 @@
+//! Decodes votes from protobuf, checking that the valid ones round-trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+use tendermint::vote::Vote;
+use tendermint_proto::v0_37::types::Vote as Raw;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = Raw::decode(data) else {
+        return;
+    };
+    let Ok(value) = Vote::try_from(raw) else {
+        return;
+    };
+    let encoded = Raw::from(value.clone()).encode_to_vec();
+    let decoded = Raw::decode(encoded.as_slice()).expect("re-encoded value must decode");
+    assert_eq!(Vote::try_from(decoded).ok(), Some(value));
+});
@@ -0,0 +1,41 @@
+//! Verifies a block against a trusted one, as the light client does, with
+//! both blocks and the current time taken from the input.
+
+#![no_main]
+
+use core::time::Duration;
+
+use libfuzzer_sys::fuzz_target;
+use tendermint_light_client_verifier::{
+    options::Options, types::LightBlock, ProdVerifier, Verifier,
+};
+use tendermint_testgen::{corpus::VerifierInput, light_block::TmLightBlock};
+
+fn light_block(block: TmLightBlock) -> LightBlock {
+    LightBlock::new(
+        block.signed_header,
+        block.validators,
+        block.next_validators,
+        block.provider,
+    )
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = serde_json::from_slice::<VerifierInput>(data) else {
+        return;
+    };
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(14 * 24 * 60 * 60),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+    let trusted = light_block(input.trusted);
+    let untrusted = light_block(input.untrusted);
+    let _ = ProdVerifier::default().verify(
+        untrusted.as_untrusted_state(),
+        trusted.as_trusted_state(),
+        &options,
+        input.now,
+    );
+});
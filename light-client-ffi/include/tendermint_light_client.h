@@ -0,0 +1,139 @@
+#ifndef TENDERMINT_LIGHT_CLIENT_H
+#define TENDERMINT_LIGHT_CLIENT_H
+
+/* Generated with cbindgen:0.24.3 */
+
+/* Warning, this file is autogenerated by cbindgen. Don't modify this manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a verification.
+ */
+typedef enum TmStatus {
+  /**
+   * The header is verified, or the misbehaviour is proven.
+   */
+  TM_STATUS_SUCCESS = 0,
+  /**
+   * The header is valid, but not signed by enough of the trusted
+   * validators: an intermediate header must be verified first.
+   */
+  TM_STATUS_NOT_ENOUGH_TRUST = 1,
+  /**
+   * The header is invalid, or the headers do not prove a misbehaviour.
+   */
+  TM_STATUS_INVALID = 2,
+  /**
+   * An input could not be decoded.
+   */
+  TM_STATUS_DECODE_ERROR = 3,
+  /**
+   * A pointer is null, or the options are invalid.
+   */
+  TM_STATUS_INVALID_ARGUMENT = 4,
+  /**
+   * The verification panicked, which is a bug of the verifier.
+   */
+  TM_STATUS_PANIC = 5,
+} TmStatus;
+
+/**
+ * The verification options.
+ */
+typedef struct TmOptions {
+  /**
+   * Numerator of the fraction of the trusted voting power which must sign
+   * a header, usually 1.
+   */
+  uint64_t trust_threshold_numerator;
+  /**
+   * Denominator of the trust threshold, usually 3.
+   */
+  uint64_t trust_threshold_denominator;
+  /**
+   * How long a header is trusted after its time, in seconds.
+   */
+  uint64_t trusting_period_secs;
+  /**
+   * How far ahead of the current time a header may be, in seconds.
+   */
+  uint64_t clock_drift_secs;
+} TmOptions;
+
+/**
+ * A point in time, as seconds and nanoseconds since the Unix epoch.
+ */
+typedef struct TmTimestamp {
+  int64_t seconds;
+  /**
+   * Between 0 and 999,999,999.
+   */
+  uint32_t nanos;
+} TmTimestamp;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Verifies a header against the trusted state, at the given time.
+ *
+ * `trusted_state` holds a protobuf-encoded `TrustedState` message, and
+ * `header` a protobuf-encoded `Header` message, which may skip heights
+ * after the trusted one.
+ *
+ * Unless the header is verified, a NUL-terminated description of the
+ * failure is written into `error`, truncated to `error_len` bytes.
+ *
+ * # Safety
+ *
+ * `trusted_state` and `header` must point to `trusted_state_len` and
+ * `header_len` readable bytes, `options` to a valid `TmOptions`, and `error`,
+ * unless null, to `error_len` writable bytes.
+ */
+TmStatus tm_verify_update_header(const uint8_t *trusted_state,
+                                 size_t trusted_state_len,
+                                 const uint8_t *header,
+                                 size_t header_len,
+                                 const TmOptions *options,
+                                 TmTimestamp now,
+                                 char *error,
+                                 size_t error_len);
+
+/**
+ * Verifies that two conflicting headers were both signed by enough of the
+ * trusted validators, at the given time.
+ *
+ * `trusted_state` holds a protobuf-encoded `TrustedState` message, and
+ * `misbehaviour` a protobuf-encoded `Misbehaviour` message. The headers
+ * conflict if they are different headers at the same height, or if the
+ * higher one is not later than the other one.
+ *
+ * Unless the misbehaviour is proven, a NUL-terminated description of the
+ * failure is written into `error`, truncated to `error_len` bytes.
+ *
+ * # Safety
+ *
+ * `trusted_state` and `misbehaviour` must point to `trusted_state_len` and
+ * `misbehaviour_len` readable bytes, `options` to a valid `TmOptions`, and
+ * `error`, unless null, to `error_len` writable bytes.
+ */
+TmStatus tm_verify_misbehaviour(const uint8_t *trusted_state,
+                                size_t trusted_state_len,
+                                const uint8_t *misbehaviour,
+                                size_t misbehaviour_len,
+                                const TmOptions *options,
+                                TmTimestamp now,
+                                char *error,
+                                size_t error_len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* TENDERMINT_LIGHT_CLIENT_H */
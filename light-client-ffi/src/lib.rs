@@ -0,0 +1,368 @@
+//! C bindings for the Tendermint light client verifier, for embedding it in
+//! applications written in other languages, like Go, Swift or Kotlin.
+//!
+//! The trusted state and the headers are passed as protobuf-encoded messages,
+//! described in `proto/light_client_ffi.proto`, which embed the protobuf types
+//! of Tendermint. The C header `include/tendermint_light_client.h` is
+//! generated from this crate with cbindgen.
+//!
+//! The verification is the one of the Rust light client: every function
+//! returns a [`TmStatus`], and writes a description of the failure, if any,
+//! into a buffer provided by the caller.
+
+use core::{convert::TryFrom, slice, time::Duration};
+use std::{
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+};
+
+use prost::Message;
+use tendermint::Time;
+use tendermint_light_client_verifier::{
+    options::Options, types::TrustThreshold, ProdVerifier, Verdict, Verifier,
+};
+
+pub mod messages;
+
+use messages::{Header, Misbehaviour, Trusted, TrustedState, Untrusted};
+
+/// The outcome of a verification.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TmStatus {
+    /// The header is verified, or the misbehaviour is proven.
+    Success = 0,
+    /// The header is valid, but not signed by enough of the trusted
+    /// validators: an intermediate header must be verified first.
+    NotEnoughTrust = 1,
+    /// The header is invalid, or the headers do not prove a misbehaviour.
+    Invalid = 2,
+    /// An input could not be decoded.
+    DecodeError = 3,
+    /// A pointer is null, or the options are invalid.
+    InvalidArgument = 4,
+    /// The verification panicked, which is a bug of the verifier.
+    Panic = 5,
+}
+
+/// The verification options.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TmOptions {
+    /// Numerator of the fraction of the trusted voting power which must sign
+    /// a header, usually 1.
+    pub trust_threshold_numerator: u64,
+    /// Denominator of the trust threshold, usually 3.
+    pub trust_threshold_denominator: u64,
+    /// How long a header is trusted after its time, in seconds.
+    pub trusting_period_secs: u64,
+    /// How far ahead of the current time a header may be, in seconds.
+    pub clock_drift_secs: u64,
+}
+
+impl TryFrom<TmOptions> for Options {
+    type Error = String;
+
+    fn try_from(options: TmOptions) -> Result<Self, Self::Error> {
+        let trust_threshold = TrustThreshold::new(
+            options.trust_threshold_numerator,
+            options.trust_threshold_denominator,
+        )
+        .map_err(|e| format!("invalid trust threshold: {e}"))?;
+        Ok(Options {
+            trust_threshold,
+            trusting_period: Duration::from_secs(options.trusting_period_secs),
+            clock_drift: Duration::from_secs(options.clock_drift_secs),
+            time_monotonicity: Default::default(),
+        })
+    }
+}
+
+/// A point in time, as seconds and nanoseconds since the Unix epoch.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TmTimestamp {
+    pub seconds: i64,
+    /// Between 0 and 999,999,999.
+    pub nanos: u32,
+}
+
+/// Verifies a header against the trusted state, at the given time.
+///
+/// `trusted_state` holds a protobuf-encoded `TrustedState` message, and
+/// `header` a protobuf-encoded `Header` message, which may skip heights
+/// after the trusted one.
+///
+/// Unless the header is verified, a NUL-terminated description of the
+/// failure is written into `error`, truncated to `error_len` bytes.
+///
+/// # Safety
+///
+/// `trusted_state` and `header` must point to `trusted_state_len` and
+/// `header_len` readable bytes, `options` to a valid `TmOptions`, and `error`,
+/// unless null, to `error_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn tm_verify_update_header(
+    trusted_state: *const u8,
+    trusted_state_len: usize,
+    header: *const u8,
+    header_len: usize,
+    options: *const TmOptions,
+    now: TmTimestamp,
+    error: *mut c_char,
+    error_len: usize,
+) -> TmStatus {
+    run(error, error_len, || {
+        let trusted = decode_trusted(bytes(trusted_state, trusted_state_len, "trusted_state")?)?;
+        let untrusted = decode_header(bytes(header, header_len, "header")?, "header")?;
+        let options = read_options(options)?;
+        let now = read_time(now)?;
+        verify_update_header(&trusted, &untrusted, &options, now)
+    })
+}
+
+/// Verifies that two conflicting headers were both signed by enough of the
+/// trusted validators, at the given time.
+///
+/// `trusted_state` holds a protobuf-encoded `TrustedState` message, and
+/// `misbehaviour` a protobuf-encoded `Misbehaviour` message. The headers
+/// conflict if they are different headers at the same height, or if the
+/// higher one is not later than the other one.
+///
+/// Unless the misbehaviour is proven, a NUL-terminated description of the
+/// failure is written into `error`, truncated to `error_len` bytes.
+///
+/// # Safety
+///
+/// `trusted_state` and `misbehaviour` must point to `trusted_state_len` and
+/// `misbehaviour_len` readable bytes, `options` to a valid `TmOptions`, and
+/// `error`, unless null, to `error_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn tm_verify_misbehaviour(
+    trusted_state: *const u8,
+    trusted_state_len: usize,
+    misbehaviour: *const u8,
+    misbehaviour_len: usize,
+    options: *const TmOptions,
+    now: TmTimestamp,
+    error: *mut c_char,
+    error_len: usize,
+) -> TmStatus {
+    run(error, error_len, || {
+        let trusted = decode_trusted(bytes(trusted_state, trusted_state_len, "trusted_state")?)?;
+        let misbehaviour =
+            Misbehaviour::decode(bytes(misbehaviour, misbehaviour_len, "misbehaviour")?)
+                .map_err(|e| Failure::decode(format!("failed to decode misbehaviour: {e}")))?;
+        let header_1 = convert_header(misbehaviour.header_1, "header_1")?;
+        let header_2 = convert_header(misbehaviour.header_2, "header_2")?;
+        let options = read_options(options)?;
+        let now = read_time(now)?;
+        verify_misbehaviour(&trusted, &header_1, &header_2, &options, now)
+    })
+}
+
+/// A failed verification, with the description of the failure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Failure {
+    status: TmStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: TmStatus, message: String) -> Self {
+        Failure { status, message }
+    }
+
+    fn decode(message: String) -> Self {
+        Self::new(TmStatus::DecodeError, message)
+    }
+
+    fn invalid_argument(message: String) -> Self {
+        Self::new(TmStatus::InvalidArgument, message)
+    }
+
+    fn invalid(message: String) -> Self {
+        Self::new(TmStatus::Invalid, message)
+    }
+}
+
+fn verify_update_header(
+    trusted: &Trusted,
+    untrusted: &Untrusted,
+    options: &Options,
+    now: Time,
+) -> Result<(), Failure> {
+    let verdict = ProdVerifier::default().verify(
+        untrusted.as_untrusted_state(),
+        trusted.as_trusted_state(),
+        options,
+        now,
+    );
+    match verdict {
+        Verdict::Success | Verdict::SuccessWithTimeRegression(_) => Ok(()),
+        Verdict::NotEnoughTrust(tally) => Err(Failure::new(
+            TmStatus::NotEnoughTrust,
+            format!("not enough trust: {tally}"),
+        )),
+        Verdict::Invalid(detail) => Err(Failure::invalid(detail.to_string())),
+    }
+}
+
+fn verify_misbehaviour(
+    trusted: &Trusted,
+    header_1: &Untrusted,
+    header_2: &Untrusted,
+    options: &Options,
+    now: Time,
+) -> Result<(), Failure> {
+    let (h1, h2) = (
+        &header_1.signed_header.header,
+        &header_2.signed_header.header,
+    );
+    if h1.chain_id != h2.chain_id {
+        return Err(Failure::invalid(format!(
+            "headers of different chains: {} and {}",
+            h1.chain_id, h2.chain_id
+        )));
+    }
+    let conflicting = if h1.height == h2.height {
+        h1.hash() != h2.hash()
+    } else {
+        // a higher header must have a later time, unless it was forged
+        let (higher, lower) = if h1.height > h2.height {
+            (h1, h2)
+        } else {
+            (h2, h1)
+        };
+        higher.time <= lower.time
+    };
+    if !conflicting {
+        return Err(Failure::invalid("the headers do not conflict".to_string()));
+    }
+
+    verify_update_header(trusted, header_1, options, now)
+        .map_err(|f| Failure::new(f.status, format!("header_1: {}", f.message)))?;
+    verify_update_header(trusted, header_2, options, now)
+        .map_err(|f| Failure::new(f.status, format!("header_2: {}", f.message)))
+}
+
+fn decode_trusted(bytes: &[u8]) -> Result<Trusted, Failure> {
+    let raw = TrustedState::decode(bytes)
+        .map_err(|e| Failure::decode(format!("failed to decode trusted_state: {e}")))?;
+    Trusted::try_from(raw).map_err(|e| Failure::decode(format!("trusted_state: {e}")))
+}
+
+fn decode_header(bytes: &[u8], name: &str) -> Result<Untrusted, Failure> {
+    let raw = Header::decode(bytes)
+        .map_err(|e| Failure::decode(format!("failed to decode {name}: {e}")))?;
+    convert_header(Some(raw), name)
+}
+
+fn convert_header(raw: Option<Header>, name: &str) -> Result<Untrusted, Failure> {
+    let raw = raw.ok_or_else(|| Failure::decode(format!("missing field: {name}")))?;
+    Untrusted::try_from(raw).map_err(|e| Failure::decode(format!("{name}: {e}")))
+}
+
+/// # Safety
+///
+/// Unless null, `ptr` must point to `len` readable bytes.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize, name: &str) -> Result<&'a [u8], Failure> {
+    if ptr.is_null() {
+        if len == 0 {
+            return Ok(&[]);
+        }
+        return Err(Failure::invalid_argument(format!("{name} is null")));
+    }
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+/// # Safety
+///
+/// Unless null, `options` must point to a valid `TmOptions`.
+unsafe fn read_options(options: *const TmOptions) -> Result<Options, Failure> {
+    let options = options
+        .as_ref()
+        .ok_or_else(|| Failure::invalid_argument("options is null".to_string()))?;
+    Options::try_from(*options).map_err(Failure::invalid_argument)
+}
+
+fn read_time(now: TmTimestamp) -> Result<Time, Failure> {
+    Time::from_unix_timestamp(now.seconds, now.nanos)
+        .map_err(|e| Failure::invalid_argument(format!("invalid time: {e}")))
+}
+
+/// Runs a verification, turning panics into [`TmStatus::Panic`], and writes
+/// the description of its failure into `error`.
+///
+/// # Safety
+///
+/// Unless null, `error` must point to `error_len` writable bytes.
+unsafe fn run<F>(error: *mut c_char, error_len: usize, f: F) -> TmStatus
+where
+    F: FnOnce() -> Result<(), Failure>,
+{
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| {
+        Err(Failure::new(
+            TmStatus::Panic,
+            "the verification panicked".to_string(),
+        ))
+    });
+    match result {
+        Ok(()) => TmStatus::Success,
+        Err(failure) => {
+            write_error(error, error_len, &failure.message);
+            failure.status
+        },
+    }
+}
+
+/// Writes `message` into `error` as a NUL-terminated string, truncated on a
+/// character boundary to fit `error_len` bytes.
+///
+/// # Safety
+///
+/// Unless null, `error` must point to `error_len` writable bytes.
+unsafe fn write_error(error: *mut c_char, error_len: usize, message: &str) {
+    if error.is_null() || error_len == 0 {
+        return;
+    }
+    let mut len = message.len().min(error_len - 1);
+    while !message.is_char_boundary(len) {
+        len -= 1;
+    }
+    let error = slice::from_raw_parts_mut(error as *mut u8, error_len);
+    error[..len].copy_from_slice(&message.as_bytes()[..len]);
+    error[len] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_error() {
+        let mut buf = [0x7f as c_char; 8];
+        unsafe { write_error(buf.as_mut_ptr(), buf.len(), "héllo wörld") };
+        // the "é" takes two bytes, so that only "héllo " fits before the NUL
+        let written: Vec<u8> = buf.iter().map(|&c| c as u8).collect();
+        assert_eq!(&written, b"h\xc3\xa9llo \0");
+
+        let mut buf = [0x7f as c_char; 2];
+        unsafe { write_error(buf.as_mut_ptr(), buf.len(), "é") };
+        assert_eq!(buf[0], 0);
+
+        unsafe { write_error(core::ptr::null_mut(), 8, "ignored") };
+    }
+
+    #[test]
+    fn test_options() {
+        let options = TmOptions {
+            trust_threshold_numerator: 2,
+            trust_threshold_denominator: 1,
+            trusting_period_secs: 3600,
+            clock_drift_secs: 10,
+        };
+        assert!(Options::try_from(options).is_err());
+        assert!(unsafe { read_options(core::ptr::null()) }.is_err());
+    }
+}
@@ -0,0 +1,115 @@
+//! The messages passed through the C ABI, encoded in protobuf, as described in
+//! `proto/light_client_ffi.proto`.
+
+use core::convert::TryFrom;
+
+use tendermint::{block, block::signed_header::SignedHeader, validator};
+use tendermint_light_client_verifier::types::{TrustedBlockState, UntrustedBlockState};
+use tendermint_proto::v0_37::types::{
+    Header as RawHeader, SignedHeader as RawSignedHeader, ValidatorSet as RawValidatorSet,
+};
+
+/// The trusted state of the light client: the header of the latest trusted
+/// block, and the validators of the block following it.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TrustedState {
+    #[prost(message, optional, tag = "1")]
+    pub header: Option<RawHeader>,
+    #[prost(message, optional, tag = "2")]
+    pub next_validators: Option<RawValidatorSet>,
+}
+
+/// A header to verify against the trusted state, along with the validators
+/// which signed it.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Header {
+    #[prost(message, optional, tag = "1")]
+    pub signed_header: Option<RawSignedHeader>,
+    #[prost(message, optional, tag = "2")]
+    pub validator_set: Option<RawValidatorSet>,
+    /// Validators of the next block, checked against the header if present
+    #[prost(message, optional, tag = "3")]
+    pub next_validator_set: Option<RawValidatorSet>,
+}
+
+/// Two conflicting headers, both signed by enough of the trusted validators.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Misbehaviour {
+    #[prost(message, optional, tag = "1")]
+    pub header_1: Option<Header>,
+    #[prost(message, optional, tag = "2")]
+    pub header_2: Option<Header>,
+}
+
+/// A decoded [`TrustedState`].
+#[derive(Clone, Debug)]
+pub struct Trusted {
+    pub header: block::Header,
+    pub next_validators: validator::Set,
+}
+
+impl Trusted {
+    pub fn as_trusted_state(&self) -> TrustedBlockState<'_> {
+        TrustedBlockState {
+            chain_id: &self.header.chain_id,
+            header_time: self.header.time,
+            height: self.header.height,
+            next_validators: &self.next_validators,
+            next_validators_hash: self.header.next_validators_hash,
+        }
+    }
+}
+
+impl TryFrom<TrustedState> for Trusted {
+    type Error = String;
+
+    fn try_from(value: TrustedState) -> Result<Self, Self::Error> {
+        Ok(Trusted {
+            header: convert(value.header, "header")?,
+            next_validators: convert(value.next_validators, "next_validators")?,
+        })
+    }
+}
+
+/// A decoded [`Header`].
+#[derive(Clone, Debug)]
+pub struct Untrusted {
+    pub signed_header: SignedHeader,
+    pub validators: validator::Set,
+    pub next_validators: Option<validator::Set>,
+}
+
+impl Untrusted {
+    pub fn as_untrusted_state(&self) -> UntrustedBlockState<'_> {
+        UntrustedBlockState {
+            signed_header: &self.signed_header,
+            validators: &self.validators,
+            next_validators: self.next_validators.as_ref(),
+        }
+    }
+}
+
+impl TryFrom<Header> for Untrusted {
+    type Error = String;
+
+    fn try_from(value: Header) -> Result<Self, Self::Error> {
+        Ok(Untrusted {
+            signed_header: convert(value.signed_header, "signed_header")?,
+            validators: convert(value.validator_set, "validator_set")?,
+            next_validators: value
+                .next_validator_set
+                .map(|raw| convert(Some(raw), "next_validator_set"))
+                .transpose()?,
+        })
+    }
+}
+
+/// Converts a required field into its domain type.
+fn convert<Raw, T>(raw: Option<Raw>, field: &str) -> Result<T, String>
+where
+    T: TryFrom<Raw>,
+    T::Error: core::fmt::Display,
+{
+    let raw = raw.ok_or_else(|| format!("missing field: {field}"))?;
+    T::try_from(raw).map_err(|e| format!("invalid field {field}: {e}"))
+}
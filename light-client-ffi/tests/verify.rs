@@ -0,0 +1,204 @@
+//! Calls the C ABI with light blocks generated by testgen.
+
+use std::{ffi::CStr, os::raw::c_char};
+
+use prost::Message;
+use tendermint_light_client_ffi::{
+    messages::{Header, Misbehaviour, TrustedState},
+    tm_verify_misbehaviour, tm_verify_update_header, TmOptions, TmStatus, TmTimestamp,
+};
+use tendermint_testgen::{light_block::TmLightBlock, Generator, LightChain};
+
+const OPTIONS: TmOptions = TmOptions {
+    trust_threshold_numerator: 1,
+    trust_threshold_denominator: 3,
+    trusting_period_secs: 3600,
+    clock_drift_secs: 10,
+};
+
+fn light_blocks(chain: &LightChain) -> Vec<TmLightBlock> {
+    chain
+        .light_blocks
+        .iter()
+        .map(|lb| lb.generate().unwrap())
+        .collect()
+}
+
+fn trusted_state(block: &TmLightBlock) -> Vec<u8> {
+    TrustedState {
+        header: Some(block.signed_header.header.clone().into()),
+        next_validators: Some(block.next_validators.clone().into()),
+    }
+    .encode_to_vec()
+}
+
+fn header(block: &TmLightBlock) -> Header {
+    Header {
+        signed_header: Some(block.signed_header.clone().into()),
+        validator_set: Some(block.validators.clone().into()),
+        next_validator_set: Some(block.next_validators.clone().into()),
+    }
+}
+
+fn after(block: &TmLightBlock) -> TmTimestamp {
+    TmTimestamp {
+        seconds: block.signed_header.header.time.unix_timestamp() + 1,
+        nanos: 0,
+    }
+}
+
+fn update_header(trusted: &[u8], header: &[u8], now: TmTimestamp) -> (TmStatus, String) {
+    let mut error = [0 as c_char; 256];
+    let status = unsafe {
+        tm_verify_update_header(
+            trusted.as_ptr(),
+            trusted.len(),
+            header.as_ptr(),
+            header.len(),
+            &OPTIONS,
+            now,
+            error.as_mut_ptr(),
+            error.len(),
+        )
+    };
+    let error = unsafe { CStr::from_ptr(error.as_ptr()) };
+    (status, error.to_string_lossy().into_owned())
+}
+
+fn misbehaviour(
+    trusted: &[u8],
+    misbehaviour: &Misbehaviour,
+    now: TmTimestamp,
+) -> (TmStatus, String) {
+    let misbehaviour = misbehaviour.encode_to_vec();
+    let mut error = [0 as c_char; 256];
+    let status = unsafe {
+        tm_verify_misbehaviour(
+            trusted.as_ptr(),
+            trusted.len(),
+            misbehaviour.as_ptr(),
+            misbehaviour.len(),
+            &OPTIONS,
+            now,
+            error.as_mut_ptr(),
+            error.len(),
+        )
+    };
+    let error = unsafe { CStr::from_ptr(error.as_ptr()) };
+    (status, error.to_string_lossy().into_owned())
+}
+
+#[test]
+fn verify_update_header() {
+    let blocks = light_blocks(&LightChain::default_with_length(3));
+    let trusted = trusted_state(&blocks[0]);
+
+    for block in &blocks[1..] {
+        let encoded = header(block).encode_to_vec();
+        assert_eq!(
+            update_header(&trusted, &encoded, after(block)),
+            (TmStatus::Success, String::new())
+        );
+    }
+
+    let encoded = header(&blocks[2]).encode_to_vec();
+    let expired = TmTimestamp {
+        seconds: after(&blocks[2]).seconds + 3600,
+        nanos: 0,
+    };
+    let (status, error) = update_header(&trusted, &encoded, expired);
+    assert_eq!(status, TmStatus::Invalid);
+    assert!(!error.is_empty());
+
+    let mut forged = blocks[2].clone();
+    forged.signed_header.commit = blocks[1].signed_header.commit.clone();
+    let encoded = header(&forged).encode_to_vec();
+    let (status, _) = update_header(&trusted, &encoded, after(&forged));
+    assert_eq!(status, TmStatus::Invalid);
+}
+
+#[test]
+fn verify_update_header_bad_input() {
+    let blocks = light_blocks(&LightChain::default_with_length(2));
+    let trusted = trusted_state(&blocks[0]);
+    let encoded = header(&blocks[1]).encode_to_vec();
+
+    let (status, error) = update_header(&trusted, &[0xff, 0xff], after(&blocks[1]));
+    assert_eq!(status, TmStatus::DecodeError);
+    assert!(error.starts_with("failed to decode header"));
+
+    let (status, error) = update_header(&trusted, &[], after(&blocks[1]));
+    assert_eq!(status, TmStatus::DecodeError);
+    assert_eq!(error, "header: missing field: signed_header");
+
+    let invalid_time = TmTimestamp {
+        seconds: 0,
+        nanos: 1_000_000_000,
+    };
+    let (status, _) = update_header(&trusted, &encoded, invalid_time);
+    assert_eq!(status, TmStatus::InvalidArgument);
+
+    let status = unsafe {
+        tm_verify_update_header(
+            trusted.as_ptr(),
+            trusted.len(),
+            encoded.as_ptr(),
+            encoded.len(),
+            std::ptr::null(),
+            after(&blocks[1]),
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    assert_eq!(status, TmStatus::InvalidArgument);
+}
+
+#[test]
+fn verify_misbehaviour() {
+    let chain = LightChain::default_with_length(3);
+    let mut forked = chain.clone();
+    forked.shift_block_time(3, 1);
+    let (blocks, forked) = (light_blocks(&chain), light_blocks(&forked));
+    let trusted = trusted_state(&blocks[0]);
+    let now = after(&forked[2]);
+
+    // different headers at the same height
+    let fork = Misbehaviour {
+        header_1: Some(header(&blocks[2])),
+        header_2: Some(header(&forked[2])),
+    };
+    assert_eq!(
+        misbehaviour(&trusted, &fork, now),
+        (TmStatus::Success, String::new())
+    );
+
+    // the same header twice
+    let duplicate = Misbehaviour {
+        header_1: Some(header(&blocks[2])),
+        header_2: Some(header(&blocks[2])),
+    };
+    assert_eq!(
+        misbehaviour(&trusted, &duplicate, now),
+        (TmStatus::Invalid, "the headers do not conflict".to_string())
+    );
+
+    // subsequent headers with increasing times
+    let subsequent = Misbehaviour {
+        header_1: Some(header(&blocks[1])),
+        header_2: Some(header(&blocks[2])),
+    };
+    assert_eq!(
+        misbehaviour(&trusted, &subsequent, now).0,
+        TmStatus::Invalid
+    );
+
+    let mut forged = forked[2].clone();
+    forged.signed_header.commit = blocks[2].signed_header.commit.clone();
+    let unsigned = Misbehaviour {
+        header_1: Some(header(&blocks[2])),
+        header_2: Some(header(&forged)),
+    };
+    let (status, error) = misbehaviour(&trusted, &unsigned, now);
+    assert_eq!(status, TmStatus::Invalid);
+    assert!(error.starts_with("header_2: "));
+}
@@ -1,7 +1,10 @@
 //! Tendermint Light Client JavaScript/WASM interface.
 //!
 //! This crate exposes some of the [`tendermint-light-client-verifier`] crate's
-//! functionality to be used from the JavaScript ecosystem.
+//! functionality to be used from the JavaScript ecosystem: the verification
+//! of a single untrusted block with [`verify`], and a [`LightClient`] which
+//! bisects to its targets and cross-checks them against witnesses, fetching
+//! the light blocks through JavaScript callbacks.
 //!
 //! For a detailed example, please see the [`verifier-web` example] in the
 //! repository.
@@ -9,18 +12,23 @@
 //! [`tendermint-light-client-verifier`]: https://github.com/informalsystems/tendermint-rs/tree/main/light-client-verifier
 //! [`verifier-web` example]: https://github.com/informalsystems/tendermint-rs/tree/main/light-client-js/examples/verifier-web
 
+pub mod light_client;
 mod utils;
 
-use std::time::Duration;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
+use js_sys::{Function, Promise};
 use serde::{Deserialize, Serialize};
-use tendermint::Time;
+use tendermint::{block::Height, Time};
 use tendermint_light_client_verifier::{
     options::Options,
     types::{LightBlock, TrustThreshold},
     Verifier,
 };
 use wasm_bindgen::{prelude::*, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use crate::light_client::TrustedStore;
 
 // TODO: Use Web Crypto API for cryptographic routines.
 // https://github.com/informalsystems/tendermint-rs/issues/1241
@@ -43,6 +51,172 @@ pub fn verify(untrusted: JsValue, trusted: JsValue, options: JsValue, now: JsVal
     serde_wasm_bindgen::to_value(&result).unwrap()
 }
 
+/// A light client keeping its trusted state in memory, and fetching the light
+/// blocks through JavaScript functions.
+///
+/// The fetchers are called with a height, and return the light block at that
+/// height, or a promise of it.
+#[wasm_bindgen]
+pub struct LightClient {
+    store: Rc<RefCell<TrustedStore>>,
+    options: Options,
+}
+
+#[wasm_bindgen]
+impl LightClient {
+    /// Create a light client trusting the given light block, obtained out
+    /// of band.
+    #[wasm_bindgen(constructor)]
+    pub fn new(trusted: JsValue, options: JsValue) -> Result<LightClient, JsValue> {
+        let trusted = deserialize_param::<LightBlock>(trusted, "trusted")?;
+        Self::with_store(TrustedStore::new(trusted), options)
+    }
+
+    /// Create a light client from a trusted state exported with
+    /// [`LightClient::trusted_state`].
+    pub fn from_trusted_state(state: JsValue, options: JsValue) -> Result<LightClient, JsValue> {
+        let store = deserialize_param::<TrustedStore>(state, "state")?;
+        if store.latest().is_none() {
+            return Err(to_js(&Error::NoTrustedState { height: 0 }));
+        }
+        Self::with_store(store, options)
+    }
+
+    fn with_store(store: TrustedStore, options: JsValue) -> Result<LightClient, JsValue> {
+        let options = deserialize_param::<JsOptions>(options, "options")?.into();
+        Ok(Self {
+            store: Rc::new(RefCell::new(store)),
+            options,
+        })
+    }
+
+    /// Export the trusted state of this light client.
+    pub fn trusted_state(&self) -> JsValue {
+        to_js(&*self.store.borrow())
+    }
+
+    /// The highest trusted light block.
+    pub fn latest_trusted(&self) -> JsValue {
+        to_js(&self.store.borrow().latest())
+    }
+
+    /// Forget all the trusted light blocks but the `keep` highest ones, and
+    /// the lowest one, the root of trust.
+    pub fn prune(&self, keep: usize) {
+        self.store.borrow_mut().prune(keep);
+    }
+
+    /// Verify the light block at the `target` height by bisection, fetching
+    /// the light blocks from the primary with `fetch`.
+    ///
+    /// Resolves to the verified light block, now trusted.
+    pub fn verify_to_target(&self, target: u64, now: JsValue, fetch: Function) -> Promise {
+        let store = self.store.clone();
+        let options = self.options;
+        future_to_promise(async move {
+            let target = deserialize_height(target)?;
+            let now = deserialize_param::<Time>(now, "now")?;
+            let fetch = Rc::new(fetch);
+
+            // The store is not borrowed while fetching, so that it can be
+            // read from JavaScript in the meantime.
+            let mut verified = store.borrow().clone();
+            let result = light_client::verify_to_target(
+                &mut verified,
+                target,
+                &options,
+                now,
+                fetcher(fetch),
+            )
+            .await;
+            *store.borrow_mut() = verified;
+
+            result
+                .map(|light_block| to_js(&light_block))
+                .map_err(|e| to_js(&e))
+        })
+    }
+
+    /// Cross-check the trusted light block at the given height against the
+    /// one of a witness, fetching the light blocks from the witness with
+    /// `fetch_witness`.
+    ///
+    /// Resolves to the outcome of the cross-check, tagged with its `type`:
+    /// `agreed`, `forked` or `faulty_witness`.
+    pub fn cross_check(&self, height: u64, now: JsValue, fetch_witness: Function) -> Promise {
+        let store = self.store.borrow().clone();
+        let options = self.options;
+        future_to_promise(async move {
+            let height = deserialize_height(height)?;
+            let now = deserialize_param::<Time>(now, "now")?;
+
+            light_client::cross_check(
+                &store,
+                height,
+                &options,
+                now,
+                fetcher(Rc::new(fetch_witness)),
+            )
+            .await
+            .map(|outcome| to_js(&outcome))
+            .map_err(|e| to_js(&e))
+        })
+    }
+}
+
+/// Adapt a JavaScript function, returning a light block or a promise of one,
+/// to a fetcher of light blocks.
+fn fetcher(
+    fetch: Rc<Function>,
+) -> impl FnMut(Height) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<LightBlock, Error>>>>
+{
+    move |height| {
+        let fetch = fetch.clone();
+        Box::pin(async move {
+            let fetch_error = |msg: String| Error::Fetch {
+                height: height.value(),
+                msg,
+            };
+
+            let mut value = fetch
+                .call1(&JsValue::NULL, &JsValue::from_f64(height.value() as f64))
+                .map_err(|e| fetch_error(format!("{e:?}")))?;
+            if value.is_instance_of::<Promise>() {
+                value = JsFuture::from(Promise::from(value))
+                    .await
+                    .map_err(|e| fetch_error(format!("{e:?}")))?;
+            }
+
+            serde_wasm_bindgen::from_value(value).map_err(|e| fetch_error(e.to_string()))
+        })
+    }
+}
+
+fn deserialize_param<T: serde::de::DeserializeOwned>(
+    value: JsValue,
+    param: &str,
+) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| {
+        to_js(&Error::Serialization {
+            param: param.into(),
+            msg: e.to_string(),
+        })
+    })
+}
+
+fn deserialize_height(height: u64) -> Result<Height, JsValue> {
+    Height::try_from(height).map_err(|e| {
+        to_js(&Error::Serialization {
+            param: "height".into(),
+            msg: e.to_string(),
+        })
+    })
+}
+
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    serde_wasm_bindgen::to_value(value).unwrap()
+}
+
 fn deserialize_params(
     untrusted: JsValue,
     trusted: JsValue,
@@ -82,6 +256,18 @@ pub enum Error {
     /// A serialization/deserialization error occurred.
     #[serde(rename = "serialization")]
     Serialization { param: String, msg: String },
+
+    /// A light block could not be fetched.
+    #[serde(rename = "fetch")]
+    Fetch { height: u64, msg: String },
+
+    /// A light block could not be verified.
+    #[serde(rename = "verification")]
+    Verification { height: u64, msg: String },
+
+    /// No light block is trusted at or below the given height.
+    #[serde(rename = "no_trusted_state")]
+    NoTrustedState { height: u64 },
 }
 
 // Simplified options supplied from JavaScript.
@@ -99,6 +285,7 @@ impl From<JsOptions> for Options {
             trust_threshold: TrustThreshold::new(num, den).unwrap(),
             trusting_period: Duration::from_secs(o.trusting_period),
             clock_drift: Duration::from_secs(o.clock_drift),
+            time_monotonicity: Default::default(),
         }
     }
 }
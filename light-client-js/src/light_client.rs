@@ -0,0 +1,420 @@
+//! A light client whose light blocks are fetched by the caller.
+//!
+//! The light client keeps the light blocks it trusts in a [`TrustedStore`],
+//! verifies the light blocks at the heights it targets by bisection from the
+//! highest one it trusts below them, and cross-checks them against the ones
+//! of witnesses to detect forks. It has no I/O of its own: the light blocks
+//! are fetched through the futures returned by a callback, e.g. wrapping a
+//! JavaScript function which queries a full node.
+
+use std::{collections::BTreeMap, future::Future};
+
+use serde::{Deserialize, Serialize};
+use tendermint::{block::Height, Time};
+use tendermint_light_client_verifier::{
+    options::Options, types::LightBlock, ProdVerifier, Verdict, Verifier,
+};
+
+use crate::Error;
+
+/// The light blocks trusted by a light client, indexed by height.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TrustedStore {
+    blocks: BTreeMap<Height, LightBlock>,
+}
+
+impl TrustedStore {
+    /// A store trusting the given light block, obtained out of band.
+    pub fn new(trusted: LightBlock) -> Self {
+        Self {
+            blocks: [(trusted.height(), trusted)].into_iter().collect(),
+        }
+    }
+
+    /// The highest trusted light block.
+    pub fn latest(&self) -> Option<&LightBlock> {
+        self.blocks.values().next_back()
+    }
+
+    /// The lowest trusted light block, the root of trust from which all the
+    /// others have been verified.
+    pub fn lowest(&self) -> Option<&LightBlock> {
+        self.blocks.values().next()
+    }
+
+    /// The trusted light block at the given height, if any.
+    pub fn get(&self, height: Height) -> Option<&LightBlock> {
+        self.blocks.get(&height)
+    }
+
+    /// The highest trusted light block at or below the given height.
+    pub fn highest_up_to(&self, height: Height) -> Option<&LightBlock> {
+        self.blocks
+            .range(..=height)
+            .next_back()
+            .map(|(_, block)| block)
+    }
+
+    /// The highest trusted light block strictly below the given height.
+    pub fn highest_below(&self, height: Height) -> Option<&LightBlock> {
+        self.blocks
+            .range(..height)
+            .next_back()
+            .map(|(_, block)| block)
+    }
+
+    /// The heights of the trusted light blocks, in ascending order.
+    pub fn heights(&self) -> impl Iterator<Item = Height> + '_ {
+        self.blocks.keys().copied()
+    }
+
+    /// Trust the given light block.
+    pub fn insert(&mut self, light_block: LightBlock) {
+        self.blocks.insert(light_block.height(), light_block);
+    }
+
+    /// Forget all the trusted light blocks but the `keep` highest ones, and
+    /// the lowest one, the root of trust.
+    pub fn prune(&mut self, keep: usize) {
+        let lowest = self.lowest().map(LightBlock::height);
+        let pruned: Vec<_> = self
+            .blocks
+            .keys()
+            .rev()
+            .skip(keep)
+            .copied()
+            .filter(|height| Some(*height) != lowest)
+            .collect();
+        for height in pruned {
+            self.blocks.remove(&height);
+        }
+    }
+}
+
+/// The outcome of cross-checking a verified light block against a witness.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CrossCheck {
+    /// The witness has the same header at that height.
+    Agreed,
+    /// The witness has a different header at that height, which can be
+    /// verified from the same trusted state: the chain forked, and either
+    /// the primary or the witness serves an attack.
+    Forked {
+        /// The conflicting light block of the witness
+        witness: Box<LightBlock>,
+    },
+    /// The witness has a different header at that height, which cannot be
+    /// verified from the same trusted state: the witness is faulty.
+    FaultyWitness {
+        /// Why the light block of the witness could not be verified
+        reason: String,
+    },
+}
+
+/// Verify the light block at the `target` height by bisection, starting from
+/// the highest trusted light block below it, and trust it along with the
+/// light blocks verified on the way.
+///
+/// The light blocks are fetched by calling `fetch` with their height.
+pub async fn verify_to_target<F, Fut>(
+    store: &mut TrustedStore,
+    target: Height,
+    options: &Options,
+    now: Time,
+    mut fetch: F,
+) -> Result<LightBlock, Error>
+where
+    F: FnMut(Height) -> Fut,
+    Fut: Future<Output = Result<LightBlock, Error>>,
+{
+    let mut trusted =
+        store
+            .highest_up_to(target)
+            .cloned()
+            .ok_or_else(|| Error::NoTrustedState {
+                height: target.value(),
+            })?;
+
+    let verifier = ProdVerifier::default();
+    let mut pending = target;
+    while trusted.height() < target {
+        let untrusted = fetch(pending).await?;
+        if untrusted.height() != pending {
+            return Err(Error::Fetch {
+                height: pending.value(),
+                msg: format!("fetched a light block at height {}", untrusted.height()),
+            });
+        }
+
+        let verdict = verifier.verify(
+            untrusted.as_untrusted_state(),
+            trusted.as_trusted_state(),
+            options,
+            now,
+        );
+        match verdict {
+            Verdict::Success | Verdict::SuccessWithTimeRegression(_) => {
+                store.insert(untrusted.clone());
+                trusted = untrusted;
+                pending = target;
+            },
+            Verdict::NotEnoughTrust(_) => {
+                let (low, high) = (trusted.height().value(), pending.value());
+                let pivot = low + (high - low) / 2;
+                if pivot == low {
+                    return Err(Error::Verification {
+                        height: pending.value(),
+                        msg: "not enough trust in an adjacent light block".to_string(),
+                    });
+                }
+                pending = Height::try_from(pivot).expect("height between two valid heights");
+            },
+            Verdict::Invalid(e) => {
+                return Err(Error::Verification {
+                    height: pending.value(),
+                    msg: e.to_string(),
+                });
+            },
+        }
+    }
+
+    Ok(trusted)
+}
+
+/// Cross-check the trusted light block at the given height against the one of
+/// a witness, fetched by calling `fetch_witness` with its height.
+///
+/// If their headers differ, the light block of the witness is verified by
+/// bisection, as in [`verify_to_target`], from the highest trusted light block
+/// below that height, to tell a fork from a faulty witness.
+pub async fn cross_check<F, Fut>(
+    store: &TrustedStore,
+    height: Height,
+    options: &Options,
+    now: Time,
+    mut fetch_witness: F,
+) -> Result<CrossCheck, Error>
+where
+    F: FnMut(Height) -> Fut,
+    Fut: Future<Output = Result<LightBlock, Error>>,
+{
+    let (verified, trusted) = match (store.get(height), store.highest_below(height)) {
+        (Some(verified), Some(trusted)) => (verified, trusted),
+        _ => {
+            return Err(Error::NoTrustedState {
+                height: height.value(),
+            })
+        },
+    };
+
+    let witness = fetch_witness(height).await?;
+    if witness.signed_header.header.hash() == verified.signed_header.header.hash() {
+        return Ok(CrossCheck::Agreed);
+    }
+
+    let mut witness_store = TrustedStore::new(trusted.clone());
+    match verify_to_target(&mut witness_store, height, options, now, fetch_witness).await {
+        Ok(witness) => Ok(CrossCheck::Forked {
+            witness: Box::new(witness),
+        }),
+        Err(Error::Verification { height, msg }) => Ok(CrossCheck::FaultyWitness {
+            reason: format!("invalid light block at height {height}: {msg}"),
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::executor::block_on;
+    use tendermint_light_client_verifier::types::TrustThreshold;
+    use tendermint_testgen::{
+        helpers::get_time, light_block::TmLightBlock, Commit, Generator, LightChain,
+    };
+
+    use super::*;
+
+    fn into_light_block(tm_lb: TmLightBlock) -> LightBlock {
+        LightBlock {
+            signed_header: tm_lb.signed_header,
+            validators: tm_lb.validators,
+            next_validators: tm_lb.next_validators,
+            provider: tm_lb.provider,
+        }
+    }
+
+    fn generate(chain: LightChain) -> Vec<LightBlock> {
+        chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| into_light_block(lb.generate().unwrap()))
+            .collect()
+    }
+
+    /// A chain of the given length, and a fork of it at its last height.
+    fn chain_and_fork(length: u64) -> (Vec<LightBlock>, Vec<LightBlock>) {
+        let mut chain = LightChain::default_with_length(length);
+        let blocks = generate(chain.clone());
+
+        let last = chain.light_blocks.last_mut().unwrap();
+        let mut header = last.header.clone().unwrap();
+        header.time = Some((header.time.unwrap() + Duration::from_secs(3)).unwrap());
+        last.header = Some(header.clone());
+        last.commit = Some(Commit::new(header, 1));
+
+        (blocks, generate(chain))
+    }
+
+    fn options() -> Options {
+        Options {
+            trust_threshold: TrustThreshold::TWO_THIRDS,
+            trusting_period: Duration::from_secs(60 * 60 * 24 * 14),
+            clock_drift: Duration::from_secs(10),
+            time_monotonicity: Default::default(),
+        }
+    }
+
+    fn fetcher(
+        blocks: &[LightBlock],
+    ) -> impl FnMut(Height) -> std::future::Ready<Result<LightBlock, Error>> + '_ {
+        |height| {
+            std::future::ready(blocks.get(height.value() as usize - 1).cloned().ok_or(
+                Error::Fetch {
+                    height: height.value(),
+                    msg: "no such block".to_string(),
+                },
+            ))
+        }
+    }
+
+    #[test]
+    fn verifies_to_target_and_trusts_it() {
+        let (blocks, _) = chain_and_fork(10);
+        let mut store = TrustedStore::new(blocks[0].clone());
+        let now = get_time(11).unwrap();
+        let target = Height::from(10_u32);
+
+        let verified = block_on(verify_to_target(
+            &mut store,
+            target,
+            &options(),
+            now,
+            fetcher(&blocks),
+        ))
+        .unwrap();
+        assert_eq!(verified, blocks[9]);
+        assert_eq!(store.latest(), Some(&blocks[9]));
+
+        // The target is now trusted, nothing is fetched anymore.
+        let verified = block_on(verify_to_target(
+            &mut store,
+            target,
+            &options(),
+            now,
+            fetcher(&[]),
+        ))
+        .unwrap();
+        assert_eq!(verified, blocks[9]);
+
+        store.prune(0);
+        assert_eq!(
+            store.heights().collect::<Vec<_>>(),
+            vec![Height::from(1_u32)]
+        );
+    }
+
+    #[test]
+    fn cross_checks_against_witnesses() {
+        let (blocks, forked) = chain_and_fork(5);
+        let mut store = TrustedStore::new(blocks[0].clone());
+        let now = get_time(6).unwrap();
+        let height = Height::from(5_u32);
+        block_on(verify_to_target(
+            &mut store,
+            height,
+            &options(),
+            now,
+            fetcher(&blocks),
+        ))
+        .unwrap();
+
+        let agreed = block_on(cross_check(
+            &store,
+            height,
+            &options(),
+            now,
+            fetcher(&blocks),
+        ))
+        .unwrap();
+        assert_eq!(agreed, CrossCheck::Agreed);
+
+        let forked_check = block_on(cross_check(
+            &store,
+            height,
+            &options(),
+            now,
+            fetcher(&forked),
+        ))
+        .unwrap();
+        assert_eq!(
+            forked_check,
+            CrossCheck::Forked {
+                witness: Box::new(forked[4].clone())
+            }
+        );
+
+        // A header which does not match its commit.
+        let mut faulty = blocks.clone();
+        faulty[4].signed_header.header = forked[4].signed_header.header.clone();
+        let faulty_check = block_on(cross_check(
+            &store,
+            height,
+            &options(),
+            now,
+            fetcher(&faulty),
+        ))
+        .unwrap();
+        assert!(matches!(faulty_check, CrossCheck::FaultyWitness { .. }));
+    }
+
+    #[test]
+    fn cross_checks_from_the_highest_trusted_block_below() {
+        let (blocks, forked) = chain_and_fork(5);
+        let mut store = TrustedStore::new(blocks[0].clone());
+        for target in [3_u32, 5] {
+            block_on(verify_to_target(
+                &mut store,
+                Height::from(target),
+                &options(),
+                get_time(6).unwrap(),
+                fetcher(&blocks),
+            ))
+            .unwrap();
+        }
+
+        // The root of trust has expired by now, but not the light block at
+        // height 3, from which the light block of the witness is verified.
+        let options = Options {
+            trusting_period: Duration::from_secs(4),
+            ..options()
+        };
+        let forked_check = block_on(cross_check(
+            &store,
+            Height::from(5_u32),
+            &options,
+            get_time(6).unwrap(),
+            fetcher(&forked),
+        ))
+        .unwrap();
+        assert_eq!(
+            forked_check,
+            CrossCheck::Forked {
+                witness: Box::new(forked[4].clone())
+            }
+        );
+    }
+}
@@ -0,0 +1,57 @@
+#![forbid(unsafe_code)]
+#![deny(
+    warnings,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications,
+    rust_2018_idioms,
+    nonstandard_style
+)]
+
+//! Declarative test scenarios for the Tendermint light client, the fork
+//! detector and the supervisor.
+//!
+//! A scenario describes, as data, a chain, the trust the light client starts
+//! from, how each provider (the primary and the witnesses) behaves at each
+//! height, and the outcome expected from each of the components under test.
+//! New edge cases can thus be covered by adding a JSON file, rather than
+//! bespoke test code.
+//!
+//! ## Format
+//!
+//! ```json
+//! {
+//!   "description": "A witness forks from height 3",
+//!   "chain": {
+//!     "height": 5,
+//!     "validators": [{ "id": "a", "voting_power": 50 }, { "id": "b", "voting_power": 50 }],
+//!     "validator_changes": { "4": [{ "id": "c", "voting_power": 100 }] }
+//!   },
+//!   "trust": { "height": 1, "period": 864000 },
+//!   "primary": [],
+//!   "witnesses": [[{ "from": 3, "behavior": "fork" }]],
+//!   "target": 5,
+//!   "expect": {
+//!     "light_client": { "verdict": "success" },
+//!     "fork_detector": { "verdict": "success", "forks": ["forked"] },
+//!     "supervisor": { "verdict": "error", "error": "ForkDetected", "evidence": [0] }
+//!   }
+//! }
+//! ```
+//!
+//! The block at height `h` has time `h` seconds after the Unix epoch, and the
+//! current time (`now`, in the same unit) defaults to one second after the
+//! last block of the chain. See [`Scenario`] for all the fields, and
+//! [`Behavior`] for the behaviors of the providers.
+//!
+//! Only the components for which an outcome is expected run a scenario; see
+//! [`run_dir`].
+
+mod runner;
+mod scenario;
+
+pub use runner::{run, run_dir, Harness};
+pub use scenario::{
+    Behavior, BehaviorRule, Chain, Expectations, ForkKind, Outcome, Scenario, Trust, Verdict,
+};
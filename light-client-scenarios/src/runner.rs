@@ -0,0 +1,337 @@
+//! Running scenarios against the light client, the fork detector and the
+//! supervisor.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tendermint::{hash::Algorithm, Hash};
+use tendermint_light_client::{
+    components::{io::IoError, scheduler},
+    errors::Error,
+    evidence::{Evidence, EvidenceReporter},
+    fork_detector::{Fork, ForkDetection, ForkDetector, ProdForkDetector},
+    light_client::LightClient,
+    peer_list::PeerList,
+    state::State,
+    store::{memory::MemoryStore, LightStore},
+    supervisor::{Handle, Instance, Supervisor},
+    tests::{MockClock, MockIo},
+    verifier::{
+        options::Options,
+        types::{Height, LightBlock, PeerId, Status},
+        ProdVerifier,
+    },
+};
+
+use crate::scenario::{ForkKind, Outcome, Scenario, Verdict};
+
+/// The component a scenario is run against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Harness {
+    /// Verify the target with the primary only.
+    LightClient,
+    /// Verify the target with the primary, then look for forks among the
+    /// witnesses.
+    ForkDetector,
+    /// Verify the target with a supervisor over all the providers.
+    Supervisor,
+}
+
+impl Harness {
+    fn expected<'a>(&self, scenario: &'a Scenario) -> Option<&'a Outcome> {
+        match self {
+            Harness::LightClient => scenario.expect.light_client.as_ref(),
+            Harness::ForkDetector => scenario.expect.fork_detector.as_ref(),
+            Harness::Supervisor => scenario.expect.supervisor.as_ref(),
+        }
+    }
+}
+
+/// Run all the scenarios of the JSON files in the given directory which
+/// expect an outcome from the given component, and panic with a summary of
+/// those whose outcome differs from the expected one.
+pub fn run_dir(dir: impl AsRef<Path>, harness: Harness) {
+    let dir = dir.as_ref();
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("cannot read scenarios in {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut ran = 0;
+    let mut failures = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(&path).unwrap();
+        let scenario: Scenario = serde_json::from_str(&content)
+            .unwrap_or_else(|e| panic!("cannot parse scenario {}: {}", path.display(), e));
+        if harness.expected(&scenario).is_none() {
+            continue;
+        }
+
+        ran += 1;
+        if let Err(e) = run(&scenario, harness) {
+            failures.push(format!(
+                "{} ({}): {}",
+                path.display(),
+                scenario.description,
+                e
+            ));
+        }
+    }
+
+    assert!(
+        ran > 0,
+        "no scenario for {:?} in {}",
+        harness,
+        dir.display()
+    );
+    assert!(
+        failures.is_empty(),
+        "{} of {} scenarios failed for {:?}:\n{}",
+        failures.len(),
+        ran,
+        harness,
+        failures.join("\n")
+    );
+}
+
+/// Run the given scenario against the given component, and compare the
+/// outcome to the expected one, if any.
+pub fn run(scenario: &Scenario, harness: Harness) -> Result<(), String> {
+    let expected = match harness.expected(scenario) {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+
+    match harness {
+        Harness::LightClient => run_light_client(scenario, expected),
+        Harness::ForkDetector => run_fork_detector(scenario, expected),
+        Harness::Supervisor => run_supervisor(scenario, expected),
+    }
+}
+
+fn run_light_client(scenario: &Scenario, expected: &Outcome) -> Result<(), String> {
+    let mut instance = make_instance(scenario, scenario.primary_id(), scenario.primary_blocks()?)?;
+    let target = height(scenario.target)?;
+
+    let result = instance
+        .light_client
+        .verify_to_target(target, &mut instance.state);
+    check_verdict(expected, &result)?;
+
+    if let Some(trace) = &expected.trace {
+        let mut actual: Vec<u64> = instance
+            .state
+            .get_trace(target)
+            .iter()
+            .map(|block| block.height().value())
+            .collect();
+        actual.sort_unstable();
+        if &actual != trace {
+            return Err(format!("expected trace {trace:?}, got {actual:?}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_fork_detector(scenario: &Scenario, expected: &Outcome) -> Result<(), String> {
+    let mut primary = make_instance(scenario, scenario.primary_id(), scenario.primary_blocks()?)?;
+    let witnesses = (0..scenario.witnesses.len())
+        .map(|index| {
+            make_instance(
+                scenario,
+                scenario.witness_id(index),
+                scenario.witness_blocks(index)?,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let trusted_block = scenario.trusted_block()?;
+
+    let result = primary
+        .light_client
+        .verify_to_target(height(scenario.target)?, &mut primary.state)
+        .and_then(|verified| {
+            futures::executor::block_on(ProdForkDetector::default().detect_forks(
+                &verified,
+                &trusted_block,
+                witnesses.iter().collect(),
+            ))
+        });
+    check_verdict(expected, &result)?;
+
+    if let (Some(forks), Ok(detection)) = (&expected.forks, &result) {
+        let mut actual = vec![ForkKind::None; scenario.witnesses.len()];
+        if let ForkDetection::Detected(detected) = detection {
+            for fork in detected {
+                let (provider, kind) = match fork {
+                    Fork::Forked { witness, .. } => (witness.provider, ForkKind::Forked),
+                    Fork::Faulty(block, _) => (block.provider, ForkKind::Faulty),
+                    Fork::Timeout(provider, _) => (*provider, ForkKind::Timeout),
+                };
+                actual[witness_index(scenario, provider)?] = kind;
+            }
+        }
+        if &actual != forks {
+            return Err(format!("expected forks {forks:?}, got {actual:?}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_supervisor(scenario: &Scenario, expected: &Outcome) -> Result<(), String> {
+    let mut peer_list = PeerList::builder();
+    peer_list.primary(
+        scenario.primary_id(),
+        make_instance(scenario, scenario.primary_id(), scenario.primary_blocks()?)?,
+    );
+    for index in 0..scenario.witnesses.len() {
+        let peer_id = scenario.witness_id(index);
+        peer_list.witness(
+            peer_id,
+            make_instance(scenario, peer_id, scenario.witness_blocks(index)?)?,
+        );
+    }
+
+    let reporter = RecordingEvidenceReporter::default();
+    let supervisor = Supervisor::new(
+        peer_list.build(),
+        ProdForkDetector::default(),
+        reporter.clone(),
+    );
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(height(scenario.target)?);
+    handle.terminate().map_err(|e| e.to_string())?;
+    check_verdict(expected, &result)?;
+
+    if let Ok(verified) = &result {
+        if verified.height().value() != scenario.target {
+            return Err(format!(
+                "verified height {} instead of {}",
+                verified.height(),
+                scenario.target
+            ));
+        }
+    }
+
+    if let Some(evidence) = &expected.evidence {
+        // The evidence against a witness is submitted to the primary as well.
+        let (to_primary, to_witnesses): (Vec<_>, Vec<_>) = reporter
+            .reported()
+            .into_iter()
+            .partition(|&peer| peer == scenario.primary_id());
+        let mut actual = to_witnesses
+            .into_iter()
+            .map(|peer| witness_index(scenario, peer))
+            .collect::<Result<Vec<_>, _>>()?;
+        actual.sort_unstable();
+        if &actual != evidence {
+            return Err(format!(
+                "expected evidence reported to {evidence:?}, got {actual:?}"
+            ));
+        }
+        if to_primary.len() != actual.len() {
+            return Err(format!(
+                "expected evidence reported {} times to the primary, got {}",
+                actual.len(),
+                to_primary.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn make_instance(
+    scenario: &Scenario,
+    peer_id: PeerId,
+    blocks: Vec<LightBlock>,
+) -> Result<Instance, String> {
+    let mut light_store = MemoryStore::new();
+    light_store.insert(scenario.trusted_block()?, Status::Trusted);
+    let state = State {
+        light_store: Box::new(light_store),
+        verification_trace: HashMap::new(),
+    };
+
+    let options = Options {
+        trust_threshold: scenario.trust.level,
+        trusting_period: Duration::from_secs(scenario.trust.period),
+        clock_drift: Duration::from_secs(scenario.trust.clock_drift),
+        time_monotonicity: Default::default(),
+    };
+    let light_client = LightClient::new(
+        peer_id,
+        options,
+        MockClock {
+            now: scenario.now()?,
+        },
+        scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        MockIo::new(blocks),
+    );
+
+    Ok(Instance::new(light_client, state))
+}
+
+fn check_verdict<T>(expected: &Outcome, result: &Result<T, Error>) -> Result<(), String> {
+    match (expected.verdict, result) {
+        (Verdict::Success, Ok(_)) => Ok(()),
+        (Verdict::Success, Err(e)) => Err(format!("expected success, got error: {e}")),
+        (Verdict::Error, Ok(_)) => Err("expected an error, got success".to_string()),
+        (Verdict::Error, Err(e)) => match &expected.error {
+            Some(name) if *name != error_name(e) => Err(format!("expected error {name}, got: {e}")),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// The name of the variant of the error detail, e.g. `ForkDetected`.
+fn error_name(e: &Error) -> String {
+    format!("{:?}", e.detail())
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn witness_index(scenario: &Scenario, peer_id: PeerId) -> Result<usize, String> {
+    (0..scenario.witnesses.len())
+        .find(|&index| scenario.witness_id(index) == peer_id)
+        .ok_or_else(|| format!("unexpected peer {peer_id}"))
+}
+
+fn height(height: u64) -> Result<Height, String> {
+    Height::try_from(height).map_err(|e| e.to_string())
+}
+
+/// An evidence reporter which records the peers evidence is reported to.
+#[derive(Clone, Debug, Default)]
+struct RecordingEvidenceReporter {
+    reported: Arc<Mutex<Vec<PeerId>>>,
+}
+
+impl RecordingEvidenceReporter {
+    fn reported(&self) -> Vec<PeerId> {
+        self.reported.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EvidenceReporter for RecordingEvidenceReporter {
+    async fn report(&self, _evidence: Evidence, peer: PeerId) -> Result<Hash, IoError> {
+        self.reported.lock().unwrap().push(peer);
+        Ok(Hash::from_bytes(Algorithm::Sha256, &[0; 32]).unwrap())
+    }
+}
@@ -0,0 +1,282 @@
+//! The scenario format, and the generation of the blocks served by the
+//! providers of a scenario.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::Deserialize;
+use tendermint_light_client::verifier::types::{LightBlock, PeerId, Time, TrustThreshold};
+use tendermint_testgen::{
+    helpers::get_time, light_block::TmLightBlock, Commit, Generator, Header,
+    LightBlock as TestgenLightBlock, Validator,
+};
+
+const CHAIN_ID: &str = "test-chain";
+
+/// A test scenario. See the [crate documentation](crate) for an example.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub description: String,
+    #[serde(default)]
+    pub chain: Chain,
+    #[serde(default)]
+    pub trust: Trust,
+    /// The current time, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub now: Option<u64>,
+    /// The behaviors of the primary, honest if empty.
+    #[serde(default)]
+    pub primary: Vec<BehaviorRule>,
+    /// The behaviors of each witness.
+    #[serde(default)]
+    pub witnesses: Vec<Vec<BehaviorRule>>,
+    /// The height to verify.
+    pub target: u64,
+    pub expect: Expectations,
+}
+
+/// The honest chain.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Chain {
+    /// The height of the latest block.
+    pub height: u64,
+    /// The validators of the first block.
+    #[serde(default = "default_validators")]
+    pub validators: Vec<Validator>,
+    /// The validators from the given heights onwards.
+    #[serde(default)]
+    pub validator_changes: BTreeMap<u64, Vec<Validator>>,
+}
+
+impl Default for Chain {
+    fn default() -> Self {
+        Self {
+            height: 5,
+            validators: default_validators(),
+            validator_changes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Chain {
+    /// The validators of the block at the given height.
+    pub fn validators_at(&self, height: u64) -> &[Validator] {
+        self.validator_changes
+            .range(..=height)
+            .next_back()
+            .map_or(&self.validators, |(_, validators)| validators)
+    }
+}
+
+fn default_validators() -> Vec<Validator> {
+    ["a", "b", "c", "d"]
+        .iter()
+        .map(|id| Validator::new(id).voting_power(25))
+        .collect()
+}
+
+/// The trust the light client starts from.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Trust {
+    /// The height of the trusted block.
+    pub height: u64,
+    /// The trusting period, in seconds.
+    pub period: u64,
+    #[serde(default)]
+    pub level: TrustThreshold,
+    /// The maximum clock drift, in seconds.
+    #[serde(default = "default_clock_drift")]
+    pub clock_drift: u64,
+}
+
+impl Default for Trust {
+    fn default() -> Self {
+        Self {
+            height: 1,
+            period: 60 * 60 * 24 * 10,
+            level: TrustThreshold::default(),
+            clock_drift: default_clock_drift(),
+        }
+    }
+}
+
+fn default_clock_drift() -> u64 {
+    10
+}
+
+/// The behavior of a provider from a given height, up to an optional
+/// height (inclusive). The last rule which applies at a height wins.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BehaviorRule {
+    pub from: u64,
+    #[serde(default)]
+    pub to: Option<u64>,
+    pub behavior: Behavior,
+}
+
+/// How a provider behaves at a given height.
+///
+/// Each block links to the previous block served by the same provider, so
+/// that a provider which stops forking still serves a chain of its own.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Behavior {
+    /// Serve the block of the honest chain.
+    Honest,
+    /// Serve a block which conflicts with the honest one, but is signed by
+    /// the same validators, as happens on an actual fork.
+    Fork,
+    /// Serve a block signed by validators unrelated to the honest chain.
+    Lunatic,
+    /// Do not serve any block.
+    Unavailable,
+}
+
+/// The outcomes expected from each component. The components without an
+/// expected outcome do not run the scenario.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    #[serde(default)]
+    pub light_client: Option<Outcome>,
+    #[serde(default)]
+    pub fork_detector: Option<Outcome>,
+    #[serde(default)]
+    pub supervisor: Option<Outcome>,
+}
+
+/// The outcome of a run. Only the fields which are specified are checked.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    /// The name of the error variant, e.g. `NotWithinTrustPeriod`.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The heights of the blocks in the verification trace of the target,
+    /// in ascending order (light client only).
+    #[serde(default)]
+    pub trace: Option<Vec<u64>>,
+    /// What was detected for each witness (fork detector only).
+    #[serde(default)]
+    pub forks: Option<Vec<ForkKind>>,
+    /// The indices of the witnesses evidence was reported to, the primary
+    /// being reported the evidence against each of them (supervisor only).
+    #[serde(default)]
+    pub evidence: Option<Vec<usize>>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Success,
+    Error,
+}
+
+/// What the fork detector found out about a witness.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkKind {
+    None,
+    Forked,
+    Faulty,
+    Timeout,
+}
+
+impl Scenario {
+    /// The peer ID of the primary.
+    pub fn primary_id(&self) -> PeerId {
+        peer_id(0)
+    }
+
+    /// The peer ID of the witness with the given index.
+    pub fn witness_id(&self, index: usize) -> PeerId {
+        peer_id(index + 1)
+    }
+
+    /// The current time.
+    pub fn now(&self) -> Result<Time, String> {
+        seconds(self.now.unwrap_or(self.chain.height + 1))
+    }
+
+    /// The blocks served by the primary.
+    pub fn primary_blocks(&self) -> Result<Vec<LightBlock>, String> {
+        self.blocks(&self.primary, self.primary_id())
+    }
+
+    /// The blocks served by the witness with the given index.
+    pub fn witness_blocks(&self, index: usize) -> Result<Vec<LightBlock>, String> {
+        self.blocks(&self.witnesses[index], self.witness_id(index))
+    }
+
+    /// The block of the honest chain at the trusted height.
+    pub fn trusted_block(&self) -> Result<LightBlock, String> {
+        self.blocks(&[], self.primary_id())?
+            .into_iter()
+            .find(|block| block.height().value() == self.trust.height)
+            .ok_or_else(|| format!("no block at trusted height {}", self.trust.height))
+    }
+
+    fn blocks(&self, rules: &[BehaviorRule], provider: PeerId) -> Result<Vec<LightBlock>, String> {
+        let lunatics = [Validator::new("lunatic").voting_power(100)];
+        let mut blocks = Vec::new();
+        let mut last_block_id_hash = None;
+
+        for height in 1..=self.chain.height {
+            let behavior = rules
+                .iter()
+                .rev()
+                .find(|rule| rule.from <= height && rule.to.map_or(true, |to| height <= to))
+                .map_or(Behavior::Honest, |rule| rule.behavior);
+
+            let (validators, next_validators) = match behavior {
+                Behavior::Lunatic => (&lunatics[..], &lunatics[..]),
+                _ => (
+                    self.chain.validators_at(height),
+                    self.chain.validators_at(height + 1),
+                ),
+            };
+            let mut time = seconds(height)?;
+            if behavior == Behavior::Fork {
+                time = (time + Duration::from_millis(500)).map_err(|e| e.to_string())?;
+            }
+
+            let mut header = Header::new(validators)
+                .next_validators(next_validators)
+                .chain_id(CHAIN_ID)
+                .height(height)
+                .time(time);
+            if let Some(hash) = last_block_id_hash {
+                header = header.last_block_id_hash(hash);
+            }
+            let commit = Commit::new(header.clone(), 1);
+            let block: TmLightBlock = TestgenLightBlock::new(header, commit)
+                .next_validators(next_validators)
+                .generate()
+                .map_err(|e| e.to_string())?;
+            last_block_id_hash = Some(block.signed_header.header.hash());
+
+            if behavior != Behavior::Unavailable {
+                blocks.push(LightBlock::new(
+                    block.signed_header,
+                    block.validators,
+                    block.next_validators,
+                    provider,
+                ));
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+fn peer_id(index: usize) -> PeerId {
+    PeerId::new([index as u8 + 1; 20])
+}
+
+fn seconds(secs: u64) -> Result<Time, String> {
+    get_time(secs).map_err(|e| e.to_string())
+}
@@ -0,0 +1,96 @@
+//! Sources of the current time against which headers are verified.
+//!
+//! The verifier itself only ever takes the current time as a [`Time`]: a
+//! [`Clock`] decides where that time comes from. The wall clock of the system
+//! is only available with the `clock` feature, as it is not on every target
+//! (e.g. `wasm32-unknown-unknown`), and is the wrong source anyway for
+//! deterministic simulations or for consensus contexts, which must verify
+//! headers against the time of the latest block rather than against the time
+//! of the machine executing the verification.
+
+use core::fmt;
+
+use crate::{prelude::*, types::Time};
+
+/// Abstracts over the current time.
+pub trait Clock: Send + Sync {
+    /// Get the current time.
+    fn now(&self) -> Time;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Time {
+        (**self).now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Box<C> {
+    fn now(&self) -> Time {
+        (**self).now()
+    }
+}
+
+/// Provides the current wall clock time of the system.
+#[cfg(feature = "clock")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "clock")]
+impl Clock for SystemClock {
+    fn now(&self) -> Time {
+        Time::now()
+    }
+}
+
+/// Always provides the same time, e.g. in tests and deterministic
+/// simulations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FixedClock(pub Time);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Time {
+        self.0
+    }
+}
+
+/// Provides the time given by the host of the verifier, through the given
+/// function, e.g. the time of the latest block in a consensus context, or
+/// `Date.now()` in a browser.
+///
+/// ## Example
+///
+/// ```
+/// use tendermint::Time;
+/// use tendermint_light_client_verifier::clock::{Clock, HostClock};
+///
+/// let block_time = Time::from_unix_timestamp(1_600_000_000, 0).unwrap();
+/// let clock = HostClock::new(move || block_time);
+/// assert_eq!(clock.now(), block_time);
+/// ```
+#[derive(Copy, Clone)]
+pub struct HostClock<F>(F);
+
+impl<F> HostClock<F>
+where
+    F: Fn() -> Time + Send + Sync,
+{
+    /// Provide the time returned by the given function.
+    pub fn new(now: F) -> Self {
+        Self(now)
+    }
+}
+
+impl<F> Clock for HostClock<F>
+where
+    F: Fn() -> Time + Send + Sync,
+{
+    fn now(&self) -> Time {
+        (self.0)()
+    }
+}
+
+impl<F> fmt::Debug for HostClock<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HostClock").finish()
+    }
+}
@@ -51,26 +51,36 @@ define_error! {
         DuplicateValidator
             {
                 address: ValidatorAddress,
+                // The index of the duplicate signature in the commit.
+                validator_index: usize,
             }
             | e | {
-                format_args!("duplicate validator with address {0}",
-                    e.address)
+                format_args!("duplicate validator with address {0} at index {1}",
+                    e.address, e.validator_index)
             },
 
         MissingSignature
-            | _ | {
-                format_args!("missing signature")
+            {
+                validator_address: ValidatorAddress,
+                // The index of the signature in the commit.
+                validator_index: usize,
+            }
+            | e | {
+                format_args!("missing signature of validator {0} at index {1}",
+                    e.validator_address, e.validator_index)
             },
 
         InvalidSignature
             {
                 signature: Vec<u8>,
                 validator: Box<Validator>,
+                // The index of the signature in the commit.
+                validator_index: usize,
                 sign_bytes: Vec<u8>,
             }
             | e | {
-                format_args!("failed to verify signature `{:?}` with validator `{:?}` on sign_bytes `{:?}`",
-                    e.signature, e.validator, e.sign_bytes)
+                format_args!("failed to verify signature `{:?}` of validator {} at index {} on sign_bytes `{:?}`",
+                    e.signature, e.validator.address, e.validator_index, e.sign_bytes)
             },
 
         InvalidCommitValue
@@ -146,6 +156,13 @@ define_error! {
         NoSignatureForCommit
             | _ | { "no signatures for commit"  },
 
+        IncompleteCommitBlockId
+            { block_id: tendermint::block::Id }
+            | e | {
+                format_args!("commit has signatures for an incomplete block id: {0:?}",
+                    e.block_id)
+            },
+
         MismatchPreCommitLength
             {
                 pre_commit_length: usize,
@@ -162,12 +179,15 @@ define_error! {
         FaultySigner
             {
                 signer: Id,
+                // The index of the signature in the commit.
+                validator_index: usize,
                 validator_set: ValidatorSet
             }
             | e | {
                 format_args!(
-                    "Found a faulty signer ({}) not present in the validator set",
+                    "Found a faulty signer ({}) at index {} not present in the validator set",
                     e.signer,
+                    e.validator_index,
                 )
             },
 
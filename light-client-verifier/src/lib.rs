@@ -4,6 +4,7 @@ extern crate alloc;
 
 mod prelude;
 
+pub mod clock;
 pub mod errors;
 pub mod operations;
 pub mod options;
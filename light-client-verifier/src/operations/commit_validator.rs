@@ -41,7 +41,7 @@ pub trait CommitValidator: Send + Sync {
         signed_header: &SignedHeader,
         validator_set: &ValidatorSet,
     ) -> Result<(), VerificationError> {
-        for commit_sig in signed_header.commit.signatures.iter() {
+        for (idx, commit_sig) in signed_header.commit.signatures.iter().enumerate() {
             let validator_address = match commit_sig {
                 CommitSig::BlockIdFlagAbsent => continue,
                 CommitSig::BlockIdFlagCommit {
@@ -55,6 +55,7 @@ pub trait CommitValidator: Send + Sync {
             if validator_set.validator(*validator_address).is_none() {
                 return Err(VerificationError::faulty_signer(
                     *validator_address,
+                    idx,
                     validator_set.clone(),
                 ));
             }
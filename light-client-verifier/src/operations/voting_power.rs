@@ -132,6 +132,14 @@ impl<V: signature::Verifier> VotingPowerCalculator for ProvidedVotingPowerCalcul
     ) -> Result<VotingPowerTally, VerificationError> {
         let signatures = &signed_header.commit.signatures;
 
+        // The signatures for the block are checked against the sign bytes of
+        // votes for the block ID of the commit. An empty block ID would make
+        // them indistinguishable from votes for nil, so it must be complete.
+        let block_id = signed_header.commit.block_id;
+        if !block_id.is_complete() && signatures.iter().any(CommitSig::is_commit) {
+            return Err(VerificationError::incomplete_commit_block_id(block_id));
+        }
+
         let mut tallied_voting_power = 0_u64;
         let mut seen_validators = HashSet::new();
 
@@ -142,14 +150,15 @@ impl<V: signature::Verifier> VotingPowerCalculator for ProvidedVotingPowerCalcul
                 ValidatorIndex::try_from(idx).unwrap(),
                 &signed_header.commit,
             )
-            .map(|vote| (signature, vote))
+            .map(|vote| (idx, signature, vote))
         });
 
-        for (signature, vote) in non_absent_votes {
+        for (idx, signature, vote) in non_absent_votes {
             // Ensure we only count a validator's power once
             if seen_validators.contains(&vote.validator_address) {
                 return Err(VerificationError::duplicate_validator(
                     vote.validator_address,
+                    idx,
                 ));
             } else {
                 seen_validators.insert(vote.validator_address);
@@ -162,7 +171,9 @@ impl<V: signature::Verifier> VotingPowerCalculator for ProvidedVotingPowerCalcul
 
             let signed_vote =
                 SignedVote::from_vote(vote.clone(), signed_header.header.chain_id.clone())
-                    .ok_or_else(VerificationError::missing_signature)?;
+                    .ok_or_else(|| {
+                        VerificationError::missing_signature(vote.validator_address, idx)
+                    })?;
 
             // Check vote is valid
             let sign_bytes = signed_vote.sign_bytes();
@@ -173,6 +184,7 @@ impl<V: signature::Verifier> VotingPowerCalculator for ProvidedVotingPowerCalcul
                 return Err(VerificationError::invalid_signature(
                     signed_vote.signature().as_bytes().to_vec(),
                     Box::new(validator),
+                    idx,
                     sign_bytes,
                 ));
             }
@@ -239,7 +251,7 @@ fn non_absent_vote(
 // see https://github.com/informalsystems/tendermint-rs/pull/383
 // This is essentially to remove the heavy dependency on MBT
 // TODO: We plan to add Lightweight MBT for `voting_power_in` in the near future
-#[cfg(test)]
+#[cfg(all(test, feature = "rust-crypto"))]
 mod tests {
     use tendermint::trust_threshold::TrustThresholdFraction;
     use tendermint_testgen::{
@@ -344,7 +356,15 @@ mod tests {
         );
 
         match result_err {
-            Err(VerificationError(VerificationErrorDetail::InvalidSignature(_), _)) => {},
+            Err(VerificationError(VerificationErrorDetail::InvalidSignature(e), _)) => {
+                // The details point at the offending signature of the commit
+                match &light_block.signed_header.commit.signatures[e.validator_index] {
+                    CommitSig::BlockIdFlagCommit {
+                        validator_address, ..
+                    } => assert_eq!(*validator_address, e.validator.address),
+                    sig => panic!("unexpected signature: {sig:?}"),
+                }
+            },
             _ => panic!("expected InvalidSignature error"),
         }
     }
@@ -371,6 +391,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_nil_votes_do_not_count_for_an_empty_block_id() {
+        let vp_calculator = ProdVotingPowerCalculator::default();
+        let trust_threshold = TrustThreshold::default();
+
+        // A commit made of votes for nil...
+        let mut testgen_lb = TestgenLightBlock::new_default(10);
+        let mut commit = testgen_lb.commit.clone().unwrap();
+        commit.votes = Some(
+            commit
+                .votes
+                .unwrap()
+                .into_iter()
+                .map(|vote| vote.nil(true))
+                .collect(),
+        );
+        testgen_lb.commit = Some(commit);
+        let mut light_block: LightBlock = testgen_lb.generate().unwrap().into();
+
+        // ...passed off as votes for the empty block ID, whose sign bytes are
+        // the same.
+        let commit = &mut light_block.signed_header.commit;
+        commit.block_id = Default::default();
+        for sig in commit.signatures.iter_mut() {
+            if let CommitSig::BlockIdFlagNil {
+                validator_address,
+                timestamp,
+                signature,
+            } = sig.clone()
+            {
+                *sig = CommitSig::BlockIdFlagCommit {
+                    validator_address,
+                    timestamp,
+                    signature,
+                };
+            }
+        }
+
+        let result_err = vp_calculator.voting_power_in(
+            &light_block.signed_header,
+            &light_block.validators,
+            trust_threshold,
+        );
+
+        match result_err {
+            Err(VerificationError(VerificationErrorDetail::IncompleteCommitBlockId(e), _)) => {
+                assert!(e.block_id.is_zero())
+            },
+            result => panic!("expected IncompleteCommitBlockId error, got {result:?}"),
+        }
+    }
+
     #[test]
     fn test_signatures_from_diff_valset() {
         let vp_calculator = ProdVotingPowerCalculator::default();
@@ -27,4 +27,34 @@ pub struct Options {
     /// is the maximum amount that the local clock may drift behind a timestamp from the
     /// blockchain.
     pub clock_drift: Duration,
+
+    /// How strictly the time of a header must follow the one of the trusted
+    /// header it is verified against. Strict unless specified otherwise.
+    #[serde(default)]
+    pub time_monotonicity: TimeMonotonicity,
+}
+
+/// How the verifier treats a header whose time does not follow the one of
+/// the trusted header it is verified against.
+///
+/// BFT time is monotonic on a correct chain, so anything but [`Strict`] is
+/// only meant for chains known to have had their block times regress (e.g.
+/// after a faulty upgrade), with a regression as small as possible.
+///
+/// [`Strict`]: TimeMonotonicity::Strict
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeMonotonicity {
+    /// The time of the header must be strictly greater than the trusted one,
+    /// or the header is invalid.
+    #[default]
+    Strict,
+    /// The time of the header may precede (or equal) the trusted one by at
+    /// most `max_regression`. Such a header verifies with a
+    /// [`Verdict::SuccessWithTimeRegression`] carrying the regression.
+    ///
+    /// [`Verdict::SuccessWithTimeRegression`]: crate::Verdict::SuccessWithTimeRegression
+    Tolerant {
+        /// The largest regression of the time of the header.
+        max_regression: Duration,
+    },
 }
@@ -10,7 +10,7 @@ use crate::{
     errors::VerificationError,
     operations::{CommitValidator, VotingPowerCalculator},
     prelude::*,
-    types::{Header, SignedHeader, Time, TrustThreshold, ValidatorSet},
+    types::{SignedHeader, Time, TrustThreshold, ValidatorSet},
 };
 
 /// Production predicates, using the default implementation
@@ -70,12 +70,9 @@ pub trait VerificationPredicates: Send + Sync {
     }
 
     /// Check that the hash of the header in the commit matches the actual one.
-    fn header_matches_commit(
-        &self,
-        header: &Header,
-        commit_hash: Hash,
-    ) -> Result<(), VerificationError> {
-        let header_hash = header.hash_with::<Self::Sha256>();
+    fn header_matches_commit(&self, signed_header: &SignedHeader) -> Result<(), VerificationError> {
+        let header_hash = signed_header.header_hash_with::<Self::Sha256>();
+        let commit_hash = signed_header.commit.block_id.hash;
         if header_hash == commit_hash {
             Ok(())
         } else {
@@ -106,8 +103,8 @@ pub trait VerificationPredicates: Send + Sync {
         trusting_period: Duration,
         now: Time,
     ) -> Result<(), VerificationError> {
-        let expires_at =
-            (trusted_header_time + trusting_period).map_err(VerificationError::tendermint)?;
+        // A trusting period reaching past the latest valid time never expires.
+        let expires_at = trusted_header_time.saturating_add(trusting_period);
 
         if expires_at > now {
             Ok(())
@@ -123,7 +120,7 @@ pub trait VerificationPredicates: Send + Sync {
         clock_drift: Duration,
         now: Time,
     ) -> Result<(), VerificationError> {
-        let drifted = (now + clock_drift).map_err(VerificationError::tendermint)?;
+        let drifted = now.saturating_add(clock_drift);
 
         if untrusted_header_time < drifted {
             Ok(())
@@ -152,6 +149,32 @@ pub trait VerificationPredicates: Send + Sync {
         }
     }
 
+    /// Check that the time of the untrusted header precedes (or equals) the trusted one by at
+    /// most `max_regression`, returning the regression if it does not follow it.
+    fn is_bounded_bft_time_regression(
+        &self,
+        untrusted_header_time: Time,
+        trusted_header_time: Time,
+        max_regression: Duration,
+    ) -> Result<Option<Duration>, VerificationError> {
+        if untrusted_header_time > trusted_header_time {
+            return Ok(None);
+        }
+
+        let regression = trusted_header_time
+            .duration_since(untrusted_header_time)
+            .map_err(VerificationError::tendermint)?;
+
+        if regression <= max_regression {
+            Ok(Some(regression))
+        } else {
+            Err(VerificationError::non_monotonic_bft_time(
+                untrusted_header_time,
+                trusted_header_time,
+            ))
+        }
+    }
+
     /// Check that the height increased between the trusted header and the untrusted one.
     fn is_monotonic_height(
         &self,
@@ -231,7 +254,7 @@ pub trait VerificationPredicates: Send + Sync {
 mod tests {
     use core::{convert::TryInto, time::Duration};
 
-    use tendermint::{block::CommitSig, validator::Set};
+    use tendermint::{block::CommitSig, hash::AppHash, validator::Set};
     use tendermint_testgen::{
         light_block::{LightBlock as TestgenLightBlock, TmLightBlock},
         Commit, Generator, Header, Validator, ValidatorSet,
@@ -243,7 +266,7 @@ mod tests {
         operations::{ProdCommitValidator, ProdVotingPowerCalculator, VotingPowerTally},
         predicates::{ProdPredicates, VerificationPredicates},
         prelude::*,
-        types::{LightBlock, TrustThreshold},
+        types::{LightBlock, Time, TrustThreshold},
     };
 
     impl From<TmLightBlock> for LightBlock {
@@ -280,6 +303,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_bounded_bft_time_regression() {
+        let trusted = Time::from_unix_timestamp(100, 0).unwrap();
+        let max_regression = Duration::from_secs(5);
+
+        let vp = ProdPredicates::default();
+
+        // 1. ensure a later header has no regression
+        let later = Time::from_unix_timestamp(101, 0).unwrap();
+        let result = vp.is_bounded_bft_time_regression(later, trusted, max_regression);
+        assert_eq!(result.unwrap(), None);
+
+        // 2. ensure a header within the bound reports its regression
+        let earlier = Time::from_unix_timestamp(97, 0).unwrap();
+        let result = vp.is_bounded_bft_time_regression(earlier, trusted, max_regression);
+        assert_eq!(result.unwrap(), Some(Duration::from_secs(3)));
+
+        let result = vp.is_bounded_bft_time_regression(trusted, trusted, max_regression);
+        assert_eq!(result.unwrap(), Some(Duration::ZERO));
+
+        // 3. ensure a header beyond the bound fails
+        let too_early = Time::from_unix_timestamp(94, 0).unwrap();
+        let result = vp.is_bounded_bft_time_regression(too_early, trusted, max_regression);
+        match result {
+            Err(VerificationError(VerificationErrorDetail::NonMonotonicBftTime(e), _)) => {
+                assert_eq!(e.header_bft_time, too_early);
+                assert_eq!(e.trusted_header_bft_time, trusted);
+            },
+            _ => panic!("expected NonMonotonicBftTime error"),
+        }
+    }
+
     #[test]
     fn test_is_monotonic_height() {
         let val = vec![Validator::new("val-1")];
@@ -355,6 +410,12 @@ mod tests {
             },
             _ => panic!("expected NotWithinTrustPeriod error"),
         }
+
+        // 3. ensure a trusting period reaching past the latest valid time never expires
+        let trusted = Time::MAX.saturating_sub(Duration::new(10, 0));
+        let now = Time::MAX.saturating_sub(Duration::new(5, 0));
+        let result_ok = vp.is_within_trust_period(trusted, Duration::new(1000, 0), now);
+        assert!(result_ok.is_ok());
     }
 
     #[test]
@@ -383,6 +444,10 @@ mod tests {
             },
             _ => panic!("expected HeaderFromTheFuture error"),
         }
+
+        // 3. ensure a drift reaching past the latest valid time does not fail the check
+        let result_ok = vp.is_header_from_past(header.time, one_second, Time::MAX);
+        assert!(result_ok.is_ok());
     }
 
     #[test]
@@ -461,8 +526,7 @@ mod tests {
         let vp = ProdPredicates::default();
 
         // 1. ensure valid signed header verifies
-        let result_ok =
-            vp.header_matches_commit(&signed_header.header, signed_header.commit.block_id.hash);
+        let result_ok = vp.header_matches_commit(&signed_header);
 
         assert!(result_ok.is_ok());
 
@@ -471,8 +535,7 @@ mod tests {
             "15F15EF50BDE2018F4B129A827F90C18222C757770C8295EB8EE7BF50E761BC0"
                 .parse()
                 .unwrap();
-        let result_err =
-            vp.header_matches_commit(&signed_header.header, signed_header.commit.block_id.hash);
+        let result_err = vp.header_matches_commit(&signed_header);
 
         // 3. ensure it fails with: VerificationVerificationError::InvalidCommitValue
         let header_hash = signed_header.header.hash();
@@ -484,6 +547,18 @@ mod tests {
             },
             _ => panic!("expected InvalidCommitValue error"),
         }
+
+        // 4. ensure a header modified after being hashed is hashed again
+        signed_header.header.app_hash = AppHash::try_from(vec![1, 2, 3]).unwrap();
+        let result_err = vp.header_matches_commit(&signed_header);
+
+        match result_err {
+            Err(VerificationError(VerificationErrorDetail::InvalidCommitValue(e), _)) => {
+                assert_ne!(e.header_hash, header_hash);
+                assert_eq!(e.header_hash, signed_header.header.hash());
+            },
+            _ => panic!("expected InvalidCommitValue error"),
+        }
     }
 
     #[test]
@@ -542,7 +617,7 @@ mod tests {
                 .generate()
                 .expect("Failed to generate validator"),
         );
-        let val_set_with_faulty_signer = Set::without_proposer(bad_vals);
+        let val_set_with_faulty_signer = Set::without_proposer(bad_vals).unwrap();
 
         // reset signatures
         signed_header.commit.signatures = signatures;
@@ -566,6 +641,7 @@ mod tests {
                         .validator_address()
                         .unwrap()
                 );
+                assert_eq!(e.validator_index, signed_header.commit.signatures.len() - 1);
 
                 assert_eq!(e.validator_set, val_set_with_faulty_signer);
             },
@@ -650,7 +726,7 @@ mod tests {
                 .generate()
                 .unwrap(),
         );
-        let bad_valset = Set::without_proposer(vals);
+        let bad_valset = Set::without_proposer(vals).unwrap();
 
         trust_threshold = TrustThreshold::new(2, 3).expect("Cannot make trust threshold");
 
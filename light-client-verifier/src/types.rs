@@ -6,9 +6,10 @@ use tendermint::{
     account::Id as TMAccountId,
     block::{
         header::Header as TMHeader, signed_header::SignedHeader as TMSignedHeader,
-        Commit as TMCommit,
+        Commit as TMCommit, CommitSig,
     },
     chain::Id as ChainId,
+    evidence::ConflictingBlock,
     trust_threshold::TrustThresholdFraction,
     validator::{Info as TMValidatorInfo, Set as TMValidatorSet},
 };
@@ -141,6 +142,43 @@ impl LightBlock {
         self.signed_header.header.height
     }
 
+    /// Returns the time of this block.
+    ///
+    /// ## Note
+    /// This is a shorthand for `block.signed_header.header.time`.
+    pub fn time(&self) -> Time {
+        self.signed_header.header.time
+    }
+
+    /// Returns an approximation of the memory used by this block, in bytes.
+    ///
+    /// ## Note
+    /// Only the structure of the block, the chain ID, the signatures of the
+    /// commit and the validators are accounted for, which make up most of the
+    /// memory used by the blocks of large validator sets.
+    pub fn approximate_size(&self) -> usize {
+        let signatures: usize = self
+            .signed_header
+            .commit
+            .signatures
+            .iter()
+            .map(|sig| {
+                let signature = match sig {
+                    CommitSig::BlockIdFlagCommit { signature, .. }
+                    | CommitSig::BlockIdFlagNil { signature, .. } => signature.as_ref(),
+                    CommitSig::BlockIdFlagAbsent => None,
+                };
+                core::mem::size_of::<CommitSig>() + signature.map_or(0, |s| s.as_bytes().len())
+            })
+            .sum();
+        let validators =
+            self.validators.validators().len() + self.next_validators.validators().len();
+        core::mem::size_of::<Self>()
+            + self.signed_header.header.chain_id.as_str().len()
+            + signatures
+            + validators * core::mem::size_of::<Validator>()
+    }
+
     /// Obtain the verification parameters for the light block when using it as
     /// trusted state.
     pub fn as_trusted_state(&self) -> TrustedBlockState<'_> {
@@ -164,6 +202,15 @@ impl LightBlock {
     }
 }
 
+impl From<LightBlock> for ConflictingBlock {
+    fn from(block: LightBlock) -> Self {
+        Self {
+            signed_header: block.signed_header,
+            validator_set: block.validators,
+        }
+    }
+}
+
 /// Contains the local status information, like the latest height, latest block and valset hashes,
 /// list of of connected full nodes (primary and witnesses).
 #[derive(Clone, Debug, Display, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,11 +1,14 @@
 //! Provides an interface and default implementation of the `Verifier` component
 
+use core::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    clock::Clock,
     errors::{ErrorExt, VerificationError, VerificationErrorDetail},
     operations::{voting_power::VotingPowerTally, CommitValidator, VotingPowerCalculator},
-    options::Options,
+    options::{Options, TimeMonotonicity},
     predicates::VerificationPredicates,
     types::{Time, TrustedBlockState, UntrustedBlockState},
 };
@@ -22,6 +25,10 @@ use crate::{
 pub enum Verdict {
     /// Verification succeeded, the block is valid.
     Success,
+    /// Verification succeeded, the block is valid, but its time precedes (or equals) the one of
+    /// the trusted block by the given amount, which the [`TimeMonotonicity`] of the options
+    /// tolerates.
+    SuccessWithTimeRegression(Duration),
     /// The minimum voting power threshold is not reached,
     /// the block cannot be trusted yet.
     NotEnoughTrust(VotingPowerTally),
@@ -29,6 +36,13 @@ pub enum Verdict {
     Invalid(VerificationErrorDetail),
 }
 
+impl Verdict {
+    /// Whether the verification succeeded, be it with a time regression or not.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success | Self::SuccessWithTimeRegression(_))
+    }
+}
+
 impl From<Result<(), VerificationError>> for Verdict {
     fn from(result: Result<(), VerificationError>) -> Self {
         match result {
@@ -59,6 +73,18 @@ pub trait Verifier: Send + Sync {
         options: &Options,
         now: Time,
     ) -> Verdict;
+
+    /// Perform the verification against the current time given by the given
+    /// clock.
+    fn verify_with_clock(
+        &self,
+        untrusted: UntrustedBlockState<'_>,
+        trusted: TrustedBlockState<'_>,
+        options: &Options,
+        clock: &dyn Clock,
+    ) -> Verdict {
+        self.verify(untrusted, trusted, options, clock.now())
+    }
 }
 
 macro_rules! verdict {
@@ -120,10 +146,9 @@ where
         }
 
         // Ensure the header matches the commit
-        verdict!(self.predicates.header_matches_commit(
-            &untrusted.signed_header.header,
-            untrusted.signed_header.commit.block_id.hash,
-        ));
+        verdict!(self
+            .predicates
+            .header_matches_commit(untrusted.signed_header));
 
         // Additional implementation specific validation
         verdict!(self.predicates.valid_commit(
@@ -169,10 +194,27 @@ where
             now,
         ));
 
-        // Check that the untrusted block is more recent than the trusted state
-        verdict!(self
-            .predicates
-            .is_monotonic_bft_time(untrusted.signed_header.header.time, trusted.header_time));
+        // Check that the untrusted block is more recent than the trusted state, or at least
+        // not older than the options tolerate
+        let time_regression = match options.time_monotonicity {
+            TimeMonotonicity::Strict => {
+                verdict!(self.predicates.is_monotonic_bft_time(
+                    untrusted.signed_header.header.time,
+                    trusted.header_time
+                ));
+                None
+            },
+            TimeMonotonicity::Tolerant { max_regression } => {
+                match self.predicates.is_bounded_bft_time_regression(
+                    untrusted.signed_header.header.time,
+                    trusted.header_time,
+                    max_regression,
+                ) {
+                    Ok(time_regression) => time_regression,
+                    Err(e) => return Err(e).into(),
+                }
+            },
+        };
 
         // Check that the chain-id of the untrusted block matches that of the trusted state
         verdict!(self
@@ -196,7 +238,10 @@ where
                 .is_monotonic_height(untrusted.signed_header.header.height, trusted.height));
         }
 
-        Verdict::Success
+        match time_regression {
+            Some(time_regression) => Verdict::SuccessWithTimeRegression(time_regression),
+            None => Verdict::Success,
+        }
     }
 
     /// Check there is enough overlap between the validator sets of the trusted and untrusted
@@ -240,7 +285,8 @@ where
     /// - Validate the untrusted header against the trusted header
     ///     - Ensure the latest trusted header hasn't expired
     ///     - Ensure the header isn't from a future time
-    ///     - Check that the untrusted block is more recent than the trusted state
+    ///     - Check that the untrusted block is more recent than the trusted state, or not older
+    ///       than the `time_monotonicity` of the options tolerates
     ///     - If the untrusted block is the very next block after the trusted block, check that
     ///       their (next) validator sets hashes match.
     ///     - Otherwise, ensure that the untrusted block has a greater height than the trusted
@@ -260,10 +306,13 @@ where
         now: Time,
     ) -> Verdict {
         ensure_verdict_success!(self.verify_validator_sets(&untrusted));
-        ensure_verdict_success!(self.validate_against_trusted(&untrusted, &trusted, options, now));
+        let verdict = self.validate_against_trusted(&untrusted, &trusted, options, now);
+        if !verdict.is_success() {
+            return verdict;
+        }
         ensure_verdict_success!(self.verify_commit_against_trusted(&untrusted, &trusted, options));
         ensure_verdict_success!(self.verify_commit(&untrusted));
-        Verdict::Success
+        verdict
     }
 }
 
@@ -272,7 +321,7 @@ where
 pub type ProdVerifier =
     PredicateVerifier<ProdPredicates, ProdVotingPowerCalculator, ProdCommitValidator>;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rust-crypto"))]
 mod tests {
     use alloc::{borrow::ToOwned, string::ToString};
     use core::{ops::Sub, time::Duration};
@@ -281,11 +330,13 @@ mod tests {
     use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
 
     use crate::{
-        errors::VerificationErrorDetail, options::Options, types::LightBlock, ProdVerifier,
-        Verdict, Verifier,
+        clock::{FixedClock, HostClock},
+        errors::VerificationErrorDetail,
+        options::{Options, TimeMonotonicity},
+        types::LightBlock,
+        ProdVerifier, Verdict, Verifier,
     };
 
-    #[cfg(feature = "rust-crypto")]
     #[derive(Clone, Debug, PartialEq, Eq)]
     struct ProdVerifierSupportsCommonDerivedTraits {
         verifier: ProdVerifier,
@@ -322,6 +373,7 @@ mod tests {
             trust_threshold: Default::default(),
             trusting_period: Duration::from_secs(60),
             clock_drift: Default::default(),
+            time_monotonicity: Default::default(),
         };
 
         let verdict = vp.verify(
@@ -341,4 +393,115 @@ mod tests {
             v => panic!("expected ChainIdMismatch error, got: {:?}", v),
         }
     }
+
+    #[test]
+    fn test_time_regression_tolerated_by_options() {
+        let now = Time::now();
+
+        // Create a trusted block at height `1` with a timestamp 20 secs before now, and an
+        // untrusted one at height `2` whose timestamp is 2 secs before the trusted one
+        let light_block_1: LightBlock = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "chain-1".to_owned(),
+            now.sub(Duration::from_secs(20)).unwrap(),
+            1u64,
+        )
+        .generate()
+        .unwrap()
+        .into();
+        let light_block_2: LightBlock = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "chain-1".to_owned(),
+            now.sub(Duration::from_secs(22)).unwrap(),
+            2u64,
+        )
+        .generate()
+        .unwrap()
+        .into();
+
+        let vp = ProdVerifier::default();
+        let verify = |time_monotonicity| {
+            let opt = Options {
+                trust_threshold: Default::default(),
+                trusting_period: Duration::from_secs(60),
+                clock_drift: Default::default(),
+                time_monotonicity,
+            };
+            vp.verify(
+                light_block_2.as_untrusted_state(),
+                light_block_1.as_trusted_state(),
+                &opt,
+                now,
+            )
+        };
+
+        match verify(TimeMonotonicity::Strict) {
+            Verdict::Invalid(VerificationErrorDetail::NonMonotonicBftTime(_)) => {},
+            v => panic!("expected NonMonotonicBftTime error, got: {:?}", v),
+        }
+
+        let verdict = verify(TimeMonotonicity::Tolerant {
+            max_regression: Duration::from_secs(5),
+        });
+        assert_eq!(
+            verdict,
+            Verdict::SuccessWithTimeRegression(Duration::from_secs(2))
+        );
+
+        match verify(TimeMonotonicity::Tolerant {
+            max_regression: Duration::from_secs(1),
+        }) {
+            Verdict::Invalid(VerificationErrorDetail::NonMonotonicBftTime(_)) => {},
+            v => panic!("expected NonMonotonicBftTime error, got: {:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_verification_against_clock() {
+        let genesis = Time::from_unix_timestamp(1_600_000_000, 0).unwrap();
+        let light_block_1: LightBlock = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "chain-1".to_owned(),
+            genesis,
+            1u64,
+        )
+        .generate()
+        .unwrap()
+        .into();
+        let light_block_2: LightBlock = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "chain-1".to_owned(),
+            (genesis + Duration::from_secs(10)).unwrap(),
+            2u64,
+        )
+        .generate()
+        .unwrap()
+        .into();
+
+        let vp = ProdVerifier::default();
+        let opt = Options {
+            trust_threshold: Default::default(),
+            trusting_period: Duration::from_secs(60),
+            clock_drift: Default::default(),
+            time_monotonicity: Default::default(),
+        };
+
+        // The time of the block following the untrusted one, as a consensus
+        // context would provide it.
+        let block_time = (genesis + Duration::from_secs(20)).unwrap();
+        let verdict = vp.verify_with_clock(
+            light_block_2.as_untrusted_state(),
+            light_block_1.as_trusted_state(),
+            &opt,
+            &HostClock::new(move || block_time),
+        );
+        assert_eq!(verdict, Verdict::Success);
+
+        let verdict = vp.verify_with_clock(
+            light_block_2.as_untrusted_state(),
+            light_block_1.as_trusted_state(),
+            &opt,
+            &FixedClock((genesis + Duration::from_secs(120)).unwrap()),
+        );
+        match verdict {
+            Verdict::Invalid(VerificationErrorDetail::NotWithinTrustPeriod(_)) => {},
+            v => panic!("expected NotWithinTrustPeriod error, got: {:?}", v),
+        }
+    }
 }
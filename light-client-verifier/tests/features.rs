@@ -0,0 +1,102 @@
+//! Smoke tests for the optional features of this crate.
+//!
+//! Every test is gated on the features it exercises, so that this file builds
+//! and passes under every feature combination checked by the workspace
+//! feature matrix (see `tendermint-test`).
+
+use tendermint::{
+    crypto::{sha256::HASH_SIZE, Sha256},
+    merkle::{Hash, MerkleHash, NonIncremental},
+};
+use tendermint_light_client_verifier::{
+    operations::ProdCommitValidator, predicates::VerificationPredicates, types::LightBlock,
+};
+use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
+
+/// A SHA-256 provider standing in for a host-provided implementation, which
+/// is how the verifier is used when the `rust-crypto` feature is disabled.
+#[derive(Default)]
+struct HostSha256(NonIncremental<HostDigest>);
+
+struct HostDigest;
+
+impl Sha256 for HostDigest {
+    fn digest(data: impl AsRef<[u8]>) -> [u8; HASH_SIZE] {
+        <sha2::Sha256 as sha2::Digest>::digest(data.as_ref()).into()
+    }
+}
+
+impl Sha256 for HostSha256 {
+    fn digest(data: impl AsRef<[u8]>) -> [u8; HASH_SIZE] {
+        HostDigest::digest(data)
+    }
+}
+
+impl MerkleHash for HostSha256 {
+    fn empty_hash(&mut self) -> Hash {
+        self.0.empty_hash()
+    }
+
+    fn leaf_hash(&mut self, bytes: &[u8]) -> Hash {
+        self.0.leaf_hash(bytes)
+    }
+
+    fn inner_hash(&mut self, left: Hash, right: Hash) -> Hash {
+        self.0.inner_hash(left, right)
+    }
+}
+
+struct HostPredicates;
+
+impl VerificationPredicates for HostPredicates {
+    type Sha256 = HostSha256;
+}
+
+fn light_block(height: u64) -> LightBlock {
+    let block = TestgenLightBlock::new_default(height).generate().unwrap();
+    LightBlock::new(
+        block.signed_header,
+        block.validators,
+        block.next_validators,
+        block.provider,
+    )
+}
+
+#[test]
+fn predicates_with_host_provided_hasher() {
+    let block = light_block(1);
+    let predicates = HostPredicates;
+
+    predicates
+        .validator_sets_match(
+            &block.validators,
+            block.signed_header.header.validators_hash,
+        )
+        .unwrap();
+    predicates
+        .next_validators_match(
+            &block.next_validators,
+            block.signed_header.header.next_validators_hash,
+        )
+        .unwrap();
+    predicates
+        .header_matches_commit(&block.signed_header)
+        .unwrap();
+    predicates
+        .valid_commit(
+            &block.signed_header,
+            &block.validators,
+            &ProdCommitValidator::default(),
+        )
+        .unwrap();
+}
+
+#[cfg(feature = "rust-crypto")]
+#[test]
+fn prod_verifier_accepts_valid_block() {
+    use tendermint_light_client_verifier::{ProdVerifier, Verdict};
+
+    let block = light_block(1);
+    let verdict = ProdVerifier::default().verify_validator_sets(&block.as_untrusted_state());
+    assert_eq!(verdict, Verdict::Success);
+}
@@ -0,0 +1,93 @@
+//! Runs the time skew test cases generated by `tendermint-testgen` against
+//! the production verifier.
+
+#![cfg(feature = "rust-crypto")]
+
+use tendermint_light_client_verifier::{
+    errors::VerificationErrorDetail,
+    options::Options,
+    types::{LightBlock, TrustedBlockState},
+    ProdVerifier, Verdict, Verifier,
+};
+use tendermint_testgen::{
+    time_skew::{ExpectedVerdict, TimeSkew},
+    Generator, Validator,
+};
+
+fn run(time_skew: TimeSkew) {
+    let batch = time_skew.generate().unwrap();
+    let verifier = ProdVerifier::default();
+
+    for test_case in batch.test_cases {
+        let initial = &test_case.initial;
+        let options = Options {
+            trust_threshold: Default::default(),
+            trusting_period: initial.trusting_period.into(),
+            clock_drift: initial.clock_drift.into(),
+            time_monotonicity: Default::default(),
+        };
+        let header = &initial.signed_header.header;
+        let trusted = || TrustedBlockState {
+            chain_id: &header.chain_id,
+            header_time: header.time,
+            height: header.height,
+            next_validators: &initial.next_validator_set,
+            next_validators_hash: header.next_validators_hash,
+        };
+
+        for (i, step) in test_case.input.into_iter().enumerate() {
+            let block = LightBlock::new(
+                step.block.signed_header,
+                step.block.validators,
+                step.block.next_validators,
+                step.block.provider,
+            );
+            let verdict =
+                verifier.verify(block.as_untrusted_state(), trusted(), &options, step.now);
+            match (step.verdict, &verdict) {
+                (ExpectedVerdict::Success, Verdict::Success)
+                | (
+                    ExpectedVerdict::NotWithinTrustPeriod,
+                    Verdict::Invalid(VerificationErrorDetail::NotWithinTrustPeriod(_)),
+                )
+                | (
+                    ExpectedVerdict::HeaderFromTheFuture,
+                    Verdict::Invalid(VerificationErrorDetail::HeaderFromTheFuture(_)),
+                )
+                | (
+                    ExpectedVerdict::NonMonotonicBftTime,
+                    Verdict::Invalid(VerificationErrorDetail::NonMonotonicBftTime(_)),
+                ) => {},
+                (expected, verdict) => panic!(
+                    "{} (input {}): expected {:?}, got {:?}",
+                    test_case.description, i, expected, verdict
+                ),
+            }
+        }
+    }
+}
+
+#[test]
+fn time_skew_with_defaults() {
+    run(TimeSkew::default());
+}
+
+#[test]
+fn time_skew_with_short_periods() {
+    run(TimeSkew::default()
+        .now(1)
+        .trusting_period(2)
+        .clock_drift(1)
+        .seed(7));
+}
+
+#[test]
+fn time_skew_with_unequal_validators() {
+    run(TimeSkew::new(&[
+        Validator::new("a").voting_power(10),
+        Validator::new("b").voting_power(20),
+        Validator::new("c").voting_power(70),
+    ])
+    .now(600)
+    .clock_drift(0));
+}
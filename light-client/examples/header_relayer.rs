@@ -0,0 +1,340 @@
+//! A minimal IBC-style header relayer.
+//!
+//! The relayer follows chain A with a light client [`Supervisor`], and relays
+//! the headers it verified to a mock light client of chain A hosted on
+//! chain B. Chain B does not trust the relayer: it verifies each header
+//! against the consensus state it stored for an earlier height, the same way
+//! an IBC client does.
+//!
+//! Chain A is generated in memory and served by in-process peers, which
+//! implement the public [`Io`] interface, so this example runs without any
+//! node:
+//!
+//! ```text
+//! cargo run -p tendermint-light-client --example header_relayer
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tendermint::{block::signed_header::SignedHeader, chain, validator, Hash};
+use tendermint_light_client::{
+    builder::{LightClientBuilder, SupervisorBuilder},
+    components::{
+        clock::FixedClock,
+        io::{AsyncIo, AtHeight, IoError},
+        scheduler,
+    },
+    evidence::{Evidence, EvidenceReporter},
+    fork_detector::ProdForkDetector,
+    store::memory::MemoryStore,
+    supervisor::{Handle as _, Instance, Supervisor},
+    verifier::{
+        options::Options,
+        predicates::ProdPredicates,
+        types::{Height, LightBlock, PeerId, Time, TrustedBlockState, UntrustedBlockState},
+        ProdVerifier, Verifier,
+    },
+};
+use tendermint_rpc as rpc;
+use tendermint_testgen::{
+    helpers::get_time,
+    light_block::{default_peer_id, TmLightBlock},
+    Generator, LightChain,
+};
+
+/// The length of chain A.
+const CHAIN_LENGTH: u64 = 20;
+
+/// The heights of chain A whose headers are relayed to chain B.
+const RELAYED_HEIGHTS: [u64; 4] = [4, 9, 15, 20];
+
+fn main() {
+    let now = get_time(CHAIN_LENGTH + 1).unwrap();
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 14),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+
+    // The light blocks of chain A, as served by its full nodes.
+    let chain_a: Vec<LightBlock> = LightChain::default_with_length(CHAIN_LENGTH)
+        .light_blocks
+        .into_iter()
+        .map(|lb| lb.generate().unwrap())
+        .map(into_light_block)
+        .collect();
+    let genesis = chain_a[0].clone();
+
+    // The relayer follows chain A from its first block, trusted out of band.
+    let supervisor = make_supervisor(&chain_a, options, now);
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    // Chain B hosts a client of chain A, created from the same first block.
+    let mut chain_b = ChainB::new(MockClient::new(&genesis, options));
+
+    let mut last_relayed = genesis;
+    for height in RELAYED_HEIGHTS {
+        let height = Height::try_from(height).unwrap();
+
+        // Only relay the headers the light client verified...
+        let verified = handle.verify_to_target(height).unwrap();
+        println!("[relayer] verified header of chain A at height {height}");
+
+        // ...along with the validators trusted by the client on chain B.
+        let msg = MsgUpdateClient {
+            signed_header: verified.signed_header.clone(),
+            validators: verified.validators.clone(),
+            trusted_height: last_relayed.height(),
+            trusted_validators: last_relayed.next_validators.clone(),
+        };
+        let tx = serde_json::to_vec(&msg).unwrap();
+
+        match chain_b.deliver_tx(&tx, now) {
+            Ok(height) => println!("[chain B] updated the client of chain A to height {height}"),
+            Err(e) => panic!("[chain B] rejected the header at height {height}: {e}"),
+        }
+
+        last_relayed = verified;
+    }
+
+    // Chain B now knows the state of chain A at the relayed heights.
+    let heights: Vec<_> = chain_b
+        .client
+        .consensus_states
+        .keys()
+        .map(Height::value)
+        .collect();
+    let latest = chain_b.client.latest_height;
+    println!(
+        "[chain B] consensus states of chain A at heights {heights:?}, the latest at {}",
+        chain_b.client.consensus_states[&latest].time
+    );
+
+    handle.terminate().unwrap();
+}
+
+/// The message updating the client of chain A on chain B, as in IBC.
+#[derive(Serialize, Deserialize)]
+struct MsgUpdateClient {
+    /// The new header of chain A, along with its commit
+    signed_header: SignedHeader,
+    /// The validators which signed the new header
+    validators: validator::Set,
+    /// The height of the consensus state to verify the new header against
+    trusted_height: Height,
+    /// The next validators of the block at the trusted height
+    trusted_validators: validator::Set,
+}
+
+/// What the client of chain A stores about each header it accepted.
+struct ConsensusState {
+    time: Time,
+    next_validators_hash: Hash,
+}
+
+/// A light client of chain A, as hosted on chain B.
+struct MockClient {
+    chain_id: chain::Id,
+    options: Options,
+    latest_height: Height,
+    consensus_states: BTreeMap<Height, ConsensusState>,
+}
+
+impl MockClient {
+    fn new(trusted: &LightBlock, options: Options) -> Self {
+        let header = &trusted.signed_header.header;
+        let consensus_state = ConsensusState {
+            time: header.time,
+            next_validators_hash: header.next_validators_hash,
+        };
+
+        Self {
+            chain_id: header.chain_id.clone(),
+            options,
+            latest_height: header.height,
+            consensus_states: [(header.height, consensus_state)].into_iter().collect(),
+        }
+    }
+
+    /// Verify the new header against the stored consensus state at the
+    /// trusted height, and store the consensus state of the new header.
+    fn update(&mut self, msg: MsgUpdateClient, now: Time) -> Result<Height, String> {
+        let trusted = self
+            .consensus_states
+            .get(&msg.trusted_height)
+            .ok_or_else(|| format!("no consensus state at height {}", msg.trusted_height))?;
+
+        let trusted_validators_hash = msg.trusted_validators.hash();
+        if trusted_validators_hash != trusted.next_validators_hash {
+            return Err("the trusted validators do not match the consensus state".to_string());
+        }
+
+        let verdict = ProdVerifier::default().verify(
+            UntrustedBlockState {
+                signed_header: &msg.signed_header,
+                validators: &msg.validators,
+                next_validators: None,
+            },
+            TrustedBlockState {
+                chain_id: &self.chain_id,
+                header_time: trusted.time,
+                height: msg.trusted_height,
+                next_validators: &msg.trusted_validators,
+                next_validators_hash: trusted_validators_hash,
+            },
+            &self.options,
+            now,
+        );
+        if !verdict.is_success() {
+            return Err(format!("verification failed: {verdict:?}"));
+        }
+
+        let header = msg.signed_header.header;
+        self.consensus_states.insert(
+            header.height,
+            ConsensusState {
+                time: header.time,
+                next_validators_hash: header.next_validators_hash,
+            },
+        );
+        self.latest_height = self.latest_height.max(header.height);
+
+        Ok(header.height)
+    }
+}
+
+/// Chain B, reduced to delivering the transactions updating its client of
+/// chain A.
+struct ChainB {
+    client: MockClient,
+}
+
+impl ChainB {
+    fn new(client: MockClient) -> Self {
+        Self { client }
+    }
+
+    fn deliver_tx(&mut self, tx: &[u8], now: Time) -> Result<Height, String> {
+        let msg = serde_json::from_slice(tx).map_err(|e| format!("malformed message: {e}"))?;
+        self.client.update(msg, now)
+    }
+}
+
+/// A supervisor following chain A with a primary and a witness, both
+/// serving the given light blocks.
+fn make_supervisor(chain_a: &[LightBlock], options: Options, now: Time) -> Supervisor {
+    let primary_id = default_peer_id();
+    let witness_id = PeerId::new([0xAA; 20]);
+
+    let primary = make_instance(primary_id, chain_a.to_vec(), options, now);
+    let witness = make_instance(
+        witness_id,
+        chain_a
+            .iter()
+            .cloned()
+            .map(|lb| LightBlock {
+                provider: witness_id,
+                ..lb
+            })
+            .collect(),
+        options,
+        now,
+    );
+
+    // The peers have no address, their RPC endpoints are never used.
+    let address: tendermint_rpc::Url = "http://127.0.0.1:26657".parse().unwrap();
+    let (instances, _addresses) = SupervisorBuilder::new()
+        .primary(primary_id, address.clone(), primary)
+        .witness(witness_id, address, witness)
+        .inner();
+
+    Supervisor::new(instances, ProdForkDetector::default(), PanicReporter)
+}
+
+/// A full node of chain A, serving the given light blocks from memory.
+struct Peer {
+    light_blocks: HashMap<Height, LightBlock>,
+    latest_height: Height,
+}
+
+impl Peer {
+    fn new(light_blocks: Vec<LightBlock>) -> Self {
+        let latest_height = light_blocks.iter().map(LightBlock::height).max().unwrap();
+        let light_blocks = light_blocks
+            .into_iter()
+            .map(|lb| (lb.height(), lb))
+            .collect();
+
+        Self {
+            light_blocks,
+            latest_height,
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncIo for Peer {
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        let height = match height {
+            AtHeight::Highest => self.latest_height,
+            AtHeight::At(height) => height,
+        };
+
+        self.light_blocks.get(&height).cloned().ok_or_else(|| {
+            IoError::rpc(rpc::Error::response(
+                rpc::response_error::ResponseError::new((-32600).into(), None),
+            ))
+        })
+    }
+}
+
+/// The peers of chain A all serve the same blocks, so the supervisor never
+/// finds evidence of an attack to report.
+struct PanicReporter;
+
+#[async_trait]
+impl EvidenceReporter for PanicReporter {
+    async fn report(&self, evidence: Evidence, peer: PeerId) -> Result<Hash, IoError> {
+        panic!("unexpected fork detected for peer {peer} with evidence: {evidence:?}");
+    }
+}
+
+fn make_instance(
+    peer_id: PeerId,
+    light_blocks: Vec<LightBlock>,
+    options: Options,
+    now: Time,
+) -> Instance {
+    let trusted = &light_blocks[0].signed_header.header;
+    let (trusted_height, trusted_hash) = (trusted.height, trusted.hash());
+
+    LightClientBuilder::custom(
+        peer_id,
+        options,
+        Box::new(MemoryStore::new()),
+        Box::new(Peer::new(light_blocks)),
+        Box::new(FixedClock(now)),
+        Box::<ProdVerifier>::default(),
+        Box::new(scheduler::basic_bisecting_schedule),
+        Box::new(ProdPredicates),
+    )
+    .trust_primary_at(trusted_height, trusted_hash)
+    .unwrap()
+    .build()
+}
+
+fn into_light_block(tm_lb: TmLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
@@ -38,8 +38,19 @@ define_error! {
         NoTrustedStateInStore
             | _ | { "no trusted state in store" },
 
+        BrokenAncestry
+            { height: Height }
+            | e | {
+                format_args!("the snapshot block at height {0} is not the parent of the block above it",
+                    e.height)
+            },
+
         EmptyWitnessList
             | _ | { "empty witness list" },
 
+        InvalidWitnessPolicy
+            [ crate::errors::Error ]
+            | _ | { "invalid witness policy" },
+
     }
 }
@@ -14,15 +14,18 @@ use {
 
 use crate::{
     builder::error::Error,
+    checkpoint::Checkpoints,
     components::{
         clock::Clock,
-        io::{AtHeight, Io},
+        io::{AsyncIo, AtHeight},
         scheduler::Scheduler,
     },
-    light_client::LightClient,
+    light_client::{ChainInfo, LightClient},
+    snapshot::TrustedStateSnapshot,
     state::{State, VerificationTrace},
     store::LightStore,
     supervisor::Instance,
+    utils::run_blocking,
     verifier::{
         options::Options,
         predicates::VerificationPredicates,
@@ -42,12 +45,14 @@ pub struct HasTrustedState;
 pub struct LightClientBuilder<State, H: MerkleHash + Sha256 + Default> {
     peer_id: PeerId,
     options: Options,
-    io: Box<dyn Io>,
+    io: Box<dyn AsyncIo>,
     clock: Box<dyn Clock>,
     verifier: Box<dyn Verifier>,
     scheduler: Box<dyn Scheduler>,
     predicates: Box<dyn VerificationPredicates<Sha256 = H>>,
     light_store: Box<dyn LightStore>,
+    chain_info: Option<ChainInfo>,
+    checkpoints: Checkpoints,
 
     #[allow(dead_code)]
     state: State,
@@ -68,9 +73,25 @@ where
             scheduler: self.scheduler,
             predicates: self.predicates,
             light_store: self.light_store,
+            chain_info: self.chain_info,
+            checkpoints: self.checkpoints,
             state,
         }
     }
+
+    /// Set the initial height and genesis time of the chain, so that the
+    /// light client rejects targets which cannot exist on the chain.
+    pub fn chain_info(mut self, chain_info: ChainInfo) -> Self {
+        self.chain_info = Some(chain_info);
+        self
+    }
+
+    /// Set the checkpoints to fast-forward the trusted state to, see
+    /// [`LightClient::fast_forward`].
+    pub fn checkpoints(mut self, checkpoints: Checkpoints) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
 }
 
 #[cfg(feature = "rpc-client")]
@@ -108,7 +129,7 @@ where
         peer_id: PeerId,
         options: Options,
         light_store: Box<dyn LightStore>,
-        io: Box<dyn Io>,
+        io: Box<dyn AsyncIo>,
         clock: Box<dyn Clock>,
         verifier: Box<dyn Verifier>,
         scheduler: Box<dyn Scheduler>,
@@ -123,6 +144,8 @@ where
             scheduler,
             options,
             predicates,
+            chain_info: None,
+            checkpoints: Checkpoints::new(),
             state: NoTrustedState,
         }
     }
@@ -152,14 +175,27 @@ where
     }
 
     /// Set the block from the primary peer at the given height as the trusted state.
+    ///
+    /// Blocking variant of [`LightClientBuilder::trust_primary_at_async`].
     pub fn trust_primary_at(
         self,
         trusted_height: Height,
         trusted_hash: Hash,
+    ) -> Result<LightClientBuilder<HasTrustedState, H>, Error> {
+        run_blocking(self.trust_primary_at_async(trusted_height, trusted_hash))
+            .map_err(Error::io)?
+    }
+
+    /// Set the block from the primary peer at the given height as the trusted state.
+    pub async fn trust_primary_at_async(
+        self,
+        trusted_height: Height,
+        trusted_hash: Hash,
     ) -> Result<LightClientBuilder<HasTrustedState, H>, Error> {
         let trusted_state = self
             .io
             .fetch_light_block(AtHeight::At(trusted_height))
+            .await
             .map_err(Error::io)?;
 
         if trusted_state.height() != trusted_height {
@@ -169,12 +205,58 @@ where
             ));
         }
 
-        let header_hash = trusted_state.signed_header.header.hash_with::<H>();
+        let header_hash = trusted_state.signed_header.header_hash_with::<H>();
+
+        if header_hash != trusted_hash {
+            return Err(Error::hash_mismatch(trusted_hash, header_hash));
+        }
+
+        self.trust_light_block(trusted_state)
+    }
+
+    /// Set the trusted light block of the given snapshot as the trusted
+    /// state, and its ancestors as trusted too.
+    ///
+    /// The `trusted_hash` of the header of the trusted light block must be
+    /// obtained out of band, as the snapshot is only as trustworthy as
+    /// this hash.
+    pub fn trust_snapshot(
+        mut self,
+        snapshot: TrustedStateSnapshot,
+        trusted_hash: Hash,
+    ) -> Result<LightClientBuilder<HasTrustedState, H>, Error> {
+        let trusted_state = snapshot.trusted;
 
+        let header_hash = trusted_state.signed_header.header_hash_with::<H>();
         if header_hash != trusted_hash {
             return Err(Error::hash_mismatch(trusted_hash, header_hash));
         }
 
+        self.predicates
+            .header_matches_commit(&trusted_state.signed_header)
+            .map_err(Error::invalid_light_block)?;
+
+        let mut child = &trusted_state;
+        for ancestor in &snapshot.ancestry {
+            let parent_hash = child
+                .signed_header
+                .header
+                .last_block_id
+                .map(|block_id| block_id.hash);
+            let is_parent = ancestor.height().increment() == child.height()
+                && Some(ancestor.signed_header.header_hash_with::<H>()) == parent_hash;
+            if !is_parent {
+                return Err(Error::broken_ancestry(ancestor.height()));
+            }
+
+            self.validate(ancestor)?;
+            child = ancestor;
+        }
+
+        for ancestor in snapshot.ancestry {
+            self.light_store.insert(ancestor, Status::Trusted);
+        }
+
         self.trust_light_block(trusted_state)
     }
 
@@ -220,7 +302,7 @@ where
             verification_trace: VerificationTrace::new(),
         };
 
-        let light_client = LightClient::from_boxed(
+        let mut light_client = LightClient::from_boxed(
             self.peer_id,
             self.options,
             self.clock,
@@ -228,6 +310,8 @@ where
             self.verifier,
             self.io,
         );
+        light_client.chain_info = self.chain_info;
+        light_client.checkpoints = self.checkpoints;
 
         Instance::new(light_client, state)
     }
@@ -9,7 +9,8 @@ use {
 use crate::{
     builder::error::Error,
     peer_list::{PeerList, PeerListBuilder},
-    supervisor::Instance,
+    pruning::PruningPolicy,
+    supervisor::{Instance, RefreshPolicy, WitnessPolicy},
     verifier::types::PeerId,
 };
 
@@ -23,6 +24,9 @@ pub struct SupervisorBuilder<State> {
     instances: PeerListBuilder<Instance>,
     addresses: PeerListBuilder<tendermint_rpc::Url>,
     evidence_reporting_timeout: Option<Duration>,
+    refresh_policy: Option<RefreshPolicy>,
+    witness_policy: WitnessPolicy,
+    pruning_policy: Option<PruningPolicy>,
     #[allow(dead_code)]
     state: State,
 }
@@ -34,6 +38,9 @@ impl<Current> SupervisorBuilder<Current> {
             instances: self.instances,
             addresses: self.addresses,
             evidence_reporting_timeout: self.evidence_reporting_timeout,
+            refresh_policy: self.refresh_policy,
+            witness_policy: self.witness_policy,
+            pruning_policy: self.pruning_policy,
             state,
         }
     }
@@ -43,6 +50,27 @@ impl<Current> SupervisorBuilder<Current> {
         self.evidence_reporting_timeout = timeout;
         self
     }
+
+    /// Proactively refresh the trusted state according to the given policy
+    pub fn refresh_policy(mut self, policy: RefreshPolicy) -> Self {
+        self.refresh_policy = Some(policy);
+        self
+    }
+
+    /// Manage the witnesses according to the given policy
+    ///
+    /// Fails if the policy is invalid (see [`WitnessPolicy::validate`]).
+    pub fn witness_policy(mut self, policy: WitnessPolicy) -> Result<Self, Error> {
+        policy.validate().map_err(Error::invalid_witness_policy)?;
+        self.witness_policy = policy;
+        Ok(self)
+    }
+
+    /// Prune the light store of the primary according to the given policy
+    pub fn pruning_policy(mut self, policy: PruningPolicy) -> Self {
+        self.pruning_policy = Some(policy);
+        self
+    }
 }
 
 impl Default for SupervisorBuilder<Init> {
@@ -58,6 +86,9 @@ impl SupervisorBuilder<Init> {
             instances: PeerListBuilder::default(),
             addresses: PeerListBuilder::default(),
             evidence_reporting_timeout: None,
+            refresh_policy: None,
+            witness_policy: WitnessPolicy::default(),
+            pruning_policy: None,
             state: Init,
         }
     }
@@ -115,13 +146,26 @@ impl SupervisorBuilder<Done> {
     #[cfg(feature = "rpc-client")]
     pub fn build_prod(self) -> Supervisor {
         let timeout = self.evidence_reporting_timeout;
+        let refresh_policy = self.refresh_policy;
+        let witness_policy = self.witness_policy;
+        let pruning_policy = self.pruning_policy;
         let (instances, addresses) = self.inner();
 
-        Supervisor::new(
+        let supervisor = Supervisor::new(
             instances,
             ProdForkDetector::default(),
             ProdEvidenceReporter::new(addresses.into_values(), timeout),
         )
+        .witness_policy(witness_policy)
+        .expect("the witness policy was validated by the builder");
+        let supervisor = match refresh_policy {
+            Some(policy) => supervisor.refresh_policy(policy),
+            None => supervisor,
+        };
+        match pruning_policy {
+            Some(policy) => supervisor.pruning_policy(policy),
+            None => supervisor,
+        }
     }
 
     /// Get the underlying list of instances and addresses.
@@ -0,0 +1,77 @@
+//! Checkpoints pinning the hashes of the headers at known heights.
+//!
+//! A checkpoint is a height along with the hash of the header of the block
+//! at that height, obtained out of band from a source the user trusts, e.g.
+//! a block explorer or the documentation of the chain. A light client given
+//! checkpoints fast-forwards its trusted state to the highest checkpoint
+//! below its target, instead of verifying the blocks in between: see
+//! [`LightClient::fast_forward`](crate::light_client::LightClient::fast_forward).
+//!
+//! The blocks trusted this way are recorded in the light store with the
+//! [`Provenance::Checkpoint`](crate::store::Provenance::Checkpoint)
+//! provenance.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::verifier::types::{Hash, Height};
+
+/// A set of checkpoints, mapping heights to the hash of the header at that
+/// height.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Checkpoints {
+    hashes: BTreeMap<Height, Hash>,
+}
+
+impl Checkpoints {
+    /// An empty set of checkpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin the hash of the header at the given height, replacing the one
+    /// pinned before, if any.
+    pub fn insert(&mut self, height: Height, hash: Hash) -> Option<Hash> {
+        self.hashes.insert(height, hash)
+    }
+
+    /// The hash pinned at the given height, if any.
+    pub fn get(&self, height: Height) -> Option<Hash> {
+        self.hashes.get(&height).copied()
+    }
+
+    /// The highest checkpoint above `above` (if any) and at most at
+    /// `up_to`.
+    pub fn highest_between(&self, above: Option<Height>, up_to: Height) -> Option<(Height, Hash)> {
+        self.hashes
+            .range(..=up_to)
+            .next_back()
+            .filter(|(&height, _)| Some(height) > above)
+            .map(|(&height, &hash)| (height, hash))
+    }
+
+    /// The checkpoints, in ascending order of height.
+    pub fn iter(&self) -> impl Iterator<Item = (Height, Hash)> + '_ {
+        self.hashes.iter().map(|(&height, &hash)| (height, hash))
+    }
+
+    /// The number of checkpoints.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Whether there is no checkpoint.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+impl FromIterator<(Height, Hash)> for Checkpoints {
+    fn from_iter<I: IntoIterator<Item = (Height, Hash)>>(iter: I) -> Self {
+        Self {
+            hashes: iter.into_iter().collect(),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::time::Duration;
 
+use async_trait::async_trait;
 use flex_error::{define_error, TraceError};
 use tendermint_rpc as rpc;
 #[cfg(feature = "rpc-client")]
@@ -16,6 +17,7 @@ type TimeoutError = flex_error::DisplayOnly<tokio::time::error::Elapsed>;
 type TimeoutError = flex_error::NoSource;
 
 /// Type for selecting either a specific height or the latest one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AtHeight {
     /// A specific height
     At(Height),
@@ -61,6 +63,10 @@ define_error! {
             [ TraceError<std::io::Error> ]
             | _ | { "failed to initialize runtime" },
 
+        InvalidGenesis
+            [ tendermint::Error ]
+            | _ | { "fetched genesis is invalid" },
+
     }
 }
 
@@ -74,7 +80,12 @@ impl IoErrorDetail {
     }
 }
 
-/// Interface for fetching light blocks from a full node, typically via the RPC client.
+/// Interface for fetching light blocks from a full node, typically via the RPC client,
+/// blocking the calling thread.
+///
+/// The [`LightClient`](crate::light_client::LightClient) fetches its light
+/// blocks with an [`AsyncIo`]: this interface is meant for fetching them
+/// outside of an async runtime.
 pub trait Io: Send + Sync {
     /// Fetch a light block at the given height from a peer
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError>;
@@ -89,12 +100,53 @@ where
     }
 }
 
+/// Interface for fetching light blocks from a full node without blocking,
+/// typically via the RPC client.
+///
+/// This is the interface through which the
+/// [`LightClient`](crate::light_client::LightClient) fetches its light blocks.
+/// The futures it returns are `Send`, so that they can be spawned onto
+/// multi-threaded runtimes.
+#[async_trait]
+pub trait AsyncIo: Send + Sync {
+    /// Fetch a light block at the given height from a peer
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError>;
+}
+
+#[async_trait]
+impl<F: Send + Sync> AsyncIo for F
+where
+    F: Fn(AtHeight) -> Result<LightBlock, IoError>,
+{
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        self(height)
+    }
+}
+
+/// Variant of [`AsyncIo`] whose futures need not be `Send`, nor its
+/// implementations `Send` and `Sync`, e.g. to fetch light blocks with the
+/// `fetch` API of browsers on `wasm32` targets.
+///
+/// Every [`AsyncIo`] implementation is a `LocalAsyncIo` as well.
+#[async_trait(?Send)]
+pub trait LocalAsyncIo {
+    /// Fetch a light block at the given height from a peer
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError>;
+}
+
+#[async_trait(?Send)]
+impl<T: AsyncIo + ?Sized> LocalAsyncIo for T {
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        AsyncIo::fetch_light_block(self, height).await
+    }
+}
+
 #[cfg(feature = "rpc-client")]
 pub use self::prod::ProdIo;
 
 #[cfg(feature = "rpc-client")]
 mod prod {
-    use std::time::Duration;
+    use std::{future::Future, time::Duration};
 
     use tendermint::{
         account::Id as TMAccountId, block::signed_header::SignedHeader as TMSignedHeader,
@@ -103,10 +155,13 @@ mod prod {
     use tendermint_rpc::Paging;
 
     use super::*;
-    use crate::{utils::block_on, verifier::types::PeerId};
+    use crate::{light_client::ChainInfo, utils::block_on, verifier::types::PeerId};
 
     /// Production implementation of the Io component, which fetches
     /// light blocks from full nodes via RPC.
+    ///
+    /// It implements both [`AsyncIo`], natively, and [`Io`], by running the
+    /// former on a thread and runtime of its own.
     #[derive(Clone, Debug)]
     pub struct ProdIo {
         peer_id: PeerId,
@@ -114,14 +169,19 @@ mod prod {
         timeout: Option<Duration>,
     }
 
-    impl Io for ProdIo {
-        fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
-            let signed_header = self.fetch_signed_header(height)?;
+    #[async_trait]
+    impl AsyncIo for ProdIo {
+        async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+            let signed_header = self.fetch_signed_header(height).await?;
             let height = signed_header.header.height;
             let proposer_address = signed_header.header.proposer_address;
 
-            let validator_set = self.fetch_validator_set(height.into(), Some(proposer_address))?;
-            let next_validator_set = self.fetch_validator_set(height.increment().into(), None)?;
+            let validator_set = self
+                .fetch_validator_set(height.into(), Some(proposer_address))
+                .await?;
+            let next_validator_set = self
+                .fetch_validator_set(height.increment().into(), None)
+                .await?;
 
             let light_block = LightBlock::new(
                 signed_header,
@@ -134,6 +194,12 @@ mod prod {
         }
     }
 
+    impl Io for ProdIo {
+        fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+            block_on(None, AsyncIo::fetch_light_block(self, height))?
+        }
+    }
+
     impl ProdIo {
         /// Constructs a new ProdIo component.
         ///
@@ -151,14 +217,41 @@ mod prod {
             }
         }
 
-        fn fetch_signed_header(&self, height: AtHeight) -> Result<TMSignedHeader, IoError> {
-            let client = self.rpc_client.clone();
-            let res = block_on(self.timeout, async move {
-                match height {
-                    AtHeight::Highest => client.latest_commit().await,
-                    AtHeight::At(height) => client.commit(height).await,
-                }
-            })?;
+        /// Fetch the initial height and genesis time of the chain from the
+        /// `/genesis` endpoint of the node.
+        pub fn fetch_chain_info(&self) -> Result<ChainInfo, IoError> {
+            block_on(None, self.fetch_chain_info_async())?
+        }
+
+        /// Fetch the initial height and genesis time of the chain from the
+        /// `/genesis` endpoint of the node, without blocking.
+        pub async fn fetch_chain_info_async(&self) -> Result<ChainInfo, IoError> {
+            let genesis = self
+                .with_timeout(self.rpc_client.genesis::<serde_json::Value>())
+                .await?
+                .map_err(IoError::rpc)?;
+
+            ChainInfo::try_from(&genesis).map_err(IoError::invalid_genesis)
+        }
+
+        async fn with_timeout<F: Future>(&self, f: F) -> Result<F::Output, IoError> {
+            match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, f)
+                    .await
+                    .map_err(|e| IoError::timeout(timeout, e)),
+                None => Ok(f.await),
+            }
+        }
+
+        async fn fetch_signed_header(&self, height: AtHeight) -> Result<TMSignedHeader, IoError> {
+            let res = self
+                .with_timeout(async {
+                    match height {
+                        AtHeight::Highest => self.rpc_client.latest_commit().await,
+                        AtHeight::At(height) => self.rpc_client.commit(height).await,
+                    }
+                })
+                .await?;
 
             match res {
                 Ok(response) => Ok(response.signed_header),
@@ -166,7 +259,7 @@ mod prod {
             }
         }
 
-        fn fetch_validator_set(
+        async fn fetch_validator_set(
             &self,
             height: AtHeight,
             proposer_address: Option<TMAccountId>,
@@ -178,18 +271,18 @@ mod prod {
                 AtHeight::At(height) => height,
             };
 
-            let client = self.rpc_client.clone();
-            let response = block_on(self.timeout, async move {
-                client.validators(height, Paging::All).await
-            })?
-            .map_err(IoError::rpc)?;
+            let response = self
+                .with_timeout(self.rpc_client.validators(height, Paging::All))
+                .await?
+                .map_err(IoError::rpc)?;
 
             let validator_set = match proposer_address {
                 Some(proposer_address) => {
                     TMValidatorSet::with_proposer(response.validators, proposer_address)
                         .map_err(IoError::invalid_validator_set)?
                 },
-                None => TMValidatorSet::without_proposer(response.validators),
+                None => TMValidatorSet::without_proposer(response.validators)
+                    .map_err(IoError::invalid_validator_set)?,
             };
 
             Ok(validator_set)
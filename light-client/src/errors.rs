@@ -13,7 +13,7 @@ use crate::{
         errors::VerificationErrorDetail,
         operations::voting_power::VotingPowerTally,
         options::Options,
-        types::{Hash, Height, LightBlock, PeerId, Status},
+        types::{Hash, Height, LightBlock, PeerId, Status, Time},
     },
 };
 
@@ -39,6 +39,26 @@ define_error! {
         NoWitnessesLeft
             | _ | { "no witnesses left" },
 
+        NotEnoughWitnesses
+            {
+                available: usize,
+                required: usize,
+            }
+            | e | {
+                format_args!("not enough witnesses: {0} available, {1} required",
+                    e.available, e.required)
+            },
+
+        NoQuorum
+            {
+                agreeing: usize,
+                quorum: usize,
+            }
+            | e | {
+                format_args!("only {0} witnesses agree with the primary, {1} required",
+                    e.agreeing, e.quorum)
+            },
+
         ForkDetected
             { peers: Vec<PeerId> }
             | e | {
@@ -56,6 +76,27 @@ define_error! {
                     e.status)
             },
 
+        HeightBelowInitial
+            {
+                target_height: Height,
+                initial_height: Height,
+            }
+            | e | {
+                format_args!("target height ({0}) is lower than the initial height of the chain ({1})",
+                    e.target_height, e.initial_height)
+            },
+
+        TimeBeforeGenesis
+            {
+                height: Height,
+                time: Time,
+                genesis_time: Time,
+            }
+            | e | {
+                format_args!("block at height {0} has time {1} before the genesis time of the chain ({2})",
+                    e.height, e.time, e.genesis_time)
+            },
+
         TargetLowerThanTrustedState
             {
                 target_height: Height,
@@ -66,13 +107,23 @@ define_error! {
                     e.target_height, e.trusted_height)
             },
 
+        NoTrustedStateAbove
+            { target_height: Height }
+            | e | {
+                format_args!("no trusted state above the target height ({0}) to verify backwards from",
+                    e.target_height)
+            },
+
         TrustedStateOutsideTrustingPeriod
             {
                 trusted_state: Box<LightBlock>,
                 options: Options,
+                expires_at: Time,
+                now: Time,
             }
-            | _ | {
-                format_args!("trusted state outside of trusting period")
+            | e | {
+                format_args!("trusted state outside of trusting period: expires_at={0} now={1}",
+                    e.expires_at, e.now)
             },
 
         BisectionFailed
@@ -89,6 +140,21 @@ define_error! {
             [ DisplayError<VerificationErrorDetail> ]
             | _ | { "invalid light block" },
 
+        InvalidEvidence
+            [ tendermint::Error ]
+            | _ | { "invalid evidence" },
+
+        CheckpointMismatch
+            {
+                height: Height,
+                expected: Hash,
+                got: Hash,
+            }
+            | e | {
+                format_args!("header at height {0} does not match its checkpoint: {1} != {2}",
+                    e.height, e.got, e.expected)
+            },
+
         InvalidAdjacentHeaders
             {
                 hash1: Hash,
@@ -106,6 +172,13 @@ define_error! {
                     e.height)
             },
 
+        InvalidWitnessPolicy
+            { reason: String }
+            | e | {
+                format_args!("invalid witness policy: {0}",
+                    e.reason)
+            },
+
         ChannelDisconnected
             | _ | { "internal channel disconnected" },
 
@@ -117,6 +190,90 @@ define_error! {
             [ TraceError<serde_cbor::Error> ]
             | _ | { "serde cbor error" },
 
+        InvalidStoreVersion
+            { bytes: Vec<u8> }
+            | e | {
+                format_args!("invalid light store schema version: {0:?}",
+                    e.bytes)
+            },
+
+        UnsupportedStoreVersion
+            {
+                version: u32,
+                supported: u32,
+            }
+            | e | {
+                format_args!("light store schema version {0} is newer than the supported version {1}",
+                    e.version, e.supported)
+            },
+
+        AbciQuery
+            {
+                code: u32,
+                log: String,
+            }
+            | e | {
+                format_args!("abci query failed with code {0}: {1}",
+                    e.code, e.log)
+            },
+
+        MissingAbciProof
+            { height: Height }
+            | e | {
+                format_args!("no proof of the abci query result at height {0}",
+                    e.height)
+            },
+
+        InvalidAbciProof
+            { height: Height }
+            [ tendermint::Error ]
+            | e | {
+                format_args!("invalid proof of the abci query result at height {0}",
+                    e.height)
+            },
+
+        UnsupportedAbciProof
+            {
+                height: Height,
+                proof_type: String,
+            }
+            | e | {
+                format_args!("unsupported proof operation of type {0} for the abci query result at height {1}",
+                    e.proof_type, e.height)
+            },
+
+        MissingTxProof
+            { height: Height }
+            | e | {
+                format_args!("no proof of the inclusion of the transaction at height {0}",
+                    e.height)
+            },
+
+        InvalidTxProof
+            { height: Height }
+            [ tendermint::Error ]
+            | e | {
+                format_args!("invalid proof of the inclusion of the transaction at height {0}",
+                    e.height)
+            },
+
+        MismatchedTx
+            {
+                height: Height,
+                index: u32,
+            }
+            | e | {
+                format_args!("the full node reported the transaction at another position than index {1} at height {0}",
+                    e.height, e.index)
+            },
+
+        MismatchedBlock
+            { height: Height }
+            | e | {
+                format_args!("the block at height {0} does not match the header verified by the light client",
+                    e.height)
+            },
+
     }
 }
 
@@ -148,11 +305,15 @@ impl ErrorExt for ErrorDetail {
 }
 
 impl Error {
-    pub fn send<T>(_e: crossbeam::SendError<T>) -> Error {
+    pub fn send<T>(_e: futures::channel::mpsc::TrySendError<T>) -> Error {
         Error::channel_disconnected()
     }
 
     pub fn recv(_e: crossbeam::RecvError) -> Error {
         Error::channel_disconnected()
     }
+
+    pub fn canceled(_e: futures::channel::oneshot::Canceled) -> Error {
+        Error::channel_disconnected()
+    }
 }
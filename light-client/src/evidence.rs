@@ -1,17 +1,45 @@
 //! Fork evidence data structures and interfaces.
 
-use contracts::contract_trait;
+use async_trait::async_trait;
 pub use tendermint::evidence::Evidence;
-use tendermint::Hash;
+use tendermint::{evidence::LightClientAttackEvidence, Hash};
 
 use crate::{components::io::IoError, verifier::types::PeerId};
 
+pub mod queue;
+
 /// Interface for reporting evidence to full nodes, typically via the RPC client.
-#[contract_trait]
-#[allow(missing_docs)] // This is required because of the `contracts` crate (TODO: open/link issue)
+#[async_trait]
 pub trait EvidenceReporter: Send + Sync {
     /// Report evidence to all connected full nodes.
-    fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError>;
+    async fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError>;
+}
+
+/// Decides whether the evidence of a light client attack found by the
+/// [`Supervisor`](crate::supervisor::Supervisor) is submitted to the given
+/// peer, e.g. to have an operator approve it manually.
+pub trait EvidencePolicy: Send + Sync {
+    /// Whether to submit the given evidence to the given peer.
+    fn approve(&self, evidence: &LightClientAttackEvidence, peer: PeerId) -> bool;
+}
+
+impl<F: Send + Sync> EvidencePolicy for F
+where
+    F: Fn(&LightClientAttackEvidence, PeerId) -> bool,
+{
+    fn approve(&self, evidence: &LightClientAttackEvidence, peer: PeerId) -> bool {
+        self(evidence, peer)
+    }
+}
+
+/// The default [`EvidencePolicy`], which submits all the evidence.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SubmitAll;
+
+impl EvidencePolicy for SubmitAll {
+    fn approve(&self, _evidence: &LightClientAttackEvidence, _peer: PeerId) -> bool {
+        true
+    }
 }
 
 #[cfg(feature = "rpc-client")]
@@ -26,7 +54,6 @@ mod prod {
     use tendermint_rpc::Client;
 
     use super::*;
-    use crate::utils::block_on;
 
     /// Production implementation of the EvidenceReporter component, which reports evidence to full
     /// nodes via RPC.
@@ -36,16 +63,17 @@ mod prod {
         timeout: Option<Duration>,
     }
 
-    #[contract_trait]
+    #[async_trait]
     impl EvidenceReporter for ProdEvidenceReporter {
-        #[requires(self.peer_map.contains_key(&peer))]
-        fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError> {
+        async fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError> {
             let client = self.rpc_client_for(peer)?;
 
-            let response = block_on(
-                self.timeout,
-                async move { client.broadcast_evidence(e).await },
-            )?
+            let response = match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, client.broadcast_evidence(e))
+                    .await
+                    .map_err(|e| IoError::timeout(timeout, e))?,
+                None => client.broadcast_evidence(e).await,
+            }
             .map_err(IoError::rpc)?;
 
             Ok(response.hash)
@@ -0,0 +1,248 @@
+//! Queue of the evidence of light client attacks to submit to peers.
+//!
+//! The [`Supervisor`](crate::supervisor::Supervisor) records the evidence of
+//! every fork it detects in its [`EvidenceQueue`] before submitting it, and
+//! marks it as acknowledged once a peer accepted it. The evidence which was
+//! not acknowledged yet, e.g. because the peer was unreachable or the process
+//! crashed in between, is submitted again by
+//! [`Supervisor::resubmit_evidence`](crate::supervisor::Supervisor::resubmit_evidence),
+//! so that it is submitted at least once as long as the queue is persistent,
+//! until it is given up on as per the
+//! [`EvidenceRetryPolicy`](crate::supervisor::EvidenceRetryPolicy).
+//!
+//! See:
+//!
+//! - [`MemoryEvidenceQueue`] for a transient queue, the default one
+//! - `SledEvidenceQueue` for a persistent, sled-backed queue, with the
+//!   `lightstore-sled` feature
+
+use std::{collections::BTreeMap, fmt::Debug};
+
+use serde::{Deserialize, Serialize};
+use tendermint::Hash;
+
+use super::Evidence;
+use crate::{errors::Error, verifier::types::PeerId};
+
+/// The identifier of a piece of evidence in an [`EvidenceQueue`], assigned
+/// in the order in which the evidence was queued.
+pub type EvidenceId = u64;
+
+/// The status of the submission of a piece of evidence to its peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubmissionStatus {
+    /// The evidence was not acknowledged by the peer yet, and is to be
+    /// submitted (again).
+    Pending,
+    /// The evidence was not approved by the
+    /// [`EvidencePolicy`](super::EvidencePolicy), and is not submitted.
+    Held,
+    /// The evidence was acknowledged by the peer, which returned its hash.
+    Acknowledged(Hash),
+    /// The evidence was not acknowledged after the maximum number of
+    /// submissions of the
+    /// [`EvidenceRetryPolicy`](crate::supervisor::EvidenceRetryPolicy), e.g.
+    /// because the peer rejects it for good, and is not submitted anymore.
+    Failed,
+}
+
+/// A piece of evidence queued for submission to a peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedEvidence {
+    /// The identifier of the evidence in the queue.
+    pub id: EvidenceId,
+    /// The peer to submit the evidence to.
+    pub peer: PeerId,
+    /// The evidence of the attack.
+    pub evidence: Evidence,
+    /// The status of the submission.
+    pub status: SubmissionStatus,
+    /// The number of times the evidence was submitted.
+    pub attempts: u32,
+    /// The error of the last failed submission, if any.
+    pub last_error: Option<String>,
+}
+
+impl QueuedEvidence {
+    /// Whether the evidence is to be submitted (again).
+    pub fn is_pending(&self) -> bool {
+        self.status == SubmissionStatus::Pending
+    }
+}
+
+/// Store of the evidence to submit to peers, along with the status of its
+/// submission.
+///
+/// The evidence must be durable once the methods writing to the queue
+/// return, for the evidence to survive a crash.
+pub trait EvidenceQueue: Debug + Send + Sync {
+    /// Queue the given evidence for the given peer with the given status,
+    /// returning its identifier.
+    fn push(
+        &mut self,
+        peer: PeerId,
+        evidence: Evidence,
+        status: SubmissionStatus,
+    ) -> Result<EvidenceId, Error>;
+
+    /// Replace the queued evidence with the same identifier, e.g. once its
+    /// status changed.
+    fn update(&mut self, entry: &QueuedEvidence) -> Result<(), Error>;
+
+    /// The queued evidence with the given identifier, if any.
+    fn get(&self, id: EvidenceId) -> Option<QueuedEvidence>;
+
+    /// All the queued evidence, in the order in which it was queued.
+    fn all(&self) -> Vec<QueuedEvidence>;
+
+    /// The evidence which is to be submitted (again), in the order in which
+    /// it was queued.
+    fn pending(&self) -> Vec<QueuedEvidence> {
+        self.all()
+            .into_iter()
+            .filter(QueuedEvidence::is_pending)
+            .collect()
+    }
+}
+
+/// Transient in-memory evidence queue, which loses its evidence when the
+/// process exits.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryEvidenceQueue {
+    entries: BTreeMap<EvidenceId, QueuedEvidence>,
+}
+
+impl MemoryEvidenceQueue {
+    /// Create a new, empty, in-memory queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EvidenceQueue for MemoryEvidenceQueue {
+    fn push(
+        &mut self,
+        peer: PeerId,
+        evidence: Evidence,
+        status: SubmissionStatus,
+    ) -> Result<EvidenceId, Error> {
+        let id = self.entries.keys().next_back().map_or(0, |id| id + 1);
+        let entry = QueuedEvidence {
+            id,
+            peer,
+            evidence,
+            status,
+            attempts: 0,
+            last_error: None,
+        };
+        self.entries.insert(id, entry);
+        Ok(id)
+    }
+
+    fn update(&mut self, entry: &QueuedEvidence) -> Result<(), Error> {
+        self.entries.insert(entry.id, entry.clone());
+        Ok(())
+    }
+
+    fn get(&self, id: EvidenceId) -> Option<QueuedEvidence> {
+        self.entries.get(&id).cloned()
+    }
+
+    fn all(&self) -> Vec<QueuedEvidence> {
+        self.entries.values().cloned().collect()
+    }
+}
+
+#[cfg(feature = "lightstore-sled")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lightstore-sled")))]
+pub use self::sled::SledEvidenceQueue;
+
+#[cfg(feature = "lightstore-sled")]
+mod sled {
+    use std::path::Path;
+
+    use super::*;
+
+    /// The name of the tree of the evidence in the database.
+    const EVIDENCE: &str = "evidence";
+
+    /// Persistent evidence queue backed by an on-disk `sled` database.
+    ///
+    /// The evidence is kept in its own tree, indexed by identifier, so that
+    /// the queue can share the database of a
+    /// [`SledStore`](crate::store::sled::SledStore). The database is flushed
+    /// to disk on every write.
+    #[derive(Clone, Debug)]
+    pub struct SledEvidenceQueue {
+        tree: ::sled::Tree,
+    }
+
+    impl SledEvidenceQueue {
+        /// Open a sled database and create a new persistent queue from it.
+        pub fn open(db: impl AsRef<Path>) -> Result<Self, Error> {
+            Self::new(&::sled::open(db).map_err(Error::sled)?)
+        }
+
+        /// Create a new persistent queue from a sled database that is already
+        /// open, e.g. the one of the light store.
+        pub fn new(db: &::sled::Db) -> Result<Self, Error> {
+            let tree = db.open_tree(EVIDENCE).map_err(Error::sled)?;
+            Ok(Self { tree })
+        }
+
+        fn write(&self, entry: &QueuedEvidence) -> Result<(), Error> {
+            let value = serde_cbor::to_vec(entry).map_err(Error::serde_cbor)?;
+            self.tree
+                .insert(entry.id.to_be_bytes(), value)
+                .map_err(Error::sled)?;
+            self.tree.flush().map_err(Error::sled)?;
+            Ok(())
+        }
+    }
+
+    fn decode(value: &[u8]) -> Option<QueuedEvidence> {
+        serde_cbor::from_slice(value).ok()
+    }
+
+    impl EvidenceQueue for SledEvidenceQueue {
+        fn push(
+            &mut self,
+            peer: PeerId,
+            evidence: Evidence,
+            status: SubmissionStatus,
+        ) -> Result<EvidenceId, Error> {
+            // The identifiers are stored in big-endian form, so that the last
+            // key is the highest identifier.
+            let last = self.tree.last().map_err(Error::sled)?;
+            let id = last
+                .and_then(|(key, _)| <[u8; 8]>::try_from(&key[..]).ok())
+                .map_or(0, |key| EvidenceId::from_be_bytes(key) + 1);
+            self.write(&QueuedEvidence {
+                id,
+                peer,
+                evidence,
+                status,
+                attempts: 0,
+                last_error: None,
+            })?;
+            Ok(id)
+        }
+
+        fn update(&mut self, entry: &QueuedEvidence) -> Result<(), Error> {
+            self.write(entry)
+        }
+
+        fn get(&self, id: EvidenceId) -> Option<QueuedEvidence> {
+            let value = self.tree.get(id.to_be_bytes()).ok().flatten()?;
+            decode(&value)
+        }
+
+        fn all(&self) -> Vec<QueuedEvidence> {
+            self.tree
+                .iter()
+                .values()
+                .filter_map(|value| decode(&value.ok()?))
+                .collect()
+        }
+    }
+}
@@ -1,8 +1,13 @@
 //! Fork detection data structures and implementation.
 
 use core::marker::PhantomData;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
-use tendermint::{crypto::Sha256, merkle::MerkleHash};
+use async_trait::async_trait;
+use tendermint::{block::Height, crypto::Sha256, merkle::MerkleHash, Hash};
 
 use crate::{
     errors::{Error, ErrorDetail},
@@ -43,10 +48,11 @@ pub enum Fork {
 }
 
 /// Interface for a fork detector
+#[async_trait]
 pub trait ForkDetector: Send + Sync {
     /// Detect forks using the given verified block, trusted block,
     /// and list of witnesses to verify the given light block against.
-    fn detect_forks(
+    async fn detect_forks(
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
@@ -64,7 +70,11 @@ pub trait ForkDetector: Send + Sync {
 /// - If the verification succeeds, we have a real fork
 /// - If verification fails because of lack of trust, we have a potential fork.
 /// - If verification fails for any other reason, the witness is deemed faulty.
+///
+/// With a [`CrossCheckCache`], the witnesses which already served the very
+/// same header at a given height are not asked for it again.
 pub struct ProvidedForkDetector<H> {
+    cache: Option<CrossCheckCache>,
     _crypto: PhantomData<H>,
 }
 
@@ -75,6 +85,20 @@ impl<H> ProvidedForkDetector<H> {
     /// Construct a new fork detector that will use the given header hasher.
     pub fn new() -> Self {
         Self {
+            cache: None,
+            _crypto: PhantomData,
+        }
+    }
+
+    /// Construct a new fork detector that will use the given header hasher,
+    /// and skip the witnesses the given cache records as agreeing with the
+    /// header to check.
+    ///
+    /// The cache is shared with its clones, e.g. with an RPC proxy serving
+    /// the verified blocks, which can tell which heights were cross-checked.
+    pub fn with_cache(cache: CrossCheckCache) -> Self {
+        Self {
+            cache: Some(cache),
             _crypto: PhantomData,
         }
     }
@@ -86,33 +110,46 @@ impl<H: Default> Default for ProvidedForkDetector<H> {
     }
 }
 
+#[async_trait]
 impl<H> ForkDetector for ProvidedForkDetector<H>
 where
     // Sync + Send have to be added only because of forbid(unsafe_code)
     H: MerkleHash + Sha256 + Default + Sync + Send,
 {
     /// Perform fork detection. See the documentation `ProdForkDetector` for details.
-    fn detect_forks(
+    async fn detect_forks(
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
         witnesses: Vec<&Instance>,
     ) -> Result<ForkDetection, Error> {
-        let primary_hash = verified_block.signed_header.header.hash_with::<H>();
+        let primary_hash = verified_block.signed_header.header_hash_with::<H>();
 
         let mut forks = Vec::with_capacity(witnesses.len());
 
         for witness in witnesses {
+            let peer = witness.light_client.peer;
+            let height = verified_block.height();
+            if let Some(cache) = &self.cache {
+                if cache.contains(peer, height, primary_hash) {
+                    continue;
+                }
+            }
+
             let mut state = State::new(MemoryStore::new());
 
             let (witness_block, _) = witness
                 .light_client
-                .get_or_fetch_block(verified_block.height(), &mut state)?;
+                .get_or_fetch_block_async(verified_block.height(), &mut state)
+                .await?;
 
-            let witness_hash = witness_block.signed_header.header.hash_with::<H>();
+            let witness_hash = witness_block.signed_header.header_hash_with::<H>();
 
             if primary_hash == witness_hash {
                 // Hashes match, continue with next witness, if any.
+                if let Some(cache) = &self.cache {
+                    cache.insert(peer, height, primary_hash);
+                }
                 continue;
             }
 
@@ -126,7 +163,8 @@ where
 
             let result = witness
                 .light_client
-                .verify_to_target(verified_block.height(), &mut state);
+                .verify_to_target_async(verified_block.height(), &mut state)
+                .await;
 
             match result {
                 Ok(_) => forks.push(Fork::Forked {
@@ -156,3 +194,177 @@ where
         }
     }
 }
+
+/// The most recent cross-checks of the headers of the primary against the
+/// witnesses, i.e. which witness served which header at which height.
+///
+/// The cache is bounded: once full, the oldest cross-check is evicted for
+/// every new one. Its clones share the same cross-checks.
+#[derive(Clone, Debug)]
+pub struct CrossCheckCache {
+    inner: Arc<Mutex<CrossChecks>>,
+}
+
+#[derive(Debug)]
+struct CrossChecks {
+    capacity: usize,
+    // In the order they were recorded, the oldest first.
+    entries: VecDeque<(PeerId, Height, Hash)>,
+}
+
+impl CrossCheckCache {
+    /// A cache of the given number of cross-checks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CrossChecks {
+                capacity,
+                entries: VecDeque::with_capacity(capacity),
+            })),
+        }
+    }
+
+    /// Whether the given witness was recently found to serve the header
+    /// with the given hash at the given height.
+    pub fn contains(&self, witness: PeerId, height: Height, header_hash: Hash) -> bool {
+        self.lock()
+            .entries
+            .contains(&(witness, height, header_hash))
+    }
+
+    /// Whether the header with the given hash was recently cross-checked
+    /// against at least one witness at the given height.
+    pub fn is_cross_checked(&self, height: Height, header_hash: Hash) -> bool {
+        self.lock()
+            .entries
+            .iter()
+            .any(|&(_, h, hash)| h == height && hash == header_hash)
+    }
+
+    /// Record that the given witness serves the header with the given hash
+    /// at the given height.
+    pub fn insert(&self, witness: PeerId, height: Height, header_hash: Hash) {
+        let mut checks = self.lock();
+        let entry = (witness, height, header_hash);
+        if checks.capacity == 0 || checks.entries.contains(&entry) {
+            return;
+        }
+        if checks.entries.len() == checks.capacity {
+            checks.entries.pop_front();
+        }
+        checks.entries.push_back(entry);
+    }
+
+    /// Forget the cross-checks of the given witness, e.g. once it is
+    /// replaced.
+    pub fn remove_witness(&self, witness: PeerId) {
+        self.lock().entries.retain(|&(peer, _, _)| peer != witness);
+    }
+
+    /// The number of cross-checks in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache holds no cross-check.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CrossChecks> {
+        // The cross-checks are always left consistent, even by a panic.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::executor::block_on;
+    use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
+
+    use super::*;
+    use crate::{
+        components::scheduler,
+        light_client::LightClient,
+        tests::{MockClock, MockIo},
+        verifier::{options::Options, ProdVerifier},
+    };
+
+    const WITNESS: &str = "0BEFEEDC0C0ADEADBEBADFADADEFC0FFEEFACADE";
+
+    fn light_block(height: u64) -> LightBlock {
+        let tm_block = TestgenLightBlock::new_default(height).generate().unwrap();
+        LightBlock {
+            signed_header: tm_block.signed_header,
+            validators: tm_block.validators,
+            next_validators: tm_block.next_validators,
+            provider: WITNESS.parse().unwrap(),
+        }
+    }
+
+    fn witness(blocks: Vec<LightBlock>) -> Instance {
+        let options = Options {
+            trust_threshold: Default::default(),
+            trusting_period: core::time::Duration::from_secs(60 * 60),
+            clock_drift: Default::default(),
+            time_monotonicity: Default::default(),
+        };
+        let light_client = LightClient::new(
+            WITNESS.parse().unwrap(),
+            options,
+            MockClock {
+                now: blocks[0].time(),
+            },
+            scheduler::basic_bisecting_schedule,
+            ProdVerifier::default(),
+            MockIo::new(blocks),
+        );
+        let state = State {
+            light_store: Box::new(MemoryStore::new()),
+            verification_trace: HashMap::new(),
+        };
+        Instance::new(light_client, state)
+    }
+
+    #[test]
+    fn cached_cross_checks_are_not_fetched_again() {
+        let trusted = light_block(1);
+        let verified = light_block(2);
+        let cache = CrossCheckCache::new(8);
+        let detector = ProdForkDetector::with_cache(cache.clone());
+
+        let serving = witness(vec![trusted.clone(), verified.clone()]);
+        let detection =
+            block_on(detector.detect_forks(&verified, &trusted, vec![&serving])).unwrap();
+        assert!(matches!(detection, ForkDetection::NotDetected));
+
+        let hash = verified.signed_header.header.hash();
+        assert!(cache.contains(WITNESS.parse().unwrap(), verified.height(), hash));
+        assert!(cache.is_cross_checked(verified.height(), hash));
+
+        // The witness can no longer serve the block, but is not asked for it.
+        let failing = witness(vec![trusted.clone()]);
+        let detection =
+            block_on(detector.detect_forks(&verified, &trusted, vec![&failing])).unwrap();
+        assert!(matches!(detection, ForkDetection::NotDetected));
+
+        cache.remove_witness(WITNESS.parse().unwrap());
+        let detector = ProdForkDetector::with_cache(cache);
+        assert!(block_on(detector.detect_forks(&verified, &trusted, vec![&failing])).is_err());
+    }
+
+    #[test]
+    fn oldest_cross_checks_are_evicted() {
+        let cache = CrossCheckCache::new(2);
+        let peer = WITNESS.parse().unwrap();
+        let hash = Hash::None;
+        for height in 1..=3_u32 {
+            cache.insert(peer, height.into(), hash);
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(peer, 1_u32.into(), hash));
+        assert!(cache.contains(peer, 3_u32.into(), hash));
+    }
+}
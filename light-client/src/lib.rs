@@ -16,21 +16,31 @@
 //! See the `light_client` module for the main documentation.
 
 pub mod builder;
+pub mod checkpoint;
 pub mod components;
 pub mod contracts;
 pub mod errors;
 pub mod evidence;
 pub mod fork_detector;
 pub mod light_client;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod peer_list;
+pub mod prelude;
+pub mod pruning;
+pub mod snapshot;
 pub mod state;
 pub mod store;
 pub mod supervisor;
+#[cfg(feature = "rpc-client")]
+pub mod verified_client;
 
 pub(crate) mod utils;
 
-// Re-export `tendermint-light-client-verifier` crate.
+// Re-export the crates the types of the light client come from.
+pub use tendermint;
 pub use tendermint_light_client_verifier as verifier;
+pub use tendermint_rpc as rpc;
 // Re-export for backward compatibility
 pub use verifier::{operations, predicates, types};
 
@@ -2,23 +2,97 @@
 //!
 //! [1]: https://github.com/informalsystems/tendermint-rs/blob/main/docs/spec/lightclient/verification/verification.md
 
-use core::fmt;
+use core::{convert::TryFrom, fmt};
 
 use contracts::*;
+use tendermint::Genesis;
 
 // Re-export for backward compatibility
 pub use crate::verifier::options::Options;
 use crate::{
+    checkpoint::Checkpoints,
     components::{clock::Clock, io::*, scheduler::*},
     contracts::*,
     errors::Error,
     state::State,
+    utils::run_blocking,
     verifier::{
-        types::{Height, LightBlock, PeerId, Status},
+        types::{Height, LightBlock, PeerId, Status, Time},
         Verdict, Verifier,
     },
 };
 
+/// Information about the chain whose blocks the light client verifies, used
+/// to reject verification targets which cannot exist on that chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChainInfo {
+    /// The height of the first block of the chain, which is greater than 1
+    /// for chains started from an exported state.
+    pub initial_height: Height,
+    /// The time of genesis, which every block of the chain comes after.
+    pub genesis_time: Time,
+}
+
+impl<AppState> TryFrom<&Genesis<AppState>> for ChainInfo {
+    type Error = tendermint::Error;
+
+    fn try_from(genesis: &Genesis<AppState>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            initial_height: Height::try_from(genesis.initial_height)?,
+            genesis_time: genesis.genesis_time,
+        })
+    }
+}
+
+/// The progress of the verification of a block by a [`LightClient`], see
+/// [`LightClient::on_event`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationEvent {
+    /// The light client started verifying the block at the target height,
+    /// from its trusted block at the given height.
+    Started {
+        /// The height of the block to verify.
+        target: Height,
+        /// The height of the trusted block verification starts from.
+        trusted: Height,
+    },
+    /// The block at the given height was checked against the trusted block,
+    /// when verifying forward.
+    BisectionStep {
+        /// The height of the trusted block.
+        trusted: Height,
+        /// The height of the checked block.
+        height: Height,
+        /// The outcome of the check.
+        outcome: StepOutcome,
+    },
+    /// The block at the target height was verified.
+    Verified {
+        /// The height of the verified block.
+        target: Height,
+    },
+    /// A block could not be fetched from the peer of the light client.
+    FetchFailed {
+        /// The height of the block, or `None` for the highest block.
+        height: Option<Height>,
+        /// The error of the peer.
+        reason: String,
+    },
+}
+
+/// The outcome of a [`VerificationEvent::BisectionStep`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The block was verified.
+    Verified,
+    /// The block is valid, but the validators of the trusted block which
+    /// signed it do not have enough voting power: a block in between is to
+    /// be verified first.
+    NotEnoughTrust,
+    /// The block is invalid, which fails the verification.
+    Invalid,
+}
+
 /// The light client implements a read operation of a header from the blockchain,
 /// by communicating with full nodes. As full nodes may be faulty, it cannot trust
 /// the received information, but the light client has to check whether the header
@@ -29,16 +103,27 @@ use crate::{
 /// of the header, more than two-thirds of the next validators of a new block are
 /// correct for the duration of the trusted period.  The fault-tolerant read operation
 /// is designed for this security model.
+///
+/// The light client fetches the light blocks through an [`AsyncIo`], and its
+/// verification methods are `async`: each of them has a blocking variant,
+/// which runs it to completion on a thread of its own, for use outside of an
+/// async runtime.
 pub struct LightClient {
     /// The peer id of the peer this client is connected to
     pub peer: PeerId,
     /// Options for this light client
     pub options: Options,
+    /// Information about the chain, if known
+    pub chain_info: Option<ChainInfo>,
+    /// The checkpoints to fast-forward the trusted state to, if any (only
+    /// used with the `rust-crypto` feature)
+    pub checkpoints: Checkpoints,
 
     clock: Box<dyn Clock>,
     scheduler: Box<dyn Scheduler>,
     verifier: Box<dyn Verifier>,
-    io: Box<dyn Io>,
+    io: Box<dyn AsyncIo>,
+    event_handlers: Vec<Box<dyn Fn(&VerificationEvent) + Send + Sync>>,
 }
 
 impl fmt::Debug for LightClient {
@@ -58,15 +143,18 @@ impl LightClient {
         clock: impl Clock + 'static,
         scheduler: impl Scheduler + 'static,
         verifier: impl Verifier + 'static,
-        io: impl Io + 'static,
+        io: impl AsyncIo + 'static,
     ) -> Self {
         Self {
             peer,
             options,
+            chain_info: None,
+            checkpoints: Checkpoints::new(),
             clock: Box::new(clock),
             scheduler: Box::new(scheduler),
             verifier: Box::new(verifier),
             io: Box::new(io),
+            event_handlers: Vec::new(),
         }
     }
 
@@ -77,28 +165,92 @@ impl LightClient {
         clock: Box<dyn Clock>,
         scheduler: Box<dyn Scheduler>,
         verifier: Box<dyn Verifier>,
-        io: Box<dyn Io>,
+        io: Box<dyn AsyncIo>,
     ) -> Self {
         Self {
             peer,
             options,
+            chain_info: None,
+            checkpoints: Checkpoints::new(),
             clock,
             scheduler,
             verifier,
             io,
+            event_handlers: Vec::new(),
         }
     }
 
+    /// Call the given handler with the [`VerificationEvent`]s of this light
+    /// client, along with the handlers added before.
+    ///
+    /// The handlers are called synchronously, as the verification proceeds,
+    /// and must therefore return quickly, e.g. by sending the events through
+    /// a channel.
+    pub fn on_event(&mut self, handler: impl Fn(&VerificationEvent) + Send + Sync + 'static) {
+        self.event_handlers.push(Box::new(handler));
+    }
+
+    fn emit(&self, event: VerificationEvent) {
+        for handler in &self.event_handlers {
+            handler(&event);
+        }
+    }
+
+    async fn fetch(&self, height: AtHeight) -> Result<LightBlock, Error> {
+        self.io.fetch_light_block(height).await.map_err(|e| {
+            self.emit(VerificationEvent::FetchFailed {
+                height: match height {
+                    AtHeight::At(height) => Some(height),
+                    AtHeight::Highest => None,
+                },
+                reason: e.to_string(),
+            });
+            Error::io(e)
+        })
+    }
+
+    /// The current time, according to the clock of the light client.
+    pub fn now(&self) -> Time {
+        self.clock.now()
+    }
+
     /// Attempt to update the light client to the highest block of the primary node.
     ///
-    /// Note: This function delegates the actual work to `verify_to_target`.
+    /// Blocking variant of [`LightClient::verify_to_highest_async`].
     pub fn verify_to_highest(&mut self, state: &mut State) -> Result<LightBlock, Error> {
-        let target_block = self
-            .io
-            .fetch_light_block(AtHeight::Highest)
-            .map_err(Error::io)?;
+        run_blocking(self.verify_to_highest_async(state)).map_err(Error::io)?
+    }
+
+    /// Attempt to update the light client to the highest block of the primary node.
+    ///
+    /// Note: This function delegates the actual work to `verify_to_target_async`.
+    pub async fn verify_to_highest_async(&self, state: &mut State) -> Result<LightBlock, Error> {
+        let target_block = self.fetch(AtHeight::Highest).await?;
 
-        self.verify_to_target(target_block.height(), state)
+        self.verify_to_target_async(target_block.height(), state)
+            .await
+    }
+
+    /// Update the light client to a block of the primary node at the given height.
+    ///
+    /// Blocking variant of [`LightClient::verify_to_target_async`].
+    ///
+    /// ## Postcondition
+    /// - The light store contains a light block that corresponds to a block of the blockchain of
+    ///   height `target_height` [LCV-POST-LS.1]
+    #[allow(clippy::nonminimal_bool)]
+    #[ensures(
+        ret.is_ok() -> trusted_store_contains_block_at_target_height(
+            state.light_store.as_ref(),
+            target_height,
+        )
+    )]
+    pub fn verify_to_target(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<LightBlock, Error> {
+        run_blocking(self.verify_to_target_async(target_height, state)).map_err(Error::io)?
     }
 
     /// Update the light client to a block of the primary node at the given height.
@@ -131,24 +283,31 @@ impl LightClient {
     /// - If the core verification loop invariant is violated [LCV-INV-TP.1]
     /// - If verification of a light block fails
     /// - If the fetching a light block from the primary node fails
-    #[allow(clippy::nonminimal_bool)]
-    #[ensures(
-        ret.is_ok() -> trusted_store_contains_block_at_target_height(
-            state.light_store.as_ref(),
-            target_height,
-        )
-    )]
-    pub fn verify_to_target(
+    /// - If the target height is lower than the initial height of the chain
+    pub async fn verify_to_target_async(
         &self,
         target_height: Height,
         state: &mut State,
     ) -> Result<LightBlock, Error> {
+        if let Some(chain_info) = &self.chain_info {
+            if target_height < chain_info.initial_height {
+                return Err(Error::height_below_initial(
+                    target_height,
+                    chain_info.initial_height,
+                ));
+            }
+        }
+
         // Let's first look in the store to see whether
         // we have already successfully verified this block.
         if let Some(light_block) = state.light_store.get_trusted_or_verified(target_height) {
             return Ok(light_block);
         }
 
+        // Skip the blocks below the highest checkpoint, if any
+        #[cfg(feature = "rust-crypto")]
+        self.fast_forward_async(target_height, state).await?;
+
         // Get the highest trusted state
         let highest = state
             .light_store
@@ -156,17 +315,30 @@ impl LightClient {
             .or_else(|| state.light_store.lowest_trusted_or_verified())
             .ok_or_else(Error::no_initial_trusted_state)?;
 
-        if target_height >= highest.height() {
+        self.emit(VerificationEvent::Started {
+            target: target_height,
+            trusted: highest.height(),
+        });
+
+        let result = if target_height >= highest.height() {
             // Perform forward verification with bisection
-            self.verify_forward(target_height, state)
+            self.verify_forward(target_height, state).await
         } else {
             // Perform sequential backward verification
-            self.verify_backward(target_height, state)
+            self.verify_backward(target_height, state).await
+        };
+
+        if result.is_ok() {
+            self.emit(VerificationEvent::Verified {
+                target: target_height,
+            });
         }
+
+        result
     }
 
     /// Perform forward verification with bisection.
-    fn verify_forward(
+    async fn verify_forward(
         &self,
         target_height: Height,
         state: &mut State,
@@ -191,10 +363,7 @@ impl LightClient {
 
             // Check invariant [LCV-INV-TP.1]
             if !is_within_trust_period(&trusted_block, self.options.trusting_period, now) {
-                return Err(Error::trusted_state_outside_trusting_period(
-                    Box::new(trusted_block),
-                    self.options,
-                ));
+                return Err(outside_trusting_period(trusted_block, self.options, now));
             }
 
             // Log the current height as a dependency of the block at the target height
@@ -208,7 +377,8 @@ impl LightClient {
 
             // Fetch the block at the current height from the light store if already present,
             // or from the primary peer otherwise.
-            let (current_block, status) = self.get_or_fetch_block(current_height, state)?;
+            let (current_block, status) =
+                self.get_or_fetch_block_async(current_height, state).await?;
 
             // Validate and verify the current block
             let verdict = self.verifier.verify(
@@ -218,10 +388,22 @@ impl LightClient {
                 now,
             );
 
+            let outcome = match verdict {
+                Verdict::Success | Verdict::SuccessWithTimeRegression(_) => StepOutcome::Verified,
+                Verdict::NotEnoughTrust(_) => StepOutcome::NotEnoughTrust,
+                Verdict::Invalid(_) => StepOutcome::Invalid,
+            };
+            self.emit(VerificationEvent::BisectionStep {
+                trusted: trusted_block.height(),
+                height: current_height,
+                outcome,
+            });
+
             match verdict {
-                Verdict::Success => {
+                Verdict::Success | Verdict::SuccessWithTimeRegression(_) => {
                     // Verification succeeded, add the block to the light store with
-                    // the `Verified` status or higher if already trusted.
+                    // the `Verified` status or higher if already trusted. A time regression
+                    // is only reported when tolerated by the options.
                     let new_status = Status::most_trusted(Status::Verified, status);
                     state.light_store.update(&current_block, new_status);
                 },
@@ -252,7 +434,7 @@ impl LightClient {
     /// Stub for when "unstable" feature is disabled.
     #[doc(hidden)]
     #[cfg(not(feature = "unstable"))]
-    fn verify_backward(
+    async fn verify_backward(
         &self,
         target_height: Height,
         state: &mut State,
@@ -269,56 +451,122 @@ impl LightClient {
         ))
     }
 
-    /// Perform sequential backward verification.
+    /// Perform sequential backward verification, see [`LightClient::verify_backwards_async`].
+    ///
+    /// ## Stability
+    /// Verifying backwards from `verify_to_target` is only available if the
+    /// `unstable` flag is enabled. If the flag is disabled, then any attempt
+    /// to verify a block whose height is lower than the highest trusted state
+    /// with `verify_to_target` will result in a `TargetLowerThanTrustedState`
+    /// error, and `verify_backwards_async` is to be called instead.
+    #[cfg(feature = "unstable")]
+    async fn verify_backward(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<LightBlock, Error> {
+        self.verify_backwards_async(target_height, state).await
+    }
+
+    /// Verify the block of the primary node at the given height, which is
+    /// lower than the trusted state, by following the hashes which link the
+    /// blocks down to it.
+    ///
+    /// Blocking variant of [`LightClient::verify_backwards_async`].
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify_backwards(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<LightBlock, Error> {
+        run_blocking(self.verify_backwards_async(target_height, state)).map_err(Error::io)?
+    }
+
+    /// Verify the block of the primary node at the given height, which is
+    /// lower than the trusted state, by following the hashes which link the
+    /// blocks down to it.
+    ///
+    /// Backward verification is implemented by taking a sliding window of
+    /// length two between the lowest trusted or verified block above the
+    /// target and the target block, and checking whether the `last_block_id`
+    /// hash of the higher block matches the computed hash of the lower block.
+    /// The blocks checked along the way are added to the light store with the
+    /// `Trusted` status, so that later calls resume from the closest one.
     ///
-    /// Backward verification is implemented by taking a sliding window
-    /// of length two between the trusted state and the target block and
-    /// checking whether the last_block_id hash of the higher block
-    /// matches the computed hash of the lower block.
+    /// ## Security
+    /// The blocks verified backwards are only as trustworthy as the trusted
+    /// state they are linked to, and the collision resistance of the header
+    /// hash:
+    /// - the header of every block is authenticated by the hash link to the
+    ///   block above it, and its validator sets by the hashes of the header;
+    /// - the signatures of their commits are *not* verified, so the commits of
+    ///   these blocks must not be relied upon as proof of anything.
+    ///
+    /// The highest trusted state of the light client must be within the
+    /// trusting period, as when verifying forward.
     ///
     /// ## Performance
-    /// The algorithm implemented is very inefficient in case the target
-    /// block is much lower than the highest trusted state.
-    /// For a trusted state at height `T`, and a target block at height `H`,
-    /// it will fetch and check hashes of `T - H` blocks.
+    /// For a closest trusted block at height `T`, and a target block at
+    /// height `H`, it will fetch and check the hashes of `T - H` blocks.
     ///
-    /// ## Stability
-    /// This feature is only available if the `unstable` flag of is enabled.
-    /// If the flag is disabled, then any attempt to verify a block whose
-    /// height is lower than the highest trusted state will result in a
-    /// `TargetLowerThanTrustedState` error.
-    #[cfg(feature = "unstable")]
-    fn verify_backward(
+    /// ## Error conditions
+    /// - The light store does not contain a trusted light block within the
+    ///   trusting period [LCV-PRE-TP.1]
+    /// - The light store does not contain a trusted or verified light block
+    ///   above the target height
+    /// - If a block is not linked by its hash to the block above it, or its
+    ///   validator sets do not match the hashes of its header
+    /// - If the fetching a light block from the primary node fails
+    /// - If the target height is lower than the initial height of the chain
+    #[cfg(feature = "rust-crypto")]
+    pub async fn verify_backwards_async(
         &self,
         target_height: Height,
         state: &mut State,
     ) -> Result<LightBlock, Error> {
         use tendermint::crypto::default::Sha256;
 
-        let root = state
-            .light_store
-            .highest_trusted_or_verified_before(target_height)
-            .or_else(|| state.light_store.lowest_trusted_or_verified())
-            .ok_or_else(Error::no_initial_trusted_state)?;
+        use crate::verifier::{
+            errors::VerificationError,
+            predicates::{ProdPredicates, VerificationPredicates},
+        };
 
-        assert!(root.height() >= target_height);
+        if let Some(chain_info) = &self.chain_info {
+            if target_height < chain_info.initial_height {
+                return Err(Error::height_below_initial(
+                    target_height,
+                    chain_info.initial_height,
+                ));
+            }
+        }
+
+        if let Some(light_block) = state.light_store.get_trusted_or_verified(target_height) {
+            return Ok(light_block);
+        }
 
         // Check invariant [LCV-INV-TP.1]
-        if !is_within_trust_period(&root, self.options.trusting_period, self.clock.now()) {
-            return Err(Error::trusted_state_outside_trusting_period(
-                Box::new(root),
-                self.options,
-            ));
+        let highest = state
+            .light_store
+            .highest_trusted_or_verified()
+            .ok_or_else(Error::no_initial_trusted_state)?;
+        let now = self.clock.now();
+        if !is_within_trust_period(&highest, self.options.trusting_period, now) {
+            return Err(outside_trusting_period(highest, self.options, now));
         }
 
-        // Compute a range of `Height`s from `trusted_height - 1` to `target_height`, inclusive.
+        let root = state
+            .light_store
+            .lowest_trusted_or_verified_after(target_height)
+            .ok_or_else(|| Error::no_trusted_state_above(target_height))?;
+
+        // Compute a range of `Height`s from `root_height - 1` to `target_height`, inclusive.
         let range = (target_height.value()..root.height().value()).rev();
         let heights = range.map(|h| Height::try_from(h).unwrap());
 
         let mut latest = root;
 
         for height in heights {
-            let (current, _status) = self.get_or_fetch_block(height, state)?;
+            let (current, _status) = self.get_or_fetch_block_async(height, state).await?;
 
             let latest_last_block_id = latest
                 .signed_header
@@ -326,21 +574,37 @@ impl LightClient {
                 .last_block_id
                 .ok_or_else(|| Error::missing_last_block_id(latest.height()))?;
 
-            let current_hash = current.signed_header.header.hash_with::<Sha256>();
+            let current_hash = current.signed_header.header_hash_with::<Sha256>();
 
             if current_hash != latest_last_block_id.hash {
+                state.light_store.update(&current, Status::Failed);
+
                 return Err(Error::invalid_adjacent_headers(
                     current_hash,
                     latest_last_block_id.hash,
                 ));
             }
 
+            let header = &current.signed_header.header;
+            let validators_match = ProdPredicates
+                .validator_sets_match(&current.validators, header.validators_hash)
+                .and_then(|()| {
+                    ProdPredicates.next_validators_match(
+                        &current.next_validators,
+                        header.next_validators_hash,
+                    )
+                });
+            if let Err(VerificationError(e, _)) = validators_match {
+                state.light_store.update(&current, Status::Failed);
+
+                return Err(Error::invalid_light_block(e));
+            }
+
             // `latest` and `current` are linked together by `last_block_id`,
             // therefore it is not relevant which we verified first.
             // For consistency, we say that `latest` was verifed using
             // `current` so that the trace is always pointing down the chain.
-            state.light_store.insert(current.clone(), Status::Trusted);
-            state.light_store.insert(latest.clone(), Status::Trusted);
+            state.light_store.update(&current, Status::Trusted);
             state.trace_block(latest.height(), current.height());
 
             latest = current;
@@ -352,11 +616,107 @@ impl LightClient {
         Ok(latest)
     }
 
+    /// Fast-forward the trusted state to the highest checkpoint at or below
+    /// the target height, if it is above the highest trusted state, without
+    /// verifying the blocks in between. Returns the block of the checkpoint
+    /// if the trusted state was fast-forwarded.
+    ///
+    /// Blocking variant of [`LightClient::fast_forward_async`].
+    #[cfg(feature = "rust-crypto")]
+    pub fn fast_forward(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<Option<LightBlock>, Error> {
+        run_blocking(self.fast_forward_async(target_height, state)).map_err(Error::io)?
+    }
+
+    /// Fast-forward the trusted state to the highest checkpoint at or below
+    /// the target height, if it is above the highest trusted state, without
+    /// verifying the blocks in between. Returns the block of the checkpoint
+    /// if the trusted state was fast-forwarded.
+    ///
+    /// The block of the checkpoint is added to the light store with the
+    /// `Trusted` status and the [`Provenance::Checkpoint`] provenance, and
+    /// the blocks above it are then verified from it as usual. This is done
+    /// by [`LightClient::verify_to_target_async`] on its own.
+    ///
+    /// ## Security
+    /// The block of a checkpoint is only as trustworthy as the source of the
+    /// checkpoint: its header is authenticated by the pinned hash, and its
+    /// validator sets by the hashes of the header, but the signatures of its
+    /// commit are *not* verified. Nor is the block required to be within the
+    /// trusting period: verifying blocks from it requires it, though.
+    ///
+    /// ## Error conditions
+    /// - If the hash of the header of the block does not match the checkpoint
+    /// - If the validator sets or the commit of the block do not match its
+    ///   header
+    /// - If the fetching a light block from the primary node fails
+    ///
+    /// [`Provenance::Checkpoint`]: crate::store::Provenance::Checkpoint
+    #[cfg(feature = "rust-crypto")]
+    pub async fn fast_forward_async(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<Option<LightBlock>, Error> {
+        use tendermint::crypto::default::Sha256;
+
+        use crate::{
+            store::Provenance,
+            verifier::{
+                errors::VerificationError,
+                predicates::{ProdPredicates, VerificationPredicates},
+            },
+        };
+
+        let trusted_height = state
+            .light_store
+            .highest_trusted_or_verified_before(target_height)
+            .map(|lb| lb.height());
+        let Some((height, hash)) = self
+            .checkpoints
+            .highest_between(trusted_height, target_height)
+        else {
+            return Ok(None);
+        };
+
+        let (block, _status) = self.get_or_fetch_block_async(height, state).await?;
+
+        let header_hash = block.signed_header.header_hash_with::<Sha256>();
+        if header_hash != hash {
+            state.light_store.update(&block, Status::Failed);
+
+            return Err(Error::checkpoint_mismatch(height, hash, header_hash));
+        }
+
+        let header = &block.signed_header.header;
+        let valid = ProdPredicates
+            .validator_sets_match(&block.validators, header.validators_hash)
+            .and_then(|()| {
+                ProdPredicates
+                    .next_validators_match(&block.next_validators, header.next_validators_hash)
+            })
+            .and_then(|()| ProdPredicates.header_matches_commit(&block.signed_header));
+        if let Err(VerificationError(e, _)) = valid {
+            state.light_store.update(&block, Status::Failed);
+
+            return Err(Error::invalid_light_block(e));
+        }
+
+        state.light_store.update(&block, Status::Trusted);
+        state
+            .light_store
+            .set_provenance(height, Provenance::Checkpoint);
+
+        Ok(Some(block))
+    }
+
     /// Look in the light store for a block from the given peer at the given height,
     /// which has not previously failed verification (ie. its status is not `Failed`).
     ///
-    /// If one cannot be found, fetch the block from the given peer and store
-    /// it in the light store with `Unverified` status.
+    /// Blocking variant of [`LightClient::get_or_fetch_block_async`].
     ///
     /// ## Postcondition
     /// - The provider of block that is returned matches the given peer.
@@ -365,6 +725,20 @@ impl LightClient {
         &self,
         height: Height,
         state: &mut State,
+    ) -> Result<(LightBlock, Status), Error> {
+        run_blocking(self.get_or_fetch_block_async(height, state)).map_err(Error::io)?
+    }
+
+    /// Look in the light store for a block from the given peer at the given height,
+    /// which has not previously failed verification (ie. its status is not `Failed`).
+    ///
+    /// If one cannot be found, fetch the block from the given peer and store
+    /// it in the light store with `Unverified` status. A fetched block whose
+    /// time precedes the genesis time of the chain is rejected.
+    pub async fn get_or_fetch_block_async(
+        &self,
+        height: Height,
+        state: &mut State,
     ) -> Result<(LightBlock, Status), Error> {
         let block = state.light_store.get_non_failed(height);
 
@@ -372,13 +746,29 @@ impl LightClient {
             return Ok(block);
         }
 
-        let block = self
-            .io
-            .fetch_light_block(AtHeight::At(height))
-            .map_err(Error::io)?;
+        let block = self.fetch(AtHeight::At(height)).await?;
+
+        if let Some(chain_info) = &self.chain_info {
+            if block.time() < chain_info.genesis_time {
+                return Err(Error::time_before_genesis(
+                    block.height(),
+                    block.time(),
+                    chain_info.genesis_time,
+                ));
+            }
+        }
 
         state.light_store.insert(block.clone(), Status::Unverified);
 
         Ok((block, Status::Unverified))
     }
 }
+
+/// The error raised when the given trusted block is outside of the trusting
+/// period at time `now`.
+fn outside_trusting_period(trusted_block: LightBlock, options: Options, now: Time) -> Error {
+    // The trusting period ended before `now`, so its end is representable.
+    let expires_at =
+        (trusted_block.signed_header.header.time + options.trusting_period).unwrap_or(now);
+    Error::trusted_state_outside_trusting_period(Box::new(trusted_block), options, expires_at, now)
+}
@@ -0,0 +1,365 @@
+//! Prometheus metrics of the operation of the light client.
+//!
+//! [`Metrics`] are collected from the events of the [`Supervisor`], which
+//! records them when given the metrics with [`Supervisor::metrics`], and are
+//! rendered in the Prometheus text exposition format by [`Metrics::render`],
+//! to be served by the metrics endpoint of the application.
+//!
+//! [`Supervisor`]: crate::supervisor::Supervisor
+//! [`Supervisor::metrics`]: crate::supervisor::Supervisor::metrics
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use tendermint::Time;
+use tendermint_std_ext::metrics::{Encoder, Histogram, MetricType};
+
+use crate::{
+    components::clock::{Clock, SystemClock},
+    light_client::{StepOutcome, VerificationEvent},
+    supervisor::SupervisorEvent,
+    verifier::types::{Height, PeerId},
+};
+
+/// The upper bounds of the buckets of the histogram of the number of blocks
+/// checked per verification.
+const STEP_BUCKETS: [f64; 8] = [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0];
+
+/// The upper bounds of the buckets of the histogram of the latency of the
+/// verifications, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// The metrics of the operation of a light client:
+///
+/// - `tendermint_light_client_bisection_steps`, a histogram of the number of
+///   blocks checked to verify each target block, per peer;
+/// - `tendermint_light_client_verification_duration_seconds`, a histogram of
+///   the latency of the successful verifications, per peer;
+/// - `tendermint_light_client_verification_failures_total`, a counter of the
+///   verifications which failed on an invalid block, per peer;
+/// - `tendermint_light_client_rpc_failures_total`, a counter of the failures
+///   of the peers to serve a block or to accept evidence, per peer;
+/// - `tendermint_light_client_forks_detected_total`, a counter of the
+///   witnesses found to fork from the primary;
+/// - `tendermint_light_client_trusted_height`, a gauge of the height of the
+///   latest trusted block;
+/// - `tendermint_light_client_trusting_period_remaining_seconds`, a gauge of
+///   the time left before the latest trusted block leaves the trusting period,
+///   after which the light client must be initialized again;
+/// - `tendermint_light_client_pruned_blocks_total`, a counter of the light
+///   blocks pruned from the light store of the primary;
+/// - `tendermint_light_client_light_store_bytes`, a gauge of the approximate
+///   size of the light store of the primary, once it was pruned.
+///
+/// The metrics of the witnesses are those of the verifications of their
+/// blocks when they differ from the blocks of the primary.
+///
+/// The metrics are shared between the clones of a `Metrics`, so that a clone
+/// can be given to the supervisor while the original is served.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    verifying: HashMap<PeerId, Verification>,
+    peers: BTreeMap<String, PeerMetrics>,
+    forks_detected: u64,
+    trusted_height: Option<Height>,
+    trust_expires_at: Option<Time>,
+    pruned_blocks: u64,
+    light_store_bytes: Option<usize>,
+}
+
+// The verification in progress of a peer.
+#[derive(Debug)]
+struct Verification {
+    started: Instant,
+    steps: u64,
+}
+
+#[derive(Debug)]
+struct PeerMetrics {
+    steps: Histogram,
+    duration: Histogram,
+    verification_failures: u64,
+    rpc_failures: u64,
+}
+
+impl Default for PeerMetrics {
+    fn default() -> Self {
+        Self {
+            steps: Histogram::new(&STEP_BUCKETS),
+            duration: Histogram::new(&LATENCY_BUCKETS),
+            verification_failures: 0,
+            rpc_failures: 0,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the given event of a supervisor.
+    ///
+    /// This is done by the supervisor given these metrics with
+    /// [`Supervisor::metrics`](crate::supervisor::Supervisor::metrics).
+    pub fn observe(&self, event: &SupervisorEvent) {
+        let mut inner = self.inner.lock().unwrap();
+        match event {
+            SupervisorEvent::Verification { peer, event } => inner.verification(*peer, event),
+            SupervisorEvent::BlockTrusted { height, expires_at } => {
+                inner.trusted_height = Some(*height);
+                inner.trust_expires_at = Some(*expires_at);
+            },
+            SupervisorEvent::ForkDetected { peers } => {
+                inner.forks_detected += peers.len() as u64;
+            },
+            SupervisorEvent::EvidenceSubmissionFailed { peer, .. } => {
+                inner.peer(*peer).rpc_failures += 1;
+            },
+            SupervisorEvent::LightStorePruned {
+                pruned,
+                approximate_size,
+            } => {
+                inner.pruned_blocks += pruned.len() as u64;
+                inner.light_store_bytes = Some(*approximate_size);
+            },
+            _ => {},
+        }
+    }
+
+    /// Record the given event of the light client of the given peer, for
+    /// light clients used without a supervisor (see
+    /// [`LightClient::on_event`](crate::light_client::LightClient::on_event)).
+    pub fn observe_verification(&self, peer: PeerId, event: &VerificationEvent) {
+        self.inner.lock().unwrap().verification(peer, event);
+    }
+
+    /// Render the metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let inner = self.inner.lock().unwrap();
+        let mut encoder = Encoder::new();
+
+        let name = "tendermint_light_client_bisection_steps";
+        encoder.family(
+            name,
+            MetricType::Histogram,
+            "Blocks checked to verify a target block.",
+        );
+        for (peer, m) in &inner.peers {
+            encoder.histogram(name, &[("peer", peer.as_str())], &m.steps);
+        }
+
+        let name = "tendermint_light_client_verification_duration_seconds";
+        encoder.family(
+            name,
+            MetricType::Histogram,
+            "Latency of the successful verifications.",
+        );
+        for (peer, m) in &inner.peers {
+            encoder.histogram(name, &[("peer", peer.as_str())], &m.duration);
+        }
+
+        let name = "tendermint_light_client_verification_failures_total";
+        encoder.family(
+            name,
+            MetricType::Counter,
+            "Verifications failed on an invalid block.",
+        );
+        for (peer, m) in &inner.peers {
+            encoder.sample(name, &[("peer", peer.as_str())], m.verification_failures);
+        }
+
+        let name = "tendermint_light_client_rpc_failures_total";
+        encoder.family(
+            name,
+            MetricType::Counter,
+            "Failures of the peers to serve a block or to accept evidence.",
+        );
+        for (peer, m) in &inner.peers {
+            encoder.sample(name, &[("peer", peer.as_str())], m.rpc_failures);
+        }
+
+        let name = "tendermint_light_client_forks_detected_total";
+        encoder.family(
+            name,
+            MetricType::Counter,
+            "Witnesses found to fork from the primary.",
+        );
+        encoder.sample(name, &[], inner.forks_detected);
+
+        if let (Some(height), Some(expires_at)) = (inner.trusted_height, inner.trust_expires_at) {
+            let name = "tendermint_light_client_trusted_height";
+            encoder.family(
+                name,
+                MetricType::Gauge,
+                "Height of the latest trusted block.",
+            );
+            encoder.sample(name, &[], height);
+
+            let name = "tendermint_light_client_trusting_period_remaining_seconds";
+            encoder.family(
+                name,
+                MetricType::Gauge,
+                "Time before the latest trusted block leaves the trusting period.",
+            );
+            let remaining = expires_at
+                .duration_since(SystemClock.now())
+                .unwrap_or_default();
+            encoder.sample(name, &[], remaining.as_secs_f64());
+        }
+
+        let name = "tendermint_light_client_pruned_blocks_total";
+        encoder.family(
+            name,
+            MetricType::Counter,
+            "Light blocks pruned from the light store.",
+        );
+        encoder.sample(name, &[], inner.pruned_blocks);
+
+        if let Some(bytes) = inner.light_store_bytes {
+            let name = "tendermint_light_client_light_store_bytes";
+            encoder.family(
+                name,
+                MetricType::Gauge,
+                "Approximate size of the light store.",
+            );
+            encoder.sample(name, &[], bytes);
+        }
+
+        encoder.finish()
+    }
+}
+
+impl Inner {
+    fn peer(&mut self, peer: PeerId) -> &mut PeerMetrics {
+        self.peers.entry(peer.to_string()).or_default()
+    }
+
+    fn verification(&mut self, peer: PeerId, event: &VerificationEvent) {
+        match event {
+            VerificationEvent::Started { .. } => {
+                self.verifying.insert(
+                    peer,
+                    Verification {
+                        started: Instant::now(),
+                        steps: 0,
+                    },
+                );
+            },
+            VerificationEvent::BisectionStep { outcome, .. } => {
+                if let Some(verification) = self.verifying.get_mut(&peer) {
+                    verification.steps += 1;
+                }
+                if *outcome == StepOutcome::Invalid {
+                    self.verifying.remove(&peer);
+                    self.peer(peer).verification_failures += 1;
+                }
+            },
+            VerificationEvent::Verified { .. } => {
+                if let Some(verification) = self.verifying.remove(&peer) {
+                    let m = self.peer(peer);
+                    m.steps.observe(verification.steps as f64);
+                    m.duration
+                        .observe(verification.started.elapsed().as_secs_f64());
+                }
+            },
+            VerificationEvent::FetchFailed { .. } => {
+                self.peer(peer).rpc_failures += 1;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn renders_metrics_per_peer() {
+        let metrics = Metrics::new();
+        let peer = PeerId::new([1; 20]);
+        let height = |h: u32| Height::from(h);
+        for event in [
+            VerificationEvent::Started {
+                target: height(10),
+                trusted: height(1),
+            },
+            VerificationEvent::BisectionStep {
+                trusted: height(1),
+                height: height(10),
+                outcome: StepOutcome::NotEnoughTrust,
+            },
+            VerificationEvent::BisectionStep {
+                trusted: height(1),
+                height: height(5),
+                outcome: StepOutcome::Verified,
+            },
+            VerificationEvent::BisectionStep {
+                trusted: height(5),
+                height: height(10),
+                outcome: StepOutcome::Verified,
+            },
+            VerificationEvent::Verified { target: height(10) },
+            VerificationEvent::FetchFailed {
+                height: None,
+                reason: "timeout".to_string(),
+            },
+        ] {
+            metrics.observe(&SupervisorEvent::Verification { peer, event });
+        }
+        metrics.observe(&SupervisorEvent::ForkDetected {
+            peers: vec![PeerId::new([2; 20]), PeerId::new([3; 20])],
+        });
+        let expires_at = (SystemClock.now() + Duration::from_secs(3600)).unwrap();
+        metrics.observe(&SupervisorEvent::BlockTrusted {
+            height: height(10),
+            expires_at,
+        });
+        metrics.observe(&SupervisorEvent::LightStorePruned {
+            pruned: vec![height(2), height(3)],
+            approximate_size: 4096,
+        });
+
+        let rendered = metrics.render();
+        let peer = peer.to_string();
+        for line in [
+            format!("tendermint_light_client_bisection_steps_bucket{{peer=\"{peer}\",le=\"2\"}} 0"),
+            format!("tendermint_light_client_bisection_steps_bucket{{peer=\"{peer}\",le=\"4\"}} 1"),
+            format!("tendermint_light_client_bisection_steps_sum{{peer=\"{peer}\"}} 3"),
+            format!(
+                "tendermint_light_client_verification_duration_seconds_count{{peer=\"{peer}\"}} 1"
+            ),
+            format!("tendermint_light_client_verification_failures_total{{peer=\"{peer}\"}} 0"),
+            format!("tendermint_light_client_rpc_failures_total{{peer=\"{peer}\"}} 1"),
+            "tendermint_light_client_forks_detected_total 2".to_string(),
+            "tendermint_light_client_trusted_height 10".to_string(),
+            "tendermint_light_client_pruned_blocks_total 2".to_string(),
+            "tendermint_light_client_light_store_bytes 4096".to_string(),
+            "# TYPE tendermint_light_client_trusting_period_remaining_seconds gauge".to_string(),
+        ] {
+            assert!(
+                rendered.lines().any(|l| l == line),
+                "missing {line:?} in\n{rendered}"
+            );
+        }
+
+        let remaining = rendered
+            .lines()
+            .find_map(|l| {
+                l.strip_prefix("tendermint_light_client_trusting_period_remaining_seconds ")
+            })
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap();
+        assert!(remaining > 3500.0 && remaining <= 3600.0, "{remaining}");
+    }
+}
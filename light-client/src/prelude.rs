@@ -0,0 +1,25 @@
+//! The types and traits most commonly needed to verify headers and run a
+//! light client, including the ones of [`tendermint::prelude`], to be
+//! glob-imported:
+//!
+//! ```
+//! use tendermint_light_client::prelude::*;
+//! ```
+
+pub use tendermint::prelude::*;
+
+#[cfg(feature = "rpc-client")]
+pub use crate::{components::io::ProdIo, verified_client::VerifiedClient};
+#[cfg(feature = "rust-crypto")]
+pub use crate::verifier::ProdVerifier;
+pub use crate::{
+    builder::{LightClientBuilder, SupervisorBuilder},
+    components::io::{AsyncIo, Io},
+    light_client::{LightClient, Options},
+    store::{memory::MemoryStore, LightStore},
+    supervisor::{Handle, Instance, Supervisor, SupervisorHandle},
+    verifier::{
+        types::{LightBlock, Status, TrustedBlockState, UntrustedBlockState},
+        Verdict, Verifier,
+    },
+};
@@ -0,0 +1,259 @@
+//! Pruning of the light blocks accumulated in the light store of a
+//! long-running light client.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use crate::{
+    state::State,
+    store::LightStore,
+    verifier::types::{Height, Status, Time},
+};
+
+/// Which light blocks to keep in the light store when pruning it, see
+/// [`Supervisor::pruning_policy`](crate::supervisor::Supervisor::pruning_policy).
+///
+/// A light block is kept if any of the rules of the policy keeps it. Whatever
+/// the policy, the lowest and the latest trusted or verified blocks, the light
+/// blocks above the latter, and the light blocks pinned by a checkpoint are
+/// always kept, so that any height above the lowest trusted block can still
+/// be verified: a height below the latest trusted block is verified from the
+/// highest trusted block kept below it, and backward verification (with the
+/// `unstable` feature) from the lowest trusted block kept above it.
+///
+/// Verifying a height from a trusted block requires the latter to be within
+/// the trusting period, which only the blocks kept by
+/// [`PruningPolicy::keep_within_trusting_period`] are guaranteed to be.
+///
+/// With a [`PruningPolicy::max_bytes`] budget, the light blocks kept by the
+/// rules are pruned as well, lowest first, until the light store fits in the
+/// budget or only the light blocks which are always kept remain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PruningPolicy {
+    /// Keep this many of the latest trusted or verified light blocks.
+    pub keep_last: Option<usize>,
+    /// Keep the light blocks which are still within the trusting period.
+    pub keep_within_trusting_period: bool,
+    /// Keep the light blocks whose height is a multiple of this interval, so
+    /// that verifying a pruned height never has to start more than this many
+    /// blocks away from it.
+    pub checkpoint_interval: Option<u64>,
+    /// Keep the approximate size of the light store (see
+    /// [`LightStore::approximate_size`](crate::store::LightStore::approximate_size))
+    /// under this many bytes.
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for PruningPolicy {
+    /// Keep the light blocks which are still within the trusting period.
+    fn default() -> Self {
+        Self {
+            keep_last: None,
+            keep_within_trusting_period: true,
+            checkpoint_interval: None,
+            max_bytes: None,
+        }
+    }
+}
+
+impl PruningPolicy {
+    /// Remove the light blocks which this policy does not keep from the light
+    /// store of the given state, along with their verification traces, and
+    /// return their heights.
+    ///
+    /// Does nothing if there is no trusted or verified light block.
+    pub fn prune(&self, state: &mut State, trusting_period: Duration, now: Time) -> Vec<Height> {
+        let store = &mut state.light_store;
+        let (lowest, latest) = match (
+            store.lowest_trusted_or_verified(),
+            store.highest_trusted_or_verified(),
+        ) {
+            (Some(lowest), Some(latest)) => (lowest.height(), latest.height()),
+            _ => return Vec::new(),
+        };
+
+        let mut trusted: Vec<Height> = store
+            .all(Status::Trusted)
+            .chain(store.all(Status::Verified))
+            .map(|lb| lb.height())
+            .collect();
+        trusted.sort_unstable_by(|a, b| b.cmp(a));
+        let latest_kept: BTreeSet<Height> = match self.keep_last {
+            Some(count) => trusted.into_iter().take(count).collect(),
+            None => BTreeSet::new(),
+        };
+
+        let always_kept = |store: &dyn LightStore, height| {
+            height >= latest || height == lowest || store.provenance(height).is_some()
+        };
+        let mut pruned = BTreeSet::new();
+        for status in Status::iter() {
+            let blocks: Vec<_> = store.all(*status).collect();
+            for lb in blocks {
+                let height = lb.height();
+                let keep = always_kept(store.as_ref(), height)
+                    || latest_kept.contains(&height)
+                    || (self.keep_within_trusting_period
+                        && (lb.time() + trusting_period).map_or(true, |expiry| expiry > now))
+                    || self
+                        .checkpoint_interval
+                        .is_some_and(|k| k != 0 && height.value() % k == 0);
+                if !keep {
+                    store.remove(height, *status);
+                    pruned.insert(height);
+                }
+            }
+        }
+
+        if let Some(max_bytes) = self.max_bytes {
+            let mut size = store.approximate_size();
+            if size > max_bytes {
+                let mut prunable: Vec<_> = Status::iter()
+                    .iter()
+                    .flat_map(|status| store.all(*status).map(move |lb| (lb, *status)))
+                    .filter(|(lb, _)| !always_kept(store.as_ref(), lb.height()))
+                    .map(|(lb, status)| (lb.height(), status, lb.approximate_size()))
+                    .collect();
+                prunable.sort_unstable_by_key(|(height, ..)| *height);
+                for (height, status, lb_size) in prunable {
+                    if size <= max_bytes {
+                        break;
+                    }
+                    store.remove(height, status);
+                    size = size.saturating_sub(lb_size);
+                    pruned.insert(height);
+                }
+            }
+        }
+
+        state
+            .verification_trace
+            .retain(|target, _| !pruned.contains(target));
+        for heights in state.verification_trace.values_mut() {
+            heights.retain(|height| !pruned.contains(height));
+        }
+
+        pruned.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint_testgen::{light_block::TmLightBlock as TGLightBlock, Generator, LightChain};
+
+    use super::*;
+    use crate::{
+        store::{memory::MemoryStore, LightStore, Provenance},
+        verifier::types::LightBlock,
+    };
+
+    fn state(count: u64) -> (State, Vec<LightBlock>) {
+        let blocks: Vec<LightBlock> = LightChain::default_with_length(count)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap())
+            .map(|lb: TGLightBlock| {
+                LightBlock::new(
+                    lb.signed_header,
+                    lb.validators,
+                    lb.next_validators,
+                    lb.provider,
+                )
+            })
+            .collect();
+        let mut store = MemoryStore::new();
+        for lb in &blocks {
+            store.insert(lb.clone(), Status::Trusted);
+        }
+        (State::new(store), blocks)
+    }
+
+    fn heights(state: &State) -> Vec<u64> {
+        Status::iter()
+            .iter()
+            .flat_map(|status| state.light_store.all(*status))
+            .map(|lb| lb.height().value())
+            .collect()
+    }
+
+    fn height(h: u64) -> Height {
+        Height::try_from(h).unwrap()
+    }
+
+    #[test]
+    fn keeps_the_blocks_of_any_rule() {
+        let (mut state, blocks) = state(12);
+        state.light_store.update(&blocks[11], Status::Verified);
+        state.light_store.insert(blocks[3].clone(), Status::Failed);
+        state
+            .light_store
+            .set_provenance(height(2), Provenance::Checkpoint);
+        state.trace_block(height(12), height(3));
+        state.trace_block(height(12), height(5));
+
+        let policy = PruningPolicy {
+            keep_last: Some(3),
+            keep_within_trusting_period: false,
+            checkpoint_interval: Some(5),
+            max_bytes: None,
+        };
+        let pruned = policy.prune(&mut state, Duration::from_secs(3600), Time::now());
+
+        assert_eq!(
+            pruned,
+            [3, 4, 6, 7, 8, 9]
+                .into_iter()
+                .map(height)
+                .collect::<Vec<_>>()
+        );
+        let mut kept = heights(&state);
+        kept.sort_unstable();
+        assert_eq!(kept, [1, 2, 5, 10, 11, 12]);
+        assert_eq!(
+            state.verification_trace[&height(12)],
+            [height(5)].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn keeps_the_blocks_within_the_trusting_period() {
+        let (mut state, blocks) = state(5);
+        // The testgen blocks are one second apart.
+        let now = (blocks[4].time() + Duration::from_secs(10)).unwrap();
+
+        let pruned = PruningPolicy::default().prune(&mut state, Duration::from_millis(12_500), now);
+
+        assert_eq!(pruned, [height(2)]);
+        assert_eq!(heights(&state), [1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn prunes_the_lowest_blocks_beyond_the_budget() {
+        let (mut state, blocks) = state(6);
+        state
+            .light_store
+            .set_provenance(height(3), Provenance::Checkpoint);
+        // All the blocks are within the trusting period.
+        let now = blocks[5].time();
+        let block_size = blocks[0].approximate_size();
+        assert_eq!(state.light_store.approximate_size(), 6 * block_size);
+
+        let policy = PruningPolicy {
+            max_bytes: Some(4 * block_size),
+            ..PruningPolicy::default()
+        };
+        let pruned = policy.prune(&mut state, Duration::from_secs(3600), now);
+
+        // The lowest, latest and pinned blocks are always kept.
+        assert_eq!(pruned, [height(2), height(4)]);
+        assert_eq!(heights(&state), [1, 3, 5, 6]);
+        assert_eq!(state.light_store.approximate_size(), 4 * block_size);
+
+        let policy = PruningPolicy {
+            max_bytes: Some(0),
+            ..policy
+        };
+        let pruned = policy.prune(&mut state, Duration::from_secs(3600), now);
+        assert_eq!(pruned, [height(5)]);
+        assert_eq!(heights(&state), [1, 3, 6]);
+    }
+}
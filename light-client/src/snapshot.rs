@@ -0,0 +1,80 @@
+//! Snapshots of the trusted state of a light client.
+//!
+//! A snapshot holds the latest trusted light block along with a few of its
+//! ancestors, so that a new light client can be bootstrapped from it
+//! instead of from a trusted height and hash: see
+//! [`LightClientBuilder::trust_snapshot`](crate::builder::LightClientBuilder::trust_snapshot).
+//!
+//! A snapshot is not trusted on its own: the hash of the header of its
+//! trusted block must be obtained out of band, from a source the user
+//! trusts. As the commit of that block signs over the header, and each
+//! header links to the one of its parent, this hash covers the whole
+//! snapshot.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::Error,
+    store::LightStore,
+    verifier::types::{Height, LightBlock},
+};
+
+/// A snapshot of the trusted state of a light client.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedStateSnapshot {
+    /// The latest trusted light block
+    pub trusted: LightBlock,
+
+    /// The ancestors of the trusted light block, in descending order of
+    /// height, each one being the parent of the one before it
+    pub ancestry: Vec<LightBlock>,
+}
+
+impl TrustedStateSnapshot {
+    /// Take a snapshot of the highest trusted or verified light block in the
+    /// given store, along with at most `ancestry` of its ancestors.
+    ///
+    /// Only the ancestors at the heights right below the trusted light block
+    /// which are in the store are included, as they are linked to it by the
+    /// hashes of their headers. Returns `None` if the store has no trusted
+    /// or verified light block.
+    pub fn from_store(light_store: &dyn LightStore, ancestry: usize) -> Option<Self> {
+        let trusted = light_store.highest_trusted_or_verified()?;
+
+        let mut ancestors = Vec::with_capacity(ancestry);
+        let mut height = trusted.height();
+        while ancestors.len() < ancestry {
+            let parent = match height.value().checked_sub(1).map(Height::try_from) {
+                Some(Ok(parent)) => parent,
+                _ => break,
+            };
+
+            match light_store.get_trusted_or_verified(parent) {
+                Some(light_block) => ancestors.push(light_block),
+                None => break,
+            }
+
+            height = parent;
+        }
+
+        Some(Self {
+            trusted,
+            ancestry: ancestors,
+        })
+    }
+
+    /// The height of the trusted light block.
+    pub fn height(&self) -> Height {
+        self.trusted.height()
+    }
+
+    /// Encode the snapshot in its compact binary (CBOR) form.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        serde_cbor::to_vec(self).map_err(Error::serde_cbor)
+    }
+
+    /// Decode a snapshot from its compact binary (CBOR) form.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        serde_cbor::from_slice(bytes).map_err(Error::serde_cbor)
+    }
+}
@@ -4,20 +4,35 @@
 //!
 //! - a transient, in-memory implementation for testing purposes
 //! - a persistent, on-disk, sled-backed implementation for production
+//!
+//! The `log` module provides a wrapper of any store keeping an append-only log
+//! of what the light client trusted and when.
 
 use std::fmt::Debug;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     utils::std_ext,
     verifier::types::{Height, LightBlock, Status},
 };
 
+pub mod log;
 pub mod memory;
 
 #[cfg(feature = "lightstore-sled")]
 #[cfg_attr(docsrs, doc(cfg(feature = "lightstore-sled")))]
 pub mod sled;
 
+/// How a light block came to be trusted, when not by being verified from
+/// another trusted light block.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Provenance {
+    /// The hash of the header of the light block was pinned by a checkpoint
+    /// supplied by the user, see [`Checkpoints`](crate::checkpoint::Checkpoints).
+    Checkpoint,
+}
+
 /// Store for light blocks.
 ///
 /// The light store records light blocks received from peers, and their verification status.
@@ -99,9 +114,49 @@ pub trait LightStore: Debug + Send + Sync {
         })
     }
 
+    /// Get the light block of lowest height above the given height with the
+    /// trusted or verified status.
+    fn lowest_trusted_or_verified_after(&self, height: Height) -> Option<LightBlock> {
+        self.all(Status::Trusted)
+            .chain(self.all(Status::Verified))
+            .filter(|lb| lb.height() > height)
+            .min_by_key(|lb| lb.height())
+    }
+
     /// Get the light block of the given height with the trusted or verified status.
     fn get_trusted_or_verified(&self, height: Height) -> Option<LightBlock> {
         self.get(height, Status::Trusted)
             .or_else(|| self.get(height, Status::Verified))
     }
+
+    /// Get an approximation of the memory used by the light blocks of the
+    /// store, in bytes (see [`LightBlock::approximate_size`]).
+    ///
+    /// The default implementation goes through all the light blocks.
+    fn approximate_size(&self) -> usize {
+        Status::iter()
+            .iter()
+            .flat_map(|status| self.all(*status))
+            .map(|lb| lb.approximate_size())
+            .sum()
+    }
+
+    /// Get the provenance of the light block at the given height, if one was
+    /// recorded.
+    ///
+    /// The stores which do not record provenances return `None`.
+    fn provenance(&self, _height: Height) -> Option<Provenance> {
+        None
+    }
+
+    /// Record the provenance of the light block at the given height.
+    ///
+    /// The stores which do not record provenances ignore it.
+    fn set_provenance(&mut self, _height: Height, _provenance: Provenance) {}
+
+    /// Record that the given light block, received from a witness, conflicts
+    /// with the light block trusted at the same height.
+    ///
+    /// The stores which do not record conflicts ignore it.
+    fn record_conflict(&mut self, _conflicting: &LightBlock) {}
 }
@@ -0,0 +1,482 @@
+//! Append-only log of the light blocks trusted by the light client, and of
+//! when they stopped being trusted, maintained by a wrapper of a light store.
+//!
+//! The log keeps its latest entries in memory, up to its capacity, and can
+//! write all of them to a sink, e.g. a file, as they are recorded.
+//!
+//! ## Example
+//!
+//! ```
+//! use tendermint_light_client::store::{
+//!     log::{LoggedStore, TrustLog},
+//!     memory::MemoryStore,
+//! };
+//!
+//! let log = TrustLog::new();
+//! let light_store = LoggedStore::new(MemoryStore::new(), log.clone());
+//! // Give the light store to the light client, and keep the log to query it.
+//! assert!(log.is_empty());
+//! # drop(light_store);
+//! ```
+
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    ops::RangeBounds,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::clock::{Clock, SystemClock},
+    store::{LightStore, Provenance},
+    verifier::types::{Hash, Height, LightBlock, Status, Time},
+};
+
+/// The default number of entries kept in memory by a [`TrustLog`].
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// What happened to a light block recorded in a [`TrustLog`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogAction {
+    /// The light block became trusted.
+    Trusted,
+    /// The light block stopped being trusted, be it because it was removed
+    /// from the store or because its status changed.
+    Pruned,
+    /// The light block conflicts with the one trusted at the same height, as
+    /// found out by the fork detection.
+    Conflict,
+}
+
+impl fmt::Display for LogAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Trusted => write!(f, "trusted"),
+            Self::Pruned => write!(f, "pruned"),
+            Self::Conflict => write!(f, "conflict"),
+        }
+    }
+}
+
+/// An entry of a [`TrustLog`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// When the entry was recorded, according to the clock of the log.
+    pub timestamp: Time,
+    /// The height of the light block.
+    pub height: Height,
+    /// The hash of the header of the light block.
+    pub hash: Hash,
+    /// What happened to the light block.
+    pub action: LogAction,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.timestamp.to_rfc3339(),
+            self.height,
+            self.hash,
+            self.action
+        )
+    }
+}
+
+/// An append-only log of what the light client trusted and when, filled by a
+/// [`LoggedStore`].
+///
+/// Cloning a log is cheap, and the clones share the same entries, so that a
+/// clone can be kept to query the log once the store has been handed over to
+/// the light client. Entries can only ever be appended, by the store.
+///
+/// Only the latest entries are kept in memory, up to the capacity of the log
+/// ([`DEFAULT_CAPACITY`] unless set with [`TrustLog::capacity`]): the oldest
+/// entries are dropped to make room for the new ones. Give the log a sink
+/// with [`TrustLog::sink`] to keep all of them.
+#[derive(Clone)]
+pub struct TrustLog {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    clock: Box<dyn Clock>,
+    entries: Mutex<Entries>,
+    sink: Mutex<Option<Box<dyn io::Write + Send>>>,
+}
+
+struct Entries {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl TrustLog {
+    /// Create an empty log, whose entries are timestamped with the wall clock
+    /// time of the system.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    /// Create an empty log, whose entries are timestamped with the time of
+    /// the given clock.
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                clock: Box::new(clock),
+                entries: Mutex::new(Entries {
+                    entries: VecDeque::new(),
+                    capacity: DEFAULT_CAPACITY,
+                }),
+                sink: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Keep at most the given number of the latest entries in memory,
+    /// dropping the oldest ones beyond it.
+    pub fn capacity(self, capacity: usize) -> Self {
+        {
+            let mut entries = self.inner.entries();
+            entries.capacity = capacity;
+            let excess = entries.entries.len().saturating_sub(capacity);
+            entries.entries.drain(..excess);
+        }
+        self
+    }
+
+    /// Also write every new entry to the given sink as soon as it is
+    /// recorded, one per line in the format of [`TrustLog::export`], e.g. to
+    /// a file opened in append mode, so that the log outlives the process.
+    ///
+    /// Failing to write to the sink does not fail the operation of the store
+    /// which recorded the entry. The log can be queried while an entry is
+    /// being written to the sink.
+    pub fn sink(self, sink: impl io::Write + Send + 'static) -> Self {
+        *self.inner.sink() = Some(Box::new(sink));
+        self
+    }
+
+    /// The number of entries of the log kept in memory.
+    pub fn len(&self) -> usize {
+        self.inner.entries().entries.len()
+    }
+
+    /// Whether the log has no entry in memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All the entries of the log kept in memory, in the order in which they
+    /// were recorded.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.inner.entries().entries.iter().cloned().collect()
+    }
+
+    /// The entries of the light blocks whose height is within the given
+    /// range, in the order in which they were recorded.
+    pub fn heights(&self, range: impl RangeBounds<Height>) -> Vec<LogEntry> {
+        self.filter(|entry| range.contains(&entry.height))
+    }
+
+    /// The entries recorded within the given range of time, in the order in
+    /// which they were recorded.
+    pub fn between(&self, range: impl RangeBounds<Time>) -> Vec<LogEntry> {
+        self.filter(|entry| range.contains(&entry.timestamp))
+    }
+
+    /// Write all the entries of the log kept in memory to the given writer,
+    /// one per line, as their timestamp in RFC 3339 format, height, hash and
+    /// action, separated by spaces.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if writing to the writer fails.
+    pub fn export(&self, mut writer: impl io::Write) -> io::Result<()> {
+        for entry in self.entries() {
+            writeln!(writer, "{entry}")?;
+        }
+        writer.flush()
+    }
+
+    fn filter(&self, predicate: impl Fn(&LogEntry) -> bool) -> Vec<LogEntry> {
+        self.inner
+            .entries()
+            .entries
+            .iter()
+            .filter(|entry| predicate(entry))
+            .cloned()
+            .collect()
+    }
+
+    fn append(&self, light_block: &LightBlock, action: LogAction) {
+        let entry = LogEntry {
+            timestamp: self.inner.clock.now(),
+            height: light_block.height(),
+            hash: light_block.signed_header.header.hash(),
+            action,
+        };
+
+        // The sink is locked first, so that the entries are written to it in
+        // the order in which they are recorded, but written to once the
+        // entries are unlocked, so that a slow sink does not block queries.
+        let mut sink = self.inner.sink();
+        {
+            let mut entries = self.inner.entries();
+            if entries.capacity > 0 {
+                if entries.entries.len() >= entries.capacity {
+                    entries.entries.pop_front();
+                }
+                entries.entries.push_back(entry.clone());
+            }
+        }
+        if let Some(sink) = sink.as_mut() {
+            writeln!(sink, "{entry}").and_then(|_| sink.flush()).ok();
+        }
+    }
+}
+
+impl Inner {
+    // A panic while the lock was held, e.g. in the sink, leaves the entries
+    // consistent: the lock is recovered rather than propagating the panic.
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn sink(&self) -> MutexGuard<'_, Option<Box<dyn io::Write + Send>>> {
+        self.sink.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for TrustLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TrustLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustLog")
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+/// A light store recording in a [`TrustLog`] the light blocks which become
+/// trusted in the wrapped store, those which stop being trusted, and the
+/// conflicting blocks reported with [`LightStore::record_conflict`].
+#[derive(Debug)]
+pub struct LoggedStore<S> {
+    store: S,
+    log: TrustLog,
+}
+
+impl<S: LightStore> LoggedStore<S> {
+    /// Wrap the given store, recording its changes in the given log.
+    pub fn new(store: S, log: TrustLog) -> Self {
+        Self { store, log }
+    }
+
+    /// The log of the store.
+    pub fn log(&self) -> &TrustLog {
+        &self.log
+    }
+
+    /// Unwrap the wrapped store.
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+
+    // Record the changes of the trusted block at the given height made by
+    // the given operation on the wrapped store.
+    fn logged(&mut self, height: Height, operation: impl FnOnce(&mut S)) {
+        let before = self.store.get(height, Status::Trusted);
+        operation(&mut self.store);
+        let after = self.store.get(height, Status::Trusted);
+
+        if before == after {
+            return;
+        }
+        if let Some(before) = before {
+            self.log.append(&before, LogAction::Pruned);
+        }
+        if let Some(after) = after {
+            self.log.append(&after, LogAction::Trusted);
+        }
+    }
+}
+
+impl<S: LightStore> LightStore for LoggedStore<S> {
+    fn get(&self, height: Height, status: Status) -> Option<LightBlock> {
+        self.store.get(height, status)
+    }
+
+    fn update(&mut self, light_block: &LightBlock, status: Status) {
+        self.logged(light_block.height(), |store| {
+            store.update(light_block, status)
+        });
+    }
+
+    fn insert(&mut self, light_block: LightBlock, status: Status) {
+        self.logged(light_block.height(), |store| {
+            store.insert(light_block, status)
+        });
+    }
+
+    fn remove(&mut self, height: Height, status: Status) {
+        self.logged(height, |store| store.remove(height, status));
+    }
+
+    fn highest(&self, status: Status) -> Option<LightBlock> {
+        self.store.highest(status)
+    }
+
+    fn highest_before(&self, height: Height, status: Status) -> Option<LightBlock> {
+        self.store.highest_before(height, status)
+    }
+
+    fn lowest(&self, status: Status) -> Option<LightBlock> {
+        self.store.lowest(status)
+    }
+
+    fn all(&self, status: Status) -> Box<dyn Iterator<Item = LightBlock>> {
+        self.store.all(status)
+    }
+
+    fn approximate_size(&self) -> usize {
+        self.store.approximate_size()
+    }
+
+    fn provenance(&self, height: Height) -> Option<Provenance> {
+        self.store.provenance(height)
+    }
+
+    fn set_provenance(&mut self, height: Height, provenance: Provenance) {
+        self.store.set_provenance(height, provenance)
+    }
+
+    fn record_conflict(&mut self, conflicting: &LightBlock) {
+        self.log.append(conflicting, LogAction::Conflict);
+        self.store.record_conflict(conflicting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic::AssertUnwindSafe, time::Duration};
+
+    use tendermint_testgen::{light_block::TmLightBlock as TGLightBlock, Generator, LightChain};
+
+    use super::*;
+    use crate::{components::clock::FixedClock, store::memory::MemoryStore};
+
+    fn blocks(count: u64) -> Vec<LightBlock> {
+        LightChain::default_with_length(count)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap())
+            .map(|lb: TGLightBlock| {
+                LightBlock::new(
+                    lb.signed_header,
+                    lb.validators,
+                    lb.next_validators,
+                    lb.provider,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_trusted_pruned_and_conflicting_blocks() {
+        let now = Time::from_unix_timestamp(1_700_000_000, 0).unwrap();
+        let log = TrustLog::with_clock(FixedClock(now));
+        let mut store = LoggedStore::new(MemoryStore::new(), log.clone());
+        let blocks = blocks(4);
+
+        store.insert(blocks[0].clone(), Status::Trusted);
+        store.insert(blocks[1].clone(), Status::Unverified);
+        store.update(&blocks[1], Status::Verified);
+        store.update(&blocks[1], Status::Trusted);
+        // Trusting a trusted block again is not recorded.
+        store.update(&blocks[1], Status::Trusted);
+        store.insert(blocks[2].clone(), Status::Trusted);
+        store.remove(blocks[0].height(), Status::Trusted);
+        store.record_conflict(&blocks[3]);
+
+        let entry = |lb: &LightBlock, action| LogEntry {
+            timestamp: now,
+            height: lb.height(),
+            hash: lb.signed_header.header.hash(),
+            action,
+        };
+        assert_eq!(
+            log.entries(),
+            vec![
+                entry(&blocks[0], LogAction::Trusted),
+                entry(&blocks[1], LogAction::Trusted),
+                entry(&blocks[2], LogAction::Trusted),
+                entry(&blocks[0], LogAction::Pruned),
+                entry(&blocks[3], LogAction::Conflict),
+            ]
+        );
+
+        assert_eq!(
+            log.heights(blocks[1].height()..=blocks[2].height()),
+            vec![
+                entry(&blocks[1], LogAction::Trusted),
+                entry(&blocks[2], LogAction::Trusted),
+            ]
+        );
+        assert_eq!(log.between(..now).len(), 0);
+        assert_eq!(
+            log.between(now..(now + Duration::from_secs(1)).unwrap())
+                .len(),
+            5
+        );
+
+        let mut exported = Vec::new();
+        log.export(&mut exported).unwrap();
+        let exported = String::from_utf8(exported).unwrap();
+        assert_eq!(
+            exported.lines().next().unwrap(),
+            format!(
+                "2023-11-14T22:13:20Z 1 {} trusted",
+                blocks[0].signed_header.header.hash()
+            )
+        );
+        assert_eq!(exported.lines().count(), 5);
+    }
+
+    #[test]
+    fn keeps_the_latest_entries_despite_a_panicking_sink() {
+        struct PanickingSink;
+
+        impl io::Write for PanickingSink {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                panic!("the sink failed");
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let log = TrustLog::new().capacity(2).sink(PanickingSink);
+        let mut store = LoggedStore::new(MemoryStore::new(), log.clone());
+        let blocks = blocks(3);
+
+        // Every panic poisons the lock of the sink, which is recovered by
+        // the next entry.
+        for block in &blocks {
+            let inserted = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                store.insert(block.clone(), Status::Trusted)
+            }));
+            assert!(inserted.is_err());
+        }
+
+        let heights: Vec<_> = log.entries().iter().map(|entry| entry.height).collect();
+        assert_eq!(heights, vec![blocks[1].height(), blocks[2].height()]);
+    }
+}
@@ -3,7 +3,7 @@
 use std::collections::{btree_map::Entry::*, BTreeMap};
 
 use crate::{
-    store::{LightStore, Status},
+    store::{LightStore, Provenance, Status},
     verifier::types::{Height, LightBlock},
 };
 
@@ -27,6 +27,9 @@ impl StoreEntry {
 #[derive(Debug, Clone, Default)]
 pub struct MemoryStore {
     store: BTreeMap<Height, StoreEntry>,
+    provenances: BTreeMap<Height, Provenance>,
+    // The approximate size of the light blocks of the store.
+    size: usize,
 }
 
 impl MemoryStore {
@@ -34,6 +37,8 @@ impl MemoryStore {
     pub fn new() -> Self {
         Self {
             store: BTreeMap::new(),
+            provenances: BTreeMap::new(),
+            size: 0,
         }
     }
 }
@@ -48,14 +53,21 @@ impl LightStore for MemoryStore {
     }
 
     fn insert(&mut self, light_block: LightBlock, status: Status) {
-        self.store
+        self.size += light_block.approximate_size();
+        let replaced = self
+            .store
             .insert(light_block.height(), StoreEntry::new(light_block, status));
+        if let Some(replaced) = replaced {
+            self.size -= replaced.light_block.approximate_size();
+        }
     }
 
     fn remove(&mut self, height: Height, status: Status) {
         if let Occupied(e) = self.store.entry(height) {
             if e.get().status == status {
-                e.remove_entry();
+                let (_, removed) = e.remove_entry();
+                self.size -= removed.light_block.approximate_size();
+                self.provenances.remove(&height);
             }
         }
     }
@@ -100,4 +112,16 @@ impl LightStore for MemoryStore {
 
         Box::new(light_blocks.into_iter())
     }
+
+    fn approximate_size(&self) -> usize {
+        self.size
+    }
+
+    fn provenance(&self, height: Height) -> Option<Provenance> {
+        self.provenances.get(&height).copied()
+    }
+
+    fn set_provenance(&mut self, height: Height, provenance: Provenance) {
+        self.provenances.insert(height, provenance);
+    }
 }
@@ -1,43 +1,110 @@
 //! Persistent store backed by an on-disk `sled` database.
+//!
+//! The light blocks are stored in one tree per [`Status`], indexed by height,
+//! and their [`Provenance`] (if any) in another one.
+//! Updating the status of a light block moves it between the trees of the
+//! statuses in a single transaction, and the database is flushed to disk
+//! once a light block is verified or trusted, so that the store never loses
+//! its trusted state nor holds a light block with two statuses after a
+//! crash.
+//!
+//! The layout of the database is versioned: opening a database created by
+//! a previous version of this crate migrates it to the current
+//! [`SCHEMA_VERSION`], while opening a database created by a later version
+//! fails with an [`UnsupportedStoreVersion`](crate::errors::ErrorDetail::UnsupportedStoreVersion)
+//! error rather than corrupting it.
 
 pub mod utils;
-use std::path::Path;
+use std::{convert::Infallible, path::Path};
 
+use sled::{
+    transaction::{TransactionError, TransactionResult},
+    Transactional,
+};
 use utils::HeightIndexedDb;
 
-use super::{LightStore, Status};
-use crate::verifier::types::{Height, LightBlock};
+use super::{LightStore, Provenance, Status};
+use crate::{
+    errors::Error,
+    verifier::types::{Height, LightBlock},
+};
 
 const UNVERIFIED: &str = "unverified";
 const VERIFIED: &str = "verified";
 const TRUSTED: &str = "trusted";
 const FAILED: &str = "failed";
+const PROVENANCE: &str = "provenance";
+
+/// The key of the version of the layout of the database, in its default tree.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// The version of the layout of the databases created by this version of the
+/// crate.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The migrations of the database, where the migration at index `i` moves
+/// the database from version `i` to version `i + 1`.
+const MIGRATIONS: [fn(&sled::Db) -> Result<(), Error>; SCHEMA_VERSION as usize] = [
+    // The databases created before the layout was versioned have the layout
+    // of the first version, which only adds the version itself.
+    |_| Ok(()),
+];
 
 /// Persistent store backed by an on-disk `sled` database.
 #[derive(Debug, Clone)]
 pub struct SledStore {
+    db: sled::Db,
     unverified_db: HeightIndexedDb<LightBlock>,
     verified_db: HeightIndexedDb<LightBlock>,
     trusted_db: HeightIndexedDb<LightBlock>,
     failed_db: HeightIndexedDb<LightBlock>,
+    provenance_db: HeightIndexedDb<Provenance>,
 }
 
 impl SledStore {
     /// Open a sled database and create a new persistent store from it.
-    pub fn open(db: impl AsRef<Path>) -> Result<Self, sled::Error> {
-        Ok(Self::new(sled::open(db)?))
+    pub fn open(db: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::new(sled::open(db).map_err(Error::sled)?)
+    }
+
+    /// Create a new persistent store from a sled database that is already
+    /// open, migrating it to the current [`SCHEMA_VERSION`] if needed.
+    pub fn new(db: sled::Db) -> Result<Self, Error> {
+        migrate(&db)?;
+
+        let open_tree = |name| db.open_tree(name).map_err(Error::sled);
+
+        Ok(Self {
+            unverified_db: HeightIndexedDb::new(open_tree(UNVERIFIED)?),
+            verified_db: HeightIndexedDb::new(open_tree(VERIFIED)?),
+            trusted_db: HeightIndexedDb::new(open_tree(TRUSTED)?),
+            failed_db: HeightIndexedDb::new(open_tree(FAILED)?),
+            provenance_db: HeightIndexedDb::new(open_tree(PROVENANCE)?),
+            db,
+        })
     }
 
-    /// Create a new persistent store from a sled database that is already open.
-    pub fn new(db: sled::Db) -> Self {
-        Self {
-            unverified_db: HeightIndexedDb::new(db.open_tree(UNVERIFIED).unwrap()),
-            verified_db: HeightIndexedDb::new(db.open_tree(VERIFIED).unwrap()),
-            trusted_db: HeightIndexedDb::new(db.open_tree(TRUSTED).unwrap()),
-            failed_db: HeightIndexedDb::new(db.open_tree(FAILED).unwrap()),
+    /// The version of the layout of the given database, `0` standing for the
+    /// databases created before the layout was versioned.
+    pub fn schema_version(db: &sled::Db) -> Result<u32, Error> {
+        let version = db.get(SCHEMA_VERSION_KEY).map_err(Error::sled)?;
+
+        match version {
+            Some(bytes) => {
+                let bytes = <[u8; 4]>::try_from(&bytes[..])
+                    .map_err(|_| Error::invalid_store_version(bytes.to_vec()))?;
+                Ok(u32::from_be_bytes(bytes))
+            },
+            None => Ok(0),
         }
     }
 
+    /// Flush the pending writes to disk, returning once they are durable.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.db.flush().map_err(Error::sled)?;
+        Ok(())
+    }
+
     fn db(&self, status: Status) -> &HeightIndexedDb<LightBlock> {
         match status {
             Status::Unverified => &self.unverified_db,
@@ -46,6 +113,76 @@ impl SledStore {
             Status::Failed => &self.failed_db,
         }
     }
+
+    /// Store the light block with the given status, after removing it from
+    /// the other statuses if `exclusive`, in a single transaction.
+    fn write(
+        &self,
+        light_block: &LightBlock,
+        status: Status,
+        exclusive: bool,
+    ) -> Result<(), Error> {
+        let key = utils::key_bytes(light_block.height());
+        let value = utils::encode(light_block)?;
+
+        let trees = (
+            self.unverified_db.tree(),
+            self.verified_db.tree(),
+            self.trusted_db.tree(),
+            self.failed_db.tree(),
+        );
+
+        let result: TransactionResult<(), Infallible> =
+            trees.transaction(|(unverified, verified, trusted, failed)| {
+                for (tree_status, tree) in [
+                    (Status::Unverified, unverified),
+                    (Status::Verified, verified),
+                    (Status::Trusted, trusted),
+                    (Status::Failed, failed),
+                ] {
+                    if tree_status == status {
+                        tree.insert(&key[..], value.as_slice())?;
+                    } else if exclusive {
+                        tree.remove(&key[..])?;
+                    }
+                }
+                Ok(())
+            });
+
+        result.map_err(|e| match e {
+            TransactionError::Abort(never) => match never {},
+            TransactionError::Storage(e) => Error::sled(e),
+        })?;
+
+        // Make sure that the trusted state survives a crash, as the light
+        // client would otherwise have to verify it again.
+        if matches!(status, Status::Verified | Status::Trusted) {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Migrate the given database to the current [`SCHEMA_VERSION`].
+fn migrate(db: &sled::Db) -> Result<(), Error> {
+    let version = SledStore::schema_version(db)?;
+    if version > SCHEMA_VERSION {
+        return Err(Error::unsupported_store_version(version, SCHEMA_VERSION));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(db)?;
+
+        // Record each step, so that an interrupted migration resumes where
+        // it stopped.
+        let to = from as u32 + 1;
+        db.insert(SCHEMA_VERSION_KEY, &to.to_be_bytes()[..])
+            .map_err(Error::sled)?;
+        db.flush().map_err(Error::sled)?;
+    }
+
+    Ok(())
 }
 
 impl LightStore for SledStore {
@@ -54,25 +191,19 @@ impl LightStore for SledStore {
     }
 
     fn update(&mut self, light_block: &LightBlock, status: Status) {
-        let height = light_block.height();
-
-        for other in Status::iter() {
-            if status != *other {
-                self.db(*other).remove(height).ok();
-            }
-        }
-
-        self.db(status).insert(height, light_block).ok();
+        self.write(light_block, status, true).ok();
     }
 
     fn insert(&mut self, light_block: LightBlock, status: Status) {
-        self.db(status)
-            .insert(light_block.height(), &light_block)
-            .ok();
+        self.write(&light_block, status, false).ok();
     }
 
     fn remove(&mut self, height: Height, status: Status) {
-        self.db(status).remove(height).ok();
+        let db = self.db(status);
+        if let Ok(true) = db.tree().contains_key(utils::key_bytes(height)) {
+            db.remove(height).ok();
+            self.provenance_db.remove(height).ok();
+        }
     }
 
     fn highest(&self, status: Status) -> Option<LightBlock> {
@@ -90,6 +221,16 @@ impl LightStore for SledStore {
     fn all(&self, status: Status) -> Box<dyn Iterator<Item = LightBlock>> {
         Box::new(self.db(status).iter())
     }
+
+    fn provenance(&self, height: Height) -> Option<Provenance> {
+        self.provenance_db.get(height).ok().flatten()
+    }
+
+    fn set_provenance(&mut self, height: Height, provenance: Provenance) {
+        if self.provenance_db.insert(height, &provenance).is_ok() {
+            self.flush().ok();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -141,19 +282,80 @@ mod tests {
         })
     }
 
+    #[test]
+    fn update_moves_block_between_statuses() {
+        with_blocks(1, |mut db, blocks| {
+            let block = blocks[0].clone();
+            db.insert(block.clone(), Status::Unverified);
+            db.update(&block, Status::Trusted);
+
+            assert_eq!(db.get(block.height(), Status::Unverified), None);
+            assert_eq!(db.get(block.height(), Status::Trusted), Some(block));
+        })
+    }
+
+    #[test]
+    fn trusted_state_survives_reopening() {
+        let tmp_dir = tempdir().unwrap();
+        let block = blocks(1).remove(0);
+
+        let mut db = SledStore::open(tmp_dir.path()).unwrap();
+        db.update(&block, Status::Trusted);
+        drop(db);
+
+        let db = SledStore::open(tmp_dir.path()).unwrap();
+        assert_eq!(db.highest(Status::Trusted), Some(block));
+    }
+
+    #[test]
+    fn schema_version_is_recorded_and_checked() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        assert_eq!(SledStore::schema_version(&db).unwrap(), 0);
+
+        SledStore::new(db.clone()).unwrap();
+        assert_eq!(SledStore::schema_version(&db).unwrap(), SCHEMA_VERSION);
+
+        let newer = SCHEMA_VERSION + 1;
+        db.insert(SCHEMA_VERSION_KEY, &newer.to_be_bytes()[..])
+            .unwrap();
+        let err = SledStore::new(db).unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            crate::errors::ErrorDetail::UnsupportedStoreVersion(e)
+                if e.version == newer && e.supported == SCHEMA_VERSION
+        ));
+    }
+
+    #[test]
+    fn unversioned_store_is_migrated() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let block = blocks(1).remove(0);
+
+        // The layout of the stores created before the schema was versioned.
+        HeightIndexedDb::new(db.open_tree(TRUSTED).unwrap())
+            .insert(block.height(), &block)
+            .unwrap();
+
+        let store = SledStore::new(db.clone()).unwrap();
+        assert_eq!(SledStore::schema_version(&db).unwrap(), SCHEMA_VERSION);
+        assert_eq!(store.highest(Status::Trusted), Some(block));
+    }
+
     fn with_blocks(height: u64, f: impl FnOnce(SledStore, Vec<LightBlock>)) {
         let tmp_dir = tempdir().unwrap();
         let db = SledStore::open(tmp_dir).unwrap();
 
+        f(db, blocks(height))
+    }
+
+    fn blocks(height: u64) -> Vec<LightBlock> {
         let chain = LightChain::default_with_length(height);
-        let blocks = chain
+        chain
             .light_blocks
             .into_iter()
             .map(|lb| lb.generate().unwrap())
             .map(testgen_to_lb)
-            .collect::<Vec<_>>();
-
-        f(db, blocks)
+            .collect()
     }
 
     fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
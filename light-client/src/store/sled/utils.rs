@@ -24,15 +24,25 @@ impl<V> HeightIndexedDb<V> {
             marker: PhantomData,
         }
     }
+
+    /// The underlying tree, e.g. to update it in a transaction.
+    pub(super) fn tree(&self) -> &sled::Tree {
+        &self.tree
+    }
 }
 
-fn key_bytes(height: Height) -> [u8; 8] {
+pub(super) fn key_bytes(height: Height) -> [u8; 8] {
     // we need to store the height in big-endian form for
     // sled's iterators and ordered operations to work properly.
     // See https://github.com/spacejam/sled#a-note-on-lexicographic-ordering-and-endianness
     height.value().to_be_bytes()
 }
 
+/// Encode the given value as stored in the database.
+pub(super) fn encode<V: Serialize>(value: &V) -> Result<Vec<u8>, Error> {
+    serde_cbor::to_vec(value).map_err(Error::serde_cbor)
+}
+
 // Can be removed once bound_map is stabilized. See https://github.com/rust-lang/rust/issues/86026
 fn map_bound(bound: Bound<&Height>) -> Bound<[u8; 8]> {
     match bound {
@@ -72,7 +82,7 @@ where
     /// Insert a value associated with a height within this tree
     pub fn insert(&self, height: Height, value: &V) -> Result<(), Error> {
         let key = key_bytes(height);
-        let bytes = serde_cbor::to_vec(&value).map_err(Error::serde_cbor)?;
+        let bytes = encode(value)?;
 
         self.tree.insert(key, bytes).map_err(Error::sled)?;
 
@@ -1,19 +1,50 @@
 //! Supervisor and Handle implementation.
 
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
 use crossbeam_channel as channel;
-use tendermint::evidence::Evidence;
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, Either},
+    StreamExt,
+};
+use tendermint::{
+    evidence::{Evidence, LightClientAttackEvidence},
+    Hash,
+};
+#[cfg(feature = "rpc-client")]
+use tendermint_rpc::Subscription;
 
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
 use crate::{
+    components::io::IoError,
     errors::Error,
-    evidence::EvidenceReporter,
+    evidence::{
+        queue::{EvidenceId, EvidenceQueue, MemoryEvidenceQueue, QueuedEvidence, SubmissionStatus},
+        EvidencePolicy, EvidenceReporter, SubmitAll,
+    },
     fork_detector::{Fork, ForkDetection, ForkDetector},
-    light_client::LightClient,
+    light_client::{LightClient, VerificationEvent},
     peer_list::PeerList,
+    pruning::PruningPolicy,
+    snapshot::TrustedStateSnapshot,
     state::State,
-    verifier::types::{Height, LatestStatus, LightBlock, PeerId, Status},
+    utils::{run_blocking, sleep},
+    verifier::types::{Height, LatestStatus, LightBlock, PeerId, Status, Time},
 };
 
 /// Provides an interface to the supervisor for use in downstream code.
+///
+/// The methods of this interface block the calling thread until the
+/// supervisor replies: see [`AsyncHandle`] for the one which can be used from
+/// within an async runtime.
 pub trait Handle: Send + Sync {
     /// Get latest trusted block.
     fn latest_trusted(&self) -> Result<Option<LightBlock>, Error>;
@@ -27,28 +58,310 @@ pub trait Handle: Send + Sync {
     /// Verify to the block at the given height.
     fn verify_to_target(&self, _height: Height) -> Result<LightBlock, Error>;
 
+    /// Get the queued evidence of the detected forks, along with the status
+    /// of its submission.
+    fn evidence(&self) -> Result<Vec<QueuedEvidence>, Error>;
+
     /// Terminate the underlying [`Supervisor`].
     fn terminate(&self) -> Result<(), Error>;
 }
 
-/// Input events sent by the [`Handle`]s to the [`Supervisor`]. They carry a [`Callback`] which is
-/// used to communicate back the responses of the requests.
+/// Provides an interface to the supervisor for use in downstream code
+/// running within an async runtime.
+///
+/// The futures of its methods wait for the supervisor to reply without
+/// blocking the thread polling them, and are `Send`. Dropping one of these
+/// futures cancels the wait, but not the request, which the supervisor still
+/// carries out.
+#[async_trait]
+pub trait AsyncHandle: Send + Sync {
+    /// Get latest trusted block.
+    async fn latest_trusted(&self) -> Result<Option<LightBlock>, Error>;
+
+    /// Get the latest status.
+    async fn latest_status(&self) -> Result<LatestStatus, Error>;
+
+    /// Verify to the highest block.
+    async fn verify_to_highest(&self) -> Result<LightBlock, Error>;
+
+    /// Verify to the block at the given height.
+    async fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error>;
+
+    /// Get the queued evidence of the detected forks, along with the status
+    /// of its submission.
+    async fn evidence(&self) -> Result<Vec<QueuedEvidence>, Error>;
+
+    /// Terminate the underlying [`Supervisor`].
+    async fn terminate(&self) -> Result<(), Error>;
+}
+
+/// Input events sent by the [`Handle`]s to the [`Supervisor`]. They carry a
+/// channel which is used to communicate back the responses of the requests.
 #[derive(Debug)]
 enum HandleInput {
     /// Terminate the supervisor process
-    Terminate(channel::Sender<()>),
+    Terminate(oneshot::Sender<()>),
 
-    /// Verify to the highest height, call the provided callback with result
-    VerifyToHighest(channel::Sender<Result<LightBlock, Error>>),
+    /// Verify to the highest height, reply with the result
+    VerifyToHighest(oneshot::Sender<Result<LightBlock, Error>>),
 
-    /// Verify to the given height, call the provided callback with result
-    VerifyToTarget(Height, channel::Sender<Result<LightBlock, Error>>),
+    /// Verify to the given height, reply with the result
+    VerifyToTarget(Height, oneshot::Sender<Result<LightBlock, Error>>),
 
     /// Get the latest trusted block.
-    LatestTrusted(channel::Sender<Option<LightBlock>>),
+    LatestTrusted(oneshot::Sender<Option<LightBlock>>),
 
     /// Get the current status of the LightClient
-    GetStatus(channel::Sender<LatestStatus>),
+    GetStatus(oneshot::Sender<LatestStatus>),
+
+    /// Get the queued evidence.
+    Evidence(oneshot::Sender<Vec<QueuedEvidence>>),
+}
+
+/// Events emitted by the [`Supervisor`] about the progress of the
+/// verification, its trusted state and its peers, see [`Supervisor::events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SupervisorEvent {
+    /// The light client of a peer made progress verifying a block, be it
+    /// the primary verifying the target or a witness cross-checking it.
+    Verification {
+        /// The peer of the light client.
+        peer: PeerId,
+        /// The progress of the light client.
+        event: VerificationEvent,
+    },
+    /// The block verified by the primary was cross-checked against the
+    /// witnesses, and is now trusted.
+    BlockTrusted {
+        /// The height of the trusted block.
+        height: Height,
+        /// The time at which the trusted block leaves the trusting period.
+        expires_at: Time,
+    },
+    /// Some witnesses forked from the primary, which fails the verification.
+    ForkDetected {
+        /// The witnesses which forked.
+        peers: Vec<PeerId>,
+    },
+    /// The latest trusted state is older than the refresh threshold of the
+    /// [`RefreshPolicy`], and is about to be refreshed.
+    TrustedStateAging {
+        /// The height of the latest trusted state.
+        height: Height,
+        /// The time elapsed since the latest trusted state.
+        age: Duration,
+        /// The time at which the latest trusted state leaves the trusting
+        /// period.
+        expires_at: Time,
+    },
+    /// The trusted state was refreshed by verifying the highest block.
+    TrustedStateRefreshed {
+        /// The height of the previous trusted state.
+        from: Height,
+        /// The height of the new trusted state.
+        to: Height,
+    },
+    /// The trusted state could not be refreshed. It is retried at the next
+    /// check, until the trusted state expires.
+    RefreshFailed {
+        /// The height of the latest trusted state.
+        height: Height,
+        /// The time at which the latest trusted state leaves the trusting
+        /// period.
+        expires_at: Time,
+        /// The error of the verification.
+        reason: String,
+    },
+    /// The latest trusted state left the trusting period: the light client
+    /// cannot verify any block from it anymore, and must be initialized again
+    /// from a trusted block obtained out of band.
+    TrustedStateExpired {
+        /// The height of the latest trusted state.
+        height: Height,
+        /// The time at which the latest trusted state left the trusting
+        /// period.
+        expires_at: Time,
+    },
+    /// The evidence of a fork was submitted to a peer.
+    EvidenceSubmitted {
+        /// The peer the evidence was submitted to.
+        peer: PeerId,
+        /// The evidence of the attack.
+        evidence: Box<LightClientAttackEvidence>,
+        /// The hash of the evidence, as returned by the peer.
+        hash: Hash,
+    },
+    /// The evidence of a fork was not approved by the [`EvidencePolicy`],
+    /// and was not submitted to the peer.
+    EvidenceHeld {
+        /// The peer the evidence was meant for.
+        peer: PeerId,
+        /// The evidence of the attack.
+        evidence: Box<LightClientAttackEvidence>,
+    },
+    /// The evidence of a fork could not be submitted to a peer.
+    EvidenceSubmissionFailed {
+        /// The peer the evidence was meant for.
+        peer: PeerId,
+        /// The evidence of the attack.
+        evidence: Box<LightClientAttackEvidence>,
+        /// The error of the submission.
+        reason: String,
+    },
+    /// The [`EvidenceQueue`] failed to record the evidence of a fork, or the
+    /// outcome of its submission. The evidence is submitted nonetheless.
+    EvidenceQueueFailed {
+        /// The error of the queue.
+        reason: String,
+    },
+    /// The primary failed to serve a valid block, and was replaced by a
+    /// witness.
+    PrimaryReplaced {
+        /// The former primary.
+        faulty: PeerId,
+        /// The witness which became the primary.
+        primary: PeerId,
+        /// The error of the former primary.
+        reason: String,
+    },
+    /// A witness was replaced by a full node (if any), and is never used
+    /// again, according to the [`WitnessPolicy`].
+    WitnessReplaced {
+        /// The faulty witness.
+        faulty: PeerId,
+        /// The full node which became a witness, if any.
+        witness: Option<PeerId>,
+        /// The error of the faulty witness.
+        reason: String,
+    },
+    /// The light store of the primary was pruned according to the
+    /// [`PruningPolicy`].
+    LightStorePruned {
+        /// The heights of the light blocks which were removed, if any.
+        pruned: Vec<Height>,
+        /// The approximate size of the light store after pruning, in bytes.
+        approximate_size: usize,
+    },
+}
+
+/// When the [`Supervisor`] refreshes its trusted state, so that it does not
+/// fall out of the trusting period while the light client is idle, e.g. over
+/// a weekend.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RefreshPolicy {
+    /// The fraction of the trusting period after which the latest trusted
+    /// state is refreshed, between 0 and 1.
+    pub threshold: f64,
+    /// How often the age of the latest trusted state is checked by
+    /// [`Supervisor::run`].
+    pub check_interval: Duration,
+}
+
+impl Default for RefreshPolicy {
+    /// Refresh the trusted state after two thirds of the trusting period,
+    /// checking every minute.
+    fn default() -> Self {
+        Self {
+            threshold: 2.0 / 3.0,
+            check_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// How the [`Supervisor`] submits again the evidence of forks which was not
+/// acknowledged by its peer, e.g. because the peer was unreachable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EvidenceRetryPolicy {
+    /// The number of submissions after which the evidence is given up on,
+    /// and marked as [`SubmissionStatus::Failed`].
+    pub max_attempts: u32,
+    /// How long to wait after the first failed submission before submitting
+    /// the evidence again, doubled after every failed one.
+    pub initial_backoff: Duration,
+    /// The maximum time to wait between two submissions.
+    pub max_backoff: Duration,
+}
+
+impl Default for EvidenceRetryPolicy {
+    /// Give up after 10 submissions, waiting from 5 seconds up to 10 minutes
+    /// in between.
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(600),
+        }
+    }
+}
+
+impl EvidenceRetryPolicy {
+    /// How long to wait after the given number of failed submissions.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(31);
+        self.initial_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_backoff)
+    }
+}
+
+/// How the [`Supervisor`] manages its witnesses.
+///
+/// The primary is always replaced by a witness when it fails to serve a
+/// valid block, and the witnesses which serve an invalid block are always
+/// replaced by a full node, if any, and never used again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WitnessPolicy {
+    /// The minimum number of witnesses to cross-check a block against:
+    /// below it, the verification fails without cross-checking.
+    pub min_witnesses: usize,
+    /// The number of witnesses which must serve the very same block as the
+    /// primary for the block to be trusted.
+    pub quorum: usize,
+    /// Whether the witnesses which time out are replaced and never used
+    /// again, as the faulty ones are. Otherwise, they are kept but do not
+    /// count towards the quorum.
+    pub replace_timed_out: bool,
+}
+
+impl Default for WitnessPolicy {
+    /// Require a single witness, replacing the ones which time out.
+    fn default() -> Self {
+        Self {
+            min_witnesses: 1,
+            quorum: 1,
+            replace_timed_out: true,
+        }
+    }
+}
+
+impl WitnessPolicy {
+    /// Check that the quorum is at least one witness, and at most the
+    /// minimum number of witnesses, so that it can be reached.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.quorum == 0 {
+            return Err(Error::invalid_witness_policy(
+                "the quorum must be at least one witness".to_string(),
+            ));
+        }
+        if self.quorum > self.min_witnesses {
+            return Err(Error::invalid_witness_policy(format!(
+                "the quorum ({}) exceeds the minimum number of witnesses ({})",
+                self.quorum, self.min_witnesses
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What came of the forks detected against the witnesses.
+#[derive(Debug, Default)]
+struct ProcessedForks {
+    /// The witnesses which forked from the primary.
+    forked: Vec<PeerId>,
+    /// The witnesses which were replaced.
+    replaced: Vec<PeerId>,
+    /// The witnesses which timed out, but were kept.
+    timed_out: Vec<PeerId>,
 }
 
 /// A light client `Instance` packages a `LightClient` together with its `State`.
@@ -75,6 +388,13 @@ impl Instance {
         self.state.light_store.highest(Status::Trusted)
     }
 
+    /// Take a snapshot of the trusted state of this instance, along with at
+    /// most `ancestry` ancestors of its latest trusted block, see
+    /// [`TrustedStateSnapshot::from_store`].
+    pub fn snapshot(&self, ancestry: usize) -> Option<TrustedStateSnapshot> {
+        TrustedStateSnapshot::from_store(self.state.light_store.as_ref(), ancestry)
+    }
+
     /// Trust the given block.
     pub fn trust_block(&mut self, lb: &LightBlock) {
         self.state.light_store.update(lb, Status::Trusted);
@@ -90,32 +410,145 @@ impl Instance {
 /// is promoted to primary. If a witness is deemed faulty, then the witness is
 /// removed.
 ///
-/// The supervisor is intended to be ran in its own thread, and queried
-/// via a `Handle`.
+/// The supervisor is intended to be ran in its own task, with
+/// [`Supervisor::run_async`], or in its own thread, with [`Supervisor::run`],
+/// and queried via an [`AsyncHandle`] or a [`Handle`].
+///
+/// The verification methods of the supervisor are `async`, as are those of
+/// its light clients, which fetch the light blocks through their
+/// [`AsyncIo`](crate::components::io::AsyncIo), and of the fork detector and
+/// the evidence reporter: only the [`Scheduler`](crate::components::scheduler::Scheduler)
+/// and the verifier, which do not wait on I/O, are synchronous. The
+/// supervisor thus runs on the runtime of the application, which must be a
+/// Tokio runtime with [`ProdIo`](crate::components::io::ProdIo). Each of these
+/// methods has a blocking variant, which runs it to completion on a thread
+/// and runtime of its own, for use outside of an async runtime.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "rpc-client")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
 ///
-/// ## Example
+/// use tendermint_light_client::{
+///     builder::{LightClientBuilder, SupervisorBuilder},
+///     store::memory::MemoryStore,
+///     supervisor::{Handle, Instance},
+///     verifier::{
+///         options::Options,
+///         types::{Height, PeerId},
+///     },
+/// };
+/// use tendermint_rpc::{HttpClient, Url};
 ///
-/// ```rust,ignore
-/// let mut supervisor: Supervisor = todo!();
-/// let mut handle = supervisor.handle();
+/// let options = Options {
+///     trust_threshold: Default::default(),
+///     trusting_period: Duration::from_secs(36000),
+///     clock_drift: Duration::from_secs(1),
+///     time_monotonicity: Default::default(),
+/// };
+/// let trusted_height = Height::from(1_000_u32);
+/// let trusted_hash: tendermint::Hash =
+///     "0E4C18B4A4B2EBFCDFB6B1A0AE3E5F0B3B2C4A34BDBEB2A6E4D4C3D4E5F60718".parse()?;
+/// let instance = |peer_id: PeerId, address: &Url| -> Result<Instance, Box<dyn std::error::Error>> {
+///     let client = HttpClient::new(address.clone())?;
+///     let store = Box::new(MemoryStore::new());
+///     let builder = LightClientBuilder::prod(peer_id, client, store, options, None);
+///     Ok(builder.trust_primary_at(trusted_height, trusted_hash)?.build())
+/// };
+///
+/// let (primary, witness): (PeerId, PeerId) = (
+///     "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse()?,
+///     "CEFEEDBADFADAD0C0CEEFACADE0ADEADBEEFC0FF".parse()?,
+/// );
+/// let (primary_address, witness_address): (Url, Url) = (
+///     "http://127.0.0.1:26657".parse()?,
+///     "http://127.0.0.1:36657".parse()?,
+/// );
+/// let supervisor = SupervisorBuilder::new()
+///     .primary(primary, primary_address.clone(), instance(primary, &primary_address)?)
+///     .witness(witness, witness_address.clone(), instance(witness, &witness_address)?)
+///     .build_prod();
+/// let handle = supervisor.handle();
 ///
 /// // Spawn the supervisor in its own thread.
 /// std::thread::spawn(|| supervisor.run());
 ///
 /// loop {
-///     // Asynchronously query the supervisor via a handle
-///     let maybe_block = handle.verify_to_highest();
-///     match maybe_block {
-///         Ok(light_block) => {
-///             println!("[info] synced to block {}", light_block.height());
-///         }
-///         Err(e) => {
-///             println!("[error] sync failed: {}", e);
-///         }
-///     };
+///     // Block until the supervisor replies.
+///     match handle.verify_to_highest() {
+///         Ok(light_block) => println!("[info] synced to block {}", light_block.height()),
+///         Err(e) => println!("[error] sync failed: {e}"),
+///     }
 ///
 ///     std::thread::sleep(Duration::from_millis(800));
 /// }
+/// # }
+/// # #[cfg(not(feature = "rpc-client"))]
+/// # fn main() {}
+/// ```
+///
+/// Or, from within a Tokio runtime, with the same supervisor:
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "rpc-client")]
+/// # async fn follow() -> Result<(), Box<dyn std::error::Error>> {
+/// # use std::time::Duration;
+/// #
+/// # use tendermint_light_client::{
+/// #     builder::{LightClientBuilder, SupervisorBuilder},
+/// #     store::memory::MemoryStore,
+/// #     supervisor::{AsyncHandle, Instance},
+/// #     verifier::{
+/// #         options::Options,
+/// #         types::{Height, PeerId},
+/// #     },
+/// # };
+/// # use tendermint_rpc::{HttpClient, Url};
+/// #
+/// # let options = Options {
+/// #     trust_threshold: Default::default(),
+/// #     trusting_period: Duration::from_secs(36000),
+/// #     clock_drift: Duration::from_secs(1),
+/// #     time_monotonicity: Default::default(),
+/// # };
+/// # let trusted_height = Height::from(1_000_u32);
+/// # let trusted_hash: tendermint::Hash =
+/// #     "0E4C18B4A4B2EBFCDFB6B1A0AE3E5F0B3B2C4A34BDBEB2A6E4D4C3D4E5F60718".parse()?;
+/// # let instance = |peer_id: PeerId, address: &Url| -> Result<Instance, Box<dyn std::error::Error>> {
+/// #     let client = HttpClient::new(address.clone())?;
+/// #     let store = Box::new(MemoryStore::new());
+/// #     let builder = LightClientBuilder::prod(peer_id, client, store, options, None);
+/// #     Ok(builder.trust_primary_at(trusted_height, trusted_hash)?.build())
+/// # };
+/// #
+/// # let (primary, witness): (PeerId, PeerId) = (
+/// #     "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse()?,
+/// #     "CEFEEDBADFADAD0C0CEEFACADE0ADEADBEEFC0FF".parse()?,
+/// # );
+/// # let (primary_address, witness_address): (Url, Url) = (
+/// #     "http://127.0.0.1:26657".parse()?,
+/// #     "http://127.0.0.1:36657".parse()?,
+/// # );
+/// # let supervisor = SupervisorBuilder::new()
+/// #     .primary(primary, primary_address.clone(), instance(primary, &primary_address)?)
+/// #     .witness(witness, witness_address.clone(), instance(witness, &witness_address)?)
+/// #     .build_prod();
+/// let handle = supervisor.handle();
+///
+/// // Spawn the supervisor in its own task.
+/// tokio::spawn(supervisor.run_async());
+///
+/// loop {
+///     // Wait for the reply without blocking the runtime.
+///     match AsyncHandle::verify_to_highest(&handle).await {
+///         Ok(light_block) => println!("[info] synced to block {}", light_block.height()),
+///         Err(e) => println!("[error] sync failed: {e}"),
+///     }
+/// }
+/// # }
+/// # fn main() {}
 /// ```
 pub struct Supervisor {
     /// List of peers and their instances (primary, witnesses, full and faulty nodes)
@@ -124,10 +557,27 @@ pub struct Supervisor {
     fork_detector: Box<dyn ForkDetector>,
     /// Reporter of fork evidence
     evidence_reporter: Box<dyn EvidenceReporter>,
+    /// Which fork evidence to report
+    evidence_policy: Box<dyn EvidencePolicy>,
+    /// The evidence to report, until acknowledged
+    evidence_queue: Box<dyn EvidenceQueue>,
+    /// When to submit the evidence again, if not acknowledged
+    evidence_retry_policy: EvidenceRetryPolicy,
+    /// When the evidence which failed to be submitted is due again, by
+    /// identifier
+    evidence_retry_at: HashMap<EvidenceId, Instant>,
+    /// How to manage the witnesses
+    witness_policy: WitnessPolicy,
     /// Channel through which to reply to `Handle`s
-    sender: channel::Sender<HandleInput>,
+    sender: mpsc::UnboundedSender<HandleInput>,
     /// Channel through which to receive events from the `Handle`s
-    receiver: channel::Receiver<HandleInput>,
+    receiver: mpsc::UnboundedReceiver<HandleInput>,
+    /// When to refresh the trusted state, if at all
+    refresh_policy: Option<RefreshPolicy>,
+    /// Which light blocks to keep in the light store, if not all
+    pruning_policy: Option<PruningPolicy>,
+    /// Channels through which to emit events, shared with the light clients
+    event_senders: EventSenders,
 }
 
 impl std::fmt::Debug for Supervisor {
@@ -138,17 +588,57 @@ impl std::fmt::Debug for Supervisor {
     }
 }
 
-// Ensure the `Supervisor` can be sent across thread boundaries.
-static_assertions::assert_impl_all!(Supervisor: Send);
+/// Where the events of a supervisor go.
+#[derive(Default)]
+struct EventSinks {
+    senders: Vec<channel::Sender<SupervisorEvent>>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Metrics>,
+}
+
+type EventSenders = Arc<Mutex<EventSinks>>;
+
+/// Send the event through the given channels, discarding the ones whose
+/// receiver was dropped, and record it in the metrics, if any.
+fn emit_to(senders: &EventSenders, event: SupervisorEvent) {
+    // The senders are always left consistent, even by a panic.
+    let mut sinks = senders.lock().unwrap_or_else(|e| e.into_inner());
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = &sinks.metrics {
+        metrics.observe(&event);
+    }
+    sinks
+        .senders
+        .retain(|sender| sender.send(event.clone()).is_ok());
+}
+
+// Ensure the `Supervisor` can be sent across thread boundaries, and its
+// futures spawned onto multi-threaded runtimes.
+static_assertions::assert_impl_all!(Supervisor: Send, Sync);
 
 impl Supervisor {
     /// Constructs a new supervisor from the given list of peers and fork detector instance.
     pub fn new(
-        peers: PeerList<Instance>,
+        mut peers: PeerList<Instance>,
         fork_detector: impl ForkDetector + 'static,
         evidence_reporter: impl EvidenceReporter + 'static,
     ) -> Self {
-        let (sender, receiver) = channel::unbounded::<HandleInput>();
+        let (sender, receiver) = mpsc::unbounded::<HandleInput>();
+
+        let event_senders = EventSenders::default();
+        let ids: Vec<PeerId> = peers.values().keys().copied().collect();
+        for peer in ids {
+            if let Some(instance) = peers.get_mut(&peer) {
+                let senders = Arc::clone(&event_senders);
+                instance.light_client.on_event(move |event| {
+                    let event = SupervisorEvent::Verification {
+                        peer,
+                        event: event.clone(),
+                    };
+                    emit_to(&senders, event);
+                });
+            }
+        }
 
         Self {
             peers,
@@ -156,7 +646,193 @@ impl Supervisor {
             receiver,
             fork_detector: Box::new(fork_detector),
             evidence_reporter: Box::new(evidence_reporter),
+            evidence_policy: Box::new(SubmitAll),
+            evidence_queue: Box::new(MemoryEvidenceQueue::new()),
+            evidence_retry_policy: EvidenceRetryPolicy::default(),
+            evidence_retry_at: HashMap::new(),
+            witness_policy: WitnessPolicy::default(),
+            refresh_policy: None,
+            pruning_policy: None,
+            event_senders,
+        }
+    }
+
+    /// Proactively refresh the trusted state according to the given policy.
+    #[must_use]
+    pub fn refresh_policy(mut self, policy: RefreshPolicy) -> Self {
+        self.refresh_policy = Some(policy);
+        self
+    }
+
+    /// Only submit the evidence of forks approved by the given policy, the
+    /// other being emitted as [`SupervisorEvent::EvidenceHeld`] events.
+    ///
+    /// All the evidence is submitted by default.
+    #[must_use]
+    pub fn evidence_policy(mut self, policy: impl EvidencePolicy + 'static) -> Self {
+        self.evidence_policy = Box::new(policy);
+        self
+    }
+
+    /// Record the evidence of forks in the given queue until it is
+    /// acknowledged by the peers it is submitted to.
+    ///
+    /// The evidence is kept in memory by default: with a persistent queue,
+    /// e.g. a [`SledEvidenceQueue`], the evidence which was not acknowledged
+    /// before the process exited is submitted again by
+    /// [`Supervisor::resubmit_evidence`].
+    ///
+    /// [`SledEvidenceQueue`]: crate::evidence::queue::SledEvidenceQueue
+    #[must_use]
+    pub fn evidence_queue(mut self, queue: impl EvidenceQueue + 'static) -> Self {
+        self.evidence_queue = Box::new(queue);
+        self
+    }
+
+    /// Submit again the evidence which was not acknowledged according to the
+    /// given policy.
+    #[must_use]
+    pub fn evidence_retry_policy(mut self, policy: EvidenceRetryPolicy) -> Self {
+        self.evidence_retry_policy = policy;
+        self
+    }
+
+    /// Manage the witnesses according to the given policy.
+    ///
+    /// Fails if the policy is invalid (see [`WitnessPolicy::validate`]).
+    pub fn witness_policy(mut self, policy: WitnessPolicy) -> Result<Self, Error> {
+        policy.validate()?;
+        self.witness_policy = policy;
+        Ok(self)
+    }
+
+    /// Prune the light store of the primary according to the given policy
+    /// every time a block is trusted.
+    ///
+    /// All the light blocks are kept by default.
+    #[must_use]
+    pub fn pruning_policy(mut self, policy: PruningPolicy) -> Self {
+        self.pruning_policy = Some(policy);
+        self
+    }
+
+    /// Create a new channel through which the supervisor emits its events.
+    ///
+    /// Channels whose receiver was dropped are discarded.
+    pub fn events(&mut self) -> channel::Receiver<SupervisorEvent> {
+        let (sender, receiver) = channel::unbounded();
+        self.event_senders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .senders
+            .push(sender);
+        receiver
+    }
+
+    /// Record the metrics of the verifications of the light clients, of the
+    /// trusted state and of the peers into the given metrics (see
+    /// [`Metrics`]), replacing the metrics given before, if any.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn metrics(self, metrics: Metrics) -> Self {
+        if let Some(trusted) = self.latest_trusted() {
+            metrics.observe(&SupervisorEvent::BlockTrusted {
+                height: trusted.height(),
+                expires_at: self.expires_at(&trusted),
+            });
+        }
+        self.event_senders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .metrics = Some(metrics);
+        self
+    }
+
+    fn emit(&mut self, event: SupervisorEvent) {
+        emit_to(&self.event_senders, event);
+    }
+
+    /// Refresh the latest trusted state of the primary by verifying the
+    /// highest block, if it is older than the threshold of the refresh
+    /// policy, emitting the corresponding events.
+    ///
+    /// Blocking variant of [`Supervisor::check_trusted_state_async`].
+    pub fn check_trusted_state(&mut self) {
+        if let Some((height, expires_at)) = self.aging_trusted_state() {
+            let result = self.verify_to_highest();
+            self.refreshed(height, expires_at, result);
+        }
+    }
+
+    /// Refresh the latest trusted state of the primary by verifying the
+    /// highest block, if it is older than the threshold of the refresh
+    /// policy, emitting the corresponding events.
+    ///
+    /// This is called periodically by [`Supervisor::run_async`], and does
+    /// nothing without a refresh policy.
+    pub async fn check_trusted_state_async(&mut self) {
+        if let Some((height, expires_at)) = self.aging_trusted_state() {
+            let result = self.verify_to_highest_async().await;
+            self.refreshed(height, expires_at, result);
+        }
+    }
+
+    /// The height of the latest trusted state and the time it expires at, if
+    /// it is to be refreshed, emitting the corresponding events.
+    fn aging_trusted_state(&mut self) -> Option<(Height, Time)> {
+        let policy = self.refresh_policy?;
+        let trusted = self.latest_trusted()?;
+
+        let light_client = &self.peers.primary().light_client;
+        let trusting_period = light_client.options.trusting_period;
+        let now = light_client.now();
+        let height = trusted.height();
+        let age = now.duration_since(trusted.time()).unwrap_or_default();
+        let expires_at = self.expires_at(&trusted);
+
+        if age >= trusting_period {
+            self.emit(SupervisorEvent::TrustedStateExpired { height, expires_at });
+            return None;
         }
+        if age < trusting_period.mul_f64(policy.threshold) {
+            return None;
+        }
+
+        self.emit(SupervisorEvent::TrustedStateAging {
+            height,
+            age,
+            expires_at,
+        });
+        Some((height, expires_at))
+    }
+
+    /// Emit the outcome of the refresh of the trusted state at the given
+    /// height.
+    fn refreshed(&mut self, height: Height, expires_at: Time, result: Result<LightBlock, Error>) {
+        let event = match result {
+            Ok(verified) if verified.height() > height => SupervisorEvent::TrustedStateRefreshed {
+                from: height,
+                to: verified.height(),
+            },
+            Ok(_) => SupervisorEvent::RefreshFailed {
+                height,
+                expires_at,
+                reason: "no block higher than the trusted state".to_string(),
+            },
+            Err(e) => SupervisorEvent::RefreshFailed {
+                height,
+                expires_at,
+                reason: e.to_string(),
+            },
+        };
+        self.emit(event);
+    }
+
+    /// The time at which the given trusted block leaves the trusting period
+    /// of the primary.
+    fn expires_at(&self, trusted: &LightBlock) -> Time {
+        let light_client = &self.peers.primary().light_client;
+        (trusted.time() + light_client.options.trusting_period).unwrap_or_else(|_| trusted.time())
     }
 
     /// Create a new handle to this supervisor.
@@ -170,8 +846,15 @@ impl Supervisor {
     }
 
     /// Verify to the highest block.
+    ///
+    /// Blocking variant of [`Supervisor::verify_to_highest_async`].
     pub fn verify_to_highest(&mut self) -> Result<LightBlock, Error> {
-        self.verify(None)
+        run_blocking(self.verify(None)).map_err(Error::io)?
+    }
+
+    /// Verify to the highest block.
+    pub async fn verify_to_highest_async(&mut self) -> Result<LightBlock, Error> {
+        self.verify(None).await
     }
 
     /// Return latest trusted status summary.
@@ -183,7 +866,7 @@ impl Supervisor {
         match latest_trusted {
             Some(trusted) => LatestStatus::new(
                 Some(trusted.signed_header.header.height.value()),
-                Some(trusted.signed_header.header.hash()),
+                Some(trusted.signed_header.header_hash()),
                 Some(trusted.next_validators.hash()),
                 connected_nodes,
             ),
@@ -192,22 +875,136 @@ impl Supervisor {
         }
     }
 
+    /// The queued evidence of the detected forks, along with the status of
+    /// its submission, in the order in which it was detected.
+    pub fn evidence(&self) -> Vec<QueuedEvidence> {
+        self.evidence_queue.all()
+    }
+
+    /// The status of the submission of the queued evidence with the given
+    /// identifier, if any.
+    pub fn evidence_status(&self, id: EvidenceId) -> Option<SubmissionStatus> {
+        self.evidence_queue.get(id).map(|entry| entry.status)
+    }
+
+    /// Submit again the queued evidence which was not acknowledged yet,
+    /// returning the number of pieces of evidence acknowledged this time.
+    ///
+    /// Blocking variant of [`Supervisor::resubmit_evidence_async`], which
+    /// only fails if the evidence cannot be submitted at all, e.g. if no
+    /// runtime can be started to submit it.
+    pub fn resubmit_evidence(&mut self) -> Result<usize, Error> {
+        run_blocking(self.resubmit_evidence_async()).map_err(Error::io)
+    }
+
+    /// Submit again the queued evidence which was not acknowledged yet,
+    /// returning the number of pieces of evidence acknowledged this time.
+    ///
+    /// The evidence meant for peers the supervisor does not know about, e.g.
+    /// after a restart with other peers, is left pending. The evidence whose
+    /// last submission failed is only submitted again once its backoff
+    /// elapsed, and is marked as [`SubmissionStatus::Failed`] after the
+    /// maximum number of attempts, as per the [`EvidenceRetryPolicy`].
+    ///
+    /// This is called by [`Supervisor::run_async`] when it starts, and before
+    /// every verification: the errors of the queue are emitted as
+    /// [`SupervisorEvent::EvidenceQueueFailed`] events, so that they do not
+    /// fail the verification.
+    pub async fn resubmit_evidence_async(&mut self) -> usize {
+        let now = Instant::now();
+        let mut acknowledged = 0;
+        for entry in self.evidence_queue.pending() {
+            if self.peers.get(&entry.peer).is_none() {
+                continue;
+            }
+            if matches!(self.evidence_retry_at.get(&entry.id), Some(at) if *at > now) {
+                continue;
+            }
+            if self.deliver_evidence(entry).await {
+                acknowledged += 1;
+            }
+        }
+        acknowledged
+    }
+
     /// Verify to the block at the given height.
+    ///
+    /// Blocking variant of [`Supervisor::verify_to_target_async`].
     pub fn verify_to_target(&mut self, height: Height) -> Result<LightBlock, Error> {
-        self.verify(Some(height))
+        run_blocking(self.verify(Some(height))).map_err(Error::io)?
+    }
+
+    /// Verify to the block at the given height.
+    pub async fn verify_to_target_async(&mut self, height: Height) -> Result<LightBlock, Error> {
+        self.verify(Some(height)).await
+    }
+
+    /// Follow the chain from the given height: the returned iterator yields
+    /// the verified light block at every height, in order, up to the highest
+    /// block of the primary and then past it, as new blocks are committed.
+    ///
+    /// The first block is reached by bisection from the trusted state, and
+    /// each of the next ones is verified sequentially from its predecessor.
+    /// Once the highest block of the primary is reached, the iterator waits
+    /// for the next one, as told by the `NewBlock` subscription given to
+    /// [`Follow::new_blocks`], or else by polling the primary at the interval
+    /// set with [`Follow::poll_interval`]. Every block is cross-checked
+    /// against the witnesses and trusted, as by
+    /// [`Supervisor::verify_to_target`].
+    ///
+    /// The iterator never ends: an error is yielded for a height which could
+    /// not be verified, and the next call retries it.
+    ///
+    /// This is meant for applications which must process every height, in
+    /// place of [`Supervisor::run`]: the supervisor does not serve its
+    /// handles while it is followed. The iterator blocks the calling thread,
+    /// as do the blocking variants of the verification methods.
+    pub fn follow(&mut self, from_height: Height) -> Follow<'_> {
+        Follow {
+            supervisor: self,
+            next_height: from_height,
+            highest: None,
+            poll_interval: DEFAULT_FOLLOW_POLL_INTERVAL,
+            #[cfg(feature = "rpc-client")]
+            new_blocks: None,
+        }
     }
 
     /// Verify either to the latest block (if `height == None`) or to a given block (if `height ==
     /// Some(height)`).
-    fn verify(&mut self, height: Option<Height>) -> Result<LightBlock, Error> {
+    ///
+    /// The verification starts over whenever the primary or some witnesses
+    /// are replaced.
+    async fn verify(&mut self, height: Option<Height>) -> Result<LightBlock, Error> {
+        loop {
+            if let Some(verified) = self.verify_once(height).await? {
+                return Ok(verified);
+            }
+        }
+    }
+
+    /// Verify either to the latest block or to a given block, returning
+    /// `None` if the verification is to start over, the primary or some
+    /// witnesses having been replaced.
+    async fn verify_once(&mut self, height: Option<Height>) -> Result<Option<LightBlock>, Error> {
+        self.resubmit_evidence_async().await;
+
         let primary = self.peers.primary_mut();
 
         // Perform light client core verification for the given height (or highest).
         let verdict = match height {
-            None => primary.light_client.verify_to_highest(&mut primary.state),
-            Some(height) => primary
-                .light_client
-                .verify_to_target(height, &mut primary.state),
+            None => {
+                primary
+                    .light_client
+                    .verify_to_highest_async(&mut primary.state)
+                    .await
+            },
+            Some(height) => {
+                primary
+                    .light_client
+                    .verify_to_target_async(height, &mut primary.state)
+                    .await
+            },
         };
 
         match verdict {
@@ -218,95 +1015,269 @@ impl Supervisor {
                     .ok_or_else(|| Error::no_trusted_state(Status::Trusted))?;
 
                 // Perform fork detection with the highest verified block and the trusted block.
-                let outcome = self.detect_forks(&verified_block, &trusted_block)?;
+                let witnesses = self.peers.witnesses_ids().len();
+                let outcome = self.detect_forks(&verified_block, &trusted_block).await?;
 
-                match outcome {
+                let agreeing = match outcome {
                     // There was a fork or a faulty peer
                     ForkDetection::Detected(forks) => {
-                        let forked = self.process_forks(forks)?;
-                        if !forked.is_empty() {
+                        let processed = self.process_forks(forks, &trusted_block).await?;
+                        if !processed.forked.is_empty() {
                             // Fork detected, exiting
-                            return Err(Error::fork_detected(forked));
+                            self.emit(SupervisorEvent::ForkDetected {
+                                peers: processed.forked.clone(),
+                            });
+                            return Err(Error::fork_detected(processed.forked));
                         }
 
-                        // If there were no hard forks, perform verification again
-                        self.verify(height)
-                    },
-                    ForkDetection::NotDetected => {
-                        // We need to re-ask for the primary here as the compiler
-                        // is not smart enough to realize that we do not mutate
-                        // the `primary` field of `PeerList` between the initial
-                        // borrow of the primary and here (can't blame it, it's
-                        // not that obvious).
-                        self.peers.primary_mut().trust_block(&verified_block);
-
-                        // No fork detected, exiting
-                        Ok(verified_block)
+                        // If there were no hard forks but some witnesses were replaced,
+                        // perform verification again
+                        if !processed.replaced.is_empty() {
+                            return Ok(None);
+                        }
+
+                        witnesses - processed.timed_out.len()
                     },
+                    ForkDetection::NotDetected => witnesses,
+                };
+
+                let quorum = self.witness_policy.quorum;
+                if agreeing < quorum {
+                    return Err(Error::no_quorum(agreeing, quorum));
                 }
+
+                // We need to re-ask for the primary here as the compiler
+                // is not smart enough to realize that we do not mutate
+                // the `primary` field of `PeerList` between the initial
+                // borrow of the primary and here (can't blame it, it's
+                // not that obvious).
+                self.peers.primary_mut().trust_block(&verified_block);
+                self.emit(SupervisorEvent::BlockTrusted {
+                    height: verified_block.height(),
+                    expires_at: self.expires_at(&verified_block),
+                });
+
+                if let Some(policy) = self.pruning_policy {
+                    let primary = self.peers.primary_mut();
+                    let trusting_period = primary.light_client.options.trusting_period;
+                    let now = primary.light_client.now();
+                    let pruned = policy.prune(&mut primary.state, trusting_period, now);
+                    let approximate_size = primary.state.light_store.approximate_size();
+                    self.emit(SupervisorEvent::LightStorePruned {
+                        pruned,
+                        approximate_size,
+                    });
+                }
+
+                // No fork detected, exiting
+                Ok(Some(verified_block))
             },
             // Verification failed
             Err(err) => {
                 // Swap primary, and continue with new primary, if there is any witness left.
-                self.peers.replace_faulty_primary(Some(err))?;
-                self.verify(height)
+                let faulty = self.peers.primary_id();
+                let reason = err.to_string();
+                let primary = self.peers.replace_faulty_primary(Some(err))?;
+                self.emit(SupervisorEvent::PrimaryReplaced {
+                    faulty,
+                    primary,
+                    reason,
+                });
+                Ok(None)
             },
         }
     }
 
-    fn process_forks(&mut self, forks: Vec<Fork>) -> Result<Vec<PeerId>, Error> {
-        let mut forked = Vec::with_capacity(forks.len());
+    async fn process_forks(
+        &mut self,
+        forks: Vec<Fork>,
+        trusted_block: &LightBlock,
+    ) -> Result<ProcessedForks, Error> {
+        let mut processed = ProcessedForks::default();
 
         for fork in forks {
             match fork {
-                // An actual fork was detected, report evidence and record forked peer.
-                // TODO: also report to primary
+                // An actual fork was detected, report evidence to both peers and record forked
+                // peer.
                 Fork::Forked { primary, witness } => {
                     let provider = witness.provider;
-                    self.report_evidence(provider, &primary, &witness)?;
-
-                    forked.push(provider);
+                    self.peers
+                        .primary_mut()
+                        .state
+                        .light_store
+                        .record_conflict(&witness);
+                    self.submit_evidence(provider, &primary, &witness, trusted_block)
+                        .await?;
+                    self.submit_evidence(primary.provider, &witness, &primary, trusted_block)
+                        .await?;
+
+                    processed.forked.push(provider);
                 },
-                // A witness has timed out, remove it from the peer list.
-                Fork::Timeout(provider, _error) => {
-                    self.peers.replace_faulty_witness(provider);
-                    // TODO: Log/record the error
+                // A witness has timed out, remove it from the peer list if the policy says so.
+                Fork::Timeout(provider, error) => {
+                    if self.witness_policy.replace_timed_out {
+                        self.replace_witness(provider, error.to_string());
+                        processed.replaced.push(provider);
+                    } else {
+                        processed.timed_out.push(provider);
+                    }
                 },
                 // A witness has been deemed faulty, remove it from the peer list.
-                Fork::Faulty(block, _error) => {
-                    self.peers.replace_faulty_witness(block.provider);
-                    // TODO: Log/record the error
+                Fork::Faulty(block, error) => {
+                    self.replace_witness(block.provider, error.to_string());
+                    processed.replaced.push(block.provider);
                 },
             }
         }
 
-        Ok(forked)
+        Ok(processed)
+    }
+
+    fn replace_witness(&mut self, faulty: PeerId, reason: String) {
+        let witness = self.peers.replace_faulty_witness(faulty);
+        self.emit(SupervisorEvent::WitnessReplaced {
+            faulty,
+            witness,
+            reason,
+        });
     }
 
-    /// Report the given evidence of a fork.
-    // TODO: rework to supply LightClientAttackEvidence data
-    fn report_evidence(
+    /// Submit the evidence of a fork to the given peer, if approved by the
+    /// evidence policy: from its point of view, the `conflicting` block
+    /// conflicts with its own `trusted` block, and both were derived from the
+    /// `common` block.
+    ///
+    /// The evidence is queued before being submitted, so that it is submitted
+    /// again if the submission fails. The outcome is emitted as an event:
+    /// failing to submit the evidence does not fail the verification, which
+    /// fails with the fork anyway.
+    async fn submit_evidence(
         &mut self,
-        provider: PeerId,
-        _primary: &LightBlock,
-        _witness: &LightBlock,
+        peer: PeerId,
+        conflicting: &LightBlock,
+        trusted: &LightBlock,
+        common: &LightBlock,
     ) -> Result<(), Error> {
-        self.evidence_reporter
-            .report(Evidence::LightClientAttackEvidence, provider)
-            .map_err(Error::io)?;
+        let evidence = LightClientAttackEvidence::from_conflicting_blocks(
+            conflicting.clone().into(),
+            &trusted.clone().into(),
+            &common.clone().into(),
+        )
+        .map_err(Error::invalid_evidence)?;
+        let evidence = Box::new(evidence);
+
+        if !self.evidence_policy.approve(&evidence, peer) {
+            let held = self.evidence_queue.push(
+                peer,
+                Evidence::LightClientAttack(evidence.clone()),
+                SubmissionStatus::Held,
+            );
+            if let Err(e) = held {
+                self.evidence_queue_failed(e);
+            }
+            self.emit(SupervisorEvent::EvidenceHeld { peer, evidence });
+            return Ok(());
+        }
+
+        let evidence = Evidence::LightClientAttack(evidence);
+        match self
+            .evidence_queue
+            .push(peer, evidence.clone(), SubmissionStatus::Pending)
+        {
+            Ok(id) => {
+                if let Some(entry) = self.evidence_queue.get(id) {
+                    self.deliver_evidence(entry).await;
+                }
+            },
+            // The evidence which could not be queued is submitted once.
+            Err(e) => {
+                self.evidence_queue_failed(e);
+                let _ = self.report_evidence(peer, evidence).await;
+            },
+        }
 
         Ok(())
     }
 
+    /// Submit the given queued evidence to its peer, recording the outcome
+    /// in the queue along with when to submit it again, if need be. Returns
+    /// whether the peer acknowledged the evidence.
+    async fn deliver_evidence(&mut self, mut entry: QueuedEvidence) -> bool {
+        let result = self
+            .report_evidence(entry.peer, entry.evidence.clone())
+            .await;
+        entry.attempts += 1;
+        match &result {
+            Ok(hash) => {
+                entry.status = SubmissionStatus::Acknowledged(*hash);
+                entry.last_error = None;
+                self.evidence_retry_at.remove(&entry.id);
+            },
+            Err(e) => {
+                entry.last_error = Some(e.to_string());
+                if entry.attempts >= self.evidence_retry_policy.max_attempts {
+                    entry.status = SubmissionStatus::Failed;
+                    self.evidence_retry_at.remove(&entry.id);
+                } else {
+                    let backoff = self.evidence_retry_policy.backoff(entry.attempts);
+                    self.evidence_retry_at
+                        .insert(entry.id, Instant::now() + backoff);
+                }
+            },
+        }
+        if let Err(e) = self.evidence_queue.update(&entry) {
+            self.evidence_queue_failed(e);
+        }
+
+        result.is_ok()
+    }
+
+    /// Submit the given evidence to the given peer, emitting the outcome as
+    /// an event.
+    async fn report_evidence(&mut self, peer: PeerId, evidence: Evidence) -> Result<Hash, IoError> {
+        let result = self.evidence_reporter.report(evidence.clone(), peer).await;
+
+        let Evidence::LightClientAttack(evidence) = evidence else {
+            return result;
+        };
+        let event = match &result {
+            Ok(hash) => SupervisorEvent::EvidenceSubmitted {
+                peer,
+                evidence,
+                hash: *hash,
+            },
+            Err(e) => SupervisorEvent::EvidenceSubmissionFailed {
+                peer,
+                evidence,
+                reason: e.to_string(),
+            },
+        };
+        self.emit(event);
+
+        result
+    }
+
+    /// Emit the given error of the evidence queue as an event.
+    fn evidence_queue_failed(&mut self, e: Error) {
+        self.emit(SupervisorEvent::EvidenceQueueFailed {
+            reason: e.to_string(),
+        });
+    }
+
     /// Perform fork detection with the given verified block and trusted block.
-    fn detect_forks(
+    async fn detect_forks(
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
     ) -> Result<ForkDetection, Error> {
-        if self.peers.witnesses_ids().is_empty() {
+        let available = self.peers.witnesses_ids().len();
+        if available == 0 {
             return Err(Error::no_witnesses());
         }
+        let required = self.witness_policy.min_witnesses;
+        if available < required {
+            return Err(Error::not_enough_witnesses(available, required));
+        }
 
         let witnesses = self
             .peers
@@ -317,103 +1288,245 @@ impl Supervisor {
 
         self.fork_detector
             .detect_forks(verified_block, trusted_block, witnesses)
+            .await
     }
 
     /// Run the supervisor event loop in the same thread.
     ///
     /// This method should typically be called within a new thread with `std::thread::spawn`.
-    pub fn run(mut self) -> Result<(), Error> {
+    ///
+    /// Blocking variant of [`Supervisor::run_async`].
+    pub fn run(self) -> Result<(), Error> {
+        run_blocking(self.run_async()).map_err(Error::io)?
+    }
+
+    /// Run the supervisor event loop in the current task.
+    ///
+    /// This method should typically be spawned as a task of its own, e.g.
+    /// with `tokio::spawn`.
+    pub async fn run_async(mut self) -> Result<(), Error> {
+        // Submit the evidence left over by a previous run, if any.
+        self.resubmit_evidence_async().await;
+
+        let mut next_check = None;
         loop {
-            let event = self.receiver.recv().map_err(Error::recv)?;
+            if next_check.is_none() {
+                if let Some(policy) = self.refresh_policy {
+                    self.check_trusted_state_async().await;
+                    next_check = Some(sleep(policy.check_interval));
+                }
+            }
 
-            match event {
+            let event = match next_check.take() {
+                Some(check) => match future::select(check, self.receiver.next()).await {
+                    Either::Left(((), _)) => continue,
+                    Either::Right((event, check)) => {
+                        next_check = Some(check);
+                        event
+                    },
+                },
+                None => self.receiver.next().await,
+            };
+
+            // The replies whose handle gave up waiting for them are dropped.
+            match event.ok_or_else(Error::channel_disconnected)? {
                 HandleInput::LatestTrusted(sender) => {
                     let outcome = self.latest_trusted();
-                    sender.send(outcome).map_err(Error::send)?;
+                    sender.send(outcome).ok();
                 },
                 HandleInput::Terminate(sender) => {
-                    sender.send(()).map_err(Error::send)?;
+                    sender.send(()).ok();
                     return Ok(());
                 },
                 HandleInput::VerifyToTarget(height, sender) => {
-                    let outcome = self.verify_to_target(height);
-                    sender.send(outcome).map_err(Error::send)?;
+                    let outcome = self.verify_to_target_async(height).await;
+                    sender.send(outcome).ok();
                 },
                 HandleInput::VerifyToHighest(sender) => {
-                    let outcome = self.verify_to_highest();
-                    sender.send(outcome).map_err(Error::send)?;
+                    let outcome = self.verify_to_highest_async().await;
+                    sender.send(outcome).ok();
                 },
                 HandleInput::GetStatus(sender) => {
                     let outcome = self.latest_status();
-                    sender.send(outcome).map_err(Error::send)?;
+                    sender.send(outcome).ok();
+                },
+                HandleInput::Evidence(sender) => {
+                    let outcome = self.evidence();
+                    sender.send(outcome).ok();
                 },
             }
         }
     }
 }
 
-/// A [`Handle`] to the [`Supervisor`] which allows to communicate with
-/// the supervisor across thread boundaries via message passing.
+/// How often [`Supervisor::follow`] polls the primary for new blocks by
+/// default, about the time between two blocks of most chains.
+pub const DEFAULT_FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The iterator over the verified light blocks of every height, returned by
+/// [`Supervisor::follow`].
+#[derive(Debug)]
+pub struct Follow<'a> {
+    supervisor: &'a mut Supervisor,
+    /// The height of the next block to yield
+    next_height: Height,
+    /// The height of the highest block of the primary, once verified
+    highest: Option<Height>,
+    /// How long to wait before polling the primary for new blocks again
+    poll_interval: Duration,
+    /// The subscription to the new blocks, until it ends
+    #[cfg(feature = "rpc-client")]
+    new_blocks: Option<Subscription>,
+}
+
+impl Follow<'_> {
+    /// Set how long to wait before polling the primary for new blocks again,
+    /// once its highest block was yielded.
+    #[must_use]
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Wait for the new blocks with the given subscription to the `NewBlock`
+    /// events of the primary, rather than by polling it, once its highest
+    /// block was yielded.
+    ///
+    /// The subscription is typically made by a
+    /// [`WebSocketClient`](tendermint_rpc::WebSocketClient) whose driver runs
+    /// on another thread, with `subscribe(EventType::NewBlock.into())`. Once
+    /// it ends or fails, the iterator falls back to polling.
+    #[cfg(feature = "rpc-client")]
+    #[must_use]
+    pub fn new_blocks(mut self, subscription: Subscription) -> Self {
+        self.new_blocks = Some(subscription);
+        self
+    }
+
+    /// The height of the next block to yield.
+    pub fn next_height(&self) -> Height {
+        self.next_height
+    }
+
+    /// Wait until the primary may have a new block.
+    fn wait(&mut self) {
+        #[cfg(feature = "rpc-client")]
+        if let Some(new_blocks) = self.new_blocks.as_mut() {
+            if let Some(Ok(_)) = futures::executor::block_on(new_blocks.next()) {
+                return;
+            }
+            self.new_blocks = None;
+        }
+        thread::sleep(self.poll_interval);
+    }
+}
+
+impl Iterator for Follow<'_> {
+    type Item = Result<LightBlock, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.highest {
+                Some(highest) if highest >= self.next_height => break,
+                Some(_) => self.wait(),
+                None => {},
+            }
+            match self.supervisor.verify_to_highest() {
+                Ok(highest) => self.highest = Some(highest.height()),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        let result = self.supervisor.verify_to_target(self.next_height);
+        if result.is_ok() {
+            self.next_height = self.next_height.increment();
+        }
+        Some(result)
+    }
+}
+
+/// A [`Handle`] and [`AsyncHandle`] to the [`Supervisor`] which allows to
+/// communicate with the supervisor across thread boundaries via message
+/// passing.
 #[derive(Clone)]
 pub struct SupervisorHandle {
-    sender: channel::Sender<HandleInput>,
+    sender: mpsc::UnboundedSender<HandleInput>,
 }
 
 impl SupervisorHandle {
     /// Crate a new handle that sends events to the supervisor via
     /// the given channel. For internal use only.
-    fn new(sender: channel::Sender<HandleInput>) -> Self {
+    fn new(sender: mpsc::UnboundedSender<HandleInput>) -> Self {
         Self { sender }
     }
 
-    fn verify(
+    /// Send the event made with the given reply channel to the supervisor,
+    /// and wait for the reply.
+    async fn request<T>(
         &self,
-        make_event: impl FnOnce(channel::Sender<Result<LightBlock, Error>>) -> HandleInput,
-    ) -> Result<LightBlock, Error> {
-        let (sender, receiver) = channel::bounded::<Result<LightBlock, Error>>(1);
-
-        let event = make_event(sender);
-        self.sender.send(event).map_err(Error::send)?;
-
-        receiver.recv().map_err(Error::recv)?
-    }
-}
-
-impl Handle for SupervisorHandle {
-    fn latest_trusted(&self) -> Result<Option<LightBlock>, Error> {
-        let (sender, receiver) = channel::bounded::<Option<LightBlock>>(1);
+        make_event: impl FnOnce(oneshot::Sender<T>) -> HandleInput,
+    ) -> Result<T, Error> {
+        let (sender, receiver) = oneshot::channel();
 
         self.sender
-            .send(HandleInput::LatestTrusted(sender))
+            .unbounded_send(make_event(sender))
             .map_err(Error::send)?;
 
-        receiver.recv().map_err(Error::recv)
+        receiver.await.map_err(Error::canceled)
     }
+}
 
-    fn latest_status(&self) -> Result<LatestStatus, Error> {
-        let (sender, receiver) = channel::bounded::<LatestStatus>(1);
-        self.sender
-            .send(HandleInput::GetStatus(sender))
-            .map_err(Error::send)?;
-        receiver.recv().map_err(Error::recv)
+#[async_trait]
+impl AsyncHandle for SupervisorHandle {
+    async fn latest_trusted(&self) -> Result<Option<LightBlock>, Error> {
+        self.request(HandleInput::LatestTrusted).await
     }
 
-    fn verify_to_highest(&self) -> Result<LightBlock, Error> {
-        self.verify(HandleInput::VerifyToHighest)
+    async fn latest_status(&self) -> Result<LatestStatus, Error> {
+        self.request(HandleInput::GetStatus).await
     }
 
-    fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error> {
-        self.verify(|sender| HandleInput::VerifyToTarget(height, sender))
+    async fn verify_to_highest(&self) -> Result<LightBlock, Error> {
+        self.request(HandleInput::VerifyToHighest).await?
     }
 
-    fn terminate(&self) -> Result<(), Error> {
-        let (sender, receiver) = channel::bounded::<()>(1);
+    async fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error> {
+        self.request(|sender| HandleInput::VerifyToTarget(height, sender))
+            .await?
+    }
 
-        self.sender
-            .send(HandleInput::Terminate(sender))
-            .map_err(Error::send)?;
+    async fn evidence(&self) -> Result<Vec<QueuedEvidence>, Error> {
+        self.request(HandleInput::Evidence).await
+    }
 
-        receiver.recv().map_err(Error::recv)
+    async fn terminate(&self) -> Result<(), Error> {
+        self.request(HandleInput::Terminate).await
+    }
+}
+
+impl Handle for SupervisorHandle {
+    fn latest_trusted(&self) -> Result<Option<LightBlock>, Error> {
+        futures::executor::block_on(AsyncHandle::latest_trusted(self))
+    }
+
+    fn latest_status(&self) -> Result<LatestStatus, Error> {
+        futures::executor::block_on(AsyncHandle::latest_status(self))
+    }
+
+    fn verify_to_highest(&self) -> Result<LightBlock, Error> {
+        futures::executor::block_on(AsyncHandle::verify_to_highest(self))
+    }
+
+    fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error> {
+        futures::executor::block_on(AsyncHandle::verify_to_target(self, height))
+    }
+
+    fn evidence(&self) -> Result<Vec<QueuedEvidence>, Error> {
+        futures::executor::block_on(AsyncHandle::evidence(self))
+    }
+
+    fn terminate(&self) -> Result<(), Error> {
+        futures::executor::block_on(AsyncHandle::terminate(self))
     }
 }
 
@@ -423,7 +1536,10 @@ mod tests {
         convert::{Into, TryFrom},
         time::Duration,
     };
-    use std::collections::HashMap;
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    };
 
     use tendermint::{
         block::Height, evidence::Duration as DurationStr, trust_threshold::TrustThresholdFraction,
@@ -440,11 +1556,12 @@ mod tests {
     use super::*;
     use crate::{
         components::{
-            io::{self, AtHeight, Io},
+            io::{self, AsyncIo, AtHeight},
             scheduler,
         },
         errors::{Error, ErrorDetail},
         fork_detector::ProdForkDetector,
+        light_client::StepOutcome,
         store::{memory::MemoryStore, LightStore},
         tests::{MockClock, MockEvidenceReporter, MockIo, TrustOptions},
         verifier::{options::Options, types::Time, ProdVerifier},
@@ -468,21 +1585,22 @@ mod tests {
     fn make_instance(
         peer_id: PeerId,
         trust_options: TrustOptions,
-        io: MockIo,
+        io: impl AsyncIo + 'static,
         now: Time,
     ) -> Instance {
         let trusted_height = trust_options.height;
-        let trusted_state = io
-            .fetch_light_block(AtHeight::At(trusted_height))
-            .expect("could not 'request' light block");
+        let trusted_state =
+            futures::executor::block_on(io.fetch_light_block(AtHeight::At(trusted_height)))
+                .expect("could not 'request' light block");
 
         let mut light_store = MemoryStore::new();
         light_store.insert(trusted_state, Status::Trusted);
 
         for extra_trusted_height in trust_options.extra_heights {
-            let trusted_state = io
-                .fetch_light_block(AtHeight::At(extra_trusted_height))
-                .expect("could not 'request' light block");
+            let trusted_state = futures::executor::block_on(
+                io.fetch_light_block(AtHeight::At(extra_trusted_height)),
+            )
+            .expect("could not 'request' light block");
 
             light_store.insert(trusted_state, Status::Trusted);
         }
@@ -496,6 +1614,7 @@ mod tests {
             trust_threshold: trust_options.trust_level,
             trusting_period: trust_options.period.into(),
             clock_drift: Duration::from_secs(0),
+            time_monotonicity: Default::default(),
         };
 
         let verifier = ProdVerifier::default();
@@ -523,8 +1642,8 @@ mod tests {
         let target_height = Height::try_from(height_to_verify).expect("Error while making height");
 
         (
-            handle.verify_to_target(target_height),
-            handle.latest_status().unwrap(),
+            Handle::verify_to_target(&handle, target_height),
+            Handle::latest_status(&handle).unwrap(),
         )
     }
 
@@ -729,8 +1848,7 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_bisection_fork_detected() {
+    fn make_forked_peer_list() -> (PeerList<Instance>, PeerId, PeerId) {
         let mut chain = LightChain::default_with_length(5);
         let primary = chain
             .light_blocks
@@ -754,8 +1872,16 @@ mod tests {
             None,
         );
 
+        let (primary_id, witness_id) = (primary[0].provider, witness[0].provider);
         let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11).unwrap());
 
+        (peer_list, primary_id, witness_id)
+    }
+
+    #[test]
+    fn test_bisection_fork_detected() {
+        let (peer_list, _, _) = make_forked_peer_list();
+
         let (result, _) = run_bisection_test(peer_list, 5);
 
         match result {
@@ -764,6 +1890,331 @@ mod tests {
         }
     }
 
+    fn run_fork_test(
+        policy: impl EvidencePolicy + 'static,
+    ) -> (Vec<SupervisorEvent>, PeerId, PeerId) {
+        let (peer_list, primary_id, witness_id) = make_forked_peer_list();
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .evidence_policy(policy);
+        let events = supervisor.events();
+
+        let result = supervisor.verify_to_target(Height::try_from(5_u64).unwrap());
+        assert!(matches!(
+            result,
+            Err(Error(ErrorDetail::ForkDetected(_), _))
+        ));
+
+        let evidence_events = events
+            .try_iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    SupervisorEvent::EvidenceSubmitted { .. }
+                        | SupervisorEvent::EvidenceHeld { .. }
+                        | SupervisorEvent::EvidenceSubmissionFailed { .. }
+                )
+            })
+            .collect();
+
+        (evidence_events, primary_id, witness_id)
+    }
+
+    #[test]
+    fn test_fork_evidence_submitted_to_both_peers() {
+        let (events, primary_id, witness_id) = run_fork_test(SubmitAll);
+
+        let peers: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                SupervisorEvent::EvidenceSubmitted { peer, evidence, .. } => {
+                    assert_eq!(
+                        evidence
+                            .conflicting_block
+                            .signed_header
+                            .header
+                            .height
+                            .value(),
+                        5
+                    );
+                    *peer
+                },
+                _ => panic!("unexpected event: {event:?}"),
+            })
+            .collect();
+        assert_eq!(peers, vec![witness_id, primary_id]);
+    }
+
+    #[test]
+    fn test_fork_evidence_held_by_policy() {
+        // The provider of the witness blocks, see `change_provider`.
+        let witness: PeerId = "0BEFEEDC0C0ADEADBEBADFADADEFC0FFEEFACADE".parse().unwrap();
+        let (events, primary_id, witness_id) =
+            run_fork_test(move |_: &LightClientAttackEvidence, peer| peer == witness);
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            SupervisorEvent::EvidenceSubmitted { peer, .. } if *peer == witness_id
+        ));
+        assert!(matches!(
+            &events[1],
+            SupervisorEvent::EvidenceHeld { peer, .. } if *peer == primary_id
+        ));
+    }
+
+    /// Fails to report evidence until told to accept it.
+    struct UnreachableReporter(Arc<AtomicBool>);
+
+    #[async_trait]
+    impl EvidenceReporter for UnreachableReporter {
+        async fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, io::IoError> {
+            if self.0.load(Ordering::SeqCst) {
+                MockEvidenceReporter::new().report(e, peer).await
+            } else {
+                Err(io::IoError::timeout(Duration::from_secs(1)))
+            }
+        }
+    }
+
+    #[test]
+    fn test_fork_evidence_resubmitted_until_acknowledged() {
+        let (peer_list, primary_id, witness_id) = make_forked_peer_list();
+        let reachable = Arc::new(AtomicBool::new(false));
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            UnreachableReporter(reachable.clone()),
+        )
+        .evidence_retry_policy(EvidenceRetryPolicy {
+            initial_backoff: Duration::ZERO,
+            ..EvidenceRetryPolicy::default()
+        });
+
+        let result = supervisor.verify_to_target(Height::try_from(5_u64).unwrap());
+        assert!(matches!(
+            result,
+            Err(Error(ErrorDetail::ForkDetected(_), _))
+        ));
+
+        let queued = supervisor.evidence();
+        let peers: Vec<_> = queued.iter().map(|entry| entry.peer).collect();
+        assert_eq!(peers, vec![witness_id, primary_id]);
+        for entry in &queued {
+            assert!(entry.is_pending());
+            assert_eq!(entry.attempts, 1);
+            assert!(entry.last_error.is_some());
+        }
+
+        // Still unreachable.
+        assert_eq!(supervisor.resubmit_evidence().unwrap(), 0);
+        assert_eq!(supervisor.evidence()[0].attempts, 2);
+
+        reachable.store(true, Ordering::SeqCst);
+        assert_eq!(supervisor.resubmit_evidence().unwrap(), 2);
+        for entry in supervisor.evidence() {
+            assert!(matches!(
+                supervisor.evidence_status(entry.id),
+                Some(SubmissionStatus::Acknowledged(_))
+            ));
+            assert_eq!(entry.attempts, 3);
+            assert_eq!(entry.last_error, None);
+        }
+
+        // Acknowledged evidence is not submitted again.
+        assert_eq!(supervisor.resubmit_evidence().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fork_evidence_given_up_after_max_attempts() {
+        let (peer_list, _, _) = make_forked_peer_list();
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            UnreachableReporter(Arc::new(AtomicBool::new(false))),
+        )
+        .evidence_retry_policy(EvidenceRetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(150),
+        });
+
+        let result = supervisor.verify_to_target(Height::try_from(5_u64).unwrap());
+        assert!(matches!(
+            result,
+            Err(Error(ErrorDetail::ForkDetected(_), _))
+        ));
+
+        // The evidence is not submitted again until its backoff elapsed.
+        assert_eq!(supervisor.resubmit_evidence().unwrap(), 0);
+        assert!(supervisor
+            .evidence()
+            .iter()
+            .all(|entry| entry.attempts == 1));
+
+        for attempts in 2..=3 {
+            std::thread::sleep(Duration::from_millis(150));
+            assert_eq!(supervisor.resubmit_evidence().unwrap(), 0);
+            assert!(supervisor
+                .evidence()
+                .iter()
+                .all(|entry| entry.attempts == attempts));
+        }
+
+        // Given up on after the third attempt.
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(supervisor.resubmit_evidence().unwrap(), 0);
+        for entry in supervisor.evidence() {
+            assert_eq!(entry.status, SubmissionStatus::Failed);
+            assert_eq!(entry.attempts, 3);
+        }
+    }
+
+    #[test]
+    fn test_evidence_retry_backoff() {
+        let policy = EvidenceRetryPolicy {
+            max_attempts: 100,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(4), Duration::from_secs(8));
+        assert_eq!(policy.backoff(5), Duration::from_secs(10));
+        assert_eq!(policy.backoff(99), Duration::from_secs(10));
+    }
+
+    fn make_two_witnesses_peer_list(primary_length: usize) -> (PeerList<Instance>, PeerId) {
+        let chain = LightChain::default_with_length(10);
+        let blocks = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+
+        let mut primary = blocks.clone();
+        primary.truncate(primary_length);
+        let witness1 = change_provider(blocks.clone(), None);
+        let witness2 = change_provider(blocks, Some("EDC0C0ADEADBEBA0BEFEDFADADEFC0FFEEFACADE"));
+
+        let primary_id = primary[0].provider;
+        let peer_list = make_peer_list(
+            Some(primary),
+            Some(vec![witness1, witness2]),
+            get_time(11).unwrap(),
+        );
+
+        (peer_list, primary_id)
+    }
+
+    fn run_witness_policy_test(
+        peer_list: PeerList<Instance>,
+        policy: WitnessPolicy,
+    ) -> (Result<LightBlock, Error>, Vec<SupervisorEvent>) {
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .witness_policy(policy)
+        .unwrap();
+        let events = supervisor.events();
+
+        let result = supervisor.verify_to_target(Height::try_from(10_u64).unwrap());
+
+        (result, non_verification_events(&events))
+    }
+
+    #[test]
+    fn test_witness_policy_min_witnesses() {
+        let (peer_list, _) = make_two_witnesses_peer_list(10);
+        let policy = WitnessPolicy {
+            min_witnesses: 3,
+            ..WitnessPolicy::default()
+        };
+
+        let (result, _) = run_witness_policy_test(peer_list, policy);
+
+        match result {
+            Err(Error(ErrorDetail::NotEnoughWitnesses(e), _)) => {
+                assert_eq!((e.available, e.required), (2, 3));
+            },
+            _ => panic!("expected NotEnoughWitnesses error, instead got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_witness_policy_quorum() {
+        let policy = WitnessPolicy {
+            min_witnesses: 2,
+            quorum: 2,
+            ..WitnessPolicy::default()
+        };
+
+        let (peer_list, _) = make_two_witnesses_peer_list(10);
+        let (result, _) = run_witness_policy_test(peer_list, policy);
+        assert_eq!(result.unwrap().height().value(), 10);
+
+        // Once the primary is replaced by a witness, a single witness is left.
+        let (peer_list, _) = make_two_witnesses_peer_list(9);
+        let (result, _) = run_witness_policy_test(peer_list, policy);
+        match result {
+            Err(Error(ErrorDetail::NotEnoughWitnesses(e), _)) => {
+                assert_eq!((e.available, e.required), (1, 2));
+            },
+            _ => panic!("expected NotEnoughWitnesses error, instead got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_witness_policy_validation() {
+        let (peer_list, _) = make_two_witnesses_peer_list(10);
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+        for (min_witnesses, quorum) in [(1, 0), (1, 2)] {
+            let policy = WitnessPolicy {
+                min_witnesses,
+                quorum,
+                ..WitnessPolicy::default()
+            };
+            match policy.validate() {
+                Err(Error(ErrorDetail::InvalidWitnessPolicy(_), _)) => {},
+                result => panic!("expected InvalidWitnessPolicy error, instead got {result:?}"),
+            }
+        }
+        assert!(supervisor
+            .witness_policy(WitnessPolicy {
+                min_witnesses: 3,
+                quorum: 2,
+                ..WitnessPolicy::default()
+            })
+            .is_ok());
+    }
+
+    #[test]
+    fn test_faulty_primary_replaced() {
+        let (peer_list, primary_id) = make_two_witnesses_peer_list(9);
+
+        let (result, events) = run_witness_policy_test(peer_list, WitnessPolicy::default());
+
+        assert_eq!(result.unwrap().height().value(), 10);
+        match events.as_slice() {
+            [SupervisorEvent::PrimaryReplaced {
+                faulty, primary, ..
+            }, SupervisorEvent::BlockTrusted { .. }] => {
+                assert_eq!(*faulty, primary_id);
+                assert_ne!(*primary, primary_id);
+            },
+            _ => panic!("expected PrimaryReplaced event, instead got {events:?}"),
+        }
+    }
+
     #[test]
     fn test_bisection_no_initial_trusted_state() {
         let chain = LightChain::default_with_length(10);
@@ -842,6 +2293,259 @@ mod tests {
             .any(|&peer| peer == primary[0].provider));
     }
 
+    /// The events received so far, except for the progress of the light clients.
+    fn non_verification_events(
+        events: &channel::Receiver<SupervisorEvent>,
+    ) -> Vec<SupervisorEvent> {
+        events
+            .try_iter()
+            .filter(|event| !matches!(event, SupervisorEvent::Verification { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn test_pruning_policy() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+        let witness = change_provider(primary.clone(), None);
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11).unwrap());
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .pruning_policy(PruningPolicy {
+            keep_last: Some(1),
+            keep_within_trusting_period: false,
+            checkpoint_interval: None,
+            max_bytes: None,
+        });
+        let events = supervisor.events();
+        let stored = |supervisor: &Supervisor| -> Vec<u64> {
+            let store = &supervisor.peers.primary().state.light_store;
+            Status::iter()
+                .iter()
+                .flat_map(|status| store.all(*status))
+                .map(|lb| lb.height().value())
+                .collect()
+        };
+
+        supervisor
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+        assert_eq!(stored(&supervisor), [1, 10]);
+        let size = supervisor
+            .peers
+            .primary()
+            .state
+            .light_store
+            .approximate_size();
+        assert!(matches!(
+            non_verification_events(&events).last(),
+            Some(SupervisorEvent::LightStorePruned { approximate_size, .. }) if *approximate_size == size
+        ));
+
+        // Pruned heights can still be verified.
+        let block = supervisor
+            .verify_to_target(Height::try_from(5_u64).unwrap())
+            .unwrap();
+        assert_eq!(block.height().value(), 5);
+        assert_eq!(stored(&supervisor), [1, 10]);
+    }
+
+    #[test]
+    fn test_verification_events() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+        let witness = change_provider(primary.clone(), None);
+        let primary_id = primary[0].provider;
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11).unwrap());
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+        let events = supervisor.events();
+
+        let target = Height::try_from(10_u64).unwrap();
+        supervisor.verify_to_target(target).unwrap();
+
+        let events: Vec<_> = events.try_iter().collect();
+        let primary_events: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                SupervisorEvent::Verification { peer, event } if *peer == primary_id => {
+                    Some(event.clone())
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            primary_events.first(),
+            Some(&VerificationEvent::Started {
+                target,
+                trusted: Height::try_from(1_u64).unwrap(),
+            })
+        );
+        assert!(primary_events.iter().any(|event| matches!(
+            event,
+            VerificationEvent::BisectionStep {
+                height,
+                outcome: StepOutcome::Verified,
+                ..
+            } if *height == target
+        )));
+        assert_eq!(
+            primary_events.last(),
+            Some(&VerificationEvent::Verified { target })
+        );
+        assert!(matches!(
+            events.last(),
+            Some(SupervisorEvent::BlockTrusted { height, .. }) if *height == target
+        ));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+        let witness = change_provider(primary.clone(), None);
+        let primary_id = primary[0].provider;
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11).unwrap());
+
+        let metrics = Metrics::new();
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .metrics(metrics.clone());
+        assert!(metrics
+            .render()
+            .lines()
+            .any(|line| line == "tendermint_light_client_trusted_height 1"));
+
+        supervisor
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+
+        let rendered = metrics.render();
+        for line in [
+            "tendermint_light_client_trusted_height 10".to_string(),
+            format!(
+                "tendermint_light_client_verification_duration_seconds_count{{peer=\"{primary_id}\"}} 1"
+            ),
+            "tendermint_light_client_forks_detected_total 0".to_string(),
+        ] {
+            assert!(
+                rendered.lines().any(|l| l == line),
+                "missing {line:?} in\n{rendered}"
+            );
+        }
+    }
+
+    fn make_refreshing_supervisor(now: u64) -> (Supervisor, Vec<LightBlock>) {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let peer_list = make_peer_list_opts(
+            Some(primary.clone()),
+            Some(vec![witness]),
+            get_time(now).unwrap(),
+            TrustOptions {
+                period: DurationStr(Duration::new(30, 0)),
+                height: Height::try_from(1_u64).expect("Error while making height"),
+                extra_heights: vec![],
+                trust_level: TrustThresholdFraction::TWO_THIRDS,
+            },
+        );
+
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .refresh_policy(RefreshPolicy::default());
+
+        (supervisor, primary)
+    }
+
+    #[test]
+    fn test_refresh_aging_trusted_state() {
+        // The trusted block at height 1 is 24s old, past two thirds of the
+        // trusting period of 30s.
+        let (mut supervisor, primary) = make_refreshing_supervisor(25);
+        let receiver = supervisor.events();
+        let events = || non_verification_events(&receiver);
+
+        supervisor.check_trusted_state();
+
+        let mut checked = events().into_iter();
+        let from = Height::try_from(1_u64).unwrap();
+        match checked.next() {
+            Some(SupervisorEvent::TrustedStateAging { height, age, .. }) => {
+                assert_eq!(height, from);
+                assert_eq!(age, Duration::from_secs(24));
+            },
+            event => panic!("expected TrustedStateAging event, instead got {event:?}"),
+        }
+        assert_eq!(
+            checked.next(),
+            Some(SupervisorEvent::BlockTrusted {
+                height: primary[9].height(),
+                expires_at: (primary[9].time() + Duration::from_secs(30)).unwrap(),
+            })
+        );
+        assert_eq!(
+            checked.next(),
+            Some(SupervisorEvent::TrustedStateRefreshed {
+                from,
+                to: primary[9].height(),
+            })
+        );
+        assert_eq!(supervisor.latest_trusted(), Some(primary[9].clone()));
+
+        // The refreshed trusted state is recent enough.
+        supervisor.check_trusted_state();
+        assert!(events().is_empty());
+    }
+
+    #[test]
+    fn test_refresh_expired_trusted_state() {
+        let (mut supervisor, _) = make_refreshing_supervisor(40);
+        let events = supervisor.events();
+
+        supervisor.check_trusted_state();
+
+        match non_verification_events(&events).as_slice() {
+            [SupervisorEvent::TrustedStateExpired { height, .. }] => {
+                assert_eq!(*height, Height::try_from(1_u64).unwrap())
+            },
+            events => panic!("expected TrustedStateExpired event, instead got {events:?}"),
+        }
+    }
+
     #[test]
     fn test_bisection_invalid_light_block() {
         let chain = LightChain::default_with_length(10);
@@ -908,4 +2612,194 @@ mod tests {
 
         assert_eq!(expected_state, new_state);
     }
+
+    /// Serves the blocks of a chain up to a height, which can be raised to
+    /// simulate new blocks being committed.
+    #[derive(Clone)]
+    struct GrowingIo {
+        io: MockIo,
+        highest: Arc<AtomicU64>,
+    }
+
+    #[async_trait]
+    impl AsyncIo for GrowingIo {
+        async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, io::IoError> {
+            let highest = Height::try_from(self.highest.load(Ordering::SeqCst)).unwrap();
+            match height {
+                AtHeight::At(height) if height > highest => Err(io::IoError::rpc(
+                    rpc::Error::response(ResponseError::new(Code::InvalidParams, None)),
+                )),
+                AtHeight::At(height) => self.io.fetch_light_block(AtHeight::At(height)).await,
+                AtHeight::Highest => self.io.fetch_light_block(AtHeight::At(highest)).await,
+            }
+        }
+    }
+
+    /// A supervisor of a chain of 10 blocks whose primary and witness have
+    /// the first 5 ones, along with the height of their highest block.
+    fn follow_supervisor() -> (Vec<LightBlock>, Arc<AtomicU64>, Supervisor) {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+        let witness = change_provider(primary.clone(), None);
+
+        let highest = Arc::new(AtomicU64::new(5));
+        let now = get_time(11).unwrap();
+        let trust_options = TrustOptions {
+            period: DurationStr(Duration::new(604800, 0)),
+            height: Height::try_from(1_u64).unwrap(),
+            extra_heights: vec![],
+            trust_level: TrustThresholdFraction::TWO_THIRDS,
+        };
+        let mut peer_list = PeerList::builder();
+        for (peer_id, blocks) in [
+            (primary[0].provider, primary.clone()),
+            (witness[0].provider, witness),
+        ] {
+            let io = GrowingIo {
+                io: MockIo::new(blocks),
+                highest: highest.clone(),
+            };
+            let instance = make_instance(peer_id, trust_options.clone(), io, now);
+            if peer_id == primary[0].provider {
+                peer_list.primary(peer_id, instance);
+            } else {
+                peer_list.witness(peer_id, instance);
+            }
+        }
+
+        let supervisor = Supervisor::new(
+            peer_list.build(),
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+        (primary, highest, supervisor)
+    }
+
+    #[test]
+    fn test_follow() {
+        let (primary, highest, mut supervisor) = follow_supervisor();
+
+        // The chain grows to 10 blocks once the first 5 ones were followed.
+        let grower = {
+            let highest = highest.clone();
+            std::thread::spawn(move || {
+                while highest.load(Ordering::SeqCst) < 10 {
+                    std::thread::sleep(Duration::from_millis(50));
+                    highest.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+
+        let followed = supervisor
+            .follow(Height::try_from(3_u64).unwrap())
+            .poll_interval(Duration::from_millis(10))
+            .take(8)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        grower.join().unwrap();
+
+        assert_eq!(followed, primary[2..10].to_vec());
+        assert_eq!(supervisor.latest_trusted(), Some(primary[9].clone()));
+    }
+
+    #[cfg(feature = "rpc-client")]
+    #[test]
+    fn test_follow_new_blocks() {
+        use tendermint_rpc::{
+            event::{Event, EventData},
+            query::{EventType, Query},
+            MockClient, MockRequestMethodMatcher, SubscriptionClient,
+        };
+
+        let (primary, highest, mut supervisor) = follow_supervisor();
+
+        let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+        let driver = std::thread::spawn(move || futures::executor::block_on(driver.run()));
+        let new_blocks =
+            futures::executor::block_on(client.subscribe(EventType::NewBlock.into())).unwrap();
+
+        // The chain grows to 10 blocks, with an event for each new block.
+        let grower = {
+            let highest = highest.clone();
+            std::thread::spawn(move || {
+                while highest.load(Ordering::SeqCst) < 10 {
+                    std::thread::sleep(Duration::from_millis(50));
+                    highest.fetch_add(1, Ordering::SeqCst);
+                    client.publish(&Event {
+                        query: Query::from(EventType::NewBlock).to_string(),
+                        data: EventData::GenericJsonEvent(serde_json::Value::Null),
+                        events: None,
+                    });
+                }
+                client.close();
+            })
+        };
+
+        // The primary is never polled, so that only the events let the
+        // iterator go past the 5 first blocks.
+        let followed = supervisor
+            .follow(Height::try_from(3_u64).unwrap())
+            .poll_interval(Duration::from_secs(3600))
+            .new_blocks(new_blocks)
+            .take(8)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        grower.join().unwrap();
+        driver.join().unwrap().unwrap();
+
+        assert_eq!(followed, primary[2..10].to_vec());
+        assert_eq!(supervisor.latest_trusted(), Some(primary[9].clone()));
+    }
+
+    #[test]
+    fn test_async_handle() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let peer_list = make_peer_list(
+            Some(primary.clone()),
+            Some(vec![witness]),
+            get_time(11).unwrap(),
+        );
+
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+
+        let handle = supervisor.handle();
+
+        // The supervisor and its handle run on the same thread.
+        let (outcome, ()) =
+            futures::executor::block_on(future::join(supervisor.run_async(), async {
+                let target_height = Height::try_from(10_u64).unwrap();
+                let new_state = AsyncHandle::verify_to_target(&handle, target_height)
+                    .await
+                    .unwrap();
+                assert_eq!(new_state, primary[9]);
+
+                let latest_trusted = AsyncHandle::latest_trusted(&handle).await.unwrap();
+                assert_eq!(latest_trusted, Some(primary[9].clone()));
+
+                AsyncHandle::terminate(&handle).await.unwrap();
+            }));
+        outcome.unwrap();
+
+        let result = Handle::latest_status(&handle);
+        assert!(matches!(
+            result.unwrap_err().detail(),
+            ErrorDetail::ChannelDisconnected(_)
+        ));
+    }
 }
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 #[cfg(feature = "rust-crypto")]
 use std::time::Duration;
 
-use contracts::contract_trait;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tendermint::{
     block::Height as HeightStr,
@@ -18,7 +18,7 @@ use tendermint_rpc as rpc;
 use crate::{
     components::{
         clock::Clock,
-        io::{AtHeight, Io, IoError},
+        io::{AsyncIo, AtHeight, Io, IoError},
     },
     errors::Error,
     evidence::EvidenceReporter,
@@ -134,12 +134,19 @@ impl Io for MockIo {
     }
 }
 
+#[async_trait]
+impl AsyncIo for MockIo {
+    async fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        Io::fetch_light_block(self, height)
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct MockEvidenceReporter;
 
-#[contract_trait]
+#[async_trait]
 impl EvidenceReporter for MockEvidenceReporter {
-    fn report(&self, _e: Evidence, _peer: PeerId) -> Result<Hash, IoError> {
+    async fn report(&self, _e: Evidence, _peer: PeerId) -> Result<Hash, IoError> {
         Ok(Hash::from_bytes(Algorithm::Sha256, &[0; 32]).unwrap())
     }
 }
@@ -167,6 +174,7 @@ pub fn verify_single(
         trust_threshold,
         trusting_period,
         clock_drift,
+        time_monotonicity: Default::default(),
     };
 
     let result = verifier.verify(
@@ -176,9 +184,10 @@ pub fn verify_single(
         now,
     );
 
-    match result {
-        Verdict::Success => Ok(input),
-        error => Err(error),
+    if result.is_success() {
+        Ok(input)
+    } else {
+        Err(result)
     }
 }
 
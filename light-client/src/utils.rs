@@ -1,8 +1,11 @@
 //! Various general-purpose utilities
 
-#[cfg(feature = "rpc-client")]
 mod block_on;
 #[cfg(feature = "rpc-client")]
 pub use block_on::block_on;
+pub use block_on::run_blocking;
+
+mod sleep;
+pub use sleep::sleep;
 
 pub mod std_ext;
@@ -1,28 +1,58 @@
-use std::{future::Future, time::Duration};
+use std::future::Future;
+#[cfg(feature = "rpc-client")]
+use std::time::Duration;
 
 use crate::components::io::IoError;
 
 /// Run a future to completion on a new thread, with the given timeout.
 ///
 /// This function will block the caller until the given future has completed.
+#[cfg(feature = "rpc-client")]
 pub fn block_on<F>(timeout: Option<Duration>, f: F) -> Result<F::Output, IoError>
 where
-    F: Future + Send + 'static,
+    F: Future + Send,
     F::Output: Send,
 {
-    std::thread::spawn(move || {
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(IoError::runtime)?;
+    std::thread::scope(|scope| {
+        scope
+            .spawn(move || {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(IoError::runtime)?;
 
-        if let Some(timeout) = timeout {
-            let task = async { tokio::time::timeout(timeout, f).await };
-            rt.block_on(task).map_err(|e| IoError::timeout(timeout, e))
-        } else {
-            Ok(rt.block_on(f))
-        }
+                if let Some(timeout) = timeout {
+                    let task = async { tokio::time::timeout(timeout, f).await };
+                    rt.block_on(task).map_err(|e| IoError::timeout(timeout, e))
+                } else {
+                    Ok(rt.block_on(f))
+                }
+            })
+            .join()
+            .unwrap()
     })
-    .join()
-    .unwrap()
+}
+
+/// Run a future to completion, blocking the caller.
+///
+/// With the `rpc-client` feature, the future is run on a new thread, within a
+/// Tokio runtime of its own as required by the RPC clients, so that it never
+/// blocks on the runtime of the caller, if any.
+#[cfg(feature = "rpc-client")]
+pub fn run_blocking<F>(f: F) -> Result<F::Output, IoError>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    block_on(None, f)
+}
+
+/// Run a future to completion, blocking the caller.
+#[cfg(not(feature = "rpc-client"))]
+pub fn run_blocking<F>(f: F) -> Result<F::Output, IoError>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    Ok(futures::executor::block_on(f))
 }
@@ -0,0 +1,14 @@
+use std::{future::Future, thread, time::Duration};
+
+use futures::{channel::oneshot, FutureExt};
+
+/// A future which completes once the given duration has elapsed, whichever
+/// the executor polling it, as it sleeps on a thread of its own.
+pub fn sleep(duration: Duration) -> impl Future<Output = ()> + Send + Unpin {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(duration);
+        sender.send(()).ok();
+    });
+    receiver.map(|_| ())
+}
@@ -0,0 +1,228 @@
+//! ABCI queries and events verified by the light client.
+//!
+//! The result of an ABCI query at a given height is proven against the
+//! application hash of the state at that height, which is only committed to
+//! by the header of the next block. A [`VerifiedClient`] has the light client
+//! verify that header, and accepts the value returned by the full node only if
+//! its proof checks out against the application hash of the header.
+//!
+//! Likewise, the transaction of a `Tx` event is proven to be included in the
+//! block at its height against the data hash of the verified header of that
+//! block, and the block of a `NewBlock` event must match that header.
+
+use futures::{Stream, StreamExt};
+use tendermint::{
+    crypto::{default::Sha256, Sha256 as _},
+    hash::Algorithm,
+    merkle::{self, proof::VALUE_OP_TYPE},
+    Block, Hash,
+};
+use tendermint_rpc::{
+    event::{Event, EventData, TxInfo},
+    Client,
+};
+
+use crate::{
+    components::io::IoError, errors::Error, supervisor::AsyncHandle, verifier::types::Height,
+};
+
+/// The prefix of the paths of the ABCI queries for a key in a store of a
+/// Cosmos SDK application, i.e. `/store/<store>/key`.
+const STORE_PATH_PREFIX: &str = "/store/";
+
+/// A client performing ABCI queries through an RPC client, and verifying
+/// their results with a light client.
+pub struct VerifiedClient<C, H> {
+    rpc_client: C,
+    light_client: H,
+}
+
+impl<C, H> VerifiedClient<C, H>
+where
+    C: Client + Send + Sync,
+    H: AsyncHandle,
+{
+    /// Combine an RPC client, to query a full node, with the handle of a
+    /// light client following the same chain.
+    pub fn new(rpc_client: C, light_client: H) -> Self {
+        Self {
+            rpc_client,
+            light_client,
+        }
+    }
+
+    /// The RPC client.
+    pub fn rpc_client(&self) -> &C {
+        &self.rpc_client
+    }
+
+    /// The handle of the light client.
+    pub fn light_client(&self) -> &H {
+        &self.light_client
+    }
+
+    /// Query the value stored at `key` at the given path, in the state of
+    /// the application at the given height, and verify it.
+    ///
+    /// The query is performed with `prove=true`, the header of the block at
+    /// the next height is verified by the light client, and the value is
+    /// only returned if the returned proof shows it is stored at `key` in the
+    /// tree whose root is the application hash of that header. For a query
+    /// of a key in a store of a Cosmos SDK application, at the path
+    /// `/store/<store>/key`, the proof must also show that the store is the
+    /// one with that name.
+    ///
+    /// Only the proofs made of operations of type [`VALUE_OP_TYPE`] are
+    /// supported, which cannot prove the absence of a value. A proof with an
+    /// operation of any other type, such as the `ics23:iavl` and
+    /// `ics23:simple` ones of the Cosmos SDK, is rejected with
+    /// [`ErrorDetail::UnsupportedAbciProof`](crate::errors::ErrorDetail::UnsupportedAbciProof)
+    /// before any light block is verified.
+    pub async fn query_verified(
+        &self,
+        path: &str,
+        key: impl Into<Vec<u8>>,
+        height: Height,
+    ) -> Result<Vec<u8>, Error> {
+        let key = key.into();
+        let response = self
+            .rpc_client
+            .abci_query(Some(path.to_string()), key.clone(), Some(height), true)
+            .await
+            .map_err(|e| Error::io(IoError::rpc(e)))?;
+
+        if response.code.is_err() {
+            return Err(Error::abci_query(response.code.value(), response.log));
+        }
+        let proof = response
+            .proof
+            .ok_or_else(|| Error::missing_abci_proof(height))?;
+        if let Some(op) = proof.ops.iter().find(|op| op.field_type != VALUE_OP_TYPE) {
+            return Err(Error::unsupported_abci_proof(height, op.field_type.clone()));
+        }
+
+        let header = self
+            .light_client
+            .verify_to_target(height.increment())
+            .await?
+            .signed_header
+            .header;
+        let app_hash = Hash::from_bytes(Algorithm::Sha256, header.app_hash.as_bytes())
+            .map_err(|e| Error::invalid_abci_proof(height, e))?;
+
+        let keypath = match store_name(path) {
+            Some(store) => vec![store.as_bytes(), key.as_slice()],
+            None => vec![key.as_slice()],
+        };
+        proof
+            .verify_value(app_hash, &keypath, &response.value)
+            .map_err(|e| Error::invalid_abci_proof(height, e))?;
+
+        Ok(response.value)
+    }
+
+    /// Verify that the transaction of a `Tx` event was included in the block
+    /// at its height, at its index.
+    ///
+    /// The transaction is looked up by its hash with `prove=true`, the header
+    /// of the block at its height is verified by the light client, and the
+    /// transaction is only accepted if the returned proof shows it is in the
+    /// tree whose root is the data hash of that header.
+    ///
+    /// The result of the transaction is not verified, as it is only committed
+    /// to along with the results of all the other transactions of the block.
+    pub async fn verify_tx(&self, tx: &TxInfo) -> Result<(), Error> {
+        let hash = Hash::Sha256(Sha256::digest(&tx.tx));
+        let response = self
+            .rpc_client
+            .tx(hash, true)
+            .await
+            .map_err(|e| Error::io(IoError::rpc(e)))?;
+
+        let proof = response
+            .proof
+            .ok_or_else(|| Error::missing_tx_proof(tx.height))?;
+        if response.height != tx.height || proof.proof.index != u64::from(tx.index) {
+            return Err(Error::mismatched_tx(tx.height, tx.index));
+        }
+
+        let header = self
+            .light_client
+            .verify_to_target(tx.height)
+            .await?
+            .signed_header
+            .header;
+        proof
+            .verify(header.data_hash.unwrap_or_default(), &tx.tx)
+            .map_err(|e| Error::invalid_tx_proof(tx.height, e))
+    }
+
+    /// Verify that the given block is the one at its height, i.e. that its
+    /// header is the one verified by the light client, and that its
+    /// transactions are those committed to by the data hash of the header.
+    pub async fn verify_block(&self, block: &Block) -> Result<(), Error> {
+        let height = block.header.height;
+        let header = self
+            .light_client
+            .verify_to_target(height)
+            .await?
+            .signed_header
+            .header;
+
+        // The leaves of the data hash tree are the hashes of the transactions.
+        let leaves: Vec<Vec<u8>> = block
+            .data
+            .iter()
+            .map(|tx| Sha256::digest(tx).to_vec())
+            .collect();
+        let data_hash = Hash::Sha256(merkle::simple_hash_from_byte_vectors::<Sha256>(&leaves));
+        let data_matches = match header.data_hash {
+            Some(hash) => hash == data_hash,
+            None => block.data.is_empty(),
+        };
+
+        if block.header.hash() != header.hash() || !data_matches {
+            return Err(Error::mismatched_block(height));
+        }
+        Ok(())
+    }
+
+    /// Verify the transaction of a `Tx` event, or the block of a `NewBlock`
+    /// event, with [`verify_tx`](Self::verify_tx) or
+    /// [`verify_block`](Self::verify_block). The other events are accepted
+    /// as they are.
+    pub async fn verify_event(&self, event: &Event) -> Result<(), Error> {
+        match &event.data {
+            EventData::Tx { tx_result } => self.verify_tx(tx_result).await,
+            EventData::NewBlock {
+                block: Some(block), ..
+            } => self.verify_block(block).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Verify the events of the given stream, e.g. of a subscription, with
+    /// [`verify_event`](Self::verify_event) before yielding them.
+    ///
+    /// An event which fails verification is yielded as an error, and the
+    /// following events are still verified and yielded.
+    pub fn verify_events<'a, S>(
+        &'a self,
+        events: S,
+    ) -> impl Stream<Item = Result<Event, Error>> + 'a
+    where
+        S: Stream<Item = Result<Event, tendermint_rpc::Error>> + 'a,
+    {
+        events.then(move |event| async move {
+            let event = event.map_err(|e| Error::io(IoError::rpc(e)))?;
+            self.verify_event(&event).await?;
+            Ok(event)
+        })
+    }
+}
+
+/// The name of the store queried at the given path, if it is the path of a
+/// query for a key in a store of a Cosmos SDK application.
+fn store_name(path: &str) -> Option<&str> {
+    path.strip_prefix(STORE_PATH_PREFIX)?.strip_suffix("/key")
+}
@@ -1,4 +1,4 @@
-#![cfg(feature = "unstable")]
+#![cfg(feature = "rust-crypto")]
 
 use std::{collections::HashMap, time::Duration};
 
@@ -9,7 +9,7 @@ use tendermint_light_client::{
         io::{AtHeight, Io},
         scheduler,
     },
-    errors::Error,
+    errors::{Error, ErrorDetail},
     light_client::LightClient,
     state::State,
     store::{memory::MemoryStore, LightStore},
@@ -54,6 +54,7 @@ fn make(chain: LightChain, trusted_height: Height) -> (LightClient, State) {
         trust_threshold: Default::default(),
         trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
         clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
     };
 
     let light_blocks = chain
@@ -92,8 +93,18 @@ fn make(chain: LightChain, trusted_height: Height) -> (LightClient, State) {
 }
 
 fn verify(tc: TestCase) -> Result<LightBlock, Error> {
-    let (light_client, mut state) = make(tc.chain, tc.trusted_height);
-    light_client.verify_to_target(tc.target_height, &mut state)
+    let (light_client, mut state) = make(tc.chain.clone(), tc.trusted_height);
+    let result = light_client.verify_backwards(tc.target_height, &mut state);
+
+    // With the `unstable` feature, `verify_to_target` verifies backwards too.
+    #[cfg(feature = "unstable")]
+    {
+        let (light_client, mut state) = make(tc.chain, tc.trusted_height);
+        let to_target = light_client.verify_to_target(tc.target_height, &mut state);
+        assert_eq!(to_target.is_ok(), result.is_ok());
+    }
+
+    result
 }
 
 fn ok_test(tc: TestCase) -> Result<(), TestCaseError> {
@@ -202,6 +213,43 @@ fn tc_corrupted_hash(max: u32) -> impl Strategy<Value = TestCase> {
         .prop_perturb(corrupt_hash)
 }
 
+#[test]
+fn verified_blocks_are_trusted_in_the_store() {
+    let (light_client, mut state) = make(LightChain::default_with_length(10), 8_u32.into());
+
+    let block = light_client
+        .verify_backwards(5_u32.into(), &mut state)
+        .unwrap();
+    assert_eq!(block.height(), 5_u32.into());
+
+    for height in 5_u32..8 {
+        let height = height.into();
+        assert!(state.light_store.get(height, Status::Trusted).is_some());
+        assert!(state.light_store.get(height, Status::Unverified).is_none());
+    }
+
+    // Resumes from the closest trusted block.
+    let block = light_client
+        .verify_backwards(3_u32.into(), &mut state)
+        .unwrap();
+    assert_eq!(block.height(), 3_u32.into());
+    assert!(state
+        .light_store
+        .get(4_u32.into(), Status::Trusted)
+        .is_some());
+}
+
+#[test]
+fn target_above_trusted_state() {
+    let (light_client, mut state) = make(LightChain::default_with_length(10), 5_u32.into());
+
+    let result = light_client.verify_backwards(8_u32.into(), &mut state);
+    assert!(matches!(
+        result.unwrap_err().detail(),
+        ErrorDetail::NoTrustedStateAbove(_)
+    ));
+}
+
 proptest! {
     #![proptest_config(ProptestConfig {
         cases: 20,
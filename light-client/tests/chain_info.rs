@@ -0,0 +1,122 @@
+use std::{collections::HashMap, time::Duration};
+
+use tendermint_light_client::{
+    components::{
+        io::{AtHeight, Io},
+        scheduler,
+    },
+    errors::ErrorDetail,
+    light_client::{ChainInfo, LightClient},
+    state::State,
+    store::{memory::MemoryStore, LightStore},
+    tests::{MockClock, MockIo},
+    verifier::{
+        options::Options,
+        types::{Height, LightBlock, Status},
+        ProdVerifier,
+    },
+};
+use tendermint_testgen::{
+    helpers::get_time,
+    light_block::{default_peer_id, TmLightBlock as TGLightBlock},
+    Generator, LightChain,
+};
+
+const CHAIN_LENGTH: u64 = 5;
+
+fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
+
+fn make(chain_info: ChainInfo) -> (LightClient, State) {
+    let light_blocks = LightChain::default_with_length(CHAIN_LENGTH)
+        .light_blocks
+        .into_iter()
+        .map(|lb| lb.generate().unwrap())
+        .map(testgen_to_lb)
+        .collect();
+    let io = MockIo::new(light_blocks);
+
+    let trusted_state = io
+        .fetch_light_block(AtHeight::At(Height::from(3_u32)))
+        .unwrap();
+    let mut light_store = MemoryStore::new();
+    light_store.insert(trusted_state, Status::Trusted);
+    let state = State {
+        light_store: Box::new(light_store),
+        verification_trace: HashMap::new(),
+    };
+
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+    let mut light_client = LightClient::new(
+        default_peer_id(),
+        options,
+        MockClock {
+            now: get_time(CHAIN_LENGTH + 1).unwrap(),
+        },
+        scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        io,
+    );
+    light_client.chain_info = Some(chain_info);
+
+    (light_client, state)
+}
+
+#[test]
+fn verifies_within_chain() {
+    let (light_client, mut state) = make(ChainInfo {
+        initial_height: Height::from(3_u32),
+        genesis_time: get_time(0).unwrap(),
+    });
+
+    let light_block = light_client
+        .verify_to_target(Height::from(CHAIN_LENGTH as u32), &mut state)
+        .unwrap();
+    assert_eq!(light_block.height().value(), CHAIN_LENGTH);
+}
+
+#[test]
+fn rejects_height_below_initial() {
+    let (light_client, mut state) = make(ChainInfo {
+        initial_height: Height::from(3_u32),
+        genesis_time: get_time(0).unwrap(),
+    });
+
+    let err = light_client
+        .verify_to_target(Height::from(2_u32), &mut state)
+        .unwrap_err();
+    match err.detail() {
+        ErrorDetail::HeightBelowInitial(e) => {
+            assert_eq!(e.target_height, Height::from(2_u32));
+            assert_eq!(e.initial_height, Height::from(3_u32));
+        },
+        e => panic!("unexpected error: {e:?}"),
+    }
+}
+
+#[test]
+fn rejects_block_before_genesis() {
+    let (light_client, mut state) = make(ChainInfo {
+        initial_height: Height::from(1_u32),
+        genesis_time: get_time(CHAIN_LENGTH).unwrap(),
+    });
+
+    let err = light_client
+        .verify_to_target(Height::from(4_u32), &mut state)
+        .unwrap_err();
+    assert!(matches!(
+        err.detail(),
+        ErrorDetail::TimeBeforeGenesis(e) if e.height == Height::from(4_u32)
+    ));
+}
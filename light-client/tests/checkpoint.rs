@@ -0,0 +1,139 @@
+#![cfg(feature = "rust-crypto")]
+
+use std::{collections::HashMap, time::Duration};
+
+use tendermint_light_client::{
+    checkpoint::Checkpoints,
+    components::{
+        io::{AtHeight, Io},
+        scheduler,
+    },
+    errors::ErrorDetail,
+    light_client::LightClient,
+    state::State,
+    store::{memory::MemoryStore, LightStore, Provenance},
+    tests::{MockClock, MockIo},
+    verifier::{
+        options::Options,
+        types::{Height, LightBlock, Status},
+        ProdVerifier,
+    },
+};
+use tendermint_testgen::{
+    helpers::get_time,
+    light_block::{default_peer_id, TmLightBlock as TGLightBlock},
+    Generator, LightChain,
+};
+
+const CHAIN_LENGTH: u64 = 20;
+
+fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
+
+fn height(height: u32) -> Height {
+    Height::from(height)
+}
+
+fn make(pins: impl FnOnce(&MockIo) -> Checkpoints) -> (LightClient, State) {
+    let light_blocks = LightChain::default_with_length(CHAIN_LENGTH)
+        .light_blocks
+        .into_iter()
+        .map(|lb| lb.generate().unwrap())
+        .map(testgen_to_lb)
+        .collect();
+    let io = MockIo::new(light_blocks);
+    let checkpoints = pins(&io);
+
+    let trusted_state = io.fetch_light_block(AtHeight::At(height(1))).unwrap();
+    let mut light_store = MemoryStore::new();
+    light_store.insert(trusted_state, Status::Trusted);
+    let state = State {
+        light_store: Box::new(light_store),
+        verification_trace: HashMap::new(),
+    };
+
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+    let mut light_client = LightClient::new(
+        default_peer_id(),
+        options,
+        MockClock {
+            now: get_time(CHAIN_LENGTH + 1).unwrap(),
+        },
+        scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        io,
+    );
+    light_client.checkpoints = checkpoints;
+
+    (light_client, state)
+}
+
+/// Pin the hash of the header at height `at` to the one at height `of`.
+fn pin(io: &MockIo, at: u32, of: u32) -> Checkpoints {
+    let block = io.fetch_light_block(AtHeight::At(height(of))).unwrap();
+    [(height(at), block.signed_header.header.hash())]
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn skips_the_blocks_below_the_checkpoint() {
+    let (light_client, mut state) = make(|io| pin(io, 15, 15));
+
+    let light_block = light_client
+        .verify_to_target(height(18), &mut state)
+        .unwrap();
+    assert_eq!(light_block.height(), height(18));
+
+    let store = &state.light_store;
+    assert!(store.get(height(15), Status::Trusted).is_some());
+    assert_eq!(store.provenance(height(15)), Some(Provenance::Checkpoint));
+    assert_eq!(store.provenance(height(18)), None);
+    for h in 2..15 {
+        assert!(store.get_non_failed(height(h)).is_none());
+    }
+}
+
+#[test]
+fn ignores_the_checkpoints_above_the_target() {
+    let (light_client, mut state) = make(|io| pin(io, 15, 15));
+
+    let light_block = light_client.fast_forward(height(10), &mut state).unwrap();
+    assert!(light_block.is_none());
+    assert_eq!(state.light_store.provenance(height(15)), None);
+}
+
+#[test]
+fn rejects_a_mismatching_block() {
+    let (light_client, mut state) = make(|io| pin(io, 15, 14));
+
+    let err = light_client
+        .verify_to_target(height(18), &mut state)
+        .unwrap_err();
+    match err.detail() {
+        ErrorDetail::CheckpointMismatch(e) => assert_eq!(e.height, height(15)),
+        e => panic!("unexpected error: {e:?}"),
+    }
+    assert!(state.light_store.get(height(15), Status::Failed).is_some());
+}
+
+#[test]
+fn checkpoints_roundtrip_through_json() {
+    let (light_client, _) = make(|io| pin(io, 15, 15));
+
+    let json = serde_json::to_string(&light_client.checkpoints).unwrap();
+    let checkpoints: Checkpoints = serde_json::from_str(&json).unwrap();
+    assert_eq!(checkpoints, light_client.checkpoints);
+    assert_eq!(checkpoints.len(), 1);
+}
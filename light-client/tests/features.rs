@@ -0,0 +1,71 @@
+//! Smoke tests for the optional features of this crate.
+//!
+//! Every test is gated on the features it exercises, so that this file builds
+//! and passes under every feature combination checked by the workspace
+//! feature matrix (see `tendermint-test`).
+
+use tendermint_light_client::{
+    store::{memory::MemoryStore, LightStore},
+    verifier::types::{LightBlock, Status},
+};
+use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
+
+fn light_block(height: u64) -> LightBlock {
+    let block = TestgenLightBlock::new_default(height).generate().unwrap();
+    LightBlock::new(
+        block.signed_header,
+        block.validators,
+        block.next_validators,
+        block.provider,
+    )
+}
+
+fn check_store(store: &mut dyn LightStore) {
+    let block = light_block(1);
+    store.insert(block.clone(), Status::Verified);
+
+    assert_eq!(
+        store.get(block.height(), Status::Verified),
+        Some(block.clone())
+    );
+    assert_eq!(store.get(block.height(), Status::Trusted), None);
+    assert_eq!(store.highest(Status::Verified), Some(block));
+}
+
+#[test]
+fn memory_store() {
+    check_store(&mut MemoryStore::new());
+}
+
+#[cfg(feature = "lightstore-sled")]
+#[test]
+fn sled_store() {
+    use tendermint_light_client::store::sled::SledStore;
+
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    check_store(&mut SledStore::new(db).unwrap());
+}
+
+#[cfg(feature = "rust-crypto")]
+#[test]
+fn prod_verifier_accepts_valid_block() {
+    use tendermint_light_client::verifier::{ProdVerifier, Verdict};
+
+    let block = light_block(1);
+    let verdict = ProdVerifier::default().verify_validator_sets(&block.as_untrusted_state());
+    assert_eq!(verdict, Verdict::Success);
+}
+
+#[cfg(feature = "rpc-client")]
+#[test]
+fn rpc_io_can_be_constructed() {
+    use core::str::FromStr;
+
+    use tendermint_light_client::components::io::ProdIo;
+    use tendermint_rpc::HttpClient;
+
+    let peer_id =
+        tendermint::node::Id::from_str("BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE").unwrap();
+    let rpc_client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+    let _io = ProdIo::new(peer_id, rpc_client, None);
+}
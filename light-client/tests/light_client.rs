@@ -1,3 +1,5 @@
+#![cfg(feature = "rust-crypto")]
+
 use std::{collections::HashMap, time::Duration};
 
 use tendermint_light_client::{
@@ -44,6 +46,7 @@ fn run_test(tc: LightClientTest<LightBlock>) -> BisectionTestResult {
         trust_threshold,
         trusting_period: trusting_period.into(),
         clock_drift,
+        time_monotonicity: Default::default(),
     };
 
     let provider = tc.primary;
@@ -255,7 +255,8 @@ mod mbt {
                 Validator::new("2"),
                 Validator::new("3"),
             ];
-            let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap());
+            let valset =
+                ValidatorSet::without_proposer(generate_validators(&vals).unwrap()).unwrap();
 
             input.block.validators = valset;
             (String::from("header validators_hash"), LiteVerdict::Invalid)
@@ -271,7 +272,8 @@ mod mbt {
                 Validator::new("2"),
                 Validator::new("3"),
             ];
-            let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap());
+            let valset =
+                ValidatorSet::without_proposer(generate_validators(&vals).unwrap()).unwrap();
 
             input.block.next_validators = valset;
             (
@@ -554,11 +556,12 @@ mod mbt {
                 // Below is a temporary work around to get rid of bug-gy validator sorting
                 // which was making all the tests fail
                 let current_vals = input.block.validators.clone();
-                let current_resorted = Set::without_proposer(current_vals.validators().to_vec());
+                let current_resorted =
+                    Set::without_proposer(current_vals.validators().to_vec()).unwrap();
 
                 let current_next_vals = input.block.next_validators.clone();
                 let current_next_resorted =
-                    Set::without_proposer(current_next_vals.validators().to_vec());
+                    Set::without_proposer(current_next_vals.validators().to_vec()).unwrap();
 
                 let mut mutated_block = input.block.clone();
                 mutated_block.validators = current_resorted;
@@ -592,8 +595,8 @@ mod mbt {
                             Verdict::NotEnoughTrust(_) => {
                                 assert_eq!(input.verdict, LiteVerdict::NotEnoughTrust)
                             },
-                            Verdict::Success => {
-                                panic!("verify_single() returned error with Verdict::Success")
+                            Verdict::Success | Verdict::SuccessWithTimeRegression(_) => {
+                                panic!("verify_single() returned error with {e:?}")
                             },
                         }
                     },
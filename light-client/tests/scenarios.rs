@@ -0,0 +1,25 @@
+//! Runs the declarative scenarios of `tests/support/scenarios` against the
+//! light client, the fork detector and the supervisor.
+//!
+//! See the `tendermint-light-client-scenarios` crate for the format.
+
+#![cfg(feature = "rust-crypto")]
+
+use tendermint_light_client_scenarios::{run_dir, Harness};
+
+const SCENARIOS_PATH: &str = "./tests/support/scenarios";
+
+#[test]
+fn light_client() {
+    run_dir(SCENARIOS_PATH, Harness::LightClient);
+}
+
+#[test]
+fn fork_detector() {
+    run_dir(SCENARIOS_PATH, Harness::ForkDetector);
+}
+
+#[test]
+fn supervisor() {
+    run_dir(SCENARIOS_PATH, Harness::Supervisor);
+}
@@ -0,0 +1,84 @@
+//! Compile-time checks of the `Send` and `Sync` guarantees of the IO
+//! components, of the light client, and of the supervisor and its handles.
+//!
+//! The futures of [`AsyncIo`], [`LightClient`], [`Supervisor`] and
+//! [`AsyncHandle`] are meant to be spawned onto multi-threaded runtimes, while [`LocalAsyncIo`] accepts `!Send`
+//! implementations, as found on `wasm32` targets. The futures below are only
+//! created, never polled: these tests fail to build rather than to run.
+
+#![cfg(feature = "rpc-client")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use async_trait::async_trait;
+use tendermint_light_client::{
+    components::io::{AsyncIo, AtHeight, IoError, LocalAsyncIo, ProdIo},
+    light_client::LightClient,
+    state::State,
+    supervisor::{AsyncHandle, Supervisor, SupervisorHandle},
+    verifier::types::{Height, LightBlock},
+};
+
+fn assert_send<T: Send>(_: T) {}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn assert_local_io<T: LocalAsyncIo>() {}
+
+/// An IO component holding `!Send` state, such as the handles of a browser.
+struct LocalIo {
+    blocks: Rc<RefCell<Vec<LightBlock>>>,
+}
+
+#[async_trait(?Send)]
+impl LocalAsyncIo for LocalIo {
+    async fn fetch_light_block(&self, _height: AtHeight) -> Result<LightBlock, IoError> {
+        let blocks = self.blocks.borrow();
+        Ok(blocks.last().cloned().expect("no light block"))
+    }
+}
+
+fn io_futures<I: AsyncIo>(io: &I) {
+    assert_send(AsyncIo::fetch_light_block(io, AtHeight::Highest));
+}
+
+fn light_client_futures(light_client: &LightClient, state: &mut State) {
+    assert_send(light_client.verify_to_target_async(Height::from(1_u32), state));
+}
+
+fn supervisor_futures(mut supervisor: Supervisor) {
+    assert_send(supervisor.verify_to_highest_async());
+    assert_send(supervisor.run_async());
+}
+
+fn handle_futures<H: AsyncHandle>(handle: &H) {
+    assert_send(AsyncHandle::latest_trusted(handle));
+    assert_send(AsyncHandle::latest_status(handle));
+    assert_send(AsyncHandle::verify_to_highest(handle));
+    assert_send(AsyncHandle::evidence(handle));
+    assert_send(AsyncHandle::terminate(handle));
+}
+
+#[test]
+fn components_are_send_and_sync() {
+    assert_send_sync::<ProdIo>();
+    assert_send_sync::<SupervisorHandle>();
+}
+
+#[test]
+fn futures_are_send() {
+    let _ = io_futures::<ProdIo>;
+    let _ = light_client_futures;
+    let _ = supervisor_futures;
+    let _ = handle_futures::<SupervisorHandle>;
+}
+
+#[test]
+fn local_io_accepts_non_send_implementations() {
+    let io = LocalIo {
+        blocks: Rc::default(),
+    };
+    let _ = LocalAsyncIo::fetch_light_block(&io, AtHeight::Highest);
+    // Every `AsyncIo` implementation is a `LocalAsyncIo` as well.
+    assert_local_io::<ProdIo>();
+}
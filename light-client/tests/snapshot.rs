@@ -0,0 +1,147 @@
+#![cfg(feature = "rust-crypto")]
+
+use std::time::Duration;
+
+use tendermint::crypto::default::Sha256;
+use tendermint_light_client::{
+    builder::{
+        error::{Error, ErrorDetail},
+        LightClientBuilder,
+    },
+    components::scheduler,
+    snapshot::TrustedStateSnapshot,
+    store::{memory::MemoryStore, LightStore},
+    supervisor::Instance,
+    tests::{MockClock, MockIo},
+    verifier::{
+        options::Options,
+        predicates::ProdPredicates,
+        types::{Height, LightBlock, Status},
+        ProdVerifier,
+    },
+};
+use tendermint_testgen::{
+    helpers::get_time,
+    light_block::{default_peer_id, TmLightBlock as TGLightBlock},
+    Generator, LightChain,
+};
+
+const CHAIN_LENGTH: u64 = 10;
+
+fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
+
+fn height(height: u32) -> Height {
+    Height::from(height)
+}
+
+fn light_blocks() -> Vec<LightBlock> {
+    LightChain::default_with_length(CHAIN_LENGTH)
+        .light_blocks
+        .into_iter()
+        .map(|lb| lb.generate().unwrap())
+        .map(testgen_to_lb)
+        .collect()
+}
+
+/// A store trusting the light blocks at the given heights.
+fn store_with(light_blocks: &[LightBlock], heights: &[u32]) -> MemoryStore {
+    let mut light_store = MemoryStore::new();
+    for &h in heights {
+        light_store.insert(light_blocks[h as usize - 1].clone(), Status::Trusted);
+    }
+    light_store
+}
+
+fn restore(
+    light_blocks: Vec<LightBlock>,
+    snapshot: TrustedStateSnapshot,
+    trusted_hash: tendermint::Hash,
+) -> Result<Instance, Error> {
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+
+    let builder = LightClientBuilder::<_, Sha256>::custom(
+        default_peer_id(),
+        options,
+        Box::new(MemoryStore::new()),
+        Box::new(MockIo::new(light_blocks)),
+        Box::new(MockClock {
+            now: get_time(CHAIN_LENGTH + 1).unwrap(),
+        }),
+        Box::<ProdVerifier>::default(),
+        Box::new(scheduler::basic_bisecting_schedule),
+        Box::new(ProdPredicates),
+    );
+
+    Ok(builder.trust_snapshot(snapshot, trusted_hash)?.build())
+}
+
+#[test]
+fn restores_the_trusted_state_and_its_ancestry() {
+    let light_blocks = light_blocks();
+    let light_store = store_with(&light_blocks, &[1, 3, 4, 5]);
+
+    let snapshot = TrustedStateSnapshot::from_store(&light_store, 3).unwrap();
+    assert_eq!(snapshot.height(), height(5));
+    let ancestry: Vec<_> = snapshot.ancestry.iter().map(LightBlock::height).collect();
+    assert_eq!(ancestry, vec![height(4), height(3)]);
+
+    let bytes = snapshot.to_bytes().unwrap();
+    let decoded = TrustedStateSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, snapshot);
+
+    let trusted_hash = light_blocks[4].signed_header.header.hash();
+    let instance = restore(light_blocks, decoded, trusted_hash).unwrap();
+
+    assert_eq!(instance.latest_trusted().unwrap().height(), height(5));
+    for h in [3, 4] {
+        assert!(instance
+            .state
+            .light_store
+            .get(height(h), Status::Trusted)
+            .is_some());
+    }
+    assert_eq!(instance.snapshot(3), Some(snapshot));
+}
+
+#[test]
+fn rejects_a_mismatching_hash() {
+    let light_blocks = light_blocks();
+    let light_store = store_with(&light_blocks, &[4, 5]);
+    let snapshot = TrustedStateSnapshot::from_store(&light_store, 1).unwrap();
+
+    let trusted_hash = light_blocks[3].signed_header.header.hash();
+    let result = restore(light_blocks, snapshot, trusted_hash);
+
+    match result.map(|_| ()) {
+        Err(Error(ErrorDetail::HashMismatch(_), _)) => {},
+        result => panic!("expected a hash mismatch, got {result:?}"),
+    }
+}
+
+#[test]
+fn rejects_a_broken_ancestry() {
+    let light_blocks = light_blocks();
+    let light_store = store_with(&light_blocks, &[3, 4, 5]);
+    let mut snapshot = TrustedStateSnapshot::from_store(&light_store, 2).unwrap();
+    snapshot.ancestry.swap(0, 1);
+
+    let trusted_hash = light_blocks[4].signed_header.header.hash();
+    let result = restore(light_blocks, snapshot, trusted_hash);
+
+    match result.map(|_| ()) {
+        Err(Error(ErrorDetail::BrokenAncestry(e), _)) => assert_eq!(e.height, height(3)),
+        result => panic!("expected a broken ancestry, got {result:?}"),
+    }
+}
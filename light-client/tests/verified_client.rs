@@ -0,0 +1,429 @@
+#![cfg(feature = "rpc-client")]
+
+use std::time::Duration;
+
+use tendermint::{
+    crypto::default::Sha256,
+    merkle::{
+        self,
+        proof::{Proof, ProofOps, ValueOp},
+        MerkleHash,
+    },
+    tx, Block, Hash,
+};
+use tendermint_light_client::{
+    builder::{LightClientBuilder, SupervisorBuilder},
+    components::scheduler,
+    errors::{Error, ErrorDetail},
+    fork_detector::ProdForkDetector,
+    store::memory::MemoryStore,
+    supervisor::{Handle, Instance, Supervisor, SupervisorHandle},
+    tests::{MockClock, MockEvidenceReporter, MockIo},
+    verified_client::VerifiedClient,
+    verifier::{
+        options::Options,
+        predicates::ProdPredicates,
+        types::{Height, LightBlock, PeerId, Time},
+        ProdVerifier,
+    },
+};
+use tendermint_rpc::{
+    endpoint::abci_query::{self, AbciQuery},
+    event::{Event, EventData, TxInfo},
+    Method, MockClient, MockRequestMethodMatcher,
+};
+use tendermint_testgen::{
+    helpers::get_time,
+    light_block::{default_peer_id, TmLightBlock},
+    Commit, Generator, Header, LightChain,
+};
+
+const PATH: &str = "/store/bank/key";
+const KEY: &[u8] = b"balance";
+const VALUE: &[u8] = b"42";
+
+/// A proof of the value at the key in the store, and the application hash it
+/// is proven against.
+fn store_proof() -> (ProofOps, Hash) {
+    let (key_op, store_root) = single_leaf(KEY, VALUE);
+    let (store_op, app_hash) = single_leaf(b"bank", store_root.as_bytes());
+    let proof = ProofOps {
+        ops: vec![key_op.into(), store_op.into()],
+    };
+    (proof, app_hash)
+}
+
+/// The operation proving the value at the key of a tree with a single leaf,
+/// along with the root of that tree.
+fn single_leaf(key: &[u8], value: &[u8]) -> (ValueOp, Hash) {
+    let mut leaf = vec![key.len() as u8];
+    leaf.extend_from_slice(key);
+    leaf.push(32);
+    leaf.extend_from_slice(&<Sha256 as tendermint::crypto::Sha256>::digest(value));
+    let root = Hash::Sha256(Sha256::default().leaf_hash(&leaf));
+
+    let op = ValueOp {
+        key: key.to_vec(),
+        proof: Proof {
+            total: 1,
+            index: 0,
+            leaf_hash: root,
+            aunts: vec![],
+        },
+    };
+    (op, root)
+}
+
+/// A chain of two blocks, the header of the second one being customized with
+/// the given function.
+fn light_blocks(customize: impl FnOnce(Header) -> Header) -> Vec<LightBlock> {
+    let mut chain = LightChain::default_with_length(2);
+    let last = chain.light_blocks.last_mut().unwrap();
+    let header = customize(last.header.clone().unwrap());
+    last.header = Some(header.clone());
+    last.commit = Some(Commit::new(header, 1));
+
+    chain
+        .light_blocks
+        .into_iter()
+        .map(|lb| into_light_block(lb.generate().unwrap()))
+        .collect()
+}
+
+fn into_light_block(tm_lb: TmLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
+
+fn make_instance(peer_id: PeerId, light_blocks: Vec<LightBlock>) -> Instance {
+    let options = Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 14),
+        clock_drift: Duration::from_secs(10),
+        time_monotonicity: Default::default(),
+    };
+    let now: Time = get_time(3).unwrap();
+    let trusted = &light_blocks[0].signed_header.header;
+    let (trusted_height, trusted_hash) = (trusted.height, trusted.hash());
+
+    LightClientBuilder::custom(
+        peer_id,
+        options,
+        Box::new(MemoryStore::new()),
+        Box::new(MockIo::new(light_blocks)),
+        Box::new(MockClock { now }),
+        Box::<ProdVerifier>::default(),
+        Box::new(scheduler::basic_bisecting_schedule),
+        Box::new(ProdPredicates),
+    )
+    .trust_primary_at(trusted_height, trusted_hash)
+    .unwrap()
+    .build()
+}
+
+/// A supervisor following the given chain, with a primary and a witness.
+fn start_supervisor(light_blocks: Vec<LightBlock>) -> SupervisorHandle {
+    let primary_id = default_peer_id();
+    let witness_id = PeerId::new([0xAA; 20]);
+    let witness_blocks = light_blocks
+        .iter()
+        .cloned()
+        .map(|lb| LightBlock {
+            provider: witness_id,
+            ..lb
+        })
+        .collect();
+
+    let address: tendermint_rpc::Url = "http://127.0.0.1:26657".parse().unwrap();
+    let (instances, _addresses) = SupervisorBuilder::new()
+        .primary(
+            primary_id,
+            address.clone(),
+            make_instance(primary_id, light_blocks),
+        )
+        .witness(
+            witness_id,
+            address,
+            make_instance(witness_id, witness_blocks),
+        )
+        .inner();
+
+    let supervisor = Supervisor::new(
+        instances,
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+    );
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+    handle
+}
+
+/// A client whose full node answers the query with the given value and
+/// proof, at height 1.
+fn verified_client(
+    value: &[u8],
+    proof: Option<ProofOps>,
+    app_hash: Hash,
+) -> VerifiedClient<MockClient<MockRequestMethodMatcher>, SupervisorHandle> {
+    let response = abci_query::Response {
+        response: AbciQuery {
+            key: KEY.to_vec(),
+            value: value.to_vec(),
+            proof,
+            height: Height::from(1_u32),
+            ..Default::default()
+        },
+    };
+    let json = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "",
+        "result": response,
+    });
+    let matcher = MockRequestMethodMatcher::default().map(Method::AbciQuery, Ok(json.to_string()));
+    let (rpc_client, _driver) = MockClient::new(matcher);
+
+    VerifiedClient::new(
+        rpc_client,
+        start_supervisor(light_blocks(|header| header.app_hash(app_hash))),
+    )
+}
+
+fn query(
+    client: &VerifiedClient<MockClient<MockRequestMethodMatcher>, SupervisorHandle>,
+) -> Result<Vec<u8>, Error> {
+    let result = futures::executor::block_on(client.query_verified(PATH, KEY, Height::from(1_u32)));
+    Handle::terminate(client.light_client()).unwrap();
+    result
+}
+
+#[test]
+fn returns_the_proven_value() {
+    let (proof, app_hash) = store_proof();
+    let client = verified_client(VALUE, Some(proof), app_hash);
+
+    assert_eq!(query(&client).unwrap(), VALUE);
+}
+
+#[test]
+fn rejects_a_value_not_matching_the_proof() {
+    let (proof, app_hash) = store_proof();
+    let client = verified_client(b"1000000", Some(proof), app_hash);
+
+    match query(&client) {
+        Err(Error(ErrorDetail::InvalidAbciProof(e), _)) => {
+            assert_eq!(e.height, Height::from(1_u32))
+        },
+        result => panic!("expected an invalid proof, got {result:?}"),
+    }
+}
+
+#[test]
+fn rejects_a_value_without_proof() {
+    let (_, app_hash) = store_proof();
+    let client = verified_client(VALUE, None, app_hash);
+
+    match query(&client) {
+        Err(Error(ErrorDetail::MissingAbciProof(_), _)) => {},
+        result => panic!("expected a missing proof, got {result:?}"),
+    }
+}
+
+#[test]
+fn rejects_unsupported_proof_types() {
+    let (mut proof, app_hash) = store_proof();
+    proof.ops[0].field_type = "ics23:iavl".to_string();
+    let client = verified_client(VALUE, Some(proof), app_hash);
+
+    match query(&client) {
+        Err(Error(ErrorDetail::UnsupportedAbciProof(e), _)) => {
+            assert_eq!(e.proof_type, "ics23:iavl")
+        },
+        result => panic!("expected an unsupported proof, got {result:?}"),
+    }
+}
+
+const TXS: [&[u8]; 3] = [b"a=1", b"b=2", b"c=3"];
+
+fn tx_hash(tx: &[u8]) -> [u8; 32] {
+    <Sha256 as tendermint::crypto::Sha256>::digest(tx)
+}
+
+/// The data hash of the block of the transactions.
+fn data_hash() -> Hash {
+    let leaves: Vec<Vec<u8>> = TXS.iter().map(|tx| tx_hash(tx).to_vec()).collect();
+    Hash::Sha256(merkle::simple_hash_from_byte_vectors::<Sha256>(&leaves))
+}
+
+/// The proof of the inclusion of the second transaction.
+fn tx_proof() -> tx::Proof {
+    let mut hasher = Sha256::default();
+    let leaves: Vec<_> = TXS
+        .iter()
+        .map(|tx| hasher.leaf_hash(&tx_hash(tx)))
+        .collect();
+    tx::Proof {
+        root_hash: data_hash(),
+        data: TXS[1].to_vec(),
+        proof: merkle::Proof {
+            total: 3,
+            index: 1,
+            leaf_hash: Hash::Sha256(leaves[1]),
+            // The first two leaves are siblings, the third one is promoted.
+            aunts: vec![Hash::Sha256(leaves[0]), Hash::Sha256(leaves[2])],
+        },
+    }
+}
+
+/// A client whose full node reports the given proof of the inclusion of the
+/// second transaction at height 2, on a chain whose second block has the
+/// given data hash.
+fn tx_client(
+    proof: Option<tx::Proof>,
+    data_hash: Hash,
+) -> VerifiedClient<MockClient<MockRequestMethodMatcher>, SupervisorHandle> {
+    let json = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "",
+        "result": {
+            "hash": Hash::Sha256(tx_hash(TXS[1])).to_string(),
+            "height": "2",
+            "index": 1,
+            "tx_result": {},
+            // `b=2`
+            "tx": "Yj0y",
+            "proof": proof,
+        },
+    });
+    let matcher = MockRequestMethodMatcher::default().map(Method::Tx, Ok(json.to_string()));
+    let (rpc_client, _driver) = MockClient::new(matcher);
+
+    VerifiedClient::new(
+        rpc_client,
+        start_supervisor(light_blocks(|header| header.data_hash(data_hash))),
+    )
+}
+
+fn tx_event(tx: &[u8], index: u32) -> Event {
+    Event {
+        query: "tm.event = 'Tx'".to_string(),
+        data: EventData::Tx {
+            tx_result: TxInfo {
+                height: Height::from(2_u32),
+                index,
+                tx: tx.to_vec(),
+                result: Default::default(),
+            },
+        },
+        events: None,
+    }
+}
+
+fn verify(
+    client: &VerifiedClient<MockClient<MockRequestMethodMatcher>, SupervisorHandle>,
+    event: &Event,
+) -> Result<(), Error> {
+    let result = futures::executor::block_on(client.verify_event(event));
+    Handle::terminate(client.light_client()).unwrap();
+    result
+}
+
+#[test]
+fn verifies_included_txs() {
+    let client = tx_client(Some(tx_proof()), data_hash());
+
+    verify(&client, &tx_event(TXS[1], 1)).unwrap();
+}
+
+#[test]
+fn rejects_a_tx_not_matching_the_data_hash() {
+    let client = tx_client(Some(tx_proof()), Hash::Sha256([0xAB; 32]));
+
+    match verify(&client, &tx_event(TXS[1], 1)) {
+        Err(Error(ErrorDetail::InvalidTxProof(e), _)) => {
+            assert_eq!(e.height, Height::from(2_u32))
+        },
+        result => panic!("expected an invalid proof, got {result:?}"),
+    }
+}
+
+#[test]
+fn rejects_a_tx_at_another_index() {
+    let client = tx_client(Some(tx_proof()), data_hash());
+
+    match verify(&client, &tx_event(TXS[1], 0)) {
+        Err(Error(ErrorDetail::MismatchedTx(e), _)) => assert_eq!(e.index, 0),
+        result => panic!("expected a mismatched transaction, got {result:?}"),
+    }
+}
+
+#[test]
+fn rejects_a_tx_without_proof() {
+    let client = tx_client(None, data_hash());
+
+    match verify(&client, &tx_event(TXS[1], 1)) {
+        Err(Error(ErrorDetail::MissingTxProof(_), _)) => {},
+        result => panic!("expected a missing proof, got {result:?}"),
+    }
+}
+
+#[test]
+fn verifies_blocks_against_their_header() {
+    let light_blocks = light_blocks(|header| header.data_hash(data_hash()));
+    let header = light_blocks[1].signed_header.header.clone();
+    let last_commit = light_blocks[0].signed_header.commit.clone();
+    let (rpc_client, _driver) = MockClient::new(MockRequestMethodMatcher::default());
+    let client = VerifiedClient::new(rpc_client, start_supervisor(light_blocks));
+
+    let block = |header, data: &[&[u8]]| EventData::NewBlock {
+        block: Some(
+            Block::new(
+                header,
+                data.iter().map(|tx| tx.to_vec()).collect(),
+                Default::default(),
+                Some(last_commit.clone()),
+            )
+            .unwrap(),
+        ),
+        result_begin_block: None,
+        result_end_block: None,
+    };
+    let event = |data| Event {
+        query: "tm.event = 'NewBlock'".to_string(),
+        data,
+        events: None,
+    };
+
+    let verified = futures::executor::block_on(async {
+        let valid = client
+            .verify_event(&event(block(header.clone(), &TXS)))
+            .await;
+        let missing_tx = client
+            .verify_event(&event(block(header.clone(), &TXS[..2])))
+            .await;
+        let other_header = client
+            .verify_event(&event(block(
+                tendermint::block::Header {
+                    app_hash: "00".parse().unwrap(),
+                    ..header
+                },
+                &TXS,
+            )))
+            .await;
+        (valid, missing_tx, other_header)
+    });
+    Handle::terminate(client.light_client()).unwrap();
+
+    verified.0.unwrap();
+    for result in [verified.1, verified.2] {
+        match result {
+            Err(Error(ErrorDetail::MismatchedBlock(e), _)) => {
+                assert_eq!(e.height, Height::from(2_u32))
+            },
+            result => panic!("expected a mismatched block, got {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Persistent address book of known peers.
+//!
+//! The address book keeps track of the peers a node knows about, where it
+//! heard of them, and how attempts to connect to them went, including which
+//! of them are banned. It is stored in `addrbook.json`, in the same format as
+//! CometBFT's, so that it can be exchanged with existing nodes and tooling.
+//!
+//! As in CometBFT, addresses are either in "new" buckets, until the node
+//! successfully connects to them, or in "old" buckets afterwards. The bucket
+//! of an address is derived from its network group and the key of the book.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs,
+    io::Write as _,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use rand_core::{OsRng, RngCore};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{digest::Digest, Sha256};
+use tendermint::{node, Time};
+
+use crate::error::Error;
+
+/// Number of "new" buckets, as in CometBFT
+pub const NEW_BUCKET_COUNT: u32 = 256;
+
+/// Number of "old" buckets, as in CometBFT
+pub const OLD_BUCKET_COUNT: u32 = 64;
+
+/// Default duration of a ban, as in CometBFT
+pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Size of the random key of an address book, in bytes
+const KEY_SIZE: usize = 12;
+
+/// Network address of a peer: its ID, IP address and port
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NetAddress {
+    /// Node ID of the peer
+    pub id: node::Id,
+    /// IP address of the peer
+    pub ip: IpAddr,
+    /// Port of the peer
+    pub port: u16,
+}
+
+impl NetAddress {
+    /// Network group of this address, which peers in the same network
+    /// (e.g. the same /16 IPv4 subnet) share
+    fn group(&self) -> Vec<u8> {
+        let ip = match self.ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+            ip @ IpAddr::V4(_) => ip,
+        };
+        match ip {
+            IpAddr::V4(ip) if ip.is_loopback() || ip.is_private() || ip.is_unspecified() => {
+                b"local".to_vec()
+            },
+            IpAddr::V6(ip) if ip.is_loopback() || ip.is_unspecified() => b"local".to_vec(),
+            IpAddr::V4(ip) => ip.octets()[..2].to_vec(),
+            IpAddr::V6(ip) => ip.octets()[..4].to_vec(),
+        }
+    }
+}
+
+impl Display for NetAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ip {
+            IpAddr::V4(ip) => write!(f, "{}@{}:{}", self.id, ip, self.port),
+            IpAddr::V6(ip) => write!(f, "{}@[{}]:{}", self.id, ip, self.port),
+        }
+    }
+}
+
+impl FromStr for NetAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::invalid_net_address(s.to_owned());
+        let (id, addr) = s.split_once('@').ok_or_else(invalid)?;
+        let (ip, port) = addr.rsplit_once(':').ok_or_else(invalid)?;
+        let ip = ip.trim_start_matches('[').trim_end_matches(']');
+        Ok(Self {
+            id: id.parse().map_err(|_| invalid())?,
+            ip: ip.parse().map_err(|_| invalid())?,
+            port: port.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Type of the buckets an address is in
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BucketType {
+    /// Addresses the node has not successfully connected to yet
+    New,
+    /// Addresses the node has successfully connected to
+    Old,
+}
+
+impl BucketType {
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::New => 0x01,
+            Self::Old => 0x02,
+        }
+    }
+}
+
+impl Serialize for BucketType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_u8().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BucketType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0x01 => Ok(Self::New),
+            0x02 => Ok(Self::Old),
+            other => Err(de::Error::custom(format!("invalid bucket type {other}"))),
+        }
+    }
+}
+
+/// Address of a peer, along with what the node knows about it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KnownAddress {
+    /// Network address of the peer
+    pub addr: NetAddress,
+    /// Address of the peer the node heard of this address from
+    pub src: NetAddress,
+    /// Buckets this address is in
+    pub buckets: Vec<u32>,
+    /// Number of failed attempts to connect to the peer since the last
+    /// successful one
+    pub attempts: i32,
+    /// Type of the buckets this address is in
+    pub bucket_type: BucketType,
+    /// Time of the last attempt to connect to the peer, or [`Time::MIN`]
+    pub last_attempt: Time,
+    /// Time of the last successful connection to the peer, or [`Time::MIN`]
+    pub last_success: Time,
+    /// Time the ban of the peer ends at, or [`Time::MIN`] if it was never
+    /// banned
+    pub last_ban_time: Time,
+}
+
+impl KnownAddress {
+    /// Whether the peer is banned at the given time
+    #[must_use]
+    pub fn is_banned(&self, now: Time) -> bool {
+        self.last_ban_time > now
+    }
+}
+
+/// Contents of `addrbook.json`
+#[derive(Serialize, Deserialize)]
+struct AddrBookJson {
+    key: String,
+    addrs: Vec<KnownAddress>,
+}
+
+/// Address book of known peers, indexed by their node ID
+#[derive(Clone, Debug)]
+pub struct AddrBook {
+    path: Option<PathBuf>,
+    key: String,
+    addrs: BTreeMap<node::Id, KnownAddress>,
+}
+
+impl Default for AddrBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddrBook {
+    /// Create an empty address book with a new random key
+    #[must_use]
+    pub fn new() -> Self {
+        let mut key = [0_u8; KEY_SIZE];
+        OsRng.fill_bytes(&mut key);
+        Self {
+            path: None,
+            key: key.iter().map(|byte| format!("{byte:02x}")).collect(),
+            addrs: BTreeMap::new(),
+        }
+    }
+
+    /// Load the address book from the given `addrbook.json` file, which it
+    /// is then saved to, or create an empty one if the file does not exist
+    ///
+    /// # Errors
+    ///
+    /// * if the file exists but cannot be read
+    /// * if its contents are not a valid address book
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        if !path.exists() {
+            return Ok(Self {
+                path: Some(path),
+                ..Self::new()
+            });
+        }
+        let json =
+            fs::read_to_string(&path).map_err(|e| Error::file_io(path.display().to_string(), e))?;
+        let mut book = Self::parse_json(&json)?;
+        book.path = Some(path);
+        Ok(book)
+    }
+
+    /// Parse the contents of an `addrbook.json` file
+    ///
+    /// # Errors
+    ///
+    /// * if the JSON is not a valid address book
+    pub fn parse_json(json: &str) -> Result<Self, Error> {
+        let book: AddrBookJson = serde_json::from_str(json).map_err(Error::json)?;
+        Ok(Self {
+            path: None,
+            key: book.key,
+            addrs: book
+                .addrs
+                .into_iter()
+                .map(|known| (known.addr.id, known))
+                .collect(),
+        })
+    }
+
+    /// Serialize this address book as the contents of an `addrbook.json`
+    /// file
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let book = AddrBookJson {
+            key: self.key.clone(),
+            addrs: self.addrs.values().cloned().collect(),
+        };
+        serde_json::to_string_pretty(&book).expect("address books to serialize to JSON")
+    }
+
+    /// Save the address book to the file it was loaded from, if any
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be written
+    pub fn save(&self) -> Result<(), Error> {
+        self.path.as_ref().map_or(Ok(()), |path| self.save_to(path))
+    }
+
+    /// Save the address book to the given file, replacing it atomically
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be written
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let tmp_path = path.with_extension("json.tmp");
+        fs::File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(self.to_json().as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&tmp_path, path))
+            .map_err(|e| Error::file_io(path.display().to_string(), e))
+    }
+
+    /// Key of the address book, which the buckets of addresses are derived
+    /// from
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Number of known addresses, including the banned ones
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.addrs.len()
+    }
+
+    /// Whether no address is known
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.addrs.is_empty()
+    }
+
+    /// Known address of the peer with the given ID
+    #[must_use]
+    pub fn get(&self, id: &node::Id) -> Option<&KnownAddress> {
+        self.addrs.get(id)
+    }
+
+    /// All the known addresses, including the banned ones
+    pub fn addresses(&self) -> impl Iterator<Item = &KnownAddress> {
+        self.addrs.values()
+    }
+
+    /// Add the address of a peer, heard of from the given source, to the
+    /// "new" buckets. Returns whether it was added, i.e. whether the peer was
+    /// not known yet.
+    pub fn add_address(&mut self, addr: NetAddress, src: NetAddress) -> bool {
+        if self.addrs.contains_key(&addr.id) {
+            return false;
+        }
+        let bucket = self.bucket(&[&addr.group(), &src.group()], NEW_BUCKET_COUNT);
+        self.addrs.insert(
+            addr.id,
+            KnownAddress {
+                addr,
+                src,
+                buckets: vec![bucket],
+                attempts: 0,
+                bucket_type: BucketType::New,
+                last_attempt: Time::MIN,
+                last_success: Time::MIN,
+                last_ban_time: Time::MIN,
+            },
+        );
+        true
+    }
+
+    /// Remove the address of the peer with the given ID
+    pub fn remove_address(&mut self, id: &node::Id) -> Option<KnownAddress> {
+        self.addrs.remove(id)
+    }
+
+    /// Record an attempt to connect to the peer with the given ID
+    pub fn mark_attempt(&mut self, id: &node::Id, now: Time) {
+        if let Some(known) = self.addrs.get_mut(id) {
+            known.attempts = known.attempts.saturating_add(1);
+            known.last_attempt = now;
+        }
+    }
+
+    /// Record a successful connection to the peer with the given ID, which
+    /// moves its address to the "old" buckets
+    pub fn mark_good(&mut self, id: &node::Id, now: Time) {
+        let Some(group) = self.addrs.get(id).map(|known| known.addr.group()) else {
+            return;
+        };
+        let bucket = self.bucket(&[&group, id.as_bytes()], OLD_BUCKET_COUNT);
+        if let Some(known) = self.addrs.get_mut(id) {
+            known.attempts = 0;
+            known.last_attempt = now;
+            known.last_success = now;
+            known.bucket_type = BucketType::Old;
+            known.buckets = vec![bucket];
+        }
+    }
+
+    /// Ban the peer with the given ID for the given duration, unless it is
+    /// already banned for longer
+    pub fn mark_bad(&mut self, id: &node::Id, now: Time, ban_duration: Duration) {
+        if let Some(known) = self.addrs.get_mut(id) {
+            let until = now.saturating_add(ban_duration);
+            if known.last_ban_time < until {
+                known.last_ban_time = until;
+            }
+        }
+    }
+
+    /// Whether the peer with the given ID is banned at the given time
+    #[must_use]
+    pub fn is_banned(&self, id: &node::Id, now: Time) -> bool {
+        self.addrs
+            .get(id)
+            .map_or(false, |known| known.is_banned(now))
+    }
+
+    /// The addresses of the peers which are not banned at the given time,
+    /// those the node has successfully connected to first
+    #[must_use]
+    pub fn dialable(&self, now: Time) -> Vec<&NetAddress> {
+        let mut addrs: Vec<_> = self
+            .addrs
+            .values()
+            .filter(|known| !known.is_banned(now))
+            .collect();
+        addrs.sort_by_key(|known| (known.bucket_type == BucketType::New, known.attempts));
+        addrs.into_iter().map(|known| &known.addr).collect()
+    }
+
+    /// Bucket derived from the key of the book and the given data, among
+    /// the given number of buckets
+    fn bucket(&self, data: &[&[u8]], count: u32) -> u32 {
+        let mut hasher = Sha256::new();
+        hasher.update(self.key.as_bytes());
+        for part in data {
+            hasher.update(part);
+        }
+        let hash = hasher.finalize();
+        let mut prefix = [0_u8; 8];
+        prefix.copy_from_slice(&hash[..8]);
+        // The remainder is less than `count`, so it fits into a `u32`.
+        #[allow(clippy::cast_possible_truncation)]
+        let bucket = (u64::from_be_bytes(prefix) % u64::from(count)) as u32;
+        bucket
+    }
+}
@@ -6,6 +6,7 @@
 
 use flex_error::{define_error, DisplayOnly};
 use prost::DecodeError;
+use tendermint::node;
 
 define_error! {
     Error {
@@ -57,7 +58,104 @@ define_error! {
 
         TransportClone
             { detail: String }
-            | e | { format_args!("failed to clone underlying transport: {}", e.detail) }
+            | e | { format_args!("failed to clone underlying transport: {}", e.detail) },
+
+        PeerIdMismatch
+            { expected: node::Id, actual: node::Id }
+            | e | { format_args!("expected to connect to peer {}, but got {}", e.expected, e.actual) },
+
+        BindKeyMismatch
+            | _ | { "public key to bind with does not match the transport's private key" },
+
+        StreamUnavailable
+            { stream_id: String }
+            | e | { format_args!("stream {} cannot be opened on this connection", e.stream_id) },
+
+        MessageTooLarge
+            { size: usize, max: usize }
+            | e | { format_args!("message of {} bytes exceeds the maximum of {} bytes", e.size, e.max) },
+
+        FileIo
+            { path: String }
+            [ DisplayOnly<std::io::Error> ]
+            | e | { format_args!("failed to access {}", e.path) },
+
+        Json
+            [ DisplayOnly<serde_json::Error> ]
+            | _ | { "malformed JSON" },
+
+        InvalidNetAddress
+            { addr: String }
+            | e | { format_args!("invalid network address {:?}, expected id@ip:port", e.addr) },
+
+        PexDecode
+            [ DisplayOnly<DecodeError> ]
+            | _ | { "malformed PEX message" },
+
+        MalformedPexMessage
+            | _ | { "PEX message without content" },
+
+        PexRateLimited
+            { peer: node::Id }
+            | e | { format_args!("peer {} requested addresses too often", e.peer) },
+
+        PexUnsolicited
+            { peer: node::Id }
+            | e | { format_args!("peer {} sent addresses which were not requested", e.peer) },
+
+        PexTooManyAddrs
+            { count: usize, max: usize }
+            | e | { format_args!("received {} addresses, more than the maximum of {}", e.count, e.max) },
+
+        InvalidPexAddress
+            { addr: String, reason: String }
+            | e | { format_args!("invalid address {}: {}", e.addr, e.reason) },
+
+        PacketDecode
+            [ DisplayOnly<DecodeError> ]
+            | _ | { "malformed packet" },
+
+        MalformedPacket
+            | _ | { "packet without content" },
+
+        UnknownChannel
+            { channel_id: i32 }
+            | e | { format_args!("unknown channel {:#04x}", e.channel_id) },
+
+        SendQueueFull
+            { channel_id: u8 }
+            | e | { format_args!("send queue of channel {:#04x} is full", e.channel_id) },
+
+        PongTimeout
+            | _ | { "peer did not respond to ping in time" },
+
+        ConnectionClosed
+            | _ | { "connection closed by peer" },
+
+        ConnectionStopped
+            | _ | { "connection stopped" },
+
+        PrivvalDecode
+            [ DisplayOnly<DecodeError> ]
+            | _ | { "malformed privval message" },
+
+        UnexpectedPrivvalMessage
+            | _ | { "unexpected privval message" },
+
+        InvalidPrivvalMessage
+            [ DisplayOnly<tendermint::Error> ]
+            | _ | { "invalid privval message" },
+
+        DoubleSign
+            { requested: String, last: String }
+            | e | {
+                format_args!("refusing to sign at height/round/step {}, after signing at {}",
+                    e.requested, e.last)
+            },
+
+        RemoteSigner
+            { code: i32, description: String }
+            | e | { format_args!("remote signer error {}: {}", e.code, e.description) },
 
     }
 }
@@ -23,6 +23,12 @@
     html_logo_url = "https://raw.githubusercontent.com/informalsystems/tendermint-rs/master/img/logo-tendermint-rs_3961x4001.png"
 )]
 
+pub mod addrbook;
 pub mod error;
+pub mod mconnection;
+pub mod node_key;
+pub mod pex;
+pub mod privval;
+pub mod probe;
 pub mod secret_connection;
 pub mod transport;
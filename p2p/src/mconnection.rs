@@ -0,0 +1,684 @@
+//! Multiplexed connections, compatible with CometBFT's `MConnection`.
+//!
+//! An [`MConnection`] multiplexes the messages of several channels over a
+//! single connection, usually a [`SecretConnection`]. Messages are split into
+//! [`Packet::Msg`] packets of at most
+//! [`MConnConfig::max_packet_msg_payload_size`] bytes, which are sent from
+//! the queues of the channels in proportion to their priorities, at a rate
+//! limited by [`MConnConfig::send_rate`]. [`Packet::Ping`] and
+//! [`Packet::Pong`] packets keep the connection alive, and detect peers which
+//! stopped responding.
+//!
+//! Packets are sent and received by two threads, which stop as soon as the
+//! connection fails or [`MConnection::stop`] is called.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{Read, Write},
+    net::{Shutdown, TcpStream},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use eyre::{eyre, Result};
+use prost::Message as _;
+use tendermint_proto::p2p as proto;
+
+use crate::{
+    error::Error,
+    secret_connection::{PublicKey, SecretConnection, Version},
+    transport::tcp::read_delimited,
+};
+
+/// Default maximum size of the payload of a [`Packet::Msg`], as in CometBFT
+pub const DEFAULT_MAX_PACKET_MSG_PAYLOAD_SIZE: usize = 1024;
+
+/// Default maximum send and receive rate, in bytes per second, as in CometBFT
+pub const DEFAULT_RATE: u64 = 512_000;
+
+/// Default interval between pings, as in CometBFT
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default time to wait for a pong, as in CometBFT
+pub const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(45);
+
+/// Default capacity of the send queue of a channel, in messages
+pub const DEFAULT_SEND_QUEUE_CAPACITY: usize = 1;
+
+/// Default maximum size of a message received on a channel, as in CometBFT
+pub const DEFAULT_RECV_MESSAGE_CAPACITY: usize = 22_020_096;
+
+/// Time [`MConnection::send`] waits for room in the queue of a channel
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval at which the statistics of the channels decay
+const STATS_UPDATE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Maximum number of packets sent between flushes
+const PACKETS_PER_FLUSH: usize = 10;
+
+/// Maximum size of the Protobuf encoding of a packet, besides its payload
+const PACKET_OVERHEAD: usize = 32;
+
+/// Number of received messages buffered until the application reads them
+const INCOMING_QUEUE_CAPACITY: usize = 100;
+
+/// Channel of an [`MConnection`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChannelDescriptor {
+    /// ID of the channel
+    pub id: u8,
+    /// Priority of the channel: channels are sent from in proportion to
+    /// their priorities
+    pub priority: u32,
+    /// Capacity of the send queue of the channel, in messages
+    pub send_queue_capacity: usize,
+    /// Maximum size of a message received on the channel
+    pub recv_message_capacity: usize,
+}
+
+impl ChannelDescriptor {
+    /// Describe the channel with the given ID and priority, with the default
+    /// capacities
+    #[must_use]
+    pub const fn new(id: u8, priority: u32) -> Self {
+        Self {
+            id,
+            priority,
+            send_queue_capacity: DEFAULT_SEND_QUEUE_CAPACITY,
+            recv_message_capacity: DEFAULT_RECV_MESSAGE_CAPACITY,
+        }
+    }
+
+    /// Set the capacity of the send queue of the channel
+    #[must_use]
+    pub const fn send_queue_capacity(mut self, capacity: usize) -> Self {
+        self.send_queue_capacity = capacity;
+        self
+    }
+
+    /// Set the maximum size of a message received on the channel
+    #[must_use]
+    pub const fn recv_message_capacity(mut self, capacity: usize) -> Self {
+        self.recv_message_capacity = capacity;
+        self
+    }
+}
+
+/// Configuration of an [`MConnection`]
+#[derive(Clone, Debug)]
+pub struct MConnConfig {
+    /// Maximum send rate, in bytes per second, or 0 for no limit
+    pub send_rate: u64,
+    /// Maximum receive rate, in bytes per second, or 0 for no limit
+    pub recv_rate: u64,
+    /// Maximum size of the payload of a [`Packet::Msg`]
+    pub max_packet_msg_payload_size: usize,
+    /// Interval between pings
+    pub ping_interval: Duration,
+    /// Time to wait for a pong before considering the peer gone
+    pub pong_timeout: Duration,
+}
+
+impl Default for MConnConfig {
+    fn default() -> Self {
+        Self {
+            send_rate: DEFAULT_RATE,
+            recv_rate: DEFAULT_RATE,
+            max_packet_msg_payload_size: DEFAULT_MAX_PACKET_MSG_PAYLOAD_SIZE,
+            ping_interval: DEFAULT_PING_INTERVAL,
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
+        }
+    }
+}
+
+/// Packet exchanged over an [`MConnection`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packet {
+    /// Keep-alive request
+    Ping,
+    /// Keep-alive response
+    Pong,
+    /// Part of a message of a channel
+    Msg {
+        /// ID of the channel
+        channel_id: u8,
+        /// Whether this is the last part of the message
+        eof: bool,
+        /// Part of the message
+        data: Vec<u8>,
+    },
+}
+
+impl Packet {
+    /// Encode this packet as a length-delimited Protobuf `tendermint.p2p.Packet`
+    #[must_use]
+    pub fn encode_length_delimited_vec(&self) -> Vec<u8> {
+        let sum = match self {
+            Self::Ping => proto::packet::Sum::PacketPing(proto::PacketPing {}),
+            Self::Pong => proto::packet::Sum::PacketPong(proto::PacketPong {}),
+            Self::Msg {
+                channel_id,
+                eof,
+                data,
+            } => proto::packet::Sum::PacketMsg(proto::PacketMsg {
+                channel_id: (*channel_id).into(),
+                eof: *eof,
+                data: data.clone(),
+            }),
+        };
+        proto::Packet { sum: Some(sum) }.encode_length_delimited_to_vec()
+    }
+
+    /// Decode a packet from a Protobuf `tendermint.p2p.Packet`
+    ///
+    /// # Errors
+    ///
+    /// * if the packet is malformed
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let packet = proto::Packet::decode(bytes).map_err(Error::packet_decode)?;
+        match packet.sum {
+            Some(proto::packet::Sum::PacketPing(_)) => Ok(Self::Ping),
+            Some(proto::packet::Sum::PacketPong(_)) => Ok(Self::Pong),
+            Some(proto::packet::Sum::PacketMsg(msg)) => Ok(Self::Msg {
+                channel_id: msg
+                    .channel_id
+                    .try_into()
+                    .map_err(|_| Error::unknown_channel(msg.channel_id))?,
+                eof: msg.eof,
+                data: msg.data,
+            }),
+            None => Err(Error::malformed_packet()),
+        }
+    }
+}
+
+/// Send state of a channel
+struct Channel {
+    desc: ChannelDescriptor,
+    queue: VecDeque<Vec<u8>>,
+    /// Message being sent, and how much of it was sent
+    sending: Option<(Vec<u8>, usize)>,
+    /// Number of bytes recently sent, decaying over time
+    recently_sent: u64,
+}
+
+impl Channel {
+    fn is_pending(&self) -> bool {
+        self.sending.is_some() || !self.queue.is_empty()
+    }
+
+    /// Next packet of the messages of this channel, if any
+    fn next_packet(&mut self, max_payload_size: usize) -> Option<Packet> {
+        if self.sending.is_none() {
+            self.sending = self.queue.pop_front().map(|msg| (msg, 0));
+        }
+        let (msg, offset) = self.sending.as_mut()?;
+        let end = msg.len().min(*offset + max_payload_size);
+        let data = msg[*offset..end].to_vec();
+        *offset = end;
+        let eof = end == msg.len();
+        if eof {
+            self.sending = None;
+        }
+        self.recently_sent = self.recently_sent.saturating_add(data.len() as u64);
+        Some(Packet::Msg {
+            channel_id: self.desc.id,
+            eof,
+            data,
+        })
+    }
+}
+
+/// State shared by an [`MConnection`] and its threads
+struct SendState {
+    channels: Vec<Channel>,
+    send_pong: bool,
+    pong_deadline: Option<Instant>,
+    stopped: bool,
+}
+
+impl SendState {
+    /// Channel with pending messages which sent the least recently in
+    /// proportion to its priority
+    fn next_channel(&mut self) -> Option<&mut Channel> {
+        self.channels
+            .iter_mut()
+            .filter(|channel| channel.is_pending())
+            .min_by(|a, b| {
+                let a_ratio = u128::from(a.recently_sent) * u128::from(b.desc.priority.max(1));
+                let b_ratio = u128::from(b.recently_sent) * u128::from(a.desc.priority.max(1));
+                a_ratio.cmp(&b_ratio)
+            })
+    }
+
+    fn channel_mut(&mut self, id: u8) -> Option<&mut Channel> {
+        self.channels
+            .iter_mut()
+            .find(|channel| channel.desc.id == id)
+    }
+}
+
+struct Shared {
+    state: Mutex<SendState>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, SendState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn stop(&self) {
+        self.lock().stopped = true;
+        self.changed.notify_all();
+    }
+}
+
+/// Connection multiplexing the messages of several channels
+pub struct MConnection {
+    shared: Arc<Shared>,
+    incoming: flume::Receiver<Result<(u8, Vec<u8>)>>,
+    stream: Option<TcpStream>,
+    remote_pubkey: Option<PublicKey>,
+}
+
+impl MConnection {
+    /// Perform the Secret Connection handshake over the given TCP stream and
+    /// multiplex the resulting connection. The stream is shut down when the
+    /// connection stops.
+    ///
+    /// # Errors
+    ///
+    /// * if the handshake fails
+    /// * if the TCP stream cannot be cloned
+    pub fn handshake(
+        stream: TcpStream,
+        private_key: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+        channels: Vec<ChannelDescriptor>,
+        config: MConnConfig,
+    ) -> Result<Self> {
+        let handle = stream.try_clone()?;
+        let conn = SecretConnection::new(stream, private_key, protocol_version)?;
+        let remote_pubkey = conn.remote_pubkey();
+        let (sender, receiver) = conn.split()?;
+        let mut mconn = Self::new(receiver, sender, channels, config)?;
+        mconn.stream = Some(handle);
+        mconn.remote_pubkey = Some(remote_pubkey);
+        Ok(mconn)
+    }
+
+    /// Multiplex the connection with the given read and write ends, and start
+    /// sending and receiving packets. Stopping the connection does not
+    /// interrupt a pending read, so the read end should also be closed then.
+    ///
+    /// # Errors
+    ///
+    /// * if several channels have the same ID
+    /// * if the threads cannot be started
+    pub fn new<R, W>(
+        read: R,
+        write: W,
+        channels: Vec<ChannelDescriptor>,
+        config: MConnConfig,
+    ) -> Result<Self>
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let mut recv_capacities = HashMap::new();
+        for desc in &channels {
+            if recv_capacities
+                .insert(desc.id, desc.recv_message_capacity)
+                .is_some()
+            {
+                return Err(eyre!("duplicate channel ID {:#04x}", desc.id));
+            }
+        }
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(SendState {
+                channels: channels
+                    .into_iter()
+                    .map(|desc| Channel {
+                        desc,
+                        queue: VecDeque::new(),
+                        sending: None,
+                        recently_sent: 0,
+                    })
+                    .collect(),
+                send_pong: false,
+                pong_deadline: None,
+                stopped: false,
+            }),
+            changed: Condvar::new(),
+        });
+        let (incoming_tx, incoming) = flume::bounded(INCOMING_QUEUE_CAPACITY);
+
+        let send_routine = SendRoutine {
+            shared: shared.clone(),
+            config: config.clone(),
+            errors: incoming_tx.clone(),
+        };
+        thread::Builder::new()
+            .name("mconnection-send".into())
+            .spawn(move || send_routine.run(write))?;
+
+        let recv_routine = RecvRoutine {
+            shared: shared.clone(),
+            config,
+            recv_capacities,
+            incoming: incoming_tx,
+        };
+        thread::Builder::new()
+            .name("mconnection-recv".into())
+            .spawn(move || recv_routine.run(read))?;
+
+        Ok(Self {
+            shared,
+            incoming,
+            stream: None,
+            remote_pubkey: None,
+        })
+    }
+
+    /// Queue a message to be sent on the given channel, waiting for room in
+    /// its queue if it is full
+    ///
+    /// # Errors
+    ///
+    /// * if the channel is unknown
+    /// * if the queue of the channel stays full for too long
+    /// * if the connection is stopped
+    pub fn send(&self, channel_id: u8, msg: Vec<u8>) -> Result<()> {
+        let deadline = Instant::now() + SEND_TIMEOUT;
+        let mut state = self.shared.lock();
+        loop {
+            if state.stopped {
+                return Err(Error::connection_stopped().into());
+            }
+            let channel = state
+                .channel_mut(channel_id)
+                .ok_or_else(|| Error::unknown_channel(channel_id.into()))?;
+            if channel.queue.len() < channel.desc.send_queue_capacity {
+                channel.queue.push_back(msg);
+                self.shared.changed.notify_all();
+                return Ok(());
+            }
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if timeout.is_zero() {
+                return Err(Error::send_queue_full(channel_id).into());
+            }
+            state = self
+                .shared
+                .changed
+                .wait_timeout(state, timeout)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// Queue a message to be sent on the given channel, unless its queue is
+    /// full. Returns whether the message was queued.
+    ///
+    /// # Errors
+    ///
+    /// * if the channel is unknown
+    /// * if the connection is stopped
+    pub fn try_send(&self, channel_id: u8, msg: Vec<u8>) -> Result<bool> {
+        let mut state = self.shared.lock();
+        if state.stopped {
+            return Err(Error::connection_stopped().into());
+        }
+        let channel = state
+            .channel_mut(channel_id)
+            .ok_or_else(|| Error::unknown_channel(channel_id.into()))?;
+        if channel.queue.len() >= channel.desc.send_queue_capacity {
+            return Ok(false);
+        }
+        channel.queue.push_back(msg);
+        self.shared.changed.notify_all();
+        Ok(true)
+    }
+
+    /// Wait for the next message received on any channel, along with the ID
+    /// of the channel
+    ///
+    /// # Errors
+    ///
+    /// * if the connection failed, e.g. the peer sent a malformed packet or
+    ///   did not respond to a ping in time
+    /// * if the connection is stopped
+    pub fn recv(&self) -> Result<(u8, Vec<u8>)> {
+        self.incoming
+            .recv()
+            .map_err(|_| Error::connection_stopped())?
+    }
+
+    /// Public key of the peer, if the connection was established with
+    /// [`MConnection::handshake`]
+    #[must_use]
+    pub const fn remote_pubkey(&self) -> Option<PublicKey> {
+        self.remote_pubkey
+    }
+
+    /// Whether the connection is still running
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        !self.shared.lock().stopped
+    }
+
+    /// Stop sending and receiving packets, and shut down the underlying TCP
+    /// stream, if any
+    pub fn stop(&self) {
+        self.shared.stop();
+        if let Some(stream) = &self.stream {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+impl Drop for MConnection {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Limits the rate of a sequence of transfers
+struct RateLimiter {
+    rate: u64,
+    start: Instant,
+    bytes: u64,
+}
+
+impl RateLimiter {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            start: Instant::now(),
+            bytes: 0,
+        }
+    }
+
+    /// Account for a transfer of the given size, sleeping as long as needed
+    /// to keep to the rate
+    fn throttle(&mut self, size: usize) {
+        if self.rate == 0 {
+            return;
+        }
+        let elapsed = self.start.elapsed();
+        // Do not let idle periods allow for bursts.
+        if elapsed > Duration::from_secs(1) && self.expected(0) < elapsed {
+            self.start = Instant::now();
+            self.bytes = 0;
+        }
+        let expected = self.expected(size as u64);
+        if let Some(wait) = expected.checked_sub(self.start.elapsed()) {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Time the bytes transferred so far, and the given additional ones,
+    /// take at the rate
+    fn expected(&mut self, additional: u64) -> Duration {
+        self.bytes = self.bytes.saturating_add(additional);
+        let nanos = u128::from(self.bytes) * 1_000_000_000 / u128::from(self.rate);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+struct SendRoutine {
+    shared: Arc<Shared>,
+    config: MConnConfig,
+    errors: flume::Sender<Result<(u8, Vec<u8>)>>,
+}
+
+impl SendRoutine {
+    fn run<W: Write>(self, mut write: W) {
+        if let Err(e) = self.send_packets(&mut write) {
+            let _ = self.errors.send(Err(e));
+        }
+        self.shared.stop();
+    }
+
+    fn send_packets<W: Write>(&self, write: &mut W) -> Result<()> {
+        let mut limiter = RateLimiter::new(self.config.send_rate);
+        let mut next_ping = Instant::now() + self.config.ping_interval;
+        let mut next_stats_update = Instant::now() + STATS_UPDATE_INTERVAL;
+
+        loop {
+            let packets = {
+                let mut state = self.shared.lock();
+                loop {
+                    if state.stopped {
+                        return Ok(());
+                    }
+                    let now = Instant::now();
+                    if state
+                        .pong_deadline
+                        .map_or(false, |deadline| now >= deadline)
+                    {
+                        return Err(Error::pong_timeout().into());
+                    }
+                    if now >= next_stats_update {
+                        for channel in &mut state.channels {
+                            channel.recently_sent = channel.recently_sent * 4 / 5;
+                        }
+                        next_stats_update = now + STATS_UPDATE_INTERVAL;
+                    }
+                    if state.send_pong
+                        || now >= next_ping
+                        || state.channels.iter().any(Channel::is_pending)
+                    {
+                        break;
+                    }
+                    let wake_at = state
+                        .pong_deadline
+                        .map_or(next_ping, |deadline| deadline.min(next_ping))
+                        .min(next_stats_update);
+                    state = self
+                        .shared
+                        .changed
+                        .wait_timeout(state, wake_at.saturating_duration_since(now))
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+
+                let mut packets = Vec::new();
+                if std::mem::take(&mut state.send_pong) {
+                    packets.push(Packet::Pong);
+                }
+                if Instant::now() >= next_ping {
+                    packets.push(Packet::Ping);
+                    next_ping = Instant::now() + self.config.ping_interval;
+                    if state.pong_deadline.is_none() {
+                        state.pong_deadline = Some(Instant::now() + self.config.pong_timeout);
+                    }
+                }
+                while packets.len() < PACKETS_PER_FLUSH {
+                    let max_payload_size = self.config.max_packet_msg_payload_size;
+                    match state
+                        .next_channel()
+                        .and_then(|channel| channel.next_packet(max_payload_size))
+                    {
+                        Some(packet) => packets.push(packet),
+                        None => break,
+                    }
+                }
+                // Messages may have left the queues, making room for others.
+                self.shared.changed.notify_all();
+                packets
+            };
+
+            for packet in packets {
+                let bytes = packet.encode_length_delimited_vec();
+                limiter.throttle(bytes.len());
+                write.write_all(&bytes)?;
+            }
+            write.flush()?;
+        }
+    }
+}
+
+struct RecvRoutine {
+    shared: Arc<Shared>,
+    config: MConnConfig,
+    recv_capacities: HashMap<u8, usize>,
+    incoming: flume::Sender<Result<(u8, Vec<u8>)>>,
+}
+
+impl RecvRoutine {
+    fn run<R: Read>(self, mut read: R) {
+        if let Err(e) = self.recv_packets(&mut read) {
+            let _ = self.incoming.send(Err(e));
+        }
+        self.shared.stop();
+    }
+
+    fn recv_packets<R: Read>(&self, read: &mut R) -> Result<()> {
+        let mut limiter = RateLimiter::new(self.config.recv_rate);
+        let mut buffers: HashMap<u8, Vec<u8>> = HashMap::new();
+        let max_packet_size = self.config.max_packet_msg_payload_size + PACKET_OVERHEAD;
+
+        loop {
+            let Some(bytes) = read_delimited(read, max_packet_size)? else {
+                return Err(Error::connection_closed().into());
+            };
+            limiter.throttle(bytes.len());
+
+            match Packet::decode(&bytes)? {
+                Packet::Ping => {
+                    self.shared.lock().send_pong = true;
+                    self.shared.changed.notify_all();
+                },
+                Packet::Pong => self.shared.lock().pong_deadline = None,
+                Packet::Msg {
+                    channel_id,
+                    eof,
+                    data,
+                } => {
+                    let capacity = *self
+                        .recv_capacities
+                        .get(&channel_id)
+                        .ok_or_else(|| Error::unknown_channel(channel_id.into()))?;
+                    let buffer = buffers.entry(channel_id).or_default();
+                    if buffer.len() + data.len() > capacity {
+                        return Err(
+                            Error::message_too_large(buffer.len() + data.len(), capacity).into(),
+                        );
+                    }
+                    buffer.extend_from_slice(&data);
+                    if eof {
+                        let msg = std::mem::take(buffer);
+                        if self.incoming.send(Ok((channel_id, msg))).is_err() {
+                            // The connection was dropped.
+                            return Ok(());
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Node keys, which identify nodes on the P2P network.
+//!
+//! A node key is the Ed25519 key a node authenticates its Secret Connections
+//! with, and from which its [`node::Id`] is derived. It is stored in
+//! `node_key.json`, in the same format as CometBFT's, so that existing node
+//! keys can be reused.
+
+use std::{fs, io::Write as _, path::Path};
+
+use rand_core::{OsRng, RngCore};
+use tendermint::{crypto::ed25519, node, private_key::PrivateKey};
+
+use crate::{error::Error, secret_connection::PublicKey};
+
+/// Key identifying a node on the P2P network
+#[derive(Clone)]
+pub struct NodeKey {
+    signing_key: ed25519_consensus::SigningKey,
+}
+
+impl NodeKey {
+    /// Generate a new random node key
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut bytes = [0_u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        Self::from(ed25519_consensus::SigningKey::from(bytes))
+    }
+
+    /// Parse the contents of a `node_key.json` file
+    ///
+    /// # Errors
+    ///
+    /// * if the JSON is malformed or does not contain an Ed25519 key
+    pub fn parse_json(json: &str) -> Result<Self, Error> {
+        let node_key: tendermint_config::NodeKey =
+            serde_json::from_str(json).map_err(Error::json)?;
+        match node_key.priv_key {
+            PrivateKey::Ed25519(signing_key) => {
+                ed25519_consensus::SigningKey::try_from(signing_key)
+                    .map(Self::from)
+                    .map_err(|_| Error::invalid_key())
+            },
+            _ => Err(Error::unsupported_key()),
+        }
+    }
+
+    /// Serialize this key as the contents of a `node_key.json` file
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let signing_key = ed25519::SigningKey::try_from(self.signing_key.as_bytes().as_slice())
+            .expect("a valid Ed25519 signing key");
+        let node_key = tendermint_config::NodeKey {
+            priv_key: PrivateKey::Ed25519(signing_key),
+        };
+        serde_json::to_string(&node_key).expect("node keys to serialize to JSON")
+    }
+
+    /// Load a node key from a `node_key.json` file
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be read
+    /// * if its contents are not a valid node key
+    pub fn load_json_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json =
+            fs::read_to_string(path).map_err(|e| Error::file_io(path.display().to_string(), e))?;
+        Self::parse_json(&json)
+    }
+
+    /// Save this node key to a `node_key.json` file, which is only readable
+    /// by its owner on Unix
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be written
+    pub fn save_json_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options
+            .open(path)
+            .and_then(|mut file| file.write_all(self.to_json().as_bytes()))
+            .map_err(|e| Error::file_io(path.display().to_string(), e))
+    }
+
+    /// Load the node key from the given `node_key.json` file, or generate a
+    /// new one and save it there if the file does not exist
+    ///
+    /// # Errors
+    ///
+    /// * if the file exists but does not contain a valid node key
+    /// * if the new node key cannot be saved
+    pub fn load_or_generate(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Self::load_json_file(path);
+        }
+        let node_key = Self::generate();
+        node_key.save_json_file(path)?;
+        Ok(node_key)
+    }
+
+    /// Signing key, to authenticate Secret Connections with
+    #[must_use]
+    pub const fn signing_key(&self) -> &ed25519_consensus::SigningKey {
+        &self.signing_key
+    }
+
+    /// Public key of this node key
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(&self.signing_key)
+    }
+
+    /// ID of the node identified by this key
+    #[must_use]
+    pub fn node_id(&self) -> node::Id {
+        self.public_key().peer_id()
+    }
+}
+
+impl From<ed25519_consensus::SigningKey> for NodeKey {
+    fn from(signing_key: ed25519_consensus::SigningKey) -> Self {
+        Self { signing_key }
+    }
+}
@@ -0,0 +1,361 @@
+//! Peer exchange (PEX) protocol.
+//!
+//! Peers exchange the addresses they know of over the PEX channel
+//! ([`CHANNEL_ID`]) of an [`MConnection`], as CometBFT nodes do: a node sends
+//! a [`PexMessage::Request`], to which the peer responds with a
+//! [`PexMessage::Addrs`] drawn at random from its address book. As in
+//! CometBFT, a node answers requests at most once per
+//! [`PexConfig::min_request_interval`] from the same peer, rejects addresses
+//! it did not request, and only adds valid addresses to its [`AddrBook`].
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use eyre::Result;
+use prost::Message as _;
+use rand_core::{OsRng, RngCore};
+use tendermint::{node, Time};
+use tendermint_proto::p2p as proto;
+
+use crate::{
+    addrbook::{AddrBook, NetAddress},
+    error::Error,
+    mconnection::{ChannelDescriptor, MConnection},
+};
+
+/// ID of the PEX channel of an [`MConnection`], as in CometBFT
+pub const CHANNEL_ID: u8 = 0x00;
+
+/// Default maximum number of addresses in a [`PexMessage::Addrs`], as in
+/// CometBFT
+pub const DEFAULT_MAX_ADDRS: usize = 250;
+
+/// Default minimum interval between the requests of a peer, as in CometBFT
+pub const DEFAULT_MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Minimum number of addresses to respond with, if known
+const MIN_SELECTION: usize = 32;
+
+/// Percentage of the known addresses to respond with
+const SELECTION_PERCENT: usize = 23;
+
+/// Maximum size of an encoded address, as in CometBFT
+const MAX_ADDRESS_SIZE: usize = 256;
+
+/// Descriptor of the PEX channel of an [`MConnection`], with the priority
+/// and capacities of CometBFT's
+#[must_use]
+pub const fn channel_descriptor() -> ChannelDescriptor {
+    ChannelDescriptor::new(CHANNEL_ID, 1)
+        .send_queue_capacity(10)
+        .recv_message_capacity(MAX_ADDRESS_SIZE * DEFAULT_MAX_ADDRS)
+}
+
+/// Message of the PEX protocol
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PexMessage {
+    /// Request for the addresses the peer knows of
+    Request,
+    /// Addresses known to the peer
+    Addrs(Vec<NetAddress>),
+}
+
+impl PexMessage {
+    /// Encode this message as a Protobuf `tendermint.p2p.Message`
+    #[must_use]
+    pub fn encode_vec(&self) -> Vec<u8> {
+        let sum = match self {
+            Self::Request => proto::message::Sum::PexRequest(proto::PexRequest {}),
+            Self::Addrs(addrs) => proto::message::Sum::PexAddrs(proto::PexAddrs {
+                addrs: addrs
+                    .iter()
+                    .map(|addr| proto::NetAddress {
+                        id: addr.id.to_string(),
+                        ip: addr.ip.to_string(),
+                        port: addr.port.into(),
+                    })
+                    .collect(),
+            }),
+        };
+        proto::Message { sum: Some(sum) }.encode_to_vec()
+    }
+
+    /// Decode a message from a Protobuf `tendermint.p2p.Message`
+    ///
+    /// # Errors
+    ///
+    /// * if the message is malformed
+    /// * if one of its addresses cannot be parsed
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let msg = proto::Message::decode(bytes).map_err(Error::pex_decode)?;
+        match msg.sum {
+            Some(proto::message::Sum::PexRequest(_)) => Ok(Self::Request),
+            Some(proto::message::Sum::PexAddrs(addrs)) => addrs
+                .addrs
+                .into_iter()
+                .map(net_address)
+                .collect::<Result<_, _>>()
+                .map(Self::Addrs),
+            None => Err(Error::malformed_pex_message()),
+        }
+    }
+}
+
+/// Parse a network address received in a PEX message
+fn net_address(addr: proto::NetAddress) -> Result<NetAddress, Error> {
+    let invalid = || Error::invalid_net_address(format!("{}@{}:{}", addr.id, addr.ip, addr.port));
+    Ok(NetAddress {
+        id: addr.id.parse().map_err(|_| invalid())?,
+        ip: addr.ip.parse().map_err(|_| invalid())?,
+        port: addr.port.try_into().map_err(|_| invalid())?,
+    })
+}
+
+/// Configuration of the PEX protocol
+#[derive(Clone, Debug)]
+pub struct PexConfig {
+    /// Maximum number of addresses in a [`PexMessage::Addrs`], sent or
+    /// received
+    pub max_addrs: usize,
+    /// Minimum interval between the requests of a peer
+    pub min_request_interval: Duration,
+    /// Whether to accept addresses which are not routable on the public
+    /// internet, e.g. private or loopback ones, as for local testnets
+    pub allow_non_routable: bool,
+}
+
+impl Default for PexConfig {
+    fn default() -> Self {
+        Self {
+            max_addrs: DEFAULT_MAX_ADDRS,
+            min_request_interval: DEFAULT_MIN_REQUEST_INTERVAL,
+            allow_non_routable: false,
+        }
+    }
+}
+
+/// State of the PEX protocol with the peers of a node
+#[derive(Clone, Debug)]
+pub struct Pex {
+    config: PexConfig,
+    local_id: node::Id,
+    last_requests: HashMap<node::Id, Time>,
+    requested: HashSet<node::Id>,
+}
+
+impl Pex {
+    /// Create the PEX protocol state of the node with the given ID
+    #[must_use]
+    pub fn new(local_id: node::Id, config: PexConfig) -> Self {
+        Self {
+            config,
+            local_id,
+            last_requests: HashMap::new(),
+            requested: HashSet::new(),
+        }
+    }
+
+    /// Request the addresses the given peer knows of, which it is then
+    /// allowed to respond with
+    pub fn request(&mut self, peer: node::Id) -> PexMessage {
+        self.requested.insert(peer);
+        PexMessage::Request
+    }
+
+    /// Handle a message received from the given peer, returning the
+    /// response to send back to it, if any
+    ///
+    /// # Errors
+    ///
+    /// * if the peer violated the protocol, in which case it should be
+    ///   disconnected from
+    pub fn receive(
+        &mut self,
+        book: &mut AddrBook,
+        src: &NetAddress,
+        msg: PexMessage,
+        now: Time,
+    ) -> Result<Option<PexMessage>, Error> {
+        match msg {
+            PexMessage::Request => self.handle_request(book, &src.id, now).map(Some),
+            PexMessage::Addrs(addrs) => self.handle_addrs(book, src, addrs).map(|_| None),
+        }
+    }
+
+    /// Respond to a request of the given peer with a random selection of
+    /// the addresses in the book
+    ///
+    /// # Errors
+    ///
+    /// * if the peer sent its previous request too recently
+    pub fn handle_request(
+        &mut self,
+        book: &AddrBook,
+        peer: &node::Id,
+        now: Time,
+    ) -> Result<PexMessage, Error> {
+        if let Some(last) = self.last_requests.get(peer) {
+            if now < last.saturating_add(self.config.min_request_interval) {
+                return Err(Error::pex_rate_limited(*peer));
+            }
+        }
+        self.last_requests.insert(*peer, now);
+
+        let mut candidates: Vec<_> = book
+            .dialable(now)
+            .into_iter()
+            .filter(|addr| addr.id != *peer)
+            .collect();
+        let count = (candidates.len() * SELECTION_PERCENT / 100)
+            .max(MIN_SELECTION)
+            .min(self.config.max_addrs)
+            .min(candidates.len());
+        shuffle_front(&mut candidates, count);
+        Ok(PexMessage::Addrs(
+            candidates.into_iter().take(count).cloned().collect(),
+        ))
+    }
+
+    /// Add the addresses the given peer responded with to the book, and
+    /// return how many of them were new. Invalid addresses and the address
+    /// of the node itself are skipped.
+    ///
+    /// # Errors
+    ///
+    /// * if the addresses were not requested from the peer
+    /// * if the peer sent too many addresses
+    pub fn handle_addrs(
+        &mut self,
+        book: &mut AddrBook,
+        src: &NetAddress,
+        addrs: Vec<NetAddress>,
+    ) -> Result<usize, Error> {
+        if !self.requested.remove(&src.id) {
+            return Err(Error::pex_unsolicited(src.id));
+        }
+        if addrs.len() > self.config.max_addrs {
+            return Err(Error::pex_too_many_addrs(
+                addrs.len(),
+                self.config.max_addrs,
+            ));
+        }
+
+        let mut added = 0;
+        for addr in addrs {
+            if addr.id == self.local_id
+                || validate_address(&addr, self.config.allow_non_routable).is_err()
+            {
+                continue;
+            }
+            if book.add_address(addr, src.clone()) {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Forget the state of the protocol with the given peer, once it is
+    /// disconnected
+    pub fn peer_disconnected(&mut self, peer: &node::Id) {
+        self.last_requests.remove(peer);
+        self.requested.remove(peer);
+    }
+}
+
+/// Move a uniformly random selection of `count` of the given items to their
+/// front, with a partial Fisher-Yates shuffle
+fn shuffle_front<T>(items: &mut [T], count: usize) {
+    for i in 0..count.min(items.len()) {
+        let remaining = u64::try_from(items.len() - i).unwrap_or(u64::MAX);
+        let offset = usize::try_from(OsRng.next_u64() % remaining).unwrap_or(0);
+        items.swap(i, i + offset);
+    }
+}
+
+/// Validate an address received from a peer, which must have a port and an
+/// IP address other peers can connect to
+///
+/// # Errors
+///
+/// * if the address is not valid
+/// * if it is not routable on the public internet, unless allowed
+pub fn validate_address(addr: &NetAddress, allow_non_routable: bool) -> Result<(), Error> {
+    let invalid = |reason: &str| Err(Error::invalid_pex_address(addr.to_string(), reason.into()));
+    if addr.port == 0 {
+        return invalid("zero port");
+    }
+    if addr.ip.is_unspecified() || addr.ip == IpAddr::V4(Ipv4Addr::BROADCAST) {
+        return invalid("unspecified or broadcast IP address");
+    }
+    if !allow_non_routable && !is_routable(addr.ip) {
+        return invalid("non-routable IP address");
+    }
+    Ok(())
+}
+
+/// Whether the given IP address is routable on the public internet
+fn is_routable(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_documentation()
+                // Shared address space (RFC 6598) and benchmarking (RFC 2544)
+                || (a == 100 && b & 0xc0 == 64)
+                || (a == 198 && b & 0xfe == 18))
+        },
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_routable(IpAddr::V4(ip)),
+            None => {
+                let [a, b, ..] = ip.segments();
+                !(ip.is_loopback()
+                    // Unique local (RFC 4193), link-local and documentation
+                    // (RFC 3849) addresses
+                    || a & 0xfe00 == 0xfc00
+                    || a & 0xffc0 == 0xfe80
+                    || (a == 0x2001 && b == 0x0db8)
+                    || ip == Ipv6Addr::UNSPECIFIED)
+            },
+        },
+    }
+}
+
+/// Exchange addresses with a peer over the PEX channel of a connection,
+/// multiplexed with the [`channel_descriptor`]: request the addresses it
+/// knows of, answering its own requests meanwhile, and add those it responds
+/// with to the book. Returns the number of new addresses.
+///
+/// The messages received on the other channels of the connection, if any,
+/// are discarded.
+///
+/// # Errors
+///
+/// * if the connection fails or is stopped before the peer responds
+/// * if the peer violates the protocol
+pub fn exchange(
+    pex: &mut Pex,
+    book: &mut AddrBook,
+    src: &NetAddress,
+    mconn: &MConnection,
+    now: Time,
+) -> Result<usize> {
+    mconn.send(CHANNEL_ID, pex.request(src.id).encode_vec())?;
+    loop {
+        let (channel_id, bytes) = mconn.recv()?;
+        if channel_id != CHANNEL_ID {
+            continue;
+        }
+        match PexMessage::decode(&bytes)? {
+            PexMessage::Addrs(addrs) => return Ok(pex.handle_addrs(book, src, addrs)?),
+            PexMessage::Request => {
+                let response = pex.handle_request(book, &src.id, now)?;
+                mconn.send(CHANNEL_ID, response.encode_vec())?;
+            },
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Remote signing (privval) protocol, compatible with CometBFT's.
+//!
+//! Validators may keep their consensus key in a separate signer process, e.g.
+//! one backed by an HSM, which connects to the node over TCP, authenticated
+//! and encrypted with a [`SecretConnection`], or over a Unix socket. The node
+//! then sends it [`Request`]s for its public key and for signatures of votes
+//! and proposals, which it answers with [`Response`]s.
+//!
+//! [`SignerServer`] answers requests with any [`Signer`], refusing to sign
+//! conflicting messages as tracked by its [`SignState`], while
+//! [`PrivvalClient`] sends requests on behalf of nodes and tools.
+//!
+//! [`SecretConnection`]: crate::secret_connection::SecretConnection
+
+use std::io::{Read, Write};
+
+use eyre::Result;
+use prost::Message as _;
+use tendermint::{
+    proposal::{SignProposalRequest, SignedProposalResponse},
+    public_key::{PubKeyRequest, PubKeyResponse},
+    vote::{SignVoteRequest, SignedVoteResponse},
+};
+use tendermint_proto::privval as proto;
+
+use crate::{error::Error, transport::tcp::read_delimited};
+
+pub mod client;
+pub mod signer;
+pub mod state;
+
+pub use self::{
+    client::PrivvalClient,
+    signer::{Signer, SignerServer},
+    state::{LastSignature, SignState},
+};
+
+/// Maximum size of a message of the protocol, as in CometBFT
+pub const MAX_MSG_SIZE: usize = 10 * 1024;
+
+/// Request sent to a remote signer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Request {
+    /// Request for the public key of the validator
+    PubKey(PubKeyRequest),
+    /// Request to sign a vote
+    SignVote(SignVoteRequest),
+    /// Request to sign a proposal
+    SignProposal(SignProposalRequest),
+    /// Keep-alive request
+    Ping,
+}
+
+/// Response of a remote signer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Response {
+    /// Public key of the validator
+    PubKey(PubKeyResponse),
+    /// Signed vote
+    SignedVote(SignedVoteResponse),
+    /// Signed proposal
+    SignedProposal(SignedProposalResponse),
+    /// Keep-alive response
+    Ping,
+}
+
+impl From<Request> for proto::message::Sum {
+    fn from(request: Request) -> Self {
+        match request {
+            Request::PubKey(req) => Self::PubKeyRequest(req.into()),
+            Request::SignVote(req) => Self::SignVoteRequest(req.into()),
+            Request::SignProposal(req) => Self::SignProposalRequest(req.into()),
+            Request::Ping => Self::PingRequest(proto::PingRequest {}),
+        }
+    }
+}
+
+impl From<Response> for proto::message::Sum {
+    fn from(response: Response) -> Self {
+        match response {
+            Response::PubKey(res) => Self::PubKeyResponse(res.into()),
+            Response::SignedVote(res) => Self::SignedVoteResponse(res.into()),
+            Response::SignedProposal(res) => Self::SignedProposalResponse(res.into()),
+            Response::Ping => Self::PingResponse(proto::PingResponse {}),
+        }
+    }
+}
+
+impl TryFrom<proto::message::Sum> for Request {
+    type Error = Error;
+
+    fn try_from(sum: proto::message::Sum) -> Result<Self, Self::Error> {
+        use proto::message::Sum;
+
+        match sum {
+            Sum::PubKeyRequest(req) => req.try_into().map(Self::PubKey),
+            Sum::SignVoteRequest(req) => req.try_into().map(Self::SignVote),
+            Sum::SignProposalRequest(req) => req.try_into().map(Self::SignProposal),
+            Sum::PingRequest(_) => Ok(Self::Ping),
+            _ => return Err(Error::unexpected_privval_message()),
+        }
+        .map_err(Error::invalid_privval_message)
+    }
+}
+
+impl TryFrom<proto::message::Sum> for Response {
+    type Error = Error;
+
+    fn try_from(sum: proto::message::Sum) -> Result<Self, Self::Error> {
+        use proto::message::Sum;
+
+        match sum {
+            Sum::PubKeyResponse(res) => res.try_into().map(Self::PubKey),
+            Sum::SignedVoteResponse(res) => res.try_into().map(Self::SignedVote),
+            Sum::SignedProposalResponse(res) => res.try_into().map(Self::SignedProposal),
+            Sum::PingResponse(_) => Ok(Self::Ping),
+            _ => return Err(Error::unexpected_privval_message()),
+        }
+        .map_err(Error::invalid_privval_message)
+    }
+}
+
+/// Write a message of the protocol, length-delimited
+fn write_msg<W: Write>(write: &mut W, sum: proto::message::Sum) -> Result<()> {
+    let bytes = proto::Message { sum: Some(sum) }.encode_length_delimited_to_vec();
+    write.write_all(&bytes)?;
+    write.flush()?;
+    Ok(())
+}
+
+/// Read a message of the protocol. Returns `None` if the connection was
+/// closed.
+fn read_msg<R, T>(read: &mut R) -> Result<Option<T>>
+where
+    R: Read,
+    T: TryFrom<proto::message::Sum, Error = Error>,
+{
+    let Some(bytes) = read_delimited(read, MAX_MSG_SIZE)? else {
+        return Ok(None);
+    };
+    let msg = proto::Message::decode(bytes.as_slice()).map_err(Error::privval_decode)?;
+    let sum = msg.sum.ok_or_else(Error::unexpected_privval_message)?;
+    Ok(Some(T::try_from(sum)?))
+}
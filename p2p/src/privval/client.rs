@@ -0,0 +1,152 @@
+//! Client requesting signatures from a remote signer.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+use eyre::{eyre, Result};
+use tendermint::{
+    chain, privval::RemoteSignerError, proposal::SignProposalRequest, public_key::PubKeyRequest,
+    vote::SignVoteRequest, Proposal, PublicKey, Vote,
+};
+
+use super::{read_msg, write_msg, Request, Response};
+use crate::{
+    error::Error,
+    secret_connection::{SecretConnection, Version},
+};
+
+/// Client requesting signatures of the messages of a chain from a remote
+/// signer, over an established connection
+pub struct PrivvalClient<C> {
+    conn: C,
+    chain_id: chain::Id,
+}
+
+impl PrivvalClient<SecretConnection<TcpStream>> {
+    /// Accept the connection of a remote signer on the given listener,
+    /// authenticating with the given identity key
+    ///
+    /// # Errors
+    ///
+    /// * if no connection can be accepted
+    /// * if the handshake fails
+    pub fn accept_tcp(
+        listener: &TcpListener,
+        identity_key: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+        chain_id: chain::Id,
+    ) -> Result<Self> {
+        let (stream, _) = listener.accept()?;
+        let conn = SecretConnection::new(stream, identity_key, protocol_version)?;
+        Ok(Self::new(conn, chain_id))
+    }
+}
+
+#[cfg(unix)]
+impl PrivvalClient<std::os::unix::net::UnixStream> {
+    /// Accept the connection of a remote signer on the given Unix socket
+    ///
+    /// # Errors
+    ///
+    /// * if no connection can be accepted
+    pub fn accept_unix(
+        listener: &std::os::unix::net::UnixListener,
+        chain_id: chain::Id,
+    ) -> Result<Self> {
+        let (stream, _) = listener.accept()?;
+        Ok(Self::new(stream, chain_id))
+    }
+}
+
+impl<C: Read + Write> PrivvalClient<C> {
+    /// Request signatures of the messages of the given chain over the given
+    /// connection
+    pub const fn new(conn: C, chain_id: chain::Id) -> Self {
+        Self { conn, chain_id }
+    }
+
+    /// Request the public key of the validator
+    ///
+    /// # Errors
+    ///
+    /// * if the request fails
+    /// * if the signer responds with an error
+    pub fn public_key(&mut self) -> Result<PublicKey> {
+        let request = Request::PubKey(PubKeyRequest {
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::PubKey(res) => {
+                check_error(res.error)?;
+                res.pub_key
+                    .ok_or_else(|| eyre!("remote signer responded without a public key"))
+            },
+            _ => Err(Error::unexpected_privval_message().into()),
+        }
+    }
+
+    /// Request the signature of the given vote, which is returned signed
+    ///
+    /// # Errors
+    ///
+    /// * if the request fails
+    /// * if the signer refuses to sign the vote
+    pub fn sign_vote(&mut self, vote: Vote) -> Result<Vote> {
+        let request = Request::SignVote(SignVoteRequest {
+            vote,
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::SignedVote(res) => {
+                check_error(res.error)?;
+                res.vote
+                    .ok_or_else(|| eyre!("remote signer responded without a vote"))
+            },
+            _ => Err(Error::unexpected_privval_message().into()),
+        }
+    }
+
+    /// Request the signature of the given proposal, which is returned signed
+    ///
+    /// # Errors
+    ///
+    /// * if the request fails
+    /// * if the signer refuses to sign the proposal
+    pub fn sign_proposal(&mut self, proposal: Proposal) -> Result<Proposal> {
+        let request = Request::SignProposal(SignProposalRequest {
+            proposal,
+            chain_id: self.chain_id.clone(),
+        });
+        match self.request(request)? {
+            Response::SignedProposal(res) => {
+                check_error(res.error)?;
+                res.proposal
+                    .ok_or_else(|| eyre!("remote signer responded without a proposal"))
+            },
+            _ => Err(Error::unexpected_privval_message().into()),
+        }
+    }
+
+    /// Check that the signer is still responding
+    ///
+    /// # Errors
+    ///
+    /// * if the request fails
+    pub fn ping(&mut self) -> Result<()> {
+        match self.request(Request::Ping)? {
+            Response::Ping => Ok(()),
+            _ => Err(Error::unexpected_privval_message().into()),
+        }
+    }
+
+    fn request(&mut self, request: Request) -> Result<Response> {
+        write_msg(&mut self.conn, request.into())?;
+        read_msg(&mut self.conn)?.ok_or_else(|| Error::connection_closed().into())
+    }
+}
+
+fn check_error(error: Option<RemoteSignerError>) -> Result<(), Error> {
+    error.map_or(Ok(()), |e| Err(Error::remote_signer(e.code, e.description)))
+}
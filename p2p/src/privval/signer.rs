@@ -0,0 +1,257 @@
+//! Signers, and the server answering the requests of a node with them.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+use eyre::Result;
+use tendermint::{
+    block, chain,
+    privval::{RemoteSignerError, Step},
+    proposal::{SignProposalRequest, SignedProposalResponse},
+    public_key::PubKeyResponse,
+    vote::{SignVoteRequest, SignedVoteResponse},
+    PublicKey, Signature, SignedMsgType, Time,
+};
+use tendermint_proto::privval::Errors;
+
+use super::{read_msg, write_msg, Request, Response, SignState};
+use crate::secret_connection::{SecretConnection, Version};
+
+/// Holder of the consensus key of a validator, e.g. an HSM
+pub trait Signer {
+    /// Public key of the validator on the given chain
+    ///
+    /// # Errors
+    ///
+    /// * if the key is not available
+    fn public_key(&self, chain_id: &chain::Id) -> Result<PublicKey, RemoteSignerError>;
+
+    /// Sign the given bytes with the key of the validator on the given chain
+    ///
+    /// # Errors
+    ///
+    /// * if the bytes cannot be signed
+    fn sign(
+        &mut self,
+        chain_id: &chain::Id,
+        sign_bytes: &[u8],
+    ) -> Result<Signature, RemoteSignerError>;
+}
+
+/// Software signer, keeping the key in memory
+impl Signer for ed25519_consensus::SigningKey {
+    fn public_key(&self, _chain_id: &chain::Id) -> Result<PublicKey, RemoteSignerError> {
+        PublicKey::from_raw_ed25519(self.verification_key().as_bytes())
+            .ok_or_else(|| remote_signer_error("invalid Ed25519 public key"))
+    }
+
+    fn sign(
+        &mut self,
+        _chain_id: &chain::Id,
+        sign_bytes: &[u8],
+    ) -> Result<Signature, RemoteSignerError> {
+        let signature = ed25519_consensus::SigningKey::sign(self, sign_bytes);
+        Signature::try_from(signature.to_bytes().as_slice()).map_err(remote_signer_error)
+    }
+}
+
+fn remote_signer_error(description: impl ToString) -> RemoteSignerError {
+    RemoteSignerError {
+        code: Errors::Unknown as i32,
+        description: description.to_string(),
+    }
+}
+
+/// Server answering the requests of a node with a [`Signer`], for a single
+/// chain, refusing to sign messages which conflict with those it signed
+/// before
+pub struct SignerServer<S> {
+    signer: S,
+    chain_id: chain::Id,
+    state: SignState,
+}
+
+impl<S: Signer> SignerServer<S> {
+    /// Create a server signing messages of the given chain, from the given
+    /// double-sign protection state
+    pub const fn new(signer: S, chain_id: chain::Id, state: SignState) -> Self {
+        Self {
+            signer,
+            chain_id,
+            state,
+        }
+    }
+
+    /// Double-sign protection state of the server
+    pub const fn state(&self) -> &SignState {
+        &self.state
+    }
+
+    /// Answer a request of the node
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::PubKey(req) => {
+                let pub_key = self
+                    .check_chain_id(&req.chain_id)
+                    .and_then(|()| self.signer.public_key(&self.chain_id));
+                Response::PubKey(match pub_key {
+                    Ok(pub_key) => PubKeyResponse {
+                        pub_key: Some(pub_key),
+                        error: None,
+                    },
+                    Err(error) => PubKeyResponse {
+                        pub_key: None,
+                        error: Some(error),
+                    },
+                })
+            },
+            Request::SignVote(req) => Response::SignedVote(self.sign_vote(req)),
+            Request::SignProposal(req) => Response::SignedProposal(self.sign_proposal(req)),
+            Request::Ping => Response::Ping,
+        }
+    }
+
+    /// Answer the requests of the node over the given connection, until it
+    /// is closed
+    ///
+    /// # Errors
+    ///
+    /// * if the connection fails
+    /// * if the node sends a malformed request
+    pub fn serve<C: Read + Write>(&mut self, conn: &mut C) -> Result<()> {
+        while let Some(request) = read_msg::<_, Request>(conn)? {
+            let response = self.handle(request);
+            write_msg(conn, response.into())?;
+        }
+        Ok(())
+    }
+
+    /// Connect to the node listening at the given address, authenticating
+    /// with the given identity key, and answer its requests until the
+    /// connection is closed
+    ///
+    /// # Errors
+    ///
+    /// * if the node cannot be connected to
+    /// * if the connection fails
+    pub fn dial_tcp<A: ToSocketAddrs>(
+        &mut self,
+        addr: A,
+        identity_key: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+    ) -> Result<()> {
+        let stream = TcpStream::connect(addr)?;
+        let mut conn = SecretConnection::new(stream, identity_key, protocol_version)?;
+        self.serve(&mut conn)
+    }
+
+    /// Connect to the node listening on the given Unix socket, and answer
+    /// its requests until the connection is closed
+    ///
+    /// # Errors
+    ///
+    /// * if the node cannot be connected to
+    /// * if the connection fails
+    #[cfg(unix)]
+    pub fn dial_unix(&mut self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+        self.serve(&mut stream)
+    }
+
+    fn check_chain_id(&self, chain_id: &chain::Id) -> Result<(), RemoteSignerError> {
+        if *chain_id == self.chain_id {
+            Ok(())
+        } else {
+            Err(remote_signer_error(format!(
+                "unexpected chain ID {chain_id}, expected {}",
+                self.chain_id
+            )))
+        }
+    }
+
+    fn sign_vote(&mut self, req: SignVoteRequest) -> SignedVoteResponse {
+        let step = SignedMsgType::from(req.vote.vote_type).step();
+        let (height, round, timestamp) = (req.vote.height, req.vote.round, req.vote.timestamp);
+        let signature = self
+            .check_chain_id(&req.chain_id)
+            .and_then(|()| req.to_signable_vec().map_err(remote_signer_error))
+            .and_then(|sign_bytes| self.sign(height, round, step, timestamp, sign_bytes));
+        match signature {
+            Ok((signature, timestamp)) => {
+                let mut vote = req.vote;
+                vote.signature = Some(signature);
+                vote.timestamp = timestamp;
+                SignedVoteResponse {
+                    vote: Some(vote),
+                    error: None,
+                }
+            },
+            Err(error) => SignedVoteResponse {
+                vote: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    fn sign_proposal(&mut self, req: SignProposalRequest) -> SignedProposalResponse {
+        let (height, round, timestamp) = (
+            req.proposal.height,
+            req.proposal.round,
+            req.proposal.timestamp,
+        );
+        let signature = self
+            .check_chain_id(&req.chain_id)
+            .and_then(|()| req.to_signable_vec().map_err(remote_signer_error))
+            .and_then(|sign_bytes| self.sign(height, round, Step::Propose, timestamp, sign_bytes));
+        match signature {
+            Ok((signature, timestamp)) => {
+                let mut proposal = req.proposal;
+                proposal.signature = Some(signature);
+                proposal.timestamp = timestamp;
+                SignedProposalResponse {
+                    proposal: Some(proposal),
+                    error: None,
+                }
+            },
+            Err(error) => SignedProposalResponse {
+                proposal: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    /// Sign the given bytes of a message with the given HRS and timestamp,
+    /// unless that would be a double sign, and persist the new state before
+    /// returning the signature along with the timestamp it is valid with:
+    /// the one of the last signed message when it is signed again.
+    fn sign(
+        &mut self,
+        height: block::Height,
+        round: block::Round,
+        step: Step,
+        timestamp: Option<Time>,
+        sign_bytes: Vec<u8>,
+    ) -> Result<(Signature, Option<Time>), RemoteSignerError> {
+        if let Some(last) = self
+            .state
+            .check(height, round, step, &sign_bytes)
+            .map_err(remote_signer_error)?
+        {
+            let signature = Signature::try_from(last.signature).map_err(remote_signer_error)?;
+            return Ok((signature, last.timestamp));
+        }
+
+        let signature = self.signer.sign(&self.chain_id, &sign_bytes)?;
+        self.state.update(
+            height,
+            round,
+            step,
+            sign_bytes,
+            signature.as_bytes().to_vec(),
+        );
+        self.state.save().map_err(remote_signer_error)?;
+        Ok((signature, timestamp))
+    }
+}
@@ -0,0 +1,227 @@
+//! Double-sign protection state of signers.
+
+use std::{
+    fs,
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use tendermint::{block, privval::Step, Time};
+use tendermint_proto::{
+    serializers::bytes::{base64string, hexstring},
+    types as proto,
+};
+
+use crate::error::Error;
+
+/// Last message a signer signed, which it refuses to sign conflicting
+/// messages with, stored in `priv_validator_state.json`, in the same format
+/// as CometBFT's.
+///
+/// A signer may only sign messages for a height, round and step (HRS) higher
+/// than the ones of the last message it signed. Signing the same message
+/// again returns the same signature, even if only its timestamp differs, as
+/// with CometBFT's `FilePV`, while signing another message for the same HRS
+/// is refused.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignState {
+    /// Height of the last signed message
+    pub height: block::Height,
+    /// Round of the last signed message
+    #[serde(with = "round_number")]
+    pub round: block::Round,
+    /// Step of the last signed message
+    pub step: Step,
+    /// Signature of the last signed message
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "base64string")]
+    pub signature: Vec<u8>,
+    /// Sign bytes of the last signed message
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "hexstring")]
+    pub signbytes: Vec<u8>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Default for SignState {
+    fn default() -> Self {
+        Self {
+            height: block::Height::from(0_u8),
+            round: block::Round::default(),
+            step: Step::None,
+            signature: Vec::new(),
+            signbytes: Vec::new(),
+            path: None,
+        }
+    }
+}
+
+/// Signature of the last signed message, to reuse when it is signed again
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LastSignature<'a> {
+    /// Signature of the last signed message
+    pub signature: &'a [u8],
+    /// Timestamp of the last signed message, if any, which the message
+    /// signed again must carry for the signature to be valid
+    pub timestamp: Option<Time>,
+}
+
+impl SignState {
+    /// Load the state from the given `priv_validator_state.json` file, which
+    /// it is then saved to, or start from an empty state if the file does not
+    /// exist
+    ///
+    /// # Errors
+    ///
+    /// * if the file exists but cannot be read
+    /// * if its contents are not a valid state
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let mut state = if path.exists() {
+            let json = fs::read_to_string(&path)
+                .map_err(|e| Error::file_io(path.display().to_string(), e))?;
+            serde_json::from_str(&json).map_err(Error::json)?
+        } else {
+            Self::default()
+        };
+        state.path = Some(path);
+        Ok(state)
+    }
+
+    /// Save the state to the file it was loaded from, if any
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be written
+    pub fn save(&self) -> Result<(), Error> {
+        self.path.as_ref().map_or(Ok(()), |path| self.save_to(path))
+    }
+
+    /// Save the state to the given file, replacing it atomically
+    ///
+    /// # Errors
+    ///
+    /// * if the file cannot be written
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).map_err(Error::json)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&tmp_path, path))
+            .map_err(|e| Error::file_io(path.display().to_string(), e))
+    }
+
+    /// Check whether a message for the given HRS may be signed. Returns the
+    /// signature to reuse, along with its timestamp, if it is the last signed
+    /// message again, possibly with another timestamp.
+    ///
+    /// # Errors
+    ///
+    /// * if the HRS is lower than the one of the last signed message
+    /// * if another message was signed for the same HRS
+    pub fn check(
+        &self,
+        height: block::Height,
+        round: block::Round,
+        step: Step,
+        sign_bytes: &[u8],
+    ) -> Result<Option<LastSignature<'_>>, Error> {
+        let last = (self.height, self.round, self.step);
+        let double_sign = || {
+            Error::double_sign(
+                format!("{height}/{round}/{step}"),
+                format!("{}/{}/{}", last.0, last.1, last.2),
+            )
+        };
+        match (height, round, step).cmp(&last) {
+            std::cmp::Ordering::Less => Err(double_sign()),
+            std::cmp::Ordering::Equal => {
+                let last_signed = split_timestamp(step, &self.signbytes);
+                let timestamp = if self.signbytes == sign_bytes {
+                    last_signed.and_then(|(_, timestamp)| timestamp)
+                } else {
+                    match (last_signed, split_timestamp(step, sign_bytes)) {
+                        (Some((last, timestamp)), Some((msg, _))) if last == msg => timestamp,
+                        _ => return Err(double_sign()),
+                    }
+                };
+                Ok(Some(LastSignature {
+                    signature: &self.signature,
+                    timestamp,
+                }))
+            },
+            std::cmp::Ordering::Greater => Ok(None),
+        }
+    }
+
+    /// Record the signature of a message for the given HRS, which must have
+    /// been allowed by [`SignState::check`]
+    pub fn update(
+        &mut self,
+        height: block::Height,
+        round: block::Round,
+        step: Step,
+        sign_bytes: Vec<u8>,
+        signature: Vec<u8>,
+    ) {
+        self.height = height;
+        self.round = round;
+        self.step = step;
+        self.signbytes = sign_bytes;
+        self.signature = signature;
+    }
+}
+
+/// Canonical vote or proposal, as signed
+#[derive(PartialEq)]
+enum Canonical {
+    Vote(proto::CanonicalVote),
+    Proposal(proto::CanonicalProposal),
+}
+
+/// Decode the canonical vote or proposal of the given step from its sign
+/// bytes, taking its timestamp out
+fn split_timestamp(step: Step, sign_bytes: &[u8]) -> Option<(Canonical, Option<Time>)> {
+    let (canonical, timestamp) = match step {
+        Step::None => return None,
+        Step::Propose => {
+            let mut proposal =
+                proto::CanonicalProposal::decode_length_delimited(sign_bytes).ok()?;
+            let timestamp = proposal.timestamp.take();
+            (Canonical::Proposal(proposal), timestamp)
+        },
+        Step::Prevote | Step::Precommit => {
+            let mut vote = proto::CanonicalVote::decode_length_delimited(sign_bytes).ok()?;
+            let timestamp = vote.timestamp.take();
+            (Canonical::Vote(vote), timestamp)
+        },
+    };
+    let timestamp = timestamp.map(Time::try_from).transpose().ok()?;
+    Some((canonical, timestamp))
+}
+
+/// Rounds are numbers in `priv_validator_state.json`, rather than strings.
+mod round_number {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+    use tendermint::block;
+
+    pub fn serialize<S: Serializer>(
+        round: &block::Round,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        u32::from(*round).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<block::Round, D::Error> {
+        let value = i32::deserialize(deserializer)?;
+        block::Round::try_from(value)
+            .map_err(|_| D::Error::custom(format!("invalid round {value}")))
+    }
+}
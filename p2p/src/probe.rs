@@ -0,0 +1,312 @@
+//! Health probing of the persistent peers of a node.
+//!
+//! [`PeerProber`] dials each peer of a `persistent_peers` list (see
+//! [`tendermint_config::P2PConfig::persistent_peers`]), performs the
+//! [`SecretConnection`] handshake to authenticate it, and records whether the
+//! peer is reachable, whether its node ID matches the one in the list, and how
+//! long it took to establish the connection. The resulting [`ProbeReport`]
+//! renders the list of the healthy peers, in the format of the
+//! `persistent_peers` setting.
+
+use std::{
+    fmt,
+    net::TcpStream,
+    thread,
+    time::{Duration, Instant},
+};
+
+use tendermint::node;
+use tendermint_config::net;
+use tendermint_std_ext::resolve::Resolver;
+
+use crate::secret_connection::{SecretConnection, Version};
+
+/// Default time allowed to connect to a peer and to complete the handshake.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Probes peers with [`SecretConnection`] handshakes.
+pub struct PeerProber {
+    private_key: ed25519_consensus::SigningKey,
+    protocol_version: Version,
+    timeout: Duration,
+    resolver: Resolver,
+}
+
+impl PeerProber {
+    /// Creates a prober which identifies itself to the peers with the given key.
+    ///
+    /// A throwaway key is fine, as the probes do not go further than the
+    /// handshake.
+    #[must_use]
+    pub fn new(private_key: ed25519_consensus::SigningKey, protocol_version: Version) -> Self {
+        Self {
+            private_key,
+            protocol_version,
+            timeout: DEFAULT_TIMEOUT,
+            resolver: Resolver::default(),
+        }
+    }
+
+    /// Sets the time allowed to connect to each peer, and then to complete the
+    /// handshake.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Resolves the host names of the peers with the given resolver, e.g. to
+    /// override the addresses of some of them, instead of the resolver of the
+    /// operating system.
+    #[must_use]
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Probes the given peers concurrently.
+    #[must_use]
+    pub fn probe_all(&self, peers: &[net::Address]) -> ProbeReport {
+        let probes = thread::scope(|scope| {
+            // Spawn all the probes before waiting for any of them.
+            #[allow(clippy::needless_collect)]
+            let handles: Vec<_> = peers
+                .iter()
+                .map(|peer| scope.spawn(move || self.probe(peer)))
+                .collect();
+            handles
+                .into_iter()
+                .zip(peers)
+                .map(|(handle, peer)| {
+                    handle.join().unwrap_or_else(|_| PeerProbe {
+                        address: peer.clone(),
+                        outcome: ProbeOutcome::Unreachable {
+                            reason: "probe panicked".to_owned(),
+                        },
+                    })
+                })
+                .collect()
+        });
+        ProbeReport { probes }
+    }
+
+    /// Probes a single peer.
+    #[must_use]
+    pub fn probe(&self, peer: &net::Address) -> PeerProbe {
+        let outcome = match peer {
+            net::Address::Tcp {
+                peer_id,
+                host,
+                port,
+            } => self.handshake(*peer_id, host, *port),
+            net::Address::Unix { .. } => ProbeOutcome::Unreachable {
+                reason: "peers cannot be dialed over UNIX sockets".to_owned(),
+            },
+        };
+        PeerProbe {
+            address: peer.clone(),
+            outcome,
+        }
+    }
+
+    fn handshake(&self, expected: Option<node::Id>, host: &str, port: u16) -> ProbeOutcome {
+        let unreachable = |reason: String| ProbeOutcome::Unreachable { reason };
+        let start = Instant::now();
+
+        let addrs = match self.resolver.resolve(host, port) {
+            Ok(addrs) => addrs,
+            Err(e) => return unreachable(format!("failed to resolve {host}: {e}")),
+        };
+        let mut last_error = format!("{host} resolved to no address");
+        let mut stream = None;
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, self.timeout) {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                },
+                Err(e) => last_error = format!("failed to connect to {addr}: {e}"),
+            }
+        }
+        let Some(stream) = stream else {
+            return unreachable(last_error);
+        };
+
+        let timeouts = stream
+            .set_read_timeout(Some(self.timeout))
+            .and_then(|()| stream.set_write_timeout(Some(self.timeout)));
+        if let Err(e) = timeouts {
+            return unreachable(format!("failed to configure the connection: {e}"));
+        }
+        let conn =
+            match SecretConnection::new(stream, self.private_key.clone(), self.protocol_version) {
+                Ok(conn) => conn,
+                Err(e) => {
+                    return ProbeOutcome::HandshakeFailed {
+                        reason: e.detail().to_string(),
+                    }
+                },
+            };
+        let latency = start.elapsed();
+
+        let actual = conn.remote_pubkey().peer_id();
+        match expected {
+            Some(expected) if expected != actual => ProbeOutcome::IdMismatch {
+                expected,
+                actual,
+                latency,
+            },
+            _ => ProbeOutcome::Reachable {
+                id: actual,
+                latency,
+            },
+        }
+    }
+}
+
+/// The outcome of the probe of a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// The peer completed the handshake with the expected node ID, if any.
+    Reachable {
+        /// The node ID of the peer.
+        id: node::Id,
+        /// The time taken to connect to the peer and complete the handshake.
+        latency: Duration,
+    },
+    /// The peer completed the handshake with another node ID than expected,
+    /// e.g. because its node key was regenerated, or because another node now
+    /// listens on this address.
+    IdMismatch {
+        /// The node ID in the peer list.
+        expected: node::Id,
+        /// The node ID of the peer.
+        actual: node::Id,
+        /// The time taken to connect to the peer and complete the handshake.
+        latency: Duration,
+    },
+    /// The peer accepted the connection, but failed the handshake, e.g.
+    /// because it is not a Tendermint node, or timed out.
+    HandshakeFailed {
+        /// The error of the handshake.
+        reason: String,
+    },
+    /// The peer could not be connected to.
+    Unreachable {
+        /// The error of the connection.
+        reason: String,
+    },
+}
+
+/// The probe of a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerProbe {
+    /// The address of the peer, as given in the peer list.
+    pub address: net::Address,
+    /// The outcome of the probe.
+    pub outcome: ProbeOutcome,
+}
+
+impl PeerProbe {
+    /// Whether the peer completed the handshake with the expected node ID.
+    #[must_use]
+    pub const fn is_healthy(&self) -> bool {
+        matches!(self.outcome, ProbeOutcome::Reachable { .. })
+    }
+}
+
+impl fmt::Display for PeerProbe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.address)?;
+        match &self.outcome {
+            ProbeOutcome::Reachable { id, latency } => {
+                write!(f, "reachable as {id} in {latency:?}")
+            },
+            ProbeOutcome::IdMismatch {
+                expected,
+                actual,
+                latency,
+            } => write!(
+                f,
+                "node ID mismatch, expected {expected} but got {actual} in {latency:?}"
+            ),
+            ProbeOutcome::HandshakeFailed { reason } => write!(f, "handshake failed: {reason}"),
+            ProbeOutcome::Unreachable { reason } => write!(f, "unreachable: {reason}"),
+        }
+    }
+}
+
+/// The probes of a list of peers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProbeReport {
+    /// The probes, in the order of the peer list.
+    pub probes: Vec<PeerProbe>,
+}
+
+impl ProbeReport {
+    /// The healthy peers, without duplicates, in the order of the peer list.
+    ///
+    /// The node ID of the peers which were listed without one is filled in
+    /// with the ID they authenticated with.
+    #[must_use]
+    pub fn healthy_peers(&self) -> Vec<net::Address> {
+        let mut peers: Vec<net::Address> = Vec::new();
+        for probe in &self.probes {
+            if let (net::Address::Tcp { host, port, .. }, ProbeOutcome::Reachable { id, .. }) =
+                (&probe.address, &probe.outcome)
+            {
+                let peer = net::Address::Tcp {
+                    peer_id: Some(*id),
+                    host: host.clone(),
+                    port: *port,
+                };
+                if !peers.contains(&peer) {
+                    peers.push(peer);
+                }
+            }
+        }
+        peers
+    }
+
+    /// The healthy peers, formatted as the `persistent_peers` setting:
+    /// comma-separated `<id>@<host>:<port>` addresses.
+    #[must_use]
+    pub fn cleaned_peer_list(&self) -> String {
+        self.healthy_peers()
+            .iter()
+            .filter_map(|peer| match peer {
+                net::Address::Tcp {
+                    peer_id: Some(id),
+                    host,
+                    port,
+                } => Some(format!("{id}@{host}:{port}")),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl fmt::Display for ProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for probe in &self.probes {
+            writeln!(f, "{probe}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `persistent_peers` setting: comma-separated `<id>@<host>:<port>`
+/// addresses, optionally prefixed with `tcp://`.
+///
+/// # Errors
+///
+/// Returns the error of the first address which fails to parse.
+pub fn parse_peer_list(peers: &str) -> Result<Vec<net::Address>, tendermint_config::Error> {
+    peers
+        .split(',')
+        .map(str::trim)
+        .filter(|peer| !peer.is_empty())
+        .map(str::parse)
+        .collect()
+}
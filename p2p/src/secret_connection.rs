@@ -23,6 +23,8 @@ use tendermint_proto::v0_37 as proto;
 use tendermint_std_ext::TryClone;
 use x25519_dalek::{EphemeralSecret, PublicKey as EphemeralPublic};
 
+#[cfg(feature = "async")]
+pub use self::asynchronous::{AsyncSecretConnection, AsyncSecretReadHalf, AsyncSecretWriteHalf};
 pub use self::{
     kdf::Kdf,
     nonce::{Nonce, SIZE as NONCE_SIZE},
@@ -34,6 +36,9 @@ use crate::error::Error;
 #[cfg(feature = "amino")]
 mod amino_types;
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+
 mod kdf;
 mod nonce;
 mod protocol;
@@ -241,11 +246,17 @@ macro_rules! checked_io {
 /// one can either read from the connection or write to it at a given time, but
 /// not both simultaneously).
 ///
-/// If, however, the underlying I/O handler class implements
+/// If, however, the underlying I/O handler can be read from and written to
+/// through shared references, as [`std::net::TcpStream`] can, then you can use
+/// [`SecretConnection::into_split`] to split the `SecretConnection` into its
+/// reading and writing halves, which share the I/O handler but each hold the
+/// cipher state of their direction. Each of these halves can then be used in
+/// a separate thread to facilitate full-duplex communication.
+///
+/// Alternatively, if the underlying I/O handler class implements
 /// [`tendermint_std_ext::TryClone`], then you can use
 /// [`SecretConnection::split`] to split the `SecretConnection` into its
-/// sending and receiving halves. Each of these halves can then be used in a
-/// separate thread to facilitate full-duplex communication.
+/// sending and receiving halves, each with its own clone of the I/O handler.
 ///
 /// ## Contracts
 ///
@@ -323,6 +334,38 @@ impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
     }
 }
 
+impl<IoHandler> SecretConnection<IoHandler>
+where
+    for<'a> &'a IoHandler: Read + Write,
+{
+    /// Splits the connection into its reading and writing halves, which can
+    /// be used concurrently, e.g. in separate threads, to facilitate
+    /// full-duplex communications.
+    ///
+    /// The halves share the underlying I/O handler, which must thus support
+    /// being read from and written to through shared references, as
+    /// [`std::net::TcpStream`] does. A read or write failure of either half
+    /// terminates both of them.
+    pub fn into_split(self) -> (SecretReadHalf<IoHandler>, SecretWriteHalf<IoHandler>) {
+        let remote_pubkey = self.remote_pubkey.expect("remote_pubkey to be initialized");
+        let io_handler = Arc::new(self.io_handler);
+        (
+            SecretReadHalf {
+                io_handler: io_handler.clone(),
+                remote_pubkey,
+                state: self.recv_state,
+                terminate: self.terminate.clone(),
+            },
+            SecretWriteHalf {
+                io_handler,
+                remote_pubkey,
+                state: self.send_state,
+                terminate: self.terminate,
+            },
+        )
+    }
+}
+
 impl<IoHandler> SecretConnection<IoHandler>
 where
     IoHandler: TryClone,
@@ -447,6 +490,66 @@ impl<IoHandler: Read> Read for Receiver<IoHandler> {
     }
 }
 
+/// The reading half of a [`SecretConnection`], see
+/// [`SecretConnection::into_split`].
+pub struct SecretReadHalf<IoHandler> {
+    io_handler: Arc<IoHandler>,
+    remote_pubkey: PublicKey,
+    state: ReceiveState,
+    terminate: Arc<AtomicBool>,
+}
+
+impl<IoHandler> SecretReadHalf<IoHandler> {
+    /// Returns the remote pubkey.
+    pub const fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey
+    }
+}
+
+impl<IoHandler> Read for SecretReadHalf<IoHandler>
+where
+    for<'a> &'a IoHandler: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        checked_io!(
+            self.terminate,
+            read_and_decrypt(&mut &*self.io_handler, &mut self.state, buf)
+        )
+    }
+}
+
+/// The writing half of a [`SecretConnection`], see
+/// [`SecretConnection::into_split`].
+pub struct SecretWriteHalf<IoHandler> {
+    io_handler: Arc<IoHandler>,
+    remote_pubkey: PublicKey,
+    state: SendState,
+    terminate: Arc<AtomicBool>,
+}
+
+impl<IoHandler> SecretWriteHalf<IoHandler> {
+    /// Returns the remote pubkey.
+    pub const fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey
+    }
+}
+
+impl<IoHandler> Write for SecretWriteHalf<IoHandler>
+where
+    for<'a> &'a IoHandler: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        checked_io!(
+            self.terminate,
+            encrypt_and_write(&mut &*self.io_handler, &mut self.state, buf)
+        )
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        checked_io!(self.terminate, (&*self.io_handler).flush())
+    }
+}
+
 /// Returns `remote_eph_pubkey`
 fn share_eph_pubkey<IoHandler: Read + Write + Send + Sync>(
     handler: &mut IoHandler,
@@ -591,35 +694,16 @@ fn decrypt(
     Ok(in_out.len())
 }
 
-fn read_and_decrypt<IoHandler: Read>(
-    io_handler: &mut IoHandler,
+/// Decrypts a sealed frame of `TAG_SIZE` + `TOTAL_FRAME_SIZE` and returns
+/// the chunk of data it carries.
+fn open_frame(
+    sealed_frame: &[u8; TAG_SIZE + TOTAL_FRAME_SIZE],
     recv_state: &mut ReceiveState,
-    data: &mut [u8],
-) -> io::Result<usize> {
-    if !recv_state.buffer.is_empty() {
-        let n = cmp::min(data.len(), recv_state.buffer.len());
-        data.copy_from_slice(&recv_state.buffer[..n]);
-        let mut leftover_portion = vec![
-            0;
-            recv_state
-                .buffer
-                .len()
-                .checked_sub(n)
-                .expect("leftover calculation failed")
-        ];
-        leftover_portion.clone_from_slice(&recv_state.buffer[n..]);
-        recv_state.buffer = leftover_portion;
-
-        return Ok(n);
-    }
-
-    let mut sealed_frame = [0_u8; TAG_SIZE + TOTAL_FRAME_SIZE];
-    io_handler.read_exact(&mut sealed_frame)?;
-
+) -> io::Result<Vec<u8>> {
     // decrypt the frame
     let mut frame = [0_u8; TOTAL_FRAME_SIZE];
     let res = decrypt(
-        &sealed_frame,
+        sealed_frame,
         &recv_state.cipher,
         &recv_state.nonce,
         &mut frame,
@@ -649,9 +733,39 @@ fn read_and_decrypt<IoHandler: Read>(
                 .expect("chunk size addition overflow"))],
     );
 
+    Ok(chunk)
+}
+
+fn read_and_decrypt<IoHandler: Read>(
+    io_handler: &mut IoHandler,
+    recv_state: &mut ReceiveState,
+    data: &mut [u8],
+) -> io::Result<usize> {
+    if !recv_state.buffer.is_empty() {
+        let n = cmp::min(data.len(), recv_state.buffer.len());
+        data[..n].copy_from_slice(&recv_state.buffer[..n]);
+        let mut leftover_portion = vec![
+            0;
+            recv_state
+                .buffer
+                .len()
+                .checked_sub(n)
+                .expect("leftover calculation failed")
+        ];
+        leftover_portion.clone_from_slice(&recv_state.buffer[n..]);
+        recv_state.buffer = leftover_portion;
+
+        return Ok(n);
+    }
+
+    let mut sealed_frame = [0_u8; TAG_SIZE + TOTAL_FRAME_SIZE];
+    io_handler.read_exact(&mut sealed_frame)?;
+    let mut chunk = open_frame(&sealed_frame, recv_state)?;
+
     let n = cmp::min(data.len(), chunk.len());
     data[..n].copy_from_slice(&chunk[..n]);
-    recv_state.buffer.copy_from_slice(&chunk[n..]);
+    // Keep what does not fit in `data` for the next reads.
+    recv_state.buffer = chunk.split_off(n);
 
     Ok(n)
 }
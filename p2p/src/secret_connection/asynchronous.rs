@@ -0,0 +1,421 @@
+//! Asynchronous `SecretConnection`, for streams implementing Tokio's
+//! [`AsyncRead`] and [`AsyncWrite`].
+
+use std::{
+    cmp, io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+};
+
+use tokio::io::{
+    split, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf, ReadHalf, WriteHalf,
+};
+
+use super::{
+    encrypt, open_frame, Handshake, Nonce, PublicKey, ReceiveState, SendState, Version,
+    DATA_MAX_SIZE, TAG_SIZE, TOTAL_FRAME_SIZE,
+};
+use crate::error::Error;
+
+/// Size of a sealed frame, as written to the underlying stream
+const SEALED_FRAME_SIZE: usize = TAG_SIZE + TOTAL_FRAME_SIZE;
+
+/// Encrypted connection between peers in a Tendermint network, over an
+/// asynchronous stream, e.g. a [`tokio::net::TcpStream`].
+///
+/// This is the asynchronous counterpart of the
+/// [`SecretConnection`](super::SecretConnection), with the same handshake
+/// and framing, so that both can talk to each other. It implements
+/// [`AsyncRead`] and [`AsyncWrite`], and can be split into halves to be used
+/// by separate tasks with [`AsyncSecretConnection::into_split`].
+///
+/// ## Connection integrity and failures
+///
+/// As for the `SecretConnection`, a read or write failure terminates the
+/// connection, which must be dropped before reconnecting to the remote peer.
+///
+/// ## Buffering
+///
+/// The data is written to the underlying stream in frames of at most
+/// [`DATA_MAX_SIZE`] bytes. A frame may still be buffered once a write
+/// returns, so the connection must be flushed for the data to be sent.
+pub struct AsyncSecretConnection<S> {
+    io: S,
+    remote_pubkey: Option<PublicKey>,
+    reader: FrameReader,
+    writer: FrameWriter,
+    terminate: Arc<AtomicBool>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncSecretConnection<S> {
+    /// Performs a handshake over the given stream and returns a new
+    /// `AsyncSecretConnection`.
+    ///
+    /// # Errors
+    ///
+    /// * if sharing of the pubkey fails
+    /// * if sharing of the signature fails
+    /// * if receiving the signature fails
+    pub async fn new(
+        mut io: S,
+        local_privkey: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+    ) -> Result<Self, Error> {
+        // Start a handshake process.
+        let local_pubkey = PublicKey::from(&local_privkey);
+        let (mut h, local_eph_pubkey) = Handshake::new(local_privkey, protocol_version);
+
+        // Write local ephemeral pubkey and receive one too.
+        io.write_all(&protocol_version.encode_initial_handshake(&local_eph_pubkey))
+            .await?;
+        io.flush().await?;
+
+        let response_len = io.read_u8().await?;
+        let mut buf = vec![0; response_len as usize];
+        io.read_exact(&mut buf).await?;
+        let remote_eph_pubkey = protocol_version.decode_initial_handshake(&buf)?;
+
+        // Compute a local signature (also recv_cipher & send_cipher)
+        let mut h = h.got_key(remote_eph_pubkey)?;
+
+        let mut sc = Self {
+            io,
+            remote_pubkey: None,
+            reader: FrameReader::new(ReceiveState {
+                cipher: h.state.recv_cipher.clone(),
+                nonce: Nonce::default(),
+                buffer: vec![],
+            }),
+            writer: FrameWriter::new(SendState {
+                cipher: h.state.send_cipher.clone(),
+                nonce: Nonce::default(),
+            }),
+            terminate: Arc::new(AtomicBool::new(false)),
+        };
+
+        // Share each other's pubkey & challenge signature.
+        // NOTE: the data must be encrypted/decrypted using ciphers.
+        let buf = match local_pubkey {
+            PublicKey::Ed25519(ref pk) => {
+                protocol_version.encode_auth_signature(pk, &h.state.local_signature)
+            },
+        };
+        sc.write_all(&buf).await?;
+        sc.flush().await?;
+
+        let mut buf = vec![0; protocol_version.auth_sig_msg_response_len()];
+        sc.read_exact(&mut buf).await?;
+        let auth_sig_msg = protocol_version.decode_auth_signature(&buf)?;
+
+        // Authenticate remote pubkey.
+        let remote_pubkey = h.got_signature(auth_sig_msg)?;
+
+        // All good!
+        sc.remote_pubkey = Some(remote_pubkey);
+        Ok(sc)
+    }
+}
+
+impl<S> AsyncSecretConnection<S> {
+    /// Returns the remote pubkey. Panics if there's no key.
+    pub fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey.expect("remote_pubkey uninitialized")
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite> AsyncSecretConnection<S> {
+    /// Splits the connection into its reading and writing halves, which can
+    /// be used concurrently, e.g. in separate tasks, to facilitate
+    /// full-duplex communications.
+    ///
+    /// The halves share the underlying stream through [`tokio::io::split`].
+    /// A read or write failure of either half terminates both of them.
+    pub fn into_split(self) -> (AsyncSecretReadHalf<S>, AsyncSecretWriteHalf<S>) {
+        let remote_pubkey = self.remote_pubkey.expect("remote_pubkey to be initialized");
+        let (read_half, write_half) = split(self.io);
+        (
+            AsyncSecretReadHalf {
+                io: read_half,
+                remote_pubkey,
+                reader: self.reader,
+                terminate: self.terminate.clone(),
+            },
+            AsyncSecretWriteHalf {
+                io: write_half,
+                remote_pubkey,
+                writer: self.writer,
+                terminate: self.terminate,
+            },
+        )
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for AsyncSecretConnection<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.reader.poll_read(&mut this.io, cx, buf)
+        })
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for AsyncSecretConnection<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.writer.poll_write(&mut this.io, cx, buf)
+        })
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || this.writer.poll_flush(&mut this.io, cx))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.writer.poll_shutdown(&mut this.io, cx)
+        })
+    }
+}
+
+/// The reading half of an [`AsyncSecretConnection`], see
+/// [`AsyncSecretConnection::into_split`].
+pub struct AsyncSecretReadHalf<S> {
+    io: ReadHalf<S>,
+    remote_pubkey: PublicKey,
+    reader: FrameReader,
+    terminate: Arc<AtomicBool>,
+}
+
+impl<S> AsyncSecretReadHalf<S> {
+    /// Returns the remote pubkey.
+    pub const fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey
+    }
+}
+
+impl<S: AsyncRead> AsyncRead for AsyncSecretReadHalf<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.reader.poll_read(&mut this.io, cx, buf)
+        })
+    }
+}
+
+/// The writing half of an [`AsyncSecretConnection`], see
+/// [`AsyncSecretConnection::into_split`].
+pub struct AsyncSecretWriteHalf<S> {
+    io: WriteHalf<S>,
+    remote_pubkey: PublicKey,
+    writer: FrameWriter,
+    terminate: Arc<AtomicBool>,
+}
+
+impl<S> AsyncSecretWriteHalf<S> {
+    /// Returns the remote pubkey.
+    pub const fn remote_pubkey(&self) -> PublicKey {
+        self.remote_pubkey
+    }
+}
+
+impl<S: AsyncWrite> AsyncWrite for AsyncSecretWriteHalf<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.writer.poll_write(&mut this.io, cx, buf)
+        })
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || this.writer.poll_flush(&mut this.io, cx))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        poll_checked(&this.terminate, || {
+            this.writer.poll_shutdown(&mut this.io, cx)
+        })
+    }
+}
+
+/// The asynchronous counterpart of the `checked_io!` macro: fails once the
+/// connection was terminated, and terminates it upon failure.
+fn poll_checked<T>(
+    terminate: &AtomicBool,
+    f: impl FnOnce() -> Poll<io::Result<T>>,
+) -> Poll<io::Result<T>> {
+    if terminate.load(Ordering::SeqCst) {
+        return Poll::Ready(Err(io::Error::new(
+            io::ErrorKind::Other,
+            "secret connection was terminated elsewhere by previous error",
+        )));
+    }
+    let result = f();
+    if let Poll::Ready(Err(_)) = result {
+        terminate.store(true, Ordering::SeqCst);
+    }
+    result
+}
+
+/// Reads sealed frames from a stream, which may be interrupted at any point,
+/// and decrypts them.
+struct FrameReader {
+    state: ReceiveState,
+    sealed_frame: Box<[u8; SEALED_FRAME_SIZE]>,
+    filled: usize,
+}
+
+impl FrameReader {
+    fn new(state: ReceiveState) -> Self {
+        Self {
+            state,
+            sealed_frame: Box::new([0_u8; SEALED_FRAME_SIZE]),
+            filled: 0,
+        }
+    }
+
+    fn poll_read<R: AsyncRead + Unpin>(
+        &mut self,
+        io: &mut R,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        while self.state.buffer.is_empty() {
+            while self.filled < SEALED_FRAME_SIZE {
+                let mut frame_buf = ReadBuf::new(&mut self.sealed_frame[self.filled..]);
+                ready!(Pin::new(&mut *io).poll_read(cx, &mut frame_buf))?;
+                let n = frame_buf.filled().len();
+                if n == 0 {
+                    // The stream ended, which is fine in between frames only.
+                    return Poll::Ready(if self.filled == 0 {
+                        Ok(())
+                    } else {
+                        Err(io::ErrorKind::UnexpectedEof.into())
+                    });
+                }
+                self.filled += n;
+            }
+            self.filled = 0;
+            self.state.buffer = open_frame(&self.sealed_frame, &mut self.state)?;
+        }
+
+        let n = cmp::min(buf.remaining(), self.state.buffer.len());
+        buf.put_slice(&self.state.buffer[..n]);
+        // Keep what does not fit in `buf` for the next reads.
+        self.state.buffer.drain(..n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Encrypts data into sealed frames, and writes them to a stream, which may
+/// be interrupted at any point.
+struct FrameWriter {
+    state: SendState,
+    sealed_frame: Vec<u8>,
+    written: usize,
+}
+
+impl FrameWriter {
+    const fn new(state: SendState) -> Self {
+        Self {
+            state,
+            sealed_frame: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Writes what is left of the last sealed frame.
+    fn poll_write_frame<W: AsyncWrite + Unpin>(
+        &mut self,
+        io: &mut W,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.written < self.sealed_frame.len() {
+            let n = ready!(Pin::new(&mut *io).poll_write(cx, &self.sealed_frame[self.written..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.written += n;
+        }
+        self.sealed_frame.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_write<W: AsyncWrite + Unpin>(
+        &mut self,
+        io: &mut W,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        ready!(self.poll_write_frame(io, cx))?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let chunk = &buf[..cmp::min(buf.len(), DATA_MAX_SIZE)];
+        let mut sealed_frame = [0_u8; SEALED_FRAME_SIZE];
+        encrypt(
+            chunk,
+            &self.state.cipher,
+            &self.state.nonce,
+            &mut sealed_frame,
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        self.state.nonce.increment();
+        self.sealed_frame.extend_from_slice(&sealed_frame);
+
+        // The chunk is accepted once encrypted: the frame is written right
+        // away if possible, or else by the next write or flush.
+        if let Poll::Ready(Err(e)) = self.poll_write_frame(io, cx) {
+            return Poll::Ready(Err(e));
+        }
+        Poll::Ready(Ok(chunk.len()))
+    }
+
+    fn poll_flush<W: AsyncWrite + Unpin>(
+        &mut self,
+        io: &mut W,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_frame(io, cx))?;
+        Pin::new(io).poll_flush(cx)
+    }
+
+    fn poll_shutdown<W: AsyncWrite + Unpin>(
+        &mut self,
+        io: &mut W,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_frame(io, cx))?;
+        Pin::new(io).poll_shutdown(cx)
+    }
+}
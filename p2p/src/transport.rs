@@ -6,6 +6,10 @@ use std::net::{SocketAddr, ToSocketAddrs};
 use eyre::Result;
 use tendermint::{node, public_key::PublicKey};
 
+pub mod tcp;
+
+pub use tcp::TcpTransport;
+
 /// Information which resources to bind to and how to identify on the network.
 pub struct BindInfo<A>
 where
@@ -37,7 +41,7 @@ where
 }
 
 /// Known list of typed streams.
-#[derive(Clone, Copy, Hash, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 pub enum StreamId {
     /// Stream to exchange message concerning Peer Exchange.
     Pex,
@@ -61,7 +65,7 @@ pub trait StreamSend {
     /// * If the underlying I/O operations fail.
     /// * If the stream is closed.
     /// * If the peer is gone
-    fn send<B: AsRef<[u8]>>(msg: B) -> Result<()>;
+    fn send<B: AsRef<[u8]>>(&mut self, msg: B) -> Result<()>;
 }
 
 /// Trait which describes the core concept of a connection between two peers established by
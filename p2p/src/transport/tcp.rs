@@ -0,0 +1,321 @@
+//! Default [`Transport`] implementation: plain TCP connections authenticated and encrypted with
+//! [`SecretConnection`].
+//!
+//! Messages exchanged over the streams of a [`TcpConnection`] are framed with a uvarint length
+//! prefix, like the delimited protobuf messages exchanged by Tendermint nodes.
+
+use std::{
+    io::{self, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::Mutex,
+};
+
+use eyre::{eyre, Result};
+use tendermint::public_key::PublicKey;
+
+use super::{BindInfo, ConnectInfo, Connection, Endpoint, StreamId, StreamSend, Transport};
+use crate::{
+    error::Error,
+    secret_connection::{self, Receiver, SecretConnection, Sender, Version},
+};
+
+/// Maximum size of a single framed message exchanged over a stream.
+pub const MAX_MSG_SIZE: usize = 10 * 1024 * 1024;
+
+/// Transport establishing TCP connections secured by [`SecretConnection`].
+pub struct TcpTransport {
+    private_key: ed25519_consensus::SigningKey,
+    protocol_version: Version,
+}
+
+impl TcpTransport {
+    /// Creates a new transport which identifies the local node with the given key.
+    #[must_use]
+    pub const fn new(
+        private_key: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+    ) -> Self {
+        Self {
+            private_key,
+            protocol_version,
+        }
+    }
+}
+
+impl<A> Transport<A> for TcpTransport
+where
+    A: ToSocketAddrs,
+{
+    type Connection = TcpConnection;
+    type Endpoint = TcpEndpoint;
+    type Incoming = TcpIncoming;
+
+    fn bind(self, bind_info: BindInfo<A>) -> Result<(Self::Endpoint, Self::Incoming)> {
+        let local_key = secret_connection::PublicKey::from(&self.private_key);
+        if to_public_key(local_key) != bind_info.public_key {
+            return Err(Error::bind_key_mismatch().into());
+        }
+
+        let listener = TcpListener::bind(bind_info.bind_addrs)?;
+        let listen_addrs = vec![listener.local_addr()?];
+        let advertise_addrs = bind_info.advertise_addrs.to_socket_addrs()?.collect();
+
+        let endpoint = TcpEndpoint {
+            private_key: self.private_key.clone(),
+            protocol_version: self.protocol_version,
+            listen_addrs,
+            advertise_addrs,
+        };
+        let incoming = TcpIncoming {
+            listener,
+            private_key: self.private_key,
+            protocol_version: self.protocol_version,
+            advertise_addrs: endpoint.advertise_addrs.clone(),
+        };
+
+        Ok((endpoint, incoming))
+    }
+}
+
+/// Handle used to dial remote peers, obtained from [`TcpTransport::bind`].
+///
+/// The addresses of [`ConnectInfo`] are resolved as any [`ToSocketAddrs`]: to have their host
+/// names resolved by a [`Resolver`] rather than by the operating system, e.g. to override the
+/// addresses of some peers, pass the addresses returned by [`Resolver::addr`].
+///
+/// [`Resolver`]: tendermint_std_ext::resolve::Resolver
+/// [`Resolver::addr`]: tendermint_std_ext::resolve::Resolver::addr
+pub struct TcpEndpoint {
+    private_key: ed25519_consensus::SigningKey,
+    protocol_version: Version,
+    listen_addrs: Vec<SocketAddr>,
+    advertise_addrs: Vec<SocketAddr>,
+}
+
+impl<A> Endpoint<A> for TcpEndpoint
+where
+    A: ToSocketAddrs,
+{
+    type Connection = TcpConnection;
+
+    fn connect(&self, info: ConnectInfo<A>) -> Result<Self::Connection> {
+        let stream = TcpStream::connect(info.addrs)?;
+        let conn = TcpConnection::handshake(
+            stream,
+            self.private_key.clone(),
+            self.protocol_version,
+            self.advertise_addrs.clone(),
+        )?;
+
+        let actual = conn.remote_key.peer_id();
+        if actual != info.id {
+            conn.close()?;
+            return Err(Error::peer_id_mismatch(info.id, actual).into());
+        }
+
+        Ok(conn)
+    }
+
+    fn listen_addrs(&self) -> Vec<SocketAddr> {
+        self.listen_addrs.clone()
+    }
+}
+
+impl Drop for TcpEndpoint {
+    fn drop(&mut self) {
+        // Nothing to release: the listener is owned by `TcpIncoming`.
+    }
+}
+
+/// Infinite stream of inbound connections, obtained from [`TcpTransport::bind`].
+///
+/// Each accepted TCP connection goes through the [`SecretConnection`] handshake before being
+/// yielded; failed handshakes are yielded as errors.
+pub struct TcpIncoming {
+    listener: TcpListener,
+    private_key: ed25519_consensus::SigningKey,
+    protocol_version: Version,
+    advertise_addrs: Vec<SocketAddr>,
+}
+
+impl Iterator for TcpIncoming {
+    type Item = Result<TcpConnection>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let conn = self
+            .listener
+            .accept()
+            .map_err(Into::into)
+            .and_then(|(stream, _)| {
+                TcpConnection::handshake(
+                    stream,
+                    self.private_key.clone(),
+                    self.protocol_version,
+                    self.advertise_addrs.clone(),
+                )
+            });
+
+        Some(conn)
+    }
+}
+
+/// An authenticated connection to a remote peer over TCP.
+///
+/// As long as the connection is not multiplexed, only a single stream can be opened on it.
+pub struct TcpConnection {
+    conn: Mutex<Option<SecretConnection<TcpStream>>>,
+    stream: TcpStream,
+    remote_key: secret_connection::PublicKey,
+    local_addr: SocketAddr,
+    remote_addr: SocketAddr,
+    advertise_addrs: Vec<SocketAddr>,
+}
+
+impl TcpConnection {
+    fn handshake(
+        stream: TcpStream,
+        private_key: ed25519_consensus::SigningKey,
+        protocol_version: Version,
+        advertise_addrs: Vec<SocketAddr>,
+    ) -> Result<Self> {
+        let local_addr = stream.local_addr()?;
+        let remote_addr = stream.peer_addr()?;
+        let handle = stream.try_clone()?;
+        let conn = SecretConnection::new(stream, private_key, protocol_version)?;
+
+        Ok(Self {
+            remote_key: conn.remote_pubkey(),
+            conn: Mutex::new(Some(conn)),
+            stream: handle,
+            local_addr,
+            remote_addr,
+            advertise_addrs,
+        })
+    }
+}
+
+impl Connection for TcpConnection {
+    type Error = eyre::Report;
+    type StreamRead = TcpStreamRead;
+    type StreamSend = TcpStreamSend;
+
+    fn advertised_addrs(&self) -> Vec<SocketAddr> {
+        self.advertise_addrs.clone()
+    }
+
+    fn close(&self) -> Result<()> {
+        match self.stream.shutdown(Shutdown::Both) {
+            Err(e) if e.kind() != io::ErrorKind::NotConnected => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    fn open_bidirectional(
+        &self,
+        stream_id: StreamId,
+    ) -> Result<(Self::StreamRead, Self::StreamSend), Self::Error> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|_| eyre!("connection lock poisoned"))?
+            .take()
+            .ok_or_else(|| Error::stream_unavailable(format!("{stream_id:?}")))?;
+        let (sender, receiver) = conn.split()?;
+
+        Ok((TcpStreamRead { receiver }, TcpStreamSend { sender }))
+    }
+
+    fn public_key(&self) -> PublicKey {
+        to_public_key(self.remote_key)
+    }
+
+    fn remote_addr(&self) -> SocketAddr {
+        self.remote_addr
+    }
+}
+
+/// Read end of a stream opened on a [`TcpConnection`].
+pub struct TcpStreamRead {
+    receiver: Receiver<TcpStream>,
+}
+
+impl Iterator for TcpStreamRead {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match read_delimited(&mut self.receiver, MAX_MSG_SIZE) {
+            Ok(Some(msg)) => Some(Ok(msg)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Send end of a stream opened on a [`TcpConnection`].
+pub struct TcpStreamSend {
+    sender: Sender<TcpStream>,
+}
+
+impl StreamSend for TcpStreamSend {
+    fn send<B: AsRef<[u8]>>(&mut self, msg: B) -> Result<()> {
+        let msg = msg.as_ref();
+        if msg.len() > MAX_MSG_SIZE {
+            return Err(Error::message_too_large(msg.len(), MAX_MSG_SIZE).into());
+        }
+
+        let mut buf = Vec::with_capacity(msg.len() + prost::length_delimiter_len(msg.len()));
+        prost::encode_length_delimiter(msg.len(), &mut buf)
+            .map_err(|e| eyre!("failed to encode message length: {}", e))?;
+        buf.extend_from_slice(msg);
+        self.sender.write_all(&buf)?;
+        self.sender.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Reads a single uvarint length-prefixed message of at most `max_size` bytes. Returns `None` if
+/// the stream was closed before the start of a new message.
+pub(crate) fn read_delimited<R: Read>(reader: &mut R, max_size: usize) -> Result<Option<Vec<u8>>> {
+    let mut len_buf = Vec::with_capacity(10);
+    loop {
+        let mut byte = [0_u8; 1];
+        match reader.read(&mut byte) {
+            Ok(0) if len_buf.is_empty() => return Ok(None),
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+            Ok(_) => {},
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+
+        len_buf.push(byte[0]);
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+        if len_buf.len() == 10 {
+            return Err(eyre!("invalid message length prefix"));
+        }
+    }
+
+    let len = prost::decode_length_delimiter(len_buf.as_slice())
+        .map_err(|e| eyre!("invalid message length prefix: {}", e))?;
+    if len > max_size {
+        return Err(Error::message_too_large(len, max_size).into());
+    }
+
+    let mut msg = vec![0_u8; len];
+    reader.read_exact(&mut msg)?;
+
+    Ok(Some(msg))
+}
+
+fn to_public_key(key: secret_connection::PublicKey) -> PublicKey {
+    match key {
+        secret_connection::PublicKey::Ed25519(pk) => PublicKey::from_raw_ed25519(pk.as_bytes())
+            .expect("verification key bytes are always a valid ed25519 public key"),
+    }
+}
@@ -0,0 +1,675 @@
+//! Conversions between the messages of consecutive Tendermint versions.
+//!
+//! The messages whose definitions correspond in both versions are converted
+//! field by field, with the implementations generated by the proto-compiler
+//! in `tendermint/convert.rs`. The struct literals of these implementations
+//! name every field, so that a field added upstream fails compilation until
+//! the conversions are generated again. The messages whose definitions differ
+//! are converted by hand below.
+//!
+//! The conversions which may lose information, e.g. of a request the other
+//! version does not support, implement `TryFrom` instead of `From`.
+
+use bytes::Bytes;
+
+use crate::{prelude::*, v0_34, v0_37, v0_38, Error};
+
+/// Convert the fields of a message, given as `field: mode` where the mode is
+/// the conversion of the value of the field:
+///
+/// - none: the value is moved as is.
+/// - `opt`, `vec`: each of the optional or repeated values is converted.
+/// - `try_opt`, `try_vec`: the same, with fallible conversions.
+macro_rules! convert_message {
+    (From, $($from:ident)::+ => $($to:ident)::+ {}) => {
+        impl From<$($from)::+> for $($to)::+ {
+            fn from(_: $($from)::+) -> Self {
+                Self {}
+            }
+        }
+    };
+    (From, $($from:ident)::+ => $($to:ident)::+ { $($field:ident $(: $mode:ident)?,)+ }) => {
+        impl From<$($from)::+> for $($to)::+ {
+            fn from(value: $($from)::+) -> Self {
+                Self {
+                    $($field: convert_field!($($mode)? value.$field),)+
+                }
+            }
+        }
+    };
+    (TryFrom, $($from:ident)::+ => $($to:ident)::+ { $($field:ident $(: $mode:ident)?,)+ }) => {
+        impl TryFrom<$($from)::+> for $($to)::+ {
+            type Error = Error;
+
+            fn try_from(value: $($from)::+) -> Result<Self, Self::Error> {
+                Ok(Self {
+                    $($field: convert_field!($($mode)? value.$field),)+
+                })
+            }
+        }
+    };
+}
+
+/// Convert the variants of a `oneof` enum, given as `Variant: mode` where the
+/// mode is `into` or `try_into` for a message, and none for a scalar value.
+macro_rules! convert_oneof {
+    (From, $($from:ident)::+ => $($to:ident)::+ { $($variant:ident $(: $mode:ident)?,)+ }) => {
+        impl From<$($from)::+> for $($to)::+ {
+            fn from(value: $($from)::+) -> Self {
+                type Source = $($from)::+;
+
+                match value {
+                    $(Source::$variant(value) => Self::$variant(convert_field!($($mode)? value)),)+
+                }
+            }
+        }
+    };
+    (TryFrom, $($from:ident)::+ => $($to:ident)::+ { $($variant:ident $(: $mode:ident)?,)+ }) => {
+        impl TryFrom<$($from)::+> for $($to)::+ {
+            type Error = Error;
+
+            fn try_from(value: $($from)::+) -> Result<Self, Self::Error> {
+                type Source = $($from)::+;
+
+                Ok(match value {
+                    $(Source::$variant(value) => Self::$variant(convert_field!($($mode)? value)),)+
+                })
+            }
+        }
+    };
+}
+
+macro_rules! convert_field {
+    (opt $value:expr) => {
+        $value.map(Into::into)
+    };
+    (vec $value:expr) => {
+        $value.into_iter().map(Into::into).collect()
+    };
+    (into $value:expr) => {
+        $value.into()
+    };
+    (try_opt $value:expr) => {
+        $value.map(TryInto::try_into).transpose()?
+    };
+    (try_vec $value:expr) => {
+        $value
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?
+    };
+    (try_into $value:expr) => {
+        $value.try_into()?
+    };
+    ($value:expr) => {
+        $value
+    };
+}
+
+include!("tendermint/convert.rs");
+
+/// The minimum time between consecutive blocks, in milliseconds, set by
+/// default by Tendermint 0.34, which requires it to be positive.
+const DEFAULT_TIME_IOTA_MS: i64 = 1000;
+
+impl From<v0_34::types::BlockParams> for v0_37::types::BlockParams {
+    fn from(value: v0_34::types::BlockParams) -> Self {
+        Self {
+            max_bytes: value.max_bytes,
+            max_gas: value.max_gas,
+        }
+    }
+}
+
+impl From<v0_37::types::BlockParams> for v0_34::types::BlockParams {
+    fn from(value: v0_37::types::BlockParams) -> Self {
+        Self {
+            max_bytes: value.max_bytes,
+            max_gas: value.max_gas,
+            time_iota_ms: DEFAULT_TIME_IOTA_MS,
+        }
+    }
+}
+
+impl From<v0_34::types::VersionParams> for v0_37::types::VersionParams {
+    fn from(value: v0_34::types::VersionParams) -> Self {
+        Self {
+            app: value.app_version,
+        }
+    }
+}
+
+impl From<v0_37::types::VersionParams> for v0_34::types::VersionParams {
+    fn from(value: v0_37::types::VersionParams) -> Self {
+        Self {
+            app_version: value.app,
+        }
+    }
+}
+
+/// The keys and values of the attributes are strings since Tendermint 0.37.
+impl TryFrom<v0_34::abci::EventAttribute> for v0_37::abci::EventAttribute {
+    type Error = Error;
+
+    fn try_from(value: v0_34::abci::EventAttribute) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: String::from_utf8(value.key.to_vec()).map_err(Error::invalid_utf8)?,
+            value: String::from_utf8(value.value.to_vec()).map_err(Error::invalid_utf8)?,
+            index: value.index,
+        })
+    }
+}
+
+impl From<v0_37::abci::EventAttribute> for v0_34::abci::EventAttribute {
+    fn from(value: v0_37::abci::EventAttribute) -> Self {
+        Self {
+            key: Bytes::from(value.key),
+            value: Bytes::from(value.value),
+            index: value.index,
+        }
+    }
+}
+
+/// The ABCI version is only sent by Tendermint 0.37, and left empty.
+impl From<v0_34::abci::RequestInfo> for v0_37::abci::RequestInfo {
+    fn from(value: v0_34::abci::RequestInfo) -> Self {
+        Self {
+            version: value.version,
+            block_version: value.block_version,
+            p2p_version: value.p2p_version,
+            abci_version: String::new(),
+        }
+    }
+}
+
+impl From<v0_37::abci::RequestInfo> for v0_34::abci::RequestInfo {
+    fn from(value: v0_37::abci::RequestInfo) -> Self {
+        Self {
+            version: value.version,
+            block_version: value.block_version,
+            p2p_version: value.p2p_version,
+        }
+    }
+}
+
+/// `SetOption` was removed by Tendermint 0.37.
+impl TryFrom<v0_34::abci::request::Value> for v0_37::abci::request::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_34::abci::request::Value) -> Result<Self, Self::Error> {
+        use v0_34::abci::request::Value;
+
+        Ok(match value {
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::SetOption(_) => return Err(unsupported("RequestSetOption", "0.37")),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(begin_block) => Self::BeginBlock(begin_block.into()),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::DeliverTx(deliver_tx) => Self::DeliverTx(deliver_tx.into()),
+            Value::EndBlock(end_block) => Self::EndBlock(end_block.into()),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+        })
+    }
+}
+
+/// `PrepareProposal` and `ProcessProposal` were added by Tendermint 0.37.
+impl TryFrom<v0_37::abci::request::Value> for v0_34::abci::request::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_37::abci::request::Value) -> Result<Self, Self::Error> {
+        use v0_37::abci::request::Value;
+
+        Ok(match value {
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(begin_block) => Self::BeginBlock(begin_block.into()),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::DeliverTx(deliver_tx) => Self::DeliverTx(deliver_tx.into()),
+            Value::EndBlock(end_block) => Self::EndBlock(end_block.into()),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(_) => return Err(unsupported("RequestPrepareProposal", "0.34")),
+            Value::ProcessProposal(_) => return Err(unsupported("RequestProcessProposal", "0.34")),
+        })
+    }
+}
+
+impl TryFrom<v0_34::abci::response::Value> for v0_37::abci::response::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_34::abci::response::Value) -> Result<Self, Self::Error> {
+        use v0_34::abci::response::Value;
+
+        Ok(match value {
+            Value::Exception(exception) => Self::Exception(exception.into()),
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::SetOption(_) => return Err(unsupported("ResponseSetOption", "0.37")),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(begin_block) => Self::BeginBlock(begin_block.try_into()?),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.try_into()?),
+            Value::DeliverTx(deliver_tx) => Self::DeliverTx(deliver_tx.try_into()?),
+            Value::EndBlock(end_block) => Self::EndBlock(end_block.try_into()?),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+        })
+    }
+}
+
+impl TryFrom<v0_37::abci::response::Value> for v0_34::abci::response::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_37::abci::response::Value) -> Result<Self, Self::Error> {
+        use v0_37::abci::response::Value;
+
+        Ok(match value {
+            Value::Exception(exception) => Self::Exception(exception.into()),
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(begin_block) => Self::BeginBlock(begin_block.into()),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::DeliverTx(deliver_tx) => Self::DeliverTx(deliver_tx.into()),
+            Value::EndBlock(end_block) => Self::EndBlock(end_block.into()),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(_) => {
+                return Err(unsupported("ResponsePrepareProposal", "0.34"))
+            },
+            Value::ProcessProposal(_) => {
+                return Err(unsupported("ResponseProcessProposal", "0.34"))
+            },
+        })
+    }
+}
+
+/// The mempool fields were removed by Tendermint 0.38, and are left empty.
+impl From<v0_37::abci::ResponseCheckTx> for v0_38::abci::ResponseCheckTx {
+    fn from(value: v0_37::abci::ResponseCheckTx) -> Self {
+        Self {
+            code: value.code,
+            data: value.data,
+            log: value.log,
+            info: value.info,
+            gas_wanted: value.gas_wanted,
+            gas_used: value.gas_used,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+        }
+    }
+}
+
+impl From<v0_38::abci::ResponseCheckTx> for v0_37::abci::ResponseCheckTx {
+    fn from(value: v0_38::abci::ResponseCheckTx) -> Self {
+        Self {
+            code: value.code,
+            data: value.data,
+            log: value.log,
+            info: value.info,
+            gas_wanted: value.gas_wanted,
+            gas_used: value.gas_used,
+            events: value.events.into_iter().map(Into::into).collect(),
+            codespace: value.codespace,
+            sender: String::new(),
+            priority: 0,
+            mempool_error: String::new(),
+        }
+    }
+}
+
+/// The app hash is returned by `FinalizeBlock` since Tendermint 0.38, and
+/// left empty.
+impl From<v0_37::abci::ResponseCommit> for v0_38::abci::ResponseCommit {
+    fn from(value: v0_37::abci::ResponseCommit) -> Self {
+        Self {
+            retain_height: value.retain_height,
+        }
+    }
+}
+
+impl From<v0_38::abci::ResponseCommit> for v0_37::abci::ResponseCommit {
+    fn from(value: v0_38::abci::ResponseCommit) -> Self {
+        Self {
+            data: Bytes::new(),
+            retain_height: value.retain_height,
+        }
+    }
+}
+
+/// Whether a validator signed the last block is told by its `BlockIDFlag`
+/// since Tendermint 0.38, which also tells the nil votes apart.
+impl From<v0_37::abci::VoteInfo> for v0_38::abci::VoteInfo {
+    fn from(value: v0_37::abci::VoteInfo) -> Self {
+        Self {
+            validator: value.validator.map(Into::into),
+            block_id_flag: block_id_flag(value.signed_last_block),
+        }
+    }
+}
+
+impl From<v0_38::abci::VoteInfo> for v0_37::abci::VoteInfo {
+    fn from(value: v0_38::abci::VoteInfo) -> Self {
+        Self {
+            validator: value.validator.map(Into::into),
+            signed_last_block: signed_last_block(value.block_id_flag),
+        }
+    }
+}
+
+/// The vote extensions are only signed since Tendermint 0.38.
+impl From<v0_37::abci::ExtendedVoteInfo> for v0_38::abci::ExtendedVoteInfo {
+    fn from(value: v0_37::abci::ExtendedVoteInfo) -> Self {
+        Self {
+            validator: value.validator.map(Into::into),
+            vote_extension: value.vote_extension,
+            extension_signature: Bytes::new(),
+            block_id_flag: block_id_flag(value.signed_last_block),
+        }
+    }
+}
+
+impl TryFrom<v0_38::abci::ExtendedVoteInfo> for v0_37::abci::ExtendedVoteInfo {
+    type Error = Error;
+
+    fn try_from(value: v0_38::abci::ExtendedVoteInfo) -> Result<Self, Self::Error> {
+        if !value.extension_signature.is_empty() {
+            return Err(unsupported_field(
+                "ExtendedVoteInfo.extension_signature",
+                "0.37",
+            ));
+        }
+        Ok(Self {
+            validator: value.validator.map(Into::into),
+            signed_last_block: signed_last_block(value.block_id_flag),
+            vote_extension: value.vote_extension,
+        })
+    }
+}
+
+fn block_id_flag(signed_last_block: bool) -> i32 {
+    if signed_last_block {
+        v0_38::types::BlockIdFlag::Commit as i32
+    } else {
+        v0_38::types::BlockIdFlag::Absent as i32
+    }
+}
+
+fn signed_last_block(block_id_flag: i32) -> bool {
+    block_id_flag == v0_38::types::BlockIdFlag::Commit as i32
+        || block_id_flag == v0_38::types::BlockIdFlag::Nil as i32
+}
+
+/// The ABCI parameters were added by Tendermint 0.38, to enable the vote
+/// extensions.
+impl From<v0_37::types::ConsensusParams> for v0_38::types::ConsensusParams {
+    fn from(value: v0_37::types::ConsensusParams) -> Self {
+        Self {
+            block: value.block.map(Into::into),
+            evidence: value.evidence.map(Into::into),
+            validator: value.validator.map(Into::into),
+            version: value.version.map(Into::into),
+            abci: None,
+        }
+    }
+}
+
+impl TryFrom<v0_38::types::ConsensusParams> for v0_37::types::ConsensusParams {
+    type Error = Error;
+
+    fn try_from(value: v0_38::types::ConsensusParams) -> Result<Self, Self::Error> {
+        let vote_extensions_enable_height = value
+            .abci
+            .map(|abci| abci.vote_extensions_enable_height)
+            .unwrap_or_default();
+        if vote_extensions_enable_height != 0 {
+            return Err(unsupported_field(
+                "AbciParams.vote_extensions_enable_height",
+                "0.37",
+            ));
+        }
+        Ok(Self {
+            block: value.block.map(Into::into),
+            evidence: value.evidence.map(Into::into),
+            validator: value.validator.map(Into::into),
+            version: value.version.map(Into::into),
+        })
+    }
+}
+
+/// The vote extensions were added by Tendermint 0.38.
+impl From<v0_37::types::Vote> for v0_38::types::Vote {
+    fn from(value: v0_37::types::Vote) -> Self {
+        Self {
+            r#type: value.r#type,
+            height: value.height,
+            round: value.round,
+            block_id: value.block_id.map(Into::into),
+            timestamp: value.timestamp,
+            validator_address: value.validator_address,
+            validator_index: value.validator_index,
+            signature: value.signature,
+            extension: Vec::new(),
+            extension_signature: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<v0_38::types::Vote> for v0_37::types::Vote {
+    type Error = Error;
+
+    fn try_from(value: v0_38::types::Vote) -> Result<Self, Self::Error> {
+        if !value.extension.is_empty() {
+            return Err(unsupported_field("Vote.extension", "0.37"));
+        }
+        if !value.extension_signature.is_empty() {
+            return Err(unsupported_field("Vote.extension_signature", "0.37"));
+        }
+        Ok(Self {
+            r#type: value.r#type,
+            height: value.height,
+            round: value.round,
+            block_id: value.block_id.map(Into::into),
+            timestamp: value.timestamp,
+            validator_address: value.validator_address,
+            validator_index: value.validator_index,
+            signature: value.signature,
+        })
+    }
+}
+
+/// `BeginBlock`, `DeliverTx` and `EndBlock` were replaced by `FinalizeBlock`
+/// in Tendermint 0.38.
+impl TryFrom<v0_37::abci::request::Value> for v0_38::abci::request::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_37::abci::request::Value) -> Result<Self, Self::Error> {
+        use v0_37::abci::request::Value;
+
+        Ok(match value {
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(_) => return Err(unsupported("RequestBeginBlock", "0.38")),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::DeliverTx(_) => return Err(unsupported("RequestDeliverTx", "0.38")),
+            Value::EndBlock(_) => return Err(unsupported("RequestEndBlock", "0.38")),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(prepare_proposal) => {
+                Self::PrepareProposal(prepare_proposal.into())
+            },
+            Value::ProcessProposal(process_proposal) => {
+                Self::ProcessProposal(process_proposal.into())
+            },
+        })
+    }
+}
+
+/// `ExtendVote`, `VerifyVoteExtension` and `FinalizeBlock` were added by
+/// Tendermint 0.38.
+impl TryFrom<v0_38::abci::request::Value> for v0_37::abci::request::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_38::abci::request::Value) -> Result<Self, Self::Error> {
+        use v0_38::abci::request::Value;
+
+        Ok(match value {
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.try_into()?),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(prepare_proposal) => {
+                Self::PrepareProposal(prepare_proposal.try_into()?)
+            },
+            Value::ProcessProposal(process_proposal) => {
+                Self::ProcessProposal(process_proposal.into())
+            },
+            Value::ExtendVote(_) => return Err(unsupported("RequestExtendVote", "0.37")),
+            Value::VerifyVoteExtension(_) => {
+                return Err(unsupported("RequestVerifyVoteExtension", "0.37"))
+            },
+            Value::FinalizeBlock(_) => return Err(unsupported("RequestFinalizeBlock", "0.37")),
+        })
+    }
+}
+
+impl TryFrom<v0_37::abci::response::Value> for v0_38::abci::response::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_37::abci::response::Value) -> Result<Self, Self::Error> {
+        use v0_37::abci::response::Value;
+
+        Ok(match value {
+            Value::Exception(exception) => Self::Exception(exception.into()),
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.into()),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::BeginBlock(_) => return Err(unsupported("ResponseBeginBlock", "0.38")),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::DeliverTx(_) => return Err(unsupported("ResponseDeliverTx", "0.38")),
+            Value::EndBlock(_) => return Err(unsupported("ResponseEndBlock", "0.38")),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(prepare_proposal) => {
+                Self::PrepareProposal(prepare_proposal.into())
+            },
+            Value::ProcessProposal(process_proposal) => {
+                Self::ProcessProposal(process_proposal.into())
+            },
+        })
+    }
+}
+
+impl TryFrom<v0_38::abci::response::Value> for v0_37::abci::response::Value {
+    type Error = Error;
+
+    fn try_from(value: v0_38::abci::response::Value) -> Result<Self, Self::Error> {
+        use v0_38::abci::response::Value;
+
+        Ok(match value {
+            Value::Exception(exception) => Self::Exception(exception.into()),
+            Value::Echo(echo) => Self::Echo(echo.into()),
+            Value::Flush(flush) => Self::Flush(flush.into()),
+            Value::Info(info) => Self::Info(info.into()),
+            Value::InitChain(init_chain) => Self::InitChain(init_chain.try_into()?),
+            Value::Query(query) => Self::Query(query.into()),
+            Value::CheckTx(check_tx) => Self::CheckTx(check_tx.into()),
+            Value::Commit(commit) => Self::Commit(commit.into()),
+            Value::ListSnapshots(list_snapshots) => Self::ListSnapshots(list_snapshots.into()),
+            Value::OfferSnapshot(offer_snapshot) => Self::OfferSnapshot(offer_snapshot.into()),
+            Value::LoadSnapshotChunk(load_snapshot_chunk) => {
+                Self::LoadSnapshotChunk(load_snapshot_chunk.into())
+            },
+            Value::ApplySnapshotChunk(apply_snapshot_chunk) => {
+                Self::ApplySnapshotChunk(apply_snapshot_chunk.into())
+            },
+            Value::PrepareProposal(prepare_proposal) => {
+                Self::PrepareProposal(prepare_proposal.into())
+            },
+            Value::ProcessProposal(process_proposal) => {
+                Self::ProcessProposal(process_proposal.into())
+            },
+            Value::ExtendVote(_) => return Err(unsupported("ResponseExtendVote", "0.37")),
+            Value::VerifyVoteExtension(_) => {
+                return Err(unsupported("ResponseVerifyVoteExtension", "0.37"))
+            },
+            Value::FinalizeBlock(_) => return Err(unsupported("ResponseFinalizeBlock", "0.37")),
+        })
+    }
+}
+
+fn unsupported(message: &str, version: &str) -> Error {
+    Error::unsupported_message(message.to_string(), version.to_string())
+}
+
+fn unsupported_field(field: &str, version: &str) -> Error {
+    Error::unsupported_field(field.to_string(), version.to_string())
+}
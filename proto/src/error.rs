@@ -1,5 +1,6 @@
 //! This module defines the various errors that be raised during Protobuf conversions.
 
+use alloc::string::FromUtf8Error;
 use core::{convert::TryFrom, fmt::Display, num::TryFromIntError};
 
 use flex_error::{define_error, DisplayOnly};
@@ -27,6 +28,24 @@ define_error! {
         ParseLength
             [ DisplayOnly<TryFromIntError> ]
             | _ | { "error parsing encoded length" },
+
+        UnsupportedMessage
+            { message: String, version: String }
+            | e | {
+                format!("message {} is not supported by Tendermint {}",
+                    e.message, e.version)
+            },
+
+        UnsupportedField
+            { field: String, version: String }
+            | e | {
+                format!("field {} is not supported by Tendermint {}",
+                    e.field, e.version)
+            },
+
+        InvalidUtf8
+            [ DisplayOnly<FromUtf8Error> ]
+            | _ | { "invalid UTF-8 string" },
     }
 }
 
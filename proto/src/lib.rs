@@ -1,6 +1,6 @@
 //! tendermint-proto library gives the developer access to the Tendermint proto-defined structs.
 
-#![no_std]
+#![cfg_attr(not(feature = "grpc-server"), no_std)]
 #![deny(warnings, trivial_casts, trivial_numeric_casts, unused_import_braces)]
 #![allow(clippy::large_enum_variant)]
 #![forbid(unsafe_code)]
@@ -18,6 +18,7 @@ pub mod google {
     }
 }
 
+mod convert;
 mod error;
 #[allow(warnings)]
 mod tendermint;
@@ -130,6 +131,30 @@ where
             .map_err(Error::encode_message)
     }
 
+    /// Encode into a buffer in the deterministic Protobuf format required by
+    /// signing contexts, e.g. for sign bytes or signatures over Tendermint
+    /// types by applications.
+    ///
+    /// The encoding is the same as the one of Go's marshaler with the
+    /// `Deterministic` option:
+    ///
+    /// - Fields are written in the order of their field numbers, which
+    ///   `prost` guarantees. The fields of a `oneof` are written at the
+    ///   position of its lowest field number, which is the same as long as
+    ///   the field numbers of the `oneof` are not interleaved with the ones
+    ///   of other fields, which is the case of all the Tendermint messages.
+    /// - Fields set to their default value are omitted, and repeated scalar
+    ///   fields are packed, as for proto3 messages in Go.
+    /// - Map entries are written in the order of their keys, the messages of
+    ///   this crate being generated with `BTreeMap`s for map fields (none of
+    ///   the Tendermint messages defines any at present).
+    /// - Unknown fields are dropped by `prost` when decoding, so they are
+    ///   never written when encoding a decoded message again, whereas Go
+    ///   writes them back.
+    fn encode_deterministic<B: BufMut>(&self, buf: &mut B) -> Result<(), Error> {
+        self.encode(buf)
+    }
+
     /// Encode with a length-delimiter to a buffer in Protobuf format.
     ///
     /// An error will be returned if the buffer does not have sufficient capacity.
@@ -189,6 +214,13 @@ where
         self.encode(&mut wire).map(|_| wire)
     }
 
+    /// Encodes into a deterministic Protobuf-encoded `Vec<u8>` (see
+    /// [`Protobuf::encode_deterministic`]).
+    fn encode_deterministic_vec(&self) -> Result<Vec<u8>, Error> {
+        let mut wire = Vec::with_capacity(self.encoded_len());
+        self.encode_deterministic(&mut wire).map(|_| wire)
+    }
+
     /// Constructor that attempts to decode a Protobuf-encoded instance from a
     /// `Vec<u8>` (or equivalent).
     fn decode_vec(v: &[u8]) -> Result<Self, Error> {
@@ -64,7 +64,7 @@ pub struct BlockId {
     #[serde(with = "crate::serializers::bytes::hexstring")]
     pub hash: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "2")]
-    #[serde(alias = "parts")]
+    #[serde(rename = "parts", alias = "part_set_header")]
     pub part_set_header: ::core::option::Option<PartSetHeader>,
 }
 /// Header defines the structure of a Tendermint block header.
@@ -435,13 +435,13 @@ pub struct DuplicateVoteEvidence {
     #[prost(message, optional, tag = "2")]
     pub vote_b: ::core::option::Option<Vote>,
     #[prost(int64, tag = "3")]
-    #[serde(alias = "TotalVotingPower", with = "crate::serializers::from_str")]
+    #[serde(rename = "TotalVotingPower", alias = "total_voting_power", with = "crate::serializers::from_str")]
     pub total_voting_power: i64,
     #[prost(int64, tag = "4")]
-    #[serde(alias = "ValidatorPower", with = "crate::serializers::from_str")]
+    #[serde(rename = "ValidatorPower", alias = "validator_power", with = "crate::serializers::from_str")]
     pub validator_power: i64,
     #[prost(message, optional, tag = "5")]
-    #[serde(alias = "Timestamp")]
+    #[serde(rename = "Timestamp", alias = "timestamp")]
     pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
 }
 /// LightClientAttackEvidence contains evidence of a set of validators attempting to mislead a light client.
@@ -450,14 +450,19 @@ pub struct DuplicateVoteEvidence {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LightClientAttackEvidence {
     #[prost(message, optional, tag = "1")]
+    #[serde(rename = "ConflictingBlock", alias = "conflicting_block")]
     pub conflicting_block: ::core::option::Option<LightBlock>,
     #[prost(int64, tag = "2")]
+    #[serde(rename = "CommonHeight", alias = "common_height", with = "crate::serializers::from_str")]
     pub common_height: i64,
     #[prost(message, repeated, tag = "3")]
+    #[serde(rename = "ByzantineValidators", alias = "byzantine_validators", with = "crate::serializers::nullable")]
     pub byzantine_validators: ::prost::alloc::vec::Vec<Validator>,
     #[prost(int64, tag = "4")]
+    #[serde(rename = "TotalVotingPower", alias = "total_voting_power", with = "crate::serializers::from_str")]
     pub total_voting_power: i64,
     #[prost(message, optional, tag = "5")]
+    #[serde(rename = "Timestamp", alias = "timestamp")]
     pub timestamp: ::core::option::Option<crate::google::protobuf::Timestamp>,
 }
 #[derive(::serde::Deserialize, ::serde::Serialize)]
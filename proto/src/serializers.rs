@@ -61,6 +61,7 @@ pub mod nullable;
 pub mod optional;
 pub mod optional_from_str;
 pub mod part_set_header_total;
+mod public_key;
 pub mod time_duration;
 pub mod timestamp;
 pub mod txs;
@@ -34,7 +34,7 @@ where
 {
     let value_string = String::deserialize(deserializer)?;
     let t = OffsetDateTime::parse(&value_string, &Rfc3339Format).map_err(D::Error::custom)?;
-    let t = t.to_offset(offset!(UTC));
+    let t = checked_to_utc(t).ok_or_else(|| D::Error::custom("date is out of range"))?;
     if !matches!(t.year(), 1..=9999) {
         return Err(D::Error::custom("date is out of range"));
     }
@@ -56,9 +56,19 @@ where
     let total_nanos = value.seconds as i128 * 1_000_000_000 + value.nanos as i128;
     let datetime = OffsetDateTime::from_unix_timestamp_nanos(total_nanos)
         .map_err(|_| S::Error::custom("invalid time"))?;
+    if !matches!(datetime.year(), 1..=9999) {
+        return Err(S::Error::custom("date is out of range"));
+    }
     to_rfc3339_nanos(datetime).serialize(serializer)
 }
 
+/// Convert the given date and time to UTC, returning `None` rather than
+/// panicking if the converted date is not representable, e.g. for
+/// `9999-12-31T23:59:59-01:00`.
+pub fn checked_to_utc(t: OffsetDateTime) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(t.unix_timestamp_nanos()).ok()
+}
+
 /// Serialization helper for converting an [`OffsetDateTime`] object to a string.
 ///
 /// This reproduces the behavior of Go's `time.RFC3339Nano` format,
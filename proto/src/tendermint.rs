@@ -1,3 +1,4 @@
 pub mod v0_34;
 pub mod v0_37;
+pub mod v0_38;
 pub use v0_37::*;
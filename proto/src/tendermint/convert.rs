@@ -0,0 +1,1870 @@
+// Conversions between the messages of consecutive Tendermint versions, generated
+// for the messages whose definitions correspond. The others are converted by hand.
+
+convert_message!(From, v0_34::abci::BlockParams => v0_37::types::BlockParams {
+    max_bytes,
+    max_gas,
+});
+
+convert_message!(From, v0_34::abci::ConsensusParams => v0_37::types::ConsensusParams {
+    block: opt,
+    evidence: opt,
+    validator: opt,
+    version: opt,
+});
+
+convert_message!(TryFrom, v0_34::abci::Event => v0_37::abci::Event {
+    r#type,
+    attributes: try_vec,
+});
+
+convert_message!(From, v0_34::abci::Evidence => v0_37::abci::Misbehavior {
+    r#type,
+    validator: opt,
+    height,
+    time,
+    total_voting_power,
+});
+
+convert_message!(From, v0_34::abci::LastCommitInfo => v0_37::abci::CommitInfo {
+    round,
+    votes: vec,
+});
+
+convert_message!(TryFrom, v0_34::abci::Request => v0_37::abci::Request {
+    value: try_opt,
+});
+
+convert_message!(From, v0_34::abci::RequestApplySnapshotChunk => v0_37::abci::RequestApplySnapshotChunk {
+    index,
+    chunk,
+    sender,
+});
+
+convert_message!(From, v0_34::abci::RequestBeginBlock => v0_37::abci::RequestBeginBlock {
+    hash,
+    header: opt,
+    last_commit_info: opt,
+    byzantine_validators: vec,
+});
+
+convert_message!(From, v0_34::abci::RequestCheckTx => v0_37::abci::RequestCheckTx {
+    tx,
+    r#type,
+});
+
+convert_message!(From, v0_34::abci::RequestCommit => v0_37::abci::RequestCommit {});
+
+convert_message!(From, v0_34::abci::RequestDeliverTx => v0_37::abci::RequestDeliverTx {
+    tx,
+});
+
+convert_message!(From, v0_34::abci::RequestEcho => v0_37::abci::RequestEcho {
+    message,
+});
+
+convert_message!(From, v0_34::abci::RequestEndBlock => v0_37::abci::RequestEndBlock {
+    height,
+});
+
+convert_message!(From, v0_34::abci::RequestFlush => v0_37::abci::RequestFlush {});
+
+convert_message!(From, v0_34::abci::RequestInitChain => v0_37::abci::RequestInitChain {
+    time,
+    chain_id,
+    consensus_params: opt,
+    validators: vec,
+    app_state_bytes,
+    initial_height,
+});
+
+convert_message!(From, v0_34::abci::RequestListSnapshots => v0_37::abci::RequestListSnapshots {});
+
+convert_message!(From, v0_34::abci::RequestLoadSnapshotChunk => v0_37::abci::RequestLoadSnapshotChunk {
+    height,
+    format,
+    chunk,
+});
+
+convert_message!(From, v0_34::abci::RequestOfferSnapshot => v0_37::abci::RequestOfferSnapshot {
+    snapshot: opt,
+    app_hash,
+});
+
+convert_message!(From, v0_34::abci::RequestQuery => v0_37::abci::RequestQuery {
+    data,
+    path,
+    height,
+    prove,
+});
+
+convert_message!(TryFrom, v0_34::abci::Response => v0_37::abci::Response {
+    value: try_opt,
+});
+
+convert_message!(From, v0_34::abci::ResponseApplySnapshotChunk => v0_37::abci::ResponseApplySnapshotChunk {
+    result,
+    refetch_chunks,
+    reject_senders,
+});
+
+convert_message!(TryFrom, v0_34::abci::ResponseBeginBlock => v0_37::abci::ResponseBeginBlock {
+    events: try_vec,
+});
+
+convert_message!(TryFrom, v0_34::abci::ResponseCheckTx => v0_37::abci::ResponseCheckTx {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: try_vec,
+    codespace,
+    sender,
+    priority,
+    mempool_error,
+});
+
+convert_message!(From, v0_34::abci::ResponseCommit => v0_37::abci::ResponseCommit {
+    data,
+    retain_height,
+});
+
+convert_message!(TryFrom, v0_34::abci::ResponseDeliverTx => v0_37::abci::ResponseDeliverTx {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: try_vec,
+    codespace,
+});
+
+convert_message!(From, v0_34::abci::ResponseEcho => v0_37::abci::ResponseEcho {
+    message,
+});
+
+convert_message!(TryFrom, v0_34::abci::ResponseEndBlock => v0_37::abci::ResponseEndBlock {
+    validator_updates: vec,
+    consensus_param_updates: opt,
+    events: try_vec,
+});
+
+convert_message!(From, v0_34::abci::ResponseException => v0_37::abci::ResponseException {
+    error,
+});
+
+convert_message!(From, v0_34::abci::ResponseFlush => v0_37::abci::ResponseFlush {});
+
+convert_message!(From, v0_34::abci::ResponseInfo => v0_37::abci::ResponseInfo {
+    data,
+    version,
+    app_version,
+    last_block_height,
+    last_block_app_hash,
+});
+
+convert_message!(From, v0_34::abci::ResponseInitChain => v0_37::abci::ResponseInitChain {
+    consensus_params: opt,
+    validators: vec,
+    app_hash,
+});
+
+convert_message!(From, v0_34::abci::ResponseListSnapshots => v0_37::abci::ResponseListSnapshots {
+    snapshots: vec,
+});
+
+convert_message!(From, v0_34::abci::ResponseLoadSnapshotChunk => v0_37::abci::ResponseLoadSnapshotChunk {
+    chunk,
+});
+
+convert_message!(From, v0_34::abci::ResponseOfferSnapshot => v0_37::abci::ResponseOfferSnapshot {
+    result,
+});
+
+convert_message!(From, v0_34::abci::ResponseQuery => v0_37::abci::ResponseQuery {
+    code,
+    log,
+    info,
+    index,
+    key,
+    value,
+    proof_ops: opt,
+    height,
+    codespace,
+});
+
+convert_message!(From, v0_34::abci::Snapshot => v0_37::abci::Snapshot {
+    height,
+    format,
+    chunks,
+    hash,
+    metadata,
+});
+
+convert_message!(TryFrom, v0_34::abci::TxResult => v0_37::abci::TxResult {
+    height,
+    index,
+    tx,
+    result: try_opt,
+});
+
+convert_message!(From, v0_34::abci::Validator => v0_37::abci::Validator {
+    address,
+    power,
+});
+
+convert_message!(From, v0_34::abci::ValidatorUpdate => v0_37::abci::ValidatorUpdate {
+    pub_key: opt,
+    power,
+});
+
+convert_message!(From, v0_34::abci::VoteInfo => v0_37::abci::VoteInfo {
+    validator: opt,
+    signed_last_block,
+});
+
+convert_message!(From, v0_34::crypto::DominoOp => v0_37::crypto::DominoOp {
+    key,
+    input,
+    output,
+});
+
+convert_message!(From, v0_34::crypto::Proof => v0_37::crypto::Proof {
+    total,
+    index,
+    leaf_hash,
+    aunts,
+});
+
+convert_message!(From, v0_34::crypto::ProofOp => v0_37::crypto::ProofOp {
+    r#type,
+    key,
+    data,
+});
+
+convert_message!(From, v0_34::crypto::ProofOps => v0_37::crypto::ProofOps {
+    ops: vec,
+});
+
+convert_message!(From, v0_34::crypto::PublicKey => v0_37::crypto::PublicKey {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_34::crypto::public_key::Sum => v0_37::crypto::public_key::Sum {
+    Ed25519,
+    Secp256k1,
+});
+
+convert_message!(From, v0_34::crypto::ValueOp => v0_37::crypto::ValueOp {
+    key,
+    proof: opt,
+});
+
+convert_message!(From, v0_34::types::Block => v0_37::types::Block {
+    header: opt,
+    data: opt,
+    evidence: opt,
+    last_commit: opt,
+});
+
+convert_message!(From, v0_34::types::BlockId => v0_37::types::BlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_34::types::BlockMeta => v0_37::types::BlockMeta {
+    block_id: opt,
+    block_size,
+    header: opt,
+    num_txs,
+});
+
+convert_message!(From, v0_34::types::CanonicalBlockId => v0_37::types::CanonicalBlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_34::types::CanonicalPartSetHeader => v0_37::types::CanonicalPartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_34::types::CanonicalProposal => v0_37::types::CanonicalProposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_34::types::CanonicalVote => v0_37::types::CanonicalVote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_34::types::Commit => v0_37::types::Commit {
+    height,
+    round,
+    block_id: opt,
+    signatures: vec,
+});
+
+convert_message!(From, v0_34::types::CommitSig => v0_37::types::CommitSig {
+    block_id_flag,
+    validator_address,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_34::types::ConsensusParams => v0_37::types::ConsensusParams {
+    block: opt,
+    evidence: opt,
+    validator: opt,
+    version: opt,
+});
+
+convert_message!(From, v0_34::types::Data => v0_37::types::Data {
+    txs,
+});
+
+convert_message!(From, v0_34::types::DuplicateVoteEvidence => v0_37::types::DuplicateVoteEvidence {
+    vote_a: opt,
+    vote_b: opt,
+    total_voting_power,
+    validator_power,
+    timestamp,
+});
+
+convert_message!(From, v0_34::types::EventDataRoundState => v0_37::types::EventDataRoundState {
+    height,
+    round,
+    step,
+});
+
+convert_message!(From, v0_34::types::Evidence => v0_37::types::Evidence {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_34::types::evidence::Sum => v0_37::types::evidence::Sum {
+    DuplicateVoteEvidence: into,
+    LightClientAttackEvidence: into,
+});
+
+convert_message!(From, v0_34::types::EvidenceList => v0_37::types::EvidenceList {
+    evidence: vec,
+});
+
+convert_message!(From, v0_34::types::EvidenceParams => v0_37::types::EvidenceParams {
+    max_age_num_blocks,
+    max_age_duration,
+    max_bytes,
+});
+
+convert_message!(From, v0_34::types::HashedParams => v0_37::types::HashedParams {
+    block_max_bytes,
+    block_max_gas,
+});
+
+convert_message!(From, v0_34::types::Header => v0_37::types::Header {
+    version: opt,
+    chain_id,
+    height,
+    time,
+    last_block_id: opt,
+    last_commit_hash,
+    data_hash,
+    validators_hash,
+    next_validators_hash,
+    consensus_hash,
+    app_hash,
+    last_results_hash,
+    evidence_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_34::types::LightBlock => v0_37::types::LightBlock {
+    signed_header: opt,
+    validator_set: opt,
+});
+
+convert_message!(From, v0_34::types::LightClientAttackEvidence => v0_37::types::LightClientAttackEvidence {
+    conflicting_block: opt,
+    common_height,
+    byzantine_validators: vec,
+    total_voting_power,
+    timestamp,
+});
+
+convert_message!(From, v0_34::types::Part => v0_37::types::Part {
+    index,
+    bytes,
+    proof: opt,
+});
+
+convert_message!(From, v0_34::types::PartSetHeader => v0_37::types::PartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_34::types::Proposal => v0_37::types::Proposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_34::types::SignedHeader => v0_37::types::SignedHeader {
+    header: opt,
+    commit: opt,
+});
+
+convert_message!(From, v0_34::types::SimpleValidator => v0_37::types::SimpleValidator {
+    pub_key: opt,
+    voting_power,
+});
+
+convert_message!(From, v0_34::types::TxProof => v0_37::types::TxProof {
+    root_hash,
+    data,
+    proof: opt,
+});
+
+convert_message!(From, v0_34::types::Validator => v0_37::types::Validator {
+    address,
+    pub_key: opt,
+    voting_power,
+    proposer_priority,
+});
+
+convert_message!(From, v0_34::types::ValidatorParams => v0_37::types::ValidatorParams {
+    pub_key_types,
+});
+
+convert_message!(From, v0_34::types::ValidatorSet => v0_37::types::ValidatorSet {
+    validators: vec,
+    proposer: opt,
+    total_voting_power,
+});
+
+convert_message!(From, v0_34::types::Vote => v0_37::types::Vote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    validator_address,
+    validator_index,
+    signature,
+});
+
+convert_message!(From, v0_34::version::App => v0_37::version::App {
+    protocol,
+    software,
+});
+
+convert_message!(From, v0_34::version::Consensus => v0_37::version::Consensus {
+    block,
+    app,
+});
+
+convert_message!(From, v0_37::types::BlockParams => v0_34::abci::BlockParams {
+    max_bytes,
+    max_gas,
+});
+
+convert_message!(From, v0_37::types::ConsensusParams => v0_34::abci::ConsensusParams {
+    block: opt,
+    evidence: opt,
+    validator: opt,
+    version: opt,
+});
+
+convert_message!(From, v0_37::abci::Event => v0_34::abci::Event {
+    r#type,
+    attributes: vec,
+});
+
+convert_message!(From, v0_37::abci::Misbehavior => v0_34::abci::Evidence {
+    r#type,
+    validator: opt,
+    height,
+    time,
+    total_voting_power,
+});
+
+convert_message!(From, v0_37::abci::CommitInfo => v0_34::abci::LastCommitInfo {
+    round,
+    votes: vec,
+});
+
+convert_message!(TryFrom, v0_37::abci::Request => v0_34::abci::Request {
+    value: try_opt,
+});
+
+convert_message!(From, v0_37::abci::RequestApplySnapshotChunk => v0_34::abci::RequestApplySnapshotChunk {
+    index,
+    chunk,
+    sender,
+});
+
+convert_message!(From, v0_37::abci::RequestBeginBlock => v0_34::abci::RequestBeginBlock {
+    hash,
+    header: opt,
+    last_commit_info: opt,
+    byzantine_validators: vec,
+});
+
+convert_message!(From, v0_37::abci::RequestCheckTx => v0_34::abci::RequestCheckTx {
+    tx,
+    r#type,
+});
+
+convert_message!(From, v0_37::abci::RequestCommit => v0_34::abci::RequestCommit {});
+
+convert_message!(From, v0_37::abci::RequestDeliverTx => v0_34::abci::RequestDeliverTx {
+    tx,
+});
+
+convert_message!(From, v0_37::abci::RequestEcho => v0_34::abci::RequestEcho {
+    message,
+});
+
+convert_message!(From, v0_37::abci::RequestEndBlock => v0_34::abci::RequestEndBlock {
+    height,
+});
+
+convert_message!(From, v0_37::abci::RequestFlush => v0_34::abci::RequestFlush {});
+
+convert_message!(From, v0_37::abci::RequestInitChain => v0_34::abci::RequestInitChain {
+    time,
+    chain_id,
+    consensus_params: opt,
+    validators: vec,
+    app_state_bytes,
+    initial_height,
+});
+
+convert_message!(From, v0_37::abci::RequestListSnapshots => v0_34::abci::RequestListSnapshots {});
+
+convert_message!(From, v0_37::abci::RequestLoadSnapshotChunk => v0_34::abci::RequestLoadSnapshotChunk {
+    height,
+    format,
+    chunk,
+});
+
+convert_message!(From, v0_37::abci::RequestOfferSnapshot => v0_34::abci::RequestOfferSnapshot {
+    snapshot: opt,
+    app_hash,
+});
+
+convert_message!(From, v0_37::abci::RequestQuery => v0_34::abci::RequestQuery {
+    data,
+    path,
+    height,
+    prove,
+});
+
+convert_message!(TryFrom, v0_37::abci::Response => v0_34::abci::Response {
+    value: try_opt,
+});
+
+convert_message!(From, v0_37::abci::ResponseApplySnapshotChunk => v0_34::abci::ResponseApplySnapshotChunk {
+    result,
+    refetch_chunks,
+    reject_senders,
+});
+
+convert_message!(From, v0_37::abci::ResponseBeginBlock => v0_34::abci::ResponseBeginBlock {
+    events: vec,
+});
+
+convert_message!(From, v0_37::abci::ResponseCheckTx => v0_34::abci::ResponseCheckTx {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: vec,
+    codespace,
+    sender,
+    priority,
+    mempool_error,
+});
+
+convert_message!(From, v0_37::abci::ResponseCommit => v0_34::abci::ResponseCommit {
+    data,
+    retain_height,
+});
+
+convert_message!(From, v0_37::abci::ResponseDeliverTx => v0_34::abci::ResponseDeliverTx {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: vec,
+    codespace,
+});
+
+convert_message!(From, v0_37::abci::ResponseEcho => v0_34::abci::ResponseEcho {
+    message,
+});
+
+convert_message!(From, v0_37::abci::ResponseEndBlock => v0_34::abci::ResponseEndBlock {
+    validator_updates: vec,
+    consensus_param_updates: opt,
+    events: vec,
+});
+
+convert_message!(From, v0_37::abci::ResponseException => v0_34::abci::ResponseException {
+    error,
+});
+
+convert_message!(From, v0_37::abci::ResponseFlush => v0_34::abci::ResponseFlush {});
+
+convert_message!(From, v0_37::abci::ResponseInfo => v0_34::abci::ResponseInfo {
+    data,
+    version,
+    app_version,
+    last_block_height,
+    last_block_app_hash,
+});
+
+convert_message!(From, v0_37::abci::ResponseInitChain => v0_34::abci::ResponseInitChain {
+    consensus_params: opt,
+    validators: vec,
+    app_hash,
+});
+
+convert_message!(From, v0_37::abci::ResponseListSnapshots => v0_34::abci::ResponseListSnapshots {
+    snapshots: vec,
+});
+
+convert_message!(From, v0_37::abci::ResponseLoadSnapshotChunk => v0_34::abci::ResponseLoadSnapshotChunk {
+    chunk,
+});
+
+convert_message!(From, v0_37::abci::ResponseOfferSnapshot => v0_34::abci::ResponseOfferSnapshot {
+    result,
+});
+
+convert_message!(From, v0_37::abci::ResponseQuery => v0_34::abci::ResponseQuery {
+    code,
+    log,
+    info,
+    index,
+    key,
+    value,
+    proof_ops: opt,
+    height,
+    codespace,
+});
+
+convert_message!(From, v0_37::abci::Snapshot => v0_34::abci::Snapshot {
+    height,
+    format,
+    chunks,
+    hash,
+    metadata,
+});
+
+convert_message!(From, v0_37::abci::TxResult => v0_34::abci::TxResult {
+    height,
+    index,
+    tx,
+    result: opt,
+});
+
+convert_message!(From, v0_37::abci::Validator => v0_34::abci::Validator {
+    address,
+    power,
+});
+
+convert_message!(From, v0_37::abci::ValidatorUpdate => v0_34::abci::ValidatorUpdate {
+    pub_key: opt,
+    power,
+});
+
+convert_message!(From, v0_37::abci::VoteInfo => v0_34::abci::VoteInfo {
+    validator: opt,
+    signed_last_block,
+});
+
+convert_message!(From, v0_37::crypto::DominoOp => v0_34::crypto::DominoOp {
+    key,
+    input,
+    output,
+});
+
+convert_message!(From, v0_37::crypto::Proof => v0_34::crypto::Proof {
+    total,
+    index,
+    leaf_hash,
+    aunts,
+});
+
+convert_message!(From, v0_37::crypto::ProofOp => v0_34::crypto::ProofOp {
+    r#type,
+    key,
+    data,
+});
+
+convert_message!(From, v0_37::crypto::ProofOps => v0_34::crypto::ProofOps {
+    ops: vec,
+});
+
+convert_message!(From, v0_37::crypto::PublicKey => v0_34::crypto::PublicKey {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_37::crypto::public_key::Sum => v0_34::crypto::public_key::Sum {
+    Ed25519,
+    Secp256k1,
+});
+
+convert_message!(From, v0_37::crypto::ValueOp => v0_34::crypto::ValueOp {
+    key,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::Block => v0_34::types::Block {
+    header: opt,
+    data: opt,
+    evidence: opt,
+    last_commit: opt,
+});
+
+convert_message!(From, v0_37::types::BlockId => v0_34::types::BlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_37::types::BlockMeta => v0_34::types::BlockMeta {
+    block_id: opt,
+    block_size,
+    header: opt,
+    num_txs,
+});
+
+convert_message!(From, v0_37::types::CanonicalBlockId => v0_34::types::CanonicalBlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_37::types::CanonicalPartSetHeader => v0_34::types::CanonicalPartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_37::types::CanonicalProposal => v0_34::types::CanonicalProposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_37::types::CanonicalVote => v0_34::types::CanonicalVote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_37::types::Commit => v0_34::types::Commit {
+    height,
+    round,
+    block_id: opt,
+    signatures: vec,
+});
+
+convert_message!(From, v0_37::types::CommitSig => v0_34::types::CommitSig {
+    block_id_flag,
+    validator_address,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_37::types::ConsensusParams => v0_34::types::ConsensusParams {
+    block: opt,
+    evidence: opt,
+    validator: opt,
+    version: opt,
+});
+
+convert_message!(From, v0_37::types::Data => v0_34::types::Data {
+    txs,
+});
+
+convert_message!(From, v0_37::types::DuplicateVoteEvidence => v0_34::types::DuplicateVoteEvidence {
+    vote_a: opt,
+    vote_b: opt,
+    total_voting_power,
+    validator_power,
+    timestamp,
+});
+
+convert_message!(From, v0_37::types::EventDataRoundState => v0_34::types::EventDataRoundState {
+    height,
+    round,
+    step,
+});
+
+convert_message!(From, v0_37::types::Evidence => v0_34::types::Evidence {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_37::types::evidence::Sum => v0_34::types::evidence::Sum {
+    DuplicateVoteEvidence: into,
+    LightClientAttackEvidence: into,
+});
+
+convert_message!(From, v0_37::types::EvidenceList => v0_34::types::EvidenceList {
+    evidence: vec,
+});
+
+convert_message!(From, v0_37::types::EvidenceParams => v0_34::types::EvidenceParams {
+    max_age_num_blocks,
+    max_age_duration,
+    max_bytes,
+});
+
+convert_message!(From, v0_37::types::HashedParams => v0_34::types::HashedParams {
+    block_max_bytes,
+    block_max_gas,
+});
+
+convert_message!(From, v0_37::types::Header => v0_34::types::Header {
+    version: opt,
+    chain_id,
+    height,
+    time,
+    last_block_id: opt,
+    last_commit_hash,
+    data_hash,
+    validators_hash,
+    next_validators_hash,
+    consensus_hash,
+    app_hash,
+    last_results_hash,
+    evidence_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_37::types::LightBlock => v0_34::types::LightBlock {
+    signed_header: opt,
+    validator_set: opt,
+});
+
+convert_message!(From, v0_37::types::LightClientAttackEvidence => v0_34::types::LightClientAttackEvidence {
+    conflicting_block: opt,
+    common_height,
+    byzantine_validators: vec,
+    total_voting_power,
+    timestamp,
+});
+
+convert_message!(From, v0_37::types::Part => v0_34::types::Part {
+    index,
+    bytes,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::PartSetHeader => v0_34::types::PartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_37::types::Proposal => v0_34::types::Proposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_37::types::SignedHeader => v0_34::types::SignedHeader {
+    header: opt,
+    commit: opt,
+});
+
+convert_message!(From, v0_37::types::SimpleValidator => v0_34::types::SimpleValidator {
+    pub_key: opt,
+    voting_power,
+});
+
+convert_message!(From, v0_37::types::TxProof => v0_34::types::TxProof {
+    root_hash,
+    data,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::Validator => v0_34::types::Validator {
+    address,
+    pub_key: opt,
+    voting_power,
+    proposer_priority,
+});
+
+convert_message!(From, v0_37::types::ValidatorParams => v0_34::types::ValidatorParams {
+    pub_key_types,
+});
+
+convert_message!(From, v0_37::types::ValidatorSet => v0_34::types::ValidatorSet {
+    validators: vec,
+    proposer: opt,
+    total_voting_power,
+});
+
+convert_message!(From, v0_37::types::Vote => v0_34::types::Vote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    validator_address,
+    validator_index,
+    signature,
+});
+
+convert_message!(From, v0_37::version::App => v0_34::version::App {
+    protocol,
+    software,
+});
+
+convert_message!(From, v0_37::version::Consensus => v0_34::version::Consensus {
+    block,
+    app,
+});
+
+convert_message!(From, v0_37::abci::CommitInfo => v0_38::abci::CommitInfo {
+    round,
+    votes: vec,
+});
+
+convert_message!(From, v0_37::abci::Event => v0_38::abci::Event {
+    r#type,
+    attributes: vec,
+});
+
+convert_message!(From, v0_37::abci::EventAttribute => v0_38::abci::EventAttribute {
+    key,
+    value,
+    index,
+});
+
+convert_message!(From, v0_37::abci::ExtendedCommitInfo => v0_38::abci::ExtendedCommitInfo {
+    round,
+    votes: vec,
+});
+
+convert_message!(From, v0_37::abci::Misbehavior => v0_38::abci::Misbehavior {
+    r#type,
+    validator: opt,
+    height,
+    time,
+    total_voting_power,
+});
+
+convert_message!(TryFrom, v0_37::abci::Request => v0_38::abci::Request {
+    value: try_opt,
+});
+
+convert_message!(From, v0_37::abci::RequestApplySnapshotChunk => v0_38::abci::RequestApplySnapshotChunk {
+    index,
+    chunk,
+    sender,
+});
+
+convert_message!(From, v0_37::abci::RequestCheckTx => v0_38::abci::RequestCheckTx {
+    tx,
+    r#type,
+});
+
+convert_message!(From, v0_37::abci::RequestCommit => v0_38::abci::RequestCommit {});
+
+convert_message!(From, v0_37::abci::RequestEcho => v0_38::abci::RequestEcho {
+    message,
+});
+
+convert_message!(From, v0_37::abci::RequestFlush => v0_38::abci::RequestFlush {});
+
+convert_message!(From, v0_37::abci::RequestInfo => v0_38::abci::RequestInfo {
+    version,
+    block_version,
+    p2p_version,
+    abci_version,
+});
+
+convert_message!(From, v0_37::abci::RequestInitChain => v0_38::abci::RequestInitChain {
+    time,
+    chain_id,
+    consensus_params: opt,
+    validators: vec,
+    app_state_bytes,
+    initial_height,
+});
+
+convert_message!(From, v0_37::abci::RequestListSnapshots => v0_38::abci::RequestListSnapshots {});
+
+convert_message!(From, v0_37::abci::RequestLoadSnapshotChunk => v0_38::abci::RequestLoadSnapshotChunk {
+    height,
+    format,
+    chunk,
+});
+
+convert_message!(From, v0_37::abci::RequestOfferSnapshot => v0_38::abci::RequestOfferSnapshot {
+    snapshot: opt,
+    app_hash,
+});
+
+convert_message!(From, v0_37::abci::RequestPrepareProposal => v0_38::abci::RequestPrepareProposal {
+    max_tx_bytes,
+    txs,
+    local_last_commit: opt,
+    misbehavior: vec,
+    height,
+    time,
+    next_validators_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_37::abci::RequestProcessProposal => v0_38::abci::RequestProcessProposal {
+    txs,
+    proposed_last_commit: opt,
+    misbehavior: vec,
+    hash,
+    height,
+    time,
+    next_validators_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_37::abci::RequestQuery => v0_38::abci::RequestQuery {
+    data,
+    path,
+    height,
+    prove,
+});
+
+convert_message!(TryFrom, v0_37::abci::Response => v0_38::abci::Response {
+    value: try_opt,
+});
+
+convert_message!(From, v0_37::abci::ResponseApplySnapshotChunk => v0_38::abci::ResponseApplySnapshotChunk {
+    result,
+    refetch_chunks,
+    reject_senders,
+});
+
+convert_message!(From, v0_37::abci::ResponseDeliverTx => v0_38::abci::ExecTxResult {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: vec,
+    codespace,
+});
+
+convert_message!(From, v0_37::abci::ResponseEcho => v0_38::abci::ResponseEcho {
+    message,
+});
+
+convert_message!(From, v0_37::abci::ResponseException => v0_38::abci::ResponseException {
+    error,
+});
+
+convert_message!(From, v0_37::abci::ResponseFlush => v0_38::abci::ResponseFlush {});
+
+convert_message!(From, v0_37::abci::ResponseInfo => v0_38::abci::ResponseInfo {
+    data,
+    version,
+    app_version,
+    last_block_height,
+    last_block_app_hash,
+});
+
+convert_message!(From, v0_37::abci::ResponseInitChain => v0_38::abci::ResponseInitChain {
+    consensus_params: opt,
+    validators: vec,
+    app_hash,
+});
+
+convert_message!(From, v0_37::abci::ResponseListSnapshots => v0_38::abci::ResponseListSnapshots {
+    snapshots: vec,
+});
+
+convert_message!(From, v0_37::abci::ResponseLoadSnapshotChunk => v0_38::abci::ResponseLoadSnapshotChunk {
+    chunk,
+});
+
+convert_message!(From, v0_37::abci::ResponseOfferSnapshot => v0_38::abci::ResponseOfferSnapshot {
+    result,
+});
+
+convert_message!(From, v0_37::abci::ResponsePrepareProposal => v0_38::abci::ResponsePrepareProposal {
+    txs,
+});
+
+convert_message!(From, v0_37::abci::ResponseProcessProposal => v0_38::abci::ResponseProcessProposal {
+    status,
+});
+
+convert_message!(From, v0_37::abci::ResponseQuery => v0_38::abci::ResponseQuery {
+    code,
+    log,
+    info,
+    index,
+    key,
+    value,
+    proof_ops: opt,
+    height,
+    codespace,
+});
+
+convert_message!(From, v0_37::abci::Snapshot => v0_38::abci::Snapshot {
+    height,
+    format,
+    chunks,
+    hash,
+    metadata,
+});
+
+convert_message!(From, v0_37::abci::TxResult => v0_38::abci::TxResult {
+    height,
+    index,
+    tx,
+    result: opt,
+});
+
+convert_message!(From, v0_37::abci::Validator => v0_38::abci::Validator {
+    address,
+    power,
+});
+
+convert_message!(From, v0_37::abci::ValidatorUpdate => v0_38::abci::ValidatorUpdate {
+    pub_key: opt,
+    power,
+});
+
+convert_message!(From, v0_37::crypto::DominoOp => v0_38::crypto::DominoOp {
+    key,
+    input,
+    output,
+});
+
+convert_message!(From, v0_37::crypto::Proof => v0_38::crypto::Proof {
+    total,
+    index,
+    leaf_hash,
+    aunts,
+});
+
+convert_message!(From, v0_37::crypto::ProofOp => v0_38::crypto::ProofOp {
+    r#type,
+    key,
+    data,
+});
+
+convert_message!(From, v0_37::crypto::ProofOps => v0_38::crypto::ProofOps {
+    ops: vec,
+});
+
+convert_message!(From, v0_37::crypto::PublicKey => v0_38::crypto::PublicKey {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_37::crypto::public_key::Sum => v0_38::crypto::public_key::Sum {
+    Ed25519,
+    Secp256k1,
+});
+
+convert_message!(From, v0_37::crypto::ValueOp => v0_38::crypto::ValueOp {
+    key,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::Block => v0_38::types::Block {
+    header: opt,
+    data: opt,
+    evidence: opt,
+    last_commit: opt,
+});
+
+convert_message!(From, v0_37::types::BlockId => v0_38::types::BlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_37::types::BlockMeta => v0_38::types::BlockMeta {
+    block_id: opt,
+    block_size,
+    header: opt,
+    num_txs,
+});
+
+convert_message!(From, v0_37::types::BlockParams => v0_38::types::BlockParams {
+    max_bytes,
+    max_gas,
+});
+
+convert_message!(From, v0_37::types::CanonicalBlockId => v0_38::types::CanonicalBlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_37::types::CanonicalPartSetHeader => v0_38::types::CanonicalPartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_37::types::CanonicalProposal => v0_38::types::CanonicalProposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_37::types::CanonicalVote => v0_38::types::CanonicalVote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_37::types::Commit => v0_38::types::Commit {
+    height,
+    round,
+    block_id: opt,
+    signatures: vec,
+});
+
+convert_message!(From, v0_37::types::CommitSig => v0_38::types::CommitSig {
+    block_id_flag,
+    validator_address,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_37::types::Data => v0_38::types::Data {
+    txs,
+});
+
+convert_message!(From, v0_37::types::DuplicateVoteEvidence => v0_38::types::DuplicateVoteEvidence {
+    vote_a: opt,
+    vote_b: opt,
+    total_voting_power,
+    validator_power,
+    timestamp,
+});
+
+convert_message!(From, v0_37::types::EventDataRoundState => v0_38::types::EventDataRoundState {
+    height,
+    round,
+    step,
+});
+
+convert_message!(From, v0_37::types::Evidence => v0_38::types::Evidence {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_37::types::evidence::Sum => v0_38::types::evidence::Sum {
+    DuplicateVoteEvidence: into,
+    LightClientAttackEvidence: into,
+});
+
+convert_message!(From, v0_37::types::EvidenceList => v0_38::types::EvidenceList {
+    evidence: vec,
+});
+
+convert_message!(From, v0_37::types::EvidenceParams => v0_38::types::EvidenceParams {
+    max_age_num_blocks,
+    max_age_duration,
+    max_bytes,
+});
+
+convert_message!(From, v0_37::types::HashedParams => v0_38::types::HashedParams {
+    block_max_bytes,
+    block_max_gas,
+});
+
+convert_message!(From, v0_37::types::Header => v0_38::types::Header {
+    version: opt,
+    chain_id,
+    height,
+    time,
+    last_block_id: opt,
+    last_commit_hash,
+    data_hash,
+    validators_hash,
+    next_validators_hash,
+    consensus_hash,
+    app_hash,
+    last_results_hash,
+    evidence_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_37::types::LightBlock => v0_38::types::LightBlock {
+    signed_header: opt,
+    validator_set: opt,
+});
+
+convert_message!(From, v0_37::types::LightClientAttackEvidence => v0_38::types::LightClientAttackEvidence {
+    conflicting_block: opt,
+    common_height,
+    byzantine_validators: vec,
+    total_voting_power,
+    timestamp,
+});
+
+convert_message!(From, v0_37::types::Part => v0_38::types::Part {
+    index,
+    bytes,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::PartSetHeader => v0_38::types::PartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_37::types::Proposal => v0_38::types::Proposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_37::types::SignedHeader => v0_38::types::SignedHeader {
+    header: opt,
+    commit: opt,
+});
+
+convert_message!(From, v0_37::types::SimpleValidator => v0_38::types::SimpleValidator {
+    pub_key: opt,
+    voting_power,
+});
+
+convert_message!(From, v0_37::types::TxProof => v0_38::types::TxProof {
+    root_hash,
+    data,
+    proof: opt,
+});
+
+convert_message!(From, v0_37::types::Validator => v0_38::types::Validator {
+    address,
+    pub_key: opt,
+    voting_power,
+    proposer_priority,
+});
+
+convert_message!(From, v0_37::types::ValidatorParams => v0_38::types::ValidatorParams {
+    pub_key_types,
+});
+
+convert_message!(From, v0_37::types::ValidatorSet => v0_38::types::ValidatorSet {
+    validators: vec,
+    proposer: opt,
+    total_voting_power,
+});
+
+convert_message!(From, v0_37::types::VersionParams => v0_38::types::VersionParams {
+    app,
+});
+
+convert_message!(From, v0_37::version::App => v0_38::version::App {
+    protocol,
+    software,
+});
+
+convert_message!(From, v0_37::version::Consensus => v0_38::version::Consensus {
+    block,
+    app,
+});
+
+convert_message!(From, v0_38::abci::CommitInfo => v0_37::abci::CommitInfo {
+    round,
+    votes: vec,
+});
+
+convert_message!(From, v0_38::abci::Event => v0_37::abci::Event {
+    r#type,
+    attributes: vec,
+});
+
+convert_message!(From, v0_38::abci::EventAttribute => v0_37::abci::EventAttribute {
+    key,
+    value,
+    index,
+});
+
+convert_message!(TryFrom, v0_38::abci::ExtendedCommitInfo => v0_37::abci::ExtendedCommitInfo {
+    round,
+    votes: try_vec,
+});
+
+convert_message!(From, v0_38::abci::Misbehavior => v0_37::abci::Misbehavior {
+    r#type,
+    validator: opt,
+    height,
+    time,
+    total_voting_power,
+});
+
+convert_message!(TryFrom, v0_38::abci::Request => v0_37::abci::Request {
+    value: try_opt,
+});
+
+convert_message!(From, v0_38::abci::RequestApplySnapshotChunk => v0_37::abci::RequestApplySnapshotChunk {
+    index,
+    chunk,
+    sender,
+});
+
+convert_message!(From, v0_38::abci::RequestCheckTx => v0_37::abci::RequestCheckTx {
+    tx,
+    r#type,
+});
+
+convert_message!(From, v0_38::abci::RequestCommit => v0_37::abci::RequestCommit {});
+
+convert_message!(From, v0_38::abci::RequestEcho => v0_37::abci::RequestEcho {
+    message,
+});
+
+convert_message!(From, v0_38::abci::RequestFlush => v0_37::abci::RequestFlush {});
+
+convert_message!(From, v0_38::abci::RequestInfo => v0_37::abci::RequestInfo {
+    version,
+    block_version,
+    p2p_version,
+    abci_version,
+});
+
+convert_message!(TryFrom, v0_38::abci::RequestInitChain => v0_37::abci::RequestInitChain {
+    time,
+    chain_id,
+    consensus_params: try_opt,
+    validators: vec,
+    app_state_bytes,
+    initial_height,
+});
+
+convert_message!(From, v0_38::abci::RequestListSnapshots => v0_37::abci::RequestListSnapshots {});
+
+convert_message!(From, v0_38::abci::RequestLoadSnapshotChunk => v0_37::abci::RequestLoadSnapshotChunk {
+    height,
+    format,
+    chunk,
+});
+
+convert_message!(From, v0_38::abci::RequestOfferSnapshot => v0_37::abci::RequestOfferSnapshot {
+    snapshot: opt,
+    app_hash,
+});
+
+convert_message!(TryFrom, v0_38::abci::RequestPrepareProposal => v0_37::abci::RequestPrepareProposal {
+    max_tx_bytes,
+    txs,
+    local_last_commit: try_opt,
+    misbehavior: vec,
+    height,
+    time,
+    next_validators_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_38::abci::RequestProcessProposal => v0_37::abci::RequestProcessProposal {
+    txs,
+    proposed_last_commit: opt,
+    misbehavior: vec,
+    hash,
+    height,
+    time,
+    next_validators_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_38::abci::RequestQuery => v0_37::abci::RequestQuery {
+    data,
+    path,
+    height,
+    prove,
+});
+
+convert_message!(TryFrom, v0_38::abci::Response => v0_37::abci::Response {
+    value: try_opt,
+});
+
+convert_message!(From, v0_38::abci::ResponseApplySnapshotChunk => v0_37::abci::ResponseApplySnapshotChunk {
+    result,
+    refetch_chunks,
+    reject_senders,
+});
+
+convert_message!(From, v0_38::abci::ExecTxResult => v0_37::abci::ResponseDeliverTx {
+    code,
+    data,
+    log,
+    info,
+    gas_wanted,
+    gas_used,
+    events: vec,
+    codespace,
+});
+
+convert_message!(From, v0_38::abci::ResponseEcho => v0_37::abci::ResponseEcho {
+    message,
+});
+
+convert_message!(From, v0_38::abci::ResponseException => v0_37::abci::ResponseException {
+    error,
+});
+
+convert_message!(From, v0_38::abci::ResponseFlush => v0_37::abci::ResponseFlush {});
+
+convert_message!(From, v0_38::abci::ResponseInfo => v0_37::abci::ResponseInfo {
+    data,
+    version,
+    app_version,
+    last_block_height,
+    last_block_app_hash,
+});
+
+convert_message!(TryFrom, v0_38::abci::ResponseInitChain => v0_37::abci::ResponseInitChain {
+    consensus_params: try_opt,
+    validators: vec,
+    app_hash,
+});
+
+convert_message!(From, v0_38::abci::ResponseListSnapshots => v0_37::abci::ResponseListSnapshots {
+    snapshots: vec,
+});
+
+convert_message!(From, v0_38::abci::ResponseLoadSnapshotChunk => v0_37::abci::ResponseLoadSnapshotChunk {
+    chunk,
+});
+
+convert_message!(From, v0_38::abci::ResponseOfferSnapshot => v0_37::abci::ResponseOfferSnapshot {
+    result,
+});
+
+convert_message!(From, v0_38::abci::ResponsePrepareProposal => v0_37::abci::ResponsePrepareProposal {
+    txs,
+});
+
+convert_message!(From, v0_38::abci::ResponseProcessProposal => v0_37::abci::ResponseProcessProposal {
+    status,
+});
+
+convert_message!(From, v0_38::abci::ResponseQuery => v0_37::abci::ResponseQuery {
+    code,
+    log,
+    info,
+    index,
+    key,
+    value,
+    proof_ops: opt,
+    height,
+    codespace,
+});
+
+convert_message!(From, v0_38::abci::Snapshot => v0_37::abci::Snapshot {
+    height,
+    format,
+    chunks,
+    hash,
+    metadata,
+});
+
+convert_message!(From, v0_38::abci::TxResult => v0_37::abci::TxResult {
+    height,
+    index,
+    tx,
+    result: opt,
+});
+
+convert_message!(From, v0_38::abci::Validator => v0_37::abci::Validator {
+    address,
+    power,
+});
+
+convert_message!(From, v0_38::abci::ValidatorUpdate => v0_37::abci::ValidatorUpdate {
+    pub_key: opt,
+    power,
+});
+
+convert_message!(From, v0_38::crypto::DominoOp => v0_37::crypto::DominoOp {
+    key,
+    input,
+    output,
+});
+
+convert_message!(From, v0_38::crypto::Proof => v0_37::crypto::Proof {
+    total,
+    index,
+    leaf_hash,
+    aunts,
+});
+
+convert_message!(From, v0_38::crypto::ProofOp => v0_37::crypto::ProofOp {
+    r#type,
+    key,
+    data,
+});
+
+convert_message!(From, v0_38::crypto::ProofOps => v0_37::crypto::ProofOps {
+    ops: vec,
+});
+
+convert_message!(From, v0_38::crypto::PublicKey => v0_37::crypto::PublicKey {
+    sum: opt,
+});
+
+convert_oneof!(From, v0_38::crypto::public_key::Sum => v0_37::crypto::public_key::Sum {
+    Ed25519,
+    Secp256k1,
+});
+
+convert_message!(From, v0_38::crypto::ValueOp => v0_37::crypto::ValueOp {
+    key,
+    proof: opt,
+});
+
+convert_message!(TryFrom, v0_38::types::Block => v0_37::types::Block {
+    header: opt,
+    data: opt,
+    evidence: try_opt,
+    last_commit: opt,
+});
+
+convert_message!(From, v0_38::types::BlockId => v0_37::types::BlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_38::types::BlockMeta => v0_37::types::BlockMeta {
+    block_id: opt,
+    block_size,
+    header: opt,
+    num_txs,
+});
+
+convert_message!(From, v0_38::types::BlockParams => v0_37::types::BlockParams {
+    max_bytes,
+    max_gas,
+});
+
+convert_message!(From, v0_38::types::CanonicalBlockId => v0_37::types::CanonicalBlockId {
+    hash,
+    part_set_header: opt,
+});
+
+convert_message!(From, v0_38::types::CanonicalPartSetHeader => v0_37::types::CanonicalPartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_38::types::CanonicalProposal => v0_37::types::CanonicalProposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_38::types::CanonicalVote => v0_37::types::CanonicalVote {
+    r#type,
+    height,
+    round,
+    block_id: opt,
+    timestamp,
+    chain_id,
+});
+
+convert_message!(From, v0_38::types::Commit => v0_37::types::Commit {
+    height,
+    round,
+    block_id: opt,
+    signatures: vec,
+});
+
+convert_message!(From, v0_38::types::CommitSig => v0_37::types::CommitSig {
+    block_id_flag,
+    validator_address,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_38::types::Data => v0_37::types::Data {
+    txs,
+});
+
+convert_message!(TryFrom, v0_38::types::DuplicateVoteEvidence => v0_37::types::DuplicateVoteEvidence {
+    vote_a: try_opt,
+    vote_b: try_opt,
+    total_voting_power,
+    validator_power,
+    timestamp,
+});
+
+convert_message!(From, v0_38::types::EventDataRoundState => v0_37::types::EventDataRoundState {
+    height,
+    round,
+    step,
+});
+
+convert_message!(TryFrom, v0_38::types::Evidence => v0_37::types::Evidence {
+    sum: try_opt,
+});
+
+convert_oneof!(TryFrom, v0_38::types::evidence::Sum => v0_37::types::evidence::Sum {
+    DuplicateVoteEvidence: try_into,
+    LightClientAttackEvidence: into,
+});
+
+convert_message!(TryFrom, v0_38::types::EvidenceList => v0_37::types::EvidenceList {
+    evidence: try_vec,
+});
+
+convert_message!(From, v0_38::types::EvidenceParams => v0_37::types::EvidenceParams {
+    max_age_num_blocks,
+    max_age_duration,
+    max_bytes,
+});
+
+convert_message!(From, v0_38::types::HashedParams => v0_37::types::HashedParams {
+    block_max_bytes,
+    block_max_gas,
+});
+
+convert_message!(From, v0_38::types::Header => v0_37::types::Header {
+    version: opt,
+    chain_id,
+    height,
+    time,
+    last_block_id: opt,
+    last_commit_hash,
+    data_hash,
+    validators_hash,
+    next_validators_hash,
+    consensus_hash,
+    app_hash,
+    last_results_hash,
+    evidence_hash,
+    proposer_address,
+});
+
+convert_message!(From, v0_38::types::LightBlock => v0_37::types::LightBlock {
+    signed_header: opt,
+    validator_set: opt,
+});
+
+convert_message!(From, v0_38::types::LightClientAttackEvidence => v0_37::types::LightClientAttackEvidence {
+    conflicting_block: opt,
+    common_height,
+    byzantine_validators: vec,
+    total_voting_power,
+    timestamp,
+});
+
+convert_message!(From, v0_38::types::Part => v0_37::types::Part {
+    index,
+    bytes,
+    proof: opt,
+});
+
+convert_message!(From, v0_38::types::PartSetHeader => v0_37::types::PartSetHeader {
+    total,
+    hash,
+});
+
+convert_message!(From, v0_38::types::Proposal => v0_37::types::Proposal {
+    r#type,
+    height,
+    round,
+    pol_round,
+    block_id: opt,
+    timestamp,
+    signature,
+});
+
+convert_message!(From, v0_38::types::SignedHeader => v0_37::types::SignedHeader {
+    header: opt,
+    commit: opt,
+});
+
+convert_message!(From, v0_38::types::SimpleValidator => v0_37::types::SimpleValidator {
+    pub_key: opt,
+    voting_power,
+});
+
+convert_message!(From, v0_38::types::TxProof => v0_37::types::TxProof {
+    root_hash,
+    data,
+    proof: opt,
+});
+
+convert_message!(From, v0_38::types::Validator => v0_37::types::Validator {
+    address,
+    pub_key: opt,
+    voting_power,
+    proposer_priority,
+});
+
+convert_message!(From, v0_38::types::ValidatorParams => v0_37::types::ValidatorParams {
+    pub_key_types,
+});
+
+convert_message!(From, v0_38::types::ValidatorSet => v0_37::types::ValidatorSet {
+    validators: vec,
+    proposer: opt,
+    total_voting_power,
+});
+
+convert_message!(From, v0_38::types::VersionParams => v0_37::types::VersionParams {
+    app,
+});
+
+convert_message!(From, v0_38::version::App => v0_37::version::App {
+    protocol,
+    software,
+});
+
+convert_message!(From, v0_38::version::Consensus => v0_37::version::Consensus {
+    block,
+    app,
+});
@@ -64,3 +64,73 @@ pub mod meta {
     pub const REPOSITORY: &str = "https://github.com/tendermint/tendermint";
     pub const COMMITISH: &str = "v0.37.0-alpha.1";
 }
+
+/// Exhaustive matches on the `oneof` enums mapped to domain types. A variant
+/// added upstream fails compilation here until the mapping handles it.
+#[allow(dead_code)]
+mod exhaustiveness {
+    fn abci_request_value(value: &super::abci::request::Value) {
+        use super::abci::request::Value;
+
+        match value {
+            Value::Echo(_) => {},
+            Value::Flush(_) => {},
+            Value::Info(_) => {},
+            Value::InitChain(_) => {},
+            Value::Query(_) => {},
+            Value::BeginBlock(_) => {},
+            Value::CheckTx(_) => {},
+            Value::DeliverTx(_) => {},
+            Value::EndBlock(_) => {},
+            Value::Commit(_) => {},
+            Value::ListSnapshots(_) => {},
+            Value::OfferSnapshot(_) => {},
+            Value::LoadSnapshotChunk(_) => {},
+            Value::ApplySnapshotChunk(_) => {},
+            Value::PrepareProposal(_) => {},
+            Value::ProcessProposal(_) => {},
+        }
+    }
+
+    fn abci_response_value(value: &super::abci::response::Value) {
+        use super::abci::response::Value;
+
+        match value {
+            Value::Exception(_) => {},
+            Value::Echo(_) => {},
+            Value::Flush(_) => {},
+            Value::Info(_) => {},
+            Value::InitChain(_) => {},
+            Value::Query(_) => {},
+            Value::BeginBlock(_) => {},
+            Value::CheckTx(_) => {},
+            Value::DeliverTx(_) => {},
+            Value::EndBlock(_) => {},
+            Value::Commit(_) => {},
+            Value::ListSnapshots(_) => {},
+            Value::OfferSnapshot(_) => {},
+            Value::LoadSnapshotChunk(_) => {},
+            Value::ApplySnapshotChunk(_) => {},
+            Value::PrepareProposal(_) => {},
+            Value::ProcessProposal(_) => {},
+        }
+    }
+
+    fn crypto_public_key_sum(value: &super::crypto::public_key::Sum) {
+        use super::crypto::public_key::Sum;
+
+        match value {
+            Sum::Ed25519(_) => {},
+            Sum::Secp256k1(_) => {},
+        }
+    }
+
+    fn types_evidence_sum(value: &super::types::evidence::Sum) {
+        use super::types::evidence::Sum;
+
+        match value {
+            Sum::DuplicateVoteEvidence(_) => {},
+            Sum::LightClientAttackEvidence(_) => {},
+        }
+    }
+}
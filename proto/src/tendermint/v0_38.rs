@@ -0,0 +1,136 @@
+//! Tendermint-proto auto-generated sub-modules for Tendermint
+
+pub mod abci {
+    include!("../prost/v0_38/tendermint.abci.rs");
+}
+
+pub mod blocksync {
+    include!("../prost/v0_38/tendermint.blocksync.rs");
+}
+
+pub mod consensus {
+    include!("../prost/v0_38/tendermint.consensus.rs");
+}
+
+pub mod crypto {
+    include!("../prost/v0_38/tendermint.crypto.rs");
+}
+
+pub mod libs {
+    pub mod bits {
+        include!("../prost/v0_38/tendermint.libs.bits.rs");
+    }
+}
+
+pub mod mempool {
+    include!("../prost/v0_38/tendermint.mempool.rs");
+}
+
+pub mod p2p {
+    include!("../prost/v0_38/tendermint.p2p.rs");
+}
+
+pub mod privval {
+    include!("../prost/v0_38/tendermint.privval.rs");
+}
+
+pub mod rpc {
+    pub mod grpc {
+        include!("../prost/v0_38/tendermint.rpc.grpc.rs");
+    }
+}
+
+pub mod state {
+    include!("../prost/v0_38/tendermint.state.rs");
+}
+
+pub mod statesync {
+    include!("../prost/v0_38/tendermint.statesync.rs");
+}
+
+pub mod store {
+    include!("../prost/v0_38/tendermint.store.rs");
+}
+
+pub mod types {
+    include!("../prost/v0_38/tendermint.types.rs");
+}
+
+pub mod version {
+    include!("../prost/v0_38/tendermint.version.rs");
+}
+
+pub mod meta {
+    pub const REPOSITORY: &str = "https://github.com/cometbft/cometbft";
+    pub const COMMITISH: &str = "v0.38.0";
+}
+
+/// Exhaustive matches on the `oneof` enums mapped to domain types. A variant
+/// added upstream fails compilation here until the mapping handles it.
+#[allow(dead_code)]
+mod exhaustiveness {
+    fn abci_request_value(value: &super::abci::request::Value) {
+        use super::abci::request::Value;
+
+        match value {
+            Value::Echo(_) => {},
+            Value::Flush(_) => {},
+            Value::Info(_) => {},
+            Value::InitChain(_) => {},
+            Value::Query(_) => {},
+            Value::CheckTx(_) => {},
+            Value::Commit(_) => {},
+            Value::ListSnapshots(_) => {},
+            Value::OfferSnapshot(_) => {},
+            Value::LoadSnapshotChunk(_) => {},
+            Value::ApplySnapshotChunk(_) => {},
+            Value::PrepareProposal(_) => {},
+            Value::ProcessProposal(_) => {},
+            Value::ExtendVote(_) => {},
+            Value::VerifyVoteExtension(_) => {},
+            Value::FinalizeBlock(_) => {},
+        }
+    }
+
+    fn abci_response_value(value: &super::abci::response::Value) {
+        use super::abci::response::Value;
+
+        match value {
+            Value::Exception(_) => {},
+            Value::Echo(_) => {},
+            Value::Flush(_) => {},
+            Value::Info(_) => {},
+            Value::InitChain(_) => {},
+            Value::Query(_) => {},
+            Value::CheckTx(_) => {},
+            Value::Commit(_) => {},
+            Value::ListSnapshots(_) => {},
+            Value::OfferSnapshot(_) => {},
+            Value::LoadSnapshotChunk(_) => {},
+            Value::ApplySnapshotChunk(_) => {},
+            Value::PrepareProposal(_) => {},
+            Value::ProcessProposal(_) => {},
+            Value::ExtendVote(_) => {},
+            Value::VerifyVoteExtension(_) => {},
+            Value::FinalizeBlock(_) => {},
+        }
+    }
+
+    fn crypto_public_key_sum(value: &super::crypto::public_key::Sum) {
+        use super::crypto::public_key::Sum;
+
+        match value {
+            Sum::Ed25519(_) => {},
+            Sum::Secp256k1(_) => {},
+        }
+    }
+
+    fn types_evidence_sum(value: &super::types::evidence::Sum) {
+        use super::types::evidence::Sum;
+
+        match value {
+            Sum::DuplicateVoteEvidence(_) => {},
+            Sum::LightClientAttackEvidence(_) => {},
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Conversions between the messages of the Tendermint versions.
+
+use core::convert::TryFrom;
+
+use bytes::Bytes;
+use tendermint_proto::{google::protobuf::Timestamp, v0_34, v0_37, v0_38};
+
+fn header() -> v0_34::types::Header {
+    v0_34::types::Header {
+        version: Some(v0_34::version::Consensus { block: 11, app: 1 }),
+        chain_id: "test-chain".to_string(),
+        height: 42,
+        time: Some(Timestamp {
+            seconds: 1_600_000_000,
+            nanos: 1,
+        }),
+        last_block_id: Some(v0_34::types::BlockId {
+            hash: vec![1; 32],
+            part_set_header: Some(v0_34::types::PartSetHeader {
+                total: 1,
+                hash: vec![2; 32],
+            }),
+        }),
+        app_hash: vec![3; 32],
+        proposer_address: vec![4; 20],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn header_round_trip() {
+    let header = header();
+    let converted = v0_37::types::Header::from(header.clone());
+    assert_eq!(converted.chain_id, header.chain_id);
+    assert_eq!(converted.version.as_ref().unwrap().block, 11);
+    assert_eq!(
+        converted.last_block_id.as_ref().unwrap().part_set_header,
+        Some(v0_37::types::PartSetHeader {
+            total: 1,
+            hash: vec![2; 32],
+        })
+    );
+    assert_eq!(v0_34::types::Header::from(converted), header);
+}
+
+#[test]
+fn consensus_params() {
+    let params = v0_34::types::ConsensusParams {
+        block: Some(v0_34::types::BlockParams {
+            max_bytes: 22020096,
+            max_gas: -1,
+            time_iota_ms: 500,
+        }),
+        version: Some(v0_34::types::VersionParams { app_version: 3 }),
+        ..Default::default()
+    };
+    let converted = v0_37::types::ConsensusParams::from(params);
+    assert_eq!(
+        converted.block,
+        Some(v0_37::types::BlockParams {
+            max_bytes: 22020096,
+            max_gas: -1,
+        })
+    );
+    assert_eq!(
+        converted.version,
+        Some(v0_37::types::VersionParams { app: 3 })
+    );
+
+    // Tendermint 0.37 no longer has a minimum time between blocks.
+    let back = v0_34::types::ConsensusParams::from(converted.clone());
+    assert_eq!(back.block.unwrap().time_iota_ms, 1000);
+
+    // The consensus parameters of ABCI have their own definition in 0.34.
+    let response = v0_34::abci::ResponseInitChain::from(v0_37::abci::ResponseInitChain {
+        consensus_params: Some(converted),
+        ..Default::default()
+    });
+    assert_eq!(
+        response.consensus_params.unwrap().block,
+        Some(v0_34::abci::BlockParams {
+            max_bytes: 22020096,
+            max_gas: -1,
+        })
+    );
+}
+
+#[test]
+fn event_attributes() {
+    let response = |key: &'static [u8]| v0_34::abci::ResponseDeliverTx {
+        code: 1,
+        events: vec![v0_34::abci::Event {
+            r#type: "transfer".to_string(),
+            attributes: vec![v0_34::abci::EventAttribute {
+                key: Bytes::from_static(key),
+                value: Bytes::from_static(b"alice"),
+                index: true,
+            }],
+        }],
+        ..Default::default()
+    };
+
+    let converted = v0_37::abci::ResponseDeliverTx::try_from(response(b"sender")).unwrap();
+    assert_eq!(converted.code, 1);
+    assert_eq!(
+        converted.events[0].attributes,
+        vec![v0_37::abci::EventAttribute {
+            key: "sender".to_string(),
+            value: "alice".to_string(),
+            index: true,
+        }]
+    );
+    assert_eq!(
+        v0_34::abci::ResponseDeliverTx::from(converted),
+        response(b"sender")
+    );
+
+    assert!(v0_37::abci::ResponseDeliverTx::try_from(response(b"\xff")).is_err());
+}
+
+#[test]
+fn requests() {
+    let request = |value| v0_34::abci::Request { value: Some(value) };
+
+    let echo = v0_34::abci::request::Value::Echo(v0_34::abci::RequestEcho {
+        message: "hello".to_string(),
+    });
+    let converted = v0_37::abci::Request::try_from(request(echo.clone())).unwrap();
+    assert_eq!(
+        converted.value,
+        Some(v0_37::abci::request::Value::Echo(
+            v0_37::abci::RequestEcho {
+                message: "hello".to_string(),
+            }
+        ))
+    );
+    assert_eq!(
+        v0_34::abci::Request::try_from(converted).unwrap(),
+        request(echo)
+    );
+
+    let set_option = v0_34::abci::request::Value::SetOption(Default::default());
+    assert!(v0_37::abci::Request::try_from(request(set_option)).is_err());
+
+    let prepare_proposal = v0_37::abci::Request {
+        value: Some(v0_37::abci::request::Value::PrepareProposal(
+            Default::default(),
+        )),
+    };
+    assert!(v0_34::abci::Request::try_from(prepare_proposal).is_err());
+}
+
+#[test]
+fn header_and_commit_round_trip_to_0_38() {
+    let header = v0_37::types::Header::from(header());
+    let converted = v0_38::types::Header::from(header.clone());
+    assert_eq!(converted.height, 42);
+    assert_eq!(v0_37::types::Header::from(converted), header);
+
+    let commit = v0_37::types::Commit {
+        height: 42,
+        round: 1,
+        block_id: header.last_block_id.clone(),
+        signatures: vec![v0_37::types::CommitSig {
+            block_id_flag: v0_37::types::BlockIdFlag::Commit as i32,
+            validator_address: vec![4; 20],
+            timestamp: header.time.clone(),
+            signature: vec![5; 64],
+        }],
+    };
+    let converted = v0_38::types::Commit::from(commit.clone());
+    assert_eq!(converted.signatures.len(), 1);
+    assert_eq!(v0_37::types::Commit::from(converted), commit);
+}
+
+#[test]
+fn consensus_params_to_0_38() {
+    let params = v0_37::types::ConsensusParams {
+        block: Some(v0_37::types::BlockParams {
+            max_bytes: 22020096,
+            max_gas: -1,
+        }),
+        ..Default::default()
+    };
+    let converted = v0_38::types::ConsensusParams::from(params.clone());
+    assert_eq!(converted.abci, None);
+    assert_eq!(
+        v0_37::types::ConsensusParams::try_from(converted.clone()).unwrap(),
+        params
+    );
+
+    // Tendermint 0.37 has no vote extensions to enable.
+    let with_extensions = v0_38::types::ConsensusParams {
+        abci: Some(v0_38::types::AbciParams {
+            vote_extensions_enable_height: 10,
+        }),
+        ..converted
+    };
+    assert!(v0_37::types::ConsensusParams::try_from(with_extensions).is_err());
+}
+
+#[test]
+fn votes_to_0_38() {
+    let vote = v0_37::types::Vote {
+        height: 42,
+        validator_index: 3,
+        signature: vec![5; 64],
+        ..Default::default()
+    };
+    let converted = v0_38::types::Vote::from(vote.clone());
+    assert!(converted.extension.is_empty());
+    assert_eq!(
+        v0_37::types::Vote::try_from(converted.clone()).unwrap(),
+        vote
+    );
+
+    let extended = v0_38::types::Vote {
+        extension: b"extension".to_vec(),
+        extension_signature: vec![6; 64],
+        ..converted
+    };
+    assert!(v0_37::types::Vote::try_from(extended).is_err());
+
+    let vote_info = |signed_last_block| v0_37::abci::VoteInfo {
+        validator: None,
+        signed_last_block,
+    };
+    let flag = |info: v0_37::abci::VoteInfo| v0_38::abci::VoteInfo::from(info).block_id_flag;
+    assert_eq!(
+        flag(vote_info(true)),
+        v0_38::types::BlockIdFlag::Commit as i32
+    );
+    assert_eq!(
+        flag(vote_info(false)),
+        v0_38::types::BlockIdFlag::Absent as i32
+    );
+
+    // A nil vote was still signed by the validator.
+    let nil = v0_38::abci::VoteInfo {
+        validator: None,
+        block_id_flag: v0_38::types::BlockIdFlag::Nil as i32,
+    };
+    assert_eq!(v0_37::abci::VoteInfo::from(nil), vote_info(true));
+}
+
+#[test]
+fn requests_to_0_38() {
+    let request = |value| v0_37::abci::Request { value: Some(value) };
+
+    let echo = v0_37::abci::request::Value::Echo(v0_37::abci::RequestEcho {
+        message: "hello".to_string(),
+    });
+    let converted = v0_38::abci::Request::try_from(request(echo.clone())).unwrap();
+    assert_eq!(
+        converted.value,
+        Some(v0_38::abci::request::Value::Echo(
+            v0_38::abci::RequestEcho {
+                message: "hello".to_string(),
+            }
+        ))
+    );
+    assert_eq!(
+        v0_37::abci::Request::try_from(converted).unwrap(),
+        request(echo)
+    );
+
+    let begin_block = v0_37::abci::request::Value::BeginBlock(Default::default());
+    assert!(v0_38::abci::Request::try_from(request(begin_block)).is_err());
+
+    let finalize_block = v0_38::abci::Request {
+        value: Some(v0_38::abci::request::Value::FinalizeBlock(
+            Default::default(),
+        )),
+    };
+    assert!(v0_37::abci::Request::try_from(finalize_block).is_err());
+}
@@ -1,6 +1,9 @@
 use core::convert::TryFrom;
 
-use tendermint_proto::v0_37::types::{BlockId as RawBlockId, PartSetHeader as RawPartSetHeader};
+use tendermint_proto::google::protobuf::Timestamp;
+use tendermint_proto::v0_37::types::{
+    BlockId as RawBlockId, CanonicalVote as RawCanonicalVote, PartSetHeader as RawPartSetHeader,
+};
 use tendermint_proto::Protobuf;
 
 impl Protobuf<RawBlockId> for BlockId {}
@@ -109,3 +112,81 @@ pub fn protobuf_struct_conveniences_example() {
     let new_domain_type = BlockId::decode_length_delimited_vec(&wire).unwrap();
     assert_eq!(my_domain_type, new_domain_type);
 }
+
+// Identity domain type, to compare the encoding of raw messages with the one
+// of Go.
+#[derive(Clone, Debug)]
+pub struct CanonicalVote(RawCanonicalVote);
+
+impl Protobuf<RawCanonicalVote> for CanonicalVote {}
+
+impl TryFrom<RawCanonicalVote> for CanonicalVote {
+    type Error = &'static str;
+
+    fn try_from(value: RawCanonicalVote) -> Result<Self, Self::Error> {
+        Ok(Self(value))
+    }
+}
+
+impl From<CanonicalVote> for RawCanonicalVote {
+    fn from(value: CanonicalVote) -> Self {
+        value.0
+    }
+}
+
+#[test]
+pub fn deterministic_encoding_matches_go() {
+    // The zero time of Go.
+    let timestamp = Some(Timestamp {
+        seconds: -62135596800,
+        nanos: 0,
+    });
+    let vote = |r#type, height, round, chain_id: &str| {
+        CanonicalVote(RawCanonicalVote {
+            r#type,
+            height,
+            round,
+            block_id: None,
+            timestamp: timestamp.clone(),
+            chain_id: chain_id.to_string(),
+        })
+    };
+    let zero_time = [
+        0x2a, 0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+    ];
+    let height_and_round = [
+        0x11, 0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x19, 0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,
+    ];
+
+    // The sign bytes of `TestVoteSignBytesTestVectors` in Go, without their
+    // length delimiter.
+    let vectors = [
+        (vote(0, 0, 0, ""), zero_time.to_vec()),
+        (
+            vote(2, 1, 1, ""),
+            [&[0x8, 0x2][..], &height_and_round, &zero_time].concat(),
+        ),
+        (
+            vote(1, 1, 1, ""),
+            [&[0x8, 0x1][..], &height_and_round, &zero_time].concat(),
+        ),
+        (
+            vote(0, 1, 1, ""),
+            [&height_and_round[..], &zero_time].concat(),
+        ),
+        (
+            vote(0, 1, 1, "test_chain_id"),
+            [
+                &height_and_round[..],
+                &zero_time,
+                &[0x32, 0xd],
+                b"test_chain_id",
+            ]
+            .concat(),
+        ),
+    ];
+    for (vote, want) in vectors {
+        assert_eq!(vote.encode_deterministic_vec().unwrap(), want, "{vote:?}");
+        assert_eq!(vote.encode_vec().unwrap(), want);
+    }
+}
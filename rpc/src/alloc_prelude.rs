@@ -0,0 +1,11 @@
+// Re-export according to alloc::prelude::v1 because it is not yet stabilized
+// https://doc.rust-lang.org/src/alloc/prelude/v1.rs.html
+pub use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+pub use core::prelude::v1::*;
@@ -2,31 +2,63 @@
 
 mod compat;
 pub use compat::CompatMode;
+pub mod blocks;
+pub mod handover;
+pub mod layer;
+pub mod loadtest;
+pub mod metrics;
+pub mod power;
+pub mod rounds;
+mod search;
+pub use search::SearchStreamConfig;
+pub mod spill;
 mod subscription;
 pub use subscription::{Subscription, SubscriptionClient};
 pub mod sync;
+pub mod watch;
 
 mod transport;
 
+#[cfg(feature = "grpc-client")]
+pub use transport::grpc::{GrpcClient, NodeVersion};
 #[cfg(feature = "http-client")]
-pub use transport::http::{HttpClient, HttpClientUrl};
-pub use transport::mock::{MockClient, MockRequestMatcher, MockRequestMethodMatcher};
+pub use transport::http::{
+    Batch, BatchEntry, BatchResponse, Builder as HttpClientBuilder, HttpClient, HttpClientUrl,
+};
+#[cfg(feature = "prometheus")]
+pub use transport::instrument::PrometheusMetrics;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use transport::instrument::{CallOutcome, ErrorClass, Instrumentation};
+pub use transport::mock::{
+    Fixture, FixtureMatcher, Interaction, MockClient, MockClientDriver, MockRequestMatcher,
+    MockRequestMethodMatcher, RecordedEvent, RecordingClient, RecordingDriver,
+};
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use transport::proxy::ProxyUrl;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use transport::resolve::Resolver;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use transport::timing::{CallTimings, SlowCallLogger, TimingObserver};
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use transport::tls::TlsConfig;
 #[cfg(feature = "websocket-client")]
 pub use transport::websocket::{
+    Builder as WebSocketClientBuilder, KeepAliveConfig, ReconnectConfig, SubscriptionStats,
     WebSocketClient, WebSocketClientDriver, WebSocketClientUrl, WebSocketConfig,
 };
 
 use core::{fmt, time::Duration};
 
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use tendermint::{abci, block::Height, evidence::Evidence, Genesis, Hash};
 use tokio::time;
 
 use crate::{
+    alloc_prelude::*,
     endpoint::{validators::DEFAULT_VALIDATORS_PER_PAGE, *},
     paging::Paging,
-    prelude::*,
     query::Query,
     Error, Order, SimpleRequest,
 };
@@ -38,6 +70,12 @@ use crate::{
 /// [`SubscriptionClient`] trait.
 ///
 /// [`SubscriptionClient`]: trait.SubscriptionClient.html
+///
+/// The futures returned by the methods of this trait are `Send` for every
+/// client which is `Sync`, so that they can be spawned onto multi-threaded
+/// runtimes (e.g. with `tokio::spawn`). All the clients of this crate are
+/// `Send + Sync`, as are their drivers and the futures of their inherent
+/// async methods, which is checked by the `send` tests of this crate.
 #[async_trait]
 pub trait Client {
     /// `/abci_info`: get information about the ABCI application.
@@ -125,6 +163,51 @@ pub trait Client {
             .await
     }
 
+    /// `/block_search`: stream all the blocks matching the given query,
+    /// walking through the pages of results.
+    ///
+    /// See [`block_search_stream_with`](Client::block_search_stream_with).
+    fn block_search_stream(
+        &self,
+        query: Query,
+        order: Order,
+    ) -> BoxStream<'_, Result<block::Response, Error>>
+    where
+        Self: Sync,
+    {
+        self.block_search_stream_with(query, order, SearchStreamConfig::default())
+    }
+
+    /// `/block_search`: stream all the blocks matching the given query,
+    /// walking through the pages of results as configured.
+    ///
+    /// The stream ends after the first error.
+    fn block_search_stream_with(
+        &self,
+        query: Query,
+        order: Order,
+        config: SearchStreamConfig,
+    ) -> BoxStream<'_, Result<block::Response, Error>>
+    where
+        Self: Sync,
+    {
+        search::paginate(
+            config,
+            move |page| {
+                let response =
+                    self.block_search(query.clone(), page, config.per_page, order.clone());
+                async move {
+                    let response = response.await?;
+                    Ok(search::Page {
+                        items: response.blocks,
+                        total_count: response.total_count,
+                    })
+                }
+            },
+            |block| block.block.header.height,
+        )
+    }
+
     /// `/blockchain`: get block headers for `min` <= `height` <= `max`.
     ///
     /// Block headers are returned in descending order (highest first).
@@ -188,6 +271,12 @@ pub trait Client {
         self.perform(consensus_state::Request::new()).await
     }
 
+    /// `/dump_consensus_state`: get the full consensus state, including the
+    /// round states of the peers
+    async fn dump_consensus_state(&self) -> Result<dump_consensus_state::Response, Error> {
+        self.perform(dump_consensus_state::Request::new()).await
+    }
+
     // TODO(thane): Simplify once validators endpoint removes pagination.
     /// `/validators`: get validators a given height.
     async fn validators<H>(&self, height: H, paging: Paging) -> Result<validators::Response, Error>
@@ -263,6 +352,59 @@ pub trait Client {
         Ok(self.perform(genesis::Request::default()).await?.genesis)
     }
 
+    /// `/genesis_chunked`: get a chunk of the genesis file.
+    async fn genesis_chunked(&self, chunk: u64) -> Result<genesis_chunked::Response, Error> {
+        self.perform(genesis_chunked::Request::new(chunk)).await
+    }
+
+    /// `/genesis_chunked`: stream the decoded chunks of the genesis file, in
+    /// order, for genesis files too large to be fetched with `/genesis`.
+    ///
+    /// The stream ends after the first error.
+    fn genesis_chunked_stream(&self) -> BoxStream<'_, Result<Vec<u8>, Error>>
+    where
+        Self: Sync,
+    {
+        // The index of the next chunk and the total number of chunks once
+        // known, or `None` after an error.
+        stream::unfold(Some((0, None)), move |state| async move {
+            let (chunk, total) = state?;
+            if total.map_or(false, |total| chunk >= total) {
+                return None;
+            }
+            match self.genesis_chunked(chunk).await {
+                Ok(response) => Some((Ok(response.data), Some((chunk + 1, Some(response.total))))),
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+        .boxed()
+    }
+
+    /// `/genesis_chunked`: get the genesis file by assembling its chunks, for
+    /// genesis files too large to be fetched with `/genesis`.
+    ///
+    /// Fails without fetching the remaining chunks as soon as the chunks
+    /// fetched so far exceed `max_size` bytes. The genesis is deserialized
+    /// once all the chunks are fetched.
+    async fn genesis_chunked_assembled<AppState>(
+        &self,
+        max_size: usize,
+    ) -> Result<Genesis<AppState>, Error>
+    where
+        AppState: fmt::Debug + Serialize + DeserializeOwned + Send,
+    {
+        let mut chunks = self.genesis_chunked_stream();
+        let mut genesis = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            if genesis.len() + chunk.len() > max_size {
+                return Err(Error::genesis_too_large(max_size));
+            }
+            genesis.extend(chunk);
+        }
+        serde_json::from_slice(&genesis).map_err(Error::serde)
+    }
+
     /// `/net_info`: obtain information about P2P and other network connections.
     async fn net_info(&self) -> Result<net_info::Response, Error> {
         self.perform(net_info::Request).await
@@ -279,6 +421,57 @@ pub trait Client {
         self.perform(evidence::Request::new(e)).await
     }
 
+    /// `/dial_seeds`: dial the given seeds, to crawl their address books
+    /// for peers (UNSAFE).
+    ///
+    /// Fails with an [`UnsafeRpcDisabled`](crate::error::ErrorDetail::UnsafeRpcDisabled)
+    /// error if the node does not enable the unsafe RPC endpoints.
+    #[cfg(feature = "unsafe-rpc")]
+    async fn dial_seeds(
+        &self,
+        seeds: Vec<dial_seeds::PeerAddress>,
+    ) -> Result<dial_seeds::Response, Error> {
+        self.perform(dial_seeds::Request::new(seeds))
+            .await
+            .map_err(|e| unsafe_rpc_error(crate::Method::DialSeeds, e))
+    }
+
+    /// `/dial_peers`: dial the given peers, optionally making them
+    /// persistent, unconditional or private peers of the node (UNSAFE).
+    ///
+    /// Fails with an [`UnsafeRpcDisabled`](crate::error::ErrorDetail::UnsafeRpcDisabled)
+    /// error if the node does not enable the unsafe RPC endpoints.
+    #[cfg(feature = "unsafe-rpc")]
+    async fn dial_peers(
+        &self,
+        peers: Vec<dial_peers::PeerAddress>,
+        persistent: bool,
+        unconditional: bool,
+        private: bool,
+    ) -> Result<dial_peers::Response, Error> {
+        self.perform(dial_peers::Request::new(
+            peers,
+            persistent,
+            unconditional,
+            private,
+        ))
+        .await
+        .map_err(|e| unsafe_rpc_error(crate::Method::DialPeers, e))
+    }
+
+    /// `/unsafe_flush_mempool`: remove all the transactions from the mempool
+    /// of the node (UNSAFE).
+    ///
+    /// Fails with an [`UnsafeRpcDisabled`](crate::error::ErrorDetail::UnsafeRpcDisabled)
+    /// error if the node does not enable the unsafe RPC endpoints.
+    #[cfg(feature = "unsafe-rpc")]
+    async fn unsafe_flush_mempool(&self) -> Result<(), Error> {
+        self.perform(unsafe_flush_mempool::Request)
+            .await
+            .map_err(|e| unsafe_rpc_error(crate::Method::UnsafeFlushMempool, e))?;
+        Ok(())
+    }
+
     /// `/tx`: find transaction by hash.
     async fn tx(&self, hash: Hash, prove: bool) -> Result<tx::Response, Error> {
         self.perform(tx::Request::new(hash, prove)).await
@@ -297,6 +490,56 @@ pub trait Client {
             .await
     }
 
+    /// `/tx_search`: stream all the transactions matching the given query,
+    /// walking through the pages of results.
+    ///
+    /// See [`tx_search_stream_with`](Client::tx_search_stream_with).
+    fn tx_search_stream(
+        &self,
+        query: Query,
+        order: Order,
+    ) -> BoxStream<'_, Result<tx::Response, Error>>
+    where
+        Self: Sync,
+    {
+        self.tx_search_stream_with(query, order, SearchStreamConfig::default())
+    }
+
+    /// `/tx_search`: stream all the transactions matching the given query,
+    /// walking through the pages of results as configured.
+    ///
+    /// The stream ends after the first error.
+    fn tx_search_stream_with(
+        &self,
+        query: Query,
+        order: Order,
+        config: SearchStreamConfig,
+    ) -> BoxStream<'_, Result<tx::Response, Error>>
+    where
+        Self: Sync,
+    {
+        search::paginate(
+            config,
+            move |page| {
+                let response = self.tx_search(
+                    query.clone(),
+                    config.prove,
+                    page,
+                    config.per_page,
+                    order.clone(),
+                );
+                async move {
+                    let response = response.await?;
+                    Ok(search::Page {
+                        items: response.txs,
+                        total_count: response.total_count,
+                    })
+                }
+            },
+            |tx| (tx.height, tx.index),
+        )
+    }
+
     /// Poll the `/health` endpoint until it returns a successful result or
     /// the given `timeout` has elapsed.
     async fn wait_until_healthy<T>(&self, timeout: T) -> Result<(), Error>
@@ -327,3 +570,52 @@ pub trait Client {
     where
         R: SimpleRequest;
 }
+
+/// Nodes which do not enable the unsafe RPC endpoints do not route them, and
+/// respond to them as to unknown methods.
+#[cfg(feature = "unsafe-rpc")]
+fn unsafe_rpc_error(method: crate::Method, e: Error) -> Error {
+    use crate::{error::ErrorDetail, response_error::Code};
+
+    match e.detail() {
+        ErrorDetail::Response(r) if r.source.code() == Code::MethodNotFound => {
+            Error::unsafe_rpc_disabled(method.to_string())
+        },
+        ErrorDetail::MethodNotFound(_) => Error::unsafe_rpc_disabled(method.to_string()),
+        _ => e,
+    }
+}
+
+#[cfg(all(test, feature = "unsafe-rpc"))]
+mod tests {
+    use super::*;
+    use crate::{
+        error::ErrorDetail, response_error::ResponseError, Method, MockClient,
+        MockRequestMethodMatcher,
+    };
+
+    #[tokio::test]
+    async fn unsafe_rpc_disabled() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(
+                Method::UnsafeFlushMempool,
+                Err(Error::response(ResponseError::method_not_found(""))),
+            )
+            .map(
+                Method::DialSeeds,
+                Ok(r#"{"jsonrpc":"2.0","id":-1,"result":{"log":"Dialing seeds in progress. See /net_info for details"}}"#
+                    .to_string()),
+            );
+        let (client, _driver) = MockClient::new(matcher);
+
+        let err = client.unsafe_flush_mempool().await.unwrap_err();
+        match err.detail() {
+            ErrorDetail::UnsafeRpcDisabled(e) => assert_eq!(e.method, "unsafe_flush_mempool"),
+            e => panic!("unexpected error: {e:?}"),
+        }
+        assert!(err.to_string().contains("rpc.unsafe = true"));
+
+        let response = client.dial_seeds(vec![]).await.unwrap();
+        assert!(response.log.starts_with("Dialing seeds"));
+    }
+}
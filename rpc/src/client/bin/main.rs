@@ -6,7 +6,10 @@ use futures::StreamExt;
 use structopt::StructOpt;
 use tendermint::Hash;
 use tendermint_rpc::{
-    client::CompatMode,
+    client::{
+        loadtest::{BroadcastMode, LoadTest},
+        CompatMode,
+    },
     dialect::{Dialect, LatestDialect},
     event::DialectEvent,
     query::Query,
@@ -60,6 +63,43 @@ enum Request {
         #[structopt(long)]
         max_time: Option<u32>,
     },
+    /// Broadcast synthetic transactions at a target rate, and report the
+    /// throughput and the latencies of their broadcast and, with WebSocket
+    /// URLs, of their inclusion in a block.
+    LoadTest(LoadTestOpt),
+}
+
+#[derive(Debug, StructOpt)]
+struct LoadTestOpt {
+    /// The number of transactions to broadcast per second.
+    #[structopt(long, default_value = "100")]
+    rate: f64,
+    /// How long to broadcast transactions for, in seconds.
+    #[structopt(long, default_value = "60")]
+    duration: u64,
+    /// The size of the transactions, in bytes.
+    #[structopt(long, default_value = "250")]
+    tx_size: usize,
+    /// How to broadcast the transactions: "async" or "sync".
+    #[structopt(long, default_value = "sync")]
+    mode: BroadcastMode,
+    /// How long to wait for the transactions to be included once the last
+    /// one was broadcast, in seconds.
+    #[structopt(long, default_value = "30")]
+    inclusion_timeout: u64,
+    /// Additional endpoints to broadcast the transactions through, in turn
+    /// with the main one. Must have the same scheme as the main one.
+    #[structopt(long)]
+    endpoint: Vec<Url>,
+}
+
+impl LoadTestOpt {
+    fn load_test(&self) -> LoadTest {
+        LoadTest::new(self.rate, Duration::from_secs(self.duration))
+            .tx_size(self.tx_size)
+            .mode(self.mode)
+            .inclusion_timeout(Duration::from_secs(self.inclusion_timeout))
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -135,6 +175,8 @@ enum ClientRequest {
     },
     /// Get the current consensus state.
     ConsensusState,
+    /// Get the full consensus state, including the round states of the peers.
+    DumpConsensusState,
     /// Get the node's genesis data.
     Genesis,
     /// Get the node's health.
@@ -212,13 +254,16 @@ async fn main() {
         },
     };
     let result = match opt.url.scheme() {
-        Scheme::Http | Scheme::Https => http_request(opt.url, proxy_url, opt.req).await,
+        Scheme::Http | Scheme::Https | Scheme::Unix => {
+            http_request(opt.url, proxy_url, opt.req).await
+        },
         Scheme::WebSocket | Scheme::SecureWebSocket => match opt.proxy_url {
             Some(_) => Err(Error::invalid_params(
                 "proxies are only supported for use with HTTP clients at present".to_string(),
             )),
             None => websocket_request(opt.url, opt.req).await,
         },
+        Scheme::Socks5 => Err(Error::unsupported_scheme(opt.url.scheme().to_string())),
     };
     if let Err(e) = result {
         error!("Failed: {}", e);
@@ -274,6 +319,7 @@ async fn http_request(url: Url, proxy_url: Option<Url>, req: Request) -> Result<
 
     match req {
         Request::ClientRequest(r) => client_request(&client, r).await,
+        Request::LoadTest(opt) => http_load_test(client, opt).await,
         _ => Err(Error::invalid_params("HTTP/S clients do not support subscription capabilities (please use the WebSocket client instead)".to_owned()))
     }
 }
@@ -289,12 +335,56 @@ async fn websocket_request(url: Url, req: Request) -> Result<(), Error> {
             max_events,
             max_time,
         } => subscription_client_request(&client, query, max_events, max_time).await,
+        Request::LoadTest(opt) => websocket_load_test(&client, opt).await,
     };
 
     stop_websocket_client(client, driver_hdl).await?;
     result
 }
 
+async fn http_load_test(client: HttpClient, opt: LoadTestOpt) -> Result<(), Error> {
+    let mut clients = vec![client];
+    for url in &opt.endpoint {
+        clients.push(HttpClient::new(url.clone())?);
+    }
+    warn!("HTTP/S clients cannot subscribe to events: the inclusion of the transactions is not tracked (please use a WebSocket URL instead)");
+
+    info!(
+        "Broadcasting {} tx/s for {}s through {} endpoint(s)",
+        opt.rate,
+        opt.duration,
+        clients.len()
+    );
+    let report = opt.load_test().run(&clients, None).await;
+    print!("{report}");
+    Ok(())
+}
+
+async fn websocket_load_test(client: &WebSocketClient, opt: LoadTestOpt) -> Result<(), Error> {
+    let mut clients = vec![client.clone()];
+    let mut drivers = Vec::new();
+    for url in &opt.endpoint {
+        let (client, driver_hdl) = start_websocket_client(url.clone()).await?;
+        clients.push(client.clone());
+        drivers.push((client, driver_hdl));
+    }
+    let inclusions = client.subscribe(LoadTest::query()).await?;
+
+    info!(
+        "Broadcasting {} tx/s for {}s through {} endpoint(s)",
+        opt.rate,
+        opt.duration,
+        clients.len()
+    );
+    let report = opt.load_test().run(&clients, Some(inclusions)).await;
+    print!("{report}");
+
+    for (client, driver_hdl) in drivers {
+        stop_websocket_client(client, driver_hdl).await?;
+    }
+    Ok(())
+}
+
 async fn start_websocket_client(
     url: Url,
 ) -> Result<(WebSocketClient, JoinHandle<Result<(), Error>>), Error> {
@@ -408,6 +498,10 @@ where
         ClientRequest::ConsensusState => {
             serde_json::to_string_pretty(&client.consensus_state().await?).map_err(Error::serde)?
         },
+        ClientRequest::DumpConsensusState => {
+            serde_json::to_string_pretty(&client.dump_consensus_state().await?)
+                .map_err(Error::serde)?
+        },
         ClientRequest::Genesis => {
             serde_json::to_string_pretty(&client.genesis::<serde_json::Value>().await?)
                 .map_err(Error::serde)?
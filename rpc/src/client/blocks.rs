@@ -0,0 +1,305 @@
+//! A stream of the complete data of the new blocks of a chain.
+//!
+//! The `NewBlock` events carry the block along with the results of
+//! `BeginBlock` and `EndBlock`, but never the results of the transactions,
+//! and some nodes omit the block itself. [`subscribe_full_blocks`] subscribes
+//! to these events and fetches what they lack with a [`Client`], typically
+//! an `HttpClient`: the block when it is missing, and the block results of
+//! every block.
+//!
+//! The blocks are yielded in order of height, without gaps: the blocks of
+//! the heights whose event was missed (e.g. while the subscription was
+//! reconnecting) are fetched too, and the events of the heights which were
+//! already yielded are skipped.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{client::blocks::subscribe_full_blocks, HttpClient, WebSocketClient};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (subscriber, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+//!
+//!     let mut blocks = subscribe_full_blocks(&subscriber, client).await.unwrap();
+//!     while let Some(full_block) = blocks.next().await {
+//!         let full_block = full_block.unwrap();
+//!         println!(
+//!             "block {} with {} transaction results",
+//!             full_block.block.header.height,
+//!             full_block.results.txs_results.map_or(0, |r| r.len()),
+//!         );
+//!     }
+//! }
+//! ```
+
+use alloc::collections::VecDeque;
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use tendermint::{block::Height, Block};
+
+use crate::{
+    alloc_prelude::*,
+    endpoint::block_results,
+    error::ErrorDetail,
+    event::{Event, EventData},
+    query::EventType,
+    Client, Error, SubscriptionClient,
+};
+
+/// A block, along with the results of its execution.
+#[derive(Clone, Debug)]
+pub struct FullBlock {
+    /// The block.
+    pub block: Block,
+    /// The results of the execution of the block.
+    pub results: block_results::Response,
+}
+
+/// Subscribe to the `NewBlock` events with `subscriber`, and stream the
+/// complete data of the new blocks, fetching what the events lack with
+/// `client`.
+///
+/// The subscription errors are yielded by the stream, except for the gaps
+/// in the subscription, whose blocks are fetched once the subscription
+/// resumes. Failing to fetch the data of a block yields an error, and the
+/// stream moves on to the next block.
+pub async fn subscribe_full_blocks<S, C>(
+    subscriber: &S,
+    client: C,
+) -> Result<BoxStream<'static, Result<FullBlock, Error>>, Error>
+where
+    S: SubscriptionClient + Sync,
+    C: Client + Send + Sync + 'static,
+{
+    let subscription = subscriber.subscribe(EventType::NewBlock.into()).await?;
+    Ok(full_blocks(subscription, client).boxed())
+}
+
+struct State<E> {
+    events: E,
+    // Unknown until the first event is received.
+    next_height: Option<Height>,
+    // The heights to yield before waiting for the next event, along with
+    // their block if the event carried it.
+    pending: VecDeque<(Height, Option<Block>)>,
+}
+
+/// The heights of the given `NewBlock` events, in order and without gaps,
+/// along with their block if the event carried it.
+///
+/// The subscription gaps are skipped, and their heights yielded once the
+/// subscription resumes.
+pub(super) fn new_blocks<E>(events: E) -> impl Stream<Item = Result<(Height, Option<Block>), Error>>
+where
+    E: Stream<Item = Result<Event, Error>> + Unpin,
+{
+    let state = State {
+        events,
+        next_height: None,
+        pending: VecDeque::new(),
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(new_block) = state.pending.pop_front() {
+                return Some((Ok(new_block), state));
+            }
+
+            let event = match state.events.next().await? {
+                Ok(event) => event,
+                // The missed heights are yielded with the next event.
+                Err(e) if matches!(e.detail(), ErrorDetail::SubscriptionGap(_)) => continue,
+                Err(e) => return Some((Err(e), state)),
+            };
+            let (height, block) = match new_block(event, state.next_height) {
+                Ok(Some(new_block)) => new_block,
+                Ok(None) => continue,
+                Err(e) => return Some((Err(e), state)),
+            };
+
+            let mut next_height = state.next_height.unwrap_or(height);
+            if height < next_height {
+                continue;
+            }
+            while next_height < height {
+                state.pending.push_back((next_height, None));
+                next_height = next_height.increment();
+            }
+            state.pending.push_back((height, block));
+            state.next_height = Some(height.increment());
+        }
+    })
+}
+
+fn full_blocks<E, C>(events: E, client: C) -> impl Stream<Item = Result<FullBlock, Error>>
+where
+    E: Stream<Item = Result<Event, Error>> + Unpin,
+    C: Client + Sync,
+{
+    let state = (Box::pin(new_blocks(events)), client);
+
+    stream::unfold(state, |(mut new_blocks, client)| async move {
+        let full_block = match new_blocks.next().await? {
+            Ok((height, block)) => fetch(&client, height, block).await,
+            Err(e) => Err(e),
+        };
+        Some((full_block, (new_blocks, client)))
+    })
+}
+
+/// The height and the block (if any) of the given `NewBlock` event, or
+/// `None` for the other events.
+///
+/// The height is taken from the block, or from the `block.height` attribute
+/// of the event if it has no block. Without either, the event is taken to
+/// be the one of `next_height`, if known.
+fn new_block(
+    event: Event,
+    next_height: Option<Height>,
+) -> Result<Option<(Height, Option<Block>)>, Error> {
+    let block = match event.data {
+        EventData::NewBlock { block, .. } => block,
+        _ => return Ok(None),
+    };
+    if let Some(block) = block {
+        return Ok(Some((block.header.height, Some(block))));
+    }
+
+    let attribute = event
+        .events
+        .as_ref()
+        .and_then(|events| events.get("block.height"))
+        .and_then(|values| values.first());
+    let height = match (attribute, next_height) {
+        (Some(height), _) => height.parse().map_err(Error::tendermint)?,
+        (None, Some(height)) => height,
+        (None, None) => {
+            return Err(Error::parse(
+                "NewBlock event without a block nor a height".to_string(),
+            ))
+        },
+    };
+    Ok(Some((height, None)))
+}
+
+async fn fetch<C>(client: &C, height: Height, block: Option<Block>) -> Result<FullBlock, Error>
+where
+    C: Client + Sync,
+{
+    let block = match block {
+        Some(block) => block,
+        None => client.block(height).await?.block,
+    };
+    let results = client.block_results(height).await?;
+    Ok(FullBlock { block, results })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMethodMatcher},
+        endpoint, Method, Response,
+    };
+
+    const BLOCK: &str =
+        include_str!("../../tests/kvstore_fixtures/v0_37/incoming/block_at_height_10.json");
+    const BLOCK_RESULTS: &str =
+        include_str!("../../tests/kvstore_fixtures/v0_37/incoming/block_results_at_height_10.json");
+
+    fn block() -> Block {
+        endpoint::block::Response::from_string(BLOCK).unwrap().block
+    }
+
+    fn event(block: Option<Block>, height: Option<u64>) -> Result<Event, Error> {
+        let events = height
+            .map(|height| BTreeMap::from([("block.height".to_string(), vec![height.to_string()])]));
+        Ok(Event {
+            query: "tm.event = 'NewBlock'".to_string(),
+            data: EventData::NewBlock {
+                block,
+                result_begin_block: None,
+                result_end_block: None,
+            },
+            events,
+        })
+    }
+
+    fn client() -> MockClient<MockRequestMethodMatcher> {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::Block, Ok(BLOCK.to_string()))
+            .map(Method::BlockResults, Ok(BLOCK_RESULTS.to_string()));
+        MockClient::new(matcher).0
+    }
+
+    async fn collect(events: Vec<Result<Event, Error>>) -> Vec<Result<FullBlock, Error>> {
+        full_blocks(stream::iter(events), client()).collect().await
+    }
+
+    #[tokio::test]
+    async fn fetches_missing_data_in_order() {
+        let gap = Err(Error::subscription_gap("reconnected".to_string()));
+        let blocks = collect(vec![
+            event(Some(block()), None),
+            gap,
+            event(None, Some(13)),
+            // Already yielded.
+            event(None, Some(12)),
+            // Without a height: the next one.
+            event(None, None),
+        ])
+        .await;
+
+        let blocks: Vec<_> = blocks.into_iter().map(Result::unwrap).collect();
+        assert_eq!(blocks.len(), 5);
+        for full_block in &blocks {
+            assert_eq!(full_block.results.height.value(), 10);
+        }
+        assert_eq!(blocks[0].block, block());
+    }
+
+    #[tokio::test]
+    async fn yields_errors() {
+        let blocks = collect(vec![
+            event(None, None),
+            Err(Error::client_internal("failure".to_string())),
+            event(None, Some(10)),
+        ])
+        .await;
+
+        assert_eq!(blocks.len(), 3);
+        assert!(matches!(
+            blocks[0].as_ref().unwrap_err().detail(),
+            ErrorDetail::Parse(_)
+        ));
+        assert!(matches!(
+            blocks[1].as_ref().unwrap_err().detail(),
+            ErrorDetail::ClientInternal(_)
+        ));
+        assert!(blocks[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn subscribes_to_new_blocks() {
+        let (subscriber, driver) = MockClient::new(MockRequestMethodMatcher::default());
+        let driver = tokio::spawn(async move { driver.run().await });
+
+        let mut blocks = subscribe_full_blocks(&subscriber, client()).await.unwrap();
+        subscriber.publish(&event(Some(block()), None).unwrap());
+
+        let full_block = blocks.next().await.unwrap().unwrap();
+        assert_eq!(full_block.block, block());
+        assert_eq!(full_block.results.height.value(), 10);
+
+        subscriber.close();
+        driver.await.unwrap().unwrap();
+    }
+}
@@ -4,7 +4,7 @@ use core::fmt;
 
 use tendermint::Version;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::Error;
 
 /// Protocol compatibility mode for a Tendermint RPC client.
@@ -66,7 +66,7 @@ impl fmt::Display for CompatMode {
 #[cfg(test)]
 mod tests {
     use super::CompatMode;
-    use crate::prelude::*;
+    use crate::alloc_prelude::*;
     use tendermint::Version;
 
     fn parse_version(s: &str) -> Version {
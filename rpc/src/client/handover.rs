@@ -0,0 +1,817 @@
+//! Live handover of a client from one RPC endpoint to another.
+//!
+//! A [`HandoverClient`] wraps a client whose endpoint can be swapped at any
+//! time, e.g. to move away from a node about to be taken down for
+//! maintenance, without the application observing errors:
+//!
+//! - The requests in flight are drained: the swap waits for them to complete
+//!   on the previous endpoint, while the requests issued in the meantime wait
+//!   for the swap to complete and are then sent to the new endpoint.
+//! - The subscriptions are re-established on the new endpoint, their events
+//!   keeping on being delivered to the same [`Subscription`]s.
+//! - The `NewBlock` and `Tx` events which were missed while switching
+//!   endpoints are replayed from the new endpoint, up to a window of recent
+//!   heights, and the events which were already delivered are not delivered
+//!   again.
+//!
+//! The events of a subscription are forwarded by a [`HandoverDriver`], which
+//! must be run for the subscriptions to receive anything.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{
+//!     client::handover::HandoverClient, query::EventType, SubscriptionClient, WebSocketClient,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (client, driver) = WebSocketClient::new("ws://node-a:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let (client, handover_driver) = HandoverClient::new(client);
+//!     tokio::spawn(async move { handover_driver.run().await });
+//!
+//!     let mut blocks = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+//!     println!("{:?}", blocks.next().await);
+//!
+//!     // Node A goes down for maintenance.
+//!     let (replacement, driver) = WebSocketClient::new("ws://node-b:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let previous = client.swap(replacement).await.unwrap();
+//!     previous.close().unwrap();
+//!
+//!     // The blocks keep on coming, now from node B.
+//!     println!("{:?}", blocks.next().await);
+//! }
+//! ```
+
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use futures::stream::{AbortHandle, Abortable, BoxStream, SelectAll, StreamExt};
+use tendermint::{
+    abci::{
+        self,
+        response::{BeginBlock, EndBlock},
+    },
+    block::Height,
+    Hash,
+};
+use tokio::sync::RwLock;
+
+use crate::{
+    alloc_prelude::*,
+    client::{
+        subscription::SubscriptionTx,
+        sync::{unbounded, ChannelRx, ChannelTx},
+        Client, Subscription, SubscriptionClient,
+    },
+    endpoint,
+    event::{Event, EventData, TxInfo},
+    query::{EventType, Query},
+    Error, Order, SimpleRequest,
+};
+
+/// Default number of most recent heights whose events are replayed after a
+/// swap.
+pub const DEFAULT_REPLAY_WINDOW: u64 = 20;
+
+// A subscription as known to the client, to re-establish it on the next
+// endpoint.
+#[derive(Clone, Debug)]
+struct Relayed {
+    query: Query,
+    // The height of the last event delivered, if any.
+    last_height: Option<u64>,
+}
+
+type Relays = Arc<Mutex<BTreeMap<u64, Relayed>>>;
+
+/// A client whose endpoint can be swapped without interrupting the
+/// application.
+///
+/// The clones of a handover client share the same endpoint and
+/// subscriptions.
+pub struct HandoverClient<C> {
+    client: Arc<RwLock<C>>,
+    relays: Relays,
+    next_id: Arc<AtomicU64>,
+    replay_window: u64,
+    driver_tx: ChannelTx<DriverCommand>,
+}
+
+impl<C> HandoverClient<C> {
+    /// Wrap the given client, replaying the events of the last
+    /// [`DEFAULT_REPLAY_WINDOW`] heights at most after a swap.
+    pub fn new(client: C) -> (Self, HandoverDriver) {
+        Self::with_replay_window(client, DEFAULT_REPLAY_WINDOW)
+    }
+
+    /// Wrap the given client, replaying the events of the given number of
+    /// most recent heights at most after a swap, e.g. 0 to never replay
+    /// events.
+    pub fn with_replay_window(client: C, replay_window: u64) -> (Self, HandoverDriver) {
+        let relays = Relays::default();
+        let (driver_tx, driver_rx) = unbounded();
+        let client = Self {
+            client: Arc::new(RwLock::new(client)),
+            relays: relays.clone(),
+            next_id: Arc::new(AtomicU64::new(0)),
+            replay_window,
+            driver_tx,
+        };
+        let driver = HandoverDriver {
+            relays,
+            forwards: BTreeMap::new(),
+            streams: SelectAll::new(),
+            rx: driver_rx,
+        };
+        (client, driver)
+    }
+}
+
+impl<C> HandoverClient<C>
+where
+    C: Client + SubscriptionClient + Send + Sync,
+{
+    /// Swap the endpoint of the client for the one of the given client, and
+    /// return the previous client once nothing uses it anymore (e.g. to
+    /// close it).
+    ///
+    /// The requests in flight are completed first, and the requests issued
+    /// during the swap are held until it completes. The subscriptions are
+    /// then re-established with the new client, which replays the `NewBlock`
+    /// and `Tx` events since the last delivered ones, within the replay
+    /// window. The replayed events are rebuilt from `/block`,
+    /// `/block_results` and `/tx_search`; the `NewBlock` events are replayed
+    /// whatever the other conditions of their query.
+    ///
+    /// Events are deduplicated by height, assuming that the events of a
+    /// height are delivered together: the events of the height of the last
+    /// delivered event are not replayed.
+    ///
+    /// If any subscription cannot be re-established, or any event cannot be
+    /// replayed, the swap is aborted and the client keeps using the previous
+    /// endpoint.
+    pub async fn swap(&self, new: C) -> Result<C, Error> {
+        let mut client = self.client.write().await;
+
+        let relays: Vec<(u64, Relayed)> = self
+            .relays
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, relayed)| (*id, relayed.clone()))
+            .collect();
+        let mut subscriptions = Vec::with_capacity(relays.len());
+        let replays = async {
+            for (_, relayed) in &relays {
+                subscriptions.push(new.subscribe(relayed.query.clone()).await?);
+            }
+
+            let mut replays = Vec::with_capacity(relays.len());
+            if relays
+                .iter()
+                .any(|(_, relayed)| relayed.last_height.is_some())
+            {
+                let latest = new.status().await?.sync_info.latest_block_height.value();
+                let earliest = (latest + 1).saturating_sub(self.replay_window);
+                for (_, relayed) in &relays {
+                    replays.push(match relayed.last_height {
+                        Some(last) => {
+                            replay(&new, &relayed.query, earliest.max(last + 1), latest).await?
+                        },
+                        None => Vec::new(),
+                    });
+                }
+            } else {
+                replays.resize_with(relays.len(), Vec::new);
+            }
+            Ok::<_, Error>(replays)
+        }
+        .await;
+        let replays = match replays {
+            Ok(replays) => replays,
+            Err(e) => {
+                // Leave no subscription behind on the new endpoint.
+                let mut queries: Vec<&Query> = Vec::new();
+                for subscription in &subscriptions {
+                    if !queries.contains(&subscription.query()) {
+                        queries.push(subscription.query());
+                    }
+                }
+                for query in queries {
+                    if let Err(e) = new.unsubscribe(query.clone()).await {
+                        tracing::warn!(
+                            "failed to unsubscribe from {query} after an aborted swap: {e}"
+                        );
+                    }
+                }
+                return Err(e);
+            },
+        };
+
+        for (((id, _), subscription), replay) in relays.into_iter().zip(subscriptions).zip(replays)
+        {
+            self.driver_tx.send(DriverCommand::Replace {
+                id,
+                subscription,
+                replay,
+            })?;
+        }
+        Ok(core::mem::replace(&mut *client, new))
+    }
+}
+
+impl<C> Clone for HandoverClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            relays: self.relays.clone(),
+            next_id: self.next_id.clone(),
+            replay_window: self.replay_window,
+            driver_tx: self.driver_tx.clone(),
+        }
+    }
+}
+
+impl<C> fmt::Debug for HandoverClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandoverClient")
+            .field("relays", &*self.relays.lock().unwrap())
+            .field("replay_window", &self.replay_window)
+            .finish_non_exhaustive()
+    }
+}
+
+// The methods which the clients of this crate override to support older
+// protocol versions are forwarded to the wrapped client, rather than
+// implemented in terms of `perform`.
+#[async_trait]
+impl<C> Client for HandoverClient<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        self.client.read().await.perform(request).await
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        self.client.read().await.block_results(height).await
+    }
+
+    async fn header<H>(&self, height: H) -> Result<endpoint::header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        self.client.read().await.header(height).await
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<endpoint::header_by_hash::Response, Error> {
+        self.client.read().await.header_by_hash(hash).await
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
+        self.client.read().await.tx(hash, prove).await
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::tx_search::Response, Error> {
+        self.client
+            .read()
+            .await
+            .tx_search(query, prove, page, per_page, order)
+            .await
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<endpoint::broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        self.client.read().await.broadcast_tx_commit(tx).await
+    }
+}
+
+#[async_trait]
+impl<C> SubscriptionClient for HandoverClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
+        // Holding the client until the subscription is registered, so that
+        // a concurrent swap re-establishes it.
+        let client = self.client.read().await;
+        let subscription = client.subscribe(query.clone()).await?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let subscription_id = subscription.id().to_string();
+        let (tx, rx) = unbounded();
+        self.relays.lock().unwrap().insert(
+            id,
+            Relayed {
+                query: query.clone(),
+                last_height: None,
+            },
+        );
+        self.driver_tx.send(DriverCommand::Relay {
+            id,
+            subscription,
+            tx,
+        })?;
+        Ok(Subscription::new(subscription_id, query, rx))
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<(), Error> {
+        let client = self.client.read().await;
+        client.unsubscribe(query.clone()).await?;
+        let mut relays = self.relays.lock().unwrap();
+        let ids: Vec<u64> = relays
+            .iter()
+            .filter(|(_, relayed)| relayed.query == query)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &ids {
+            relays.remove(id);
+        }
+        self.driver_tx.send(DriverCommand::Stop { ids })
+    }
+
+    /// Terminate the driver, and close the wrapped client if no clone of
+    /// this client is left.
+    fn close(self) -> Result<(), Error> {
+        self.driver_tx.send(DriverCommand::Terminate)?;
+        match Arc::try_unwrap(self.client) {
+            Ok(client) => client.into_inner().close(),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+// Fetch the events matching the given query of the heights in the given
+// range.
+async fn replay<C>(client: &C, query: &Query, from: u64, to: u64) -> Result<Vec<Event>, Error>
+where
+    C: Client + Sync,
+{
+    let mut events = Vec::new();
+    match query.event_type {
+        Some(EventType::NewBlock) => {
+            for height in from..=to {
+                let height = Height::try_from(height).map_err(Error::tendermint)?;
+                let block = client.block(height).await?.block;
+                let results = client.block_results(height).await?;
+                let begin_block = BeginBlock {
+                    events: results.begin_block_events.unwrap_or_default(),
+                };
+                let end_block = EndBlock {
+                    validator_updates: results.validator_updates,
+                    consensus_param_updates: results.consensus_param_updates,
+                    events: results
+                        .end_block_events
+                        .into_iter()
+                        .chain(results.finalize_block_events)
+                        .flatten()
+                        .collect(),
+                };
+                let mut attributes = attributes(
+                    EventType::NewBlock,
+                    begin_block.events.iter().chain(&end_block.events),
+                );
+                attributes.insert("block.height".into(), vec![height.to_string()]);
+                events.push(Event {
+                    query: query.to_string(),
+                    data: EventData::NewBlock {
+                        block: Some(block),
+                        result_begin_block: Some(begin_block),
+                        result_end_block: Some(end_block),
+                    },
+                    events: Some(attributes),
+                });
+            }
+        },
+        Some(EventType::Tx) if from <= to => {
+            let search = Query {
+                event_type: None,
+                conditions: query.conditions.clone(),
+            }
+            .and_gte("tx.height", from)
+            .and_lte("tx.height", to);
+            let mut txs = client.tx_search_stream(search, Order::Ascending);
+            while let Some(tx) = txs.next().await {
+                let tx = tx?;
+                if !(from..=to).contains(&tx.height.value()) {
+                    continue;
+                }
+                let mut attributes = attributes(EventType::Tx, &tx.tx_result.events);
+                attributes.insert("tx.hash".into(), vec![tx.hash.to_string()]);
+                attributes.insert("tx.height".into(), vec![tx.height.to_string()]);
+                events.push(Event {
+                    query: query.to_string(),
+                    data: EventData::Tx {
+                        tx_result: TxInfo {
+                            height: tx.height,
+                            index: tx.index,
+                            tx: tx.tx,
+                            result: tx.tx_result,
+                        },
+                    },
+                    events: Some(attributes),
+                });
+            }
+        },
+        _ => {},
+    }
+    Ok(events)
+}
+
+// The attributes of the given ABCI events, by composite key, as they are
+// attached to the events of subscriptions.
+fn attributes<'a>(
+    event_type: EventType,
+    events: impl IntoIterator<Item = &'a abci::Event>,
+) -> BTreeMap<String, Vec<String>> {
+    let mut attributes = BTreeMap::new();
+    attributes.insert("tm.event".to_string(), vec![event_type.to_string()]);
+    for event in events {
+        for attribute in &event.attributes {
+            attributes
+                .entry(format!("{}.{}", event.kind, attribute.key))
+                .or_insert_with(Vec::new)
+                .push(attribute.value.clone());
+        }
+    }
+    attributes
+}
+
+// The height of the block an event is about, if any.
+fn event_height(event: &Event) -> Option<u64> {
+    match &event.data {
+        EventData::NewBlock {
+            block: Some(block), ..
+        } => Some(block.header.height.value()),
+        EventData::Tx { tx_result } => Some(tx_result.height.value()),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+enum DriverCommand {
+    // Forward the events of a new subscription.
+    Relay {
+        id: u64,
+        subscription: Subscription,
+        tx: SubscriptionTx,
+    },
+    // Deliver the replayed events, and forward the events of the given
+    // subscription instead of the previous one.
+    Replace {
+        id: u64,
+        subscription: Subscription,
+        replay: Vec<Event>,
+    },
+    // Stop forwarding the events of the given subscriptions.
+    Stop {
+        ids: Vec<u64>,
+    },
+    Terminate,
+}
+
+// The forwarding of the events of a subscription.
+struct Forward {
+    tx: SubscriptionTx,
+    abort: AbortHandle,
+    // The events up to this height are skipped, having been replayed.
+    resume_after: Option<u64>,
+}
+
+/// Forwards the events of the subscriptions of a [`HandoverClient`] from
+/// its current endpoint.
+pub struct HandoverDriver {
+    relays: Relays,
+    forwards: BTreeMap<u64, Forward>,
+    streams: SelectAll<BoxStream<'static, (u64, Result<Event, Error>)>>,
+    rx: ChannelRx<DriverCommand>,
+}
+
+impl HandoverDriver {
+    /// Forward events until the client is closed, or all of its clones are
+    /// dropped.
+    pub async fn run(mut self) -> Result<(), Error> {
+        loop {
+            tokio::select! {
+                Some((id, result)) = self.streams.next(), if !self.streams.is_empty() => {
+                    self.forward(id, result);
+                },
+                cmd = self.rx.recv() => match cmd {
+                    Some(DriverCommand::Relay { id, subscription, tx }) => {
+                        let abort = self.relay(id, subscription);
+                        self.forwards.insert(id, Forward { tx, abort, resume_after: None });
+                    },
+                    Some(DriverCommand::Replace { id, subscription, replay }) => {
+                        self.replace(id, subscription, replay);
+                    },
+                    Some(DriverCommand::Stop { ids }) => {
+                        for id in ids {
+                            self.stop(id);
+                        }
+                    },
+                    Some(DriverCommand::Terminate) | None => return Ok(()),
+                },
+            }
+        }
+    }
+
+    fn relay(&mut self, id: u64, subscription: Subscription) -> AbortHandle {
+        let (abort, registration) = AbortHandle::new_pair();
+        self.streams.push(
+            Abortable::new(subscription, registration)
+                .map(move |result| (id, result))
+                .boxed(),
+        );
+        abort
+    }
+
+    fn replace(&mut self, id: u64, subscription: Subscription, replay: Vec<Event>) {
+        let last_height = self.last_height(id);
+        let Some(forward) = self.forwards.get_mut(&id) else {
+            return;
+        };
+        forward.abort.abort();
+        // The replayed events of the heights already delivered are skipped,
+        // and so are the events of the new subscription which were replayed.
+        forward.resume_after = last_height;
+        for event in replay {
+            self.forward(id, Ok(event));
+        }
+        let resume_after = self.last_height(id);
+        let abort = self.relay(id, subscription);
+        if let Some(forward) = self.forwards.get_mut(&id) {
+            forward.abort = abort;
+            forward.resume_after = resume_after;
+        }
+    }
+
+    fn forward(&mut self, id: u64, result: Result<Event, Error>) {
+        let Some(forward) = self.forwards.get_mut(&id) else {
+            return;
+        };
+        if let Some(height) = result.as_ref().ok().and_then(event_height) {
+            if matches!(forward.resume_after, Some(after) if height <= after) {
+                return;
+            }
+            forward.resume_after = None;
+            if let Some(relayed) = self.relays.lock().unwrap().get_mut(&id) {
+                relayed.last_height = Some(height);
+            }
+        }
+        if forward.tx.send(result).is_err() {
+            // The subscription was dropped.
+            self.relays.lock().unwrap().remove(&id);
+            self.stop(id);
+        }
+    }
+
+    fn last_height(&self, id: u64) -> Option<u64> {
+        self.relays
+            .lock()
+            .unwrap()
+            .get(&id)
+            .and_then(|relayed| relayed.last_height)
+    }
+
+    fn stop(&mut self, id: u64) {
+        if let Some(forward) = self.forwards.remove(&id) {
+            forward.abort.abort();
+        }
+    }
+}
+
+impl fmt::Debug for HandoverDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandoverDriver")
+            .field("relays", &*self.relays.lock().unwrap())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMethodMatcher},
+        Method,
+    };
+
+    fn fixture(name: &str) -> String {
+        fs::read_to_string(
+            PathBuf::from("./tests/kvstore_fixtures/v0_37/incoming")
+                .join(name.to_owned() + ".json"),
+        )
+        .unwrap()
+    }
+
+    fn tx_event(query: &Query, height: i64) -> Event {
+        Event {
+            query: query.to_string(),
+            data: EventData::Tx {
+                tx_result: TxInfo {
+                    height: height.try_into().unwrap(),
+                    index: 0,
+                    tx: Vec::new(),
+                    result: Default::default(),
+                },
+            },
+            events: None,
+        }
+    }
+
+    fn heights(events: &[Event]) -> Vec<u64> {
+        events.iter().filter_map(event_height).collect()
+    }
+
+    /// Records the queries unsubscribed from with the wrapped client.
+    struct Unsubscribes<C> {
+        client: C,
+        queries: Arc<Mutex<Vec<Query>>>,
+    }
+
+    #[async_trait]
+    impl<C: Client + Send + Sync> Client for Unsubscribes<C> {
+        async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+        where
+            R: SimpleRequest,
+        {
+            self.client.perform(request).await
+        }
+    }
+
+    #[async_trait]
+    impl<C: SubscriptionClient + Send + Sync> SubscriptionClient for Unsubscribes<C> {
+        async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
+            self.client.subscribe(query).await
+        }
+
+        async fn unsubscribe(&self, query: Query) -> Result<(), Error> {
+            self.queries.lock().unwrap().push(query.clone());
+            self.client.unsubscribe(query).await
+        }
+
+        fn close(self) -> Result<(), Error> {
+            self.client.close()
+        }
+    }
+
+    #[tokio::test]
+    async fn swaps_endpoints_without_losing_events() {
+        // The previous node serves no requests, the new one is at height 53
+        // and has transactions at heights 44 to 74 (the mock ignores the
+        // height range of the search).
+        let (previous, previous_driver) = MockClient::new(MockRequestMethodMatcher::default());
+        tokio::spawn(previous_driver.run());
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::Status, Ok(fixture("status")))
+            .map(Method::TxSearch, Ok(fixture("tx_search_no_prove")));
+        let (new, new_driver) = MockClient::new(matcher);
+        tokio::spawn(new_driver.run());
+
+        let (client, driver) = HandoverClient::new(previous);
+        let driver = tokio::spawn(driver.run());
+        let query = Query::from(EventType::Tx).and_eq("app.key", "async-key");
+        let mut subscription = client.subscribe(query.clone()).await.unwrap();
+        assert!(client.status().await.is_err());
+
+        {
+            let previous = client.client.read().await;
+            previous.publish(&tx_event(&query, 44));
+            previous.publish(&tx_event(&query, 46));
+        }
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            events.push(subscription.next().await.unwrap().unwrap());
+        }
+
+        let previous = client.swap(new).await.unwrap();
+        previous.close();
+        assert_eq!(
+            client.status().await.unwrap().sync_info.latest_block_height,
+            53_u32.into()
+        );
+
+        // The new node delivers the transaction at height 48 too, which was
+        // replayed already.
+        {
+            let new = client.client.read().await;
+            new.publish(&tx_event(&query, 48));
+            new.publish(&tx_event(&query, 54));
+        }
+        for _ in 0..2 {
+            events.push(subscription.next().await.unwrap().unwrap());
+        }
+        assert_eq!(heights(&events), vec![44, 46, 48, 54]);
+        let EventData::Tx { tx_result } = &events[2].data else {
+            panic!("not a transaction event");
+        };
+        assert_eq!(tx_result.tx, b"commit-key=value");
+        assert_eq!(
+            events[2].events.as_ref().unwrap()["tx.height"],
+            vec!["48".to_string()]
+        );
+
+        client.close().unwrap();
+        driver.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn aborted_swaps_leave_no_subscription_behind() {
+        // The new node serves no requests, so that the events since the last
+        // delivered one cannot be replayed.
+        let (previous, previous_driver) = MockClient::new(MockRequestMethodMatcher::default());
+        tokio::spawn(previous_driver.run());
+        let (new, new_driver) = MockClient::new(MockRequestMethodMatcher::default());
+        tokio::spawn(new_driver.run());
+        let unsubscribed = Arc::new(Mutex::new(Vec::new()));
+
+        let (client, driver) = HandoverClient::new(Unsubscribes {
+            client: previous,
+            queries: Default::default(),
+        });
+        let driver = tokio::spawn(driver.run());
+        let query = Query::from(EventType::Tx).and_eq("app.key", "async-key");
+        let mut subscription = client.subscribe(query.clone()).await.unwrap();
+        let mut second = client.subscribe(query.clone()).await.unwrap();
+        client
+            .client
+            .read()
+            .await
+            .client
+            .publish(&tx_event(&query, 44));
+        subscription.next().await.unwrap().unwrap();
+        second.next().await.unwrap().unwrap();
+
+        let new = Unsubscribes {
+            client: new,
+            queries: unsubscribed.clone(),
+        };
+        assert!(client.swap(new).await.is_err());
+        assert_eq!(*unsubscribed.lock().unwrap(), vec![query.clone()]);
+
+        // The previous endpoint is still used.
+        client
+            .client
+            .read()
+            .await
+            .client
+            .publish(&tx_event(&query, 46));
+        let event = subscription.next().await.unwrap().unwrap();
+        assert_eq!(heights(&[event]), vec![46]);
+
+        client.close().unwrap();
+        driver.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn unsubscribing_ends_the_subscription() {
+        let (mock, mock_driver) = MockClient::new(MockRequestMethodMatcher::default());
+        tokio::spawn(mock_driver.run());
+        let (client, driver) = HandoverClient::new(mock);
+        let driver = tokio::spawn(driver.run());
+
+        let query = Query::from(EventType::NewBlock);
+        let mut subscription = client.subscribe(query.clone()).await.unwrap();
+        client.unsubscribe(query).await.unwrap();
+        assert!(subscription.next().await.is_none());
+        assert!(client.relays.lock().unwrap().is_empty());
+
+        client.close().unwrap();
+        driver.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,859 @@
+//! Middleware wrapping any [`Client`] with timeouts, retries, rate limiting
+//! and circuit breaking.
+//!
+//! A [`Middleware`] decides how each call of the wrapped client is carried
+//! out. Wrapping a client with [`ClientExt::layer`] gives a [`Layered`]
+//! client, which can itself be wrapped again, the last layer being the
+//! outermost one:
+//!
+//! ```rust,ignore
+//! use core::time::Duration;
+//! use tendermint_rpc::{
+//!     client::layer::{CircuitBreaker, ClientExt, RateLimit, Retry, Timeout},
+//!     Client, HttpClient,
+//! };
+//!
+//! let client = HttpClient::new("http://127.0.0.1:26657")?
+//!     // Each attempt times out after 5 seconds, or 30 seconds for
+//!     // transactions waiting to be committed.
+//!     .layer(Timeout::new(Duration::from_secs(5)).method("broadcast_tx_commit", Duration::from_secs(30)))
+//!     // At most 10 attempts per second, with bursts of up to 20 attempts.
+//!     .layer(RateLimit::new(10.0, 20))
+//!     // Failed attempts are retried up to 3 times.
+//!     .layer(Retry::new(3))
+//!     // After 5 consecutive failed calls, calls fail immediately for 10 seconds.
+//!     .layer(CircuitBreaker::new(5, Duration::from_secs(10)));
+//!
+//! let status = client.status().await?;
+//! ```
+//!
+//! Only the errors deemed transient by [`is_transient`] are retried and
+//! counted as failures by the circuit breaker, by default. Calls rate limited
+//! by the server, i.e. failing with [`ErrorDetail::RateLimited`], are
+//! transient, and are retried no sooner than the server asked for.
+
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::{fmt, future::Future, time::Duration};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use tendermint::{block::Height, Hash};
+use tokio::time::{self, Instant};
+
+use crate::{
+    alloc_prelude::*,
+    client::{Client, Subscription, SubscriptionClient},
+    endpoint,
+    error::{Error, ErrorDetail},
+    query::Query,
+    Order, SimpleRequest,
+};
+
+/// Decides how the calls of a [`Layered`] client are carried out.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Carry out the call of the given RPC method, where `call` performs a
+    /// single attempt of the call with the wrapped client.
+    async fn call<T, F, Fut>(&self, method: &str, call: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, Error>> + Send;
+}
+
+/// Wraps clients with middleware.
+pub trait ClientExt: Client + Sized {
+    /// Wrap this client with the given middleware.
+    fn layer<M: Middleware>(self, middleware: M) -> Layered<Self, M> {
+        Layered {
+            inner: self,
+            middleware,
+        }
+    }
+}
+
+impl<C: Client> ClientExt for C {}
+
+/// A client whose calls are carried out by a [`Middleware`].
+///
+/// Subscriptions, if supported by the wrapped client, are not subject to
+/// the middleware.
+#[derive(Clone, Debug)]
+pub struct Layered<C, M> {
+    inner: C,
+    middleware: M,
+}
+
+impl<C, M> Layered<C, M> {
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the wrapped client.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+// The methods which the clients of this crate override to support older
+// protocol versions are forwarded to the wrapped client, rather than
+// implemented in terms of `perform`.
+#[async_trait]
+impl<C, M> Client for Layered<C, M>
+where
+    C: Client + Send + Sync,
+    M: Middleware,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method().to_string();
+        // Requests cannot be cloned, so each attempt deserializes its own
+        // copy of the request.
+        let params = serde_json::to_value(&request).map_err(Error::serde)?;
+        let inner = &self.inner;
+        self.middleware
+            .call(&method, || {
+                let params = params.clone();
+                async move {
+                    let request: R = serde_json::from_value(params).map_err(Error::serde)?;
+                    inner.perform(request).await
+                }
+            })
+            .await
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.middleware
+            .call("block_results", || self.inner.block_results(height))
+            .await
+    }
+
+    async fn header<H>(&self, height: H) -> Result<endpoint::header::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let height = height.into();
+        self.middleware
+            .call("header", || self.inner.header(height))
+            .await
+    }
+
+    async fn header_by_hash(
+        &self,
+        hash: Hash,
+    ) -> Result<endpoint::header_by_hash::Response, Error> {
+        self.middleware
+            .call("header_by_hash", || self.inner.header_by_hash(hash))
+            .await
+    }
+
+    async fn tx(&self, hash: Hash, prove: bool) -> Result<endpoint::tx::Response, Error> {
+        self.middleware
+            .call("tx", || self.inner.tx(hash, prove))
+            .await
+    }
+
+    async fn tx_search(
+        &self,
+        query: Query,
+        prove: bool,
+        page: u32,
+        per_page: u8,
+        order: Order,
+    ) -> Result<endpoint::tx_search::Response, Error> {
+        self.middleware
+            .call("tx_search", || {
+                self.inner
+                    .tx_search(query.clone(), prove, page, per_page, order.clone())
+            })
+            .await
+    }
+
+    async fn broadcast_tx_commit<T>(
+        &self,
+        tx: T,
+    ) -> Result<endpoint::broadcast::tx_commit::Response, Error>
+    where
+        T: Into<Vec<u8>> + Send,
+    {
+        let tx = tx.into();
+        self.middleware
+            .call("broadcast_tx_commit", || {
+                self.inner.broadcast_tx_commit(tx.clone())
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<C, M> SubscriptionClient for Layered<C, M>
+where
+    C: SubscriptionClient + Send + Sync,
+    M: Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
+        self.inner.subscribe(query).await
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<(), Error> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<(), Error> {
+        self.inner.close()
+    }
+}
+
+/// Whether the given error may not occur again when retrying the call, i.e.
+/// it is a transport error rather than an error returned by the node.
+pub fn is_transient(error: &Error) -> bool {
+    matches!(
+        error.detail(),
+        ErrorDetail::Io(_)
+            | ErrorDetail::Http(_)
+            | ErrorDetail::Hyper(_)
+            | ErrorDetail::WebSocket(_)
+            | ErrorDetail::WebSocketTimeout(_)
+            | ErrorDetail::Tungstenite(_)
+            | ErrorDetail::RequestTimeout(_)
+            | ErrorDetail::RateLimited(_)
+    )
+}
+
+/// The delay before which the server asked not to retry the call, if the
+/// given error is a rate limit.
+fn retry_after(error: &Error) -> Option<Duration> {
+    match error.detail() {
+        ErrorDetail::RateLimited(e) => e.retry_after,
+        _ => None,
+    }
+}
+
+type ErrorPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// Fails the calls which take longer than a timeout.
+#[derive(Clone, Debug)]
+pub struct Timeout {
+    timeout: Duration,
+    methods: BTreeMap<String, Duration>,
+}
+
+impl Timeout {
+    /// Time out the calls after the given duration.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            methods: BTreeMap::new(),
+        }
+    }
+
+    /// Time out the calls of the given RPC method, e.g. `broadcast_tx_commit`,
+    /// after the given duration instead.
+    pub fn method(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.methods.insert(method.into(), timeout);
+        self
+    }
+
+    fn timeout(&self, method: &str) -> Duration {
+        self.methods.get(method).copied().unwrap_or(self.timeout)
+    }
+}
+
+#[async_trait]
+impl Middleware for Timeout {
+    async fn call<T, F, Fut>(&self, method: &str, mut call: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, Error>> + Send,
+    {
+        let timeout = self.timeout(method);
+        time::timeout(timeout, call())
+            .await
+            .map_err(|_| Error::request_timeout(method.to_string(), timeout))?
+    }
+}
+
+/// Retries the calls which fail with a transient error, with exponential
+/// backoff.
+///
+/// Calls are retried whether or not the node may have executed them. Retries
+/// of non-idempotent methods, such as `broadcast_tx_commit`, can be disabled
+/// with [`Retry::method`].
+///
+/// Rate limited calls are retried after the delay given by the server, if it
+/// is longer than the backoff.
+#[derive(Clone)]
+pub struct Retry {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    methods: BTreeMap<String, u32>,
+    retry_if: ErrorPredicate,
+}
+
+impl Retry {
+    /// Retry the failed calls up to the given number of times, waiting
+    /// 100ms before the first retry, and doubling the delay after every
+    /// retry up to 10s.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            methods: BTreeMap::new(),
+            retry_if: Arc::new(is_transient),
+        }
+    }
+
+    /// Wait `initial` before the first retry, doubling the delay after every
+    /// retry up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Retry the failed calls of the given RPC method up to the given number
+    /// of times instead, e.g. 0 to never retry them.
+    pub fn method(mut self, method: impl Into<String>, max_retries: u32) -> Self {
+        self.methods.insert(method.into(), max_retries);
+        self
+    }
+
+    /// Retry the calls which fail with the errors matching the given
+    /// predicate, instead of the [transient](is_transient) ones.
+    pub fn retry_if(mut self, predicate: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_if = Arc::new(predicate);
+        self
+    }
+}
+
+impl fmt::Debug for Retry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("methods", &self.methods)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Middleware for Retry {
+    async fn call<T, F, Fut>(&self, method: &str, mut call: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, Error>> + Send,
+    {
+        let max_retries = self
+            .methods
+            .get(method)
+            .copied()
+            .unwrap_or(self.max_retries);
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+        loop {
+            let e = match call().await {
+                Err(e) if retries < max_retries && (self.retry_if)(&e) => e,
+                result => return result,
+            };
+            let delay = retry_after(&e).map_or(backoff, |after| after.max(backoff));
+            tracing::debug!("Retrying {} in {:?} after error: {}", method, delay, e);
+            time::sleep(delay).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+            retries += 1;
+        }
+    }
+}
+
+/// Limits the rate of the calls with a token bucket, and slows down when the
+/// server rate limits them.
+///
+/// Up to `burst` calls are carried out right away, after which the calls wait
+/// for the bucket to refill at `rate` calls per second. When a call is rate
+/// limited by the server, the rate is halved, down to a tenth of the
+/// configured rate, and no call is carried out before the delay given by the
+/// server, if any. Each successful call then increases the rate by a tenth of
+/// the configured rate, up to the configured rate.
+///
+/// The bucket is shared by the clones of the rate limit, and thus by the
+/// clones of the client it wraps; see [`RateLimits`] to share it among the
+/// clients of the same host. To retry the rate limited calls, wrap the rate
+/// limited client with [`Retry`], so that each attempt waits for the bucket.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    max_rate: f64,
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    updated: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.updated = now;
+    }
+
+    // Takes a token, or returns how long to wait for one.
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Some(until - now);
+            }
+            self.paused_until = None;
+            self.updated = now;
+        }
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+impl RateLimit {
+    /// Carry out up to `rate` calls per second, with bursts of up to `burst`
+    /// calls.
+    ///
+    /// # Panics
+    ///
+    /// If the rate is not positive, or the burst is zero.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        assert!(rate > 0.0, "the rate limit must be positive");
+        assert!(burst > 0, "the burst of the rate limit must be positive");
+        Self {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                max_rate: rate,
+                rate,
+                burst: burst.into(),
+                tokens: burst.into(),
+                updated: Instant::now(),
+                paused_until: None,
+            })),
+        }
+    }
+
+    /// The current rate, in calls per second, which is lower than the
+    /// configured one after the server rate limited the calls.
+    pub fn rate(&self) -> f64 {
+        self.bucket.lock().unwrap().rate
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = self.bucket.lock().unwrap().take(Instant::now());
+            match wait {
+                Some(wait) => time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    fn record(&self, error: Option<&Error>) {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        bucket.refill(now);
+        let step = bucket.max_rate / 10.0;
+        match error.map(Error::detail) {
+            Some(ErrorDetail::RateLimited(e)) => {
+                bucket.rate = (bucket.rate / 2.0).max(step);
+                bucket.tokens = bucket.tokens.min(0.0);
+                if let Some(after) = e.retry_after {
+                    bucket.paused_until = Some(now + after);
+                }
+                tracing::warn!(
+                    "Rate limited by the server, slowing down to {:.2} calls per second",
+                    bucket.rate
+                );
+            },
+            None => bucket.rate = (bucket.rate + step).min(bucket.max_rate),
+            Some(_) => {},
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimit {
+    async fn call<T, F, Fut>(&self, _method: &str, mut call: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, Error>> + Send,
+    {
+        self.acquire().await;
+        let result = call().await;
+        self.record(result.as_ref().err());
+        result
+    }
+}
+
+/// Rate limits per host, shared by the clients of the same host.
+///
+/// ```rust,ignore
+/// let limits = RateLimits::new(10.0, 20).host("rpc.example.com", 2.0, 5);
+/// let url: Url = "https://rpc.example.com".parse()?;
+/// let client = HttpClient::new(url.clone())?.layer(limits.for_host(url.host()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimits {
+    rate: f64,
+    burst: u32,
+    overrides: BTreeMap<String, (f64, u32)>,
+    limits: Arc<Mutex<BTreeMap<String, RateLimit>>>,
+}
+
+impl RateLimits {
+    /// Limit the calls to each host to `rate` calls per second, with bursts
+    /// of up to `burst` calls.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate,
+            burst,
+            overrides: BTreeMap::new(),
+            limits: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Limit the calls to the given host to `rate` calls per second, with
+    /// bursts of up to `burst` calls instead.
+    pub fn host(mut self, host: impl Into<String>, rate: f64, burst: u32) -> Self {
+        self.overrides.insert(host.into(), (rate, burst));
+        self
+    }
+
+    /// The rate limit of the given host, shared by all the calls to it.
+    pub fn for_host(&self, host: &str) -> RateLimit {
+        let host = host.to_ascii_lowercase();
+        let (rate, burst) = self
+            .overrides
+            .get(&host)
+            .copied()
+            .unwrap_or((self.rate, self.burst));
+        self.limits
+            .lock()
+            .unwrap()
+            .entry(host)
+            .or_insert_with(|| RateLimit::new(rate, burst))
+            .clone()
+    }
+}
+
+/// Fails the calls immediately for a while after a number of consecutive
+/// calls failed, to spare an unhealthy node.
+///
+/// Once the circuit has been open for the reset timeout, calls are carried
+/// out again: the circuit closes on the first success, and opens again on
+/// the first failure. The state is shared by the clones of the circuit
+/// breaker, and thus by the clones of the client it wraps.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    is_failure: ErrorPredicate,
+    state: Arc<Mutex<CircuitState>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum CircuitState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+impl CircuitBreaker {
+    /// Open the circuit for `reset_timeout` after `failure_threshold`
+    /// consecutive calls failed with a transient error.
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+            is_failure: Arc::new(is_transient),
+            state: Arc::new(Mutex::new(CircuitState::Closed { failures: 0 })),
+        }
+    }
+
+    /// Count the calls which fail with the errors matching the given
+    /// predicate as failures, instead of the [transient](is_transient) ones.
+    pub fn failure_if(
+        mut self,
+        predicate: impl Fn(&Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.is_failure = Arc::new(predicate);
+        self
+    }
+
+    /// Whether the calls currently fail immediately.
+    pub fn is_open(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), CircuitState::Open { until } if Instant::now() < until)
+    }
+
+    // Fails if the circuit is open.
+    fn acquire(&self) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        if let CircuitState::Open { until } = *state {
+            let now = Instant::now();
+            if now < until {
+                return Err(Error::circuit_open(until - now));
+            }
+            *state = CircuitState::HalfOpen;
+        }
+        Ok(())
+    }
+
+    fn record(&self, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+        *state = match (*state, failed) {
+            (_, false) => CircuitState::Closed { failures: 0 },
+            (CircuitState::Closed { failures }, true) if failures + 1 < self.failure_threshold => {
+                CircuitState::Closed {
+                    failures: failures + 1,
+                }
+            },
+            // Calls which started before the circuit opened do not extend
+            // the time it stays open.
+            (CircuitState::Open { until }, true) => CircuitState::Open { until },
+            (_, true) => {
+                tracing::warn!(
+                    "Opening the circuit for {:?} after repeated failures",
+                    self.reset_timeout
+                );
+                CircuitState::Open {
+                    until: Instant::now() + self.reset_timeout,
+                }
+            },
+        };
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("reset_timeout", &self.reset_timeout)
+            .field("state", &*self.state.lock().unwrap())
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Middleware for CircuitBreaker {
+    async fn call<T, F, Fut>(&self, _method: &str, mut call: F) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = Result<T, Error>> + Send,
+    {
+        self.acquire()?;
+        let result = call().await;
+        self.record(matches!(&result, Err(e) if (self.is_failure)(e)));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMethodMatcher},
+        Method,
+    };
+
+    fn io_error() -> Error {
+        Error::io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection reset",
+        ))
+    }
+
+    fn health_client(response: Result<String, Error>) -> MockClient<MockRequestMethodMatcher> {
+        let matcher = MockRequestMethodMatcher::default().map(Method::Health, response);
+        MockClient::new(matcher).0
+    }
+
+    #[tokio::test]
+    async fn retry_until_success() {
+        let attempts = AtomicU32::new(0);
+        let retry = Retry::new(3).backoff(Duration::from_millis(1), Duration::from_millis(1));
+        let result = retry
+            .call("status", || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt < 2 {
+                        Err(io_error())
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn retry_only_transient_errors() {
+        let attempts = AtomicU32::new(0);
+        let retry = Retry::new(3).backoff(Duration::from_millis(1), Duration::from_millis(1));
+        let result: Result<(), _> = retry
+            .call("status", || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(Error::method_not_found("status".to_string())) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let result: Result<(), _> = retry
+            .method("broadcast_tx_commit", 0)
+            .call("broadcast_tx_commit", || {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(io_error()) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn timeout_per_method() {
+        let timeout =
+            Timeout::new(Duration::from_millis(10)).method("slow", Duration::from_secs(10));
+        let sleep = || async {
+            time::sleep(Duration::from_millis(50)).await;
+            Ok(())
+        };
+
+        let err = timeout.call("status", sleep).await.unwrap_err();
+        match err.detail() {
+            ErrorDetail::RequestTimeout(e) => assert_eq!(e.method, "status"),
+            _ => panic!("expected a request timeout, got {err}"),
+        }
+        timeout.call("slow", sleep).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_opens_and_recovers() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(50));
+        for _ in 0..2 {
+            let result: Result<(), _> = breaker.call("status", || async { Err(io_error()) }).await;
+            assert!(result.is_err());
+        }
+        assert!(breaker.is_open());
+
+        let called = AtomicU32::new(0);
+        let err = breaker
+            .call("status", || {
+                called.fetch_add(1, Ordering::SeqCst);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::CircuitOpen(_)));
+        assert_eq!(called.load(Ordering::SeqCst), 0);
+
+        time::sleep(Duration::from_millis(60)).await;
+        breaker.call("status", || async { Ok(()) }).await.unwrap();
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn retry_after_rate_limit() {
+        let attempts = AtomicU32::new(0);
+        let retry = Retry::new(1).backoff(Duration::from_millis(1), Duration::from_millis(1));
+        let start = Instant::now();
+        let result = retry
+            .call("status", || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Err(Error::rate_limited(Some(Duration::from_millis(50))))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 1);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_waits_for_tokens() {
+        let limit = RateLimit::new(50.0, 2);
+        let start = Instant::now();
+        for _ in 0..4 {
+            limit.call("status", || async { Ok(()) }).await.unwrap();
+        }
+        // The burst is served right away, then a call every 20ms.
+        assert!(start.elapsed() >= Duration::from_millis(35));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_slows_down_and_recovers() {
+        let limit = RateLimit::new(100.0, 10);
+        let result: Result<(), _> = limit
+            .call("status", || async {
+                Err(Error::rate_limited(Some(Duration::from_millis(30))))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(limit.rate(), 50.0);
+
+        // No call is carried out before the delay given by the server.
+        let start = Instant::now();
+        limit.call("status", || async { Ok(()) }).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(limit.rate(), 60.0);
+
+        // Other errors do not change the rate.
+        let result: Result<(), _> = limit.call("status", || async { Err(io_error()) }).await;
+        assert!(result.is_err());
+        assert_eq!(limit.rate(), 60.0);
+    }
+
+    #[test]
+    fn rate_limits_per_host() {
+        let limits = RateLimits::new(10.0, 1).host("slow.example.com", 1.0, 1);
+        let limit = limits.for_host("rpc.example.com");
+        limit.record(Some(&Error::rate_limited(None)));
+        assert_eq!(limits.for_host("RPC.example.com").rate(), 5.0);
+        assert_eq!(limits.for_host("other.example.com").rate(), 10.0);
+        assert_eq!(limits.for_host("slow.example.com").rate(), 1.0);
+    }
+
+    #[tokio::test]
+    async fn layered_client() {
+        let client = health_client(Ok(r#"{"jsonrpc":"2.0","id":"","result":{}}"#.to_string()))
+            .layer(Timeout::new(Duration::from_secs(1)))
+            .layer(Retry::new(1));
+        client.health().await.unwrap();
+
+        // Methods without a mocked response fail with a non-transient error.
+        let err = client.abci_info().await.unwrap_err();
+        assert!(!is_transient(&err));
+
+        let client = health_client(Err(io_error()))
+            .layer(Retry::new(1).backoff(Duration::from_millis(1), Duration::from_millis(1)))
+            .layer(CircuitBreaker::new(1, Duration::from_secs(10)));
+        let err = client.health().await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Io(_)));
+        let err = client.health().await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::CircuitOpen(_)));
+    }
+}
@@ -0,0 +1,650 @@
+//! Load testing of a network with synthetic transactions, in the manner of
+//! `tm-load-test`.
+//!
+//! A [`LoadTest`] broadcasts the transactions of a [`TxGenerator`] at a
+//! target rate for a given duration, in turn through each of the clients of
+//! several endpoints, with `/broadcast_tx_async` or `/broadcast_tx_sync` (see
+//! [`BroadcastMode`]). Given a subscription to the [`LoadTest::query`]
+//! events, it also measures how long the transactions took to be included in
+//! a block, waiting for the last ones for up to an inclusion timeout. The
+//! outcome is summarized in a [`LoadReport`], with the percentiles of the
+//! latencies of the broadcasts and of the inclusions.
+//!
+//! The transactions are of the form `<key>=<value>`, the key being unique to
+//! each transaction and the value padding the transaction to the configured
+//! size, so that key/value store applications accept them all.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use core::time::Duration;
+//!
+//! use tendermint_rpc::{
+//!     client::loadtest::{BroadcastMode, LoadTest},
+//!     HttpClient, SubscriptionClient, WebSocketClient,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (subscriber, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let inclusions = subscriber.subscribe(LoadTest::query()).await.unwrap();
+//!
+//!     let clients = [
+//!         HttpClient::new("http://127.0.0.1:26657").unwrap(),
+//!         HttpClient::new("http://127.0.0.1:36657").unwrap(),
+//!     ];
+//!     let report = LoadTest::new(200.0, Duration::from_secs(60))
+//!         .tx_size(250)
+//!         .mode(BroadcastMode::Sync)
+//!         .run(&clients, Some(inclusions))
+//!         .await;
+//!     println!("{report}");
+//! }
+//! ```
+
+use alloc::collections::BTreeMap;
+use core::{fmt, str::FromStr, time::Duration};
+use std::time::Instant;
+
+use futures::{future, stream::FuturesUnordered, StreamExt};
+use tendermint::abci::Code;
+use tokio::time;
+
+use super::rounds::Latencies;
+use crate::{
+    alloc_prelude::*,
+    event::{Event, EventData},
+    query::{EventType, Query},
+    utils::uuid_str,
+    Client, Error, Subscription,
+};
+
+/// The size of the generated transactions by default, in bytes.
+pub const DEFAULT_TX_SIZE: usize = 250;
+
+/// How long a load test waits by default for the transactions to be included
+/// once it stopped broadcasting them.
+pub const DEFAULT_INCLUSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The lowest rate of a load test, in transactions per second.
+const MIN_RATE: f64 = 0.001;
+
+/// The shortest period between two broadcasts, which bounds the highest rate
+/// of a load test.
+const MIN_PERIOD: Duration = Duration::from_nanos(1);
+
+/// The endpoint used to broadcast the transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BroadcastMode {
+    /// `/broadcast_tx_async`, which returns without waiting for `CheckTx`.
+    Async,
+    /// `/broadcast_tx_sync`, which returns the result of `CheckTx`.
+    #[default]
+    Sync,
+}
+
+impl FromStr for BroadcastMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "async" => Ok(Self::Async),
+            "sync" => Ok(Self::Sync),
+            _ => Err(Error::invalid_params(format!(
+                "invalid broadcast mode: {s} (must be \"async\" or \"sync\")"
+            ))),
+        }
+    }
+}
+
+/// An endless sequence of distinct synthetic transactions of a given size.
+///
+/// The keys of the transactions start with an identifier of the generator,
+/// so that the transactions of different runs do not collide.
+#[derive(Clone, Debug)]
+pub struct TxGenerator {
+    id: String,
+    size: usize,
+    next: u64,
+}
+
+impl TxGenerator {
+    /// Create a generator of transactions of the given size, in bytes. The
+    /// transactions are larger when the size is too small to fit their key.
+    pub fn new(size: usize) -> Self {
+        Self {
+            id: uuid_str(),
+            size,
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for TxGenerator {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut tx = format!("{}-{:08}=", self.id, self.next).into_bytes();
+        self.next += 1;
+        if tx.len() < self.size {
+            tx.resize(self.size, b'x');
+        }
+        Some(tx)
+    }
+}
+
+/// A load test, broadcasting synthetic transactions at a target rate.
+#[derive(Clone, Debug)]
+pub struct LoadTest {
+    rate: f64,
+    duration: Duration,
+    tx_size: usize,
+    mode: BroadcastMode,
+    inclusion_timeout: Duration,
+}
+
+impl LoadTest {
+    /// Create a load test broadcasting the given number of transactions per
+    /// second for the given duration.
+    pub fn new(rate: f64, duration: Duration) -> Self {
+        Self {
+            rate: rate.max(MIN_RATE),
+            duration,
+            tx_size: DEFAULT_TX_SIZE,
+            mode: BroadcastMode::default(),
+            inclusion_timeout: DEFAULT_INCLUSION_TIMEOUT,
+        }
+    }
+
+    /// Generate transactions of the given size, in bytes (see
+    /// [`DEFAULT_TX_SIZE`]).
+    #[must_use]
+    pub fn tx_size(mut self, bytes: usize) -> Self {
+        self.tx_size = bytes;
+        self
+    }
+
+    /// Broadcast the transactions with the given endpoint.
+    #[must_use]
+    pub fn mode(mut self, mode: BroadcastMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Wait for up to the given duration for the transactions to be included
+    /// once the test stopped broadcasting them (see
+    /// [`DEFAULT_INCLUSION_TIMEOUT`]).
+    #[must_use]
+    pub fn inclusion_timeout(mut self, timeout: Duration) -> Self {
+        self.inclusion_timeout = timeout;
+        self
+    }
+
+    /// The period between two broadcasts: the highest rates are rounded
+    /// down to one broadcast per nanosecond.
+    fn period(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.rate).max(MIN_PERIOD)
+    }
+
+    /// The query of the events through which the inclusion of the
+    /// transactions is tracked.
+    pub fn query() -> Query {
+        Query::from(EventType::Tx)
+    }
+
+    /// Run the test, broadcasting the transactions through each of the given
+    /// clients in turn, and tracking their inclusion through the given
+    /// subscription to the [`LoadTest::query`] events, if any.
+    ///
+    /// The broadcasts are not retried: the transactions whose broadcast
+    /// failed are reported as such.
+    pub async fn run<C>(&self, clients: &[C], mut inclusions: Option<Subscription>) -> LoadReport
+    where
+        C: Client + Sync,
+    {
+        let mut txs = TxGenerator::new(self.tx_size);
+        let mut tracker = LoadTracker::new();
+        let mut in_flight = FuturesUnordered::new();
+        let mut next_endpoint = 0;
+
+        // The time of the runtime is used throughout, so that it can be
+        // paused in tests.
+        let start = time::Instant::now();
+        let stop_at = start + self.duration;
+        let give_up_at = stop_at + self.inclusion_timeout;
+        let mut sending = !clients.is_empty();
+        let mut ticks = time::interval(self.period());
+        tracker.started_at(start.into_std());
+
+        loop {
+            if !sending
+                && in_flight.is_empty()
+                && (inclusions.is_none() || tracker.awaiting_inclusion() == 0)
+            {
+                break;
+            }
+
+            tokio::select! {
+                _ = ticks.tick(), if sending => {
+                    let now = time::Instant::now();
+                    if now >= stop_at {
+                        sending = false;
+                        tracker.stopped_at(now.into_std());
+                        continue;
+                    }
+                    let tx = txs.next().expect("transactions are endless");
+                    let endpoint = next_endpoint;
+                    next_endpoint = (next_endpoint + 1) % clients.len();
+                    tracker.sent_at(tx.clone(), endpoint, now.into_std());
+                    let response = broadcast(&clients[endpoint], self.mode, tx.clone());
+                    in_flight.push(async move {
+                        (tx, endpoint, response.await, time::Instant::now().into_std())
+                    });
+                },
+                Some((tx, endpoint, result, at)) = in_flight.next(), if !in_flight.is_empty() => {
+                    tracker.broadcast_at(&tx, endpoint, &result, at);
+                },
+                event = next_event(&mut inclusions) => match event {
+                    Some(Ok(event)) => tracker.observe_at(&event, time::Instant::now().into_std()),
+                    Some(Err(_)) => {},
+                    // The inclusions are no longer tracked.
+                    None => inclusions = None,
+                },
+                _ = time::sleep_until(give_up_at), if !sending => break,
+            }
+        }
+
+        tracker.report()
+    }
+}
+
+async fn broadcast<C>(client: &C, mode: BroadcastMode, tx: Vec<u8>) -> Result<Code, Error>
+where
+    C: Client + Sync,
+{
+    match mode {
+        BroadcastMode::Async => client.broadcast_tx_async(tx).await.map(|r| r.code),
+        BroadcastMode::Sync => client.broadcast_tx_sync(tx).await.map(|r| r.code),
+    }
+}
+
+async fn next_event(inclusions: &mut Option<Subscription>) -> Option<Result<Event, Error>> {
+    match inclusions {
+        Some(subscription) => subscription.next().await,
+        None => future::pending().await,
+    }
+}
+
+/// Keeps track of the transactions of a load test, of their broadcast and of
+/// their inclusion, and summarizes them in a [`LoadReport`].
+///
+/// This is what a [`LoadTest`] uses to measure its transactions, for load
+/// generators which broadcast their transactions themselves.
+#[derive(Clone, Debug, Default)]
+pub struct LoadTracker {
+    started_at: Option<Instant>,
+    stopped_at: Option<Instant>,
+    // The time at which the transactions which may still be included were
+    // sent.
+    sent: BTreeMap<Vec<u8>, Instant>,
+    endpoints: Vec<EndpointReport>,
+    included: usize,
+    broadcast_latencies: Vec<Duration>,
+    inclusion_latencies: Vec<Duration>,
+}
+
+impl LoadTracker {
+    /// Create a tracker of no transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the test started broadcasting at the given instant.
+    pub fn started_at(&mut self, at: Instant) {
+        self.started_at = Some(at);
+    }
+
+    /// Record that the test stopped broadcasting at the given instant.
+    pub fn stopped_at(&mut self, at: Instant) {
+        self.stopped_at = Some(at);
+    }
+
+    /// Record that the given transaction was sent to the endpoint with the
+    /// given index at the given instant.
+    pub fn sent_at(&mut self, tx: Vec<u8>, endpoint: usize, at: Instant) {
+        self.endpoint(endpoint).sent += 1;
+        self.sent.insert(tx, at);
+    }
+
+    /// Record the result of the broadcast of the given transaction to the
+    /// endpoint with the given index, as received at the given instant.
+    ///
+    /// The transactions which were rejected by `CheckTx`, or whose broadcast
+    /// failed, are not expected to be included.
+    pub fn broadcast_at(
+        &mut self,
+        tx: &[u8],
+        endpoint: usize,
+        result: &Result<Code, Error>,
+        at: Instant,
+    ) {
+        let sent_at = self.sent.get(tx).copied();
+        let report = self.endpoint(endpoint);
+        match result {
+            Ok(code) if code.is_ok() => report.accepted += 1,
+            Ok(_) => report.rejected += 1,
+            Err(_) => report.failed += 1,
+        }
+        if let Some(sent_at) = sent_at {
+            self.broadcast_latencies
+                .push(at.saturating_duration_since(sent_at));
+            if !matches!(result, Ok(code) if code.is_ok()) {
+                self.sent.remove(tx);
+            }
+        }
+    }
+
+    /// Observe the given event, as received at the given instant.
+    ///
+    /// The events which are not about the inclusion of a transaction of the
+    /// test are ignored.
+    pub fn observe_at(&mut self, event: &Event, at: Instant) {
+        if let EventData::Tx { tx_result } = &event.data {
+            if let Some(sent_at) = self.sent.remove(&tx_result.tx) {
+                self.included += 1;
+                self.inclusion_latencies
+                    .push(at.saturating_duration_since(sent_at));
+            }
+        }
+    }
+
+    /// The number of transactions which were sent and may still be
+    /// included.
+    pub fn awaiting_inclusion(&self) -> usize {
+        self.sent.len()
+    }
+
+    /// Summarize the transactions of the test.
+    pub fn report(&self) -> LoadReport {
+        let mut report = LoadReport {
+            endpoints: self.endpoints.clone(),
+            included: self.included,
+            broadcast_latency: Latencies::of(self.broadcast_latencies.clone()),
+            inclusion_latency: Latencies::of(self.inclusion_latencies.clone()),
+            ..LoadReport::default()
+        };
+        for endpoint in &self.endpoints {
+            report.sent += endpoint.sent;
+            report.accepted += endpoint.accepted;
+            report.rejected += endpoint.rejected;
+            report.failed += endpoint.failed;
+        }
+        if let (Some(started), Some(stopped)) = (self.started_at, self.stopped_at) {
+            report.duration = stopped.saturating_duration_since(started);
+        }
+        report
+    }
+
+    fn endpoint(&mut self, index: usize) -> &mut EndpointReport {
+        if self.endpoints.len() <= index {
+            self.endpoints.resize(index + 1, EndpointReport::default());
+        }
+        &mut self.endpoints[index]
+    }
+}
+
+/// A summary of a load test.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadReport {
+    /// How long the test broadcast transactions.
+    pub duration: Duration,
+    /// The number of transactions which were sent.
+    pub sent: usize,
+    /// The number of transactions which were accepted by the endpoints.
+    pub accepted: usize,
+    /// The number of transactions which were rejected by `CheckTx`.
+    pub rejected: usize,
+    /// The number of transactions whose broadcast failed.
+    pub failed: usize,
+    /// The number of transactions which were seen included in a block.
+    pub included: usize,
+    /// The broadcasts of each endpoint, in the order of the clients.
+    pub endpoints: Vec<EndpointReport>,
+    /// The time taken by the endpoints to respond to the broadcasts.
+    pub broadcast_latency: Option<Latencies>,
+    /// The time from the broadcast of the transactions to their inclusion.
+    pub inclusion_latency: Option<Latencies>,
+}
+
+impl LoadReport {
+    /// The number of transactions sent per second.
+    pub fn send_rate(&self) -> f64 {
+        per_second(self.sent, self.duration)
+    }
+
+    /// The number of transactions included per second.
+    pub fn inclusion_rate(&self) -> f64 {
+        per_second(self.included, self.duration)
+    }
+}
+
+fn per_second(count: usize, duration: Duration) -> f64 {
+    if duration.is_zero() {
+        return 0.0;
+    }
+    count as f64 / duration.as_secs_f64()
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "sent {} transactions in {:.1}s ({:.1} tx/s)",
+            self.sent,
+            self.duration.as_secs_f64(),
+            self.send_rate()
+        )?;
+        writeln!(
+            f,
+            "accepted: {}, rejected: {}, failed: {}",
+            self.accepted, self.rejected, self.failed
+        )?;
+        writeln!(
+            f,
+            "included: {} ({:.1} tx/s)",
+            self.included,
+            self.inclusion_rate()
+        )?;
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            writeln!(
+                f,
+                "endpoint {index}: sent: {}, accepted: {}, rejected: {}, failed: {}",
+                endpoint.sent, endpoint.accepted, endpoint.rejected, endpoint.failed
+            )?;
+        }
+        for (name, latency) in [
+            ("broadcast", &self.broadcast_latency),
+            ("inclusion", &self.inclusion_latency),
+        ] {
+            if let Some(latency) = latency {
+                writeln!(
+                    f,
+                    "{name} latency: p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+                    latency.p50, latency.p90, latency.p99, latency.max
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The broadcasts of a load test through one endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EndpointReport {
+    /// The number of transactions which were sent.
+    pub sent: usize,
+    /// The number of transactions which were accepted.
+    pub accepted: usize,
+    /// The number of transactions which were rejected by `CheckTx`.
+    pub rejected: usize,
+    /// The number of transactions whose broadcast failed.
+    pub failed: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use tendermint::abci::response::DeliverTx;
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMethodMatcher},
+        event::TxInfo,
+        Method,
+    };
+
+    fn fixture(name: &str) -> String {
+        fs::read_to_string(
+            PathBuf::from("./tests/kvstore_fixtures/v0_37/incoming")
+                .join(name.to_owned() + ".json"),
+        )
+        .unwrap()
+    }
+
+    fn tx_event(tx: &[u8]) -> Event {
+        Event {
+            query: LoadTest::query().to_string(),
+            data: EventData::Tx {
+                tx_result: TxInfo {
+                    height: 1_u32.into(),
+                    index: 0,
+                    tx: tx.to_vec(),
+                    result: DeliverTx::default(),
+                },
+            },
+            events: None,
+        }
+    }
+
+    #[test]
+    fn generates_distinct_txs() {
+        let txs: Vec<_> = TxGenerator::new(100).take(3).collect();
+        for tx in &txs {
+            assert_eq!(tx.len(), 100);
+            assert_eq!(tx.iter().filter(|b| **b == b'=').count(), 1);
+        }
+        assert_ne!(txs[0], txs[1]);
+        assert_ne!(txs[1], txs[2]);
+
+        // The key is never truncated.
+        let tx = TxGenerator::new(1).next().unwrap();
+        assert!(tx.ends_with(b"-00000000="));
+        assert_ne!(TxGenerator::new(1).next().unwrap(), tx);
+    }
+
+    #[test]
+    fn parses_broadcast_modes() {
+        assert_eq!(
+            "async".parse::<BroadcastMode>().unwrap(),
+            BroadcastMode::Async
+        );
+        assert_eq!(
+            "sync".parse::<BroadcastMode>().unwrap(),
+            BroadcastMode::Sync
+        );
+        assert!("commit".parse::<BroadcastMode>().is_err());
+    }
+
+    #[test]
+    fn tracks_broadcasts_and_inclusions() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut tracker = LoadTracker::new();
+        tracker.started_at(start);
+
+        let txs: Vec<_> = TxGenerator::new(64).take(4).collect();
+        for (i, tx) in txs.iter().enumerate() {
+            tracker.sent_at(tx.clone(), i % 2, start + ms(10 * i as u64));
+        }
+        tracker.broadcast_at(&txs[0], 0, &Ok(Code::Ok), start + ms(5));
+        tracker.broadcast_at(&txs[1], 1, &Ok(Code::Ok), start + ms(15));
+        tracker.broadcast_at(&txs[2], 0, &Ok(Code::from(1)), start + ms(25));
+        tracker.broadcast_at(&txs[3], 1, &Err(Error::mismatch_response()), start + ms(35));
+        assert_eq!(tracker.awaiting_inclusion(), 2);
+
+        tracker.stopped_at(start + ms(1_000));
+        tracker.observe_at(&tx_event(&txs[0]), start + ms(1_000));
+        tracker.observe_at(&tx_event(&txs[1]), start + ms(2_010));
+        // The transactions of other clients are ignored, as are those which
+        // were already included.
+        tracker.observe_at(&tx_event(b"other=tx"), start + ms(2_010));
+        tracker.observe_at(&tx_event(&txs[0]), start + ms(3_000));
+        assert_eq!(tracker.awaiting_inclusion(), 0);
+
+        let report = tracker.report();
+        assert_eq!(report.duration, ms(1_000));
+        assert_eq!(
+            (report.sent, report.accepted, report.rejected, report.failed),
+            (4, 2, 1, 1)
+        );
+        assert_eq!(report.included, 2);
+        assert_eq!(report.send_rate(), 4.0);
+        assert_eq!(
+            report.endpoints,
+            [
+                EndpointReport {
+                    sent: 2,
+                    accepted: 1,
+                    rejected: 1,
+                    failed: 0
+                },
+                EndpointReport {
+                    sent: 2,
+                    accepted: 1,
+                    rejected: 0,
+                    failed: 1
+                },
+            ]
+        );
+        assert_eq!(report.broadcast_latency.unwrap().max, ms(5));
+        let inclusion = report.inclusion_latency.unwrap();
+        assert_eq!(inclusion.samples, 2);
+        assert_eq!(inclusion.p50, ms(1_000));
+        assert_eq!(inclusion.max, ms(2_000));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn broadcasts_across_endpoints_at_rate() {
+        let clients: Vec<_> = (0..2)
+            .map(|_| {
+                let matcher = MockRequestMethodMatcher::default()
+                    .map(Method::BroadcastTxSync, Ok(fixture("broadcast_tx_sync")));
+                MockClient::new(matcher).0
+            })
+            .collect();
+
+        let report = LoadTest::new(100.0, Duration::from_millis(200))
+            .run(&clients, None)
+            .await;
+        assert_eq!(report.sent, 20);
+        assert_eq!(report.accepted, report.sent);
+        assert_eq!(report.endpoints.len(), 2);
+        assert_eq!(report.endpoints[0].sent, 10);
+        assert_eq!(report.endpoints[1].sent, 10);
+        assert_eq!(report.included, 0);
+        assert!(report.inclusion_latency.is_none());
+    }
+
+    #[test]
+    fn bounds_the_period() {
+        let period = |rate| LoadTest::new(rate, Duration::ZERO).period();
+        assert_eq!(period(4.0), Duration::from_millis(250));
+        assert_eq!(period(1e12), MIN_PERIOD);
+        assert_eq!(period(f64::INFINITY), MIN_PERIOD);
+        assert_eq!(period(0.0), Duration::from_secs(1_000));
+    }
+}
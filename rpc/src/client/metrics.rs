@@ -0,0 +1,363 @@
+//! A summary of the health of a node and of its chain, which can be exported
+//! as metrics in the OpenMetrics text format.
+//!
+//! A [`HealthReport`] is made of the responses of `/status`, `/net_info` and
+//! `/consensus_state`, and is fetched with one call to
+//! [`HealthReport::fetch`]. The reports of several nodes are rendered by
+//! [`encode_openmetrics`], e.g. to be served by the `/metrics` endpoint of an
+//! application, with the following gauges, labelled with the `chain_id`,
+//! `node_id` and `moniker` of their node:
+//!
+//! - `tendermint_latest_block_height`, the height of the latest block;
+//! - `tendermint_latest_block_time_seconds`, the time of the latest block,
+//!   as a Unix timestamp;
+//! - `tendermint_catching_up`, 1 if the node is syncing, 0 otherwise;
+//! - `tendermint_rpc_response_time_seconds`, the time taken by the node to
+//!   respond to `/status`;
+//! - `tendermint_peers`, the number of peers of the node, labelled with
+//!   their `direction`: `inbound` or `outbound`;
+//! - `tendermint_consensus_height`, `tendermint_consensus_round` and
+//!   `tendermint_consensus_step`, the current step of consensus;
+//! - `tendermint_consensus_prevoted_power_ratio` and
+//!   `tendermint_consensus_precommitted_power_ratio`, the fraction of the
+//!   voting power which voted in the current round.
+//!
+//! The gauges of the optional parts of a report are omitted when they are
+//! missing.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use tendermint_rpc::{client::metrics::HealthReport, HttpClient};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+//!     let report = HealthReport::fetch(&client).await.unwrap();
+//!     println!("{}", report.to_openmetrics());
+//! }
+//! ```
+
+use core::time::Duration;
+use std::time::Instant;
+
+use tendermint::{
+    block::{Height, Round},
+    chain, node, Time,
+};
+use tendermint_std_ext::metrics::{Encoder, MetricType};
+
+use crate::{
+    alloc_prelude::*,
+    endpoint::{
+        consensus_state::{self, RoundStep},
+        net_info, status,
+    },
+    Client, Error,
+};
+
+/// A summary of the health of a node and of its chain.
+#[derive(Clone, Debug)]
+pub struct HealthReport {
+    /// The chain of the node.
+    pub chain_id: chain::Id,
+    /// The ID of the node.
+    pub node_id: node::Id,
+    /// The moniker of the node.
+    pub moniker: String,
+    /// The height of the latest block of the node.
+    pub latest_block_height: Height,
+    /// The time of the latest block of the node.
+    pub latest_block_time: Time,
+    /// Whether the node is syncing.
+    pub catching_up: bool,
+    /// The time taken by the node to respond to `/status`, if measured.
+    pub response_time: Option<Duration>,
+    /// The peers of the node, if known.
+    pub peers: Option<PeerCounts>,
+    /// The current step of consensus, if known.
+    pub consensus: Option<ConsensusHealth>,
+}
+
+/// The number of peers of a node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PeerCounts {
+    /// The peers which connected to the node.
+    pub inbound: u64,
+    /// The peers the node connected to.
+    pub outbound: u64,
+}
+
+/// The current step of consensus, as seen by a node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConsensusHealth {
+    /// The height being decided.
+    pub height: Height,
+    /// The current round.
+    pub round: Round,
+    /// The current step of the round.
+    pub step: RoundStep,
+    /// The fraction of the voting power which prevoted in the current round.
+    pub prevoted_power_ratio: Option<f64>,
+    /// The fraction of the voting power which precommitted in the current
+    /// round.
+    pub precommitted_power_ratio: Option<f64>,
+}
+
+impl HealthReport {
+    /// Make a report out of the responses of a node.
+    pub fn new(
+        status: &status::Response,
+        net_info: Option<&net_info::Response>,
+        consensus_state: Option<&consensus_state::Response>,
+    ) -> Self {
+        Self {
+            chain_id: status.node_info.network.clone(),
+            node_id: status.node_info.id,
+            moniker: status.node_info.moniker.to_string(),
+            latest_block_height: status.sync_info.latest_block_height,
+            latest_block_time: status.sync_info.latest_block_time,
+            catching_up: status.sync_info.catching_up,
+            response_time: None,
+            peers: net_info.map(PeerCounts::new),
+            consensus: consensus_state.map(ConsensusHealth::new),
+        }
+    }
+
+    /// Fetch the report of the node of the given client.
+    ///
+    /// Only failing to fetch `/status` fails the report: the parts of the
+    /// report coming from the other endpoints are missing when these fail,
+    /// e.g. because a proxy in front of the node does not expose them.
+    pub async fn fetch<C>(client: &C) -> Result<Self, Error>
+    where
+        C: Client + Sync,
+    {
+        let started = Instant::now();
+        let status = client.status().await?;
+        let response_time = started.elapsed();
+
+        let net_info = client.net_info().await.ok();
+        let consensus_state = client.consensus_state().await.ok();
+
+        Ok(Self {
+            response_time: Some(response_time),
+            ..Self::new(&status, net_info.as_ref(), consensus_state.as_ref())
+        })
+    }
+
+    /// Render the report in the OpenMetrics text format.
+    pub fn to_openmetrics(&self) -> String {
+        encode_openmetrics(core::slice::from_ref(self))
+    }
+}
+
+impl PeerCounts {
+    fn new(net_info: &net_info::Response) -> Self {
+        let outbound = net_info
+            .peers
+            .iter()
+            .filter(|peer| peer.is_outbound)
+            .count() as u64;
+        Self {
+            inbound: net_info.peers.len() as u64 - outbound,
+            outbound,
+        }
+    }
+}
+
+impl ConsensusHealth {
+    fn new(consensus_state: &consensus_state::Response) -> Self {
+        let hrs = &consensus_state.round_state.height_round_step;
+        let votes = consensus_state
+            .round_state
+            .height_vote_set
+            .iter()
+            .find(|votes| votes.round == hrs.round.value());
+        let ratio = |bit_array: &consensus_state::VoteBitArray| {
+            (bit_array.total_power > 0)
+                .then(|| bit_array.voted_power as f64 / bit_array.total_power as f64)
+        };
+        Self {
+            height: hrs.height,
+            round: hrs.round,
+            step: hrs.step,
+            prevoted_power_ratio: votes.and_then(|votes| ratio(&votes.prevotes_bit_array)),
+            precommitted_power_ratio: votes.and_then(|votes| ratio(&votes.precommits_bit_array)),
+        }
+    }
+}
+
+/// Render the reports of several nodes in the OpenMetrics text format.
+pub fn encode_openmetrics(reports: &[HealthReport]) -> String {
+    let mut encoder = Encoder::new();
+
+    let mut gauge = |name: &str, help: &str, samples: &dyn Fn(&HealthReport) -> Vec<Sample>| {
+        encoder.family(name, MetricType::Gauge, help);
+        for report in reports {
+            let node_id = report.node_id.to_string();
+            for (label, value) in samples(report) {
+                let mut labels = vec![
+                    ("chain_id", report.chain_id.as_str()),
+                    ("node_id", node_id.as_str()),
+                    ("moniker", report.moniker.as_str()),
+                ];
+                labels.extend(label);
+                encoder.sample(name, &labels, value);
+            }
+        }
+    };
+
+    gauge(
+        "tendermint_latest_block_height",
+        "Height of the latest block of the node.",
+        &|r| vec![(None, r.latest_block_height.value() as f64)],
+    );
+    gauge(
+        "tendermint_latest_block_time_seconds",
+        "Time of the latest block of the node, as a Unix timestamp.",
+        &|r| {
+            vec![(
+                None,
+                r.latest_block_time.unix_timestamp_nanos() as f64 / 1e9,
+            )]
+        },
+    );
+    gauge(
+        "tendermint_catching_up",
+        "Whether the node is syncing.",
+        &|r| vec![(None, u8::from(r.catching_up) as f64)],
+    );
+    gauge(
+        "tendermint_rpc_response_time_seconds",
+        "Time taken by the node to respond to /status.",
+        &|r| {
+            r.response_time
+                .map(|time| (None, time.as_secs_f64()))
+                .into_iter()
+                .collect()
+        },
+    );
+    gauge("tendermint_peers", "Number of peers of the node.", &|r| {
+        r.peers
+            .map(|peers| {
+                vec![
+                    (Some(("direction", "inbound")), peers.inbound as f64),
+                    (Some(("direction", "outbound")), peers.outbound as f64),
+                ]
+            })
+            .unwrap_or_default()
+    });
+    gauge(
+        "tendermint_consensus_height",
+        "Height being decided by consensus.",
+        &|r| consensus(r, |c| Some(c.height.value() as f64)),
+    );
+    gauge(
+        "tendermint_consensus_round",
+        "Current round of consensus.",
+        &|r| consensus(r, |c| Some(c.round.value() as f64)),
+    );
+    gauge(
+        "tendermint_consensus_step",
+        "Current step of the consensus round.",
+        &|r| consensus(r, |c| Some(u8::from(c.step) as f64)),
+    );
+    gauge(
+        "tendermint_consensus_prevoted_power_ratio",
+        "Fraction of the voting power which prevoted in the current round.",
+        &|r| consensus(r, |c| c.prevoted_power_ratio),
+    );
+    gauge(
+        "tendermint_consensus_precommitted_power_ratio",
+        "Fraction of the voting power which precommitted in the current round.",
+        &|r| consensus(r, |c| c.precommitted_power_ratio),
+    );
+
+    encoder.finish_openmetrics()
+}
+
+/// A sample of a gauge, with the label it has besides those of its node, if
+/// any.
+type Sample = (Option<(&'static str, &'static str)>, f64);
+
+/// The sample of a gauge of the consensus part of a report, if any.
+fn consensus(
+    report: &HealthReport,
+    value: impl Fn(&ConsensusHealth) -> Option<f64>,
+) -> Vec<Sample> {
+    report
+        .consensus
+        .as_ref()
+        .and_then(value)
+        .map(|value| (None, value))
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Response;
+
+    const STATUS: &str = include_str!("../../tests/kvstore_fixtures/v0_37/incoming/status.json");
+    const NET_INFO: &str =
+        include_str!("../../tests/kvstore_fixtures/v0_37/incoming/net_info.json");
+    const CONSENSUS_STATE: &str =
+        include_str!("../../tests/kvstore_fixtures/v0_37/incoming/consensus_state.json");
+
+    fn report() -> HealthReport {
+        let status = status::Response::from_string(STATUS).unwrap();
+        let net_info = net_info::Response::from_string(NET_INFO).unwrap();
+        let consensus_state = consensus_state::Response::from_string(CONSENSUS_STATE).unwrap();
+        HealthReport::new(&status, Some(&net_info), Some(&consensus_state))
+    }
+
+    #[test]
+    fn encodes_full_report() {
+        let report = HealthReport {
+            response_time: Some(Duration::from_millis(250)),
+            ..report()
+        };
+        let metrics = report.to_openmetrics();
+
+        let labels = format!(
+            "chain_id=\"{}\",node_id=\"{}\",moniker=\"{}\"",
+            report.chain_id, report.node_id, report.moniker
+        );
+        let height = report.latest_block_height.value();
+        assert!(metrics.contains(&format!(
+            "tendermint_latest_block_height{{{labels}}} {height}\n"
+        )));
+        assert!(metrics.contains(&format!("tendermint_catching_up{{{labels}}} 0\n")));
+        assert!(metrics.contains(&format!(
+            "tendermint_rpc_response_time_seconds{{{labels}}} 0.25\n"
+        )));
+        assert!(metrics.contains("# TYPE tendermint_peers gauge\n"));
+        assert!(metrics.contains("# TYPE tendermint_consensus_step gauge\n"));
+        assert!(metrics.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn omits_missing_parts() {
+        let status = status::Response::from_string(STATUS).unwrap();
+        let metrics = encode_openmetrics(&[HealthReport::new(&status, None, None)]);
+
+        assert!(metrics.contains("# TYPE tendermint_peers gauge\n"));
+        assert!(!metrics.contains("tendermint_peers{"));
+        assert!(!metrics.contains("tendermint_consensus_round{"));
+        assert!(!metrics.contains("tendermint_rpc_response_time_seconds{"));
+        assert!(metrics.contains("tendermint_latest_block_time_seconds{"));
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        let report = HealthReport {
+            moniker: "a\"b\\c\nd".to_string(),
+            ..report()
+        };
+        let metrics = report.to_openmetrics();
+
+        assert!(metrics.contains("moniker=\"a\\\"b\\\\c\\nd\"}"));
+    }
+}
@@ -0,0 +1,512 @@
+//! A stream of the changes of the voting power of the validators.
+//!
+//! The application returns the changes of the validator set in the results
+//! of the blocks (`validator_updates`), which take effect two blocks later.
+//! [`subscribe_power_changes`] follows the new blocks, fetches their block
+//! results, and yields a [`PowerChange`] for every validator whose voting
+//! power changed, while maintaining the resulting set of validators in a
+//! [`ValidatorPowers`].
+//!
+//! When the block results of a height cannot be fetched (e.g. because the
+//! node pruned them), the set is instead brought up to date at the next
+//! height, by comparing it with the validators returned by `/validators`.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{client::power::subscribe_power_changes, HttpClient, WebSocketClient};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (subscriber, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+//!
+//!     let mut changes = subscribe_power_changes(&subscriber, client).await.unwrap();
+//!     while let Some(change) = changes.next().await {
+//!         let change = change.unwrap();
+//!         let total_power = changes.validators().total_power();
+//!         println!(
+//!             "validator {:?}: {} -> {} at height {} ({} in total)",
+//!             change.validator, change.old, change.new, change.height, total_power,
+//!         );
+//!     }
+//! }
+//! ```
+
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::sync::Mutex;
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use tendermint::{block::Height, validator, vote, PublicKey};
+
+use crate::{
+    alloc_prelude::*, client::blocks::new_blocks, event::Event, query::EventType, Client, Error,
+    Paging, SubscriptionClient,
+};
+
+/// A change of the voting power of a validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerChange {
+    /// The public key of the validator.
+    pub validator: PublicKey,
+    /// The voting power of the validator before the change, zero if it was
+    /// not a validator.
+    pub old: vote::Power,
+    /// The voting power of the validator after the change, zero if it is no
+    /// longer a validator.
+    pub new: vote::Power,
+    /// The height of the block whose results carried the change, which takes
+    /// effect two blocks later.
+    pub height: Height,
+}
+
+impl PowerChange {
+    /// Whether the validator joined the validator set.
+    pub fn is_added(&self) -> bool {
+        self.old.is_zero()
+    }
+
+    /// Whether the validator left the validator set.
+    pub fn is_removed(&self) -> bool {
+        self.new.is_zero()
+    }
+}
+
+/// The voting powers of a set of validators.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorPowers {
+    powers: BTreeMap<PublicKey, vote::Power>,
+}
+
+impl ValidatorPowers {
+    /// The voting powers of the given validators.
+    pub fn new(validators: &[validator::Info]) -> Self {
+        let powers = validators
+            .iter()
+            .filter(|info| !info.power.is_zero())
+            .map(|info| (info.pub_key, info.power))
+            .collect();
+        Self { powers }
+    }
+
+    /// The voting power of the given validator, if it is in the set.
+    pub fn power(&self, validator: &PublicKey) -> Option<vote::Power> {
+        self.powers.get(validator).copied()
+    }
+
+    /// The validators and their voting power, ordered by public key.
+    pub fn iter(&self) -> impl Iterator<Item = (&PublicKey, &vote::Power)> {
+        self.powers.iter()
+    }
+
+    /// The number of validators.
+    pub fn len(&self) -> usize {
+        self.powers.len()
+    }
+
+    /// Whether the set has no validators.
+    pub fn is_empty(&self) -> bool {
+        self.powers.is_empty()
+    }
+
+    /// The sum of the voting powers of the validators.
+    pub fn total_power(&self) -> u64 {
+        self.powers.values().map(|power| power.value()).sum()
+    }
+
+    /// Apply the validator updates of the results of the block at the given
+    /// height, returning the changes of voting power.
+    ///
+    /// The updates which do not change the voting power of their validator
+    /// are ignored.
+    pub fn apply_updates(
+        &mut self,
+        height: Height,
+        updates: &[validator::Update],
+    ) -> Vec<PowerChange> {
+        let changes: Vec<_> = updates
+            .iter()
+            .filter_map(|update| self.change(update.pub_key, update.power, height))
+            .collect();
+        for change in &changes {
+            self.apply(change);
+        }
+        changes
+    }
+
+    /// Replace the set with the given validators, returning the changes of
+    /// voting power, attributed to the given height.
+    pub fn apply_validators(
+        &mut self,
+        height: Height,
+        validators: &[validator::Info],
+    ) -> Vec<PowerChange> {
+        let new = Self::new(validators);
+        let removed = self
+            .powers
+            .keys()
+            .filter(|validator| !new.powers.contains_key(validator))
+            .map(|validator| (*validator, vote::Power::default()));
+        let changes: Vec<_> = new
+            .powers
+            .iter()
+            .map(|(validator, power)| (*validator, *power))
+            .chain(removed)
+            .filter_map(|(validator, power)| self.change(validator, power, height))
+            .collect();
+        *self = new;
+        changes
+    }
+
+    fn change(
+        &self,
+        validator: PublicKey,
+        new: vote::Power,
+        height: Height,
+    ) -> Option<PowerChange> {
+        let old = self.power(&validator).unwrap_or_default();
+        (old != new).then_some(PowerChange {
+            validator,
+            old,
+            new,
+            height,
+        })
+    }
+
+    fn apply(&mut self, change: &PowerChange) {
+        if change.is_removed() {
+            self.powers.remove(&change.validator);
+        } else {
+            self.powers.insert(change.validator, change.new);
+        }
+    }
+}
+
+/// The stream of [`PowerChange`]s returned by [`subscribe_power_changes`].
+pub struct PowerChangeStream {
+    changes: BoxStream<'static, Result<PowerChange, Error>>,
+    validators: Arc<Mutex<ValidatorPowers>>,
+}
+
+impl PowerChangeStream {
+    /// The current set of validators, once all the changes yielded so far
+    /// are applied.
+    ///
+    /// The set is empty until the first block is received.
+    pub fn validators(&self) -> ValidatorPowers {
+        self.validators.lock().unwrap().clone()
+    }
+}
+
+impl Stream for PowerChangeStream {
+    type Item = Result<PowerChange, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.changes.poll_next_unpin(cx)
+    }
+}
+
+/// Subscribe to the `NewBlock` events with `subscriber`, and stream the
+/// changes of voting power of the validators, fetching the block results
+/// and the validators with `client`.
+///
+/// The errors of the subscription are yielded by the stream, as are the
+/// failures to fetch the validators, after which the set is brought up to
+/// date at the next height.
+pub async fn subscribe_power_changes<S, C>(
+    subscriber: &S,
+    client: C,
+) -> Result<PowerChangeStream, Error>
+where
+    S: SubscriptionClient + Sync,
+    C: Client + Send + Sync + 'static,
+{
+    let subscription = subscriber.subscribe(EventType::NewBlock.into()).await?;
+    Ok(power_changes(subscription, client))
+}
+
+struct State<B, C> {
+    new_blocks: B,
+    client: C,
+    // Unknown until the validators of the first height are fetched.
+    powers: Option<ValidatorPowers>,
+    // Whether the block results of the previous height were missed.
+    stale: bool,
+    // The changes to yield before moving on to the next height.
+    pending: VecDeque<PowerChange>,
+    // The set shared with the stream, with the changes yielded so far.
+    validators: Arc<Mutex<ValidatorPowers>>,
+}
+
+impl<B, C> State<B, C>
+where
+    C: Client + Sync,
+{
+    async fn update(&mut self, height: Height) -> Result<(), Error> {
+        if self.powers.is_none() || self.stale {
+            // The validators of the next height result from the block
+            // results up to the previous height.
+            let validators = self
+                .client
+                .validators(height.increment(), Paging::All)
+                .await?
+                .validators;
+            match &mut self.powers {
+                Some(powers) => {
+                    let previous =
+                        Height::try_from(height.value() - 1).map_err(Error::tendermint)?;
+                    let changes = powers.apply_validators(previous, &validators);
+                    self.pending.extend(changes);
+                },
+                None => {
+                    let powers = ValidatorPowers::new(&validators);
+                    *self.validators.lock().unwrap() = powers.clone();
+                    self.powers = Some(powers);
+                },
+            }
+            self.stale = false;
+        }
+
+        match self.client.block_results(height).await {
+            Ok(results) => {
+                let powers = self.powers.as_mut().expect("the validators are known");
+                let changes = powers.apply_updates(height, &results.validator_updates);
+                self.pending.extend(changes);
+            },
+            Err(_) => self.stale = true,
+        }
+        Ok(())
+    }
+}
+
+fn power_changes<E, C>(events: E, client: C) -> PowerChangeStream
+where
+    E: Stream<Item = Result<Event, Error>> + Unpin + Send + 'static,
+    C: Client + Send + Sync + 'static,
+{
+    let validators = Arc::new(Mutex::new(ValidatorPowers::default()));
+    let state = State {
+        new_blocks: Box::pin(new_blocks(events)),
+        client,
+        powers: None,
+        stale: false,
+        pending: VecDeque::new(),
+        validators: validators.clone(),
+    };
+
+    let changes = stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(change) = state.pending.pop_front() {
+                state.validators.lock().unwrap().apply(&change);
+                return Some((Ok(change), state));
+            }
+
+            let height = match state.new_blocks.next().await? {
+                Ok((height, _)) => height,
+                Err(e) => return Some((Err(e), state)),
+            };
+            if let Err(e) = state.update(height).await {
+                return Some((Err(e), state));
+            }
+        }
+    });
+
+    PowerChangeStream {
+        changes: changes.boxed(),
+        validators,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMatcher},
+        dialect::Dialect,
+        event::EventData,
+        Request, Response,
+    };
+
+    const KEYS: [&str; 3] = [
+        "OKAnhjqSneoGRrC37lMmw13vpj3pge3Au8/5Q1YfGq0=",
+        "OYpM2RXHEO1/R3jJRhAbjY8JhvjTBbiNJKBStEKu12s=",
+        "sor2GvQvyJm5myIU0gI+VtPSvwUkyZQFYF8abzvOLPs=",
+    ];
+
+    fn key(index: usize) -> PublicKey {
+        serde_json::from_value(json!({ "type": "tendermint/PubKeyEd25519", "value": KEYS[index] }))
+            .unwrap()
+    }
+
+    /// A chain whose validator set starts with `genesis`, and is updated by
+    /// the results of the blocks in `updates`, except for the `pruned` ones.
+    struct Chain {
+        genesis: Vec<(usize, u64)>,
+        updates: BTreeMap<u64, Vec<(usize, u64)>>,
+        pruned: BTreeSet<u64>,
+    }
+
+    impl Chain {
+        fn validators(&self, height: u64) -> serde_json::Value {
+            let mut powers: BTreeMap<_, _> = self.genesis.iter().copied().collect();
+            let updates = self
+                .updates
+                .range(..height.saturating_sub(1))
+                .flat_map(|(_, u)| u);
+            for (index, power) in updates {
+                powers.insert(*index, *power);
+            }
+            let validators: Vec<_> = powers
+                .into_iter()
+                .filter(|(_, power)| *power > 0)
+                .map(|(index, power)| {
+                    json!({
+                        "address": format!("{:040X}", index),
+                        "pub_key": { "type": "tendermint/PubKeyEd25519", "value": KEYS[index] },
+                        "voting_power": power.to_string(),
+                        "proposer_priority": "0",
+                    })
+                })
+                .collect();
+            json!({
+                "block_height": height.to_string(),
+                "total": validators.len().to_string(),
+                "count": validators.len().to_string(),
+                "validators": validators,
+            })
+        }
+
+        fn block_results(&self, height: u64) -> serde_json::Value {
+            let updates: Vec<_> = self
+                .updates
+                .get(&height)
+                .into_iter()
+                .flatten()
+                .map(|(index, power)| {
+                    json!({
+                        "pub_key": { "type": "ed25519", "data": KEYS[*index] },
+                        "power": power.to_string(),
+                    })
+                })
+                .collect();
+            json!({
+                "height": height.to_string(),
+                "txs_results": null,
+                "begin_block_events": null,
+                "end_block_events": null,
+                "validator_updates": updates,
+                "consensus_param_updates": null,
+            })
+        }
+    }
+
+    impl MockRequestMatcher for Chain {
+        fn response_for<R, S>(&self, request: R) -> Option<Result<R::Response, Error>>
+        where
+            R: Request<S>,
+            S: Dialect,
+        {
+            let request: serde_json::Value = serde_json::from_str(&request.into_json()).unwrap();
+            let height: u64 = request["params"]["height"].as_str()?.parse().unwrap();
+            let result = match request["method"].as_str()? {
+                "validators" => self.validators(height),
+                "block_results" if self.pruned.contains(&height) => {
+                    return Some(Err(Error::client_internal("pruned".to_string())));
+                },
+                "block_results" => self.block_results(height),
+                _ => return None,
+            };
+            let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+            Some(R::Response::from_string(response.to_string()))
+        }
+    }
+
+    fn event(height: u64) -> Result<Event, Error> {
+        Ok(Event {
+            query: "tm.event = 'NewBlock'".to_string(),
+            data: EventData::NewBlock {
+                block: None,
+                result_begin_block: None,
+                result_end_block: None,
+            },
+            events: Some(BTreeMap::from([(
+                "block.height".to_string(),
+                vec![height.to_string()],
+            )])),
+        })
+    }
+
+    fn change(index: usize, old: u32, new: u32, height: u32) -> PowerChange {
+        PowerChange {
+            validator: key(index),
+            old: old.into(),
+            new: new.into(),
+            height: height.into(),
+        }
+    }
+
+    async fn run(chain: Chain, heights: u64) -> (Vec<PowerChange>, ValidatorPowers) {
+        let client = MockClient::new(chain).0;
+        let events = stream::iter((1..=heights).map(event));
+        let mut stream = power_changes(events, client);
+        let mut changes = Vec::new();
+        while let Some(change) = stream.next().await {
+            changes.push(change.unwrap());
+        }
+        (changes, stream.validators())
+    }
+
+    fn chain() -> Chain {
+        Chain {
+            genesis: vec![(0, 10), (1, 10)],
+            updates: BTreeMap::from([
+                (2, vec![(2, 5)]),
+                (3, vec![(0, 0)]),
+                // Unchanged.
+                (4, vec![(1, 10)]),
+            ]),
+            pruned: BTreeSet::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_validator_updates() {
+        let (changes, validators) = run(chain(), 4).await;
+
+        assert_eq!(changes, vec![change(2, 0, 5, 2), change(0, 10, 0, 3)]);
+        assert!(changes[0].is_added());
+        assert!(changes[1].is_removed());
+        assert_eq!(validators.len(), 2);
+        assert_eq!(validators.power(&key(2)), Some(5_u32.into()));
+        assert_eq!(validators.total_power(), 15);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_validators() {
+        let chain = Chain {
+            pruned: BTreeSet::from([2, 3]),
+            ..chain()
+        };
+        let (changes, validators) = run(chain, 4).await;
+
+        // The changes of heights 2 and 3 are found with the validators of
+        // heights 4 and 5, when the next blocks are received.
+        assert_eq!(changes, vec![change(2, 0, 5, 2), change(0, 10, 0, 3)]);
+        assert_eq!(validators.total_power(), 15);
+    }
+}
@@ -0,0 +1,495 @@
+//! Estimation of how the timeouts of consensus behave on a network, from the
+//! `NewRound`, `Vote` and `TimeoutPropose` events of a node.
+//!
+//! A [`RoundAnalyzer`] observes the events of the subscriptions to its
+//! [`queries`](RoundAnalyzer::queries), and summarizes them in a
+//! [`RoundReport`]: how many rounds the heights took to be decided, how often
+//! the node timed out waiting for the proposal of a round, and how long the
+//! proposals took to be prevoted on, from which it recommends whether to
+//! change the `timeout_propose` of the configuration of the nodes.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use futures::{stream::select_all, StreamExt};
+//! use tendermint_rpc::{client::rounds::RoundAnalyzer, SubscriptionClient, WebSocketClient};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (client, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!
+//!     let mut subscriptions = Vec::new();
+//!     for query in RoundAnalyzer::queries() {
+//!         subscriptions.push(client.subscribe(query).await.unwrap());
+//!     }
+//!     let mut events = select_all(subscriptions).take(10_000);
+//!
+//!     let mut analyzer = RoundAnalyzer::new(Duration::from_secs(3));
+//!     while let Some(event) = events.next().await {
+//!         analyzer.observe(&event.unwrap());
+//!     }
+//!     println!("{:?}", analyzer.report());
+//! }
+//! ```
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::time::Duration;
+use std::time::Instant;
+
+use tendermint::{
+    block::{Height, Round},
+    vote,
+};
+
+use crate::{
+    alloc_prelude::*,
+    event::{Event, EventData},
+    query::{EventType, Query},
+};
+
+/// The number of heights an analyzer remembers by default.
+pub const DEFAULT_WINDOW: usize = 1000;
+
+/// The number of rounds an analyzer must have measured the latency of the
+/// proposal of to recommend anything.
+pub const MIN_SAMPLES: usize = 20;
+
+/// Observes the consensus events of a node, and estimates how its timeouts
+/// behave.
+///
+/// Only the heights which are over, i.e. those below the latest height
+/// observed, are taken into account, and only the latest heights are
+/// remembered (see [`RoundAnalyzer::window`]).
+#[derive(Clone, Debug)]
+pub struct RoundAnalyzer {
+    timeout_propose: Duration,
+    window: usize,
+    heights: VecDeque<(Height, BTreeMap<Round, RoundObservation>)>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct RoundObservation {
+    started_at: Option<Instant>,
+    first_prevote_at: Option<Instant>,
+    timed_out: bool,
+}
+
+impl RoundAnalyzer {
+    /// Create an analyzer of the rounds of the nodes configured with the
+    /// given `timeout_propose`.
+    pub fn new(timeout_propose: Duration) -> Self {
+        Self {
+            timeout_propose,
+            window: DEFAULT_WINDOW,
+            heights: VecDeque::new(),
+        }
+    }
+
+    /// Only remember the given number of latest heights (see
+    /// [`DEFAULT_WINDOW`]).
+    #[must_use]
+    pub fn window(mut self, heights: usize) -> Self {
+        self.window = heights.max(1);
+        self
+    }
+
+    /// The queries of the events the analyzer observes.
+    pub fn queries() -> [Query; 3] {
+        [
+            Query::from(EventType::NewRound),
+            Query::from(EventType::Vote),
+            Query::from(EventType::TimeoutPropose),
+        ]
+    }
+
+    /// Observe the given event, as received now.
+    ///
+    /// The events which are not consensus events are ignored.
+    pub fn observe(&mut self, event: &Event) {
+        self.observe_at(event, Instant::now())
+    }
+
+    /// Observe the given event, as received at the given instant.
+    pub fn observe_at(&mut self, event: &Event, at: Instant) {
+        match &event.data {
+            EventData::NewRound { height, round, .. } => {
+                if let Some(observation) = self.round(*height, *round) {
+                    observation.started_at.get_or_insert(at);
+                }
+            },
+            EventData::Vote { vote } => {
+                if let Some(observation) = self.round(vote.height, vote.round) {
+                    if vote.vote_type == vote::Type::Prevote {
+                        observation.first_prevote_at.get_or_insert(at);
+                    }
+                }
+            },
+            EventData::RoundState { height, round, .. }
+                if event.event_type() == Some(EventType::TimeoutPropose) =>
+            {
+                if let Some(observation) = self.round(*height, *round) {
+                    observation.timed_out = true;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Summarize the heights which are over.
+    pub fn report(&self) -> RoundReport {
+        let mut report = RoundReport::default();
+        let mut latencies = Vec::new();
+
+        // The latest height is not over yet.
+        let over = self.heights.len().saturating_sub(1);
+        for (_, rounds) in self.heights.iter().take(over) {
+            // The round in which the height was decided is the last one.
+            let count = rounds
+                .keys()
+                .next_back()
+                .map_or(0, |round| round.value() + 1);
+            *report.rounds_per_height.entry(count).or_default() += 1;
+            report.heights += 1;
+            report.rounds += rounds.len();
+
+            for observation in rounds.values() {
+                if observation.timed_out {
+                    report.timeouts_propose += 1;
+                    continue;
+                }
+                if let (Some(started), Some(prevoted)) =
+                    (observation.started_at, observation.first_prevote_at)
+                {
+                    latencies.push(prevoted.saturating_duration_since(started));
+                }
+            }
+        }
+
+        report.proposal_latency = Latencies::of(latencies);
+        report.recommendation = self.recommend(&report);
+        report
+    }
+
+    // Recommend to increase `timeout_propose` when the proposals which are
+    // received are slow enough to explain the timeouts, and to decrease it
+    // when the proposals are much faster than it and almost never time out.
+    fn recommend(&self, report: &RoundReport) -> Recommendation {
+        let latency = match &report.proposal_latency {
+            Some(latency) if latency.samples >= MIN_SAMPLES => latency,
+            _ => return Recommendation::NotEnoughData,
+        };
+        let timeout_ratio = report.timeout_propose_ratio();
+        let suggested = round_up(latency.p99 * 2);
+
+        if timeout_ratio > 0.05 && latency.p99 > self.timeout_propose / 2 {
+            Recommendation::IncreaseTimeoutPropose { suggested }
+        } else if timeout_ratio <= 0.01 && latency.p99 < self.timeout_propose / 4 {
+            Recommendation::DecreaseTimeoutPropose { suggested }
+        } else {
+            Recommendation::Keep
+        }
+    }
+
+    // The observation of the given round, unless its height was forgotten.
+    fn round(&mut self, height: Height, round: Round) -> Option<&mut RoundObservation> {
+        let index = match self.heights.binary_search_by_key(&height, |(h, _)| *h) {
+            Ok(index) => index,
+            Err(index) => {
+                if index == 0 && self.heights.len() >= self.window {
+                    return None;
+                }
+                self.heights.insert(index, (height, BTreeMap::new()));
+                if self.heights.len() > self.window {
+                    self.heights.pop_front();
+                    index - 1
+                } else {
+                    index
+                }
+            },
+        };
+        Some(self.heights[index].1.entry(round).or_default())
+    }
+}
+
+/// A summary of the rounds observed by a [`RoundAnalyzer`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoundReport {
+    /// The number of heights which are over.
+    pub heights: usize,
+    /// The number of rounds of these heights.
+    pub rounds: usize,
+    /// How many heights were decided after each number of rounds.
+    pub rounds_per_height: BTreeMap<u32, usize>,
+    /// The number of rounds in which the node timed out waiting for the
+    /// proposal.
+    pub timeouts_propose: usize,
+    /// The time from the start of a round to the first prevote of a
+    /// validator, in the rounds which did not time out.
+    pub proposal_latency: Option<Latencies>,
+    /// What to do with `timeout_propose`.
+    pub recommendation: Recommendation,
+}
+
+impl RoundReport {
+    /// The fraction of the rounds which timed out waiting for the proposal.
+    pub fn timeout_propose_ratio(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.timeouts_propose as f64 / self.rounds as f64
+    }
+}
+
+/// The distribution of durations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Latencies {
+    /// The number of durations.
+    pub samples: usize,
+    /// The median duration.
+    pub p50: Duration,
+    /// The 90th percentile of the durations.
+    pub p90: Duration,
+    /// The 99th percentile of the durations.
+    pub p99: Duration,
+    /// The longest duration.
+    pub max: Duration,
+}
+
+impl Latencies {
+    pub(crate) fn of(mut durations: Vec<Duration>) -> Option<Self> {
+        durations.sort_unstable();
+        let max = *durations.last()?;
+        // The nearest-rank percentile.
+        let percentile = |p: usize| durations[(durations.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            samples: durations.len(),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max,
+        })
+    }
+}
+
+/// A recommendation of a [`RoundReport`] about the `timeout_propose` of the
+/// configuration of the nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Recommendation {
+    /// Not enough rounds were observed (see [`MIN_SAMPLES`]).
+    #[default]
+    NotEnoughData,
+    /// `timeout_propose` fits the latency of the proposals.
+    Keep,
+    /// Rounds often time out while the proposals which are received are
+    /// slow: the proposals are likely late rather than missing.
+    IncreaseTimeoutPropose {
+        /// Twice the 99th percentile of the latency of the proposals.
+        suggested: Duration,
+    },
+    /// Rounds almost never time out, and the proposals are much faster than
+    /// `timeout_propose`, which only delays the rounds whose proposer is down.
+    DecreaseTimeoutPropose {
+        /// Twice the 99th percentile of the latency of the proposals.
+        suggested: Duration,
+    },
+}
+
+// Round the given duration up to the next 100 milliseconds.
+fn round_up(duration: Duration) -> Duration {
+    let millis = duration.as_millis().div_ceil(100) * 100;
+    Duration::from_millis(millis.try_into().unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{dialect::v0_37, event::DialectEvent};
+
+    fn event(event_type: &str, data: serde_json::Value) -> Event {
+        let event: DialectEvent<v0_37::Event> = serde_json::from_value(json!({
+            "query": format!("tm.event = '{event_type}'"),
+            "data": data,
+            "events": { "tm.event": [event_type] },
+        }))
+        .unwrap();
+        event.into()
+    }
+
+    fn new_round(height: u64, round: u32) -> Event {
+        event(
+            "NewRound",
+            json!({
+                "type": "tendermint/event/NewRound",
+                "value": {
+                    "height": height.to_string(),
+                    "round": round,
+                    "step": "RoundStepNewRound",
+                    "proposer": {
+                        "address": "9319035301DA526CC78DCF174A47A74F81401291",
+                        "index": "0"
+                    }
+                }
+            }),
+        )
+    }
+
+    fn timeout_propose(height: u64, round: u32) -> Event {
+        event(
+            "TimeoutPropose",
+            json!({
+                "type": "tendermint/event/RoundState",
+                "value": {
+                    "height": height.to_string(),
+                    "round": round,
+                    "step": "RoundStepPropose"
+                }
+            }),
+        )
+    }
+
+    fn prevote(height: u64, round: u32) -> Event {
+        event(
+            "Vote",
+            json!({
+                "type": "tendermint/event/Vote",
+                "value": {
+                    "Vote": {
+                        "type": 1,
+                        "height": height.to_string(),
+                        "round": round,
+                        "block_id": {
+                            "hash": "3A6E54F9A4D4E4D2C7A37B43E6B5E0A9B8E0D0A1C2F3E4D5C6B7A8998877665F",
+                            "parts": {
+                                "total": 1,
+                                "hash": "0DCBB02A8DFB86E78859A24426ED1D9D2A2C9C3D5C6CD1851477B98705564DD8"
+                            }
+                        },
+                        "timestamp": "2022-09-07T22:52:09.078399096Z",
+                        "validator_address": "9319035301DA526CC78DCF174A47A74F81401291",
+                        "validator_index": 8,
+                        "signature": "wjyIJ6WxLl38O0gKAWPmCjP0c3HhTfyNjRfc5LlDzQ4jw/7XHmu4tNbW8NV1C7DeuceLYT2OGAAgf1g1R7QXDw=="
+                    }
+                }
+            }),
+        )
+    }
+
+    // Observe the given number of heights, starting at the given instant,
+    // whose proposals take the given latency, and of which every `retried`
+    // height times out in its first round.
+    fn observe(
+        analyzer: &mut RoundAnalyzer,
+        start: Instant,
+        heights: u64,
+        latency: Duration,
+        retried: u64,
+    ) {
+        let mut now = start;
+        for height in 1..=heights {
+            let mut round = 0;
+            if height % retried == 0 {
+                analyzer.observe_at(&new_round(height, 0), now);
+                now += Duration::from_secs(3);
+                analyzer.observe_at(&timeout_propose(height, 0), now);
+                round = 1;
+            }
+            analyzer.observe_at(&new_round(height, round), now);
+            now += latency;
+            analyzer.observe_at(&prevote(height, round), now);
+            // Votes of other validators come later.
+            analyzer.observe_at(&prevote(height, round), now + Duration::from_millis(10));
+            now += Duration::from_secs(1);
+        }
+    }
+
+    #[test]
+    fn parses_consensus_events() {
+        assert_eq!(
+            new_round(5, 1).data,
+            EventData::NewRound {
+                height: 5_u32.into(),
+                round: 1_u16.into(),
+                step: "RoundStepNewRound".to_string(),
+            }
+        );
+        let timeout = timeout_propose(5, 1);
+        assert_eq!(timeout.event_type(), Some(EventType::TimeoutPropose));
+        match prevote(5, 1).data {
+            EventData::Vote { vote } => {
+                assert_eq!(vote.vote_type, vote::Type::Prevote);
+                assert_eq!(vote.height.value(), 5);
+            },
+            data => panic!("expected a vote, got {data:?}"),
+        }
+    }
+
+    #[test]
+    fn recommends_increasing_timeout_propose() {
+        let mut analyzer = RoundAnalyzer::new(Duration::from_secs(3));
+        observe(
+            &mut analyzer,
+            Instant::now(),
+            41,
+            Duration::from_millis(2_400),
+            4,
+        );
+
+        let report = analyzer.report();
+        // The last height is not over.
+        assert_eq!(report.heights, 40);
+        assert_eq!(report.rounds, 50);
+        assert_eq!(report.rounds_per_height, BTreeMap::from([(1, 30), (2, 10)]));
+        assert_eq!(report.timeouts_propose, 10);
+        assert_eq!(report.timeout_propose_ratio(), 0.2);
+        let latency = report.proposal_latency.unwrap();
+        assert_eq!(latency.samples, 40);
+        assert_eq!(latency.p50, Duration::from_millis(2_400));
+        assert_eq!(
+            report.recommendation,
+            Recommendation::IncreaseTimeoutPropose {
+                suggested: Duration::from_millis(4_800)
+            }
+        );
+    }
+
+    #[test]
+    fn recommends_decreasing_timeout_propose() {
+        let mut analyzer = RoundAnalyzer::new(Duration::from_secs(3));
+        observe(
+            &mut analyzer,
+            Instant::now(),
+            31,
+            Duration::from_millis(240),
+            u64::MAX,
+        );
+
+        let report = analyzer.report();
+        assert_eq!(report.rounds_per_height, BTreeMap::from([(1, 30)]));
+        assert_eq!(
+            report.recommendation,
+            Recommendation::DecreaseTimeoutPropose {
+                suggested: Duration::from_millis(500)
+            }
+        );
+
+        // Too few rounds were observed.
+        let mut analyzer = RoundAnalyzer::new(Duration::from_secs(3)).window(10);
+        observe(
+            &mut analyzer,
+            Instant::now(),
+            31,
+            Duration::from_millis(240),
+            u64::MAX,
+        );
+        let report = analyzer.report();
+        assert_eq!(report.heights, 9);
+        assert_eq!(report.recommendation, Recommendation::NotEnoughData);
+    }
+}
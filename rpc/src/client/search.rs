@@ -0,0 +1,224 @@
+//! Streams walking through the pages of the results of `/tx_search` and
+//! `/block_search`.
+
+use alloc::collections::{BTreeSet, VecDeque};
+use core::future::Future;
+
+use futures::stream::{self, BoxStream, FuturesOrdered, StreamExt};
+
+use crate::{alloc_prelude::*, Error};
+
+/// The maximum number of results per page supported by Tendermint.
+pub const MAX_PER_PAGE: u8 = 100;
+
+/// How the results of a search are fetched by
+/// [`Client::tx_search_stream_with`](crate::Client::tx_search_stream_with) and
+/// [`Client::block_search_stream_with`](crate::Client::block_search_stream_with).
+#[derive(Clone, Copy, Debug)]
+pub struct SearchStreamConfig {
+    /// The number of results per page, at most [`MAX_PER_PAGE`].
+    pub per_page: u8,
+    /// The maximum number of pages requested at the same time. The results
+    /// are still streamed in order.
+    pub concurrency: usize,
+    /// Whether to include proofs of the transactions in the results (for
+    /// transaction searches only).
+    pub prove: bool,
+}
+
+impl Default for SearchStreamConfig {
+    fn default() -> Self {
+        Self {
+            per_page: MAX_PER_PAGE,
+            concurrency: 1,
+            prove: false,
+        }
+    }
+}
+
+// A page of results, along with the total number of results at the time it
+// was fetched.
+pub(crate) struct Page<T> {
+    pub items: Vec<T>,
+    pub total_count: u32,
+}
+
+struct State<T, K, F, Fut: Future> {
+    fetch: F,
+    key: fn(&T) -> K,
+    per_page: u32,
+    concurrency: usize,
+    next_page: u32,
+    // Unknown until the first page is received.
+    page_count: Option<u32>,
+    in_flight: FuturesOrdered<Fut>,
+    ready: VecDeque<T>,
+    previous_keys: BTreeSet<K>,
+    done: bool,
+}
+
+/// Stream the results of all the pages fetched with `fetch`, given the
+/// number of a page (starting from 1).
+///
+/// The number of pages is recomputed from the total number of results of
+/// every page received, so that the results added to the last pages while
+/// walking through them are streamed too. As new results shift the following
+/// pages when the search is in descending order, the results of a page which
+/// were already part of the previous page, as identified by `key`, are
+/// skipped.
+pub(crate) fn paginate<'a, T, K, F, Fut>(
+    config: SearchStreamConfig,
+    fetch: F,
+    key: fn(&T) -> K,
+) -> BoxStream<'a, Result<T, Error>>
+where
+    T: Send + 'a,
+    K: Ord + Send + 'a,
+    F: FnMut(u32) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<Page<T>, Error>> + Send + 'a,
+{
+    let state = State {
+        fetch,
+        key,
+        per_page: config.per_page.clamp(1, MAX_PER_PAGE).into(),
+        concurrency: config.concurrency.max(1),
+        next_page: 1,
+        page_count: None,
+        in_flight: FuturesOrdered::new(),
+        ready: VecDeque::new(),
+        previous_keys: BTreeSet::new(),
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.ready.pop_front() {
+                return Some((Ok(item), state));
+            }
+            if state.done {
+                return None;
+            }
+
+            while state.in_flight.len() < state.concurrency
+                && state
+                    .page_count
+                    .map_or(state.next_page == 1, |count| state.next_page <= count)
+            {
+                state.in_flight.push((state.fetch)(state.next_page));
+                state.next_page += 1;
+            }
+
+            match state.in_flight.next().await {
+                None => return None,
+                Some(Err(e)) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                },
+                Some(Ok(page)) => {
+                    state.page_count = Some(page.total_count.div_ceil(state.per_page));
+                    let keys: BTreeSet<K> = page.items.iter().map(state.key).collect();
+                    let previous_keys = core::mem::replace(&mut state.previous_keys, keys);
+                    state.ready.extend(
+                        page.items
+                            .into_iter()
+                            .filter(|item| !previous_keys.contains(&(state.key)(item))),
+                    );
+                },
+            }
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    // Serve the given results, whose number may change with every request.
+    fn pages(
+        results: Arc<Mutex<Vec<u32>>>,
+        per_page: u32,
+    ) -> impl FnMut(u32) -> futures::future::Ready<Result<Page<u32>, Error>> {
+        move |page| {
+            let results = results.lock().unwrap();
+            let start = ((page - 1) * per_page) as usize;
+            let end = (start + per_page as usize).min(results.len());
+            futures::future::ready(Ok(Page {
+                items: results[start.min(end)..end].to_vec(),
+                total_count: results.len() as u32,
+            }))
+        }
+    }
+
+    fn config(per_page: u8, concurrency: usize) -> SearchStreamConfig {
+        SearchStreamConfig {
+            per_page,
+            concurrency,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn walks_all_pages() {
+        for concurrency in [1, 2, 10] {
+            let results = Arc::new(Mutex::new((1..=7).collect()));
+            let items: Vec<_> = paginate(config(3, concurrency), pages(results, 3), |&i| i)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+            assert_eq!(items, (1..=7).collect::<Vec<_>>());
+        }
+
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let items: Vec<_> = paginate(config(3, 1), pages(results, 3), |&i| i)
+            .collect()
+            .await;
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn follows_new_results() {
+        // Results in descending order, with new ones added on top after the
+        // first page is fetched.
+        let results = Arc::new(Mutex::new(vec![6, 5, 4, 3, 2, 1]));
+        let fetch = {
+            let results = results.clone();
+            let mut fetch = pages(results.clone(), 3);
+            move |page| {
+                let response = fetch(page);
+                if page == 1 {
+                    results.lock().unwrap().splice(0..0, [8, 7]);
+                }
+                response
+            }
+        };
+        let items: Vec<_> = paginate(config(3, 1), fetch, |&i| i)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        // The new results are not streamed, but neither are the results
+        // shifted to the next page streamed twice.
+        assert_eq!(items, vec![6, 5, 4, 3, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn stops_on_error() {
+        let mut served = pages(Arc::new(Mutex::new((1..=7).collect())), 3);
+        let fetch = move |page| {
+            let response = served(page);
+            async move {
+                if page == 2 {
+                    Err(Error::server("unavailable".to_string()))
+                } else {
+                    response.await
+                }
+            }
+        };
+        let items: Vec<_> = paginate(config(3, 1), fetch, |&i| i).collect().await;
+        assert_eq!(items.len(), 4);
+        assert_eq!(*items[2].as_ref().unwrap(), 3);
+        assert!(items[3].is_err());
+    }
+}
@@ -0,0 +1,916 @@
+//! Bounded-memory buffering of subscription events, spilling to disk.
+//!
+//! Event bursts, such as those produced during chain upgrades, can outpace
+//! the consumers of a [`Subscription`]. Since subscriptions are backed by
+//! unbounded channels, the pending events would otherwise accumulate in
+//! memory. A [`SpillingSubscription`] instead keeps at most a configured
+//! number of pending events, and optionally of bytes, in memory and appends
+//! the rest to segment files on disk, from which they are read back in order
+//! once the consumer catches up.
+//!
+//! The spill directory is scratch space: segment files are removed once they
+//! have been consumed, as well as when the buffer is dropped, and are not
+//! meant to survive a restart. The segment files of a [`SpillingSubscription`]
+//! are written and read on the blocking threads of the Tokio runtime, so that
+//! disk I/O never blocks the tasks polling the subscription and its driver.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{
+//!     client::spill::{SpillConfig, SpillingSubscription},
+//!     query::EventType,
+//!     SubscriptionClient, WebSocketClient,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (client, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!
+//!     let subscription = client.subscribe(EventType::Tx.into()).await.unwrap();
+//!     let config = SpillConfig::new("/tmp/tx-events").memory_capacity(10_000);
+//!     let (mut subscription, spill_driver) =
+//!         SpillingSubscription::new(subscription, config).unwrap();
+//!     tokio::spawn(async move { spill_driver.run().await });
+//!
+//!     while let Some(event) = subscription.next().await {
+//!         println!("{:?}", event.unwrap());
+//!     }
+//! }
+//! ```
+
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufReader, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use futures::{
+    task::{AtomicWaker, Context, Poll},
+    Stream, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{
+    alloc_prelude::*,
+    client::Subscription,
+    dialect::v0_37,
+    event::{DialectEvent, Event},
+    query::Query,
+    Error,
+};
+
+/// Default number of items kept in memory before spilling to disk.
+pub const DEFAULT_MEMORY_CAPACITY: usize = 1024;
+
+/// Default size, in bytes, after which a new segment file is started.
+pub const DEFAULT_SEGMENT_SIZE: u64 = 16 * 1024 * 1024;
+
+const SEGMENT_PREFIX: &str = "spill-";
+const SEGMENT_EXTENSION: &str = "seg";
+
+/// Configuration of a [`SpillBuffer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpillConfig {
+    /// Directory holding the segment files. It is created if it does not
+    /// exist, and must not be shared with another buffer.
+    pub dir: PathBuf,
+    /// Maximum number of items held in memory before spilling to disk.
+    pub memory_capacity: usize,
+    /// Maximum approximate size, in bytes, of the items held in memory before
+    /// spilling to disk, if any. The size of an item is the size of its JSON
+    /// serialization.
+    pub memory_budget: Option<usize>,
+    /// Size, in bytes, after which a new segment file is started.
+    pub segment_size: u64,
+}
+
+impl SpillConfig {
+    /// Default configuration, spilling to the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            memory_capacity: DEFAULT_MEMORY_CAPACITY,
+            memory_budget: None,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+        }
+    }
+
+    /// Set the maximum number of items held in memory.
+    pub fn memory_capacity(mut self, memory_capacity: usize) -> Self {
+        self.memory_capacity = memory_capacity;
+        self
+    }
+
+    /// Set the maximum approximate size, in bytes, of the items held in
+    /// memory.
+    pub fn memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self
+    }
+
+    /// Set the size after which a new segment file is started.
+    pub fn segment_size(mut self, segment_size: u64) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+}
+
+/// Index entry of a segment file.
+#[derive(Debug)]
+struct Segment {
+    path: PathBuf,
+    /// Number of bytes written to the segment.
+    size: u64,
+    /// Number of records written to the segment.
+    written: usize,
+    /// Number of records read back from the segment.
+    read: usize,
+}
+
+/// Reads the records of a segment file.
+#[derive(Debug)]
+struct SegmentReader {
+    path: PathBuf,
+    reader: BufReader<File>,
+}
+
+/// A FIFO queue holding up to a configured number of items in memory, and
+/// spilling the remaining ones to append-only segment files.
+///
+/// Items are stored on disk as length-prefixed JSON records. Segments are
+/// indexed in memory, and deleted once all of their records have been read.
+#[derive(Debug)]
+pub struct SpillBuffer<T> {
+    config: SpillConfig,
+    // The items held in memory, with their approximate size.
+    memory: VecDeque<(T, usize)>,
+    memory_bytes: usize,
+    // Oldest segment first. Only the last one is written to.
+    segments: VecDeque<Segment>,
+    next_segment: u64,
+    writer: Option<File>,
+    // Reads records from the first segment with records left to read.
+    reader: Option<SegmentReader>,
+    spilled: usize,
+}
+
+impl<T> SpillBuffer<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    /// Create an empty buffer, removing any segment file left over in the
+    /// spill directory.
+    pub fn new(config: SpillConfig) -> Result<Self, Error> {
+        prepare_dir(&config.dir)?;
+        Ok(Self {
+            config,
+            memory: VecDeque::new(),
+            memory_bytes: 0,
+            segments: VecDeque::new(),
+            next_segment: 0,
+            writer: None,
+            reader: None,
+            spilled: 0,
+        })
+    }
+
+    /// Number of items in the buffer.
+    pub fn len(&self) -> usize {
+        self.memory.len() + self.spilled
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of items currently stored on disk.
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    /// Approximate size, in bytes, of the items currently held in memory.
+    pub fn memory_bytes(&self) -> usize {
+        self.memory_bytes
+    }
+
+    /// Append an item to the buffer.
+    pub fn push(&mut self, item: T) -> Result<(), Error> {
+        let size = json_size(&item)?;
+        let item = match self.push_memory(item, size) {
+            Ok(()) => return Ok(()),
+            Err(item) => item,
+        };
+
+        let record = encode_record(&item)?;
+        let len = record.len() as u64;
+        if let Some(path) = self.segment_for(len, self.writer.is_some()) {
+            self.writer = Some(create_segment(&path)?);
+            self.add_segment(path);
+        }
+        let writer = self.writer.as_mut().expect("a segment is open");
+        writer.write_all(&record).map_err(Error::io)?;
+        self.record_written(len);
+        Ok(())
+    }
+
+    /// Remove the oldest item from the buffer.
+    pub fn pop(&mut self) -> Result<Option<T>, Error> {
+        if let Some(item) = self.pop_memory() {
+            return Ok(Some(item));
+        }
+        if self.spilled == 0 {
+            return Ok(None);
+        }
+
+        let path = self.unread_segment().to_owned();
+        let item = read_record(&mut self.reader, &path)?;
+        self.record_read(&path);
+
+        let read_segments = self.take_read_segments(false);
+        if self.segments.is_empty() {
+            self.writer = None;
+        }
+        remove_segments(&mut self.reader, read_segments)?;
+        Ok(Some(item))
+    }
+}
+
+// The bookkeeping of the buffer, apart from the I/O on the segment files, so
+// that the latter can be carried out without holding a lock on the buffer.
+impl<T> SpillBuffer<T> {
+    /// Keep an item of the given size in memory if it fits, and hand it back
+    /// to be spilled otherwise.
+    fn push_memory(&mut self, item: T, size: usize) -> Result<(), T> {
+        // Once anything has been spilled, new items have to go to disk as
+        // well so that they are read back in order.
+        let fits = self.memory.len() < self.config.memory_capacity
+            && self.memory_bytes + size <= self.config.memory_budget.unwrap_or(usize::MAX);
+        if self.spilled > 0 || !fits {
+            return Err(item);
+        }
+        self.memory.push_back((item, size));
+        self.memory_bytes += size;
+        Ok(())
+    }
+
+    fn pop_memory(&mut self) -> Option<T> {
+        let (item, size) = self.memory.pop_front()?;
+        self.memory_bytes -= size;
+        Some(item)
+    }
+
+    /// The path of the segment to start before appending a record of the
+    /// given length, if the last one is not open for writing, would exceed
+    /// the configured segment size, or has been read entirely, so that it
+    /// can be removed.
+    fn segment_for(&mut self, record_len: u64, writer_open: bool) -> Option<PathBuf> {
+        let full = match self.segments.back() {
+            Some(segment) => {
+                segment.read == segment.written
+                    || (segment.size > 0 && segment.size + record_len > self.config.segment_size)
+            },
+            None => true,
+        };
+        (full || !writer_open).then(|| {
+            let path = segment_path(&self.config.dir, self.next_segment);
+            self.next_segment += 1;
+            path
+        })
+    }
+
+    fn add_segment(&mut self, path: PathBuf) {
+        self.segments.push_back(Segment {
+            path,
+            size: 0,
+            written: 0,
+            read: 0,
+        });
+    }
+
+    /// Account for a record of the given length appended to the last segment.
+    fn record_written(&mut self, record_len: u64) {
+        let segment = self.segments.back_mut().expect("a segment is open");
+        segment.size += record_len;
+        segment.written += 1;
+        self.spilled += 1;
+    }
+
+    /// The path of the first segment with records left to read.
+    fn unread_segment(&self) -> &Path {
+        &self
+            .segments
+            .iter()
+            .find(|segment| segment.read < segment.written)
+            .expect("spilled items are in a segment")
+            .path
+    }
+
+    /// Account for a record read from the given segment.
+    fn record_read(&mut self, path: &Path) {
+        self.spilled -= 1;
+        if let Some(segment) = self.segments.iter_mut().find(|s| s.path == path) {
+            segment.read += 1;
+        }
+    }
+
+    /// Remove the segments read entirely from the index, returning their
+    /// paths. The last segment is kept if `keep_last` is set, e.g. as it may
+    /// still be appended to.
+    fn take_read_segments(&mut self, keep_last: bool) -> Vec<PathBuf> {
+        let keep = usize::from(keep_last);
+        let mut read = vec![];
+        while self.segments.len() > keep && self.segments[0].read == self.segments[0].written {
+            read.push(self.segments.pop_front().unwrap().path);
+        }
+        read
+    }
+}
+
+impl<T> Drop for SpillBuffer<T> {
+    fn drop(&mut self) {
+        self.writer = None;
+        self.reader = None;
+        for segment in self.segments.drain(..) {
+            let _ = fs::remove_file(segment.path);
+        }
+    }
+}
+
+/// Counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Create the given spill directory if needed, removing any segment file left
+/// over in it.
+fn prepare_dir(dir: &Path) -> Result<(), Error> {
+    fs::create_dir_all(dir).map_err(Error::io)?;
+    for entry in fs::read_dir(dir).map_err(Error::io)? {
+        let path = entry.map_err(Error::io)?.path();
+        if is_segment_file(&path) {
+            fs::remove_file(&path).map_err(Error::io)?;
+        }
+    }
+    Ok(())
+}
+
+/// The size of the JSON serialization of an item.
+fn json_size<T: Serialize>(item: &T) -> Result<usize, Error> {
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, item).map_err(Error::serde)?;
+    Ok(counter.0)
+}
+
+/// Serialize an item as a length-prefixed JSON record.
+fn encode_record<T: Serialize>(item: &T) -> Result<Vec<u8>, Error> {
+    let mut record = vec![0; 4];
+    serde_json::to_writer(&mut record, item).map_err(Error::serde)?;
+    let len = u32::try_from(record.len() - 4).map_err(Error::out_of_range)?;
+    record[..4].copy_from_slice(&len.to_le_bytes());
+    Ok(record)
+}
+
+/// Read the next record written by [`encode_record`].
+fn decode_record<T: DeserializeOwned>(reader: &mut impl Read) -> Result<T, Error> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len).map_err(Error::io)?;
+    let mut record = vec![0; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut record).map_err(Error::io)?;
+    serde_json::from_slice(&record).map_err(Error::serde)
+}
+
+/// Read the next record of the given segment, opening it unless it is the
+/// one being read already.
+fn read_record<T: DeserializeOwned>(
+    reader: &mut Option<SegmentReader>,
+    path: &Path,
+) -> Result<T, Error> {
+    if !matches!(reader, Some(reader) if reader.path == path) {
+        let file = File::open(path).map_err(Error::io)?;
+        *reader = Some(SegmentReader {
+            path: path.to_owned(),
+            reader: BufReader::new(file),
+        });
+    }
+    decode_record(&mut reader.as_mut().unwrap().reader)
+}
+
+/// Remove the given segment files, closing the reader if it reads one of them.
+fn remove_segments(reader: &mut Option<SegmentReader>, paths: Vec<PathBuf>) -> Result<(), Error> {
+    for path in paths {
+        if matches!(reader, Some(reader) if reader.path == path) {
+            *reader = None;
+        }
+        fs::remove_file(path).map_err(Error::io)?;
+    }
+    Ok(())
+}
+
+fn segment_path(dir: &Path, number: u64) -> PathBuf {
+    dir.join(format!("{SEGMENT_PREFIX}{number:020}.{SEGMENT_EXTENSION}"))
+}
+
+fn create_segment(path: &Path) -> Result<File, Error> {
+    OpenOptions::new()
+        .create_new(true)
+        .append(true)
+        .open(path)
+        .map_err(Error::io)
+}
+
+fn is_segment_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|name| name.to_str());
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    matches!(name, Some(name) if name.starts_with(SEGMENT_PREFIX))
+        && extension == Some(SEGMENT_EXTENSION)
+}
+
+/// A buffered subscription item. Errors cannot be serialized, so they are
+/// kept in memory and only their position is recorded in the buffer.
+#[derive(Debug, Serialize, Deserialize)]
+enum Record {
+    Event(DialectEvent<v0_37::Event>),
+    Error(u64),
+}
+
+/// The buffer of a [`SpillingSubscription`]. Its segment files are written
+/// by the [`SpillDriver`] and read by the subscription, which hold the file
+/// handles, without holding the lock.
+#[derive(Debug)]
+struct State {
+    buffer: SpillBuffer<Record>,
+    errors: BTreeMap<u64, Error>,
+    next_error: u64,
+    // Set once the driver has stopped, with the error that stopped it, if any.
+    finished: Option<Option<Error>>,
+}
+
+impl State {
+    fn item(&mut self, record: Record) -> Option<Result<Event, Error>> {
+        match record {
+            Record::Event(event) => Some(Ok(event.into())),
+            Record::Error(id) => self.errors.remove(&id).map(Err),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    waker: AtomicWaker,
+    closed: AtomicBool,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The reader of the segments along with the next record read from them.
+type PendingRead = JoinHandle<(Option<SegmentReader>, Result<Record, Error>)>;
+
+/// Read the next spilled record, after removing the segments read entirely
+/// but the last one, which the driver may still append to.
+fn read_spilled(shared: &Shared, reader: &mut Option<SegmentReader>) -> Result<Record, Error> {
+    let (read_segments, path) = {
+        let mut state = shared.lock();
+        let read_segments = state.buffer.take_read_segments(true);
+        (read_segments, state.buffer.unread_segment().to_owned())
+    };
+    remove_segments(reader, read_segments)?;
+    let record = read_record(reader, &path)?;
+    shared.lock().buffer.record_read(&path);
+    Ok(record)
+}
+
+enum Next {
+    Ready(Option<Result<Event, Error>>),
+    Spilled,
+    Pending,
+}
+
+/// A [`Subscription`] whose pending events are buffered in a [`SpillBuffer`].
+///
+/// Events are moved from the underlying subscription to the buffer by the
+/// accompanying [`SpillDriver`], which must be running for events to be
+/// produced. Both must be used within a Tokio runtime.
+#[derive(Debug)]
+pub struct SpillingSubscription {
+    id: String,
+    query: Query,
+    // The read of the next spilled event, if in progress.
+    read: Option<PendingRead>,
+    reader: Option<SegmentReader>,
+    shared: Arc<Shared>,
+}
+
+impl SpillingSubscription {
+    /// Buffer the events of the given subscription according to `config`.
+    ///
+    /// The returned driver has to be spawned onto the Tokio runtime.
+    pub fn new(
+        subscription: Subscription,
+        config: SpillConfig,
+    ) -> Result<(Self, SpillDriver), Error> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                buffer: SpillBuffer::new(config)?,
+                errors: BTreeMap::new(),
+                next_error: 0,
+                finished: None,
+            }),
+            waker: AtomicWaker::new(),
+            closed: AtomicBool::new(false),
+        });
+        let this = Self {
+            id: subscription.id().to_owned(),
+            query: subscription.query().clone(),
+            read: None,
+            reader: None,
+            shared: shared.clone(),
+        };
+        let driver = SpillDriver {
+            subscription,
+            shared,
+        };
+        Ok((this, driver))
+    }
+
+    /// Return this subscription's ID for informational purposes.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Number of events waiting to be consumed.
+    pub fn pending(&self) -> usize {
+        self.shared.lock().buffer.len()
+    }
+
+    /// Number of pending events currently stored on disk.
+    pub fn spilled(&self) -> usize {
+        self.shared.lock().buffer.spilled()
+    }
+
+    /// Approximate size, in bytes, of the pending events currently held in
+    /// memory.
+    pub fn memory_bytes(&self) -> usize {
+        self.shared.lock().buffer.memory_bytes()
+    }
+
+    fn try_next(&self) -> Next {
+        let mut state = self.shared.lock();
+        if let Some(record) = state.buffer.pop_memory() {
+            return Next::Ready(state.item(record));
+        }
+        if state.buffer.spilled() > 0 {
+            return Next::Spilled;
+        }
+        match state.finished.as_mut() {
+            Some(failure) => Next::Ready(failure.take().map(Err)),
+            None => Next::Pending,
+        }
+    }
+
+    fn poll_read(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Event, Error>>> {
+        let read = self.read.as_mut().expect("a read is in progress");
+        let result = match Pin::new(read).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.read = None;
+        let record = match result {
+            Ok((reader, record)) => {
+                self.reader = reader;
+                record
+            },
+            Err(e) => Err(Error::client_internal(e.to_string())),
+        };
+        Poll::Ready(match record {
+            Ok(record) => self.shared.lock().item(record),
+            Err(e) => Some(Err(e)),
+        })
+    }
+}
+
+impl Stream for SpillingSubscription {
+    type Item = Result<Event, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut registered = false;
+        loop {
+            if this.read.is_some() {
+                return this.poll_read(cx);
+            }
+            match this.try_next() {
+                Next::Ready(item) => return Poll::Ready(item),
+                Next::Spilled => {
+                    let shared = this.shared.clone();
+                    let mut reader = this.reader.take();
+                    this.read = Some(tokio::task::spawn_blocking(move || {
+                        let record = read_spilled(&shared, &mut reader);
+                        (reader, record)
+                    }));
+                },
+                Next::Pending if registered => return Poll::Pending,
+                Next::Pending => {
+                    this.shared.waker.register(cx.waker());
+                    // Check again in case the driver pushed an item or
+                    // stopped before the waker was registered.
+                    registered = true;
+                },
+            }
+        }
+    }
+}
+
+impl Drop for SpillingSubscription {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+    }
+}
+
+/// Moves events from a [`Subscription`] to the buffer of a
+/// [`SpillingSubscription`].
+#[derive(Debug)]
+pub struct SpillDriver {
+    subscription: Subscription,
+    shared: Arc<Shared>,
+}
+
+impl SpillDriver {
+    /// Run the driver until the underlying subscription terminates, the
+    /// [`SpillingSubscription`] is dropped, or an event could not be buffered.
+    ///
+    /// Once all buffered events have been consumed, a buffering failure is
+    /// reported by the [`SpillingSubscription`] as its last item.
+    pub async fn run(mut self) -> Result<(), Error> {
+        let mut writer = None;
+        let mut result = Ok(());
+        while let Some(item) = self.subscription.next().await {
+            if self.shared.closed.load(Ordering::Acquire) {
+                break;
+            }
+            let pushed = self.push(item, &mut writer).await;
+            self.shared.waker.wake();
+            if let Err(e) = pushed {
+                result = Err(e);
+                break;
+            }
+        }
+        drop(writer);
+
+        self.shared.lock().finished = Some(result.clone().err());
+        self.shared.waker.wake();
+        result
+    }
+
+    /// Buffer an item in memory if it fits, and otherwise append it to the
+    /// last segment file, written to on a blocking thread.
+    async fn push(
+        &self,
+        item: Result<Event, Error>,
+        writer: &mut Option<File>,
+    ) -> Result<(), Error> {
+        let (record, new_segment) = {
+            let mut state = self.shared.lock();
+            let record = match item {
+                Ok(event) => Record::Event(event.into()),
+                Err(e) => {
+                    let id = state.next_error;
+                    state.next_error += 1;
+                    state.errors.insert(id, e);
+                    Record::Error(id)
+                },
+            };
+
+            let size = json_size(&record)?;
+            let record = match state.buffer.push_memory(record, size) {
+                Ok(()) => return Ok(()),
+                Err(record) => encode_record(&record)?,
+            };
+            let new_segment = state
+                .buffer
+                .segment_for(record.len() as u64, writer.is_some());
+            (record, new_segment)
+        };
+
+        let len = record.len() as u64;
+        let mut file = writer.take();
+        let path = new_segment.clone();
+        let (file, written) = tokio::task::spawn_blocking(move || {
+            if let Some(path) = path {
+                file = None;
+                match create_segment(&path) {
+                    Ok(created) => file = Some(created),
+                    Err(e) => return (file, Err(e)),
+                }
+            }
+            let written = file
+                .as_mut()
+                .expect("a segment is open")
+                .write_all(&record)
+                .map_err(Error::io);
+            (file, written)
+        })
+        .await
+        .map_err(|e| Error::client_internal(e.to_string()))?;
+
+        let mut state = self.shared.lock();
+        if let (Some(path), Some(_)) = (new_segment, &file) {
+            state.buffer.add_segment(path);
+        }
+        *writer = file;
+        written?;
+        state.buffer.record_written(len);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::join;
+    use tendermint::{abci, block::Height};
+    use tokio_test::block_on;
+
+    use super::*;
+    use crate::{client::sync::unbounded, error::ErrorDetail, event::EventData, query::EventType};
+
+    fn spill_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "tendermint-rpc-spill-{name}-{}",
+            crate::utils::uuid_str()
+        ))
+    }
+
+    fn segment_files(config: &SpillConfig) -> usize {
+        fs::read_dir(&config.dir)
+            .unwrap()
+            .filter(|entry| is_segment_file(&entry.as_ref().unwrap().path()))
+            .count()
+    }
+
+    #[test]
+    fn keeps_order_across_memory_and_disk() {
+        let config = SpillConfig::new(spill_dir("order"))
+            .memory_capacity(3)
+            .segment_size(8);
+        let mut buffer = SpillBuffer::new(config.clone()).unwrap();
+
+        for i in 0..10_u32 {
+            buffer.push(i).unwrap();
+        }
+        assert_eq!(buffer.len(), 10);
+        assert_eq!(buffer.spilled(), 7);
+        assert!(segment_files(&config) > 1);
+
+        // Items pushed while some are on disk must not jump the queue, even
+        // once there is room in memory again.
+        for i in 0..5 {
+            assert_eq!(buffer.pop().unwrap(), Some(i));
+        }
+        buffer.push(10).unwrap();
+        let rest: Vec<u32> = core::iter::from_fn(|| buffer.pop().unwrap()).collect();
+        assert_eq!(rest, (5..=10).collect::<Vec<_>>());
+
+        assert!(buffer.is_empty());
+        assert_eq!(segment_files(&config), 0);
+
+        // The buffer can be reused once drained.
+        buffer.push(11).unwrap();
+        assert_eq!(buffer.pop().unwrap(), Some(11));
+        assert_eq!(buffer.pop().unwrap(), None);
+        drop(buffer);
+        fs::remove_dir(&config.dir).unwrap();
+    }
+
+    #[test]
+    fn spills_beyond_the_memory_budget() {
+        // Each item is serialized to 5 bytes, e.g. `"abc"`.
+        let config = SpillConfig::new(spill_dir("budget")).memory_budget(12);
+        let mut buffer = SpillBuffer::new(config.clone()).unwrap();
+
+        for item in ["abc", "def", "ghi"] {
+            buffer.push(item.to_owned()).unwrap();
+        }
+        assert_eq!(buffer.memory_bytes(), 10);
+        assert_eq!(buffer.spilled(), 1);
+
+        assert_eq!(buffer.pop().unwrap().as_deref(), Some("abc"));
+        assert_eq!(buffer.memory_bytes(), 5);
+        assert_eq!(buffer.pop().unwrap().as_deref(), Some("def"));
+        assert_eq!(buffer.pop().unwrap().as_deref(), Some("ghi"));
+        assert_eq!(buffer.memory_bytes(), 0);
+        drop(buffer);
+        fs::remove_dir_all(&config.dir).unwrap();
+    }
+
+    #[test]
+    fn cleans_up_segments() {
+        let config = SpillConfig::new(spill_dir("cleanup")).memory_capacity(0);
+        fs::create_dir_all(&config.dir).unwrap();
+        File::create(config.dir.join("spill-00000000000000000007.seg")).unwrap();
+        File::create(config.dir.join("unrelated.txt")).unwrap();
+
+        let mut buffer = SpillBuffer::new(config.clone()).unwrap();
+        assert_eq!(segment_files(&config), 0);
+
+        buffer.push("event".to_owned()).unwrap();
+        assert_eq!(segment_files(&config), 1);
+        drop(buffer);
+        assert_eq!(segment_files(&config), 0);
+        assert!(config.dir.join("unrelated.txt").exists());
+        fs::remove_dir_all(&config.dir).unwrap();
+    }
+
+    fn new_block_event(height: u32) -> Event {
+        Event {
+            query: EventType::NewBlock.to_string(),
+            data: EventData::NewBlock {
+                block: None,
+                result_begin_block: None,
+                result_end_block: Some(abci::response::EndBlock {
+                    validator_updates: vec![],
+                    consensus_param_updates: None,
+                    events: vec![abci::Event::new(
+                        "height",
+                        [("height", Height::from(height).to_string())],
+                    )],
+                }),
+            },
+            events: None,
+        }
+    }
+
+    #[test]
+    fn subscription_events_are_spilled() {
+        let (tx, rx) = unbounded();
+        let subscription = Subscription::new("id".to_owned(), EventType::NewBlock.into(), rx);
+        let config = SpillConfig::new(spill_dir("subscription"))
+            .memory_capacity(2)
+            .segment_size(1);
+        let dir = config.dir.clone();
+        let (mut subscription, driver) = SpillingSubscription::new(subscription, config).unwrap();
+
+        let events: Vec<Event> = (1..=5).map(new_block_event).collect();
+        for event in &events {
+            tx.send(Ok(event.clone())).unwrap();
+        }
+        tx.send(Err(Error::client_internal("boom".to_owned())))
+            .unwrap();
+        drop(tx);
+
+        let (driven, received) = block_on(join(driver.run(), async {
+            let mut received = vec![];
+            while let Some(item) = subscription.next().await {
+                received.push(item);
+            }
+            received
+        }));
+        driven.unwrap();
+
+        assert_eq!(received.len(), 6);
+        for (item, event) in received.iter().zip(&events) {
+            assert_eq!(item.as_ref().unwrap(), event);
+        }
+        assert!(matches!(
+            received[5].as_ref().unwrap_err().detail(),
+            ErrorDetail::ClientInternal(e) if e.reason == "boom"
+        ));
+
+        drop(subscription);
+        assert_eq!(segment_files(&SpillConfig::new(&dir)), 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
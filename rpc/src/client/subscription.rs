@@ -1,6 +1,10 @@
 //! Subscription- and subscription management-related functionality.
 
-use core::pin::Pin;
+use alloc::sync::Arc;
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use async_trait::async_trait;
 use futures::{
@@ -10,15 +14,18 @@ use futures::{
 use pin_project::pin_project;
 
 use crate::{
+    alloc_prelude::*,
     client::sync::{ChannelRx, ChannelTx},
     event::Event,
-    prelude::*,
     query::Query,
     Error,
 };
 
 /// A client that exclusively provides [`Event`] subscription capabilities,
 /// without any other RPC method support.
+///
+/// As for [`Client`](crate::Client), the futures returned by the methods of
+/// this trait are `Send` for every client which is `Sync`.
 #[async_trait]
 pub trait SubscriptionClient {
     /// `/subscribe`: subscribe to receive events produced by the given query.
@@ -78,19 +85,38 @@ pub struct Subscription {
     // Our internal result event receiver for this subscription.
     #[pin]
     rx: SubscriptionRx,
+    // The number of events received from this subscription so far.
+    received: Arc<AtomicU64>,
 }
 
 impl Stream for Subscription {
     type Item = Result<Event, Error>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.project().rx.poll_next(cx)
+        let this = self.project();
+        let poll = this.rx.poll_next(cx);
+        if let Poll::Ready(Some(Ok(_))) = &poll {
+            this.received.fetch_add(1, Ordering::Relaxed);
+        }
+        poll
     }
 }
 
 impl Subscription {
     pub(crate) fn new(id: String, query: Query, rx: SubscriptionRx) -> Self {
-        Self { id, query, rx }
+        Self {
+            id,
+            query,
+            rx,
+            received: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// The number of events received from this subscription so far, shared
+    /// with the subscription router to tell how many are buffered.
+    #[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
+    pub(crate) fn received_counter(&self) -> Arc<AtomicU64> {
+        self.received.clone()
     }
 
     /// Return this subscription's ID for informational purposes.
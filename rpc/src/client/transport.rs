@@ -1,8 +1,18 @@
 //! Tendermint RPC client implementations for different transports.
 
 mod auth;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub(crate) mod instrument;
 pub mod mock;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub(crate) mod proxy;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub(crate) mod resolve;
 mod router;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub(crate) mod timing;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub(crate) mod tls;
 
 macro_rules! perform_with_compat {
     ($self:expr, $request:expr) => {{
@@ -14,6 +24,8 @@ macro_rules! perform_with_compat {
     }};
 }
 
+#[cfg(feature = "grpc-client")]
+pub mod grpc;
 #[cfg(feature = "http-client")]
 pub mod http;
 #[cfg(feature = "websocket-client")]
@@ -0,0 +1,483 @@
+//! gRPC-based transport for the node services of CometBFT 0.38+.
+
+use core::convert::{TryFrom, TryInto};
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use http::uri::PathAndQuery;
+use hyper::Uri;
+use prost::Message;
+use tendermint::block::Height;
+use tonic::{
+    client::Grpc,
+    codec::ProstCodec,
+    transport::{Channel, Endpoint},
+    Status,
+};
+
+use crate::alloc_prelude::*;
+use crate::{
+    client::{Client, HttpClientUrl},
+    endpoint, Error, Scheme, SimpleRequest, Url,
+};
+
+const GET_VERSION: &str = "/cometbft.services.version.v1.VersionService/GetVersion";
+const GET_BY_HEIGHT: &str = "/cometbft.services.block.v1.BlockService/GetByHeight";
+const GET_LATEST_HEIGHT: &str = "/cometbft.services.block.v1.BlockService/GetLatestHeight";
+const GET_BLOCK_RESULTS: &str =
+    "/cometbft.services.block_results.v1.BlockResultsService/GetBlockResults";
+
+// The default maximum size of the messages received, as in gRPC.
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// A gRPC client for the node services of CometBFT 0.38+ (implements
+/// [`crate::Client`] where the services allow), built on [`tonic`].
+///
+/// The `BlockService`, `BlockResultsService` and `VersionService` services
+/// are served by the nodes which enable them in the `[grpc]` section of their
+/// configuration. Only the [`Client::block`], [`Client::latest_block`],
+/// [`Client::block_results`] and [`Client::latest_block_results`] methods of
+/// the client are thus supported, the other ones failing with an
+/// [`ErrorDetail::GrpcUnsupported`](crate::error::ErrorDetail::GrpcUnsupported)
+/// error.
+///
+/// CometBFT 0.38 replaced the `BeginBlock` and `EndBlock` ABCI methods with
+/// `FinalizeBlock`: the events of `FinalizeBlock` are reported as the
+/// `finalize_block_events` of the block results.
+///
+/// Only plain HTTP/2 connections are supported, CometBFT serving gRPC
+/// without TLS. The messages received are limited to 4 MiB by default (see
+/// [`GrpcClient::max_message_size`]).
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{Client, GrpcClient};
+///
+/// let client = GrpcClient::new("http://127.0.0.1:26670")?;
+/// let version = client.version().await?;
+/// let block = client.latest_block().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcClient {
+    channel: Channel,
+    max_message_size: usize,
+}
+
+/// The versions of the software and protocols of a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeVersion {
+    /// The version of CometBFT.
+    pub node: String,
+    /// The version of the ABCI protocol.
+    pub abci: String,
+    /// The version of the P2P protocol.
+    pub p2p: u64,
+    /// The version of the block protocol.
+    pub block: u64,
+}
+
+impl GrpcClient {
+    /// Construct a new gRPC client connecting to the given `http` URL.
+    ///
+    /// The connection is established by the first call. This function must
+    /// be called within a Tokio runtime.
+    pub fn new<U>(url: U) -> Result<Self, Error>
+    where
+        U: TryInto<HttpClientUrl, Error = Error>,
+    {
+        let url = url.try_into()?;
+        if Url::from(url.clone()).scheme() != Scheme::Http {
+            return Err(Error::invalid_params(
+                "the gRPC client only supports plain http URLs".to_string(),
+            ));
+        }
+        let uri: Uri = url.try_into()?;
+        Ok(Self {
+            channel: Endpoint::from(uri).connect_lazy(),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        })
+    }
+
+    /// Set the maximum size of the messages received, beyond which the calls
+    /// fail.
+    #[must_use]
+    pub fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// `VersionService/GetVersion`: get the versions of the node.
+    pub async fn version(&self) -> Result<NodeVersion, Error> {
+        let response: services::GetVersionResponse = self
+            .call(GET_VERSION, services::GetVersionRequest {})
+            .await?;
+        Ok(NodeVersion {
+            node: response.node,
+            abci: response.abci,
+            p2p: response.p2p,
+            block: response.block,
+        })
+    }
+
+    /// `BlockService/GetLatestHeight`: get the height of the latest block.
+    pub async fn latest_height(&self) -> Result<Height, Error> {
+        self.latest_height_stream()
+            .await?
+            .next()
+            .await
+            .unwrap_or_else(|| Err(Error::grpc(0, "no latest height received".to_string())))
+    }
+
+    /// `BlockService/GetLatestHeight`: stream the height of every new block.
+    pub async fn latest_height_stream(
+        &self,
+    ) -> Result<BoxStream<'static, Result<Height, Error>>, Error> {
+        tracing::debug!("Outgoing gRPC request: {}", GET_LATEST_HEIGHT);
+        let responses = self
+            .grpc()
+            .await?
+            .server_streaming(
+                tonic::Request::new(services::GetLatestHeightRequest {}),
+                PathAndQuery::from_static(GET_LATEST_HEIGHT),
+                ProstCodec::<_, services::GetLatestHeightResponse>::default(),
+            )
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        Ok(stream::unfold(Some(responses), |responses| async move {
+            let mut responses = responses?;
+            let height = match responses.message().await {
+                Ok(Some(response)) => Height::try_from(response.height).map_err(Error::tendermint),
+                Ok(None) => return None,
+                Err(status) => Err(status_error(status)),
+            };
+            // The stream ends after the first error.
+            let responses = height.is_ok().then_some(responses);
+            Some((height, responses))
+        })
+        .boxed())
+    }
+
+    async fn block_at(&self, height: i64) -> Result<endpoint::block::Response, Error> {
+        let response: services::GetByHeightResponse = self
+            .call(GET_BY_HEIGHT, services::GetByHeightRequest { height })
+            .await?;
+        let missing = |field: &str| Error::grpc(0, format!("missing {field} in the response"));
+        Ok(endpoint::block::Response {
+            block_id: response
+                .block_id
+                .ok_or_else(|| missing("block_id"))?
+                .try_into()
+                .map_err(Error::tendermint)?,
+            block: response
+                .block
+                .ok_or_else(|| missing("block"))?
+                .try_into()
+                .map_err(Error::tendermint)?,
+        })
+    }
+
+    async fn block_results_at(
+        &self,
+        height: i64,
+    ) -> Result<endpoint::block_results::Response, Error> {
+        let response: services::GetBlockResultsResponse = self
+            .call(
+                GET_BLOCK_RESULTS,
+                services::GetBlockResultsRequest { height },
+            )
+            .await?;
+        Ok(endpoint::block_results::Response {
+            height: response.height.try_into().map_err(Error::tendermint)?,
+            txs_results: Some(
+                response
+                    .tx_results
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()
+                    .map_err(Error::tendermint)?,
+            ),
+            begin_block_events: None,
+            end_block_events: None,
+            finalize_block_events: Some(
+                response
+                    .finalize_block_events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()
+                    .map_err(Error::tendermint)?,
+            ),
+            validator_updates: response
+                .validator_updates
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()
+                .map_err(Error::tendermint)?,
+            consensus_param_updates: response
+                .consensus_param_updates
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(Error::tendermint)?,
+        })
+    }
+
+    // A client ready to send a request.
+    async fn grpc(&self) -> Result<Grpc<Channel>, Error> {
+        let mut grpc =
+            Grpc::new(self.channel.clone()).max_decoding_message_size(self.max_message_size);
+        grpc.ready()
+            .await
+            .map_err(|e| Error::grpc(tonic::Code::Unavailable as u32, e.to_string()))?;
+        Ok(grpc)
+    }
+
+    // Perform a unary call.
+    async fn call<Req, Res>(&self, path: &'static str, request: Req) -> Result<Res, Error>
+    where
+        Req: Message + 'static,
+        Res: Message + Default + 'static,
+    {
+        tracing::debug!("Outgoing gRPC request: {}", path);
+        let response = self
+            .grpc()
+            .await?
+            .unary(
+                tonic::Request::new(request),
+                PathAndQuery::from_static(path),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(status_error)?;
+        Ok(response.into_inner())
+    }
+}
+
+#[async_trait]
+impl Client for GrpcClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        Err(Error::grpc_unsupported(request.method().to_string()))
+    }
+
+    async fn block<H>(&self, height: H) -> Result<endpoint::block::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        self.block_at(height.into().into()).await
+    }
+
+    async fn latest_block(&self) -> Result<endpoint::block::Response, Error> {
+        let height = self.latest_height().await?;
+        self.block_at(height.into()).await
+    }
+
+    async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        self.block_results_at(height.into().into()).await
+    }
+
+    async fn latest_block_results(&self) -> Result<endpoint::block_results::Response, Error> {
+        let height = self.latest_height().await?;
+        self.block_results_at(height.into()).await
+    }
+}
+
+fn status_error(status: Status) -> Error {
+    Error::grpc(status.code() as u32, status.message().to_string())
+}
+
+/// Messages of the node services of CometBFT, whose fields referring to
+/// Tendermint types are wire-compatible with the Tendermint 0.37 ones.
+mod services {
+    use tendermint_proto::v0_37::{abci, types};
+
+    use crate::alloc_prelude::*;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetVersionRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetVersionResponse {
+        #[prost(string, tag = "1")]
+        pub node: String,
+        #[prost(string, tag = "2")]
+        pub abci: String,
+        #[prost(uint64, tag = "3")]
+        pub p2p: u64,
+        #[prost(uint64, tag = "4")]
+        pub block: u64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetByHeightRequest {
+        #[prost(int64, tag = "1")]
+        pub height: i64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetByHeightResponse {
+        #[prost(message, optional, tag = "1")]
+        pub block_id: Option<types::BlockId>,
+        #[prost(message, optional, tag = "2")]
+        pub block: Option<types::Block>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetLatestHeightRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetLatestHeightResponse {
+        #[prost(int64, tag = "1")]
+        pub height: i64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetBlockResultsRequest {
+        #[prost(int64, tag = "1")]
+        pub height: i64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetBlockResultsResponse {
+        #[prost(int64, tag = "1")]
+        pub height: i64,
+        /// `ExecTxResult`s, with the same fields as `ResponseDeliverTx`.
+        #[prost(message, repeated, tag = "2")]
+        pub tx_results: Vec<abci::ResponseDeliverTx>,
+        #[prost(message, repeated, tag = "3")]
+        pub finalize_block_events: Vec<abci::Event>,
+        #[prost(message, repeated, tag = "4")]
+        pub validator_updates: Vec<abci::ValidatorUpdate>,
+        #[prost(message, optional, tag = "5")]
+        pub consensus_param_updates: Option<types::ConsensusParams>,
+        #[prost(bytes = "vec", tag = "6")]
+        pub app_hash: Vec<u8>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, Bytes, BytesMut};
+    use hyper::{header, server::conn::Http, service::service_fn, Body, HeaderMap, Response};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    // A message prefixed with its compression flag and length.
+    fn encode_frame(message: &impl Message) -> Bytes {
+        let mut frame = BytesMut::with_capacity(5 + message.encoded_len());
+        frame.put_u8(0);
+        frame.put_u32(message.encoded_len() as u32);
+        message
+            .encode(&mut frame)
+            .expect("the buffer has enough capacity");
+        frame.freeze()
+    }
+
+    // Serve every call with the given frames, split in chunks of at most
+    // three bytes, followed by the given trailers. Returns the URL served.
+    async fn serve(frames: Vec<Bytes>, trailers: HeaderMap) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (frames, trailers) = (frames.clone(), trailers.clone());
+                let service = service_fn(move |_| {
+                    let (frames, trailers) = (frames.clone(), trailers.clone());
+                    let (mut sender, body) = Body::channel();
+                    tokio::spawn(async move {
+                        for frame in frames {
+                            for chunk in frame.chunks(3) {
+                                let chunk = Bytes::copy_from_slice(chunk);
+                                sender.send_data(chunk).await.unwrap();
+                            }
+                        }
+                        sender.send_trailers(trailers).await.unwrap();
+                    });
+                    let response = Response::builder()
+                        .header(header::CONTENT_TYPE, "application/grpc")
+                        .body(body);
+                    async move { response }
+                });
+                tokio::spawn(
+                    Http::new()
+                        .http2_only(true)
+                        .serve_connection(stream, service),
+                );
+            }
+        });
+        url
+    }
+
+    fn status(code: u32, message: &str) -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", code.into());
+        trailers.insert("grpc-message", message.parse().unwrap());
+        trailers
+    }
+
+    #[tokio::test]
+    async fn reads_messages_split_across_chunks() {
+        let frames = [7, 8]
+            .into_iter()
+            .map(|height| encode_frame(&services::GetLatestHeightResponse { height }))
+            .collect();
+        let client = GrpcClient::new(serve(frames, status(0, "")).await.as_str()).unwrap();
+
+        let heights: Vec<Height> = client
+            .latest_height_stream()
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(heights, vec![7u32.into(), 8u32.into()]);
+    }
+
+    #[tokio::test]
+    async fn fails_with_the_status_of_the_trailers() {
+        let url = serve(vec![], status(5, "height 10 not found")).await;
+        let client = GrpcClient::new(url.as_str()).unwrap();
+
+        let err = client.block(10u32).await.unwrap_err();
+        match err.detail() {
+            ErrorDetail::Grpc(e) => {
+                assert_eq!(e.code, 5);
+                assert_eq!(e.message, "height 10 not found");
+            },
+            _ => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_messages_over_the_maximum_size() {
+        let version = services::GetVersionResponse {
+            node: "0.38.0".to_string(),
+            abci: "2.0.0".to_string(),
+            p2p: 8,
+            block: 11,
+        };
+        let frames = vec![encode_frame(&version)];
+        let url = serve(frames, status(0, "")).await;
+
+        let client = GrpcClient::new(url.as_str()).unwrap();
+        assert_eq!(client.version().await.unwrap().node, "0.38.0");
+
+        let client = client.max_message_size(version.encoded_len() - 1);
+        let err = client.version().await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Grpc(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rejects_secure_urls() {
+        assert!(GrpcClient::new("https://127.0.0.1:26670").is_err());
+        assert!(GrpcClient::new("http://127.0.0.1:26670").is_ok());
+    }
+}
@@ -3,6 +3,7 @@
 use core::{
     convert::{TryFrom, TryInto},
     str::FromStr,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -11,19 +12,42 @@ use tendermint::{block::Height, Hash};
 use tendermint_config::net;
 
 use crate::dialect::v0_34;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{
-    client::{Client, CompatMode},
+    client::{
+        transport::{
+            instrument::Instrumentation,
+            proxy::ProxyUrl,
+            resolve::Resolver,
+            timing::{Observers, SlowCallLogger, TimingObserver},
+            tls::TlsConfig,
+        },
+        Client, CompatMode,
+    },
     endpoint,
     query::Query,
     Error, Order, Scheme, SimpleRequest, Url,
 };
 
+mod batch;
+#[cfg(feature = "http-compression")]
+mod compression;
+mod socks;
+mod timed;
+mod tls;
+#[cfg(unix)]
+mod unix;
+pub use batch::{Batch, BatchEntry, BatchResponse};
+
+// The default maximum size of the (decoded) response bodies.
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 64 * 1024 * 1024;
+
 /// A JSON-RPC/HTTP Tendermint RPC client (implements [`crate::Client`]).
 ///
 /// Supports both HTTP and HTTPS connections to Tendermint RPC endpoints, and
-/// allows for the use of HTTP proxies (see [`HttpClient::new_with_proxy`] for
-/// details).
+/// allows for the use of HTTP and SOCKS5 proxies (see
+/// [`HttpClient::new_with_proxy`] for details). On Unix platforms, it can also connect to an RPC endpoint served
+/// over a Unix domain socket, given a URL such as `unix:///path/to/rpc.sock`.
 ///
 /// Does not provide [`crate::event::Event`] subscription facilities (see
 /// [`crate::WebSocketClient`] for a client that does).
@@ -49,13 +73,19 @@ use crate::{
 pub struct HttpClient {
     inner: sealed::HttpClient,
     compat: CompatMode,
+    observers: Observers,
 }
 
 /// The builder pattern constructor for [`HttpClient`].
 pub struct Builder {
     url: HttpClientUrl,
     compat: CompatMode,
-    proxy_url: Option<HttpClientUrl>,
+    proxy_url: Option<ProxyUrl>,
+    proxy_from_env: bool,
+    tls_config: Option<TlsConfig>,
+    resolver: Resolver,
+    observers: Observers,
+    max_response_size: usize,
 }
 
 impl Builder {
@@ -69,37 +99,174 @@ impl Builder {
 
     /// Specify the URL of a proxy server for the client to connect through.
     ///
-    /// If the RPC endpoint is secured (HTTPS), the proxy will automatically
-    /// attempt to connect using the [HTTP CONNECT] method.
+    /// If the RPC endpoint is secured (HTTPS), an HTTP proxy will automatically
+    /// be connected to using the [HTTP CONNECT] method. See [`ProxyUrl`] for
+    /// the supported proxies.
     ///
     /// [HTTP CONNECT]: https://en.wikipedia.org/wiki/HTTP_tunnel
-    pub fn proxy_url(mut self, url: HttpClientUrl) -> Self {
-        self.proxy_url = Some(url);
+    pub fn proxy_url(mut self, url: impl Into<ProxyUrl>) -> Self {
+        self.proxy_url = Some(url.into());
+        self
+    }
+
+    /// Connect through the proxy given by the environment, if no proxy URL
+    /// is specified with [`Builder::proxy_url`].
+    ///
+    /// See [`ProxyUrl::from_env`] for the variables which are honored.
+    pub fn proxy_from_env(mut self) -> Self {
+        self.proxy_from_env = true;
+        self
+    }
+
+    /// Use custom TLS options to connect to a secured (HTTPS) RPC endpoint,
+    /// e.g. to trust a private certificate authority or to authenticate with
+    /// a client certificate.
+    ///
+    /// Overriding the server name with [`TlsConfig::server_name`] is not
+    /// supported when connecting through an HTTP proxy.
+    pub fn tls_config(mut self, config: TlsConfig) -> Self {
+        self.tls_config = Some(config);
+        self
+    }
+
+    /// Resolve the host names of the RPC endpoint and of the proxy, if any,
+    /// with the given resolver, e.g. to override the addresses of some hosts
+    /// (see [`Resolver`]).
+    ///
+    /// The host names of the RPC endpoints reached through a proxy are
+    /// resolved by the proxy. By default, host names are resolved by the
+    /// operating system.
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Report the phase timings of every call of the client to the given
+    /// observer (see [`CallTimings`](crate::CallTimings)).
+    ///
+    /// Can be called several times to add several observers.
+    pub fn timing_observer(mut self, observer: impl TimingObserver + 'static) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Log a warning with the phase timings of the calls which take at least
+    /// the given duration.
+    ///
+    /// This is a shorthand for adding a [`SlowCallLogger`] observer.
+    pub fn slow_call_threshold(self, threshold: Duration) -> Self {
+        self.timing_observer(SlowCallLogger::new(threshold))
+    }
+
+    /// Report every call of the client to the given instrumentation, e.g. to
+    /// export metrics (see [`Instrumentation`]).
+    ///
+    /// Can be called several times to add several instrumentations.
+    pub fn instrumentation(mut self, instrumentation: impl Instrumentation + 'static) -> Self {
+        self.observers.instrument(instrumentation);
+        self
+    }
+
+    /// Fail the calls whose response body, once decompressed, is larger
+    /// than the given number of bytes. The default is 64 MiB.
+    pub fn max_response_size(mut self, max_size: usize) -> Self {
+        self.max_response_size = max_size;
         self
     }
 
     /// Try to create a client with the options specified for this builder.
     pub fn build(self) -> Result<HttpClient, Error> {
-        match self.proxy_url {
+        let max_response_size = self.max_response_size;
+        let mut client = self.build_client()?;
+        client.inner.set_max_response_size(max_response_size);
+        Ok(client)
+    }
+
+    fn build_client(self) -> Result<HttpClient, Error> {
+        let observers = self.observers;
+        let resolver = self.resolver;
+        if self.url.0.scheme() == Scheme::Unix {
+            if self.proxy_url.is_some() {
+                return Err(Error::invalid_params(
+                    "proxies are not supported for Unix domain socket connections".to_string(),
+                ));
+            }
+            if self.tls_config.is_some() {
+                return Err(Error::invalid_params(
+                    "TLS is not supported for Unix domain socket connections".to_string(),
+                ));
+            }
+            return Ok(HttpClient {
+                inner: sealed::HttpClient::new_unix(self.url.0.path())?,
+                compat: self.compat,
+                observers,
+            });
+        }
+
+        let proxy_url = match self.proxy_url {
+            Some(proxy_url) => Some(proxy_url),
+            None if self.proxy_from_env => ProxyUrl::from_env(self.url.0.is_secure())?,
+            None => None,
+        };
+
+        match proxy_url {
             None => Ok(HttpClient {
                 inner: if self.url.0.is_secure() {
-                    sealed::HttpClient::new_https(self.url.try_into()?)
+                    sealed::HttpClient::new_https(
+                        self.url.try_into()?,
+                        &self
+                            .tls_config
+                            .unwrap_or_else(|| TlsConfig::new().webpki_roots(false)),
+                        resolver,
+                    )?
                 } else {
-                    sealed::HttpClient::new_http(self.url.try_into()?)
+                    sealed::HttpClient::new_http(self.url.try_into()?, resolver)
                 },
                 compat: self.compat,
+                observers,
             }),
-            Some(proxy_url) => Ok(HttpClient {
-                inner: if proxy_url.0.is_secure() {
-                    sealed::HttpClient::new_https_proxy(
+            Some(proxy_url) if proxy_url.scheme() == Scheme::Socks5 => Ok(HttpClient {
+                inner: if self.url.0.is_secure() {
+                    sealed::HttpClient::new_https_socks(
                         self.url.try_into()?,
-                        proxy_url.try_into()?,
+                        proxy_url,
+                        &self.tls_config.unwrap_or_default(),
+                        resolver,
                     )?
                 } else {
-                    sealed::HttpClient::new_http_proxy(self.url.try_into()?, proxy_url.try_into()?)?
+                    sealed::HttpClient::new_http_socks(self.url.try_into()?, proxy_url, resolver)
                 },
                 compat: self.compat,
+                observers,
             }),
+            Some(proxy_url) => {
+                let tls_config = self.tls_config.as_ref();
+                if tls_config.map_or(false, TlsConfig::has_server_name) {
+                    return Err(Error::invalid_params(
+                        "overriding the TLS server name is not supported through HTTP proxies"
+                            .to_string(),
+                    ));
+                }
+                Ok(HttpClient {
+                    inner: if proxy_url.is_secure() {
+                        sealed::HttpClient::new_https_proxy(
+                            self.url.try_into()?,
+                            proxy_url.try_into()?,
+                            tls_config,
+                            resolver,
+                        )?
+                    } else {
+                        sealed::HttpClient::new_http_proxy(
+                            self.url.try_into()?,
+                            proxy_url.try_into()?,
+                            tls_config,
+                            resolver,
+                        )?
+                    },
+                    compat: self.compat,
+                    observers,
+                })
+            },
         }
     }
 }
@@ -111,28 +278,21 @@ impl HttpClient {
     where
         U: TryInto<HttpClientUrl, Error = Error>,
     {
-        let url = url.try_into()?;
-        Ok(Self {
-            inner: if url.0.is_secure() {
-                sealed::HttpClient::new_https(url.try_into()?)
-            } else {
-                sealed::HttpClient::new_http(url.try_into()?)
-            },
-            compat: Default::default(),
-        })
+        Self::builder(url.try_into()?).build()
     }
 
     /// Construct a new Tendermint RPC HTTP/S client connecting to the given
     /// URL, but via the specified proxy's URL.
     ///
-    /// If the RPC endpoint is secured (HTTPS), the proxy will automatically
-    /// attempt to connect using the [HTTP CONNECT] method.
+    /// If the RPC endpoint is secured (HTTPS), an HTTP proxy will automatically
+    /// be connected to using the [HTTP CONNECT] method. SOCKS5 proxies are
+    /// supported as well, see [`ProxyUrl`].
     ///
     /// [HTTP CONNECT]: https://en.wikipedia.org/wiki/HTTP_tunnel
     pub fn new_with_proxy<U, P>(url: U, proxy_url: P) -> Result<Self, Error>
     where
         U: TryInto<HttpClientUrl, Error = Error>,
-        P: TryInto<HttpClientUrl, Error = Error>,
+        P: TryInto<ProxyUrl, Error = Error>,
     {
         let url = url.try_into()?;
         Self::builder(url).proxy_url(proxy_url.try_into()?).build()
@@ -146,6 +306,11 @@ impl HttpClient {
             url,
             compat: Default::default(),
             proxy_url: None,
+            proxy_from_env: false,
+            tls_config: None,
+            resolver: Resolver::default(),
+            observers: Observers::default(),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
         }
     }
 
@@ -158,11 +323,19 @@ impl HttpClient {
         self.compat = compat;
     }
 
+    /// Start a batch of requests, to be sent to the RPC endpoint in a single
+    /// round trip.
+    ///
+    /// See [`Batch`] for details.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch::new(self)
+    }
+
     async fn perform_v0_34<R>(&self, request: R) -> Result<R::Output, Error>
     where
         R: SimpleRequest<v0_34::Dialect>,
     {
-        self.inner.perform(request).await
+        self.inner.perform(request, &self.observers).await
     }
 }
 
@@ -172,7 +345,7 @@ impl Client for HttpClient {
     where
         R: SimpleRequest,
     {
-        self.inner.perform(request).await
+        self.inner.perform(request, &self.observers).await
     }
 
     async fn block_results<H>(&self, height: H) -> Result<endpoint::block_results::Response, Error>
@@ -260,7 +433,7 @@ impl TryFrom<Url> for HttpClientUrl {
 
     fn try_from(value: Url) -> Result<Self, Error> {
         match value.scheme() {
-            Scheme::Http | Scheme::Https => Ok(Self(value)),
+            Scheme::Http | Scheme::Https | Scheme::Unix => Ok(Self(value)),
             _ => Err(Error::invalid_url(value)),
         }
     }
@@ -293,7 +466,7 @@ impl TryFrom<net::Address> for HttpClientUrl {
                 host,
                 port,
             } => format!("http://{host}:{port}").parse(),
-            net::Address::Unix { .. } => Err(Error::invalid_network_address()),
+            net::Address::Unix { path } => format!("unix://{path}").parse(),
         }
     }
 }
@@ -304,6 +477,14 @@ impl From<HttpClientUrl> for Url {
     }
 }
 
+impl TryFrom<ProxyUrl> for hyper::Uri {
+    type Error = Error;
+
+    fn try_from(value: ProxyUrl) -> Result<Self, Error> {
+        value.to_string().parse().map_err(Error::invalid_uri)
+    }
+}
+
 impl TryFrom<HttpClientUrl> for hyper::Uri {
     type Error = Error;
 
@@ -313,18 +494,32 @@ impl TryFrom<HttpClientUrl> for hyper::Uri {
 }
 
 mod sealed {
-    use std::io::Read;
+    use core::time::Duration;
+    use std::{io::Read, sync::Arc, time::Instant};
 
     use http::header::AUTHORIZATION;
     use hyper::{
-        body::Buf,
-        client::{connect::Connect, HttpConnector},
+        body::{Buf, HttpBody},
+        client::connect::Connect,
         header, Uri,
     };
     use hyper_proxy::{Intercept, Proxy, ProxyConnector};
     use hyper_rustls::HttpsConnector;
 
-    use crate::prelude::*;
+    #[cfg(unix)]
+    use super::unix::UnixConnector;
+    use super::{
+        socks::SocksConnector,
+        timed::{tcp_connector, TcpConnector, Timed, Timeline},
+        tls::TlsConnector,
+    };
+    use crate::client::transport::{
+        proxy::ProxyUrl,
+        resolve::Resolver,
+        timing::{CallTimings, Observers},
+        tls::TlsConfig,
+    };
+    use crate::alloc_prelude::*;
     use crate::{
         client::transport::auth::authorize, dialect::Dialect, Error, Response, SimpleRequest,
     };
@@ -334,11 +529,16 @@ mod sealed {
     pub struct HyperClient<C> {
         uri: Uri,
         inner: hyper::Client<C>,
+        max_response_size: usize,
     }
 
     impl<C> HyperClient<C> {
         pub fn new(uri: Uri, inner: hyper::Client<C>) -> Self {
-            Self { uri, inner }
+            Self {
+                uri,
+                inner,
+                max_response_size: super::DEFAULT_MAX_RESPONSE_SIZE,
+            }
         }
     }
 
@@ -346,16 +546,75 @@ mod sealed {
     where
         C: Connect + Clone + Send + Sync + 'static,
     {
-        pub async fn perform<R, S>(&self, request: R) -> Result<R::Output, Error>
+        pub async fn perform<R, S>(
+            &self,
+            request: R,
+            observers: &Observers,
+        ) -> Result<R::Output, Error>
         where
             R: SimpleRequest<S>,
             S: Dialect,
         {
+            let method = request.method().to_string();
             let request = self.build_request(request)?;
-            let response = self.inner.request(request).await.map_err(Error::hyper)?;
-            let response_body = response_to_string(response).await?;
-            tracing::debug!("Incoming response: {}", response_body);
-            R::Response::from_string(&response_body).map(Into::into)
+            let request_size = request.body().size_hint().exact().unwrap_or(0);
+            let meter = observers.meter(&method, request_size as usize);
+            let mut response_size = None;
+            let result = async {
+                let response_body = self.send(request, &method, observers).await?;
+                response_size = Some(response_body.len());
+                tracing::debug!("Incoming response: {}", response_body);
+                R::Response::from_string(&response_body).map(Into::into)
+            }
+            .await;
+            meter.complete(response_size, &result);
+            result
+        }
+
+        /// Send the given JSON-RPC request body as is, returning the body of
+        /// the response.
+        pub async fn perform_raw(
+            &self,
+            request_body: String,
+            observers: &Observers,
+        ) -> Result<String, Error> {
+            let request_size = request_body.len();
+            let request = self.build_raw_request(request_body)?;
+            let meter = observers.meter("batch", request_size);
+            let result = self.send(request, "batch", observers).await;
+            meter.complete(result.as_ref().ok().map(String::len), &result);
+            result
+        }
+
+        /// Send the given request, reporting the timings of the call to the
+        /// given observers, and return the body of the response.
+        async fn send(
+            &self,
+            request: hyper::Request<hyper::Body>,
+            method: &str,
+            observers: &Observers,
+        ) -> Result<String, Error> {
+            if observers.is_empty() {
+                let response = self.inner.request(request).await.map_err(Error::hyper)?;
+                return response_to_string(response, self.max_response_size).await;
+            }
+
+            let start = Instant::now();
+            let mut timings = CallTimings::default();
+            let result = async {
+                let response = self.inner.request(request).await.map_err(Error::hyper)?;
+                let headers_received = Instant::now();
+                if let Some(timeline) = response.extensions().get::<Timeline>() {
+                    timeline.fill(&mut timings);
+                }
+                let response_body = response_to_string(response, self.max_response_size).await;
+                timings.body_read = Some(headers_received.elapsed());
+                response_body
+            }
+            .await;
+            timings.total = start.elapsed();
+            observers.observe(method, &timings);
+            result
         }
     }
 
@@ -366,8 +625,14 @@ mod sealed {
             R: SimpleRequest<S>,
             S: Dialect,
         {
-            let request_body = request.into_json();
+            self.build_raw_request(request.into_json())
+        }
 
+        /// Build a request with the given JSON-RPC request body.
+        pub fn build_raw_request(
+            &self,
+            request_body: String,
+        ) -> Result<hyper::Request<hyper::Body>, Error> {
             tracing::debug!("Outgoing request: {}", request_body);
 
             let mut request = hyper::Request::builder()
@@ -389,6 +654,12 @@ mod sealed {
                 if let Some(auth) = authorize(&self.uri) {
                     headers.insert(AUTHORIZATION, auth.to_string().parse().unwrap());
                 }
+
+                #[cfg(feature = "http-compression")]
+                headers.insert(
+                    header::ACCEPT_ENCODING,
+                    header::HeaderValue::from_static(super::compression::ACCEPT_ENCODING),
+                );
             }
 
             Ok(request)
@@ -400,72 +671,211 @@ mod sealed {
     /// Here we erase the type signature of the underlying `hyper`-based
     /// client, allowing the higher-level HTTP client to operate via HTTP or
     /// HTTPS, and with or without a proxy.
+    ///
+    /// All the connectors are wrapped in [`Timed`], so as to report the
+    /// phase timings of the calls.
     #[derive(Debug, Clone)]
     pub enum HttpClient {
-        Http(HyperClient<HttpConnector>),
-        Https(HyperClient<HttpsConnector<HttpConnector>>),
-        HttpProxy(HyperClient<ProxyConnector<HttpConnector>>),
-        HttpsProxy(HyperClient<ProxyConnector<HttpsConnector<HttpConnector>>>),
+        Http(HyperClient<Timed<TcpConnector>>),
+        Https(HyperClient<Timed<TlsConnector<TcpConnector>>>),
+        HttpProxy(HyperClient<Timed<ProxyConnector<TcpConnector>>>),
+        HttpsProxy(HyperClient<Timed<ProxyConnector<HttpsConnector<TcpConnector>>>>),
+        HttpSocks(HyperClient<Timed<SocksConnector>>),
+        HttpsSocks(HyperClient<Timed<TlsConnector<SocksConnector>>>),
+        #[cfg(unix)]
+        Unix(HyperClient<Timed<UnixConnector>>),
     }
 
     impl HttpClient {
-        pub fn new_http(uri: Uri) -> Self {
-            Self::Http(HyperClient::new(uri, hyper::Client::new()))
+        pub fn new_http(uri: Uri, resolver: Resolver) -> Self {
+            Self::Http(HyperClient::new(
+                uri,
+                hyper::Client::builder().build(Timed::new(tcp_connector(resolver))),
+            ))
         }
 
-        pub fn new_https(uri: Uri) -> Self {
-            Self::Https(HyperClient::new(
+        pub fn new_https(
+            uri: Uri,
+            tls_config: &TlsConfig,
+            resolver: Resolver,
+        ) -> Result<Self, Error> {
+            Ok(Self::Https(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(HttpsConnector::with_native_roots()),
-            ))
+                hyper::Client::builder()
+                    .build(Timed::new(tls_config.connector(tcp_connector(resolver))?)),
+            )))
         }
 
-        pub fn new_http_proxy(uri: Uri, proxy_uri: Uri) -> Result<Self, Error> {
+        pub fn new_http_proxy(
+            uri: Uri,
+            proxy_uri: Uri,
+            tls_config: Option<&TlsConfig>,
+            resolver: Resolver,
+        ) -> Result<Self, Error> {
             let proxy = Proxy::new(Intercept::All, proxy_uri);
-            let proxy_connector =
-                ProxyConnector::from_proxy(HttpConnector::new(), proxy).map_err(Error::io)?;
+            let mut proxy_connector =
+                ProxyConnector::from_proxy(tcp_connector(resolver), proxy).map_err(Error::io)?;
+            if let Some(tls_config) = tls_config {
+                proxy_connector.set_tls(Some(Arc::new(tls_config.client_config()?).into()));
+            }
             Ok(Self::HttpProxy(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(proxy_connector),
+                hyper::Client::builder().build(Timed::new(proxy_connector)),
             )))
         }
 
-        pub fn new_https_proxy(uri: Uri, proxy_uri: Uri) -> Result<Self, Error> {
+        pub fn new_https_proxy(
+            uri: Uri,
+            proxy_uri: Uri,
+            tls_config: Option<&TlsConfig>,
+            resolver: Resolver,
+        ) -> Result<Self, Error> {
             let proxy = Proxy::new(Intercept::All, proxy_uri);
-            let proxy_connector =
-                ProxyConnector::from_proxy(HttpsConnector::with_native_roots(), proxy)
-                    .map_err(Error::io)?;
+            let https = HttpsConnector::from((
+                tcp_connector(resolver),
+                TlsConfig::new().webpki_roots(false).client_config()?,
+            ));
+            let mut proxy_connector =
+                ProxyConnector::from_proxy(https, proxy).map_err(Error::io)?;
+            if let Some(tls_config) = tls_config {
+                proxy_connector.set_tls(Some(Arc::new(tls_config.client_config()?).into()));
+            }
 
             Ok(Self::HttpsProxy(HyperClient::new(
                 uri,
-                hyper::Client::builder().build(proxy_connector),
+                hyper::Client::builder().build(Timed::new(proxy_connector)),
+            )))
+        }
+
+        pub fn new_http_socks(uri: Uri, proxy_url: ProxyUrl, resolver: Resolver) -> Self {
+            Self::HttpSocks(HyperClient::new(
+                uri,
+                hyper::Client::builder()
+                    .build(Timed::new(SocksConnector::new(proxy_url, resolver))),
+            ))
+        }
+
+        pub fn new_https_socks(
+            uri: Uri,
+            proxy_url: ProxyUrl,
+            tls_config: &TlsConfig,
+            resolver: Resolver,
+        ) -> Result<Self, Error> {
+            Ok(Self::HttpsSocks(HyperClient::new(
+                uri,
+                hyper::Client::builder().build(Timed::new(
+                    tls_config.connector(SocksConnector::new(proxy_url, resolver))?,
+                )),
+            )))
+        }
+
+        /// The connector ignores the URI of the requests, whose host is only
+        /// used for the `Host` header.
+        #[cfg(unix)]
+        pub fn new_unix(path: &str) -> Result<Self, Error> {
+            Ok(Self::Unix(HyperClient::new(
+                Uri::from_static("http://localhost/"),
+                hyper::Client::builder().build(Timed::new(UnixConnector::new(path))),
             )))
         }
 
-        pub async fn perform<R, S>(&self, request: R) -> Result<R::Output, Error>
+        #[cfg(not(unix))]
+        pub fn new_unix(_path: &str) -> Result<Self, Error> {
+            Err(Error::unsupported_scheme("unix".to_string()))
+        }
+
+        pub fn set_max_response_size(&mut self, max_size: usize) {
+            match self {
+                HttpClient::Http(c) => c.max_response_size = max_size,
+                HttpClient::Https(c) => c.max_response_size = max_size,
+                HttpClient::HttpProxy(c) => c.max_response_size = max_size,
+                HttpClient::HttpsProxy(c) => c.max_response_size = max_size,
+                HttpClient::HttpSocks(c) => c.max_response_size = max_size,
+                HttpClient::HttpsSocks(c) => c.max_response_size = max_size,
+                #[cfg(unix)]
+                HttpClient::Unix(c) => c.max_response_size = max_size,
+            }
+        }
+
+        pub async fn perform<R, S>(
+            &self,
+            request: R,
+            observers: &Observers,
+        ) -> Result<R::Output, Error>
         where
             R: SimpleRequest<S>,
             S: Dialect,
         {
             match self {
-                HttpClient::Http(c) => c.perform(request).await,
-                HttpClient::Https(c) => c.perform(request).await,
-                HttpClient::HttpProxy(c) => c.perform(request).await,
-                HttpClient::HttpsProxy(c) => c.perform(request).await,
+                HttpClient::Http(c) => c.perform(request, observers).await,
+                HttpClient::Https(c) => c.perform(request, observers).await,
+                HttpClient::HttpProxy(c) => c.perform(request, observers).await,
+                HttpClient::HttpsProxy(c) => c.perform(request, observers).await,
+                HttpClient::HttpSocks(c) => c.perform(request, observers).await,
+                HttpClient::HttpsSocks(c) => c.perform(request, observers).await,
+                #[cfg(unix)]
+                HttpClient::Unix(c) => c.perform(request, observers).await,
+            }
+        }
+
+        pub async fn perform_raw(
+            &self,
+            request_body: String,
+            observers: &Observers,
+        ) -> Result<String, Error> {
+            match self {
+                HttpClient::Http(c) => c.perform_raw(request_body, observers).await,
+                HttpClient::Https(c) => c.perform_raw(request_body, observers).await,
+                HttpClient::HttpProxy(c) => c.perform_raw(request_body, observers).await,
+                HttpClient::HttpsProxy(c) => c.perform_raw(request_body, observers).await,
+                HttpClient::HttpSocks(c) => c.perform_raw(request_body, observers).await,
+                HttpClient::HttpsSocks(c) => c.perform_raw(request_body, observers).await,
+                #[cfg(unix)]
+                HttpClient::Unix(c) => c.perform_raw(request_body, observers).await,
             }
         }
     }
 
-    async fn response_to_string(response: hyper::Response<hyper::Body>) -> Result<String, Error> {
-        let mut response_body = String::new();
-        hyper::body::aggregate(response.into_body())
+    async fn response_to_string(
+        response: hyper::Response<hyper::Body>,
+        max_size: usize,
+    ) -> Result<String, Error> {
+        // Rate limiting proxies in front of public nodes reply with a bare
+        // 429 rather than with a JSON-RPC error.
+        if response.status() == hyper::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::rate_limited(retry_after(response.headers())));
+        }
+
+        #[cfg(feature = "http-compression")]
+        let encoding = response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .map(|encoding| encoding.to_str().unwrap_or_default().to_owned());
+        let body = hyper::body::aggregate(response.into_body())
             .await
             .map_err(Error::hyper)?
-            .reader()
-            .read_to_string(&mut response_body)
+            .reader();
+        #[cfg(feature = "http-compression")]
+        let body = super::compression::decoder(encoding.as_deref(), body)?;
+
+        // Read one byte past the limit to tell a body of the maximum size from
+        // a larger one, which decompression could otherwise inflate at will.
+        let mut response_body = Vec::new();
+        body.take(max_size as u64 + 1)
+            .read_to_end(&mut response_body)
             .map_err(Error::io)?;
+        if response_body.len() > max_size {
+            return Err(Error::response_too_large(max_size));
+        }
 
-        Ok(response_body)
+        String::from_utf8(response_body).map_err(|e| Error::parse(e.to_string()))
+    }
+
+    /// The delay given by the `Retry-After` header of a response, if it is
+    /// given in seconds rather than as a date.
+    fn retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+        let seconds = headers.get(header::RETRY_AFTER)?.to_str().ok()?;
+        seconds.trim().parse().ok().map(Duration::from_secs)
     }
 }
 
@@ -507,4 +917,218 @@ mod tests {
 
         assert_eq!(authorization(&req), Some("Basic dG90bzp0YXRh"));
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_socket() {
+        use std::{
+            io::{BufRead, BufReader, Read, Write},
+            os::unix::net::UnixListener,
+        };
+
+        use crate::{alloc_prelude::*, Client, HttpClient};
+
+        let path = std::env::temp_dir().join(format!("tendermint-rpc-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut request = vec![0; content_length];
+            reader.read_exact(&mut request).unwrap();
+
+            let body =
+                include_str!("../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json");
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let client = HttpClient::new(format!("unix://{}", path.display()).as_str()).unwrap();
+        let response = client.abci_info().await.unwrap();
+        assert_eq!(response.app_version, 1);
+        assert!(server.join().unwrap().contains("abci_info"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn call_timings() {
+        use std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+            sync::{Arc, Mutex},
+        };
+
+        use crate::{alloc_prelude::*, CallTimings, Client, HttpClient};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            // Both calls are expected over the same connection.
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            for _ in 0..2 {
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut request = vec![0; content_length];
+                reader.read_exact(&mut request).unwrap();
+
+                let body =
+                    include_str!("../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json");
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        let observed = Arc::new(Mutex::new(Vec::<(String, CallTimings)>::new()));
+        let recorder = observed.clone();
+        let client = HttpClient::builder(url.parse().unwrap())
+            .timing_observer(move |method: &str, timings: &CallTimings| {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push((method.to_string(), timings.clone()));
+            })
+            .build()
+            .unwrap();
+        client.abci_info().await.unwrap();
+        client.abci_info().await.unwrap();
+        server.join().unwrap();
+
+        let observed = observed.lock().unwrap();
+        assert_eq!(observed.len(), 2);
+        let (method, first) = &observed[0];
+        assert_eq!(method, "abci_info");
+        // No DNS resolution for an IP address, nor TLS handshake over HTTP.
+        assert_eq!(first.dns, None);
+        assert!(first.connect.is_some());
+        assert_eq!(first.tls, None);
+        assert!(first.request_write.is_some());
+        assert!(first.first_byte.is_some());
+        assert!(first.body_read.is_some());
+
+        // The second call reuses the connection.
+        let (_, second) = &observed[1];
+        assert_eq!(second.connect, None);
+        assert!(second.request_write.is_some());
+        assert!(second.first_byte.is_some());
+    }
+
+    #[tokio::test]
+    async fn resolver_overrides() {
+        use std::{
+            io::{Read, Write},
+            net::{Ipv4Addr, TcpListener},
+        };
+
+        use crate::{alloc_prelude::*, Client, HttpClient, Resolver};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0; 4096];
+            let len = stream.read(&mut request).unwrap();
+            let body = include_str!("../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json");
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request[..len]).to_string()
+        });
+
+        let resolver = Resolver::builder()
+            .host("node0.chain", [Ipv4Addr::LOCALHOST.into()])
+            .build();
+        let client = HttpClient::builder(format!("http://node0.chain:{port}").parse().unwrap())
+            .resolver(resolver)
+            .build()
+            .unwrap();
+        let response = client.abci_info().await.unwrap();
+        assert_eq!(response.app_version, 1);
+        // The endpoint is still addressed by its host name.
+        let request = server.join().unwrap().to_lowercase();
+        assert!(request.contains(&format!("host: node0.chain:{port}")), "{request}");
+    }
+
+    #[cfg(feature = "http-compression")]
+    #[tokio::test]
+    async fn caps_decompressed_responses() {
+        use std::{
+            io::{Read, Write},
+            net::TcpListener,
+        };
+
+        use flate2::{write::GzEncoder, Compression};
+
+        use crate::{error::ErrorDetail, Client, HttpClient};
+
+        // A megabyte of zeros, compressed to a kilobyte.
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::best());
+        gzip.write_all(&[0; 1024 * 1024]).unwrap();
+        let body = gzip.finish().unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0; 4096];
+            assert!(stream.read(&mut request).unwrap() > 0);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+        });
+
+        let client = HttpClient::builder(url.parse().unwrap())
+            .max_response_size(64 * 1024)
+            .build()
+            .unwrap();
+        let err = client.abci_info().await.unwrap_err();
+        server.join().unwrap();
+        match err.detail() {
+            ErrorDetail::ResponseTooLarge(e) => assert_eq!(e.max_size, 64 * 1024),
+            _ => panic!("unexpected error: {err}"),
+        }
+    }
 }
@@ -0,0 +1,313 @@
+//! JSON-RPC batch requests over HTTP.
+
+use core::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::HttpClient;
+use crate::{
+    alloc_prelude::*,
+    client::CompatMode,
+    dialect::{v0_34, Dialect, LatestDialect},
+    request::{RequestMessage, Wrapper},
+    response, Error, Id, Response, SimpleRequest,
+};
+
+/// A batch of requests to be sent to the RPC endpoint in a single JSON-RPC
+/// batch, and thus a single round trip.
+///
+/// Created by [`HttpClient::batch`]. Each call to [`Batch::add`] returns an
+/// entry with which to retrieve the output of the request from the
+/// [`BatchResponse`] once the batch has been sent.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{endpoint::block, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+///
+///     let mut batch = client.batch();
+///     let entries: Vec<_> = (1..=100_u32)
+///         .map(|height| batch.add(block::Request::new(height.into())))
+///         .collect();
+///     let responses = batch.send().await.unwrap();
+///
+///     for entry in &entries {
+///         let block = responses.get(entry).unwrap();
+///         println!("Got block: {:?}", block.block.header.height);
+///     }
+/// }
+/// ```
+pub struct Batch<'a> {
+    client: &'a HttpClient,
+    ids: Vec<Id>,
+    requests: Vec<String>,
+}
+
+impl<'a> Batch<'a> {
+    pub(super) fn new(client: &'a HttpClient) -> Self {
+        Self {
+            client,
+            ids: Vec::new(),
+            requests: Vec::new(),
+        }
+    }
+
+    /// Add a request to the batch.
+    pub fn add<R>(&mut self, request: R) -> BatchEntry<R>
+    where
+        R: RequestMessage,
+    {
+        let wrapper = Wrapper::new(request);
+        let index = self.ids.len();
+        let id = wrapper.id().clone();
+        self.ids.push(id.clone());
+        self.requests.push(wrapper.into_json());
+        BatchEntry {
+            index,
+            id,
+            _request: PhantomData,
+        }
+    }
+
+    /// The number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether the batch has no requests.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Send all the requests of the batch in a single HTTP request.
+    ///
+    /// This fails if the whole batch is rejected by the remote endpoint. The
+    /// outcome of each request is obtained with [`BatchResponse::get`].
+    pub async fn send(self) -> Result<BatchResponse, Error> {
+        if self.requests.is_empty() {
+            return Ok(BatchResponse {
+                compat: self.client.compat,
+                ids: self.ids,
+                responses: Vec::new(),
+            });
+        }
+
+        let body = format!("[{}]", self.requests.join(","));
+        let response = self
+            .client
+            .inner
+            .perform_raw(body, &self.client.observers)
+            .await?;
+        BatchResponse::from_string(self.client.compat, self.ids, &response)
+    }
+}
+
+/// A request added to a [`Batch`], used to retrieve its output from the
+/// [`BatchResponse`].
+#[derive(Debug)]
+pub struct BatchEntry<R> {
+    index: usize,
+    // Identifies the batch the entry belongs to, as the IDs of the requests
+    // are unique.
+    id: Id,
+    _request: PhantomData<fn() -> R>,
+}
+
+/// The responses to the requests of a [`Batch`].
+#[derive(Debug, Clone)]
+pub struct BatchResponse {
+    compat: CompatMode,
+    ids: Vec<Id>,
+    // The responses in the order of the requests, as the remote endpoint may
+    // return them in any order.
+    responses: Vec<Option<Value>>,
+}
+
+impl BatchResponse {
+    fn from_string(compat: CompatMode, ids: Vec<Id>, response: &str) -> Result<Self, Error> {
+        tracing::debug!("Incoming batch response: {}", response);
+
+        let items = match serde_json::from_str(response).map_err(Error::serde)? {
+            Value::Array(items) => items,
+            // The whole batch was rejected, e.g. because it could not be parsed.
+            _ => {
+                AnyResponse::from_string(response)?;
+                return Err(Error::malformed_json());
+            },
+        };
+
+        // Responses without a valid ID, e.g. to requests which could not be
+        // parsed, cannot be matched to their request, which is then reported
+        // as having no response.
+        let mut responses = vec![None; ids.len()];
+        for item in items {
+            let index = Id::deserialize(&item["id"])
+                .ok()
+                .and_then(|id| ids.iter().position(|i| *i == id));
+            if let Some(index) = index {
+                responses[index] = Some(item);
+            }
+        }
+
+        Ok(Self {
+            compat,
+            ids,
+            responses,
+        })
+    }
+
+    /// Get the output of a request of the batch.
+    ///
+    /// The response is parsed according to the compatibility mode of the
+    /// client that sent the batch. Fails if the entry was added to another
+    /// batch.
+    pub fn get<R>(&self, entry: &BatchEntry<R>) -> Result<<R as SimpleRequest>::Output, Error>
+    where
+        R: SimpleRequest + SimpleRequest<v0_34::Dialect, Output = <R as SimpleRequest>::Output>,
+    {
+        if self.ids.get(entry.index) != Some(&entry.id) {
+            return Err(Error::foreign_batch_entry(entry.id.to_string()));
+        }
+        let response = self.responses[entry.index]
+            .as_ref()
+            .ok_or_else(|| Error::missing_batch_response(entry.id.to_string()))?;
+        match self.compat {
+            CompatMode::V0_37 => parse_response::<R, LatestDialect>(response),
+            CompatMode::V0_34 => parse_response::<R, v0_34::Dialect>(response),
+        }
+    }
+}
+
+fn parse_response<R, S>(response: &Value) -> Result<R::Output, Error>
+where
+    R: SimpleRequest<S>,
+    S: Dialect,
+{
+    response::Wrapper::<R::Response>::deserialize(response)
+        .map_err(Error::serde)?
+        .into_result()
+        .map(Into::into)
+}
+
+// Used to extract the error from a response to a whole batch.
+#[derive(Serialize, Deserialize)]
+struct AnyResponse(Value);
+
+impl Response for AnyResponse {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        endpoint::{abci_info, block},
+        error::ErrorDetail,
+    };
+
+    fn with_id(fixture: &str, id: &Id) -> Value {
+        let mut response: Value = serde_json::from_str(fixture).unwrap();
+        response["id"] = serde_json::to_value(id).unwrap();
+        response
+    }
+
+    fn entry<R>(ids: &[Id], index: usize) -> BatchEntry<R> {
+        BatchEntry {
+            index,
+            id: ids[index].clone(),
+            _request: PhantomData,
+        }
+    }
+
+    #[test]
+    fn responses_are_matched_by_id() {
+        let ids = vec![Id::uuid_v4(), Id::uuid_v4(), Id::uuid_v4()];
+        let abci_info_entry = entry::<abci_info::Request>(&ids, 0);
+        let block_entry = entry::<block::Request>(&ids, 1);
+        let missing_entry = entry::<abci_info::Request>(&ids, 2);
+
+        // The responses come back in reverse order, and one is missing, while
+        // another one has no ID.
+        let body = Value::Array(vec![
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32600, "message": "Invalid Request" }
+            }),
+            with_id(
+                include_str!(
+                    "../../../../tests/kvstore_fixtures/v0_37/incoming/block_at_height_1.json"
+                ),
+                &ids[1],
+            ),
+            with_id(
+                include_str!("../../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json"),
+                &ids[0],
+            ),
+        ]);
+        let responses =
+            BatchResponse::from_string(CompatMode::V0_37, ids, &body.to_string()).unwrap();
+
+        assert_eq!(
+            responses
+                .get(&abci_info_entry)
+                .unwrap()
+                .response
+                .app_version,
+            1
+        );
+        assert_eq!(
+            responses
+                .get(&block_entry)
+                .unwrap()
+                .block
+                .header
+                .height
+                .value(),
+            1
+        );
+        assert!(matches!(
+            responses.get(&missing_entry).unwrap_err().detail(),
+            ErrorDetail::MissingBatchResponse(_)
+        ));
+    }
+
+    #[test]
+    fn entries_of_other_batches_are_rejected() {
+        let ids = vec![Id::uuid_v4()];
+        let other_ids = vec![Id::uuid_v4(), Id::uuid_v4()];
+        let body = Value::Array(vec![with_id(
+            include_str!("../../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json"),
+            &ids[0],
+        )]);
+        let responses =
+            BatchResponse::from_string(CompatMode::V0_37, ids, &body.to_string()).unwrap();
+
+        for index in 0..other_ids.len() {
+            let other_entry = entry::<abci_info::Request>(&other_ids, index);
+            assert!(matches!(
+                responses.get(&other_entry).unwrap_err().detail(),
+                ErrorDetail::ForeignBatchEntry(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn rejected_batch() {
+        let body = r#"{
+            "jsonrpc": "2.0",
+            "id": -1,
+            "error": {
+                "code": -32700,
+                "message": "Parse error. Invalid JSON",
+                "data": "error unmarshaling request"
+            }
+        }"#;
+        let err =
+            BatchResponse::from_string(CompatMode::V0_37, vec![Id::uuid_v4()], body).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Response(_)));
+    }
+}
@@ -0,0 +1,96 @@
+//! Compression of the responses of the HTTP transport.
+//!
+//! The responses to `/block` and `/block_results` on busy chains weigh several
+//! megabytes of JSON, which gzip typically shrinks by 80% or more. The client
+//! thus advertises the encodings below, and transparently decodes the
+//! responses compressed with them.
+
+use std::io::{Cursor, Read};
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
+use crate::{alloc_prelude::*, Error};
+
+/// The value of the `Accept-Encoding` header of the requests.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+/// Wrap the given response body, compressed with the given
+/// `Content-Encoding`, if any, into a reader of the decoded body.
+pub fn decoder<'a>(
+    encoding: Option<&str>,
+    mut body: impl Read + 'a,
+) -> Result<Box<dyn Read + 'a>, Error> {
+    let encoding = encoding.map(|e| e.trim().to_ascii_lowercase());
+    Ok(match encoding.as_deref() {
+        None | Some("identity") => Box::new(body),
+        Some("gzip" | "x-gzip") => Box::new(GzDecoder::new(body)),
+        Some("deflate") => {
+            // The `deflate` encoding is meant to be zlib-wrapped, but some
+            // servers send raw deflate streams instead.
+            let mut compressed = Vec::new();
+            body.read_to_end(&mut compressed).map_err(Error::io)?;
+            if is_zlib(&compressed) {
+                Box::new(ZlibDecoder::new(Cursor::new(compressed)))
+            } else {
+                Box::new(DeflateDecoder::new(Cursor::new(compressed)))
+            }
+        },
+        Some(other) => {
+            return Err(Error::parse(format!(
+                "unsupported response content encoding: {other}"
+            )))
+        },
+    })
+}
+
+/// Whether the given stream starts with a zlib header.
+fn is_zlib(body: &[u8]) -> bool {
+    match body {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && ((u16::from(*cmf) << 8) | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder, ZlibEncoder},
+        Compression,
+    };
+
+    use super::*;
+
+    const BODY: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"block":{}}}"#;
+
+    #[test]
+    fn decodes_supported_encodings() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(BODY.as_bytes()).unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(BODY.as_bytes()).unwrap();
+        let mut deflate = DeflateEncoder::new(Vec::new(), Compression::default());
+        deflate.write_all(BODY.as_bytes()).unwrap();
+
+        for (encoding, body) in [
+            (None, BODY.as_bytes().to_vec()),
+            (Some("identity"), BODY.as_bytes().to_vec()),
+            (Some("gzip"), gzip.finish().unwrap()),
+            (Some("deflate"), zlib.finish().unwrap()),
+            (Some("Deflate"), deflate.finish().unwrap()),
+        ] {
+            let mut decoded = String::new();
+            decoder(encoding, body.as_slice())
+                .unwrap()
+                .read_to_string(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, BODY, "{encoding:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_encodings() {
+        assert!(decoder(Some("br"), BODY.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! HTTP connections through SOCKS5 proxies.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::io;
+
+use hyper::{service::Service, Uri};
+use tokio::net::TcpStream;
+
+use crate::{
+    alloc_prelude::*,
+    client::transport::{
+        proxy::{tunnel, ProxyUrl},
+        resolve::Resolver,
+    },
+};
+
+/// A `hyper` connector that tunnels connections through a SOCKS5 proxy.
+#[derive(Debug, Clone)]
+pub struct SocksConnector {
+    proxy_url: ProxyUrl,
+    resolver: Resolver,
+}
+
+impl SocksConnector {
+    pub fn new(proxy_url: ProxyUrl, resolver: Resolver) -> Self {
+        Self {
+            proxy_url,
+            resolver,
+        }
+    }
+}
+
+impl Service<Uri> for SocksConnector {
+    type Response = TcpStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy_url = self.proxy_url.clone();
+        let resolver = self.resolver.clone();
+        Box::pin(async move {
+            let host = uri.host().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "URI is missing its host")
+            })?;
+            let port = uri.port_u16().unwrap_or_else(|| {
+                if uri.scheme_str() == Some("https") {
+                    443
+                } else {
+                    80
+                }
+            });
+            tunnel(&proxy_url, host, port, &resolver).await
+        })
+    }
+}
@@ -0,0 +1,271 @@
+//! Timing of the phases of the connections and calls of the HTTP client.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use std::{
+    io::{self, IoSlice},
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+    vec,
+};
+
+use hyper::{
+    client::connect::{dns::Name, Connected, Connection, HttpConnector},
+    service::Service,
+    Uri,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{
+    alloc_prelude::*,
+    client::transport::{
+        resolve::{resolve, Resolver},
+        timing::CallTimings,
+    },
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+tokio::task_local! {
+    // The phases of the connection being opened by the current task, which
+    // the layers of the connector record their timings into.
+    static CONNECTING: Arc<Mutex<ConnectionPhases>>;
+}
+
+/// The timings of the phases of a connection.
+#[derive(Clone, Debug, Default)]
+struct ConnectionPhases {
+    dns: Option<Duration>,
+    connect: Option<Duration>,
+    tls: Option<Duration>,
+}
+
+/// Record the duration of the DNS resolution of the connection being
+/// opened, if any.
+pub fn record_dns(duration: Duration) {
+    let _ = CONNECTING.try_with(|phases| phases.lock().unwrap().dns = Some(duration));
+}
+
+/// Record the duration of the TLS handshake of the connection being opened,
+/// if any.
+pub fn record_tls(duration: Duration) {
+    let _ = CONNECTING.try_with(|phases| phases.lock().unwrap().tls = Some(duration));
+}
+
+/// An HTTP connector which resolves host names with a [`Resolver`], and
+/// times their resolution.
+pub type TcpConnector = HttpConnector<TimedResolver>;
+
+pub fn tcp_connector(resolver: Resolver) -> TcpConnector {
+    let mut connector = HttpConnector::new_with_resolver(TimedResolver(resolver));
+    // Secure connections are handled by the wrapping connectors.
+    connector.enforce_http(false);
+    connector
+}
+
+/// A DNS resolver which records the time taken by each resolution.
+#[derive(Clone, Debug)]
+pub struct TimedResolver(Resolver);
+
+impl Service<Name> for TimedResolver {
+    type Response = vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let resolver = self.0.clone();
+        Box::pin(async move {
+            let start = Instant::now();
+            // The connector sets the port of the resolved addresses.
+            let addrs = resolve(&resolver, name.as_str(), 0).await?;
+            record_dns(start.elapsed());
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+/// A `hyper` connector which times the phases of the connections opened by
+/// the inner connector, and of the requests sent over them.
+#[derive(Clone, Debug)]
+pub struct Timed<C>(C);
+
+impl<C> Timed<C> {
+    pub fn new(inner: C) -> Self {
+        Self(inner)
+    }
+}
+
+impl<C> Service<Uri> for Timed<C>
+where
+    C: Service<Uri>,
+    C::Response: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+    C::Future: Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = TimedStream<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connecting = self.0.call(uri);
+        Box::pin(async move {
+            let phases = Arc::new(Mutex::new(ConnectionPhases::default()));
+            let start = Instant::now();
+            let stream = CONNECTING
+                .scope(phases.clone(), connecting)
+                .await
+                .map_err(Into::into)?;
+
+            let mut phases = phases.lock().unwrap().clone();
+            phases.connect = Some(
+                start
+                    .elapsed()
+                    .saturating_sub(phases.dns.unwrap_or_default())
+                    .saturating_sub(phases.tls.unwrap_or_default()),
+            );
+            Ok(TimedStream {
+                inner: stream,
+                timeline: Timeline(Arc::new(Mutex::new(StreamTimeline {
+                    connection: Some(phases),
+                    ..Default::default()
+                }))),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct StreamTimeline {
+    // Taken by the first call over the connection.
+    connection: Option<ConnectionPhases>,
+    first_write: Option<Instant>,
+    last_write: Option<Instant>,
+    first_read: Option<Instant>,
+}
+
+impl StreamTimeline {
+    fn wrote(&mut self, start: Instant, end: Instant) {
+        // HTTP/1.1 requests are sent one at a time over a connection, so
+        // writing after reading a response means a new request.
+        if self.first_read.is_some() {
+            self.first_write = None;
+            self.first_read = None;
+        }
+        self.first_write.get_or_insert(start);
+        self.last_write = Some(end);
+    }
+
+    fn read(&mut self, at: Instant) {
+        if self.last_write.is_some() && self.first_read.is_none() {
+            self.first_read = Some(at);
+        }
+    }
+}
+
+/// The timeline of a connection, which `hyper` attaches to the extensions
+/// of the responses received over the connection.
+#[derive(Clone, Debug)]
+pub struct Timeline(Arc<Mutex<StreamTimeline>>);
+
+impl Timeline {
+    /// Fill in the timings of the connection and of the latest request
+    /// sent over it.
+    pub fn fill(&self, timings: &mut CallTimings) {
+        let mut timeline = self.0.lock().unwrap();
+        if let Some(phases) = timeline.connection.take() {
+            timings.dns = phases.dns;
+            timings.connect = phases.connect;
+            timings.tls = phases.tls;
+        }
+        if let (Some(first_write), Some(last_write)) = (timeline.first_write, timeline.last_write) {
+            timings.request_write = Some(last_write.saturating_duration_since(first_write));
+            timings.first_byte = timeline
+                .first_read
+                .map(|first_read| first_read.saturating_duration_since(last_write));
+        }
+    }
+}
+
+/// A connection whose reads and writes are timed.
+pub struct TimedStream<T> {
+    inner: T,
+    timeline: Timeline,
+}
+
+impl<T: Connection> Connection for TimedStream<T> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().extra(self.timeline.clone())
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for TimedStream<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if buf.filled().len() > filled {
+            self.timeline.0.lock().unwrap().read(Instant::now());
+        }
+        result
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for TimedStream<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let start = Instant::now();
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = result {
+            if n > 0 {
+                self.timeline.0.lock().unwrap().wrote(start, Instant::now());
+            }
+        }
+        result
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let start = Instant::now();
+        let result = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(n)) = result {
+            if n > 0 {
+                self.timeline.0.lock().unwrap().wrote(start, Instant::now());
+            }
+        }
+        result
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
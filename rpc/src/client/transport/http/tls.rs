@@ -0,0 +1,220 @@
+//! TLS connections of the HTTP client.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::{io, time::Instant};
+
+use hyper::{
+    client::connect::{Connected, Connection},
+    service::Service,
+    Uri,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use super::timed::record_tls;
+use crate::{
+    alloc_prelude::*,
+    client::transport::tls::{TlsClient, TlsConfig},
+    Error,
+};
+
+impl TlsConfig {
+    /// Wrap the given connector so as to secure its connections according
+    /// to these options.
+    pub(crate) fn connector<C>(&self, inner: C) -> Result<TlsConnector<C>, Error> {
+        Ok(TlsConnector {
+            inner,
+            tls: self.client()?,
+        })
+    }
+}
+
+/// A `hyper` connector that secures the connections of the inner connector
+/// with TLS.
+///
+/// Unlike the `hyper-rustls` connector, the name used to verify the server
+/// certificate can be overridden.
+#[derive(Clone)]
+pub struct TlsConnector<C> {
+    inner: C,
+    tls: TlsClient,
+}
+
+impl<C> core::fmt::Debug for TlsConnector<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TlsConnector")
+            .field("tls", &self.tls)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> Service<Uri> for TlsConnector<C>
+where
+    C: Service<Uri>,
+    C::Response: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+    C::Future: Send + 'static,
+    C::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Response = TlsStream<C::Response>;
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let host = uri.host().map(ToString::to_string);
+        let connecting = self.inner.call(uri);
+        let tls = self.tls.clone();
+        Box::pin(async move {
+            let host = host.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "URI is missing its host")
+            })?;
+            let stream = connecting.await.map_err(Into::into)?;
+            let start = Instant::now();
+            let stream = tls.connect(&host, stream).await?;
+            record_tls(start.elapsed());
+            Ok(TlsStream(stream))
+        })
+    }
+}
+
+/// A TLS connection established by a [`TlsConnector`].
+pub struct TlsStream<T>(tokio_rustls::client::TlsStream<T>);
+
+impl<T: Connection> Connection for TlsStream<T> {
+    fn connected(&self) -> Connected {
+        self.0.get_ref().0.connected()
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncRead for TlsStream<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncWrite for TlsStream<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rustls::RootCertStore;
+
+    use super::*;
+    use crate::client::transport::tls::{parse_certificates, parse_private_key};
+
+    const CA: &[u8] = include_bytes!("../../../../tests/tls_fixtures/ca.pem");
+    const CLIENT_CERT: &[u8] = include_bytes!("../../../../tests/tls_fixtures/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../../tests/tls_fixtures/client.key");
+    const SERVER_CERT: &[u8] = include_bytes!("../../../../tests/tls_fixtures/server.pem");
+    const SERVER_KEY: &[u8] = include_bytes!("../../../../tests/tls_fixtures/server.key");
+
+    // Serve a single `abci_info` response over TLS, requiring a client
+    // certificate issued by the test CA.
+    async fn serve_abci_info(listener: tokio::net::TcpListener) -> io::Result<()> {
+        use rustls::{AllowAnyAuthenticatedClient, ServerConfig};
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let mut client_roots = RootCertStore::empty();
+        client_roots
+            .add(&parse_certificates(CA).unwrap()[0])
+            .unwrap();
+        let mut config = ServerConfig::new(AllowAnyAuthenticatedClient::new(client_roots));
+        config
+            .set_single_cert(
+                parse_certificates(SERVER_CERT).unwrap(),
+                parse_private_key(SERVER_KEY).unwrap(),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let (stream, _) = listener.accept().await?;
+        let mut reader = BufReader::new(acceptor.accept(stream).await?);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await?;
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut request = vec![0; content_length];
+        reader.read_exact(&mut request).await?;
+
+        let body = include_str!("../../../../tests/kvstore_fixtures/v0_37/incoming/abci_info.json");
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        reader.get_mut().write_all(response.as_bytes()).await?;
+        reader.get_mut().shutdown().await
+    }
+
+    #[tokio::test]
+    async fn mutual_tls() {
+        use crate::{Client, HttpClient};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("https://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_abci_info(listener));
+
+        let tls_config = TlsConfig::new()
+            .native_roots(false)
+            .webpki_roots(false)
+            .add_root_certificates_pem(CA)
+            .unwrap()
+            .server_name("rpc.example");
+
+        // Without a client certificate, the server rejects the connection.
+        let client = HttpClient::builder(url.parse().unwrap())
+            .tls_config(tls_config.clone())
+            .build()
+            .unwrap();
+        client.abci_info().await.unwrap_err();
+        server.await.unwrap().unwrap_err();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("https://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_abci_info(listener));
+
+        let client = HttpClient::builder(url.parse().unwrap())
+            .tls_config(tls_config.client_auth_pem(CLIENT_CERT, CLIENT_KEY).unwrap())
+            .build()
+            .unwrap();
+        let response = client.abci_info().await.unwrap();
+        assert_eq!(response.app_version, 1);
+        server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+//! Instrumentation of the RPC calls of a client, e.g. to export metrics.
+
+use alloc::sync::Arc;
+use core::{fmt, time::Duration};
+
+use crate::alloc_prelude::*;
+use crate::{error::ErrorDetail, Error};
+
+/// The class of the error which failed an RPC call, coarse enough to be
+/// used as a metric label.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorClass {
+    /// The request could not be sent, or the response could not be read,
+    /// e.g. because the connection failed.
+    Transport,
+    /// The call timed out.
+    Timeout,
+    /// The node responded with a JSON-RPC error.
+    Rpc,
+    /// The response could not be parsed.
+    Parse,
+    /// Any other error, e.g. an invalid request.
+    Other,
+}
+
+impl ErrorClass {
+    /// The name of the class, in snake case.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transport => "transport",
+            Self::Timeout => "timeout",
+            Self::Rpc => "rpc",
+            Self::Parse => "parse",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl From<&Error> for ErrorClass {
+    fn from(error: &Error) -> Self {
+        match error.detail() {
+            ErrorDetail::Io(_)
+            | ErrorDetail::Http(_)
+            | ErrorDetail::Hyper(_)
+            | ErrorDetail::WebSocket(_)
+            | ErrorDetail::Tungstenite(_)
+            | ErrorDetail::ChannelSend(_)
+            | ErrorDetail::RateLimited(_)
+            | ErrorDetail::Grpc(_) => Self::Transport,
+            ErrorDetail::Timeout(_)
+            | ErrorDetail::RequestTimeout(_)
+            | ErrorDetail::WebSocketTimeout(_)
+            | ErrorDetail::StaleSubscriptions(_) => Self::Timeout,
+            ErrorDetail::Response(_) | ErrorDetail::MethodNotFound(_) => Self::Rpc,
+            ErrorDetail::Parse(_)
+            | ErrorDetail::Serde(_)
+            | ErrorDetail::MalformedJson(_)
+            | ErrorDetail::MismatchResponse(_)
+            | ErrorDetail::MissingBatchResponse(_)
+            | ErrorDetail::UnsupportedRpcVersion(_) => Self::Parse,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The outcome of a completed RPC call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallOutcome {
+    /// The time from sending the request to parsing the response.
+    pub latency: Duration,
+    /// The size of the JSON-RPC request, in bytes.
+    pub request_size: usize,
+    /// The size of the JSON-RPC response, in bytes, if one was received.
+    pub response_size: Option<usize>,
+    /// The class of the error which failed the call, if any.
+    pub error: Option<ErrorClass>,
+}
+
+/// Hooks called around every RPC call of a client, e.g. to export metrics
+/// or to open tracing spans.
+///
+/// Calls are identified by the name of their RPC method (`batch` for batches
+/// of requests sent over HTTP). Both hooks are called from the task
+/// performing the call, so they must not block.
+pub trait Instrumentation: Send + Sync {
+    /// Called before the request of the given call is sent.
+    fn request_started(&self, method: &str, request_size: usize) {
+        let _ = (method, request_size);
+    }
+
+    /// Called once the given call has completed, successfully or not.
+    fn request_completed(&self, method: &str, outcome: &CallOutcome);
+}
+
+/// Measures a call to report it to the instrumentations of a client.
+pub(crate) struct CallMeter<'a> {
+    instruments: &'a [Arc<dyn Instrumentation>],
+    method: &'a str,
+    request_size: usize,
+    start: std::time::Instant,
+}
+
+impl<'a> CallMeter<'a> {
+    pub fn start(
+        instruments: &'a [Arc<dyn Instrumentation>],
+        method: &'a str,
+        request_size: usize,
+    ) -> Self {
+        for instrument in instruments {
+            instrument.request_started(method, request_size);
+        }
+        Self {
+            instruments,
+            method,
+            request_size,
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Report the completion of the call, given the size of its response,
+    /// if one was received, and its result.
+    pub fn complete<T>(self, response_size: Option<usize>, result: &Result<T, Error>) {
+        if self.instruments.is_empty() {
+            return;
+        }
+        let outcome = CallOutcome {
+            latency: self.start.elapsed(),
+            request_size: self.request_size,
+            response_size,
+            error: result.as_ref().err().map(ErrorClass::from),
+        };
+        for instrument in self.instruments {
+            instrument.request_completed(self.method, &outcome);
+        }
+    }
+}
+
+#[cfg(feature = "prometheus")]
+pub use prometheus::PrometheusMetrics;
+
+#[cfg(feature = "prometheus")]
+mod prometheus {
+    use alloc::{collections::BTreeMap, sync::Arc};
+    use std::sync::Mutex;
+
+    use tendermint_std_ext::metrics::{Encoder, Histogram, MetricType};
+
+    use super::{CallOutcome, Instrumentation};
+    use crate::alloc_prelude::*;
+
+    /// The upper bounds of the buckets of the latency histograms, in seconds.
+    const LATENCY_BUCKETS: [f64; 11] = [
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ];
+
+    /// An [`Instrumentation`](super::Instrumentation) collecting the usual
+    /// metrics of RPC calls per method, rendered in the Prometheus text
+    /// exposition format by [`PrometheusMetrics::render`]:
+    ///
+    /// - `tendermint_rpc_requests_in_flight`, a gauge of the calls in progress;
+    /// - `tendermint_rpc_requests_total`, a counter of the completed calls,
+    ///   labelled with their `outcome`: `ok` or the class of their error;
+    /// - `tendermint_rpc_request_duration_seconds`, a histogram of the
+    ///   latency of the calls;
+    /// - `tendermint_rpc_request_bytes_total` and
+    ///   `tendermint_rpc_response_bytes_total`, counters of the sizes of the
+    ///   requests and responses.
+    ///
+    /// The metrics are shared between the clones of a `PrometheusMetrics`, so
+    /// that a clone can be given to the client builder while the original is
+    /// served by the metrics endpoint of the application.
+    #[derive(Clone, Debug, Default)]
+    pub struct PrometheusMetrics {
+        inner: Arc<Mutex<BTreeMap<String, MethodMetrics>>>,
+    }
+
+    #[derive(Debug)]
+    struct MethodMetrics {
+        in_flight: u64,
+        outcomes: BTreeMap<&'static str, u64>,
+        latency: Histogram,
+        request_bytes: u64,
+        response_bytes: u64,
+    }
+
+    impl Default for MethodMetrics {
+        fn default() -> Self {
+            Self {
+                in_flight: 0,
+                outcomes: BTreeMap::new(),
+                latency: Histogram::new(&LATENCY_BUCKETS),
+                request_bytes: 0,
+                response_bytes: 0,
+            }
+        }
+    }
+
+    impl PrometheusMetrics {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Render the metrics in the Prometheus text exposition format.
+        pub fn render(&self) -> String {
+            let methods = self.inner.lock().unwrap();
+            let mut encoder = Encoder::new();
+
+            let name = "tendermint_rpc_requests_in_flight";
+            encoder.family(name, MetricType::Gauge, "RPC calls in progress.");
+            for (method, m) in methods.iter() {
+                encoder.sample(name, &[("method", method.as_str())], m.in_flight);
+            }
+
+            let name = "tendermint_rpc_requests_total";
+            encoder.family(name, MetricType::Counter, "Completed RPC calls.");
+            for (method, m) in methods.iter() {
+                for (outcome, count) in &m.outcomes {
+                    encoder.sample(
+                        name,
+                        &[("method", method.as_str()), ("outcome", *outcome)],
+                        count,
+                    );
+                }
+            }
+
+            let name = "tendermint_rpc_request_duration_seconds";
+            encoder.family(name, MetricType::Histogram, "Latency of the RPC calls.");
+            for (method, m) in methods.iter() {
+                encoder.histogram(name, &[("method", method.as_str())], &m.latency);
+            }
+
+            let name = "tendermint_rpc_request_bytes_total";
+            encoder.family(name, MetricType::Counter, "Size of the RPC requests.");
+            for (method, m) in methods.iter() {
+                encoder.sample(name, &[("method", method.as_str())], m.request_bytes);
+            }
+
+            let name = "tendermint_rpc_response_bytes_total";
+            encoder.family(name, MetricType::Counter, "Size of the RPC responses.");
+            for (method, m) in methods.iter() {
+                encoder.sample(name, &[("method", method.as_str())], m.response_bytes);
+            }
+
+            encoder.finish()
+        }
+    }
+
+    impl Instrumentation for PrometheusMetrics {
+        fn request_started(&self, method: &str, _request_size: usize) {
+            let mut methods = self.inner.lock().unwrap();
+            methods.entry(method.to_string()).or_default().in_flight += 1;
+        }
+
+        fn request_completed(&self, method: &str, outcome: &CallOutcome) {
+            let mut methods = self.inner.lock().unwrap();
+            let m = methods.entry(method.to_string()).or_default();
+            m.in_flight = m.in_flight.saturating_sub(1);
+
+            let label = outcome.error.map_or("ok", |class| class.as_str());
+            *m.outcomes.entry(label).or_default() += 1;
+
+            m.latency.observe(outcome.latency.as_secs_f64());
+
+            m.request_bytes += outcome.request_size as u64;
+            m.response_bytes += outcome.response_size.unwrap_or(0) as u64;
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use core::time::Duration;
+
+        use super::*;
+        use crate::client::transport::instrument::ErrorClass;
+
+        #[test]
+        fn renders_metrics_per_method() {
+            let metrics = PrometheusMetrics::new();
+            let observed = metrics.clone();
+            observed.request_started("status", 60);
+            observed.request_completed(
+                "status",
+                &CallOutcome {
+                    latency: Duration::from_millis(30),
+                    request_size: 60,
+                    response_size: Some(1200),
+                    error: None,
+                },
+            );
+            observed.request_started("status", 60);
+            observed.request_completed(
+                "status",
+                &CallOutcome {
+                    latency: Duration::from_secs(20),
+                    request_size: 60,
+                    response_size: None,
+                    error: Some(ErrorClass::Timeout),
+                },
+            );
+            observed.request_started("block", 70);
+
+            let rendered = metrics.render();
+            for line in [
+                "tendermint_rpc_requests_in_flight{method=\"block\"} 1",
+                "tendermint_rpc_requests_in_flight{method=\"status\"} 0",
+                "tendermint_rpc_requests_total{method=\"status\",outcome=\"ok\"} 1",
+                "tendermint_rpc_requests_total{method=\"status\",outcome=\"timeout\"} 1",
+                "tendermint_rpc_request_duration_seconds_bucket{method=\"status\",le=\"0.025\"} 0",
+                "tendermint_rpc_request_duration_seconds_bucket{method=\"status\",le=\"0.05\"} 1",
+                "tendermint_rpc_request_duration_seconds_bucket{method=\"status\",le=\"10\"} 1",
+                "tendermint_rpc_request_duration_seconds_bucket{method=\"status\",le=\"+Inf\"} 2",
+                "tendermint_rpc_request_duration_seconds_count{method=\"status\"} 2",
+                "tendermint_rpc_request_bytes_total{method=\"status\"} 120",
+                "tendermint_rpc_response_bytes_total{method=\"status\"} 1200",
+                "# TYPE tendermint_rpc_request_duration_seconds histogram",
+            ] {
+                assert!(
+                    rendered.lines().any(|l| l == line),
+                    "missing {line:?} in\n{rendered}"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_errors() {
+        let cases = [
+            (
+                Error::request_timeout("status".into(), Duration::from_secs(1)),
+                ErrorClass::Timeout,
+            ),
+            (Error::method_not_found("foo".into()), ErrorClass::Rpc),
+            (Error::parse("bad".into()), ErrorClass::Parse),
+            (Error::invalid_params("bad".into()), ErrorClass::Other),
+        ];
+        for (error, class) in cases {
+            assert_eq!(ErrorClass::from(&error), class);
+        }
+    }
+}
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 
 use crate::dialect::{v0_37, Dialect};
 use crate::{
+    alloc_prelude::*,
     client::{
         subscription::SubscriptionTx,
         sync::{unbounded, ChannelRx, ChannelTx},
@@ -13,13 +14,17 @@ use crate::{
         Client,
     },
     event::Event,
-    prelude::*,
     query::Query,
     request::SimpleRequest,
     utils::uuid_str,
     Error, Method, Request, Response, Subscription, SubscriptionClient,
 };
 
+mod fixture;
+pub use fixture::{
+    Fixture, FixtureMatcher, Interaction, RecordedEvent, RecordingClient, RecordingDriver,
+};
+
 /// A mock client implementation for use in testing.
 ///
 /// ## Examples
@@ -249,7 +254,7 @@ mod test {
     use tokio::fs;
 
     use super::*;
-    use crate::query::EventType;
+    use crate::{error::ErrorDetail, query::EventType};
 
     async fn read_json_fixture(version: &str, name: &str) -> String {
         fs::read_to_string(
@@ -262,6 +267,64 @@ mod test {
         .unwrap()
     }
 
+    // Serves the given genesis file in chunks of the given size.
+    struct GenesisChunks(Vec<u8>, usize);
+
+    impl MockRequestMatcher for GenesisChunks {
+        fn response_for<R, S>(&self, request: R) -> Option<Result<R::Response, Error>>
+        where
+            R: Request<S>,
+            S: Dialect,
+        {
+            let request: serde_json::Value = serde_json::from_str(&request.into_json()).unwrap();
+            let chunk: usize = request["params"]["chunk"].as_str()?.parse().unwrap();
+            let chunks: Vec<_> = self.0.chunks(self.1).collect();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {
+                    "chunk": chunk.to_string(),
+                    "total": chunks.len().to_string(),
+                    "data": String::from_utf8(subtle_encoding::base64::encode(chunks[chunk])).unwrap(),
+                },
+            });
+            Some(R::Response::from_string(response.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn genesis_chunked() {
+        let genesis = read_json_fixture("v0_37", "genesis").await;
+        let genesis: serde_json::Value = serde_json::from_str(&genesis).unwrap();
+        let genesis = serde_json::to_vec(&genesis["result"]["genesis"]).unwrap();
+
+        let (client, driver) = MockClient::new(GenesisChunks(genesis.clone(), 100));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let chunks: Vec<_> = client
+            .genesis_chunked_stream()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), (genesis.len() + 99) / 100);
+        assert_eq!(chunks.concat(), genesis);
+
+        let assembled = client
+            .genesis_chunked_assembled::<Option<serde_json::Value>>(genesis.len())
+            .await
+            .unwrap();
+        assert_eq!("dockerchain", assembled.chain_id.as_str());
+
+        let err = client
+            .genesis_chunked_assembled::<Option<serde_json::Value>>(genesis.len() - 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::GenesisTooLarge(_)));
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
     mod v0_34 {
         use super::*;
         use crate::dialect::v0_34::Event as RpcEvent;
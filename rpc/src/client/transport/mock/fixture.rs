@@ -0,0 +1,504 @@
+//! Recording of the requests, responses and events of a live client into
+//! fixtures, and their replay by a [`MockClient`].
+//!
+//! A [`RecordingClient`] wraps a live client, capturing the responses to its
+//! requests and the events of its subscriptions into a [`Fixture`], which can
+//! be saved as a JSON file. Tests then replay the fixture hermetically: a
+//! [`FixtureMatcher`] serves the recorded responses to a [`MockClient`],
+//! matching the requests by method and parameters, and
+//! [`Fixture::replay_events`] publishes the recorded events to the
+//! subscriptions of the [`MockClient`], at the pace they were recorded.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{
+//!     client::{Fixture, FixtureMatcher, RecordingClient},
+//!     query::EventType,
+//!     Client, MockClient, SubscriptionClient, WebSocketClient,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Record against a live node.
+//!     let (client, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!     let (client, recorder) = RecordingClient::new(client);
+//!     tokio::spawn(async move { recorder.run().await });
+//!
+//!     client.status().await.unwrap();
+//!     let blocks = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+//!     let _ = blocks.take(3).collect::<Vec<_>>().await;
+//!     client.fixture().save("tests/fixtures/three_blocks.json").unwrap();
+//!
+//!     // Replay in a test.
+//!     let fixture = Fixture::load("tests/fixtures/three_blocks.json").unwrap();
+//!     let (mock, driver) = MockClient::new(FixtureMatcher::new(fixture.clone()));
+//!     tokio::spawn(async move { driver.run().await });
+//!
+//!     mock.status().await.unwrap();
+//!     let blocks = mock.subscribe(EventType::NewBlock.into()).await.unwrap();
+//!     fixture.replay_events(&mock).await.unwrap();
+//!     assert_eq!(blocks.take(3).count().await, 3);
+//! }
+//! ```
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::{path::Path, sync::Mutex, time::Instant};
+
+use async_trait::async_trait;
+use futures::{
+    stream::{BoxStream, SelectAll},
+    StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{MockClient, MockRequestMatcher};
+use crate::dialect::{v0_37, Dialect};
+use crate::{
+    alloc_prelude::*,
+    client::{
+        subscription::SubscriptionTx,
+        sync::{unbounded, ChannelRx, ChannelTx},
+        Client,
+    },
+    error::ErrorDetail,
+    event::{DialectEvent, Event},
+    query::Query,
+    request::{RequestMessage, SimpleRequest},
+    response_error::ResponseError,
+    Error, Method, Request, Response, Subscription, SubscriptionClient,
+};
+
+/// Recorded requests, responses and events, serializable as JSON.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    /// The requests and their responses, in the order they were performed.
+    #[serde(default)]
+    pub interactions: Vec<Interaction>,
+    /// The events received by the subscriptions, in the order they were
+    /// received.
+    #[serde(default)]
+    pub events: Vec<RecordedEvent>,
+}
+
+/// A request and its response.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: Method,
+    /// The parameters of the request, as sent in the JSON-RPC request.
+    pub params: Value,
+    /// The result of a successful response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// The error of a failed response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResponseError>,
+}
+
+/// An event received by a subscription.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// The time elapsed since the previous event (or since the start of the
+    /// recording, for the first event), in milliseconds.
+    pub delay_ms: u64,
+    /// The event, in the format of the v0.37 dialect of the RPC.
+    pub event: Value,
+}
+
+impl Fixture {
+    /// Load a fixture from the given JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let json = std::fs::read_to_string(path).map_err(Error::io)?;
+        serde_json::from_str(&json).map_err(Error::serde)
+    }
+
+    /// Save the fixture as the given JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(self).map_err(Error::serde)?;
+        std::fs::write(path, json).map_err(Error::io)
+    }
+
+    /// Publish the recorded events to the subscriptions of the given mock
+    /// client, waiting for the recorded delay before each event.
+    pub async fn replay_events<M: MockRequestMatcher>(
+        &self,
+        client: &MockClient<M>,
+    ) -> Result<(), Error> {
+        for recorded in &self.events {
+            tokio::time::sleep(Duration::from_millis(recorded.delay_ms)).await;
+            let event: DialectEvent<v0_37::Event> =
+                serde_json::from_value(recorded.event.clone()).map_err(Error::serde)?;
+            client.publish(&event.into());
+        }
+        Ok(())
+    }
+}
+
+/// A [`MockRequestMatcher`] serving the responses of a [`Fixture`].
+///
+/// A request is matched with the recorded interactions of the same method
+/// and parameters. If several interactions match, e.g. `status` requests
+/// recorded at different times, they are served in the order they were
+/// recorded, the last one being served again once all were served.
+#[derive(Debug)]
+pub struct FixtureMatcher {
+    interactions: Vec<Interaction>,
+    served: Mutex<Vec<bool>>,
+}
+
+impl FixtureMatcher {
+    pub fn new(fixture: Fixture) -> Self {
+        let served = vec![false; fixture.interactions.len()];
+        Self {
+            interactions: fixture.interactions,
+            served: Mutex::new(served),
+        }
+    }
+}
+
+impl MockRequestMatcher for FixtureMatcher {
+    fn response_for<R, S>(&self, request: R) -> Option<Result<R::Response, Error>>
+    where
+        R: Request<S>,
+        S: Dialect,
+    {
+        let method = request.method();
+        let params = match serde_json::to_value(&request) {
+            Ok(params) => params,
+            Err(e) => return Some(Err(Error::serde(e))),
+        };
+        let matching: Vec<usize> = self
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.method == method && i.params == params)
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut served = self.served.lock().unwrap();
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&index| !served[index])
+            .or_else(|| matching.last().copied())?;
+        served[index] = true;
+
+        let interaction = &self.interactions[index];
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "",
+            "result": interaction.result,
+            "error": interaction.error,
+        });
+        Some(R::Response::from_string(response.to_string()))
+    }
+}
+
+/// A client recording the responses to the requests and the events of the
+/// subscriptions of the wrapped client into a [`Fixture`].
+///
+/// Only the JSON-RPC errors are recorded, the requests failing with any other
+/// error (e.g. a transport error) are not. The requests are performed with the
+/// latest dialect of the RPC, so the wrapped client must be connected to a
+/// node supporting it.
+///
+/// The events are recorded by a [`RecordingDriver`], which must be run for
+/// the subscriptions to receive anything.
+pub struct RecordingClient<C> {
+    inner: C,
+    fixture: Arc<Mutex<Fixture>>,
+    driver_tx: ChannelTx<DriverCommand>,
+}
+
+impl<C> RecordingClient<C> {
+    /// Wrap the given client, returning the driver recording the events of
+    /// its subscriptions.
+    pub fn new(inner: C) -> (Self, RecordingDriver) {
+        let fixture = Arc::new(Mutex::new(Fixture::default()));
+        let (driver_tx, driver_rx) = unbounded();
+        let driver = RecordingDriver {
+            fixture: fixture.clone(),
+            rx: driver_rx,
+            streams: SelectAll::new(),
+            last_event: Instant::now(),
+        };
+        let client = Self {
+            inner,
+            fixture,
+            driver_tx,
+        };
+        (client, driver)
+    }
+
+    /// A snapshot of what was recorded so far.
+    pub fn fixture(&self) -> Fixture {
+        self.fixture.lock().unwrap().clone()
+    }
+
+    /// The wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C: core::fmt::Debug> core::fmt::Debug for RecordingClient<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecordingClient")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request whose output is its response, so that the response can be
+/// recorded before being converted into the output of the request.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct Capture<R>(R);
+
+impl<R: RequestMessage> RequestMessage for Capture<R> {
+    const METHOD: Method = R::METHOD;
+}
+
+impl<R: Request<S>, S: Dialect> Request<S> for Capture<R> {
+    type Response = R::Response;
+}
+
+impl<R: Request<S>, S: Dialect> SimpleRequest<S> for Capture<R> {
+    type Output = R::Response;
+}
+
+#[async_trait]
+impl<C> Client for RecordingClient<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        let method = request.method();
+        let params = serde_json::to_value(&request).map_err(Error::serde)?;
+        let response = self.inner.perform(Capture(request)).await;
+        let (result, error) = match &response {
+            Ok(response) => (
+                Some(serde_json::to_value(response).map_err(Error::serde)?),
+                None,
+            ),
+            Err(e) => match e.detail() {
+                ErrorDetail::Response(e) => (None, Some(e.source.clone())),
+                _ => (None, None),
+            },
+        };
+        if result.is_some() || error.is_some() {
+            self.fixture.lock().unwrap().interactions.push(Interaction {
+                method,
+                params,
+                result,
+                error,
+            });
+        }
+        response.map(Into::into)
+    }
+}
+
+#[async_trait]
+impl<C> SubscriptionClient for RecordingClient<C>
+where
+    C: SubscriptionClient + Send + Sync,
+{
+    async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
+        let subscription = self.inner.subscribe(query.clone()).await?;
+        let id = subscription.id().to_string();
+        let (tx, rx) = unbounded();
+        self.driver_tx
+            .send(DriverCommand::Record { subscription, tx })?;
+        Ok(Subscription::new(id, query, rx))
+    }
+
+    async fn unsubscribe(&self, query: Query) -> Result<(), Error> {
+        self.inner.unsubscribe(query).await
+    }
+
+    fn close(self) -> Result<(), Error> {
+        let _ = self.driver_tx.send(DriverCommand::Terminate);
+        self.inner.close()
+    }
+}
+
+#[derive(Debug)]
+enum DriverCommand {
+    Record {
+        subscription: Subscription,
+        tx: SubscriptionTx,
+    },
+    Terminate,
+}
+
+/// Records the events of the subscriptions of a [`RecordingClient`], while
+/// forwarding them to its subscribers.
+///
+/// Runs until the client is closed, or dropped.
+pub struct RecordingDriver {
+    fixture: Arc<Mutex<Fixture>>,
+    rx: ChannelRx<DriverCommand>,
+    streams: SelectAll<BoxStream<'static, (Result<Event, Error>, SubscriptionTx)>>,
+    last_event: Instant,
+}
+
+impl RecordingDriver {
+    pub async fn run(mut self) -> Result<(), Error> {
+        loop {
+            tokio::select! {
+                Some((event, tx)) = self.streams.next() => self.forward(event, &tx)?,
+                Some(cmd) = self.rx.recv() => match cmd {
+                    DriverCommand::Record { subscription, tx } => {
+                        let stream = subscription.map(move |event| (event, tx.clone()));
+                        self.streams.push(stream.boxed());
+                    },
+                    DriverCommand::Terminate => return Ok(()),
+                },
+                else => return Ok(()),
+            }
+        }
+    }
+
+    fn forward(&mut self, event: Result<Event, Error>, tx: &SubscriptionTx) -> Result<(), Error> {
+        if let Ok(event) = &event {
+            let now = Instant::now();
+            let recorded = RecordedEvent {
+                delay_ms: now.duration_since(self.last_event).as_millis() as u64,
+                event: serde_json::to_value(DialectEvent::<v0_37::Event>::from(event.clone()))
+                    .map_err(Error::serde)?,
+            };
+            self.last_event = now;
+            self.fixture.lock().unwrap().events.push(recorded);
+        }
+        // The subscriber may have dropped its subscription, in which case the
+        // events are still recorded until it unsubscribes.
+        let _ = tx.send(event);
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for RecordingDriver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecordingDriver")
+            .field("streams", &self.streams.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tendermint::block::Height;
+
+    use super::*;
+    use crate::{client::transport::mock::MockRequestMethodMatcher, query::EventType};
+
+    fn read_json_fixture(name: &str) -> String {
+        std::fs::read_to_string(
+            PathBuf::from("./tests/kvstore_fixtures/v0_37/incoming")
+                .join(name.to_owned() + ".json"),
+        )
+        .unwrap()
+    }
+
+    fn read_event(name: &str) -> Event {
+        DialectEvent::<v0_37::Event>::from_string(read_json_fixture(name))
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::AbciInfo, Ok(read_json_fixture("abci_info")))
+            .map(Method::Block, Ok(read_json_fixture("block_at_height_10")));
+        let (live, live_driver) = MockClient::new(matcher);
+        tokio::spawn(live_driver.run());
+        let (client, recorder) = RecordingClient::new(live);
+        let recorder = tokio::spawn(recorder.run());
+
+        let abci_info = client.abci_info().await.unwrap();
+        let block = client.block(Height::from(10_u32)).await.unwrap();
+        let subscription = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+        let events = [
+            read_event("subscribe_newblock_0"),
+            read_event("subscribe_newblock_1"),
+        ];
+        for event in &events {
+            client.inner().publish(event);
+        }
+        let received: Vec<_> = subscription.take(2).map(Result::unwrap).collect().await;
+        assert_eq!(received, events);
+
+        let fixture = client.fixture();
+        assert_eq!(fixture.interactions.len(), 2);
+        assert_eq!(fixture.events.len(), 2);
+        client.close().unwrap();
+        recorder.await.unwrap().unwrap();
+
+        // Round trip through JSON, as through a fixture file.
+        let fixture: Fixture =
+            serde_json::from_str(&serde_json::to_string(&fixture).unwrap()).unwrap();
+        let (mock, mock_driver) = MockClient::new(FixtureMatcher::new(fixture.clone()));
+        tokio::spawn(mock_driver.run());
+
+        assert_eq!(mock.abci_info().await.unwrap(), abci_info);
+        let replayed = mock.block(Height::from(10_u32)).await.unwrap();
+        assert_eq!(replayed.block_id, block.block_id);
+        assert_eq!(replayed.block.header, block.block.header);
+        let err = mock.block(Height::from(11_u32)).await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::MismatchResponse(_)));
+
+        let subscription = mock.subscribe(EventType::NewBlock.into()).await.unwrap();
+        fixture.replay_events(&mock).await.unwrap();
+        let replayed: Vec<_> = subscription.take(2).map(Result::unwrap).collect().await;
+        assert_eq!(replayed, events);
+    }
+
+    #[test]
+    fn serves_repeated_requests_in_order() {
+        let status = |height: &str| {
+            let mut response: Value = serde_json::from_str(&read_json_fixture("status")).unwrap();
+            response["result"]["sync_info"]["latest_block_height"] = height.into();
+            response["result"].clone()
+        };
+        let fixture = Fixture {
+            interactions: ["5", "6"]
+                .into_iter()
+                .map(|height| Interaction {
+                    method: Method::Status,
+                    params: Value::Null,
+                    result: Some(status(height)),
+                    error: None,
+                })
+                .collect(),
+            events: vec![],
+        };
+        let matcher = FixtureMatcher::new(fixture);
+        let latest_height = || {
+            let response = MockRequestMatcher::response_for::<_, v0_37::Dialect>(
+                &matcher,
+                crate::endpoint::status::Request,
+            );
+            response
+                .unwrap()
+                .unwrap()
+                .sync_info
+                .latest_block_height
+                .value()
+        };
+        assert_eq!(latest_height(), 5);
+        assert_eq!(latest_height(), 6);
+        assert_eq!(latest_height(), 6);
+    }
+}
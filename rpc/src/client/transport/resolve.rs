@@ -0,0 +1,26 @@
+//! Resolution of the host names of the endpoints and proxies of the HTTP and
+//! WebSocket transports.
+
+use std::{io, net::SocketAddr};
+
+pub use tendermint_std_ext::resolve::Resolver;
+use tokio::net::TcpStream;
+
+use crate::alloc_prelude::*;
+
+/// Resolve the given host name with the given resolver, without blocking the
+/// runtime while name servers are being queried.
+pub async fn resolve(resolver: &Resolver, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+    let resolver = resolver.clone();
+    let host = host.to_owned();
+    tokio::task::spawn_blocking(move || resolver.resolve(&host, port))
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+}
+
+/// Open a TCP connection to the given host and port, resolving the host name
+/// with the given resolver.
+pub async fn connect(resolver: &Resolver, host: &str, port: u16) -> io::Result<TcpStream> {
+    let addrs = resolve(resolver, host, port).await?;
+    TcpStream::connect(&*addrs).await
+}
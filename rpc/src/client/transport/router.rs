@@ -1,10 +1,15 @@
 //! Event routing for subscriptions.
 
-use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+use alloc::{
+    collections::{BTreeMap as HashMap, BTreeSet as HashSet},
+    sync::Arc,
+};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 
 use tracing::debug;
 
-use crate::{client::subscription::SubscriptionTx, error::Error, event::Event, prelude::*};
+use crate::{alloc_prelude::*, client::subscription::SubscriptionTx, error::Error, event::Event};
 
 pub type SubscriptionQuery = String;
 pub type SubscriptionId = String;
@@ -23,6 +28,42 @@ pub struct SubscriptionRouter {
     /// their result channels. Used for publishing events relating to a specific
     /// query.
     subscriptions: HashMap<SubscriptionQuery, HashMap<SubscriptionId, SubscriptionTx>>,
+    /// What was published to each subscription, indexed by subscription ID.
+    counters: HashMap<SubscriptionId, Counters>,
+}
+
+/// What was published to a subscription.
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+
+struct Counters {
+    delivered: u64,
+    errors: u64,
+    last_event: Option<SystemTime>,
+    /// The number of events received by the consumer of the subscription,
+    /// if it keeps count.
+    received: Option<Arc<AtomicU64>>,
+}
+
+/// Statistics about an active subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+pub struct SubscriptionStats {
+    /// The ID of the subscription.
+    pub id: SubscriptionId,
+    /// The query of the subscription.
+    pub query: SubscriptionQuery,
+    /// The number of events delivered to the subscription.
+    pub delivered: u64,
+    /// The number of events delivered to the subscription but not received
+    /// by its consumer yet.
+    pub buffered: u64,
+    /// The number of errors delivered to the subscription, e.g. the gaps in
+    /// its events after a reconnection, each of which may stand for any
+    /// number of dropped events.
+    pub errors: u64,
+    /// When the last event was delivered to the subscription.
+    pub last_event: Option<SystemTime>,
 }
 
 impl SubscriptionRouter {
@@ -75,11 +116,21 @@ impl SubscriptionRouter {
                     "Automatically disconnecting subscription with ID {} for query \"{}\" due to failure to publish to it: {}",
                     id, query, e
                 );
+            } else {
+                let counters = self.counters.entry(id.clone()).or_default();
+                match ev {
+                    Ok(_) => {
+                        counters.delivered += 1;
+                        counters.last_event = Some(SystemTime::now());
+                    },
+                    Err(_) => counters.errors += 1,
+                }
             }
         }
 
         for id in disconnected {
             subs_for_query.remove(&id);
+            self.counters.remove(&id);
         }
 
         if subs_for_query.is_empty() {
@@ -106,10 +157,66 @@ impl SubscriptionRouter {
 
     /// Removes all the subscriptions relating to the given query.
     pub fn remove_by_query(&mut self, query: impl ToString) -> usize {
+        let subs_for_query = match self.subscriptions.remove(&query.to_string()) {
+            Some(s) => s,
+            None => return 0,
+        };
+
+        for id in subs_for_query.keys() {
+            self.counters.remove(id);
+        }
+        subs_for_query.len()
+    }
+
+    /// Keep track of the number of events received by the consumer of the
+    /// given subscription, to report how many are buffered.
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub fn track_received(&mut self, id: impl ToString, received: Arc<AtomicU64>) {
+        self.counters.entry(id.to_string()).or_default().received = Some(received);
+    }
+
+    /// Removes the subscription with the given ID, closing it.
+    ///
+    /// Returns its query along with the number of subscriptions left for the
+    /// query, or `None` if there was no such subscription.
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub fn remove(&mut self, id: SubscriptionIdRef<'_>) -> Option<(SubscriptionQuery, usize)> {
+        let query = self.subscription_query(id)?.clone();
+        let subs_for_query = self.subscriptions.get_mut(&query)?;
+        subs_for_query.remove(id);
+        self.counters.remove(id);
+
+        let left = subs_for_query.len();
+        if left == 0 {
+            self.subscriptions.remove(&query);
+        }
+        Some((query, left))
+    }
+
+    /// Returns the statistics of all the active subscriptions, ordered by
+    /// query.
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub fn stats(&self) -> Vec<SubscriptionStats> {
         self.subscriptions
-            .remove(&query.to_string())
-            .map(|subs_for_query| subs_for_query.len())
-            .unwrap_or(0)
+            .iter()
+            .flat_map(|(query, subs_for_query)| {
+                subs_for_query.keys().map(move |id| {
+                    let counters = self.counters.get(id);
+                    let delivered = counters.map_or(0, |c| c.delivered);
+                    let received = counters
+                        .and_then(|c| c.received.as_ref())
+                        .map_or(delivered, |received| received.load(Ordering::Relaxed));
+                    SubscriptionStats {
+                        id: id.clone(),
+                        query: query.clone(),
+                        delivered,
+                        buffered: delivered.saturating_sub(received),
+                        errors: counters.map_or(0, |c| c.errors),
+                        last_event: counters.and_then(|c| c.last_event),
+                    }
+                })
+            })
+            .collect()
     }
 }
 
@@ -122,6 +229,27 @@ impl SubscriptionRouter {
             .map(|subs_for_query| subs_for_query.len())
             .unwrap_or(0)
     }
+
+    /// Returns whether there is any active subscription.
+    pub fn has_active_subscriptions(&self) -> bool {
+        self.subscriptions
+            .values()
+            .any(|subs_for_query| !subs_for_query.is_empty())
+    }
+
+    /// Returns the queries that have active subscriptions, each along with
+    /// the ID of one of its subscriptions.
+    pub fn active_queries(&self) -> Vec<(SubscriptionQuery, SubscriptionId)> {
+        self.subscriptions
+            .iter()
+            .filter_map(|(query, subs_for_query)| {
+                subs_for_query
+                    .keys()
+                    .next()
+                    .map(|id| (query.clone(), id.clone()))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -274,5 +402,51 @@ mod test {
             let subs3_ev = must_recv(&mut subs3_event_rx, 500).await.unwrap();
             assert_eq!(ev, subs3_ev);
         }
+
+        #[tokio::test]
+        async fn router_stats_and_remove() {
+            let mut router = SubscriptionRouter::default();
+
+            let (subs1_id, subs2_id) = (uuid_str(), uuid_str());
+            let (subs1_event_tx, mut subs1_event_rx) = unbounded();
+            let (subs2_event_tx, _subs2_event_rx) = unbounded();
+            let subs1_received = Arc::new(AtomicU64::new(0));
+
+            router.add(subs1_id.clone(), "query1", subs1_event_tx);
+            router.track_received(subs1_id.clone(), subs1_received.clone());
+            router.add(subs2_id.clone(), "query1", subs2_event_tx);
+
+            let mut ev = read_event("subscribe_newblock_0").await;
+            ev.query = "query1".into();
+            router.publish_event(ev.clone());
+            router.publish_event(ev);
+            router.publish_error(&subs1_id, Error::subscription_gap("test".to_string()));
+
+            must_recv(&mut subs1_event_rx, 500).await.unwrap();
+            subs1_received.fetch_add(1, Ordering::Relaxed);
+
+            let stats = router.stats();
+            assert_eq!(stats.len(), 2);
+            let subs1_stats = stats.iter().find(|s| s.id == subs1_id).unwrap();
+            assert_eq!(subs1_stats.query, "query1");
+            assert_eq!(subs1_stats.delivered, 2);
+            assert_eq!(subs1_stats.buffered, 1);
+            assert_eq!(subs1_stats.errors, 1);
+            assert!(subs1_stats.last_event.is_some());
+            // Without a count of the received events, nothing is reported as
+            // buffered.
+            let subs2_stats = stats.iter().find(|s| s.id == subs2_id).unwrap();
+            assert_eq!((subs2_stats.delivered, subs2_stats.buffered), (2, 0));
+
+            assert_eq!(router.remove(&subs1_id), Some(("query1".to_string(), 1)));
+            assert_eq!(router.remove(&subs1_id), None);
+            // The stream of the removed subscription ends once it is drained.
+            must_recv(&mut subs1_event_rx, 500).await.unwrap();
+            must_recv(&mut subs1_event_rx, 500).await.unwrap_err();
+            assert!(subs1_event_rx.recv().await.is_none());
+
+            assert_eq!(router.remove(&subs2_id), Some(("query1".to_string(), 0)));
+            assert!(router.stats().is_empty());
+        }
     }
 }
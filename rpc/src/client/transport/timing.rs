@@ -0,0 +1,157 @@
+//! Timing of the phases of RPC calls, to diagnose slow calls.
+
+use alloc::sync::Arc;
+use core::{fmt, time::Duration};
+
+use super::instrument::{CallMeter, Instrumentation};
+use crate::alloc_prelude::*;
+
+/// The time spent in each phase of an RPC call.
+///
+/// The connection phases (`dns`, `connect` and `tls`) are only reported for
+/// the call which opened a new connection, and are `None` for calls reusing
+/// a connection, or if the phase does not apply (e.g. no DNS resolution for
+/// an IP address, no TLS handshake over plain HTTP).
+///
+/// For the [`WebSocketClient`](crate::WebSocketClient), the connection
+/// phases are reported once per (re)connection under the `connect` method,
+/// with the WebSocket handshake counted as part of `tls`, and the calls over
+/// the connection only report `request_write`, `first_byte` and `total`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallTimings {
+    /// Resolving the host name of the endpoint.
+    pub dns: Option<Duration>,
+    /// Establishing the TCP connection, including the handshake with the
+    /// proxy, if any.
+    pub connect: Option<Duration>,
+    /// The TLS handshake.
+    pub tls: Option<Duration>,
+    /// Writing the request.
+    pub request_write: Option<Duration>,
+    /// Waiting for the first byte of the response once the request is
+    /// written.
+    pub first_byte: Option<Duration>,
+    /// Reading the body of the response, once its headers are received.
+    pub body_read: Option<Duration>,
+    /// The whole call.
+    pub total: Duration,
+}
+
+impl fmt::Display for CallTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phases = [
+            ("dns", self.dns),
+            ("connect", self.connect),
+            ("tls", self.tls),
+            ("request_write", self.request_write),
+            ("first_byte", self.first_byte),
+            ("body_read", self.body_read),
+        ];
+        write!(f, "total={:?}", self.total)?;
+        for (name, duration) in phases {
+            if let Some(duration) = duration {
+                write!(f, " {name}={duration:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A hook called with the phase timings of every RPC call of a client.
+///
+/// Implemented for closures taking the name of the RPC method (`batch` for
+/// batches of requests) and the timings of the call.
+pub trait TimingObserver: Send + Sync {
+    /// Called once the given call has completed, successfully or not.
+    fn observe(&self, method: &str, timings: &CallTimings);
+}
+
+impl<F> TimingObserver for F
+where
+    F: Fn(&str, &CallTimings) + Send + Sync,
+{
+    fn observe(&self, method: &str, timings: &CallTimings) {
+        self(method, timings)
+    }
+}
+
+/// Logs a warning with the phase timings of the calls which take at least
+/// the given threshold.
+#[derive(Clone, Debug)]
+pub struct SlowCallLogger {
+    threshold: Duration,
+}
+
+impl SlowCallLogger {
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+}
+
+impl TimingObserver for SlowCallLogger {
+    fn observe(&self, method: &str, timings: &CallTimings) {
+        if timings.total >= self.threshold {
+            tracing::warn!("Slow RPC call to {}: {}", method, timings);
+        }
+    }
+}
+
+/// The timing observers and instrumentations of a client.
+#[derive(Clone, Default)]
+pub(crate) struct Observers {
+    timing: Vec<Arc<dyn TimingObserver>>,
+    instruments: Vec<Arc<dyn Instrumentation>>,
+}
+
+impl Observers {
+    pub fn push(&mut self, observer: impl TimingObserver + 'static) {
+        self.timing.push(Arc::new(observer));
+    }
+
+    pub fn instrument(&mut self, instrumentation: impl Instrumentation + 'static) {
+        self.instruments.push(Arc::new(instrumentation));
+    }
+
+    /// Whether there are no timing observers.
+    pub fn is_empty(&self) -> bool {
+        self.timing.is_empty()
+    }
+
+    pub fn observe(&self, method: &str, timings: &CallTimings) {
+        for observer in &self.timing {
+            observer.observe(method, timings);
+        }
+    }
+
+    /// Start measuring a call for the instrumentations.
+    pub fn meter<'a>(&'a self, method: &'a str, request_size: usize) -> CallMeter<'a> {
+        CallMeter::start(&self.instruments, method, request_size)
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Observers({}, {})",
+            self.timing.len(),
+            self.instruments.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_skips_missing_phases() {
+        let timings = CallTimings {
+            dns: Some(Duration::from_millis(2)),
+            first_byte: Some(Duration::from_millis(40)),
+            total: Duration::from_millis(50),
+            ..Default::default()
+        };
+        assert_eq!(timings.to_string(), "total=50ms dns=2ms first_byte=40ms");
+    }
+}
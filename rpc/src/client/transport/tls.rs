@@ -0,0 +1,301 @@
+//! Custom TLS configuration for secure connections.
+
+use std::{io, sync::Arc};
+
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::{client::TlsStream, webpki::DNSNameRef};
+
+use crate::{alloc_prelude::*, Error};
+
+/// The TLS options of an [`HttpClient`](crate::HttpClient) or of a
+/// [`WebSocketClient`](crate::WebSocketClient).
+///
+/// By default, the server certificate is verified against both the native
+/// root certificates of the platform and the Mozilla root certificates
+/// bundled in this crate (the "webpki roots"), and no client certificate is
+/// presented.
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{HttpClient, TlsConfig, WebSocketClient};
+///
+/// let tls_config = TlsConfig::new()
+///     .native_roots(false)
+///     .webpki_roots(false)
+///     .add_root_certificates_pem(include_bytes!("ca.pem"))?
+///     .client_auth_pem(include_bytes!("client.pem"), include_bytes!("client.key"))?
+///     .server_name("node.internal");
+///
+/// let client = HttpClient::builder("https://10.0.0.1:26657".parse()?)
+///     .tls_config(tls_config.clone())
+///     .build()?;
+/// let (client, driver) = WebSocketClient::builder("wss://10.0.0.1:26657/websocket".parse()?)
+///     .tls_config(tls_config)
+///     .build()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    root_certificates: Vec<Certificate>,
+    native_roots: bool,
+    webpki_roots: bool,
+    client_auth: Option<(Vec<Certificate>, PrivateKey)>,
+    server_name: Option<String>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            root_certificates: Vec::new(),
+            native_roots: true,
+            webpki_roots: true,
+            client_auth: None,
+            server_name: None,
+        }
+    }
+}
+
+impl TlsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the given DER-encoded root certificate, in addition to the
+    /// native and webpki roots if these are enabled.
+    pub fn add_root_certificate(mut self, der: Vec<u8>) -> Self {
+        self.root_certificates.push(Certificate(der));
+        self
+    }
+
+    /// Trust all the root certificates of the given PEM bundle.
+    pub fn add_root_certificates_pem(mut self, pem: &[u8]) -> Result<Self, Error> {
+        let certificates = parse_certificates(pem)?;
+        if certificates.is_empty() {
+            return Err(Error::invalid_tls_config(
+                "no certificate found in PEM bundle".to_string(),
+            ));
+        }
+        self.root_certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Whether to trust the native root certificates of the platform.
+    pub fn native_roots(mut self, enabled: bool) -> Self {
+        self.native_roots = enabled;
+        self
+    }
+
+    /// Whether to trust the webpki roots.
+    pub fn webpki_roots(mut self, enabled: bool) -> Self {
+        self.webpki_roots = enabled;
+        self
+    }
+
+    /// Authenticate to the server with the given DER-encoded certificate
+    /// chain, leaf certificate first, and the DER-encoded private key
+    /// (PKCS#8 or PKCS#1) of the leaf certificate.
+    pub fn client_auth(mut self, certificate_chain: Vec<Vec<u8>>, private_key: Vec<u8>) -> Self {
+        self.client_auth = Some((
+            certificate_chain.into_iter().map(Certificate).collect(),
+            PrivateKey(private_key),
+        ));
+        self
+    }
+
+    /// Authenticate to the server with the certificate chain and the
+    /// private key of the given PEM files.
+    pub fn client_auth_pem(
+        mut self,
+        certificate_chain: &[u8],
+        private_key: &[u8],
+    ) -> Result<Self, Error> {
+        let certificate_chain = parse_certificates(certificate_chain)?;
+        if certificate_chain.is_empty() {
+            return Err(Error::invalid_tls_config(
+                "no client certificate found in PEM file".to_string(),
+            ));
+        }
+        let private_key = parse_private_key(private_key)?;
+        self.client_auth = Some((certificate_chain, private_key));
+        Ok(self)
+    }
+
+    /// Verify the server certificate for the given name, and send it as the
+    /// SNI extension, instead of the host of the RPC endpoint URL.
+    ///
+    /// This allows connecting to a node by IP address, or through an SSH
+    /// tunnel, while still verifying its certificate.
+    pub fn server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+
+    pub(crate) fn has_server_name(&self) -> bool {
+        self.server_name.is_some()
+    }
+
+    /// Build the `rustls` client configuration described by these options.
+    pub(crate) fn client_config(&self) -> Result<ClientConfig, Error> {
+        let mut root_store = RootCertStore::empty();
+        if self.native_roots {
+            match rustls_native_certs::load_native_certs() {
+                Ok(store) => root_store.roots.extend(store.roots),
+                Err((Some(store), e)) => {
+                    tracing::warn!("Could not load all native certificates: {:?}", e);
+                    root_store.roots.extend(store.roots);
+                },
+                Err((None, e)) => {
+                    tracing::warn!("Could not load native certificates: {:?}", e);
+                },
+            }
+        }
+        if self.webpki_roots {
+            root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        }
+        for certificate in &self.root_certificates {
+            root_store
+                .add(certificate)
+                .map_err(|e| Error::invalid_tls_config(format!("invalid root certificate: {e}")))?;
+        }
+
+        let mut config = ClientConfig::new();
+        config.root_store = root_store;
+        // Both the HTTP requests and the WebSocket handshakes of the clients
+        // are made over HTTP/1.1.
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        if let Some((certificate_chain, private_key)) = &self.client_auth {
+            config
+                .set_single_client_cert(certificate_chain.clone(), private_key.clone())
+                .map_err(|e| Error::invalid_tls_config(format!("invalid client key: {e}")))?;
+        }
+        Ok(config)
+    }
+
+    /// Build the client securing connections according to these options.
+    pub(crate) fn client(&self) -> Result<TlsClient, Error> {
+        if let Some(name) = &self.server_name {
+            DNSNameRef::try_from_ascii_str(name)
+                .map_err(|_| Error::invalid_tls_config(format!("invalid server name: {name}")))?;
+        }
+        Ok(TlsClient {
+            connector: Arc::new(self.client_config()?).into(),
+            server_name: self.server_name.clone(),
+        })
+    }
+}
+
+/// Secures connections according to a [`TlsConfig`].
+#[derive(Clone)]
+pub(crate) struct TlsClient {
+    connector: tokio_rustls::TlsConnector,
+    server_name: Option<String>,
+}
+
+impl core::fmt::Debug for TlsClient {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TlsClient")
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TlsClient {
+    /// Secure the given connection to the given host, whose certificate is
+    /// verified for the configured server name if any, and for the host
+    /// otherwise.
+    pub(crate) async fn connect<S>(&self, host: &str, stream: S) -> io::Result<TlsStream<S>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let server_name = match &self.server_name {
+            Some(server_name) => server_name,
+            None => host.trim_start_matches('[').trim_end_matches(']'),
+        };
+        let dns_name = DNSNameRef::try_from_ascii_str(server_name).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid server name: {server_name}"),
+            )
+        })?;
+        self.connector.connect(dns_name, stream).await
+    }
+}
+
+pub(crate) fn parse_certificates(pem: &[u8]) -> Result<Vec<Certificate>, Error> {
+    rustls::internal::pemfile::certs(&mut &*pem)
+        .map_err(|_| Error::invalid_tls_config("malformed PEM certificate".to_string()))
+}
+
+pub(crate) fn parse_private_key(pem: &[u8]) -> Result<PrivateKey, Error> {
+    let malformed = |_| Error::invalid_tls_config("malformed PEM private key".to_string());
+    let mut keys = rustls::internal::pemfile::pkcs8_private_keys(&mut &*pem).map_err(malformed)?;
+    if keys.is_empty() {
+        keys = rustls::internal::pemfile::rsa_private_keys(&mut &*pem).map_err(malformed)?;
+    }
+    match keys.len() {
+        1 => Ok(keys.remove(0)),
+        0 => Err(Error::invalid_tls_config(
+            "no private key found in PEM file".to_string(),
+        )),
+        _ => Err(Error::invalid_tls_config(
+            "more than one private key found in PEM file".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    const CA: &[u8] = include_bytes!("../../../tests/tls_fixtures/ca.pem");
+    const CLIENT_CERT: &[u8] = include_bytes!("../../../tests/tls_fixtures/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../tests/tls_fixtures/client.key");
+
+    #[test]
+    fn custom_roots_only() {
+        let config = TlsConfig::new()
+            .native_roots(false)
+            .webpki_roots(false)
+            .add_root_certificates_pem(CA)
+            .unwrap()
+            .client_config()
+            .unwrap();
+        assert_eq!(config.root_store.len(), 1);
+        assert_eq!(config.alpn_protocols, vec![b"http/1.1".to_vec()]);
+    }
+
+    #[test]
+    fn client_auth_pem() {
+        let config = TlsConfig::new()
+            .client_auth_pem(CLIENT_CERT, CLIENT_KEY)
+            .unwrap()
+            .client_config()
+            .unwrap();
+        assert!(config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn invalid_pem() {
+        let err = TlsConfig::new()
+            .add_root_certificates_pem(b"not a certificate")
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidTlsConfig(_)));
+
+        let err = TlsConfig::new()
+            .client_auth_pem(CLIENT_CERT, CLIENT_CERT)
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidTlsConfig(_)));
+    }
+
+    #[test]
+    fn invalid_server_name() {
+        let err = TlsConfig::new()
+            .server_name("not a name")
+            .client()
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidTlsConfig(_)));
+    }
+}
@@ -1,15 +1,15 @@
 //! WebSocket-based clients for accessing Tendermint RPC functionality.
 
-use alloc::{borrow::Cow, collections::BTreeMap as HashMap, fmt};
+use alloc::{borrow::Cow, collections::BTreeMap as HashMap, fmt, sync::Arc};
 use core::{
     convert::{TryFrom, TryInto},
     ops::Add,
     str::FromStr,
+    sync::atomic::AtomicU64,
 };
 
 use async_trait::async_trait;
 use async_tungstenite::{
-    tokio::ConnectStream,
     tungstenite::{
         protocol::{frame::coding::CloseCode, CloseFrame},
         Message,
@@ -24,37 +24,50 @@ use tracing::{debug, error};
 use tendermint::{block::Height, Hash};
 use tendermint_config::net;
 
-use super::router::{SubscriptionId, SubscriptionIdRef};
+pub use super::router::SubscriptionStats;
+use super::router::{SubscriptionId, SubscriptionIdRef, SubscriptionQuery};
 use crate::dialect::{v0_34, v0_37};
 use crate::{
+    alloc_prelude::*,
     client::{
         subscription::SubscriptionTx,
         sync::{ChannelRx, ChannelTx},
-        transport::router::{PublishResult, SubscriptionRouter},
+        transport::{
+            instrument::Instrumentation,
+            proxy::ProxyUrl,
+            resolve::Resolver,
+            router::{PublishResult, SubscriptionRouter},
+            timing::{CallTimings, Observers, SlowCallLogger, TimingObserver},
+            tls::{TlsClient, TlsConfig},
+        },
         Client, CompatMode,
     },
     endpoint::{self, subscribe, unsubscribe},
     error::Error,
     event::{DialectEvent, Event},
-    prelude::*,
     query::Query,
     request::Wrapper,
     response, Id, Order, Request, Response, Scheme, SimpleRequest, Subscription,
     SubscriptionClient, Url,
 };
+use sealed::ConnectStream;
 
-// WebSocket connection times out if we haven't heard anything at all from the
-// server in this long.
+// The read timeout of the WebSocket server, from which its ping cadence is
+// derived.
 //
 // Taken from https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L27
-const RECV_TIMEOUT_SECONDS: u64 = 30;
-
-const RECV_TIMEOUT: Duration = Duration::from_secs(RECV_TIMEOUT_SECONDS);
+const SERVER_READ_TIMEOUT_SECONDS: u64 = 30;
 
-// How frequently to send ping messages to the WebSocket server.
+// How frequently to send ping messages to the WebSocket server by default.
 //
 // Taken from https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L28
-const PING_INTERVAL: Duration = Duration::from_secs((RECV_TIMEOUT_SECONDS * 9) / 10);
+const PING_INTERVAL: Duration = Duration::from_secs((SERVER_READ_TIMEOUT_SECONDS * 9) / 10);
+
+// How long to wait by default for the WebSocket server to answer a ping,
+// matching its write timeout.
+//
+// Taken from https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L26
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Low-level WebSocket configuration
 pub use async_tungstenite::tungstenite::protocol::WebSocketConfig;
@@ -77,14 +90,6 @@ pub use async_tungstenite::tungstenite::protocol::WebSocketConfig;
 /// particular query have disconnected, the driver will automatically issue an
 /// unsubscribe request to the remote RPC endpoint.
 ///
-/// ### Timeouts
-///
-/// The WebSocket client connection times out after 30 seconds if it does not
-/// receive anything at all from the server. This will automatically return
-/// errors to all active subscriptions and terminate them.
-///
-/// This is not configurable at present.
-///
 /// ### Keep-Alive
 ///
 /// The WebSocket client implements a keep-alive mechanism whereby it sends a
@@ -92,7 +97,28 @@ pub use async_tungstenite::tungstenite::protocol::WebSocketConfig;
 /// the Tendermint server (see [this code][tendermint-websocket-ping] for
 /// details).
 ///
-/// This is not configurable at present.
+/// ### Timeouts
+///
+/// The connection is deemed dead if the client receives nothing at all from
+/// the server within 10 seconds of a PING, which may happen when a load
+/// balancer silently drops an idle connection. Optionally, it is also deemed
+/// dead when the active subscriptions receive no event for a given time. The
+/// ping cadence and both timeouts are configured with
+/// [`Builder::keep_alive`].
+///
+/// A dead connection returns an error to all active subscriptions and
+/// terminates them, unless the client reconnects.
+///
+/// ### Reconnection
+///
+/// By default, the driver terminates when the connection is lost, which ends
+/// all subscriptions. A client built with [`Builder::reconnect`] instead
+/// reconnects with exponential backoff, re-issues the `/subscribe` requests of
+/// all active subscriptions and keeps their streams open. As events published
+/// while the connection was down are lost, every subscription then receives an
+/// [`ErrorDetail::SubscriptionGap`] error marking the gap in its stream.
+/// Requests issued while reconnecting are sent once the connection is back,
+/// whereas requests that were awaiting a response when it was lost fail.
 ///
 /// ## Examples
 ///
@@ -138,6 +164,7 @@ pub use async_tungstenite::tungstenite::protocol::WebSocketConfig;
 /// ```
 ///
 /// [tendermint-websocket-ping]: https://github.com/tendermint/tendermint/blob/309e29c245a01825fc9630103311fd04de99fa5e/rpc/jsonrpc/server/ws_handler.go#L28
+/// [`ErrorDetail::SubscriptionGap`]: crate::error::ErrorDetail::SubscriptionGap
 #[derive(Debug, Clone)]
 pub struct WebSocketClient {
     inner: sealed::WebSocketClient,
@@ -149,6 +176,13 @@ pub struct Builder {
     url: WebSocketClientUrl,
     compat: CompatMode,
     transport_config: Option<WebSocketConfig>,
+    reconnect: Option<ReconnectConfig>,
+    keep_alive: KeepAliveConfig,
+    proxy_url: Option<ProxyUrl>,
+    proxy_from_env: bool,
+    tls_config: Option<TlsConfig>,
+    resolver: Resolver,
+    observers: Observers,
 }
 
 impl Builder {
@@ -166,20 +200,222 @@ impl Builder {
         self
     }
 
+    /// Reconnect with the given policy when the connection is lost, instead
+    /// of terminating the driver.
+    pub fn reconnect(mut self, config: ReconnectConfig) -> Self {
+        self.reconnect = Some(config);
+        self
+    }
+
+    /// Detect dead connections with the given keep-alive policy, instead of
+    /// the default one.
+    ///
+    /// # Panics
+    ///
+    /// If the ping interval is zero.
+    pub fn keep_alive(mut self, config: KeepAliveConfig) -> Self {
+        assert!(
+            !config.ping_interval.is_zero(),
+            "the ping interval must not be zero"
+        );
+        self.keep_alive = config;
+        self
+    }
+
+    /// Specify the URL of a proxy server for the client to connect through.
+    ///
+    /// The connection to the RPC endpoint is tunneled through HTTP proxies
+    /// with the [HTTP CONNECT] method, and through SOCKS5 proxies with their
+    /// `CONNECT` command. Connecting to the proxy itself over TLS is not
+    /// supported. See [`ProxyUrl`] for details.
+    ///
+    /// [HTTP CONNECT]: https://en.wikipedia.org/wiki/HTTP_tunnel
+    pub fn proxy_url(mut self, url: ProxyUrl) -> Self {
+        self.proxy_url = Some(url);
+        self
+    }
+
+    /// Connect through the proxy given by the environment, if no proxy URL
+    /// is specified with [`Builder::proxy_url`].
+    ///
+    /// See [`ProxyUrl::from_env`] for the variables which are honored.
+    pub fn proxy_from_env(mut self) -> Self {
+        self.proxy_from_env = true;
+        self
+    }
+
+    /// Use custom TLS options to connect to a secured (`wss://`) RPC
+    /// endpoint, e.g. to trust a private certificate authority or to
+    /// authenticate with a client certificate (see [`TlsConfig`]).
+    ///
+    /// By default, the server certificate is verified against the native
+    /// root certificates of the platform.
+    pub fn tls_config(mut self, config: TlsConfig) -> Self {
+        self.tls_config = Some(config);
+        self
+    }
+
+    /// Resolve the host names of the RPC endpoint and of the proxy, if any,
+    /// with the given resolver, e.g. to override the addresses of some hosts
+    /// (see [`Resolver`]). The resolver is used again on every reconnection.
+    ///
+    /// The host names of the RPC endpoints reached through a proxy are
+    /// resolved by the proxy. By default, host names are resolved by the
+    /// operating system.
+    pub fn resolver(mut self, resolver: Resolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Report the phase timings of every call of the client, and of every
+    /// (re)connection under the `connect` method, to the given observer (see
+    /// [`CallTimings`]).
+    ///
+    /// Can be called several times to add several observers.
+    pub fn timing_observer(mut self, observer: impl TimingObserver + 'static) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Log a warning with the phase timings of the calls which take at least
+    /// the given duration.
+    ///
+    /// This is a shorthand for adding a [`SlowCallLogger`] observer.
+    pub fn slow_call_threshold(self, threshold: Duration) -> Self {
+        self.timing_observer(SlowCallLogger::new(threshold))
+    }
+
+    /// Report every call of the client to the given instrumentation, e.g. to
+    /// export metrics (see [`Instrumentation`]).
+    ///
+    /// Can be called several times to add several instrumentations.
+    pub fn instrumentation(mut self, instrumentation: impl Instrumentation + 'static) -> Self {
+        self.observers.instrument(instrumentation);
+        self
+    }
+
     /// Try to create a client with the options specified for this builder.
     pub async fn build(self) -> Result<(WebSocketClient, WebSocketClientDriver), Error> {
         let url = self.url.0;
         let compat = self.compat;
-        let (inner, driver) = if url.is_secure() {
-            sealed::WebSocketClient::new_secure(url, compat, self.transport_config).await?
+        let proxy_url = match self.proxy_url {
+            Some(proxy_url) => Some(proxy_url),
+            None if self.proxy_from_env => ProxyUrl::from_env(url.is_secure())?,
+            None => None,
+        };
+        let tls = if url.is_secure() {
+            let tls_config = self
+                .tls_config
+                .unwrap_or_else(|| TlsConfig::new().webpki_roots(false));
+            Some(tls_config.client()?)
         } else {
-            sealed::WebSocketClient::new_unsecure(url, compat, self.transport_config).await?
+            None
         };
+        let reconnect = self.reconnect.map(|policy| Reconnect {
+            url: url.clone(),
+            transport_config: self.transport_config,
+            proxy_url: proxy_url.clone(),
+            tls: tls.clone(),
+            resolver: self.resolver.clone(),
+            policy,
+        });
+        let (inner, mut driver) = if let Some(tls) = tls {
+            sealed::WebSocketClient::new_secure(
+                url,
+                compat,
+                self.transport_config,
+                proxy_url,
+                tls,
+                self.resolver,
+                self.observers,
+            )
+            .await?
+        } else {
+            sealed::WebSocketClient::new_unsecure(
+                url,
+                compat,
+                self.transport_config,
+                proxy_url,
+                self.resolver,
+                self.observers,
+            )
+            .await?
+        };
+        driver.reconnect = reconnect;
+        driver.keep_alive = self.keep_alive;
 
         Ok((WebSocketClient { inner, compat }, driver))
     }
 }
 
+/// How a [`WebSocketClient`] reconnects when its connection is lost.
+///
+/// The delay before each reconnection attempt starts at `initial_backoff` and
+/// doubles after every failed attempt, up to `max_backoff`.
+#[derive(Debug, Clone)]
+pub struct ReconnectConfig {
+    /// Delay before the first reconnection attempt.
+    pub initial_backoff: Duration,
+    /// Maximum delay between two reconnection attempts.
+    pub max_backoff: Duration,
+    /// Number of consecutive failed attempts after which the driver gives up
+    /// and terminates with the last error. Retries forever if `None`.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+/// How a [`WebSocketClient`] detects that its connection is dead.
+///
+/// The client sends a ping message to the server every `ping_interval`, and
+/// deems the connection dead with a [`ErrorDetail::WebSocketTimeout`] error
+/// if nothing is received within `pong_timeout` of a ping. If
+/// `stale_timeout` is set, the connection is also deemed dead, with a
+/// [`ErrorDetail::StaleSubscriptions`] error, when there are active
+/// subscriptions but none of them received an event for that long.
+///
+/// [`ErrorDetail::WebSocketTimeout`]: crate::error::ErrorDetail::WebSocketTimeout
+/// [`ErrorDetail::StaleSubscriptions`]: crate::error::ErrorDetail::StaleSubscriptions
+#[derive(Debug, Clone)]
+pub struct KeepAliveConfig {
+    /// How frequently to send ping messages to the server. Must not be zero.
+    pub ping_interval: Duration,
+    /// How long to wait for any message from the server after a ping.
+    pub pong_timeout: Duration,
+    /// How long the active subscriptions may go without any event. Disabled
+    /// if `None`, as some queries match rare events.
+    pub stale_timeout: Option<Duration>,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: PING_INTERVAL,
+            pong_timeout: PONG_TIMEOUT,
+            stale_timeout: None,
+        }
+    }
+}
+
+// Everything the driver needs to reconnect.
+#[derive(Debug, Clone)]
+struct Reconnect {
+    url: Url,
+    transport_config: Option<WebSocketConfig>,
+    proxy_url: Option<ProxyUrl>,
+    tls: Option<TlsClient>,
+    resolver: Resolver,
+    policy: ReconnectConfig,
+}
+
 impl WebSocketClient {
     /// Construct a new WebSocket-based client connecting to the given
     /// Tendermint node's RPC endpoint.
@@ -217,6 +453,13 @@ impl WebSocketClient {
             url,
             compat: Default::default(),
             transport_config: Default::default(),
+            reconnect: Default::default(),
+            keep_alive: Default::default(),
+            proxy_url: None,
+            proxy_from_env: false,
+            tls_config: None,
+            resolver: Resolver::default(),
+            observers: Observers::default(),
         }
     }
 
@@ -311,6 +554,27 @@ impl Client for WebSocketClient {
     }
 }
 
+impl WebSocketClient {
+    /// The active subscriptions of this client, with statistics about the
+    /// events delivered to each of them.
+    ///
+    /// The subscriptions sharing a query are listed one by one, although the
+    /// remote endpoint only knows of one subscription per query.
+    pub async fn subscriptions(&self) -> Result<Vec<SubscriptionStats>, Error> {
+        self.inner.subscriptions().await
+    }
+
+    /// Close the subscription with the given ID, ending its stream of events,
+    /// without affecting the other subscriptions to its query.
+    ///
+    /// The client unsubscribes from the query on the remote endpoint once it
+    /// has no subscription left. Returns `false` if there was no active
+    /// subscription with the given ID.
+    pub async fn close_subscription(&self, id: &str) -> Result<bool, Error> {
+        self.inner.close_subscription(id).await
+    }
+}
+
 #[async_trait]
 impl SubscriptionClient for WebSocketClient {
     async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
@@ -394,23 +658,41 @@ impl From<WebSocketClientUrl> for Url {
 
 mod sealed {
     use async_tungstenite::{
-        tokio::{connect_async_with_config, connect_async_with_tls_connector_and_config},
+        tokio::{client_async_with_config, TokioAdapter},
         tungstenite::client::IntoClientRequest,
+        WebSocketStream,
+    };
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
     };
+    use std::{io, net::IpAddr, time::Instant};
+
+    use tokio::{
+        io::{AsyncRead, AsyncWrite, ReadBuf},
+        net::TcpStream,
+    };
+    use tokio_rustls::client::TlsStream;
     use tracing::debug;
 
     use super::{
-        DriverCommand, SimpleRequestCommand, SubscribeCommand, UnsubscribeCommand,
-        WebSocketClientDriver, WebSocketConfig,
+        CloseSubscriptionCommand, DriverCommand, SimpleRequestCommand, SubscribeCommand,
+        SubscriptionStats, UnsubscribeCommand, WebSocketClientDriver, WebSocketConfig,
     };
     use crate::{
+        alloc_prelude::*,
         client::{
             sync::{unbounded, ChannelTx},
-            transport::auth::authorize,
+            transport::{
+                auth::authorize,
+                proxy::{tunnel, ProxyUrl},
+                resolve::{resolve, Resolver},
+                timing::{CallTimings, Observers},
+                tls::TlsClient,
+            },
             CompatMode,
         },
         dialect::Dialect,
-        prelude::*,
         query::Query,
         request::Wrapper,
         utils::uuid_str,
@@ -436,9 +718,133 @@ mod sealed {
     #[derive(Debug, Clone)]
     pub struct AsyncTungsteniteClient<C> {
         cmd_tx: ChannelTx<DriverCommand>,
+        observers: Observers,
         _client_type: core::marker::PhantomData<C>,
     }
 
+    /// The stream underlying the WebSocket connections.
+    pub type ConnectStream = TokioAdapter<MaybeTlsStream>;
+
+    /// A TCP connection, secured with TLS or not.
+    #[derive(Debug)]
+    pub enum MaybeTlsStream {
+        Plain(TcpStream),
+        Tls(Box<TlsStream<TcpStream>>),
+    }
+
+    impl AsyncRead for MaybeTlsStream {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            match self.get_mut() {
+                Self::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+                Self::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+            }
+        }
+    }
+
+    impl AsyncWrite for MaybeTlsStream {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            match self.get_mut() {
+                Self::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+                Self::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+            }
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            match self.get_mut() {
+                Self::Plain(stream) => Pin::new(stream).poll_flush(cx),
+                Self::Tls(stream) => Pin::new(stream).poll_flush(cx),
+            }
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            match self.get_mut() {
+                Self::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+                Self::Tls(stream) => Pin::new(stream).poll_shutdown(cx),
+            }
+        }
+    }
+
+    /// Opens a WebSocket connection to the given URL, secured with the given
+    /// TLS client if any, and through the given proxy if any. Host names are
+    /// resolved with the given resolver.
+    ///
+    /// The timings of the connection are reported to the given observers
+    /// under the `connect` method, the WebSocket handshake being counted as
+    /// part of the TLS handshake.
+    pub async fn connect(
+        url: Url,
+        config: Option<WebSocketConfig>,
+        proxy_url: Option<&ProxyUrl>,
+        tls: Option<&TlsClient>,
+        resolver: &Resolver,
+        observers: &Observers,
+    ) -> Result<WebSocketStream<ConnectStream>, Error> {
+        let start = Instant::now();
+        let mut timings = CallTimings::default();
+        let stream = match proxy_url {
+            Some(proxy_url) => {
+                debug!(
+                    "Connecting to WebSocket endpoint {} through proxy {}",
+                    url, proxy_url
+                );
+                tunnel(proxy_url, url.host(), url.port(), resolver).await
+            },
+            None => {
+                debug!("Connecting to WebSocket endpoint: {}", url);
+                let host = url.host().trim_start_matches('[').trim_end_matches(']');
+                let resolving = Instant::now();
+                let addrs = resolve(resolver, host, url.port())
+                    .await
+                    .map_err(Error::io)?;
+                // No actual resolution takes place for IP addresses.
+                if host.parse::<IpAddr>().is_err() {
+                    timings.dns = Some(resolving.elapsed());
+                }
+                let connecting = Instant::now();
+                let stream = TcpStream::connect(&*addrs).await;
+                timings.connect = Some(connecting.elapsed());
+                stream
+            },
+        }
+        .map_err(Error::io);
+        if proxy_url.is_some() {
+            timings.connect = Some(start.elapsed());
+        }
+
+        let result = match stream {
+            Ok(stream) => {
+                let handshake = Instant::now();
+                let result = async {
+                    let stream = match tls {
+                        Some(tls) => MaybeTlsStream::Tls(Box::new(
+                            tls.connect(url.host(), stream).await.map_err(Error::io)?,
+                        )),
+                        None => MaybeTlsStream::Plain(stream),
+                    };
+                    client_async_with_config(url, stream, config)
+                        .await
+                        .map(|(stream, _response)| stream)
+                        .map_err(Error::tungstenite)
+                }
+                .await;
+                timings.tls = Some(handshake.elapsed());
+                result
+            },
+            Err(e) => Err(e),
+        };
+        timings.total = start.elapsed();
+        observers.observe("connect", &timings);
+        result
+    }
+
     impl AsyncTungsteniteClient<Unsecure> {
         /// Construct a WebSocket client. Immediately attempts to open a WebSocket
         /// connection to the node with the given address.
@@ -452,17 +858,19 @@ mod sealed {
             url: Url,
             compat: CompatMode,
             config: Option<WebSocketConfig>,
+            proxy_url: Option<ProxyUrl>,
+            resolver: Resolver,
+            observers: Observers,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
-            debug!("Connecting to unsecure WebSocket endpoint: {}", url);
-
-            let (stream, _response) = connect_async_with_config(url, config)
-                .await
-                .map_err(Error::tungstenite)?;
+            let stream =
+                connect(url, config, proxy_url.as_ref(), None, &resolver, &observers).await?;
 
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            let mut driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            driver.observers = observers.clone();
             let client = Self {
                 cmd_tx,
+                observers,
                 _client_type: Default::default(),
             };
 
@@ -484,20 +892,27 @@ mod sealed {
             url: Url,
             compat: CompatMode,
             config: Option<WebSocketConfig>,
+            proxy_url: Option<ProxyUrl>,
+            tls: TlsClient,
+            resolver: Resolver,
+            observers: Observers,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
-            debug!("Connecting to secure WebSocket endpoint: {}", url);
-
-            // Not supplying a connector means async_tungstenite will create the
-            // connector for us.
-            let (stream, _response) =
-                connect_async_with_tls_connector_and_config(url, None, config)
-                    .await
-                    .map_err(Error::tungstenite)?;
+            let stream = connect(
+                url,
+                config,
+                proxy_url.as_ref(),
+                Some(&tls),
+                &resolver,
+                &observers,
+            )
+            .await?;
 
             let (cmd_tx, cmd_rx) = unbounded();
-            let driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            let mut driver = WebSocketClientDriver::new(stream, cmd_rx, compat);
+            driver.observers = observers.clone();
             let client = Self {
                 cmd_tx,
+                observers,
                 _client_type: Default::default(),
             };
 
@@ -522,27 +937,37 @@ mod sealed {
             R: SimpleRequest<S>,
             S: Dialect,
         {
+            let method = request.method().to_string();
             let wrapper = Wrapper::new(request);
             let id = wrapper.id().to_string();
             let wrapped_request = wrapper.into_json();
 
             tracing::debug!("Outgoing request: {}", wrapped_request);
 
-            let (response_tx, mut response_rx) = unbounded();
+            let meter = self.observers.meter(&method, wrapped_request.len());
+            let mut response_size = None;
+            let result = async {
+                let (response_tx, mut response_rx) = unbounded();
 
-            self.send_cmd(DriverCommand::SimpleRequest(SimpleRequestCommand {
-                id,
-                wrapped_request,
-                response_tx,
-            }))?;
+                self.send_cmd(DriverCommand::SimpleRequest(SimpleRequestCommand {
+                    id,
+                    method: method.clone(),
+                    wrapped_request,
+                    response_tx,
+                }))?;
 
-            let response = response_rx.recv().await.ok_or_else(|| {
-                Error::client_internal("failed to hear back from WebSocket driver".to_string())
-            })??;
+                let response = response_rx.recv().await.ok_or_else(|| {
+                    Error::client_internal("failed to hear back from WebSocket driver".to_string())
+                })??;
+                response_size = Some(response.len());
 
-            tracing::debug!("Incoming response: {}", response);
+                tracing::debug!("Incoming response: {}", response);
 
-            R::Response::from_string(response).map(Into::into)
+                R::Response::from_string(response).map(Into::into)
+            }
+            .await;
+            meter.complete(response_size, &result);
+            result
         }
 
         pub async fn subscribe(&self, query: Query) -> Result<Subscription, Error> {
@@ -550,17 +975,20 @@ mod sealed {
             let (response_tx, mut response_rx) = unbounded();
             // By default we use UUIDs to differentiate subscriptions
             let id = uuid_str();
+            let query_str = query.to_string();
+            let subscription = Subscription::new(id.clone(), query, subscription_rx);
             self.send_cmd(DriverCommand::Subscribe(SubscribeCommand {
-                id: id.to_string(),
-                query: query.to_string(),
+                id,
+                query: query_str,
                 subscription_tx,
+                received: subscription.received_counter(),
                 response_tx,
             }))?;
             // Make sure our subscription request went through successfully.
             response_rx.recv().await.ok_or_else(|| {
                 Error::client_internal("failed to hear back from WebSocket driver".to_string())
             })??;
-            Ok(Subscription::new(id, query, subscription_rx))
+            Ok(subscription)
         }
 
         pub async fn unsubscribe(&self, query: Query) -> Result<(), Error> {
@@ -574,6 +1002,25 @@ mod sealed {
             })??;
             Ok(())
         }
+
+        pub async fn subscriptions(&self) -> Result<Vec<SubscriptionStats>, Error> {
+            let (response_tx, mut response_rx) = unbounded();
+            self.send_cmd(DriverCommand::Subscriptions(response_tx))?;
+            response_rx.recv().await.ok_or_else(|| {
+                Error::client_internal("failed to hear back from WebSocket driver".to_string())
+            })
+        }
+
+        pub async fn close_subscription(&self, id: &str) -> Result<bool, Error> {
+            let (response_tx, mut response_rx) = unbounded();
+            self.send_cmd(DriverCommand::CloseSubscription(CloseSubscriptionCommand {
+                id: id.to_string(),
+                response_tx,
+            }))?;
+            response_rx.recv().await.ok_or_else(|| {
+                Error::client_internal("failed to hear back from WebSocket driver".to_string())
+            })
+        }
     }
 
     /// Allows us to erase the type signatures associated with the different
@@ -589,9 +1036,14 @@ mod sealed {
             url: Url,
             compat: CompatMode,
             config: Option<WebSocketConfig>,
+            proxy_url: Option<ProxyUrl>,
+            resolver: Resolver,
+            observers: Observers,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
-            let (client, driver) =
-                AsyncTungsteniteClient::<Unsecure>::new(url, compat, config).await?;
+            let (client, driver) = AsyncTungsteniteClient::<Unsecure>::new(
+                url, compat, config, proxy_url, resolver, observers,
+            )
+            .await?;
             Ok((Self::Unsecure(client), driver))
         }
 
@@ -599,9 +1051,15 @@ mod sealed {
             url: Url,
             compat: CompatMode,
             config: Option<WebSocketConfig>,
+            proxy_url: Option<ProxyUrl>,
+            tls: TlsClient,
+            resolver: Resolver,
+            observers: Observers,
         ) -> Result<(Self, WebSocketClientDriver), Error> {
-            let (client, driver) =
-                AsyncTungsteniteClient::<Secure>::new(url, compat, config).await?;
+            let (client, driver) = AsyncTungsteniteClient::<Secure>::new(
+                url, compat, config, proxy_url, tls, resolver, observers,
+            )
+            .await?;
             Ok((Self::Secure(client), driver))
         }
 
@@ -638,6 +1096,20 @@ mod sealed {
                 WebSocketClient::Secure(c) => c.unsubscribe(query).await,
             }
         }
+
+        pub async fn subscriptions(&self) -> Result<Vec<SubscriptionStats>, Error> {
+            match self {
+                WebSocketClient::Unsecure(c) => c.subscriptions().await,
+                WebSocketClient::Secure(c) => c.subscriptions().await,
+            }
+        }
+
+        pub async fn close_subscription(&self, id: &str) -> Result<bool, Error> {
+            match self {
+                WebSocketClient::Unsecure(c) => c.close_subscription(id).await,
+                WebSocketClient::Secure(c) => c.close_subscription(id).await,
+            }
+        }
     }
 
     use async_tungstenite::tungstenite;
@@ -683,6 +1155,10 @@ enum DriverCommand {
     Unsubscribe(UnsubscribeCommand),
     // For non-subscription-related requests.
     SimpleRequest(SimpleRequestCommand),
+    // List the active subscriptions along with their statistics.
+    Subscriptions(ChannelTx<Vec<SubscriptionStats>>),
+    // Close a single subscription.
+    CloseSubscription(CloseSubscriptionCommand),
     Terminate,
 }
 
@@ -694,6 +1170,8 @@ struct SubscribeCommand {
     query: String,
     // Where to send subscription events.
     subscription_tx: SubscriptionTx,
+    // The number of events received from the subscription.
+    received: Arc<AtomicU64>,
     // Where to send the result of the subscription request.
     response_tx: ChannelTx<Result<(), Error>>,
 }
@@ -706,12 +1184,22 @@ struct UnsubscribeCommand {
     response_tx: ChannelTx<Result<(), Error>>,
 }
 
+#[derive(Debug, Clone)]
+struct CloseSubscriptionCommand {
+    // The ID of the subscription to close.
+    id: String,
+    // Where to send whether there was such a subscription.
+    response_tx: ChannelTx<bool>,
+}
+
 #[derive(Debug, Clone)]
 struct SimpleRequestCommand {
     // The desired ID for the outgoing JSON-RPC request. Technically we
     // could extract this from the wrapped request, but that would mean
     // additional unnecessary computational resources for deserialization.
     id: String,
+    // The name of the RPC method, to report the timings of the request.
+    method: String,
     // The wrapped and serialized JSON-RPC request.
     wrapped_request: String,
     // Where to send the result of the simple request.
@@ -739,6 +1227,20 @@ pub struct WebSocketClientDriver {
     pending_commands: HashMap<SubscriptionId, DriverCommand>,
     // The compatibility mode directing how to parse subscription events.
     compat: CompatMode,
+    // How to reconnect when the connection is lost, if at all.
+    reconnect: Option<Reconnect>,
+    // How to detect that the connection is dead.
+    keep_alive: KeepAliveConfig,
+    // When an event was last published to the subscriptions, or when the
+    // first subscription was added since.
+    last_event: Instant,
+    // Commands to execute once the connection is reestablished.
+    deferred_commands: Vec<DriverCommand>,
+    // Where to report the timings of the requests and (re)connections.
+    observers: Observers,
+    // When the in-flight simple requests started to be sent, and when they
+    // were sent, indexed by their ID. Only tracked if there are observers.
+    request_timings: HashMap<SubscriptionId, (Instant, Instant)>,
 }
 
 impl WebSocketClientDriver {
@@ -753,6 +1255,12 @@ impl WebSocketClientDriver {
             cmd_rx,
             pending_commands: HashMap::new(),
             compat,
+            reconnect: None,
+            keep_alive: KeepAliveConfig::default(),
+            last_event: Instant::now(),
+            deferred_commands: Vec::new(),
+            observers: Observers::default(),
+            request_timings: HashMap::new(),
         }
     }
 
@@ -763,6 +1271,7 @@ impl WebSocketClientDriver {
     }
 
     async fn simple_request(&mut self, cmd: SimpleRequestCommand) -> Result<(), Error> {
+        let start = Instant::now();
         if let Err(e) = self
             .send_msg(Message::Text(cmd.wrapped_request.clone()))
             .await
@@ -770,6 +1279,10 @@ impl WebSocketClientDriver {
             cmd.response_tx.send(Err(e.clone()))?;
             return Err(e);
         }
+        if !self.observers.is_empty() {
+            self.request_timings
+                .insert(cmd.id.clone(), (start, Instant::now()));
+        }
         self.pending_commands
             .insert(cmd.id.clone(), DriverCommand::SimpleRequest(cmd));
         Ok(())
@@ -778,42 +1291,230 @@ impl WebSocketClientDriver {
     /// Executes the WebSocket driver, which manages the underlying WebSocket
     /// transport.
     pub async fn run(mut self) -> Result<(), Error> {
-        let mut ping_interval =
-            tokio::time::interval_at(Instant::now().add(PING_INTERVAL), PING_INTERVAL);
+        loop {
+            match self.run_connection().await {
+                Ok(()) => return self.close().await,
+                Err(e) if self.reconnect.is_some() => {
+                    if !self.reconnect(e).await? {
+                        return Ok(());
+                    }
+                },
+                Err(e) => {
+                    self.publish_error_to_all(e.clone());
+                    return Err(e);
+                },
+            }
+        }
+    }
 
-        let recv_timeout = tokio::time::sleep(RECV_TIMEOUT);
-        tokio::pin!(recv_timeout);
+    // Drives the current connection until the client asks the driver to
+    // terminate or the connection fails.
+    async fn run_connection(&mut self) -> Result<(), Error> {
+        for cmd in core::mem::take(&mut self.deferred_commands) {
+            self.execute(cmd).await?;
+        }
+
+        let keep_alive = self.keep_alive.clone();
+        let mut ping_interval = tokio::time::interval_at(
+            Instant::now().add(keep_alive.ping_interval),
+            keep_alive.ping_interval,
+        );
+        // When the first ping was sent since we last received a message.
+        let mut unanswered_ping: Option<Instant> = None;
+        self.last_event = Instant::now();
 
         loop {
+            let pong_deadline = unanswered_ping.map(|sent| sent.add(keep_alive.pong_timeout));
+            let stale_deadline = keep_alive
+                .stale_timeout
+                .filter(|_| self.router.has_active_subscriptions())
+                .map(|timeout| self.last_event.add(timeout));
+
             tokio::select! {
-                Some(res) = self.stream.next() => match res {
-                    Ok(msg) => {
-                        // Reset the receive timeout every time we successfully
-                        // receive a message from the remote endpoint.
-                        recv_timeout.as_mut().reset(Instant::now().add(RECV_TIMEOUT));
+                res = self.stream.next() => match res {
+                    Some(Ok(msg)) => {
+                        // Any message from the remote endpoint shows that
+                        // the connection is alive.
+                        unanswered_ping = None;
                         self.handle_incoming_msg(msg).await?
                     },
-                    Err(e) => return Err(
+                    Some(Err(e)) => return Err(
                         Error::web_socket(
                             "failed to read from WebSocket connection".to_string(),
                             e
                         ),
                     ),
+                    None => return Err(
+                        Error::web_socket(
+                            "WebSocket connection closed by the remote endpoint".to_string(),
+                            async_tungstenite::tungstenite::Error::ConnectionClosed,
+                        ),
+                    ),
                 },
                 Some(cmd) = self.cmd_rx.recv() => match cmd {
-                    DriverCommand::Subscribe(subs_cmd) => self.subscribe(subs_cmd).await?,
-                    DriverCommand::Unsubscribe(unsubs_cmd) => self.unsubscribe(unsubs_cmd).await?,
-                    DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await?,
-                    DriverCommand::Terminate => return self.close().await,
+                    DriverCommand::Terminate => return Ok(()),
+                    cmd => self.execute(cmd).await?,
+                },
+                _ = ping_interval.tick() => {
+                    self.ping().await?;
+                    unanswered_ping.get_or_insert_with(Instant::now);
+                },
+                _ = tokio::time::sleep_until(pong_deadline.unwrap_or_else(Instant::now)),
+                    if pong_deadline.is_some() =>
+                {
+                    return Err(Error::web_socket_timeout(keep_alive.pong_timeout));
+                },
+                _ = tokio::time::sleep_until(stale_deadline.unwrap_or_else(Instant::now)),
+                    if stale_deadline.is_some() =>
+                {
+                    return Err(Error::stale_subscriptions(
+                        keep_alive.stale_timeout.unwrap_or_default(),
+                    ));
                 },
-                _ = ping_interval.tick() => self.ping().await?,
-                _ = &mut recv_timeout => {
-                    return Err(Error::web_socket_timeout(RECV_TIMEOUT));
+            }
+        }
+    }
+
+    async fn execute(&mut self, cmd: DriverCommand) -> Result<(), Error> {
+        match cmd {
+            DriverCommand::Subscribe(subs_cmd) => self.subscribe(subs_cmd).await,
+            DriverCommand::Unsubscribe(unsubs_cmd) => self.unsubscribe(unsubs_cmd).await,
+            DriverCommand::SimpleRequest(req_cmd) => self.simple_request(req_cmd).await,
+            DriverCommand::Subscriptions(response_tx) => response_tx.send(self.router.stats()),
+            DriverCommand::CloseSubscription(close_cmd) => {
+                if let Some(query) = self.close_subscription(close_cmd) {
+                    // No subscription is left for the query: we issue a
+                    // fire-and-forget unsubscribe message.
+                    if let Err(e) = self
+                        .send_request(Wrapper::new(unsubscribe::Request::new(query)))
+                        .await
+                    {
+                        error!("Failed to send unsubscribe request: {}", e);
+                    }
                 }
+                Ok(())
+            },
+            DriverCommand::Terminate => Ok(()),
+        }
+    }
+
+    // Closes the subscription with the given ID by dropping its sender.
+    //
+    // Returns its query if no subscription is left for it.
+    fn close_subscription(&mut self, cmd: CloseSubscriptionCommand) -> Option<SubscriptionQuery> {
+        let removed = self.router.remove(&cmd.id);
+        let _ = cmd.response_tx.send(removed.is_some());
+        removed.and_then(|(query, left)| (left == 0).then_some(query))
+    }
+
+    // Reestablishes the connection after it failed with the given error, and
+    // resubscribes to the queries of all active subscriptions.
+    //
+    // Returns `false` if the client asked the driver to terminate in the
+    // meantime, and an error if the reconnection policy gave up.
+    async fn reconnect(&mut self, cause: Error) -> Result<bool, Error> {
+        let reconnect = self
+            .reconnect
+            .clone()
+            .expect("reconnection is enabled when reconnecting");
+        error!("WebSocket connection failed, reconnecting: {}", cause);
+
+        // The responses to in-flight requests are lost along with the
+        // connection. Subscribing is idempotent, so those requests are sent
+        // again, whereas other requests fail as they may have been executed.
+        self.request_timings.clear();
+        for (_, cmd) in core::mem::take(&mut self.pending_commands) {
+            match cmd {
+                DriverCommand::Subscribe(subs_cmd) => self
+                    .deferred_commands
+                    .push(DriverCommand::Subscribe(subs_cmd)),
+                // The remote endpoint drops the subscriptions of a closed
+                // connection anyway.
+                DriverCommand::Unsubscribe(unsubs_cmd) => {
+                    let _ = unsubs_cmd.response_tx.send(Ok(()));
+                },
+                DriverCommand::SimpleRequest(req_cmd) => {
+                    let _ = req_cmd.response_tx.send(Err(cause.clone()));
+                },
+                DriverCommand::Subscriptions(_)
+                | DriverCommand::CloseSubscription(_)
+                | DriverCommand::Terminate => {},
+            }
+        }
+
+        self.publish_error_to_all(Error::subscription_gap(cause.detail().to_string()));
+
+        let mut backoff = reconnect.policy.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            let delay = tokio::time::sleep(backoff);
+            tokio::pin!(delay);
+            loop {
+                tokio::select! {
+                    _ = &mut delay => break,
+                    Some(cmd) = self.cmd_rx.recv() => match cmd {
+                        DriverCommand::Terminate => return Ok(false),
+                        // The registry of subscriptions is local, so it is
+                        // available while the connection is down. There is no
+                        // need to unsubscribe, as only the queries with active
+                        // subscriptions are resubscribed to.
+                        DriverCommand::Subscriptions(response_tx) => {
+                            let _ = response_tx.send(self.router.stats());
+                        },
+                        DriverCommand::CloseSubscription(close_cmd) => {
+                            self.close_subscription(close_cmd);
+                        },
+                        cmd => self.deferred_commands.push(cmd),
+                    },
+                }
+            }
+
+            attempts += 1;
+            let res = match sealed::connect(
+                reconnect.url.clone(),
+                reconnect.transport_config,
+                reconnect.proxy_url.as_ref(),
+                reconnect.tls.as_ref(),
+                &reconnect.resolver,
+                &self.observers,
+            )
+            .await
+            {
+                Ok(stream) => {
+                    self.stream = stream;
+                    self.resubscribe().await
+                },
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok(()) => {
+                    debug!(
+                        "Reconnected to {} after {} attempt(s)",
+                        reconnect.url, attempts
+                    );
+                    return Ok(true);
+                },
+                Err(e) if reconnect.policy.max_attempts == Some(attempts) => return Err(e),
+                Err(e) => {
+                    error!("Failed to reconnect to {}: {}", reconnect.url, e);
+                    backoff = (backoff * 2).min(reconnect.policy.max_backoff);
+                },
             }
         }
     }
 
+    // Issues a subscription request for each query with active subscriptions.
+    //
+    // The requests reuse the ID of one of the subscriptions to the query, so
+    // that errors in the responses are routed to its subscriptions.
+    async fn resubscribe(&mut self) -> Result<(), Error> {
+        for (query, id) in self.router.active_queries() {
+            let wrapper = Wrapper::new_with_id(Id::Str(id), subscribe::Request::new(query));
+            self.send_request(wrapper).await?;
+        }
+        Ok(())
+    }
+
     async fn send_request<R>(&mut self, wrapper: Wrapper<R>) -> Result<(), Error>
     where
         R: Request,
@@ -831,7 +1532,8 @@ impl WebSocketClientDriver {
         if self.router.num_subscriptions_for_query(cmd.query.clone()) > 0 {
             let (id, query, subscription_tx, response_tx) =
                 (cmd.id, cmd.query, cmd.subscription_tx, cmd.response_tx);
-            self.router.add(id, query, subscription_tx);
+            self.router.add(id.clone(), query, subscription_tx);
+            self.router.track_received(id, cmd.received);
             return response_tx.send(Ok(()));
         }
 
@@ -943,7 +1645,21 @@ impl WebSocketClientDriver {
         }
     }
 
+    // Publishes the error to all active subscriptions, without unsubscribing
+    // from the queries whose subscribers have all disconnected, as the
+    // connection is lost anyway.
+    fn publish_error_to_all(&mut self, err: Error) {
+        for (query, _) in self.router.active_queries() {
+            if let PublishResult::AllDisconnected(query) =
+                self.router.publish(query, Err(err.clone()))
+            {
+                self.router.remove_by_query(query);
+            }
+        }
+    }
+
     async fn publish_event(&mut self, ev: Event) {
+        self.last_event = Instant::now();
         if let PublishResult::AllDisconnected(query) = self.router.publish_event(ev) {
             debug!(
                 "All subscribers for query \"{}\" have disconnected. Unsubscribing from query...",
@@ -969,13 +1685,29 @@ impl WebSocketClientDriver {
     ) -> Result<(), Error> {
         match pending_cmd {
             DriverCommand::Subscribe(cmd) => {
+                if !self.router.has_active_subscriptions() {
+                    self.last_event = Instant::now();
+                }
                 let (id, query, subscription_tx, response_tx) =
                     (cmd.id, cmd.query, cmd.subscription_tx, cmd.response_tx);
-                self.router.add(id, query, subscription_tx);
+                self.router.add(id.clone(), query, subscription_tx);
+                self.router.track_received(id, cmd.received);
                 response_tx.send(Ok(()))
             },
             DriverCommand::Unsubscribe(cmd) => cmd.response_tx.send(Ok(())),
-            DriverCommand::SimpleRequest(cmd) => cmd.response_tx.send(Ok(response)),
+            DriverCommand::SimpleRequest(cmd) => {
+                if let Some((start, sent)) = self.request_timings.remove(&cmd.id) {
+                    let now = Instant::now();
+                    let timings = CallTimings {
+                        request_write: Some(sent - start),
+                        first_byte: Some(now - sent),
+                        total: now - start,
+                        ..Default::default()
+                    };
+                    self.observers.observe(&cmd.method, &timings);
+                }
+                cmd.response_tx.send(Ok(response))
+            },
             _ => Ok(()),
         }
     }
@@ -1032,6 +1764,8 @@ mod test {
         driver_hdl: JoinHandle<Result<(), Error>>,
         terminate_tx: ChannelTx<Result<(), Error>>,
         event_tx: ChannelTx<Event>,
+        // The queries unsubscribed from by the clients.
+        unsubscribed_rx: ChannelRx<String>,
     }
 
     impl TestServer {
@@ -1045,13 +1779,16 @@ mod test {
             };
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerDriver::new(listener, compat, event_rx, terminate_rx);
+            let (unsubscribed_tx, unsubscribed_rx) = unbounded();
+            let driver =
+                TestServerDriver::new(listener, compat, event_rx, terminate_rx, unsubscribed_tx);
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 node_addr,
                 driver_hdl,
                 terminate_tx,
                 event_tx,
+                unsubscribed_rx,
             }
         }
 
@@ -1059,6 +1796,11 @@ mod test {
             self.event_tx.send(ev)
         }
 
+        // Waits for a client to unsubscribe from a query, and returns it.
+        async fn unsubscribed(&mut self) -> String {
+            self.unsubscribed_rx.recv().await.unwrap()
+        }
+
         async fn terminate(self) -> Result<(), Error> {
             self.terminate_tx.send(Ok(())).unwrap();
             self.driver_hdl.await.unwrap()
@@ -1071,6 +1813,7 @@ mod test {
         compat: CompatMode,
         event_rx: ChannelRx<Event>,
         terminate_rx: ChannelRx<Result<(), Error>>,
+        unsubscribed_tx: ChannelTx<String>,
         handlers: Vec<TestServerHandler>,
     }
 
@@ -1080,12 +1823,14 @@ mod test {
             compat: CompatMode,
             event_rx: ChannelRx<Event>,
             terminate_rx: ChannelRx<Result<(), Error>>,
+            unsubscribed_tx: ChannelTx<String>,
         ) -> Self {
             Self {
                 listener,
                 compat,
                 event_rx,
                 terminate_rx,
+                unsubscribed_tx,
                 handlers: Vec::new(),
             }
         }
@@ -1115,8 +1860,9 @@ mod test {
         }
 
         async fn handle_incoming(&mut self, stream: TcpStream) {
-            self.handlers
-                .push(TestServerHandler::new(stream, self.compat).await);
+            self.handlers.push(
+                TestServerHandler::new(stream, self.compat, self.unsubscribed_tx.clone()).await,
+            );
         }
 
         async fn terminate(&mut self) {
@@ -1139,12 +1885,17 @@ mod test {
     }
 
     impl TestServerHandler {
-        async fn new(stream: TcpStream, compat: CompatMode) -> Self {
+        async fn new(
+            stream: TcpStream,
+            compat: CompatMode,
+            unsubscribed_tx: ChannelTx<String>,
+        ) -> Self {
             let conn: WebSocketStream<TokioAdapter<TcpStream>> =
                 accept_async(stream).await.unwrap();
             let (terminate_tx, terminate_rx) = unbounded();
             let (event_tx, event_rx) = unbounded();
-            let driver = TestServerHandlerDriver::new(conn, compat, event_rx, terminate_rx);
+            let driver =
+                TestServerHandlerDriver::new(conn, compat, event_rx, terminate_rx, unsubscribed_tx);
             let driver_hdl = tokio::spawn(async move { driver.run().await });
             Self {
                 driver_hdl,
@@ -1169,6 +1920,8 @@ mod test {
         compat: CompatMode,
         event_rx: ChannelRx<Event>,
         terminate_rx: ChannelRx<Result<(), Error>>,
+        // Where to report the queries unsubscribed from.
+        unsubscribed_tx: ChannelTx<String>,
         // A mapping of subscription queries to subscription IDs for this
         // connection.
         subscriptions: HashMap<String, String>,
@@ -1180,12 +1933,14 @@ mod test {
             compat: CompatMode,
             event_rx: ChannelRx<Event>,
             terminate_rx: ChannelRx<Result<(), Error>>,
+            unsubscribed_tx: ChannelTx<String>,
         ) -> Self {
             Self {
                 conn,
                 compat,
                 event_rx,
                 terminate_rx,
+                unsubscribed_tx,
                 subscriptions: HashMap::new(),
             }
         }
@@ -1294,6 +2049,7 @@ mod test {
             if let Some(id) = self.subscriptions.remove(&query) {
                 println!("Removed subscription {id} for query: {query}");
             }
+            let _ = self.unsubscribed_tx.send(query);
         }
 
         async fn send<R>(&mut self, id: Id, res: R)
@@ -1463,6 +2219,283 @@ mod test {
                 );
             }
         }
+
+        #[tokio::test]
+        async fn websocket_client_reconnects() {
+            let event1 = read_event("subscribe_newblock_0").await;
+            let event2 = read_event("subscribe_newblock_1").await;
+
+            let server = TestServer::new("127.0.0.1:0", CompatMode::V0_37).await;
+            let node_addr = server.node_addr.clone();
+            let url = node_addr.clone().try_into().unwrap();
+            let (client, driver) = WebSocketClient::builder(url)
+                .reconnect(ReconnectConfig {
+                    initial_backoff: Duration::from_millis(10),
+                    max_backoff: Duration::from_millis(100),
+                    max_attempts: Some(50),
+                })
+                .build()
+                .await
+                .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+
+            // Restart the server on the same address.
+            server.terminate().await.unwrap();
+            let addr = match &node_addr {
+                net::Address::Tcp { host, port, .. } => format!("{host}:{port}"),
+                _ => unreachable!(),
+            };
+            let mut server = TestServer::new(&addr, CompatMode::V0_37).await;
+
+            let gap = subs.next().await.unwrap().unwrap_err();
+            assert!(matches!(
+                gap.detail(),
+                crate::error::ErrorDetail::SubscriptionGap(_)
+            ));
+
+            // Requests are only sent once the subscriptions have been
+            // restored, so after this one completes the server knows about the
+            // new block query.
+            let _tx_subs = client.subscribe(EventType::Tx.into()).await.unwrap();
+
+            server.publish_event(event1.clone()).unwrap();
+            server.publish_event(event2.clone()).unwrap();
+            assert_eq!(subs.next().await.unwrap().unwrap(), event1);
+            assert_eq!(subs.next().await.unwrap().unwrap(), event2);
+
+            client.close().unwrap();
+            server.terminate().await.unwrap();
+            let _ = driver_handle.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn websocket_client_detects_stale_subscriptions() {
+            let event = read_event("subscribe_newblock_0").await;
+
+            let mut server = TestServer::new("127.0.0.1:0", CompatMode::V0_37).await;
+            let url = server.node_addr.clone().try_into().unwrap();
+            let (client, driver) = WebSocketClient::builder(url)
+                .keep_alive(KeepAliveConfig {
+                    stale_timeout: Some(Duration::from_millis(200)),
+                    ..Default::default()
+                })
+                .build()
+                .await
+                .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut subs = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            server.publish_event(event.clone()).unwrap();
+            assert_eq!(subs.next().await.unwrap().unwrap(), event);
+
+            // No further event is published.
+            let stale = subs.next().await.unwrap().unwrap_err();
+            assert!(matches!(
+                stale.detail(),
+                crate::error::ErrorDetail::StaleSubscriptions(_)
+            ));
+            assert!(subs.next().await.is_none());
+
+            let res = driver_handle.await.unwrap();
+            assert!(matches!(
+                res.unwrap_err().detail(),
+                crate::error::ErrorDetail::StaleSubscriptions(_)
+            ));
+            server.terminate().await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn websocket_client_times_out_without_pongs() {
+            // The server accepts the connection but never reads from it, so
+            // that the pings of the client are left unanswered.
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let local_addr = listener.local_addr().unwrap();
+            let server = tokio::spawn(async move {
+                let (stream, _) = listener.accept().await.unwrap();
+                let _conn = accept_async(stream).await.unwrap();
+                futures::future::pending::<()>().await
+            });
+
+            let node_addr = net::Address::Tcp {
+                peer_id: None,
+                host: local_addr.ip().to_string(),
+                port: local_addr.port(),
+            };
+            let pong_timeout = Duration::from_millis(200);
+            let (_client, driver) = WebSocketClient::builder(node_addr.try_into().unwrap())
+                .keep_alive(KeepAliveConfig {
+                    ping_interval: Duration::from_millis(50),
+                    pong_timeout,
+                    ..Default::default()
+                })
+                .build()
+                .await
+                .unwrap();
+
+            let res = driver.run().await;
+            match res.unwrap_err().detail() {
+                crate::error::ErrorDetail::WebSocketTimeout(e) => {
+                    assert_eq!(e.timeout, pong_timeout)
+                },
+                detail => panic!("unexpected error: {detail}"),
+            }
+            server.abort();
+        }
+
+        #[test]
+        #[should_panic(expected = "the ping interval must not be zero")]
+        fn websocket_client_rejects_zero_ping_interval() {
+            let url = "ws://127.0.0.1:26657/websocket".parse().unwrap();
+            let _ = WebSocketClient::builder(url).keep_alive(KeepAliveConfig {
+                ping_interval: Duration::ZERO,
+                ..Default::default()
+            });
+        }
+
+        // Accept a single WebSocket connection over TLS, with the test server
+        // certificate issued for `rpc.example`.
+        async fn accept_tls(listener: TcpListener) -> Result<(), Error> {
+            use crate::client::transport::tls::{parse_certificates, parse_private_key};
+
+            let mut config = rustls::ServerConfig::new(rustls::NoClientAuth::new());
+            config
+                .set_single_cert(
+                    parse_certificates(include_bytes!("../../../tests/tls_fixtures/server.pem"))?,
+                    parse_private_key(include_bytes!("../../../tests/tls_fixtures/server.key"))?,
+                )
+                .unwrap();
+            let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+            let (stream, _) = listener.accept().await.map_err(Error::io)?;
+            let stream = acceptor.accept(stream).await.map_err(Error::io)?;
+            let mut conn = accept_async(stream).await.map_err(Error::tungstenite)?;
+            while conn.next().await.is_some() {}
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn websocket_client_connects_with_tls_config() {
+            let tls_config = TlsConfig::new()
+                .native_roots(false)
+                .webpki_roots(false)
+                .server_name("rpc.example");
+
+            // The server certificate is not trusted without the test CA.
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("wss://{}/websocket", listener.local_addr().unwrap());
+            let server = tokio::spawn(accept_tls(listener));
+            let res = WebSocketClient::builder(url.parse().unwrap())
+                .tls_config(tls_config.clone())
+                .build()
+                .await;
+            match res {
+                Err(e) => assert!(matches!(e.detail(), crate::error::ErrorDetail::Io(_))),
+                Ok(_) => panic!("connected to an untrusted server"),
+            }
+            server.await.unwrap().unwrap_err();
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("wss://{}/websocket", listener.local_addr().unwrap());
+            let server = tokio::spawn(accept_tls(listener));
+            let (client, driver) = WebSocketClient::builder(url.parse().unwrap())
+                .tls_config(
+                    tls_config
+                        .add_root_certificates_pem(include_bytes!(
+                            "../../../tests/tls_fixtures/ca.pem"
+                        ))
+                        .unwrap(),
+                )
+                .build()
+                .await
+                .unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            client.close().unwrap();
+            driver_handle.await.unwrap().unwrap();
+            server.await.unwrap().unwrap();
+        }
+
+        #[tokio::test]
+        async fn websocket_client_lists_and_closes_subscriptions() {
+            let event = read_event("subscribe_newblock_0").await;
+
+            let mut server = TestServer::new("127.0.0.1:0", CompatMode::V0_37).await;
+            let url = server.node_addr.clone().try_into().unwrap();
+            let (client, driver) = WebSocketClient::builder(url).build().await.unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let mut first = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            let mut second = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            let query = first.query().to_string();
+
+            // The event is delivered to both subscriptions at once, but only
+            // received from the first one.
+            server.publish_event(event.clone()).unwrap();
+            assert_eq!(first.next().await.unwrap().unwrap(), event);
+
+            let stats = client.subscriptions().await.unwrap();
+            assert_eq!(stats.len(), 2);
+            let first_stats = stats.iter().find(|s| s.id == first.id()).unwrap();
+            assert_eq!(first_stats.query, query);
+            assert_eq!((first_stats.delivered, first_stats.buffered), (1, 0));
+            assert!(first_stats.last_event.is_some());
+            let second_stats = stats.iter().find(|s| s.id == second.id()).unwrap();
+            assert_eq!((second_stats.delivered, second_stats.buffered), (1, 1));
+
+            // Closing a subscription ends its stream once its buffered events
+            // are received, without unsubscribing from the query.
+            assert!(client.close_subscription(second.id()).await.unwrap());
+            assert!(!client.close_subscription(second.id()).await.unwrap());
+            assert_eq!(second.next().await.unwrap().unwrap(), event);
+            assert!(second.next().await.is_none());
+            let stats = client.subscriptions().await.unwrap();
+            assert_eq!(stats.len(), 1);
+            assert_eq!(stats[0].id, first.id());
+
+            // Closing the last subscription to the query unsubscribes from it.
+            assert!(client.close_subscription(first.id()).await.unwrap());
+            assert_eq!(server.unsubscribed().await, query);
+            assert!(first.next().await.is_none());
+            assert!(client.subscriptions().await.unwrap().is_empty());
+
+            client.close().unwrap();
+            server.terminate().await.unwrap();
+            let _ = driver_handle.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn websocket_client_unsubscribes_once_all_subscriptions_are_dropped() {
+            let event = read_event("subscribe_newblock_0").await;
+
+            let mut server = TestServer::new("127.0.0.1:0", CompatMode::V0_37).await;
+            let url = server.node_addr.clone().try_into().unwrap();
+            let (client, driver) = WebSocketClient::builder(url).build().await.unwrap();
+            let driver_handle = tokio::spawn(async move { driver.run().await });
+
+            let first = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            let mut second = client.subscribe(EventType::NewBlock.into()).await.unwrap();
+            let query = first.query().to_string();
+
+            // The client notices that a subscription was dropped when it next
+            // has an event for it.
+            drop(first);
+            server.publish_event(event.clone()).unwrap();
+            assert_eq!(second.next().await.unwrap().unwrap(), event);
+            let stats = client.subscriptions().await.unwrap();
+            assert_eq!(stats.len(), 1);
+            assert_eq!(stats[0].id, second.id());
+
+            drop(second);
+            server.publish_event(event).unwrap();
+            assert_eq!(server.unsubscribed().await, query);
+            assert!(client.subscriptions().await.unwrap().is_empty());
+
+            client.close().unwrap();
+            server.terminate().await.unwrap();
+            let _ = driver_handle.await.unwrap();
+        }
     }
 
     fn authorization(req: &http::Request<()>) -> Option<&str> {
@@ -0,0 +1,498 @@
+//! Watching the events of a list of values of an attribute, e.g. of the
+//! transactions of hundreds of addresses, with few subscriptions.
+//!
+//! The query language of the node has no `OR`, so watching many values with
+//! one subscription per value would exceed the number of subscriptions
+//! allowed per client (5 by default). A [`WatchList`] instead shards the
+//! watched values across a configurable number of subscriptions: the values
+//! are sorted and split into groups, each subscribed to with a query matching
+//! the common prefix of its group (`<key> CONTAINS '<prefix>'`), or the value
+//! itself for a group of one value. The events of these subscriptions are
+//! merged into a [`WatchStream`], which only yields the events whose
+//! attribute has one of the watched values, once.
+//!
+//! Values can be added to and removed from the list at any time, which only
+//! re-shards the subscriptions when a new value is not covered by the current
+//! ones, or when a subscription no longer covers any value.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use tendermint_rpc::{
+//!     client::watch::{WatchList, WatchListConfig},
+//!     query::{EventType, Query},
+//!     WebSocketClient,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (client, driver) = WebSocketClient::new("ws://127.0.0.1:26657/websocket")
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(async move { driver.run().await });
+//!
+//!     let addresses = ["cosmos1qypq...", "cosmos1xzy8..."];
+//!     let (mut watch_list, mut events) = WatchList::new(
+//!         client,
+//!         Query::from(EventType::Tx),
+//!         "transfer.recipient",
+//!         addresses,
+//!         WatchListConfig::default(),
+//!     )
+//!     .await
+//!     .unwrap();
+//!
+//!     watch_list.add("cosmos1m3h3...").await.unwrap();
+//!     while let Some(event) = events.next().await {
+//!         println!("{:?}", event);
+//!     }
+//! }
+//! ```
+
+use alloc::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::sync::Mutex;
+
+use futures::{stream::SelectAll, Stream, StreamExt};
+
+use crate::{
+    alloc_prelude::*,
+    client::sync::{unbounded, ChannelRx, ChannelTx},
+    event::Event,
+    query::Query,
+    Error, Subscription, SubscriptionClient,
+};
+
+/// The number of recent transaction hashes remembered to deliver the events
+/// matched by several subscriptions only once.
+const DEDUP_WINDOW: usize = 1024;
+
+/// Configuration of a [`WatchList`].
+#[derive(Clone, Debug)]
+pub struct WatchListConfig {
+    /// The maximum number of subscriptions to shard the watched values
+    /// across. Defaults to 5, the default maximum number of subscriptions
+    /// per client of the node.
+    pub max_subscriptions: usize,
+    /// The maximum length of the queries of the subscriptions, as accepted by
+    /// the node. Defaults to 512 bytes.
+    pub max_query_length: usize,
+}
+
+impl Default for WatchListConfig {
+    fn default() -> Self {
+        Self {
+            max_subscriptions: 5,
+            max_query_length: 512,
+        }
+    }
+}
+
+/// The values covered by a subscription of a watch list.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Shard {
+    /// A single value.
+    Exact(String),
+    /// The values containing the given prefix.
+    Contains(String),
+    /// Any value.
+    Any,
+}
+
+impl Shard {
+    fn covers(&self, value: &str) -> bool {
+        match self {
+            Shard::Exact(v) => v == value,
+            Shard::Contains(prefix) => value.contains(prefix.as_str()),
+            Shard::Any => true,
+        }
+    }
+
+    fn query(&self, base: &Query, key: &str) -> Query {
+        let query = base.clone();
+        match self {
+            Shard::Exact(value) => query.and_eq(key, value.as_str()),
+            Shard::Contains(prefix) => query.and_contains(key, prefix),
+            Shard::Any => query.and_exists(key),
+        }
+    }
+}
+
+/// Splits the given values into at most `max` shards.
+fn shard(values: &BTreeSet<String>, max: usize) -> BTreeSet<Shard> {
+    let values: Vec<&String> = values.iter().collect();
+    let groups = max.max(1).min(values.len());
+    let mut shards = BTreeSet::new();
+    let mut start = 0;
+    for group in 0..groups {
+        // Spread the remainder over the first groups.
+        let len = values.len() / groups + usize::from(group < values.len() % groups);
+        let (first, last) = (values[start], values[start + len - 1]);
+        start += len;
+        if len == 1 {
+            shards.insert(Shard::Exact(first.clone()));
+            continue;
+        }
+        // The values are sorted, so the common prefix of the first and last
+        // values of the group is that of the whole group.
+        let prefix_len = first
+            .char_indices()
+            .zip(last.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(first.len().min(last.len()), |((i, _), _)| i);
+        if prefix_len == 0 {
+            shards.insert(Shard::Any);
+        } else {
+            shards.insert(Shard::Contains(first[..prefix_len].to_owned()));
+        }
+    }
+    shards
+}
+
+/// Watches the events of a list of values of an attribute, sharded across a
+/// bounded number of subscriptions of the given client.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug)]
+pub struct WatchList<C> {
+    client: C,
+    base: Query,
+    key: String,
+    config: WatchListConfig,
+    values: Arc<Mutex<BTreeSet<String>>>,
+    shards: BTreeSet<Shard>,
+    subscriptions_tx: ChannelTx<Subscription>,
+}
+
+impl<C> WatchList<C>
+where
+    C: SubscriptionClient + Sync,
+{
+    /// Subscribe to the events matching the `base` query whose attribute
+    /// `key` (e.g. `transfer.recipient`) has one of the given values.
+    ///
+    /// Returns the watch list, through which values can be added and
+    /// removed, and the stream of the events of the watched values.
+    pub async fn new<I>(
+        client: C,
+        base: Query,
+        key: impl ToString,
+        values: I,
+        config: WatchListConfig,
+    ) -> Result<(Self, WatchStream), Error>
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let values: BTreeSet<String> = values.into_iter().map(|v| v.to_string()).collect();
+        let values = Arc::new(Mutex::new(values));
+        let (subscriptions_tx, subscriptions_rx) = unbounded();
+        let key = key.to_string();
+        let stream = WatchStream {
+            subscriptions_rx: Some(subscriptions_rx),
+            subscriptions: SelectAll::new(),
+            key: key.clone(),
+            values: values.clone(),
+            seen: VecDeque::new(),
+        };
+        let mut watch_list = Self {
+            client,
+            base,
+            key,
+            config,
+            values,
+            shards: BTreeSet::new(),
+            subscriptions_tx,
+        };
+        watch_list.reshard().await?;
+        Ok((watch_list, stream))
+    }
+
+    /// Watch the given value, re-sharding the subscriptions if none covers
+    /// it.
+    pub async fn add(&mut self, value: impl ToString) -> Result<(), Error> {
+        let value = value.to_string();
+        let covered = self.shards.iter().any(|shard| shard.covers(&value));
+        self.values.lock().unwrap().insert(value);
+        if !covered {
+            self.reshard().await?;
+        }
+        Ok(())
+    }
+
+    /// Stop watching the given value, re-sharding the subscriptions if one of
+    /// them no longer covers any watched value.
+    pub async fn remove(&mut self, value: &str) -> Result<(), Error> {
+        let idle = {
+            let mut values = self.values.lock().unwrap();
+            values.remove(value);
+            self.shards
+                .iter()
+                .any(|shard| !values.iter().any(|v| shard.covers(v)))
+        };
+        if idle {
+            self.reshard().await?;
+        }
+        Ok(())
+    }
+
+    /// The client of the subscriptions.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// The watched values.
+    pub fn values(&self) -> Vec<String> {
+        self.values.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// The queries of the current subscriptions.
+    pub fn queries(&self) -> Vec<Query> {
+        self.shards
+            .iter()
+            .map(|shard| shard.query(&self.base, &self.key))
+            .collect()
+    }
+
+    /// Unsubscribe from all the subscriptions, which ends the stream of
+    /// events.
+    pub async fn close(mut self) -> Result<(), Error> {
+        for shard in core::mem::take(&mut self.shards) {
+            self.client
+                .unsubscribe(shard.query(&self.base, &self.key))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Re-compute the shards of the watched values, subscribing to the new
+    /// shards before unsubscribing from the previous ones, so that no event
+    /// is missed in between.
+    async fn reshard(&mut self) -> Result<(), Error> {
+        let shards = shard(&self.values.lock().unwrap(), self.config.max_subscriptions);
+        for shard in shards.difference(&self.shards) {
+            let query = shard.query(&self.base, &self.key);
+            let length = query.to_string().len();
+            if length > self.config.max_query_length {
+                return Err(Error::invalid_params(format!(
+                    "watch list query of {} bytes exceeds the maximum of {} bytes: {}",
+                    length, self.config.max_query_length, query
+                )));
+            }
+            let subscription = self.client.subscribe(query).await?;
+            self.subscriptions_tx.send(subscription)?;
+        }
+        for shard in self.shards.difference(&shards) {
+            self.client
+                .unsubscribe(shard.query(&self.base, &self.key))
+                .await?;
+        }
+        self.shards = shards;
+        Ok(())
+    }
+}
+
+/// The events of the values of a [`WatchList`], merged from its
+/// subscriptions.
+///
+/// Ends once the watch list is closed, or dropped and all of its
+/// subscriptions have ended.
+#[derive(Debug)]
+pub struct WatchStream {
+    subscriptions_rx: Option<ChannelRx<Subscription>>,
+    subscriptions: SelectAll<Subscription>,
+    key: String,
+    values: Arc<Mutex<BTreeSet<String>>>,
+    seen: VecDeque<String>,
+}
+
+impl WatchStream {
+    /// Whether the given event is of a watched value, and was not already
+    /// yielded.
+    fn accept(&mut self, event: &Event) -> bool {
+        let Some(events) = &event.events else {
+            return false;
+        };
+        let watched = events.get(&self.key).is_some_and(|values| {
+            let watched = self.values.lock().unwrap();
+            values.iter().any(|v| watched.contains(v))
+        });
+        if !watched {
+            return false;
+        }
+        let Some(hash) = events.get("tx.hash").and_then(|h| h.first()) else {
+            return true;
+        };
+        if self.seen.contains(hash) {
+            return false;
+        }
+        if self.seen.len() == DEDUP_WINDOW {
+            self.seen.pop_front();
+        }
+        self.seen.push_back(hash.clone());
+        true
+    }
+}
+
+impl Stream for WatchStream {
+    type Item = Result<Event, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(rx) = &mut this.subscriptions_rx {
+            loop {
+                match rx.poll_next_unpin(cx) {
+                    Poll::Ready(Some(subscription)) => this.subscriptions.push(subscription),
+                    Poll::Ready(None) => {
+                        this.subscriptions_rx = None;
+                        break;
+                    },
+                    Poll::Pending => break,
+                }
+            }
+        }
+        loop {
+            match this.subscriptions.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if this.accept(&event) {
+                        return Poll::Ready(Some(Ok(event)));
+                    }
+                },
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                // New subscriptions may still come from the watch list.
+                Poll::Ready(None) if this.subscriptions_rx.is_some() => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        client::{MockClient, MockRequestMethodMatcher},
+        query::EventType,
+    };
+
+    fn values(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn shards_sorted_values_by_common_prefix() {
+        let shards = shard(&values(&["abc1", "abd2", "xy1", "xz"]), 2);
+        assert_eq!(
+            shards,
+            [Shard::Contains("ab".into()), Shard::Contains("x".into())]
+                .into_iter()
+                .collect()
+        );
+
+        let shards = shard(&values(&["abc", "xyz"]), 5);
+        assert_eq!(
+            shards,
+            [Shard::Exact("abc".into()), Shard::Exact("xyz".into())]
+                .into_iter()
+                .collect()
+        );
+
+        assert_eq!(
+            shard(&values(&["abc", "xyz"]), 1),
+            [Shard::Any].into_iter().collect()
+        );
+    }
+
+    fn tx_event(query: &Query, recipient: &str, hash: &str) -> Event {
+        let mut events = BTreeMap::new();
+        events.insert("transfer.recipient".to_owned(), vec![recipient.to_owned()]);
+        events.insert("tx.hash".to_owned(), vec![hash.to_owned()]);
+        Event {
+            query: query.to_string(),
+            data: crate::event::EventData::GenericJsonEvent(serde_json::Value::Null),
+            events: Some(events),
+        }
+    }
+
+    #[tokio::test]
+    async fn watches_values_across_shards() {
+        let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+        tokio::spawn(driver.run());
+        let publish = |watch_list: &WatchList<MockClient<_>>, event| {
+            watch_list.client().publish(&event);
+        };
+        let base = Query::from(EventType::Tx);
+        let config = WatchListConfig {
+            max_subscriptions: 2,
+            ..Default::default()
+        };
+        let (mut watch_list, mut events) = WatchList::new(
+            client,
+            base.clone(),
+            "transfer.recipient",
+            ["abc1", "abd2", "xy1", "xy2"],
+            config,
+        )
+        .await
+        .unwrap();
+        let queries = watch_list.queries();
+        assert_eq!(
+            queries,
+            [
+                base.clone().and_contains("transfer.recipient", "ab"),
+                base.clone().and_contains("transfer.recipient", "xy"),
+            ]
+        );
+
+        // Events of unwatched values matched by a shard are filtered out, and
+        // the events matched by several shards are yielded once.
+        publish(&watch_list, tx_event(&queries[0], "abz", "A"));
+        publish(&watch_list, tx_event(&queries[0], "abc1", "B"));
+        publish(&watch_list, tx_event(&queries[1], "abc1", "B"));
+        publish(&watch_list, tx_event(&queries[1], "xy2", "C"));
+        let hashes = |event: Event| event.events.unwrap()["tx.hash"][0].clone();
+        let received: Vec<_> = (&mut events)
+            .take(2)
+            .map(|e| hashes(e.unwrap()))
+            .collect()
+            .await;
+        assert_eq!(received, ["B", "C"]);
+
+        // A covered value does not change the subscriptions.
+        watch_list.add("xy3").await.unwrap();
+        assert_eq!(watch_list.queries(), queries);
+        publish(&watch_list, tx_event(&queries[1], "xy3", "D"));
+        assert_eq!(hashes(events.next().await.unwrap().unwrap()), "D");
+
+        // An uncovered value re-shards them.
+        watch_list.add("q1").await.unwrap();
+        let queries = watch_list.queries();
+        assert_eq!(
+            queries,
+            [
+                base.clone().and_contains("transfer.recipient", "xy"),
+                base.clone().and_exists("transfer.recipient"),
+            ]
+        );
+        publish(&watch_list, tx_event(&queries[1], "q1", "E"));
+        assert_eq!(hashes(events.next().await.unwrap().unwrap()), "E");
+
+        watch_list.remove("q1").await.unwrap();
+        publish(&watch_list, tx_event(&queries[1], "q1", "F"));
+        publish(&watch_list, tx_event(&queries[1], "abd2", "G"));
+        assert_eq!(hashes(events.next().await.unwrap().unwrap()), "G");
+
+        watch_list.close().await.unwrap();
+        assert!(events.next().await.is_none());
+    }
+}
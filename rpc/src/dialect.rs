@@ -1,6 +1,7 @@
 //! Helper types to generalize differences in serialization between
 //! Tendermint RPC protocol versions.
 
+pub mod v0_33;
 pub mod v0_34;
 pub mod v0_37;
 
@@ -27,6 +28,7 @@ pub type LatestDialect = v0_37::Dialect;
 mod sealed {
     pub trait Sealed {}
 
+    impl Sealed for super::v0_33::Dialect {}
     impl Sealed for super::v0_34::Dialect {}
     impl Sealed for super::v0_37::Dialect {}
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use tendermint::abci;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BeginBlock<Ev> {
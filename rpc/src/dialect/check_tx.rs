@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use tendermint::abci::{self, Code};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::serializers;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -27,10 +27,10 @@ pub struct CheckTx<Ev> {
     /// **May be non-deterministic**.
     pub info: String,
     /// Amount of gas requested for the transaction.
-    #[serde(with = "serializers::from_str")]
+    #[serde(with = "serializers::from_str", alias = "gasWanted")]
     pub gas_wanted: i64,
     /// Amount of gas consumed by the transaction.
-    #[serde(with = "serializers::from_str")]
+    #[serde(with = "serializers::from_str", alias = "gasUsed")]
     pub gas_used: i64,
     /// Events that occurred while checking the transaction.
     pub events: Vec<Ev>,
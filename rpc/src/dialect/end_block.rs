@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use tendermint::{abci, consensus, validator};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::serializers;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
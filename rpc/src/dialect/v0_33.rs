@@ -0,0 +1,146 @@
+//! Read-only dialect for the RPC responses of Tendermint 0.33.
+//!
+//! This dialect is only meant to decode archived responses of 0.33 nodes into
+//! the current domain types; clients cannot be configured to use it. It
+//! differs from the 0.34 dialect in that event attributes carry no `index`
+//! flag: indexing was configured per node at the time, so decoded attributes
+//! are never flagged for indexing.
+//!
+//! Data which cannot be meaningfully converted into the current domain types,
+//! such as the Amino-encoded evidence of 0.33 blocks, is not supported.
+
+use tendermint::abci;
+
+use crate::alloc_prelude::*;
+use crate::serializers::bytes::base64string;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone)]
+pub struct Dialect;
+
+impl crate::dialect::Dialect for Dialect {
+    type Event = Event;
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub attributes: Vec<EventAttribute>,
+}
+
+impl From<Event> for abci::Event {
+    fn from(msg: Event) -> Self {
+        Self {
+            kind: msg.kind,
+            attributes: msg.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<abci::Event> for Event {
+    fn from(msg: abci::Event) -> Self {
+        Self {
+            kind: msg.kind,
+            attributes: msg.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct EventAttribute {
+    /// The event key.
+    #[serde(
+        serialize_with = "base64string::serialize",
+        deserialize_with = "base64string::deserialize_to_string"
+    )]
+    pub key: String,
+    /// The event value.
+    #[serde(
+        serialize_with = "base64string::serialize",
+        deserialize_with = "base64string::deserialize_to_string"
+    )]
+    pub value: String,
+}
+
+impl From<EventAttribute> for abci::EventAttribute {
+    fn from(msg: EventAttribute) -> Self {
+        Self {
+            key: msg.key,
+            value: msg.value,
+            index: false,
+        }
+    }
+}
+
+impl From<abci::EventAttribute> for EventAttribute {
+    fn from(msg: abci::EventAttribute) -> Self {
+        Self {
+            key: msg.key,
+            value: msg.value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dialect;
+    use crate::{endpoint::block_results, Response};
+
+    #[test]
+    fn decode_block_results() {
+        // Response of a Tendermint 0.33 node, abridged
+        const RESPONSE: &str = r#"{
+            "jsonrpc": "2.0",
+            "id": -1,
+            "result": {
+                "height": "1466",
+                "txs_results": [
+                    {
+                        "code": 0,
+                        "data": null,
+                        "log": "",
+                        "info": "",
+                        "gasWanted": "0",
+                        "gasUsed": "0",
+                        "events": [
+                            {
+                                "type": "app",
+                                "attributes": [
+                                    { "key": "Y3JlYXRvcg==", "value": "Q29zbW9zaGkgTmV0b3dva28=" },
+                                    { "key": "a2V5", "value": "dHgw" }
+                                ]
+                            }
+                        ],
+                        "codespace": ""
+                    }
+                ],
+                "begin_block_events": null,
+                "end_block_events": null,
+                "validator_updates": [
+                    {
+                        "pub_key": {
+                            "type": "ed25519",
+                            "data": "VqJCr3vjQdffcLIG6RMBl2MgXDFYNY6b3Joaa43gV3o="
+                        },
+                        "power": "573929"
+                    }
+                ],
+                "consensus_param_updates": null
+            }
+        }"#;
+
+        let response: block_results::Response =
+            <block_results::Request as crate::Request<Dialect>>::Response::from_string(RESPONSE)
+                .unwrap()
+                .into();
+
+        let txs_results = response.txs_results.unwrap();
+        let event = &txs_results[0].events[0];
+        assert_eq!(event.kind, "app");
+        assert_eq!(event.attributes[0].key, "creator");
+        assert_eq!(event.attributes[0].value, "Cosmoshi Netowoko");
+        assert!(!event.attributes[0].index);
+        assert_eq!(u64::from(response.validator_updates[0].power), 573929);
+    }
+}
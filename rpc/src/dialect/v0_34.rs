@@ -1,6 +1,6 @@
 use tendermint::abci;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::serializers::bytes::base64string;
 use serde::{Deserialize, Serialize};
 
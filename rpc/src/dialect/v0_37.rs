@@ -1,6 +1,6 @@
 use tendermint::abci;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Clone)]
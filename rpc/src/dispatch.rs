@@ -0,0 +1,230 @@
+//! Dispatching of JSON-RPC requests to typed handlers.
+//!
+//! A [`Dispatcher`] maps JSON-RPC methods to handler closures taking one of the
+//! request types of the [`endpoint`](crate::endpoint) module and returning its
+//! response type. This allows implementing (partial) Tendermint-compatible RPC
+//! servers in a few lines, leaving the choice of transport to the user:
+//!
+//! ```
+//! use tendermint_rpc::{dispatch::Dispatcher, endpoint::health};
+//!
+//! let dispatcher = Dispatcher::new()
+//!     .handle(|_: health::Request| Ok(health::Response {}));
+//!
+//! let response = dispatcher.dispatch(
+//!     r#"{"jsonrpc": "2.0", "id": 1, "method": "health", "params": null}"#,
+//! );
+//! assert!(response.contains(r#""result":{}"#));
+//! ```
+
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    alloc_prelude::*,
+    dialect::{Dialect, LatestDialect},
+    Id, Method, Request, ResponseError, Version,
+};
+
+type Handler = Box<dyn Fn(Value) -> Result<Value, ResponseError> + Send + Sync>;
+
+/// Routes incoming JSON-RPC requests to the handler registered for their method.
+pub struct Dispatcher<S: Dialect = LatestDialect> {
+    handlers: BTreeMap<Method, Handler>,
+    _dialect: PhantomData<S>,
+}
+
+impl Dispatcher {
+    /// Create a dispatcher without any handler, using the latest RPC dialect.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: Dialect> Default for Dispatcher<S> {
+    fn default() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            _dialect: PhantomData,
+        }
+    }
+}
+
+impl<S: Dialect> Dispatcher<S> {
+    /// Register the handler for the method of the request type `R`,
+    /// replacing any previously registered one.
+    ///
+    /// The parameters of incoming requests for this method are decoded as the
+    /// request type `R`; decoding failures are reported to the caller as
+    /// invalid parameters.
+    pub fn handle<R, F>(mut self, handler: F) -> Self
+    where
+        R: Request<S> + 'static,
+        F: Fn(R) -> Result<R::Response, ResponseError> + Send + Sync + 'static,
+    {
+        let handler = move |params: Value| {
+            let request: R = serde_json::from_value(params)
+                .map_err(|e| ResponseError::invalid_params(&e.to_string()))?;
+            let response = handler(request)?;
+            serde_json::to_value(response).map_err(ResponseError::server_error)
+        };
+        self.handlers.insert(R::METHOD, Box::new(handler));
+        self
+    }
+
+    /// The methods for which a handler is registered.
+    pub fn methods(&self) -> impl Iterator<Item = Method> + '_ {
+        self.handlers.keys().copied()
+    }
+
+    /// Process a JSON-RPC request, returning the serialized JSON-RPC response.
+    ///
+    /// Requests for methods without a registered handler result in a
+    /// "method not found" error response.
+    pub fn dispatch(&self, request: impl AsRef<[u8]>) -> String {
+        let request: IncomingRequest = match serde_json::from_slice(request.as_ref()) {
+            Ok(request) => request,
+            Err(e) => return OutgoingResponse::error(Id::None, ResponseError::parse_error(e)),
+        };
+
+        let result = request
+            .method
+            .parse::<Method>()
+            .ok()
+            .and_then(|method| self.handlers.get(&method))
+            .ok_or_else(|| ResponseError::method_not_found(&request.method))
+            .and_then(|handler| handler(request.params));
+
+        match result {
+            Ok(result) => OutgoingResponse::result(request.id, result),
+            Err(e) => OutgoingResponse::error(request.id, e),
+        }
+    }
+}
+
+/// JSON-RPC request envelope with undecoded parameters
+#[derive(Deserialize)]
+struct IncomingRequest {
+    #[serde(default = "default_id")]
+    id: Id,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn default_id() -> Id {
+    Id::None
+}
+
+/// JSON-RPC response envelope
+#[derive(Serialize)]
+struct OutgoingResponse {
+    jsonrpc: Version,
+    id: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ResponseError>,
+}
+
+impl OutgoingResponse {
+    fn result(id: Id, result: Value) -> String {
+        Self {
+            jsonrpc: Version::current(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+        .into_json()
+    }
+
+    fn error(id: Id, error: ResponseError) -> String {
+        Self {
+            jsonrpc: Version::current(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+        .into_json()
+    }
+
+    fn into_json(self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        endpoint::{block, health},
+        request::RequestMessage,
+        Code, Response,
+    };
+
+    fn dispatcher() -> Dispatcher {
+        Dispatcher::new()
+            .handle(|_: health::Request| Ok(health::Response {}))
+            .handle(|req: block::Request| {
+                Err(ResponseError::server_error(format!(
+                    "no block at height {:?}",
+                    req.height.map(|h| h.value())
+                )))
+            })
+    }
+
+    #[test]
+    fn dispatches_to_handler() {
+        let response = dispatcher().dispatch(health::Request.into_json());
+        health::Response::from_string(response).unwrap();
+    }
+
+    #[test]
+    fn reports_handler_errors() {
+        let response = dispatcher().dispatch(block::Request::new(10u32.into()).into_json());
+        let err = health::Response::from_string(response).unwrap_err();
+        match err.detail() {
+            crate::error::ErrorDetail::Response(e) => {
+                assert_eq!(e.source.code(), Code::ServerError);
+                assert_eq!(e.source.data(), Some("no block at height Some(10)"));
+            },
+            _ => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_unknown_methods() {
+        let dispatcher = dispatcher();
+        assert_eq!(
+            dispatcher.methods().collect::<Vec<_>>(),
+            vec![Method::Block, Method::Health]
+        );
+
+        for request in [
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "status"}"#,
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "no_such_method"}"#,
+        ] {
+            let response: Value = serde_json::from_str(&dispatcher.dispatch(request)).unwrap();
+            assert_eq!(response["id"], 1);
+            assert_eq!(response["error"]["code"], Code::MethodNotFound.value());
+        }
+    }
+
+    #[test]
+    fn reports_invalid_requests() {
+        let dispatcher = dispatcher();
+
+        let response: Value = serde_json::from_str(&dispatcher.dispatch("not json")).unwrap();
+        assert_eq!(response["error"]["code"], Code::ParseError.value());
+
+        let response: Value = serde_json::from_str(&dispatcher.dispatch(
+            r#"{"jsonrpc": "2.0", "id": "a", "method": "block", "params": {"height": "x"}}"#,
+        ))
+        .unwrap();
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], Code::InvalidParams.value());
+    }
+}
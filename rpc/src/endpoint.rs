@@ -11,8 +11,14 @@ pub mod broadcast;
 pub mod commit;
 pub mod consensus_params;
 pub mod consensus_state;
+#[cfg(feature = "unsafe-rpc")]
+pub mod dial_peers;
+#[cfg(feature = "unsafe-rpc")]
+pub mod dial_seeds;
+pub mod dump_consensus_state;
 pub mod evidence;
 pub mod genesis;
+pub mod genesis_chunked;
 pub mod header;
 pub mod header_by_hash;
 pub mod health;
@@ -21,5 +27,7 @@ pub mod status;
 pub mod subscribe;
 pub mod tx;
 pub mod tx_search;
+#[cfg(feature = "unsafe-rpc")]
+pub mod unsafe_flush_mempool;
 pub mod unsubscribe;
 pub mod validators;
@@ -10,9 +10,7 @@ use crate::request::RequestMessage;
 pub struct Request;
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::AbciInfo
-    }
+    const METHOD: crate::Method = crate::Method::AbciInfo;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
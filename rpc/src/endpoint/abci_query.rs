@@ -1,10 +1,15 @@
 //! `/abci_query` endpoint JSON-RPC wrapper
 
 use serde::{Deserialize, Serialize};
-use tendermint::{abci::Code, block, merkle::proof::ProofOps, serializers};
+use tendermint::{
+    abci::Code,
+    block,
+    merkle::proof::{ProofOps, ValueOp, VALUE_OP_TYPE},
+    serializers,
+};
 
-use crate::prelude::*;
-use crate::{dialect::Dialect, request::RequestMessage};
+use crate::alloc_prelude::*;
+use crate::{dialect::Dialect, request::RequestMessage, Error};
 
 /// Query the ABCI application for information
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -39,12 +44,65 @@ impl Request {
             prove,
         }
     }
+
+    /// Query the value stored at `key` in the store named `store` of a
+    /// Cosmos SDK application, i.e. at the path `/store/<store>/key`.
+    pub fn store_key<K>(store: &str, key: K) -> Self
+    where
+        K: Into<Vec<u8>>,
+    {
+        Self::new(Some(format!("/store/{store}/key")), key, None, false)
+    }
+
+    /// Query the key/value pairs whose keys start with `prefix` in the store
+    /// named `store` of a Cosmos SDK application, i.e. at the path
+    /// `/store/<store>/subspace`.
+    pub fn store_subspace<P>(store: &str, prefix: P) -> Self
+    where
+        P: Into<Vec<u8>>,
+    {
+        Self::new(
+            Some(format!("/store/{store}/subspace")),
+            prefix,
+            None,
+            false,
+        )
+    }
+
+    /// Query the state of the application at the given height, instead of
+    /// at the latest height.
+    pub fn at_height(mut self, height: block::Height) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Request a proof of the returned value.
+    pub fn with_proof(mut self) -> Self {
+        self.prove = true;
+        self
+    }
+}
+
+/// Prefix `bytes` with their length in a single byte, as the Cosmos SDK does
+/// with the addresses in the keys of its stores.
+///
+/// Fails if `bytes` are longer than 255 bytes.
+pub fn length_prefixed(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = u8::try_from(bytes.len()).map_err(|_| {
+        Error::invalid_params(format!(
+            "too long to be length-prefixed: {} bytes (max 255)",
+            bytes.len()
+        ))
+    })?;
+
+    let mut prefixed = Vec::with_capacity(bytes.len() + 1);
+    prefixed.push(len);
+    prefixed.extend_from_slice(bytes);
+    Ok(prefixed)
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::AbciQuery
-    }
+    const METHOD: crate::Method = crate::Method::AbciQuery;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -101,3 +159,82 @@ pub struct AbciQuery {
     #[serde(default = "String::new")]
     pub codespace: String,
 }
+
+impl AbciQuery {
+    /// Decode the proof of the value at the queried key, if the response
+    /// carries a proof operation of type [`VALUE_OP_TYPE`] for that key.
+    ///
+    /// The other proof operations, e.g. the ICS23 ones of the Cosmos SDK, are
+    /// left to the caller to decode.
+    pub fn value_proof(&self) -> Result<Option<ValueOp>, Error> {
+        let op = self.proof.as_ref().and_then(|proof| {
+            proof
+                .ops
+                .iter()
+                .find(|op| op.field_type == VALUE_OP_TYPE && op.key == self.key)
+        });
+
+        op.map(ValueOp::try_from)
+            .transpose()
+            .map_err(Error::tendermint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::merkle::proof::{Proof, ProofOp};
+
+    use super::*;
+
+    #[test]
+    fn builds_store_queries() {
+        let height = block::Height::from(42_u32);
+        let key = [b"\x02".as_slice(), &length_prefixed(b"addr").unwrap()].concat();
+
+        let request = Request::store_key("bank", key.clone())
+            .at_height(height)
+            .with_proof();
+        assert_eq!(request.path.as_deref(), Some("/store/bank/key"));
+        assert_eq!(request.data, b"\x02\x04addr");
+        assert_eq!(request.height, Some(height));
+        assert!(request.prove);
+
+        let request = Request::store_subspace("staking", b"\x21".to_vec());
+        assert_eq!(request.path.as_deref(), Some("/store/staking/subspace"));
+        assert_eq!(request.height, None);
+        assert!(!request.prove);
+
+        assert!(length_prefixed(&[0; 256]).is_err());
+    }
+
+    #[test]
+    fn decodes_value_proof() {
+        let value_op = ValueOp {
+            key: b"key".to_vec(),
+            proof: Proof {
+                total: 1,
+                index: 0,
+                leaf_hash: tendermint::Hash::Sha256([1; 32]),
+                aunts: vec![],
+            },
+        };
+        let op = |field_type: &str| ProofOp {
+            field_type: field_type.to_string(),
+            ..ProofOp::from(value_op.clone())
+        };
+
+        let mut query = AbciQuery {
+            key: b"key".to_vec(),
+            proof: Some(ProofOps {
+                ops: vec![op("multistore"), op(VALUE_OP_TYPE)],
+            }),
+            ..Default::default()
+        };
+        assert_eq!(query.value_proof().unwrap(), Some(value_op.clone()));
+
+        query.proof = Some(ProofOps {
+            ops: vec![op("ics23:iavl")],
+        });
+        assert_eq!(query.value_proof().unwrap(), None);
+    }
+}
@@ -24,9 +24,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Block
-    }
+    const METHOD: crate::Method = crate::Method::Block;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -33,9 +33,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::BlockByHash
-    }
+    const METHOD: crate::Method = crate::Method::BlockByHash;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -4,8 +4,8 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use tendermint::{abci, block, consensus, validator};
 
+use crate::alloc_prelude::*;
 use crate::dialect::{self, Dialect};
-use crate::prelude::*;
 use crate::request::RequestMessage;
 use crate::serializers;
 
@@ -28,9 +28,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::BlockResults
-    }
+    const METHOD: crate::Method = crate::Method::BlockResults;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -56,6 +54,10 @@ pub struct Response {
     /// End block events (might be explicit null)
     pub end_block_events: Option<Vec<abci::Event>>,
 
+    /// Finalize block events, which replace the begin and end block events
+    /// since CometBFT 0.38 (absent before)
+    pub finalize_block_events: Option<Vec<abci::Event>>,
+
     /// Validator updates (might be explicit null)
     pub validator_updates: Vec<validator::Update>,
 
@@ -78,6 +80,11 @@ pub struct DialectResponse<Ev> {
     /// End block events (might be explicit null)
     pub end_block_events: Option<Vec<Ev>>,
 
+    /// Finalize block events, which replace the begin and end block events
+    /// since CometBFT 0.38 (absent before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finalize_block_events: Option<Vec<Ev>>,
+
     /// Validator updates (might be explicit null)
     #[serde(deserialize_with = "serializers::nullable::deserialize")]
     pub validator_updates: Vec<validator::Update>,
@@ -104,6 +111,9 @@ where
             end_block_events: msg
                 .end_block_events
                 .map(|v| v.into_iter().map(Into::into).collect()),
+            finalize_block_events: msg
+                .finalize_block_events
+                .map(|v| v.into_iter().map(Into::into).collect()),
             validator_updates: msg.validator_updates,
             consensus_param_updates: msg.consensus_param_updates,
         }
@@ -3,7 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 pub use super::{block, block_results};
-use crate::{dialect::Dialect, prelude::*, request::RequestMessage, serializers, Method, Order};
+use crate::{
+    alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers, Method, Order,
+};
 
 /// Request for searching for blocks by their BeginBlock and EndBlock events.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -29,9 +31,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> Method {
-        Method::BlockSearch
-    }
+    const METHOD: Method = Method::BlockSearch;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -5,7 +5,7 @@ use core::ops::Range;
 use serde::{Deserialize, Serialize};
 use tendermint::block;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{dialect::Dialect, request::RequestMessage};
 
 /// Get information about a specific block
@@ -37,9 +37,7 @@ impl From<Range<block::Height>> for Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Blockchain
-    }
+    const METHOD: crate::Method = crate::Method::Blockchain;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use tendermint::{abci, block, Hash};
 
 use crate::dialect::{self, Dialect};
-use crate::{prelude::*, request::RequestMessage, serializers};
+use crate::{alloc_prelude::*, request::RequestMessage, serializers};
 
 /// `/broadcast_tx_commit`: only returns error if `mempool.CheckTx()` errs or
 /// if we timeout waiting for tx to commit.
@@ -29,9 +29,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::BroadcastTxCommit
-    }
+    const METHOD: crate::Method = crate::Method::BroadcastTxCommit;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -64,7 +62,8 @@ pub struct DialectResponse<Ev> {
     /// `CheckTx` result
     pub check_tx: dialect::CheckTx<Ev>,
 
-    /// `DeliverTx` result
+    /// `DeliverTx` result, reported as `tx_result` since CometBFT 0.38
+    #[serde(alias = "tx_result")]
     pub deliver_tx: dialect::DeliverTx<Ev>,
 
     /// Transaction
@@ -4,7 +4,7 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use tendermint::{abci::Code, Hash};
 
-use crate::{dialect::Dialect, prelude::*, request::RequestMessage, serializers};
+use crate::{alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers};
 
 /// `/broadcast_tx_sync`: returns with the response from `CheckTx`.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -22,9 +22,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::BroadcastTxSync
-    }
+    const METHOD: crate::Method = crate::Method::BroadcastTxSync;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -21,9 +21,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Commit
-    }
+    const METHOD: crate::Method = crate::Method::Commit;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -23,9 +23,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::ConsensusParams
-    }
+    const METHOD: crate::Method = crate::Method::ConsensusParams;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
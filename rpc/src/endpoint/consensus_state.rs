@@ -10,11 +10,16 @@ use tendermint::{
     hash, vote, Hash, Time,
 };
 
-use crate::{dialect::Dialect, prelude::*, request::RequestMessage, Error, Method};
+use crate::{
+    alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers, Error, Method,
+};
 
 // From <https://github.com/tendermint/tendermint/blob/e820e68acd69737cfb63bc9ccca5f5450a42b5cf/types/vote.go#L16>
 const NIL_VOTE_STR: &str = "nil-Vote";
 
+// From <https://github.com/tendermint/tendermint/blob/e820e68acd69737cfb63bc9ccca5f5450a42b5cf/libs/bits/bit_array.go#L267>
+const NIL_BIT_ARRAY_STR: &str = "nil-BitArray";
+
 /// Get the current consensus state.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Request;
@@ -26,9 +31,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> Method {
-        Method::ConsensusState
-    }
+    const METHOD: Method = Method::ConsensusState;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -73,14 +76,53 @@ pub struct RoundState {
 }
 
 /// A compound object indicating a height, round and step for consensus state.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HeightRoundStep {
     /// Current block height
     pub height: Height,
     /// Current consensus round
     pub round: Round,
     /// Current consensus step
-    pub step: i8,
+    pub step: RoundStep,
+}
+
+impl FromStr for HeightRoundStep {
+    type Err = Error;
+
+    /// Parses the `height/round/step` format used by Tendermint, e.g. `14/0/1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hrs: Vec<&str> = s.split('/').collect();
+        if hrs.len() != 3 {
+            return Err(Error::client_internal(format!(
+                "expected 3 components to height/round/step field, but got {}",
+                hrs.len()
+            )));
+        }
+        let height = Height::from_str(hrs[0]).map_err(|e| {
+            Error::client_internal(format!("failed to parse height/round/step height: {e}"))
+        })?;
+        let round = Round::from_str(hrs[1]).map_err(|e| {
+            Error::client_internal(format!("failed to parse height/round/step round: {e}"))
+        })?;
+        let step = RoundStep::from_str(hrs[2])?;
+        Ok(Self {
+            height,
+            round,
+            step,
+        })
+    }
+}
+
+impl fmt::Display for HeightRoundStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.height.value(),
+            self.round.value(),
+            u8::from(self.step)
+        )
+    }
 }
 
 impl Serialize for HeightRoundStep {
@@ -88,13 +130,7 @@ impl Serialize for HeightRoundStep {
     where
         S: Serializer,
     {
-        let hrs = format!(
-            "{}/{}/{}",
-            self.height.value(),
-            self.round.value(),
-            self.step
-        );
-        serializer.serialize_str(&hrs)
+        serializer.serialize_str(&self.to_string())
     }
 }
 
@@ -104,21 +140,126 @@ impl<'de> Deserialize<'de> for HeightRoundStep {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        let hrs: Vec<&str> = s.split('/').collect();
-        if hrs.len() != 3 {
-            return Err(serde::de::Error::custom(format!(
-                "expected 3 components to height/round/step field, but got {}",
-                hrs.len()
-            )));
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The step of a consensus round.
+///
+/// Based on <https://github.com/tendermint/tendermint/blob/e820e68acd69737cfb63bc9ccca5f5450a42b5cf/consensus/types/round_state.go#L17>
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RoundStep {
+    /// Wait til CommitTime + timeoutCommit
+    NewHeight,
+    /// Setup new round and go to RoundStepPropose
+    NewRound,
+    /// Did propose, gossip proposal
+    Propose,
+    /// Did prevote, gossip prevotes
+    Prevote,
+    /// Did receive any +2/3 prevotes, start timeout
+    PrevoteWait,
+    /// Did precommit, gossip precommits
+    Precommit,
+    /// Did receive any +2/3 precommits, start timeout
+    PrecommitWait,
+    /// Entered commit state machine
+    Commit,
+}
+
+impl From<RoundStep> for u8 {
+    fn from(step: RoundStep) -> Self {
+        match step {
+            RoundStep::NewHeight => 1,
+            RoundStep::NewRound => 2,
+            RoundStep::Propose => 3,
+            RoundStep::Prevote => 4,
+            RoundStep::PrevoteWait => 5,
+            RoundStep::Precommit => 6,
+            RoundStep::PrecommitWait => 7,
+            RoundStep::Commit => 8,
         }
-        let height = Height::from_str(hrs[0]).map_err(serde::de::Error::custom)?;
-        let round = Round::from_str(hrs[1]).map_err(serde::de::Error::custom)?;
-        let step = i8::from_str(hrs[2]).map_err(serde::de::Error::custom)?;
-        Ok(Self {
-            height,
-            round,
-            step,
-        })
+    }
+}
+
+impl TryFrom<u8> for RoundStep {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RoundStep::NewHeight),
+            2 => Ok(RoundStep::NewRound),
+            3 => Ok(RoundStep::Propose),
+            4 => Ok(RoundStep::Prevote),
+            5 => Ok(RoundStep::PrevoteWait),
+            6 => Ok(RoundStep::Precommit),
+            7 => Ok(RoundStep::PrecommitWait),
+            8 => Ok(RoundStep::Commit),
+            _ => Err(Error::client_internal(format!(
+                "invalid consensus round step: {value}"
+            ))),
+        }
+    }
+}
+
+impl FromStr for RoundStep {
+    type Err = Error;
+
+    /// Parses either the numeric value of the step, as found in
+    /// `height/round/step` strings, or its name, e.g. `RoundStepPrevote`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RoundStepNewHeight" => Ok(RoundStep::NewHeight),
+            "RoundStepNewRound" => Ok(RoundStep::NewRound),
+            "RoundStepPropose" => Ok(RoundStep::Propose),
+            "RoundStepPrevote" => Ok(RoundStep::Prevote),
+            "RoundStepPrevoteWait" => Ok(RoundStep::PrevoteWait),
+            "RoundStepPrecommit" => Ok(RoundStep::Precommit),
+            "RoundStepPrecommitWait" => Ok(RoundStep::PrecommitWait),
+            "RoundStepCommit" => Ok(RoundStep::Commit),
+            _ => u8::from_str(s)
+                .map_err(|e| {
+                    Error::client_internal(format!(
+                        "failed to parse consensus round step: {e} ({s})"
+                    ))
+                })?
+                .try_into(),
+        }
+    }
+}
+
+impl fmt::Display for RoundStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RoundStep::NewHeight => "RoundStepNewHeight",
+            RoundStep::NewRound => "RoundStepNewRound",
+            RoundStep::Propose => "RoundStepPropose",
+            RoundStep::Prevote => "RoundStepPrevote",
+            RoundStep::PrevoteWait => "RoundStepPrevoteWait",
+            RoundStep::Precommit => "RoundStepPrecommit",
+            RoundStep::PrecommitWait => "RoundStepPrecommitWait",
+            RoundStep::Commit => "RoundStepCommit",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Serialize for RoundStep {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(u8::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for RoundStep {
+    /// Deserializes either the numeric value of the step, or its name.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serializers::str_or_int::deserialize(deserializer)
     }
 }
 
@@ -128,11 +269,162 @@ pub struct RoundVotes {
     // A Tendermint node currently serializes this particular field as an
     // integer and not a string (unlike that which is expected from the `Round`
     // type).
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
     pub round: u32,
     pub prevotes: Vec<RoundVote>,
-    pub prevotes_bit_array: String,
+    pub prevotes_bit_array: VoteBitArray,
     pub precommits: Vec<RoundVote>,
-    pub precommits_bit_array: String,
+    pub precommits_bit_array: VoteBitArray,
+}
+
+/// The validators which voted in a vote set, along with the voting power of
+/// these validators, as formatted by Tendermint: `BA{4:xx_x} 30/40 = 0.75`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteBitArray {
+    /// Whether each validator of the set voted, by validator index
+    pub votes: BitArray,
+    /// The voting power of the validators which voted
+    pub voted_power: u64,
+    /// The total voting power of the validator set
+    pub total_power: u64,
+}
+
+impl FromStr for VoteBitArray {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            Error::client_internal(format!(
+                "invalid format for consensus state vote bit array: {s}"
+            ))
+        };
+
+        let (votes, tally) = s.split_once(' ').ok_or_else(invalid)?;
+        let (tally, _fraction) = tally.split_once(" = ").ok_or_else(invalid)?;
+        let (voted_power, total_power) = tally.split_once('/').ok_or_else(invalid)?;
+
+        Ok(Self {
+            votes: votes.parse()?,
+            voted_power: voted_power.parse().map_err(|_| invalid())?,
+            total_power: total_power.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl fmt::Display for VoteBitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}/{} = {:.2}",
+            self.votes,
+            self.voted_power,
+            self.total_power,
+            self.voted_power as f64 / self.total_power as f64
+        )
+    }
+}
+
+impl Serialize for VoteBitArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for VoteBitArray {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A bit array as formatted by Tendermint: `BA{4:xx_x}`, where `x` denotes a
+/// set bit and `_` an unset one, or `nil-BitArray` if empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitArray(Vec<bool>);
+
+impl BitArray {
+    /// The number of bits of the array.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the array has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of the bit at the given index, if in range.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.0.get(index).copied()
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.0.iter().filter(|bit| **bit).count()
+    }
+
+    /// Iterate over the bits of the array.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl From<Vec<bool>> for BitArray {
+    fn from(bits: Vec<bool>) -> Self {
+        Self(bits)
+    }
+}
+
+impl FromStr for BitArray {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == NIL_BIT_ARRAY_STR {
+            return Ok(Self::default());
+        }
+
+        let invalid =
+            || Error::client_internal(format!("invalid format for consensus state bit array: {s}"));
+
+        let (len, bits) = s
+            .strip_prefix("BA{")
+            .and_then(|s| s.strip_suffix('}'))
+            .and_then(|s| s.split_once(':'))
+            .ok_or_else(invalid)?;
+        let len = usize::from_str(len).map_err(|_| invalid())?;
+        let bits = bits
+            .chars()
+            .map(|c| match c {
+                'x' => Ok(true),
+                '_' => Ok(false),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if bits.len() != len {
+            return Err(invalid());
+        }
+
+        Ok(Self(bits))
+    }
+}
+
+impl fmt::Display for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str(NIL_BIT_ARRAY_STR);
+        }
+        write!(f, "BA{{{}:", self.len())?;
+        for bit in self.iter() {
+            f.write_str(if bit { "x" } else { "_" })?;
+        }
+        f.write_str("}")
+    }
 }
 
 /// Details of a single vote from a particular consensus round.
@@ -336,12 +628,14 @@ impl AsRef<[u8]> for Fingerprint {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorInfo {
     pub address: account::Id,
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
     pub index: i32,
 }
 
 #[cfg(test)]
 mod test {
     use lazy_static::lazy_static;
+    use proptest::prelude::*;
 
     use super::*;
 
@@ -397,4 +691,86 @@ mod test {
             assert_eq!(expected.clone(), actual);
         }
     }
+
+    #[test]
+    fn parse_height_round_step() {
+        let hrs = HeightRoundStep::from_str("14/0/1").unwrap();
+        assert_eq!(hrs.height, Height::from(14_u32));
+        assert_eq!(hrs.round, Round::from(0_u8));
+        assert_eq!(hrs.step, RoundStep::NewHeight);
+        assert_eq!(hrs.to_string(), "14/0/1");
+
+        for invalid in [
+            "14/0", "14/0/1/2", "14/0/0", "14/0/9", "x/0/1", "14/-1/1", "",
+        ] {
+            assert!(HeightRoundStep::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn parse_round_step() {
+        for value in 1..=8_u8 {
+            let step = RoundStep::try_from(value).unwrap();
+            assert_eq!(u8::from(step), value);
+            assert_eq!(RoundStep::from_str(&value.to_string()).unwrap(), step);
+            assert_eq!(RoundStep::from_str(&step.to_string()).unwrap(), step);
+        }
+    }
+
+    #[test]
+    fn parse_vote_bit_array() {
+        let votes = VoteBitArray::from_str("BA{4:xx_x} 30/40 = 0.75").unwrap();
+        assert_eq!(votes.votes.len(), 4);
+        assert_eq!(votes.votes.count_ones(), 3);
+        assert_eq!(votes.votes.get(2), Some(false));
+        assert_eq!(votes.votes.get(4), None);
+        assert_eq!(votes.voted_power, 30);
+        assert_eq!(votes.total_power, 40);
+        assert_eq!(votes.to_string(), "BA{4:xx_x} 30/40 = 0.75");
+
+        let nil = VoteBitArray::from_str("nil-BitArray 0/0 = NaN").unwrap();
+        assert!(nil.votes.is_empty());
+        assert_eq!(nil.to_string(), "nil-BitArray 0/0 = NaN");
+
+        for invalid in [
+            "BA{4:xx_x}",
+            "BA{4:xx_} 30/40 = 0.75",
+            "BA{4:xxyx} 30/40 = 0.75",
+            "BA{4:xx_x 30/40 = 0.75",
+            "BA{18446744073709551616:x} 1/1 = 1.00",
+            "BA{4:xx_x} 30 = 0.75",
+            "BA{4:xx_x} -1/40 = 0.75",
+        ] {
+            assert!(VoteBitArray::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parsers_never_panic(s in "\\PC*") {
+            let _ = HeightRoundStep::from_str(&s);
+            let _ = BitArray::from_str(&s);
+            let _ = VoteBitArray::from_str(&s);
+            let _ = VoteSummary::from_str(&s);
+        }
+
+        #[test]
+        fn parsers_never_panic_on_near_valid_input(
+            s in "(BA\\{[0-9]{0,3}:[x_]{0,8}\\}|nil-BitArray) [0-9]{0,3}/[0-9]{0,3} = [0-9.]{0,4}"
+        ) {
+            let _ = VoteBitArray::from_str(&s);
+        }
+
+        #[test]
+        fn bit_array_roundtrip(bits in prop::collection::vec(any::<bool>(), 0..100)) {
+            let bits = BitArray::from(bits);
+            prop_assert_eq!(BitArray::from_str(&bits.to_string()).unwrap(), bits);
+        }
+
+        #[test]
+        fn height_round_step_roundtrip(height in 1..i64::MAX as u64, round in 0..i32::MAX as u32, step in 1..=8_u8) {
+            let hrs = format!("{height}/{round}/{step}");
+            prop_assert_eq!(HeightRoundStep::from_str(&hrs).unwrap().to_string(), hrs);
+        }
+    }
 }
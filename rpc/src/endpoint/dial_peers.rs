@@ -0,0 +1,202 @@
+//! `/dial_peers` endpoint JSON-RPC wrapper (UNSAFE)
+
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use tendermint::node;
+use tendermint_config::net;
+
+use crate::{alloc_prelude::*, dialect::Dialect, request::RequestMessage, Error, Method};
+
+/// Dial the given peers, optionally making them persistent, unconditional or
+/// private peers of the node.
+///
+/// Only served by nodes which enable the unsafe RPC endpoints
+/// (`rpc.unsafe = true`).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// The peers to dial.
+    pub peers: Vec<PeerAddress>,
+    /// Whether to keep reconnecting to the peers.
+    pub persistent: bool,
+    /// Whether to accept the peers regardless of the peer limits of the node.
+    pub unconditional: bool,
+    /// Whether to keep the addresses of the peers out of the address book
+    /// and from being gossiped.
+    pub private: bool,
+}
+
+impl Request {
+    /// Constructor.
+    pub fn new(
+        peers: Vec<PeerAddress>,
+        persistent: bool,
+        unconditional: bool,
+        private: bool,
+    ) -> Self {
+        Self {
+            peers,
+            persistent,
+            unconditional,
+            private,
+        }
+    }
+}
+
+impl RequestMessage for Request {
+    const METHOD: Method = Method::DialPeers;
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Dial peers response.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Response {
+    /// A message about the peers being dialed.
+    pub log: String,
+}
+
+impl crate::Response for Response {}
+
+/// The address of a peer, made of its node ID and of the host and port at
+/// which it can be reached, e.g. `f00baa...@127.0.0.1:26656`.
+///
+/// Parsed with or without the `tcp://` scheme.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PeerAddress {
+    /// The node ID of the peer.
+    pub id: node::Id,
+    /// The hostname or IP address of the peer.
+    pub host: String,
+    /// The P2P port of the peer.
+    pub port: u16,
+}
+
+impl PeerAddress {
+    /// Constructor.
+    pub fn new(id: node::Id, host: impl Into<String>, port: u16) -> Self {
+        Self {
+            id,
+            host: host.into(),
+            port,
+        }
+    }
+}
+
+impl Display for PeerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}:{}", self.id, self.host, self.port)
+    }
+}
+
+impl TryFrom<net::Address> for PeerAddress {
+    type Error = Error;
+
+    fn try_from(value: net::Address) -> Result<Self, Error> {
+        match value {
+            net::Address::Tcp {
+                peer_id: Some(id),
+                host,
+                port,
+            } => Ok(Self { id, host, port }),
+            net::Address::Tcp { peer_id: None, .. } => Err(Error::parse(format!(
+                "peer address without a node ID: {value}"
+            ))),
+            net::Address::Unix { .. } => Err(Error::invalid_network_address()),
+        }
+    }
+}
+
+impl FromStr for PeerAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let address = s
+            .parse::<net::Address>()
+            .map_err(|e| Error::parse(format!("invalid peer address {s:?}: {e}")))?;
+        address.try_into()
+    }
+}
+
+impl Serialize for PeerAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PeerAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_str(&String::deserialize(deserializer)?)
+            .map_err(|e| D::Error::custom(format!("{e}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response as _;
+
+    const ID: &str = "a6d5d5b5d2e5e6c5b1b8c2e0f0e2d7d9f3b4a6c1";
+
+    #[test]
+    fn parses_peer_addresses() {
+        let address = PeerAddress::from_str(&format!("{ID}@127.0.0.1:26656")).unwrap();
+        assert_eq!(
+            address,
+            PeerAddress::new(ID.parse().unwrap(), "127.0.0.1", 26656)
+        );
+        assert_eq!(address.to_string(), format!("{ID}@127.0.0.1:26656"));
+        assert_eq!(
+            PeerAddress::from_str(&format!("tcp://{ID}@127.0.0.1:26656")).unwrap(),
+            address
+        );
+
+        assert!(PeerAddress::from_str("127.0.0.1:26656").is_err());
+        assert!(PeerAddress::from_str(&format!("{ID}@127.0.0.1")).is_err());
+        assert!(PeerAddress::from_str("unix:///tmp/node.sock").is_err());
+    }
+
+    #[test]
+    fn serializes_requests() {
+        let request = Request::new(
+            vec![PeerAddress::new(ID.parse().unwrap(), "10.0.0.1", 26656)],
+            true,
+            false,
+            true,
+        );
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "peers": [format!("{ID}@10.0.0.1:26656")],
+                "persistent": true,
+                "unconditional": false,
+                "private": true,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_responses() {
+        let response = Response::from_string(
+            r#"{
+              "jsonrpc": "2.0",
+              "id": -1,
+              "result": { "log": "Dialing peers in progress. See /net_info for details" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.log,
+            "Dialing peers in progress. See /net_info for details"
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! `/dial_seeds` endpoint JSON-RPC wrapper (UNSAFE)
+
+use serde::{Deserialize, Serialize};
+
+pub use super::dial_peers::PeerAddress;
+use crate::{alloc_prelude::*, dialect::Dialect, request::RequestMessage, Method};
+
+/// Dial the given seeds, to crawl their address books for peers.
+///
+/// Only served by nodes which enable the unsafe RPC endpoints
+/// (`rpc.unsafe = true`).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// The seeds to dial.
+    pub seeds: Vec<PeerAddress>,
+}
+
+impl Request {
+    /// Constructor.
+    pub fn new(seeds: Vec<PeerAddress>) -> Self {
+        Self { seeds }
+    }
+}
+
+impl RequestMessage for Request {
+    const METHOD: Method = Method::DialSeeds;
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Dial seeds response.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Response {
+    /// A message about the seeds being dialed.
+    pub log: String,
+}
+
+impl crate::Response for Response {}
@@ -0,0 +1,493 @@
+//! `/dump_consensus_state` endpoint JSON-RPC wrapper
+
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tendermint::{
+    block::{self, Block, Height, Round},
+    validator, Time,
+};
+
+pub use super::consensus_state::{BitArray, RoundStep, RoundVote, RoundVotes, VoteBitArray};
+use crate::{alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers, Method};
+
+/// Get the full consensus state of the node, and the consensus state of its
+/// peers as known to the node.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl Request {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RequestMessage for Request {
+    const METHOD: Method = Method::DumpConsensusState;
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// The full consensus state of the node (UNSTABLE).
+///
+/// The format of this response is not part of the stable RPC API: it is
+/// parsed leniently, accepting the integers encoded either as numbers or as
+/// strings, and defaulting the fields missing from some versions.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    pub round_state: RoundState,
+    #[serde(default)]
+    pub peers: Vec<PeerStateInfo>,
+}
+
+impl crate::Response for Response {}
+
+/// The consensus state of the node.
+///
+/// Based on <https://github.com/cometbft/cometbft/blob/v0.37.0/consensus/types/round_state.go#L65>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoundState {
+    #[serde(with = "height")]
+    pub height: Height,
+    #[serde(with = "round")]
+    pub round: Round,
+    pub step: RoundStep,
+    #[serde(with = "tendermint::serializers::time")]
+    pub start_time: Time,
+    /// The time at which the block of the previous height was committed.
+    #[serde(with = "tendermint::serializers::time")]
+    pub commit_time: Time,
+    #[serde(with = "validator_set", default)]
+    pub validators: Option<validator::Set>,
+    #[serde(default)]
+    pub proposal: Option<Proposal>,
+    #[serde(default)]
+    pub proposal_block: Option<Block>,
+    #[serde(default)]
+    pub proposal_block_parts: Option<PartSetSummary>,
+    /// The round of the locked block, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub locked_round: i32,
+    #[serde(default)]
+    pub locked_block: Option<Block>,
+    #[serde(default)]
+    pub locked_block_parts: Option<PartSetSummary>,
+    /// The round of the valid block, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub valid_round: i32,
+    #[serde(default)]
+    pub valid_block: Option<Block>,
+    #[serde(default)]
+    pub valid_block_parts: Option<PartSetSummary>,
+    /// The votes of every round of the current height.
+    #[serde(default)]
+    pub votes: Vec<RoundVotes>,
+    /// The round whose precommits committed the block, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub commit_round: i32,
+    #[serde(default)]
+    pub last_commit: Option<VoteSetSummary>,
+    #[serde(with = "validator_set", default)]
+    pub last_validators: Option<validator::Set>,
+    #[serde(default)]
+    pub triggered_timeout_precommit: bool,
+}
+
+/// A proposal, as found in the consensus state.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Proposal {
+    #[serde(rename = "type")]
+    pub msg_type: i32,
+    #[serde(with = "height")]
+    pub height: Height,
+    #[serde(with = "round")]
+    pub round: Round,
+    /// The proof-of-lock round of the proposal, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub pol_round: i32,
+    pub block_id: block::Id,
+    #[serde(with = "tendermint::serializers::time")]
+    pub timestamp: Time,
+    #[serde(with = "serializers::bytes::base64string", default)]
+    pub signature: Vec<u8>,
+}
+
+/// The parts of a block received by the node, formatted by Tendermint as
+/// `{"count/total": "1/2", "parts_bit_array": "x_"}`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PartSetSummary {
+    #[serde(rename = "count/total", with = "count_total")]
+    pub count_total: (u32, u32),
+    #[serde(with = "bits")]
+    pub parts_bit_array: BitArray,
+}
+
+impl PartSetSummary {
+    /// The number of parts received.
+    pub fn count(&self) -> u32 {
+        self.count_total.0
+    }
+
+    /// The number of parts of the block.
+    pub fn total(&self) -> u32 {
+        self.count_total.1
+    }
+}
+
+/// The votes of a vote set, such as the last commit.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VoteSetSummary {
+    pub votes: Vec<RoundVote>,
+    pub votes_bit_array: VoteBitArray,
+    /// The blocks for which peers claimed to have seen +2/3 of the votes, by
+    /// peer ID.
+    #[serde(default)]
+    pub peer_maj23s: BTreeMap<String, block::Id>,
+}
+
+/// The consensus state of a peer of the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStateInfo {
+    pub node_address: String,
+    pub peer_state: PeerState,
+}
+
+/// The consensus state of a peer, as known to the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerState {
+    pub round_state: PeerRoundState,
+    #[serde(default)]
+    pub stats: PeerStats,
+}
+
+/// The round state of a peer, as known to the node.
+///
+/// The bit arrays tell which validators the peer is known to have the votes
+/// of, by validator index, or which parts of a block it is known to have.
+///
+/// Based on <https://github.com/cometbft/cometbft/blob/v0.37.0/consensus/types/peer_round_state.go#L14>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerRoundState {
+    #[serde(with = "height")]
+    pub height: Height,
+    #[serde(with = "round")]
+    pub round: Round,
+    pub step: RoundStep,
+    #[serde(with = "tendermint::serializers::time")]
+    pub start_time: Time,
+    /// Whether the peer has the proposal of the round.
+    #[serde(default)]
+    pub proposal: bool,
+    pub proposal_block_part_set_header: block::parts::Header,
+    #[serde(with = "bits", default)]
+    pub proposal_block_parts: BitArray,
+    /// The proof-of-lock round of the proposal, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub proposal_pol_round: i32,
+    #[serde(with = "bits", default)]
+    pub proposal_pol: BitArray,
+    #[serde(with = "bits", default)]
+    pub prevotes: BitArray,
+    #[serde(with = "bits", default)]
+    pub precommits: BitArray,
+    /// The round of the last commit of the peer, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub last_commit_round: i32,
+    #[serde(with = "bits", default)]
+    pub last_commit: BitArray,
+    /// The round of the commit the peer is catching up to, -1 if none.
+    #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+    pub catchup_commit_round: i32,
+    #[serde(with = "bits", default)]
+    pub catchup_commit: BitArray,
+}
+
+/// The number of useful messages received from a peer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PeerStats {
+    #[serde(with = "str_or_int_as_str")]
+    pub votes: u64,
+    #[serde(with = "str_or_int_as_str")]
+    pub block_parts: u64,
+}
+
+// Integers which Tendermint encodes as strings.
+mod str_or_int_as_str {
+    pub use crate::serializers::{from_str::serialize, str_or_int::deserialize};
+}
+
+mod height {
+    pub use super::str_or_int_as_str::*;
+}
+
+// Rounds are encoded as numbers, unlike elsewhere.
+mod round {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Round, D::Error> {
+        serializers::str_or_int::deserialize(deserializer)
+    }
+
+    pub fn serialize<S: Serializer>(value: &Round, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(value.value())
+    }
+}
+
+// Bit arrays are encoded as their bits only, e.g. `"xx_x"`, or `null` if
+// empty.
+mod bits {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BitArray, D::Error> {
+        let bits = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        BitArray::from_str(&format!("BA{{{}:{}}}", bits.chars().count(), bits))
+            .map_err(serde::de::Error::custom)
+    }
+
+    pub fn serialize<S: Serializer>(value: &BitArray, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_empty() {
+            return serializer.serialize_none();
+        }
+        let bits: String = value
+            .iter()
+            .map(|bit| if bit { 'x' } else { '_' })
+            .collect();
+        serializer.serialize_str(&bits)
+    }
+}
+
+mod count_total {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(u32, u32), D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let invalid = || serde::de::Error::custom(format!("invalid part set count/total: {s}"));
+        let (count, total) = s.split_once('/').ok_or_else(invalid)?;
+        Ok((
+            count.parse().map_err(|_| invalid())?,
+            total.parse().map_err(|_| invalid())?,
+        ))
+    }
+
+    pub fn serialize<S: Serializer>(
+        (count, total): &(u32, u32),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{count}/{total}"))
+    }
+}
+
+// Validator sets are encoded without their total voting power, which is
+// computed again.
+mod validator_set {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct RawSet {
+        #[serde(default)]
+        validators: Vec<validator::Info>,
+        proposer: Option<validator::Info>,
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<validator::Set>, D::Error> {
+        match Option::<RawSet>::deserialize(deserializer)? {
+            Some(set) => validator::Set::new(set.validators, set.proposer)
+                .map(Some)
+                .map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<validator::Set>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response as _;
+
+    const RESPONSE: &str = r#"{
+      "jsonrpc": "2.0",
+      "id": -1,
+      "result": {
+        "round_state": {
+          "height": "184",
+          "round": 0,
+          "step": 3,
+          "start_time": "2023-02-27T07:14:38.877277579Z",
+          "commit_time": "2023-02-27T07:14:37.877277579Z",
+          "validators": {
+            "validators": [
+              {
+                "address": "DD8A65495B6240145764A74E78CF203D51510371",
+                "pub_key": {
+                  "type": "tendermint/PubKeyEd25519",
+                  "value": "7Bps1HlaiOn7YrPF6hvsXmOqIY5bNbWS9HXSRzOLZdI="
+                },
+                "voting_power": "10",
+                "proposer_priority": "0"
+              }
+            ],
+            "proposer": {
+              "address": "DD8A65495B6240145764A74E78CF203D51510371",
+              "pub_key": {
+                "type": "tendermint/PubKeyEd25519",
+                "value": "7Bps1HlaiOn7YrPF6hvsXmOqIY5bNbWS9HXSRzOLZdI="
+              },
+              "voting_power": "10",
+              "proposer_priority": "0"
+            }
+          },
+          "proposal": {
+            "type": 32,
+            "height": "184",
+            "round": 0,
+            "pol_round": -1,
+            "block_id": {
+              "hash": "2A9DD4F66BCB1A86C3C4A6F1D9A0E8BEF0A1B6DC8B5B0F4C2EE5CBE0E9F0E4A7",
+              "parts": {
+                "total": 1,
+                "hash": "8BB5F4A1D5B2F9A3C6E8D0F2A4B6C8E0F2A4B6C8D0E2F4A6B8C0D2E4F6A8B0C2"
+              }
+            },
+            "timestamp": "2023-02-27T07:14:38.977277579Z",
+            "signature": "cB4ALHxvS5dxvVvZV9MSUUEtWL5k8ovg4Ot0rt4CfB1aJSJY5pUZ6F9H/zCBvM6C1w8sdnhzDHw7n4S04d8FDQ=="
+          },
+          "proposal_block": null,
+          "proposal_block_parts": {
+            "count/total": "0/1",
+            "parts_bit_array": "_"
+          },
+          "locked_round": -1,
+          "locked_block": null,
+          "locked_block_parts": null,
+          "valid_round": -1,
+          "valid_block": null,
+          "valid_block_parts": null,
+          "votes": [
+            {
+              "round": 0,
+              "prevotes": ["nil-Vote"],
+              "prevotes_bit_array": "BA{1:_} 0/10 = 0.00",
+              "precommits": ["nil-Vote"],
+              "precommits_bit_array": "BA{1:_} 0/10 = 0.00"
+            }
+          ],
+          "commit_round": -1,
+          "last_commit": {
+            "votes": [
+              "Vote{0:DD8A65495B62 183/00/SIGNED_MSG_TYPE_PRECOMMIT(Precommit) 634ADAF1F402 7BB974E1BA40 @ 2023-02-27T07:14:37.777277579Z}"
+            ],
+            "votes_bit_array": "BA{1:x} 10/10 = 1.00",
+            "peer_maj23s": {}
+          },
+          "last_validators": null,
+          "triggered_timeout_precommit": false
+        },
+        "peers": [
+          {
+            "node_address": "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678@127.0.0.1:26656",
+            "peer_state": {
+              "round_state": {
+                "height": "184",
+                "round": "0",
+                "step": "RoundStepPropose",
+                "start_time": "2023-02-27T07:14:38.877277579Z",
+                "proposal": false,
+                "proposal_block_part_set_header": {
+                  "total": 0,
+                  "hash": ""
+                },
+                "proposal_block_parts": null,
+                "proposal_pol_round": -1,
+                "proposal_pol": null,
+                "prevotes": "_",
+                "precommits": "_",
+                "last_commit_round": 0,
+                "last_commit": "x",
+                "catchup_commit_round": -1,
+                "catchup_commit": null
+              },
+              "stats": {
+                "votes": "12",
+                "block_parts": 3
+              }
+            }
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn deserialize_dump_consensus_state() {
+        let response = Response::from_string(RESPONSE).unwrap();
+
+        let state = &response.round_state;
+        assert_eq!(state.height, Height::from(184_u32));
+        assert_eq!(state.step, RoundStep::Propose);
+        assert_eq!(state.validators.as_ref().unwrap().validators().len(), 1);
+        assert!(state.last_validators.is_none());
+        let proposal = state.proposal.as_ref().unwrap();
+        assert_eq!(proposal.pol_round, -1);
+        assert_eq!(proposal.signature.len(), 64);
+        let parts = state.proposal_block_parts.as_ref().unwrap();
+        assert_eq!((parts.count(), parts.total()), (0, 1));
+        assert_eq!(state.locked_round, -1);
+        assert_eq!(state.votes.len(), 1);
+        let last_commit = state.last_commit.as_ref().unwrap();
+        assert_eq!(last_commit.votes_bit_array.voted_power, 10);
+        assert!(matches!(last_commit.votes[0], RoundVote::Vote(_)));
+
+        let peer = &response.peers[0].peer_state;
+        assert_eq!(peer.round_state.step, RoundStep::Propose);
+        assert!(peer.round_state.proposal_block_parts.is_empty());
+        assert_eq!(peer.round_state.last_commit.count_ones(), 1);
+        assert_eq!(peer.round_state.prevotes.get(0), Some(false));
+        assert_eq!(
+            peer.stats,
+            PeerStats {
+                votes: 12,
+                block_parts: 3
+            }
+        );
+    }
+
+    #[test]
+    fn serialize_dump_consensus_state() {
+        let response = Response::from_string(RESPONSE).unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        let state = &json["round_state"];
+        assert_eq!(state["height"], "184");
+        assert_eq!(state["round"], 0);
+        assert_eq!(state["step"], 3);
+        assert_eq!(state["proposal_block_parts"]["count/total"], "0/1");
+        assert_eq!(state["proposal_block_parts"]["parts_bit_array"], "_");
+        let peer = &json["peers"][0]["peer_state"];
+        assert_eq!(
+            peer["round_state"]["catchup_commit"],
+            serde_json::Value::Null
+        );
+        assert_eq!(peer["stats"]["block_parts"], "3");
+
+        let again: Response = serde_json::from_value(json).unwrap();
+        assert_eq!(again.round_state.height, response.round_state.height);
+        assert_eq!(
+            again.peers[0].peer_state.round_state.last_commit,
+            response.peers[0].peer_state.round_state.last_commit
+        );
+    }
+}
@@ -3,26 +3,27 @@
 use serde::{Deserialize, Serialize};
 use tendermint::{evidence::Evidence, Hash};
 
-use crate::{dialect::Dialect, request::RequestMessage, Method};
+use crate::{dialect::Dialect, request::RequestMessage, serializers, Method};
 
 /// `/broadcast_evidence`: broadcast an evidence.
+///
+/// The evidence is encoded as expected by Tendermint 0.34 and 0.37 alike,
+/// i.e. in the Amino JSON format, tagged with its type.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Request {
     /// Evidence to broadcast
-    pub ev: Evidence,
+    pub evidence: Evidence,
 }
 
 impl Request {
     /// Create a new evidence broadcast RPC request
-    pub fn new(ev: Evidence) -> Request {
-        Request { ev }
+    pub fn new(evidence: Evidence) -> Request {
+        Request { evidence }
     }
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> Method {
-        Method::BroadcastEvidence
-    }
+    const METHOD: Method = Method::BroadcastEvidence;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -33,10 +34,11 @@ impl<S: Dialect> crate::SimpleRequest<S> for Request {
     type Output = Response;
 }
 
-/// Response from either an evidence broadcast request.
+/// Response from an evidence broadcast request.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Response {
     /// Evidence hash
+    #[serde(with = "serializers::tm_hash_base64")]
     pub hash: Hash,
 }
 
@@ -21,9 +21,7 @@ impl<AppState> RequestMessage for Request<AppState>
 where
     AppState: Serialize + DeserializeOwned,
 {
-    fn method(&self) -> crate::Method {
-        crate::Method::Genesis
-    }
+    const METHOD: crate::Method = crate::Method::Genesis;
 }
 
 impl<AppState, S> crate::Request<S> for Request<AppState>
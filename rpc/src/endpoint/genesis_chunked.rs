@@ -0,0 +1,52 @@
+//! `/genesis_chunked` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+use tendermint::serializers;
+
+use crate::{alloc_prelude::*, dialect::Dialect, request::RequestMessage};
+
+/// Get a chunk of the genesis file, for genesis files too large to be
+/// returned at once by `/genesis`
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// The index of the chunk, starting from 0.
+    #[serde(with = "serializers::from_str")]
+    pub chunk: u64,
+}
+
+impl Request {
+    /// Create a new request for the chunk at the given index
+    pub fn new(chunk: u64) -> Self {
+        Self { chunk }
+    }
+}
+
+impl RequestMessage for Request {
+    const METHOD: crate::Method = crate::Method::GenesisChunked;
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Genesis chunk responses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// The index of the chunk.
+    #[serde(with = "serializers::from_str")]
+    pub chunk: u64,
+
+    /// The total number of chunks.
+    #[serde(with = "serializers::from_str")]
+    pub total: u64,
+
+    /// The content of the chunk.
+    #[serde(with = "serializers::bytes::base64string")]
+    pub data: Vec<u8>,
+}
+
+impl crate::Response for Response {}
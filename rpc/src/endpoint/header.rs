@@ -25,9 +25,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Header
-    }
+    const METHOD: crate::Method = crate::Method::Header;
 }
 
 impl crate::Request<v0_37::Dialect> for Request {
@@ -31,9 +31,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::HeaderByHash
-    }
+    const METHOD: crate::Method = crate::Method::HeaderByHash;
 }
 
 impl crate::Request<v0_37::Dialect> for Request {
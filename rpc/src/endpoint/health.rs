@@ -9,9 +9,7 @@ use crate::{dialect::Dialect, request::RequestMessage};
 pub struct Request;
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Health
-    }
+    const METHOD: crate::Method = crate::Method::Health;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
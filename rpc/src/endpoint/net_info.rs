@@ -9,7 +9,7 @@ use std::net::IpAddr;
 use serde::{Deserialize, Serialize};
 use tendermint::{channel::Channel, node, serializers, Time};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{dialect::Dialect, request::RequestMessage};
 
 /// Request network information from a node
@@ -17,9 +17,7 @@ use crate::{dialect::Dialect, request::RequestMessage};
 pub struct Request;
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::NetInfo
-    }
+    const METHOD: crate::Method = crate::Method::NetInfo;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -10,9 +10,7 @@ use crate::{dialect::Dialect, request::RequestMessage};
 pub struct Request;
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Status
-    }
+    const METHOD: crate::Method = crate::Method::Status;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{dialect::Dialect, request::RequestMessage};
 
 /// Subscription request for events.
@@ -25,9 +25,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Subscribe
-    }
+    const METHOD: crate::Method = crate::Method::Subscribe;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use tendermint::{abci, block, tx, Hash};
 
 use crate::dialect::{DeliverTx, Dialect};
-use crate::{prelude::*, request::RequestMessage, serializers, Method};
+use crate::{alloc_prelude::*, request::RequestMessage, serializers, Method};
 
 /// Request for finding a transaction by its hash.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -29,9 +29,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> Method {
-        Method::Tx
-    }
+    const METHOD: Method = Method::Tx;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
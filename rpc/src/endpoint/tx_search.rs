@@ -5,7 +5,9 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tendermint::abci;
 
 pub use super::tx;
-use crate::{dialect::Dialect, prelude::*, request::RequestMessage, serializers, Method, Order};
+use crate::{
+    alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers, Method, Order,
+};
 
 /// Request for searching for transactions with their results.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -39,9 +41,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> Method {
-        Method::TxSearch
-    }
+    const METHOD: Method = Method::TxSearch;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
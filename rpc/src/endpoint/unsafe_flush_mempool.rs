@@ -0,0 +1,30 @@
+//! `/unsafe_flush_mempool` endpoint JSON-RPC wrapper (UNSAFE)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{dialect::Dialect, request::RequestMessage, Method};
+
+/// Remove all the transactions from the mempool of the node.
+///
+/// Only served by nodes which enable the unsafe RPC endpoints
+/// (`rpc.unsafe = true`).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl RequestMessage for Request {
+    const METHOD: Method = Method::UnsafeFlushMempool;
+}
+
+impl<S: Dialect> crate::Request<S> for Request {
+    type Response = Response;
+}
+
+impl<S: Dialect> crate::SimpleRequest<S> for Request {
+    type Output = Response;
+}
+
+/// Flush mempool response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {}
+
+impl crate::Response for Response {}
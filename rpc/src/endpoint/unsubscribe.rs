@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{dialect::Dialect, request::RequestMessage};
 
 /// Request to unsubscribe from events relating to a given query.
@@ -19,9 +19,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Unsubscribe
-    }
+    const METHOD: crate::Method = crate::Method::Unsubscribe;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
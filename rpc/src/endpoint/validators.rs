@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use tendermint::{block, validator};
 
 use crate::{
-    dialect::Dialect, prelude::*, request::RequestMessage, serializers, PageNumber, PerPage,
+    alloc_prelude::*, dialect::Dialect, request::RequestMessage, serializers, PageNumber, PerPage,
 };
 
 /// The default number of validators to return per page.
@@ -46,9 +46,7 @@ impl Request {
 }
 
 impl RequestMessage for Request {
-    fn method(&self) -> crate::Method {
-        crate::Method::Validators
-    }
+    const METHOD: crate::Method = crate::Method::Validators;
 }
 
 impl<S: Dialect> crate::Request<S> for Request {
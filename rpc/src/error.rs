@@ -4,7 +4,7 @@ use core::time::Duration;
 
 use flex_error::{define_error, DefaultTracer, DisplayError, DisplayOnly, ErrorMessageTracer};
 
-use crate::{prelude::*, response_error::ResponseError, rpc_url::Url};
+use crate::{alloc_prelude::*, response_error::ResponseError, rpc_url::Url};
 
 #[cfg(feature = "http")]
 type HttpError = flex_error::DisplayOnly<http::Error>;
@@ -81,6 +81,24 @@ define_error! {
                     e.timeout.as_secs())
             },
 
+        StaleSubscriptions
+            {
+                timeout: Duration
+            }
+            | e | {
+                format_args!("no event received on the active subscriptions for {} seconds",
+                    e.timeout.as_secs())
+            },
+
+        SubscriptionGap
+            {
+                reason: String
+            }
+            | e | {
+                format_args!("connection lost, events may have been missed while reconnecting: {}",
+                    e.reason)
+            },
+
         MethodNotFound
             {
                 method: String
@@ -113,6 +131,14 @@ define_error! {
                 format_args!("client internal error: {}", e.reason)
             },
 
+        InvalidTlsConfig
+            {
+                reason: String
+            }
+            | e | {
+                format_args!("invalid TLS configuration: {}", e.reason)
+            },
+
         Timeout
             {
                 duration: Duration
@@ -124,6 +150,81 @@ define_error! {
                 )
             },
 
+        RequestTimeout
+            {
+                method: String,
+                timeout: Duration,
+            }
+            | e | {
+                format_args!("request {} timed out after {}ms",
+                    e.method, e.timeout.as_millis())
+            },
+
+        GenesisTooLarge
+            {
+                max_size: usize,
+            }
+            | e | {
+                format_args!("genesis file larger than the maximum size of {} bytes",
+                    e.max_size)
+            },
+
+        ResponseTooLarge
+            {
+                max_size: usize,
+            }
+            | e | {
+                format_args!("response body larger than the maximum size of {} bytes",
+                    e.max_size)
+            },
+
+        CircuitOpen
+            {
+                retry_after: Duration,
+            }
+            | e | {
+                format_args!("circuit breaker is open after repeated failures, retry in {}ms",
+                    e.retry_after.as_millis())
+            },
+
+        RateLimited
+            {
+                retry_after: Option<Duration>,
+            }
+            | e | {
+                format_args!("rate limited by the server{}",
+                    e.retry_after
+                        .map(|d| format!(", retry in {}ms", d.as_millis()))
+                        .unwrap_or_default())
+            },
+
+        Grpc
+            {
+                code: u32,
+                message: String,
+            }
+            | e | {
+                format_args!("gRPC status {}: {}", e.code, e.message)
+            },
+
+        GrpcUnsupported
+            {
+                method: String,
+            }
+            | e | {
+                format_args!("method {} is not served by the gRPC services of the node",
+                    e.method)
+            },
+
+        UnsafeRpcDisabled
+            {
+                method: String,
+            }
+            | e | {
+                format_args!("method {} is only served by nodes with the unsafe RPC endpoints enabled (rpc.unsafe = true)",
+                    e.method)
+            },
+
         ChannelSend
             | _ | { "failed to send message to internal channel" },
 
@@ -185,6 +286,22 @@ define_error! {
         MalformedJson
             | _ | { "server returned malformatted JSON (no 'result' or 'error')" },
 
+        MissingBatchResponse
+            {
+                id: String
+            }
+            | e | {
+                format_args!("server returned no response to batched request with ID {}", e.id)
+            },
+
+        ForeignBatchEntry
+            {
+                id: String
+            }
+            | e | {
+                format_args!("batched request with ID {} is not part of this batch", e.id)
+            },
+
         UnsupportedScheme
             {
                 scheme: String
@@ -3,9 +3,15 @@
 use alloc::collections::BTreeMap as HashMap;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tendermint::{abci, Block};
+use tendermint::{
+    abci,
+    block::{Height, Round},
+    Block, Vote,
+};
 
-use crate::{dialect, prelude::*, query::EventType, response::Wrapper, serializers, Response};
+use crate::{
+    alloc_prelude::*, dialect, query::EventType, response::Wrapper, serializers, Response,
+};
 
 /// An incoming event produced by a [`Subscription`].
 ///
@@ -45,6 +51,14 @@ impl Event {
         match self.data {
             EventData::NewBlock { .. } => Some(EventType::NewBlock),
             EventData::Tx { .. } => Some(EventType::Tx),
+            EventData::NewRound { .. } => Some(EventType::NewRound),
+            EventData::Vote { .. } => Some(EventType::Vote),
+            EventData::RoundState { .. } => self
+                .events
+                .as_ref()
+                .and_then(|events| events.get("tm.event"))
+                .filter(|types| types.iter().any(|t| t == "TimeoutPropose"))
+                .map(|_| EventType::TimeoutPropose),
             _ => None,
         }
     }
@@ -88,6 +102,23 @@ pub enum EventData {
     Tx {
         tx_result: TxInfo,
     },
+    /// A new round of consensus started.
+    NewRound {
+        height: Height,
+        round: Round,
+        step: String,
+    },
+    /// A validator voted.
+    Vote {
+        vote: Vote,
+    },
+    /// Consensus reached a step of a round, e.g. timed out waiting for the
+    /// proposal: which one is told by the `tm.event` attribute of the event.
+    RoundState {
+        height: Height,
+        round: Round,
+        step: String,
+    },
     GenericJsonEvent(serde_json::Value),
 }
 
@@ -106,6 +137,27 @@ pub enum DialectEventData<Ev> {
         #[serde(rename = "TxResult")]
         tx_result: DialectTxInfo<Ev>,
     },
+    #[serde(alias = "tendermint/event/NewRound")]
+    NewRound {
+        #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+        height: Height,
+        #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+        round: Round,
+        step: String,
+    },
+    #[serde(alias = "tendermint/event/Vote")]
+    Vote {
+        #[serde(rename = "Vote")]
+        vote: Vote,
+    },
+    #[serde(alias = "tendermint/event/RoundState")]
+    RoundState {
+        #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+        height: Height,
+        #[serde(deserialize_with = "serializers::str_or_int::deserialize")]
+        round: Round,
+        step: String,
+    },
     GenericJsonEvent(serde_json::Value),
 }
 
@@ -127,6 +179,25 @@ where
             DialectEventData::Tx { tx_result } => EventData::Tx {
                 tx_result: tx_result.into(),
             },
+            DialectEventData::NewRound {
+                height,
+                round,
+                step,
+            } => EventData::NewRound {
+                height,
+                round,
+                step,
+            },
+            DialectEventData::Vote { vote } => EventData::Vote { vote },
+            DialectEventData::RoundState {
+                height,
+                round,
+                step,
+            } => EventData::RoundState {
+                height,
+                round,
+                step,
+            },
             DialectEventData::GenericJsonEvent(v) => EventData::GenericJsonEvent(v),
         }
     }
@@ -150,28 +221,56 @@ where
             EventData::Tx { tx_result } => DialectEventData::Tx {
                 tx_result: tx_result.into(),
             },
+            EventData::NewRound {
+                height,
+                round,
+                step,
+            } => DialectEventData::NewRound {
+                height,
+                round,
+                step,
+            },
+            EventData::Vote { vote } => DialectEventData::Vote { vote },
+            EventData::RoundState {
+                height,
+                round,
+                step,
+            } => DialectEventData::RoundState {
+                height,
+                round,
+                step,
+            },
             EventData::GenericJsonEvent(v) => DialectEventData::GenericJsonEvent(v),
         }
     }
 }
 
 /// Transaction result info.
+///
+/// Decoded into the same domain types as the results of the `/tx` and
+/// `/block_results` endpoints, the height and index of the transaction being
+/// those at which its inclusion in a block can be proven.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TxInfo {
-    pub height: i64,
-    pub index: Option<i64>,
+    /// The height of the block which includes the transaction.
+    pub height: Height,
+    /// The index of the transaction in the block.
+    pub index: u32,
+    /// The transaction.
     pub tx: Vec<u8>,
-    pub result: TxResult,
+    /// The result of the execution of the transaction.
+    pub result: abci::response::DeliverTx,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DialectTxInfo<Ev> {
-    #[serde(with = "serializers::from_str")]
-    pub height: i64,
-    pub index: Option<i64>,
+    pub height: Height,
+    // Omitted if zero.
+    #[serde(default)]
+    pub index: u32,
     #[serde(with = "serializers::bytes::base64string")]
     pub tx: Vec<u8>,
-    pub result: DialectTxResult<Ev>,
+    pub result: dialect::DeliverTx<Ev>,
 }
 
 impl<Ev> From<DialectTxInfo<Ev>> for TxInfo
@@ -202,47 +301,64 @@ where
     }
 }
 
-/// Transaction result.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TxResult {
-    pub log: Option<String>,
-    pub gas_wanted: Option<String>,
-    pub gas_used: Option<String>,
-    pub events: Vec<abci::Event>,
-}
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Code;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct DialectTxResult<Ev> {
-    pub log: Option<String>,
-    pub gas_wanted: Option<String>,
-    pub gas_used: Option<String>,
-    pub events: Vec<Ev>,
-}
+    use super::*;
+    use crate::dialect::v0_37;
 
-impl<Ev> From<DialectTxResult<Ev>> for TxResult
-where
-    Ev: Into<abci::Event>,
-{
-    fn from(msg: DialectTxResult<Ev>) -> Self {
-        TxResult {
-            log: msg.log,
-            gas_wanted: msg.gas_wanted,
-            gas_used: msg.gas_used,
-            events: msg.events.into_iter().map(Into::into).collect(),
-        }
-    }
-}
+    #[test]
+    fn decodes_tx_results() {
+        let event = DialectEvent::<v0_37::Event>::from_string(
+            r#"{
+              "jsonrpc": "2.0",
+              "id": 0,
+              "result": {
+                "query": "tm.event = 'Tx'",
+                "data": {
+                  "type": "tendermint/event/Tx",
+                  "value": {
+                    "TxResult": {
+                      "height": "12",
+                      "index": 3,
+                      "tx": "YT0x",
+                      "result": {
+                        "code": 5,
+                        "log": "insufficient funds",
+                        "gas_wanted": "100",
+                        "gas_used": "42",
+                        "events": [],
+                        "codespace": "bank"
+                      }
+                    }
+                  }
+                },
+                "events": null
+              }
+            }"#,
+        )
+        .unwrap();
+        let EventData::Tx { tx_result } = Event::from(event).data else {
+            panic!("not a tx");
+        };
+        assert_eq!(tx_result.height.value(), 12);
+        assert_eq!(tx_result.index, 3);
+        assert_eq!(tx_result.tx, b"a=1");
+        assert_eq!(tx_result.result.code, Code::from(5));
+        assert_eq!(tx_result.result.log, "insufficient funds");
+        assert_eq!(tx_result.result.gas_wanted, 100);
+        assert_eq!(tx_result.result.gas_used, 42);
+        assert_eq!(tx_result.result.codespace, "bank");
 
-impl<Ev> From<TxResult> for DialectTxResult<Ev>
-where
-    abci::Event: Into<Ev>,
-{
-    fn from(msg: TxResult) -> Self {
-        DialectTxResult {
-            log: msg.log,
-            gas_wanted: msg.gas_wanted,
-            gas_used: msg.gas_used,
-            events: msg.events.into_iter().map(Into::into).collect(),
+        let event: DialectEvent<v0_37::Event> = Event {
+            query: "tm.event = 'Tx'".to_string(),
+            data: EventData::Tx { tx_result },
+            events: None,
         }
+        .into();
+        let json = serde_json::to_value(event).unwrap();
+        assert_eq!(json["data"]["value"]["TxResult"]["height"], "12");
+        assert_eq!(json["data"]["value"]["TxResult"]["index"], 3);
     }
 }
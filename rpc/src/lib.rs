@@ -14,6 +14,18 @@
 //! * `websocket-client` - Provides [`WebSocketClient`], which provides full client functionality,
 //!   including general RPC functionality as well as [`event::Event`] subscription functionality.
 //!   Can be used over secure (`wss://`) and unsecure (`ws://`) connections.
+//! * `grpc-client` - Provides `GrpcClient`, which interacts with the node services of
+//!   CometBFT 0.38+ (blocks, block results and versions) via **gRPC over HTTP/2**, implementing
+//!   the subset of the [`Client`] trait served by these services.
+//! * `http-compression` - Makes the `HttpClient` request gzip or deflate compressed responses,
+//!   and transparently decompress them, which typically cuts the transfer of large payloads
+//!   such as blocks by 80% or more.
+//! * `prometheus` - Provides `PrometheusMetrics`, an [`Instrumentation`] of the calls of the HTTP
+//!   and WebSocket clients rendering per-method request counts, latencies and payload sizes in
+//!   the Prometheus text format.
+//! * `unsafe-rpc` - Provides the endpoints which nodes only serve with `rpc.unsafe = true`, used
+//!   to manage peers (`/dial_seeds` and `/dial_peers`) and to flush the mempool
+//!   (`/unsafe_flush_mempool`), along with the corresponding [`Client`] methods.
 //!
 //! ### Mock Clients
 //!
@@ -22,6 +34,13 @@
 //! [`MockClient`], which implements both [`Client`] and [`SubscriptionClient`]
 //! traits.
 //!
+//! ### Thread safety
+//!
+//! The clients, their drivers and the futures of their methods are all `Send`,
+//! and the clients are `Sync`, so that they can be shared and spawned onto
+//! multi-threaded runtimes. Changes to these guarantees are considered
+//! breaking.
+//!
 //! [Tendermint RPC]: https://docs.tendermint.com/v0.34/rpc/
 //! [`/subscribe` endpoint]: https://docs.tendermint.com/v0.34/rpc/#/Websocket/subscribe
 
@@ -30,21 +49,37 @@
 extern crate alloc;
 extern crate std;
 
-mod prelude;
+mod alloc_prelude;
 
 #[cfg(any(feature = "http-client", feature = "websocket-client"))]
 pub mod client;
 #[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use client::ProxyUrl;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use client::Resolver;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use client::{CallTimings, SlowCallLogger, TimingObserver};
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use client::{CallOutcome, ErrorClass, Instrumentation};
+#[cfg(feature = "prometheus")]
+pub use client::PrometheusMetrics;
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
 pub use client::{
-    Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, Subscription,
-    SubscriptionClient,
+    Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, SearchStreamConfig,
+    Subscription, SubscriptionClient, TlsConfig,
 };
+#[cfg(feature = "grpc-client")]
+pub use client::GrpcClient;
 #[cfg(feature = "http-client")]
-pub use client::{HttpClient, HttpClientUrl};
+pub use client::{HttpClient, HttpClientBuilder, HttpClientUrl};
 #[cfg(feature = "websocket-client")]
-pub use client::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl, WebSocketConfig};
+pub use client::{
+    KeepAliveConfig, ReconnectConfig, SubscriptionStats, WebSocketClient, WebSocketClientBuilder,
+    WebSocketClientDriver, WebSocketClientUrl, WebSocketConfig,
+};
 
 pub mod dialect;
+pub mod dispatch;
 pub mod endpoint;
 pub mod error;
 pub mod event;
@@ -52,6 +87,7 @@ mod id;
 mod method;
 mod order;
 mod paging;
+pub mod prelude;
 pub mod query;
 pub mod request;
 pub mod response;
@@ -7,7 +7,7 @@ use core::{
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{prelude::*, Error};
+use crate::{alloc_prelude::*, Error};
 
 /// JSON-RPC request methods.
 ///
@@ -53,9 +53,21 @@ pub enum Method {
     /// Get consensus state
     ConsensusState,
 
+    /// Dial peers (unsafe)
+    DialPeers,
+
+    /// Dial seeds (unsafe)
+    DialSeeds,
+
+    /// Get the full consensus state, including the peers' round states
+    DumpConsensusState,
+
     /// Get genesis file
     Genesis,
 
+    /// Get a chunk of the genesis file
+    GenesisChunked,
+
     /// Get block header
     Header,
 
@@ -88,6 +100,9 @@ pub enum Method {
 
     /// Broadcast evidence
     BroadcastEvidence,
+
+    /// Remove all the transactions from the mempool (unsafe)
+    UnsafeFlushMempool,
 }
 
 impl Method {
@@ -108,7 +123,11 @@ impl Method {
             Method::Commit => "commit",
             Method::ConsensusParams => "consensus_params",
             Method::ConsensusState => "consensus_state",
+            Method::DialPeers => "dial_peers",
+            Method::DialSeeds => "dial_seeds",
+            Method::DumpConsensusState => "dump_consensus_state",
             Method::Genesis => "genesis",
+            Method::GenesisChunked => "genesis_chunked",
             Method::Header => "header",
             Method::HeaderByHash => "header_by_hash",
             Method::Health => "health",
@@ -118,6 +137,7 @@ impl Method {
             Method::Tx => "tx",
             Method::TxSearch => "tx_search",
             Method::Unsubscribe => "unsubscribe",
+            Method::UnsafeFlushMempool => "unsafe_flush_mempool",
             Method::Validators => "validators",
         }
     }
@@ -144,7 +164,11 @@ impl FromStr for Method {
             "commit" => Method::Commit,
             "consensus_params" => Method::ConsensusParams,
             "consensus_state" => Method::ConsensusState,
+            "dial_peers" => Method::DialPeers,
+            "dial_seeds" => Method::DialSeeds,
+            "dump_consensus_state" => Method::DumpConsensusState,
             "genesis" => Method::Genesis,
+            "genesis_chunked" => Method::GenesisChunked,
             "health" => Method::Health,
             "net_info" => Method::NetInfo,
             "status" => Method::Status,
@@ -152,6 +176,7 @@ impl FromStr for Method {
             "tx" => Method::Tx,
             "tx_search" => Method::TxSearch,
             "unsubscribe" => Method::Unsubscribe,
+            "unsafe_flush_mempool" => Method::UnsafeFlushMempool,
             "validators" => Method::Validators,
             other => return Err(Error::method_not_found(other.to_string())),
         })
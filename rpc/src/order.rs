@@ -4,7 +4,7 @@ use core::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{prelude::*, Error};
+use crate::{alloc_prelude::*, Error};
 
 /// Ordering of paginated RPC responses.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
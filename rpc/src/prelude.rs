@@ -1,11 +1,20 @@
-// Re-export according to alloc::prelude::v1 because it is not yet stabilized
-// https://doc.rust-lang.org/src/alloc/prelude/v1.rs.html
-pub use alloc::{
-    borrow::ToOwned,
-    boxed::Box,
-    format,
-    string::{String, ToString},
-    vec,
-    vec::Vec,
+//! The traits and types most commonly needed to query Tendermint nodes, to be
+//! glob-imported:
+//!
+//! ```
+//! use tendermint_rpc::prelude::*;
+//! ```
+
+#[cfg(feature = "http-client")]
+pub use crate::client::{HttpClient, HttpClientBuilder, HttpClientUrl};
+#[cfg(any(feature = "http-client", feature = "websocket-client"))]
+pub use crate::client::{Client, CompatMode, Subscription, SubscriptionClient};
+#[cfg(feature = "websocket-client")]
+pub use crate::client::{
+    WebSocketClient, WebSocketClientBuilder, WebSocketClientDriver, WebSocketClientUrl,
+};
+pub use crate::{
+    event::Event,
+    query::{EventType, Query},
+    Order, Paging, Url,
 };
-pub use core::prelude::v1::*;
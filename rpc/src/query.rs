@@ -12,7 +12,11 @@ use time::{
     Date, OffsetDateTime,
 };
 
-use crate::{prelude::*, serializers::timestamp, Error};
+use crate::{alloc_prelude::*, serializers::timestamp, Error};
+
+mod matching;
+
+pub use matching::composite_events;
 
 /// A structured query for use in interacting with the Tendermint RPC event
 /// subscription system.
@@ -36,6 +40,29 @@ use crate::{prelude::*, serializers::timestamp, Error};
 ///
 /// let query = Query::from(EventType::Tx).and_gte("tx.height", 100_u64);
 /// assert_eq!("tm.event = 'Tx' AND tx.height >= 100", query.to_string());
+///
+/// let query = Query::event_type(EventType::Tx)
+///     .and_eq("transfer.sender", "it's me")
+///     .and_gte("tx.height", 100_u64);
+/// assert_eq!(
+///     "tm.event = 'Tx' AND transfer.sender = 'it\\'s me' AND tx.height >= 100",
+///     query.to_string(),
+/// );
+/// assert!(query.validate().is_ok());
+/// ```
+///
+/// ### Validation
+///
+/// Queries built from their parts are only checked against the query
+/// grammar by [`Query::validate`]: keys must not contain whitespace nor any
+/// of `\()"'=<>`, and only numbers, dates and times can be compared with
+/// `<`, `<=`, `>` and `>=`.
+///
+/// ```rust
+/// use tendermint_rpc::query::Query;
+///
+/// assert!(Query::lt("transfer.sender", "abc").validate().is_err());
+/// assert!(Query::eq("transfer sender", "abc").validate().is_err());
 /// ```
 ///
 /// ### Query parsing
@@ -51,6 +78,24 @@ use crate::{prelude::*, serializers::timestamp, Error};
 ///
 /// let query: Query = "tm.event = 'Tx' AND tx.height >= 100".parse().unwrap();
 /// assert_eq!(query, Query::from(EventType::Tx).and_gte("tx.height", 100_u64));
+///
+/// // Displayed queries parse back into the same query.
+/// let query = Query::from(EventType::Tx).and_eq("transfer.sender", "it's me");
+/// assert_eq!(query.to_string().parse::<Query>().unwrap(), query);
+/// ```
+///
+/// ### Matching
+///
+/// Queries can be matched locally against events, as the node would, e.g.
+/// to filter the events of a subscription.
+///
+/// ```rust
+/// use tendermint::abci::Event;
+/// use tendermint_rpc::query::{composite_events, Query};
+///
+/// let events = composite_events(&[Event::new("transfer", [("amount", "10stake", true)])]);
+/// assert!(Query::gte("transfer.amount", 10_u64).matches(&events));
+/// assert!(!Query::eq("transfer.amount", "10").matches(&events));
 /// ```
 ///
 /// [subscribe endpoint documentation]: https://docs.tendermint.com/v0.34/rpc/#/Websocket/subscribe
@@ -64,6 +109,11 @@ pub struct Query {
 }
 
 impl Query {
+    /// Query constructor testing whether the event is of the given type.
+    pub fn event_type(t: EventType) -> Self {
+        Self::from(t)
+    }
+
     /// Query constructor testing whether `<key> = <value>`
     pub fn eq(key: impl ToString, value: impl Into<Operand>) -> Self {
         Self {
@@ -169,6 +219,13 @@ impl Query {
         self.conditions.push(Condition::exists(key.to_string()));
         self
     }
+
+    /// Check that the query complies with the query grammar of the node.
+    ///
+    /// Parsed queries are always valid.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.conditions.iter().try_for_each(Condition::validate)
+    }
 }
 
 impl Default for Query {
@@ -216,8 +273,9 @@ peg::parser! {
         // At least some whitespace.
         rule __() = quiet!{[' ']+}
 
-        rule string() -> &'input str
-            = "'" s:$([^'\'']*) "'" { s }
+        // Quotes and backslashes are escaped with a backslash.
+        rule string() -> String
+            = "'" s:(("\\" c:$([_]) { c }) / $([^'\'' | '\\']))* "'" { s.concat() }
 
         rule unsigned() -> u64
             = s:$(['0'..='9']+) {?
@@ -265,7 +323,7 @@ peg::parser! {
             }
 
         rule string_op() -> Operand
-            = s:string() { Operand::String(s.to_owned()) }
+            = s:string() { Operand::String(s) }
 
         rule unsigned_op() -> Operand
             = u:unsigned() { Operand::Unsigned(u) }
@@ -276,8 +334,10 @@ peg::parser! {
         rule datetime_op() -> Operand
             = "TIME" __ dt:datetime() {?
                 OffsetDateTime::parse(dt, &Rfc3339)
-                    .map(|dt| Operand::DateTime(dt.to_offset(offset!(UTC))))
-                    .map_err(|_| "failed to parse as RFC3339-compatible date/time")
+                    .ok()
+                    .and_then(timestamp::checked_to_utc)
+                    .map(Operand::DateTime)
+                    .ok_or("failed to parse as RFC3339-compatible date/time")
             }
 
         rule date_op() -> Operand
@@ -291,34 +351,38 @@ peg::parser! {
             = f:float() { Operand::Float(f) }
 
         rule tag() -> &'input str
-            = $(['a'..='z' | 'A'..='Z'] ['a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.']*)
+            = $([^' ' | '\t' | '\n' | '\r' | '\\' | '(' | ')' | '"' | '\'' | '=' | '>' | '<']+)
 
         rule operand() -> Operand
             = datetime_op() / date_op() / string_op() / float_op() / signed_op() / unsigned_op()
 
+        // Strings cannot be ordered.
+        rule ordered_operand() -> Operand
+            = datetime_op() / date_op() / float_op() / signed_op() / unsigned_op()
+
         rule eq() -> Condition
             = t:tag() _ "=" _ op:operand() { Condition::eq(t.to_owned(), op) }
 
         rule lte() -> Condition
-            = t:tag() _ "<=" _ op:operand() { Condition::lte(t.to_owned(), op) }
+            = t:tag() _ "<=" _ op:ordered_operand() { Condition::lte(t.to_owned(), op) }
 
         rule lt() -> Condition
-            = t:tag() _ "<" _ op:operand() { Condition::lt(t.to_owned(), op) }
+            = t:tag() _ "<" _ op:ordered_operand() { Condition::lt(t.to_owned(), op) }
 
         rule gte() -> Condition
-            = t:tag() _ ">=" _ op:operand() { Condition::gte(t.to_owned(), op) }
+            = t:tag() _ ">=" _ op:ordered_operand() { Condition::gte(t.to_owned(), op) }
 
         rule gt() -> Condition
-            = t:tag() _ ">" _ op:operand() { Condition::gt(t.to_owned(), op) }
+            = t:tag() _ ">" _ op:ordered_operand() { Condition::gt(t.to_owned(), op) }
 
         rule contains() -> Condition
-            = t:tag() __ "CONTAINS" __ op:string() { Condition::contains(t.to_owned(), op.to_owned()) }
+            = t:tag() __ "CONTAINS" __ op:string() { Condition::contains(t.to_owned(), op) }
 
         rule exists() -> Condition
             = t:tag() __ "EXISTS" { Condition::exists(t.to_owned()) }
 
         rule event_type() -> Term
-            = "tm.event" _ "=" _ "'" et:$("NewBlock" / "Tx") "'" {
+            = "tm.event" _ "=" _ "'" et:$("NewBlock" / "NewRound" / "Vote" / "TimeoutPropose" / "Tx") "'" {
                 Term::EventType(EventType::from_str(et).unwrap())
             }
 
@@ -367,10 +431,12 @@ impl FromStr for Query {
                 "tm.event can only be used once in a query".to_owned(),
             ));
         }
-        Ok(Query {
+        let query = Query {
             event_type: event_types.first().cloned(),
             conditions,
-        })
+        };
+        query.validate()?;
+        Ok(query)
     }
 }
 
@@ -397,6 +463,12 @@ where
 pub enum EventType {
     NewBlock,
     Tx,
+    /// A new round of consensus started.
+    NewRound,
+    /// A validator voted.
+    Vote,
+    /// The proposal of a round was not received within `timeout_propose`.
+    TimeoutPropose,
 }
 
 impl fmt::Display for EventType {
@@ -404,6 +476,9 @@ impl fmt::Display for EventType {
         match self {
             EventType::NewBlock => write!(f, "NewBlock"),
             EventType::Tx => write!(f, "Tx"),
+            EventType::NewRound => write!(f, "NewRound"),
+            EventType::Vote => write!(f, "Vote"),
+            EventType::TimeoutPropose => write!(f, "TimeoutPropose"),
         }
     }
 }
@@ -415,6 +490,9 @@ impl FromStr for EventType {
         match s {
             "NewBlock" => Ok(Self::NewBlock),
             "Tx" => Ok(Self::Tx),
+            "NewRound" => Ok(Self::NewRound),
+            "Vote" => Ok(Self::Vote),
+            "TimeoutPropose" => Ok(Self::TimeoutPropose),
             invalid => Err(Error::unrecognized_event_type(invalid.to_string())),
         }
     }
@@ -470,6 +548,36 @@ impl Condition {
     pub fn exists(key: String) -> Self {
         Self::new(key, Operation::Exists)
     }
+
+    /// Check that the condition complies with the query grammar of the node.
+    pub fn validate(&self) -> Result<(), Error> {
+        const RESERVED: &[char] = &['\\', '(', ')', '"', '\'', '=', '<', '>'];
+
+        if self.key.is_empty()
+            || self
+                .key
+                .contains(|c: char| c.is_whitespace() || RESERVED.contains(&c))
+        {
+            return Err(Error::invalid_params(format!(
+                "invalid key in query condition: {:?}",
+                self.key
+            )));
+        }
+        if self.key == "tm.event" {
+            return Err(Error::invalid_params(
+                "tm.event can only be tested for a known event type".to_owned(),
+            ));
+        }
+        match &self.operation {
+            Operation::Lt(Operand::String(_))
+            | Operation::Lte(Operand::String(_))
+            | Operation::Gt(Operand::String(_))
+            | Operation::Gte(Operand::String(_)) => Err(Error::invalid_params(format!(
+                "strings cannot be ordered in query condition: {self}"
+            ))),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl fmt::Display for Condition {
@@ -678,6 +786,9 @@ mod test {
 
         let query = Query::from(EventType::Tx);
         assert_eq!("tm.event = 'Tx'", query.to_string());
+
+        let query = Query::from(EventType::TimeoutPropose);
+        assert_eq!("tm.event = 'TimeoutPropose'", query.to_string());
     }
 
     #[test]
@@ -769,6 +880,10 @@ mod test {
         let query = Query::from_str("tm.event='NewBlock'").unwrap();
         assert_eq!(query.event_type, Some(EventType::NewBlock));
         assert!(query.conditions.is_empty());
+        let query = Query::from_str("tm.event='NewRound'").unwrap();
+        assert_eq!(query.event_type, Some(EventType::NewRound));
+        let query = Query::from_str("tm.event='Vote'").unwrap();
+        assert_eq!(query.event_type, Some(EventType::Vote));
 
         // One event type, with whitespace
         let query = Query::from_str("tm.event =  'NewBlock'").unwrap();
@@ -868,7 +983,9 @@ mod test {
                 "some.datetime".to_owned(),
                 Operand::DateTime(datetime!(2021-2-26 17:05:02.149500000 UTC))
             )]
-        )
+        );
+        // Not representable in UTC.
+        assert!(Query::from_str("some.datetime = TIME 9999-12-31T23:59:59-01:00").is_err());
     }
 
     #[test]
@@ -920,6 +1037,39 @@ mod test {
         a == b
     }
 
+    #[test]
+    fn escaped_strings_round_trip() {
+        for value in ["it's", "back\\slash", "\\'", "''", ""] {
+            let query = Query::event_type(EventType::Tx)
+                .and_eq("transfer.sender", value)
+                .and_contains("message.memo", value);
+            assert_eq!(Query::from_str(&query.to_string()).unwrap(), query);
+        }
+    }
+
+    #[test]
+    fn keys_follow_the_grammar() {
+        let query = Query::from_str("wasm-execute._contract_address = 'abc'").unwrap();
+        assert_eq!(query, Query::eq("wasm-execute._contract_address", "abc"));
+
+        for key in ["", "transfer sender", "a=b", "a'b", "tm.event"] {
+            assert!(Query::eq(key, "abc").validate().is_err(), "{key:?}");
+        }
+        assert!(Query::from_str("tm.event = 'Other'").is_err());
+    }
+
+    #[test]
+    fn strings_cannot_be_ordered() {
+        assert!(Query::from_str("some.field < 'abc'").is_err());
+        assert!(Query::from_str("some.field >= 'abc'").is_err());
+
+        assert!(Query::gte("some.field", "abc").validate().is_err());
+        assert!(Query::gte("some.field", 5_u64).validate().is_ok());
+        assert!(Query::lt("some.field", date!(2020 - 09 - 24))
+            .validate()
+            .is_ok());
+    }
+
     #[test]
     fn query_conditions() {
         let query = Query::from_str("some.field = 'string'").unwrap();
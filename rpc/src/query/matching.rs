@@ -0,0 +1,346 @@
+//! Local matching of queries against events, with the semantics of the node's
+//! event subscriptions and indexer, so that clients can filter events and
+//! check search results without trusting the node.
+
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+
+use tendermint::{abci, crypto::Sha256, Hash};
+use time::{
+    format_description::well_known::Rfc3339, macros::format_description, Date, OffsetDateTime,
+};
+
+use super::{Condition, Operand, Operation, Query};
+use crate::{
+    alloc_prelude::*,
+    event::{Event, EventData, TxInfo},
+};
+
+impl Query {
+    /// Whether the given events match the query.
+    ///
+    /// The events are indexed by composite key, `{type}.{attribute key}`, as
+    /// in [`Event::events`] or as returned by [`composite_events`]. As on the
+    /// node, each condition must be satisfied by one of the values of its
+    /// key, and the event type is matched against the values of `tm.event`.
+    /// Different conditions may be satisfied by different events.
+    pub fn matches(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        if let Some(event_type) = &self.event_type {
+            let event_type = event_type.to_string();
+            let matches_type = events
+                .get("tm.event")
+                .map_or(false, |types| types.contains(&event_type));
+            if !matches_type {
+                return false;
+            }
+        }
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(events))
+    }
+
+    /// Whether the transaction matches the query, as indexed by the node:
+    /// along with the attributes of its events, a transaction has the keys
+    /// `tm.event`, `tx.height` and `tx.hash`, the latter being computed
+    /// with the hasher `H`.
+    pub fn matches_tx_with<H>(&self, tx: &TxInfo) -> bool
+    where
+        H: Sha256,
+    {
+        let mut events = composite_events(&tx.result.events);
+        push(&mut events, "tm.event", "Tx".to_string());
+        push(
+            &mut events,
+            "tx.hash",
+            Hash::Sha256(H::digest(&tx.tx)).to_string(),
+        );
+        push(&mut events, "tx.height", tx.height.to_string());
+        self.matches(&events)
+    }
+
+    /// Whether an event received from a subscription matches the query.
+    ///
+    /// The events reported by the node along with the event are matched, if
+    /// any. Otherwise the events are derived from the data of the event,
+    /// without `tx.hash`, which can be matched with [`Query::matches_tx_with`].
+    pub fn matches_event(&self, event: &Event) -> bool {
+        match &event.events {
+            Some(events) => self.matches(events),
+            None => self.matches(&data_events(event)),
+        }
+    }
+}
+
+impl Condition {
+    /// Whether one of the values of the key of the condition satisfies it.
+    ///
+    /// Numbers, dates and times are compared to the values parsed as such,
+    /// values which fail to parse not satisfying the condition. As on the
+    /// node, a value starting with a number, like `10stake`, is compared as
+    /// that number.
+    pub fn matches(&self, events: &BTreeMap<String, Vec<String>>) -> bool {
+        let values = match events.get(&self.key) {
+            Some(values) => values,
+            None => return false,
+        };
+        let any = |f: &dyn Fn(&str) -> bool| values.iter().any(|value| f(value));
+        match &self.operation {
+            Operation::Exists => true,
+            Operation::Contains(s) => any(&|value| value.contains(s.as_str())),
+            Operation::Eq(Operand::String(s)) => any(&|value| value == s),
+            Operation::Eq(op) => any(&|value| compare(value, op) == Some(Ordering::Equal)),
+            Operation::Lt(op) => any(&|value| compare(value, op) == Some(Ordering::Less)),
+            Operation::Lte(op) => {
+                any(&|value| matches!(compare(value, op), Some(Ordering::Less | Ordering::Equal)))
+            },
+            Operation::Gt(op) => any(&|value| compare(value, op) == Some(Ordering::Greater)),
+            Operation::Gte(op) => any(&|value| {
+                matches!(
+                    compare(value, op),
+                    Some(Ordering::Greater | Ordering::Equal)
+                )
+            }),
+        }
+    }
+}
+
+/// Index the attributes of the given events by composite key,
+/// `{type}.{attribute key}`, as the node does before matching queries.
+///
+/// Events without type and attributes without key are skipped.
+pub fn composite_events<'a>(
+    events: impl IntoIterator<Item = &'a abci::Event>,
+) -> BTreeMap<String, Vec<String>> {
+    let mut composite = BTreeMap::new();
+    for event in events {
+        if event.kind.is_empty() {
+            continue;
+        }
+        for attribute in &event.attributes {
+            if attribute.key.is_empty() {
+                continue;
+            }
+            push(
+                &mut composite,
+                &format!("{}.{}", event.kind, attribute.key),
+                attribute.value.clone(),
+            );
+        }
+    }
+    composite
+}
+
+fn push(events: &mut BTreeMap<String, Vec<String>>, key: &str, value: String) {
+    events.entry(key.to_string()).or_default().push(value);
+}
+
+/// The events of a subscription event, derived from its data.
+fn data_events(event: &Event) -> BTreeMap<String, Vec<String>> {
+    let mut events = match &event.data {
+        EventData::NewBlock {
+            block,
+            result_begin_block,
+            result_end_block,
+        } => {
+            let mut events = composite_events(
+                result_begin_block
+                    .iter()
+                    .flat_map(|result| &result.events)
+                    .chain(result_end_block.iter().flat_map(|result| &result.events)),
+            );
+            if let Some(block) = block {
+                push(&mut events, "block.height", block.header.height.to_string());
+            }
+            events
+        },
+        EventData::Tx { tx_result } => {
+            let mut events = composite_events(&tx_result.result.events);
+            push(&mut events, "tx.height", tx_result.height.to_string());
+            events
+        },
+        _ => BTreeMap::new(),
+    };
+    if let Some(event_type) = event.event_type() {
+        push(&mut events, "tm.event", event_type.to_string());
+    }
+    events
+}
+
+/// Compare a value to an operand other than a string, parsing the value
+/// according to the type of the operand.
+fn compare(value: &str, operand: &Operand) -> Option<Ordering> {
+    match operand {
+        Operand::String(_) => None,
+        Operand::Signed(i) => Number::parse(value)?.compare(Number::Integer(*i as i128)),
+        Operand::Unsigned(u) => Number::parse(value)?.compare(Number::Integer(*u as i128)),
+        Operand::Float(f) => Number::parse(value)?.compare(Number::Float(*f)),
+        Operand::Date(date) => {
+            let value = Date::parse(value, &format_description!("[year]-[month]-[day]")).ok()?;
+            Some(value.cmp(date))
+        },
+        Operand::DateTime(datetime) => {
+            let value = OffsetDateTime::parse(value, &Rfc3339).ok()?;
+            Some(value.cmp(datetime))
+        },
+    }
+}
+
+/// A number parsed from the value of an attribute.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Number {
+    /// Parse a value as an integer or, failing that, its leading number,
+    /// made of digits optionally followed by a fractional part.
+    fn parse(value: &str) -> Option<Self> {
+        if let Ok(i) = value.parse() {
+            return Some(Number::Integer(i));
+        }
+        let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let mut end = digits(value);
+        if end == 0 {
+            return None;
+        }
+        if let Some(fraction) = value[end..].strip_prefix('.') {
+            let fraction_digits = digits(fraction);
+            if fraction_digits > 0 {
+                end += 1 + fraction_digits;
+            }
+        }
+        let number = &value[..end];
+        match number.parse() {
+            Ok(i) => Some(Number::Integer(i)),
+            Err(_) => number.parse().ok().map(Number::Float),
+        }
+    }
+
+    fn compare(self, other: Self) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(a.cmp(&b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint::{
+        abci::response::DeliverTx, block::Height, crypto::default::Sha256 as DefaultSha256,
+    };
+    use time::macros::{date, datetime};
+
+    use super::*;
+    use crate::query::EventType;
+
+    fn events(pairs: &[(&str, &str)]) -> BTreeMap<String, Vec<String>> {
+        let mut events = BTreeMap::new();
+        for (key, value) in pairs {
+            push(&mut events, key, value.to_string());
+        }
+        events
+    }
+
+    fn query(s: &str) -> Query {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn event_type_and_strings() {
+        let events = events(&[
+            ("tm.event", "Tx"),
+            ("transfer.sender", "alice"),
+            ("transfer.sender", "bob"),
+        ]);
+        assert!(Query::default().matches(&events));
+        assert!(query("tm.event = 'Tx'").matches(&events));
+        assert!(!query("tm.event = 'NewBlock'").matches(&events));
+        assert!(query("transfer.sender = 'bob'").matches(&events));
+        assert!(!query("transfer.sender = 'carol'").matches(&events));
+        assert!(query("transfer.sender CONTAINS 'li'").matches(&events));
+        assert!(query("transfer.sender EXISTS").matches(&events));
+        assert!(!query("transfer.recipient EXISTS").matches(&events));
+        assert!(!query("tm.event = 'Tx' AND transfer.sender = 'carol'").matches(&events));
+    }
+
+    #[test]
+    fn numbers() {
+        let events = events(&[
+            ("tx.height", "100"),
+            ("transfer.amount", "10stake"),
+            ("transfer.fee", "0.25"),
+            ("transfer.memo", "none"),
+        ]);
+        assert!(query("tx.height = 100").matches(&events));
+        assert!(query("tx.height > 99 AND tx.height <= 100").matches(&events));
+        assert!(!query("tx.height < 100").matches(&events));
+        assert!(query("tx.height >= -1").matches(&events));
+        assert!(query("tx.height < 100.5").matches(&events));
+        assert!(query("transfer.amount = 10").matches(&events));
+        assert!(!query("transfer.amount = '10'").matches(&events));
+        assert!(query("transfer.fee > 0.2 AND transfer.fee < 1").matches(&events));
+        assert!(!query("transfer.memo >= 0").matches(&events));
+    }
+
+    #[test]
+    fn number_parsing() {
+        assert_eq!(Number::parse("-7"), Some(Number::Integer(-7)));
+        assert_eq!(Number::parse("12.5atom"), Some(Number::Float(12.5)));
+        assert_eq!(Number::parse("12.atom"), Some(Number::Integer(12)));
+        assert_eq!(Number::parse("-1.5"), None);
+        assert_eq!(Number::parse("atom"), None);
+    }
+
+    #[test]
+    fn dates_and_times() {
+        let events = events(&[
+            ("account.created", "2020-01-02"),
+            ("account.updated", "2020-01-02T03:04:05Z"),
+        ]);
+        assert!(Query::eq("account.created", date!(2020 - 01 - 02)).matches(&events));
+        assert!(Query::gt("account.created", date!(2020 - 01 - 01)).matches(&events));
+        assert!(Query::lt("account.updated", datetime!(2020-01-02 03:04:06 UTC)).matches(&events));
+        assert!(!Query::lt("account.created", datetime!(2020-01-03 00:00 UTC)).matches(&events));
+    }
+
+    #[test]
+    fn transactions() {
+        let tx = TxInfo {
+            height: Height::from(42_u32),
+            index: 0,
+            tx: b"tx".to_vec(),
+            result: DeliverTx {
+                events: vec![
+                    abci::Event::new("transfer", [("sender", "alice", true)]),
+                    abci::Event::new("", [("ignored", "value", true)]),
+                ],
+                ..Default::default()
+            },
+        };
+        let hash = Hash::Sha256(DefaultSha256::digest(b"tx"));
+
+        let query = Query::from(EventType::Tx)
+            .and_eq("tx.height", 42_u64)
+            .and_eq("transfer.sender", "alice");
+        assert!(query.matches_tx_with::<DefaultSha256>(&tx));
+        assert!(Query::eq("tx.hash", hash.to_string()).matches_tx_with::<DefaultSha256>(&tx));
+        assert!(!Query::exists(".ignored").matches_tx_with::<DefaultSha256>(&tx));
+
+        let event = Event {
+            query: query.to_string(),
+            data: EventData::Tx { tx_result: tx },
+            events: None,
+        };
+        assert!(query.matches_event(&event));
+        assert!(!Query::from(EventType::NewBlock).matches_event(&event));
+    }
+}
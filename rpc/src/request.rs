@@ -6,12 +6,17 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{Id, Method, Version};
 use crate::dialect::{Dialect, LatestDialect};
-use crate::{prelude::*, Error};
+use crate::{alloc_prelude::*, Error};
 
 /// Serialization for JSON-RPC requests
 pub trait RequestMessage: DeserializeOwned + Serialize + Sized {
     /// Request method
-    fn method(&self) -> Method;
+    const METHOD: Method;
+
+    /// The method of this request, i.e. [`RequestMessage::METHOD`].
+    fn method(&self) -> Method {
+        Self::METHOD
+    }
 
     /// Serialize this request as JSON
     fn into_json(self) -> String {
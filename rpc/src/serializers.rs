@@ -8,5 +8,6 @@
 pub use tendermint::serializers::*;
 
 pub mod opt_tm_hash_base64;
+pub mod str_or_int;
 pub mod tm_hash_base64;
 pub mod tx_hash_base64;
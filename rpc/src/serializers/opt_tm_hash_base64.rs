@@ -2,7 +2,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tendermint::hash::Hash;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[derive(Serialize, Deserialize)]
 struct Helper(#[serde(with = "crate::serializers::tm_hash_base64")] Hash);
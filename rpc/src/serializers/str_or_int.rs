@@ -0,0 +1,30 @@
+//! Deserialize integers which some versions of Tendermint and CometBFT
+//! encode as JSON numbers, and others as strings.
+
+use core::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::alloc_prelude::*;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StrOrInt {
+    Str(String),
+    Int(i64),
+}
+
+/// Deserialize a string or an integer into any `T` parsed from the decimal
+/// representation of the integer.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = match StrOrInt::deserialize(deserializer)? {
+        StrOrInt::Str(s) => s,
+        StrOrInt::Int(i) => i.to_string(),
+    };
+    s.parse().map_err(serde::de::Error::custom)
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::base64;
 use tendermint::hash::{Algorithm::Sha256, Hash, SHA256_HASH_SIZE};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 /// Deserialize a base64-encoded string into an tendermint::Hash
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::base64;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use tendermint::{hash::Algorithm, Hash};
 
 /// Deserialize a base64-encoded string into an abci::transaction::Hash
@@ -2,7 +2,7 @@
 
 use getrandom::getrandom;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 /// Produce a string containing a UUID.
 ///
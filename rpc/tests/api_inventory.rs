@@ -0,0 +1,254 @@
+//! Inventory of the public RPC surface of this crate.
+//!
+//! The inventory lists the names of the RPC methods, the methods of the
+//! [`Client`] and [`SubscriptionClient`] traits, and the shapes of the
+//! public request and response types of the endpoints, i.e. their public
+//! fields and variants. It is generated from the sources and compared with
+//! the approved snapshot in `tests/api_inventory.txt`, so that any change to
+//! this surface fails the test until the snapshot is updated along with it:
+//!
+//! ```text
+//! UPDATE_API_INVENTORY=1 cargo test -p tendermint-rpc --test api_inventory
+//! ```
+//!
+//! The diff of the snapshot then shows the changes to the surface, to be
+//! accounted for in the version of the next release.
+//!
+//! [`Client`]: tendermint_rpc::Client
+//! [`SubscriptionClient`]: tendermint_rpc::SubscriptionClient
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const SNAPSHOT: &str = "tests/api_inventory.txt";
+
+const HEADER: &str = "\
+# Public RPC surface of tendermint-rpc, generated by tests/api_inventory.rs.
+# Update with: UPDATE_API_INVENTORY=1 cargo test -p tendermint-rpc --test api_inventory
+";
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()))
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// The lines of the item starting at the given line, up to its closing brace
+/// at the same indentation.
+fn item_body<'a, 'b>(lines: &'b [&'a str], start: usize) -> &'b [&'a str] {
+    let level = indent(lines[start]);
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| indent(line) == level && line.trim_start().starts_with('}'))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    &lines[start + 1..end]
+}
+
+/// The meaningful lines of the given body, without comments, attributes
+/// (except `cfg` attributes, which make items conditional) and blank lines.
+fn significant(body: &[&str]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut attribute = String::new();
+    for line in body {
+        let trimmed = line.trim();
+        if !attribute.is_empty() || trimmed.starts_with("#[") {
+            attribute.push_str(trimmed);
+            if attribute.matches('[').count() == attribute.matches(']').count() {
+                if attribute.starts_with("#[cfg(") {
+                    lines.push(format!("{}{attribute}", " ".repeat(indent(line))));
+                }
+                attribute.clear();
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        lines.push(line.trim_end().to_owned());
+    }
+    lines
+}
+
+/// The method names, in the order of `Method::as_str`.
+fn methods(out: &mut Vec<String>) {
+    out.push("## methods".to_owned());
+    let source = read(&manifest_dir().join("src/method.rs"));
+    let lines: Vec<_> = source.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.contains("pub fn as_str"))
+        .expect("Method::as_str");
+    for line in item_body(&lines, start) {
+        if let Some((variant, name)) = line.trim().split_once(" => ") {
+            out.push(format!(
+                "{} = {}",
+                name.trim_end_matches(',').trim_matches('"'),
+                variant.trim_start_matches("Method::")
+            ));
+        }
+    }
+}
+
+/// The signatures of the methods of the given trait, one per line.
+fn trait_methods(out: &mut Vec<String>, path: &str, name: &str) {
+    out.push(format!("## trait {name}"));
+    let source = read(&manifest_dir().join(path));
+    let lines: Vec<_> = source.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with(&format!("pub trait {name}")))
+        .unwrap_or_else(|| panic!("trait {name} in {path}"));
+    let body = item_body(&lines, start);
+
+    let mut signature: Option<String> = None;
+    let mut cfg = String::new();
+    for line in significant(body) {
+        let trimmed = line.trim();
+        if indent(&line) == 4 && trimmed.starts_with("#[cfg(") {
+            cfg = format!("{trimmed} ");
+            continue;
+        }
+        if indent(&line) == 4 && (trimmed.starts_with("fn ") || trimmed.starts_with("async fn ")) {
+            signature = Some(String::new());
+        }
+        if let Some(sig) = signature.as_mut() {
+            if !sig.is_empty() && !sig.ends_with('(') && !trimmed.starts_with(')') {
+                sig.push(' ');
+            }
+            sig.push_str(trimmed);
+            if trimmed.ends_with('{') || trimmed.ends_with(';') {
+                let sig = sig
+                    .trim_end_matches(|c| c == '{' || c == ';')
+                    .trim_end()
+                    .trim_end_matches(',')
+                    .replace(",)", ")")
+                    .replace(", )", ")");
+                out.push(format!("{cfg}{sig}"));
+                signature = None;
+                cfg.clear();
+            }
+        }
+    }
+}
+
+/// The endpoint modules, sorted by path.
+fn endpoint_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).expect("endpoint directory") {
+        let path = entry.expect("directory entry").path();
+        if path.is_dir() {
+            endpoint_files(&path, files);
+        } else if path.extension().map_or(false, |ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+}
+
+/// The public types of the endpoint modules, with their public fields and
+/// their variants.
+fn endpoint_types(out: &mut Vec<String>) {
+    let root = manifest_dir().join("src");
+    let mut files = Vec::new();
+    endpoint_files(&root.join("endpoint"), &mut files);
+
+    for path in files {
+        let source = read(&path);
+        let lines: Vec<_> = source.lines().collect();
+        let mut types = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let is_struct = line.starts_with("pub struct ");
+            if !is_struct && !line.starts_with("pub enum ") {
+                continue;
+            }
+            types.push(line.trim_end().to_owned());
+            if !line.ends_with('{') {
+                continue;
+            }
+            let mut field = false;
+            for member in significant(item_body(&lines, i)) {
+                // Only the public fields of structs are part of their shape.
+                if is_struct && indent(&member) == 4 {
+                    field = member.trim_start().starts_with("pub ");
+                }
+                if !is_struct || field {
+                    types.push(member);
+                }
+            }
+            types.push("}".to_owned());
+        }
+        if !types.is_empty() {
+            let module = path.strip_prefix(&root).expect("source path");
+            out.push(format!("## {}", module.display()));
+            out.extend(types);
+        }
+    }
+}
+
+fn inventory() -> String {
+    let mut out = Vec::new();
+    methods(&mut out);
+    trait_methods(&mut out, "src/client.rs", "Client");
+    trait_methods(&mut out, "src/client/subscription.rs", "SubscriptionClient");
+    endpoint_types(&mut out);
+    format!("{HEADER}{}\n", out.join("\n"))
+}
+
+#[test]
+fn api_inventory_matches_snapshot() {
+    let path = manifest_dir().join(SNAPSHOT);
+    let actual = inventory();
+    if env::var_os("UPDATE_API_INVENTORY").is_some() {
+        fs::write(&path, &actual).expect("to write the snapshot");
+        return;
+    }
+
+    let expected = read(&path);
+    if actual == expected {
+        return;
+    }
+    let changes: Vec<_> = diff(&expected, &actual).into_iter().take(50).collect();
+    panic!(
+        "the public RPC surface changed:\n{}\n\n\
+         If this change is intended, update {SNAPSHOT} with \
+         `UPDATE_API_INVENTORY=1 cargo test -p tendermint-rpc --test api_inventory`, \
+         and account for it in the version of the next release.",
+        changes.join("\n")
+    );
+}
+
+/// The lines removed from and added to the snapshot, prefixed with `-` and
+/// `+`, along with the section they belong to.
+fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let sections = |text: &str| -> Vec<(String, String)> {
+        let mut section = String::new();
+        text.lines()
+            .filter(|line| !line.starts_with('#') || line.starts_with("## "))
+            .filter_map(|line| {
+                if line.starts_with("## ") {
+                    section = line.to_owned();
+                    None
+                } else {
+                    Some((section.clone(), line.to_owned()))
+                }
+            })
+            .collect()
+    };
+    let (expected, actual) = (sections(expected), sections(actual));
+    let removed = expected
+        .iter()
+        .filter(|line| !actual.contains(line))
+        .map(|(section, line)| format!("- {section}: {}", line.trim()));
+    let added = actual
+        .iter()
+        .filter(|line| !expected.contains(line))
+        .map(|(section, line)| format!("+ {section}: {}", line.trim()));
+    removed.chain(added).collect()
+}
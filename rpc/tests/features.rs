@@ -0,0 +1,47 @@
+//! Smoke tests for the optional features of this crate.
+//!
+//! Every test is gated on the features it exercises, so that this file builds
+//! and passes under every feature combination checked by the workspace
+//! feature matrix (see `tendermint-test`).
+
+use core::str::FromStr;
+
+use tendermint_rpc::{endpoint, Response, Scheme, Url};
+
+#[test]
+fn responses_parse_without_client_features() {
+    let content = include_str!("kvstore_fixtures/v0_37/incoming/abci_info.json");
+    let result = endpoint::abci_info::Response::from_string(content).unwrap();
+    assert_eq!(result.response.app_version, 1);
+
+    let url = Url::from_str("http://127.0.0.1:26657").unwrap();
+    assert_eq!(url.scheme(), Scheme::Http);
+}
+
+#[cfg(feature = "http-client")]
+#[test]
+fn http_client_urls() {
+    use tendermint_rpc::HttpClientUrl;
+
+    assert!(HttpClientUrl::from_str("https://127.0.0.1:26657").is_ok());
+    assert!(HttpClientUrl::from_str("ws://127.0.0.1:26657/websocket").is_err());
+}
+
+#[cfg(feature = "websocket-client")]
+#[test]
+fn websocket_client_urls() {
+    use tendermint_rpc::WebSocketClientUrl;
+
+    assert!(WebSocketClientUrl::from_str("wss://127.0.0.1:26657/websocket").is_ok());
+    assert!(WebSocketClientUrl::from_str("https://127.0.0.1:26657").is_err());
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn secp256k1_keys_are_forwarded_to_tendermint() {
+    let bytes = subtle_encoding::hex::decode_upper(
+        "02950E1CDFCB133D6024109FD489F734EEB4502418E538C28481F22BCE276F248C",
+    )
+    .unwrap();
+    assert!(tendermint::PublicKey::from_raw_secp256k1(&bytes).is_some());
+}
@@ -91,6 +91,47 @@ fn outgoing_fixtures() {
                 assert_eq!(wrapped.params().min_height.value(), 1);
                 assert_eq!(wrapped.params().max_height.value(), 10);
             },
+            "broadcast_evidence" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::evidence::Request>>(&content)
+                        .unwrap();
+                let evidence = &wrapped.params().evidence;
+                if let Evidence::DuplicateVote(dup) = evidence {
+                    assert_eq!(dup.total_voting_power.value(), 121);
+                    assert_eq!(dup.validator_power.value(), 1);
+                    assert_ne!(dup.vote_a.block_id, dup.vote_b.block_id);
+                } else {
+                    panic!("not a duplicate vote: {evidence:?}");
+                }
+                // The evidence is encoded back as Tendermint expects it.
+                let fixture: serde_json::Value = serde_json::from_str(&content).unwrap();
+                assert_eq!(
+                    serde_json::to_value(wrapped.params()).unwrap(),
+                    fixture["params"]
+                );
+            },
+            "broadcast_evidence_light_client_attack" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::evidence::Request>>(&content)
+                        .unwrap();
+                let evidence = &wrapped.params().evidence;
+                if let Evidence::LightClientAttack(attack) = evidence {
+                    assert_eq!(attack.common_height.value(), 9);
+                    assert_eq!(
+                        attack.conflicting_block.signed_header.header.height.value(),
+                        10
+                    );
+                    assert_eq!(attack.byzantine_validators.len(), 1);
+                    assert_eq!(attack.total_voting_power.value(), 10);
+                } else {
+                    panic!("not a light client attack: {evidence:?}");
+                }
+                let fixture: serde_json::Value = serde_json::from_str(&content).unwrap();
+                assert_eq!(
+                    serde_json::to_value(wrapped.params()).unwrap(),
+                    fixture["params"]
+                );
+            },
             "broadcast_tx_async" => {
                 let wrapped = serde_json::from_str::<
                     RequestWrapper<endpoint::broadcast::tx_async::Request>,
@@ -480,6 +521,13 @@ fn incoming_fixtures() {
                     }
                 }
             },
+            "broadcast_evidence" => {
+                let result = endpoint::evidence::Response::from_string(content).unwrap();
+                assert_ne!(
+                    result.hash,
+                    Hash::from_bytes(Algorithm::Sha256, &[0; 32]).unwrap()
+                );
+            },
             "broadcast_tx_async" => {
                 let result = endpoint::broadcast::tx_async::Response::from_string(content).unwrap();
                 assert_eq!(result.code, abci::Code::Ok);
@@ -1106,10 +1154,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 1);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1139,10 +1189,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 1);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1173,10 +1225,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 1);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1206,10 +1260,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 1);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1239,10 +1295,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 1);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
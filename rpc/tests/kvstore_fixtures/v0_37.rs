@@ -90,6 +90,47 @@ fn outgoing_fixtures() {
                 assert_eq!(wrapped.params().min_height.value(), 1);
                 assert_eq!(wrapped.params().max_height.value(), 10);
             },
+            "broadcast_evidence" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::evidence::Request>>(&content)
+                        .unwrap();
+                let evidence = &wrapped.params().evidence;
+                if let Evidence::DuplicateVote(dup) = evidence {
+                    assert_eq!(dup.total_voting_power.value(), 121);
+                    assert_eq!(dup.validator_power.value(), 1);
+                    assert_ne!(dup.vote_a.block_id, dup.vote_b.block_id);
+                } else {
+                    panic!("not a duplicate vote: {evidence:?}");
+                }
+                // The evidence is encoded back as Tendermint expects it.
+                let fixture: serde_json::Value = serde_json::from_str(&content).unwrap();
+                assert_eq!(
+                    serde_json::to_value(wrapped.params()).unwrap(),
+                    fixture["params"]
+                );
+            },
+            "broadcast_evidence_light_client_attack" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::evidence::Request>>(&content)
+                        .unwrap();
+                let evidence = &wrapped.params().evidence;
+                if let Evidence::LightClientAttack(attack) = evidence {
+                    assert_eq!(attack.common_height.value(), 9);
+                    assert_eq!(
+                        attack.conflicting_block.signed_header.header.height.value(),
+                        10
+                    );
+                    assert_eq!(attack.byzantine_validators.len(), 1);
+                    assert_eq!(attack.total_voting_power.value(), 10);
+                } else {
+                    panic!("not a light client attack: {evidence:?}");
+                }
+                let fixture: serde_json::Value = serde_json::from_str(&content).unwrap();
+                assert_eq!(
+                    serde_json::to_value(wrapped.params()).unwrap(),
+                    fixture["params"]
+                );
+            },
             "broadcast_tx_async" => {
                 let wrapped = serde_json::from_str::<
                     RequestWrapper<endpoint::broadcast::tx_async::Request>,
@@ -142,6 +183,22 @@ fn outgoing_fixtures() {
                 RequestWrapper<endpoint::genesis::Request::<serde_json::Value>>,
             >(&content)
             .is_ok()),
+            "header_at_height_10" => {
+                let wrapped =
+                    serde_json::from_str::<RequestWrapper<endpoint::header::Request>>(&content)
+                        .unwrap();
+                assert_eq!(wrapped.params().height.unwrap().value(), 10);
+            },
+            "header_by_hash" => {
+                let wrapped = serde_json::from_str::<
+                    RequestWrapper<endpoint::header_by_hash::Request>,
+                >(&content)
+                .unwrap();
+                assert_eq!(
+                    wrapped.params().hash.unwrap().to_string(),
+                    "FCF9C2537FC3534CA71001FE1F14C4F769090948C1A521682F612E7CF73AE639"
+                );
+            },
             "net_info" => assert!(serde_json::from_str::<
                 RequestWrapper<endpoint::net_info::Request>,
             >(&content)
@@ -479,6 +536,13 @@ fn incoming_fixtures() {
                     }
                 }
             },
+            "broadcast_evidence" => {
+                let result = endpoint::evidence::Response::from_string(content).unwrap();
+                assert_ne!(
+                    result.hash,
+                    Hash::from_bytes(Algorithm::Sha256, &[0; 32]).unwrap()
+                );
+            },
             "broadcast_tx_async" => {
                 let result = endpoint::broadcast::tx_async::Response::from_string(content).unwrap();
                 assert_eq!(result.code, abci::Code::Ok);
@@ -675,6 +739,22 @@ fn incoming_fixtures() {
                 assert_eq!(result.genesis.validators[0].proposer_priority.value(), 0);
                 assert_eq!(result.genesis.consensus_params.block.time_iota_ms, 1000);
             },
+            "header_at_height_10" => {
+                let result = endpoint::header::Response::from_string(content).unwrap();
+                assert_eq!(result.header.chain_id.as_str(), CHAIN_ID);
+                assert_eq!(result.header.height.value(), 10);
+                assert_eq!(result.header.data_hash, empty_merkle_root_hash);
+                assert!(result.header.last_block_id.is_some());
+            },
+            "header_by_hash" => {
+                let result = endpoint::header_by_hash::Response::from_string(content).unwrap();
+                let header = result.header.unwrap();
+                assert_eq!(
+                    header.hash().to_string(),
+                    "FCF9C2537FC3534CA71001FE1F14C4F769090948C1A521682F612E7CF73AE639"
+                );
+                assert_eq!(header.height.value(), 10);
+            },
             "net_info" => {
                 let result = endpoint::net_info::Response::from_string(content).unwrap();
                 assert_eq!(result.listeners.len(), 1);
@@ -1105,10 +1185,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 2);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1138,10 +1220,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 2);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1172,10 +1256,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 2);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1205,10 +1291,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 2);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
@@ -1238,10 +1326,12 @@ fn incoming_fixtures() {
                     tendermint_rpc::event::DialectEvent::<RpcEvent>::from_string(content).unwrap();
                 let height;
                 if let tendermint_rpc::event::EventData::Tx { tx_result } = result.data.into() {
-                    height = tx_result.height;
-                    assert!(tx_result.result.log.is_none());
-                    assert!(tx_result.result.gas_wanted.is_none());
-                    assert!(tx_result.result.gas_used.is_none());
+                    height = tx_result.height.into();
+                    assert_eq!(tx_result.index, 0);
+                    assert!(tx_result.result.code.is_ok());
+                    assert!(tx_result.result.log.is_empty());
+                    assert_eq!(tx_result.result.gas_wanted, 0);
+                    assert_eq!(tx_result.result.gas_used, 0);
                     assert_eq!(tx_result.result.events.len(), 2);
                     assert_eq!(tx_result.result.events[0].kind, "app");
                     for attr in &tx_result.result.events[0].attributes {
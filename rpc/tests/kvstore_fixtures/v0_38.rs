@@ -0,0 +1,105 @@
+//! Responses of CometBFT 0.38+ nodes, parsed with the latest dialect.
+//!
+//! The fixtures are generated by the `cometbft` plan of `tools/rpc-probe`.
+//! Every fixture must be checked here, so that the responses whose format
+//! diverges from the one of the latest dialect get noticed when the
+//! fixtures are regenerated.
+
+use super::*;
+
+#[test]
+fn incoming_fixtures() {
+    use tendermint_rpc::dialect::v0_37::Event as RpcEvent;
+
+    for json_file in find_fixtures("v0_38", "incoming") {
+        let file_name = json_file
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .strip_suffix(".json")
+            .unwrap();
+        let content = fs::read_to_string(&json_file).unwrap();
+        match file_name {
+            "abci_info" => {
+                let result = endpoint::abci_info::Response::from_string(content).unwrap();
+                assert_eq!(result.response.app_version, 1);
+                assert_eq!(result.response.data, "{\"size\":9}");
+                assert_eq!(result.response.last_block_height.value(), 161);
+                assert_eq!(result.response.version, "1.0.0");
+            },
+            "block_results_at_height_10" => {
+                let result: endpoint::block_results::Response =
+                    endpoint::block_results::DialectResponse::<RpcEvent>::from_string(content)
+                        .unwrap()
+                        .into();
+                assert_eq!(result.height.value(), 10);
+                assert!(result.begin_block_events.is_none());
+                assert!(result.end_block_events.is_none());
+                let events = result.finalize_block_events.unwrap();
+                assert_eq!(events.len(), 1);
+                assert_eq!(events[0].kind, "finalize");
+                assert_eq!(events[0].attributes[0].key, "height");
+                assert_eq!(events[0].attributes[0].value, "10");
+                let txs_results = result.txs_results.unwrap();
+                assert_eq!(txs_results.len(), 1);
+                assert_eq!(txs_results[0].code, abci::Code::Ok);
+                assert_eq!(txs_results[0].events[0].attributes[1].value, "commit-key");
+                assert!(result.validator_updates.is_empty());
+                assert!(result.consensus_param_updates.is_none());
+            },
+            "broadcast_tx_commit" => {
+                let result: endpoint::broadcast::tx_commit::Response =
+                    endpoint::broadcast::tx_commit::DialectResponse::<RpcEvent>::from_string(
+                        content,
+                    )
+                    .unwrap()
+                    .into();
+                assert_eq!(result.check_tx.code, abci::Code::Ok);
+                assert!(result.check_tx.sender.is_empty());
+                assert_eq!(result.check_tx.priority, 0);
+                assert_eq!(result.deliver_tx.code, abci::Code::Ok);
+                assert_eq!(result.deliver_tx.events.len(), 1);
+                assert_eq!(result.deliver_tx.events[0].kind, "app");
+                assert_eq!(
+                    result.deliver_tx.events[0].attributes[1].value,
+                    "commit-key"
+                );
+                assert_eq!(
+                    result.hash,
+                    Hash::from_hex_upper(
+                        Algorithm::Sha256,
+                        "D63F9C23791E610410B576D8C27BB5AEAC93CC1A58522428A7B32A1276085860"
+                    )
+                    .unwrap()
+                );
+                assert_eq!(result.height.value(), 48);
+            },
+            "consensus_params" => {
+                let result = endpoint::consensus_params::Response::from_string(content).unwrap();
+                assert_eq!(u64::from(result.block_height), 10_u64);
+                assert_eq!(result.consensus_params.block.max_bytes, 22020096_u64);
+                assert_eq!(result.consensus_params.block.max_gas, -1_i64);
+                assert_eq!(
+                    result.consensus_params.evidence.max_age_duration,
+                    Duration(core::time::Duration::from_nanos(172800000000000_u64))
+                );
+                assert_eq!(
+                    result.consensus_params.validator.pub_key_types,
+                    vec![public_key::Algorithm::Ed25519]
+                );
+            },
+            "status" => {
+                let result = endpoint::status::Response::from_string(content).unwrap();
+                assert_eq!(result.node_info.version.to_string(), "0.38.0");
+                assert_eq!(result.node_info.network.to_string(), CHAIN_ID);
+                assert_eq!(result.node_info.protocol_version.block, 11);
+                assert!(!result.sync_info.catching_up);
+                assert_eq!(result.sync_info.latest_block_height.value(), 53);
+            },
+            _ => {
+                panic!("cannot parse file name: {file_name}");
+            },
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Compile-time checks of the `Send` and `Sync` guarantees of the clients.
+//!
+//! The clients, their drivers and the futures of their public async methods
+//! are all meant to be spawned onto multi-threaded runtimes (e.g. with
+//! `tokio::spawn`), so none of them may lose these bounds. The futures below
+//! are only created, never polled: these tests fail to build rather than to
+//! run.
+
+#![cfg(feature = "http-client")]
+
+use core::time::Duration;
+
+use tendermint_rpc::{
+    client::{
+        blocks::subscribe_full_blocks,
+        handover::HandoverClient,
+        layer::{ClientExt, Retry, Timeout},
+        power::{subscribe_power_changes, PowerChangeStream},
+        spill::{SpillDriver, SpillingSubscription},
+        sync::unbounded,
+        watch::{WatchList, WatchListConfig, WatchStream},
+        Fixture, RecordingClient,
+    },
+    query::{EventType, Query},
+    Client, HttpClient, Method, MockClient, MockRequestMethodMatcher, Subscription,
+    SubscriptionClient,
+};
+
+fn assert_send<T: Send>(_: T) {}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn mock_client() -> MockClient<MockRequestMethodMatcher> {
+    let matcher = MockRequestMethodMatcher::default().map(Method::Status, Ok(String::new()));
+    MockClient::new(matcher).0
+}
+
+/// The futures of the provided methods of [`Client`] are `Send` for every
+/// client which is `Sync`.
+fn client_futures<C: Client + Sync>(client: &C) {
+    assert_send(client.status());
+    assert_send(client.latest_block());
+    assert_send(client.abci_query(None, vec![], None, false));
+    assert_send(client.broadcast_tx_sync(vec![]));
+}
+
+/// The futures of the methods of the helpers built on subscriptions.
+fn helper_futures<C: SubscriptionClient + Send + Sync>(
+    mut watch_list: WatchList<C>,
+    driver: SpillDriver,
+) {
+    assert_send(watch_list.add("b"));
+    assert_send(watch_list.remove("a"));
+    assert_send(watch_list.close());
+    assert_send(driver.run());
+}
+
+fn subscription_client_futures<C: SubscriptionClient + Sync>(client: &C) {
+    assert_send(client.subscribe(EventType::NewBlock.into()));
+    assert_send(client.unsubscribe(EventType::NewBlock.into()));
+}
+
+#[test]
+fn clients_are_send_and_sync() {
+    assert_send_sync::<HttpClient>();
+    assert_send_sync::<MockClient<MockRequestMethodMatcher>>();
+    assert_send_sync::<HandoverClient<MockClient<MockRequestMethodMatcher>>>();
+    assert_send_sync::<RecordingClient<HttpClient>>();
+    assert_send_sync::<tendermint_rpc::client::layer::Layered<HttpClient, Retry>>();
+    assert_send_sync::<WatchList<MockClient<MockRequestMethodMatcher>>>();
+}
+
+#[test]
+fn streams_and_drivers_are_send() {
+    fn assert_send_type<T: Send>() {}
+
+    assert_send_type::<Subscription>();
+    assert_send_type::<SpillingSubscription>();
+    assert_send_type::<SpillDriver>();
+    assert_send_type::<WatchStream>();
+    assert_send_type::<PowerChangeStream>();
+    assert_send_type::<tendermint_rpc::client::MockClientDriver>();
+    assert_send_type::<tendermint_rpc::client::RecordingDriver>();
+    assert_send_type::<tendermint_rpc::client::handover::HandoverDriver>();
+}
+
+#[test]
+fn client_futures_are_send() {
+    let http = HttpClient::new("http://127.0.0.1:26657").unwrap();
+    client_futures(&http);
+    assert_send(http.batch().send());
+
+    let mock = mock_client();
+    client_futures(&mock);
+    subscription_client_futures(&mock);
+
+    let layered = mock_client().layer(Timeout::new(Duration::from_secs(1)));
+    client_futures(&layered);
+
+    let (handover, driver) = HandoverClient::new(mock_client());
+    client_futures(&handover);
+    subscription_client_futures(&handover);
+    assert_send(handover.swap(mock_client()));
+    assert_send(driver.run());
+
+    let (recording, driver) = RecordingClient::new(mock_client());
+    client_futures(&recording);
+    subscription_client_futures(&recording);
+    assert_send(driver.run());
+    assert_send(Fixture::default().replay_events(&mock));
+}
+
+#[test]
+fn helper_futures_are_send() {
+    let (mock, driver) = MockClient::new(MockRequestMethodMatcher::default());
+    assert_send(driver.run());
+
+    let base = Query::from(EventType::Tx);
+    let config = WatchListConfig::default();
+    assert_send(WatchList::new(
+        mock,
+        base,
+        "transfer.recipient",
+        ["a"],
+        config,
+    ));
+
+    let subscriber = mock_client();
+    assert_send(subscribe_full_blocks(&subscriber, mock_client()));
+    assert_send(subscribe_power_changes(&subscriber, mock_client()));
+
+    let (_tx, mut rx) = unbounded::<u64>();
+    assert_send(rx.recv());
+
+    let _ = helper_futures::<MockClient<MockRequestMethodMatcher>>;
+}
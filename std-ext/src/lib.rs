@@ -3,6 +3,8 @@
 //! [std]: https://doc.rust-lang.org/std/
 //! [tendermint-rs]: https://github.com/informalsystems/tendermint-rs/
 
+pub mod metrics;
+pub mod resolve;
 mod try_clone;
 
 pub use try_clone::TryClone;
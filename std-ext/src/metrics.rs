@@ -0,0 +1,158 @@
+//! Rendering of metrics in the Prometheus text exposition format, shared by
+//! the metrics of the RPC clients and of the light client.
+//!
+//! An [`Encoder`] writes the metric families one after the other: a family
+//! is started with its name, type and description by [`Encoder::family`],
+//! and is followed by its samples. The values of the labels are escaped as
+//! the format requires.
+//!
+//! The output is valid OpenMetrics text as well once terminated with
+//! [`Encoder::finish_openmetrics`].
+
+use std::fmt::{self, Display, Write};
+
+/// The type of a metric family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    /// A value which only goes up.
+    Counter,
+    /// A value which goes up and down.
+    Gauge,
+    /// A distribution of values in buckets.
+    Histogram,
+}
+
+impl MetricType {
+    /// The name of the type in the exposition formats.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+impl fmt::Display for MetricType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A histogram with fixed buckets.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    /// Create an empty histogram whose buckets have the given upper bounds,
+    /// in increasing order, the `+Inf` one excluded.
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Record a value.
+    pub fn observe(&mut self, value: f64) {
+        if let Some(i) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.buckets[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// The number of values recorded.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of the values recorded.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+}
+
+/// A writer of metrics in the Prometheus text exposition format.
+#[derive(Clone, Debug, Default)]
+pub struct Encoder {
+    out: String,
+}
+
+impl Encoder {
+    /// Create an encoder with nothing written yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the metric family of the given name, type and description.
+    pub fn family(&mut self, name: &str, metric_type: MetricType, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {}", escape_help(help));
+        let _ = writeln!(self.out, "# TYPE {name} {metric_type}");
+    }
+
+    /// Write a sample of the current family, with the given labels.
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{label}=\"{}\"", escape_label_value(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    /// Write the buckets, sum and count samples of a histogram of the current
+    /// family, with the given labels.
+    pub fn histogram(&mut self, name: &str, labels: &[(&str, &str)], histogram: &Histogram) {
+        let bucket = format!("{name}_bucket");
+        let bounds: Vec<String> = histogram.bounds.iter().map(ToString::to_string).collect();
+        let mut bucket_labels = labels.to_vec();
+        let mut cumulative = 0;
+        for (bound, count) in bounds.iter().zip(&histogram.buckets) {
+            cumulative += count;
+            bucket_labels.push(("le", bound.as_str()));
+            self.sample(&bucket, &bucket_labels, cumulative);
+            bucket_labels.pop();
+        }
+        bucket_labels.push(("le", "+Inf"));
+        self.sample(&bucket, &bucket_labels, histogram.count);
+        self.sample(&format!("{name}_sum"), labels, histogram.sum);
+        self.sample(&format!("{name}_count"), labels, histogram.count);
+    }
+
+    /// The metrics written, in the Prometheus text exposition format.
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    /// The metrics written, in the OpenMetrics text format.
+    pub fn finish_openmetrics(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+/// Escape a label value as per the exposition formats.
+pub fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
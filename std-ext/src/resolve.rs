@@ -0,0 +1,340 @@
+//! Resolution of host names into IP addresses, shared by the RPC clients and
+//! the P2P dialers.
+//!
+//! By default, a [`Resolver`] defers to the resolver of the operating system,
+//! just like [`ToSocketAddrs`] does. It can be configured with static
+//! overrides of the addresses of some hosts, which take precedence over any
+//! other source (as with `/etc/hosts`, but without having to edit it), with
+//! the name servers to query instead of those of the system, and with a cache
+//! of the resolved addresses.
+
+use std::{
+    collections::HashMap,
+    fmt, io,
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+    vec,
+};
+
+mod dns;
+
+/// Default time allowed to each name server to answer a query.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The port of the name servers given without one.
+pub const DNS_PORT: u16 = 53;
+
+/// The longest time for which addresses are cached, whatever the TTL of their
+/// records or the one given with [`Builder::cache_ttl`]: the largest TTL
+/// allowed by RFC 2181.
+const MAX_CACHE_TTL: Duration = Duration::from_secs(i32::MAX as u64);
+
+/// A resolver of host names.
+///
+/// Host names are resolved in the following order:
+///
+/// 1. IP addresses are returned as is.
+/// 2. The static overrides given with [`Builder::host`] or
+///    [`Builder::hosts_file`] are returned, if any.
+/// 3. The addresses in the cache are returned, if caching is enabled with
+///    [`Builder::cache_ttl`] and they have not expired.
+/// 4. The name servers given with [`Builder::name_server`] are queried one
+///    after the other for the `A` and `AAAA` records of the host, until one of
+///    them answers. If no name server is given, the resolver of the operating
+///    system is used instead.
+///
+/// Cloning a resolver is cheap, and the clones share the same cache.
+///
+/// ## Example
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// use tendermint_std_ext::resolve::Resolver;
+///
+/// let resolver = Resolver::builder()
+///     .host("validator-0.chain", [IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7))])
+///     .build();
+/// let addrs = resolver.resolve("validator-0.chain", 26656).unwrap();
+/// assert_eq!(addrs, vec!["10.0.0.7:26656".parse().unwrap()]);
+/// ```
+#[derive(Clone, Default)]
+pub struct Resolver {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    hosts: HashMap<String, Vec<IpAddr>>,
+    name_servers: Vec<SocketAddr>,
+    timeout: Option<Duration>,
+    cache_ttl: Option<Duration>,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+}
+
+struct CacheEntry {
+    addrs: Vec<IpAddr>,
+    expires_at: Instant,
+}
+
+impl Resolver {
+    /// Initiate a builder for a resolver, so that overrides, name servers and
+    /// caching can be configured.
+    #[must_use]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Resolve the given host name into its IP addresses.
+    ///
+    /// The host may be an IP address, possibly enclosed in square brackets
+    /// as IPv6 addresses are in URLs.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the host name cannot be resolved, in which case the kind of
+    /// the error is [`io::ErrorKind::NotFound`] if the host does not exist or
+    /// has no address.
+    pub fn lookup(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+
+        let name = normalize(host);
+        if let Some(addrs) = self.inner.hosts.get(&name) {
+            return Ok(addrs.clone());
+        }
+        if let Some(addrs) = self.cached(&name) {
+            return Ok(addrs);
+        }
+
+        let (addrs, ttl) = if self.inner.name_servers.is_empty() {
+            let addrs: Vec<_> = (host, 0).to_socket_addrs()?.map(|addr| addr.ip()).collect();
+            (addrs, self.inner.cache_ttl)
+        } else {
+            let answer = dns::query(
+                &self.inner.name_servers,
+                &name,
+                self.inner.timeout.unwrap_or(DEFAULT_TIMEOUT),
+            )?;
+            let ttl = self.inner.cache_ttl.map(|ttl| ttl.min(answer.ttl));
+            (answer.addrs, ttl)
+        };
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{host} has no address"),
+            ));
+        }
+
+        let expires_at = ttl
+            .filter(|ttl| !ttl.is_zero())
+            .and_then(|ttl| Instant::now().checked_add(ttl.min(MAX_CACHE_TTL)));
+        if let Some(expires_at) = expires_at {
+            self.inner.cache.lock().unwrap().insert(
+                name,
+                CacheEntry {
+                    addrs: addrs.clone(),
+                    expires_at,
+                },
+            );
+        }
+        Ok(addrs)
+    }
+
+    /// Resolve the given host name into the socket addresses with the given
+    /// port.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the host name cannot be resolved (see [`Resolver::lookup`]).
+    pub fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        Ok(self
+            .lookup(host)?
+            .into_iter()
+            .map(|ip| SocketAddr::new(ip, port))
+            .collect())
+    }
+
+    /// The given host and port, as an address which can be passed wherever
+    /// a [`ToSocketAddrs`] is expected to be resolved by this resolver, e.g.
+    /// to [`std::net::TcpStream::connect`].
+    #[must_use]
+    pub fn addr(&self, host: impl Into<String>, port: u16) -> ResolverAddr {
+        ResolverAddr {
+            resolver: self.clone(),
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Forget all the addresses in the cache.
+    pub fn clear_cache(&self) {
+        self.inner.cache.lock().unwrap().clear();
+    }
+
+    fn cached(&self, name: &str) -> Option<Vec<IpAddr>> {
+        self.inner.cache_ttl?;
+        let mut cache = self.inner.cache.lock().unwrap();
+        match cache.get(name) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.addrs.clone()),
+            Some(_) => {
+                cache.remove(name);
+                None
+            },
+            None => None,
+        }
+    }
+}
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolver")
+            .field("hosts", &self.inner.hosts)
+            .field("name_servers", &self.inner.name_servers)
+            .field("timeout", &self.inner.timeout)
+            .field("cache_ttl", &self.inner.cache_ttl)
+            .finish()
+    }
+}
+
+/// The builder pattern constructor for [`Resolver`].
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    hosts: HashMap<String, Vec<IpAddr>>,
+    name_servers: Vec<SocketAddr>,
+    timeout: Option<Duration>,
+    cache_ttl: Option<Duration>,
+}
+
+impl Builder {
+    /// Resolve the given host name into the given addresses, without
+    /// querying any name server.
+    ///
+    /// Host names are matched case-insensitively. Calling this again for the
+    /// same host adds to its addresses.
+    #[must_use]
+    pub fn host(mut self, host: &str, addrs: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.hosts.entry(normalize(host)).or_default().extend(addrs);
+        self
+    }
+
+    /// Add the static overrides of the given contents of a file in the
+    /// format of `/etc/hosts`, e.g. as mounted from a Kubernetes `ConfigMap`.
+    ///
+    /// Each line holds an IP address followed by the host names which
+    /// resolve to it. Comments start with `#`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a line does not start with a valid IP address, or does not
+    /// have any host name.
+    pub fn hosts_file(mut self, contents: &str) -> io::Result<Self> {
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+            let Some(ip) = fields.next() else {
+                continue;
+            };
+            let invalid = |reason: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid hosts file at line {}: {reason}", number + 1),
+                )
+            };
+            let ip: IpAddr = ip.parse().map_err(|_| invalid("invalid IP address"))?;
+            let mut hosts = fields.peekable();
+            if hosts.peek().is_none() {
+                return Err(invalid("missing host name"));
+            }
+            for host in hosts {
+                self = self.host(host, [ip]);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Query the given name server instead of the resolver of the operating
+    /// system. The port defaults to [`DNS_PORT`] if it is zero.
+    ///
+    /// Can be called several times to add several name servers, which are
+    /// queried in order until one of them answers. Host names are queried as
+    /// is: the search domains of the system are not applied to them, so the
+    /// names of Kubernetes services must be fully qualified, as in
+    /// `node.namespace.svc.cluster.local`.
+    #[must_use]
+    pub fn name_server(mut self, mut addr: SocketAddr) -> Self {
+        if addr.port() == 0 {
+            addr.set_port(DNS_PORT);
+        }
+        self.name_servers.push(addr);
+        self
+    }
+
+    /// Set the time allowed to each name server to answer a query (see
+    /// [`DEFAULT_TIMEOUT`]).
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Cache the resolved addresses for at most the given duration.
+    ///
+    /// The addresses given by name servers are cached for the TTL of their
+    /// records if it is shorter, while those given by the resolver of the
+    /// operating system, which does not report any TTL, are cached for the
+    /// given duration. Nothing is cached by default.
+    #[must_use]
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Create a resolver with the options specified for this builder.
+    #[must_use]
+    pub fn build(self) -> Resolver {
+        Resolver {
+            inner: Arc::new(Inner {
+                hosts: self.hosts,
+                name_servers: self.name_servers,
+                timeout: self.timeout,
+                cache_ttl: self.cache_ttl,
+                cache: Mutex::default(),
+            }),
+        }
+    }
+}
+
+/// A host and port to be resolved by a [`Resolver`], obtained from
+/// [`Resolver::addr`].
+#[derive(Clone, Debug)]
+pub struct ResolverAddr {
+    resolver: Resolver,
+    host: String,
+    port: u16,
+}
+
+impl ToSocketAddrs for ResolverAddr {
+    type Iter = vec::IntoIter<SocketAddr>;
+
+    fn to_socket_addrs(&self) -> io::Result<Self::Iter> {
+        self.resolver
+            .resolve(&self.host, self.port)
+            .map(Vec::into_iter)
+    }
+}
+
+impl fmt::Display for ResolverAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+// Fully qualified names are looked up like relative ones, and host names are
+// case-insensitive.
+fn normalize(host: &str) -> String {
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
@@ -0,0 +1,326 @@
+//! A minimal client of the DNS protocol (RFC 1035), querying the `A` and
+//! `AAAA` records of host names from recursive name servers.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io::{self, Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket},
+    time::{Duration, Instant, SystemTime},
+};
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_TRUNCATED: u16 = 0x0200;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+const RCODE_NXDOMAIN: u16 = 3;
+
+/// The maximum size of a response over UDP, beyond which it is truncated.
+const MAX_UDP_SIZE: usize = 512;
+
+/// The maximum number of pointers followed to read a name, so that a
+/// malicious response cannot have them loop.
+const MAX_NAME_POINTERS: usize = 16;
+
+/// The addresses of a host, as given by a name server.
+pub(super) struct Answer {
+    pub addrs: Vec<IpAddr>,
+    /// The shortest TTL of the records of the addresses.
+    pub ttl: Duration,
+}
+
+/// Query the addresses of the given host from the given name servers, one
+/// after the other until one of them answers.
+pub(super) fn query(servers: &[SocketAddr], name: &str, timeout: Duration) -> io::Result<Answer> {
+    let mut last_error = None;
+    for server in servers {
+        let answer = lookup(*server, name, TYPE_A, timeout).and_then(|v4| {
+            let v6 = lookup(*server, name, TYPE_AAAA, timeout)?;
+            Ok(Answer {
+                ttl: v4.ttl.min(v6.ttl),
+                addrs: v4.addrs.into_iter().chain(v6.addrs).collect(),
+            })
+        });
+        match answer {
+            Ok(answer) => return Ok(answer),
+            // Invalid host names are invalid for all name servers.
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput => return Err(e),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "no name server")))
+}
+
+fn lookup(server: SocketAddr, name: &str, qtype: u16, timeout: Duration) -> io::Result<Answer> {
+    let id = query_id();
+    let query = encode_query(id, name, qtype)?;
+
+    let response = exchange_udp(server, id, &query, timeout)?;
+    let flags = u16::from_be_bytes([response[2], response[3]]);
+    let response = if flags & FLAG_TRUNCATED != 0 {
+        exchange_tcp(server, &query, timeout)?
+    } else {
+        response
+    };
+    decode_response(id, name, qtype, &response)
+}
+
+fn exchange_udp(
+    server: SocketAddr,
+    id: u16,
+    query: &[u8],
+    timeout: Duration,
+) -> io::Result<Vec<u8>> {
+    let local: SocketAddr = if server.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.connect(server)?;
+    socket.send(query)?;
+
+    // Datagrams which are not the response to this query, e.g. late
+    // responses to previous queries, are ignored.
+    let deadline = Instant::now() + timeout;
+    let mut buf = [0_u8; MAX_UDP_SIZE];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(timed_out(server));
+        }
+        socket.set_read_timeout(Some(remaining))?;
+        let len = match socket.recv(&mut buf) {
+            Ok(len) => len,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Err(timed_out(server))
+            },
+            Err(e) => return Err(e),
+        };
+        if len >= 12 && buf[..2] == id.to_be_bytes() {
+            return Ok(buf[..len].to_vec());
+        }
+    }
+}
+
+fn exchange_tcp(server: SocketAddr, query: &[u8], timeout: Duration) -> io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&server, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    // Messages are prefixed with their length over TCP.
+    let mut message = (query.len() as u16).to_be_bytes().to_vec();
+    message.extend_from_slice(query);
+    stream.write_all(&message)?;
+
+    let mut len = [0_u8; 2];
+    stream.read_exact(&mut len)?;
+    let mut response = vec![0_u8; u16::from_be_bytes(len).into()];
+    stream.read_exact(&mut response)?;
+    Ok(response)
+}
+
+fn encode_query(id: u16, name: &str, qtype: u16) -> io::Result<Vec<u8>> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid host name: {name}"),
+        )
+    };
+    if name.is_empty() || name.len() > 253 {
+        return Err(invalid());
+    }
+
+    let mut query = Vec::with_capacity(18 + name.len());
+    // Header: ID, flags, and the counts of questions, answers, authority
+    // records and additional records.
+    query.extend_from_slice(&id.to_be_bytes());
+    query.extend_from_slice(&FLAG_RECURSION_DESIRED.to_be_bytes());
+    query.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid());
+        }
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&qtype.to_be_bytes());
+    query.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(query)
+}
+
+fn decode_response(id: u16, name: &str, qtype: u16, response: &[u8]) -> io::Result<Answer> {
+    let mut reader = Reader {
+        buf: response,
+        pos: 0,
+    };
+    if reader.u16()? != id {
+        return Err(malformed("mismatching ID"));
+    }
+    let flags = reader.u16()?;
+    if flags & FLAG_RESPONSE == 0 {
+        return Err(malformed("not a response"));
+    }
+    let no_answer = Answer {
+        addrs: Vec::new(),
+        ttl: Duration::MAX,
+    };
+    let rcode = flags & 0x000f;
+    if rcode != 0 && rcode != RCODE_NXDOMAIN {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("name server failed with response code {rcode}"),
+        ));
+    }
+
+    // The response must be to the question which was asked, so that the
+    // answer to another one cannot be passed off as the addresses of the host.
+    let questions = reader.u16()?;
+    let answers = reader.u16()?;
+    reader.skip(4)?;
+    if questions != 1 {
+        return Err(malformed("not a single question"));
+    }
+    let qname = reader.name()?;
+    if !qname.eq_ignore_ascii_case(name.trim_end_matches('.'))
+        || reader.u16()? != qtype
+        || reader.u16()? != CLASS_IN
+    {
+        return Err(malformed("mismatching question"));
+    }
+    if rcode == RCODE_NXDOMAIN {
+        return Ok(no_answer);
+    }
+
+    // Recursive name servers follow the aliases of the host, whose records
+    // come along with those of its addresses: only the latter are of interest.
+    let mut answer = no_answer;
+    for _ in 0..answers {
+        reader.skip_name()?;
+        let rtype = reader.u16()?;
+        let class = reader.u16()?;
+        let ttl = u32::from(reader.u16()?) << 16 | u32::from(reader.u16()?);
+        // TTLs with the most significant bit set are to be treated as zero
+        // (RFC 2181, section 8).
+        let ttl = if ttl > i32::MAX as u32 { 0 } else { ttl };
+        let len = usize::from(reader.u16()?);
+        let data = reader.take(len)?;
+        if rtype != qtype || class != CLASS_IN {
+            continue;
+        }
+        let ip = match (rtype, data.len()) {
+            (TYPE_A, 4) => IpAddr::from(<[u8; 4]>::try_from(data).unwrap()),
+            (TYPE_AAAA, 16) => IpAddr::from(<[u8; 16]>::try_from(data).unwrap()),
+            _ => return Err(malformed("invalid address record")),
+        };
+        answer.addrs.push(ip);
+        answer.ttl = answer.ttl.min(Duration::from_secs(ttl.into()));
+    }
+    Ok(answer)
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| malformed("truncated message"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> io::Result<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    // Reads a name, following the pointers to the rest of the name elsewhere
+    // in the message, and returns its labels separated by dots.
+    fn name(&mut self) -> io::Result<String> {
+        let mut name = String::new();
+        let mut reader = Reader {
+            buf: self.buf,
+            pos: self.pos,
+        };
+        // Where the name ends in this reader, i.e. after its first pointer.
+        let mut end = None;
+        let mut pointers = 0;
+        loop {
+            let len = reader.take(1)?[0];
+            match len {
+                0 => break,
+                len if len & 0xc0 == 0xc0 => {
+                    let offset = usize::from(len & 0x3f) << 8 | usize::from(reader.take(1)?[0]);
+                    end.get_or_insert(reader.pos);
+                    pointers += 1;
+                    if pointers > MAX_NAME_POINTERS {
+                        return Err(malformed("too many name pointers"));
+                    }
+                    reader.pos = offset;
+                },
+                len if len > 63 => return Err(malformed("invalid label length")),
+                len => {
+                    let label = reader.take(len.into())?;
+                    if !name.is_empty() {
+                        name.push('.');
+                    }
+                    name.push_str(&String::from_utf8_lossy(label));
+                },
+            }
+        }
+        self.pos = end.unwrap_or(reader.pos);
+        Ok(name)
+    }
+
+    // Names end with an empty label, or with a pointer to the rest of the
+    // name elsewhere in the message.
+    fn skip_name(&mut self) -> io::Result<()> {
+        loop {
+            let len = self.take(1)?[0];
+            match len {
+                0 => return Ok(()),
+                len if len & 0xc0 == 0xc0 => return self.skip(1),
+                len => self.skip(len.into())?,
+            }
+        }
+    }
+}
+
+fn query_id() -> u16 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(now) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+    hasher.finish() as u16
+}
+
+fn malformed(reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("malformed DNS response: {reason}"),
+    )
+}
+
+fn timed_out(server: SocketAddr) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("name server {server} did not answer in time"),
+    )
+}
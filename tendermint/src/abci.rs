@@ -47,7 +47,7 @@ pub mod types;
 pub use crate::v0_37::abci::request::Request;
 pub use crate::v0_37::abci::response::Response;
 
-pub use event::{Event, EventAttribute, EventAttributeIndexExt};
+pub use event::{Event, EventAttribute, EventAttributeIndexExt, TypedEvent};
 
 #[doc(inline)]
 pub use self::{
@@ -1,6 +1,8 @@
+use core::{fmt::Display, str::FromStr};
+
 use serde::Serialize;
 
-use crate::prelude::*;
+use crate::{alloc_prelude::*, Error};
 
 /// An event that occurred while processing a request.
 ///
@@ -55,6 +57,151 @@ impl Event {
     }
 }
 
+impl Event {
+    /// Returns the first attribute with the given key, if any.
+    pub fn attribute(&self, key: &str) -> Option<&EventAttribute> {
+        self.attributes.iter().find(|attr| attr.key == key)
+    }
+
+    /// Returns the value of the first attribute with the given key, if any.
+    pub fn attribute_value(&self, key: &str) -> Option<&str> {
+        self.attribute(key).map(|attr| attr.value.as_str())
+    }
+
+    /// Returns the values of all the attributes with the given key, in order.
+    pub fn attribute_values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |attr| attr.key == key)
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// Returns the value of the first attribute with the given key, failing
+    /// if the event has no such attribute.
+    pub fn required_attribute(&self, key: &str) -> Result<&str, Error> {
+        self.attribute_value(key)
+            .ok_or_else(|| Error::missing_event_attribute(self.composite_key(key)))
+    }
+
+    /// Parses the value of the first attribute with the given key.
+    pub fn parse_attribute<T>(&self, key: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse_value(key, self.required_attribute(key)?)
+    }
+
+    /// Parses the value of the first attribute with the given key, if the
+    /// event has such an attribute.
+    pub fn parse_optional_attribute<T>(&self, key: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.attribute_value(key)
+            .map(|value| self.parse_value(key, value))
+            .transpose()
+    }
+
+    fn parse_value<T>(&self, key: &str, value: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        value.parse().map_err(|e: T::Err| {
+            Error::invalid_event_attribute(self.composite_key(key), e.to_string())
+        })
+    }
+
+    /// Fails unless this event is of the given kind.
+    pub fn expect_kind(&self, kind: &str) -> Result<(), Error> {
+        if self.kind == kind {
+            Ok(())
+        } else {
+            Err(Error::unexpected_event_kind(kind.into(), self.kind.clone()))
+        }
+    }
+
+    /// Formats the composite key under which Tendermint indexes the attribute
+    /// with the given key, i.e. `{kind}.{key}`.
+    ///
+    /// This is the key to use in `tx_search` and `block_search` queries.
+    pub fn composite_key(&self, key: &str) -> String {
+        format!("{}.{}", self.kind, key)
+    }
+
+    /// Returns the composite keys and values of the attributes of this event
+    /// that are flagged for indexing.
+    pub fn indexed_attributes(&self) -> impl Iterator<Item = (String, &str)> + '_ {
+        self.attributes
+            .iter()
+            .filter(|attr| attr.index)
+            .map(move |attr| (self.composite_key(&attr.key), attr.value.as_str()))
+    }
+
+    /// Decodes this event into the typed event `T`.
+    pub fn decode<T: TypedEvent>(self) -> Result<T, Error> {
+        T::try_from(self)
+    }
+}
+
+/// An [`Event`] of a specific kind, with a known set of attributes.
+///
+/// Implementors decode the attributes of the generic [`Event`] in their
+/// `TryFrom` implementation, using the attribute helpers provided by [`Event`]
+/// instead of matching attribute keys and values by hand:
+///
+/// ```
+/// use tendermint::abci::{Event, TypedEvent};
+///
+/// struct Transfer {
+///     recipient: String,
+///     amount: u64,
+/// }
+///
+/// impl TypedEvent for Transfer {
+///     const KIND: &'static str = "transfer";
+/// }
+///
+/// impl TryFrom<Event> for Transfer {
+///     type Error = tendermint::Error;
+///
+///     fn try_from(event: Event) -> Result<Self, Self::Error> {
+///         event.expect_kind(Self::KIND)?;
+///         Ok(Self {
+///             recipient: event.required_attribute("recipient")?.into(),
+///             amount: event.parse_attribute("amount")?,
+///         })
+///     }
+/// }
+///
+/// let events = vec![
+///     Event::new("message", [("action", "send")]),
+///     Event::new("transfer", [("recipient", "alice"), ("amount", "10")]),
+/// ];
+/// let transfers = Transfer::decode_all(&events).unwrap();
+/// assert_eq!(transfers[0].recipient, "alice");
+/// assert_eq!(transfers[0].amount, 10);
+/// ```
+pub trait TypedEvent: TryFrom<Event, Error = Error> {
+    /// The [`Event::kind`] of the events this type decodes.
+    const KIND: &'static str;
+
+    /// Decodes all the events of kind [`Self::KIND`] in `events`, skipping
+    /// events of any other kind.
+    fn decode_all<'a, I>(events: I) -> Result<Vec<Self>, Error>
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        events
+            .into_iter()
+            .filter(|event| event.kind == Self::KIND)
+            .map(|event| Self::try_from(event.clone()))
+            .collect()
+    }
+}
+
 /// A key-value pair describing an [`Event`].
 ///
 /// Generic methods are provided for more ergonomic attribute construction, see
@@ -112,7 +259,7 @@ impl<K: Into<String>, V: Into<String>> EventAttributeIndexExt for (K, V) {
 }
 
 mod private {
-    use crate::prelude::*;
+    use crate::alloc_prelude::*;
 
     pub trait Sealed {}
 
@@ -125,13 +272,91 @@ impl<K: Into<String>, V: Into<String>> From<(K, V)> for EventAttribute {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Transfer {
+        recipient: String,
+        amount: u64,
+        memo: Option<String>,
+    }
+
+    impl TypedEvent for Transfer {
+        const KIND: &'static str = "transfer";
+    }
+
+    impl TryFrom<Event> for Transfer {
+        type Error = Error;
+
+        fn try_from(event: Event) -> Result<Self, Self::Error> {
+            event.expect_kind(Self::KIND)?;
+            Ok(Self {
+                recipient: event.required_attribute("recipient")?.into(),
+                amount: event.parse_attribute("amount")?,
+                memo: event.parse_optional_attribute("memo")?,
+            })
+        }
+    }
+
+    fn transfer(amount: &str) -> Event {
+        Event::new(
+            "transfer",
+            [
+                ("recipient", "alice").index(),
+                ("amount", amount).no_index(),
+            ],
+        )
+    }
+
+    #[test]
+    fn decodes_typed_events() {
+        let events = [Event::new("message", [("action", "send")]), transfer("10")];
+        let decoded = Transfer::decode_all(&events).unwrap();
+        assert_eq!(
+            decoded,
+            vec![Transfer {
+                recipient: "alice".into(),
+                amount: 10,
+                memo: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_decoding_errors() {
+        let err = transfer("ten").decode::<Transfer>().unwrap_err();
+        assert!(err.to_string().contains("transfer.amount"));
+
+        let err = Event::new("transfer", [("amount", "10")])
+            .decode::<Transfer>()
+            .unwrap_err();
+        assert!(err.to_string().contains("transfer.recipient"));
+
+        let err = Event::new("message", [("action", "send")])
+            .decode::<Transfer>()
+            .unwrap_err();
+        assert!(err.to_string().contains("unexpected event kind"));
+    }
+
+    #[test]
+    fn lists_indexed_attributes() {
+        let event = transfer("10");
+        assert_eq!(
+            event.indexed_attributes().collect::<Vec<_>>(),
+            vec![("transfer.recipient".to_string(), "alice")]
+        );
+    }
+}
+
 // =============================================================================
 // Protobuf conversions
 // =============================================================================
 
 mod v0_34 {
     use super::{Event, EventAttribute};
-    use crate::prelude::*;
+    use crate::alloc_prelude::*;
     use core::convert::{TryFrom, TryInto};
 
     use tendermint_proto::v0_34::abci as pb;
@@ -193,7 +418,7 @@ mod v0_34 {
 
 mod v0_37 {
     use super::{Event, EventAttribute};
-    use crate::prelude::*;
+    use crate::alloc_prelude::*;
     use core::convert::{TryFrom, TryInto};
 
     use tendermint_proto::v0_37::abci as pb;
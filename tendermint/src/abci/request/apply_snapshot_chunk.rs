@@ -3,7 +3,7 @@ use bytes::Bytes;
 // bring into scope for doc links
 #[allow(unused)]
 use super::{super::types::Snapshot, Info, LoadSnapshotChunk};
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 /// Applies a snapshot chunk.
 ///
@@ -3,9 +3,8 @@
 use super::DeliverTx;
 use crate::{
     abci::types::{CommitInfo, Misbehavior},
-    block,
-    prelude::*,
-    Hash,
+    alloc_prelude::*,
+    block, Hash,
 };
 
 #[doc = include_str!("../doc/request-beginblock.md")]
@@ -1,6 +1,6 @@
 use bytes::Bytes;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/request-delivertx.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/request-echo.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -1,6 +1,6 @@
 use bytes::Bytes;
 
-use crate::{block, consensus, prelude::*, validator, Time};
+use crate::{alloc_prelude::*, block, consensus, validator, Time};
 
 /// Called on genesis to initialize chain state.
 ///
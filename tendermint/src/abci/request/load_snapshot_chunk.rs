@@ -1,4 +1,4 @@
-use crate::{block, prelude::*};
+use crate::{alloc_prelude::*, block};
 
 #[doc = include_str!("../doc/request-loadsnapshotchunk.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
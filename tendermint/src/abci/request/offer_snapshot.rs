@@ -2,7 +2,7 @@ use super::super::types::Snapshot;
 // bring into scope for doc links
 #[allow(unused)]
 use super::ApplySnapshotChunk;
-use crate::{prelude::*, AppHash};
+use crate::{alloc_prelude::*, AppHash};
 
 #[doc = include_str!("../doc/request-offersnapshot.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 use crate::{
     abci::types::{CommitInfo, Misbehavior},
     account, block, Error, Hash, Time,
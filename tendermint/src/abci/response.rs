@@ -18,7 +18,7 @@
 // bring into scope for doc links
 #[allow(unused)]
 use super::types::Snapshot;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 mod apply_snapshot_chunk;
 mod begin_block;
@@ -1,5 +1,5 @@
 use super::super::Event;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-beginblock.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -2,7 +2,7 @@ use bytes::Bytes;
 use serde::Serialize;
 
 use super::super::{Code, Event};
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-checktx.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default, Serialize)]
@@ -1,6 +1,6 @@
 use bytes::Bytes;
 
-use crate::{block, prelude::*};
+use crate::{alloc_prelude::*, block};
 
 #[doc = include_str!("../doc/response-commit.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -1,5 +1,5 @@
 use super::super::Event;
-use crate::{consensus, prelude::*, validator};
+use crate::{alloc_prelude::*, consensus, validator};
 
 #[doc = include_str!("../doc/response-endblock.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
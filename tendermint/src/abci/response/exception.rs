@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-exception.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
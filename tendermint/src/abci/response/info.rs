@@ -1,4 +1,4 @@
-use crate::{block, prelude::*, AppHash};
+use crate::{alloc_prelude::*, block, AppHash};
 use tendermint_proto::v0_37::abci as pb;
 
 use serde::{Deserialize, Serialize};
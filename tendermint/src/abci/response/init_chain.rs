@@ -1,6 +1,6 @@
 use crate::AppHash;
 
-use crate::{consensus, prelude::*, validator};
+use crate::{alloc_prelude::*, consensus, validator};
 
 #[doc = include_str!("../doc/response-initchain.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -1,5 +1,5 @@
 use super::super::types::Snapshot;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-listsnapshots.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
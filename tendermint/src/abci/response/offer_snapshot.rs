@@ -1,7 +1,7 @@
 // bring into scope for doc links
 #[allow(unused)]
 use super::super::types::Snapshot;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-offersnapshot.md")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -1,6 +1,6 @@
 use bytes::Bytes;
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-prepareproposal.md")]
 #[derive(Clone, Debug, PartialEq, Eq)]
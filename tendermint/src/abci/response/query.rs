@@ -2,7 +2,7 @@ use bytes::Bytes;
 
 /// XXX(hdevalence): hide merkle::proof and re-export its contents from merkle?
 use crate::merkle::proof as merkle;
-use crate::{abci::Code, block, prelude::*};
+use crate::{abci::Code, alloc_prelude::*, block};
 
 #[doc = include_str!("../doc/response-query.md")]
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
@@ -1,5 +1,5 @@
 use crate::abci::Code;
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 #[doc = include_str!("../doc/response-setoption.md")]
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -7,7 +7,7 @@
 
 use bytes::Bytes;
 
-use crate::{block, prelude::*, vote, Time};
+use crate::{alloc_prelude::*, block, vote, Time};
 
 /// A validator address with voting power.
 ///
@@ -120,7 +120,7 @@ pub struct Snapshot {
 
 mod v0_34 {
     use super::{CommitInfo, Misbehavior, MisbehaviorKind, Snapshot, Validator, VoteInfo};
-    use crate::{prelude::*, Error};
+    use crate::{alloc_prelude::*, Error};
     use tendermint_proto::v0_34::abci as pb;
     use tendermint_proto::Protobuf;
 
@@ -279,7 +279,7 @@ mod v0_34 {
 
 mod v0_37 {
     use super::{CommitInfo, Misbehavior, MisbehaviorKind, Snapshot, Validator, VoteInfo};
-    use crate::{prelude::*, Error};
+    use crate::{alloc_prelude::*, Error};
     use tendermint_proto::v0_37::abci as pb;
     use tendermint_proto::Protobuf;
 
@@ -9,11 +9,13 @@ use core::{
 use bytes::Bytes;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use subtle::{self, ConstantTimeEq};
+#[cfg(feature = "bech32")]
+use subtle_encoding::bech32;
 use subtle_encoding::hex;
 
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Size of an  account ID in bytes
 pub const LENGTH: usize = 20;
@@ -72,6 +74,49 @@ impl Id {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0[..]
     }
+
+    /// Encode the account ID as a bech32 address with the given
+    /// human-readable part, e.g. `cosmos` or `cosmosvaloper`.
+    #[cfg(feature = "bech32")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bech32")))]
+    pub fn to_bech32(&self, hrp: &str) -> Result<String, Error> {
+        validate_bech32_hrp(hrp)?;
+        Ok(bech32::encode(hrp, self.as_bytes()))
+    }
+
+    /// Decode an account ID from a bech32 address, which must have the given
+    /// human-readable part.
+    #[cfg(feature = "bech32")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bech32")))]
+    pub fn from_bech32(encoded: &str, hrp: &str) -> Result<Self, Error> {
+        validate_bech32_hrp(hrp)?;
+        let (actual, bytes) = bech32::decode(encoded).map_err(Error::subtle_encoding)?;
+        if actual != hrp {
+            return Err(Error::bech32_hrp_mismatch(hrp.into(), actual));
+        }
+        bytes.try_into()
+    }
+}
+
+/// Maximum length of the human-readable part of a bech32 string, as per
+/// [BIP-173](https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki).
+#[cfg(feature = "bech32")]
+const MAX_BECH32_HRP_LENGTH: usize = 83;
+
+/// Checks that the human-readable part of a bech32 string is non-empty, not
+/// too long, and only made of lowercase printable ASCII characters.
+#[cfg(feature = "bech32")]
+fn validate_bech32_hrp(hrp: &str) -> Result<(), Error> {
+    let valid = !hrp.is_empty()
+        && hrp.len() <= MAX_BECH32_HRP_LENGTH
+        && hrp
+            .bytes()
+            .all(|c| (33..=126).contains(&c) && !c.is_ascii_uppercase());
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::invalid_bech32_hrp(hrp.into()))
+    }
 }
 
 impl AsRef<[u8]> for Id {
@@ -184,6 +229,37 @@ impl Serialize for Id {
     }
 }
 
+#[cfg(all(test, feature = "bech32"))]
+mod bech32_tests {
+    use super::*;
+
+    const ID_HEX: &str = "0CDA3F47EF3C4906693B170EF650EB968C5F4B2C";
+
+    #[test]
+    fn bech32_roundtrip() {
+        let id = Id::from_str(ID_HEX).unwrap();
+        let encoded = id.to_bech32("cosmos").unwrap();
+        assert!(encoded.starts_with("cosmos1"));
+        assert_eq!(Id::from_bech32(&encoded, "cosmos").unwrap(), id);
+    }
+
+    #[test]
+    fn bech32_hrp_validation() {
+        let id = Id::from_str(ID_HEX).unwrap();
+        assert!(id.to_bech32("").is_err());
+        assert!(id.to_bech32("Cosmos").is_err());
+        assert!(id.to_bech32("cos mos").is_err());
+        assert!(id.to_bech32(&"a".repeat(84)).is_err());
+
+        let encoded = id.to_bech32("cosmosvaloper").unwrap();
+        assert!(Id::from_bech32(&encoded, "cosmos").is_err());
+        assert!(Id::from_bech32("cosmos1qqqqqqqq", "cosmos").is_err());
+
+        let short = bech32::encode("cosmos", [0_u8; 10]);
+        assert!(Id::from_bech32(&short, "cosmos").is_err());
+    }
+}
+
 #[cfg(all(test, feature = "rust-crypto"))]
 mod tests {
     use super::*;
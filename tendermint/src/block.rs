@@ -5,11 +5,13 @@ pub mod commit_sig;
 pub mod header;
 mod height;
 mod id;
+pub mod limits;
 mod meta;
 pub mod parts;
 mod round;
 pub mod signed_header;
 mod size;
+pub mod skew;
 
 use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::Block as RawBlock;
@@ -24,7 +26,7 @@ pub use self::{
     round::*,
     size::Size,
 };
-use crate::{error::Error, evidence, prelude::*};
+use crate::{alloc_prelude::*, error::Error, evidence};
 
 /// Blocks consist of a header, transactions, votes (the commit), and a list of
 /// evidence of malfeasance (i.e. signing conflicting votes).
@@ -50,7 +52,7 @@ pub struct Block {
 
 tendermint_pb_modules! {
     use super::{Block, Header, Commit};
-    use crate::{Error, prelude::*};
+    use crate::{Error, alloc_prelude::*};
     use pb::types::Block as RawBlock;
 
     impl Protobuf<RawBlock> for Block {}
@@ -146,4 +148,10 @@ impl Block {
     pub fn last_commit(&self) -> &Option<Commit> {
         &self.last_commit
     }
+
+    /// Get the size of the Protobuf encoding of the block, which is limited
+    /// by the `block.max_bytes` consensus parameter (see [`limits`]).
+    pub fn size(&self) -> u64 {
+        tendermint_proto::Protobuf::<RawBlock>::encoded_len(self) as u64
+    }
 }
@@ -4,8 +4,8 @@ use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::Commit as RawCommit;
 
 use crate::{
+    alloc_prelude::*,
     block::{commit_sig::CommitSig, Height, Id, Round},
-    prelude::*,
 };
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
@@ -34,7 +34,7 @@ tendermint_pb_modules! {
     use crate::{
         block::commit_sig::CommitSig,
         error::Error,
-        prelude::*,
+        alloc_prelude::*,
     };
     use pb::types::Commit as RawCommit;
 
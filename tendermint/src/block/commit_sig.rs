@@ -1,6 +1,6 @@
 //! CommitSig within Commit
 
-use crate::{account, prelude::*, Signature, Time};
+use crate::{account, alloc_prelude::*, Signature, Time};
 
 /// CommitSig represents a signature of a validator.
 /// It's a part of the Commit and can be used to reconstruct the vote set given the validator set.
@@ -42,6 +42,15 @@ impl CommitSig {
         }
     }
 
+    /// Get the timestamp of the vote of this validator if a vote was received.
+    pub fn timestamp(&self) -> Option<Time> {
+        match self {
+            Self::BlockIdFlagCommit { timestamp, .. } => Some(*timestamp),
+            Self::BlockIdFlagNil { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        }
+    }
+
     /// Whether this signature is absent (no vote was received from validator)
     pub fn is_absent(&self) -> bool {
         self == &Self::BlockIdFlagAbsent
@@ -60,7 +69,7 @@ impl CommitSig {
 
 tendermint_pb_modules! {
     use super::CommitSig;
-    use crate::{error::Error, prelude::*, Signature};
+    use crate::{error::Error, alloc_prelude::*, Signature};
     use num_traits::ToPrimitive;
     use pb::types::{BlockIdFlag, CommitSig as RawCommitSig};
 
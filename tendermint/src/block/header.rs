@@ -8,10 +8,11 @@ use tendermint_proto::v0_37::{
 use tendermint_proto::Protobuf;
 
 use crate::{
-    account, block, chain,
+    account,
+    alloc_prelude::*,
+    block, chain,
     crypto::Sha256,
     merkle::{self, MerkleHash},
-    prelude::*,
     AppHash, Hash, Time,
 };
 
@@ -7,7 +7,7 @@ use core::{
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Block height for a particular chain (i.e. number of blocks created since
 /// the chain began)
@@ -7,10 +7,10 @@ use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::BlockId as RawBlockId;
 
 use crate::{
+    alloc_prelude::*,
     block::parts::Header as PartSetHeader,
     error::Error,
     hash::{Algorithm, Hash},
-    prelude::*,
 };
 
 /// Length of a block ID prefix displayed for debugging purposes
@@ -63,7 +63,7 @@ tendermint_pb_modules! {
         }
     };
     use super::Id;
-    use crate::{prelude::*, Error};
+    use crate::{alloc_prelude::*, Error};
 
     impl Protobuf<RawBlockId> for Id {}
 
@@ -133,6 +133,22 @@ tendermint_pb_modules! {
 }
 
 impl Id {
+    /// Whether this is the empty block ID, which stands for no block, e.g.
+    /// in the votes for nil.
+    pub fn is_zero(&self) -> bool {
+        self == &Id::default()
+    }
+
+    /// Whether this block ID identifies a block: it has the hash of a header,
+    /// and the hash of a part set of at least one part.
+    ///
+    /// A block ID which is neither complete nor empty is malformed.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.hash, Hash::Sha256(_))
+            && self.part_set_header.total > 0
+            && matches!(self.part_set_header.hash, Hash::Sha256(_))
+    }
+
     /// Get a shortened 12-character prefix of a block ID (ala git)
     pub fn prefix(&self) -> String {
         let mut result = self.to_string();
@@ -0,0 +1,194 @@
+//! Accounting of the size of blocks against the `block.max_bytes` consensus
+//! parameter, the way the Go implementation does it.
+//!
+//! The size of a block is the size of its Protobuf encoding. As the header,
+//! the last commit and the evidence of a proposal take some of it, only
+//! [`max_data_bytes`] are left for the transactions, whose size is in turn
+//! accounted as in the `txs` field of the Protobuf `Data` message (see
+//! [`txs_size`]). [`DataLimit`] packs both for applications selecting
+//! transactions in `PrepareProposal`:
+//!
+//! ```
+//! use tendermint::block::limits::{DataLimit, TxSet};
+//!
+//! let limit = DataLimit::new(1024);
+//! let txs = limit.select(vec![vec![0; 600], vec![0; 600], vec![0; 300]]);
+//! assert_eq!(txs.len(), 2);
+//! assert!(txs.fits_in(limit));
+//! ```
+
+use prost::{encoding::encoded_len_varint, Message};
+use tendermint_proto::v0_37::types::EvidenceList as RawEvidenceList;
+
+use crate::{alloc_prelude::*, consensus, evidence};
+
+/// The maximum size of an encoded header (`MaxHeaderBytes`).
+pub const MAX_HEADER_BYTES: u64 = 626;
+
+/// The maximum size of the fields of a block other than the header, the
+/// transactions, the evidence and the last commit (`MaxOverheadForBlock`).
+pub const MAX_OVERHEAD_FOR_BLOCK: u64 = 11;
+
+/// The maximum size of an encoded commit without signatures
+/// (`MaxCommitOverheadBytes`).
+pub const MAX_COMMIT_OVERHEAD_BYTES: u64 = 94;
+
+/// The maximum size of an encoded commit signature (`MaxCommitSigBytes`).
+pub const MAX_COMMIT_SIG_BYTES: u64 = 109;
+
+/// The encoding overhead of each signature in the repeated field of a commit.
+const COMMIT_SIG_ENCODING_OVERHEAD: u64 = 2;
+
+/// The maximum size of an encoded commit signed by the given number of
+/// validators (`MaxCommitBytes`).
+pub fn max_commit_bytes(vals_count: usize) -> u64 {
+    MAX_COMMIT_OVERHEAD_BYTES
+        + (MAX_COMMIT_SIG_BYTES + COMMIT_SIG_ENCODING_OVERHEAD) * vals_count as u64
+}
+
+/// The size left for the transactions of a block of at most `max_bytes`,
+/// holding `evidence_bytes` of evidence and the commit of the given number
+/// of validators (`MaxDataBytes`).
+///
+/// Returns `None` if `max_bytes` is too small to hold anything else.
+pub fn max_data_bytes(max_bytes: u64, evidence_bytes: u64, vals_count: usize) -> Option<u64> {
+    max_bytes
+        .checked_sub(MAX_OVERHEAD_FOR_BLOCK)?
+        .checked_sub(MAX_HEADER_BYTES)?
+        .checked_sub(max_commit_bytes(vals_count))?
+        .checked_sub(evidence_bytes)
+}
+
+/// The size left for the transactions of a block of at most `max_bytes`
+/// without evidence, holding the commit of the given number of validators
+/// (`MaxDataBytesNoEvidence`).
+///
+/// Returns `None` if `max_bytes` is too small to hold anything else.
+pub fn max_data_bytes_no_evidence(max_bytes: u64, vals_count: usize) -> Option<u64> {
+    max_data_bytes(max_bytes, 0, vals_count)
+}
+
+/// The size accounted for the given transaction in a block.
+pub fn tx_size(tx: &[u8]) -> u64 {
+    // The tag of the `txs` field, the length of the transaction and the
+    // transaction itself.
+    (1 + encoded_len_varint(tx.len() as u64) + tx.len()) as u64
+}
+
+/// The size accounted for the given transactions in a block
+/// (`ComputeProtoSizeForTxs`).
+pub fn txs_size<T: AsRef<[u8]>>(txs: impl IntoIterator<Item = T>) -> u64 {
+    txs.into_iter().map(|tx| tx_size(tx.as_ref())).sum()
+}
+
+/// The size accounted for the given evidence in a block
+/// (`EvidenceList.ByteSize`).
+pub fn evidence_size(evidence: &evidence::Data) -> u64 {
+    RawEvidenceList::from(evidence.clone()).encoded_len() as u64
+}
+
+/// The size available to the transactions of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DataLimit {
+    max_bytes: u64,
+}
+
+impl DataLimit {
+    /// A limit of the given size, e.g. the `max_tx_bytes` of a
+    /// `PrepareProposal` request.
+    pub const fn new(max_bytes: u64) -> Self {
+        Self { max_bytes }
+    }
+
+    /// The limit of the transactions of a block under the given consensus
+    /// parameters, holding `evidence_bytes` of evidence and the commit of the
+    /// given number of validators.
+    ///
+    /// Returns `None` if the blocks are too small to hold anything else.
+    pub fn from_params(
+        params: &consensus::Params,
+        evidence_bytes: u64,
+        vals_count: usize,
+    ) -> Option<Self> {
+        max_data_bytes(params.block.max_bytes, evidence_bytes, vals_count).map(Self::new)
+    }
+
+    /// The size available to the transactions.
+    pub const fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Select the transactions which fit under the limit, in order, skipping
+    /// those which would exceed it.
+    pub fn select<T: AsRef<[u8]>>(&self, txs: impl IntoIterator<Item = T>) -> Vec<T> {
+        let mut remaining = self.max_bytes;
+        txs.into_iter()
+            .filter(|tx| match remaining.checked_sub(tx_size(tx.as_ref())) {
+                Some(left) => {
+                    remaining = left;
+                    true
+                },
+                None => false,
+            })
+            .collect()
+    }
+}
+
+/// Sets of transactions whose size is accounted as in a block.
+pub trait TxSet {
+    /// The size accounted for the transactions in a block.
+    fn size(&self) -> u64;
+
+    /// Whether the transactions fit under the given limit.
+    fn fits_in(&self, limit: DataLimit) -> bool {
+        self.size() <= limit.max_bytes()
+    }
+}
+
+impl<T: AsRef<[u8]>> TxSet for [T] {
+    fn size(&self) -> u64 {
+        txs_size(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_data_bytes_matches_go() {
+        // The test cases of `TestBlockMaxDataBytes` in the Go implementation.
+        assert_eq!(max_data_bytes(10, 0, 1), None);
+        assert_eq!(max_data_bytes(841, 0, 1), None);
+        assert_eq!(max_data_bytes(842, 0, 1), Some(0));
+        assert_eq!(max_data_bytes(843, 0, 1), Some(1));
+        assert_eq!(max_data_bytes(954, 0, 2), Some(1));
+        assert_eq!(max_data_bytes(1053, 100, 2), Some(0));
+        assert_eq!(max_data_bytes_no_evidence(843, 1), Some(1));
+    }
+
+    #[test]
+    fn tx_sizes_match_data_encoding() {
+        use tendermint_proto::v0_37::types::Data as RawData;
+
+        let txs = vec![
+            vec![],
+            vec![1; 10],
+            vec![2; 127],
+            vec![3; 128],
+            vec![4; 20_000],
+        ];
+        let raw = RawData { txs: txs.clone() };
+        assert_eq!(txs.size(), raw.encoded_len() as u64);
+        assert_eq!(tx_size(&[3; 128]), 131);
+    }
+
+    #[test]
+    fn selects_fitting_transactions() {
+        let limit = DataLimit::new(100);
+        let txs = limit.select([&[0; 60][..], &[1; 50], &[2; 30], &[3; 1]]);
+        assert_eq!(txs, [&[0; 60][..], &[2; 30], &[3; 1]]);
+        assert!(txs.fits_in(limit));
+        assert!(!vec![[0; 99]].fits_in(limit));
+    }
+}
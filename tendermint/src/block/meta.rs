@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::BlockMeta as RawMeta;
 
 use super::{Header, Id};
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 /// Block metadata - Todo: implement constructor and getters
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -25,7 +25,7 @@ pub struct Meta {
 
 tendermint_pb_modules! {
     use super::Meta;
-    use crate::{error::Error, prelude::*};
+    use crate::{error::Error, alloc_prelude::*};
     use pb::types::BlockMeta as RawMeta;
 
     impl TryFrom<RawMeta> for Meta {
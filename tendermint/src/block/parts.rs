@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::PartSetHeader as RawPartSetHeader;
 
-use crate::{error::Error, prelude::*, Hash};
+use crate::{alloc_prelude::*, error::Error, Hash};
 
 /// Block parts header
 #[derive(
@@ -26,7 +26,7 @@ tendermint_pb_modules! {
     use crate::{
         error::Error,
         hash::{Algorithm, SHA256_HASH_SIZE},
-        prelude::*,
+        alloc_prelude::*,
         Hash,
     };
     use super::Header;
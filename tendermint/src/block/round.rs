@@ -6,7 +6,7 @@ use core::{
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Block round for a particular chain
 #[derive(Copy, Clone, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
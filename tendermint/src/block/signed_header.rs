@@ -5,7 +5,13 @@
 use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::SignedHeader as RawSignedHeader;
 
-use crate::{block, Error};
+use crate::{
+    block,
+    crypto::Sha256,
+    hash::{Hash, HashCache},
+    merkle::MerkleHash,
+    Error,
+};
 
 /// Signed block headers
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,6 +22,8 @@ pub struct SignedHeader {
     pub header: block::Header,
     /// Commit containing signatures for the header
     pub commit: block::Commit,
+    /// Cached hash of `header`
+    header_hash: HashCache<block::Header>,
 }
 
 tendermint_pb_modules! {
@@ -57,7 +65,11 @@ impl SignedHeader {
         if header.height != commit.height {
             return Err(Error::invalid_signed_header());
         }
-        Ok(Self { header, commit })
+        Ok(Self {
+            header,
+            commit,
+            header_hash: HashCache::default(),
+        })
     }
 
     /// Get header
@@ -69,4 +81,25 @@ impl SignedHeader {
     pub fn commit(&self) -> &block::Commit {
         &self.commit
     }
+
+    /// Compute the hash of the header.
+    ///
+    /// See [`SignedHeader::header_hash_with`].
+    #[cfg(feature = "rust-crypto")]
+    pub fn header_hash(&self) -> Hash {
+        self.header_hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Hash the header with a Merkle hasher provided by a crypto provider.
+    ///
+    /// Unlike [`block::Header::hash_with`], the hash is computed on first use
+    /// and cached for subsequent calls. It is recomputed if the header has
+    /// been modified since.
+    pub fn header_hash_with<H>(&self) -> Hash
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        self.header_hash
+            .get_or_compute(&self.header, || self.header.hash_with::<H>())
+    }
 }
@@ -0,0 +1,285 @@
+//! Deviation of the timestamps of the signatures of commits from the block
+//! time, to spot validators whose clock is off.
+//!
+//! The timestamps of the signatures of the commit for a block are aggregated
+//! into the time of the *next* block (the weighted median of the timestamps,
+//! see [BFT time]), which is thus the natural reference to compare them to.
+//!
+//! [BFT time]: https://github.com/tendermint/tendermint/blob/v0.34.x/spec/consensus/bft-time.md
+
+use alloc::collections::BTreeMap;
+use core::{fmt, time::Duration};
+
+use crate::{account, alloc_prelude::*, block::Commit, Time};
+
+/// How far a timestamp is from a reference time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Skew {
+    /// The timestamp is before the reference time.
+    Behind(Duration),
+    /// The timestamp is after (or at) the reference time.
+    Ahead(Duration),
+}
+
+impl Skew {
+    /// The skew of `timestamp` relative to `reference`.
+    pub fn between(timestamp: Time, reference: Time) -> Self {
+        Self::from_nanos(timestamp.unix_timestamp_nanos() - reference.unix_timestamp_nanos())
+    }
+
+    /// The skew for a signed number of nanoseconds, negative when behind.
+    fn from_nanos(nanos: i128) -> Self {
+        let duration = Duration::from_nanos(nanos.unsigned_abs().try_into().unwrap_or(u64::MAX));
+        if nanos < 0 {
+            Self::Behind(duration)
+        } else {
+            Self::Ahead(duration)
+        }
+    }
+
+    /// The skew as a signed number of nanoseconds, negative when behind.
+    fn as_nanos(&self) -> i128 {
+        match self {
+            Self::Behind(duration) => -(duration.as_nanos() as i128),
+            Self::Ahead(duration) => duration.as_nanos() as i128,
+        }
+    }
+
+    /// The magnitude of the skew, whatever its direction.
+    pub fn abs(&self) -> Duration {
+        match self {
+            Self::Behind(duration) | Self::Ahead(duration) => *duration,
+        }
+    }
+}
+
+impl fmt::Display for Skew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Behind(duration) => write!(f, "-{duration:?}"),
+            Self::Ahead(duration) => write!(f, "+{duration:?}"),
+        }
+    }
+}
+
+/// The skew of the timestamp of the signature of a validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureSkew {
+    /// The address of the validator.
+    pub validator_address: account::Id,
+    /// The skew of the timestamp of its signature.
+    pub skew: Skew,
+}
+
+/// The skews of the timestamps of all the signatures of a commit (whether
+/// for the block or for nil) relative to the given block time, in the order
+/// of the signatures.
+pub fn commit_skews(commit: &Commit, block_time: Time) -> Vec<SignatureSkew> {
+    commit
+        .signatures
+        .iter()
+        .filter_map(|sig| Some((sig.validator_address()?, sig.timestamp()?)))
+        .map(|(validator_address, timestamp)| SignatureSkew {
+            validator_address,
+            skew: Skew::between(timestamp, block_time),
+        })
+        .collect()
+}
+
+/// Statistics on the skews of the signatures of a validator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkewStats {
+    /// The number of signatures.
+    pub signatures: u64,
+    /// The number of signatures skewed by more than the tolerance of the
+    /// analysis.
+    pub beyond_tolerance: u64,
+    /// The average skew of the signatures.
+    pub mean: Skew,
+    /// The largest skew of the signatures, in either direction.
+    pub max: Skew,
+    total_nanos: i128,
+}
+
+impl SkewStats {
+    fn new(skew: Skew) -> Self {
+        Self {
+            signatures: 0,
+            beyond_tolerance: 0,
+            mean: skew,
+            max: skew,
+            total_nanos: 0,
+        }
+    }
+
+    fn record(&mut self, skew: Skew, tolerance: Duration) {
+        self.signatures += 1;
+        if skew.abs() > tolerance {
+            self.beyond_tolerance += 1;
+        }
+        if skew.abs() > self.max.abs() {
+            self.max = skew;
+        }
+        self.total_nanos += skew.as_nanos();
+        self.mean = Skew::from_nanos(self.total_nanos / i128::from(self.signatures));
+    }
+}
+
+/// Analysis of the skews of the signatures of the validators over a range
+/// of commits.
+///
+/// ```
+/// use core::time::Duration;
+/// use tendermint::block::skew::SkewAnalysis;
+/// # fn blocks() -> Vec<(tendermint::block::Commit, tendermint::Time)> { vec![] }
+///
+/// let mut analysis = SkewAnalysis::new(Duration::from_millis(500));
+/// // The commits, along with the time of the blocks they are the last commit of.
+/// for (commit, next_block_time) in blocks() {
+///     analysis.record(&commit, next_block_time);
+/// }
+/// // The validators whose signatures are skewed by more than 500ms at least
+/// // half of the time.
+/// for validator in analysis.chronically_skewed(50) {
+///     println!("{validator}: {:?}", analysis.validators()[&validator]);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SkewAnalysis {
+    tolerance: Duration,
+    commits: u64,
+    validators: BTreeMap<account::Id, SkewStats>,
+}
+
+impl SkewAnalysis {
+    /// Start an analysis counting the signatures skewed by more than the
+    /// given tolerance.
+    pub fn new(tolerance: Duration) -> Self {
+        Self {
+            tolerance,
+            commits: 0,
+            validators: BTreeMap::new(),
+        }
+    }
+
+    /// Record the skews of the signatures of a commit relative to the given
+    /// block time.
+    pub fn record(&mut self, commit: &Commit, block_time: Time) {
+        self.commits += 1;
+        for SignatureSkew {
+            validator_address,
+            skew,
+        } in commit_skews(commit, block_time)
+        {
+            self.validators
+                .entry(validator_address)
+                .or_insert_with(|| SkewStats::new(skew))
+                .record(skew, self.tolerance);
+        }
+    }
+
+    /// The number of commits recorded.
+    pub fn commits(&self) -> u64 {
+        self.commits
+    }
+
+    /// The statistics of each validator which signed any of the commits.
+    pub fn validators(&self) -> &BTreeMap<account::Id, SkewStats> {
+        &self.validators
+    }
+
+    /// The validators for which at least the given percentage of the
+    /// signatures are skewed by more than the tolerance.
+    pub fn chronically_skewed(&self, min_percent: u64) -> Vec<account::Id> {
+        self.validators
+            .iter()
+            .filter(|(_, stats)| stats.beyond_tolerance * 100 >= stats.signatures * min_percent)
+            .map(|(address, _)| *address)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{CommitSig, Height, Id, Round};
+
+    fn commit(timestamps: &[(u8, Option<i64>)]) -> Commit {
+        Commit {
+            height: Height::from(1_u32),
+            round: Round::default(),
+            block_id: Id::default(),
+            signatures: timestamps
+                .iter()
+                .map(|&(validator, millis)| match millis {
+                    Some(millis) => CommitSig::BlockIdFlagCommit {
+                        validator_address: account::Id::new([validator; 20]),
+                        timestamp: time(millis),
+                        signature: None,
+                    },
+                    None => CommitSig::BlockIdFlagAbsent,
+                })
+                .collect(),
+        }
+    }
+
+    fn time(millis: i64) -> Time {
+        Time::from_unix_timestamp(
+            1_000 + millis.div_euclid(1_000),
+            (millis.rem_euclid(1_000) * 1_000_000) as u32,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn skews_of_a_commit() {
+        let skews = commit_skews(
+            &commit(&[(1, Some(-250)), (2, None), (3, Some(40))]),
+            time(0),
+        );
+        assert_eq!(
+            skews,
+            vec![
+                SignatureSkew {
+                    validator_address: account::Id::new([1; 20]),
+                    skew: Skew::Behind(Duration::from_millis(250)),
+                },
+                SignatureSkew {
+                    validator_address: account::Id::new([3; 20]),
+                    skew: Skew::Ahead(Duration::from_millis(40)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_chronically_skewed_validators() {
+        let mut analysis = SkewAnalysis::new(Duration::from_millis(100));
+        // Validator 1 is always 2s behind, validator 2 is once 1s ahead.
+        analysis.record(&commit(&[(1, Some(-2_000)), (2, Some(10))]), time(0));
+        analysis.record(&commit(&[(1, Some(-1_900)), (2, Some(1_000))]), time(0));
+        analysis.record(
+            &commit(&[(1, Some(-2_100)), (2, Some(-10)), (3, None)]),
+            time(0),
+        );
+
+        assert_eq!(analysis.commits(), 3);
+        let stats = &analysis.validators()[&account::Id::new([1; 20])];
+        assert_eq!(stats.signatures, 3);
+        assert_eq!(stats.beyond_tolerance, 3);
+        assert_eq!(stats.mean, Skew::Behind(Duration::from_secs(2)));
+        assert_eq!(stats.max, Skew::Behind(Duration::from_millis(2_100)));
+        let stats = &analysis.validators()[&account::Id::new([2; 20])];
+        assert_eq!(stats.beyond_tolerance, 1);
+        assert_eq!(stats.max, Skew::Ahead(Duration::from_secs(1)));
+        assert!(!analysis
+            .validators()
+            .contains_key(&account::Id::new([3; 20])));
+
+        assert_eq!(
+            analysis.chronically_skewed(50),
+            vec![account::Id::new([1; 20])]
+        );
+        assert_eq!(analysis.chronically_skewed(30).len(), 2);
+    }
+}
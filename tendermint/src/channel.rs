@@ -7,7 +7,7 @@ use core::fmt::{self, Display};
 use serde::{Deserialize, Serialize};
 
 pub use self::id::Id;
-use crate::{prelude::*, serializers};
+use crate::{alloc_prelude::*, serializers};
 
 /// Channels
 #[derive(Clone, Debug, Deserialize, Serialize)]
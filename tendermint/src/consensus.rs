@@ -3,4 +3,7 @@
 pub mod params;
 pub mod state;
 
-pub use self::{params::Params, state::State};
+pub use self::{
+    params::{Params, ParamsUpdate},
+    state::State,
+};
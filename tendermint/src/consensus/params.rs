@@ -1,8 +1,12 @@
 //! Tendermint consensus parameters
 
 use serde::{Deserialize, Serialize};
+use tendermint_proto::v0_37::types::HashedParams as RawHashedParams;
 
-use crate::{block, evidence, prelude::*, public_key};
+use crate::{alloc_prelude::*, block, crypto::Sha256, error::Error, evidence, public_key, Hash};
+
+/// The maximum size of a block, in bytes (`MaxBlockSizeBytes` in the Go implementation).
+pub const MAX_BLOCK_SIZE_BYTES: u64 = 104_857_600;
 
 /// All consensus-relevant parameters that can be adjusted by the ABCI app.
 ///
@@ -21,6 +25,128 @@ pub struct Params {
     pub version: Option<VersionParams>,
 }
 
+impl Params {
+    /// Check the parameters for validity, following `ConsensusParams.ValidateBasic`
+    /// from the Go implementation.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.block.max_bytes == 0 {
+            return Err(Error::invalid_consensus_params(
+                "block.max_bytes must be greater than 0".to_string(),
+            ));
+        }
+        if self.block.max_bytes > MAX_BLOCK_SIZE_BYTES {
+            return Err(Error::invalid_consensus_params(format!(
+                "block.max_bytes is too big ({} > {MAX_BLOCK_SIZE_BYTES})",
+                self.block.max_bytes
+            )));
+        }
+        if self.block.max_gas < -1 {
+            return Err(Error::invalid_consensus_params(format!(
+                "block.max_gas must be greater or equal to -1 (got {})",
+                self.block.max_gas
+            )));
+        }
+        if self.evidence.max_age_num_blocks == 0 {
+            return Err(Error::invalid_consensus_params(
+                "evidence.max_age_num_blocks must be greater than 0".to_string(),
+            ));
+        }
+        if self.evidence.max_age_duration.0.is_zero() {
+            return Err(Error::invalid_consensus_params(
+                "evidence.max_age_duration must be greater than 0".to_string(),
+            ));
+        }
+        if self.evidence.max_bytes < 0 {
+            return Err(Error::invalid_consensus_params(format!(
+                "evidence.max_bytes must be non-negative (got {})",
+                self.evidence.max_bytes
+            )));
+        }
+        if self.evidence.max_bytes as u64 > self.block.max_bytes {
+            return Err(Error::invalid_consensus_params(format!(
+                "evidence.max_bytes is greater than block.max_bytes ({} > {})",
+                self.evidence.max_bytes, self.block.max_bytes
+            )));
+        }
+        if self.validator.pub_key_types.is_empty() {
+            return Err(Error::invalid_consensus_params(
+                "validator.pub_key_types must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return a copy of these parameters with the given update applied.
+    ///
+    /// As in the Go implementation, the groups of parameters absent from the update
+    /// keep their current values. The result is not validated; callers should
+    /// invoke [`Params::validate`] on it before use.
+    pub fn apply_update(&self, update: &ParamsUpdate) -> Params {
+        Params {
+            block: update.block.clone().unwrap_or_else(|| self.block.clone()),
+            evidence: update
+                .evidence
+                .clone()
+                .unwrap_or_else(|| self.evidence.clone()),
+            validator: update
+                .validator
+                .clone()
+                .unwrap_or_else(|| self.validator.clone()),
+            version: update.version.clone().or_else(|| self.version.clone()),
+        }
+    }
+
+    /// Compute the hash of the parameters, as recorded in `Header.consensus_hash`.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Compute the hash of the parameters with a SHA256 hasher provided by a crypto
+    /// provider.
+    ///
+    /// Only a subset of the parameters (the block size limits) is hashed, as in
+    /// `ConsensusParams.Hash` in the Go implementation.
+    pub fn hash_with<H>(&self) -> Hash
+    where
+        H: Sha256,
+    {
+        let hashed_params = RawHashedParams {
+            block_max_bytes: self.block.max_bytes as i64,
+            block_max_gas: self.block.max_gas,
+        };
+        Hash::Sha256(H::digest(prost::Message::encode_to_vec(&hashed_params)))
+    }
+}
+
+/// An update to the consensus parameters, e.g. as returned by the application
+/// in `EndBlock` or `FinalizeBlock`.
+///
+/// Groups of parameters set to `None` are left unchanged when the update is applied
+/// with [`Params::apply_update`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParamsUpdate {
+    /// Parameters limiting the size of a block and time between consecutive blocks.
+    pub block: Option<block::Size>,
+    /// Parameters limiting the validity of evidence of byzantine behaviour.
+    pub evidence: Option<evidence::Params>,
+    /// Parameters limiting the types of public keys validators can use.
+    pub validator: Option<ValidatorParams>,
+    /// Version parameters
+    pub version: Option<VersionParams>,
+}
+
+impl From<Params> for ParamsUpdate {
+    fn from(params: Params) -> Self {
+        ParamsUpdate {
+            block: Some(params.block),
+            evidence: Some(params.evidence),
+            validator: Some(params.validator),
+            version: params.version,
+        }
+    }
+}
+
 /// ValidatorParams restrict the public key types validators can use.
 ///
 /// [Tendermint documentation](https://docs.tendermint.com/master/spec/core/data_structures.html#validatorparams)
@@ -65,8 +191,8 @@ mod v0_34 {
     };
     use tendermint_proto::Protobuf;
 
-    use super::{key_type, Params, ValidatorParams, VersionParams};
-    use crate::{error::Error, prelude::*, public_key};
+    use super::{key_type, Params, ParamsUpdate, ValidatorParams, VersionParams};
+    use crate::{alloc_prelude::*, error::Error, public_key};
 
     impl Protobuf<RawParams> for Params {}
 
@@ -103,6 +229,54 @@ mod v0_34 {
         }
     }
 
+    impl TryFrom<RawParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
+    impl TryFrom<RawAbciParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawAbciParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawAbciParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawAbciParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
     impl Protobuf<RawAbciParams> for Params {}
 
     impl TryFrom<RawAbciParams> for Params {
@@ -193,8 +367,8 @@ mod v0_37 {
     };
     use tendermint_proto::Protobuf;
 
-    use super::{key_type, Params, ValidatorParams, VersionParams};
-    use crate::{error::Error, prelude::*, public_key};
+    use super::{key_type, Params, ParamsUpdate, ValidatorParams, VersionParams};
+    use crate::{alloc_prelude::*, error::Error, public_key};
 
     impl Protobuf<RawParams> for Params {}
 
@@ -231,6 +405,30 @@ mod v0_37 {
         }
     }
 
+    impl TryFrom<RawParams> for ParamsUpdate {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value.block.map(TryFrom::try_from).transpose()?,
+                evidence: value.evidence.map(TryFrom::try_from).transpose()?,
+                validator: value.validator.map(TryFrom::try_from).transpose()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+            })
+        }
+    }
+
+    impl From<ParamsUpdate> for RawParams {
+        fn from(value: ParamsUpdate) -> Self {
+            RawParams {
+                block: value.block.map(From::from),
+                evidence: value.evidence.map(From::from),
+                validator: value.validator.map(From::from),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+
     impl Protobuf<RawValidatorParams> for ValidatorParams {}
 
     impl TryFrom<RawValidatorParams> for ValidatorParams {
@@ -274,3 +472,95 @@ mod v0_37 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+
+    fn params() -> Params {
+        Params {
+            block: block::Size {
+                max_bytes: 22020096,
+                max_gas: -1,
+                time_iota_ms: 1000,
+            },
+            evidence: evidence::Params {
+                max_age_num_blocks: 100000,
+                max_age_duration: evidence::Duration(Duration::from_secs(48 * 3600)),
+                max_bytes: 1048576,
+            },
+            validator: ValidatorParams {
+                pub_key_types: vec![public_key::Algorithm::Ed25519],
+            },
+            version: Some(VersionParams { app: 1 }),
+        }
+    }
+
+    #[test]
+    fn validate_params() {
+        params().validate().unwrap();
+
+        let mut invalid = params();
+        invalid.block.max_bytes = 0;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.block.max_bytes = MAX_BLOCK_SIZE_BYTES + 1;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.block.max_gas = -2;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.evidence.max_bytes = invalid.block.max_bytes as i64 + 1;
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.evidence.max_age_duration = evidence::Duration(Duration::ZERO);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = params();
+        invalid.validator.pub_key_types.clear();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn apply_update() {
+        let params = params();
+        assert_eq!(params.apply_update(&ParamsUpdate::default()), params);
+
+        let update = ParamsUpdate {
+            block: Some(block::Size {
+                max_bytes: 1024,
+                max_gas: 100,
+                time_iota_ms: 1000,
+            }),
+            version: Some(VersionParams { app: 2 }),
+            ..Default::default()
+        };
+        let updated = params.apply_update(&update);
+        assert_eq!(updated.block, update.block.unwrap());
+        assert_eq!(updated.version, Some(VersionParams { app: 2 }));
+        assert_eq!(updated.evidence, params.evidence);
+        assert_eq!(updated.validator, params.validator);
+
+        assert_eq!(params.apply_update(&params.clone().into()), params);
+    }
+
+    #[test]
+    #[cfg(feature = "rust-crypto")]
+    fn hash_covers_block_size_only() {
+        let params = params();
+
+        let mut other = params.clone();
+        other.evidence.max_age_num_blocks = 1;
+        other.version = None;
+        assert_eq!(params.hash(), other.hash());
+
+        other.block.max_gas = 100;
+        assert_ne!(params.hash(), other.hash());
+    }
+}
@@ -4,8 +4,8 @@ pub use core::{cmp::Ordering, fmt};
 
 use serde::{Deserialize, Serialize};
 
+use crate::alloc_prelude::*;
 pub use crate::block;
-use crate::prelude::*;
 
 /// Placeholder string to show when block ID is absent. Syntax from:
 /// <https://tendermint.com/docs/spec/consensus/consensus.html>
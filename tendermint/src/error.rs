@@ -50,6 +50,10 @@ define_error! {
         InvalidMessageType
             |_| { format_args!("invalid message type") },
 
+        InvalidPrivvalStep
+            { step: i8 }
+            | e | { format_args!("invalid privval step: {}", e.step) },
+
         NegativeHeight
             [ DisplayOnly<TryFromIntError> ]
             |_| { format_args!("negative height") },
@@ -141,6 +145,30 @@ define_error! {
             { reason: String }
             | e | { format_args!("invalid block: {}", e.reason) },
 
+        InvalidBlockId
+            { reason: String }
+            | e | { format_args!("invalid block id: {}", e.reason) },
+
+        InvalidConsensusParams
+            { reason: String }
+            | e | { format_args!("invalid consensus params: {}", e.reason) },
+
+        InvalidGenesis
+            { reason: String }
+            | e | { format_args!("invalid genesis: {}", e.reason) },
+
+        UnexpectedEventKind
+            { expected: String, actual: String }
+            | e | { format_args!("unexpected event kind: expected {}, got {}", e.expected, e.actual) },
+
+        MissingEventAttribute
+            { key: String }
+            | e | { format_args!("missing event attribute: {}", e.key) },
+
+        InvalidEventAttribute
+            { key: String, reason: String }
+            | e | { format_args!("invalid event attribute {}: {}", e.key, e.reason) },
+
         InvalidFirstHeader
             |_| { format_args!("last_block_id is not null on first height") },
 
@@ -157,6 +185,14 @@ define_error! {
         InvalidEvidence
             |_| { format_args!("invalid evidence") },
 
+        InvalidDuplicateVote
+            { reason: String }
+            | e | { format_args!("invalid duplicate vote evidence: {}", e.reason) },
+
+        InvalidLightClientAttack
+            { reason: String }
+            | e | { format_args!("invalid light client attack evidence: {}", e.reason) },
+
         InvalidValidatorParams
             |_| { format_args!("invalid validator parameters") },
 
@@ -195,6 +231,10 @@ define_error! {
             { raw: vote::Power, computed: vote::Power }
             |e| { format_args!("mismatch between raw voting ({0:?}) and computed one ({1:?})", e.raw, e.computed) },
 
+        TotalVotingPowerOverflow
+            { max: u64 }
+            |e| { format_args!("total voting power of the validators exceeds the maximum ({0})", e.max) },
+
         NegativeMaxAgeNum
             [ DisplayOnly<TryFromIntError> ]
             |_| { format_args!("negative max_age_num_blocks") },
@@ -207,6 +247,14 @@ define_error! {
             [ DisplayOnly<time::error::Parse> ]
             |_| { format_args!("time parsing error") },
 
+        InvalidBech32Hrp
+            { hrp: String }
+            | e | { format_args!("invalid bech32 human-readable part: {:?}", e.hrp) },
+
+        Bech32HrpMismatch
+            { expected: String, actual: String }
+            | e | { format_args!("unexpected bech32 human-readable part: expected {:?}, got {:?}", e.expected, e.actual) },
+
         SubtleEncoding
             [ DisplayOnly<subtle_encoding::Error> ]
             |_| { format_args!("subtle encoding error") },
@@ -230,6 +278,14 @@ define_error! {
         NegativeProofIndex
             [ DisplayOnly<TryFromIntError> ]
             |_| { "negative item index in proof" },
+
+        InvalidMerkleProof
+            { reason: String }
+            | e | { format_args!("invalid Merkle proof: {}", e.reason) },
+
+        InvalidTxProof
+            { reason: String }
+            | e | { format_args!("invalid transaction proof: {}", e.reason) },
     }
 }
 
@@ -1,6 +1,7 @@
 //! Evidence of malfeasance by validators (i.e. signing conflicting votes).
 
 use core::{
+    cmp::Reverse,
     convert::{TryFrom, TryInto},
     slice,
 };
@@ -10,11 +11,17 @@ use tendermint_proto::google::protobuf::Duration as RawDuration;
 use tendermint_proto::v0_37::types::Evidence as RawEvidence;
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*, serializers, vote::Power, Time, Vote};
+use crate::{
+    alloc_prelude::*,
+    block::{self, signed_header::SignedHeader},
+    error::Error,
+    serializers, validator,
+    vote::Power,
+    Time, Vote,
+};
 
-/// Evidence of malfeasance by validators (i.e. signing conflicting votes).
-/// encoded using an Amino prefix. There is currently only a single type of
-/// evidence: `DuplicateVoteEvidence`.
+/// Evidence of malfeasance by validators (i.e. signing conflicting votes or
+/// conflicting blocks).
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidence>
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -24,8 +31,8 @@ pub enum Evidence {
     /// Duplicate vote evidence
     DuplicateVote(DuplicateVoteEvidence),
 
-    /// LightClient attack evidence - Todo: Implement details
-    LightClientAttackEvidence,
+    /// Light client attack evidence
+    LightClientAttack(Box<LightClientAttackEvidence>),
 }
 
 /// Duplicate vote evidence
@@ -53,12 +60,205 @@ impl DuplicateVoteEvidence {
             timestamp: Time::unix_epoch(),
         })
     }
+
+    /// Build the evidence of a validator having signed the two given
+    /// conflicting votes.
+    ///
+    /// `block_time` is the time of the block at the height of the votes, and
+    /// `validator_set` the validator set at that height, which must contain
+    /// the offending validator. The votes are ordered by block ID, as
+    /// expected by Tendermint.
+    ///
+    /// The votes must be for the same height, round and step, by the same
+    /// validator, and for different blocks. Their signatures are not verified.
+    pub fn from_conflicting_votes(
+        vote_a: Vote,
+        vote_b: Vote,
+        block_time: Time,
+        validator_set: &validator::Set,
+    ) -> Result<Self, Error> {
+        if vote_a.height != vote_b.height
+            || vote_a.round != vote_b.round
+            || vote_a.vote_type != vote_b.vote_type
+        {
+            return Err(Error::invalid_duplicate_vote(
+                "votes are for different heights, rounds or steps".into(),
+            ));
+        }
+        if vote_a.validator_address != vote_b.validator_address {
+            return Err(Error::invalid_duplicate_vote(
+                "votes are from different validators".into(),
+            ));
+        }
+        if vote_a.block_id == vote_b.block_id {
+            return Err(Error::invalid_duplicate_vote(
+                "votes are for the same block".into(),
+            ));
+        }
+
+        let validator = validator_set
+            .validator(vote_a.validator_address)
+            .ok_or_else(|| {
+                Error::invalid_duplicate_vote(format!(
+                    "validator {} is not in the validator set",
+                    vote_a.validator_address
+                ))
+            })?;
+
+        let (vote_a, vote_b) = if vote_a.block_id < vote_b.block_id {
+            (vote_a, vote_b)
+        } else {
+            (vote_b, vote_a)
+        };
+
+        Ok(Self {
+            vote_a,
+            vote_b,
+            total_voting_power: validator_set.total_voting_power(),
+            validator_power: validator.power,
+            timestamp: block_time,
+        })
+    }
+
     /// Get votes
     pub fn votes(&self) -> (&Vote, &Vote) {
         (&self.vote_a, &self.vote_b)
     }
 }
 
+/// A signed header along with the validator set which signed it.
+///
+/// This is the `LightBlock` of the Tendermint protobuf definitions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictingBlock {
+    /// Header and commit of the block
+    pub signed_header: SignedHeader,
+    /// Validator set at the block height
+    pub validator_set: validator::Set,
+}
+
+/// Light client attack evidence, i.e. a block conflicting with the one
+/// committed by the chain at the same height.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LightClientAttackEvidence {
+    /// The block conflicting with the one committed by the chain
+    pub conflicting_block: ConflictingBlock,
+    /// Height of the last block trusted by both the chain and the attacked
+    /// light client
+    pub common_height: block::Height,
+    /// Validators which signed the conflicting block
+    pub byzantine_validators: Vec<validator::Info>,
+    /// Total voting power of the validator set at the common height
+    pub total_voting_power: Power,
+    /// Time of the block at the common height
+    pub timestamp: Time,
+}
+
+impl LightClientAttackEvidence {
+    /// Build the evidence of an attack from a block conflicting with the
+    /// `trusted` block at the same height.
+    ///
+    /// `common` is the latest block trusted by the light client which the
+    /// conflicting block was verified from. When the conflicting header could
+    /// not have been derived from the state of the chain (a lunatic attack),
+    /// the evidence refers to this common block and the byzantine validators
+    /// are the validators of the common block which signed the conflicting
+    /// block. Otherwise, the evidence refers to the trusted block and the
+    /// byzantine validators are those which signed both blocks in the same
+    /// round (equivocation), or none if the rounds differ (amnesia).
+    pub fn from_conflicting_blocks(
+        conflicting_block: ConflictingBlock,
+        trusted: &ConflictingBlock,
+        common: &ConflictingBlock,
+    ) -> Result<Self, Error> {
+        let conflicting = &conflicting_block.signed_header;
+        let trusted_header = &trusted.signed_header;
+        if conflicting.header.height != trusted_header.header.height {
+            return Err(Error::invalid_light_client_attack(format!(
+                "conflicting block at height {} and trusted block at height {}",
+                conflicting.header.height, trusted_header.header.height
+            )));
+        }
+        if conflicting.commit.block_id == trusted_header.commit.block_id {
+            return Err(Error::invalid_light_client_attack(
+                "blocks do not conflict".into(),
+            ));
+        }
+        if common.signed_header.header.height > trusted_header.header.height {
+            return Err(Error::invalid_light_client_attack(format!(
+                "common block at height {} is higher than the trusted block",
+                common.signed_header.header.height
+            )));
+        }
+
+        let mut evidence = if Self::is_lunatic(conflicting, trusted_header) {
+            Self {
+                conflicting_block,
+                common_height: common.signed_header.header.height,
+                byzantine_validators: Vec::new(),
+                total_voting_power: common.validator_set.total_voting_power(),
+                timestamp: common.signed_header.header.time,
+            }
+        } else {
+            Self {
+                conflicting_block,
+                common_height: trusted_header.header.height,
+                byzantine_validators: Vec::new(),
+                total_voting_power: trusted.validator_set.total_voting_power(),
+                timestamp: trusted_header.header.time,
+            }
+        };
+        evidence.byzantine_validators =
+            evidence.find_byzantine_validators(&common.validator_set, trusted_header);
+
+        Ok(evidence)
+    }
+
+    /// Whether the conflicting header could not have been derived from the
+    /// state of the chain, in which case it is a lunatic attack.
+    fn is_lunatic(conflicting: &SignedHeader, trusted: &SignedHeader) -> bool {
+        let (a, b) = (&conflicting.header, &trusted.header);
+        a.validators_hash != b.validators_hash
+            || a.next_validators_hash != b.next_validators_hash
+            || a.consensus_hash != b.consensus_hash
+            || a.app_hash != b.app_hash
+            || a.last_results_hash != b.last_results_hash
+    }
+
+    fn find_byzantine_validators(
+        &self,
+        common_validators: &validator::Set,
+        trusted: &SignedHeader,
+    ) -> Vec<validator::Info> {
+        let conflicting = &self.conflicting_block;
+        let signatures = &conflicting.signed_header.commit.signatures;
+
+        let mut validators: Vec<_> = if Self::is_lunatic(&conflicting.signed_header, trusted) {
+            signatures
+                .iter()
+                .filter(|sig| sig.is_commit())
+                .filter_map(|sig| common_validators.validator(sig.validator_address()?))
+                .collect()
+        } else if conflicting.signed_header.commit.round == trusted.commit.round {
+            signatures
+                .iter()
+                .zip(trusted.commit.signatures.iter())
+                .filter(|(sig_a, sig_b)| sig_a.is_commit() && sig_b.is_commit())
+                .filter_map(|(sig, _)| {
+                    conflicting
+                        .validator_set
+                        .validator(sig.validator_address()?)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        validators.sort_by_key(|v| (Reverse(v.power), v.address));
+        validators
+    }
+}
+
 /// Evidence data is a wrapper for a list of `Evidence`.
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidencedata>
@@ -125,11 +325,14 @@ tendermint_pb_modules! {
     use pb::types::{
         evidence::Sum as RawSum, DuplicateVoteEvidence as RawDuplicateVoteEvidence,
         Evidence as RawEvidence, EvidenceList as RawEvidenceList,
-        EvidenceParams as RawEvidenceParams,
+        EvidenceParams as RawEvidenceParams, LightBlock as RawLightBlock,
+        LightClientAttackEvidence as RawLightClientAttackEvidence,
     };
 
-    use super::{Data, DuplicateVoteEvidence, Evidence, Params};
-    use crate::{error::Error, prelude::*};
+    use super::{
+        ConflictingBlock, Data, DuplicateVoteEvidence, Evidence, LightClientAttackEvidence, Params,
+    };
+    use crate::{error::Error, alloc_prelude::*};
 
     impl TryFrom<RawEvidence> for Evidence {
         type Error = Error;
@@ -138,7 +341,9 @@ tendermint_pb_modules! {
             use RawSum::*;
             match message.sum.ok_or_else(Error::invalid_evidence)? {
                 DuplicateVoteEvidence(ev) => Ok(Evidence::DuplicateVote(ev.try_into()?)),
-                LightClientAttackEvidence(_ev) => Ok(Evidence::LightClientAttackEvidence),
+                LightClientAttackEvidence(ev) => {
+                    Ok(Evidence::LightClientAttack(Box::new(ev.try_into()?)))
+                },
             }
         }
     }
@@ -146,10 +351,10 @@ tendermint_pb_modules! {
     impl From<Evidence> for RawEvidence {
         fn from(value: Evidence) -> Self {
             let sum = match value {
-                Evidence::DuplicateVote(ev) => Some(RawSum::DuplicateVoteEvidence(ev.into())),
-                Evidence::LightClientAttackEvidence => None,
+                Evidence::DuplicateVote(ev) => RawSum::DuplicateVoteEvidence(ev.into()),
+                Evidence::LightClientAttack(ev) => RawSum::LightClientAttackEvidence((*ev).into()),
             };
-            RawEvidence { sum }
+            RawEvidence { sum: Some(sum) }
         }
     }
 
@@ -182,7 +387,73 @@ tendermint_pb_modules! {
                 vote_a: Some(value.vote_a.into()),
                 vote_b: Some(value.vote_b.into()),
                 total_voting_power: value.total_voting_power.into(),
-                validator_power: value.total_voting_power.into(),
+                validator_power: value.validator_power.into(),
+                timestamp: Some(value.timestamp.into()),
+            }
+        }
+    }
+
+    impl TryFrom<RawLightBlock> for ConflictingBlock {
+        type Error = Error;
+
+        fn try_from(value: RawLightBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                signed_header: value
+                    .signed_header
+                    .ok_or_else(Error::missing_header)?
+                    .try_into()?,
+                validator_set: value
+                    .validator_set
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+            })
+        }
+    }
+
+    impl From<ConflictingBlock> for RawLightBlock {
+        fn from(value: ConflictingBlock) -> Self {
+            RawLightBlock {
+                signed_header: Some(value.signed_header.into()),
+                validator_set: Some(value.validator_set.into()),
+            }
+        }
+    }
+
+    impl TryFrom<RawLightClientAttackEvidence> for LightClientAttackEvidence {
+        type Error = Error;
+
+        fn try_from(value: RawLightClientAttackEvidence) -> Result<Self, Self::Error> {
+            Ok(Self {
+                conflicting_block: value
+                    .conflicting_block
+                    .ok_or_else(Error::missing_evidence)?
+                    .try_into()?,
+                common_height: value.common_height.try_into()?,
+                byzantine_validators: value
+                    .byzantine_validators
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<Vec<_>, _>>()?,
+                total_voting_power: value.total_voting_power.try_into()?,
+                timestamp: value
+                    .timestamp
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+            })
+        }
+    }
+
+    impl From<LightClientAttackEvidence> for RawLightClientAttackEvidence {
+        fn from(value: LightClientAttackEvidence) -> Self {
+            RawLightClientAttackEvidence {
+                conflicting_block: Some(value.conflicting_block.into()),
+                common_height: value.common_height.into(),
+                byzantine_validators: value
+                    .byzantine_validators
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                total_voting_power: value.total_voting_power.into(),
                 timestamp: Some(value.timestamp.into()),
             }
         }
@@ -276,3 +547,211 @@ impl From<Duration> for RawDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account, block::CommitSig, chain, public_key::PublicKey, test::dummy_signature, Hash,
+    };
+
+    fn validator(id: u8, power: u32) -> validator::Info {
+        validator::Info {
+            address: account::Id::new([id; account::LENGTH]),
+            pub_key: PublicKey::from_raw_ed25519(&[id; 32]).unwrap(),
+            power: power.into(),
+            name: None,
+            proposer_priority: Default::default(),
+        }
+    }
+
+    fn validator_set() -> validator::Set {
+        validator::Set::without_proposer(vec![validator(1, 10), validator(2, 20), validator(3, 30)])
+            .unwrap()
+    }
+
+    fn block_id(id: u8) -> block::Id {
+        block::Id {
+            hash: Hash::Sha256([id; 32]),
+            part_set_header: Default::default(),
+        }
+    }
+
+    fn vote(validator: u8, block: u8) -> Vote {
+        Vote {
+            height: 10_u32.into(),
+            validator_address: account::Id::new([validator; account::LENGTH]),
+            block_id: Some(block_id(block)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn duplicate_vote_from_conflicting_votes() {
+        let time = Time::from_unix_timestamp(1_000, 0).unwrap();
+        let evidence = DuplicateVoteEvidence::from_conflicting_votes(
+            vote(2, 9),
+            vote(2, 5),
+            time,
+            &validator_set(),
+        )
+        .unwrap();
+
+        assert_eq!(evidence.vote_a.block_id, Some(block_id(5)));
+        assert_eq!(evidence.vote_b.block_id, Some(block_id(9)));
+        assert_eq!(evidence.total_voting_power, 60_u32.into());
+        assert_eq!(evidence.validator_power, 20_u32.into());
+        assert_eq!(evidence.timestamp, time);
+    }
+
+    #[test]
+    fn duplicate_vote_rejects_non_conflicting_votes() {
+        let time = Time::unix_epoch();
+        let vals = validator_set();
+
+        let same_block =
+            DuplicateVoteEvidence::from_conflicting_votes(vote(1, 5), vote(1, 5), time, &vals);
+        assert!(same_block.is_err());
+
+        let other_validator =
+            DuplicateVoteEvidence::from_conflicting_votes(vote(1, 5), vote(2, 6), time, &vals);
+        assert!(other_validator.is_err());
+
+        let mut other_round = vote(1, 6);
+        other_round.round = 1_u16.into();
+        let other_round =
+            DuplicateVoteEvidence::from_conflicting_votes(vote(1, 5), other_round, time, &vals);
+        assert!(other_round.is_err());
+
+        let unknown =
+            DuplicateVoteEvidence::from_conflicting_votes(vote(4, 5), vote(4, 6), time, &vals);
+        assert!(unknown.is_err());
+    }
+
+    fn light_block(
+        height: u32,
+        block: u8,
+        app_hash: u8,
+        round: u16,
+        signers: &[u8],
+    ) -> ConflictingBlock {
+        let vals = validator_set();
+        let header = block::Header {
+            version: block::header::Version { block: 11, app: 0 },
+            chain_id: chain::Id::try_from("test-chain").unwrap(),
+            height: height.into(),
+            time: Time::from_unix_timestamp(i64::from(height), 0).unwrap(),
+            last_block_id: None,
+            last_commit_hash: None,
+            data_hash: Some(Hash::Sha256([block; 32])),
+            validators_hash: Hash::None,
+            next_validators_hash: Hash::None,
+            consensus_hash: Hash::None,
+            app_hash: vec![app_hash].try_into().unwrap(),
+            last_results_hash: None,
+            evidence_hash: None,
+            proposer_address: vals.validators()[0].address,
+        };
+        let signatures = vals
+            .validators()
+            .iter()
+            .map(|val| {
+                if signers.contains(&val.address.as_bytes()[0]) {
+                    CommitSig::BlockIdFlagCommit {
+                        validator_address: val.address,
+                        timestamp: header.time,
+                        signature: Some(dummy_signature()),
+                    }
+                } else {
+                    CommitSig::BlockIdFlagAbsent
+                }
+            })
+            .collect();
+        let commit = block::Commit {
+            height: height.into(),
+            round: round.into(),
+            block_id: block_id(block),
+            signatures,
+        };
+
+        ConflictingBlock {
+            signed_header: SignedHeader::new(header, commit).unwrap(),
+            validator_set: vals,
+        }
+    }
+
+    #[test]
+    fn lunatic_light_client_attack() {
+        let common = light_block(5, 1, 0, 0, &[1, 2, 3]);
+        let trusted = light_block(10, 2, 0, 0, &[1, 2, 3]);
+        let conflicting = light_block(10, 3, 1, 0, &[1, 3]);
+
+        let evidence =
+            LightClientAttackEvidence::from_conflicting_blocks(conflicting, &trusted, &common)
+                .unwrap();
+
+        assert_eq!(evidence.common_height, 5_u32.into());
+        assert_eq!(evidence.timestamp, common.signed_header.header.time);
+        assert_eq!(evidence.total_voting_power, 60_u32.into());
+        assert_eq!(
+            evidence.byzantine_validators,
+            vec![validator(3, 30), validator(1, 10)]
+        );
+    }
+
+    #[test]
+    fn equivocation_light_client_attack() {
+        let common = light_block(5, 1, 0, 0, &[1, 2, 3]);
+        let trusted = light_block(10, 2, 0, 0, &[2, 3]);
+        let conflicting = light_block(10, 3, 0, 0, &[1, 2]);
+
+        let evidence = LightClientAttackEvidence::from_conflicting_blocks(
+            conflicting.clone(),
+            &trusted,
+            &common,
+        )
+        .unwrap();
+
+        assert_eq!(evidence.common_height, 10_u32.into());
+        assert_eq!(evidence.timestamp, trusted.signed_header.header.time);
+        assert_eq!(evidence.byzantine_validators, vec![validator(2, 20)]);
+
+        // Commits in different rounds: amnesia attack
+        let conflicting = light_block(10, 3, 0, 1, &[1, 2]);
+        let evidence =
+            LightClientAttackEvidence::from_conflicting_blocks(conflicting, &trusted, &common)
+                .unwrap();
+        assert!(evidence.byzantine_validators.is_empty());
+    }
+
+    #[test]
+    fn light_client_attack_rejects_non_conflicting_blocks() {
+        let common = light_block(5, 1, 0, 0, &[1, 2, 3]);
+        let trusted = light_block(10, 2, 0, 0, &[1, 2, 3]);
+
+        let same_block =
+            LightClientAttackEvidence::from_conflicting_blocks(trusted.clone(), &trusted, &common);
+        assert!(same_block.is_err());
+
+        let other_height = LightClientAttackEvidence::from_conflicting_blocks(
+            light_block(11, 3, 1, 0, &[1]),
+            &trusted,
+            &common,
+        );
+        assert!(other_height.is_err());
+    }
+
+    #[test]
+    fn light_client_attack_proto_roundtrip() {
+        let common = light_block(5, 1, 0, 0, &[1, 2, 3]);
+        let trusted = light_block(10, 2, 0, 0, &[1, 2, 3]);
+        let conflicting = light_block(10, 3, 1, 0, &[1, 3]);
+        let evidence = Evidence::LightClientAttack(Box::new(
+            LightClientAttackEvidence::from_conflicting_blocks(conflicting, &trusted, &common)
+                .unwrap(),
+        ));
+
+        let raw = RawEvidence::from(evidence.clone());
+        assert_eq!(Evidence::try_from(raw).unwrap(), evidence);
+    }
+}
@@ -2,7 +2,21 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{chain, consensus, prelude::*, serializers, validator, AppHash, Time};
+use crate::{
+    alloc_prelude::*,
+    chain, consensus,
+    crypto::Sha256,
+    error::Error,
+    public_key::{Algorithm, PublicKey},
+    serializers, validator, AppHash, Hash, Time,
+};
+
+/// The maximum size of the serialized `app_state`, in bytes.
+///
+/// This matches the size above which Tendermint refuses to serve the genesis
+/// document through the `/genesis` RPC endpoint (requiring `/genesis_chunked`
+/// instead), which makes larger documents impractical to distribute.
+pub const MAX_APP_STATE_BYTES: usize = 16 * 1024 * 1024;
 
 /// Genesis data
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -31,3 +45,241 @@ pub struct Genesis<AppState = serde_json::Value> {
     /// App state
     pub app_state: AppState,
 }
+
+impl<AppState> Genesis<AppState>
+where
+    AppState: Serialize,
+{
+    /// Check the genesis document for semantic validity.
+    ///
+    /// This mirrors `GenesisDoc.ValidateAndComplete` from the Go implementation,
+    /// except that it does not modify the document: an `initial_height` of 0 is
+    /// accepted here, and is replaced by 1 in [`Genesis::validate_and_complete`].
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.chain_id.as_str().is_empty() {
+            return Err(Error::invalid_genesis("chain_id is empty".to_string()));
+        }
+        if self.chain_id.as_str().len() > chain::id::MAX_LENGTH {
+            return Err(Error::invalid_genesis(format!(
+                "chain_id is too long (max: {})",
+                chain::id::MAX_LENGTH
+            )));
+        }
+        if self.initial_height < 0 {
+            return Err(Error::invalid_genesis(format!(
+                "initial_height cannot be negative (got {})",
+                self.initial_height
+            )));
+        }
+
+        self.consensus_params.validate()?;
+        self.validate_validators()?;
+
+        let app_state_len = serde_json::to_vec(&self.app_state)
+            .map_err(|e| Error::invalid_genesis(format!("cannot serialize app_state: {e}")))?
+            .len();
+        if app_state_len > MAX_APP_STATE_BYTES {
+            return Err(Error::invalid_genesis(format!(
+                "app_state is too large ({app_state_len} bytes, max: {MAX_APP_STATE_BYTES})"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validate the genesis document and fill in the defaults for missing values,
+    /// i.e. set `initial_height` to 1 if it is 0.
+    pub fn validate_and_complete(&mut self) -> Result<(), Error> {
+        self.validate()?;
+        if self.initial_height == 0 {
+            self.initial_height = 1;
+        }
+        Ok(())
+    }
+
+    /// Compute the hash of this genesis document.
+    #[cfg(feature = "rust-crypto")]
+    pub fn hash(&self) -> Result<Hash, Error> {
+        self.hash_with::<crate::crypto::default::Sha256>()
+    }
+
+    /// Compute the hash of this genesis document with a SHA256 hasher provided
+    /// by a crypto provider.
+    ///
+    /// The hash is computed over the JSON serialization of the document, as
+    /// returned by the `/genesis` RPC endpoint. Note that Tendermint nodes hash
+    /// the raw bytes of their `genesis.json` file, which only results in the
+    /// same value if that file is formatted the same way; use [`hash_bytes`]
+    /// to hash a file as-is.
+    pub fn hash_with<H>(&self) -> Result<Hash, Error>
+    where
+        H: Sha256,
+    {
+        let bytes = serde_json::to_vec(self)
+            .map_err(|e| Error::invalid_genesis(format!("cannot serialize genesis: {e}")))?;
+        Ok(hash_bytes::<H>(&bytes))
+    }
+
+    fn validate_validators(&self) -> Result<(), Error> {
+        let mut total_power: u64 = 0;
+        for (i, val) in self.validators.iter().enumerate() {
+            if val.power.is_zero() {
+                return Err(Error::invalid_genesis(format!(
+                    "validator {i} ({}) has no voting power",
+                    val.address
+                )));
+            }
+
+            #[cfg(feature = "rust-crypto")]
+            if crate::account::Id::from(val.pub_key) != val.address {
+                return Err(Error::invalid_genesis(format!(
+                    "validator {i} has address {} that does not match its public key",
+                    val.address
+                )));
+            }
+
+            let algorithm = key_algorithm(&val.pub_key);
+            if !self
+                .consensus_params
+                .validator
+                .pub_key_types
+                .contains(&algorithm)
+            {
+                return Err(Error::invalid_genesis(format!(
+                    "validator {i} ({}) uses a public key type ({algorithm}) not allowed by the consensus params",
+                    val.address
+                )));
+            }
+
+            total_power = total_power.saturating_add(val.power());
+        }
+
+        if total_power > validator::MAX_TOTAL_VOTING_POWER {
+            return Err(Error::invalid_genesis(format!(
+                "total voting power of validators ({total_power}) exceeds the maximum ({})",
+                validator::MAX_TOTAL_VOTING_POWER
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Hash the raw bytes of a genesis document, e.g. the contents of a `genesis.json` file,
+/// the same way Tendermint nodes do.
+pub fn hash_bytes<H>(bytes: &[u8]) -> Hash
+where
+    H: Sha256,
+{
+    Hash::Sha256(H::digest(bytes))
+}
+
+fn key_algorithm(pub_key: &PublicKey) -> Algorithm {
+    match pub_key {
+        PublicKey::Ed25519(_) => Algorithm::Ed25519,
+        #[cfg(feature = "secp256k1")]
+        PublicKey::Secp256k1(_) => Algorithm::Secp256k1,
+    }
+}
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::{block, evidence, vote};
+
+    fn genesis() -> Genesis {
+        let pub_key = PublicKey::from_raw_ed25519(&[
+            215, 90, 152, 1, 130, 177, 10, 183, 213, 75, 254, 211, 201, 100, 7, 58, 14, 225, 114,
+            243, 218, 166, 35, 37, 175, 2, 26, 104, 247, 7, 81, 26,
+        ])
+        .unwrap();
+
+        Genesis {
+            genesis_time: Time::unix_epoch(),
+            chain_id: "test-chain".parse().unwrap(),
+            initial_height: 1,
+            consensus_params: consensus::Params {
+                block: block::Size {
+                    max_bytes: 22020096,
+                    max_gas: -1,
+                    time_iota_ms: 1000,
+                },
+                evidence: evidence::Params {
+                    max_age_num_blocks: 100000,
+                    max_age_duration: evidence::Duration(Duration::from_secs(48 * 3600)),
+                    max_bytes: 1048576,
+                },
+                validator: consensus::params::ValidatorParams {
+                    pub_key_types: vec![Algorithm::Ed25519],
+                },
+                version: None,
+            },
+            validators: vec![validator::Info::new(pub_key, vote::Power::from(10_u32))],
+            app_hash: AppHash::default(),
+            app_state: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn valid_genesis() {
+        genesis().validate().unwrap();
+    }
+
+    #[test]
+    fn negative_initial_height() {
+        let mut genesis = genesis();
+        genesis.initial_height = -1;
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn complete_initial_height() {
+        let mut genesis = genesis();
+        genesis.initial_height = 0;
+        genesis.validate_and_complete().unwrap();
+        assert_eq!(genesis.initial_height, 1);
+    }
+
+    #[test]
+    fn validator_without_power() {
+        let mut genesis = genesis();
+        genesis.validators[0].power = vote::Power::from(0_u32);
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn validator_address_mismatch() {
+        let mut genesis = genesis();
+        genesis.validators[0].address = crate::account::Id::new([0; 20]);
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn validator_key_type_not_allowed() {
+        let mut genesis = genesis();
+        genesis.consensus_params.validator.pub_key_types = vec![Algorithm::Secp256k1];
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn app_state_too_large() {
+        let mut genesis = genesis();
+        genesis.app_state = serde_json::Value::String("x".repeat(MAX_APP_STATE_BYTES));
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        let genesis = genesis();
+        let bytes = serde_json::to_vec(&genesis).unwrap();
+        assert_eq!(
+            genesis.hash().unwrap(),
+            hash_bytes::<crate::crypto::default::Sha256>(&bytes)
+        );
+        let mut other = genesis.clone();
+        other.initial_height = 2;
+        assert_ne!(genesis.hash().unwrap(), other.hash().unwrap());
+    }
+}
@@ -7,11 +7,13 @@ use core::{
 };
 
 use bytes::Bytes;
+use digest::{consts::U32, generic_array::GenericArray};
+use once_cell::race::OnceBox;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use subtle_encoding::{Encoding, Hex};
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Output size for the SHA-256 hash function
 pub const SHA256_HASH_SIZE: usize = 32;
@@ -79,7 +81,68 @@ impl TryFrom<Bytes> for Hash {
     }
 }
 
+impl From<[u8; SHA256_HASH_SIZE]> for Hash {
+    fn from(value: [u8; SHA256_HASH_SIZE]) -> Self {
+        Hash::Sha256(value)
+    }
+}
+
+impl TryFrom<Hash> for [u8; SHA256_HASH_SIZE] {
+    type Error = Error;
+
+    fn try_from(value: Hash) -> Result<Self, Self::Error> {
+        match value {
+            Hash::Sha256(h) => Ok(h),
+            Hash::None => Err(Error::invalid_hash_size()),
+        }
+    }
+}
+
+/// Conversion from the output of a SHA-256 [`digest`] implementation.
+impl From<GenericArray<u8, U32>> for Hash {
+    fn from(value: GenericArray<u8, U32>) -> Self {
+        Hash::Sha256(value.into())
+    }
+}
+
+impl TryFrom<Hash> for GenericArray<u8, U32> {
+    type Error = Error;
+
+    fn try_from(value: Hash) -> Result<Self, Self::Error> {
+        <[u8; SHA256_HASH_SIZE]>::try_from(value).map(Into::into)
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<primitive_types::H256> for Hash {
+    fn from(value: primitive_types::H256) -> Self {
+        Hash::Sha256(value.0)
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl TryFrom<Hash> for primitive_types::H256 {
+    type Error = Error;
+
+    fn try_from(value: Hash) -> Result<Self, Self::Error> {
+        <[u8; SHA256_HASH_SIZE]>::try_from(value).map(primitive_types::H256)
+    }
+}
+
 impl Hash {
+    /// Create a SHA-256 `Hash` from its bytes, in a `const` context
+    pub const fn from_sha256(bytes: [u8; SHA256_HASH_SIZE]) -> Hash {
+        Hash::Sha256(bytes)
+    }
+
+    /// Return the bytes of a SHA-256 hash, or `None` for an empty hash
+    pub const fn as_sha256(&self) -> Option<&[u8; SHA256_HASH_SIZE]> {
+        match self {
+            Hash::Sha256(h) => Some(h),
+            Hash::None => None,
+        }
+    }
+
     /// Create a new `Hash` with the given algorithm type
     pub fn from_bytes(alg: Algorithm, bytes: &[u8]) -> Result<Hash, Error> {
         if bytes.is_empty() {
@@ -115,7 +178,7 @@ impl Hash {
     }
 
     /// Return the digest algorithm used to produce this hash
-    pub fn algorithm(self) -> Algorithm {
+    pub const fn algorithm(self) -> Algorithm {
         match self {
             Hash::Sha256(_) => Algorithm::Sha256,
             Hash::None => Algorithm::Sha256,
@@ -123,16 +186,16 @@ impl Hash {
     }
 
     /// Borrow the `Hash` as a byte slice
-    pub fn as_bytes(&self) -> &[u8] {
+    pub const fn as_bytes(&self) -> &[u8] {
         match self {
-            Hash::Sha256(ref h) => h.as_ref(),
+            Hash::Sha256(h) => h,
             Hash::None => &[],
         }
     }
 
     /// Convenience function to check for Hash::None
-    pub fn is_empty(&self) -> bool {
-        self == &Hash::None
+    pub const fn is_empty(&self) -> bool {
+        matches!(self, Hash::None)
     }
 }
 
@@ -206,6 +269,68 @@ pub mod allow_empty {
     }
 }
 
+/// Lazily computed hash of a value of type `K`.
+///
+/// The hash is remembered together with a snapshot of the value it was
+/// computed from, so that a cache embedded in a type with public fields never
+/// hands out a stale hash after those fields have been modified. Any SHA-256
+/// provider yields the same digest, so the cached value is shared between
+/// hashers.
+pub(crate) struct HashCache<K> {
+    cell: OnceBox<(K, Hash)>,
+}
+
+impl<K: Clone + PartialEq> HashCache<K> {
+    /// Return the cached hash of `key`, computing it with `compute` if nothing
+    /// has been cached yet or if `key` no longer matches the cached snapshot.
+    pub(crate) fn get_or_compute(&self, key: &K, compute: impl FnOnce() -> Hash) -> Hash {
+        match self.cell.get() {
+            Some((cached_key, hash)) if cached_key == key => *hash,
+            Some(_) => compute(),
+            None => {
+                let hash = compute();
+                // Losing a race against another thread is fine, as both
+                // computed the same hash.
+                let _ = self.cell.set(Box::new((key.clone(), hash)));
+                hash
+            },
+        }
+    }
+}
+
+impl<K> Default for HashCache<K> {
+    fn default() -> Self {
+        Self {
+            cell: OnceBox::new(),
+        }
+    }
+}
+
+impl<K: Clone> Clone for HashCache<K> {
+    fn clone(&self) -> Self {
+        let cell = OnceBox::new();
+        if let Some(cached) = self.cell.get() {
+            let _ = cell.set(Box::new(cached.clone()));
+        }
+        Self { cell }
+    }
+}
+
+impl<K> Debug for HashCache<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashCache").finish_non_exhaustive()
+    }
+}
+
+/// Caches never take part in comparisons of the values they are embedded in.
+impl<K> PartialEq for HashCache<K> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<K> Eq for HashCache<K> {}
+
 /// AppHash is usually a SHA256 hash, but in reality it can be any kind of data
 #[derive(Clone, PartialEq, Eq, Default)]
 pub struct AppHash(Vec<u8>);
@@ -238,6 +363,60 @@ impl From<AppHash> for Bytes {
     }
 }
 
+impl From<[u8; SHA256_HASH_SIZE]> for AppHash {
+    fn from(value: [u8; SHA256_HASH_SIZE]) -> Self {
+        AppHash(value.to_vec())
+    }
+}
+
+impl TryFrom<AppHash> for [u8; SHA256_HASH_SIZE] {
+    type Error = Error;
+
+    fn try_from(value: AppHash) -> Result<Self, Self::Error> {
+        value
+            .0
+            .try_into()
+            .map_err(|_| Error::invalid_app_hash_length())
+    }
+}
+
+impl From<GenericArray<u8, U32>> for AppHash {
+    fn from(value: GenericArray<u8, U32>) -> Self {
+        AppHash(value.to_vec())
+    }
+}
+
+impl TryFrom<AppHash> for GenericArray<u8, U32> {
+    type Error = Error;
+
+    fn try_from(value: AppHash) -> Result<Self, Self::Error> {
+        <[u8; SHA256_HASH_SIZE]>::try_from(value).map(Into::into)
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<primitive_types::H256> for AppHash {
+    fn from(value: primitive_types::H256) -> Self {
+        AppHash(value.0.to_vec())
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl TryFrom<AppHash> for primitive_types::H256 {
+    type Error = Error;
+
+    fn try_from(value: AppHash) -> Result<Self, Self::Error> {
+        <[u8; SHA256_HASH_SIZE]>::try_from(value).map(primitive_types::H256)
+    }
+}
+
+/// An empty app hash, or the bytes of a SHA-256 hash.
+impl From<Hash> for AppHash {
+    fn from(value: Hash) -> Self {
+        AppHash(value.into())
+    }
+}
+
 impl AppHash {
     /// Return the hash bytes as a byte slice.
     pub fn as_bytes(&self) -> &[u8] {
@@ -290,3 +469,52 @@ impl FromStr for AppHash {
         Self::from_hex_upper(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; SHA256_HASH_SIZE] = [0xAB; SHA256_HASH_SIZE];
+    const HASH: Hash = Hash::from_sha256(BYTES);
+
+    #[test]
+    fn hash_array_conversions() {
+        assert_eq!(Hash::from(BYTES), HASH);
+        assert_eq!(<[u8; SHA256_HASH_SIZE]>::try_from(HASH).unwrap(), BYTES);
+        assert!(<[u8; SHA256_HASH_SIZE]>::try_from(Hash::None).is_err());
+        assert_eq!(HASH.as_sha256(), Some(&BYTES));
+        assert_eq!(Hash::None.as_sha256(), None);
+
+        let array = GenericArray::<u8, U32>::from(BYTES);
+        assert_eq!(Hash::from(array), HASH);
+        assert_eq!(GenericArray::<u8, U32>::try_from(HASH).unwrap(), array);
+    }
+
+    #[test]
+    fn app_hash_array_conversions() {
+        let app_hash = AppHash::from(BYTES);
+        assert_eq!(app_hash.as_bytes(), &BYTES);
+        assert_eq!(AppHash::from(HASH), app_hash);
+        assert_eq!(AppHash::from(Hash::None), AppHash::default());
+        assert_eq!(
+            <[u8; SHA256_HASH_SIZE]>::try_from(app_hash.clone()).unwrap(),
+            BYTES
+        );
+        assert!(<[u8; SHA256_HASH_SIZE]>::try_from(AppHash::default()).is_err());
+        assert_eq!(
+            GenericArray::<u8, U32>::try_from(app_hash).unwrap(),
+            GenericArray::from(BYTES)
+        );
+    }
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn h256_conversions() {
+        use primitive_types::H256;
+
+        assert_eq!(Hash::from(H256(BYTES)), HASH);
+        assert_eq!(H256::try_from(HASH).unwrap(), H256(BYTES));
+        assert!(H256::try_from(Hash::None).is_err());
+        assert_eq!(H256::try_from(AppHash::from(BYTES)).unwrap(), H256(BYTES));
+    }
+}
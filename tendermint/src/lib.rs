@@ -3,6 +3,20 @@
 //! This crate provides core types for representing information about Tendermint
 //! blockchain networks, including chain information types, secret connections,
 //! and remote procedure calls (JSON-RPC).
+//!
+//! The crate is `no_std`, and only requires `alloc` without its default
+//! features, so that on-chain light clients (e.g. CosmWasm contracts,
+//! Substrate pallets or SGX enclaves) can use the same domain types:
+//!
+//! * `std` (default) - Implements `std::error::Error` for the errors, and
+//!   enables `clock`.
+//! * `clock` - Provides `Time::now`, from the system clock. Without it, the
+//!   current time is given by the host, e.g. to the light client verifier
+//!   through its `Clock`.
+//! * `rust-crypto` (default) - Hashes and verifies Ed25519 signatures with
+//!   pure Rust implementations, which also build without `std`.
+//! * `secp256k1` - Supports secp256k1 public keys.
+//! * `bech32` - Encodes account IDs as bech32 addresses.
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -30,6 +44,7 @@ pub mod error;
 
 pub mod abci;
 pub mod account;
+mod alloc_prelude;
 pub mod block;
 pub mod chain;
 pub mod channel;
@@ -41,13 +56,14 @@ pub mod hash;
 pub mod merkle;
 mod moniker;
 pub mod node;
-mod prelude;
+pub mod prelude;
 pub mod private_key;
 pub mod privval;
 pub mod proposal;
 pub mod public_key;
 pub mod serializers;
 pub mod signature;
+pub mod signed_msg_type;
 pub mod time;
 mod timeout;
 pub mod trust_threshold;
@@ -72,6 +88,7 @@ pub use crate::{
     proposal::Proposal,
     public_key::{PublicKey, TendermintKey},
     signature::Signature,
+    signed_msg_type::SignedMsgType,
     time::Time,
     timeout::Timeout,
     version::Version,
@@ -8,8 +8,8 @@ use core::marker::PhantomData;
 
 use digest::{consts::U32, Digest, FixedOutputReset};
 
+use crate::alloc_prelude::*;
 use crate::crypto::Sha256;
-use crate::prelude::*;
 
 /// Size of Merkle root hash
 pub use crate::crypto::sha256::HASH_SIZE;
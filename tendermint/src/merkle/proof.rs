@@ -1,9 +1,16 @@
 //! Merkle proofs
 
 use serde::{Deserialize, Serialize};
-use tendermint_proto::v0_37::crypto::Proof as RawProof;
+use tendermint_proto::{
+    v0_37::crypto::{Proof as RawProof, ValueOp as RawValueOp},
+    Protobuf,
+};
 
-use crate::{prelude::*, serializers, Hash};
+use super::MerkleHash;
+use crate::{alloc_prelude::*, crypto::Sha256, serializers, Error, Hash};
+
+/// Maximum number of aunts in a proof, as accepted by CometBFT.
+const MAX_AUNTS: usize = 100;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "RawProof", into = "RawProof")]
@@ -18,6 +25,194 @@ pub struct Proof {
     pub aunts: Vec<Hash>,
 }
 
+impl Proof {
+    /// Verifies that `leaf` is the item at `index` in the Merkle tree with the
+    /// given root hash.
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify(&self, root_hash: Hash, leaf: &[u8]) -> Result<(), Error> {
+        self.verify_with::<crate::crypto::default::Sha256>(root_hash, leaf)
+    }
+
+    /// Verifies that `leaf` is the item at `index` in the Merkle tree with the
+    /// given root hash, using a Merkle hasher provided by a crypto provider.
+    pub fn verify_with<H>(&self, root_hash: Hash, leaf: &[u8]) -> Result<(), Error>
+    where
+        H: MerkleHash + Default,
+    {
+        let computed = self.compute_root_with::<H>(leaf)?;
+        if computed != root_hash {
+            return Err(Error::invalid_merkle_proof(format!(
+                "root hash mismatch: expected {}, computed {}",
+                root_hash, computed
+            )));
+        }
+        Ok(())
+    }
+
+    /// Computes the root hash of the Merkle tree in which `leaf` is the item
+    /// at `index`, using a Merkle hasher provided by a crypto provider.
+    pub fn compute_root_with<H>(&self, leaf: &[u8]) -> Result<Hash, Error>
+    where
+        H: MerkleHash + Default,
+    {
+        if self.aunts.len() > MAX_AUNTS {
+            return Err(Error::invalid_merkle_proof(format!(
+                "too many aunts: {} (max {})",
+                self.aunts.len(),
+                MAX_AUNTS
+            )));
+        }
+
+        let mut hasher = H::default();
+        let leaf_hash = hasher.leaf_hash(leaf);
+        if Hash::Sha256(leaf_hash) != self.leaf_hash {
+            return Err(Error::invalid_merkle_proof(format!(
+                "leaf hash mismatch: expected {}, got {}",
+                self.leaf_hash,
+                Hash::Sha256(leaf_hash)
+            )));
+        }
+
+        let aunts = self
+            .aunts
+            .iter()
+            .map(|aunt| match aunt {
+                Hash::Sha256(hash) => Ok(*hash),
+                Hash::None => Err(Error::invalid_merkle_proof("empty aunt hash".to_string())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        compute_hash_from_aunts(&mut hasher, self.index, self.total, leaf_hash, &aunts)
+            .map(Hash::Sha256)
+            .ok_or_else(|| {
+                Error::invalid_merkle_proof(format!(
+                    "aunts do not match index {} of a tree of {} items",
+                    self.index, self.total
+                ))
+            })
+    }
+}
+
+/// Computes the root of a tree of `total` items from the hash of the item at
+/// `index` and its aunts, ordered from the leaf's sibling up to the root's
+/// child. Returns `None` if the number of aunts does not match the position of
+/// the item in the tree.
+fn compute_hash_from_aunts<H: MerkleHash>(
+    hasher: &mut H,
+    index: u64,
+    total: u64,
+    leaf_hash: super::Hash,
+    aunts: &[super::Hash],
+) -> Option<super::Hash> {
+    if index >= total {
+        return None;
+    }
+    match total {
+        1 if aunts.is_empty() => Some(leaf_hash),
+        1 => None,
+        _ => {
+            let (aunt, rest) = aunts.split_last()?;
+            let split = total.next_power_of_two() / 2;
+            if index < split {
+                let left = compute_hash_from_aunts(hasher, index, split, leaf_hash, rest)?;
+                Some(hasher.inner_hash(left, *aunt))
+            } else {
+                let right =
+                    compute_hash_from_aunts(hasher, index - split, total - split, leaf_hash, rest)?;
+                Some(hasher.inner_hash(*aunt, right))
+            }
+        },
+    }
+}
+
+/// Type of the [`ProofOp`]s which carry a [`ValueOp`].
+pub const VALUE_OP_TYPE: &str = "simple:v";
+
+/// Proof that a value is stored at a key of a simple Merkle tree, as carried
+/// by the [`ProofOp`]s of type [`VALUE_OP_TYPE`].
+/// <https://github.com/cometbft/cometbft/blob/v0.37.0/crypto/merkle/proof_value.go>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueOp {
+    /// Key of the value
+    pub key: Vec<u8>,
+    /// Proof of the key/value pair in the tree
+    pub proof: Proof,
+}
+
+impl ValueOp {
+    /// Verifies that `value` is stored at the key of this operation in the
+    /// Merkle tree with the given root hash.
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify(&self, root_hash: Hash, value: &[u8]) -> Result<(), Error> {
+        self.verify_with::<crate::crypto::default::Sha256>(root_hash, value)
+    }
+
+    /// Verifies that `value` is stored at the key of this operation in the
+    /// Merkle tree with the given root hash, using a hasher provided by a
+    /// crypto provider.
+    pub fn verify_with<H>(&self, root_hash: Hash, value: &[u8]) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        self.proof
+            .verify_with::<H>(root_hash, &self.leaf::<H>(value))
+    }
+
+    /// Computes the root hash of the Merkle tree in which `value` is stored
+    /// at the key of this operation, using a hasher provided by a crypto
+    /// provider.
+    pub fn compute_root_with<H>(&self, value: &[u8]) -> Result<Hash, Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        self.proof.compute_root_with::<H>(&self.leaf::<H>(value))
+    }
+
+    fn leaf<H: Sha256>(&self, value: &[u8]) -> Vec<u8> {
+        // The leaf is the length-prefixed key followed by the
+        // length-prefixed hash of the value.
+        let value_hash = H::digest(value);
+        let mut leaf = Vec::with_capacity(self.key.len() + value_hash.len() + 20);
+        prost::encoding::encode_varint(self.key.len() as u64, &mut leaf);
+        leaf.extend_from_slice(&self.key);
+        prost::encoding::encode_varint(value_hash.len() as u64, &mut leaf);
+        leaf.extend_from_slice(&value_hash);
+        leaf
+    }
+}
+
+impl TryFrom<&ProofOp> for ValueOp {
+    type Error = Error;
+
+    fn try_from(op: &ProofOp) -> Result<Self, Self::Error> {
+        if op.field_type != VALUE_OP_TYPE {
+            return Err(Error::invalid_merkle_proof(format!(
+                "unexpected proof operation type: expected {}, got {}",
+                VALUE_OP_TYPE, op.field_type
+            )));
+        }
+
+        let value_op = <Self as Protobuf<RawValueOp>>::decode_vec(&op.data)
+            .map_err(|e| Error::invalid_merkle_proof(format!("malformed value operation: {e}")))?;
+        if value_op.key != op.key {
+            return Err(Error::invalid_merkle_proof(
+                "key mismatch between the proof operation and its value operation".to_string(),
+            ));
+        }
+
+        Ok(value_op)
+    }
+}
+
+impl From<ValueOp> for ProofOp {
+    fn from(value_op: ValueOp) -> Self {
+        Self {
+            field_type: VALUE_OP_TYPE.to_string(),
+            key: value_op.key.clone(),
+            data: prost::Message::encode_to_vec(&RawValueOp::from(value_op)),
+        }
+    }
+}
+
 /// Merkle proof defined by the list of ProofOps
 /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -26,6 +221,73 @@ pub struct ProofOps {
     pub ops: Vec<ProofOp>,
 }
 
+impl ProofOps {
+    /// Verifies that `value` is stored at the given key path in the Merkle
+    /// tree with the given root hash.
+    ///
+    /// See [`ProofOps::verify_value_with`].
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify_value(
+        &self,
+        root_hash: Hash,
+        keypath: &[&[u8]],
+        value: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_value_with::<crate::crypto::default::Sha256>(root_hash, keypath, value)
+    }
+
+    /// Verifies that `value` is stored at the given key path in the Merkle
+    /// tree with the given root hash, using a hasher provided by a crypto
+    /// provider.
+    ///
+    /// The key path lists the keys from the outermost tree to the innermost
+    /// one, e.g. the name of a store and then the key of the value in that
+    /// store. Each operation, from the innermost one, proves the root computed
+    /// by the previous one under the matching key, starting from the value.
+    /// Only the operations of type [`VALUE_OP_TYPE`] are supported.
+    pub fn verify_value_with<H>(
+        &self,
+        root_hash: Hash,
+        keypath: &[&[u8]],
+        value: &[u8],
+    ) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        if self.ops.len() != keypath.len() {
+            return Err(Error::invalid_merkle_proof(format!(
+                "expected {} proof operations for the key path, got {}",
+                keypath.len(),
+                self.ops.len()
+            )));
+        }
+
+        let mut value = value.to_vec();
+        let mut computed = None;
+        for (op, key) in self.ops.iter().zip(keypath.iter().rev()) {
+            if op.key != *key {
+                return Err(Error::invalid_merkle_proof(
+                    "key mismatch between the proof operation and the key path".to_string(),
+                ));
+            }
+            let root = ValueOp::try_from(op)?.compute_root_with::<H>(&value)?;
+            value = root.as_bytes().to_vec();
+            computed = Some(root);
+        }
+
+        match computed {
+            Some(computed) if computed == root_hash => Ok(()),
+            Some(computed) => Err(Error::invalid_merkle_proof(format!(
+                "root hash mismatch: expected {}, computed {}",
+                root_hash, computed
+            ))),
+            None => Err(Error::invalid_merkle_proof(
+                "no proof operations".to_string(),
+            )),
+        }
+    }
+}
+
 /// ProofOp defines an operation used for calculating Merkle root
 /// The data could be arbitrary format, providing necessary data
 /// for example neighbouring node hash
@@ -48,10 +310,13 @@ pub struct ProofOp {
 // =============================================================================
 
 tendermint_pb_modules! {
-    use super::{Proof, ProofOp, ProofOps};
-    use crate::{prelude::*, Error};
+    use super::{Proof, ProofOp, ProofOps, ValueOp};
+    use crate::{alloc_prelude::*, Error};
     use pb::{
-        crypto::{Proof as RawProof, ProofOp as RawProofOp, ProofOps as RawProofOps},
+        crypto::{
+            Proof as RawProof, ProofOp as RawProofOp, ProofOps as RawProofOps,
+            ValueOp as RawValueOp,
+        },
     };
 
     impl Protobuf<RawProof> for Proof {}
@@ -93,6 +358,31 @@ tendermint_pb_modules! {
         }
     }
 
+    impl Protobuf<RawValueOp> for ValueOp {}
+
+    impl TryFrom<RawValueOp> for ValueOp {
+        type Error = Error;
+
+        fn try_from(message: RawValueOp) -> Result<Self, Self::Error> {
+            Ok(Self {
+                key: message.key,
+                proof: message
+                    .proof
+                    .ok_or_else(|| Error::invalid_merkle_proof("missing proof".to_string()))?
+                    .try_into()?,
+            })
+        }
+    }
+
+    impl From<ValueOp> for RawValueOp {
+        fn from(value: ValueOp) -> Self {
+            Self {
+                key: value.key,
+                proof: Some(value.proof.into()),
+            }
+        }
+    }
+
     impl Protobuf<RawProofOp> for ProofOp {}
 
     impl TryFrom<RawProofOp> for ProofOp {
@@ -143,6 +433,92 @@ mod test {
     use super::ProofOps;
     use crate::test::test_serialization_roundtrip;
 
+    #[cfg(feature = "rust-crypto")]
+    #[test]
+    fn verifies_value_op() {
+        use super::{Proof, ProofOp, ValueOp};
+        use crate::alloc_prelude::*;
+        use crate::{
+            crypto::{default::Sha256, Sha256 as _},
+            merkle::MerkleHash,
+            Hash,
+        };
+
+        // The leaf of a tree with a single key/value pair.
+        let mut leaf = vec![3];
+        leaf.extend_from_slice(b"key");
+        leaf.push(32);
+        leaf.extend_from_slice(&Sha256::digest(b"value"));
+        let root_hash = Hash::Sha256(Sha256::default().leaf_hash(&leaf));
+
+        let value_op = ValueOp {
+            key: b"key".to_vec(),
+            proof: Proof {
+                total: 1,
+                index: 0,
+                leaf_hash: root_hash,
+                aunts: vec![],
+            },
+        };
+        let op = ProofOp::from(value_op.clone());
+
+        let decoded = ValueOp::try_from(&op).unwrap();
+        assert_eq!(decoded, value_op);
+        decoded.verify(root_hash, b"value").unwrap();
+        assert!(decoded.verify(root_hash, b"other").is_err());
+
+        let other_type = ProofOp {
+            field_type: "ics23:iavl".to_string(),
+            ..op.clone()
+        };
+        assert!(ValueOp::try_from(&other_type).is_err());
+
+        let other_key = ProofOp {
+            key: b"other".to_vec(),
+            ..op
+        };
+        assert!(ValueOp::try_from(&other_key).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rust-crypto")]
+    fn verifies_chained_proof_ops() {
+        use super::{Proof, ProofOps, ValueOp};
+        use crate::alloc_prelude::*;
+        use crate::{crypto::default::Sha256, merkle::MerkleHash, Hash};
+
+        // A value in a store, whose root is in turn stored under the name of
+        // the store.
+        let single_leaf = |key: &[u8], value: &[u8]| {
+            let mut op = ValueOp {
+                key: key.to_vec(),
+                proof: Proof {
+                    total: 1,
+                    index: 0,
+                    leaf_hash: Hash::None,
+                    aunts: vec![],
+                },
+            };
+            let root = Hash::Sha256(Sha256::default().leaf_hash(&op.leaf::<Sha256>(value)));
+            op.proof.leaf_hash = root;
+            (op, root)
+        };
+        let (key_op, store_root) = single_leaf(b"key", b"value");
+        let (store_op, app_hash) = single_leaf(b"bank", store_root.as_bytes());
+
+        let proof = ProofOps {
+            ops: vec![key_op.into(), store_op.into()],
+        };
+        let keypath: &[&[u8]] = &[b"bank", b"key"];
+        proof.verify_value(app_hash, keypath, b"value").unwrap();
+        assert!(proof.verify_value(app_hash, keypath, b"other").is_err());
+        assert!(proof.verify_value(store_root, keypath, b"value").is_err());
+        assert!(proof
+            .verify_value(app_hash, &[b"staking", b"key"], b"value")
+            .is_err());
+        assert!(proof.verify_value(app_hash, &[b"key"], b"value").is_err());
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let payload = r#"
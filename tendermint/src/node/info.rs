@@ -4,7 +4,7 @@ use core::fmt::{self, Display};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{chain, channel::Channels, node, prelude::*, serializers, Moniker, Version};
+use crate::{alloc_prelude::*, chain, channel::Channels, node, serializers, Moniker, Version};
 
 /// Node information
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -1,11 +1,22 @@
-// Re-export according to alloc::prelude::v1 because it is not yet stabilized
-// https://doc.rust-lang.org/src/alloc/prelude/v1.rs.html
-pub use alloc::{
-    borrow::ToOwned,
-    boxed::Box,
-    format,
-    string::{String, ToString},
-    vec,
-    vec::Vec,
+//! The types and traits most commonly needed to work with Tendermint data
+//! structures, to be glob-imported:
+//!
+//! ```
+//! use tendermint::prelude::*;
+//! ```
+
+pub use crate::{
+    account::Id as AccountId,
+    block::{signed_header::SignedHeader, Block, Commit, Header, Height},
+    chain::Id as ChainId,
+    crypto::Sha256,
+    hash::{AppHash, Hash},
+    merkle::MerkleHash,
+    node::Id as NodeId,
+    public_key::PublicKey,
+    signature::Signature,
+    time::Time,
+    trust_threshold::{TrustThreshold, TrustThresholdFraction},
+    validator::{Info as ValidatorInfo, Set as ValidatorSet},
+    vote::Vote,
 };
-pub use core::prelude::v1::*;
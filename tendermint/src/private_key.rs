@@ -1,7 +1,7 @@
 //! Cryptographic private keys
 
+use crate::alloc_prelude::*;
 pub use crate::crypto::ed25519::SigningKey as Ed25519;
-use crate::prelude::*;
 
 #[cfg(feature = "rust-crypto")]
 use crate::public_key::PublicKey;
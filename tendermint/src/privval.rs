@@ -2,7 +2,99 @@
 //!
 //! [ADR-063]: https://github.com/tendermint/tendermint/blob/main/docs/architecture/adr-063-privval-grpc.md
 
-use crate::prelude::*;
+use core::{convert::TryFrom, fmt};
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{alloc_prelude::*, error::Error, signed_msg_type::SignedMsgType};
+
+/// The steps of the privval protocol at which validators sign messages, as
+/// numbered in the state of the signers (e.g. `priv_validator_state.json`).
+///
+/// Signers refuse to sign a message for a height, round and step lower than
+/// the ones of the last message they signed, which prevents them from
+/// double signing.
+#[repr(i8)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Step {
+    /// Nothing was signed yet
+    #[default]
+    None = 0,
+
+    /// Signing a proposal
+    Propose = 1,
+
+    /// Signing a prevote
+    Prevote = 2,
+
+    /// Signing a precommit
+    Precommit = 3,
+}
+
+impl TryFrom<i8> for Step {
+    type Error = Error;
+
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Propose),
+            2 => Ok(Self::Prevote),
+            3 => Ok(Self::Precommit),
+            _ => Err(Error::invalid_privval_step(value)),
+        }
+    }
+}
+
+impl From<Step> for i8 {
+    fn from(value: Step) -> Self {
+        value as i8
+    }
+}
+
+impl From<SignedMsgType> for Step {
+    fn from(value: SignedMsgType) -> Self {
+        value.step()
+    }
+}
+
+impl TryFrom<Step> for SignedMsgType {
+    type Error = Error;
+
+    fn try_from(value: Step) -> Result<Self, Self::Error> {
+        match value {
+            Step::Propose => Ok(Self::Proposal),
+            Step::Prevote => Ok(Self::Prevote),
+            Step::Precommit => Ok(Self::Precommit),
+            Step::None => Err(Error::invalid_privval_step(value.into())),
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::None => "None",
+            Self::Propose => "Propose",
+            Self::Prevote => "Prevote",
+            Self::Precommit => "Precommit",
+        };
+        write!(f, "{id}")
+    }
+}
+
+impl Serialize for Step {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        i8::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Step {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = i8::deserialize(deserializer)?;
+        Self::try_from(value)
+            .map_err(|_| D::Error::custom(format!("invalid privval step: {value}")))
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RemoteSignerError {
@@ -12,10 +12,10 @@ use tendermint_proto::{Error as ProtobufError, Protobuf};
 
 pub use self::canonical_proposal::CanonicalProposal;
 use crate::{
+    alloc_prelude::*,
     block::{Height, Id as BlockId, Round},
     chain::Id as ChainId,
     consensus::State,
-    prelude::*,
     Signature, Time,
 };
 
@@ -126,10 +126,10 @@ mod tests {
     use time::macros::datetime;
 
     use crate::{
+        alloc_prelude::*,
         block::{parts::Header, Height, Id as BlockId, Round},
         chain::Id as ChainId,
         hash::{Algorithm, Hash},
-        prelude::*,
         proposal::{SignProposalRequest, Type},
         test::dummy_signature,
         Proposal,
@@ -2,9 +2,9 @@
 
 use super::Type;
 use crate::{
+    alloc_prelude::*,
     block::{Height, Id as BlockId, Round},
     chain::Id as ChainId,
-    prelude::*,
     Time,
 };
 
@@ -32,7 +32,7 @@ tendermint_pb_modules! {
         block::{Id as BlockId, Round},
         chain::Id as ChainId,
         error::Error,
-        prelude::*,
+        alloc_prelude::*,
     };
     use super::CanonicalProposal;
     use pb::types::CanonicalProposal as RawCanonicalProposal;
@@ -114,7 +114,7 @@ mod tests {
         };
 
         use crate::{
-            prelude::*,
+            alloc_prelude::*,
             proposal::{canonical_proposal::CanonicalProposal, Type},
         };
 
@@ -3,7 +3,7 @@ use core::convert::TryFrom;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error, signed_msg_type::SignedMsgType};
 
 /// Types of proposals
 #[repr(u8)]
@@ -19,16 +19,13 @@ impl TryFrom<i32> for Type {
     type Error = Error;
 
     fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            32 => Ok(Type::Proposal),
-            _ => Err(Error::invalid_message_type()),
-        }
+        SignedMsgType::try_from(value)?.try_into()
     }
 }
 
 impl From<Type> for i32 {
     fn from(value: Type) -> Self {
-        value as i32
+        SignedMsgType::from(value).into()
     }
 }
 
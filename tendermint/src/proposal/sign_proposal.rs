@@ -2,7 +2,7 @@ use bytes::BufMut;
 use tendermint_proto::Error as ProtobufError;
 
 use super::Proposal;
-use crate::{chain::Id as ChainId, prelude::*, privval::RemoteSignerError};
+use crate::{alloc_prelude::*, chain::Id as ChainId, privval::RemoteSignerError};
 
 /// SignProposalRequest is a request to sign a proposal
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -47,7 +47,7 @@ tendermint_pb_modules! {
         SignProposalRequest as RawSignProposalRequest,
         SignedProposalResponse as RawSignedProposalResponse,
     };
-    use crate::{Error, Proposal, chain::Id as ChainId, prelude::*};
+    use crate::{Error, Proposal, chain::Id as ChainId, alloc_prelude::*};
     use super::{SignProposalRequest, SignedProposalResponse};
 
     impl Protobuf<RawSignProposalRequest> for SignProposalRequest {}
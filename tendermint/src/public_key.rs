@@ -16,7 +16,7 @@ use serde_json::Value;
 use subtle_encoding::{base64, bech32, hex};
 
 pub use crate::crypto::ed25519::VerificationKey as Ed25519;
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 // Note:On the golang side this is generic in the sense that it could everything that implements
 // github.com/tendermint/tendermint/crypto.PubKey
@@ -70,6 +70,29 @@ impl From<ProtobufPublicKeyWrapper> for PublicKey {
     }
 }
 
+// Internal thunk type to facilitate deserialization of the ABCI public keys
+// found in the JSON responses of Tendermint 0.33 and earlier.
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "data")]
+enum LegacyAbciPublicKey {
+    #[serde(rename = "ed25519", deserialize_with = "deserialize_ed25519_base64")]
+    Ed25519(Ed25519),
+
+    #[cfg(feature = "secp256k1")]
+    #[serde(rename = "secp256k1", deserialize_with = "deserialize_secp256k1_base64")]
+    Secp256k1(Secp256k1),
+}
+
+impl From<LegacyAbciPublicKey> for PublicKey {
+    fn from(key: LegacyAbciPublicKey) -> Self {
+        match key {
+            LegacyAbciPublicKey::Ed25519(pk) => PublicKey::Ed25519(pk),
+            #[cfg(feature = "secp256k1")]
+            LegacyAbciPublicKey::Secp256k1(pk) => PublicKey::Secp256k1(pk),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")] // JSON custom serialization for priv_validator_key.json
 enum ProtobufPublicKey {
@@ -104,11 +127,11 @@ where
     D: Deserializer<'de>,
 {
     let v = Value::deserialize(deserializer)?;
-    if v.as_object()
-        .map(|obj| obj.contains_key("Sum"))
-        .unwrap_or(false)
-    {
+    let has_key = |key: &str| v.as_object().map(|obj| obj.contains_key(key)).unwrap_or(false);
+    if has_key("Sum") {
         serde_json::from_value::<ProtobufPublicKeyWrapper>(v).map(Into::into)
+    } else if has_key("data") {
+        serde_json::from_value::<LegacyAbciPublicKey>(v).map(Into::into)
     } else {
         serde_json::from_value::<PublicKey>(v)
     }
@@ -118,7 +141,7 @@ where
 tendermint_pb_modules! {
     use super::{PublicKey, Ed25519};
     use pb::crypto::{PublicKey as RawPublicKey, public_key::Sum};
-    use crate::{prelude::*, Error};
+    use crate::{alloc_prelude::*, Error};
 
     impl Protobuf<RawPublicKey> for PublicKey {}
 
@@ -404,7 +427,7 @@ mod tests {
     use subtle_encoding::hex;
 
     use super::{PublicKey, TendermintKey};
-    use crate::{prelude::*, public_key::PubKeyResponse};
+    use crate::{alloc_prelude::*, public_key::PubKeyResponse};
 
     const EXAMPLE_CONSENSUS_KEY: &str =
         "4A25C6640A1F72B9C975338294EF51B6D1C33158BB6ECBA69FBC3FB5A33C9DCE";
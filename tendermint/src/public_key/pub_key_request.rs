@@ -1,4 +1,4 @@
-use crate::{chain::Id as ChainId, prelude::*};
+use crate::{alloc_prelude::*, chain::Id as ChainId};
 
 /// PubKeyRequest requests the consensus public key from the remote signer.
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -9,7 +9,7 @@ pub struct PubKeyRequest {
 
 tendermint_pb_modules! {
     use super::PubKeyRequest;
-    use crate::{chain::Id as ChainId, prelude::*};
+    use crate::{chain::Id as ChainId, alloc_prelude::*};
     use pb::privval::PubKeyRequest as RawPubKeyRequest;
 
     impl Protobuf<RawPubKeyRequest> for PubKeyRequest {}
@@ -38,7 +38,7 @@ mod tests {
     tendermint_pb_modules! {
         use super::super::PubKeyRequest;
         use pb::privval::PubKeyRequest as RawPubKeyRequest;
-        use crate::{chain::Id as ChainId, prelude::*};
+        use crate::{chain::Id as ChainId, alloc_prelude::*};
         use core::str::FromStr;
 
         #[test]
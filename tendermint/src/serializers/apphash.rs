@@ -3,7 +3,7 @@
 use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::hex;
 
-use crate::{prelude::*, AppHash};
+use crate::{alloc_prelude::*, AppHash};
 
 /// Deserialize hexstring into AppHash
 pub fn deserialize<'de, D>(deserializer: D) -> Result<AppHash, D::Error>
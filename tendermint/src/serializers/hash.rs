@@ -3,7 +3,7 @@
 use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::hex;
 
-use crate::{hash::Algorithm, prelude::*, Hash};
+use crate::{alloc_prelude::*, hash::Algorithm, Hash};
 
 /// Deserialize hexstring into Hash
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
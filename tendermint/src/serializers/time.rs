@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{prelude::*, Time};
+use crate::{alloc_prelude::*, Time};
 
 /// Serialize from `Time` into `String`
 pub fn serialize<S>(value: &Time, serializer: S) -> Result<S::Ok, S::Error>
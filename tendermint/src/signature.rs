@@ -7,7 +7,7 @@ pub use ed25519::Signature as Ed25519Signature;
 pub use k256::ecdsa::Signature as Secp256k1Signature;
 use tendermint_proto::Protobuf;
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// The expected length of all currently supported signatures, in bytes.
 pub const SIGNATURE_LENGTH: usize = 64;
@@ -0,0 +1,221 @@
+//! Types of the messages signed by validators
+
+use core::{convert::TryFrom, fmt};
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use tendermint_proto::Protobuf;
+
+use crate::{alloc_prelude::*, error::Error, privval::Step, proposal, vote};
+
+/// Types of the messages signed by validators, i.e. of the votes and of the
+/// proposals.
+///
+/// This is the `SignedMsgType` of the Tendermint protobuf definitions, whose
+/// values are the ones of [`vote::Type`] and [`proposal::Type`].
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum SignedMsgType {
+    /// Votes for blocks which validators observe are valid for a given round
+    Prevote = 1,
+
+    /// Votes to commit to a particular block for a given round
+    Precommit = 2,
+
+    /// Proposals of blocks
+    Proposal = 32,
+}
+
+impl SignedMsgType {
+    /// Is this the type of a vote?
+    pub fn is_vote(self) -> bool {
+        matches!(self, Self::Prevote | Self::Precommit)
+    }
+
+    /// The step of the privval protocol at which a message of this type is
+    /// signed.
+    pub fn step(self) -> Step {
+        match self {
+            Self::Proposal => Step::Propose,
+            Self::Prevote => Step::Prevote,
+            Self::Precommit => Step::Precommit,
+        }
+    }
+}
+
+impl Protobuf<i32> for SignedMsgType {}
+
+impl TryFrom<i32> for SignedMsgType {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Prevote),
+            2 => Ok(Self::Precommit),
+            32 => Ok(Self::Proposal),
+            _ => Err(Error::invalid_message_type()),
+        }
+    }
+}
+
+impl From<SignedMsgType> for i32 {
+    fn from(value: SignedMsgType) -> Self {
+        value as i32
+    }
+}
+
+impl From<vote::Type> for SignedMsgType {
+    fn from(value: vote::Type) -> Self {
+        match value {
+            vote::Type::Prevote => Self::Prevote,
+            vote::Type::Precommit => Self::Precommit,
+        }
+    }
+}
+
+impl From<proposal::Type> for SignedMsgType {
+    fn from(value: proposal::Type) -> Self {
+        match value {
+            proposal::Type::Proposal => Self::Proposal,
+        }
+    }
+}
+
+impl TryFrom<SignedMsgType> for vote::Type {
+    type Error = Error;
+
+    fn try_from(value: SignedMsgType) -> Result<Self, Self::Error> {
+        match value {
+            SignedMsgType::Prevote => Ok(Self::Prevote),
+            SignedMsgType::Precommit => Ok(Self::Precommit),
+            SignedMsgType::Proposal => Err(Error::invalid_message_type()),
+        }
+    }
+}
+
+impl TryFrom<SignedMsgType> for proposal::Type {
+    type Error = Error;
+
+    fn try_from(value: SignedMsgType) -> Result<Self, Self::Error> {
+        match value {
+            SignedMsgType::Proposal => Ok(Self::Proposal),
+            SignedMsgType::Prevote | SignedMsgType::Precommit => Err(Error::invalid_message_type()),
+        }
+    }
+}
+
+impl fmt::Display for SignedMsgType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::Prevote => "Prevote",
+            Self::Precommit => "Precommit",
+            Self::Proposal => "Proposal",
+        };
+        write!(f, "{id}")
+    }
+}
+
+impl Serialize for SignedMsgType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        i32::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SignedMsgType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = i32::deserialize(deserializer)?;
+        Self::try_from(value)
+            .map_err(|_| D::Error::custom(format!("invalid signed message type: {value}")))
+    }
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+tendermint_pb_modules! {
+    use pb::types::SignedMsgType as RawSignedMsgType;
+
+    use super::SignedMsgType;
+    use crate::Error;
+
+    impl TryFrom<RawSignedMsgType> for SignedMsgType {
+        type Error = Error;
+
+        fn try_from(value: RawSignedMsgType) -> Result<Self, Self::Error> {
+            match value {
+                RawSignedMsgType::Prevote => Ok(Self::Prevote),
+                RawSignedMsgType::Precommit => Ok(Self::Precommit),
+                RawSignedMsgType::Proposal => Ok(Self::Proposal),
+                RawSignedMsgType::Unknown => Err(Error::invalid_message_type()),
+            }
+        }
+    }
+
+    impl From<SignedMsgType> for RawSignedMsgType {
+        fn from(value: SignedMsgType) -> Self {
+            match value {
+                SignedMsgType::Prevote => Self::Prevote,
+                SignedMsgType::Precommit => Self::Precommit,
+                SignedMsgType::Proposal => Self::Proposal,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint_proto::{v0_34, v0_37};
+
+    use super::*;
+
+    const ALL: [SignedMsgType; 3] = [
+        SignedMsgType::Prevote,
+        SignedMsgType::Precommit,
+        SignedMsgType::Proposal,
+    ];
+
+    #[test]
+    fn proto_round_trip() {
+        for msg_type in ALL {
+            let raw = v0_34::types::SignedMsgType::from(msg_type);
+            assert_eq!(raw as i32, i32::from(msg_type));
+            assert_eq!(SignedMsgType::try_from(raw).unwrap(), msg_type);
+
+            let raw = v0_37::types::SignedMsgType::from(msg_type);
+            assert_eq!(raw as i32, i32::from(msg_type));
+            assert_eq!(SignedMsgType::try_from(raw).unwrap(), msg_type);
+
+            assert_eq!(
+                SignedMsgType::try_from(i32::from(msg_type)).unwrap(),
+                msg_type
+            );
+        }
+        assert!(SignedMsgType::try_from(v0_37::types::SignedMsgType::Unknown).is_err());
+        assert!(SignedMsgType::try_from(0).is_err());
+    }
+
+    #[test]
+    fn vote_and_proposal_types() {
+        for vote_type in [vote::Type::Prevote, vote::Type::Precommit] {
+            let msg_type = SignedMsgType::from(vote_type);
+            assert!(msg_type.is_vote());
+            assert_eq!(i32::from(msg_type), i32::from(vote_type));
+            assert_eq!(vote::Type::try_from(msg_type).unwrap(), vote_type);
+            assert!(proposal::Type::try_from(msg_type).is_err());
+        }
+
+        let msg_type = SignedMsgType::from(proposal::Type::Proposal);
+        assert!(!msg_type.is_vote());
+        assert_eq!(i32::from(msg_type), i32::from(proposal::Type::Proposal));
+        assert!(vote::Type::try_from(msg_type).is_err());
+    }
+
+    #[test]
+    fn steps() {
+        let steps: Vec<_> = ALL.iter().map(|msg_type| msg_type.step()).collect();
+        assert_eq!(steps, [Step::Prevote, Step::Precommit, Step::Propose]);
+        for msg_type in ALL {
+            assert_eq!(SignedMsgType::try_from(msg_type.step()).unwrap(), msg_type);
+        }
+    }
+}
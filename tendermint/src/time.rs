@@ -16,7 +16,7 @@ use time::{
     OffsetDateTime, PrimitiveDateTime,
 };
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Tendermint timestamps
 ///
@@ -64,6 +64,12 @@ impl From<Time> for Timestamp {
 }
 
 impl Time {
+    /// The earliest valid time, `0001-01-01T00:00:00Z`.
+    pub const MIN: Time = Time(datetime!(0001-01-01 00:00:00));
+
+    /// The latest valid time, `9999-12-31T23:59:59.999999999Z`.
+    pub const MAX: Time = Time(datetime!(9999-12-31 23:59:59.999_999_999));
+
     #[cfg(any(feature = "clock"))]
     pub fn now() -> Time {
         OffsetDateTime::now_utc().try_into().unwrap()
@@ -98,19 +104,29 @@ impl Time {
 
     /// Calculate the amount of time which has passed since another [`Time`]
     /// as a [`core::time::Duration`]
+    ///
+    /// Fails if `other` is later than `self`, see
+    /// [`Time::signed_duration_since`] for the times which may be in any
+    /// order.
     pub fn duration_since(&self, other: Time) -> Result<Duration, Error> {
-        let duration = self.0.assume_utc() - other.0.assume_utc();
-        duration
+        self.signed_duration_since(other)
             .try_into()
             .map_err(|_| Error::duration_out_of_range())
     }
 
+    /// Calculate the amount of time which has passed since another [`Time`],
+    /// which is negative if `other` is later than `self`.
+    ///
+    /// Unlike [`Time::duration_since`], this never fails: the difference
+    /// between any two valid times is representable.
+    pub fn signed_duration_since(&self, other: Time) -> time::Duration {
+        self.0 - other.0
+    }
+
     /// Parse [`Time`] from an RFC 3339 date
     pub fn parse_from_rfc3339(s: &str) -> Result<Self, Error> {
-        let date = OffsetDateTime::parse(s, &Rfc3339)
-            .map_err(Error::time_parse)?
-            .to_offset(offset!(UTC));
-        Self::from_utc(date)
+        let date = OffsetDateTime::parse(s, &Rfc3339).map_err(Error::time_parse)?;
+        Self::from_utc(timestamp::checked_to_utc(date).ok_or_else(Error::date_out_of_range)?)
     }
 
     /// Return an RFC 3339 and ISO 8601 date and time string with subseconds (if nonzero) and Z.
@@ -141,6 +157,16 @@ impl Time {
         let t = self.0.checked_sub(duration)?;
         Self::from_utc(t.assume_utc()).ok()
     }
+
+    /// Computes `self + duration`, saturating at [`Time::MAX`].
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        self.checked_add(duration).unwrap_or(Self::MAX)
+    }
+
+    /// Computes `self - duration`, saturating at [`Time::MIN`].
+    pub fn saturating_sub(self, duration: Duration) -> Self {
+        self.checked_sub(duration).unwrap_or(Self::MIN)
+    }
 }
 
 impl fmt::Display for Time {
@@ -161,7 +187,7 @@ impl TryFrom<OffsetDateTime> for Time {
     type Error = Error;
 
     fn try_from(t: OffsetDateTime) -> Result<Time, Error> {
-        Self::from_utc(t.to_offset(offset!(UTC)))
+        Self::from_utc(timestamp::checked_to_utc(t).ok_or_else(Error::date_out_of_range)?)
     }
 }
 
@@ -334,6 +360,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bounds_are_the_valid_range() {
+        assert_eq!(Time::MIN.to_rfc3339(), "0001-01-01T00:00:00Z");
+        assert_eq!(Time::MAX.to_rfc3339(), "9999-12-31T23:59:59.999999999Z");
+        assert_eq!(Time::MAX.checked_add(Duration::from_nanos(1)), None);
+        assert_eq!(Time::MIN.checked_sub(Duration::from_nanos(1)), None);
+        assert_eq!(Time::MAX.saturating_add(Duration::from_secs(1)), Time::MAX);
+        assert_eq!(Time::MIN.saturating_sub(Duration::MAX), Time::MIN);
+        let t = Time::unix_epoch();
+        assert_eq!(t.saturating_add(Duration::from_secs(1)).unix_timestamp(), 1);
+    }
+
+    #[test]
+    fn signed_duration_since() {
+        let earlier = Time::from_unix_timestamp(100, 0).unwrap();
+        let later = Time::from_unix_timestamp(101, 500).unwrap();
+        assert_eq!(
+            later.signed_duration_since(earlier),
+            time::Duration::new(1, 500)
+        );
+        assert_eq!(
+            earlier.signed_duration_since(later),
+            time::Duration::new(-1, -500)
+        );
+        assert_eq!(
+            later.duration_since(earlier).unwrap(),
+            Duration::new(1, 500)
+        );
+        assert!(matches!(
+            earlier.duration_since(later).unwrap_err().detail(),
+            ErrorDetail::DurationOutOfRange(_)
+        ));
+        assert_eq!(
+            Time::MAX
+                .signed_duration_since(Time::MIN)
+                .whole_nanoseconds(),
+            Time::MAX.unix_timestamp_nanos() - Time::MIN.unix_timestamp_nanos()
+        );
+    }
+
+    #[test]
+    fn serde_rejects_out_of_range_times() {
+        for json in [
+            r#""9999-12-31T23:59:59.999999999-00:01""#,
+            r#""0001-01-01T00:00:00+00:01""#,
+            r#""10000-01-01T00:00:00Z""#,
+        ] {
+            assert!(serde_json::from_str::<Time>(json).is_err(), "{json}");
+        }
+        assert_eq!(
+            serde_json::from_str::<Time>(r#""0001-01-01T00:59:00+00:59""#).unwrap(),
+            Time::MIN
+        );
+
+        let out_of_range = Timestamp {
+            seconds: Time::MAX.unix_timestamp() + 1,
+            nanos: 0,
+        };
+        assert!(Time::try_from(out_of_range.clone()).is_err());
+        assert!(serde_json::to_string(&out_of_range).is_err());
+    }
+
     fn duration_from_nanos(whole_nanos: u128) -> Duration {
         let secs: u64 = (whole_nanos / 1_000_000_000).try_into().unwrap();
         let nanos = (whole_nanos % 1_000_000_000) as u32;
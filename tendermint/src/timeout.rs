@@ -2,7 +2,7 @@ use core::{fmt, ops::Deref, str::FromStr, time::Duration};
 
 use serde::{de, de::Error as _, ser, Deserialize, Serialize};
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Timeout durations
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
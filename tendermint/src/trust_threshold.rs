@@ -7,7 +7,7 @@ use core::{
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{error::Error, prelude::*, serializers};
+use crate::{alloc_prelude::*, error::Error, serializers};
 
 /// TrustThreshold defines how much of the total voting power of a known
 /// and trusted validator set is sufficient for a commit to be
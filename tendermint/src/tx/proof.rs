@@ -2,7 +2,12 @@ use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::TxProof as RawTxProof;
 use tendermint_proto::Protobuf;
 
-use crate::{merkle, prelude::*, Error, Hash};
+use crate::{
+    alloc_prelude::*,
+    crypto::Sha256,
+    merkle::{self, MerkleHash},
+    Error, Hash,
+};
 
 /// Merkle proof of the presence of a transaction in the Merkle tree.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +18,40 @@ pub struct Proof {
     pub proof: merkle::Proof,
 }
 
+impl Proof {
+    /// Verifies that `tx` is included in the block whose header has the given
+    /// data hash.
+    ///
+    /// Together with a verified header, this proves that the transaction
+    /// returned by the `/tx` RPC endpoint with `prove=true` was included in the
+    /// block.
+    #[cfg(feature = "rust-crypto")]
+    pub fn verify(&self, data_hash: Hash, tx: &[u8]) -> Result<(), Error> {
+        self.verify_with::<crate::crypto::default::Sha256>(data_hash, tx)
+    }
+
+    /// Verifies that `tx` is included in the block whose header has the given
+    /// data hash, using a Merkle hasher provided by a crypto provider.
+    pub fn verify_with<H>(&self, data_hash: Hash, tx: &[u8]) -> Result<(), Error>
+    where
+        H: MerkleHash + Sha256 + Default,
+    {
+        if self.data != tx {
+            return Err(Error::invalid_tx_proof(
+                "proof is for a different transaction".to_string(),
+            ));
+        }
+        if self.root_hash != data_hash {
+            return Err(Error::invalid_tx_proof(format!(
+                "root hash mismatch: expected data hash {}, got {}",
+                data_hash, self.root_hash
+            )));
+        }
+        // The leaves of the data hash tree are the hashes of the transactions.
+        self.proof.verify_with::<H>(self.root_hash, &H::digest(tx))
+    }
+}
+
 impl Protobuf<RawTxProof> for Proof {}
 
 impl TryFrom<RawTxProof> for Proof {
@@ -36,3 +75,118 @@ impl From<Proof> for RawTxProof {
         }
     }
 }
+
+#[cfg(all(test, feature = "rust-crypto"))]
+mod tests {
+    use super::*;
+    use crate::{hash::Algorithm, merkle::simple_hash_from_byte_vectors};
+
+    type Sha256 = crate::crypto::default::Sha256;
+
+    fn txs() -> Vec<Vec<u8>> {
+        (0..5).map(|i| format!("tx{i}").into_bytes()).collect()
+    }
+
+    /// Builds a proof for the transaction at `index` the way CometBFT does.
+    fn proof(txs: &[Vec<u8>], index: usize) -> Proof {
+        let leaves: Vec<Vec<u8>> = txs
+            .iter()
+            .map(|tx| <Sha256 as crate::crypto::Sha256>::digest(tx).to_vec())
+            .collect();
+
+        fn collect_aunts(leaves: &[Vec<u8>], index: usize, aunts: &mut Vec<Hash>) {
+            if leaves.len() <= 1 {
+                return;
+            }
+            let split = leaves.len().next_power_of_two() / 2;
+            let (left, right) = leaves.split_at(split);
+            if index < split {
+                collect_aunts(left, index, aunts);
+                aunts.push(Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(right)));
+            } else {
+                collect_aunts(right, index - split, aunts);
+                aunts.push(Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(left)));
+            }
+        }
+        let mut proof_aunts = vec![];
+        collect_aunts(&leaves, index, &mut proof_aunts);
+
+        Proof {
+            root_hash: Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(&leaves)),
+            data: txs[index].clone(),
+            proof: merkle::Proof {
+                total: txs.len() as u64,
+                index: index as u64,
+                leaf_hash: Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(
+                    &leaves[index..=index],
+                )),
+                aunts: proof_aunts,
+            },
+        }
+    }
+
+    #[test]
+    fn verifies_single_tx_proof() {
+        // Returned by the kvstore app for a block with a single transaction.
+        let proof: Proof = serde_json::from_str(
+            r#"{
+                "data": "YXN5bmMta2V5PXZhbHVl",
+                "proof": {
+                    "aunts": [],
+                    "index": "0",
+                    "leaf_hash": "MIH5kVBA0TizrX+JVzLSdnwp6Ful2EOI0E4XpdgmK3o=",
+                    "total": "1"
+                },
+                "root_hash": "3081F9915040D138B3AD7F895732D2767C29E85BA5D84388D04E17A5D8262B7A"
+            }"#,
+        )
+        .unwrap();
+        let data_hash = Hash::from_hex_upper(
+            Algorithm::Sha256,
+            "3081F9915040D138B3AD7F895732D2767C29E85BA5D84388D04E17A5D8262B7A",
+        )
+        .unwrap();
+
+        proof.verify(data_hash, b"async-key=value").unwrap();
+        assert!(proof.verify(data_hash, b"async-key=other").is_err());
+        assert!(proof.verify(Hash::None, b"async-key=value").is_err());
+    }
+
+    #[test]
+    fn verifies_every_tx_of_a_block() {
+        let txs = txs();
+        let data_hash = Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(
+            &txs.iter()
+                .map(|tx| <Sha256 as crate::crypto::Sha256>::digest(tx).to_vec())
+                .collect::<Vec<_>>(),
+        ));
+
+        for index in 0..txs.len() {
+            let proof = proof(&txs, index);
+            proof.verify(data_hash, &txs[index]).unwrap();
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let txs = txs();
+        let valid = proof(&txs, 2);
+        let data_hash = valid.root_hash;
+
+        let mut wrong_index = valid.clone();
+        wrong_index.proof.index = 3;
+        assert!(wrong_index.verify(data_hash, &txs[2]).is_err());
+
+        let mut wrong_aunt = valid.clone();
+        wrong_aunt.proof.aunts[0] = Hash::Sha256([0; 32]);
+        assert!(wrong_aunt.verify(data_hash, &txs[2]).is_err());
+
+        let mut missing_aunt = valid.clone();
+        missing_aunt.proof.aunts.pop();
+        assert!(missing_aunt.verify(data_hash, &txs[2]).is_err());
+
+        let mut wrong_root = valid;
+        wrong_root.root_hash = Hash::Sha256([0; 32]);
+        assert!(wrong_root.verify(wrong_root.root_hash, &txs[2]).is_err());
+    }
+}
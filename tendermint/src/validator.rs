@@ -6,45 +6,73 @@ use tendermint_proto::Protobuf;
 
 use crate::{
     account,
+    alloc_prelude::*,
     crypto::signature::Verifier,
     crypto::Sha256,
-    hash::Hash,
+    hash::{Hash, HashCache},
     merkle::{self, MerkleHash},
-    prelude::*,
     public_key::deserialize_public_key,
     vote, Error, PublicKey, Signature,
 };
 
+/// The maximum total voting power of a validator set, as enforced by Tendermint
+/// (`MaxTotalVotingPower` in the Go implementation). It is chosen so that
+/// proposer priority computations cannot overflow an `i64`.
+pub const MAX_TOTAL_VOTING_POWER: u64 = i64::MAX as u64 / 8;
+
 /// Validator set contains a vector of validators
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedSet")]
 pub struct Set {
     validators: Vec<Info>,
     proposer: Option<Info>,
     total_voting_power: vote::Power,
+    #[serde(skip)]
+    hash: HashCache<()>,
+}
+
+// The fields of a validator set, as deserialized before their total voting
+// power is checked.
+#[derive(Deserialize)]
+struct UncheckedSet {
+    validators: Vec<Info>,
+    proposer: Option<Info>,
+    total_voting_power: vote::Power,
+}
+
+impl TryFrom<UncheckedSet> for Set {
+    type Error = Error;
+
+    fn try_from(value: UncheckedSet) -> Result<Self, Self::Error> {
+        Set::compute_total_voting_power(&value.validators)?;
+        Ok(Set {
+            validators: value.validators,
+            proposer: value.proposer,
+            total_voting_power: value.total_voting_power,
+            hash: HashCache::default(),
+        })
+    }
 }
 
 impl Set {
     /// Constructor
-    pub fn new(mut validators: Vec<Info>, proposer: Option<Info>) -> Set {
+    ///
+    /// Fails if the total voting power of the validators exceeds
+    /// [`MAX_TOTAL_VOTING_POWER`].
+    pub fn new(mut validators: Vec<Info>, proposer: Option<Info>) -> Result<Set, Error> {
         Self::sort_validators(&mut validators);
+        let total_voting_power = Self::compute_total_voting_power(&validators)?;
 
-        // Compute the total voting power
-        let total_voting_power = validators
-            .iter()
-            .map(|v| v.power.value())
-            .sum::<u64>()
-            .try_into()
-            .unwrap();
-
-        Set {
+        Ok(Set {
             validators,
             proposer,
             total_voting_power,
-        }
+            hash: HashCache::default(),
+        })
     }
 
     /// Convenience constructor for cases where there is no proposer
-    pub fn without_proposer(validators: Vec<Info>) -> Set {
+    pub fn without_proposer(validators: Vec<Info>) -> Result<Set, Error> {
         Self::new(validators, None)
     }
 
@@ -62,7 +90,13 @@ impl Set {
 
         // Create the validator set with the given proposer.
         // This is required by IBC on-chain validation.
-        Ok(Self::new(validators, Some(proposer)))
+        Self::new(validators, Some(proposer))
+    }
+
+    fn compute_total_voting_power(validators: &[Info]) -> Result<vote::Power, Error> {
+        vote::Power::checked_sum(validators.iter().map(|v| v.power))
+            .filter(|total| total.value() <= MAX_TOTAL_VOTING_POWER)
+            .ok_or_else(|| Error::total_voting_power_overflow(MAX_TOTAL_VOTING_POWER))
     }
 
     /// Get Info of the underlying validators.
@@ -100,18 +134,22 @@ impl Set {
         self.hash_with::<crate::crypto::default::Sha256>()
     }
 
-    /// Hash this header with a SHA256 hasher provided by a crypto provider.
+    /// Hash this validator set with a SHA256 hasher provided by a crypto provider.
+    ///
+    /// The hash is computed on first use and cached for subsequent calls.
     pub fn hash_with<H>(&self) -> Hash
     where
         H: MerkleHash + Sha256 + Default,
     {
-        let validator_bytes: Vec<Vec<u8>> = self
-            .validators()
-            .iter()
-            .map(|validator| validator.hash_bytes())
-            .collect();
-
-        Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&validator_bytes))
+        self.hash.get_or_compute(&(), || {
+            let validator_bytes: Vec<Vec<u8>> = self
+                .validators()
+                .iter()
+                .map(|validator| validator.hash_bytes())
+                .collect();
+
+            Hash::Sha256(merkle::simple_hash_from_byte_vectors::<H>(&validator_bytes))
+        })
     }
 }
 
@@ -251,7 +289,7 @@ tendermint_pb_modules! {
         },
     };
     use super::{Info, Set, SimpleValidator, Update};
-    use crate::{prelude::*, Error};
+    use crate::{alloc_prelude::*, Error};
 
     impl Protobuf<RawValidatorSet> for Set {}
 
@@ -266,7 +304,7 @@ tendermint_pb_modules! {
                 .collect::<Result<Vec<_>, _>>()?;
 
             let proposer = value.proposer.map(TryInto::try_into).transpose()?;
-            let validator_set = Self::new(validators, proposer);
+            let validator_set = Self::new(validators, proposer)?;
 
             // Ensure that the raw voting power matches the computed one
             let raw_voting_power = value.total_voting_power.try_into()?;
@@ -440,10 +478,18 @@ mod tests {
                 17, 22, 57, 84, 71, 122, 200, 169, 192, 252, 41, 148, 223, 180,
             ];
 
-            let val_set = Set::without_proposer(vec![v1.clone(), v2.clone(), v3.clone()]);
+            let val_set = Set::without_proposer(vec![v1.clone(), v2.clone(), v3.clone()]).unwrap();
             let hash = val_set.hash();
             assert_eq!(hash_expect, hash.as_bytes().to_vec());
 
+            // The cached hash is carried over by clones and rebuilt after a serde round trip
+            assert_eq!(val_set.hash(), hash);
+            assert_eq!(val_set.clone().hash(), hash);
+            let round_tripped: Set =
+                serde_json::from_str(&serde_json::to_string(&val_set).unwrap()).unwrap();
+            assert_eq!(round_tripped, val_set);
+            assert_eq!(round_tripped.hash(), hash);
+
             let not_in_set = make_validator(
                 vec![
                     110, 147, 87, 120, 27, 218, 66, 209, 81, 4, 169, 153, 64, 163, 137, 89, 168,
@@ -461,6 +507,41 @@ mod tests {
                 148_151_478_422_287_875 + 158_095_448_483_785_107 + 770_561_664_770_006_272
             );
         }
+
+        #[test]
+        fn total_voting_power_overflow() {
+            let key = |n| match n {
+                1 => vec![
+                    48, 163, 55, 132, 231, 147, 230, 163, 56, 158, 127, 218, 179, 139, 212, 103,
+                    218, 89, 122, 126, 229, 88, 84, 48, 32, 0, 185, 174, 63, 72, 203, 52,
+                ],
+                _ => vec![
+                    54, 253, 174, 153, 121, 74, 145, 180, 111, 16, 214, 48, 193, 109, 104, 134, 55,
+                    162, 151, 16, 182, 114, 125, 135, 32, 195, 236, 248, 64, 112, 74, 101,
+                ],
+            };
+            let half = MAX_TOTAL_VOTING_POWER / 2 + 1;
+            let vals = vec![make_validator(key(1), half), make_validator(key(2), half)];
+            let err = Set::without_proposer(vals.clone()).unwrap_err();
+            assert!(matches!(
+                err.detail(),
+                crate::error::ErrorDetail::TotalVotingPowerOverflow(_)
+            ));
+
+            // The sum of the voting powers overflows an i64.
+            let vals = vec![
+                make_validator(key(1), i64::MAX as u64),
+                make_validator(key(2), i64::MAX as u64),
+            ];
+            assert!(Set::without_proposer(vals).is_err());
+
+            let json = serde_json::json!({
+                "validators": [make_validator(key(1), half), make_validator(key(2), half)],
+                "proposer": null,
+                "total_voting_power": "0",
+            });
+            assert!(serde_json::from_value::<Set>(json).is_err());
+        }
     }
 
     #[test]
@@ -483,11 +564,21 @@ mod tests {
             },
             "power": "573929"
         }"#;
+        // Tendermint 0.33 and earlier
+        const FMT3: &str = r#"{
+            "pub_key": {
+                "type": "ed25519",
+                "data": "VqJCr3vjQdffcLIG6RMBl2MgXDFYNY6b3Joaa43gV3o="
+            },
+            "power": "573929"
+        }"#;
 
         let update1 = serde_json::from_str::<Update>(FMT1).unwrap();
         let update2 = serde_json::from_str::<Update>(FMT2).unwrap();
+        let update3 = serde_json::from_str::<Update>(FMT3).unwrap();
 
         assert_eq!(u64::from(update1.power), 573929);
         assert_eq!(update1, update2);
+        assert_eq!(update1, update3);
     }
 }
@@ -2,7 +2,7 @@ use core::fmt::{self, Debug, Display};
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::*;
+use crate::alloc_prelude::*;
 
 /// Tendermint version
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -16,8 +16,8 @@ pub use self::{
     canonical_vote::CanonicalVote, power::Power, sign_vote::*, validator_index::ValidatorIndex,
 };
 use crate::{
-    account, block, chain::Id as ChainId, consensus::State, error::Error, hash, prelude::*,
-    signature::Ed25519Signature, Signature, Time,
+    account, alloc_prelude::*, block, chain::Id as ChainId, consensus::State, error::Error, hash,
+    signature::Ed25519Signature, signed_msg_type::SignedMsgType, Signature, Time,
 };
 
 /// Votes are signed messages from validators for a particular block which
@@ -54,7 +54,7 @@ pub struct Vote {
 
 tendermint_pb_modules! {
     use super::Vote;
-    use crate::{prelude::*, block, Error, Signature};
+    use crate::{alloc_prelude::*, Error, Signature};
     use pb::types::Vote as RawVote;
 
     impl Protobuf<RawVote> for Vote {}
@@ -71,11 +71,9 @@ tendermint_pb_modules! {
                 height: value.height.try_into()?,
                 round: value.round.try_into()?,
                 // block_id can be nil in the Go implementation
-                block_id: value
-                    .block_id
-                    .map(TryInto::try_into)
-                    .transpose()?
-                    .filter(|i| i != &block::Id::default()),
+                block_id: super::nil_or_complete(
+                    value.block_id.map(TryInto::try_into).transpose()?,
+                )?,
                 timestamp: value.timestamp.map(|t| t.try_into()).transpose()?,
                 validator_address: value.validator_address.try_into()?,
                 validator_index: value.validator_index.try_into()?,
@@ -100,6 +98,23 @@ tendermint_pb_modules! {
     }
 }
 
+/// Normalizes the ID of the block a vote is for: the empty block ID stands
+/// for a vote for nil, as in the Go implementation, and any other block ID
+/// must be complete.
+///
+/// A block ID with an empty hash but a part set header is rejected rather
+/// than treated as nil, as it is not encoded as nil in the sign bytes.
+pub(crate) fn nil_or_complete(block_id: Option<block::Id>) -> Result<Option<block::Id>, Error> {
+    match block_id {
+        Some(block_id) if block_id.is_zero() => Ok(None),
+        Some(block_id) if !block_id.is_complete() => Err(Error::invalid_block_id(format!(
+            "the block id {:?} of a vote must be either empty or complete",
+            block_id
+        ))),
+        block_id => Ok(block_id),
+    }
+}
+
 impl Vote {
     /// Is this vote a prevote?
     pub fn is_prevote(&self) -> bool {
@@ -244,17 +259,13 @@ impl TryFrom<i32> for Type {
     type Error = Error;
 
     fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            1 => Ok(Type::Prevote),
-            2 => Ok(Type::Precommit),
-            _ => Err(Error::invalid_message_type()),
-        }
+        SignedMsgType::try_from(value)?.try_into()
     }
 }
 
 impl From<Type> for i32 {
     fn from(value: Type) -> Self {
-        value as i32
+        SignedMsgType::from(value).into()
     }
 }
 
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use tendermint_proto::v0_37::types::CanonicalVote as RawCanonicalVote;
 
-use crate::{block, chain::Id as ChainId, prelude::*, Time};
+use crate::{alloc_prelude::*, block, chain::Id as ChainId, Time};
 
 /// CanonicalVote is used for protobuf encoding a Vote
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -30,7 +30,7 @@ pub struct CanonicalVote {
 tendermint_pb_modules! {
     use super::CanonicalVote;
     use crate::Error;
-    use crate::{block, chain::Id as ChainId, prelude::*};
+    use crate::{block, chain::Id as ChainId, alloc_prelude::*};
     use pb::types::CanonicalVote as RawCanonicalVote;
 
     impl Protobuf<RawCanonicalVote> for CanonicalVote {}
@@ -44,14 +44,16 @@ tendermint_pb_modules! {
             }
             let _val: i32 = value.round.try_into().map_err(Error::integer_overflow)?;
 
-            // If the Hash is empty in BlockId, the BlockId should be empty.
-            // See: https://github.com/informalsystems/tendermint-rs/issues/663
-            let block_id = value.block_id.filter(|i| !i.hash.is_empty());
+            // The empty BlockId stands for nil, and any other BlockId must be
+            // complete. See: https://github.com/informalsystems/tendermint-rs/issues/663
+            let block_id = crate::vote::nil_or_complete(
+                value.block_id.map(TryInto::try_into).transpose()?,
+            )?;
             Ok(CanonicalVote {
                 vote_type: value.r#type.try_into()?,
                 height: value.height.try_into()?,
                 round: (value.round as i32).try_into()?,
-                block_id: block_id.map(|b| b.try_into()).transpose()?,
+                block_id,
                 timestamp: value.timestamp.map(|t| t.try_into()).transpose()?,
                 chain_id: ChainId::try_from(value.chain_id)?,
             })
@@ -100,14 +102,14 @@ mod tests {
             CanonicalVote as RawCanonicalVote,
         };
         use crate::{
-            prelude::*,
+            alloc_prelude::*,
             vote::{canonical_vote::CanonicalVote, Type},
         };
 
         #[test]
         fn canonical_vote_domain_checks() {
             // RawCanonicalVote with edge cases to test domain knowledge
-            // block_id with empty hash should decode to None
+            // the empty block_id should decode to None
             // timestamp at EPOCH is still considered valid time
             let proto_cp = RawCanonicalVote {
                 r#type: 1,
@@ -116,8 +118,8 @@ mod tests {
                 block_id: Some(RawCanonicalBlockId {
                     hash: vec![],
                     part_set_header: Some(RawCanonicalPartSetHeader {
-                        total: 1,
-                        hash: vec![1],
+                        total: 0,
+                        hash: vec![],
                     }),
                 }),
                 timestamp: Some(Timestamp {
@@ -126,11 +128,23 @@ mod tests {
                 }),
                 chain_id: "testchain".to_string(),
             };
-            let cp = CanonicalVote::try_from(proto_cp).unwrap();
+            let cp = CanonicalVote::try_from(proto_cp.clone()).unwrap();
             assert_eq!(cp.vote_type, Type::Prevote);
             assert!(cp.block_id.is_none());
             assert!(cp.timestamp.is_some());
 
+            // A block_id with an empty hash but a part set header is not nil,
+            // as it is not encoded as nil in the sign bytes: it is rejected.
+            let mut partial_cp = proto_cp.clone();
+            partial_cp.block_id = Some(RawCanonicalBlockId {
+                hash: vec![],
+                part_set_header: Some(RawCanonicalPartSetHeader {
+                    total: 1,
+                    hash: vec![1; 32],
+                }),
+            });
+            assert!(CanonicalVote::try_from(partial_cp).is_err());
+
             // No timestamp is not acceptable
             // See: https://github.com/informalsystems/tendermint-rs/issues/649
             let mut proto_cp: RawCanonicalVote = cp.into();
@@ -7,7 +7,7 @@ use core::{
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// Voting power
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Default)]
@@ -77,6 +77,32 @@ impl Power {
     pub fn is_zero(self) -> bool {
         self.0 == 0
     }
+
+    /// Add the given voting power, returning `None` if the sum is not a
+    /// valid voting power, i.e. exceeds `i64::MAX`.
+    pub fn checked_add(self, rhs: Power) -> Option<Power> {
+        self.0
+            .checked_add(rhs.0)
+            .filter(|&sum| sum <= i64::MAX as u64)
+            .map(Power)
+    }
+
+    /// Subtract the given voting power, returning `None` if it exceeds this
+    /// one.
+    pub fn checked_sub(self, rhs: Power) -> Option<Power> {
+        self.0.checked_sub(rhs.0).map(Power)
+    }
+
+    /// Sum the given voting powers, returning `None` if the sum is not a
+    /// valid voting power, i.e. exceeds `i64::MAX`.
+    pub fn checked_sum<I>(powers: I) -> Option<Power>
+    where
+        I: IntoIterator<Item = Power>,
+    {
+        powers
+            .into_iter()
+            .try_fold(Power::default(), Power::checked_add)
+    }
 }
 
 impl<'de> Deserialize<'de> for Power {
@@ -97,3 +123,21 @@ impl Serialize for Power {
         proto_int.to_string().serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_sum() {
+        let powers = [Power::from(1u32), Power::from(2u32), Power::from(3u32)];
+        assert_eq!(Power::checked_sum(powers), Some(Power::from(6u32)));
+        assert_eq!(Power::checked_sum([]), Some(Power::default()));
+
+        let max = Power::try_from(i64::MAX).unwrap();
+        assert_eq!(max.checked_add(Power::from(0u8)), Some(max));
+        assert_eq!(max.checked_add(Power::from(1u8)), None);
+        assert_eq!(Power::checked_sum([max, max, max]), None);
+        assert_eq!(Power::from(1u8).checked_sub(max), None);
+    }
+}
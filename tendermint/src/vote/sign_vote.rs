@@ -1,7 +1,7 @@
 use bytes::BufMut;
 use tendermint_proto::Error as ProtobufError;
 
-use crate::{chain, prelude::*, privval::RemoteSignerError, Vote};
+use crate::{alloc_prelude::*, chain, privval::RemoteSignerError, Vote};
 
 /// SignVoteRequest is a request to sign a vote
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -43,7 +43,7 @@ pub struct SignedVoteResponse {
 
 tendermint_pb_modules! {
     use super::{SignVoteRequest, SignedVoteResponse};
-    use crate::{Error, prelude::*};
+    use crate::{Error, alloc_prelude::*};
     use pb::privval::{
         SignVoteRequest as RawSignVoteRequest, SignedVoteResponse as RawSignedVoteResponse,
     };
@@ -106,10 +106,10 @@ mod tests {
 
     use crate::{
         account::Id as AccountId,
+        alloc_prelude::*,
         block::{parts::Header, Height, Id as BlockId, Round},
         chain::Id as ChainId,
         hash::Algorithm,
-        prelude::*,
         signature::{Ed25519Signature, Signature},
         vote::{CanonicalVote, SignVoteRequest, Type, ValidatorIndex},
         Hash, Vote,
@@ -275,6 +275,103 @@ mod tests {
         assert_eq!(got, want);
     }
 
+    /// A precommit for a block, as found in a commit.
+    fn precommit_for_block() -> Vote {
+        let dt = datetime!(2017-12-25 03:00:01.234 UTC);
+        Vote {
+            vote_type: Type::Precommit,
+            height: Height::from(12345_u32),
+            round: Round::from(2_u16),
+            timestamp: Some(dt.try_into().unwrap()),
+            block_id: Some(BlockId {
+                hash: Hash::from_hex_upper(Algorithm::Sha256, &"AB".repeat(32)).unwrap(),
+                part_set_header: Header::new(
+                    1,
+                    Hash::from_hex_upper(Algorithm::Sha256, &"CD".repeat(32)).unwrap(),
+                )
+                .unwrap(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sign_bytes_domain_separation() {
+        let vote = precommit_for_block();
+        let sign_bytes = |chain_id: &str| {
+            vote.to_signable_vec(ChainId::from_str(chain_id).unwrap())
+                .unwrap()
+        };
+        let (bytes_a, bytes_b, bytes_ab) = (sign_bytes("A"), sign_bytes("B"), sign_bytes("AB"));
+
+        // The chain ID is the last, length-delimited, field of the sign bytes.
+        assert!(bytes_a.ends_with(&[0x32, 1, b'A']));
+        assert!(bytes_ab.ends_with(&[0x32, 2, b'A', b'B']));
+        assert_ne!(bytes_a, bytes_b);
+        // The sign bytes are themselves length-delimited, so that the sign
+        // bytes for a chain are not a prefix of those for another one.
+        assert_eq!(bytes_a[0] as usize, bytes_a.len() - 1);
+        assert!(!bytes_ab.starts_with(&bytes_a[..bytes_a.len() - 1]));
+    }
+
+    #[test]
+    #[cfg(feature = "rust-crypto")]
+    fn test_vote_for_one_chain_does_not_verify_for_another() {
+        use crate::{
+            crypto::default::signature::Verifier, validator::Info, vote::Power, PublicKey,
+        };
+
+        let signing_key = ed25519_consensus::SigningKey::from([7; 32]);
+        let pub_key =
+            PublicKey::from_raw_ed25519(&signing_key.verification_key().to_bytes()).unwrap();
+        let validator = Info::new(pub_key, Power::from(1_u32));
+
+        let vote = precommit_for_block();
+        let sign_bytes_a = vote
+            .to_signable_vec(ChainId::from_str("chain-A").unwrap())
+            .unwrap();
+        let sign_bytes_b = vote
+            .to_signable_vec(ChainId::from_str("chain-B").unwrap())
+            .unwrap();
+        let signature = Signature::new(signing_key.sign(&sign_bytes_a).to_bytes())
+            .unwrap()
+            .unwrap();
+
+        validator
+            .verify_signature::<Verifier>(&sign_bytes_a, &signature)
+            .unwrap();
+        assert!(validator
+            .verify_signature::<Verifier>(&sign_bytes_b, &signature)
+            .is_err());
+    }
+
+    #[test]
+    // Regression test for the canonicalization of the nil block ID: as in Go,
+    // the empty block ID is encoded as nil in the sign bytes.
+    fn test_nil_block_id_canonicalization() {
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+        let nil_vote = Vote {
+            block_id: None,
+            ..precommit_for_block()
+        };
+        let empty_block_id_vote = Vote {
+            block_id: Some(BlockId::default()),
+            ..precommit_for_block()
+        };
+
+        let nil_bytes = nil_vote.to_signable_vec(chain_id.clone()).unwrap();
+        assert_eq!(
+            empty_block_id_vote
+                .to_signable_vec(chain_id.clone())
+                .unwrap(),
+            nil_bytes
+        );
+        assert_ne!(
+            precommit_for_block().to_signable_vec(chain_id).unwrap(),
+            nil_bytes
+        );
+    }
+
     tendermint_pb_modules! {
         use super::*;
         use pb::types::CanonicalVote as RawCanonicalVote;
@@ -395,6 +492,34 @@ mod tests {
             assert_eq!(got, want);
         }
 
+        #[test]
+        fn test_vote_decoding_rejects_ambiguous_block_ids() {
+            let raw = |block_id: BlockId| {
+                let mut raw_vote = pb::types::Vote::from(precommit_for_block());
+                raw_vote.block_id = Some(block_id.into());
+                raw_vote
+            };
+
+            // The empty block ID stands for nil.
+            let vote = Vote::try_from(raw(BlockId::default())).unwrap();
+            assert_eq!(vote.block_id, None);
+
+            // A block ID with a part set header but no hash is neither empty
+            // nor complete.
+            let partial = BlockId {
+                hash: Hash::None,
+                ..precommit_for_block().block_id.unwrap()
+            };
+            assert!(Vote::try_from(raw(partial)).is_err());
+
+            // Nor is a block ID with a hash but no part set header.
+            let partial = BlockId {
+                part_set_header: Header::default(),
+                ..precommit_for_block().block_id.unwrap()
+            };
+            assert!(Vote::try_from(raw(partial)).is_err());
+        }
+
         #[test]
         fn test_vote_rountrip_with_sig() {
             let dt = datetime!(2017-12-25 03:00:01.234 UTC);
@@ -4,7 +4,7 @@ use core::{
     str::FromStr,
 };
 
-use crate::{error::Error, prelude::*};
+use crate::{alloc_prelude::*, error::Error};
 
 /// ValidatorIndex for a particular Vote
 #[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -0,0 +1,100 @@
+//! Smoke tests for the optional features of this crate.
+//!
+//! Every test is gated on the features it exercises, so that this file builds
+//! and passes under every feature combination checked by the workspace
+//! feature matrix (see `tendermint-test`).
+
+use core::str::FromStr;
+
+use tendermint::{account, Hash, PublicKey};
+
+const ED25519_PUBKEY_HEX: &str = "14253D61EF42D166D02E68D540D07FDF8D65A9AF0ACAA46302688E788A8521E2";
+const ED25519_ID_HEX: &str = "0CDA3F47EF3C4906693B170EF650EB968C5F4B2C";
+
+fn ed25519_public_key() -> PublicKey {
+    let bytes = subtle_encoding::hex::decode_upper(ED25519_PUBKEY_HEX).unwrap();
+    PublicKey::from_raw_ed25519(&bytes).unwrap()
+}
+
+#[test]
+fn core_types_without_optional_features() {
+    let id = account::Id::from_str(ED25519_ID_HEX).unwrap();
+    assert_eq!(id.to_string(), ED25519_ID_HEX);
+
+    let hash =
+        Hash::from_str("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855").unwrap();
+    assert_eq!(hash.as_bytes().len(), 32);
+
+    assert!(matches!(ed25519_public_key(), PublicKey::Ed25519(_)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn errors_implement_std_error() {
+    fn assert_std_error<E: std::error::Error + Send + Sync + 'static>() {}
+
+    assert_std_error::<tendermint::Error>();
+}
+
+#[cfg(feature = "clock")]
+#[test]
+fn clock_reads_the_current_time() {
+    let now = tendermint::Time::now();
+    assert!(now > tendermint::Time::unix_epoch());
+}
+
+#[cfg(feature = "rust-crypto")]
+#[test]
+fn rust_crypto_derives_addresses_and_hashes() {
+    let id = account::Id::from(ed25519_public_key());
+    assert_eq!(id, account::Id::from_str(ED25519_ID_HEX).unwrap());
+
+    // The Merkle root of an empty validator set is the SHA-256 of no data.
+    let empty_set = tendermint::validator::Set::without_proposer(vec![]).unwrap();
+    assert_eq!(
+        empty_set.hash(),
+        Hash::from_str("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855").unwrap()
+    );
+}
+
+#[cfg(feature = "secp256k1")]
+#[test]
+fn secp256k1_public_keys() {
+    let bytes = subtle_encoding::hex::decode_upper(
+        "02950E1CDFCB133D6024109FD489F734EEB4502418E538C28481F22BCE276F248C",
+    )
+    .unwrap();
+    let public_key = PublicKey::from_raw_secp256k1(&bytes).unwrap();
+    assert_eq!(public_key.to_bytes(), bytes);
+}
+
+#[cfg(all(feature = "secp256k1", feature = "rust-crypto"))]
+#[test]
+fn secp256k1_addresses() {
+    let bytes = subtle_encoding::hex::decode_upper(
+        "02950E1CDFCB133D6024109FD489F734EEB4502418E538C28481F22BCE276F248C",
+    )
+    .unwrap();
+    let public_key = PublicKey::from_raw_secp256k1(&bytes).unwrap();
+    assert_eq!(
+        account::Id::from(public_key),
+        account::Id::from_str("7C2BB42A8BE69791EC763E51F5A49BCD41E82237").unwrap()
+    );
+}
+
+#[cfg(feature = "bech32")]
+#[test]
+fn bech32_addresses() {
+    let id = account::Id::from_str(ED25519_ID_HEX).unwrap();
+    let encoded = id.to_bech32("cosmos").unwrap();
+    assert_eq!(account::Id::from_bech32(&encoded, "cosmos").unwrap(), id);
+}
+
+#[cfg(feature = "primitive-types")]
+#[test]
+fn primitive_types_h256_conversions() {
+    let hash =
+        Hash::from_str("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855").unwrap();
+    let h256 = primitive_types::H256::try_from(hash).unwrap();
+    assert_eq!(Hash::from(h256), hash);
+}
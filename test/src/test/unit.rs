@@ -1 +1,2 @@
 mod p2p;
+mod std_ext;
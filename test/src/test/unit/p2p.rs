@@ -1 +1,7 @@
+mod addrbook;
+mod mconnection;
+mod node_key;
+mod pex;
+mod privval;
 mod secret_connection;
+mod transport;
@@ -0,0 +1,167 @@
+use std::{env, fs, process, time::Duration};
+
+use tendermint::Time;
+use tendermint_p2p::addrbook::{
+    AddrBook, BucketType, NetAddress, DEFAULT_BAN_DURATION, NEW_BUCKET_COUNT, OLD_BUCKET_COUNT,
+};
+
+/// `addrbook.json` as written by CometBFT
+const ADDRBOOK_JSON: &str = r#"{
+	"key": "4a4e1a7e2a9b7c5b3e2f1d0c",
+	"addrs": [
+		{
+			"addr": {
+				"id": "65b60673d6ed884bf01c2c222d82ada0740f29ac",
+				"ip": "35.233.155.199",
+				"port": 26656
+			},
+			"src": {
+				"id": "2c9a5b4b0b4f1ad0fcb6b5f7e1a7e6a0ed3c8c3f",
+				"ip": "104.196.12.7",
+				"port": 26656
+			},
+			"buckets": [
+				142
+			],
+			"attempts": 2,
+			"bucket_type": 1,
+			"last_attempt": "2023-01-12T10:13:17.437548318Z",
+			"last_success": "0001-01-01T00:00:00Z",
+			"last_ban_time": "0001-01-01T00:00:00Z"
+		},
+		{
+			"addr": {
+				"id": "2c9a5b4b0b4f1ad0fcb6b5f7e1a7e6a0ed3c8c3f",
+				"ip": "104.196.12.7",
+				"port": 26656
+			},
+			"src": {
+				"id": "2c9a5b4b0b4f1ad0fcb6b5f7e1a7e6a0ed3c8c3f",
+				"ip": "104.196.12.7",
+				"port": 26656
+			},
+			"buckets": [
+				17
+			],
+			"attempts": 0,
+			"bucket_type": 2,
+			"last_attempt": "2023-01-12T10:12:02.104365131Z",
+			"last_success": "2023-01-12T10:12:02.104365131Z",
+			"last_ban_time": "0001-01-01T00:00:00Z"
+		}
+	]
+}"#;
+
+fn addr(s: &str) -> NetAddress {
+    s.parse().expect("a valid network address")
+}
+
+fn time(s: &str) -> Time {
+    Time::parse_from_rfc3339(s).unwrap()
+}
+
+#[test]
+fn test_parse_cometbft_addrbook() {
+    let book = AddrBook::parse_json(ADDRBOOK_JSON).expect("a valid address book");
+    assert_eq!(book.key(), "4a4e1a7e2a9b7c5b3e2f1d0c");
+    assert_eq!(book.len(), 2);
+
+    let peer = addr("65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:26656");
+    let known = book.get(&peer.id).expect("a known address");
+    assert_eq!(known.addr, peer);
+    assert_eq!(known.bucket_type, BucketType::New);
+    assert_eq!(known.buckets, vec![142]);
+    assert_eq!(known.attempts, 2);
+    assert_eq!(known.last_attempt, time("2023-01-12T10:13:17.437548318Z"));
+    assert_eq!(known.last_success, Time::MIN);
+    assert!(!known.is_banned(time("2023-01-12T10:14:00Z")));
+
+    let reparsed = AddrBook::parse_json(&book.to_json()).expect("a valid address book");
+    assert_eq!(reparsed.key(), book.key());
+    assert!(reparsed.addresses().eq(book.addresses()));
+}
+
+#[test]
+fn test_net_address_display_and_parse() {
+    let v4 = "65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:26656";
+    assert_eq!(addr(v4).to_string(), v4);
+    let v6 = "65b60673d6ed884bf01c2c222d82ada0740f29ac@[2001:db8::1]:26656";
+    assert_eq!(addr(v6).to_string(), v6);
+
+    for invalid in [
+        "35.233.155.199:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199",
+        "xyz@35.233.155.199:26656",
+    ] {
+        assert!(invalid.parse::<NetAddress>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_add_mark_good_and_ban() {
+    let mut book = AddrBook::new();
+    let src = addr("2c9a5b4b0b4f1ad0fcb6b5f7e1a7e6a0ed3c8c3f@104.196.12.7:26656");
+    let peer = addr("65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:26656");
+    assert!(book.add_address(peer.clone(), src.clone()));
+    assert!(
+        !book.add_address(peer.clone(), src),
+        "the peer is already known"
+    );
+
+    let known = book.get(&peer.id).unwrap();
+    assert_eq!(known.bucket_type, BucketType::New);
+    assert!(known
+        .buckets
+        .iter()
+        .all(|&bucket| bucket < NEW_BUCKET_COUNT));
+
+    let now = time("2023-01-12T10:00:00Z");
+    book.mark_attempt(&peer.id, now);
+    book.mark_attempt(&peer.id, now);
+    assert_eq!(book.get(&peer.id).unwrap().attempts, 2);
+
+    book.mark_good(&peer.id, now);
+    let known = book.get(&peer.id).unwrap();
+    assert_eq!(known.bucket_type, BucketType::Old);
+    assert!(known
+        .buckets
+        .iter()
+        .all(|&bucket| bucket < OLD_BUCKET_COUNT));
+    assert_eq!(known.attempts, 0);
+    assert_eq!(known.last_success, now);
+    assert_eq!(book.dialable(now), vec![&peer]);
+
+    book.mark_bad(&peer.id, now, DEFAULT_BAN_DURATION);
+    assert!(book.is_banned(&peer.id, now));
+    assert!(book.dialable(now).is_empty());
+    // A shorter ban does not shorten the current one.
+    book.mark_bad(&peer.id, now, Duration::from_secs(60));
+    let ban_end = now.checked_add(DEFAULT_BAN_DURATION).unwrap();
+    assert!(book.is_banned(
+        &peer.id,
+        ban_end.checked_sub(Duration::from_secs(1)).unwrap()
+    ));
+    assert!(!book.is_banned(&peer.id, ban_end));
+
+    assert!(book.remove_address(&peer.id).is_some());
+    assert!(book.is_empty());
+}
+
+#[test]
+fn test_save_and_load() {
+    let path = env::temp_dir().join(format!("tendermint-p2p-{}-addrbook.json", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut book = AddrBook::load(&path).expect("an empty address book");
+    assert!(book.is_empty());
+    let peer = addr("65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:26656");
+    book.add_address(peer.clone(), peer.clone());
+    book.mark_bad(&peer.id, time("2023-01-12T10:00:00Z"), DEFAULT_BAN_DURATION);
+    book.save().expect("to save the address book");
+
+    let loaded = AddrBook::load(&path).expect("to load the address book");
+    assert_eq!(loaded.key(), book.key());
+    assert_eq!(loaded.get(&peer.id), book.get(&peer.id));
+    assert!(loaded.is_banned(&peer.id, time("2023-01-12T12:00:00Z")));
+    fs::remove_file(&path).unwrap();
+}
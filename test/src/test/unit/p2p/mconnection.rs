@@ -0,0 +1,183 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use rand_core::OsRng;
+use tendermint_p2p::{
+    mconnection::{ChannelDescriptor, MConnConfig, MConnection, Packet},
+    secret_connection::Version,
+};
+
+const CHANNEL_A: u8 = 0x20;
+const CHANNEL_B: u8 = 0x30;
+
+fn channels() -> Vec<ChannelDescriptor> {
+    vec![
+        ChannelDescriptor::new(CHANNEL_A, 5).send_queue_capacity(10),
+        ChannelDescriptor::new(CHANNEL_B, 1).recv_message_capacity(4096),
+    ]
+}
+
+fn tcp_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    (client, server)
+}
+
+/// Multiplexes a raw TCP stream, without a Secret Connection, so that the
+/// packets can be inspected on the other end.
+fn raw_mconnection(stream: &TcpStream, config: MConnConfig) -> MConnection {
+    MConnection::new(
+        stream.try_clone().unwrap(),
+        stream.try_clone().unwrap(),
+        channels(),
+        config,
+    )
+    .unwrap()
+}
+
+fn read_packet(stream: &mut TcpStream) -> Packet {
+    let mut len = [0_u8; 1];
+    stream.read_exact(&mut len).unwrap();
+    assert!(len[0] < 0x80, "test packets are shorter than 128 bytes");
+    let mut bytes = vec![0_u8; len[0].into()];
+    stream.read_exact(&mut bytes).unwrap();
+    Packet::decode(&bytes).expect("a valid packet")
+}
+
+#[test]
+fn test_packet_encoding() {
+    assert_eq!(
+        Packet::Ping.encode_length_delimited_vec(),
+        vec![0x02, 0x0a, 0x00]
+    );
+    assert_eq!(
+        Packet::Pong.encode_length_delimited_vec(),
+        vec![0x02, 0x12, 0x00]
+    );
+
+    let msg = Packet::Msg {
+        channel_id: CHANNEL_A,
+        eof: true,
+        data: b"hi".to_vec(),
+    };
+    let bytes = msg.encode_length_delimited_vec();
+    assert_eq!(
+        bytes,
+        vec![0x0a, 0x1a, 0x08, 0x08, 0x20, 0x10, 0x01, 0x1a, 0x02, b'h', b'i']
+    );
+    assert_eq!(Packet::decode(&bytes[1..]).unwrap(), msg);
+    assert!(Packet::decode(&[]).is_err());
+}
+
+#[test]
+fn test_mconnection_exchange_messages() {
+    let (client, server) = tcp_pair();
+    let server = thread::spawn(move || {
+        let key = ed25519_consensus::SigningKey::new(OsRng {});
+        MConnection::handshake(
+            server,
+            key,
+            Version::V0_34,
+            channels(),
+            MConnConfig::default(),
+        )
+        .expect("handshake to succeed")
+    });
+    let key = ed25519_consensus::SigningKey::new(OsRng {});
+    let client = MConnection::handshake(
+        client,
+        key,
+        Version::V0_34,
+        channels(),
+        MConnConfig::default(),
+    )
+    .expect("handshake to succeed");
+    let server = server.join().expect("server thread has panicked");
+    assert!(client.remote_pubkey().is_some());
+
+    // Spans several packets.
+    let large = vec![7_u8; 3000];
+    client.send(CHANNEL_A, large.clone()).unwrap();
+    client.send(CHANNEL_B, b"small".to_vec()).unwrap();
+    client.send(CHANNEL_A, Vec::new()).unwrap();
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        received.push(server.recv().expect("a message"));
+    }
+    let on_channel = |id| {
+        received
+            .iter()
+            .filter(|(channel_id, _)| *channel_id == id)
+            .map(|(_, msg)| msg.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(on_channel(CHANNEL_A), vec![large, Vec::new()]);
+    assert_eq!(on_channel(CHANNEL_B), vec![b"small".to_vec()]);
+
+    server.send(CHANNEL_B, b"reply".to_vec()).unwrap();
+    assert_eq!(client.recv().unwrap(), (CHANNEL_B, b"reply".to_vec()));
+
+    assert!(client.send(0x99, Vec::new()).is_err(), "unknown channel");
+    client.stop();
+    assert!(!client.is_running());
+    assert!(server.recv().is_err(), "the peer is gone");
+}
+
+#[test]
+fn test_mconnection_answers_pings() {
+    let (local, mut remote) = tcp_pair();
+    let mconn = raw_mconnection(&local, MConnConfig::default());
+
+    remote
+        .write_all(&Packet::Ping.encode_length_delimited_vec())
+        .unwrap();
+    assert_eq!(read_packet(&mut remote), Packet::Pong);
+    assert!(mconn.is_running());
+}
+
+#[test]
+fn test_mconnection_pong_timeout() {
+    let (local, mut remote) = tcp_pair();
+    let config = MConnConfig {
+        ping_interval: Duration::from_millis(50),
+        pong_timeout: Duration::from_millis(100),
+        ..MConnConfig::default()
+    };
+    let mconn = raw_mconnection(&local, config);
+
+    assert_eq!(read_packet(&mut remote), Packet::Ping);
+    // Not answering the ping makes the connection fail.
+    assert!(mconn.recv().is_err());
+    assert!(!mconn.is_running());
+}
+
+#[test]
+fn test_mconnection_rejects_unknown_channel_and_oversized_messages() {
+    for (channel_id, data) in [(0x99, vec![1_u8]), (CHANNEL_B, vec![1_u8; 100])] {
+        let (local, mut remote) = tcp_pair();
+        let channels = vec![ChannelDescriptor::new(CHANNEL_B, 1).recv_message_capacity(64)];
+        let mconn = MConnection::new(
+            local.try_clone().unwrap(),
+            local,
+            channels,
+            MConnConfig::default(),
+        )
+        .unwrap();
+
+        let packet = Packet::Msg {
+            channel_id,
+            eof: true,
+            data,
+        };
+        remote
+            .write_all(&packet.encode_length_delimited_vec())
+            .unwrap();
+        assert!(mconn.recv().is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::{env, fs, path::PathBuf, process};
+
+use tendermint_p2p::{error::ErrorDetail, node_key::NodeKey};
+
+/// `node_key.json` as written by CometBFT
+const NODE_KEY_JSON: &str = r#"{"priv_key":{"type":"tendermint/PrivKeyEd25519","value":"AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyB5tVYuj+ZU+UB4sRLoqYunkB+FOuaVvtfg45ELrQSWZA=="}}"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("tendermint-p2p-{}-{name}", process::id()))
+}
+
+#[test]
+fn test_parse_cometbft_node_key() {
+    let node_key = NodeKey::parse_json(NODE_KEY_JSON).expect("a valid node key");
+    assert_eq!(
+        node_key.node_id().to_string(),
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac"
+    );
+    let reparsed = NodeKey::parse_json(&node_key.to_json()).expect("a valid node key");
+    assert_eq!(reparsed.node_id(), node_key.node_id());
+    assert_eq!(
+        node_key.to_json(),
+        NODE_KEY_JSON,
+        "the key must be serialized as CometBFT does"
+    );
+}
+
+#[test]
+fn test_node_id_derived_from_public_key() {
+    let node_key = NodeKey::generate();
+    assert_eq!(node_key.node_id(), node_key.public_key().peer_id());
+    assert_ne!(node_key.node_id(), NodeKey::generate().node_id());
+}
+
+#[test]
+fn test_load_or_generate_persists_key() {
+    let path = temp_path("node_key.json");
+    let _ = fs::remove_file(&path);
+
+    let generated = NodeKey::load_or_generate(&path).expect("to generate a node key");
+    let loaded = NodeKey::load_or_generate(&path).expect("to load the node key");
+    assert_eq!(loaded.node_id(), generated.node_id());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_parse_malformed_node_key() {
+    let err = NodeKey::parse_json(r#"{"priv_key":{}}"#).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::Json(_)));
+
+    let err = NodeKey::load_json_file(temp_path("missing_node_key.json")).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::FileIo(_)));
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::HashSet,
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    thread,
+};
+
+use tendermint::Time;
+use tendermint_p2p::{
+    addrbook::{AddrBook, NetAddress},
+    error::ErrorDetail,
+    mconnection::{MConnConfig, MConnection},
+    node_key::NodeKey,
+    pex::{self, Pex, PexConfig, PexMessage},
+    secret_connection::Version,
+};
+
+fn addr(s: &str) -> NetAddress {
+    s.parse().expect("a valid network address")
+}
+
+fn peer(i: u8) -> NetAddress {
+    NetAddress {
+        id: tendermint::node::Id::new([i; 20]),
+        ip: Ipv4Addr::new(35, 233, i, 1).into(),
+        port: 26656,
+    }
+}
+
+fn now() -> Time {
+    Time::parse_from_rfc3339("2023-01-12T10:00:00Z").unwrap()
+}
+
+#[test]
+fn test_pex_message_encoding() {
+    let request = PexMessage::Request;
+    assert_eq!(request.encode_vec(), vec![0x0a, 0x00]);
+    assert_eq!(PexMessage::decode(&request.encode_vec()).unwrap(), request);
+
+    let addrs = PexMessage::Addrs(vec![
+        peer(1),
+        addr("65b60673d6ed884bf01c2c222d82ada0740f29ac@[2001:4860::1]:26656"),
+    ]);
+    assert_eq!(PexMessage::decode(&addrs.encode_vec()).unwrap(), addrs);
+
+    let err = PexMessage::decode(&[]).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::MalformedPexMessage(_)));
+}
+
+#[test]
+fn test_pex_rate_limits_requests() {
+    let mut book = AddrBook::new();
+    for i in 1..=3 {
+        book.add_address(peer(i), peer(1));
+    }
+    let mut pex = Pex::new(peer(0).id, PexConfig::default());
+
+    let PexMessage::Addrs(addrs) = pex.handle_request(&book, &peer(1).id, now()).unwrap() else {
+        panic!("expected addresses");
+    };
+    // The addresses are selected in random order.
+    let addrs: HashSet<_> = addrs.into_iter().collect();
+    assert_eq!(addrs, HashSet::from([peer(2), peer(3)]));
+
+    let err = pex
+        .handle_request(
+            &book,
+            &peer(1).id,
+            now()
+                .checked_add(pex::DEFAULT_MIN_REQUEST_INTERVAL / 2)
+                .unwrap(),
+        )
+        .unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::PexRateLimited(_)));
+    // Other peers are not affected by the requests of the first one.
+    assert!(pex.handle_request(&book, &peer(2).id, now()).is_ok());
+
+    let later = now()
+        .checked_add(pex::DEFAULT_MIN_REQUEST_INTERVAL)
+        .unwrap();
+    assert!(pex.handle_request(&book, &peer(1).id, later).is_ok());
+}
+
+#[test]
+fn test_pex_selects_random_addrs() {
+    let mut book = AddrBook::new();
+    for i in 1..=200 {
+        book.add_address(peer(i), peer(1));
+    }
+    let mut pex = Pex::new(peer(0).id, PexConfig::default());
+
+    let selections: HashSet<_> = (201..=210)
+        .map(|i| {
+            let PexMessage::Addrs(addrs) = pex.handle_request(&book, &peer(i).id, now()).unwrap()
+            else {
+                panic!("expected addresses");
+            };
+            // 23% of the known addresses, as in CometBFT.
+            assert_eq!(addrs.len(), 46);
+            addrs
+        })
+        .collect();
+    assert!(
+        selections.len() > 1,
+        "the same addresses were always selected"
+    );
+}
+
+#[test]
+fn test_pex_accepts_only_requested_valid_addrs() {
+    let mut book = AddrBook::new();
+    let mut pex = Pex::new(peer(0).id, PexConfig::default());
+    let src = peer(1);
+
+    let err = pex
+        .handle_addrs(&mut book, &src, vec![peer(2)])
+        .unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::PexUnsolicited(_)));
+
+    assert_eq!(pex.request(src.id), PexMessage::Request);
+    let addrs = vec![
+        peer(2),
+        peer(0),
+        addr("2c9a5b4b0b4f1ad0fcb6b5f7e1a7e6a0ed3c8c3f@192.168.1.7:26656"),
+        addr("65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:0"),
+    ];
+    assert_eq!(pex.handle_addrs(&mut book, &src, addrs).unwrap(), 1);
+    assert_eq!(book.len(), 1);
+    assert_eq!(book.get(&peer(2).id).unwrap().src, src);
+
+    // Each request allows a single response.
+    assert!(pex.handle_addrs(&mut book, &src, vec![peer(3)]).is_err());
+
+    pex.request(src.id);
+    let too_many = (0..=250).map(peer).collect();
+    let err = pex.handle_addrs(&mut book, &src, too_many).unwrap_err();
+    assert!(matches!(err.detail(), ErrorDetail::PexTooManyAddrs(_)));
+}
+
+#[test]
+fn test_validate_address() {
+    for valid in [
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@[2001:4860::1]:26656",
+    ] {
+        assert!(
+            pex::validate_address(&addr(valid), false).is_ok(),
+            "{valid}"
+        );
+    }
+    for non_routable in [
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@127.0.0.1:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@10.0.0.1:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@100.64.0.1:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@[fd00::1]:26656",
+    ] {
+        assert!(
+            pex::validate_address(&addr(non_routable), false).is_err(),
+            "{non_routable}"
+        );
+        assert!(
+            pex::validate_address(&addr(non_routable), true).is_ok(),
+            "{non_routable}"
+        );
+    }
+    for invalid in [
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@0.0.0.0:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@255.255.255.255:26656",
+        "65b60673d6ed884bf01c2c222d82ada0740f29ac@35.233.155.199:0",
+    ] {
+        assert!(
+            pex::validate_address(&addr(invalid), true).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+fn tcp_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    (client, server)
+}
+
+fn net_address(node_key: &NodeKey, stream: &TcpStream) -> NetAddress {
+    let addr = stream.local_addr().unwrap();
+    NetAddress {
+        id: node_key.node_id(),
+        ip: addr.ip(),
+        port: addr.port(),
+    }
+}
+
+fn mconnection(stream: TcpStream, node_key: &NodeKey) -> MConnection {
+    MConnection::handshake(
+        stream,
+        node_key.signing_key().clone(),
+        Version::V0_34,
+        vec![pex::channel_descriptor()],
+        MConnConfig::default(),
+    )
+    .expect("handshake to succeed")
+}
+
+#[test]
+fn test_pex_exchange_over_mconnection() {
+    let (server_key, client_key) = (NodeKey::generate(), NodeKey::generate());
+    let (client_stream, server_stream) = tcp_pair();
+    let server_net_addr = net_address(&server_key, &server_stream);
+    let client_net_addr = net_address(&client_key, &client_stream);
+
+    let server = thread::spawn(move || {
+        let mconn = mconnection(server_stream, &server_key);
+        let mut book = AddrBook::new();
+        book.add_address(peer(1), peer(1));
+        book.add_address(peer(2), peer(1));
+        let mut pex = Pex::new(server_key.node_id(), PexConfig::default());
+
+        let added = pex::exchange(&mut pex, &mut book, &client_net_addr, &mconn, now())
+            .expect("to exchange addresses");
+        // The connection is kept until the client got its response.
+        (added, book, mconn)
+    });
+
+    let mconn = mconnection(client_stream, &client_key);
+    let mut book = AddrBook::new();
+    book.add_address(peer(3), peer(3));
+    let mut pex = Pex::new(client_key.node_id(), PexConfig::default());
+
+    let added = pex::exchange(&mut pex, &mut book, &server_net_addr, &mconn, now())
+        .expect("to exchange addresses");
+    assert_eq!(added, 2);
+    assert_eq!(book.get(&peer(2).id).unwrap().src, server_net_addr);
+
+    let (server_added, server_book, _) = server.join().expect("server thread has panicked");
+    assert_eq!(server_added, 1);
+    assert!(server_book.get(&peer(3).id).is_some());
+}
@@ -0,0 +1,302 @@
+use std::{env, fs, net::TcpListener, process, thread};
+
+use rand_core::OsRng;
+use tendermint::{
+    block, chain, privval::Step, proposal, vote, Hash, Proposal, PublicKey, Time, Vote,
+};
+use tendermint_p2p::{
+    privval::{LastSignature, PrivvalClient, Request, Response, SignState, SignerServer},
+    secret_connection::Version,
+};
+
+fn chain_id() -> chain::Id {
+    "test-chain".parse().unwrap()
+}
+
+fn vote(height: u32, round: u16, vote_type: vote::Type) -> Vote {
+    Vote {
+        vote_type,
+        height: height.into(),
+        round: round.into(),
+        signature: None,
+        ..Vote::default()
+    }
+}
+
+fn proposal(height: u32, round: u16) -> Proposal {
+    Proposal {
+        msg_type: proposal::Type::Proposal,
+        height: height.into(),
+        round: round.into(),
+        pol_round: None,
+        block_id: None,
+        timestamp: Some(Time::unix_epoch()),
+        signature: None,
+    }
+}
+
+fn verify(public_key: PublicKey, sign_bytes: &[u8], signature: &[u8]) {
+    let key =
+        ed25519_consensus::VerificationKey::try_from(public_key.to_bytes().as_slice()).unwrap();
+    let signature = ed25519_consensus::Signature::try_from(signature).unwrap();
+    key.verify(&signature, sign_bytes)
+        .expect("a valid signature");
+}
+
+#[test]
+fn test_sign_state_cometbft_json() {
+    let state: SignState =
+        serde_json::from_str(r#"{"height":"0","round":0,"step":0}"#).expect("the initial state");
+    assert_eq!(state, SignState::default());
+
+    let json = r#"{
+  "height": "12",
+  "round": 1,
+  "step": 3,
+  "signature": "AQID",
+  "signbytes": "0A0B0C"
+}"#;
+    let state: SignState = serde_json::from_str(json).expect("a valid state");
+    assert_eq!(state.height, block::Height::from(12_u32));
+    assert_eq!(state.round, block::Round::from(1_u16));
+    assert_eq!(state.step, Step::Precommit);
+    assert_eq!(state.signature, vec![1, 2, 3]);
+    assert_eq!(state.signbytes, vec![0x0a, 0x0b, 0x0c]);
+    assert_eq!(serde_json::to_string_pretty(&state).unwrap(), json);
+}
+
+#[test]
+fn test_sign_state_prevents_double_signing() {
+    let mut state = SignState::default();
+    let (height, round) = (block::Height::from(5_u32), block::Round::from(1_u16));
+    assert_eq!(
+        state.check(height, round, Step::Prevote, b"vote").unwrap(),
+        None
+    );
+    state.update(
+        height,
+        round,
+        Step::Prevote,
+        b"vote".to_vec(),
+        b"sig".to_vec(),
+    );
+
+    // The same message may be signed again, with the same signature.
+    assert_eq!(
+        state.check(height, round, Step::Prevote, b"vote").unwrap(),
+        Some(LastSignature {
+            signature: b"sig",
+            timestamp: None
+        })
+    );
+    // Another message for the same height, round and step may not.
+    assert!(state
+        .check(height, round, Step::Prevote, b"other vote")
+        .is_err());
+    // Nor may messages for a lower height, round or step.
+    assert!(state
+        .check(height, round, Step::Propose, b"proposal")
+        .is_err());
+    assert!(state
+        .check(height, 0_u16.into(), Step::Precommit, b"vote")
+        .is_err());
+    assert!(state
+        .check(4_u32.into(), round, Step::Precommit, b"vote")
+        .is_err());
+    // Messages for a higher height, round or step may be signed.
+    assert!(state.check(height, round, Step::Precommit, b"vote").is_ok());
+    assert!(state
+        .check(height, 2_u16.into(), Step::Propose, b"vote")
+        .is_ok());
+    assert!(state
+        .check(6_u32.into(), 0_u16.into(), Step::Propose, b"vote")
+        .is_ok());
+}
+
+#[test]
+fn test_sign_state_allows_timestamp_only_differences() {
+    let at = |secs| Some(Time::from_unix_timestamp(secs, 0).unwrap());
+    let sign_bytes = |vote: Vote| vote.to_signable_vec(chain_id()).unwrap();
+    let first = Vote {
+        timestamp: at(1),
+        ..vote(5, 1, vote::Type::Prevote)
+    };
+    let (height, round) = (first.height, first.round);
+
+    let mut state = SignState::default();
+    state.update(
+        height,
+        round,
+        Step::Prevote,
+        sign_bytes(first.clone()),
+        b"sig".to_vec(),
+    );
+
+    // The same vote with another timestamp gets the signature of the first
+    // one, along with its timestamp.
+    let later = Vote {
+        timestamp: at(2),
+        ..first.clone()
+    };
+    assert_eq!(
+        state
+            .check(height, round, Step::Prevote, &sign_bytes(later))
+            .unwrap(),
+        Some(LastSignature {
+            signature: b"sig",
+            timestamp: at(1)
+        })
+    );
+
+    // A vote for another block may not be signed.
+    let conflicting = Vote {
+        block_id: Some(block::Id {
+            hash: Hash::Sha256([1; 32]),
+            ..block::Id::default()
+        }),
+        ..first
+    };
+    assert!(state
+        .check(height, round, Step::Prevote, &sign_bytes(conflicting))
+        .is_err());
+}
+
+#[test]
+fn test_sign_state_persistence() {
+    let path = env::temp_dir().join(format!(
+        "tendermint-p2p-{}-priv_validator_state.json",
+        process::id()
+    ));
+    let _ = fs::remove_file(&path);
+
+    let key = ed25519_consensus::SigningKey::new(OsRng {});
+    let mut server = SignerServer::new(key, chain_id(), SignState::load(&path).unwrap());
+    let request = Request::SignVote(tendermint::vote::SignVoteRequest {
+        vote: vote(7, 0, vote::Type::Precommit),
+        chain_id: chain_id(),
+    });
+    assert!(matches!(
+        server.handle(request),
+        Response::SignedVote(res) if res.error.is_none()
+    ));
+
+    let state = SignState::load(&path).unwrap();
+    assert_eq!(state.height, block::Height::from(7_u32));
+    assert_eq!(state.step, Step::Precommit);
+    assert_eq!(&state, server.state());
+    fs::remove_file(&path).unwrap();
+
+    // Messages of other chains are refused.
+    let request = Request::SignVote(tendermint::vote::SignVoteRequest {
+        vote: vote(8, 0, vote::Type::Precommit),
+        chain_id: "other-chain".parse().unwrap(),
+    });
+    assert!(matches!(
+        server.handle(request),
+        Response::SignedVote(res) if res.vote.is_none() && res.error.is_some()
+    ));
+    assert_eq!(server.state().height, block::Height::from(7_u32));
+}
+
+#[test]
+fn test_remote_signer_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    let consensus_key = ed25519_consensus::SigningKey::new(OsRng {});
+    let expected_public_key =
+        PublicKey::from_raw_ed25519(consensus_key.verification_key().as_bytes()).unwrap();
+    let signer = thread::spawn(move || {
+        let mut server = SignerServer::new(consensus_key, chain_id(), SignState::default());
+        let identity_key = ed25519_consensus::SigningKey::new(OsRng {});
+        server
+            .dial_tcp(addr, identity_key, Version::V0_34)
+            .expect("to serve the node until it disconnects");
+        server.state().clone()
+    });
+
+    let node_key = ed25519_consensus::SigningKey::new(OsRng {});
+    let mut client = PrivvalClient::accept_tcp(&listener, node_key, Version::V0_34, chain_id())
+        .expect("the signer to connect");
+
+    client.ping().expect("the signer to respond");
+    let public_key = client.public_key().expect("the public key");
+    assert_eq!(public_key, expected_public_key);
+
+    let unsigned = proposal(10, 0);
+    let signed = client.sign_proposal(unsigned.clone()).unwrap();
+    let sign_bytes = unsigned.to_signable_vec(chain_id()).unwrap();
+    verify(
+        public_key,
+        &sign_bytes,
+        signed.signature.unwrap().as_bytes(),
+    );
+
+    let unsigned = vote(10, 0, vote::Type::Prevote);
+    let signed = client.sign_vote(unsigned.clone()).unwrap();
+    let signature = signed.signature.unwrap();
+    verify(
+        public_key,
+        &unsigned.to_signable_vec(chain_id()).unwrap(),
+        signature.as_bytes(),
+    );
+    // Signing the same vote again yields the same signature.
+    let signed_again = client.sign_vote(unsigned).unwrap();
+    assert_eq!(signed_again.signature, Some(signature.clone()));
+
+    // Signing it with another timestamp yields the same signature, with the
+    // timestamp it was made for.
+    let later = Vote {
+        timestamp: Some(Time::from_unix_timestamp(1, 0).unwrap()),
+        ..vote(10, 0, vote::Type::Prevote)
+    };
+    let signed_later = client.sign_vote(later).unwrap();
+    assert_eq!(signed_later.signature, Some(signature));
+    assert_eq!(
+        signed_later.timestamp,
+        vote(10, 0, vote::Type::Prevote).timestamp
+    );
+
+    // Conflicting votes are refused.
+    let conflicting = Vote {
+        block_id: Some(block::Id {
+            hash: Hash::Sha256([1; 32]),
+            ..block::Id::default()
+        }),
+        ..vote(10, 0, vote::Type::Prevote)
+    };
+    assert!(client.sign_vote(conflicting).is_err());
+    assert!(client.sign_vote(vote(9, 0, vote::Type::Precommit)).is_err());
+
+    drop(client);
+    let state = signer.join().expect("signer thread has panicked");
+    assert_eq!(state.height, block::Height::from(10_u32));
+    assert_eq!(state.step, Step::Prevote);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_remote_signer_over_unix_socket() {
+    use std::os::unix::net::UnixListener;
+
+    let path = env::temp_dir().join(format!("tendermint-p2p-{}-privval.sock", process::id()));
+    let _ = fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).expect("to be able to bind the socket");
+
+    let signer_path = path.clone();
+    let signer = thread::spawn(move || {
+        let key = ed25519_consensus::SigningKey::new(OsRng {});
+        let mut server = SignerServer::new(key, chain_id(), SignState::default());
+        server
+            .dial_unix(signer_path)
+            .expect("to serve the node until it disconnects");
+    });
+
+    let mut client = PrivvalClient::accept_unix(&listener, chain_id()).unwrap();
+    client.ping().expect("the signer to respond");
+    assert!(client.sign_vote(vote(1, 0, vote::Type::Prevote)).is_ok());
+
+    drop(client);
+    signer.join().expect("signer thread has panicked");
+    fs::remove_file(&path).unwrap();
+}
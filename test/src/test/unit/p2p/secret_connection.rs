@@ -11,6 +11,7 @@ use x25519_dalek::PublicKey as EphemeralPublic;
 
 use crate::pipe;
 
+mod asynchronous;
 mod nonce;
 mod public_key;
 
@@ -178,6 +179,54 @@ fn test_split_secret_connection() {
     peer1.join().expect("peer 1's thread to run to completion")
 }
 
+#[test]
+fn test_into_split_full_duplex() {
+    const MESSAGES: usize = 16;
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    // Both peers write all their messages before reading any, which only
+    // completes if each of them reads and writes concurrently.
+    let run_peer = |stream: TcpStream, name: &'static str| {
+        let conn = new_peer_conn(stream).expect("handshake to succeed");
+        let (mut read_half, mut write_half) = conn.into_split();
+        assert_eq!(read_half.remote_pubkey(), write_half.remote_pubkey());
+
+        let writer = thread::spawn(move || {
+            for i in 0..MESSAGES {
+                let msg = format!("{name}: {i:04}");
+                write_half
+                    .write_all(msg.as_bytes())
+                    .expect("to write message");
+            }
+        });
+        let mut received = Vec::new();
+        for _ in 0..MESSAGES {
+            let mut buf = [0_u8; 11];
+            read_half.read_exact(&mut buf).expect("to read message");
+            received.push(String::from_utf8_lossy(&buf).to_string());
+        }
+        writer.join().expect("writer thread has panicked");
+        received
+    };
+
+    let peer1 = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("an incoming TCP stream");
+        run_peer(stream, "peer1")
+    });
+    let stream = TcpStream::connect(addr).expect("to be able to connect to peer 1");
+    let received_by_peer2 = run_peer(stream, "peer2");
+    let received_by_peer1 = peer1.join().expect("peer 1's thread has panicked");
+
+    let expected = |name: &str| {
+        (0..MESSAGES)
+            .map(|i| format!("{name}: {i:04}"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(received_by_peer1, expected("peer2"));
+    assert_eq!(received_by_peer2, expected("peer1"));
+}
+
 fn new_peer_conn<IoHandler>(
     io_handler: IoHandler,
 ) -> Result<SecretConnection<IoHandler>, tendermint_p2p::error::Error>
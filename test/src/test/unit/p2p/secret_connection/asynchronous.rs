@@ -0,0 +1,140 @@
+use std::{
+    io::{Read as _, Write as _},
+    net, thread,
+};
+
+use rand_core::OsRng;
+use tendermint_p2p::secret_connection::{
+    AsyncSecretConnection, PublicKey, SecretConnection, Version, DATA_MAX_SIZE,
+};
+use tokio::{
+    io::{duplex, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+};
+
+async fn new_async_peer_conn<S>(
+    io: S,
+) -> Result<AsyncSecretConnection<S>, tendermint_p2p::error::Error>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let privkey = ed25519_consensus::SigningKey::new(OsRng {});
+    AsyncSecretConnection::new(io, privkey, Version::V0_34).await
+}
+
+#[tokio::test]
+async fn test_async_handshake_and_read_write() {
+    // Spans several frames, the last of which is partial.
+    let message = vec![7_u8; 3 * DATA_MAX_SIZE + 42];
+    let (stream1, stream2) = duplex(512);
+
+    let (conn1, conn2) = tokio::join!(new_async_peer_conn(stream1), new_async_peer_conn(stream2));
+    let (mut conn1, mut conn2) = (conn1.unwrap(), conn2.unwrap());
+    assert_ne!(conn1.remote_pubkey(), conn2.remote_pubkey());
+
+    let expected = message.clone();
+    let (written, received) = tokio::join!(
+        async move {
+            conn1.write_all(&message).await?;
+            conn1.flush().await
+        },
+        async move {
+            let mut buf = vec![0_u8; expected.len()];
+            conn2.read_exact(&mut buf).await.map(|_| buf)
+        },
+    );
+    written.expect("to write the message");
+    assert_eq!(received.expect("to read the message"), expected);
+}
+
+#[tokio::test]
+async fn test_async_into_split_full_duplex() {
+    const MESSAGES: usize = 16;
+    let (stream1, stream2) = duplex(64);
+
+    let (conn1, conn2) = tokio::join!(new_async_peer_conn(stream1), new_async_peer_conn(stream2));
+
+    // Both peers write all their messages before reading any, which only
+    // completes if each of them reads and writes concurrently.
+    let run_peer = |conn: AsyncSecretConnection<_>, name: &'static str| async move {
+        let (mut read_half, mut write_half) = conn.into_split();
+        assert_eq!(read_half.remote_pubkey(), write_half.remote_pubkey());
+
+        let writer = tokio::spawn(async move {
+            for i in 0..MESSAGES {
+                let msg = format!("{name}: {i:04}");
+                write_half
+                    .write_all(msg.as_bytes())
+                    .await
+                    .expect("to write message");
+            }
+            write_half.flush().await.expect("to flush messages");
+        });
+        let mut received = Vec::new();
+        for _ in 0..MESSAGES {
+            let mut buf = [0_u8; 11];
+            read_half
+                .read_exact(&mut buf)
+                .await
+                .expect("to read message");
+            received.push(String::from_utf8_lossy(&buf).to_string());
+        }
+        writer.await.expect("writer task has panicked");
+        received
+    };
+
+    let (received_by_peer1, received_by_peer2) = tokio::join!(
+        run_peer(conn1.unwrap(), "peer1"),
+        run_peer(conn2.unwrap(), "peer2"),
+    );
+
+    let expected = |name: &str| {
+        (0..MESSAGES)
+            .map(|i| format!("{name}: {i:04}"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(received_by_peer1, expected("peer2"));
+    assert_eq!(received_by_peer2, expected("peer1"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_async_to_sync_connection() {
+    const PING: &str = "ping from the async peer";
+    const PONG: &str = "pong from the sync peer";
+
+    let listener = net::TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    let sync_peer = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("an incoming TCP stream");
+        let privkey = ed25519_consensus::SigningKey::new(OsRng {});
+        let mut conn =
+            SecretConnection::new(stream, privkey, Version::V0_34).expect("handshake to succeed");
+
+        let mut buf = [0_u8; PING.len()];
+        conn.read_exact(&mut buf).expect("to read the ping");
+        assert_eq!(&buf, PING.as_bytes());
+        conn.write_all(PONG.as_bytes()).expect("to write the pong");
+        conn.remote_pubkey()
+    });
+
+    let stream = TcpStream::connect(addr)
+        .await
+        .expect("to be able to connect to the sync peer");
+    let privkey = ed25519_consensus::SigningKey::new(OsRng {});
+    let local_pubkey = PublicKey::from(&privkey);
+    let mut conn = AsyncSecretConnection::new(stream, privkey, Version::V0_34)
+        .await
+        .expect("handshake to succeed");
+
+    conn.write_all(PING.as_bytes())
+        .await
+        .expect("to write the ping");
+    conn.flush().await.expect("to flush the ping");
+    let mut buf = [0_u8; PONG.len()];
+    conn.read_exact(&mut buf).await.expect("to read the pong");
+    assert_eq!(&buf, PONG.as_bytes());
+
+    let remote_pubkey = sync_peer.join().expect("sync peer's thread has panicked");
+    assert_eq!(remote_pubkey, local_pubkey);
+}
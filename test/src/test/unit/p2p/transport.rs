@@ -0,0 +1,143 @@
+use std::{net::Ipv4Addr, thread};
+
+use rand_core::OsRng;
+use tendermint::PublicKey;
+use tendermint_p2p::{
+    secret_connection::{self, Version},
+    transport::{
+        BindInfo, ConnectInfo, Connection, Endpoint, StreamId, StreamSend, TcpTransport, Transport,
+    },
+};
+use tendermint_std_ext::resolve::Resolver;
+
+fn new_key() -> (ed25519_consensus::SigningKey, PublicKey) {
+    let private_key = ed25519_consensus::SigningKey::new(OsRng {});
+    let public_key =
+        PublicKey::from_raw_ed25519(private_key.verification_key().as_bytes()).unwrap();
+    (private_key, public_key)
+}
+
+#[test]
+fn test_tcp_transport_exchange_messages() {
+    let (server_key, server_public_key) = new_key();
+    let server_id = secret_connection::PublicKey::from(&server_key).peer_id();
+    let (server_endpoint, mut incoming) = TcpTransport::new(server_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: server_public_key,
+        })
+        .expect("to be able to bind to 127.0.0.1");
+    let server_addr = Endpoint::<&str>::listen_addrs(&server_endpoint)[0];
+
+    let (client_key, client_public_key) = new_key();
+    let server = thread::spawn(move || {
+        let conn = incoming
+            .next()
+            .unwrap()
+            .expect("an incoming connection from the client");
+        assert_eq!(conn.public_key(), client_public_key);
+
+        let (mut read, mut send) = conn.open_bidirectional(StreamId::Pex).unwrap();
+        let msg = read.next().unwrap().expect("to read a message");
+        send.send(msg).expect("to echo the message");
+
+        // only a single stream can be opened on a connection
+        assert!(conn.open_bidirectional(StreamId::Pex).is_err());
+    });
+
+    let (client_endpoint, _) = TcpTransport::new(client_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: client_public_key,
+        })
+        .unwrap();
+    let conn = client_endpoint
+        .connect(ConnectInfo {
+            addrs: server_addr,
+            id: server_id,
+        })
+        .expect("to connect to the server");
+    assert_eq!(conn.public_key(), server_public_key);
+
+    let msg = vec![42_u8; 3000];
+    let (mut read, mut send) = conn.open_bidirectional(StreamId::Pex).unwrap();
+    send.send(&msg).unwrap();
+    assert_eq!(read.next().unwrap().unwrap(), msg);
+
+    server.join().expect("server thread has panicked");
+}
+
+#[test]
+fn test_tcp_transport_rejects_unexpected_peer() {
+    let (server_key, server_public_key) = new_key();
+    let (server_endpoint, mut incoming) = TcpTransport::new(server_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: server_public_key,
+        })
+        .unwrap();
+    let server_addr = Endpoint::<&str>::listen_addrs(&server_endpoint)[0];
+    let server = thread::spawn(move || {
+        let _ = incoming.next();
+    });
+
+    let (client_key, client_public_key) = new_key();
+    let (client_endpoint, _) = TcpTransport::new(client_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: client_public_key,
+        })
+        .unwrap();
+    let res = client_endpoint.connect(ConnectInfo {
+        addrs: server_addr,
+        id: tendermint::node::Id::new([0; 20]),
+    });
+    assert!(res.is_err());
+
+    server.join().expect("server thread has panicked");
+}
+
+#[test]
+fn test_tcp_transport_dials_resolved_host_names() {
+    let (server_key, server_public_key) = new_key();
+    let server_id = secret_connection::PublicKey::from(&server_key).peer_id();
+    let (server_endpoint, mut incoming) = TcpTransport::new(server_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: server_public_key,
+        })
+        .unwrap();
+    let server_addr = Endpoint::<&str>::listen_addrs(&server_endpoint)[0];
+    let server = thread::spawn(move || {
+        incoming
+            .next()
+            .unwrap()
+            .expect("an incoming connection from the client");
+    });
+
+    let resolver = Resolver::builder()
+        .host("server.chain", [Ipv4Addr::LOCALHOST.into()])
+        .build();
+    let (client_key, client_public_key) = new_key();
+    let (client_endpoint, _) = TcpTransport::new(client_key, Version::V0_34)
+        .bind(BindInfo {
+            advertise_addrs: "127.0.0.1:0",
+            bind_addrs: "127.0.0.1:0",
+            public_key: client_public_key,
+        })
+        .unwrap();
+    let conn = client_endpoint
+        .connect(ConnectInfo {
+            addrs: resolver.addr("server.chain", server_addr.port()),
+            id: server_id,
+        })
+        .expect("to connect to the server by its overridden host name");
+    assert_eq!(conn.public_key(), server_public_key);
+
+    server.join().expect("server thread has panicked");
+}
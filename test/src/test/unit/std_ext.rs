@@ -0,0 +1,195 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use tendermint_std_ext::resolve::Resolver;
+
+const NAME_SERVER_ADDR: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 1);
+
+/// Spawns a name server answering the `A` queries of any name with
+/// [`NAME_SERVER_ADDR`], with a TTL of 0 for the names starting with
+/// `volatile`, and `NXDOMAIN` for the names starting with `missing`. The
+/// names starting with `spoofed` and `mistyped` are answered as if another
+/// name, or another type of record, had been queried. Returns its address and
+/// the number of queries it has received.
+fn spawn_name_server() -> (SocketAddr, Arc<AtomicUsize>) {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    let addr = socket.local_addr().unwrap();
+    let queries = Arc::new(AtomicUsize::new(0));
+    let counter = queries.clone();
+    thread::spawn(move || {
+        let mut buf = [0_u8; 512];
+        while let Ok((len, peer)) = socket.recv_from(&mut buf) {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let query = &buf[..len];
+            let name = &query[13..];
+            let qtype = u16::from_be_bytes([query[len - 4], query[len - 3]]);
+
+            let mut response = query[..2].to_vec();
+            let (flags, answers) = if name.starts_with(b"missing") {
+                (0x8183_u16, 0)
+            } else if qtype == 1 {
+                (0x8180, 1)
+            } else {
+                (0x8180, 0)
+            };
+            response.extend_from_slice(&flags.to_be_bytes());
+            response.extend_from_slice(&[0, 1, 0, answers, 0, 0, 0, 0]);
+            let mut question = query[12..].to_vec();
+            if name.starts_with(b"spoofed") {
+                question[1] = b'x';
+            } else if name.starts_with(b"mistyped") {
+                let qtype = question.len() - 3;
+                question[qtype] = 5;
+            }
+            response.extend_from_slice(&question);
+            if answers == 1 {
+                let ttl: u32 = if name.starts_with(b"volatile") {
+                    0
+                } else {
+                    300
+                };
+                // The owner name is a pointer to the name of the question.
+                response.extend_from_slice(&[0xc0, 12, 0, 1, 0, 1]);
+                response.extend_from_slice(&ttl.to_be_bytes());
+                response.extend_from_slice(&[0, 4]);
+                response.extend_from_slice(&NAME_SERVER_ADDR.octets());
+            }
+            socket.send_to(&response, peer).unwrap();
+        }
+    });
+    (addr, queries)
+}
+
+#[test]
+fn test_resolver_static_overrides() {
+    let override_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10));
+    let resolver = Resolver::builder()
+        .host("Node0.Chain", [override_ip])
+        .build();
+
+    for host in ["node0.chain", "NODE0.CHAIN", "node0.chain."] {
+        assert_eq!(resolver.lookup(host).unwrap(), vec![override_ip]);
+    }
+    assert_eq!(
+        resolver.resolve("node0.chain", 26657).unwrap(),
+        vec![SocketAddr::new(override_ip, 26657)]
+    );
+    // IP addresses are never looked up.
+    assert_eq!(
+        resolver.lookup("[::1]").unwrap(),
+        vec!["::1".parse::<IpAddr>().unwrap()]
+    );
+}
+
+#[test]
+fn test_resolver_hosts_file() {
+    let resolver = Resolver::builder()
+        .hosts_file(
+            "# Validators\n\
+             10.0.0.2 validator-0 validator-0.chain\n\
+             \n\
+             fd00::2 validator-0 # dual-stack\n",
+        )
+        .unwrap()
+        .build();
+    assert_eq!(
+        resolver.lookup("validator-0").unwrap(),
+        vec![
+            "10.0.0.2".parse::<IpAddr>().unwrap(),
+            "fd00::2".parse().unwrap()
+        ]
+    );
+    assert_eq!(
+        resolver.lookup("validator-0.chain").unwrap(),
+        vec!["10.0.0.2".parse::<IpAddr>().unwrap()]
+    );
+
+    for contents in ["10.0.0.300 validator-0", "10.0.0.2"] {
+        let err = Resolver::builder().hosts_file(contents).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn test_resolver_queries_name_servers_and_caches_answers() {
+    let (name_server, queries) = spawn_name_server();
+    let resolver = Resolver::builder()
+        .name_server(name_server)
+        .cache_ttl(Duration::from_secs(60))
+        .build();
+
+    let expected = vec![IpAddr::V4(NAME_SERVER_ADDR)];
+    assert_eq!(
+        resolver.lookup("node0.svc.cluster.local").unwrap(),
+        expected
+    );
+    // Both the `A` and the `AAAA` records are queried.
+    assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+    // Cached answers are shared by the clones of the resolver.
+    assert_eq!(
+        resolver.clone().lookup("node0.svc.cluster.local").unwrap(),
+        expected
+    );
+    assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+    // Records with a TTL of 0 are not cached.
+    resolver.lookup("volatile.svc.cluster.local").unwrap();
+    resolver.lookup("volatile.svc.cluster.local").unwrap();
+    assert_eq!(queries.load(Ordering::SeqCst), 6);
+
+    resolver.clear_cache();
+    resolver.lookup("node0.svc.cluster.local").unwrap();
+    assert_eq!(queries.load(Ordering::SeqCst), 8);
+
+    let err = resolver.lookup("missing.svc.cluster.local").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_resolver_rejects_answers_to_other_questions() {
+    let (name_server, _) = spawn_name_server();
+    let resolver = Resolver::builder().name_server(name_server).build();
+
+    for host in ["spoofed.svc.cluster.local", "mistyped.svc.cluster.local"] {
+        let err = resolver.lookup(host).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{host}");
+    }
+}
+
+#[test]
+fn test_resolver_clamps_the_cache_ttl() {
+    let resolver = Resolver::builder().cache_ttl(Duration::MAX).build();
+
+    for _ in 0..2 {
+        assert!(resolver.lookup("localhost").unwrap()[0].is_loopback());
+    }
+}
+
+#[test]
+fn test_resolver_falls_back_to_the_next_name_server() {
+    // Nothing answers on the first name server.
+    let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let (name_server, _) = spawn_name_server();
+    let resolver = Resolver::builder()
+        .name_server(silent.local_addr().unwrap())
+        .name_server(name_server)
+        .timeout(Duration::from_millis(100))
+        .build();
+
+    assert_eq!(
+        resolver.lookup("node0.svc.cluster.local").unwrap(),
+        vec![IpAddr::V4(NAME_SERVER_ADDR)]
+    );
+}
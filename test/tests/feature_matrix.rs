@@ -0,0 +1,222 @@
+//! Workspace feature matrix.
+//!
+//! Builds every library crate of the workspace with no features, its default
+//! features, each of its features on its own and all of its features, in the
+//! spirit of `cargo hack --each-feature`. For every combination, all targets
+//! are checked and, if the crate has a `tests/features.rs` smoke test, that
+//! test is run as well.
+//!
+//! The matrix takes a long time to go through, so it is ignored by default:
+//!
+//! ```text
+//! cargo test -p tendermint-test --test feature_matrix -- --ignored
+//! ```
+//!
+//! Set `FEATURE_MATRIX_CRATES` to a comma-separated list of crate names to
+//! only check some of the crates.
+
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde_json::Value;
+
+/// Crates whose feature combinations are checked.
+const CRATES: &[&str] = &[
+    "tendermint",
+    "tendermint-proto",
+    "tendermint-config",
+    "tendermint-rpc",
+    "tendermint-abci",
+    "tendermint-p2p",
+    "tendermint-light-client-verifier",
+    "tendermint-light-client",
+    "tendermint-testgen",
+];
+
+/// A crate of the workspace, along with the features it advertises.
+struct Package {
+    name: String,
+    manifest_dir: PathBuf,
+    features: Vec<String>,
+}
+
+impl Package {
+    fn has_smoke_test(&self) -> bool {
+        self.manifest_dir
+            .join("tests")
+            .join("features.rs")
+            .is_file()
+    }
+
+    /// The feature combinations to check for this crate.
+    fn feature_sets(&self) -> Vec<FeatureSet> {
+        let mut sets = vec![FeatureSet::NoDefault, FeatureSet::Default];
+        sets.extend(self.features.iter().cloned().map(FeatureSet::Only));
+        sets.push(FeatureSet::All);
+        sets
+    }
+}
+
+enum FeatureSet {
+    NoDefault,
+    Default,
+    Only(String),
+    All,
+}
+
+impl FeatureSet {
+    fn args(&self) -> Vec<&str> {
+        match self {
+            FeatureSet::NoDefault => vec!["--no-default-features"],
+            FeatureSet::Default => vec![],
+            FeatureSet::Only(feature) => vec!["--no-default-features", "--features", feature],
+            FeatureSet::All => vec!["--all-features"],
+        }
+    }
+}
+
+impl fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureSet::NoDefault => write!(f, "no default features"),
+            FeatureSet::Default => write!(f, "default features"),
+            FeatureSet::Only(feature) => write!(f, "feature `{feature}`"),
+            FeatureSet::All => write!(f, "all features"),
+        }
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn cargo() -> Command {
+    let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    cmd.current_dir(workspace_root());
+    cmd
+}
+
+/// Reads the advertised features of the workspace crates from `cargo metadata`.
+///
+/// Features that cargo implicitly defines for optional dependencies are left
+/// out, as they are not part of a crate's interface.
+fn workspace_packages() -> Vec<Package> {
+    let output = cargo()
+        .args(["metadata", "--format-version", "1", "--no-deps"])
+        .output()
+        .expect("failed to run `cargo metadata`");
+    assert!(
+        output.status.success(),
+        "`cargo metadata` failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let metadata: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    metadata["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|package| {
+            let name = package["name"].as_str().unwrap().to_owned();
+            let manifest_path = Path::new(package["manifest_path"].as_str().unwrap());
+            let mut features: Vec<String> = package["features"]
+                .as_object()
+                .unwrap()
+                .iter()
+                .filter(|(feature, enables)| {
+                    let implicit = enables.as_array().unwrap().len() == 1
+                        && enables[0].as_str() == Some(&format!("dep:{feature}"));
+                    *feature != "default" && !implicit
+                })
+                .map(|(feature, _)| feature.clone())
+                .collect();
+            features.sort();
+
+            Package {
+                name,
+                manifest_dir: manifest_path.parent().unwrap().to_owned(),
+                features,
+            }
+        })
+        .collect()
+}
+
+fn selected_crates() -> Vec<String> {
+    match env::var("FEATURE_MATRIX_CRATES") {
+        Ok(crates) => crates.split(',').map(|c| c.trim().to_owned()).collect(),
+        Err(_) => CRATES.iter().map(|c| (*c).to_owned()).collect(),
+    }
+}
+
+/// Runs the given cargo command, returning a description of the failure if any.
+fn run(mut cmd: Command, description: String) -> Result<(), String> {
+    eprintln!("feature matrix: {description}");
+    let status = cmd
+        .env(
+            "CARGO_TARGET_DIR",
+            workspace_root().join("target").join("feature-matrix"),
+        )
+        .status()
+        .map_err(|e| format!("{description}: failed to run cargo: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(description)
+    }
+}
+
+#[test]
+fn matrix_crates_exist() {
+    let packages = workspace_packages();
+    for name in CRATES {
+        assert!(
+            packages.iter().any(|p| p.name == *name),
+            "crate {name} is not a member of the workspace"
+        );
+    }
+}
+
+#[test]
+#[ignore = "builds every feature combination of the workspace crates"]
+fn feature_matrix() {
+    let packages = workspace_packages();
+    let mut failures = vec![];
+
+    for name in selected_crates() {
+        let package = packages
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or_else(|| panic!("crate {name} is not a member of the workspace"));
+
+        for set in package.feature_sets() {
+            let mut check = cargo();
+            check
+                .args(["check", "--all-targets", "-p", &package.name])
+                .args(set.args());
+            let result = run(check, format!("check {} with {set}", package.name));
+            if let Err(failure) = result {
+                failures.push(failure);
+                continue;
+            }
+
+            if package.has_smoke_test() {
+                let mut smoke = cargo();
+                smoke
+                    .args(["test", "-p", &package.name, "--test", "features"])
+                    .args(set.args());
+                if let Err(failure) = run(smoke, format!("test {} with {set}", package.name)) {
+                    failures.push(failure);
+                }
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "some feature combinations failed:\n  {}",
+        failures.join("\n  ")
+    );
+}
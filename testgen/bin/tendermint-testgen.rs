@@ -1,6 +1,9 @@
 use gumdrop::Options;
 use simple_error::SimpleError;
-use tendermint_testgen::{helpers::*, Commit, Generator, Header, Time, Validator, Vote};
+use tendermint_testgen::{
+    helpers::*, Commit, Corpus, ExtendedCommit, Generator, Header, LightClientAttack, Time,
+    TimeSkew, Validator, Vote,
+};
 
 const USAGE: &str = r#"
 This is a small utility for producing tendermint datastructures
@@ -65,8 +68,16 @@ enum Command {
     Vote(Vote),
     #[options(help = "produce commit from validator array and other parameters")]
     Commit(Commit),
+    #[options(help = "produce commit with vote extensions, as in CometBFT 0.38")]
+    ExtendedCommit(ExtendedCommit),
+    #[options(help = "produce conflicting light blocks of a light client attack")]
+    LightClientAttack(LightClientAttack),
     #[options(help = "produce timestamp from number of seconds since epoch")]
     Time(Time),
+    #[options(help = "produce verifier test cases with skewed block times and clocks")]
+    TimeSkew(TimeSkew),
+    #[options(help = "write a fuzzing corpus of valid and mutated inputs to a directory")]
+    Corpus(Corpus),
 }
 
 fn encode_with_stdin<Opts: Generator<T> + Options, T: serde::Serialize>(
@@ -99,6 +110,25 @@ where
     }
 }
 
+fn write_corpus(cli: Corpus, read_stdin: bool) {
+    let res = if read_stdin {
+        read_stdin()
+            .and_then(|stdin| stdin.parse::<Corpus>())
+            .and_then(|default| cli.clone().merge_with_default(default).write())
+    } else {
+        cli.write()
+    };
+    match res {
+        Ok(count) => println!("wrote {count} inputs"),
+        Err(e) => {
+            eprintln!("Error: {e}\n");
+            eprintln!("Supported parameters for this command are: ");
+            print_params(cli.self_usage());
+            std::process::exit(1);
+        },
+    }
+}
+
 fn print_params(options: &str) {
     for line in options.lines().skip(1) {
         eprintln!("{line}");
@@ -131,6 +161,10 @@ fn main() {
         Some(Command::Header(cli)) => run_command(cli, opts.stdin),
         Some(Command::Vote(cli)) => run_command(cli, opts.stdin),
         Some(Command::Commit(cli)) => run_command(cli, opts.stdin),
+        Some(Command::ExtendedCommit(cli)) => run_command(cli, opts.stdin),
+        Some(Command::LightClientAttack(cli)) => run_command(cli, opts.stdin),
         Some(Command::Time(cli)) => run_command(cli, opts.stdin),
+        Some(Command::TimeSkew(cli)) => run_command(cli, opts.stdin),
+        Some(Command::Corpus(cli)) => write_corpus(cli, opts.stdin),
     }
 }
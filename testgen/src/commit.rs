@@ -121,7 +121,10 @@ impl Generator<block::Commit> for Commit {
         let votes_vals: Vec<Validator> =
             votes.iter().map(|v| v.validator.clone().unwrap()).collect();
         all_vals.append(&mut BTreeSet::from_iter(&votes_vals));
-        let all_vals: Vec<Validator> = all_vals.iter().map(|&x| x.clone()).collect();
+        let all_vals: Vec<Validator> = all_vals
+            .iter()
+            .map(|&x| x.clone().with_default_seed(header.seed))
+            .collect();
         let all_vals = sort_validators(&all_vals);
 
         let vote_to_sig = |v: &Vote| -> Result<block::CommitSig, SimpleError> {
@@ -169,6 +172,7 @@ mod tests {
     use tendermint::Time;
 
     use super::*;
+    use crate::validator::with_default_seed;
 
     #[test]
     fn test_commit() {
@@ -227,4 +231,50 @@ mod tests {
             };
         }
     }
+
+    #[test]
+    fn test_commit_seed() {
+        let valset = [
+            Validator::new("a"),
+            Validator::new("b"),
+            Validator::new("c"),
+        ];
+        let header = Header::new(&valset).height(10).seed(42);
+
+        let block_header = header.generate().unwrap();
+        let block_commit = Commit::new(header.clone(), 1).generate().unwrap();
+        assert_eq!(block_commit, Commit::new(header, 1).generate().unwrap());
+
+        let seeded_valset = sort_validators(&with_default_seed(&valset, Some(42)));
+        for (i, sig) in block_commit.signatures.iter().enumerate() {
+            match sig {
+                block::CommitSig::BlockIdFlagCommit {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => {
+                    let val = &seeded_valset[i];
+                    assert_eq!(*validator_address, val.generate().unwrap().address);
+                    let block_vote = tendermint::vote::Vote {
+                        vote_type: tendermint::vote::Type::Precommit,
+                        height: block_header.height,
+                        round: block_commit.round,
+                        block_id: Some(block_commit.block_id),
+                        timestamp: Some(*timestamp),
+                        validator_address: *validator_address,
+                        validator_index: (i as u32).try_into().unwrap(),
+                        signature: signature.clone(),
+                    };
+                    let sign_bytes =
+                        get_vote_sign_bytes(block_header.chain_id.clone(), &block_vote);
+                    assert!(verify_signature(
+                        &val.get_public_key().unwrap(),
+                        &sign_bytes,
+                        signature.as_ref().unwrap()
+                    ));
+                },
+                _ => panic!("signature was not a commit"),
+            };
+        }
+    }
 }
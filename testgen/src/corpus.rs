@@ -0,0 +1,358 @@
+//! Export of fuzzing corpora, in the layout of `cargo fuzz`: one directory per
+//! fuzz target, holding one input per file.
+
+use core::time::Duration;
+use std::{fs, path::Path};
+
+use gumdrop::Options;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use tendermint::{block, validator, Time};
+use tendermint_proto::v0_37::types::{
+    Commit as RawCommit, Header as RawHeader, ValidatorSet as RawValidatorSet, Vote as RawVote,
+};
+
+use crate::{helpers::SeededRng, light_block::TmLightBlock, Generator, LightChain};
+
+/// The input of the `verifier` fuzz target: a block to verify against a
+/// trusted block at the given time, with the options of the fuzz target
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifierInput {
+    pub trusted: TmLightBlock,
+    pub untrusted: TmLightBlock,
+    pub now: Time,
+}
+
+/// An input of a fuzz target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorpusEntry {
+    /// Name of the fuzz target, which is also the directory of the input
+    pub target: &'static str,
+    /// File name of the input
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// The ways valid inputs are mutated
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Mutation {
+    BitFlip,
+    Truncate,
+    RandomByte,
+    Duplicate,
+    Remove,
+}
+
+const MUTATIONS: [Mutation; 5] = [
+    Mutation::BitFlip,
+    Mutation::Truncate,
+    Mutation::RandomByte,
+    Mutation::Duplicate,
+    Mutation::Remove,
+];
+
+impl Mutation {
+    fn name(self) -> &'static str {
+        match self {
+            Mutation::BitFlip => "bit-flip",
+            Mutation::Truncate => "truncate",
+            Mutation::RandomByte => "random-byte",
+            Mutation::Duplicate => "duplicate",
+            Mutation::Remove => "remove",
+        }
+    }
+
+    fn apply(self, data: &[u8], rng: &mut SeededRng) -> Vec<u8> {
+        let mut data = data.to_vec();
+        if data.is_empty() {
+            return data;
+        }
+        let len = data.len() as u64;
+        let start = (rng.next_u64() % len) as usize;
+        let end = start + 1 + (rng.next_u64() % (len - start as u64)) as usize;
+        match self {
+            Mutation::BitFlip => data[start] ^= 1 << (rng.next_u64() % 8),
+            Mutation::Truncate => data.truncate(start),
+            Mutation::RandomByte => data[start] = rng.next_u64() as u8,
+            Mutation::Duplicate => {
+                let slice = data[start..end].to_vec();
+                data.splice(end..end, slice);
+            },
+            Mutation::Remove => {
+                data.drain(start..end);
+            },
+        }
+        data
+    }
+}
+
+/// A corpus of valid and mutated headers, commits, votes, validator sets and
+/// verifier inputs, encoded in protobuf and JSON, derived from a chain of
+/// blocks generated from a seed
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct Corpus {
+    #[options(help = "directory to write the corpus to (required)", meta = "DIR")]
+    pub output: Option<String>,
+    #[options(help = "number of blocks to derive the inputs from (default: 20)")]
+    pub blocks: Option<u64>,
+    #[options(help = "number of mutations of each valid input (default: 8)")]
+    pub mutations: Option<u64>,
+    #[options(
+        help = "seed for deriving the blocks and mutations (default: 0)",
+        meta = "SEED"
+    )]
+    pub seed: Option<u64>,
+}
+
+impl Corpus {
+    pub fn new(output: &str) -> Self {
+        Corpus {
+            output: Some(output.to_string()),
+            blocks: None,
+            mutations: None,
+            seed: None,
+        }
+    }
+    set_option!(output, &str, Some(output.to_string()));
+    set_option!(blocks, u64);
+    set_option!(mutations, u64);
+    set_option!(seed, u64);
+
+    /// Fill the missing parameters with the ones of `default`
+    pub fn merge_with_default(self, default: Self) -> Self {
+        Corpus {
+            output: self.output.or(default.output),
+            blocks: self.blocks.or(default.blocks),
+            mutations: self.mutations.or(default.mutations),
+            seed: self.seed.or(default.seed),
+        }
+    }
+
+    /// Generate the inputs of all the fuzz targets
+    pub fn entries(&self) -> Result<Vec<CorpusEntry>, SimpleError> {
+        let seed = self.seed.unwrap_or(0);
+        let blocks = self.blocks.unwrap_or(20);
+        if blocks < 2 {
+            bail!("failed to generate corpus: at least 2 blocks are needed")
+        }
+
+        let chain = LightChain::default_with_length_and_seed(blocks, seed);
+        let light_blocks = chain
+            .light_blocks
+            .iter()
+            .map(|lb| lb.generate())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut votes = Vec::new();
+        for commit in chain
+            .light_blocks
+            .iter()
+            .filter_map(|lb| lb.commit.as_ref())
+        {
+            for vote in commit.votes.iter().flatten() {
+                votes.push(vote.generate()?);
+            }
+        }
+        let headers: Vec<block::Header> = light_blocks
+            .iter()
+            .map(|lb| lb.signed_header.header.clone())
+            .collect();
+        let commits: Vec<block::Commit> = light_blocks
+            .iter()
+            .map(|lb| lb.signed_header.commit.clone())
+            .collect();
+        let validator_sets: Vec<validator::Set> = light_blocks
+            .iter()
+            .map(|lb| lb.validators.clone())
+            .collect();
+
+        let mut entries = Vec::new();
+        let mut add = |target: &'static str, inputs: Vec<Vec<u8>>| {
+            self.add_inputs(&mut entries, seed, target, inputs)
+        };
+        add("proto_header", encode_all::<_, RawHeader>(&headers));
+        add("proto_commit", encode_all::<_, RawCommit>(&commits));
+        add("proto_vote", encode_all::<_, RawVote>(&votes));
+        add(
+            "proto_validator_set",
+            encode_all::<_, RawValidatorSet>(&validator_sets),
+        );
+        add("json_header", to_json_all(&headers)?);
+        add("json_commit", to_json_all(&commits)?);
+        add("json_vote", to_json_all(&votes)?);
+        add("json_validator_set", to_json_all(&validator_sets)?);
+        add("verifier", to_json_all(&verifier_inputs(&light_blocks))?);
+
+        Ok(entries)
+    }
+
+    /// Write the inputs of all the fuzz targets to the output directory,
+    /// returning their number
+    pub fn write(&self) -> Result<usize, SimpleError> {
+        let output = match &self.output {
+            None => bail!("failed to write corpus: output directory is missing"),
+            Some(output) => Path::new(output),
+        };
+        let entries = self.entries()?;
+        for entry in &entries {
+            let dir = output.join(entry.target);
+            if let Err(e) = fs::create_dir_all(&dir) {
+                bail!("failed to create {:?}: {}", dir, e)
+            }
+            let path = dir.join(&entry.name);
+            if let Err(e) = fs::write(&path, &entry.data) {
+                bail!("failed to write {:?}: {}", path, e)
+            }
+        }
+        Ok(entries.len())
+    }
+
+    /// Add the given valid inputs of a target, along with their mutations
+    fn add_inputs(
+        &self,
+        entries: &mut Vec<CorpusEntry>,
+        seed: u64,
+        target: &'static str,
+        inputs: Vec<Vec<u8>>,
+    ) {
+        let mutations = self.mutations.unwrap_or(8) as usize;
+        for (i, data) in inputs.into_iter().enumerate() {
+            let mut rng = SeededRng::with_label(seed, &format!("{target}-{i}"));
+            for (j, mutation) in MUTATIONS.iter().cycle().take(mutations).enumerate() {
+                entries.push(CorpusEntry {
+                    target,
+                    name: format!("mutated-{i:04}-{j:02}-{}", mutation.name()),
+                    data: mutation.apply(&data, &mut rng),
+                });
+            }
+            entries.push(CorpusEntry {
+                target,
+                name: format!("valid-{i:04}"),
+                data,
+            });
+        }
+    }
+}
+
+impl std::str::FromStr for Corpus {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::helpers::parse_as::<Corpus>(s)
+    }
+}
+
+fn encode_all<T: Clone, Raw: Message + From<T>>(values: &[T]) -> Vec<Vec<u8>> {
+    values
+        .iter()
+        .map(|value| Raw::from(value.clone()).encode_to_vec())
+        .collect()
+}
+
+fn to_json_all<T: Serialize>(values: &[T]) -> Result<Vec<Vec<u8>>, SimpleError> {
+    let mut inputs = Vec::new();
+    for value in values {
+        inputs.push(try_with!(
+            serde_json::to_vec(value),
+            "failed to serialize into JSON"
+        ));
+    }
+    Ok(inputs)
+}
+
+/// Inputs verifying every block against its predecessor and against the first
+/// block, along with inputs which the verifier must reject: blocks verified
+/// against their successor, blocks from the future, and forged commits
+fn verifier_inputs(light_blocks: &[TmLightBlock]) -> Vec<VerifierInput> {
+    let after =
+        |lb: &TmLightBlock| (lb.signed_header.header.time + Duration::from_secs(1)).unwrap();
+    let before =
+        |lb: &TmLightBlock| (lb.signed_header.header.time - Duration::from_secs(3600)).unwrap();
+    let input = |trusted: &TmLightBlock, untrusted: &TmLightBlock, now| VerifierInput {
+        trusted: trusted.clone(),
+        untrusted: untrusted.clone(),
+        now,
+    };
+
+    let mut inputs = Vec::new();
+    for pair in light_blocks.windows(2) {
+        let (trusted, untrusted) = (&pair[0], &pair[1]);
+        inputs.push(input(trusted, untrusted, after(untrusted)));
+        inputs.push(input(&light_blocks[0], untrusted, after(untrusted)));
+        inputs.push(input(untrusted, trusted, after(untrusted)));
+        inputs.push(input(trusted, untrusted, before(trusted)));
+
+        let mut forged = untrusted.clone();
+        forged.signed_header.commit = trusted.signed_header.commit.clone();
+        inputs.push(input(trusted, &forged, after(untrusted)));
+    }
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn test_corpus_entries() {
+        let corpus = Corpus::new("unused").blocks(3).mutations(5).seed(1);
+        let entries = corpus.entries().unwrap();
+        assert_eq!(entries, corpus.entries().unwrap());
+
+        let count = |target| entries.iter().filter(|e| e.target == target).count();
+        assert_eq!(count("proto_header"), 3 * 6);
+        assert_eq!(count("json_validator_set"), 3 * 6);
+        // 2 validators signed each of the 3 blocks
+        assert_eq!(count("proto_vote"), 6 * 6);
+        // 5 inputs for each of the 2 pairs of subsequent blocks
+        assert_eq!(count("verifier"), 10 * 6);
+
+        let valid = |target| {
+            entries
+                .iter()
+                .filter(move |e| e.target == target && e.name.starts_with("valid-"))
+        };
+        for entry in valid("proto_header") {
+            let raw = RawHeader::decode(entry.data.as_slice()).unwrap();
+            block::Header::try_from(raw).unwrap();
+        }
+        for entry in valid("json_commit") {
+            serde_json::from_slice::<block::Commit>(&entry.data).unwrap();
+        }
+        for entry in valid("verifier") {
+            serde_json::from_slice::<VerifierInput>(&entry.data).unwrap();
+        }
+
+        let mutated: Vec<_> = entries
+            .iter()
+            .filter(|e| e.target == "proto_vote" && e.name.starts_with("mutated-0000-"))
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(
+            mutated,
+            [
+                "mutated-0000-00-bit-flip",
+                "mutated-0000-01-truncate",
+                "mutated-0000-02-random-byte",
+                "mutated-0000-03-duplicate",
+                "mutated-0000-04-remove"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_corpus_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = Corpus::new(dir.path().to_str().unwrap())
+            .blocks(2)
+            .mutations(1);
+        let count = corpus.write().unwrap();
+        assert_eq!(count, corpus.entries().unwrap().len());
+
+        let valid = fs::read(dir.path().join("proto_validator_set").join("valid-0001")).unwrap();
+        let raw = RawValidatorSet::decode(valid.as_slice()).unwrap();
+        assert_eq!(raw.validators.len(), 2);
+        assert!(dir.path().join("verifier").join("valid-0004").exists());
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use tendermint::{account, block};
+
+use crate::{
+    helpers::*,
+    vote_extension::{self, ExtendedCommitSig},
+    Commit, Generator, Header, Vote,
+};
+
+/// A commit whose non-nil precommits carry vote extensions, as in
+/// CometBFT 0.38
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct ExtendedCommit {
+    #[options(help = "header (required)", parse(try_from_str = "parse_as::<Header>"))]
+    pub header: Option<Header>,
+    #[options(
+        help = "votes in this commit, with their extensions (default: from header)",
+        parse(try_from_str = "parse_as::<Vec<Vote>>")
+    )]
+    pub votes: Option<Vec<Vote>>,
+    #[options(help = "commit round (default: 1)")]
+    pub round: Option<u32>,
+    #[options(help = "extension of the votes which do not have one (default: empty)")]
+    pub extension: Option<String>,
+}
+
+impl ExtendedCommit {
+    /// Make a new extended commit using default votes produced from the
+    /// header, all extended with the given extension.
+    pub fn new(header: Header, round: u32, extension: &str) -> Self {
+        Commit::new(header, round).extended().extension(extension)
+    }
+    set_option!(header, Header);
+    set_option!(votes, Vec<Vote>);
+    set_option!(round, u32);
+    set_option!(extension, &str, Some(extension.to_string()));
+
+    fn commit(&self) -> Commit {
+        Commit {
+            header: self.header.clone(),
+            votes: self.votes.clone(),
+            round: self.round,
+        }
+    }
+}
+
+impl Commit {
+    /// Make an extended commit from this commit, whose votes may carry
+    /// extensions.
+    pub fn extended(self) -> ExtendedCommit {
+        ExtendedCommit {
+            header: self.header,
+            votes: self.votes,
+            round: self.round,
+            extension: None,
+        }
+    }
+}
+
+impl std::str::FromStr for ExtendedCommit {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let commit = match parse_as::<ExtendedCommit>(s) {
+            Ok(input) => input,
+            Err(_) => Commit::new(parse_as::<Header>(s)?, 1).extended(),
+        };
+        Ok(commit)
+    }
+}
+
+impl Generator<vote_extension::ExtendedCommit> for ExtendedCommit {
+    fn merge_with_default(self, other: Self) -> Self {
+        ExtendedCommit {
+            header: self.header.or(other.header),
+            votes: self.votes.or(other.votes),
+            round: self.round.or(other.round),
+            extension: self.extension.or(other.extension),
+        }
+    }
+
+    fn generate(&self) -> Result<vote_extension::ExtendedCommit, SimpleError> {
+        if self.header.is_none() {
+            bail!("failed to generate extended commit: header is missing")
+        }
+        let commit = self.commit().generate()?;
+        let votes = match &self.votes {
+            None => self.commit().generate_default_votes().votes.unwrap(),
+            Some(vs) => vs.to_vec(),
+        };
+
+        let mut extensions: BTreeMap<account::Id, (Vec<u8>, Vec<u8>)> = BTreeMap::new();
+        for vote in votes {
+            let vote = match (&vote.extension, &self.extension) {
+                (None, Some(extension)) if vote.prevote.is_none() && vote.nil.is_none() => {
+                    vote.extension(extension)
+                },
+                _ => vote,
+            };
+            let extended = vote.generate_extended()?;
+            extensions.insert(
+                extended.vote.validator_address,
+                (extended.extension, extended.extension_signature),
+            );
+        }
+
+        let extended_signatures = commit
+            .signatures
+            .into_iter()
+            .map(|commit_sig| {
+                let (extension, extension_signature) = match &commit_sig {
+                    block::CommitSig::BlockIdFlagCommit {
+                        validator_address, ..
+                    } => extensions.remove(validator_address).unwrap_or_default(),
+                    _ => Default::default(),
+                };
+                ExtendedCommitSig {
+                    commit_sig,
+                    extension,
+                    extension_signature,
+                }
+            })
+            .collect();
+
+        Ok(vote_extension::ExtendedCommit {
+            height: commit.height,
+            round: commit.round,
+            block_id: commit.block_id,
+            extended_signatures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::{vote, Signature, Time};
+
+    use super::*;
+    use crate::{validator::sort_validators, Validator};
+
+    #[test]
+    fn test_extended_commit() {
+        let valset = sort_validators(&[
+            Validator::new("a"),
+            Validator::new("b"),
+            Validator::new("c"),
+        ]);
+        let header = Header::new(&valset)
+            .height(10)
+            .time(Time::from_unix_timestamp(11, 0).unwrap());
+
+        let mut commit = Commit::new(header.clone(), 2);
+        let vote = commit.vote_at_index(1);
+        *vote = vote.clone().extension("b");
+        let vote = commit.vote_at_index(2);
+        *vote = vote.clone().nil(true);
+        let commit = commit.extended().extension("default");
+
+        let block_header = header.generate().unwrap();
+        let extended_commit = commit.generate().unwrap();
+        assert_eq!(extended_commit.height, block_header.height);
+        assert_eq!(extended_commit.round.value(), 2);
+        assert_eq!(
+            extended_commit.to_commit(),
+            commit.commit().generate().unwrap()
+        );
+
+        let extensions: Vec<&[u8]> = extended_commit
+            .extended_signatures
+            .iter()
+            .map(|sig| sig.extension.as_slice())
+            .collect();
+        assert_eq!(extensions, [b"default".as_slice(), b"b", b""]);
+        assert!(extended_commit.extended_signatures[2]
+            .extension_signature
+            .is_empty());
+
+        for (i, sig) in extended_commit.extended_signatures[..2].iter().enumerate() {
+            let vote = vote::Vote {
+                vote_type: vote::Type::Precommit,
+                height: extended_commit.height,
+                round: extended_commit.round,
+                ..vote::Vote::default()
+            };
+            let sign_bytes =
+                get_vote_extension_sign_bytes(block_header.chain_id.clone(), &vote, &sig.extension);
+            let signature = Signature::try_from(sig.extension_signature.as_slice()).unwrap();
+            assert!(verify_signature(
+                &valset[i].get_public_key().unwrap(),
+                &sign_bytes,
+                &signature
+            ));
+        }
+    }
+
+    #[test]
+    fn test_extended_commit_json() {
+        let valset = [Validator::new("a"), Validator::new("b")];
+        let header = Header::new(&valset).height(3).seed(7);
+        let extended_commit = ExtendedCommit::new(header, 0, "ext").generate().unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&extended_commit).unwrap();
+        assert_eq!(json["height"], "3");
+        assert_eq!(json["round"], 0);
+        let sig = &json["extended_signatures"][0];
+        assert_eq!(sig["block_id_flag"], 2);
+        assert_eq!(sig["extension"], "ZXh0");
+        assert!(sig["extension_signature"].is_string());
+        assert!(sig["signature"].is_string());
+
+        let parsed: vote_extension::ExtendedCommit = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, extended_commit);
+    }
+}
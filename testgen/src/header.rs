@@ -10,7 +10,11 @@ use simple_error::*;
 use tendermint::{block, chain, validator, AppHash, Hash, Time};
 use time::OffsetDateTime;
 
-use crate::{helpers::*, validator::generate_validators, Generator, Validator};
+use crate::{
+    helpers::*,
+    validator::{generate_validators, with_default_seed},
+    Generator, Validator,
+};
 
 #[derive(Debug, Options, Serialize, Deserialize, Clone)]
 pub struct Header {
@@ -24,11 +28,11 @@ pub struct Header {
         parse(try_from_str = "parse_as::<Vec<Validator>>")
     )]
     pub next_validators: Option<Vec<Validator>>,
-    #[options(help = "chain id (default: test-chain)")]
+    #[options(help = "chain id (default: test-chain, or derived from the seed)")]
     pub chain_id: Option<String>,
     #[options(help = "block height (default: 1)")]
     pub height: Option<u64>,
-    #[options(help = "time (default: now)")]
+    #[options(help = "time (default: now, or derived from the seed)")]
     #[serde(deserialize_with = "deserialize_time")]
     #[serde(serialize_with = "serialize_time")]
     pub time: Option<Time>,
@@ -36,6 +40,18 @@ pub struct Header {
     pub proposer: Option<usize>,
     #[options(help = "last block id hash (default: Hash::None)")]
     pub last_block_id_hash: Option<Hash>,
+    #[options(
+        help = "seed for deriving validator keys, chain id and time (default: none)",
+        meta = "SEED"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[options(help = "application hash (default: empty)")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_hash: Option<Hash>,
+    #[options(help = "hash of the transactions of the block (default: none)")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<Hash>,
 }
 
 // Serialize and deserialize time only up to second precision for integration with MBT.
@@ -74,6 +90,9 @@ impl Header {
             time: None,
             proposer: None,
             last_block_id_hash: None,
+            seed: None,
+            app_hash: None,
+            data_hash: None,
         }
     }
     set_option!(validators, &[Validator], Some(validators.to_vec()));
@@ -87,13 +106,32 @@ impl Header {
     set_option!(time, Time);
     set_option!(proposer, usize);
     set_option!(last_block_id_hash, Hash);
+    set_option!(seed, u64);
+    set_option!(app_hash, Hash);
+    set_option!(data_hash, Hash);
+
+    /// Shift the time of the header by the given number of seconds, which may be negative,
+    /// e.g. to produce non-monotonic block times or headers from the future
+    pub fn shift_time(mut self, secs: i64) -> Self {
+        let time = self.time.expect("Missing header time");
+        let offset = Duration::from_secs(secs.unsigned_abs());
+        let shifted = if secs < 0 {
+            time - offset
+        } else {
+            time + offset
+        };
+        self.time = Some(shifted.expect("Shifted header time is out of range"));
+        self
+    }
 
     pub fn next(&self) -> Self {
         let height = self.height.expect("Missing previous header's height");
-        // if no time is found, then we simple correspond it to the header height
-        let time = self
-            .time
-            .unwrap_or_else(|| Time::from_unix_timestamp(height.try_into().unwrap(), 0).unwrap());
+        // if no time is found, then we simple correspond it to the header height,
+        // or derive it from the seed if there is one
+        let time = self.time.unwrap_or_else(|| match self.seed {
+            Some(seed) => seeded_time(seed, height).unwrap(),
+            None => Time::from_unix_timestamp(height.try_into().unwrap(), 0).unwrap(),
+        });
         let validators = self.validators.clone().expect("Missing validators");
         let next_validators = self.next_validators.clone().unwrap_or(validators);
 
@@ -108,6 +146,9 @@ impl Header {
             time: Some((time + Duration::from_secs(1)).unwrap()),
             proposer: self.proposer, // TODO: proposer must be incremented
             last_block_id_hash: Some(last_block_id_hash),
+            seed: self.seed,
+            app_hash: None,
+            data_hash: None,
         }
     }
 }
@@ -133,6 +174,9 @@ impl Generator<block::Header> for Header {
             time: self.time.or(default.time),
             proposer: self.proposer.or(default.proposer),
             last_block_id_hash: self.last_block_id_hash.or(default.last_block_id_hash),
+            seed: self.seed.or(default.seed),
+            app_hash: self.app_hash.or(default.app_hash),
+            data_hash: self.data_hash.or(default.data_hash),
         }
     }
 
@@ -141,29 +185,44 @@ impl Generator<block::Header> for Header {
             None => bail!("validator array is missing"),
             Some(vals) => vals,
         };
-        let vals = generate_validators(vals)?;
+        let vals = generate_validators(&with_default_seed(vals, self.seed))?;
         let proposer_index = self.proposer.unwrap_or(0);
         let proposer_address = if !vals.is_empty() {
             vals[proposer_index].address
         } else {
             Validator::new("a").generate().unwrap().address
         };
-        let valset = validator::Set::without_proposer(vals);
+        let valset = try_with!(
+            validator::Set::without_proposer(vals),
+            "invalid validator set"
+        );
         let next_valset = match &self.next_validators {
-            Some(next_vals) => validator::Set::without_proposer(generate_validators(next_vals)?),
+            Some(next_vals) => try_with!(
+                validator::Set::without_proposer(generate_validators(&with_default_seed(
+                    next_vals, self.seed,
+                ))?),
+                "invalid next validator set"
+            ),
             None => valset.clone(),
         };
         let chain_id = match chain::Id::from_str(
             self.chain_id
                 .clone()
-                .unwrap_or_else(|| "test-chain".to_string())
+                .unwrap_or_else(|| match self.seed {
+                    Some(seed) => seeded_chain_id(seed),
+                    None => "test-chain".to_string(),
+                })
                 .as_str(),
         ) {
             Ok(id) => id,
             Err(_) => bail!("failed to construct header's chain_id"),
         };
 
-        let time: Time = self.time.unwrap_or_else(Time::now);
+        let time: Time = match (self.time, self.seed) {
+            (Some(time), _) => time,
+            (None, Some(seed)) => seeded_time(seed, self.height.unwrap_or(1))?,
+            (None, None) => Time::now(),
+        };
 
         let last_block_id = self.last_block_id_hash.map(|hash| block::Id {
             hash,
@@ -180,11 +239,14 @@ impl Generator<block::Header> for Header {
             time,
             last_block_id,
             last_commit_hash: None,
-            data_hash: None,
+            data_hash: self.data_hash,
             validators_hash: valset.hash(),
             next_validators_hash: next_valset.hash(),
             consensus_hash: valset.hash(), // TODO: currently not clear how to produce a valid hash
-            app_hash: AppHash::from_hex_upper("").unwrap(),
+            app_hash: self
+                .app_hash
+                .map(AppHash::from)
+                .unwrap_or_else(|| AppHash::from_hex_upper("").unwrap()),
             last_results_hash: None,
             evidence_hash: None,
             proposer_address,
@@ -251,4 +313,40 @@ mod tests {
         let header = header.proposer(1);
         assert_eq!(header.generate().unwrap(), block_header);
     }
+
+    #[test]
+    fn test_header_seed() {
+        let valset = [Validator::new("a"), Validator::new("b")];
+
+        let header1 = Header::new(&valset).height(10).seed(42);
+        let header2 = Header::new(&valset).height(10).seed(42);
+        let block_header1 = header1.generate().unwrap();
+        assert_eq!(block_header1, header2.generate().unwrap());
+        assert_eq!(block_header1.chain_id.as_str(), seeded_chain_id(42));
+        assert_eq!(block_header1.time, seeded_time(42, 10).unwrap());
+
+        // the seed is applied to the validators' keys
+        let seeded_valset = validator::Set::without_proposer(
+            generate_validators(&with_default_seed(&valset, Some(42))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(block_header1.validators_hash, seeded_valset.hash());
+
+        let header3 = Header::new(&valset).height(10).seed(43);
+        assert_ne!(block_header1, header3.generate().unwrap());
+
+        // explicit values take precedence over the seeded ones
+        let header4 = header1.clone().chain_id("chain1").time(Time::unix_epoch());
+        let block_header4 = header4.generate().unwrap();
+        assert_eq!(block_header4.chain_id.as_str(), "chain1");
+        assert_eq!(block_header4.time, Time::unix_epoch());
+
+        // the seed is carried over to subsequent headers
+        let next1 = header1.next();
+        assert_eq!(next1.seed, Some(42));
+        assert_eq!(
+            next1.generate().unwrap(),
+            header2.next().generate().unwrap()
+        );
+    }
 }
@@ -45,6 +45,35 @@ pub fn get_vote_sign_bytes(chain_id: chain::Id, vote: &vote::Vote) -> Vec<u8> {
     signed_vote.sign_bytes()
 }
 
+/// CometBFT 0.38's `CanonicalVoteExtension`, which validators sign along with
+/// their precommits, and which `tendermint-proto` does not define yet
+#[derive(Clone, PartialEq, prost::Message)]
+struct CanonicalVoteExtension {
+    #[prost(bytes = "vec", tag = "1")]
+    extension: Vec<u8>,
+    #[prost(sfixed64, tag = "2")]
+    height: i64,
+    #[prost(sfixed64, tag = "3")]
+    round: i64,
+    #[prost(string, tag = "4")]
+    chain_id: String,
+}
+
+pub fn get_vote_extension_sign_bytes(
+    chain_id: chain::Id,
+    vote: &vote::Vote,
+    extension: &[u8],
+) -> Vec<u8> {
+    let canonical = CanonicalVoteExtension {
+        extension: extension.to_vec(),
+        height: vote.height.value() as i64,
+        round: vote.round.value() as i64,
+        chain_id: chain_id.to_string(),
+    };
+
+    prost::Message::encode_length_delimited_to_vec(&canonical)
+}
+
 pub fn verify_signature(pubkey: &public_key::Ed25519, msg: &[u8], signature: &Signature) -> bool {
     let verifier = ed25519_consensus::VerificationKey::try_from(pubkey.as_bytes()).unwrap();
     let sig = ed25519_consensus::Signature::try_from(signature.as_bytes()).unwrap();
@@ -57,3 +86,57 @@ pub fn get_time(abs: u64) -> Result<Time, SimpleError> {
 
     Ok(res)
 }
+
+/// A small pseudo-random number generator (SplitMix64) used to derive test data from a seed.
+///
+/// The generator only relies on wrapping 64-bit integer arithmetic, so the same seed
+/// produces the same byte stream on every platform and across runs.
+#[derive(Clone, Debug)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    /// Create a generator for a particular purpose (e.g. a validator identifier),
+    /// so that different labels produce independent streams from the same seed.
+    pub fn with_label(seed: u64, label: &str) -> Self {
+        // FNV-1a hash of the label, mixed into the seed
+        let hash = label.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        SeededRng::new(seed ^ hash)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/// Produce a chain id that is unique for the given seed, e.g. `test-chain-1a2b3c4d`.
+pub fn seeded_chain_id(seed: u64) -> String {
+    let suffix = SeededRng::with_label(seed, "chain_id").next_u64() as u32;
+    format!("test-chain-{suffix:08x}")
+}
+
+/// Produce a genesis-like timestamp for the given seed, offset by `height` seconds.
+/// The resulting time lies between 2020 and roughly 2023.
+pub fn seeded_time(seed: u64, height: u64) -> Result<Time, SimpleError> {
+    const BASE_SECS: u64 = 1_577_836_800; // 2020-01-01T00:00:00Z
+    let offset = SeededRng::with_label(seed, "time").next_u64() % 100_000_000;
+    get_time(BASE_SECS + offset + height)
+}
@@ -11,20 +11,29 @@ pub mod helpers;
 /// Helper types for generating Tendermint datastructures
 pub mod commit;
 pub mod consensus;
+pub mod corpus;
+pub mod extended_commit;
 pub mod generator;
 pub mod header;
 pub mod light_block;
 pub mod light_chain;
+pub mod light_client_attack;
 pub mod time;
+pub mod time_skew;
 pub mod validator;
 pub mod validator_set;
 pub mod vote;
+pub mod vote_extension;
 
 pub use commit::Commit;
+pub use corpus::Corpus;
+pub use extended_commit::ExtendedCommit;
 pub use generator::Generator;
 pub use header::Header;
 pub use light_block::LightBlock;
 pub use light_chain::LightChain;
+pub use light_client_attack::{AttackType, LightClientAttack};
+pub use time_skew::TimeSkew;
 pub use validator::Validator;
 pub use validator_set::ValidatorSet;
 pub use vote::Vote;
@@ -7,7 +7,9 @@ use tendermint::{
 };
 
 use crate::{
-    helpers::parse_as, validator::generate_validators, Commit, Generator, Header, Validator,
+    helpers::{parse_as, seeded_chain_id, seeded_time},
+    validator::{generate_validators, with_default_seed},
+    Commit, Generator, Header, Validator,
 };
 
 /// A light block is the core data structure used by the light client.
@@ -106,6 +108,34 @@ impl LightBlock {
         }
     }
 
+    /// Constructs a default light block whose validator keys, chain id and time
+    /// are all derived from the given seed, so that it is reproducible byte-for-byte.
+    pub fn new_default_with_seed(height: u64, seed: u64) -> Self {
+        let validators = with_default_seed(
+            &[
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(50),
+            ],
+            Some(seed),
+        );
+        let header = Header::new(&validators)
+            .height(height)
+            .chain_id(&seeded_chain_id(seed))
+            .next_validators(&validators)
+            .time(seeded_time(seed, height).unwrap())
+            .seed(seed);
+
+        let commit = Commit::new(header.clone(), 1);
+
+        Self {
+            header: Some(header),
+            commit: Some(commit),
+            validators: Some(validators.to_vec()),
+            next_validators: Some(validators.to_vec()),
+            provider: Some(default_peer_id()),
+        }
+    }
+
     pub fn new_default_with_time_and_chain_id(chain_id: String, time: Time, height: u64) -> Self {
         let validators = [
             Validator::new("1").voting_power(50),
@@ -209,17 +239,28 @@ impl Generator<TmLightBlock> for LightBlock {
             generate_signed_header(header, commit).expect("Could not generate signed header");
 
         let validators = match &self.validators {
-            None => validator::Set::without_proposer(generate_validators(
+            None => generate_validators(&with_default_seed(
                 header
                     .validators
                     .as_ref()
                     .expect("missing validators in header"),
-            )?),
-            Some(vals) => validator::Set::without_proposer(generate_validators(vals)?),
+                header.seed,
+            ))?,
+            Some(vals) => generate_validators(&with_default_seed(vals, header.seed))?,
         };
+        let validators = try_with!(
+            validator::Set::without_proposer(validators),
+            "invalid validator set"
+        );
 
         let next_validators = match &self.next_validators {
-            Some(next_vals) => validator::Set::without_proposer(generate_validators(next_vals)?),
+            Some(next_vals) => try_with!(
+                validator::Set::without_proposer(generate_validators(&with_default_seed(
+                    next_vals,
+                    header.seed,
+                ))?),
+                "invalid next validator set"
+            ),
             None => validators.clone(),
         };
 
@@ -309,4 +350,25 @@ mod tests {
         assert_eq!(light_block_7.chain_id(), "test-chain");
         assert_ne!(light_block_6.generate(), light_block_7.generate());
     }
+
+    #[test]
+    fn test_light_block_seed() {
+        let light_block_1 = LightBlock::new_default_with_seed(1, 42);
+        let light_block_2 = LightBlock::new_default_with_seed(1, 42);
+        let light_block_3 = LightBlock::new_default_with_seed(1, 43);
+
+        let generated = light_block_1.generate().unwrap();
+        assert_eq!(generated, light_block_2.generate().unwrap());
+        assert_ne!(generated, light_block_3.generate().unwrap());
+        assert_ne!(generated, LightBlock::new_default(1).generate().unwrap());
+
+        assert_eq!(
+            generated.signed_header.header.validators_hash,
+            generated.validators.hash()
+        );
+        assert_eq!(
+            light_block_1.next().generate().unwrap(),
+            light_block_2.next().generate().unwrap()
+        );
+    }
 }
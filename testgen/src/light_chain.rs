@@ -5,7 +5,7 @@ use tendermint::{
     chain::Info,
 };
 
-use crate::{light_block::LightBlock, Generator};
+use crate::{light_block::LightBlock, Commit, Generator};
 
 #[derive(Clone, Debug)]
 pub struct LightChain {
@@ -21,7 +21,28 @@ impl LightChain {
     // TODO: make this fn more usable
     // TODO: like how does someone generate a chain with different validators at each height
     pub fn default_with_length(num: u64) -> Self {
-        let mut last_block = LightBlock::new_default(1);
+        Self::with_first_block(LightBlock::new_default(1), num)
+    }
+
+    /// Like `default_with_length`, but with validator keys, chain id and block times
+    /// derived from the given seed, so that the generated chain is reproducible.
+    pub fn default_with_length_and_seed(num: u64, seed: u64) -> Self {
+        Self::with_first_block(LightBlock::new_default_with_seed(1, seed), num)
+    }
+
+    /// Like `default_with_length`, with as many blocks as there are offsets, and the time of
+    /// each block shifted by the corresponding number of seconds, which may be negative.
+    pub fn default_with_time_offsets(offsets: &[i64]) -> Self {
+        let mut light_chain = Self::default_with_length(offsets.len() as u64);
+        for (block, &secs) in light_chain.light_blocks.iter_mut().zip(offsets) {
+            shift_block_time(block, secs);
+        }
+        light_chain.relink(1);
+        light_chain
+    }
+
+    fn with_first_block(first_block: LightBlock, num: u64) -> Self {
+        let mut last_block = first_block;
         let mut light_blocks: Vec<LightBlock> = vec![last_block.clone()];
 
         for _i in 2..=num {
@@ -94,6 +115,51 @@ impl LightChain {
             .find(|lb| lb.height() == target_height)
     }
 
+    /// shifts the time of the block at a certain height by the given number of seconds,
+    /// which may be negative; the subsequent blocks keep their time, so that block times
+    /// may become non-monotonic, but are linked to the shifted block
+    pub fn shift_block_time(&mut self, target_height: u64, secs: i64) {
+        let index = self
+            .light_blocks
+            .iter()
+            .position(|lb| lb.height() == target_height)
+            .expect("cannot find light block at the target height");
+        shift_block_time(&mut self.light_blocks[index], secs);
+        self.relink(index + 1);
+    }
+
+    /// links the blocks from the given index on to their predecessors, after these changed
+    fn relink(&mut self, from: usize) {
+        for i in from.max(1)..self.light_blocks.len() {
+            let last_block_id_hash = self.light_blocks[i - 1]
+                .header
+                .as_ref()
+                .expect("missing header in light block")
+                .generate()
+                .expect("failed to generate header")
+                .hash();
+            let block = &mut self.light_blocks[i];
+            let header = block
+                .header
+                .take()
+                .expect("missing header in light block")
+                .last_block_id_hash(last_block_id_hash);
+            let round = block.commit.as_ref().and_then(|c| c.round).unwrap_or(1);
+            block.commit = Some(Commit::new(header.clone(), round));
+            block.header = Some(header);
+        }
+
+        let last_block_hash = self
+            .latest_block()
+            .header
+            .as_ref()
+            .map(|h| h.generate().unwrap().hash());
+        self.info.last_block_id = last_block_hash.map(|hash| block::Id {
+            hash,
+            part_set_header: Default::default(),
+        });
+    }
+
     /// fetches the latest block from LightChain
     pub fn latest_block(&self) -> &LightBlock {
         self.light_blocks
@@ -102,8 +168,21 @@ impl LightChain {
     }
 }
 
+fn shift_block_time(block: &mut LightBlock, secs: i64) {
+    let header = block
+        .header
+        .take()
+        .expect("missing header in light block")
+        .shift_time(secs);
+    let round = block.commit.as_ref().and_then(|c| c.round).unwrap_or(1);
+    block.commit = Some(Commit::new(header.clone(), round));
+    block.header = Some(header);
+}
+
 #[cfg(test)]
 mod tests {
+    use core::time::Duration;
+
     use super::*;
 
     #[test]
@@ -142,6 +221,77 @@ mod tests {
         assert_eq!(2, second_block.height());
     }
 
+    #[test]
+    fn test_light_chain_with_seed() {
+        let generate = |chain: LightChain| {
+            chain
+                .light_blocks
+                .into_iter()
+                .map(|lb| lb.generate().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let chain_1 = LightChain::default_with_length_and_seed(5, 42);
+        let chain_2 = LightChain::default_with_length_and_seed(5, 42);
+        let chain_3 = LightChain::default_with_length_and_seed(5, 43);
+
+        assert_eq!(chain_1.info.id, chain_2.info.id);
+        assert_eq!(chain_1.info.last_block_id, chain_2.info.last_block_id);
+        assert_ne!(chain_1.info.id, chain_3.info.id);
+        assert_eq!(generate(chain_1), generate(chain_2));
+    }
+
+    #[test]
+    fn test_light_chain_with_time_offsets() {
+        let times = |chain: &LightChain| {
+            chain
+                .light_blocks
+                .iter()
+                .map(|lb| lb.generate().unwrap().signed_header.header.time)
+                .collect::<Vec<_>>()
+        };
+        let chain = LightChain::default_with_length(4);
+        let shifted = LightChain::default_with_time_offsets(&[0, 10, -5, 20]);
+        let (expected, actual) = (times(&chain), times(&shifted));
+
+        assert_eq!(actual[0], expected[0]);
+        assert_eq!(actual[1], (expected[1] + Duration::from_secs(10)).unwrap());
+        assert_eq!(actual[2], (expected[2] - Duration::from_secs(5)).unwrap());
+        assert_eq!(actual[3], (expected[3] + Duration::from_secs(20)).unwrap());
+        // non-monotonic block times
+        assert!(actual[2] < actual[1]);
+
+        let blocks: Vec<_> = shifted
+            .light_blocks
+            .iter()
+            .map(|lb| lb.generate().unwrap())
+            .collect();
+        for i in 1..blocks.len() {
+            assert_eq!(
+                blocks[i]
+                    .signed_header
+                    .header
+                    .last_block_id
+                    .map(|lbi| lbi.hash),
+                Some(blocks[i - 1].signed_header.header.hash())
+            );
+            let commit_sig = &blocks[i].signed_header.commit.signatures[0];
+            assert_eq!(commit_sig.timestamp(), Some(actual[i]));
+        }
+        assert_eq!(
+            shifted.info.last_block_id.unwrap().hash,
+            blocks[3].signed_header.header.hash()
+        );
+
+        let mut chain = chain;
+        chain.shift_block_time(2, -1);
+        assert_eq!(
+            times(&chain)[1],
+            (expected[1] - Duration::from_secs(1)).unwrap()
+        );
+        assert_eq!(times(&chain)[2], expected[2]);
+    }
+
     #[test]
     fn test_light_chain_with_length() {
         const CHAIN_HEIGHT: u64 = 10;
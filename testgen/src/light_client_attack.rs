@@ -0,0 +1,372 @@
+use std::{convert::TryInto, fmt, str::FromStr};
+
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use tendermint::{
+    evidence::{ConflictingBlock, LightClientAttackEvidence},
+    Hash,
+};
+
+use crate::{
+    helpers::*,
+    light_block::{LightBlock, TmLightBlock},
+    Commit, Generator, Header, Validator, Vote,
+};
+
+/// The hash forged by the attackers, as the application hash of a lunatic
+/// block or the data hash of an equivocating one
+const FORGED_HASH: [u8; 32] = [0xba; 32];
+
+/// The kinds of attacks on light clients, which determine how the
+/// conflicting block differs from the one committed by the chain
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttackType {
+    /// The byzantine validators sign a block which could not have been derived
+    /// from the state of the chain: its validators and application hash are
+    /// forged
+    Lunatic,
+    /// The byzantine validators sign another block with the same state in the
+    /// same round as the committed block
+    Equivocation,
+    /// The byzantine validators sign another block with the same state in a
+    /// later round than the committed block
+    Amnesia,
+}
+
+impl FromStr for AttackType {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lunatic" => Ok(AttackType::Lunatic),
+            "equivocation" => Ok(AttackType::Equivocation),
+            "amnesia" => Ok(AttackType::Amnesia),
+            _ => bail!("unknown attack: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for AttackType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttackType::Lunatic => write!(f, "lunatic"),
+            AttackType::Equivocation => write!(f, "equivocation"),
+            AttackType::Amnesia => write!(f, "amnesia"),
+        }
+    }
+}
+
+/// An attack on light clients: the block at the common height trusted by
+/// both the chain and the light client, the block committed by the chain at
+/// the attacked height, and the conflicting block signed by the attackers
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TmLightClientAttack {
+    pub attack: AttackType,
+    pub common: TmLightBlock,
+    pub trusted: TmLightBlock,
+    pub conflicting: TmLightBlock,
+}
+
+impl TmLightClientAttack {
+    /// The evidence of this attack, as the detector of a light client would
+    /// build it
+    pub fn evidence(&self) -> Result<LightClientAttackEvidence, SimpleError> {
+        let block = |light_block: &TmLightBlock| ConflictingBlock {
+            signed_header: light_block.signed_header.clone(),
+            validator_set: light_block.validators.clone(),
+        };
+        LightClientAttackEvidence::from_conflicting_blocks(
+            block(&self.conflicting),
+            &block(&self.trusted),
+            &block(&self.common),
+        )
+        .map_err(|e| SimpleError::new(e.to_string()))
+    }
+}
+
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct LightClientAttack {
+    #[options(
+        help = "validators of the chain (required), encoded as array of 'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Validator>>")
+    )]
+    pub validators: Option<Vec<Validator>>,
+    #[options(
+        help = "validators signing the conflicting block (default: all validators), encoded as array of 'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Validator>>")
+    )]
+    pub byzantine_validators: Option<Vec<Validator>>,
+    #[options(help = "attack: lunatic, equivocation or amnesia (default: lunatic)")]
+    pub attack: Option<AttackType>,
+    #[options(help = "height of the block trusted by the chain and the light client (default: 1)")]
+    pub common_height: Option<u64>,
+    #[options(help = "height of the conflicting block (default: common height + 1)")]
+    pub height: Option<u64>,
+    #[options(help = "chain id (default: test-chain, or derived from the seed)")]
+    pub chain_id: Option<String>,
+    #[options(
+        help = "seed for deriving validator keys, chain id and time (default: none)",
+        meta = "SEED"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl LightClientAttack {
+    pub fn new(attack: AttackType, validators: &[Validator]) -> Self {
+        LightClientAttack {
+            validators: Some(validators.to_vec()),
+            byzantine_validators: None,
+            attack: Some(attack),
+            common_height: None,
+            height: None,
+            chain_id: None,
+            seed: None,
+        }
+    }
+    set_option!(validators, &[Validator], Some(validators.to_vec()));
+    set_option!(
+        byzantine_validators,
+        &[Validator],
+        Some(byzantine_validators.to_vec())
+    );
+    set_option!(attack, AttackType);
+    set_option!(common_height, u64);
+    set_option!(height, u64);
+    set_option!(chain_id, &str, Some(chain_id.to_string()));
+    set_option!(seed, u64);
+}
+
+impl FromStr for LightClientAttack {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_as::<LightClientAttack>(s)
+    }
+}
+
+impl Generator<TmLightClientAttack> for LightClientAttack {
+    fn merge_with_default(self, default: Self) -> Self {
+        LightClientAttack {
+            validators: self.validators.or(default.validators),
+            byzantine_validators: self.byzantine_validators.or(default.byzantine_validators),
+            attack: self.attack.or(default.attack),
+            common_height: self.common_height.or(default.common_height),
+            height: self.height.or(default.height),
+            chain_id: self.chain_id.or(default.chain_id),
+            seed: self.seed.or(default.seed),
+        }
+    }
+
+    fn generate(&self) -> Result<TmLightClientAttack, SimpleError> {
+        let validators = match &self.validators {
+            None => bail!("failed to generate light client attack: validators are missing"),
+            Some(vals) => vals,
+        };
+        let byzantine_validators = self.byzantine_validators.as_ref().unwrap_or(validators);
+        if byzantine_validators.is_empty()
+            || byzantine_validators.iter().any(|v| !validators.contains(v))
+        {
+            bail!("failed to generate light client attack: byzantine validators must be validators of the chain")
+        }
+        let attack = self.attack.unwrap_or(AttackType::Lunatic);
+        let common_height = self.common_height.unwrap_or(1);
+        let height = self.height.unwrap_or(common_height + 1);
+        if height <= common_height {
+            bail!("failed to generate light client attack: the conflicting block must be higher than the common block")
+        }
+
+        let time = match self.seed {
+            Some(seed) => seeded_time(seed, common_height)?,
+            None => get_time(common_height)?,
+        };
+        let mut common_header = Header::new(validators)
+            .next_validators(validators)
+            .height(common_height)
+            .time(time);
+        common_header.chain_id = self.chain_id.clone();
+        common_header.seed = self.seed;
+
+        let mut trusted_header = common_header.next();
+        for _ in common_height + 1..height {
+            trusted_header = trusted_header.next();
+        }
+
+        let (conflicting_header, round) = match attack {
+            AttackType::Lunatic => (
+                trusted_header
+                    .clone()
+                    .validators(byzantine_validators)
+                    .next_validators(byzantine_validators)
+                    .app_hash(Hash::Sha256(FORGED_HASH)),
+                1,
+            ),
+            AttackType::Equivocation => (
+                trusted_header.clone().data_hash(Hash::Sha256(FORGED_HASH)),
+                1,
+            ),
+            AttackType::Amnesia => (
+                trusted_header.clone().data_hash(Hash::Sha256(FORGED_HASH)),
+                2,
+            ),
+        };
+        let conflicting_validators = conflicting_header.validators.clone().unwrap();
+        let votes = byzantine_validators
+            .iter()
+            .map(|v| {
+                let index = conflicting_validators.iter().position(|x| x == v).unwrap();
+                Vote::new(v.clone(), conflicting_header.clone())
+                    .index(index.try_into().unwrap())
+                    .round(round)
+            })
+            .collect();
+        let conflicting_commit = Commit::new_with_votes(conflicting_header.clone(), round, votes);
+
+        Ok(TmLightClientAttack {
+            attack,
+            common: LightBlock::new(common_header.clone(), Commit::new(common_header, 1))
+                .generate()?,
+            trusted: LightBlock::new(trusted_header.clone(), Commit::new(trusted_header, 1))
+                .generate()?,
+            conflicting: LightBlock::new(conflicting_header, conflicting_commit).generate()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::block::CommitSig;
+
+    use super::*;
+    use crate::validator::sort_validators;
+
+    fn validators() -> Vec<Validator> {
+        sort_validators(&[
+            Validator::new("a").voting_power(10),
+            Validator::new("b").voting_power(20),
+            Validator::new("c").voting_power(30),
+            Validator::new("d").voting_power(40),
+        ])
+    }
+
+    fn signers(light_block: &TmLightBlock) -> Vec<Validator> {
+        let signatures = &light_block.signed_header.commit.signatures;
+        validators()
+            .into_iter()
+            .filter(|v| {
+                let address = v.generate().unwrap().address;
+                signatures.iter().any(|sig| {
+                    matches!(sig, CommitSig::BlockIdFlagCommit { validator_address, .. } if *validator_address == address)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lunatic_attack() {
+        let validators = validators();
+        let byzantine: Vec<Validator> = validators
+            .iter()
+            .filter(|v| [Validator::new("b"), Validator::new("d")].contains(v))
+            .cloned()
+            .collect();
+        let attack = LightClientAttack::new(AttackType::Lunatic, &validators)
+            .byzantine_validators(&byzantine)
+            .common_height(3)
+            .height(6)
+            .generate()
+            .unwrap();
+
+        let (common, trusted, conflicting) = (&attack.common, &attack.trusted, &attack.conflicting);
+        assert_eq!(common.signed_header.header.height.value(), 3);
+        assert_eq!(trusted.signed_header.header.height.value(), 6);
+        assert_eq!(conflicting.signed_header.header.height.value(), 6);
+        assert_ne!(
+            conflicting.signed_header.commit.block_id,
+            trusted.signed_header.commit.block_id
+        );
+        assert_eq!(
+            conflicting.signed_header.header.validators_hash,
+            conflicting.validators.hash()
+        );
+        assert_ne!(
+            conflicting.signed_header.header.validators_hash,
+            trusted.signed_header.header.validators_hash
+        );
+        assert_eq!(signers(conflicting), byzantine);
+        assert_eq!(signers(trusted), validators);
+
+        let evidence = attack.evidence().unwrap();
+        assert_eq!(evidence.common_height.value(), 3);
+        assert_eq!(evidence.total_voting_power.value(), 100);
+        let byzantine_addresses: Vec<_> = evidence
+            .byzantine_validators
+            .iter()
+            .map(|v| v.address)
+            .collect();
+        // The most powerful validators first
+        assert_eq!(
+            byzantine_addresses,
+            [
+                Validator::new("d").generate().unwrap().address,
+                Validator::new("b").generate().unwrap().address
+            ]
+        );
+    }
+
+    #[test]
+    fn test_equivocation_and_amnesia_attacks() {
+        let validators = validators();
+        let byzantine = &validators[1..];
+
+        let attack = LightClientAttack::new(AttackType::Equivocation, &validators)
+            .byzantine_validators(byzantine)
+            .generate()
+            .unwrap();
+        let (trusted, conflicting) = (&attack.trusted, &attack.conflicting);
+        assert_eq!(trusted.signed_header.header.height.value(), 2);
+        assert_eq!(
+            conflicting.signed_header.header.validators_hash,
+            trusted.signed_header.header.validators_hash
+        );
+        assert_ne!(
+            conflicting.signed_header.commit.block_id,
+            trusted.signed_header.commit.block_id
+        );
+        assert_eq!(
+            conflicting.signed_header.commit.round,
+            trusted.signed_header.commit.round
+        );
+        assert_eq!(signers(conflicting), byzantine);
+
+        let evidence = attack.evidence().unwrap();
+        assert_eq!(evidence.common_height.value(), 2);
+        assert_eq!(evidence.byzantine_validators.len(), 3);
+
+        let attack = LightClientAttack::new(AttackType::Amnesia, &validators)
+            .byzantine_validators(byzantine)
+            .generate()
+            .unwrap();
+        assert_eq!(attack.conflicting.signed_header.commit.round.value(), 2);
+        assert!(attack.evidence().unwrap().byzantine_validators.is_empty());
+    }
+
+    #[test]
+    fn test_light_client_attack_json() {
+        let attack = LightClientAttack::new(AttackType::Equivocation, &validators()).seed(5);
+        let encoded = attack.encode().unwrap();
+        assert_eq!(encoded, attack.encode().unwrap());
+
+        let decoded: TmLightClientAttack = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, attack.generate().unwrap());
+        assert_eq!(decoded.attack, AttackType::Equivocation);
+
+        let input = r#"{"validators": [{"id": "a", "voting_power": 1}], "attack": "amnesia"}"#;
+        let attack = LightClientAttack::from_str(input).unwrap();
+        assert_eq!(attack.attack, Some(AttackType::Amnesia));
+        assert!(LightClientAttack::new(AttackType::Lunatic, &validators())
+            .byzantine_validators(&[Validator::new("e")])
+            .generate()
+            .is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use core::time::Duration;
+
+use gumdrop::Options;
+use serde::{Deserialize, Serialize};
+use simple_error::*;
+use tendermint::{
+    block::signed_header::SignedHeader, evidence::Duration as DurationStr, validator, Time,
+};
+
+use crate::{
+    helpers::*,
+    light_block::{LightBlock, TmLightBlock},
+    Commit, Generator, Header, Validator,
+};
+
+/// The verdict expected from the verifier for an input block
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExpectedVerdict {
+    /// The block is verified
+    Success,
+    /// The trusted block is outside of the trusting period
+    NotWithinTrustPeriod,
+    /// The block is too far ahead of the current time, given the clock drift
+    HeaderFromTheFuture,
+    /// The time of the block does not follow the one of the trusted block
+    NonMonotonicBftTime,
+}
+
+/// The trusted state and verification parameters of a test case, as in the
+/// single-step test cases of the light client, along with the clock drift
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Initial {
+    pub signed_header: SignedHeader,
+    pub next_validator_set: validator::Set,
+    pub trusting_period: DurationStr,
+    pub clock_drift: DurationStr,
+    pub now: Time,
+}
+
+/// An input block, verified against the trusted state at the given time
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockVerdict {
+    pub block: TmLightBlock,
+    pub now: Time,
+    pub verdict: ExpectedVerdict,
+}
+
+/// A verifier test case: every input block is verified against the initial
+/// trusted state, independently of the others
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifierTestCase {
+    pub description: String,
+    pub initial: Initial,
+    pub input: Vec<BlockVerdict>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifierTestBatch {
+    pub batch_name: String,
+    pub test_cases: Vec<VerifierTestCase>,
+}
+
+/// Test cases exercising the time checks of the verifier: non-monotonic block
+/// times, headers from the future relative to the current time, and the
+/// boundaries of the clock drift and of the trusting period
+#[derive(Debug, Options, Serialize, Deserialize, Clone)]
+pub struct TimeSkew {
+    #[options(
+        help = "validators (default: two validators with equal power), encoded as array of 'validator' parameters",
+        parse(try_from_str = "parse_as::<Vec<Validator>>")
+    )]
+    pub validators: Option<Vec<Validator>>,
+    #[options(help = "time of the trusted block, in seconds since epoch (default: 1000000)")]
+    pub time: Option<u64>,
+    #[options(help = "current time, in seconds after the trusted block (default: 60)")]
+    pub now: Option<u64>,
+    #[options(help = "trusting period, in seconds (default: 3600)")]
+    pub trusting_period: Option<u64>,
+    #[options(help = "clock drift, in seconds (default: 10)")]
+    pub clock_drift: Option<u64>,
+    #[options(
+        help = "seed for deriving validator keys and chain id (default: none)",
+        meta = "SEED"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+impl TimeSkew {
+    pub fn new(validators: &[Validator]) -> Self {
+        TimeSkew {
+            validators: Some(validators.to_vec()),
+            time: None,
+            now: None,
+            trusting_period: None,
+            clock_drift: None,
+            seed: None,
+        }
+    }
+    set_option!(validators, &[Validator], Some(validators.to_vec()));
+    set_option!(time, u64);
+    set_option!(now, u64);
+    set_option!(trusting_period, u64);
+    set_option!(clock_drift, u64);
+    set_option!(seed, u64);
+}
+
+impl Default for TimeSkew {
+    fn default() -> Self {
+        Self::new(&[
+            Validator::new("1").voting_power(50),
+            Validator::new("2").voting_power(50),
+        ])
+    }
+}
+
+impl std::str::FromStr for TimeSkew {
+    type Err = SimpleError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_as::<TimeSkew>(s)
+    }
+}
+
+impl Generator<VerifierTestBatch> for TimeSkew {
+    fn merge_with_default(self, default: Self) -> Self {
+        TimeSkew {
+            validators: self.validators.or(default.validators),
+            time: self.time.or(default.time),
+            now: self.now.or(default.now),
+            trusting_period: self.trusting_period.or(default.trusting_period),
+            clock_drift: self.clock_drift.or(default.clock_drift),
+            seed: self.seed.or(default.seed),
+        }
+    }
+
+    fn generate(&self) -> Result<VerifierTestBatch, SimpleError> {
+        let validators = match &self.validators {
+            None => TimeSkew::default().validators.unwrap(),
+            Some(vals) => vals.clone(),
+        };
+        let time = self.time.unwrap_or(1_000_000);
+        let now = self.now.unwrap_or(60);
+        let trusting_period = self.trusting_period.unwrap_or(3600);
+        let clock_drift = self.clock_drift.unwrap_or(10);
+        if now == 0 || now >= trusting_period {
+            bail!("the current time must be after the trusted block and within the trusting period")
+        }
+        if now + clock_drift < 2 {
+            bail!("the current time and clock drift must leave room for a block after the trusted one")
+        }
+
+        let mut trusted_header = Header::new(&validators)
+            .next_validators(&validators)
+            .height(1)
+            .time(get_time(time)?);
+        if let Some(seed) = self.seed {
+            trusted_header = trusted_header.seed(seed);
+        }
+        let trusted = LightBlock::new(
+            trusted_header.clone(),
+            Commit::new(trusted_header.clone(), 1),
+        )
+        .generate()?;
+        let initial = |now: u64| -> Result<Initial, SimpleError> {
+            Ok(Initial {
+                signed_header: trusted.signed_header.clone(),
+                next_validator_set: trusted.next_validators.clone(),
+                trusting_period: DurationStr(Duration::from_secs(trusting_period)),
+                clock_drift: DurationStr(Duration::from_secs(clock_drift)),
+                now: get_time(time + now)?,
+            })
+        };
+
+        // the subsequent block, with its time shifted from the trusted one
+        let header = trusted_header.next();
+        let block = |secs: i64| -> Result<TmLightBlock, SimpleError> {
+            let header = header.clone().time(get_time(time)?).shift_time(secs);
+            LightBlock::new(header.clone(), Commit::new(header, 1)).generate()
+        };
+        let step = |secs: i64, now: u64, verdict| -> Result<BlockVerdict, SimpleError> {
+            Ok(BlockVerdict {
+                block: block(secs)?,
+                now: get_time(time + now)?,
+                verdict,
+            })
+        };
+        let (now_secs, drift_secs) = (now as i64, clock_drift as i64);
+
+        let test_cases = vec![
+            VerifierTestCase {
+                description: "block times must increase monotonically".to_string(),
+                initial: initial(now)?,
+                input: vec![
+                    step(1, now, ExpectedVerdict::Success)?,
+                    step(0, now, ExpectedVerdict::NonMonotonicBftTime)?,
+                    step(-1, now, ExpectedVerdict::NonMonotonicBftTime)?,
+                ],
+            },
+            VerifierTestCase {
+                description: "headers from the future are tolerated up to the clock drift"
+                    .to_string(),
+                initial: initial(now)?,
+                input: vec![
+                    step(now_secs + drift_secs - 1, now, ExpectedVerdict::Success)?,
+                    step(
+                        now_secs + drift_secs,
+                        now,
+                        ExpectedVerdict::HeaderFromTheFuture,
+                    )?,
+                    step(
+                        now_secs + drift_secs + 3600,
+                        now,
+                        ExpectedVerdict::HeaderFromTheFuture,
+                    )?,
+                ],
+            },
+            VerifierTestCase {
+                description: "the trusted block expires at the end of the trusting period"
+                    .to_string(),
+                initial: initial(now)?,
+                input: vec![
+                    step(1, trusting_period - 1, ExpectedVerdict::Success)?,
+                    step(1, trusting_period, ExpectedVerdict::NotWithinTrustPeriod)?,
+                    step(
+                        1,
+                        trusting_period + 1,
+                        ExpectedVerdict::NotWithinTrustPeriod,
+                    )?,
+                ],
+            },
+        ];
+
+        Ok(VerifierTestBatch {
+            batch_name: "time skew".to_string(),
+            test_cases,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_skew() {
+        let batch = TimeSkew::default()
+            .now(100)
+            .clock_drift(5)
+            .generate()
+            .unwrap();
+        assert_eq!(batch.test_cases.len(), 3);
+
+        for test_case in &batch.test_cases {
+            let trusted = &test_case.initial.signed_header.header;
+            assert_eq!(trusted.height.value(), 1);
+            assert_eq!(trusted.time, get_time(1_000_000).unwrap());
+            assert_eq!(test_case.initial.now, get_time(1_000_100).unwrap());
+            for step in &test_case.input {
+                let header = &step.block.signed_header.header;
+                assert_eq!(header.height.value(), 2);
+                assert_eq!(
+                    step.block.signed_header.commit.signatures[0].timestamp(),
+                    Some(header.time)
+                );
+            }
+        }
+
+        let future = &batch.test_cases[1].input;
+        assert_eq!(
+            future[1].block.signed_header.header.time,
+            get_time(1_000_105).unwrap()
+        );
+        assert_eq!(future[1].verdict, ExpectedVerdict::HeaderFromTheFuture);
+
+        let expiry = &batch.test_cases[2].input;
+        assert_eq!(expiry[1].now, get_time(1_003_600).unwrap());
+
+        assert!(TimeSkew::default().now(3600).generate().is_err());
+    }
+
+    #[test]
+    fn test_time_skew_json() {
+        let time_skew = TimeSkew::default().seed(3);
+        let encoded = time_skew.encode().unwrap();
+        let decoded: VerifierTestBatch = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, time_skew.generate().unwrap());
+
+        let json: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+        let test_case = &json["test_cases"][0];
+        assert!(test_case["initial"]["signed_header"].is_object());
+        assert_eq!(test_case["initial"]["trusting_period"], "3600000000000");
+        assert_eq!(test_case["input"][1]["verdict"], "NON_MONOTONIC_BFT_TIME");
+    }
+}
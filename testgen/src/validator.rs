@@ -19,6 +19,12 @@ pub struct Validator {
         meta = "PRIORITY"
     )]
     pub proposer_priority: Option<i64>,
+    #[options(
+        help = "seed for deriving the validator key (default: derived from id only)",
+        meta = "SEED"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 impl Validator {
@@ -27,12 +33,20 @@ impl Validator {
             id: Some(id.to_string()),
             voting_power: None,
             proposer_priority: None,
+            seed: None,
         }
     }
     // Question: Why do we need this option since we're already initializing id with fn new()??
     set_option!(id, &str, Some(id.to_string()));
     set_option!(voting_power, u64);
     set_option!(proposer_priority, i64);
+    set_option!(seed, u64);
+
+    /// Use the given seed for key derivation, unless this validator already has one.
+    pub fn with_default_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = self.seed.or(seed);
+        self
+    }
 
     /// Get private key for this validator companion.
     pub fn get_private_key(&self) -> Result<private_key::Ed25519, SimpleError> {
@@ -43,6 +57,14 @@ impl Validator {
         if id.is_empty() {
             bail!("empty validator identifier")
         }
+        if let Some(seed) = self.seed {
+            let mut bytes = [0u8; 32];
+            SeededRng::with_label(seed, id).fill_bytes(&mut bytes);
+            return Ok(require_with!(
+                private_key::Ed25519::try_from(&bytes[..]).ok(),
+                "failed to construct a seed from validator identifier and seed"
+            ));
+        }
         let mut bytes = id.clone().into_bytes();
         if bytes.len() > 32 {
             bail!("validator identifier is too long")
@@ -101,6 +123,7 @@ impl Generator<validator::Info> for Validator {
             id: self.id.or(default.id),
             voting_power: self.voting_power.or(default.voting_power),
             proposer_priority: self.proposer_priority.or(default.proposer_priority),
+            seed: self.seed.or(default.seed),
         }
     }
 
@@ -127,6 +150,13 @@ pub fn generate_validators(vals: &[Validator]) -> Result<Vec<validator::Info>, S
         .collect::<Result<Vec<validator::Info>, SimpleError>>()
 }
 
+/// A helper function to apply a default key derivation seed to multiple validators at once.
+pub fn with_default_seed(vals: &[Validator], seed: Option<u64>) -> Vec<Validator> {
+    vals.iter()
+        .map(|v| v.clone().with_default_seed(seed))
+        .collect()
+}
+
 /// A helper function to sort validators according to the Tendermint specs.
 pub fn sort_validators(vals: &[Validator]) -> Vec<Validator> {
     let mut sorted = vals.to_owned();
@@ -200,4 +230,41 @@ mod tests {
         let val = val.proposer_priority(1000);
         assert_eq!(val.generate().unwrap(), block_val);
     }
+
+    #[test]
+    fn test_validator_seed() {
+        let val = Validator::new("a").voting_power(10);
+        let seeded = val.clone().seed(42);
+
+        assert_ne!(val.generate().unwrap(), seeded.generate().unwrap());
+        assert_eq!(
+            seeded.generate().unwrap(),
+            Validator::new("a")
+                .voting_power(10)
+                .seed(42)
+                .generate()
+                .unwrap()
+        );
+        assert_ne!(
+            seeded.generate().unwrap(),
+            val.clone().seed(43).generate().unwrap()
+        );
+        assert_ne!(
+            seeded.generate().unwrap(),
+            Validator::new("b")
+                .voting_power(10)
+                .seed(42)
+                .generate()
+                .unwrap()
+        );
+
+        // an explicit seed takes precedence over the default one
+        assert_eq!(seeded.clone().with_default_seed(Some(7)).seed, Some(42));
+        assert_eq!(val.with_default_seed(Some(7)).seed, Some(7));
+
+        // seeded validators are not restricted by the length of their identifier
+        let long_id = "a".repeat(64);
+        assert!(Validator::new(&long_id).generate().is_err());
+        assert!(Validator::new(&long_id).seed(42).generate().is_ok());
+    }
 }
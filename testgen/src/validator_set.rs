@@ -50,7 +50,10 @@ impl Generator<validator::Set> for ValidatorSet {
 
     fn generate(&self) -> Result<validator::Set, SimpleError> {
         let vals = generate_validators(self.validators.as_ref().unwrap())?;
-        Ok(validator::Set::without_proposer(vals))
+        Ok(try_with!(
+            validator::Set::without_proposer(vals),
+            "invalid validator set"
+        ))
     }
 }
 
@@ -66,7 +69,8 @@ mod tests {
             Validator::new("b").voting_power(50),
             Validator::new("c").voting_power(50),
         ];
-        let valset2 = validator::Set::without_proposer(generate_validators(&vals1).unwrap());
+        let valset2 =
+            validator::Set::without_proposer(generate_validators(&vals1).unwrap()).unwrap();
 
         assert_eq!(valset1.hash(), valset2.hash());
 
@@ -83,7 +87,8 @@ mod tests {
             Validator::new("b"),
             Validator::new("c"),
         ];
-        let valset5 = validator::Set::without_proposer(generate_validators(&vals2).unwrap());
+        let valset5 =
+            validator::Set::without_proposer(generate_validators(&vals2).unwrap()).unwrap();
         assert_ne!(valset2.hash(), valset5.hash());
     }
 }
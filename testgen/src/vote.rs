@@ -10,7 +10,7 @@ use tendermint::{
     vote::ValidatorIndex,
 };
 
-use crate::{helpers::*, Generator, Header, Validator};
+use crate::{helpers::*, vote_extension::ExtendedVote, Generator, Header, Validator};
 
 #[derive(Debug, Options, Serialize, Deserialize, Clone)]
 pub struct Vote {
@@ -35,6 +35,8 @@ pub struct Vote {
         help = "to indicate if the vote is nil; produces a 'BlockIdFlagNil' if set, otherwise 'BlockIdFlagCommit' (default)"
     )]
     pub nil: Option<()>,
+    #[options(help = "vote extension, for non-nil precommits (default: empty)")]
+    pub extension: Option<String>,
 }
 
 impl Vote {
@@ -48,6 +50,7 @@ impl Vote {
             time: None,
             round: None,
             nil: None,
+            extension: None,
         }
     }
     set_option!(index, u16);
@@ -57,6 +60,42 @@ impl Vote {
     set_option!(time, u64);
     set_option!(round, u32);
     set_option!(nil, bool, if nil { Some(()) } else { None });
+    set_option!(extension, &str, Some(extension.to_string()));
+
+    /// Generate the vote, along with its extension signed by the validator,
+    /// as in CometBFT 0.38. Only non-nil precommits are extended.
+    pub fn generate_extended(&self) -> Result<ExtendedVote, SimpleError> {
+        let vote = self.generate()?;
+        if vote.vote_type != vote::Type::Precommit || vote.block_id.is_none() {
+            if self.extension.is_some() {
+                bail!("failed to generate vote: only non-nil precommits can be extended")
+            }
+            return Ok(ExtendedVote {
+                vote,
+                extension: vec![],
+                extension_signature: vec![],
+            });
+        }
+        // `generate` has checked that both are present
+        let header = self.header.as_ref().unwrap();
+        let validator = self
+            .validator
+            .clone()
+            .unwrap()
+            .with_default_seed(header.seed);
+        let signing_key = validator.get_private_key()?;
+        let signing_key = ed25519_consensus::SigningKey::try_from(signing_key).unwrap();
+
+        let extension = self.extension.clone().unwrap_or_default().into_bytes();
+        let sign_bytes =
+            get_vote_extension_sign_bytes(header.generate()?.chain_id, &vote, &extension);
+        let extension_signature = signing_key.sign(&sign_bytes).to_bytes().to_vec();
+        Ok(ExtendedVote {
+            vote,
+            extension,
+            extension_signature,
+        })
+    }
 }
 
 impl std::str::FromStr for Vote {
@@ -77,6 +116,7 @@ impl Generator<vote::Vote> for Vote {
             time: self.time.or(default.time),
             round: self.round.or(default.round),
             nil: self.nil.or(default.nil),
+            extension: self.extension.or(default.extension),
         }
     }
 
@@ -89,6 +129,8 @@ impl Generator<vote::Vote> for Vote {
             None => bail!("failed to generate vote: header is missing"),
             Some(h) => h,
         };
+        // the validator's key is derived using the header's seed, unless it has its own
+        let validator = &validator.clone().with_default_seed(header.seed);
         let signing_key = validator.get_private_key()?;
         let signing_key = ed25519_consensus::SigningKey::try_from(signing_key).unwrap();
         let block_validator = validator.generate()?;
@@ -194,4 +236,38 @@ mod tests {
             block_vote.signature.as_ref().unwrap()
         ));
     }
+
+    #[test]
+    fn test_extended_vote() {
+        let valset = [Validator::new("a"), Validator::new("b")];
+        let header = Header::new(&valset).height(10).chain_id("test-chain");
+
+        let vote = Vote::new(valset[1].clone(), header.clone()).extension("ext");
+        let extended = vote.generate_extended().unwrap();
+        assert_eq!(extended.vote, vote.generate().unwrap());
+        assert_eq!(extended.extension, b"ext");
+
+        let sign_bytes = get_vote_extension_sign_bytes(
+            header.generate().unwrap().chain_id,
+            &extended.vote,
+            &extended.extension,
+        );
+        let signature = Signature::try_from(extended.extension_signature.as_slice()).unwrap();
+        assert!(verify_signature(
+            &valset[1].get_public_key().unwrap(),
+            &sign_bytes,
+            &signature
+        ));
+
+        let nil = Vote::new(valset[1].clone(), header.clone()).nil(true);
+        assert!(nil
+            .generate_extended()
+            .unwrap()
+            .extension_signature
+            .is_empty());
+        assert!(nil.extension("ext").generate_extended().is_err());
+
+        let prevote = Vote::new(valset[1].clone(), header).prevote(true);
+        assert!(prevote.generate_extended().unwrap().extension.is_empty());
+    }
 }
@@ -0,0 +1,80 @@
+//! The data structures of CometBFT 0.38 carrying vote extensions, in its JSON
+//! format, which `tendermint` does not define yet.
+
+use serde::{Deserialize, Serialize};
+use tendermint::{
+    block::{self, CommitSig},
+    serializers::bytes::base64string,
+    vote,
+};
+
+/// A vote along with its extension and the signature of the extension, as
+/// non-nil precommits carry them in CometBFT 0.38
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedVote {
+    #[serde(flatten)]
+    pub vote: vote::Vote,
+    #[serde(with = "base64string")]
+    pub extension: Vec<u8>,
+    #[serde(with = "base64string")]
+    pub extension_signature: Vec<u8>,
+}
+
+/// A commit signature along with the extension of the vote, and the
+/// signature of the extension
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedCommitSig {
+    #[serde(flatten)]
+    pub commit_sig: CommitSig,
+    #[serde(with = "base64string")]
+    pub extension: Vec<u8>,
+    #[serde(with = "base64string")]
+    pub extension_signature: Vec<u8>,
+}
+
+/// A commit whose signatures carry the extensions of the votes, which
+/// CometBFT 0.38 provides to the proposer of the next block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedCommit {
+    pub height: block::Height,
+    #[serde(with = "round_number")]
+    pub round: block::Round,
+    pub block_id: block::Id,
+    pub extended_signatures: Vec<ExtendedCommitSig>,
+}
+
+impl ExtendedCommit {
+    /// The commit without the vote extensions
+    pub fn to_commit(&self) -> block::Commit {
+        block::Commit {
+            height: self.height,
+            round: self.round,
+            block_id: self.block_id,
+            signatures: self
+                .extended_signatures
+                .iter()
+                .map(|sig| sig.commit_sig.clone())
+                .collect(),
+        }
+    }
+}
+
+/// Rounds are numbers in commits, rather than strings.
+mod round_number {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+    use tendermint::block;
+
+    pub fn serialize<S: Serializer>(
+        round: &block::Round,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        round.value().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<block::Round, D::Error> {
+        let value = u32::deserialize(deserializer)?;
+        block::Round::try_from(value).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
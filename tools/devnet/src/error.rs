@@ -0,0 +1,47 @@
+//! Errors of the setup and launch of devnets.
+
+use std::io::Error as IoError;
+
+use flex_error::{define_error, DisplayOnly};
+use tendermint::block::Height;
+
+define_error! {
+    #[derive(Debug)]
+    Error {
+        NoValidators
+            |_| { "a devnet needs at least one validator" },
+
+        PortOutOfRange
+            { base_port: u16, node: usize }
+            |e| {
+                format_args!("the ports of node {} exceed the range of ports from {}",
+                    e.node, e.base_port)
+            },
+
+        FileIo
+            { path: String }
+            [ DisplayOnly<IoError> ]
+            |e| { format_args!("failed to write file: {}", e.path) },
+
+        Spawn
+            { program: String }
+            [ DisplayOnly<IoError> ]
+            |e| { format_args!("failed to spawn {}", e.program) },
+
+        SerdeJson
+            [ DisplayOnly<serde_json::Error> ]
+            |_| { "serde json error" },
+
+        Tendermint
+            [ tendermint::Error ]
+            |_| { "tendermint error" },
+
+        Exited
+            { node: usize, program: String }
+            |e| { format_args!("{} of node {} exited", e.program, e.node) },
+
+        Timeout
+            { node: usize, height: Height }
+            |e| { format_args!("node {} did not reach height {} in time", e.node, e.height) },
+    }
+}
@@ -0,0 +1,131 @@
+//! Generation of the keys, genesis and configuration files of the nodes.
+
+use std::{fs, path::Path, time::Duration};
+
+use rand_core::{OsRng, RngCore};
+use serde_json::Value;
+use tendermint::{
+    block, consensus, evidence,
+    private_key::{self, PrivateKey},
+    public_key::Algorithm,
+    validator, Genesis, Time,
+};
+use tendermint_config::{NodeKey, PrivValidatorKey};
+
+use crate::{Error, Ports};
+
+/// The voting power of each validator of a devnet.
+const VOTING_POWER: u32 = 10;
+
+/// Generate a random Ed25519 private key.
+pub fn generate_key() -> PrivateKey {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    PrivateKey::Ed25519(private_key::Ed25519::try_from(&bytes[..]).unwrap())
+}
+
+/// The genesis of a devnet, with the default consensus parameters of
+/// Tendermint.
+pub fn genesis(chain_id: &str, validators: Vec<validator::Info>) -> Result<Genesis, Error> {
+    Ok(Genesis {
+        genesis_time: Time::now(),
+        chain_id: chain_id.parse().map_err(Error::tendermint)?,
+        initial_height: 1,
+        consensus_params: consensus::Params {
+            block: block::Size {
+                max_bytes: 22_020_096,
+                max_gas: -1,
+                time_iota_ms: block::Size::default_time_iota_ms(),
+            },
+            evidence: evidence::Params {
+                max_age_num_blocks: 100_000,
+                max_age_duration: evidence::Duration(Duration::from_secs(48 * 60 * 60)),
+                max_bytes: 1_048_576,
+            },
+            validator: consensus::params::ValidatorParams {
+                pub_key_types: vec![Algorithm::Ed25519],
+            },
+            version: None,
+        },
+        validators,
+        app_hash: Default::default(),
+        app_state: Value::Object(Default::default()),
+    })
+}
+
+/// The genesis entry of the validator with the given key.
+pub fn validator_info(index: usize, key: &PrivValidatorKey) -> validator::Info {
+    validator::Info {
+        name: Some(moniker(index)),
+        ..validator::Info::new(key.pub_key, VOTING_POWER.into())
+    }
+}
+
+pub fn moniker(index: usize) -> String {
+    format!("node{index}")
+}
+
+/// The settings of `config.toml` differing from the defaults of Tendermint,
+/// which fills in the missing ones.
+pub fn config_toml(index: usize, ports: Ports, peers: &str, timeout_commit: Duration) -> String {
+    format!(
+        r#"proxy_app = "tcp://127.0.0.1:{abci}"
+moniker = "{moniker}"
+
+[rpc]
+laddr = "tcp://127.0.0.1:{rpc}"
+
+[p2p]
+laddr = "tcp://127.0.0.1:{p2p}"
+persistent_peers = "{peers}"
+addr_book_strict = false
+allow_duplicate_ip = true
+
+[consensus]
+timeout_commit = "{timeout_commit}ms"
+"#,
+        abci = ports.abci,
+        moniker = moniker(index),
+        rpc = ports.rpc,
+        p2p = ports.p2p,
+        timeout_commit = timeout_commit.as_millis(),
+    )
+}
+
+/// The initial signing state of a validator, which Tendermint expects to
+/// find next to its key.
+pub const PRIV_VALIDATOR_STATE: &str = r#"{"height":"0","round":0,"step":0}"#;
+
+/// Write the files of a node to its home directory.
+pub fn write_node_files(
+    home: &Path,
+    node_key: &NodeKey,
+    validator_key: &PrivValidatorKey,
+    genesis: &Genesis,
+    config: &str,
+) -> Result<(), Error> {
+    let config_dir = home.join("config");
+    let data_dir = home.join("data");
+    for dir in [&config_dir, &data_dir] {
+        fs::create_dir_all(dir).map_err(|e| Error::file_io(dir.display().to_string(), e))?;
+    }
+    write(&config_dir.join("node_key.json"), &to_json(node_key)?)?;
+    write(
+        &config_dir.join("priv_validator_key.json"),
+        &to_json(validator_key)?,
+    )?;
+    write(&config_dir.join("genesis.json"), &to_json(genesis)?)?;
+    write(&config_dir.join("config.toml"), config)?;
+    write(
+        &data_dir.join("priv_validator_state.json"),
+        PRIV_VALIDATOR_STATE,
+    )
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string_pretty(value).map_err(Error::serde_json)
+}
+
+fn write(path: &Path, content: &str) -> Result<(), Error> {
+    fs::write(path, content).map_err(|e| Error::file_io(path.display().to_string(), e))
+}
@@ -0,0 +1,559 @@
+//! Local networks of Tendermint validators, each running with its own
+//! instance of the `kvstore-rs` ABCI application, for integration tests.
+//!
+//! The keys, genesis and configuration of the nodes are generated in a
+//! temporary directory, and the Tendermint nodes and applications are
+//! launched as subprocesses, which are killed when the devnet is dropped:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use devnet::Devnet;
+//! use tendermint_rpc::Client;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut devnet = Devnet::builder().validators(4).launch()?;
+//! devnet
+//!     .wait_for_height(3_u32.into(), Duration::from_secs(30))
+//!     .await?;
+//! let status = devnet.node(0).client().status().await?;
+//! assert_eq!(status.node_info.moniker.to_string(), "node0");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `tendermint` and `kvstore-rs` binaries are expected on the `PATH`,
+//! unless their location is given to the [`DevnetBuilder`].
+
+mod error;
+mod files;
+
+use std::{
+    fs::File,
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use tempfile::TempDir;
+use tendermint::{block::Height, node, validator};
+use tendermint_config::{NodeKey, PrivValidatorKey};
+use tendermint_rpc::{Client, HttpClient};
+
+pub use error::Error;
+
+/// The first port used by the nodes of a devnet, above the default ports of
+/// Tendermint so as not to clash with a node running on the same host.
+pub const DEFAULT_BASE_PORT: u16 = 27000;
+
+/// The number of ports reserved for each node, from the base port onwards.
+pub const PORTS_PER_NODE: u16 = 10;
+
+/// How long to wait for an application to accept connections.
+const APP_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the nodes are polled while waiting for them.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The ports a node and its application listen on, on `127.0.0.1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ports {
+    pub p2p: u16,
+    pub rpc: u16,
+    pub abci: u16,
+}
+
+impl Ports {
+    /// The ports of the node at the given index, failing if they exceed the
+    /// range of ports.
+    fn of(base_port: u16, index: usize) -> Result<Self, Error> {
+        let base = u16::try_from(index)
+            .ok()
+            .and_then(|index| index.checked_mul(PORTS_PER_NODE))
+            .and_then(|offset| base_port.checked_add(offset))
+            .filter(|base| base.checked_add(PORTS_PER_NODE - 1).is_some())
+            .ok_or_else(|| Error::port_out_of_range(base_port, index))?;
+        Ok(Self {
+            p2p: base,
+            rpc: base + 1,
+            abci: base + 2,
+        })
+    }
+}
+
+/// The configuration of a devnet.
+#[derive(Clone, Debug)]
+pub struct DevnetBuilder {
+    validators: usize,
+    chain_id: String,
+    base_port: u16,
+    home: Option<PathBuf>,
+    tendermint_binary: PathBuf,
+    kvstore_binary: PathBuf,
+    timeout_commit: Duration,
+}
+
+impl Default for DevnetBuilder {
+    fn default() -> Self {
+        Self {
+            validators: 4,
+            chain_id: "devnet".to_owned(),
+            base_port: DEFAULT_BASE_PORT,
+            home: None,
+            tendermint_binary: "tendermint".into(),
+            kvstore_binary: "kvstore-rs".into(),
+            timeout_commit: Duration::from_millis(500),
+        }
+    }
+}
+
+impl DevnetBuilder {
+    /// The number of validators, 4 by default.
+    pub fn validators(mut self, validators: usize) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    /// The chain ID, `devnet` by default.
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+
+    /// The first of the ports used by the nodes, see [`PORTS_PER_NODE`].
+    /// Devnets running at the same time need distinct ranges of ports.
+    pub fn base_port(mut self, base_port: u16) -> Self {
+        self.base_port = base_port;
+        self
+    }
+
+    /// Generate the home directories of the nodes in the given directory,
+    /// which is kept when the devnet is dropped, instead of a temporary one.
+    pub fn home(mut self, home: impl Into<PathBuf>) -> Self {
+        self.home = Some(home.into());
+        self
+    }
+
+    /// The path of the Tendermint binary.
+    pub fn tendermint_binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tendermint_binary = path.into();
+        self
+    }
+
+    /// The path of the `kvstore-rs` binary.
+    pub fn kvstore_binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.kvstore_binary = path.into();
+        self
+    }
+
+    /// How long the nodes wait after committing a block before starting the
+    /// next height, 500ms by default.
+    pub fn timeout_commit(mut self, timeout_commit: Duration) -> Self {
+        self.timeout_commit = timeout_commit;
+        self
+    }
+
+    /// Generate the keys, genesis and configuration of the nodes, without
+    /// launching them.
+    pub fn generate(self) -> Result<Devnet, Error> {
+        if self.validators == 0 {
+            return Err(Error::no_validators());
+        }
+        let node_ports = (0..self.validators)
+            .map(|index| Ports::of(self.base_port, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (temp_dir, home) = match &self.home {
+            Some(home) => (None, home.clone()),
+            None => {
+                let dir = tempfile::Builder::new()
+                    .prefix("devnet")
+                    .tempdir()
+                    .map_err(|e| Error::file_io(std::env::temp_dir().display().to_string(), e))?;
+                let home = dir.path().to_owned();
+                (Some(dir), home)
+            },
+        };
+
+        let keys: Vec<(NodeKey, PrivValidatorKey)> = (0..self.validators)
+            .map(|_| {
+                let node_key = NodeKey {
+                    priv_key: files::generate_key(),
+                };
+                let priv_key = files::generate_key();
+                let pub_key = priv_key.public_key();
+                let validator_key = PrivValidatorKey {
+                    address: pub_key.into(),
+                    pub_key,
+                    priv_key,
+                };
+                (node_key, validator_key)
+            })
+            .collect();
+        let validators: Vec<validator::Info> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, (_, key))| files::validator_info(index, key))
+            .collect();
+        let genesis = files::genesis(&self.chain_id, validators.clone())?;
+        let peers: Vec<String> = keys
+            .iter()
+            .enumerate()
+            .map(|(index, (node_key, _))| {
+                format!("{}@127.0.0.1:{}", node_key.node_id(), node_ports[index].p2p)
+            })
+            .collect();
+
+        let mut nodes = Vec::with_capacity(self.validators);
+        for (index, ((node_key, validator_key), validator)) in
+            keys.iter().zip(validators).enumerate()
+        {
+            let node_home = home.join(files::moniker(index));
+            let ports = node_ports[index];
+            let mut other_peers = peers.clone();
+            other_peers.remove(index);
+            let config =
+                files::config_toml(index, ports, &other_peers.join(","), self.timeout_commit);
+            files::write_node_files(&node_home, node_key, validator_key, &genesis, &config)?;
+            nodes.push(Node {
+                index,
+                home: node_home,
+                node_id: node_key.node_id(),
+                validator,
+                ports,
+                processes: None,
+            });
+        }
+
+        Ok(Devnet {
+            nodes,
+            tendermint_binary: self.tendermint_binary,
+            kvstore_binary: self.kvstore_binary,
+            home,
+            _temp_dir: temp_dir,
+        })
+    }
+
+    /// Generate the devnet and launch all of its nodes.
+    pub fn launch(self) -> Result<Devnet, Error> {
+        let mut devnet = self.generate()?;
+        devnet.start()?;
+        Ok(devnet)
+    }
+}
+
+/// A devnet, whose nodes are stopped when it is dropped.
+#[derive(Debug)]
+pub struct Devnet {
+    // Dropped before the temporary directory holding their files.
+    nodes: Vec<Node>,
+    tendermint_binary: PathBuf,
+    kvstore_binary: PathBuf,
+    home: PathBuf,
+    _temp_dir: Option<TempDir>,
+}
+
+impl Devnet {
+    /// The configuration of a devnet, see [`DevnetBuilder`].
+    pub fn builder() -> DevnetBuilder {
+        DevnetBuilder::default()
+    }
+
+    /// The directory holding the home directories of the nodes.
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// The nodes, in the order of their ports.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The node with the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such node.
+    pub fn node(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+
+    /// Launch all the nodes which are not running.
+    pub fn start(&mut self) -> Result<(), Error> {
+        (0..self.nodes.len()).try_for_each(|index| self.start_node(index))
+    }
+
+    /// Launch the given node and its application, unless already running.
+    pub fn start_node(&mut self, index: usize) -> Result<(), Error> {
+        let (tendermint_binary, kvstore_binary) = (&self.tendermint_binary, &self.kvstore_binary);
+        self.nodes[index].start(tendermint_binary, kvstore_binary)
+    }
+
+    /// Kill the given node and its application, e.g. to test the behavior of
+    /// the network with a validator down. Its state is kept, so that it can
+    /// be started again.
+    pub fn stop_node(&mut self, index: usize) {
+        self.nodes[index].stop();
+    }
+
+    /// Wait for all the running nodes to have committed the given height.
+    pub async fn wait_for_height(
+        &mut self,
+        height: Height,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+        for node in self
+            .nodes
+            .iter_mut()
+            .filter(|node| node.processes.is_some())
+        {
+            loop {
+                node.check_running()?;
+                // The RPC server is not up yet while the node starts.
+                if let Ok(status) = node.client().status().await {
+                    if status.sync_info.latest_block_height >= height {
+                        break;
+                    }
+                }
+                if Instant::now() >= deadline {
+                    return Err(Error::timeout(node.index, height));
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A validator node of a devnet.
+#[derive(Debug)]
+pub struct Node {
+    index: usize,
+    home: PathBuf,
+    node_id: node::Id,
+    validator: validator::Info,
+    ports: Ports,
+    processes: Option<Processes>,
+}
+
+/// The subprocesses of a running node.
+#[derive(Debug)]
+struct Processes {
+    app: Child,
+    tendermint: Child,
+}
+
+impl Node {
+    /// The index of the node in the devnet.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The moniker of the node, `node<index>`.
+    pub fn moniker(&self) -> String {
+        files::moniker(self.index)
+    }
+
+    /// The home directory of the node, which also holds the logs of the
+    /// node (`tendermint.log`) and of its application (`app.log`).
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// The P2P ID of the node.
+    pub fn node_id(&self) -> node::Id {
+        self.node_id
+    }
+
+    /// The genesis entry of the validator run by the node.
+    pub fn validator(&self) -> &validator::Info {
+        &self.validator
+    }
+
+    pub fn ports(&self) -> Ports {
+        self.ports
+    }
+
+    /// The URL of the RPC endpoint of the node.
+    pub fn rpc_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.ports.rpc)
+    }
+
+    /// A client of the RPC endpoint of the node.
+    pub fn client(&self) -> HttpClient {
+        HttpClient::new(self.rpc_url().as_str()).unwrap()
+    }
+
+    /// Whether the node was started and not stopped since.
+    pub fn is_running(&self) -> bool {
+        self.processes.is_some()
+    }
+
+    fn start(&mut self, tendermint_binary: &Path, kvstore_binary: &Path) -> Result<(), Error> {
+        if self.processes.is_some() {
+            return Ok(());
+        }
+        let mut app = self.spawn(
+            Command::new(kvstore_binary)
+                .args(["--host", "127.0.0.1", "--port"])
+                .arg(self.ports.abci.to_string()),
+            "app.log",
+        )?;
+        // Tendermint gives up right away if the application is not listening.
+        let deadline = Instant::now() + APP_STARTUP_TIMEOUT;
+        while TcpStream::connect(("127.0.0.1", self.ports.abci)).is_err() {
+            if Instant::now() >= deadline || matches!(app.try_wait(), Ok(Some(_))) {
+                let _ = app.kill();
+                let _ = app.wait();
+                return Err(Error::exited(
+                    self.index,
+                    kvstore_binary.display().to_string(),
+                ));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        let tendermint = match self.spawn(
+            Command::new(tendermint_binary)
+                .arg("start")
+                .arg("--home")
+                .arg(&self.home),
+            "tendermint.log",
+        ) {
+            Ok(tendermint) => tendermint,
+            Err(e) => {
+                let _ = app.kill();
+                let _ = app.wait();
+                return Err(e);
+            },
+        };
+        tracing::debug!(
+            "started {} (rpc: {}, p2p: {}, abci: {})",
+            self.moniker(),
+            self.ports.rpc,
+            self.ports.p2p,
+            self.ports.abci
+        );
+        self.processes = Some(Processes { app, tendermint });
+        Ok(())
+    }
+
+    fn spawn(&self, command: &mut Command, log: &str) -> Result<Child, Error> {
+        let log_path = self.home.join(log);
+        let log_file = File::create(&log_path)
+            .map_err(|e| Error::file_io(log_path.display().to_string(), e))?;
+        let stderr = log_file
+            .try_clone()
+            .map_err(|e| Error::file_io(log_path.display().to_string(), e))?;
+        command
+            .stdin(Stdio::null())
+            .stdout(log_file)
+            .stderr(stderr)
+            .spawn()
+            .map_err(|e| Error::spawn(command.get_program().to_string_lossy().into_owned(), e))
+    }
+
+    fn check_running(&mut self) -> Result<(), Error> {
+        let Some(processes) = &mut self.processes else {
+            return Ok(());
+        };
+        if matches!(processes.app.try_wait(), Ok(Some(_))) {
+            return Err(Error::exited(self.index, "application".to_owned()));
+        }
+        if matches!(processes.tendermint.try_wait(), Ok(Some(_))) {
+            return Err(Error::exited(self.index, "tendermint".to_owned()));
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut processes) = self.processes.take() {
+            // Stop the node first, so that it does not log errors about the
+            // application going away.
+            for child in [&mut processes.tendermint, &mut processes.app] {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+            tracing::debug!("stopped {}", self.moniker());
+        }
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tendermint::Genesis;
+
+    use super::*;
+
+    #[test]
+    fn ports_within_range() {
+        assert_eq!(
+            Ports::of(65526, 0).unwrap(),
+            Ports {
+                p2p: 65526,
+                rpc: 65527,
+                abci: 65528
+            }
+        );
+        assert!(Ports::of(65527, 0).is_err());
+        assert!(Ports::of(26656, 3888).is_err());
+        assert!(Ports::of(0, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn generates_wired_nodes() {
+        let devnet = Devnet::builder()
+            .validators(3)
+            .base_port(28000)
+            .generate()
+            .unwrap();
+        assert_eq!(devnet.nodes().len(), 3);
+
+        let genesis: Genesis = serde_json::from_str(
+            &fs::read_to_string(devnet.node(0).home().join("config/genesis.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(genesis.chain_id.as_str(), "devnet");
+        assert_eq!(
+            genesis.validators,
+            devnet
+                .nodes()
+                .iter()
+                .map(|node| node.validator().clone())
+                .collect::<Vec<_>>()
+        );
+
+        for node in devnet.nodes() {
+            let config = fs::read_to_string(node.home().join("config/config.toml")).unwrap();
+            for peer in devnet.nodes() {
+                let address = format!("{}@127.0.0.1:{}", peer.node_id(), peer.ports().p2p);
+                assert_eq!(config.contains(&address), peer.index() != node.index());
+            }
+            let key = PrivValidatorKey::load_json_file(
+                &node.home().join("config/priv_validator_key.json"),
+            )
+            .unwrap();
+            assert_eq!(key.pub_key, node.validator().pub_key);
+            let node_key =
+                NodeKey::load_json_file(&node.home().join("config/node_key.json")).unwrap();
+            assert_eq!(node_key.node_id(), node.node_id());
+        }
+        assert_eq!(
+            devnet.node(1).ports(),
+            Ports {
+                p2p: 28010,
+                rpc: 28011,
+                abci: 28012
+            }
+        );
+    }
+}
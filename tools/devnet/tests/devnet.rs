@@ -0,0 +1,47 @@
+//! Launches of devnets, which need the `tendermint` and `kvstore-rs`
+//! binaries on the `PATH`:
+//!
+//!     cargo test -- --ignored
+
+use std::time::Duration;
+
+use devnet::Devnet;
+use tendermint_rpc::{Client, Paging};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+#[tokio::test]
+#[ignore]
+async fn validators_produce_blocks() {
+    let mut devnet = Devnet::builder()
+        .validators(4)
+        .base_port(29000)
+        .launch()
+        .unwrap();
+    devnet.wait_for_height(3_u32.into(), TIMEOUT).await.unwrap();
+
+    let client = devnet.node(0).client();
+    let validators = client
+        .validators(2_u32, Paging::All)
+        .await
+        .unwrap()
+        .validators;
+    assert_eq!(validators.len(), 4);
+    for node in devnet.nodes() {
+        assert!(validators
+            .iter()
+            .any(|validator| validator.pub_key == node.validator().pub_key));
+    }
+
+    // The remaining validators still hold more than 2/3 of the voting power.
+    devnet.stop_node(3);
+    let height = client.status().await.unwrap().sync_info.latest_block_height;
+    devnet
+        .wait_for_height(height.increment().increment(), TIMEOUT)
+        .await
+        .unwrap();
+
+    devnet.start_node(3).unwrap();
+    let height = client.status().await.unwrap().sync_info.latest_block_height;
+    devnet.wait_for_height(height, TIMEOUT).await.unwrap();
+}
@@ -14,6 +14,7 @@
 
 use std::{convert::TryFrom, time::Duration};
 
+use async_trait::async_trait;
 use tendermint::Hash;
 use tendermint_light_client::{
     builder::{LightClientBuilder, SupervisorBuilder},
@@ -31,9 +32,9 @@ use tendermint_rpc as rpc;
 
 struct TestEvidenceReporter;
 
-#[contracts::contract_trait]
+#[async_trait]
 impl EvidenceReporter for TestEvidenceReporter {
-    fn report(&self, evidence: Evidence, peer: PeerId) -> Result<Hash, IoError> {
+    async fn report(&self, evidence: Evidence, peer: PeerId) -> Result<Hash, IoError> {
         panic!("unexpected fork detected for peer {peer} with evidence: {evidence:?}");
     }
 }
@@ -73,6 +74,7 @@ fn make_supervisor() -> Supervisor {
         trust_threshold: TrustThreshold::new(1, 3).unwrap(),
         trusting_period: Duration::from_secs(60 * 60), // 60 minutes
         clock_drift: Duration::from_secs(5 * 60),      // 5 minutes
+        time_monotonicity: Default::default(),
     };
 
     let primary_instance = make_instance(primary, options, node_address.clone());
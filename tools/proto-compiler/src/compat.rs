@@ -0,0 +1,358 @@
+//! Detection of breaking changes between the proto files of two commitishes.
+
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use prost::Message;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet,
+};
+use serde_json::{json, Value};
+
+/// Only the definitions of these packages are compared, the imported ones
+/// (`gogoproto`, `google.protobuf`) being outside of our control.
+const COMPARED_PACKAGE_PREFIX: &str = "tendermint.";
+
+/// A change of the proto definitions which breaks the wire compatibility of
+/// the messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BreakingChange {
+    RemovedMessage {
+        message: String,
+    },
+    RemovedField {
+        message: String,
+        field: String,
+        number: i32,
+    },
+    RenumberedField {
+        message: String,
+        field: String,
+        old_number: i32,
+        new_number: i32,
+    },
+    ChangedFieldType {
+        message: String,
+        field: String,
+        old_type: String,
+        new_type: String,
+    },
+    RemovedEnum {
+        name: String,
+    },
+    RemovedEnumValue {
+        name: String,
+        value: String,
+        number: i32,
+    },
+    RenumberedEnumValue {
+        name: String,
+        value: String,
+        old_number: i32,
+        new_number: i32,
+    },
+}
+
+impl BreakingChange {
+    fn to_json(&self) -> Value {
+        match self {
+            Self::RemovedMessage { message } => json!({
+                "kind": "removed_message",
+                "message": message,
+            }),
+            Self::RemovedField {
+                message,
+                field,
+                number,
+            } => json!({
+                "kind": "removed_field",
+                "message": message,
+                "field": field,
+                "number": number,
+            }),
+            Self::RenumberedField {
+                message,
+                field,
+                old_number,
+                new_number,
+            } => json!({
+                "kind": "renumbered_field",
+                "message": message,
+                "field": field,
+                "old_number": old_number,
+                "new_number": new_number,
+            }),
+            Self::ChangedFieldType {
+                message,
+                field,
+                old_type,
+                new_type,
+            } => json!({
+                "kind": "changed_field_type",
+                "message": message,
+                "field": field,
+                "old_type": old_type,
+                "new_type": new_type,
+            }),
+            Self::RemovedEnum { name } => json!({
+                "kind": "removed_enum",
+                "enum": name,
+            }),
+            Self::RemovedEnumValue {
+                name,
+                value,
+                number,
+            } => json!({
+                "kind": "removed_enum_value",
+                "enum": name,
+                "value": value,
+                "number": number,
+            }),
+            Self::RenumberedEnumValue {
+                name,
+                value,
+                old_number,
+                new_number,
+            } => json!({
+                "kind": "renumbered_enum_value",
+                "enum": name,
+                "value": value,
+                "old_number": old_number,
+                "new_number": new_number,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RemovedMessage { message } => write!(f, "message {message} was removed"),
+            Self::RemovedField {
+                message,
+                field,
+                number,
+            } => write!(f, "field {message}.{field} = {number} was removed"),
+            Self::RenumberedField {
+                message,
+                field,
+                old_number,
+                new_number,
+            } => write!(
+                f,
+                "field {message}.{field} was renumbered from {old_number} to {new_number}"
+            ),
+            Self::ChangedFieldType {
+                message,
+                field,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "field {message}.{field} changed type from {old_type} to {new_type}"
+            ),
+            Self::RemovedEnum { name } => write!(f, "enum {name} was removed"),
+            Self::RemovedEnumValue {
+                name,
+                value,
+                number,
+            } => write!(f, "enum value {name}.{value} = {number} was removed"),
+            Self::RenumberedEnumValue {
+                name,
+                value,
+                old_number,
+                new_number,
+            } => write!(
+                f,
+                "enum value {name}.{value} was renumbered from {old_number} to {new_number}"
+            ),
+        }
+    }
+}
+
+/// The compatibility of the proto files of a Tendermint version with the
+/// ones of the previously pinned commitish.
+#[derive(Clone, Debug)]
+pub struct CompatibilityReport {
+    pub ident: String,
+    pub previous_commitish: String,
+    pub commitish: String,
+    pub breaking_changes: Vec<BreakingChange>,
+}
+
+impl CompatibilityReport {
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking_changes.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "version": self.ident,
+            "previous_commitish": self.previous_commitish,
+            "commitish": self.commitish,
+            "breaking": self.is_breaking(),
+            "breaking_changes": self
+                .breaking_changes
+                .iter()
+                .map(BreakingChange::to_json)
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Read the commitish the given generated version module was generated
+/// from, if it exists.
+pub fn previous_commitish(version_module: &Path) -> Option<String> {
+    let content = fs::read_to_string(version_module).ok()?;
+    content.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("pub const COMMITISH: &str = \"")?
+            .strip_suffix("\";")
+            .map(ToOwned::to_owned)
+    })
+}
+
+/// Read a file descriptor set written by `protoc`.
+pub fn read_descriptors(path: &Path) -> FileDescriptorSet {
+    let bytes = fs::read(path).expect("file descriptor set read failed");
+    FileDescriptorSet::decode(bytes.as_slice()).expect("file descriptor set decoding failed")
+}
+
+/// The messages and enums of a descriptor set, by fully qualified name.
+#[derive(Default)]
+pub struct Definitions<'a> {
+    pub messages: BTreeMap<String, &'a DescriptorProto>,
+    pub enums: BTreeMap<String, &'a EnumDescriptorProto>,
+}
+
+impl<'a> Definitions<'a> {
+    pub fn of(set: &'a FileDescriptorSet) -> Self {
+        let mut definitions = Self::default();
+        for file in &set.file {
+            let package = file.package();
+            if !package.starts_with(COMPARED_PACKAGE_PREFIX) {
+                continue;
+            }
+            for message in &file.message_type {
+                definitions.add_message(package, message);
+            }
+            for enumeration in &file.enum_type {
+                definitions
+                    .enums
+                    .insert(format!("{package}.{}", enumeration.name()), enumeration);
+            }
+        }
+        definitions
+    }
+
+    fn add_message(&mut self, scope: &str, message: &'a DescriptorProto) {
+        let name = format!("{scope}.{}", message.name());
+        for nested in &message.nested_type {
+            self.add_message(&name, nested);
+        }
+        for enumeration in &message.enum_type {
+            self.enums
+                .insert(format!("{name}.{}", enumeration.name()), enumeration);
+        }
+        self.messages.insert(name, message);
+    }
+}
+
+/// Compare the definitions of the previous descriptor set with the new one.
+pub fn breaking_changes(old: &FileDescriptorSet, new: &FileDescriptorSet) -> Vec<BreakingChange> {
+    let old = Definitions::of(old);
+    let new = Definitions::of(new);
+    let mut changes = Vec::new();
+
+    for (name, old_message) in &old.messages {
+        let Some(new_message) = new.messages.get(name) else {
+            changes.push(BreakingChange::RemovedMessage {
+                message: name.clone(),
+            });
+            continue;
+        };
+        for old_field in &old_message.field {
+            let by_number = new_message
+                .field
+                .iter()
+                .find(|field| field.number() == old_field.number());
+            let by_name = new_message
+                .field
+                .iter()
+                .find(|field| field.name() == old_field.name());
+            match (by_number, by_name) {
+                // Renaming a field keeps the wire format unchanged.
+                (Some(new_field), _) => {
+                    let (old_type, new_type) = (field_type(old_field), field_type(new_field));
+                    if old_type != new_type {
+                        changes.push(BreakingChange::ChangedFieldType {
+                            message: name.clone(),
+                            field: old_field.name().to_owned(),
+                            old_type,
+                            new_type,
+                        });
+                    }
+                },
+                (None, Some(new_field)) => changes.push(BreakingChange::RenumberedField {
+                    message: name.clone(),
+                    field: old_field.name().to_owned(),
+                    old_number: old_field.number(),
+                    new_number: new_field.number(),
+                }),
+                (None, None) => changes.push(BreakingChange::RemovedField {
+                    message: name.clone(),
+                    field: old_field.name().to_owned(),
+                    number: old_field.number(),
+                }),
+            }
+        }
+    }
+
+    for (name, old_enum) in &old.enums {
+        let Some(new_enum) = new.enums.get(name) else {
+            changes.push(BreakingChange::RemovedEnum { name: name.clone() });
+            continue;
+        };
+        for old_value in &old_enum.value {
+            let by_number = new_enum
+                .value
+                .iter()
+                .any(|value| value.number() == old_value.number());
+            let by_name = new_enum
+                .value
+                .iter()
+                .find(|value| value.name() == old_value.name());
+            match (by_number, by_name) {
+                (true, _) => {},
+                (false, Some(new_value)) => changes.push(BreakingChange::RenumberedEnumValue {
+                    name: name.clone(),
+                    value: old_value.name().to_owned(),
+                    old_number: old_value.number(),
+                    new_number: new_value.number(),
+                }),
+                (false, None) => changes.push(BreakingChange::RemovedEnumValue {
+                    name: name.clone(),
+                    value: old_value.name().to_owned(),
+                    number: old_value.number(),
+                }),
+            }
+        }
+    }
+
+    changes
+}
+
+/// A description of the wire type of a field, e.g. `repeated int64` or
+/// `optional .tendermint.types.Header`.
+fn field_type(field: &FieldDescriptorProto) -> String {
+    let label = match field.label() {
+        Label::Optional => "optional",
+        Label::Required => "required",
+        Label::Repeated => "repeated",
+    };
+    let type_name = match field.r#type() {
+        Type::Message | Type::Enum | Type::Group => field.type_name().to_owned(),
+        ty => ty.as_str_name().trim_start_matches("TYPE_").to_lowercase(),
+    };
+    format!("{label} {type_name}")
+}
@@ -3,11 +3,35 @@
 /// Tendermint repository URL.
 pub const TENDERMINT_REPO: &str = "https://github.com/tendermint/tendermint";
 
+/// CometBFT repository URL, where Tendermint is developed from 0.38 on.
+pub const COMETBFT_REPO: &str = "https://github.com/cometbft/cometbft";
+
+/// File name of the compatibility report of the generated versions with the
+/// previously pinned commitishes, in the `target` folder.
+pub const COMPAT_REPORT: &str = "proto-compatibility.json";
+
+/// File names of the descriptor sets of the proto files of the new and of the
+/// previously pinned commitish, in the output folder.
+pub const DESCRIPTORS: &str = "descriptors.bin";
+pub const PREVIOUS_DESCRIPTORS: &str = "previous-descriptors.bin";
+
+/// The configuration of rustfmt for the generated code, pinned so that its
+/// formatting depends neither on the configuration of the repository, whose
+/// options require a nightly toolchain, nor on the version of prost-build.
+/// Only stable options are set.
+pub const RUSTFMT_CONFIG: &str = r#"edition = "2021"
+max_width = 100
+newline_style = "Unix"
+match_block_trailing_comma = true
+"#;
+
 /// Information on a Tendermint snapshot to generate prost structures from.
 pub struct TendermintVersion {
     /// Identifier to use in module names.
     pub ident: &'static str,
-    /// A commitish reference in the tendermint git repository, for example:
+    /// URL of the git repository the version is fetched from.
+    pub repo: &'static str,
+    /// A commitish reference in the git repository, for example:
     ///
     /// - Tag: `v0.34.0-rc4`
     /// - Branch: `main`
@@ -19,14 +43,25 @@ pub struct TendermintVersion {
 pub const TENDERMINT_VERSIONS: &[TendermintVersion] = &[
     TendermintVersion {
         ident: "v0_34",
+        repo: TENDERMINT_REPO,
         commitish: "v0.34.24",
     },
     TendermintVersion {
         ident: "v0_37",
+        repo: TENDERMINT_REPO,
         commitish: "v0.37.0-alpha.1",
     },
+    TendermintVersion {
+        ident: "v0_38",
+        repo: COMETBFT_REPO,
+        commitish: "v0.38.0",
+    },
 ];
 
+/// Identifier of the version whose modules are re-exported at the root of
+/// the generated `tendermint` module.
+pub const DEFAULT_VERSION: &str = "v0_37";
+
 /// Predefined custom attributes for message annotations
 const PRIMITIVE_ENUM: &str = r#"#[derive(::num_derive::FromPrimitive, ::num_derive::ToPrimitive)]"#;
 const SERIALIZED: &str = r#"#[derive(::serde::Deserialize, ::serde::Serialize)]"#;
@@ -53,12 +88,16 @@ const RENAME_SRPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeySr25519", wi
 const RENAME_DUPLICATEVOTE: &str = r#"#[serde(rename = "tendermint/DuplicateVoteEvidence")]"#;
 const RENAME_LIGHTCLIENTATTACK: &str =
     r#"#[serde(rename = "tendermint/LightClientAttackEvidence")]"#;
-const ALIAS_VALIDATOR_POWER_QUOTED: &str =
-    r#"#[serde(alias = "ValidatorPower", with = "crate::serializers::from_str")]"#;
-const ALIAS_TOTAL_VOTING_POWER_QUOTED: &str =
-    r#"#[serde(alias = "TotalVotingPower", with = "crate::serializers::from_str")]"#;
-const ALIAS_TIMESTAMP: &str = r#"#[serde(alias = "Timestamp")]"#;
+// The evidence fields have no JSON tags in Go, hence the capitalized names.
+const RENAME_VALIDATOR_POWER_QUOTED: &str = r#"#[serde(rename = "ValidatorPower", alias = "validator_power", with = "crate::serializers::from_str")]"#;
+const RENAME_TOTAL_VOTING_POWER_QUOTED: &str = r#"#[serde(rename = "TotalVotingPower", alias = "total_voting_power", with = "crate::serializers::from_str")]"#;
+const RENAME_TIMESTAMP: &str = r#"#[serde(rename = "Timestamp", alias = "timestamp")]"#;
+const RENAME_CONFLICTING_BLOCK: &str =
+    r#"#[serde(rename = "ConflictingBlock", alias = "conflicting_block")]"#;
+const RENAME_COMMON_HEIGHT_QUOTED: &str = r#"#[serde(rename = "CommonHeight", alias = "common_height", with = "crate::serializers::from_str")]"#;
+const RENAME_BYZANTINE_VALIDATORS_NULLABLE: &str = r#"#[serde(rename = "ByzantineValidators", alias = "byzantine_validators", with = "crate::serializers::nullable")]"#;
 const ALIAS_PARTS: &str = r#"#[serde(alias = "parts")]"#;
+const RENAME_PARTS: &str = r#"#[serde(rename = "parts", alias = "part_set_header")]"#;
 
 /// Custom type attributes applied on top of protobuf structs
 /// The first item in the tuple defines the message where the annotation should apply and
@@ -86,7 +125,6 @@ pub static CUSTOM_TYPE_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.types.Commit", SERIALIZED),
     (".tendermint.types.CommitSig", SERIALIZED),
     (".tendermint.types.ValidatorSet", SERIALIZED),
-    (".tendermint.crypto.PublicKey", SERIALIZED),
     (".tendermint.crypto.PublicKey.sum", TYPE_TAG),
     (".tendermint.abci.ResponseInfo", SERIALIZED),
     (".tendermint.types.CanonicalBlockID", SERIALIZED),
@@ -126,7 +164,7 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
         BYTES_SKIP_IF_EMPTY,
     ),
     (".tendermint.types.BlockID.hash", HEXSTRING),
-    (".tendermint.types.BlockID.part_set_header", ALIAS_PARTS),
+    (".tendermint.types.BlockID.part_set_header", RENAME_PARTS),
     (
         ".tendermint.types.CanonicalBlockID.part_set_header",
         ALIAS_PARTS,
@@ -156,20 +194,44 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.types.CommitSig.signature", BASE64STRING),
     (
         ".tendermint.types.DuplicateVoteEvidence.total_voting_power",
-        ALIAS_TOTAL_VOTING_POWER_QUOTED,
+        RENAME_TOTAL_VOTING_POWER_QUOTED,
     ),
     (
         ".tendermint.types.DuplicateVoteEvidence.validator_power",
-        ALIAS_VALIDATOR_POWER_QUOTED,
+        RENAME_VALIDATOR_POWER_QUOTED,
     ),
     (
         ".tendermint.types.DuplicateVoteEvidence.timestamp",
-        ALIAS_TIMESTAMP,
+        RENAME_TIMESTAMP,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.conflicting_block",
+        RENAME_CONFLICTING_BLOCK,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.common_height",
+        RENAME_COMMON_HEIGHT_QUOTED,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.byzantine_validators",
+        RENAME_BYZANTINE_VALIDATORS_NULLABLE,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.total_voting_power",
+        RENAME_TOTAL_VOTING_POWER_QUOTED,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.timestamp",
+        RENAME_TIMESTAMP,
     ),
     (".tendermint.types.Vote.height", QUOTED),
     (".tendermint.types.Vote.validator_address", HEXSTRING),
     (".tendermint.types.Vote.signature", BASE64STRING),
     (".tendermint.types.Vote.timestamp", OPTIONAL),
+    (".tendermint.types.Vote.extension", DEFAULT),
+    (".tendermint.types.Vote.extension", BASE64STRING),
+    (".tendermint.types.Vote.extension_signature", DEFAULT),
+    (".tendermint.types.Vote.extension_signature", BASE64STRING),
     (".tendermint.types.Validator.address", HEXSTRING),
     (
         ".tendermint.types.Validator.voting_power",
@@ -202,3 +264,397 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.crypto.Proof.aunts", VEC_BASE64STRING),
     (".tendermint.crypto.Proof.leaf_hash", BASE64STRING),
 ];
+
+/// A `oneof` enum that is mapped to a domain type, with the variants the
+/// domain type handles.
+pub struct DomainOneof {
+    /// Identifier of the Tendermint version the variants apply to.
+    pub version: &'static str,
+    /// Path of the enum, relative to the version module.
+    pub path: &'static str,
+    /// The variants handled by the domain type.
+    pub variants: &'static [&'static str],
+}
+
+/// `oneof` enums that get an exhaustive match generated, so that a variant
+/// added upstream fails compilation until the domain mapping handles it.
+pub const DOMAIN_ONEOFS: &[DomainOneof] = &[
+    DomainOneof {
+        version: "v0_34",
+        path: "abci::request::Value",
+        variants: &[
+            "Echo",
+            "Flush",
+            "Info",
+            "SetOption",
+            "InitChain",
+            "Query",
+            "BeginBlock",
+            "CheckTx",
+            "DeliverTx",
+            "EndBlock",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+        ],
+    },
+    DomainOneof {
+        version: "v0_34",
+        path: "abci::response::Value",
+        variants: &[
+            "Exception",
+            "Echo",
+            "Flush",
+            "Info",
+            "SetOption",
+            "InitChain",
+            "Query",
+            "BeginBlock",
+            "CheckTx",
+            "DeliverTx",
+            "EndBlock",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+        ],
+    },
+    DomainOneof {
+        version: "v0_34",
+        path: "crypto::public_key::Sum",
+        variants: &["Ed25519", "Secp256k1"],
+    },
+    DomainOneof {
+        version: "v0_34",
+        path: "types::evidence::Sum",
+        variants: &["DuplicateVoteEvidence", "LightClientAttackEvidence"],
+    },
+    DomainOneof {
+        version: "v0_37",
+        path: "abci::request::Value",
+        variants: &[
+            "Echo",
+            "Flush",
+            "Info",
+            "InitChain",
+            "Query",
+            "BeginBlock",
+            "CheckTx",
+            "DeliverTx",
+            "EndBlock",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+            "PrepareProposal",
+            "ProcessProposal",
+        ],
+    },
+    DomainOneof {
+        version: "v0_37",
+        path: "abci::response::Value",
+        variants: &[
+            "Exception",
+            "Echo",
+            "Flush",
+            "Info",
+            "InitChain",
+            "Query",
+            "BeginBlock",
+            "CheckTx",
+            "DeliverTx",
+            "EndBlock",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+            "PrepareProposal",
+            "ProcessProposal",
+        ],
+    },
+    DomainOneof {
+        version: "v0_37",
+        path: "crypto::public_key::Sum",
+        variants: &["Ed25519", "Secp256k1"],
+    },
+    DomainOneof {
+        version: "v0_37",
+        path: "types::evidence::Sum",
+        variants: &["DuplicateVoteEvidence", "LightClientAttackEvidence"],
+    },
+    DomainOneof {
+        version: "v0_38",
+        path: "abci::request::Value",
+        variants: &[
+            "Echo",
+            "Flush",
+            "Info",
+            "InitChain",
+            "Query",
+            "CheckTx",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+            "PrepareProposal",
+            "ProcessProposal",
+            "ExtendVote",
+            "VerifyVoteExtension",
+            "FinalizeBlock",
+        ],
+    },
+    DomainOneof {
+        version: "v0_38",
+        path: "abci::response::Value",
+        variants: &[
+            "Exception",
+            "Echo",
+            "Flush",
+            "Info",
+            "InitChain",
+            "Query",
+            "CheckTx",
+            "Commit",
+            "ListSnapshots",
+            "OfferSnapshot",
+            "LoadSnapshotChunk",
+            "ApplySnapshotChunk",
+            "PrepareProposal",
+            "ProcessProposal",
+            "ExtendVote",
+            "VerifyVoteExtension",
+            "FinalizeBlock",
+        ],
+    },
+    DomainOneof {
+        version: "v0_38",
+        path: "crypto::public_key::Sum",
+        variants: &["Ed25519", "Secp256k1"],
+    },
+    DomainOneof {
+        version: "v0_38",
+        path: "types::evidence::Sum",
+        variants: &["DuplicateVoteEvidence", "LightClientAttackEvidence"],
+    },
+];
+
+/// Pairs of consecutive versions whose messages get conversions generated,
+/// in `proto/src/tendermint/convert.rs`.
+pub const CONVERTED_VERSIONS: &[(&str, &str)] = &[("v0_34", "v0_37"), ("v0_37", "v0_38")];
+
+/// Packages whose messages get conversions generated between consecutive
+/// versions, in `proto/src/tendermint/convert.rs`.
+pub const CONVERTED_PACKAGES: &[&str] = &[
+    "tendermint.abci",
+    "tendermint.crypto",
+    "tendermint.types",
+    "tendermint.version",
+];
+
+/// File name of the generated conversions, in the module folder.
+pub const CONVERSIONS: &str = "convert.rs";
+
+/// A definition of a version and the corresponding one of the next version,
+/// by fully qualified name. A `oneof` is named after its message, e.g.
+/// `tendermint.abci.Request.value`.
+pub struct Correspondence {
+    /// Identifiers of the versions.
+    pub versions: (&'static str, &'static str),
+    /// Names of the definitions in each version.
+    pub names: (&'static str, &'static str),
+}
+
+/// Messages and enums renamed between consecutive versions. The conversions
+/// of the renamed messages are generated as long as their fields correspond.
+pub const RENAMED_DEFINITIONS: &[Correspondence] = &[
+    Correspondence {
+        versions: ("v0_34", "v0_37"),
+        names: (
+            "tendermint.abci.BlockParams",
+            "tendermint.types.BlockParams",
+        ),
+    },
+    Correspondence {
+        versions: ("v0_34", "v0_37"),
+        names: (
+            "tendermint.abci.ConsensusParams",
+            "tendermint.types.ConsensusParams",
+        ),
+    },
+    Correspondence {
+        versions: ("v0_34", "v0_37"),
+        names: ("tendermint.abci.Evidence", "tendermint.abci.Misbehavior"),
+    },
+    Correspondence {
+        versions: ("v0_34", "v0_37"),
+        names: (
+            "tendermint.abci.EvidenceType",
+            "tendermint.abci.MisbehaviorType",
+        ),
+    },
+    Correspondence {
+        versions: ("v0_34", "v0_37"),
+        names: (
+            "tendermint.abci.LastCommitInfo",
+            "tendermint.abci.CommitInfo",
+        ),
+    },
+    Correspondence {
+        versions: ("v0_37", "v0_38"),
+        names: (
+            "tendermint.abci.ResponseDeliverTx",
+            "tendermint.abci.ExecTxResult",
+        ),
+    },
+];
+
+/// A conversion between consecutive versions which is written by hand, in
+/// `proto/src/convert.rs`, as the definitions differ.
+pub struct ManualConversion {
+    pub definitions: Correspondence,
+    /// Whether the conversion to the next version, and back, is fallible.
+    pub fallible: (bool, bool),
+}
+
+/// The conversions written by hand. The generation fails if the definitions
+/// of a message differ between consecutive versions and its conversion is
+/// not listed here.
+pub const MANUAL_CONVERSIONS: &[ManualConversion] = &[
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: (
+                "tendermint.abci.EventAttribute",
+                "tendermint.abci.EventAttribute",
+            ),
+        },
+        fallible: (true, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: (
+                "tendermint.abci.Request.value",
+                "tendermint.abci.Request.value",
+            ),
+        },
+        fallible: (true, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: ("tendermint.abci.RequestInfo", "tendermint.abci.RequestInfo"),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: (
+                "tendermint.abci.Response.value",
+                "tendermint.abci.Response.value",
+            ),
+        },
+        fallible: (true, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: (
+                "tendermint.types.BlockParams",
+                "tendermint.types.BlockParams",
+            ),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_34", "v0_37"),
+            names: (
+                "tendermint.types.VersionParams",
+                "tendermint.types.VersionParams",
+            ),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.abci.ExtendedVoteInfo",
+                "tendermint.abci.ExtendedVoteInfo",
+            ),
+        },
+        fallible: (false, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.abci.Request.value",
+                "tendermint.abci.Request.value",
+            ),
+        },
+        fallible: (true, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.abci.Response.value",
+                "tendermint.abci.Response.value",
+            ),
+        },
+        fallible: (true, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.abci.ResponseCheckTx",
+                "tendermint.abci.ResponseCheckTx",
+            ),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.abci.ResponseCommit",
+                "tendermint.abci.ResponseCommit",
+            ),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: ("tendermint.abci.VoteInfo", "tendermint.abci.VoteInfo"),
+        },
+        fallible: (false, false),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: (
+                "tendermint.types.ConsensusParams",
+                "tendermint.types.ConsensusParams",
+            ),
+        },
+        fallible: (false, true),
+    },
+    ManualConversion {
+        definitions: Correspondence {
+            versions: ("v0_37", "v0_38"),
+            names: ("tendermint.types.Vote", "tendermint.types.Vote"),
+        },
+        fallible: (false, true),
+    },
+];
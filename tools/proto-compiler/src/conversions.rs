@@ -0,0 +1,557 @@
+//! Generation of the conversions between the messages of consecutive
+//! Tendermint versions, for the messages whose definitions correspond.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto, FileDescriptorSet,
+};
+
+use crate::{
+    compat::Definitions,
+    constants::{
+        Correspondence, TendermintVersion, CONVERTED_PACKAGES, CONVERTED_VERSIONS,
+        MANUAL_CONVERSIONS, RENAMED_DEFINITIONS,
+    },
+    functions::{write_file, Formatter, WriteMode},
+};
+
+/// The names of a definition of a version and of the corresponding one of
+/// the next version.
+type Pair = (String, String);
+
+/// Generate the conversions between the messages of each version and the
+/// next one, for the pairs of versions listed in [`CONVERTED_VERSIONS`],
+/// except the ones listed in [`MANUAL_CONVERSIONS`].
+///
+/// This panics if the definitions of a message differ between two versions
+/// and its conversion is not listed in [`MANUAL_CONVERSIONS`].
+pub fn generate_conversions(
+    versions: &[(&TendermintVersion, FileDescriptorSet)],
+    target: &Path,
+    formatter: &Formatter,
+    mode: WriteMode,
+) {
+    let mut content = String::from(
+        "// Conversions between the messages of consecutive Tendermint versions, generated\n\
+         // for the messages whose definitions correspond. The others are converted by hand.\n",
+    );
+    for pair in versions.windows(2) {
+        let ((old_version, old), (new_version, new)) = (&pair[0], &pair[1]);
+        if !CONVERTED_VERSIONS.contains(&(old_version.ident, new_version.ident)) {
+            continue;
+        }
+        let conversions = Conversions::new(
+            (old_version.ident, new_version.ident),
+            Definitions::of(old),
+            Definitions::of(new),
+        );
+        content.push_str(&conversions.generate());
+    }
+    write_file(target, &formatter.format(&content), mode);
+}
+
+/// The conversions between the definitions of two consecutive versions.
+struct Conversions<'a> {
+    versions: (&'static str, &'static str),
+    old: Definitions<'a>,
+    new: Definitions<'a>,
+    /// The enums whose values correspond.
+    enums: BTreeSet<Pair>,
+    /// The messages and `oneof`s converted, with whether their conversion is
+    /// fallible, to the next version and back.
+    converted: BTreeMap<Pair, (bool, bool)>,
+    /// The conversions written by hand.
+    manual: BTreeMap<Pair, (bool, bool)>,
+}
+
+impl<'a> Conversions<'a> {
+    fn new(
+        versions: (&'static str, &'static str),
+        old: Definitions<'a>,
+        new: Definitions<'a>,
+    ) -> Self {
+        let of_versions = |definitions: &Correspondence| definitions.versions == versions;
+        let renamed = RENAMED_DEFINITIONS
+            .iter()
+            .filter(|definitions| of_versions(definitions))
+            .map(|definitions| pair(definitions.names))
+            .collect::<Vec<_>>();
+        let manual = MANUAL_CONVERSIONS
+            .iter()
+            .filter(|conversion| of_versions(&conversion.definitions))
+            .map(|conversion| (pair(conversion.definitions.names), conversion.fallible))
+            .collect();
+
+        let mut messages = old
+            .messages
+            .keys()
+            .filter(|name| is_converted(name) && new.messages.contains_key(*name))
+            .map(|name| (name.clone(), name.clone()))
+            .collect::<BTreeSet<_>>();
+        messages.extend(
+            renamed
+                .iter()
+                .filter(|(old_name, new_name)| {
+                    old.messages.contains_key(old_name) && new.messages.contains_key(new_name)
+                })
+                .cloned(),
+        );
+        let mut candidates = messages.clone();
+        for (old_name, new_name) in &messages {
+            let new_oneofs = oneofs(new.messages[new_name]);
+            for oneof in oneofs(old.messages[old_name]) {
+                if new_oneofs.contains(&oneof) {
+                    candidates
+                        .insert((format!("{old_name}.{oneof}"), format!("{new_name}.{oneof}")));
+                }
+            }
+        }
+
+        let mut enums = old
+            .enums
+            .keys()
+            .filter(|name| new.enums.contains_key(*name))
+            .map(|name| (name.clone(), name.clone()))
+            .collect::<BTreeSet<_>>();
+        enums.extend(renamed.iter().cloned());
+        enums.retain(|(old_name, new_name)| {
+            match (old.enums.get(old_name), new.enums.get(new_name)) {
+                (Some(old_enum), Some(new_enum)) => old_enum.value == new_enum.value,
+                _ => false,
+            }
+        });
+
+        let mut conversions = Self {
+            versions,
+            old,
+            new,
+            enums,
+            converted: BTreeMap::new(),
+            manual,
+        };
+        conversions.convert(candidates);
+        conversions
+    }
+
+    /// Keep the candidates whose definitions correspond, the definitions
+    /// they refer to being converted too, and work out which conversions
+    /// are fallible.
+    fn convert(&mut self, candidates: BTreeSet<Pair>) {
+        let mut converted = candidates.clone();
+        converted.extend(self.manual.keys().cloned());
+        loop {
+            let differing = converted
+                .iter()
+                .filter(|pair| {
+                    !self.manual.contains_key(*pair) && !self.corresponds(pair, &converted)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if differing.is_empty() {
+                break;
+            }
+            for pair in differing {
+                converted.remove(&pair);
+            }
+        }
+
+        let differing = candidates
+            .difference(&converted)
+            .map(|(old_name, new_name)| format!("{old_name} and {new_name}"))
+            .collect::<Vec<_>>();
+        if !differing.is_empty() {
+            panic!(
+                "[error] => The definitions of {} differ between {} and {}: convert them by hand in proto/src/convert.rs, and list them in MANUAL_CONVERSIONS",
+                differing.join(", "),
+                self.versions.0,
+                self.versions.1,
+            );
+        }
+
+        self.converted = converted
+            .into_iter()
+            .map(|pair| {
+                let fallible = self.manual.get(&pair).copied().unwrap_or_default();
+                (pair, fallible)
+            })
+            .collect();
+        loop {
+            let mut changed = false;
+            let pairs = self.converted.keys().cloned().collect::<Vec<_>>();
+            for pair in pairs.iter().filter(|pair| !self.manual.contains_key(*pair)) {
+                let (forwards, backwards) = self
+                    .references(pair)
+                    .iter()
+                    .filter_map(|reference| self.converted.get(reference))
+                    .fold((false, false), |(f, b), (forwards, backwards)| {
+                        (f || *forwards, b || *backwards)
+                    });
+                let fallible = self.converted.get_mut(pair).unwrap();
+                if (forwards && !fallible.0) || (backwards && !fallible.1) {
+                    fallible.0 |= forwards;
+                    fallible.1 |= backwards;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Whether the definitions of a message or `oneof` correspond, given the
+    /// definitions converted.
+    fn corresponds(&self, (old_name, new_name): &Pair, converted: &BTreeSet<Pair>) -> bool {
+        let fields_correspond =
+            |old_fields: &[&FieldDescriptorProto], new_fields: &[&FieldDescriptorProto]| {
+                old_fields.len() == new_fields.len()
+                    && old_fields
+                        .iter()
+                        .zip(new_fields)
+                        .all(|(old_field, new_field)| {
+                            self.field_corresponds(old_field, new_field, converted)
+                        })
+            };
+        match (
+            self.old.messages.get(old_name),
+            self.new.messages.get(new_name),
+        ) {
+            (Some(old_message), Some(new_message)) => {
+                let (old_oneofs, new_oneofs) = (oneofs(old_message), oneofs(new_message));
+                fields_correspond(&plain_fields(old_message), &plain_fields(new_message))
+                    && old_oneofs == new_oneofs
+                    && old_oneofs.iter().all(|oneof| {
+                        converted.contains(&(
+                            format!("{old_name}.{oneof}"),
+                            format!("{new_name}.{oneof}"),
+                        ))
+                    })
+            },
+            _ => {
+                let (Some(old_variants), Some(new_variants)) = (
+                    oneof_fields(&self.old, old_name),
+                    oneof_fields(&self.new, new_name),
+                ) else {
+                    return false;
+                };
+                fields_correspond(&old_variants, &new_variants)
+            },
+        }
+    }
+
+    fn field_corresponds(
+        &self,
+        old_field: &FieldDescriptorProto,
+        new_field: &FieldDescriptorProto,
+        converted: &BTreeSet<Pair>,
+    ) -> bool {
+        let references = (reference(old_field), reference(new_field));
+        old_field.name() == new_field.name()
+            && old_field.number() == new_field.number()
+            && old_field.label() == new_field.label()
+            && old_field.r#type() == new_field.r#type()
+            && match old_field.r#type() {
+                Type::Message if !is_converted(&references.0) => references.0 == references.1,
+                Type::Message => converted.contains(&references),
+                Type::Enum => self.enums.contains(&references),
+                _ => true,
+            }
+    }
+
+    /// The converted messages and `oneof`s a message or `oneof` refers to.
+    fn references(&self, (old_name, new_name): &Pair) -> Vec<Pair> {
+        let message_fields = |old_fields: Vec<&FieldDescriptorProto>,
+                              new_fields: Vec<&FieldDescriptorProto>| {
+            old_fields
+                .into_iter()
+                .zip(new_fields)
+                .filter(|(old_field, _)| old_field.r#type() == Type::Message)
+                .map(|(old_field, new_field)| (reference(old_field), reference(new_field)))
+                .collect::<Vec<_>>()
+        };
+        match (
+            self.old.messages.get(old_name),
+            self.new.messages.get(new_name),
+        ) {
+            (Some(old_message), Some(new_message)) => {
+                let mut references =
+                    message_fields(plain_fields(old_message), plain_fields(new_message));
+                references.extend(
+                    oneofs(old_message).iter().map(|oneof| {
+                        (format!("{old_name}.{oneof}"), format!("{new_name}.{oneof}"))
+                    }),
+                );
+                references
+            },
+            _ => message_fields(
+                oneof_fields(&self.old, old_name).unwrap_or_default(),
+                oneof_fields(&self.new, new_name).unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// The invocations of the conversion macros, to the next version and
+    /// back.
+    fn generate(&self) -> String {
+        let mut content = String::new();
+        for backwards in [false, true] {
+            for (pair, fallible) in &self.converted {
+                if self.manual.contains_key(pair) {
+                    continue;
+                }
+                let ((from_version, from), (to_version, to), fallible) = if backwards {
+                    (
+                        (self.versions.1, &pair.1),
+                        (self.versions.0, &pair.0),
+                        fallible.1,
+                    )
+                } else {
+                    (
+                        (self.versions.0, &pair.0),
+                        (self.versions.1, &pair.1),
+                        fallible.0,
+                    )
+                };
+                let fallible_reference = |reference: &Pair| match self.converted.get(reference) {
+                    Some((forwards, back)) => *if backwards { back } else { forwards },
+                    None => false,
+                };
+
+                let mut items = Vec::new();
+                let macro_name = match self.old.messages.get(&pair.0) {
+                    Some(old_message) => {
+                        let new_message = self.new.messages[&pair.1];
+                        for (old_field, new_field) in plain_fields(old_message)
+                            .into_iter()
+                            .zip(plain_fields(new_message))
+                        {
+                            let reference = (reference(old_field), reference(new_field));
+                            let mode = match old_field.r#type() {
+                                Type::Message if is_converted(&reference.0) => {
+                                    if old_field.label() == Label::Repeated {
+                                        "vec"
+                                    } else {
+                                        "opt"
+                                    }
+                                },
+                                _ => "",
+                            };
+                            items.push(item(
+                                &field_name(old_field.name()),
+                                mode,
+                                fallible_reference(&reference),
+                            ));
+                        }
+                        for oneof in oneofs(old_message) {
+                            let reference =
+                                (format!("{}.{oneof}", pair.0), format!("{}.{oneof}", pair.1));
+                            items.push(item(
+                                &field_name(&oneof),
+                                "opt",
+                                fallible_reference(&reference),
+                            ));
+                        }
+                        "convert_message"
+                    },
+                    None => {
+                        let old_variants = oneof_fields(&self.old, &pair.0).unwrap();
+                        let new_variants = oneof_fields(&self.new, &pair.1).unwrap();
+                        for (old_field, new_field) in old_variants.into_iter().zip(new_variants) {
+                            let reference = (reference(old_field), reference(new_field));
+                            let mode = match old_field.r#type() {
+                                Type::Message if is_converted(&reference.0) => "into",
+                                _ => "",
+                            };
+                            items.push(item(
+                                &upper_camel(old_field.name()),
+                                mode,
+                                fallible_reference(&reference),
+                            ));
+                        }
+                        "convert_oneof"
+                    },
+                };
+
+                let trait_name = if fallible { "TryFrom" } else { "From" };
+                content.push_str(&format!(
+                    "\n{macro_name}!({trait_name}, {from_version}::{} => {to_version}::{} {{",
+                    rust_path(from),
+                    rust_path(to),
+                ));
+                if items.is_empty() {
+                    content.push_str("});\n");
+                    continue;
+                }
+                for item in items {
+                    content.push_str(&format!("\n    {item},"));
+                }
+                content.push_str("\n});\n");
+            }
+        }
+        content
+    }
+}
+
+fn pair((old_name, new_name): (&str, &str)) -> Pair {
+    (old_name.to_owned(), new_name.to_owned())
+}
+
+/// Whether the definition of the given name is in one of the
+/// [`CONVERTED_PACKAGES`].
+fn is_converted(name: &str) -> bool {
+    package(name).is_some()
+}
+
+fn package(name: &str) -> Option<&'static str> {
+    CONVERTED_PACKAGES.iter().copied().find(|package| {
+        name.strip_prefix(package)
+            .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// The name of the message or enum a field refers to, without the leading
+/// dot.
+fn reference(field: &FieldDescriptorProto) -> String {
+    field.type_name().trim_start_matches('.').to_owned()
+}
+
+/// The fields of a message which are not part of a `oneof`. The optional
+/// fields of proto3 are part of synthetic `oneof`s, which prost ignores.
+fn plain_fields(message: &DescriptorProto) -> Vec<&FieldDescriptorProto> {
+    message
+        .field
+        .iter()
+        .filter(|field| field.oneof_index.is_none() || field.proto3_optional())
+        .collect()
+}
+
+/// The names of the `oneof`s of a message, except the synthetic ones.
+fn oneofs(message: &DescriptorProto) -> Vec<String> {
+    message
+        .oneof_decl
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| {
+            message
+                .field
+                .iter()
+                .any(|field| field.oneof_index == Some(*index as i32) && !field.proto3_optional())
+        })
+        .map(|(_, oneof)| oneof.name().to_owned())
+        .collect()
+}
+
+/// The fields of the `oneof` of the given name, if it exists.
+fn oneof_fields<'a>(
+    definitions: &Definitions<'a>,
+    name: &str,
+) -> Option<Vec<&'a FieldDescriptorProto>> {
+    let (message_name, oneof) = name.rsplit_once('.')?;
+    let message = definitions.messages.get(message_name)?;
+    let index = message
+        .oneof_decl
+        .iter()
+        .position(|decl| decl.name() == oneof)?;
+    Some(
+        message
+            .field
+            .iter()
+            .filter(|field| field.oneof_index == Some(index as i32))
+            .collect(),
+    )
+}
+
+/// A field or variant of a macro invocation, with the mode of its
+/// conversion.
+fn item(name: &str, mode: &str, fallible: bool) -> String {
+    match (mode, fallible) {
+        ("", _) => name.to_owned(),
+        (mode, false) => format!("{name}: {mode}"),
+        (mode, true) => format!("{name}: try_{mode}"),
+    }
+}
+
+/// The path of the Rust item of a message or `oneof`, relative to the
+/// version module, as generated by prost, e.g. `abci::request::Value` for
+/// `tendermint.abci.Request.value`.
+fn rust_path(name: &str) -> String {
+    let package = package(name).unwrap();
+    let mut path = package
+        .trim_start_matches("tendermint.")
+        .split('.')
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    let parts = name[package.len() + 1..].split('.').collect::<Vec<_>>();
+    let (last, parents) = parts.split_last().unwrap();
+    path.extend(parents.iter().map(|parent| snake(parent)));
+    path.push(upper_camel(last));
+    path.join("::")
+}
+
+/// The name of a field as generated by prost, which escapes keywords.
+fn field_name(name: &str) -> String {
+    let name = snake(name);
+    match name.as_str() {
+        "as" | "break" | "const" | "continue" | "else" | "enum" | "false" | "fn" | "for" | "if"
+        | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut" | "pub" | "ref"
+        | "return" | "static" | "struct" | "trait" | "true" | "type" | "unsafe" | "use"
+        | "where" | "while" => format!("r#{name}"),
+        _ => name,
+    }
+}
+
+/// The words of an identifier, split on underscores and case changes, as
+/// by prost: `BlockIDFlag` consists of `Block`, `ID` and `Flag`.
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let boundary = c.is_uppercase()
+            && i > 0
+            && (chars[i - 1].is_lowercase()
+                || chars[i - 1].is_ascii_digit()
+                || (chars[i - 1].is_uppercase()
+                    && chars.get(i + 1).is_some_and(|next| next.is_lowercase())));
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn snake(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn upper_camel(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_uppercase();
+            first
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect::<String>()
+        })
+        .collect()
+}
@@ -1,8 +1,11 @@
 use std::{
     collections::BTreeSet,
-    fs::{copy, create_dir_all, remove_dir_all, File},
+    env::var,
+    fs::{create_dir_all, read_to_string, remove_dir_all, remove_file, write},
     io::Write,
     path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
 };
 
 use git2::{
@@ -10,9 +13,10 @@ use git2::{
     AutotagOption, Commit, FetchOptions, Oid, Reference, Repository,
 };
 use subtle_encoding::hex;
+use tempfile::{tempdir, TempDir};
 use walkdir::WalkDir;
 
-use crate::constants::TendermintVersion;
+use crate::constants::{TendermintVersion, DEFAULT_VERSION, DOMAIN_ONEOFS, RUSTFMT_CONFIG};
 
 /// Clone or open+fetch a repository and check out a specific commitish
 /// In case of an existing repository, the origin remote will be set to `url`.
@@ -158,44 +162,126 @@ fn find_reference_or_commit<'a>(
     (Some(reference), commit)
 }
 
-/// Copy generated files to target folder
-pub fn copy_files(src_dir: &Path, target_dir: &Path) {
-    // Remove old compiled files
-    remove_dir_all(target_dir).unwrap_or_default();
-    create_dir_all(target_dir).unwrap();
+/// How the generated files are written to the target folders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Remove the previously generated files and write all of them anew.
+    Overwrite,
+    /// Only write the files whose content changed, preserving the
+    /// modification times of the others, and remove the stale ones.
+    PreserveUnchanged,
+}
+
+/// Formats the generated code with rustfmt and the pinned
+/// [`RUSTFMT_CONFIG`], so that regenerating the code does not produce
+/// formatting noise. The `RUSTFMT` environment variable overrides the path of
+/// the rustfmt binary.
+pub struct Formatter {
+    config_dir: TempDir,
+}
+
+impl Formatter {
+    pub fn new() -> Self {
+        let config_dir = tempdir().unwrap();
+        write(config_dir.path().join("rustfmt.toml"), RUSTFMT_CONFIG)
+            .expect("rustfmt configuration write failed");
+        Self { config_dir }
+    }
+
+    /// Format the given source code. This panics if rustfmt fails.
+    pub fn format(&self, source: &str) -> String {
+        let mut child = Command::new(var("RUSTFMT").unwrap_or_else(|_| "rustfmt".to_string()))
+            .arg("--config-path")
+            .arg(self.config_dir.path().join("rustfmt.toml"))
+            .arg("--emit")
+            .arg("stdout")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("[error] => rustfmt is required to format the generated code");
+
+        // Write the source from another thread, rustfmt not reading all of its
+        // input before writing its output.
+        let mut stdin = child.stdin.take().unwrap();
+        let source = source.to_owned();
+        let writer = thread::spawn(move || stdin.write_all(source.as_bytes()));
+        let output = child.wait_with_output().unwrap();
+        writer.join().unwrap().unwrap();
+
+        if !output.status.success() {
+            panic!("[error] => rustfmt failed to format the generated code");
+        }
+        String::from_utf8(output.stdout).unwrap()
+    }
+}
 
-    // Copy new compiled files (prost does not use folder structures)
-    let errors = WalkDir::new(src_dir)
-        .contents_first(true)
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write the given content to a file, unless the file already has this
+/// content and the modification times are preserved. Returns whether the
+/// file was written.
+pub fn write_file(path: &Path, content: &str, mode: WriteMode) -> bool {
+    if mode == WriteMode::PreserveUnchanged
+        && matches!(read_to_string(path), Ok(existing) if existing == content)
+    {
+        return false;
+    }
+    write(path, content).unwrap_or_else(|e| panic!("[error] => Failed to write {path:?}: {e}"));
+    true
+}
+
+/// The names of the generated files in the given folder, in order.
+fn generated_files(dir: &Path) -> BTreeSet<String> {
+    WalkDir::new(dir)
+        .max_depth(1)
         .into_iter()
-        .filter_entry(|e| {
-            e.file_type().is_file()
-                && e.file_name()
-                    .to_str()
-                    .map(|name| name.starts_with("tendermint."))
-                    .unwrap_or(false)
-        })
-        .map(|res| {
-            let e = res?;
-            copy(e.path(), target_dir.join(e.file_name()))
-        })
-        .filter_map(|res| res.err())
-        .collect::<Vec<_>>();
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.file_name().to_str().map(ToOwned::to_owned))
+        .filter(|name| name.starts_with("tendermint.") && name.ends_with(".rs"))
+        .collect()
+}
 
-    if !errors.is_empty() {
-        for e in errors {
-            println!("[error] => Error while copying compiled file: {e}");
+/// Copy the generated files to the target folder, formatted, in order.
+pub fn copy_files(src_dir: &Path, target_dir: &Path, formatter: &Formatter, mode: WriteMode) {
+    if mode == WriteMode::Overwrite {
+        // Remove old compiled files
+        remove_dir_all(target_dir).unwrap_or_default();
+    }
+    create_dir_all(target_dir).unwrap();
+
+    // prost does not use folder structures
+    let file_names = generated_files(src_dir);
+    let mut written = 0;
+    for file_name in &file_names {
+        let source = read_to_string(src_dir.join(file_name))
+            .unwrap_or_else(|e| panic!("[error] => Failed to read {file_name}: {e}"));
+        if write_file(&target_dir.join(file_name), &formatter.format(&source), mode) {
+            written += 1;
         }
-        panic!("[error] => Aborted.");
     }
+    for stale in generated_files(target_dir).difference(&file_names) {
+        println!("[info] => Removing stale {stale}");
+        remove_file(target_dir.join(stale)).unwrap();
+    }
+    println!(
+        "[info] => Wrote {written} of {} generated files",
+        file_names.len()
+    );
 }
 
-/// Walk through the list of directories and gather all *.proto files
+/// Walk through the list of directories and gather all *.proto files, in
+/// order, so that they are compiled in the same order on every platform.
 pub fn find_proto_files(proto_paths: Vec<PathBuf>) -> Vec<PathBuf> {
-    let mut protos: Vec<PathBuf> = vec![];
+    let mut protos = BTreeSet::new();
     for proto_path in &proto_paths {
-        protos.append(
-            &mut WalkDir::new(proto_path)
+        protos.extend(
+            WalkDir::new(proto_path)
+                .sort_by_file_name()
                 .into_iter()
                 .filter_map(|e| e.ok())
                 .filter(|e| {
@@ -203,28 +289,23 @@ pub fn find_proto_files(proto_paths: Vec<PathBuf>) -> Vec<PathBuf> {
                         && e.path().extension().is_some()
                         && e.path().extension().unwrap() == "proto"
                 })
-                .map(|e| e.into_path())
-                .collect(),
+                .map(|e| e.into_path()),
         );
     }
-    protos
+    Vec::from_iter(protos)
 }
 
 /// Create a module including generated content for the specified
 /// Tendermint source version.
-pub fn generate_tendermint_mod(prost_dir: &Path, version: &TendermintVersion, target_dir: &Path) {
+pub fn generate_tendermint_mod(
+    prost_dir: &Path,
+    version: &TendermintVersion,
+    target_dir: &Path,
+    formatter: &Formatter,
+    mode: WriteMode,
+) {
     create_dir_all(target_dir).unwrap();
-    let file_names = WalkDir::new(prost_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.file_type().is_file()
-                && e.file_name().to_str().unwrap().starts_with("tendermint.")
-                && e.file_name().to_str().unwrap().ends_with(".rs")
-        })
-        .map(|d| d.file_name().to_str().unwrap().to_owned())
-        .collect::<BTreeSet<_>>();
-    let file_names = Vec::from_iter(file_names);
+    let file_names = generated_files(prost_dir);
 
     let mut content =
         String::from("//! Tendermint-proto auto-generated sub-modules for Tendermint\n");
@@ -266,24 +347,63 @@ pub fn generate_tendermint_mod(prost_dir: &Path, version: &TendermintVersion, ta
         "{}\npub mod meta {{\n{}pub const REPOSITORY: &str = \"{}\";\n{}pub const COMMITISH: &str = \"{}\";\n}}\n",
         content,
         tab,
-        crate::constants::TENDERMINT_REPO,
+        version.repo,
         tab,
         &version.commitish,
     );
 
+    content = format!("{}{}", content, generate_exhaustiveness_mod(version));
+
     let tendermint_mod_target = target_dir.join(format!("{}.rs", version.ident));
-    let mut file =
-        File::create(tendermint_mod_target).expect("tendermint module file create failed");
-    file.write_all(content.as_bytes())
-        .expect("tendermint module file write failed");
+    write_file(&tendermint_mod_target, &formatter.format(&content), mode);
+}
+
+/// Generates exhaustive matches on the `oneof` enums listed in
+/// [`DOMAIN_ONEOFS`] for the given version. The matches have no wildcard arm,
+/// so a variant added upstream fails compilation of the generated module.
+fn generate_exhaustiveness_mod(version: &TendermintVersion) -> String {
+    let oneofs = DOMAIN_ONEOFS
+        .iter()
+        .filter(|oneof| oneof.version == version.ident)
+        .collect::<Vec<_>>();
+    if oneofs.is_empty() {
+        return String::new();
+    }
+
+    let mut content = String::from(
+        "\n/// Exhaustive matches on the `oneof` enums mapped to domain types. A variant\n\
+         /// added upstream fails compilation here until the mapping handles it.\n\
+         #[allow(dead_code)]\n\
+         mod exhaustiveness {",
+    );
+    for (i, oneof) in oneofs.iter().enumerate() {
+        let fn_name = oneof.path.replace("::", "_").to_lowercase();
+        let enum_name = oneof.path.rsplit("::").next().unwrap();
+        if i > 0 {
+            content.push('\n');
+        }
+        content.push_str(&format!(
+            "\n    fn {fn_name}(value: &super::{path}) {{\n        use super::{path};\n\n        match value {{\n",
+            path = oneof.path,
+        ));
+        for variant in oneof.variants {
+            content.push_str(&format!("            {enum_name}::{variant}(_) => {{}},\n"));
+        }
+        content.push_str("        }\n    }");
+    }
+    content.push_str("\n}\n");
+    content
 }
 
-pub fn generate_tendermint_lib(versions: &[TendermintVersion], tendermint_lib_target: &Path) {
-    let mut file =
-        File::create(tendermint_lib_target).expect("tendermint library file create failed");
+pub fn generate_tendermint_lib(
+    versions: &[TendermintVersion],
+    tendermint_lib_target: &Path,
+    mode: WriteMode,
+) {
+    let mut content = String::new();
     for version in versions {
-        writeln!(&mut file, "pub mod {};", version.ident).unwrap();
+        content.push_str(&format!("pub mod {};\n", version.ident));
     }
-    let last_version = versions.last().unwrap();
-    writeln!(&mut file, "pub use {}::*;", last_version.ident).unwrap();
+    content.push_str(&format!("pub use {DEFAULT_VERSION}::*;\n"));
+    write_file(tendermint_lib_target, &content, mode);
 }
@@ -1,5 +1,6 @@
 use std::{
-    env::var,
+    env::{args, var},
+    fs,
     path::{Path, PathBuf},
     process,
 };
@@ -9,11 +10,22 @@ use tempfile::tempdir;
 mod functions;
 use functions::{
     copy_files, find_proto_files, generate_tendermint_lib, generate_tendermint_mod, get_commitish,
+    Formatter, WriteMode,
 };
 
+mod compat;
+use compat::{breaking_changes, previous_commitish, read_descriptors, CompatibilityReport};
+
+mod conversions;
+use conversions::generate_conversions;
+
+mod services;
+use services::ServiceDescriptors;
+
 mod constants;
 use constants::{
-    CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_REPO, TENDERMINT_VERSIONS,
+    COMPAT_REPORT, CONVERSIONS, CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, DESCRIPTORS,
+    PREVIOUS_DESCRIPTORS, TENDERMINT_VERSIONS,
 };
 
 fn main() {
@@ -28,21 +40,27 @@ fn main() {
             .to_string()
     }));
 
-    for version in TENDERMINT_VERSIONS {
-        println!(
-            "[info] => Fetching {TENDERMINT_REPO} at {} into {tendermint_dir:?}",
-            &version.commitish,
-        );
-        get_commitish(&tendermint_dir, TENDERMINT_REPO, &version.commitish); // This panics if it fails.
+    // Breaking changes of the proto files since the previously pinned
+    // commitish block the generation, unless explicitly allowed.
+    let allow_breaking = args().skip(1).any(|arg| arg == "--allow-breaking");
+    // Unchanged files are rewritten too, updating their modification times,
+    // unless these are explicitly preserved.
+    let write_mode = if args().skip(1).any(|arg| arg == "--preserve-mtimes") {
+        WriteMode::PreserveUnchanged
+    } else {
+        WriteMode::Overwrite
+    };
+    let formatter = Formatter::new();
+    let report_path = root.join("..").join("target").join(COMPAT_REPORT);
+    let mut reports = Vec::new();
+    let mut descriptors = Vec::new();
 
-        let proto_paths = vec![tendermint_dir.join("proto")];
-        let proto_includes_paths = vec![
-            tendermint_dir.join("proto"),
-            tendermint_dir.join("third_party").join("proto"),
-        ];
-        // List available proto files
-        let protos = find_proto_files(proto_paths);
+    let proto_includes_paths = vec![
+        tendermint_dir.join("proto"),
+        tendermint_dir.join("third_party").join("proto"),
+    ];
 
+    for version in TENDERMINT_VERSIONS {
         let ver_target_dir = target_dir.join("prost").join(&version.ident);
         let ver_module_dir = target_dir.join("tendermint");
 
@@ -51,11 +69,56 @@ fn main() {
             .or_else(|_| tempdir().map(|d| d.into_path()))
             .unwrap();
 
+        let previous = previous_commitish(&ver_module_dir.join(format!("{}.rs", version.ident)))
+            .filter(|commitish| commitish != version.commitish)
+            .map(|commitish| {
+                println!(
+                    "[info] => Fetching previously pinned {} at {commitish} into {tendermint_dir:?}",
+                    version.repo,
+                );
+                get_commitish(&tendermint_dir, version.repo, &commitish); // This panics if it fails.
+                let descriptors_path = out_dir.join(PREVIOUS_DESCRIPTORS);
+                println!("[info] => Describing previous proto files.");
+                let previous_out_dir = tempdir().unwrap();
+                let mut pb = prost_build::Config::new();
+                pb.out_dir(previous_out_dir.path())
+                    .file_descriptor_set_path(&descriptors_path);
+                let protos = find_proto_files(vec![tendermint_dir.join("proto")]);
+                if let Err(e) = pb.compile_protos(&protos, &proto_includes_paths) {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+                (commitish, read_descriptors(&descriptors_path))
+            });
+
+        println!(
+            "[info] => Fetching {} at {} into {tendermint_dir:?}",
+            version.repo, &version.commitish,
+        );
+        get_commitish(&tendermint_dir, version.repo, &version.commitish); // This panics if it fails.
+
+        let proto_paths = vec![tendermint_dir.join("proto")];
+        // List available proto files
+        let protos = find_proto_files(proto_paths);
+
         let mut pb = prost_build::Config::new();
+        pb.file_descriptor_set_path(out_dir.join(DESCRIPTORS));
 
         // Use shared Bytes buffers for ABCI messages:
         pb.bytes(&[".tendermint.abci"]);
 
+        // Encode map entries in the order of their keys, as Go's deterministic
+        // marshaler does:
+        pb.btree_map(["."]);
+
+        // Describe the gRPC services and generate their servers, e.g. for the
+        // ABCI gRPC server:
+        pb.service_generator(Box::new(ServiceDescriptors::new()));
+
+        // The generated code is formatted with the pinned rustfmt
+        // configuration instead, when copied:
+        pb.format(false);
+
         // Compile proto files with added annotations, exchange prost_types to our own
         pb.out_dir(&out_dir);
         for type_attribute in CUSTOM_TYPE_ATTRIBUTES {
@@ -84,14 +147,63 @@ fn main() {
             },
         }
 
+        let version_descriptors = read_descriptors(&out_dir.join(DESCRIPTORS));
+        if let Some((previous_commitish, previous_descriptors)) = previous {
+            println!("[info] => Checking compatibility with {previous_commitish}.");
+            let report = CompatibilityReport {
+                ident: version.ident.to_owned(),
+                previous_commitish,
+                commitish: version.commitish.to_owned(),
+                breaking_changes: breaking_changes(&previous_descriptors, &version_descriptors),
+            };
+            for change in &report.breaking_changes {
+                println!("[warn] => Breaking change: {change}");
+            }
+            reports.push(report.to_json());
+            write_report(&report_path, &reports);
+
+            if report.is_breaking() && !allow_breaking {
+                eprintln!(
+                    "[error] => Breaking changes since {}, see {}. Pass --allow-breaking to generate anyway.",
+                    report.previous_commitish,
+                    report_path.to_string_lossy(),
+                );
+                process::exit(1);
+            }
+        }
+
         println!(
             "[info] => Removing old structs and copying new structs to {}",
             ver_target_dir.to_string_lossy(),
         );
-        copy_files(&out_dir, &ver_target_dir); // This panics if it fails.
-        generate_tendermint_mod(&out_dir, &version, &ver_module_dir);
+        copy_files(&out_dir, &ver_target_dir, &formatter, write_mode); // This panics if it fails.
+        generate_tendermint_mod(
+            &out_dir,
+            &version,
+            &ver_module_dir,
+            &formatter,
+            write_mode,
+        );
+        descriptors.push((version, version_descriptors));
     }
-    generate_tendermint_lib(TENDERMINT_VERSIONS, &target_dir.join("tendermint.rs"));
+    // Conversions between the messages of consecutive versions:
+    generate_conversions(
+        &descriptors,
+        &target_dir.join("tendermint").join(CONVERSIONS),
+        &formatter,
+        write_mode,
+    );
+    generate_tendermint_lib(
+        TENDERMINT_VERSIONS,
+        &target_dir.join("tendermint.rs"),
+        write_mode,
+    );
 
     println!("[info] => Done!");
 }
+
+fn write_report(path: &Path, reports: &[serde_json::Value]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let content = serde_json::to_string_pretty(reports).unwrap();
+    fs::write(path, content).expect("compatibility report write failed");
+}
@@ -0,0 +1,73 @@
+//! Generation of descriptors and servers of the gRPC services of the proto
+//! files.
+//!
+//! For every service, a module named after it lists the fully qualified name
+//! of the service and the names of its methods, from which the paths of the
+//! calls are made. The servers of the services are generated by `tonic`,
+//! behind the `grpc-server` feature of the crate, so that only its users
+//! depend on `tonic`.
+
+use prost_build::{Service, ServiceGenerator};
+
+/// Emits a descriptor module for every service, followed by the `tonic`
+/// servers of the services.
+pub struct ServiceDescriptors {
+    servers: Box<dyn ServiceGenerator>,
+}
+
+impl ServiceDescriptors {
+    pub fn new() -> Self {
+        let servers = tonic_build::configure()
+            .build_client(false)
+            .build_transport(false)
+            .server_mod_attribute(".", r#"#[cfg(feature = "grpc-server")]"#)
+            .service_generator();
+        Self { servers }
+    }
+}
+
+impl Default for ServiceDescriptors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceGenerator for ServiceDescriptors {
+    fn generate(&mut self, service: Service, buf: &mut String) {
+        let full_name = format!("{}.{}", service.package, service.proto_name);
+        buf.push_str(&format!(
+            "/// Descriptor of the `{full_name}` gRPC service.\n\
+             pub mod {} {{\n    \
+                 /// The fully qualified name of the service.\n    \
+                 pub const NAME: &str = \"{full_name}\";\n    \
+                 /// The names of the methods of the service, in their order of declaration.\n    \
+                 pub const METHODS: &[&str] = &[\n",
+            to_snake_case(&service.name),
+        ));
+        for method in &service.methods {
+            buf.push_str(&format!("        \"{}\",\n", method.proto_name));
+        }
+        buf.push_str("    ];\n}\n");
+        self.servers.generate(service, buf);
+    }
+
+    fn finalize(&mut self, buf: &mut String) {
+        self.servers.finalize(buf);
+    }
+}
+
+// `AbciApplication` to `abci_application`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -0,0 +1,37 @@
+//! RPC probe-related functionality specifically targeting a CometBFT 0.38+
+//! node running the `kvstore` ABCI application.
+//!
+//! CometBFT 0.38 replaced `BeginBlock`, `DeliverTx` and `EndBlock` with
+//! `FinalizeBlock`, which changes the shape of the block results and of the
+//! results of committed transactions. This plan probes the endpoints whose
+//! responses are known to differ from the ones of Tendermint 0.34 and
+//! CometBFT 0.37.
+
+use std::path::Path;
+
+use tokio::time::Duration;
+
+use crate::{
+    common::*,
+    error::Result,
+    plan::{in_series, Plan},
+};
+
+pub fn quick_probe_plan(output_path: &Path, request_wait: Duration) -> Result<Plan> {
+    Plan::new(
+        "cometbft-quick-probe",
+        output_path,
+        request_wait,
+        vec![in_series(vec![
+            abci_info(),
+            // Committed before height 10, so that the block results below
+            // have transaction results.
+            broadcast_tx("commit", "commit-key", "value"),
+            block_results(10)
+                .with_min_height(10)
+                .with_name("block_results_at_height_10"),
+            consensus_params(10),
+            status(),
+        ])],
+    )
+}
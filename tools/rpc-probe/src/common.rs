@@ -43,6 +43,26 @@ pub fn block_by_hash(hash: &str) -> PlannedInteraction {
     .into()
 }
 
+pub fn header(height: u64) -> PlannedInteraction {
+    Request::new(
+        "header",
+        json!({
+            "height": format!("{height}"),
+        }),
+    )
+    .into()
+}
+
+pub fn header_by_hash(hash: &str) -> PlannedInteraction {
+    Request::new(
+        "header_by_hash",
+        json!({
+            "hash": format!("{hash}"),
+        }),
+    )
+    .into()
+}
+
 pub fn block_search(query: &str, page: u32, per_page: u32, order_by: &str) -> PlannedInteraction {
     Request::new(
         "block_search",
@@ -29,6 +29,10 @@ pub fn quick_probe_plan(output_path: &Path, request_wait: Duration) -> Result<Pl
                 // FIXME: need a way to discover a hash that works for the test chain
                 block_by_hash("/PnCU3/DU0ynEAH+HxTE92kJCUjBpSFoL2EufPc65jk=")
                     .with_name("block_by_hash"),
+                header(10).with_name("header_at_height_10"),
+                // Unlike `/block_by_hash`, takes the hash as a hex string.
+                header_by_hash("FCF9C2537FC3534CA71001FE1F14C4F769090948C1A521682F612E7CF73AE639")
+                    .with_name("header_by_hash"),
                 block_search("block.height > 1", 1, 100, "asc").with_name("block_search"),
                 blockchain(1, 10).with_name("blockchain_from_1_to_10"),
                 commit(10).with_name("commit_at_height_10"),
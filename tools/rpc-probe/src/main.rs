@@ -1,4 +1,5 @@
 mod client;
+mod cometbft;
 mod common;
 mod error;
 mod gaia;
@@ -77,6 +78,9 @@ enum Command {
     /// Execute a quick probe of a Tendermint node running the `kvstore` ABCI
     /// application.
     Kvstore,
+    /// Execute a quick probe of a CometBFT 0.38+ node running the `kvstore`
+    /// ABCI application, targeting the responses which changed since 0.37.
+    Cometbft,
     /// Execute a probe of a Gaia node.
     Gaia,
 }
@@ -94,6 +98,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let request_wait = Duration::from_millis(opts.request_wait);
     match opts.cmd {
         Command::Kvstore => kvstore::quick_probe_plan(&opts.output.0, request_wait)?,
+        Command::Cometbft => cometbft::quick_probe_plan(&opts.output.0, request_wait)?,
         Command::Gaia => gaia::query_plan(&opts.output.0, request_wait)?,
     }
     .execute(&opts.addr)